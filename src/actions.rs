@@ -6,20 +6,35 @@
 //!
 //! ## Action Categories
 //!
-//! - **Application**: Quit, open file, settings, shortcuts
-//! - **Canvas Navigation**: Zoom in/out/reset
+//! - **Application**: Quit, open file, settings, new window
 //! - **Selection**: Select all, deselect, delete, duplicate, copy/paste
+//! - **Item Navigation**: Moving the selection between items with Tab and Cmd/Ctrl+Alt+Arrow
 //! - **Item Nudging**: Arrow key movement of selected items
-//! - **History**: Undo/redo operations
+//! - **History**: Saving code, undo and version history panels, board export
+//! - **Media**: Play/pause of audio, video and embedded players
 //! - **Preview Panel**: Tab management, split controls, navigation
 //! - **PDF Viewer**: Page navigation and zoom
+//! - **Image Viewer**: Stepping through the board's images
 //! - **Command Palette**: Open/close, navigation, selection
 //! - **Tool Selection**: Select, text, arrow, shape tools
 //! - **Focus Management**: Canvas and preview focus control
-
-use crate::app::Humanboard;
+//! - **Registered Commands**: [`RunCommand`] runs any command of the
+//!   [`command_registry`](crate::command_registry) by its ID. Commands like
+//!   zoom, undo and redo live only there, with no action of their own.
+
+use crate::app::{Humanboard, PanAnimation};
+use crate::constants::{
+    NUDGE_BURST_MS, NUDGE_STEP, NUDGE_STEP_LARGE, ZOOM_TO_FIT_ANIMATION_MS, ZOOM_TO_FIT_MAX,
+    ZOOM_TO_FIT_PADDING,
+};
+use crate::input::guides::{Bounds, union_bounds};
+use crate::input::transform::{fit_bounds, reveal_offset};
+use crate::item_navigation::{NavDirection, nearest_in_direction, next_in_order};
+use crate::types::CanvasItem;
+use crate::undo_history::items_label;
 use gpui::*;
 use std::sync::mpsc;
+use std::time::{Duration, Instant};
 
 // Application-level actions
 actions!(
@@ -29,13 +44,14 @@ actions!(
         Quit,          // Quit the application (Cmd+Q)
         OpenFile,      // Open file picker (Cmd+O)
         OpenSettings,  // Open settings panel (Cmd+,)
-        ShowShortcuts, // Show keyboard shortcuts overlay (Cmd+/)
-        GoHome,        // Navigate to landing page (Cmd+H)
-        NewBoard,      // Create a new board (Cmd+N)
-        // === Canvas Navigation ===
-        ZoomIn,    // Zoom in on canvas (Cmd+=)
-        ZoomOut,   // Zoom out on canvas (Cmd+-)
-        ZoomReset, // Reset zoom to 100% (Cmd+0)
+        NewWindow,     // Open another window (Cmd+Shift+N)
+        // === Item Navigation ===
+        SelectNextItem,  // Select the next item in reading order (Tab)
+        SelectPrevItem,  // Select the previous item in reading order (Shift+Tab)
+        SelectItemUp,    // Select the nearest item above (Cmd+Alt+Up)
+        SelectItemDown,  // Select the nearest item below (Cmd+Alt+Down)
+        SelectItemLeft,  // Select the nearest item to the left (Cmd+Alt+Left)
+        SelectItemRight, // Select the nearest item to the right (Cmd+Alt+Right)
         // === Selection ===
         SelectAll,         // Select all items (Cmd+A)
         DeselectAll,       // Deselect all items (Escape when items selected)
@@ -44,14 +60,27 @@ actions!(
         Copy,              // Copy selected items to clipboard (Cmd+C)
         Paste,             // Paste from clipboard (Cmd+V)
         // === Item Nudging ===
-        NudgeUp,    // Move selected items up (Arrow Up)
-        NudgeDown,  // Move selected items down (Arrow Down)
-        NudgeLeft,  // Move selected items left (Arrow Left)
-        NudgeRight, // Move selected items right (Arrow Right)
+        NudgeUp,         // Move selected items up 1px (Arrow Up)
+        NudgeDown,       // Move selected items down 1px (Arrow Down)
+        NudgeLeft,       // Move selected items left 1px (Arrow Left)
+        NudgeRight,      // Move selected items right 1px (Arrow Right)
+        NudgeUpLarge,    // Move selected items up 10px (Shift+Arrow Up)
+        NudgeDownLarge,  // Move selected items down 10px (Shift+Arrow Down)
+        NudgeLeftLarge,  // Move selected items left 10px (Shift+Arrow Left)
+        NudgeRightLarge, // Move selected items right 10px (Shift+Arrow Right)
         // === History ===
-        Undo,     // Undo last action (Cmd+Z)
-        Redo,     // Redo last undone action (Cmd+Shift+Z)
         SaveCode, // Save current code file (Cmd+S)
+        ToggleVersionHistory, // Browse and restore saved board versions (Cmd+Alt+H)
+        CollectBoardAssets,   // Copy files the board links to into its assets folder
+        EncryptBoard,         // Protect the current board with a passphrase
+        RemoveBoardEncryption, // Save the current board unencrypted again
+        ExportBoardHtml,      // Export the current board as a standalone web page
+        ExportBoardPng,       // Export the current board as a PNG image
+        ToggleOutline,        // Show the board contents side panel (Cmd+Alt+O)
+        ToggleUndoHistory,    // Show the undo steps side panel (Cmd+Alt+Z)
+        // === Media ===
+        ToggleMediaPlayback, // Play or pause the selected media items (Space tap, F8)
+        PauseAllMedia,       // Pause every media item on the board (Cmd+Alt+P)
         // === Preview Panel ===
         ClosePreview,        // Close preview panel (Escape)
         ToggleSplit,         // Toggle split direction (Cmd+\)
@@ -59,13 +88,13 @@ actions!(
         PrevTab,             // Switch to previous tab (Cmd+Shift+[)
         CloseTab,            // Close current tab (Cmd+W)
         ReopenClosedTab,     // Reopen last closed tab (Cmd+Shift+T)
-        GoBack,              // Navigate back in tab history (Cmd+[)
-        GoForward,           // Navigate forward in tab history (Cmd+])
+        GoBack,              // Go back in the focused preview pane (Cmd+[)
+        GoForward,           // Go forward in the focused preview pane (Cmd+])
         TogglePreviewSearch, // Toggle search in preview panel (Cmd+F)
         NextSearchMatch,     // Go to next search match (Cmd+G)
         PrevSearchMatch,     // Go to previous search match (Cmd+Shift+G)
-        TogglePaneSplit,     // Toggle split preview into two panes (Cmd+Shift+\)
-        MoveTabToOtherPane,  // Move current tab to other pane (Cmd+Alt+Shift+Arrow)
+        TogglePaneSplit,     // Split the focused preview pane, up to four panes (Cmd+Shift+\)
+        MoveTabToOtherPane,  // Move current tab to the next pane (Cmd+Alt+Shift+Arrow)
         FocusLeftPane,       // Focus left pane
         FocusRightPane,      // Focus right pane
         // === PDF Viewer ===
@@ -74,6 +103,9 @@ actions!(
         PdfZoomIn,    // Zoom in PDF
         PdfZoomOut,   // Zoom out PDF
         PdfZoomReset, // Reset PDF zoom
+        // === Image Viewer ===
+        NextImage, // Show the next image on the board (Right in an image tab)
+        PrevImage, // Show the previous image on the board (Left in an image tab)
         // === Command Palette ===
         CommandPalette,       // Open command palette
         ToggleCommandPalette, // Toggle command palette (Cmd+K)
@@ -99,9 +131,35 @@ actions!(
     ]
 );
 
+/// Run a command of the command registry by its ID. Commands registered
+/// with a handler closure reach the keymap and the menus as this action.
+#[derive(Clone, PartialEq, Debug, Action)]
+#[action(namespace = humanboard, no_json)]
+pub struct RunCommand {
+    pub id: SharedString,
+}
+
+impl RunCommand {
+    pub fn new(id: &str) -> Self {
+        Self {
+            id: SharedString::from(id.to_string()),
+        }
+    }
+}
+
+/// Bounds of an item as (x, y, width, height)
+pub(crate) fn item_bounds(item: &CanvasItem) -> Bounds {
+    (item.position.0, item.position.1, item.size.0, item.size.1)
+}
+
+/// Nudge distance for an arrow key, with or without Shift
+fn nudge_step(large: bool) -> f32 {
+    if large { NUDGE_STEP_LARGE } else { NUDGE_STEP }
+}
+
 impl Humanboard {
     /// Get the center point of the canvas area (accounting for preview panel)
-    fn canvas_center(&self, window: &Window) -> Point<Pixels> {
+    pub(crate) fn canvas_center(&self, window: &Window) -> Point<Pixels> {
         let bounds = window.bounds();
         let window_size = bounds.size;
 
@@ -148,6 +206,135 @@ impl Humanboard {
         }
     }
 
+    /// Animate the canvas so every item is visible
+    pub fn zoom_to_fit(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(ref board) = self.canvas.board else {
+            return;
+        };
+        let bounds = union_bounds(board.items.iter().map(item_bounds));
+        self.animate_to_bounds(bounds, window, cx);
+    }
+
+    /// Animate the canvas so the selected items fill the view
+    pub fn zoom_to_selection(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(ref board) = self.canvas.board else {
+            return;
+        };
+        let bounds = union_bounds(
+            board
+                .items
+                .iter()
+                .filter(|item| self.canvas.selected_items.contains(&item.id))
+                .map(item_bounds),
+        );
+        self.animate_to_bounds(bounds, window, cx);
+    }
+
+    fn animate_to_bounds(
+        &mut self,
+        bounds: Option<Bounds>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(bounds) = bounds else {
+            return;
+        };
+        let viewport = self.canvas_viewport_size(window);
+        let (target_offset, target_zoom) = fit_bounds(
+            bounds,
+            (f32::from(viewport.width), f32::from(viewport.height)),
+            ZOOM_TO_FIT_PADDING,
+            ZOOM_TO_FIT_MAX,
+        );
+
+        if let Some(ref mut board) = self.canvas.board {
+            self.ui.pan_animation = Some(PanAnimation {
+                start_offset: board.canvas_offset,
+                target_offset,
+                start_zoom: board.zoom,
+                target_zoom,
+                start_time: Instant::now(),
+                duration: Duration::from_millis(ZOOM_TO_FIT_ANIMATION_MS),
+            });
+            cx.notify();
+        }
+    }
+
+    /// Select the next (or previous) item in reading order and scroll it into view
+    pub fn select_next_item(&mut self, reverse: bool, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(ref board) = self.canvas.board else {
+            return;
+        };
+        let items: Vec<(u64, Bounds)> =
+            board.items.iter().map(|i| (i.id, item_bounds(i))).collect();
+        if let Some(id) = next_in_order(&items, self.single_selected_item(), reverse) {
+            self.select_and_reveal(id, window, cx);
+        }
+    }
+
+    /// Select the nearest item in `direction` from the current one and scroll it into view
+    pub fn select_item_in_direction(
+        &mut self,
+        direction: NavDirection,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(ref board) = self.canvas.board else {
+            return;
+        };
+        let items: Vec<(u64, Bounds)> =
+            board.items.iter().map(|i| (i.id, item_bounds(i))).collect();
+        let Some(current) = self.single_selected_item() else {
+            // No single item to start from: go to the first one
+            if let Some(id) = next_in_order(&items, None, false) {
+                self.select_and_reveal(id, window, cx);
+            }
+            return;
+        };
+        if let Some(id) = nearest_in_direction(&items, current, direction) {
+            self.select_and_reveal(id, window, cx);
+        }
+    }
+
+    /// The selected item, when exactly one is selected
+    pub(crate) fn single_selected_item(&self) -> Option<u64> {
+        if self.canvas.selected_items.len() == 1 {
+            self.canvas.selected_items.iter().next().copied()
+        } else {
+            None
+        }
+    }
+
+    /// Make `id` the only selected item and pan just enough to show it
+    pub(crate) fn select_and_reveal(&mut self, id: u64, window: &mut Window, cx: &mut Context<Self>) {
+        self.canvas.selected_items.clear();
+        self.canvas.selected_items.insert(id);
+
+        let viewport = self.canvas_viewport_size(window);
+        if let Some(ref board) = self.canvas.board {
+            let target = board.get_item(id).and_then(|item| {
+                reveal_offset(
+                    item_bounds(item),
+                    board.canvas_offset,
+                    board.zoom,
+                    (f32::from(viewport.width), f32::from(viewport.height)),
+                    ZOOM_TO_FIT_PADDING,
+                )
+            });
+            if let Some(target_offset) = target {
+                self.ui.pan_animation = Some(PanAnimation {
+                    start_offset: board.canvas_offset,
+                    target_offset,
+                    start_zoom: board.zoom,
+                    target_zoom: board.zoom,
+                    start_time: Instant::now(),
+                    duration: Duration::from_millis(ZOOM_TO_FIT_ANIMATION_MS),
+                });
+            }
+        }
+        cx.notify();
+    }
+
     pub fn zoom_reset(&mut self, cx: &mut Context<Self>) {
         if let Some(ref mut board) = self.canvas.board {
             board.zoom_reset();
@@ -158,7 +345,14 @@ impl Humanboard {
     pub fn delete_selected(&mut self, cx: &mut Context<Self>) {
         if !self.canvas.selected_items.is_empty() {
             if let Some(ref mut board) = self.canvas.board {
-                let selected = self.canvas.selected_items.clone();
+                // Locked items stay put (and stay selected)
+                let selected: std::collections::HashSet<u64> = board
+                    .unlocked_ids(self.canvas.selected_items.iter().copied())
+                    .into_iter()
+                    .collect();
+                if selected.is_empty() {
+                    return;
+                }
 
                 // Collect paths of items being deleted (for closing preview tabs)
                 let deleted_paths: Vec<_> = board
@@ -200,34 +394,35 @@ impl Humanboard {
 
                 // Close preview tabs for deleted items
                 if let Some(ref mut preview) = self.preview.panel {
-                    let mut tabs_to_remove: Vec<usize> = Vec::new();
-                    for (i, tab) in preview.tabs.iter().enumerate() {
-                        // Check file-based tabs (PDF, Markdown, Code)
-                        if let Some(path) = tab.path() {
-                            if deleted_paths.contains(path) {
-                                tabs_to_remove.push(i);
-                                continue;
+                    for pane in (0..preview.panes.len()).rev() {
+                        let tabs = &mut preview.panes[pane].tabs;
+                        let mut tabs_to_remove: Vec<usize> = Vec::new();
+                        for (i, tab) in tabs.iter().enumerate() {
+                            // Check file-based tabs (PDF, Markdown, Code)
+                            if let Some(path) = tab.path() {
+                                if deleted_paths.contains(path) {
+                                    tabs_to_remove.push(i);
+                                    continue;
+                                }
                             }
-                        }
-                        // Check table tabs by data_source_id
-                        if let crate::app::PreviewTab::Table { data_source_id, .. } = tab {
-                            if deleted_data_sources.contains(data_source_id) {
-                                tabs_to_remove.push(i);
+                            // Check table tabs by data_source_id
+                            if let crate::app::PreviewTab::Table { data_source_id, .. } = tab {
+                                if deleted_data_sources.contains(data_source_id) {
+                                    tabs_to_remove.push(i);
+                                }
                             }
                         }
+                        // Remove in reverse order to preserve indices, cleaning up each tab
+                        for i in tabs_to_remove.into_iter().rev() {
+                            preview.panes[pane].take_tab(i).cleanup(cx);
+                        }
+                        // Panes left without tabs are closed
+                        preview.remove_pane_if_empty(pane);
                     }
-                    // Remove in reverse order to preserve indices, cleaning up each tab
-                    for i in tabs_to_remove.into_iter().rev() {
-                        preview.tabs[i].cleanup(cx);
-                        preview.tabs.remove(i);
-                    }
-                    // Adjust active tab if needed
-                    if preview.tabs.is_empty() {
+                    if preview.panes[0].tabs.is_empty() {
                         // Clean up remaining resources and close preview panel
                         preview.cleanup(cx);
                         self.preview.panel = None;
-                    } else if preview.active_tab >= preview.tabs.len() {
-                        preview.active_tab = preview.tabs.len() - 1;
                     }
                 }
 
@@ -235,9 +430,11 @@ impl Humanboard {
                 let mut ids_to_remove: Vec<u64> = selected.iter().copied().collect();
                 ids_to_remove.extend(orphan_charts);
 
+                board.begin_group(items_label("Deleted", ids_to_remove.len()));
                 board.remove_items(&ids_to_remove);
-                self.canvas.selected_items.clear();
                 board.push_history();
+                board.end_group();
+                self.canvas.selected_items.retain(|id| !selected.contains(id));
                 board.save();
                 cx.notify();
             }
@@ -248,6 +445,10 @@ impl Humanboard {
         if !self.canvas.selected_items.is_empty() {
             if let Some(ref mut board) = self.canvas.board {
                 let mut new_ids = Vec::new();
+                board.begin_group(items_label(
+                    "Duplicated",
+                    self.canvas.selected_items.len(),
+                ));
 
                 // Collect items to duplicate
                 let items_to_dup: Vec<_> = self.canvas
@@ -279,6 +480,7 @@ impl Humanboard {
 
                 // Save changes
                 board.push_history();
+                board.end_group();
                 board.save();
                 cx.notify();
             }
@@ -297,68 +499,76 @@ impl Humanboard {
 
     /// Deselect all selected items
     pub fn deselect_all(&mut self, cx: &mut Context<Self>) {
+        // Escape closes an open context menu first
+        if self.close_context_menu(cx) {
+            return;
+        }
         if !self.canvas.selected_items.is_empty() {
             self.canvas.selected_items.clear();
             cx.notify();
         }
     }
 
-    /// Copy selected items to internal clipboard
-    /// Note: For now, this uses an internal representation.
-    /// Full system clipboard integration would require serialization.
+    /// Copy the selected items to the clipboard, to paste on this board or
+    /// another one. Other apps get the items' names as text.
     pub fn copy_selected(&mut self, cx: &mut Context<Self>) {
         use crate::notifications::Toast;
 
-        if self.canvas.selected_items.is_empty() {
+        let Some(ref board) = self.canvas.board else {
             return;
-        }
-
-        if self.canvas.board.is_some() {
-            // Store copied items in a Vec for later paste
-            // For now, we'll just show a toast - full implementation would
-            // serialize to clipboard
-            let count = self.canvas.selected_items.len();
-            self.ui.toast_manager.push(Toast::success(format!(
-                "Copied {} item{}",
-                count,
-                if count == 1 { "" } else { "s" }
-            )));
-            cx.notify();
-        }
+        };
+        let Some(copied) = board.copy_items(&self.canvas.selected_items) else {
+            return;
+        };
+
+        let count = copied.items.len();
+        cx.write_to_clipboard(ClipboardItem::new_string_with_json_metadata(
+            copied.text(),
+            copied,
+        ));
+        self.ui.toast_manager.push(Toast::success(format!(
+            "Copied {} item{}",
+            count,
+            if count == 1 { "" } else { "s" }
+        )));
+        cx.notify();
     }
 
-    /// Nudge selected items by a given delta
+    /// Nudge selected items by a given delta.
+    /// A burst of nudges (e.g. a held arrow key) is a single undo step.
     fn nudge_selected(&mut self, dx: f32, dy: f32, cx: &mut Context<Self>) {
         if self.canvas.selected_items.is_empty() {
             return;
         }
         if let Some(ref mut board) = self.canvas.board {
-            for item in &mut board.items {
-                if self.canvas.selected_items.contains(&item.id) {
-                    item.position.0 += dx;
-                    item.position.1 += dy;
-                }
+            let now = Instant::now();
+            let in_burst = self
+                .canvas
+                .last_nudge_at
+                .is_some_and(|at| now.duration_since(at) < Duration::from_millis(NUDGE_BURST_MS));
+            let ids = board.unlocked_ids(self.canvas.selected_items.iter().copied());
+
+            if board.nudge_items(&ids, (dx, dy), in_burst) {
+                self.canvas.last_nudge_at = Some(now);
+                cx.notify();
             }
-            board.push_history();
-            board.mark_dirty();
-            cx.notify();
         }
     }
 
-    pub fn nudge_up(&mut self, cx: &mut Context<Self>) {
-        self.nudge_selected(0.0, -10.0, cx);
+    pub fn nudge_up(&mut self, large: bool, cx: &mut Context<Self>) {
+        self.nudge_selected(0.0, -nudge_step(large), cx);
     }
 
-    pub fn nudge_down(&mut self, cx: &mut Context<Self>) {
-        self.nudge_selected(0.0, 10.0, cx);
+    pub fn nudge_down(&mut self, large: bool, cx: &mut Context<Self>) {
+        self.nudge_selected(0.0, nudge_step(large), cx);
     }
 
-    pub fn nudge_left(&mut self, cx: &mut Context<Self>) {
-        self.nudge_selected(-10.0, 0.0, cx);
+    pub fn nudge_left(&mut self, large: bool, cx: &mut Context<Self>) {
+        self.nudge_selected(-nudge_step(large), 0.0, cx);
     }
 
-    pub fn nudge_right(&mut self, cx: &mut Context<Self>) {
-        self.nudge_selected(10.0, 0.0, cx);
+    pub fn nudge_right(&mut self, large: bool, cx: &mut Context<Self>) {
+        self.nudge_selected(nudge_step(large), 0.0, cx);
     }
 
     pub fn toggle_command_palette(&mut self, window: &mut Window, cx: &mut Context<Self>) {
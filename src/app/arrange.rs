@@ -0,0 +1,45 @@
+//! Arrange methods - aligning and distributing the selected items
+
+use crate::app::Humanboard;
+use crate::arrange::{AlignEdge, DistributeAxis, align_items, distribute_items};
+use crate::types::CanvasItem;
+use crate::undo_history::items_label;
+use gpui::*;
+
+impl Humanboard {
+    /// Align the selected items on an edge or center of their bounding box
+    pub fn align_selected(&mut self, edge: AlignEdge, cx: &mut Context<Self>) {
+        self.arrange_selected("Aligned", |items| align_items(items, edge), cx);
+    }
+
+    /// Space the selected items evenly along an axis
+    pub fn distribute_selected(&mut self, axis: DistributeAxis, cx: &mut Context<Self>) {
+        self.arrange_selected("Distributed", |items| distribute_items(items, axis), cx);
+    }
+
+    fn arrange_selected(
+        &mut self,
+        verb: &str,
+        arrange: impl FnOnce(&[&CanvasItem]) -> Vec<(u64, (f32, f32))>,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(ref mut board) = self.canvas.board else {
+            return;
+        };
+
+        let selected: Vec<&CanvasItem> = board
+            .items
+            .iter()
+            .filter(|item| self.canvas.selected_items.contains(&item.id) && !item.locked)
+            .collect();
+        let moves = arrange(&selected);
+
+        // One undo step for the whole arrangement
+        board.begin_group(items_label(verb, moves.len()));
+        let moved = board.move_items(&moves);
+        board.end_group();
+        if moved {
+            cx.notify();
+        }
+    }
+}
@@ -0,0 +1,268 @@
+//! Board encryption methods - passphrase prompts for encrypting and unlocking boards
+
+use super::PassphraseMode;
+use super::state::PassphrasePrompt;
+use crate::app::Humanboard;
+use crate::board::{Board, BoardState};
+use crate::board_crypto;
+use crate::board_index::BoardIndex;
+use crate::error::BoardError;
+use crate::notifications::Toast;
+use gpui::*;
+use gpui_component::input::{InputEvent, InputState};
+use std::sync::mpsc;
+
+impl Humanboard {
+    /// Check if a board's file on disk is encrypted
+    pub(crate) fn is_board_file_encrypted(&self, board_id: &str) -> bool {
+        let path = self
+            .navigation
+            .board_index
+            .get_board_path(board_id)
+            .unwrap_or_else(|| BoardIndex::board_path(board_id));
+        board_crypto::is_encrypted_file(&path)
+    }
+
+    /// Ask for the passphrase of an encrypted board before opening it
+    pub(crate) fn show_unlock_prompt(&mut self, board_id: String, cx: &mut Context<Self>) {
+        self.ui.board_passphrase = Some(PassphrasePrompt::new(board_id, PassphraseMode::Unlock));
+        cx.notify();
+    }
+
+    /// Ask for a new passphrase to encrypt the current board
+    pub fn show_encrypt_board_prompt(&mut self, cx: &mut Context<Self>) {
+        let Some(ref board) = self.canvas.board else {
+            return;
+        };
+
+        if board.is_encrypted() {
+            self.show_toast(Toast::info("This board is already encrypted"));
+            cx.notify();
+            return;
+        }
+
+        self.ui.board_passphrase = Some(PassphrasePrompt::new(
+            board.id.clone(),
+            PassphraseMode::Encrypt,
+        ));
+        cx.notify();
+    }
+
+    /// Create the prompt's inputs once a window is available (called from render)
+    pub(crate) fn ensure_passphrase_inputs(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(ref prompt) = self.ui.board_passphrase else {
+            return;
+        };
+        if prompt.input.is_some() {
+            return;
+        }
+        let needs_confirm = prompt.mode == PassphraseMode::Encrypt;
+
+        let input = self.new_passphrase_input("Passphrase", window, cx);
+        input.update(cx, |state, cx| {
+            state.focus(window, cx);
+        });
+        let confirm_input =
+            needs_confirm.then(|| self.new_passphrase_input("Confirm passphrase", window, cx));
+
+        if let Some(ref mut prompt) = self.ui.board_passphrase {
+            prompt.input = Some(input);
+            prompt.confirm_input = confirm_input;
+        }
+    }
+
+    fn new_passphrase_input(
+        &mut self,
+        placeholder: &'static str,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Entity<InputState> {
+        let input = cx.new(|cx| {
+            InputState::new(window, cx)
+                .masked(true)
+                .placeholder(placeholder)
+        });
+
+        cx.subscribe(&input, |this, _, event: &InputEvent, cx| {
+            if let InputEvent::PressEnter { .. } = event {
+                this.submit_board_passphrase(cx);
+            }
+        })
+        .detach();
+
+        input
+    }
+
+    /// Close the passphrase prompt without unlocking or encrypting
+    pub fn cancel_board_passphrase(&mut self, cx: &mut Context<Self>) {
+        let Some(prompt) = self.ui.board_passphrase.take() else {
+            return;
+        };
+
+        // The board was claimed for this window before asking to unlock it
+        if prompt.mode == PassphraseMode::Unlock {
+            BoardIndex::release_open_board(&prompt.board_id, self.system.window_id);
+        }
+        cx.notify();
+    }
+
+    /// Submit the passphrase prompt
+    pub fn submit_board_passphrase(&mut self, cx: &mut Context<Self>) {
+        let Some(ref prompt) = self.ui.board_passphrase else {
+            return;
+        };
+
+        let read = |input: &Option<Entity<InputState>>| {
+            input
+                .as_ref()
+                .map(|input| input.read(cx).text().to_string())
+                .unwrap_or_default()
+        };
+        let passphrase = read(&prompt.input);
+        let confirmation = read(&prompt.confirm_input);
+        let mode = prompt.mode;
+
+        match mode {
+            PassphraseMode::Unlock => self.unlock_board(&passphrase, cx),
+            PassphraseMode::Encrypt => self.encrypt_board(&passphrase, &confirmation, cx),
+        }
+    }
+
+    fn unlock_board(&mut self, passphrase: &str, cx: &mut Context<Self>) {
+        let Some(ref prompt) = self.ui.board_passphrase else {
+            return;
+        };
+        if passphrase.is_empty() || prompt.unlock_rx.is_some() {
+            return;
+        }
+
+        // Key derivation is deliberately slow, so keep it off the UI thread
+        let path = self
+            .navigation
+            .board_index
+            .get_board_path(&prompt.board_id)
+            .unwrap_or_else(|| BoardIndex::board_path(&prompt.board_id));
+        let passphrase = passphrase.to_string();
+        let (tx, rx) = mpsc::channel();
+        cx.background_executor()
+            .spawn(async move {
+                let _ = tx.send(BoardState::load_encrypted(&path, &passphrase));
+            })
+            .detach();
+
+        if let Some(ref mut prompt) = self.ui.board_passphrase {
+            prompt.error = None;
+            prompt.unlock_rx = Some(rx);
+        }
+        cx.notify();
+    }
+
+    /// Open the board once its passphrase has been checked (called from
+    /// render).
+    ///
+    /// Returns true while the key is being derived, so the caller keeps
+    /// rendering until the result arrives.
+    pub(crate) fn poll_board_unlock(&mut self, cx: &mut Context<Self>) -> bool {
+        let Some(ref mut prompt) = self.ui.board_passphrase else {
+            return false;
+        };
+        let Some(ref rx) = prompt.unlock_rx else {
+            return false;
+        };
+        let result = match rx.try_recv() {
+            Ok(result) => result,
+            Err(mpsc::TryRecvError::Empty) => return true,
+            Err(mpsc::TryRecvError::Disconnected) => {
+                Err(BoardError::Crypto("unlock was interrupted".into()))
+            }
+        };
+        prompt.unlock_rx = None;
+
+        match result {
+            Ok((state, key)) => {
+                let board = Board::from_decrypted(prompt.board_id.clone(), state, key);
+                self.ui.board_passphrase = None;
+                if !self
+                    .navigation
+                    .board_index
+                    .get_board(&board.id)
+                    .is_some_and(|b| b.encrypted)
+                {
+                    self.update_board_index(|index| index.set_board_encrypted(&board.id, true));
+                }
+                self.enter_board(board, cx);
+            }
+            Err(BoardError::WrongPassphrase) => {
+                prompt.error = Some("Incorrect passphrase".to_string());
+            }
+            Err(e) => {
+                prompt.error = Some(format!("Couldn't open board: {}", e));
+            }
+        }
+        cx.notify();
+        false
+    }
+
+    fn encrypt_board(&mut self, passphrase: &str, confirmation: &str, cx: &mut Context<Self>) {
+        let Some(ref mut prompt) = self.ui.board_passphrase else {
+            return;
+        };
+        if let Err(message) = board_crypto::validate_new_passphrase(passphrase, confirmation) {
+            prompt.error = Some(message);
+            cx.notify();
+            return;
+        }
+        self.ui.board_passphrase = None;
+
+        let Some(ref mut board) = self.canvas.board else {
+            return;
+        };
+        let board_id = board.id.clone();
+        match board.enable_encryption(passphrase) {
+            Ok(()) => {
+                self.update_board_index(|index| index.set_board_encrypted(&board_id, true));
+                self.ui.toast_manager.push(Toast::success(
+                    "Board encrypted. The passphrase can't be recovered if you forget it",
+                ));
+                self.index_board_pdfs();
+            }
+            Err(e) => {
+                self.ui
+                    .toast_manager
+                    .push(Toast::error(format!("Encryption failed: {}", e)));
+            }
+        }
+        cx.notify();
+    }
+
+    /// Save the current board in plain text again
+    pub fn remove_board_encryption(&mut self, cx: &mut Context<Self>) {
+        let Some(ref mut board) = self.canvas.board else {
+            return;
+        };
+
+        if !board.is_encrypted() {
+            self.show_toast(Toast::info("This board isn't encrypted"));
+            cx.notify();
+            return;
+        }
+
+        let board_id = board.id.clone();
+        match board.disable_encryption() {
+            Ok(()) => {
+                self.update_board_index(|index| index.set_board_encrypted(&board_id, false));
+                self.ui
+                    .toast_manager
+                    .push(Toast::success("Board encryption removed"));
+                self.refresh_board_thumbnail(true);
+                self.index_board_pdfs();
+            }
+            Err(e) => {
+                self.ui
+                    .toast_manager
+                    .push(Toast::error(format!("Couldn't remove encryption: {}", e)));
+            }
+        }
+        cx.notify();
+    }
+}
@@ -0,0 +1,449 @@
+//! Board export methods - saving the current board as a standalone web page,
+//! a PNG image or a Markdown outline, a region of it as a PNG, SVG or PDF,
+//! the selected cards as one markdown document, or copying the selection to
+//! the clipboard as a PNG
+
+use crate::app::{ExportDialog, Humanboard};
+use crate::board_export::export_html;
+use crate::canvas_snapshot::{SnapshotOptions, encode_snapshot_png, save_snapshot};
+use crate::document_export::{DocumentOrder, document_items, export_markdown, is_exportable};
+use crate::input::guides::Bounds;
+use crate::markdown_outline::export_outline;
+use crate::notifications::Toast;
+use crate::region_export::{
+    EXPORT_SCALES, ExportFormat, ExportRegion, REGION_PADDING, export_region, items_region,
+};
+use gpui::*;
+use std::sync::mpsc;
+
+/// Pixels per canvas unit of a selection copied as an image, sharp on
+/// high-density displays
+const COPY_IMAGE_SCALE: f32 = 2.0;
+
+/// Space around the items of a selection copied as an image, in pixels
+const COPY_IMAGE_PADDING: f32 = 16.0;
+
+impl Humanboard {
+    /// Ask where to save the current board as HTML, then export it
+    pub fn export_board_html(&mut self, cx: &mut Context<Self>) {
+        let Some(ref board) = self.canvas.board else {
+            return;
+        };
+
+        let title = self
+            .navigation
+            .board_index
+            .get_board(&board.id)
+            .map(|b| b.name.clone())
+            .unwrap_or_else(|| "Untitled Board".to_string());
+        let state = board.to_state();
+
+        let directory = dirs::download_dir()
+            .or_else(dirs::home_dir)
+            .unwrap_or_default();
+        let suggested_name = format!("{}.html", title.replace(['/', '\\', ':'], "-"));
+        let path_rx = cx.prompt_for_new_path(&directory, Some(&suggested_name));
+
+        // Same channel workaround as Open File - the result is polled in render.
+        // Images are read and encoded here too, off the main thread.
+        let (tx, rx) = mpsc::channel();
+        cx.background_executor()
+            .spawn(async move {
+                if let Ok(Ok(Some(path))) = path_rx.await {
+                    let result = export_html(&state, &title, &path)
+                        .map(|()| path)
+                        .map_err(|e| e.to_string());
+                    let _ = tx.send(result);
+                }
+            })
+            .detach();
+
+        self.canvas.export_rx = Some(rx);
+    }
+
+    /// Ask where to save the current board as a PNG, then render it offscreen
+    /// at full size
+    pub fn export_board_png(&mut self, cx: &mut Context<Self>) {
+        let Some(ref board) = self.canvas.board else {
+            return;
+        };
+
+        let title = self
+            .navigation
+            .board_index
+            .get_board(&board.id)
+            .map(|b| b.name.clone())
+            .unwrap_or_else(|| "Untitled Board".to_string());
+        let state = board.to_state();
+        let grid_size = crate::settings::app_settings().grid_size;
+
+        let directory = dirs::download_dir()
+            .or_else(dirs::home_dir)
+            .unwrap_or_default();
+        let suggested_name = format!("{}.png", title.replace(['/', '\\', ':'], "-"));
+        let path_rx = cx.prompt_for_new_path(&directory, Some(&suggested_name));
+
+        let (tx, rx) = mpsc::channel();
+        cx.background_executor()
+            .spawn(async move {
+                if let Ok(Ok(Some(path))) = path_rx.await {
+                    let options =
+                        SnapshotOptions::full_board(&state, 1.0, 32.0).with_grid_size(grid_size);
+                    let result = save_snapshot(&state, &options, &path)
+                        .map(|()| path)
+                        .map_err(|e| e.to_string());
+                    let _ = tx.send(result);
+                }
+            })
+            .detach();
+
+        self.canvas.export_rx = Some(rx);
+    }
+
+    /// Open the export dialog for a region of the board, set to the
+    /// selection if there is one
+    pub fn open_export_dialog(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(ref board) = self.canvas.board else {
+            return;
+        };
+        let viewport = self.canvas_viewport_size(window);
+        let visible = (
+            -f32::from(board.canvas_offset.x) / board.zoom,
+            -f32::from(board.canvas_offset.y) / board.zoom,
+            f32::from(viewport.width) / board.zoom,
+            f32::from(viewport.height) / board.zoom,
+        );
+        let region = if self.canvas.selected_items.is_empty() {
+            ExportRegion::AllItems
+        } else {
+            ExportRegion::Selection
+        };
+
+        self.ui.export_dialog = Some(ExportDialog {
+            region,
+            format: ExportFormat::Png,
+            scale: EXPORT_SCALES[1],
+            visible,
+            drawn: None,
+            picking: false,
+        });
+        cx.notify();
+    }
+
+    pub fn close_export_dialog(&mut self, cx: &mut Context<Self>) {
+        if self.ui.export_dialog.take().is_some() {
+            cx.notify();
+        }
+    }
+
+    /// Change the region, format or scale picked in the export dialog
+    pub fn update_export_dialog(
+        &mut self,
+        cx: &mut Context<Self>,
+        update: impl FnOnce(&mut ExportDialog),
+    ) {
+        if let Some(ref mut dialog) = self.ui.export_dialog {
+            update(dialog);
+            cx.notify();
+        }
+    }
+
+    /// Hide the export dialog until a region has been dragged out on the
+    /// canvas
+    pub fn start_export_region_pick(&mut self, cx: &mut Context<Self>) {
+        self.update_export_dialog(cx, |dialog| dialog.picking = true);
+    }
+
+    /// Whether the next drag on the canvas picks the region to export
+    pub(crate) fn is_picking_export_region(&self) -> bool {
+        self.ui
+            .export_dialog
+            .as_ref()
+            .is_some_and(|dialog| dialog.picking)
+    }
+
+    /// Bring the export dialog back with the region dragged out on the
+    /// canvas, or as it was if the drag was too small to be one
+    pub(crate) fn finish_export_region_pick(
+        &mut self,
+        region: Option<Bounds>,
+        cx: &mut Context<Self>,
+    ) {
+        self.update_export_dialog(cx, |dialog| {
+            dialog.picking = false;
+            if let Some(region) = region {
+                dialog.drawn = Some(region);
+                dialog.region = ExportRegion::Drawn;
+            }
+        });
+    }
+
+    /// Canvas region the export dialog is set to, if there is anything in it
+    pub(crate) fn export_dialog_region(&self) -> Option<Bounds> {
+        let dialog = self.ui.export_dialog.as_ref()?;
+        let board = self.canvas.board.as_ref()?;
+        match dialog.region {
+            ExportRegion::AllItems => items_region(&board.items, None, REGION_PADDING),
+            ExportRegion::Selection => items_region(
+                &board.items,
+                Some(&self.canvas.selected_items),
+                REGION_PADDING,
+            ),
+            ExportRegion::Visible => Some(dialog.visible),
+            ExportRegion::Drawn => dialog.drawn,
+        }
+    }
+
+    /// Ask where to save the region picked in the export dialog, then render
+    /// it offscreen in the picked format
+    pub fn export_dialog_region_to_file(&mut self, cx: &mut Context<Self>) {
+        let Some(ref board) = self.canvas.board else {
+            return;
+        };
+        let Some(ref dialog) = self.ui.export_dialog else {
+            return;
+        };
+        let Some(region) = self.export_dialog_region() else {
+            self.show_toast(Toast::info("There is nothing to export in this region"));
+            cx.notify();
+            return;
+        };
+        let (format, scale) = (dialog.format, dialog.scale);
+
+        let title = self
+            .navigation
+            .board_index
+            .get_board(&board.id)
+            .map(|b| b.name.clone())
+            .unwrap_or_else(|| "Untitled Board".to_string());
+        let state = board.to_state();
+        let grid_size = crate::settings::app_settings().grid_size;
+        self.ui.export_dialog = None;
+
+        let directory = dirs::download_dir()
+            .or_else(dirs::home_dir)
+            .unwrap_or_default();
+        let suggested_name = format!(
+            "{}.{}",
+            title.replace(['/', '\\', ':'], "-"),
+            format.extension()
+        );
+        let path_rx = cx.prompt_for_new_path(&directory, Some(&suggested_name));
+
+        let (tx, rx) = mpsc::channel();
+        cx.background_executor()
+            .spawn(async move {
+                if let Ok(Ok(Some(path))) = path_rx.await {
+                    let result = export_region(&state, region, format, scale, grid_size, &path)
+                        .map(|()| path)
+                        .map_err(|e| e.to_string());
+                    let _ = tx.send(result);
+                }
+            })
+            .detach();
+
+        self.canvas.export_rx = Some(rx);
+        cx.notify();
+    }
+
+    /// Whether any selected card can be written into a markdown document
+    pub(crate) fn has_exportable_selection(&self) -> bool {
+        self.canvas.board.as_ref().is_some_and(|board| {
+            board.items.iter().any(|item| {
+                self.canvas.selected_items.contains(&item.id) && is_exportable(&item.content)
+            })
+        })
+    }
+
+    /// Ask where to save the selected cards as one markdown document, then
+    /// write them into it in `order`
+    pub fn export_selection_markdown(&mut self, order: DocumentOrder, cx: &mut Context<Self>) {
+        let Some(ref board) = self.canvas.board else {
+            return;
+        };
+        let items: Vec<_> = document_items(&board.items, &self.canvas.selected_items, order)
+            .into_iter()
+            .cloned()
+            .collect();
+        let title = self
+            .navigation
+            .board_index
+            .get_board(&board.id)
+            .map(|b| b.name.clone())
+            .unwrap_or_else(|| "Untitled Board".to_string());
+        if items.is_empty() {
+            self.show_toast(Toast::info(
+                "Select text, markdown, code or image cards to export",
+            ));
+            cx.notify();
+            return;
+        }
+
+        let directory = dirs::document_dir()
+            .or_else(dirs::home_dir)
+            .unwrap_or_default();
+        let suggested_name = format!("{}.md", title.replace(['/', '\\', ':'], "-"));
+        let path_rx = cx.prompt_for_new_path(&directory, Some(&suggested_name));
+
+        // Code files are read and images copied off the main thread
+        let (tx, rx) = mpsc::channel();
+        cx.background_executor()
+            .spawn(async move {
+                if let Ok(Ok(Some(path))) = path_rx.await {
+                    let result = export_markdown(&items, &path)
+                        .map(|_| path)
+                        .map_err(|e| e.to_string());
+                    let _ = tx.send(result);
+                }
+            })
+            .detach();
+
+        self.canvas.export_rx = Some(rx);
+    }
+
+    /// Ask where to save the whole board as a Markdown outline, with a
+    /// section for each frame, then export it
+    pub fn export_board_outline(&mut self, cx: &mut Context<Self>) {
+        let Some(ref board) = self.canvas.board else {
+            return;
+        };
+        let title = self
+            .navigation
+            .board_index
+            .get_board(&board.id)
+            .map(|b| b.name.clone())
+            .unwrap_or_else(|| "Untitled Board".to_string());
+        let state = board.to_state();
+
+        let directory = dirs::document_dir()
+            .or_else(dirs::home_dir)
+            .unwrap_or_default();
+        let suggested_name = format!("{}.md", title.replace(['/', '\\', ':'], "-"));
+        let path_rx = cx.prompt_for_new_path(&directory, Some(&suggested_name));
+
+        // Code files are read and images copied off the main thread
+        let (tx, rx) = mpsc::channel();
+        cx.background_executor()
+            .spawn(async move {
+                if let Ok(Ok(Some(path))) = path_rx.await {
+                    let result = export_outline(&state, &title, &path)
+                        .map(|_| path)
+                        .map_err(|e| e.to_string());
+                    let _ = tx.send(result);
+                }
+            })
+            .detach();
+
+        self.canvas.export_rx = Some(rx);
+    }
+
+    /// Render the selected items offscreen and put them on the clipboard as a
+    /// PNG, on the board's background or a transparent one
+    pub fn copy_selection_as_png(&mut self, transparent: bool, cx: &mut Context<Self>) {
+        let Some(ref board) = self.canvas.board else {
+            return;
+        };
+        let mut state = board.to_state();
+        state
+            .items
+            .retain(|item| self.canvas.selected_items.contains(&item.id));
+        if state.items.is_empty() {
+            self.show_toast(Toast::info("Select items to copy as an image"));
+            cx.notify();
+            return;
+        }
+        let grid_size = crate::settings::app_settings().grid_size;
+
+        // Images are decoded and the PNG encoded off the main thread; the
+        // clipboard is written once it arrives, see `poll_copied_png`
+        let (tx, rx) = mpsc::channel();
+        cx.background_executor()
+            .spawn(async move {
+                let mut options =
+                    SnapshotOptions::full_board(&state, COPY_IMAGE_SCALE, COPY_IMAGE_PADDING)
+                        .with_grid_size(grid_size);
+                if transparent {
+                    options = options.with_transparent_background();
+                }
+                let result = encode_snapshot_png(&state, &options)
+                    .map(|bytes| (bytes, state.items.len()))
+                    .map_err(|e| e.to_string());
+                let _ = tx.send(result);
+            })
+            .detach();
+
+        self.canvas.copy_png_rx = Some(rx);
+    }
+
+    /// Put the PNG rendered by `copy_selection_as_png` on the clipboard
+    /// (called from render).
+    ///
+    /// Returns true while it is still being rendered, so the caller keeps
+    /// rendering until it arrives.
+    pub(crate) fn poll_copied_png(&mut self, cx: &mut Context<Self>) -> bool {
+        let Some(ref rx) = self.canvas.copy_png_rx else {
+            return false;
+        };
+        let result = match rx.try_recv() {
+            Ok(result) => result,
+            Err(mpsc::TryRecvError::Empty) => return true,
+            Err(mpsc::TryRecvError::Disconnected) => {
+                self.canvas.copy_png_rx = None;
+                return false;
+            }
+        };
+        self.canvas.copy_png_rx = None;
+
+        match result {
+            Ok((bytes, count)) => {
+                cx.write_to_clipboard(ClipboardItem::new_image(&Image::from_bytes(
+                    ImageFormat::Png,
+                    bytes,
+                )));
+                let msg = if count == 1 {
+                    "Copied 1 item as an image".to_string()
+                } else {
+                    format!("Copied {} items as an image", count)
+                };
+                self.show_toast(Toast::success(msg));
+            }
+            Err(e) => {
+                self.show_toast(Toast::error(format!("Couldn't copy as an image: {}", e)));
+            }
+        }
+        cx.notify();
+        false
+    }
+
+    /// Report the result of `export_board_html`, `export_board_png`,
+    /// `export_dialog_region_to_file` or `export_selection_markdown` (called
+    /// from render)
+    pub(crate) fn poll_export_result(&mut self, cx: &mut Context<Self>) {
+        let Some(ref rx) = self.canvas.export_rx else {
+            return;
+        };
+        let Ok(result) = rx.try_recv() else {
+            return;
+        };
+        self.canvas.export_rx = None;
+
+        match result {
+            Ok(path) => {
+                let name = path
+                    .file_name()
+                    .and_then(|n| n.to_str())
+                    .unwrap_or("board")
+                    .to_string();
+                self.ui
+                    .toast_manager
+                    .push(Toast::success(format!("Exported {}", name)));
+            }
+            Err(e) => {
+                self.ui
+                    .toast_manager
+                    .push(Toast::error(format!("Export failed: {}", e)));
+            }
+        }
+        cx.notify();
+    }
+}
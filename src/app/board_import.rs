@@ -0,0 +1,160 @@
+//! Board import methods - bringing an Obsidian canvas or a Miro or FigJam
+//! board export onto the current board
+
+use crate::app::{BoardImport, Humanboard, ImportReport};
+use crate::constants::{DOCK_WIDTH, HEADER_HEIGHT};
+use crate::notifications::Toast;
+use crate::obsidian_canvas::import_canvas;
+use crate::undo_history::items_label;
+use crate::whiteboard_import::import_whiteboard;
+use gpui::*;
+use std::path::Path;
+use std::sync::mpsc;
+
+/// Where imported items go on screen, from the top-left of the canvas
+const IMPORT_MARGIN: f32 = 40.0;
+
+impl Humanboard {
+    /// Ask for an Obsidian `.canvas` file and add its nodes and connections
+    /// to the board
+    pub fn import_obsidian_canvas(&mut self, cx: &mut Context<Self>) {
+        let Some(ref board) = self.canvas.board else {
+            return;
+        };
+        let board_id = board.id.clone();
+        self.import_board_file(cx, move |path| {
+            let (items, skipped) = import_canvas(path).map_err(|e| e.to_string())?;
+            Ok(BoardImport {
+                board_id,
+                source: "Obsidian",
+                items,
+                skipped,
+            })
+        });
+    }
+
+    /// Ask for a Miro (JSON or CSV) or FigJam (JSON) export and add its
+    /// sticky notes, shapes, images and connectors to the board
+    pub fn import_whiteboard_export(&mut self, cx: &mut Context<Self>) {
+        let Some(ref board) = self.canvas.board else {
+            return;
+        };
+        let board_id = board.id.clone();
+        let storage_dir = board.storage_dir();
+        self.import_board_file(cx, move |path| {
+            let (converted, items) =
+                import_whiteboard(path, &storage_dir).map_err(|e| e.to_string())?;
+            Ok(BoardImport {
+                board_id,
+                source: converted.app.label(),
+                items,
+                skipped: converted.skipped,
+            })
+        });
+    }
+
+    /// Ask for a file and read it with `import` off the main thread; the
+    /// result is added to the board by `poll_board_import`
+    fn import_board_file(
+        &mut self,
+        cx: &mut Context<Self>,
+        import: impl FnOnce(&Path) -> Result<BoardImport, String> + Send + 'static,
+    ) {
+        if self.canvas.board.is_none() {
+            return;
+        }
+        let paths_rx = cx.prompt_for_paths(PathPromptOptions {
+            files: true,
+            directories: false,
+            multiple: false,
+            prompt: None,
+        });
+
+        // Same channel workaround as Open File - the result is polled in
+        // render. The file is read, and its images looked up or downloaded,
+        // off the main thread.
+        let (tx, rx) = mpsc::channel();
+        cx.background_executor()
+            .spawn(async move {
+                if let Ok(Ok(Some(paths))) = paths_rx.await {
+                    if let Some(path) = paths.into_iter().next() {
+                        let _ = tx.send(import(&path));
+                    }
+                }
+            })
+            .detach();
+
+        self.canvas.import_rx = Some(rx);
+    }
+
+    /// Add the items of a finished import to the board, select them and
+    /// bring them into view (called from render). Anything the import
+    /// skipped is listed in the import report.
+    pub(crate) fn poll_board_import(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(ref rx) = self.canvas.import_rx else {
+            return;
+        };
+        let Ok(result) = rx.try_recv() else {
+            return;
+        };
+        self.canvas.import_rx = None;
+
+        let import = match result {
+            Ok(import) => import,
+            Err(e) => {
+                self.show_toast(Toast::error(format!("Import failed: {}", e)));
+                cx.notify();
+                return;
+            }
+        };
+        // The user may have closed or switched boards while this was running
+        let Some(board) = self
+            .canvas
+            .board
+            .as_mut()
+            .filter(|board| board.id == import.board_id)
+        else {
+            return;
+        };
+
+        let mut errors = Vec::new();
+        let position = point(
+            px(DOCK_WIDTH + IMPORT_MARGIN),
+            px(HEADER_HEIGHT + IMPORT_MARGIN),
+        );
+        let ids = board.import_items(&import.items, position, &mut errors);
+        if let Err(e) = board.flush_save() {
+            errors.push(format!("Save failed: {}", e));
+        }
+
+        let imported = ids.len();
+        if ids.is_empty() {
+            self.show_toast(Toast::info(format!(
+                "Nothing to import from {}",
+                import.source
+            )));
+        } else {
+            self.show_toast(Toast::success(items_label("Imported", imported)));
+            self.canvas.selected_items = ids.into_iter().collect();
+            self.zoom_to_selection(window, cx);
+        }
+        if !import.skipped.is_empty() {
+            self.ui.import_report = Some(ImportReport {
+                source: import.source,
+                imported,
+                skipped: import.skipped,
+            });
+        }
+        for error in errors {
+            self.show_toast(Toast::warning(error));
+        }
+        cx.notify();
+    }
+
+    /// Close the import report
+    pub fn close_import_report(&mut self, cx: &mut Context<Self>) {
+        if self.ui.import_report.take().is_some() {
+            cx.notify();
+        }
+    }
+}
@@ -1,11 +1,14 @@
-//! Board management methods - create, open, edit, delete, trash operations
+//! Board management methods - create, open, edit, delete, trash operations, assets
 
 use super::{AppView, Humanboard, StorageLocation};
 use crate::board::Board;
 use crate::board_index::BoardIndex;
+use crate::board_thumbnail::save_thumbnail;
+use crate::constants::BOARD_THUMBNAIL_INTERVAL_SECS;
 use crate::focus::FocusContext;
 use gpui::*;
 use gpui_component::input::InputState;
+use std::time::{Duration, Instant};
 
 impl Humanboard {
     // ==================== Landing Page Methods ====================
@@ -68,7 +71,7 @@ impl Humanboard {
         let location_name = location.display_name().to_owned();
 
         // Create the board with custom location
-        let metadata = self.navigation.board_index.create_board_at(name, location);
+        let metadata = self.update_board_index(|index| index.create_board_at(name, location));
 
         // Close modal immediately (no animation when confirming - we're navigating away)
         self.navigation.show_create_board_modal = false;
@@ -92,14 +95,53 @@ impl Humanboard {
     }
 
     pub fn open_board(&mut self, id: String, cx: &mut Context<Self>) {
-        self.navigation.board_index.touch_board(&id);
-        let board = Board::load(id.clone());
+        if self.canvas.board.as_ref().is_some_and(|b| b.id == id) {
+            return;
+        }
+
+        // A board lives in one window at a time - focus it there instead
+        if !self.claim_board_for_window(&id, cx) {
+            return;
+        }
+
+        // Encrypted boards can only be loaded once the passphrase is entered
+        if self.is_board_file_encrypted(&id) {
+            self.show_unlock_prompt(id, cx);
+            return;
+        }
+
+        let board = Board::load(id);
+        self.enter_board(board, cx);
+    }
+
+    /// Show a loaded board in this window
+    pub(crate) fn enter_board(&mut self, board: Board, cx: &mut Context<Self>) {
+        // Leave the board open here first, saving it and releasing its claim
+        if self.canvas.board.as_ref().is_some_and(|b| b.id != board.id) {
+            self.go_home(cx);
+        }
+        let id = board.id.clone();
+        self.update_board_index(|index| index.touch_board(&id));
         self.canvas.board = Some(board);
         self.navigation.view = AppView::Board(id);
+        self.preview.restore_session = true;
+        self.index_board_pdfs();
         cx.notify();
     }
 
+    /// Change the board index, reading it from disk first so changes other
+    /// windows made to it aren't written over
+    pub(crate) fn update_board_index<R>(
+        &mut self,
+        update: impl FnOnce(&mut BoardIndex) -> R,
+    ) -> R {
+        self.navigation.board_index.reload();
+        update(&mut self.navigation.board_index)
+    }
+
     pub fn go_home(&mut self, cx: &mut Context<Self>) {
+        self.persist_playback_positions();
+        self.persist_preview_session();
         // Force save current board before leaving
         if let Some(ref mut board) = self.canvas.board {
             if let Err(e) = board.flush_save() {
@@ -110,15 +152,50 @@ impl Humanboard {
                     )).with_action(crate::notifications::ToastAction::retry()));
             }
         }
+        self.refresh_board_thumbnail(true);
+        self.release_board_for_window();
         self.canvas.board = None;
+        self.canvas.last_thumbnail_at = None;
+        self.canvas.missing_files.clear();
+        self.canvas.missing_files_checked_at = None;
+        self.canvas.missing_files_rx = None;
+        self.canvas.import_rx = None;
+        self.canvas.relink_rx = None;
+        self.canvas.background_image_rx = None;
+        self.canvas.render_cache.clear();
+        self.canvas.text_layouts.clear();
+        self.canvas.code_excerpts.clear();
+        self.canvas.card_files.clear();
+        self.canvas.updated_cards.clear();
+        self.canvas.search_texts.clear();
+        self.canvas.search_highlight = None;
+        self.canvas.find_input = None;
+        self.canvas.find.clear();
+        self.canvas.comparison = None;
+        self.ui.checkpoint_name = None;
+        self.ui.url_paste = None;
+        self.canvas.loader.clear();
         // Clean up preview panel resources before dropping
         if let Some(ref mut preview) = self.preview.panel {
             preview.cleanup(cx);
         }
         self.preview.panel = None;
+        self.preview.pending_pdf_pages.clear();
+        self.preview.pending_code_lines.clear();
+        self.preview.restore_session = false;
         self.webviews.youtube.clear(); // Clear YouTube WebViews when leaving board
+        self.webviews.vimeo.clear(); // Clear Vimeo WebViews when leaving board
+        self.webviews.soundcloud.clear(); // Clear SoundCloud WebViews when leaving board
+        self.webviews.spotify.clear(); // Clear Spotify WebViews when leaving board
         self.webviews.audio.clear(); // Clear Audio WebViews when leaving board
         self.webviews.video.clear(); // Clear Video WebViews when leaving board
+        self.webviews.media_positions.clear();
+        self.webviews.playlist = None;
+        self.webviews.hidden_subtitles.clear();
+        self.system.waveforms.clear();
+        self.system.pdf_pages.clear();
+        self.system.pdf_thumbnails.clear();
+        self.system.audio_metadata.clear();
         self.navigation.view = AppView::Landing;
         self.canvas.selected_items.clear();
         // Reload index to get any changes
@@ -126,6 +203,77 @@ impl Humanboard {
         cx.notify();
     }
 
+    /// Regenerate the current board's thumbnail in the background.
+    ///
+    /// Unless `force` is set, refreshes are throttled to one per
+    /// `BOARD_THUMBNAIL_INTERVAL_SECS` while editing.
+    pub fn refresh_board_thumbnail(&mut self, force: bool) {
+        let Some(ref board) = self.canvas.board else {
+            return;
+        };
+
+        // Thumbnails would show an encrypted board's contents in plain text
+        if board.is_encrypted() {
+            return;
+        }
+
+        let interval = Duration::from_secs(BOARD_THUMBNAIL_INTERVAL_SECS);
+        if !force && self.canvas.last_thumbnail_at.is_some_and(|at| at.elapsed() < interval) {
+            return;
+        }
+        self.canvas.last_thumbnail_at = Some(Instant::now());
+
+        let board_dir = board.board_dir();
+        let state = board.to_state();
+        self.system.background.spawn(
+            "board_thumbnail",
+            move || save_thumbnail(&board_dir, &state).map_err(|e| e.to_string()),
+            |result| {
+                if let Err(e) = result {
+                    tracing::warn!("Failed to render board thumbnail: {}", e);
+                }
+            },
+        );
+    }
+
+    /// Copy every file the current board links to into its assets folder
+    pub fn collect_board_assets(&mut self, cx: &mut Context<Self>) {
+        let Some(ref mut board) = self.canvas.board else {
+            return;
+        };
+
+        let report = board.migrate_assets();
+        let save_result = if report.changed() { board.flush_save() } else { Ok(false) };
+
+        if let Err(e) = save_result {
+            self.ui.toast_manager.push(
+                crate::notifications::Toast::error(format!("Save failed: {}", e))
+                    .with_action(crate::notifications::ToastAction::retry()),
+            );
+        } else if report.copied > 0 {
+            self.ui.toast_manager.push(crate::notifications::Toast::success(format!(
+                "Copied {} file(s) into board assets",
+                report.copied
+            )));
+        } else if report.missing == 0 && report.errors.is_empty() {
+            self.ui
+                .toast_manager
+                .push(crate::notifications::Toast::info("All files are already in board assets"));
+        }
+
+        if report.missing > 0 {
+            self.ui.toast_manager.push(crate::notifications::Toast::warning(format!(
+                "{} linked file(s) could not be found",
+                report.missing
+            )));
+        }
+        for error in report.errors {
+            self.ui.toast_manager.push(crate::notifications::Toast::error(error));
+        }
+
+        cx.notify();
+    }
+
     pub fn start_editing_board(&mut self, id: String, window: &mut Window, cx: &mut Context<Self>) {
         if let Some(meta) = self.navigation.board_index.get_board(&id) {
             // Set focus context to Landing for input
@@ -148,7 +296,7 @@ impl Humanboard {
             if let Some(ref input) = self.navigation.edit_input {
                 let new_name = input.read(cx).value().to_string();
                 if !new_name.trim().is_empty() {
-                    self.navigation.board_index.rename_board(id, new_name);
+                    self.update_board_index(|index| index.rename_board(id, new_name));
                 }
             }
         }
@@ -170,7 +318,7 @@ impl Humanboard {
 
     /// Soft delete - moves to trash
     pub fn delete_board(&mut self, id: &str, cx: &mut Context<Self>) {
-        self.navigation.board_index.delete_board(id);
+        self.update_board_index(|index| index.delete_board(id));
         self.navigation.deleting_board_id = None;
         self.ui.toast_manager
             .push(crate::notifications::Toast::info("Board moved to trash"));
@@ -184,7 +332,7 @@ impl Humanboard {
 
     /// Restore a board from trash
     pub fn restore_board(&mut self, id: &str, cx: &mut Context<Self>) {
-        if self.navigation.board_index.restore_board(id) {
+        if self.update_board_index(|index| index.restore_board(id)) {
             self.ui.toast_manager
                 .push(crate::notifications::Toast::success("Board restored"));
         }
@@ -193,7 +341,7 @@ impl Humanboard {
 
     /// Permanently delete a board (no recovery)
     pub fn permanently_delete_board(&mut self, id: &str, cx: &mut Context<Self>) {
-        if self.navigation.board_index.permanently_delete_board(id) {
+        if self.update_board_index(|index| index.permanently_delete_board(id)) {
             self.ui.toast_manager.push(crate::notifications::Toast::info(
                 "Board permanently deleted",
             ));
@@ -203,7 +351,7 @@ impl Humanboard {
 
     /// Empty all boards from trash
     pub fn empty_trash(&mut self, cx: &mut Context<Self>) {
-        let count = self.navigation.board_index.empty_trash();
+        let count = self.update_board_index(|index| index.empty_trash());
         if count > 0 {
             self.ui.toast_manager
                 .push(crate::notifications::Toast::info(format!(
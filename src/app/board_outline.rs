@@ -0,0 +1,34 @@
+//! Outline panel methods - listing the board's contents and jumping to items
+
+use super::state::OutlinePanel;
+use crate::app::Humanboard;
+use gpui::*;
+
+impl Humanboard {
+    /// Show or hide the Outline side panel
+    pub fn toggle_outline(&mut self, cx: &mut Context<Self>) {
+        if self.ui.outline.is_some() {
+            self.ui.outline = None;
+        } else if self.canvas.board.is_some() {
+            // Both panels take the right edge
+            self.ui.undo_history = None;
+            self.ui.outline = Some(OutlinePanel::default());
+            self.refresh_outline();
+        }
+        cx.notify();
+    }
+
+    /// Re-index the board if it changed since the outline was built (called from render)
+    pub(crate) fn refresh_outline(&mut self) {
+        let (Some(panel), Some(board)) = (self.ui.outline.as_mut(), self.canvas.board.as_ref())
+        else {
+            return;
+        };
+
+        let last_change = board.last_change();
+        if panel.built_at != Some(last_change) {
+            panel.outline.rebuild(&board.items, &board.data_sources);
+            panel.built_at = Some(last_change);
+        }
+    }
+}
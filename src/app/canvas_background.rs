@@ -0,0 +1,105 @@
+//! Canvas background methods - the `bg` palette command and the background
+//! image picker
+
+use crate::app::Humanboard;
+use crate::canvas_background::CanvasBackground;
+use crate::notifications::Toast;
+use gpui::*;
+use std::sync::mpsc;
+
+impl Humanboard {
+    /// Run the `bg` palette command.
+    ///
+    /// `bg` on its own cycles through plain, dots and lines; `bg <style>`
+    /// picks a style or hex color, `bg image` asks for an image file and
+    /// `bg default` goes back to following the settings.
+    pub(crate) fn run_background_command(&mut self, arg: &str, cx: &mut Context<Self>) {
+        let arg = arg.trim();
+        if arg.eq_ignore_ascii_case("image") {
+            self.pick_background_image(cx);
+            return;
+        }
+
+        let Some(ref board) = self.canvas.board else {
+            return;
+        };
+        let background = if arg.is_empty() {
+            Some(board.background().next())
+        } else if arg.eq_ignore_ascii_case("default") {
+            None
+        } else {
+            match CanvasBackground::parse(arg) {
+                Some(background) => Some(background),
+                None => {
+                    self.show_toast(Toast::error(format!(
+                        "Unknown background '{}' (try dots, lines, plain, #hex or image)",
+                        arg
+                    )));
+                    return;
+                }
+            }
+        };
+        self.set_canvas_background(background, cx);
+    }
+
+    /// Set the board's background and confirm it with a toast
+    pub fn set_canvas_background(
+        &mut self,
+        background: Option<CanvasBackground>,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(ref mut board) = self.canvas.board else {
+            return;
+        };
+        board.set_background(background);
+        let label = board.background().label();
+        self.show_toast(Toast::info(format!("Background: {}", label)));
+        cx.notify();
+    }
+
+    /// Ask the user for an image to use as the canvas background
+    pub fn pick_background_image(&mut self, cx: &mut Context<Self>) {
+        let paths_rx = cx.prompt_for_paths(PathPromptOptions {
+            files: true,
+            directories: false,
+            multiple: false,
+            prompt: None,
+        });
+
+        // Same channel workaround as Open File - the result is polled in render
+        let (tx, rx) = mpsc::channel();
+        cx.background_executor()
+            .spawn(async move {
+                if let Ok(Ok(Some(paths))) = paths_rx.await {
+                    if let Some(path) = paths.into_iter().next() {
+                        let _ = tx.send(path);
+                    }
+                }
+            })
+            .detach();
+
+        self.canvas.background_image_rx = Some(rx);
+    }
+
+    /// Apply an image picked through `pick_background_image` (called from render)
+    pub(crate) fn poll_background_image(&mut self, cx: &mut Context<Self>) {
+        let Some(ref rx) = self.canvas.background_image_rx else {
+            return;
+        };
+        let Ok(path) = rx.try_recv() else {
+            return;
+        };
+        self.canvas.background_image_rx = None;
+
+        if image::ImageFormat::from_path(&path).is_err() {
+            self.show_toast(Toast::error(format!(
+                "'{}' isn't an image",
+                path.file_name()
+                    .map(|name| name.to_string_lossy().into_owned())
+                    .unwrap_or_default()
+            )));
+            return;
+        }
+        self.set_canvas_background(Some(CanvasBackground::Image { path }), cx);
+    }
+}
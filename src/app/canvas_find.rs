@@ -0,0 +1,127 @@
+//! Canvas find methods - the find bar over the canvas, dimming the items
+//! that don't match and stepping through the ones that do
+
+use super::Humanboard;
+use crate::canvas_find::find_on_canvas;
+use gpui::*;
+use gpui_component::input::{InputEvent, InputState};
+
+impl Humanboard {
+    /// Cmd+F: find in the preview when it has focus, on the canvas otherwise
+    pub fn toggle_find(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let in_preview = self.preview.panel.is_some()
+            && (self.preview.search.is_some()
+                || self
+                    .system
+                    .focus
+                    .is_focused(crate::focus::FocusContext::Preview));
+        if in_preview {
+            self.toggle_preview_search(window, cx);
+        } else if self.canvas.find_input.is_some() {
+            self.close_canvas_find(window, cx);
+        } else {
+            self.open_canvas_find(window, cx);
+        }
+    }
+
+    /// Cmd+G: the next match of whichever find bar is open
+    pub fn next_find_match(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        if self.canvas.find_input.is_some() && self.preview.search.is_none() {
+            self.next_canvas_find_match(window, cx);
+        } else {
+            self.next_search_match(cx);
+        }
+    }
+
+    /// Cmd+Shift+G: the previous match of whichever find bar is open
+    pub fn prev_find_match(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        if self.canvas.find_input.is_some() && self.preview.search.is_none() {
+            self.prev_canvas_find_match(window, cx);
+        } else {
+            self.prev_search_match(cx);
+        }
+    }
+
+    /// Open the find bar over the canvas
+    pub fn open_canvas_find(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        if self.canvas.board.is_none() {
+            return;
+        }
+
+        let input = cx.new(|cx| InputState::new(window, cx).placeholder("Find on board..."));
+        input.update(cx, |state, cx| {
+            state.focus(window, cx);
+        });
+
+        cx.subscribe_in(
+            &input,
+            window,
+            |this, input, event: &InputEvent, window, cx| match event {
+                InputEvent::Change { .. } => {
+                    let query = input.read(cx).text().to_string();
+                    this.update_canvas_find(&query, cx);
+                }
+                // Enter steps forward, Shift+Enter back, as in a browser
+                InputEvent::PressEnter { .. } => {
+                    if window.modifiers().shift {
+                        this.prev_canvas_find_match(window, cx);
+                    } else {
+                        this.next_canvas_find_match(window, cx);
+                    }
+                }
+                _ => {}
+            },
+        )
+        .detach();
+
+        self.canvas.find_input = Some(input);
+        self.canvas.find.clear();
+        cx.notify();
+    }
+
+    /// Close the find bar, leaving the last match selected
+    pub fn close_canvas_find(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        self.canvas.find_input = None;
+        self.canvas.find.clear();
+        self.system.focus.force_canvas_focus(window);
+        cx.notify();
+    }
+
+    /// Find the items matching `query`
+    fn update_canvas_find(&mut self, query: &str, cx: &mut Context<Self>) {
+        let Some(ref board) = self.canvas.board else {
+            return;
+        };
+        let ids = find_on_canvas(&board.items, &board.data_sources, query);
+        self.canvas.find.update(ids, !query.trim().is_empty());
+        cx.notify();
+    }
+
+    /// Match the query again, as items may have changed since it was typed
+    fn refresh_canvas_find(&mut self, cx: &mut Context<Self>) {
+        let query = self
+            .canvas
+            .find_input
+            .as_ref()
+            .map(|input| input.read(cx).text().to_string());
+        if let Some(query) = query {
+            self.update_canvas_find(&query, cx);
+        }
+    }
+
+    /// Pan to the next match
+    pub fn next_canvas_find_match(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        self.refresh_canvas_find(cx);
+        if let Some(id) = self.canvas.find.next_match() {
+            self.jump_to_item(id, window, cx);
+        }
+    }
+
+    /// Pan to the previous match
+    pub fn prev_canvas_find_match(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        self.refresh_canvas_find(cx);
+        if let Some(id) = self.canvas.find.prev_match() {
+            self.jump_to_item(id, window, cx);
+        }
+    }
+}
@@ -0,0 +1,109 @@
+//! Card refresh methods - reading markdown and code cards again when their
+//! files change on disk, and flashing the cards that were refreshed
+
+use crate::app::{Humanboard, PreviewTab};
+use crate::card_refresh::update_flash_opacity;
+use crate::types::ItemContent;
+use gpui::*;
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+use std::time::Instant;
+
+impl Humanboard {
+    /// Check the files behind markdown and code cards, about once a second,
+    /// and refresh the cards (and markdown preview tabs) of changed ones.
+    /// Code tabs notice their files changing themselves.
+    pub fn refresh_card_files(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        if !self.canvas.card_files.is_due() {
+            return;
+        }
+        let Some(ref board) = self.canvas.board else {
+            return;
+        };
+        let paths: Vec<PathBuf> = board
+            .items
+            .iter()
+            .filter_map(|item| match &item.content {
+                ItemContent::Markdown { path, .. } | ItemContent::Code { path, .. } => {
+                    Some(path.clone())
+                }
+                _ => None,
+            })
+            .collect();
+        let changed = self
+            .canvas
+            .card_files
+            .changed_files(paths.iter().map(|path| path.as_path()));
+
+        for path in changed {
+            self.refresh_card_file(path, window, cx);
+        }
+    }
+
+    /// Read one changed file into its cards and flash them. Markdown cards
+    /// already showing the file's text (after saving it here) don't flash.
+    fn refresh_card_file(&mut self, path: PathBuf, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(ref mut board) = self.canvas.board else {
+            return;
+        };
+        let is_markdown = board.items.iter().any(|item| {
+            matches!(&item.content, ItemContent::Markdown { path: item_path, .. } if *item_path == path)
+        });
+
+        if is_markdown {
+            let Ok(text) = fs::read_to_string(&path) else {
+                return;
+            };
+            if board.set_markdown_content(&path, &text) == 0 {
+                return;
+            }
+            if let Some(ref mut preview) = self.preview.panel {
+                for tab in preview.all_tabs_mut() {
+                    if let PreviewTab::Markdown {
+                        path: tab_path,
+                        content,
+                        editing: false,
+                        editor,
+                        ..
+                    } = tab
+                    {
+                        if *tab_path != path {
+                            continue;
+                        }
+                        if let Some(editor) = editor {
+                            let value = text.clone();
+                            editor.update(cx, |state, cx| state.set_value(value, window, cx));
+                        }
+                        *content = text.clone();
+                    }
+                }
+            }
+        }
+
+        let Some(ref board) = self.canvas.board else {
+            return;
+        };
+        let now = Instant::now();
+        for item in &board.items {
+            if item.content.file_paths().contains(&&path) {
+                self.canvas.updated_cards.insert(item.id, now);
+            }
+        }
+        cx.notify();
+    }
+
+    /// Opacity of the "updated" flash of each card refreshed a moment ago,
+    /// forgetting the flashes that are over
+    pub fn updated_card_flashes(&mut self) -> HashMap<u64, f32> {
+        let mut flashes = HashMap::new();
+        self.canvas.updated_cards.retain(|id, at| {
+            let opacity = update_flash_opacity(at.elapsed());
+            if let Some(opacity) = opacity {
+                flashes.insert(*id, opacity);
+            }
+            opacity.is_some()
+        });
+        flashes
+    }
+}
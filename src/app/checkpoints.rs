@@ -0,0 +1,150 @@
+//! Checkpoint methods - saving named versions of the board and comparing
+//! the board with a saved version on the canvas before restoring it
+
+use super::state::VersionComparison;
+use crate::app::Humanboard;
+use crate::board_diff::BoardDiff;
+use crate::error::BoardError;
+use crate::notifications::Toast;
+use gpui::*;
+use gpui_component::input::{InputEvent, InputState};
+
+/// Name of checkpoints saved without one
+const UNTITLED_CHECKPOINT: &str = "Untitled checkpoint";
+
+impl Humanboard {
+    /// Ask for the name of a new checkpoint of the board
+    pub fn prompt_checkpoint_name(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(ref board) = self.canvas.board else {
+            return;
+        };
+        if board.is_encrypted() {
+            self.show_toast(Toast::info("Checkpoints aren't kept for encrypted boards"));
+            cx.notify();
+            return;
+        }
+
+        let input = cx.new(|cx| InputState::new(window, cx).placeholder("Checkpoint name..."));
+        input.update(cx, |state, cx| {
+            state.focus(window, cx);
+        });
+
+        cx.subscribe_in(
+            &input,
+            window,
+            |this, input, event: &InputEvent, window, cx| {
+                if let InputEvent::PressEnter { .. } = event {
+                    let name = input.read(cx).text().to_string();
+                    this.create_checkpoint(&name, cx);
+                    this.cancel_checkpoint_name(window, cx);
+                }
+            },
+        )
+        .detach();
+
+        self.ui.checkpoint_name = Some(input);
+        cx.notify();
+    }
+
+    /// Close the checkpoint name prompt
+    pub fn cancel_checkpoint_name(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        self.ui.checkpoint_name = None;
+        self.system.focus.force_canvas_focus(window);
+        cx.notify();
+    }
+
+    /// Save the board as a checkpoint named `name`
+    pub fn create_checkpoint(&mut self, name: &str, cx: &mut Context<Self>) {
+        let Some(ref mut board) = self.canvas.board else {
+            return;
+        };
+        let name = match name.trim() {
+            "" => UNTITLED_CHECKPOINT,
+            name => name,
+        };
+
+        let toast = match board.create_checkpoint(name) {
+            Ok(_) => Toast::success(format!("Saved checkpoint \"{}\"", name)),
+            Err(BoardError::Encrypted) => {
+                Toast::info("Checkpoints aren't kept for encrypted boards")
+            }
+            Err(e) => Toast::error(format!("Couldn't save checkpoint: {}", e)),
+        };
+        self.show_toast(toast);
+        cx.notify();
+    }
+
+    /// Close the version browser and mark on the canvas how the board
+    /// differs from the version selected in it
+    pub fn compare_selected_version(&mut self, cx: &mut Context<Self>) {
+        let Some(modal) = self.ui.version_history.take() else {
+            return;
+        };
+        let (Some(state), Some(version)) = (
+            modal.selected_state,
+            modal.selected.and_then(|i| modal.versions.get(i)),
+        ) else {
+            cx.notify();
+            return;
+        };
+
+        let title = match version.name {
+            Some(ref name) => format!("\"{}\"", name),
+            None => format!("version from {}", version.formatted_age()),
+        };
+        self.canvas.comparison = Some(VersionComparison {
+            title,
+            state,
+            diff: BoardDiff::default(),
+            built_at: None,
+        });
+        self.refresh_comparison();
+        cx.notify();
+    }
+
+    /// Compare again if the board changed since the diff was built (called
+    /// from render)
+    pub(crate) fn refresh_comparison(&mut self) {
+        let (Some(comparison), Some(board)) =
+            (self.canvas.comparison.as_mut(), self.canvas.board.as_ref())
+        else {
+            return;
+        };
+
+        let last_change = board.last_change();
+        if comparison.built_at != Some(last_change) {
+            comparison.diff = BoardDiff::between(&comparison.state.items, &board.items);
+            comparison.built_at = Some(last_change);
+        }
+    }
+
+    /// Stop comparing, leaving the board as it is
+    pub fn close_comparison(&mut self, cx: &mut Context<Self>) {
+        self.canvas.comparison = None;
+        cx.notify();
+    }
+
+    /// Restore the version being compared with (undoable)
+    pub fn restore_compared_version(&mut self, cx: &mut Context<Self>) {
+        let Some(comparison) = self.canvas.comparison.take() else {
+            return;
+        };
+
+        if let Some(ref mut board) = self.canvas.board {
+            board.restore_version(&comparison.state);
+            self.canvas.selected_items.clear();
+
+            if let Err(e) = board.flush_save() {
+                self.ui.toast_manager.push(
+                    Toast::error(format!("Save failed: {}", e))
+                        .with_action(crate::notifications::ToastAction::retry()),
+                );
+            } else {
+                self.ui
+                    .toast_manager
+                    .push(Toast::success(format!("Restored {}", comparison.title)));
+            }
+        }
+        cx.notify();
+    }
+}
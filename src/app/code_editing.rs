@@ -0,0 +1,137 @@
+//! Code editing methods - saving code tabs to their files, and noticing
+//! when the files change on disk. A file changed under unsaved edits is a
+//! conflict: the tab asks whether to reload it or overwrite it.
+
+use crate::app::{Humanboard, PreviewTab};
+use crate::image_cache::FileStamp;
+use crate::notifications::Toast;
+use crate::preview::{DiskChange, disk_change};
+use gpui::*;
+use std::fs;
+use std::path::Path;
+use std::time::{Duration, Instant};
+use tracing::error;
+
+/// How often the files of code tabs are checked for changes
+const CODE_FILE_CHECK_INTERVAL: Duration = Duration::from_secs(1);
+
+impl Humanboard {
+    /// Read code files that changed on disk into their tabs again, or mark
+    /// the tabs in conflict if they have unsaved edits
+    pub fn check_code_files(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        if self
+            .preview
+            .code_files_checked_at
+            .is_some_and(|at| at.elapsed() < CODE_FILE_CHECK_INTERVAL)
+        {
+            return;
+        }
+        self.preview.code_files_checked_at = Some(Instant::now());
+        let Some(ref mut preview) = self.preview.panel else {
+            return;
+        };
+        for tab in preview.all_tabs_mut() {
+            let change = match tab {
+                PreviewTab::Code {
+                    path,
+                    dirty,
+                    disk_stamp,
+                    conflict: false,
+                    ..
+                } => disk_change(*disk_stamp, FileStamp::of(path), *dirty),
+                _ => continue,
+            };
+            match change {
+                DiskChange::Unchanged => {}
+                DiskChange::Reload => reload_code_tab(tab, window, cx),
+                DiskChange::Conflict => {
+                    if let PreviewTab::Code { conflict, .. } = tab {
+                        *conflict = true;
+                    }
+                    cx.notify();
+                }
+            }
+        }
+    }
+
+    /// Write a code tab's editor text to its file. Unless `overwrite` is
+    /// set, a file changed on disk since it was read is left alone and the
+    /// tab marked in conflict instead.
+    pub fn save_code_file(&mut self, path: &Path, overwrite: bool, cx: &mut Context<Self>) {
+        let Some(PreviewTab::Code {
+            content,
+            editor: Some(editor),
+            dirty,
+            disk_stamp,
+            conflict,
+            ..
+        }) = self.code_tab_mut(path)
+        else {
+            return;
+        };
+        if !overwrite && disk_change(*disk_stamp, FileStamp::of(path), true) == DiskChange::Conflict
+        {
+            *conflict = true;
+            cx.notify();
+            return;
+        }
+
+        let new_content = editor.read(cx).text().to_string();
+        let saved = fs::write(path, &new_content);
+        if saved.is_ok() {
+            *content = new_content;
+            *dirty = false;
+            *disk_stamp = FileStamp::of(path);
+            *conflict = false;
+        }
+        if let Err(e) = saved {
+            error!("Failed to save code file: {}", e);
+            self.show_toast(Toast::error(format!("Couldn't save {}", path.display())));
+        }
+        cx.notify();
+    }
+
+    /// Drop a code tab's unsaved edits and read its file again
+    pub fn reload_code_file(&mut self, path: &Path, window: &mut Window, cx: &mut Context<Self>) {
+        if let Some(tab) = self.code_tab_mut(path) {
+            reload_code_tab(tab, window, cx);
+        }
+    }
+
+    /// The code tab showing `path`, in either pane
+    pub(crate) fn code_tab_mut(&mut self, path: &Path) -> Option<&mut PreviewTab> {
+        let preview = self.preview.panel.as_mut()?;
+        preview
+            .all_tabs_mut()
+            .find(|tab| matches!(tab, PreviewTab::Code { path: p, .. } if p == path))
+    }
+}
+
+/// Read a code tab's file into its editor, replacing what the tab had
+fn reload_code_tab(tab: &mut PreviewTab, window: &mut Window, cx: &mut Context<Humanboard>) {
+    let PreviewTab::Code {
+        path,
+        content,
+        dirty,
+        editor,
+        disk_stamp,
+        conflict,
+        ..
+    } = tab
+    else {
+        return;
+    };
+    let stamp = FileStamp::of(path);
+    let Ok(text) = fs::read_to_string(path.as_path()) else {
+        return;
+    };
+    if let Some(editor) = editor {
+        let value = text.clone();
+        editor.update(cx, |state, cx| state.set_value(value, window, cx));
+    }
+    *content = text;
+    *dirty = false;
+    *disk_stamp = stamp;
+    *conflict = false;
+    cx.notify();
+}
@@ -0,0 +1,121 @@
+//! Code card methods - the `lines` palette command pinning the lines an
+//! expanded code card shows, pasting `path#L20-L45` references as cards and
+//! opening cards in the preview at their lines
+
+use crate::app::{Humanboard, PreviewTab};
+use crate::code_excerpt::{parse_line_range, split_line_anchor};
+use crate::notifications::Toast;
+use crate::types::ItemContent;
+use gpui::*;
+use std::path::PathBuf;
+
+impl Humanboard {
+    /// The single selected item, if it is a code card
+    pub(crate) fn selected_code_item(&self) -> Option<u64> {
+        if self.canvas.selected_items.len() != 1 {
+            return None;
+        }
+        let id = *self.canvas.selected_items.iter().next()?;
+        match self.canvas.board.as_ref()?.get_item(id)?.content {
+            ItemContent::Code { .. } => Some(id),
+            _ => None,
+        }
+    }
+
+    /// Run the `lines` palette command on the selected code card.
+    ///
+    /// `lines 10-40` pins those lines to the card, shown when it is resized
+    /// tall; `lines` on its own goes back to the start of the file.
+    pub(crate) fn run_lines_command(&mut self, arg: &str, cx: &mut Context<Self>) {
+        let Some(item_id) = self.selected_code_item() else {
+            self.show_toast(Toast::error("Select a code card to pin lines to"));
+            return;
+        };
+        let arg = arg.trim();
+        let lines = if arg.is_empty() {
+            None
+        } else {
+            match parse_line_range(arg) {
+                Some(lines) => Some(lines),
+                None => {
+                    self.show_toast(Toast::error(format!(
+                        "Unknown line range '{}' (try lines 10-40)",
+                        arg
+                    )));
+                    return;
+                }
+            }
+        };
+
+        let Some(ref mut board) = self.canvas.board else {
+            return;
+        };
+        if board.set_code_lines(item_id, lines) {
+            let message = match lines {
+                Some((first, last)) => format!("Showing lines {}–{}", first, last),
+                None => "Showing the start of the file".to_string(),
+            };
+            self.show_toast(Toast::info(message));
+            cx.notify();
+        }
+    }
+
+    /// Open a file in the preview, a code file with the cursor on the first
+    /// of `lines` when given
+    pub(crate) fn open_preview_at_lines(
+        &mut self,
+        path: PathBuf,
+        lines: Option<(usize, usize)>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.open_preview(path.clone(), window, cx);
+        let Some((first, _)) = lines else {
+            return;
+        };
+        let line = first.saturating_sub(1);
+        if let Some(PreviewTab::Code {
+            editor: Some(_), ..
+        }) = self.code_tab_mut(&path)
+        {
+            self.go_to_code_line(&path, line, window, cx);
+        } else {
+            // The editor is created on the next frame
+            self.preview.pending_code_lines.insert(path, line);
+        }
+    }
+
+    /// Add a card for a pasted code file reference, `path#L20-L45` or a
+    /// plain path. Returns false if the text isn't the path of a code file.
+    pub(crate) fn paste_code_reference(
+        &mut self,
+        text: &str,
+        position: Point<Pixels>,
+        cx: &mut Context<Self>,
+    ) -> bool {
+        let (path, lines) = split_line_anchor(text);
+        let path = PathBuf::from(path.strip_prefix("file://").unwrap_or(path));
+        if !path.is_file() {
+            return false;
+        }
+        let ItemContent::Code { path, language, .. } = ItemContent::from_path(&path) else {
+            return false;
+        };
+        let Some(ref mut board) = self.canvas.board else {
+            return false;
+        };
+        let canvas_position = board.screen_to_canvas(position);
+        let id = board.add_item(
+            canvas_position,
+            ItemContent::Code {
+                path,
+                language,
+                lines,
+            },
+        );
+        self.canvas.selected_items.clear();
+        self.canvas.selected_items.insert(id);
+        cx.notify();
+        true
+    }
+}
@@ -0,0 +1,145 @@
+//! Code outline methods - the symbol sidebar of code tabs, and jumping to
+//! symbols from it or from "Go to symbol in file" in the command palette
+
+use crate::app::{CodeOutline, Humanboard, PreviewTab};
+use crate::code_symbols::{CodeSymbol, code_symbols};
+use gpui::*;
+use gpui_component::input::{InputState, Position};
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::path::Path;
+
+impl Humanboard {
+    /// Find the symbols of code tabs with their outline shown again, after
+    /// their text has changed
+    pub fn ensure_code_outlines(&mut self, cx: &mut Context<Self>) {
+        let Some(ref mut preview) = self.preview.panel else {
+            return;
+        };
+        for tab in preview.all_tabs_mut() {
+            if let PreviewTab::Code {
+                language,
+                content,
+                editor,
+                outline: Some(outline),
+                ..
+            } = tab
+            {
+                let text = code_text(content, editor, cx);
+                let text_hash = hash_text(&text);
+                if outline.text_hash != text_hash {
+                    let symbols = code_symbols(&text, language);
+                    if symbols.len() != outline.symbols.len() {
+                        outline.collapsed.clear();
+                    }
+                    outline.symbols = symbols;
+                    outline.text_hash = text_hash;
+                }
+            }
+        }
+    }
+
+    /// Show or hide the outline sidebar of a code tab
+    pub fn toggle_code_outline(&mut self, path: &Path, cx: &mut Context<Self>) {
+        if let Some(PreviewTab::Code { outline, .. }) = self.code_tab_mut(path) {
+            *outline = match outline {
+                Some(_) => None,
+                None => Some(CodeOutline {
+                    // Differs from any text's hash, so the symbols are found
+                    text_hash: u64::MAX,
+                    ..Default::default()
+                }),
+            };
+            cx.notify();
+        }
+    }
+
+    /// Open or close the symbols nested in a symbol of a code tab's outline
+    pub fn toggle_code_symbol(&mut self, path: &Path, index: usize, cx: &mut Context<Self>) {
+        if let Some(PreviewTab::Code {
+            outline: Some(outline),
+            ..
+        }) = self.code_tab_mut(path)
+        {
+            if !outline.collapsed.remove(&index) {
+                outline.collapsed.insert(index);
+            }
+            cx.notify();
+        }
+    }
+
+    /// Put a code tab's cursor at the start of `line`, scrolled into view
+    pub fn go_to_code_line(
+        &mut self,
+        path: &Path,
+        line: usize,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.record_preview_jump(path, cx);
+        let Some(PreviewTab::Code {
+            editor: Some(editor),
+            ..
+        }) = self.code_tab_mut(path)
+        else {
+            return;
+        };
+        let editor = editor.clone();
+        editor.update(cx, |state, cx| {
+            state.set_cursor_position(Position::new(line as u32, 0), window, cx);
+            state.focus(window, cx);
+        });
+        cx.notify();
+    }
+
+    /// Symbols of the focused pane's active tab, if it is a code tab
+    pub(crate) fn active_code_symbols(&self, cx: &App) -> Option<Vec<CodeSymbol>> {
+        match self.active_code_tab()? {
+            PreviewTab::Code {
+                language,
+                content,
+                editor,
+                ..
+            } => Some(code_symbols(&code_text(content, editor, cx), language)),
+            _ => None,
+        }
+    }
+
+    /// Go to a line of the focused pane's code tab, picked from the
+    /// command palette
+    pub(crate) fn go_to_active_code_line(
+        &mut self,
+        line: usize,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if let Some(PreviewTab::Code { path, .. }) = self.active_code_tab() {
+            let path = path.clone();
+            self.go_to_code_line(&path, line, window, cx);
+        }
+    }
+
+    /// Whether the focused pane shows a code tab
+    pub(crate) fn has_active_code_tab(&self) -> bool {
+        matches!(self.active_code_tab(), Some(PreviewTab::Code { .. }))
+    }
+
+    fn active_code_tab(&self) -> Option<&PreviewTab> {
+        self.preview.panel.as_ref()?.focused().active()
+    }
+}
+
+/// A code tab's text as it is in its editor, or as read until the editor
+/// is created
+fn code_text(content: &str, editor: &Option<Entity<InputState>>, cx: &App) -> String {
+    match editor {
+        Some(editor) => editor.read(cx).text().to_string(),
+        None => content.to_string(),
+    }
+}
+
+fn hash_text(text: &str) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    text.hash(&mut hasher);
+    hasher.finish()
+}
@@ -1,16 +1,24 @@
 //! Command palette methods - show/hide, search, execute commands
 
 use super::{AppView, CmdPaletteMode, Humanboard, PanAnimation};
+use crate::code_symbols::matching_symbols;
+use crate::command_palette::{board_result_index, command_result_index, is_command_id};
 use crate::focus::FocusContext;
+use crate::frecency::PALETTE_FRECENCY;
+use crate::item_clipboard::CopiedItems;
+use crate::search_query::SearchQuery;
 use crate::settings::Settings;
 use gpui::*;
 use gpui_component::input::InputState;
+use std::path::PathBuf;
 use std::time::{Duration, Instant};
 
 impl Humanboard {
     pub fn show_command_palette(&mut self, window: &mut Window, cx: &mut Context<Self>) {
         // Set focus context to CommandPalette
         self.system.focus.focus(FocusContext::CommandPalette, window);
+        // Catch PDFs added since the board was opened
+        self.index_board_pdfs();
 
         let input = cx
             .new(|cx| InputState::new(window, cx).placeholder("Type to search or use commands..."));
@@ -147,37 +155,65 @@ impl Humanboard {
             return;
         }
 
-        // Check if typing a command prefix - show matching commands
-        if !text.is_empty() && text.len() <= 7 {
-            let text_lower = text.to_lowercase();
-            // Available commands with special IDs (using high numbers to avoid collision with item IDs)
-            let commands = [
-                (u64::MAX - 1, "theme", "Change theme"),
-                (u64::MAX - 2, "md", "Create markdown note"),
-            ];
-
-            let matching_commands: Vec<(u64, String)> = commands
-                .iter()
-                .filter(|(_, cmd, _)| cmd.starts_with(&text_lower))
-                .map(|(id, cmd, desc)| (*id, format!("{} - {}", cmd, desc)))
-                .collect();
+        // "symbol " searches the symbols of the active code tab
+        if let Some(filter) = text.strip_prefix("symbol ") {
+            self.ui.cmd_palette_mode = CmdPaletteMode::Symbols;
+            self.show_symbol_results(filter.trim(), cx);
+            return;
+        }
+        if self.ui.cmd_palette_mode == CmdPaletteMode::Symbols {
+            let filter = if text.eq_ignore_ascii_case("symbol") {
+                ""
+            } else {
+                text
+            };
+            self.show_symbol_results(filter, cx);
+            return;
+        }
 
-            if !matching_commands.is_empty() {
-                self.ui.search_results = matching_commands;
-                self.ui.selected_result = 0;
-                cx.notify();
-                return;
-            }
+        // "file " finds files to add under the quick-open folders
+        if let Some(filter) = text.strip_prefix("file ") {
+            self.ui.cmd_palette_mode = CmdPaletteMode::Files;
+            self.scan_quick_open_folders();
+            self.show_file_results(filter.trim(), cx);
+            return;
+        }
+        if self.ui.cmd_palette_mode == CmdPaletteMode::Files {
+            let filter = if text.eq_ignore_ascii_case("file") {
+                ""
+            } else {
+                text
+            };
+            self.show_file_results(filter, cx);
+            return;
         }
 
-        // Check if it's a complete command
-        if text.starts_with("md ") || text == "md" {
+        // Check if it's a complete command, with its argument being typed
+        if text.starts_with("md ") || text.starts_with("bg ") || text.starts_with("lines ") {
             self.ui.search_results.clear();
             self.ui.selected_result = 0;
             cx.notify();
             return;
         }
 
+        // Operators like "type:image" or "board:Q3" only list what passes them
+        let query = SearchQuery::parse(text);
+        if query.has_filters() {
+            self.ui.search_results = self.filtered_search_results(&query);
+            self.ui.selected_result = 0;
+            cx.notify();
+            return;
+        }
+
+        // Commands and items fuzzily matching the text, ranked together as
+        // ((score, tiebreak), id, label), with the commands used most lately
+        // raised
+        let mut ranked = if text.is_empty() {
+            Vec::new()
+        } else {
+            self.registered_command_results(text)
+        };
+
         // Search canvas items (empty string shows all items)
         if let Some(ref board) = self.canvas.board {
             if text.is_empty() {
@@ -201,12 +237,51 @@ impl Humanboard {
                     })
                     .collect();
             } else {
-                self.ui.search_results = board.find_items(text);
+                ranked.extend(
+                    board
+                        .ranked_items(text)
+                        .into_iter()
+                        .map(|(score, id, name)| ((score, 0), id, name)),
+                );
+                self.ui.search_results.clear();
             }
-            self.ui.selected_result = 0;
         } else {
             self.ui.search_results.clear();
         }
+
+        // Stable, so commands stay ahead of equally ranked items
+        ranked.sort_by(|a, b| b.0.cmp(&a.0));
+
+        // Then cards whose text matches, and PDFs whose text matches, on
+        // this board and others
+        let mut found: Vec<(u64, String)> =
+            ranked.into_iter().map(|(_, id, label)| (id, label)).collect();
+        found.append(&mut self.ui.search_results);
+        let content_results = self.content_results(text, &found);
+        found.extend(content_results);
+        let pdf_results = self.pdf_text_results(text, &found);
+        self.ui.search_results = found;
+        self.ui.search_results.extend(pdf_results);
+        self.ui.selected_result = 0;
+        cx.notify();
+    }
+
+    /// List the symbols of the active code tab whose names contain `filter`,
+    /// by the line they start on
+    fn show_symbol_results(&mut self, filter: &str, cx: &mut Context<Self>) {
+        let symbols = self.active_code_symbols(cx).unwrap_or_default();
+        self.ui.search_results = matching_symbols(&symbols, filter)
+            .map(|symbol| {
+                let label = format!(
+                    "{} {}  :{}",
+                    symbol.kind.marker(),
+                    symbol.name,
+                    symbol.line + 1
+                );
+                (symbol.line as u64, label)
+            })
+            .collect();
+        self.ui.selected_result = 0;
         cx.notify();
     }
 
@@ -217,6 +292,21 @@ impl Humanboard {
         self.update_search_results("", cx);
     }
 
+    /// Open the command palette listing everything of `mode`, like the
+    /// themes or the symbols of the active code tab
+    pub fn show_command_palette_in(
+        &mut self,
+        mode: CmdPaletteMode,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if self.ui.command_palette.is_none() {
+            self.show_command_palette(window, cx);
+        }
+        self.ui.cmd_palette_mode = mode;
+        self.update_search_results("", cx);
+    }
+
     /// Navigate search results
     pub fn select_next_result(&mut self, cx: &mut Context<Self>) {
         if !self.ui.search_results.is_empty() {
@@ -259,36 +349,61 @@ impl Humanboard {
             return;
         }
 
+        // Handle symbol mode
+        if self.ui.cmd_palette_mode == CmdPaletteMode::Symbols {
+            if let Some((line, _)) = self.ui.search_results.get(self.ui.selected_result) {
+                self.ui.pending_command = Some(format!("__symbol:{}", line));
+            }
+            self.ui.command_palette = None;
+            self.ui.search_results.clear();
+            self.ui.selected_result = 0;
+            self.ui.cmd_palette_mode = CmdPaletteMode::Items;
+            cx.notify();
+            return;
+        }
+
+        // Handle file mode
+        if self.ui.cmd_palette_mode == CmdPaletteMode::Files {
+            let path = self
+                .ui
+                .search_results
+                .get(self.ui.selected_result)
+                .and_then(|(id, _)| self.file_result_path(*id));
+            if let Some(path) = path {
+                self.ui.pending_command = Some(format!("__file:{}", path.display()));
+            }
+            self.ui.command_palette = None;
+            self.ui.search_results.clear();
+            self.ui.selected_result = 0;
+            self.ui.cmd_palette_mode = CmdPaletteMode::Items;
+            cx.notify();
+            return;
+        }
+
         // If we have search results selected, check if it's a command or an item
         if !self.ui.search_results.is_empty() {
-            let (item_id, _) = &self.ui.search_results[self.ui.selected_result];
-
-            // Check for special command IDs (u64::MAX - N for commands)
-            const CMD_THEME: u64 = u64::MAX - 1;
-            const CMD_MD: u64 = u64::MAX - 2;
-
-            match *item_id {
-                CMD_THEME => {
-                    // Enter theme mode directly
-                    self.ui.cmd_palette_mode = CmdPaletteMode::Themes;
-                    let themes = Settings::available_themes(cx);
-                    self.ui.search_results = themes
-                        .into_iter()
-                        .enumerate()
-                        .map(|(idx, name)| (idx as u64, name))
-                        .collect();
-                    self.ui.selected_result = 0;
-                    cx.notify();
-                    return; // Don't close palette, stay in theme mode
-                }
-                CMD_MD => {
-                    self.ui.pending_command = Some("md".to_string());
-                }
-                _ => {
-                    // Regular item - jump to it
-                    self.ui.pending_command = Some(format!("__jump:{}", item_id));
-                }
+            let (item_id, label) = &self.ui.search_results[self.ui.selected_result];
+
+            // Count the use, so the command ranks higher next time
+            if is_command_id(*item_id) {
+                let command = label.split(" - ").next().unwrap_or(label);
+                PALETTE_FRECENCY.record(command, &self.system.background);
             }
+
+            // Registered commands run once the palette is closed, with the window
+            if let Some(id) = command_result_index(*item_id)
+                .and_then(|index| self.ui.command_results.get(index))
+            {
+                self.ui.pending_command = Some(format!("__command:{}", id));
+                self.ui.command_palette = None;
+                self.ui.search_results.clear();
+                self.ui.selected_result = 0;
+                cx.notify();
+                return;
+            }
+
+            // Anything else is an item to jump to
+            self.ui.pending_command = Some(format!("__jump:{}", item_id));
         } else {
             let command = self
                 .ui.command_palette
@@ -316,7 +431,26 @@ impl Humanboard {
                     .unwrap_or("0")
                     .parse::<u64>()
                 {
-                    self.jump_to_item(item_id, window, cx);
+                    // Other boards found by their PDFs open in place of this one
+                    let other_board = board_result_index(item_id)
+                        .and_then(|index| self.ui.board_results.get(index).cloned());
+                    match other_board {
+                        Some(board_id) => {
+                            if self.canvas.board.is_some() {
+                                self.go_home(cx);
+                            }
+                            self.open_board(board_id, cx);
+                        }
+                        None => self.jump_to_item(item_id, window, cx),
+                    }
+                }
+            } else if let Some(id) = command.strip_prefix("__command:") {
+                self.run_registered_command(id, window, cx);
+            } else if let Some(path) = command.strip_prefix("__file:") {
+                self.add_file_at_view_center(PathBuf::from(path), window, cx);
+            } else if let Some(line) = command.strip_prefix("__symbol:") {
+                if let Ok(line) = line.parse::<usize>() {
+                    self.go_to_active_code_line(line, window, cx);
                 }
             } else if command.starts_with("__theme:") {
                 let theme_name = command.strip_prefix("__theme:").unwrap_or("");
@@ -328,12 +462,23 @@ impl Humanboard {
                 self.create_markdown_note(name.to_string(), window, cx);
             } else if command == "md" {
                 self.create_markdown_note("Untitled".to_string(), window, cx);
+            } else if command == "bg" || command.starts_with("bg ") {
+                let arg = command.strip_prefix("bg").unwrap_or("");
+                self.run_background_command(arg, cx);
+            } else if command == "lines" || command.starts_with("lines ") {
+                let arg = command.strip_prefix("lines").unwrap_or("");
+                self.run_lines_command(arg, cx);
             }
         }
     }
 
     /// Jump to and select an item by ID with smooth animation
-    fn jump_to_item(&mut self, item_id: u64, window: &mut Window, cx: &mut Context<Self>) {
+    pub(crate) fn jump_to_item(
+        &mut self,
+        item_id: u64,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
         if let Some(ref board) = self.canvas.board {
             if let Some(item) = board.items.iter().find(|i| i.id == item_id) {
                 // Get window size for centering
@@ -357,13 +502,16 @@ impl Humanboard {
                 self.ui.pan_animation = Some(PanAnimation {
                     start_offset: board.canvas_offset,
                     target_offset,
+                    start_zoom: board.zoom,
+                    target_zoom: board.zoom,
                     start_time: Instant::now(),
                     duration: Duration::from_millis(300),
                 });
 
-                // Select the item
+                // Select the item, and highlight it so it's easy to spot
                 self.canvas.selected_items.clear();
                 self.canvas.selected_items.insert(item_id);
+                self.canvas.search_highlight = Some((item_id, Instant::now()));
 
                 // Trigger first frame
                 cx.notify();
@@ -391,11 +539,15 @@ impl Humanboard {
                     px(start_x + (target_x - start_x) * eased),
                     px(start_y + (target_y - start_y) * eased),
                 );
+                board.zoom = anim.start_zoom + (anim.target_zoom - anim.start_zoom) * eased;
             }
 
             if progress >= 1.0 {
-                // Animation complete
+                // Animation complete - save the final view
                 self.ui.pan_animation = None;
+                if let Some(ref mut board) = self.canvas.board {
+                    board.mark_dirty();
+                }
                 return false;
             }
             return true;
@@ -425,7 +577,7 @@ impl Humanboard {
         cx.notify();
     }
 
-    fn create_markdown_note(&mut self, name: String, window: &mut Window, cx: &mut Context<Self>) {
+    pub(crate) fn create_markdown_note(&mut self, name: String, window: &mut Window, cx: &mut Context<Self>) {
         // Get board ID from current view
         let board_id = match &self.navigation.view {
             AppView::Board(id) => id.clone(),
@@ -495,22 +647,113 @@ impl Humanboard {
     pub fn paste(&mut self, window: &mut Window, cx: &mut Context<Self>) {
         let clipboard = cx.read_from_clipboard();
         if let Some(item) = clipboard {
+            // Items copied from a board, this one or another
+            let copied = item.entries().iter().find_map(|entry| match entry {
+                ClipboardEntry::String(text) => text.metadata_json::<CopiedItems>(),
+                _ => None,
+            });
+            if let Some(copied) = copied {
+                self.paste_copied_items(&copied, window.mouse_position(), cx);
+                return;
+            }
+            // An image (screenshot, or copied from a browser) wins over any
+            // text copied with it
+            let image = item.entries().iter().find_map(|entry| match entry {
+                ClipboardEntry::Image(image) => Some(image),
+                _ => None,
+            });
+            if let Some(image) = image {
+                self.paste_image(image.bytes(), window.mouse_position(), cx);
+                return;
+            }
             if let Some(text) = item.text() {
                 let text = text.trim();
+                // Get center of window for paste position
+                let bounds = window.bounds();
+                let center = point(
+                    px(f32::from(bounds.size.width) / 2.0),
+                    px(f32::from(bounds.size.height) / 2.0),
+                );
                 // Check if it's a URL
                 if text.starts_with("http://") || text.starts_with("https://") {
-                    // Get center of window for paste position
-                    let bounds = window.bounds();
-                    let center = point(
-                        px(f32::from(bounds.size.width) / 2.0),
-                        px(f32::from(bounds.size.height) / 2.0),
-                    );
-                    if let Some(ref mut board) = self.canvas.board {
-                        board.add_url(text, center);
-                        cx.notify();
-                    }
+                    self.paste_url(text, center, cx);
+                } else {
+                    // A code file, possibly with a line anchor (path#L20-L45)
+                    self.paste_code_reference(text, center, cx);
                 }
             }
         }
     }
+
+    /// Paste items copied from a board at `position` and select them
+    fn paste_copied_items(
+        &mut self,
+        copied: &CopiedItems,
+        position: Point<Pixels>,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(ref mut board) = self.canvas.board else {
+            return;
+        };
+        let mut errors = Vec::new();
+        let ids = board.paste_items(copied, position, &mut errors);
+
+        for error in errors {
+            self.show_toast(crate::notifications::Toast::warning(error));
+        }
+        if !ids.is_empty() {
+            self.canvas.selected_items = ids.into_iter().collect();
+        }
+        cx.notify();
+    }
+
+    /// Save pasted image data into the board and add it at `position`
+    fn paste_image(&mut self, bytes: &[u8], position: Point<Pixels>, cx: &mut Context<Self>) {
+        if self.canvas.board.is_none() {
+            return;
+        }
+        let Some(extension) = crate::board_assets::pasted_image_extension(bytes) else {
+            self.show_toast(crate::notifications::Toast::warning(
+                "The clipboard image isn't in a format Humanboard can show",
+            ));
+            return;
+        };
+        let Some(ref mut board) = self.canvas.board else {
+            return;
+        };
+
+        match board.add_pasted_image(position, extension, bytes) {
+            Ok(id) => {
+                self.canvas.selected_items.clear();
+                self.canvas.selected_items.insert(id);
+            }
+            Err(e) => self.show_toast(crate::notifications::Toast::error(format!(
+                "Couldn't paste image: {}",
+                e
+            ))),
+        }
+        cx.notify();
+    }
+
+    /// Write the profiler's recent frame and operation timings as a Chrome
+    /// trace in the temp directory (debug command)
+    pub fn export_perf_trace(&mut self) {
+        let timestamp = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+        let path = std::env::temp_dir().join(format!("humanboard-trace-{}.json", timestamp));
+
+        let toast = match self.system.perf_monitor.export_trace(&path) {
+            Ok(events) => crate::notifications::Toast::success(format!(
+                "Wrote {} trace events to {}",
+                events,
+                path.display()
+            )),
+            Err(e) => {
+                crate::notifications::Toast::error(format!("Trace export failed: {}", e))
+            }
+        };
+        self.show_toast(toast);
+    }
 }
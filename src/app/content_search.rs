@@ -0,0 +1,58 @@
+//! Content search methods - finding cards by the text inside them, and
+//! highlighting the card chosen from the results
+
+use crate::app::Humanboard;
+use crate::content_search::{search_content, search_highlight_opacity};
+use crate::types::ItemContent;
+
+impl Humanboard {
+    /// Search results for cards on the open board whose text contains
+    /// `query`, leaving out those already in `found`
+    pub(crate) fn content_results(
+        &mut self,
+        query: &str,
+        found: &[(u64, String)],
+    ) -> Vec<(u64, String)> {
+        let Some(ref board) = self.canvas.board else {
+            return Vec::new();
+        };
+        let matches = search_content(
+            &board.items,
+            &board.data_sources,
+            query,
+            &mut self.canvas.search_texts,
+        );
+
+        matches
+            .into_iter()
+            .filter(|found_match| !found.iter().any(|(id, _)| *id == found_match.item_id))
+            .filter_map(|found_match| {
+                let item = board
+                    .items
+                    .iter()
+                    .find(|item| item.id == found_match.item_id)?;
+                let name = match &item.content {
+                    ItemContent::TextBox { .. } => "Text".to_string(),
+                    ItemContent::Table { data_source_id, .. } => board
+                        .data_sources
+                        .get(data_source_id)
+                        .map(|ds| ds.name.clone())
+                        .unwrap_or_else(|| "Table".to_string()),
+                    _ => item.content.display_name(),
+                };
+                Some((item.id, found_match.label(&name)))
+            })
+            .collect()
+    }
+
+    /// The card chosen from the search results and the opacity of its
+    /// highlight, forgetting the highlight once it is over
+    pub fn search_highlight(&mut self) -> Option<(u64, f32)> {
+        let (id, at) = self.canvas.search_highlight?;
+        let opacity = search_highlight_opacity(at.elapsed());
+        if opacity.is_none() {
+            self.canvas.search_highlight = None;
+        }
+        opacity.map(|opacity| (id, opacity))
+    }
+}
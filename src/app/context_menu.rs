@@ -0,0 +1,277 @@
+//! Context menu methods - opening the right-click menus of the canvas and
+//! preview tabs and running their commands
+
+use crate::app::Humanboard;
+use crate::constants::{DOCK_WIDTH, HEADER_HEIGHT};
+use crate::context_menu::{
+    ContextMenu, ContextMenuCommand, MENU_WIDTH, canvas_menu_entries, item_menu_entries,
+    menu_height, menu_origin, tab_menu_entries,
+};
+use crate::item_styles::StyledKind;
+use crate::settings::item_styles;
+use crate::types::{ItemContent, ShapeType};
+use gpui::*;
+
+impl Humanboard {
+    /// Open the item or canvas context menu for a right-click
+    pub fn handle_right_mouse_down(
+        &mut self,
+        event: &MouseDownEvent,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if !self.canvas.input_state.is_idle() || self.system.focus.is_input_active() {
+            return;
+        }
+        self.clear_hover(cx);
+        let Some(ref board) = self.canvas.board else {
+            return;
+        };
+
+        // Only the canvas area has a context menu
+        let position = event.position;
+        let viewport = self.canvas_viewport_size(window);
+        let (x, y) = (f32::from(position.x), f32::from(position.y));
+        let bounds = (
+            DOCK_WIDTH,
+            HEADER_HEIGHT,
+            DOCK_WIDTH + f32::from(viewport.width),
+            HEADER_HEIGHT + f32::from(viewport.height),
+        );
+        if x < bounds.0 || x > bounds.2 || y < bounds.1 || y > bounds.3 {
+            return;
+        }
+
+        let canvas_position = board.screen_to_canvas(position);
+        let (canvas_x, canvas_y) = (f32::from(canvas_position.x), f32::from(canvas_position.y));
+        let entries = match board.topmost_item_at(canvas_x, canvas_y) {
+            Some(item_id) => {
+                // Right-clicking outside the selection selects just that item
+                if !self.canvas.selected_items.contains(&item_id) {
+                    self.canvas.selected_items.clear();
+                    self.canvas.selected_items.insert(item_id);
+                }
+                let all_locked = self
+                    .canvas
+                    .selected_items
+                    .iter()
+                    .all(|&id| board.is_locked(id));
+                item_menu_entries(
+                    all_locked,
+                    self.selected_preview_target().is_some(),
+                    self.selected_highlight_note().is_some(),
+                    self.selected_pdf_item().is_some(),
+                    self.selected_youtube_item().is_some(),
+                    self.selected_text_files().len(),
+                    self.canvas.selected_items.iter().any(|&id| {
+                        board
+                            .get_item(id)
+                            .is_some_and(|item| StyledKind::of(&item.content).is_some())
+                    }),
+                )
+            }
+            None => canvas_menu_entries(),
+        };
+
+        let size = (MENU_WIDTH, menu_height(&entries));
+        let (origin_x, origin_y) = menu_origin((x, y), size, bounds);
+        self.ui.context_menu = Some(ContextMenu {
+            origin: point(px(origin_x), px(origin_y)),
+            canvas_position,
+            entries,
+            tab: None,
+        });
+        self.system.focus.force_canvas_focus(window);
+        cx.stop_propagation();
+        cx.notify();
+    }
+
+    /// Open the menu of a preview tab for a right-click on it
+    pub fn open_tab_menu(
+        &mut self,
+        tab_index: usize,
+        pane: usize,
+        position: Point<Pixels>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(ref preview) = self.preview.panel else {
+            return;
+        };
+        let Some(pinned) = preview
+            .panes
+            .get(pane)
+            .and_then(|p| p.tabs.get(tab_index))
+            .map(|tab| tab.is_pinned())
+        else {
+            return;
+        };
+        self.clear_hover(cx);
+
+        let entries = tab_menu_entries(pinned);
+        let viewport = window.viewport_size();
+        let bounds = (
+            0.0,
+            0.0,
+            f32::from(viewport.width),
+            f32::from(viewport.height),
+        );
+        let size = (MENU_WIDTH, menu_height(&entries));
+        let click = (f32::from(position.x), f32::from(position.y));
+        let (origin_x, origin_y) = menu_origin(click, size, bounds);
+        self.ui.context_menu = Some(ContextMenu {
+            origin: point(px(origin_x), px(origin_y)),
+            canvas_position: Point::default(),
+            entries,
+            tab: Some((tab_index, pane)),
+        });
+        cx.stop_propagation();
+        cx.notify();
+    }
+
+    /// Close the context menu. Returns true if one was open.
+    pub fn close_context_menu(&mut self, cx: &mut Context<Self>) -> bool {
+        if self.ui.context_menu.take().is_some() {
+            cx.notify();
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Run a context menu command and close the menu
+    pub fn run_context_menu_command(
+        &mut self,
+        command: ContextMenuCommand,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(menu) = self.ui.context_menu.take() else {
+            return;
+        };
+
+        match command {
+            ContextMenuCommand::Copy => self.copy_selected(cx),
+            ContextMenuCommand::CopyAsPng => self.copy_selection_as_png(false, cx),
+            ContextMenuCommand::CopyAsPngTransparent => self.copy_selection_as_png(true, cx),
+            ContextMenuCommand::Duplicate => self.duplicate_selected(cx),
+            ContextMenuCommand::Delete => self.delete_selected(cx),
+            ContextMenuCommand::Lock => self.set_selection_locked(true, cx),
+            ContextMenuCommand::Unlock => self.set_selection_locked(false, cx),
+            ContextMenuCommand::Restack(z_order) => {
+                if let Some(ref mut board) = self.canvas.board {
+                    board.reorder_items(&self.canvas.selected_items, z_order);
+                }
+            }
+            ContextMenuCommand::OpenInPreview => self.open_selected_in_preview(window, cx),
+            ContextMenuCommand::OpenHighlight => {
+                if let Some(id) = self.selected_highlight_note() {
+                    self.open_highlight_note(id, window, cx);
+                }
+            }
+            ContextMenuCommand::CompareWithSnapshot => self.compare_selected_with_snapshot(cx),
+            ContextMenuCommand::CompareFiles => self.compare_selected_files(cx),
+            ContextMenuCommand::RegenerateThumbnail => {
+                self.regenerate_selected_pdf_thumbnail(window, cx)
+            }
+            ContextMenuCommand::CopyLinkAtTime => self.copy_youtube_link_at_time(cx),
+            ContextMenuCommand::SetDefaultStyle => self.set_default_style_from_selection(cx),
+            ContextMenuCommand::PinTab | ContextMenuCommand::UnpinTab => {
+                if let Some((tab_index, pane)) = menu.tab {
+                    self.toggle_tab_pinned_in_pane(tab_index, pane, cx);
+                }
+            }
+            ContextMenuCommand::CloseTab => {
+                if let Some((tab_index, pane)) = menu.tab {
+                    self.close_tab_in_pane(tab_index, pane, cx);
+                }
+            }
+            ContextMenuCommand::CloseOtherTabs => {
+                if let Some((tab_index, pane)) = menu.tab {
+                    self.close_other_tabs_in_pane(tab_index, pane, cx);
+                }
+            }
+            ContextMenuCommand::CloseAllTabs => {
+                if let Some((_, pane)) = menu.tab {
+                    self.close_all_tabs_in_pane(pane, cx);
+                }
+            }
+            ContextMenuCommand::Paste => self.paste(window, cx),
+            ContextMenuCommand::NewTextBox => {
+                let content = item_styles().text_box();
+                if let Some(id) = self.add_item_at(menu.canvas_position, content) {
+                    self.start_textbox_editing(id, window, cx);
+                }
+            }
+            ContextMenuCommand::NewShape => {
+                let content = item_styles().shape(ShapeType::Rectangle);
+                self.add_item_at(menu.canvas_position, content);
+            }
+            ContextMenuCommand::NewMarkdownNote => {
+                self.create_markdown_note("Untitled".to_string(), window, cx)
+            }
+            ContextMenuCommand::ZoomToFit => self.zoom_to_fit(window, cx),
+        }
+        cx.notify();
+    }
+
+    /// Lock or unlock every selected item
+    pub fn set_selection_locked(&mut self, locked: bool, cx: &mut Context<Self>) {
+        if let Some(ref mut board) = self.canvas.board {
+            let ids: Vec<u64> = self.canvas.selected_items.iter().copied().collect();
+            if board.set_locked(&ids, locked) {
+                cx.notify();
+            }
+        }
+    }
+
+    /// Add an item at a canvas position and select it
+    fn add_item_at(&mut self, canvas_position: Point<Pixels>, content: ItemContent) -> Option<u64> {
+        let board = self.canvas.board.as_mut()?;
+        let id = board.add_item(canvas_position, content);
+        self.canvas.selected_items.clear();
+        self.canvas.selected_items.insert(id);
+        Some(id)
+    }
+
+    /// What Open in Preview would open for the selection: a single file or table item
+    fn selected_preview_target(&self) -> Option<&ItemContent> {
+        if self.canvas.selected_items.len() != 1 {
+            return None;
+        }
+        let id = *self.canvas.selected_items.iter().next()?;
+        let item = self.canvas.board.as_ref()?.get_item(id)?;
+        match item.content {
+            ItemContent::Pdf { .. }
+            | ItemContent::Markdown { .. }
+            | ItemContent::Code { .. }
+            | ItemContent::Table { .. } => Some(&item.content),
+            _ => None,
+        }
+    }
+
+    fn open_selected_in_preview(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        match self.selected_preview_target().cloned() {
+            Some(ItemContent::Pdf { path, .. } | ItemContent::Markdown { path, .. }) => {
+                self.open_preview(path, window, cx)
+            }
+            Some(ItemContent::Code { path, lines, .. }) => {
+                self.open_preview_at_lines(path, lines, window, cx)
+            }
+            Some(ItemContent::Table { data_source_id, .. }) => {
+                let name = self
+                    .canvas
+                    .board
+                    .as_ref()
+                    .and_then(|board| board.data_sources.get(&data_source_id))
+                    .and_then(|ds| ds.file_path())
+                    .and_then(|p| p.file_stem())
+                    .and_then(|n| n.to_str())
+                    .unwrap_or("Table")
+                    .to_string();
+                self.open_table_preview(data_source_id, name, window, cx);
+            }
+            _ => {}
+        }
+    }
+}
@@ -0,0 +1,184 @@
+//! Data preview methods - paging, sorting and filtering the rows of CSV and
+//! table tabs, and switching table tabs between viewing and editing
+
+use crate::app::{DataView, Humanboard, PreviewPanel, PreviewTab};
+use crate::background::BackgroundExecutor;
+use crate::data::{DATA_FILES, DataRows, RowOrder, RowSelection};
+use crate::data_table::{DataTableAction, DataTableState};
+use gpui::*;
+use gpui_component::input::{InputEvent, InputState};
+
+/// Rows for a new data or table tab, with a filter input above them
+pub(crate) fn new_data_view(
+    total_rows: usize,
+    window: &mut Window,
+    cx: &mut Context<Humanboard>,
+) -> DataView {
+    let filter = cx.new(|cx| InputState::new(window, cx).placeholder("Filter rows..."));
+    cx.subscribe(&filter, |this, input, event: &InputEvent, cx| {
+        if let InputEvent::Change { .. } = event {
+            let text = input.read(cx).text().to_string();
+            this.filter_data_rows(input.entity_id(), &text, cx);
+        }
+    })
+    .detach();
+    DataView {
+        table: DataTableState::new(total_rows),
+        order: RowOrder::default(),
+        filter,
+    }
+}
+
+impl Humanboard {
+    /// Load the files of data tabs that aren't loaded yet, and keep each
+    /// view's page count in line with the rows it shows
+    pub fn ensure_data_views(&mut self, cx: &mut Context<Self>) {
+        let Some(ref mut preview) = self.preview.panel else {
+            return;
+        };
+        for tab in preview.all_tabs_mut() {
+            if let PreviewTab::Data { path, .. } = tab {
+                DATA_FILES.load(path, &self.system.background);
+            }
+            let Some(rows) = tab.data_rows(cx) else {
+                continue;
+            };
+            let Some(view) = tab.data_view_mut() else {
+                continue;
+            };
+            // A filter typed while the file was still loading
+            if view.order.selection() == RowSelection::All && !view.table.shows_all_rows() {
+                view.order.update(
+                    rows.clone(),
+                    &view.table.filter,
+                    view.table.sort,
+                    &self.system.background,
+                );
+            }
+            let shown = match view.order.selection() {
+                RowSelection::All => rows.row_count(),
+                RowSelection::Rows(order) => order.len(),
+                RowSelection::Pending => continue,
+            };
+            if view.table.total_rows != shown {
+                view.table.set_total_rows(shown);
+            }
+        }
+    }
+
+    /// Whether a data tab's file or row order is still being worked out
+    pub fn has_pending_data_rows(&self) -> bool {
+        let Some(ref preview) = self.preview.panel else {
+            return false;
+        };
+        DATA_FILES.has_pending()
+            || preview
+                .all_tabs()
+                .any(|tab| tab.data_view().is_some_and(|view| view.order.is_pending()))
+    }
+
+    /// Apply a click in a data view's table, sorting its rows again when a
+    /// column header was clicked
+    pub fn apply_data_table_action(
+        &mut self,
+        view_id: EntityId,
+        action: DataTableAction,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(tab) = find_data_tab(self.preview.panel.as_mut(), view_id) else {
+            return;
+        };
+        if let Some(view) = tab.data_view_mut() {
+            view.table.apply(action);
+        }
+        if let DataTableAction::SortBy(_) = action {
+            update_row_order(tab, &self.system.background, cx);
+        }
+        cx.notify();
+    }
+
+    /// Show only the rows of a data view containing `text`
+    fn filter_data_rows(&mut self, view_id: EntityId, text: &str, cx: &mut Context<Self>) {
+        let Some(tab) = find_data_tab(self.preview.panel.as_mut(), view_id) else {
+            return;
+        };
+        if let Some(view) = tab.data_view_mut() {
+            if view.table.filter == text {
+                return;
+            }
+            view.table.set_filter(text);
+        }
+        update_row_order(tab, &self.system.background, cx);
+        cx.notify();
+    }
+
+    /// Switch a table tab between viewing its rows and editing its cells
+    pub fn toggle_table_editing(&mut self, data_source_id: u64, cx: &mut Context<Self>) {
+        let Some(ref mut preview) = self.preview.panel else {
+            return;
+        };
+        let Some(tab) = preview.all_tabs_mut().find(
+            |tab| matches!(tab, PreviewTab::Table { data_source_id: id, .. } if *id == data_source_id),
+        ) else {
+            return;
+        };
+        if let PreviewTab::Table { editing, .. } = tab {
+            *editing = !*editing;
+        }
+        // Edits may have changed which rows match and how they sort
+        update_row_order(tab, &self.system.background, cx);
+        cx.notify();
+    }
+}
+
+impl PreviewTab {
+    /// The rows a data or table tab shows, once they are loaded
+    pub fn data_rows(&self, cx: &App) -> Option<DataRows> {
+        match self {
+            PreviewTab::Data { path, .. } => DATA_FILES.get(path),
+            PreviewTab::Table {
+                table_state: Some(state),
+                ..
+            } => Some(DataRows::Memory(
+                state.read(cx).delegate().data_source().clone(),
+            )),
+            _ => None,
+        }
+    }
+
+    pub fn data_view(&self) -> Option<&DataView> {
+        match self {
+            PreviewTab::Data { view, .. } => Some(view),
+            PreviewTab::Table { view, .. } => view.as_ref(),
+            _ => None,
+        }
+    }
+
+    pub fn data_view_mut(&mut self) -> Option<&mut DataView> {
+        match self {
+            PreviewTab::Data { view, .. } => Some(view),
+            PreviewTab::Table { view, .. } => view.as_mut(),
+            _ => None,
+        }
+    }
+}
+
+/// The tab whose data view has the filter input `view_id`
+fn find_data_tab(preview: Option<&mut PreviewPanel>, view_id: EntityId) -> Option<&mut PreviewTab> {
+    let preview = preview?;
+    preview.all_tabs_mut().find(|tab| {
+        tab.data_view()
+            .is_some_and(|view| view.filter.entity_id() == view_id)
+    })
+}
+
+/// Work out again which rows a tab shows, and in what order
+fn update_row_order(tab: &mut PreviewTab, background: &BackgroundExecutor, cx: &App) {
+    let Some(rows) = tab.data_rows(cx) else {
+        return;
+    };
+    if let Some(view) = tab.data_view_mut() {
+        view.order
+            .update(rows, &view.table.filter, view.table.sort, background);
+    }
+}
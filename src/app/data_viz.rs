@@ -119,6 +119,9 @@ impl Humanboard {
                     table_item.position.1,
                 );
 
+                // Adding and sizing the chart undo together
+                board.begin_group("Added chart");
+
                 // Create a new chart item
                 let chart_id = board.add_item(
                     point(px(chart_pos.0), px(chart_pos.1)),
@@ -143,6 +146,7 @@ impl Humanboard {
 
                 // Save
                 board.push_history();
+                board.end_group();
                 let _ = board.flush_save();
 
                 self.show_toast(crate::notifications::Toast::success(
@@ -296,6 +300,9 @@ impl Humanboard {
         let chart_x = table_pos.0 + table_size.0 + 50.0;
         let chart_y = table_pos.1;
 
+        // Adding and sizing the chart undo together
+        board.begin_group("Added chart");
+
         // Create the chart item linked to the same data source
         let chart_id = board.add_item(
             point(px(chart_x), px(chart_y)),
@@ -320,6 +327,7 @@ impl Humanboard {
 
         // Save
         board.push_history();
+        board.end_group();
         let _ = board.flush_save();
 
         cx.notify();
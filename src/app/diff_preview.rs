@@ -0,0 +1,192 @@
+//! Diff preview methods - comparing the files of code and markdown cards
+//! with each other or with their last board snapshot, in diff tabs
+
+use crate::app::{Humanboard, PreviewPanel, PreviewTab, SplitDirection, TabMeta};
+use crate::board_versions::snapshot_file_text;
+use crate::syntax_highlight::DiffHighlights;
+use crate::text_diff::{DiffBase, TextDiff};
+use crate::types::{ItemContent, language_from_extension};
+use gpui::*;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+impl Humanboard {
+    /// Open a tab comparing `path` with `base`, or switch to it if it is open
+    pub fn open_diff_preview(&mut self, path: PathBuf, base: DiffBase, cx: &mut Context<Self>) {
+        if let Some(ref mut preview) = self.preview.panel {
+            let open = preview.panes.iter().enumerate().find_map(|(pane, p)| {
+                diff_tab_index(&p.tabs, &path, &base).map(|index| (pane, index))
+            });
+            if let Some((pane, index)) = open {
+                if index != preview.panes[pane].active_tab {
+                    preview.record_visit(pane, cx);
+                }
+                preview.panes[pane].active_tab = index;
+                preview.focused_pane = pane;
+                cx.notify();
+                return;
+            }
+        }
+
+        let tab = self.new_diff_tab(path, base, TabMeta::default());
+        if let Some(ref mut preview) = self.preview.panel {
+            preview.record_visit(0, cx);
+            let pane = &mut preview.panes[0];
+            pane.tabs.push(tab);
+            pane.active_tab = pane.tabs.len() - 1;
+        } else {
+            let mut panel = PreviewPanel::new(SplitDirection::Vertical, 0.4);
+            panel.panes[0].tabs.push(tab);
+            self.preview.panel = Some(panel);
+        }
+        cx.notify();
+    }
+
+    /// A diff tab comparing the files as they are now
+    pub(crate) fn new_diff_tab(&self, path: PathBuf, base: DiffBase, meta: TabMeta) -> PreviewTab {
+        let (base_label, diff) = self.compare_file(&path, &base);
+        let language = diff_language(&path);
+        PreviewTab::Diff {
+            language: language.to_string(),
+            highlights: diff_highlights(&diff, language),
+            path,
+            base,
+            base_label,
+            diff,
+            side_by_side: false,
+            meta,
+        }
+    }
+
+    /// Compare a diff tab's files again, after either has changed
+    pub fn refresh_diff(&mut self, path: &Path, base: &DiffBase, cx: &mut Context<Self>) {
+        let (label, result) = self.compare_file(path, base);
+        if let Some(PreviewTab::Diff {
+            base_label,
+            language,
+            diff,
+            highlights,
+            ..
+        }) = self.diff_tab_mut(path, base)
+        {
+            *base_label = label;
+            *highlights = diff_highlights(&result, language);
+            *diff = result;
+            cx.notify();
+        }
+    }
+
+    /// Switch a diff tab between unified and side-by-side lines
+    pub fn toggle_diff_layout(&mut self, path: &Path, base: &DiffBase, cx: &mut Context<Self>) {
+        if let Some(PreviewTab::Diff { side_by_side, .. }) = self.diff_tab_mut(path, base) {
+            *side_by_side = !*side_by_side;
+            cx.notify();
+        }
+    }
+
+    /// Files of the selected code and markdown cards, in the order the
+    /// cards were added. Empty unless every selected item is one.
+    pub(crate) fn selected_text_files(&self) -> Vec<PathBuf> {
+        let Some(ref board) = self.canvas.board else {
+            return Vec::new();
+        };
+        let mut ids: Vec<u64> = self.canvas.selected_items.iter().copied().collect();
+        ids.sort_unstable();
+        let files: Vec<PathBuf> = ids
+            .iter()
+            .filter_map(|&id| match &board.get_item(id)?.content {
+                ItemContent::Code { path, .. } | ItemContent::Markdown { path, .. } => {
+                    Some(path.clone())
+                }
+                _ => None,
+            })
+            .collect();
+        if files.len() == ids.len() {
+            files
+        } else {
+            Vec::new()
+        }
+    }
+
+    /// Compare the selected card's file with its text in the newest board
+    /// version
+    pub fn compare_selected_with_snapshot(&mut self, cx: &mut Context<Self>) {
+        if let [path] = self.selected_text_files().as_slice() {
+            self.open_diff_preview(path.clone(), DiffBase::Snapshot, cx);
+        }
+    }
+
+    /// Compare the files of the two selected cards, the one added later
+    /// against the one added first
+    pub fn compare_selected_files(&mut self, cx: &mut Context<Self>) {
+        if let [old, new] = self.selected_text_files().as_slice() {
+            self.open_diff_preview(new.clone(), DiffBase::File(old.clone()), cx);
+        }
+    }
+
+    /// The name of the old side of a comparison, and the diff of `path`
+    /// against it
+    fn compare_file(&self, path: &Path, base: &DiffBase) -> (String, Result<TextDiff, String>) {
+        let (label, old) = match base {
+            DiffBase::File(other) => (file_name(other), read_text(other)),
+            DiffBase::Snapshot => {
+                let snapshot = self
+                    .canvas
+                    .board
+                    .as_ref()
+                    .and_then(|board| snapshot_file_text(&board.board_dir(), path));
+                match snapshot {
+                    Some((version, text)) => {
+                        (format!("Snapshot {}", version.formatted_age()), Ok(text))
+                    }
+                    None => (
+                        "Snapshot".to_string(),
+                        Err("No board version has kept this file yet".to_string()),
+                    ),
+                }
+            }
+        };
+        let diff = old.and_then(|old| Ok(TextDiff::new(&old, &read_text(path)?)));
+        (label, diff)
+    }
+
+    fn diff_tab_mut(&mut self, path: &Path, base: &DiffBase) -> Option<&mut PreviewTab> {
+        let preview = self.preview.panel.as_mut()?;
+        preview
+            .all_tabs_mut()
+            .find(|tab| is_diff_tab(tab, path, base))
+    }
+}
+
+fn diff_tab_index(tabs: &[PreviewTab], path: &Path, base: &DiffBase) -> Option<usize> {
+    tabs.iter().position(|tab| is_diff_tab(tab, path, base))
+}
+
+fn is_diff_tab(tab: &PreviewTab, path: &Path, base: &DiffBase) -> bool {
+    matches!(tab, PreviewTab::Diff { path: p, base: b, .. } if p == path && b == base)
+}
+
+/// Language the lines of a file are highlighted as
+fn diff_language(path: &Path) -> &'static str {
+    match path.extension().and_then(|e| e.to_str()) {
+        Some("md" | "markdown") => "markdown",
+        Some(ext) => language_from_extension(ext).unwrap_or("text"),
+        None => "text",
+    }
+}
+
+/// Highlights of a comparison's lines, none if it couldn't be made
+fn diff_highlights(diff: &Result<TextDiff, String>, language: &str) -> DiffHighlights {
+    DiffHighlights::new(diff.as_ref().unwrap_or(&TextDiff::default()), language)
+}
+
+fn read_text(path: &Path) -> Result<String, String> {
+    fs::read_to_string(path).map_err(|e| format!("Couldn't read {}: {}", file_name(path), e))
+}
+
+fn file_name(path: &Path) -> String {
+    path.file_name()
+        .and_then(|n| n.to_str())
+        .unwrap_or("Untitled")
+        .to_string()
+}
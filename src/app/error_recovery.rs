@@ -1,10 +1,31 @@
 //! Error recovery methods for handling toast actions.
 //!
 //! This module provides handlers for error recovery actions triggered from
-//! toast notifications, such as Retry, Save As, Reset Settings, and Reload.
+//! toast notifications, such as Retry, Save As, Reset Settings, Reload, and
+//! recovering unsaved changes from a board's crash journal. It also warns
+//! at startup when a capability like PDF rendering is missing.
 
-use crate::notifications::{Toast, ToastActionType};
+use crate::board_index::BoardIndex;
+use crate::board_journal::BoardJournal;
+use crate::notifications::{Toast, ToastAction, ToastActionType};
+use crate::pdf::PdfiumLoader;
 use gpui::*;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+
+/// How long the crash recovery prompt stays visible
+const RECOVERY_PROMPT_DURATION: Duration = Duration::from_secs(30);
+
+/// How long the missing pdfium warning stays visible
+const PDFIUM_WARNING_DURATION: Duration = Duration::from_secs(15);
+
+/// Whether the missing pdfium warning was shown, so only the first window
+/// shows it
+static PDFIUM_WARNING_SHOWN: AtomicBool = AtomicBool::new(false);
+
+/// Whether boards were checked for crash journals, so only the first window
+/// prompts to recover them
+static JOURNAL_RECOVERY_CHECKED: AtomicBool = AtomicBool::new(false);
 
 impl super::Humanboard {
     /// Handle a toast action button click
@@ -27,6 +48,12 @@ impl super::Humanboard {
             ToastActionType::ReloadWebview => {
                 self.handle_reload_webview_action(cx);
             }
+            ToastActionType::RecoverBoard(board_id) => {
+                self.handle_recover_board_action(board_id, cx);
+            }
+            ToastActionType::DiscardRecovery(board_id) => {
+                self.handle_discard_recovery_action(&board_id, cx);
+            }
             ToastActionType::Dismiss => {
                 // Just dismiss, no action needed
             }
@@ -64,11 +91,116 @@ impl super::Humanboard {
 
     /// Handle reload webview action - reload the current webview
     fn handle_reload_webview_action(&mut self, cx: &mut Context<Self>) {
-        // Clear webviews to force reload on next render
+        // Clear webviews to force reload on next render, resuming where
+        // the players were
+        self.persist_playback_positions();
         self.webviews.youtube.clear();
+        self.webviews.vimeo.clear();
+        self.webviews.soundcloud.clear();
+        self.webviews.spotify.clear();
         self.webviews.audio.clear();
         self.webviews.video.clear();
         self.ui.toast_manager.push(Toast::info("Webviews reloaded"));
         cx.notify();
     }
+
+    /// Prompt once per run to recover every board that has unsaved changes
+    /// from a crash
+    pub fn check_journal_recovery(&mut self) {
+        if JOURNAL_RECOVERY_CHECKED.swap(true, Ordering::Relaxed) {
+            return;
+        }
+        let pending: Vec<(String, String)> = self
+            .navigation
+            .board_index
+            .active_boards()
+            .into_iter()
+            // Boards open in another window are prompted for there
+            .filter(|meta| !BoardIndex::is_board_open(&meta.id))
+            .filter(|meta| BoardJournal::new(meta.board_dir()).has_pending_recovery())
+            .map(|meta| (meta.id.clone(), meta.name.clone()))
+            .collect();
+
+        for (id, name) in pending {
+            self.push_recovery_prompt(id, &name);
+        }
+    }
+
+    /// Warn once per run if pdfium can't be loaded, saying how to fix it.
+    /// PDFs still get fallback thumbnails, but pages, text and outlines
+    /// need pdfium.
+    pub fn check_pdfium_availability(&mut self) {
+        let Err(e) = PdfiumLoader::availability() else {
+            return;
+        };
+        if PDFIUM_WARNING_SHOWN.swap(true, Ordering::Relaxed) {
+            return;
+        }
+        tracing::warn!("pdfium is unavailable: {}", e);
+        self.ui.toast_manager.push(
+            Toast::warning(PdfiumLoader::remediation()).with_duration(PDFIUM_WARNING_DURATION),
+        );
+    }
+
+    /// Show the recovery prompt for a single board
+    pub(crate) fn push_recovery_prompt(&mut self, board_id: String, board_name: &str) {
+        self.ui.toast_manager.push(
+            Toast::warning(format!("Unsaved changes found in \"{}\"", board_name))
+                .with_action(ToastAction::recover_board(board_id.clone()))
+                .with_secondary_action(ToastAction::discard_recovery(board_id))
+                .with_duration(RECOVERY_PROMPT_DURATION),
+        );
+    }
+
+    /// Handle recover action - open the board and replay its crash journal
+    fn handle_recover_board_action(&mut self, board_id: String, cx: &mut Context<Self>) {
+        let is_open = self.canvas.board.as_ref().is_some_and(|b| b.id == board_id);
+        if !is_open {
+            // Save the board open here before replacing it
+            if self.canvas.board.is_some() {
+                self.go_home(cx);
+            }
+            self.open_board(board_id.clone(), cx);
+        }
+
+        // The board may have been opened in another window instead
+        if let Some(board) = self.canvas.board.as_mut().filter(|b| b.id == board_id) {
+            let recovered = board.recover_from_journal();
+            if recovered == 0 {
+                self.ui.toast_manager
+                    .push(Toast::info("Nothing to recover"));
+            } else if let Err(e) = board.flush_save() {
+                self.ui.toast_manager.push(
+                    Toast::error(format!("Save failed: {}", e)).with_action(ToastAction::retry()),
+                );
+            } else {
+                self.ui.toast_manager.push(Toast::success(format!(
+                    "Recovered {} unsaved change(s)",
+                    recovered
+                )));
+            }
+        }
+        cx.notify();
+    }
+
+    /// Handle discard action - drop a board's crash journal without replaying it
+    fn handle_discard_recovery_action(&mut self, board_id: &str, cx: &mut Context<Self>) {
+        if let Some(board) = self.canvas.board.as_mut().filter(|b| b.id == board_id) {
+            board.discard_recovery();
+        } else if BoardIndex::is_board_open(board_id) {
+            // Its window journals to the same file - leave it to that window
+            self.ui.toast_manager
+                .push(Toast::info("Board is open in another window"));
+            cx.notify();
+            return;
+        } else if let Some(meta) = self.navigation.board_index.get_board(board_id) {
+            // Not loaded since the crash, so the journal may not be stashed yet
+            let journal = BoardJournal::new(meta.board_dir());
+            journal.clear();
+            journal.discard_recovered();
+        }
+        self.ui.toast_manager
+            .push(Toast::info("Discarded unsaved changes"));
+        cx.notify();
+    }
 }
@@ -0,0 +1,153 @@
+//! File import methods - reading dropped files in the background and adding
+//! them to the board in a grid once all of them are ready
+
+use super::state::FileImport;
+use crate::app::Humanboard;
+use crate::file_import::{ImportedFile, import_file, remove_copies};
+use crate::notifications::{Toast, ToastAction};
+use gpui::*;
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+/// How long an import runs before its progress is shown
+const PROGRESS_DELAY: Duration = Duration::from_millis(300);
+
+impl Humanboard {
+    /// Import files dropped at `position` in the window. They are read in
+    /// the background and added to the board together, laid out in a grid.
+    pub(crate) fn import_dropped_files(
+        &mut self,
+        position: Point<Pixels>,
+        paths: Vec<PathBuf>,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(ref board) = self.canvas.board else {
+            return;
+        };
+        if paths.is_empty() {
+            return;
+        }
+
+        let import_dir = board.import_dir();
+        let (tx, rx) = std::sync::mpsc::channel();
+        self.canvas.file_imports.push(FileImport {
+            board_id: board.id.clone(),
+            import_dir: import_dir.clone(),
+            position,
+            total: paths.len(),
+            done: 0,
+            files: Vec::new(),
+            errors: Vec::new(),
+            rx,
+            started_at: Instant::now(),
+        });
+        cx.background_executor()
+            .spawn(async move {
+                for path in paths {
+                    let mut errors = Vec::new();
+                    let file = import_file(import_dir.as_deref(), &path, &mut errors);
+                    let _ = tx.send((file, errors));
+                }
+            })
+            .detach();
+        cx.notify();
+    }
+
+    /// Collect files read by `import_dropped_files` and add finished imports
+    /// to the board (called from render).
+    ///
+    /// Returns true while files are still being read, so the caller keeps
+    /// rendering until they arrive.
+    pub(crate) fn poll_file_imports(&mut self, cx: &mut Context<Self>) -> bool {
+        if self.canvas.file_imports.is_empty() {
+            return false;
+        }
+
+        let mut changed = false;
+        for import in &mut self.canvas.file_imports {
+            while let Ok((file, errors)) = import.rx.try_recv() {
+                import.done += 1;
+                import.files.extend(file);
+                import.errors.extend(errors);
+                changed = true;
+            }
+        }
+
+        let (finished, running): (Vec<FileImport>, Vec<FileImport>) =
+            std::mem::take(&mut self.canvas.file_imports)
+                .into_iter()
+                .partition(|import| import.done >= import.total);
+        self.canvas.file_imports = running;
+        for import in finished {
+            self.add_file_import(import);
+        }
+
+        if changed {
+            cx.notify();
+        }
+        !self.canvas.file_imports.is_empty()
+    }
+
+    /// Files read and dropped so far across running imports, once they have
+    /// been running long enough to show their progress
+    pub(crate) fn file_import_progress(&self) -> Option<(usize, usize)> {
+        let imports = &self.canvas.file_imports;
+        if !imports
+            .iter()
+            .any(|import| import.started_at.elapsed() >= PROGRESS_DELAY)
+        {
+            return None;
+        }
+        let done = imports.iter().map(|import| import.done).sum();
+        let total = imports.iter().map(|import| import.total).sum();
+        Some((done, total))
+    }
+
+    fn add_file_import(&mut self, import: FileImport) {
+        for error in import.errors {
+            self.show_toast(Toast::error(error).with_action(ToastAction::retry()));
+        }
+        // The user may have closed or switched boards while this was running
+        let Some(board) = self
+            .canvas
+            .board
+            .as_mut()
+            .filter(|board| board.id == import.board_id)
+        else {
+            self.drop_file_import(import.files, import.import_dir);
+            return;
+        };
+
+        let ids = board.add_imported_files(import.position, import.files);
+        if ids.is_empty() {
+            return;
+        }
+        let msg = if ids.len() == 1 {
+            "Added 1 item".to_string()
+        } else {
+            format!("Added {} items", ids.len())
+        };
+        self.canvas.selected_items = ids.into_iter().collect();
+        self.show_toast(Toast::success(msg));
+    }
+
+    /// Give up on files read for a board that is no longer open, deleting
+    /// the copies made of them
+    fn drop_file_import(&mut self, files: Vec<ImportedFile>, import_dir: Option<PathBuf>) {
+        if files.is_empty() {
+            return;
+        }
+        if let Some(dir) = import_dir {
+            remove_copies(&files, &dir);
+        }
+        let msg = if files.len() == 1 {
+            "The board changed before 1 file was added; drop it again".to_string()
+        } else {
+            format!(
+                "The board changed before {} files were added; drop them again",
+                files.len()
+            )
+        };
+        self.show_toast(Toast::warning(msg));
+    }
+}
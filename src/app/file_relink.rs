@@ -0,0 +1,116 @@
+//! File relinking methods - tracking missing files and the "Locate file…" flow
+
+use crate::app::Humanboard;
+use crate::constants::MISSING_FILE_CHECK_INTERVAL_SECS;
+use crate::file_links::missing_files;
+use crate::notifications::{Toast, ToastAction};
+use gpui::*;
+use std::sync::mpsc;
+use std::time::{Duration, Instant};
+
+impl Humanboard {
+    /// Re-check which items point at missing files, in the background.
+    ///
+    /// Unless `force` is set, checks are throttled to one per
+    /// `MISSING_FILE_CHECK_INTERVAL_SECS` since they stat every file.
+    pub(crate) fn refresh_missing_files(&mut self, force: bool, cx: &mut Context<Self>) {
+        let Some(ref board) = self.canvas.board else {
+            return;
+        };
+
+        let interval = Duration::from_secs(MISSING_FILE_CHECK_INTERVAL_SECS);
+        if !force
+            && self
+                .canvas
+                .missing_files_checked_at
+                .is_some_and(|at| at.elapsed() < interval)
+        {
+            return;
+        }
+        self.canvas.missing_files_checked_at = Some(Instant::now());
+
+        let files = board.linked_files();
+        let (tx, rx) = mpsc::channel();
+        cx.background_executor()
+            .spawn(async move {
+                let _ = tx.send(missing_files(&files));
+            })
+            .detach();
+        self.canvas.missing_files_rx = Some(rx);
+    }
+
+    /// Badge the items found missing once a check is done (called from
+    /// render).
+    ///
+    /// Returns true while the check is running, so the caller keeps
+    /// rendering until it is done.
+    pub(crate) fn poll_missing_files(&mut self, cx: &mut Context<Self>) -> bool {
+        let Some(ref rx) = self.canvas.missing_files_rx else {
+            return false;
+        };
+        let Ok(missing) = rx.try_recv() else {
+            return true;
+        };
+        self.canvas.missing_files_rx = None;
+        if missing != self.canvas.missing_files {
+            self.canvas.missing_files = missing;
+            cx.notify();
+        }
+        false
+    }
+
+    /// Ask the user where a missing item's file went
+    pub fn locate_missing_file(&mut self, item_id: u64, cx: &mut Context<Self>) {
+        let paths_rx = cx.prompt_for_paths(PathPromptOptions {
+            files: true,
+            directories: false,
+            multiple: false,
+            prompt: None,
+        });
+
+        // Same channel workaround as Open File - the result is polled in render
+        let (tx, rx) = mpsc::channel();
+        cx.background_executor()
+            .spawn(async move {
+                if let Ok(Ok(Some(paths))) = paths_rx.await {
+                    if let Some(path) = paths.into_iter().next() {
+                        let _ = tx.send((item_id, path));
+                    }
+                }
+            })
+            .detach();
+
+        self.canvas.relink_rx = Some(rx);
+    }
+
+    /// Apply a file located through `locate_missing_file` (called from render)
+    pub(crate) fn poll_relink_result(&mut self, cx: &mut Context<Self>) {
+        let Some(ref rx) = self.canvas.relink_rx else {
+            return;
+        };
+        let Ok((item_id, path)) = rx.try_recv() else {
+            return;
+        };
+        self.canvas.relink_rx = None;
+
+        let Some(ref mut board) = self.canvas.board else {
+            return;
+        };
+
+        let relinked = board.relink_file(item_id, &path);
+        if let Err(e) = board.flush_save() {
+            self.ui.toast_manager.push(
+                Toast::error(format!("Save failed: {}", e)).with_action(ToastAction::retry()),
+            );
+        } else if relinked > 1 {
+            self.ui
+                .toast_manager
+                .push(Toast::success(format!("Relinked {} files", relinked)));
+        } else if relinked == 1 {
+            self.ui.toast_manager.push(Toast::success("File relinked"));
+        }
+
+        self.refresh_missing_files(true, cx);
+        cx.notify();
+    }
+}
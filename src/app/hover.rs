@@ -0,0 +1,95 @@
+//! Hover methods - tracking the item under the cursor and showing its tooltip
+
+use crate::app::Humanboard;
+use crate::constants::{DOCK_WIDTH, HEADER_HEIGHT};
+use crate::item_tooltip::{HoverState, item_tooltip, media_details};
+use gpui::*;
+
+impl Humanboard {
+    /// Track the item under the cursor. Moving onto another item restarts the delay.
+    pub fn update_hover(
+        &mut self,
+        position: Point<Pixels>,
+        window: &Window,
+        cx: &mut Context<Self>,
+    ) {
+        let hovered = self.hoverable_item_at(position, window);
+        let current = self.ui.hover.as_ref().map(|hover| hover.item_id);
+        if hovered == current {
+            // Follow the cursor until the tooltip appears, then keep it still
+            if let Some(hover) = self.ui.hover.as_mut().filter(|h| h.tooltip.is_none()) {
+                hover.position = position;
+            }
+            return;
+        }
+        self.ui.hover = hovered.map(|id| HoverState::new(id, position));
+        cx.notify();
+    }
+
+    /// Hide the tooltip and forget the hovered item
+    pub fn clear_hover(&mut self, cx: &mut Context<Self>) {
+        if self.ui.hover.take().is_some() {
+            cx.notify();
+        }
+    }
+
+    /// Fill in the tooltip once the hover delay has passed.
+    /// Returns true while still waiting, so the caller can request another frame.
+    pub fn update_hover_tooltip(&mut self) -> bool {
+        let Some(ref mut hover) = self.ui.hover else {
+            return false;
+        };
+        if hover.tooltip.is_some() {
+            return false;
+        }
+        if !hover.is_due() {
+            return true;
+        }
+
+        let Some(ref board) = self.canvas.board else {
+            return false;
+        };
+        match board.get_item(hover.item_id) {
+            Some(item) => {
+                let mut tooltip = item_tooltip(item, &board.data_sources);
+                tooltip.details.splice(0..0, media_details(&item.content));
+                let backlinks = self.canvas.wiki_links.backlink_titles(item.id, &board.items);
+                if !backlinks.is_empty() {
+                    tooltip
+                        .details
+                        .push(format!("Linked from: {}", backlinks.join(", ")));
+                }
+                hover.tooltip = Some(tooltip);
+            }
+            // The item went away while hovered
+            None => self.ui.hover = None,
+        }
+        false
+    }
+
+    /// Topmost item under the cursor, when nothing else is going on
+    fn hoverable_item_at(&self, position: Point<Pixels>, window: &Window) -> Option<u64> {
+        if !self.canvas.input_state.is_idle()
+            || self.ui.context_menu.is_some()
+            || self.ui.command_palette.is_some()
+            || self.settings.show
+            || self.ui.show_shortcuts
+        {
+            return None;
+        }
+        let board = self.canvas.board.as_ref()?;
+
+        let viewport = self.canvas_viewport_size(window);
+        let (x, y) = (f32::from(position.x), f32::from(position.y));
+        if x < DOCK_WIDTH
+            || y < HEADER_HEIGHT
+            || x > DOCK_WIDTH + f32::from(viewport.width)
+            || y > HEADER_HEIGHT + f32::from(viewport.height)
+        {
+            return None;
+        }
+
+        let canvas_position = board.screen_to_canvas(position);
+        board.topmost_item_at(f32::from(canvas_position.x), f32::from(canvas_position.y))
+    }
+}
@@ -0,0 +1,60 @@
+//! Image sizing methods - measuring newly added images off the UI thread
+
+use crate::app::Humanboard;
+use crate::types::image_item_size;
+use gpui::*;
+
+impl Humanboard {
+    /// Measure images added since the last frame in the background.
+    ///
+    /// Images go on the canvas with a placeholder size so a drop never waits
+    /// on reading a large file; their real size is applied in
+    /// `poll_image_sizes` once it arrives. Called from render.
+    pub(crate) fn measure_new_images(&mut self, cx: &mut Context<Self>) {
+        let Some(ref mut board) = self.canvas.board else {
+            return;
+        };
+        let images = board.take_unmeasured_images();
+        if images.is_empty() {
+            return;
+        }
+
+        self.canvas.images_measuring += images.len();
+        let board_id = board.id.clone();
+        let tx = self.canvas.image_size_tx.clone();
+        cx.background_executor()
+            .spawn(async move {
+                for (item_id, path) in images {
+                    let _ = tx.send((board_id.clone(), item_id, image_item_size(&path)));
+                }
+            })
+            .detach();
+    }
+
+    /// Resize images measured by `measure_new_images` (called from render).
+    ///
+    /// Returns true while images are still being measured, so the caller
+    /// keeps rendering until they arrive.
+    pub(crate) fn poll_image_sizes(&mut self, cx: &mut Context<Self>) -> bool {
+        let mut resized = false;
+        while let Ok((board_id, item_id, size)) = self.canvas.image_size_rx.try_recv() {
+            self.canvas.images_measuring = self.canvas.images_measuring.saturating_sub(1);
+
+            // Unreadable images keep the placeholder size
+            let Some(size) = size else {
+                continue;
+            };
+            if let Some(ref mut board) = self.canvas.board {
+                // The user may have switched boards while this was running
+                if board.id == board_id && board.apply_image_size(item_id, size) {
+                    resized = true;
+                }
+            }
+        }
+
+        if resized {
+            cx.notify();
+        }
+        self.canvas.images_measuring > 0
+    }
+}
@@ -0,0 +1,243 @@
+//! Image viewer methods - zooming, panning and inspecting image preview
+//! tabs, and stepping through the board's images from them
+
+use crate::actions::item_bounds;
+use crate::app::{Humanboard, PreviewTab};
+use crate::image_viewer::{IMAGE_PIXELS, is_viewable_image};
+use crate::input::guides::Bounds as ItemBounds;
+use crate::item_navigation::next_in_order;
+use crate::types::ItemContent;
+use gpui::*;
+use std::path::{Path, PathBuf};
+
+impl Humanboard {
+    /// Decode the pixels of every open image tab that doesn't have them yet
+    pub fn ensure_image_pixels(&self) {
+        let Some(ref preview) = self.preview.panel else {
+            return;
+        };
+        for tab in preview.all_tabs() {
+            if let PreviewTab::Image { path, .. } = tab {
+                IMAGE_PIXELS.load(path, &self.system.background);
+            }
+        }
+    }
+
+    /// Zoom an image tab by `factor` about `anchor` (in window coordinates),
+    /// or about the middle of the viewer without one
+    pub fn zoom_preview_image(
+        &mut self,
+        path: &Path,
+        factor: f32,
+        anchor: Option<Point<Pixels>>,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(size) = image_size(path) else {
+            return;
+        };
+        if let Some(PreviewTab::Image { view, viewport, .. }) = self.preview_image_tab_mut(path) {
+            let bounds = viewport.get();
+            let anchor = anchor.unwrap_or(bounds.center());
+            view.zoom_by(
+                factor,
+                relative_position(anchor, bounds),
+                size,
+                viewport_size(bounds),
+            );
+            cx.notify();
+        }
+    }
+
+    /// Fit an image tab's image to its pane
+    pub fn fit_preview_image(&mut self, path: &Path, cx: &mut Context<Self>) {
+        if let Some(PreviewTab::Image { view, .. }) = self.preview_image_tab_mut(path) {
+            view.fit();
+            cx.notify();
+        }
+    }
+
+    /// Show an image tab's image at 100%
+    pub fn preview_image_actual_size(&mut self, path: &Path, cx: &mut Context<Self>) {
+        if let Some(PreviewTab::Image { view, .. }) = self.preview_image_tab_mut(path) {
+            view.actual_size();
+            cx.notify();
+        }
+    }
+
+    /// Start dragging an image tab's image around
+    pub fn start_preview_image_pan(&mut self, path: &Path, position: Point<Pixels>) {
+        if let Some(PreviewTab::Image { pan_from, .. }) = self.preview_image_tab_mut(path) {
+            *pan_from = Some(position);
+        }
+    }
+
+    /// Stop dragging an image tab's image around
+    pub fn end_preview_image_pan(&mut self, path: &Path) {
+        if let Some(PreviewTab::Image { pan_from, .. }) = self.preview_image_tab_mut(path) {
+            *pan_from = None;
+        }
+    }
+
+    /// Follow the mouse over an image tab: move the image while it is
+    /// dragged, otherwise track the pixel under the mouse
+    pub fn preview_image_mouse_move(
+        &mut self,
+        path: &Path,
+        position: Point<Pixels>,
+        cx: &mut Context<Self>,
+    ) {
+        let size = image_size(path);
+        let Some(PreviewTab::Image {
+            view,
+            viewport,
+            hovered_pixel,
+            pan_from,
+            ..
+        }) = self.preview_image_tab_mut(path)
+        else {
+            return;
+        };
+        let bounds = viewport.get();
+        if let Some(from) = *pan_from {
+            view.pan_by((
+                f32::from(position.x - from.x),
+                f32::from(position.y - from.y),
+            ));
+            *pan_from = Some(position);
+        }
+        let pixel = size.and_then(|size| {
+            view.pixel_at(
+                relative_position(position, bounds),
+                size,
+                viewport_size(bounds),
+            )
+        });
+        if pixel != *hovered_pixel || pan_from.is_some() {
+            *hovered_pixel = pixel;
+            cx.notify();
+        }
+    }
+
+    /// Stop tracking the pixel under the mouse once it leaves an image tab
+    pub fn clear_preview_image_hover(&mut self, path: &Path, cx: &mut Context<Self>) {
+        if let Some(PreviewTab::Image {
+            hovered_pixel,
+            pan_from,
+            ..
+        }) = self.preview_image_tab_mut(path)
+        {
+            *hovered_pixel = None;
+            *pan_from = None;
+            cx.notify();
+        }
+    }
+
+    /// Show the next (or previous) image on the board, in reading order, in
+    /// the focused image tab, and select it on the canvas
+    pub fn step_preview_image(
+        &mut self,
+        reverse: bool,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(current_path) = self.focused_image_path() else {
+            return;
+        };
+        let Some(ref board) = self.canvas.board else {
+            return;
+        };
+        let images: Vec<(u64, ItemBounds, &PathBuf)> = board
+            .items
+            .iter()
+            .filter_map(|item| match &item.content {
+                ItemContent::Image(path) if is_viewable_image(path) => {
+                    Some((item.id, item_bounds(item), path))
+                }
+                _ => None,
+            })
+            .collect();
+        // Start from the selected image when it is the one shown, as the
+        // same file may be on the board more than once
+        let current = self
+            .single_selected_item()
+            .filter(|id| {
+                images
+                    .iter()
+                    .any(|(image, _, path)| image == id && **path == current_path)
+            })
+            .or_else(|| {
+                images
+                    .iter()
+                    .find(|(_, _, path)| **path == current_path)
+                    .map(|&(id, _, _)| id)
+            });
+        let bounds: Vec<(u64, ItemBounds)> = images.iter().map(|&(id, b, _)| (id, b)).collect();
+        let Some(next) = next_in_order(&bounds, current, reverse) else {
+            return;
+        };
+        let Some(next_path) = images
+            .iter()
+            .find(|&&(id, _, _)| id == next)
+            .map(|&(_, _, path)| path.clone())
+        else {
+            return;
+        };
+
+        if next_path != current_path {
+            // Keep the tab, and with it the focus, showing the next image fitted
+            if let Some(PreviewTab::Image {
+                path,
+                view,
+                hovered_pixel,
+                pan_from,
+                ..
+            }) = self.focused_tab_mut()
+            {
+                IMAGE_PIXELS.forget(path);
+                *path = next_path.clone();
+                view.fit();
+                *hovered_pixel = None;
+                *pan_from = None;
+            }
+            IMAGE_PIXELS.load(&next_path, &self.system.background);
+        }
+        self.select_and_reveal(next, window, cx);
+    }
+
+    /// Path of the image shown in the focused pane, if it shows one
+    fn focused_image_path(&self) -> Option<PathBuf> {
+        let preview = self.preview.panel.as_ref()?;
+        match preview.focused().active()? {
+            PreviewTab::Image { path, .. } => Some(path.clone()),
+            _ => None,
+        }
+    }
+
+    fn focused_tab_mut(&mut self) -> Option<&mut PreviewTab> {
+        let preview = self.preview.panel.as_mut()?;
+        preview.focused_mut().active_mut()
+    }
+
+    pub(super) fn preview_image_tab_mut(&mut self, path: &Path) -> Option<&mut PreviewTab> {
+        let preview = self.preview.panel.as_mut()?;
+        preview
+            .all_tabs_mut()
+            .find(|tab| matches!(tab, PreviewTab::Image { path: tab_path, .. } if tab_path == path))
+    }
+}
+
+/// Size of an image, once its pixels are decoded
+fn image_size(path: &Path) -> Option<(u32, u32)> {
+    IMAGE_PIXELS.get(path).map(|pixels| pixels.dimensions())
+}
+
+fn viewport_size(bounds: Bounds<Pixels>) -> (f32, f32) {
+    (f32::from(bounds.size.width), f32::from(bounds.size.height))
+}
+
+fn relative_position(position: Point<Pixels>, bounds: Bounds<Pixels>) -> (f32, f32) {
+    (
+        f32::from(position.x - bounds.origin.x),
+        f32::from(position.y - bounds.origin.y),
+    )
+}
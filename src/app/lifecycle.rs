@@ -1,23 +1,28 @@
 //! Application lifecycle - initialization and cleanup methods.
 
-use super::{AppView, CmdPaletteMode, CountdownState, Humanboard, SettingsTab, StorageLocation};
+use super::{AppView, CmdPaletteMode, CountdownState, Humanboard, MAX_PREVIEW_PANES, SettingsTab, StorageLocation};
 use crate::animations::ModalAnimationState;
 use crate::app::state::{CanvasState, NavigationState, PreviewState, SettingsState, SystemState, TextboxState, ToolState, UiState, WebViewManager, TableEditState};
+use crate::audio_metadata::AudioMetadataCache;
 use crate::background::BackgroundExecutor;
 use crate::board_index::BoardIndex;
 use crate::focus::FocusManager;
 use crate::hit_testing::HitTester;
+use crate::image_cache::ImageThumbnailCache;
+use crate::memory::MemoryMonitor;
 use crate::notifications::ToastManager;
+use crate::pdf::{PdfPageCache, PdfThumbnailCache};
 use crate::perf::PerfMonitor;
 use crate::settings::Settings;
 use crate::settings_watcher::{SettingsEvent, SettingsWatcher};
 use crate::types::ToolType;
+use crate::waveform::WaveformCache;
 use gpui::*;
 use std::collections::{HashMap, HashSet};
 use std::time::Instant;
 
 impl Humanboard {
-    pub fn new(cx: &mut Context<Self>) -> Self {
+    pub fn new(window: &mut Window, cx: &mut Context<Self>) -> Self {
         let board_index = BoardIndex::load();
 
         // Check if onboarding has been completed
@@ -27,7 +32,11 @@ impl Humanboard {
             AppView::Onboarding
         };
 
-        Self {
+        let (image_size_tx, image_size_rx) = std::sync::mpsc::channel();
+        let (url_fetch_tx, url_fetch_rx) = std::sync::mpsc::channel();
+        let (media_position_tx, media_position_rx) = std::sync::mpsc::channel();
+
+        let mut app = Self {
             navigation: NavigationState {
                 view: initial_view,
                 board_index,
@@ -47,6 +56,38 @@ impl Humanboard {
                 input_state: crate::input::InputState::default(),
                 file_drop_rx: None,
                 last_drop_pos: None,
+                last_thumbnail_at: None,
+                missing_files: HashSet::new(),
+                missing_files_checked_at: None,
+                missing_files_rx: None,
+                relink_rx: None,
+                background_image_rx: None,
+                export_rx: None,
+                copy_png_rx: None,
+                import_rx: None,
+                placed_page_rx: None,
+                alignment_guides: Vec::new(),
+                last_nudge_at: None,
+                image_size_tx,
+                image_size_rx,
+                images_measuring: 0,
+                url_fetch_tx,
+                url_fetch_rx,
+                url_fetches: 0,
+                declined_url_images: HashSet::new(),
+                file_imports: Vec::new(),
+                render_cache: Default::default(),
+                text_layouts: Default::default(),
+                code_excerpts: Default::default(),
+                card_files: Default::default(),
+                updated_cards: HashMap::new(),
+                search_texts: Default::default(),
+                search_highlight: None,
+                find_input: None,
+                find: Default::default(),
+                comparison: None,
+                wiki_links: Default::default(),
+                loader: Default::default(),
             },
             preview: PreviewState {
                 panel: None,
@@ -54,15 +95,21 @@ impl Humanboard {
                 tab_drag_target: None,
                 tab_drag_split_zone: None,
                 tab_drag_position: None,
+                tab_drag_from_pane: 0,
+                tab_drag_target_pane: 0,
                 tab_drag_pending: None,
                 search: None,
+                replace: None,
                 search_query: String::new(),
                 search_matches: Vec::new(),
                 search_current: 0,
-                left_tab_scroll: ScrollHandle::new(),
-                right_tab_scroll: ScrollHandle::new(),
+                pending_pdf_pages: HashMap::new(),
+                pending_code_lines: HashMap::new(),
+                code_files_checked_at: None,
+                restore_session: false,
+                tab_scrolls: (0..MAX_PREVIEW_PANES).map(|_| ScrollHandle::new()).collect(),
                 dragging_splitter: false,
-                dragging_pane_splitter: false,
+                dragged_pane_split: Vec::new(),
                 splitter_drag_start: None,
             },
             settings: SettingsState {
@@ -74,12 +121,25 @@ impl Humanboard {
                 theme_scroll: ScrollHandle::new(),
                 font_index: 0,
                 font_scroll: ScrollHandle::new(),
+                theme_editor: None,
+                profile_rx: None,
             },
             webviews: WebViewManager {
                 youtube: HashMap::new(),
+                vimeo: HashMap::new(),
+                soundcloud: HashMap::new(),
+                spotify: HashMap::new(),
                 audio: HashMap::new(),
                 video: HashMap::new(),
                 out_of_range_since: HashMap::new(),
+                evicted: HashSet::new(),
+                media_positions: HashMap::new(),
+                media_position_tx,
+                media_position_rx,
+                media_positions_polled_at: None,
+                playlist: None,
+                playlist_play_sent_at: None,
+                hidden_subtitles: HashSet::new(),
             },
             tools: ToolState {
                 selected: ToolType::default(),
@@ -92,12 +152,26 @@ impl Humanboard {
                 pending_command: None,
                 search_results: Vec::new(),
                 selected_result: 0,
+                board_results: Vec::new(),
+                command_results: Vec::new(),
+                file_index: Default::default(),
                 cmd_palette_mode: CmdPaletteMode::default(),
                 cmd_palette_scroll: ScrollHandle::new(),
                 modal_focus_index: 0,
                 toast_manager: ToastManager::new(),
                 pan_animation: None,
                 modal_animations: ModalAnimationState::default(),
+                version_history: None,
+                board_passphrase: None,
+                outline: None,
+                undo_history: None,
+                checkpoint_name: None,
+                url_paste: None,
+                export_dialog: None,
+                import_report: None,
+                context_menu: None,
+                hover: None,
+                show_perf_hud: false,
             },
             system: SystemState {
                 frame_times: Vec::with_capacity(60),
@@ -107,8 +181,15 @@ impl Humanboard {
                 hit_tester: HitTester::new(),
                 perf_monitor: PerfMonitor::new(),
                 background: BackgroundExecutor::with_default_workers(),
+                image_cache: ImageThumbnailCache::default(),
+                waveforms: WaveformCache::new(),
+                audio_metadata: AudioMetadataCache::new(),
+                pdf_pages: PdfPageCache::new(),
+                pdf_thumbnails: PdfThumbnailCache::default(),
+                memory: MemoryMonitor::with_budget_mb(crate::settings::media_memory_budget_mb()),
                 settings_watcher: crate::settings_watcher::default_settings_path()
                     .and_then(|p| SettingsWatcher::new(p).ok()),
+                window_id: window.window_handle().window_id().as_u64(),
             },
             textbox: TextboxState {
                 editing_id: None,
@@ -123,7 +204,27 @@ impl Humanboard {
                 table_states: HashMap::new(),
             },
             chart_config_modal: None,
-        }
+        };
+
+        // Offer to recover boards that didn't get a final save last session
+        app.check_journal_recovery();
+
+        // Tell the user how to fix PDF rendering if pdfium is missing
+        app.check_pdfium_availability();
+
+        // Save and release the open board when the window closes
+        cx.on_release(|this, _| {
+            this.persist_preview_session();
+            if let Some(ref mut board) = this.canvas.board {
+                if board.is_dirty() {
+                    board.save_immediate();
+                }
+            }
+            this.release_board_for_window();
+        })
+        .detach();
+
+        app
     }
 
     /// Check for settings file changes and reload if needed.
@@ -149,13 +250,38 @@ impl Humanboard {
                 }
             }
         }
+
+        // One watcher serves every window, so an edit is handled once
+        let keymap_event = crate::keymap::poll_keymap_change();
+        match keymap_event {
+            Some(SettingsEvent::Modified | SettingsEvent::Created | SettingsEvent::Deleted) => {
+                tracing::info!("Keymap file changed, rebinding keys...");
+                match crate::keymap::reload_user_keymap() {
+                    Ok(()) => {
+                        self.ui.toast_manager.push(crate::notifications::Toast::info("Keymap reloaded"));
+                    }
+                    Err(e) => {
+                        tracing::error!("Failed to reload keymap: {}", e);
+                        self.ui.toast_manager
+                            .push(crate::notifications::Toast::error(format!("Keymap not reloaded: {}", e)));
+                    }
+                }
+                crate::keymap::bind_keymap(cx);
+                cx.set_menus(crate::command_registry::COMMAND_REGISTRY.read().menus());
+                cx.notify();
+            }
+            Some(SettingsEvent::Error(e)) => {
+                tracing::error!("Keymap watch error: {}", e);
+            }
+            None => {}
+        }
     }
 
     /// Returns true if a code editor is currently in edit mode
     pub fn is_code_editing(&self) -> bool {
         self.preview.panel
             .as_ref()
-            .and_then(|p| p.tabs.get(p.active_tab))
+            .and_then(|p| p.focused().active())
             .map(|tab| tab.is_editing())
             .unwrap_or(false)
     }
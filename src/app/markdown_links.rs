@@ -0,0 +1,77 @@
+//! Following links clicked in markdown previews
+
+use crate::app::Humanboard;
+use crate::code_excerpt::split_line_anchor;
+use crate::markdown_card::LinkHandler;
+use crate::markdown_links::{LinkTarget, normalize_path, resolve_link};
+use crate::notifications::Toast;
+use gpui::*;
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+
+impl Humanboard {
+    /// Link handler for a markdown preview of the file at `source`
+    pub(crate) fn markdown_link_handler(source: PathBuf, cx: &mut Context<Self>) -> LinkHandler {
+        let this = cx.entity().downgrade();
+        Rc::new(move |dest, window, cx| {
+            this.update(cx, |this, cx| {
+                this.follow_markdown_link(&source, dest, window, cx)
+            })
+            .ok();
+        })
+    }
+
+    /// Follow a link from the markdown file at `source`.
+    ///
+    /// URLs open in the default browser. File links select and center the
+    /// canvas item showing that file, or open it in a new preview tab when
+    /// no item does, at the lines of a `#L20-L45` anchor.
+    pub fn follow_markdown_link(
+        &mut self,
+        source: &Path,
+        dest: &str,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let path = match resolve_link(dest, source.parent()) {
+            LinkTarget::Anchor => return,
+            LinkTarget::External(url) => {
+                cx.open_url(&url);
+                return;
+            }
+            LinkTarget::File(path) => path,
+        };
+
+        if let Some(id) = self.canvas_item_for_path(&path) {
+            self.select_and_reveal(id, window, cx);
+        } else if path.exists() {
+            let (_, lines) = split_line_anchor(dest);
+            self.open_preview_at_lines(path, lines, window, cx);
+        } else {
+            let name = path
+                .file_name()
+                .map(|name| name.to_string_lossy().into_owned())
+                .unwrap_or_else(|| dest.to_string());
+            self.show_toast(Toast::error(format!("Linked file not found: {}", name)));
+            cx.notify();
+        }
+    }
+
+    /// ID of the first canvas item backed by the file at `path`
+    fn canvas_item_for_path(&self, path: &Path) -> Option<u64> {
+        let board = self.canvas.board.as_ref()?;
+        let canonical = path.canonicalize().ok();
+        board
+            .items
+            .iter()
+            .find(|item| {
+                item.content.file_paths().into_iter().any(|item_path| {
+                    normalize_path(item_path) == path
+                        || canonical
+                            .as_ref()
+                            .is_some_and(|c| item_path.canonicalize().ok().as_ref() == Some(c))
+                })
+            })
+            .map(|item| item.id)
+    }
+}
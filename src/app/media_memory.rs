@@ -0,0 +1,126 @@
+//! Media memory methods - tracking decoded images, webviews and PDF
+//! thumbnails against the memory budget and evicting what is off screen
+
+use crate::app::Humanboard;
+use crate::memory::{MediaResource, WEBVIEW_ESTIMATE_BYTES, decoded_image_bytes};
+use crate::types::{CanvasItem, ItemContent};
+use gpui::*;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tracing::debug;
+
+impl Humanboard {
+    /// Record which media is on screen this frame and evict the least
+    /// recently visible resources if the budget is exceeded. Called from
+    /// render with the items that are getting elements and the image file
+    /// drawn for each.
+    pub(crate) fn track_media_memory(
+        &mut self,
+        visible_items: &[CanvasItem],
+        image_sources: &HashMap<u64, PathBuf>,
+        window: &mut Window,
+        cx: &mut App,
+    ) {
+        let memory = &mut self.system.memory;
+        memory.set_budget_mb(crate::settings::media_memory_budget_mb());
+        memory.begin_frame();
+
+        for source in image_sources.values() {
+            memory.mark_visible_with(MediaResource::Image(source.clone()), || {
+                decoded_image_bytes(source)
+            });
+        }
+
+        let mut visible_ids: HashSet<u64> = visible_items.iter().map(|item| item.id).collect();
+        // The playing playlist item counts as on screen so it isn't cut off
+        if let Some(ref playlist) = self.webviews.playlist {
+            visible_ids.insert(playlist.current());
+        }
+        for item in visible_items {
+            if let ItemContent::Pdf {
+                thumbnail: Some(thumbnail),
+                ..
+            } = &item.content
+            {
+                memory.mark_visible_with(MediaResource::PdfThumbnail(thumbnail.clone()), || {
+                    decoded_image_bytes(thumbnail)
+                });
+            }
+        }
+
+        // Webviews are preloaded near the viewport, so a loaded webview isn't
+        // necessarily on screen
+        let webviews = &mut self.webviews;
+        let loaded: Vec<u64> = webviews
+            .youtube
+            .keys()
+            .chain(webviews.vimeo.keys())
+            .chain(webviews.soundcloud.keys())
+            .chain(webviews.spotify.keys())
+            .chain(webviews.audio.keys())
+            .chain(webviews.video.keys())
+            .copied()
+            .collect();
+        memory.retain_webviews(|id| loaded.contains(&id));
+        for id in loaded {
+            if visible_ids.contains(&id) {
+                memory.mark_visible(MediaResource::WebView(id), WEBVIEW_ESTIMATE_BYTES);
+            } else {
+                memory.mark_loaded(MediaResource::WebView(id), WEBVIEW_ESTIMATE_BYTES);
+            }
+        }
+        // Evicted webviews come back once their item is on screen again
+        webviews.evicted.retain(|id| !visible_ids.contains(id));
+
+        for resource in memory.evict() {
+            debug!(
+                "Evicting {:?} to stay within the media memory budget",
+                resource
+            );
+            match resource {
+                MediaResource::Image(path) | MediaResource::PdfThumbnail(path) => {
+                    release_image(&path, window, cx);
+                }
+                MediaResource::WebView(id) => {
+                    if let Some(position) = webviews.media_positions.remove(&id) {
+                        if let Some(ref mut board) = self.canvas.board {
+                            board.set_playback_position(id, position.resume_seconds());
+                        }
+                    }
+                    if let Some(webview) = webviews.youtube.remove(&id) {
+                        webview.hide(cx);
+                    }
+                    if let Some(webview) = webviews.vimeo.remove(&id) {
+                        webview.hide(cx);
+                    }
+                    if let Some(webview) = webviews.soundcloud.remove(&id) {
+                        webview.hide(cx);
+                    }
+                    if let Some(webview) = webviews.spotify.remove(&id) {
+                        webview.hide(cx);
+                    }
+                    if let Some(webview) = webviews.audio.remove(&id) {
+                        webview.hide(cx);
+                    }
+                    if let Some(webview) = webviews.video.remove(&id) {
+                        webview.hide(cx);
+                    }
+                    webviews.out_of_range_since.remove(&id);
+                    webviews.evicted.insert(id);
+                    self.canvas.loader.forget(id);
+                }
+            }
+        }
+    }
+}
+
+/// Drop a decoded image from GPUI's image cache and free its texture. It is
+/// decoded again the next time it is drawn.
+pub(crate) fn release_image(path: &Path, window: &mut Window, cx: &mut App) {
+    let source = Resource::Path(Arc::from(path));
+    if let Some(Ok(image)) = window.use_asset::<ImgResourceLoader>(&source, cx) {
+        let _ = window.drop_image(image);
+    }
+    cx.remove_asset::<ImgResourceLoader>(&source);
+}
@@ -0,0 +1,576 @@
+//! Media playback methods - playing and pausing audio, video, YouTube,
+//! Vimeo, SoundCloud and Spotify items from the canvas, following and
+//! moving audio playheads for their waveforms, remembering where players
+//! were left, setting each item's volume, copying YouTube links at the
+//! current time, turning video subtitles on and off, and running audio
+//! playlists
+
+use crate::app::Humanboard;
+use crate::constants::{
+    PLAYBACK_SAVE_INTERVAL_SECS, PLAYHEAD_POLL_INTERVAL_MS, PLAYLIST_PLAY_RETRY_MS,
+};
+use crate::input::guides::Bounds;
+use crate::item_tooltip::format_duration;
+use crate::notifications::Toast;
+use crate::playlist::{MIN_PLAYLIST_ITEMS, Playlist};
+use crate::render::volume::volume_bar_fraction;
+use crate::subtitles;
+use crate::types::{ItemContent, MediaVolume, youtube_link};
+use crate::webviews::{MediaCommand, PlaybackPosition};
+use gpui::*;
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+use tracing::warn;
+
+impl Humanboard {
+    /// Play or pause the selected media items. Returns false if no media item
+    /// is selected, so the caller can fall back to something else.
+    pub fn toggle_selected_media(&mut self, cx: &mut Context<Self>) -> bool {
+        let Some(ref board) = self.canvas.board else {
+            return false;
+        };
+        let media: Vec<(u64, String)> = board
+            .items
+            .iter()
+            .filter(|item| self.canvas.selected_items.contains(&item.id))
+            .filter(|item| {
+                matches!(
+                    item.content,
+                    ItemContent::Audio(_)
+                        | ItemContent::Video(_)
+                        | ItemContent::YouTube(_)
+                        | ItemContent::Vimeo(_)
+                        | ItemContent::SoundCloud(_)
+                        | ItemContent::Spotify(_)
+                )
+            })
+            .map(|item| (item.id, item.content.display_name_with(&self.system.audio_metadata)))
+            .collect();
+        if media.is_empty() {
+            return false;
+        }
+
+        for (item_id, name) in media {
+            match self.send_media_command(item_id, MediaCommand::Toggle, cx) {
+                Some(Ok(())) => {}
+                Some(Err(e)) => {
+                    warn!("{}", e);
+                    self.show_toast(Toast::error(format!("Couldn't control '{}'", name)));
+                }
+                None => {
+                    self.show_toast(Toast::info(format!("'{}' is still loading", name)));
+                }
+            }
+        }
+        cx.notify();
+        true
+    }
+
+    /// Pause every media player on the board
+    pub fn pause_all_media(&mut self, cx: &mut Context<Self>) {
+        // A playlist would otherwise start its next item
+        self.webviews.playlist = None;
+        let ids: Vec<u64> = self
+            .webviews
+            .youtube
+            .keys()
+            .chain(self.webviews.vimeo.keys())
+            .chain(self.webviews.soundcloud.keys())
+            .chain(self.webviews.spotify.keys())
+            .chain(self.webviews.audio.keys())
+            .chain(self.webviews.video.keys())
+            .copied()
+            .collect();
+        if ids.is_empty() {
+            self.show_toast(Toast::info("No media on this board"));
+            cx.notify();
+            return;
+        }
+
+        let failed = ids
+            .iter()
+            .filter_map(|&id| self.send_media_command(id, MediaCommand::Pause, cx))
+            .filter(|result| result.is_err())
+            .count();
+        if failed > 0 {
+            self.show_toast(Toast::error(format!(
+                "Couldn't pause {} of {} media players",
+                failed,
+                ids.len()
+            )));
+        } else {
+            self.show_toast(Toast::success("Paused all media"));
+        }
+        cx.notify();
+    }
+
+    /// Collect positions reported by audio, video and YouTube players,
+    /// save where they are on the board, move the playlist on, and ask the
+    /// players again once the poll interval has passed (called from render).
+    ///
+    /// Returns true while any audio is playing or a playlist item is
+    /// starting, so the caller keeps rendering to move the playhead. Playback
+    /// started from inside the player page is picked up at the next frame
+    /// the canvas draws.
+    pub(crate) fn poll_media_positions(&mut self, cx: &mut App) -> bool {
+        let mut playlist_item_finished = false;
+        while let Ok((item_id, position)) = self.webviews.media_position_rx.try_recv() {
+            match position {
+                Some(position) => {
+                    if let Some(ref mut playlist) = self.webviews.playlist {
+                        playlist_item_finished |= playlist.track_position(item_id, &position);
+                    }
+                    self.save_playback_position(item_id, &position);
+                    self.webviews.media_positions.insert(item_id, position);
+                }
+                None => {
+                    self.webviews.media_positions.remove(&item_id);
+                }
+            }
+        }
+        let (audio, video, youtube) = (
+            &self.webviews.audio,
+            &self.webviews.video,
+            &self.webviews.youtube,
+        );
+        self.webviews.media_positions.retain(|id, _| {
+            audio.contains_key(id) || video.contains_key(id) || youtube.contains_key(id)
+        });
+
+        let interval = Duration::from_millis(PLAYHEAD_POLL_INTERVAL_MS);
+        let due = self
+            .webviews
+            .media_positions_polled_at
+            .is_none_or(|polled_at| polled_at.elapsed() >= interval);
+        if due {
+            self.webviews.media_positions_polled_at = Some(Instant::now());
+            let tx = &self.webviews.media_position_tx;
+            let report = |item_id: u64| {
+                let tx = tx.clone();
+                move |position| {
+                    let _ = tx.send((item_id, position));
+                }
+            };
+            let mut results = Vec::new();
+            for (&item_id, webview) in &self.webviews.audio {
+                results.push(webview.query_position(report(item_id), cx));
+            }
+            for (&item_id, webview) in &self.webviews.video {
+                results.push(webview.query_position(report(item_id), cx));
+            }
+            for (&item_id, webview) in &self.webviews.youtube {
+                results.push(webview.query_position(report(item_id), cx));
+            }
+            for e in results.into_iter().filter_map(Result::err) {
+                warn!("{}", e);
+            }
+        }
+
+        let playlist_starting = self.step_playlist(playlist_item_finished, cx);
+        playlist_starting
+            || self
+                .webviews
+                .media_positions
+                .iter()
+                .any(|(id, position)| position.playing && self.webviews.audio.contains_key(id))
+    }
+
+    /// Save a reported position on the board. While playing it is only
+    /// saved every `PLAYBACK_SAVE_INTERVAL_SECS` so the board isn't written
+    /// on every poll; pausing saves it straight away.
+    fn save_playback_position(&mut self, item_id: u64, position: &PlaybackPosition) {
+        let Some(ref mut board) = self.canvas.board else {
+            return;
+        };
+        let resume = position.resume_seconds();
+        if position.playing {
+            let stored = board.playback_position(item_id).unwrap_or(0.0);
+            let moved = resume.is_none_or(|s| (s - stored).abs() >= PLAYBACK_SAVE_INTERVAL_SECS);
+            if !moved {
+                return;
+            }
+        }
+        board.set_playback_position(item_id, resume);
+    }
+
+    /// Save the last known position of a player that is about to be
+    /// unloaded, so it resumes there when loaded again
+    pub(crate) fn persist_playback_position(&mut self, item_id: u64) {
+        if let Some(position) = self.webviews.media_positions.remove(&item_id) {
+            if let Some(ref mut board) = self.canvas.board {
+                board.set_playback_position(item_id, position.resume_seconds());
+            }
+        }
+    }
+
+    /// Save the last known position of every player, e.g. before leaving
+    /// the board or reloading the players
+    pub(crate) fn persist_playback_positions(&mut self) {
+        let ids: Vec<u64> = self.webviews.media_positions.keys().copied().collect();
+        for id in ids {
+            self.persist_playback_position(id);
+        }
+    }
+
+    /// The selected item if it is a single YouTube item, with its video ID
+    pub(crate) fn selected_youtube_item(&self) -> Option<(u64, String)> {
+        if self.canvas.selected_items.len() != 1 {
+            return None;
+        }
+        let id = *self.canvas.selected_items.iter().next()?;
+        match &self.canvas.board.as_ref()?.get_item(id)?.content {
+            ItemContent::YouTube(video_id) => Some((id, video_id.clone())),
+            _ => None,
+        }
+    }
+
+    /// Copy a link to the selected YouTube item that starts where its
+    /// player is now, or where it was left if it isn't loaded
+    pub fn copy_youtube_link_at_time(&mut self, cx: &mut Context<Self>) {
+        let Some((item_id, video_id)) = self.selected_youtube_item() else {
+            return;
+        };
+        let seconds = match self.webviews.media_positions.get(&item_id) {
+            Some(position) => position.seconds,
+            None => self
+                .canvas
+                .board
+                .as_ref()
+                .and_then(|board| board.playback_position(item_id))
+                .unwrap_or(0.0),
+        };
+        let seconds = seconds.max(0.0) as u64;
+        cx.write_to_clipboard(ClipboardItem::new_string(youtube_link(&video_id, seconds)));
+        self.show_toast(Toast::success(format!(
+            "Copied link at {}",
+            format_duration(Duration::from_secs(seconds))
+        )));
+        cx.notify();
+    }
+
+    /// The selected item if it is a single video item, with its file
+    pub(crate) fn selected_video_item(&self) -> Option<(u64, PathBuf)> {
+        if self.canvas.selected_items.len() != 1 {
+            return None;
+        }
+        let id = *self.canvas.selected_items.iter().next()?;
+        match &self.canvas.board.as_ref()?.get_item(id)?.content {
+            ItemContent::Video(path) => Some((id, path.clone())),
+            _ => None,
+        }
+    }
+
+    /// Turn the selected video's subtitles on or off
+    pub fn toggle_selected_subtitles(&mut self, cx: &mut Context<Self>) {
+        let Some((item_id, path)) = self.selected_video_item() else {
+            return;
+        };
+        let has_subtitles = match self.webviews.video.get(&item_id) {
+            Some(webview) => webview.has_subtitles(),
+            None => subtitles::find_sidecar(&path).is_some(),
+        };
+        if !has_subtitles {
+            self.show_toast(Toast::info(
+                "No .vtt or .srt subtitles found next to this video",
+            ));
+            cx.notify();
+            return;
+        }
+
+        let shown = !self.webviews.hidden_subtitles.remove(&item_id);
+        if !shown {
+            self.webviews.hidden_subtitles.insert(item_id);
+        }
+        if let Some(webview) = self.webviews.video.get(&item_id) {
+            if let Err(e) = webview.set_subtitles_shown(shown, cx) {
+                warn!("{}", e);
+            }
+        }
+        self.show_toast(Toast::info(if shown {
+            "Subtitles on"
+        } else {
+            "Subtitles off"
+        }));
+        cx.notify();
+    }
+
+    /// Number of selected audio items, which play as a playlist once
+    /// there are enough of them
+    pub(crate) fn selected_audio_count(&self) -> usize {
+        self.canvas.board.as_ref().map_or(0, |board| {
+            board
+                .items
+                .iter()
+                .filter(|item| self.canvas.selected_items.contains(&item.id))
+                .filter(|item| matches!(item.content, ItemContent::Audio(_)))
+                .count()
+        })
+    }
+
+    /// Play the selected audio items one after another, in reading order
+    /// or shuffled
+    pub fn start_playlist(&mut self, shuffle: bool, cx: &mut Context<Self>) {
+        let Some(ref board) = self.canvas.board else {
+            return;
+        };
+        let items: Vec<(u64, Bounds)> = board
+            .items
+            .iter()
+            .filter(|item| self.canvas.selected_items.contains(&item.id))
+            .filter(|item| matches!(item.content, ItemContent::Audio(_)))
+            .map(|item| {
+                let (x, y) = item.position;
+                let (w, h) = item.size;
+                (item.id, (x, y, w, h))
+            })
+            .collect();
+        let playlist = if items.len() >= MIN_PLAYLIST_ITEMS {
+            Playlist::new(&items, shuffle)
+        } else {
+            None
+        };
+        let Some(playlist) = playlist else {
+            self.show_toast(Toast::info(format!(
+                "Select at least {} audio items to play as a playlist",
+                MIN_PLAYLIST_ITEMS
+            )));
+            cx.notify();
+            return;
+        };
+
+        // Only the playlist should be heard
+        let playing: Vec<u64> = self
+            .webviews
+            .media_positions
+            .iter()
+            .filter(|(_, position)| position.playing)
+            .map(|(&id, _)| id)
+            .collect();
+        for id in playing {
+            if let Some(Err(e)) = self.send_media_command(id, MediaCommand::Pause, cx) {
+                warn!("{}", e);
+            }
+        }
+
+        let verb = if playlist.is_shuffled() {
+            "Shuffling"
+        } else {
+            "Playing"
+        };
+        self.show_toast(Toast::success(format!(
+            "{} {} audio items",
+            verb,
+            playlist.order().len()
+        )));
+        self.webviews.playlist = Some(playlist);
+        self.webviews.playlist_play_sent_at = None;
+        cx.notify();
+    }
+
+    /// Stop the running playlist, pausing the item it was playing
+    pub fn stop_playlist(&mut self, cx: &mut Context<Self>) {
+        let Some(playlist) = self.webviews.playlist.take() else {
+            self.show_toast(Toast::info("No playlist is playing"));
+            cx.notify();
+            return;
+        };
+        if let Some(Err(e)) = self.send_media_command(playlist.current(), MediaCommand::Pause, cx) {
+            warn!("{}", e);
+        }
+        self.show_toast(Toast::info("Playlist stopped"));
+        cx.notify();
+    }
+
+    /// Move the playlist on once its item has finished, and keep asking the
+    /// current item to play from the start until it reports playing, since
+    /// its player may still be loading. Returns true while waiting for it.
+    fn step_playlist(&mut self, item_finished: bool, cx: &mut App) -> bool {
+        let (Some(board), Some(playlist)) =
+            (self.canvas.board.as_ref(), self.webviews.playlist.as_mut())
+        else {
+            self.webviews.playlist = None;
+            return false;
+        };
+        let still_audio = |id: u64| {
+            board
+                .get_item(id)
+                .is_some_and(|item| matches!(item.content, ItemContent::Audio(_)))
+        };
+        if !playlist.retain(still_audio) {
+            self.webviews.playlist = None;
+            return false;
+        }
+        if item_finished && playlist.advance().is_none() {
+            self.webviews.playlist = None;
+            self.show_toast(Toast::info("Playlist finished"));
+            return false;
+        }
+        if playlist.is_started() {
+            return false;
+        }
+
+        let item_id = playlist.current();
+        if item_finished {
+            self.webviews.playlist_play_sent_at = None;
+        }
+        let interval = Duration::from_millis(PLAYLIST_PLAY_RETRY_MS);
+        let due = self
+            .webviews
+            .playlist_play_sent_at
+            .is_none_or(|sent_at| sent_at.elapsed() >= interval);
+        let Some(webview) = self.webviews.audio.get(&item_id) else {
+            return true;
+        };
+        if due {
+            self.webviews.playlist_play_sent_at = Some(Instant::now());
+            if let Some(ref mut board) = self.canvas.board {
+                board.set_playback_position(item_id, None);
+            }
+            let result = webview
+                .seek(0.0, cx)
+                .and_then(|()| webview.send_command(MediaCommand::Play, cx));
+            if let Err(e) = result {
+                warn!("{}", e);
+            }
+        }
+        true
+    }
+
+    /// Seek an audio item to the point under a click on its waveform
+    pub fn seek_audio_at(&mut self, item_id: u64, position: Point<Pixels>, cx: &mut Context<Self>) {
+        let fraction = self.canvas.board.as_ref().and_then(|board| {
+            let item = board.get_item(item_id)?;
+            let canvas_x = f32::from(board.screen_to_canvas(position).x);
+            Some((canvas_x - item.position.0) / item.size.0.max(1.0))
+        });
+        if let Some(fraction) = fraction {
+            self.seek_audio(item_id, fraction, cx);
+        }
+    }
+
+    /// Move an audio item's playback to a fraction of its length
+    pub fn seek_audio(&mut self, item_id: u64, fraction: f32, cx: &mut Context<Self>) {
+        let Some(webview) = self.webviews.audio.get(&item_id) else {
+            self.show_toast(Toast::info("The player is still loading"));
+            cx.notify();
+            return;
+        };
+        match webview.seek(fraction, cx) {
+            Ok(()) => {
+                // Move the playhead now rather than at the next poll
+                let fraction = fraction.clamp(0.0, 1.0);
+                self.webviews
+                    .media_positions
+                    .entry(item_id)
+                    .and_modify(|position| position.fraction = fraction);
+            }
+            Err(e) => {
+                warn!("{}", e);
+                self.show_toast(Toast::error("Couldn't seek the audio player"));
+            }
+        }
+        cx.notify();
+    }
+
+    /// Set a media item's volume from a click on its volume bar
+    pub fn set_item_volume_at(
+        &mut self,
+        item_id: u64,
+        position: Point<Pixels>,
+        cx: &mut Context<Self>,
+    ) {
+        let volume = self.canvas.board.as_ref().and_then(|board| {
+            let item = board.get_item(item_id)?;
+            let canvas_x = f32::from(board.screen_to_canvas(position).x);
+            let level = volume_bar_fraction(canvas_x, item.position.0, item.size.0);
+            Some(board.media_volume(item_id).with_level(level))
+        });
+        if let Some(volume) = volume {
+            self.set_item_volume(item_id, volume, cx);
+        }
+    }
+
+    /// Mute or unmute a media item
+    pub fn toggle_item_mute(&mut self, item_id: u64, cx: &mut Context<Self>) {
+        let volume = self
+            .canvas
+            .board
+            .as_ref()
+            .map(|board| board.media_volume(item_id).toggled_mute());
+        if let Some(volume) = volume {
+            self.set_item_volume(item_id, volume, cx);
+        }
+    }
+
+    /// Set a media item's volume, storing it on the item so the player
+    /// gets it again whenever it is recreated
+    pub fn set_item_volume(&mut self, item_id: u64, volume: MediaVolume, cx: &mut Context<Self>) {
+        let Some(ref mut board) = self.canvas.board else {
+            return;
+        };
+        if !board
+            .get_item(item_id)
+            .is_some_and(|item| item.content.has_volume())
+        {
+            return;
+        }
+        board.set_media_volume(item_id, volume);
+        if let Some(Err(e)) = self.send_media_volume(item_id, volume, cx) {
+            warn!("{}", e);
+        }
+        cx.notify();
+    }
+
+    /// Set the volume of an item's player, if it is loaded
+    fn send_media_volume(
+        &self,
+        item_id: u64,
+        volume: MediaVolume,
+        cx: &mut App,
+    ) -> Option<Result<(), String>> {
+        if let Some(webview) = self.webviews.youtube.get(&item_id) {
+            return Some(webview.set_volume(volume, cx));
+        }
+        if let Some(webview) = self.webviews.vimeo.get(&item_id) {
+            return Some(webview.set_volume(volume, cx));
+        }
+        if let Some(webview) = self.webviews.soundcloud.get(&item_id) {
+            return Some(webview.set_volume(volume, cx));
+        }
+        if let Some(webview) = self.webviews.audio.get(&item_id) {
+            return Some(webview.set_volume(volume, cx));
+        }
+        self.webviews
+            .video
+            .get(&item_id)
+            .map(|webview| webview.set_volume(volume, cx))
+    }
+
+    /// Send a command to an item's player. `None` if it has no webview yet.
+    fn send_media_command(
+        &self,
+        item_id: u64,
+        command: MediaCommand,
+        cx: &mut App,
+    ) -> Option<Result<(), String>> {
+        if let Some(webview) = self.webviews.youtube.get(&item_id) {
+            return Some(webview.send_command(command, cx));
+        }
+        if let Some(webview) = self.webviews.vimeo.get(&item_id) {
+            return Some(webview.send_command(command, cx));
+        }
+        if let Some(webview) = self.webviews.soundcloud.get(&item_id) {
+            return Some(webview.send_command(command, cx));
+        }
+        if let Some(webview) = self.webviews.spotify.get(&item_id) {
+            return Some(webview.send_command(command, cx));
+        }
+        if let Some(webview) = self.webviews.audio.get(&item_id) {
+            return Some(webview.send_command(command, cx));
+        }
+        self.webviews
+            .video
+            .get(&item_id)
+            .map(|webview| webview.send_command(command, cx))
+    }
+}
@@ -6,32 +6,117 @@
 //! - `lifecycle` - Initialization and cleanup methods
 //! - `board_management` - Board CRUD operations
 //! - `settings_handlers` - Theme, font, and settings management
+//! - `theme_editor` - Making custom themes in settings and applying them as they are edited
+//! - `settings_profile` - Exporting and importing settings, themes, keymap and board overrides as one file
 //! - `command_palette_handlers` - Command palette functionality
 //! - `preview_core` - Core preview panel operations
-//! - `preview_webviews` - YouTube, Audio, Video webview management
-//! - `preview_tabs` - Tab close, drag, and history management
-//! - `preview_panes` - Tab switching and pane split management
+//! - `preview_webviews` - YouTube, Vimeo, SoundCloud, Spotify, Audio, Video webview management
+//! - `preview_tabs` - Tab close and drag management
+//! - `preview_panes` - Tab switching and the split tree of up to four panes
 //! - `preview_search` - Find in file functionality
+//! - `preview_navigation` - Back/forward history of each preview pane
+//! - `code_editing` - Saving code tabs and noticing their files change on disk
+//! - `code_outline` - The symbol outline of code tabs and going to symbols
+//! - `code_excerpt` - Pinning the lines expanded code cards show
+//! - `card_refresh` - Refreshing markdown and code cards when their files change
 //! - `textbox` - Textbox editing and utility methods
+//! - `version_history` - Board version history browser
+//! - `checkpoints` - Named checkpoints and comparing the board with saved versions
+//! - `file_relink` - Missing file badges and the locate/relink flow
+//! - `board_encryption` - Passphrase prompts for encrypting and unlocking boards
+//! - `board_export` - Exporting the board as a standalone web page
+//! - `board_import` - Importing Obsidian canvases and Miro or FigJam boards
+//! - `board_outline` - The Outline side panel listing board contents
+//! - `undo_history` - The History side panel listing undo steps and jumping between them
+//! - `arrange` - Aligning and distributing selected items
+//! - `context_menu` - The right-click menu on items and the canvas
+//! - `hover` - Hover tooltips with item details
+//! - `canvas_background` - Picking the board's canvas background style
+//! - `image_sizing` - Measuring newly added images off the UI thread
+//! - `url_paste` - Adding pasted URLs as embeds, images or bookmarks, and the chooser to switch
+//! - `image_viewer` - Zooming, panning and stepping through image preview tabs
+//! - `media_memory` - Keeping decoded images and webviews within the memory budget
+//! - `media_playback` - Play/pause, audio positions and seeking from waveforms
+//! - `pdf_highlights` - Highlighting PDF passages and notes that open them again
+//! - `pdf_outline` - The outline sidebar of PDF previews
+//! - `pdf_pages` - Paging through PDF cards and previews, placing pages on the canvas
+//! - `pdf_search` - Indexing PDF text and finding items and boards by it
+//! - `content_search` - Finding cards by their text and highlighting the one chosen
+//! - `canvas_find` - The canvas find bar, stepping through matching items
+//! - `search_filters` - Narrowing search results with type, tag, board and date operators
+//! - `pdf_thumbnails` - Keeping PDF card thumbnails up to date with their files
+//! - `preview_session` - Saving the preview panel with the board and reopening it
+//! - `data_preview` - Paging, sorting and filtering CSV and table tabs
+//! - `diff_preview` - Comparing card files with each other or their last snapshot
+//! - `registered_commands` - Running registry commands and listing them in the palette
+//! - `quick_open` - Finding files under the quick-open folders and adding them to the board
+//! - `windows` - Opening windows and per-window board ownership
 
 mod types;
 mod state;
 mod lifecycle;
 mod board_management;
 mod settings_handlers;
+mod theme_editor;
+mod settings_profile;
 mod command_palette_handlers;
 mod preview_core;
 mod preview_webviews;
 mod preview_tabs;
 mod preview_panes;
 mod preview_search;
+mod preview_navigation;
+mod markdown_links;
+mod tab_zoom;
 mod textbox;
 mod error_recovery;
 mod data_viz;
 mod table_editing;
+mod version_history;
+mod checkpoints;
+mod file_relink;
+mod board_encryption;
+mod board_export;
+mod board_import;
+mod board_outline;
+mod undo_history;
+mod arrange;
+mod context_menu;
+mod hover;
+mod canvas_background;
+mod data_preview;
+mod diff_preview;
+mod code_editing;
+mod code_outline;
+mod code_excerpt;
+mod card_refresh;
+mod image_sizing;
+mod file_import;
+mod url_paste;
+mod image_viewer;
+mod media_memory;
+mod media_playback;
+mod pdf_highlights;
+mod pdf_outline;
+mod pdf_pages;
+mod pdf_search;
+mod content_search;
+mod canvas_find;
+mod search_filters;
+mod pdf_thumbnails;
+mod preview_session;
+mod registered_commands;
+mod quick_open;
+mod windows;
 
 pub use types::*;
-pub use state::{ChartConfigModal, Humanboard};
+pub use windows::{open_window, window_options};
+pub use preview_webviews::EmbedKind;
+pub use preview_panes::{MAX_PREVIEW_PANES, PANE_SPLITTER_SIZE, PaneBounds, PaneTree};
+pub use state::{
+    BoardImport, ChartConfigModal, ExportDialog, FileImport, Humanboard, ImportReport, OutlinePanel, PassphrasePrompt, ProfileImport, ThemeEditor, UndoHistoryPanel,
+    UrlFetch, UrlFetched, UrlPasteChooser, VersionComparison, VersionHistoryModal,
+};
 
 // Re-export sub-structs for use in other modules
 pub use state::{
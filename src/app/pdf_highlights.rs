@@ -0,0 +1,125 @@
+//! PDF highlight methods - highlighting the current find match of a PDF tab,
+//! adding notes about highlights to the canvas and opening them again
+
+use crate::app::{Humanboard, PreviewTab};
+use crate::constants::DEFAULT_FONT_SIZE;
+use crate::notifications::Toast;
+use crate::pdf::{PDF_TEXT_INDEX, matched_text};
+use crate::types::ItemContent;
+use gpui::*;
+use std::path::PathBuf;
+
+/// The passage picked with find in file: path, page, byte offset and text
+type PdfPassage = (PathBuf, usize, usize, String);
+
+impl Humanboard {
+    /// The current find match of the active PDF tab, as it is written in
+    /// the PDF
+    pub(crate) fn pdf_search_selection(&self) -> Option<PdfPassage> {
+        let preview = self.preview.panel.as_ref()?;
+        let PreviewTab::Pdf { path, .. } = preview.panes[0].active()? else {
+            return None;
+        };
+        let &(page, offset) = self
+            .preview
+            .search_matches
+            .get(self.preview.search_current)?;
+        let page_text = PDF_TEXT_INDEX.page_text(path, page)?;
+        let text = matched_text(&page_text, offset, &self.preview.search_query)?;
+        Some((path.clone(), page, offset, text))
+    }
+
+    /// Highlight the current find match of the active PDF tab. With
+    /// `with_note`, a text box quoting the passage is added to the canvas
+    /// beside the PDF's item and opened for editing.
+    pub fn highlight_pdf_search_match(
+        &mut self,
+        with_note: bool,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some((path, page, offset, text)) = self.pdf_search_selection() else {
+            return;
+        };
+        let position = self.canvas_position_beside_pdf(&path, window);
+        let Some(ref mut board) = self.canvas.board else {
+            return;
+        };
+        let highlight_id = board.add_pdf_highlight(path.clone(), page, offset, text);
+
+        if !with_note {
+            self.show_toast(Toast::success(format!("Highlighted p. {}", page + 1)));
+            cx.notify();
+            return;
+        }
+
+        let note_text = board
+            .pdf_highlights()
+            .iter()
+            .find(|h| h.id == highlight_id)
+            .map(|h| h.note_text())
+            .unwrap_or_default();
+        let note_id = board.add_item(
+            position,
+            ItemContent::TextBox {
+                text: note_text,
+                font_size: DEFAULT_FONT_SIZE,
+                color: String::new(),
+            },
+        );
+        board.set_highlight_note(highlight_id, note_id);
+
+        self.canvas.selected_items.clear();
+        self.canvas.selected_items.insert(note_id);
+        self.start_textbox_editing(note_id, window, cx);
+        cx.notify();
+    }
+
+    /// The selected item, if the selection is a single highlight's note
+    pub(crate) fn selected_highlight_note(&self) -> Option<u64> {
+        if self.canvas.selected_items.len() != 1 {
+            return None;
+        }
+        let id = *self.canvas.selected_items.iter().next()?;
+        self.canvas.board.as_ref()?.highlight_for_note(id)?;
+        Some(id)
+    }
+
+    /// Open the PDF a note's highlight is in, at the highlighted page
+    pub fn open_highlight_note(
+        &mut self,
+        item_id: u64,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(highlight) = self
+            .canvas
+            .board
+            .as_ref()
+            .and_then(|board| board.highlight_for_note(item_id))
+            .cloned()
+        else {
+            return;
+        };
+        if !highlight.path.exists() {
+            self.show_toast(Toast::error(format!(
+                "Can't find {}",
+                highlight.path.display()
+            )));
+            cx.notify();
+            return;
+        }
+        self.open_preview(highlight.path.clone(), window, cx);
+
+        // The viewer is created on the next frame if the tab is new
+        match self.preview_pdf_webview(&highlight.path) {
+            Some(webview) => webview.show_page(highlight.page, cx),
+            None => {
+                self.preview
+                    .pending_pdf_pages
+                    .insert(highlight.path, highlight.page);
+            }
+        }
+        cx.notify();
+    }
+}
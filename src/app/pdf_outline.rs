@@ -0,0 +1,50 @@
+//! PDF outline methods - the outline sidebar of PDF preview tabs
+
+use crate::app::{Humanboard, PreviewTab};
+use crate::webviews::PdfWebView;
+use gpui::*;
+use std::path::Path;
+
+impl Humanboard {
+    /// Show or hide the outline sidebar of a PDF preview tab
+    pub fn toggle_pdf_outline(&mut self, path: &Path, cx: &mut Context<Self>) {
+        if let Some(webview) = self.preview_pdf_webview_mut(path) {
+            webview.toggle_outline();
+            cx.notify();
+        }
+    }
+
+    /// Open or close an entry of a PDF preview tab's outline
+    pub fn toggle_pdf_outline_entry(
+        &mut self,
+        path: &Path,
+        index_path: Vec<usize>,
+        cx: &mut Context<Self>,
+    ) {
+        if let Some(webview) = self.preview_pdf_webview_mut(path) {
+            webview.toggle_outline_entry(index_path);
+            cx.notify();
+        }
+    }
+
+    /// Show the page an outline entry points at
+    pub fn show_pdf_outline_page(&mut self, path: &Path, page: usize, cx: &mut Context<Self>) {
+        self.record_preview_jump(path, cx);
+        if let Some(webview) = self.preview_pdf_webview(path) {
+            webview.show_page(page, cx);
+            cx.notify();
+        }
+    }
+
+    fn preview_pdf_webview_mut(&mut self, path: &Path) -> Option<&mut PdfWebView> {
+        let preview = self.preview.panel.as_mut()?;
+        preview.all_tabs_mut().find_map(|tab| match tab {
+            PreviewTab::Pdf {
+                path: tab_path,
+                webview: Some(webview),
+                ..
+            } if tab_path == path => Some(webview),
+            _ => None,
+        })
+    }
+}
@@ -0,0 +1,155 @@
+//! PDF page methods - paging through multi-page PDF cards and preview tabs,
+//! and placing preview pages on the canvas as images
+
+use crate::app::{Humanboard, PreviewTab};
+use crate::board_index::BoardIndex;
+use crate::constants::{DOCK_WIDTH, HEADER_HEIGHT};
+use crate::notifications::Toast;
+use crate::pdf::{PDF_TEXT_INDEX, export_pdf_page, placed_page_path, turn_page};
+use crate::types::ItemContent;
+use crate::webviews::PdfWebView;
+use gpui::*;
+use std::path::Path;
+use std::sync::mpsc;
+
+/// Space between a PDF item and things placed beside it, in canvas units
+const BESIDE_PDF_GAP: f32 = 24.0;
+
+impl Humanboard {
+    /// Move a PDF item `delta` pages forward or back, remembering the page
+    /// on the item. Does nothing until the document's page count is known.
+    pub fn turn_pdf_page(&mut self, item_id: u64, delta: isize, cx: &mut Context<Self>) {
+        let Some(ref mut board) = self.canvas.board else {
+            return;
+        };
+        let Some(ItemContent::Pdf { path, page, .. }) =
+            board.get_item(item_id).map(|item| &item.content)
+        else {
+            return;
+        };
+        let Some(page_count) = self.system.pdf_pages.page_count(path) else {
+            return;
+        };
+        let new_page = turn_page(*page, delta, page_count);
+        if board.set_pdf_page(item_id, new_page) {
+            cx.notify();
+        }
+    }
+
+    /// Show the page `delta` pages away in a PDF preview tab
+    pub fn turn_preview_pdf_page(&mut self, path: &Path, delta: isize, cx: &mut Context<Self>) {
+        let Some(webview) = self.preview_pdf_webview(path) else {
+            return;
+        };
+        let page_count = PDF_TEXT_INDEX.page_count(path).unwrap_or(usize::MAX);
+        let page = turn_page(webview.page(), delta, page_count);
+        if page != webview.page() {
+            webview.show_page(page, cx);
+            cx.notify();
+        }
+    }
+
+    /// Render the page shown in a PDF preview tab at full quality in the
+    /// background, then add it to the board as an image beside the PDF's
+    /// item, or in the middle of the canvas if it isn't on the board
+    pub fn place_pdf_page_on_canvas(
+        &mut self,
+        path: &Path,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let page = self
+            .preview_pdf_webview(path)
+            .map_or(0, |webview| webview.page());
+        let position = self.canvas_position_beside_pdf(path, window);
+        let Some(ref board) = self.canvas.board else {
+            return;
+        };
+        let board_id = board.id.clone();
+        let source = path.to_path_buf();
+        let dest = placed_page_path(&BoardIndex::board_files_dir(&board_id), path, page);
+
+        // Same channel workaround as exports - the result is polled in render
+        let (tx, rx) = mpsc::channel();
+        cx.background_executor()
+            .spawn(async move {
+                let result = export_pdf_page(&source, page, &dest).map(|()| dest);
+                let _ = tx.send((board_id, position, result));
+            })
+            .detach();
+        self.canvas.placed_page_rx = Some(rx);
+    }
+
+    /// Add the page placed by `place_pdf_page_on_canvas` once it has been
+    /// rendered (called from render). Returns true while it is rendering.
+    pub(crate) fn poll_placed_pdf_page(&mut self, cx: &mut Context<Self>) -> bool {
+        let Some(ref rx) = self.canvas.placed_page_rx else {
+            return false;
+        };
+        let (board_id, position, result) = match rx.try_recv() {
+            Ok(placed) => placed,
+            Err(mpsc::TryRecvError::Empty) => return true,
+            Err(mpsc::TryRecvError::Disconnected) => {
+                self.canvas.placed_page_rx = None;
+                return false;
+            }
+        };
+        self.canvas.placed_page_rx = None;
+
+        match result {
+            Ok(image) => {
+                if let Some(ref mut board) = self.canvas.board {
+                    // The user may have switched boards while this was running
+                    if board.id == board_id {
+                        let id = board.add_item(position, ItemContent::Image(image));
+                        self.canvas.selected_items.clear();
+                        self.canvas.selected_items.insert(id);
+                    }
+                }
+            }
+            Err(e) => {
+                self.show_toast(Toast::error(format!("Couldn't place page: {}", e)));
+            }
+        }
+        cx.notify();
+        false
+    }
+
+    /// Where to put something about a PDF: beside its item on the board, or
+    /// in the middle of the canvas if it isn't on the board
+    pub(crate) fn canvas_position_beside_pdf(&self, path: &Path, window: &Window) -> Point<Pixels> {
+        let viewport = self.canvas_viewport_size(window);
+        let Some(ref board) = self.canvas.board else {
+            return point(px(0.0), px(0.0));
+        };
+        board
+            .items
+            .iter()
+            .find(|item| matches!(&item.content, ItemContent::Pdf { path: p, .. } if p == path))
+            .map(|item| {
+                point(
+                    px(item.position.0 + item.size.0 + BESIDE_PDF_GAP),
+                    px(item.position.1),
+                )
+            })
+            .unwrap_or_else(|| {
+                board.screen_to_canvas(point(
+                    px(DOCK_WIDTH) + viewport.width / 2.0,
+                    px(HEADER_HEIGHT) + viewport.height / 2.0,
+                ))
+            })
+    }
+
+    /// The viewer of an open PDF preview tab
+    pub(crate) fn preview_pdf_webview(&self, path: &Path) -> Option<&PdfWebView> {
+        let preview = self.preview.panel.as_ref()?;
+        preview.all_tabs().find_map(|tab| match tab {
+            PreviewTab::Pdf {
+                path: tab_path,
+                webview: Some(webview),
+                ..
+            } if tab_path == path => Some(webview),
+            _ => None,
+        })
+    }
+}
@@ -0,0 +1,121 @@
+//! PDF search methods - indexing board PDFs and finding items and boards
+//! by the text inside their PDFs
+
+use crate::app::Humanboard;
+use crate::command_palette::{MAX_BOARD_RESULTS, board_result_id};
+use crate::pdf::{PDF_TEXT_INDEX, PdfTextMatch};
+use crate::types::ItemContent;
+use std::path::{Path, PathBuf};
+
+impl Humanboard {
+    /// Index the text of the open board's PDFs in the background. Encrypted
+    /// boards are taken out of the index instead.
+    pub(crate) fn index_board_pdfs(&self) {
+        PDF_TEXT_INDEX.load(&self.system.background);
+        let Some(ref board) = self.canvas.board else {
+            return;
+        };
+        if board.is_encrypted() {
+            PDF_TEXT_INDEX.forget_board(&board.id);
+            return;
+        }
+        let pdfs: Vec<PathBuf> = board
+            .items
+            .iter()
+            .filter_map(|item| match &item.content {
+                ItemContent::Pdf { path, .. } => Some(path.clone()),
+                _ => None,
+            })
+            .collect();
+        PDF_TEXT_INDEX.index_board(&board.id, &pdfs, &self.system.background);
+    }
+
+    /// Index the text of a PDF opened in the preview panel, unless the open
+    /// board is encrypted
+    pub(crate) fn index_preview_pdf(&self, path: &Path) {
+        if self
+            .canvas
+            .board
+            .as_ref()
+            .is_some_and(|board| board.is_encrypted())
+        {
+            return;
+        }
+        PDF_TEXT_INDEX.index_pdf(path, None, &self.system.background);
+    }
+
+    /// Search results for PDFs whose text contains `query`: PDF items on the
+    /// open board that aren't in `found` yet, then other boards with such a
+    /// PDF. The boards are remembered in `ui.board_results`.
+    pub(crate) fn pdf_text_results(
+        &mut self,
+        query: &str,
+        found: &[(u64, String)],
+    ) -> Vec<(u64, String)> {
+        self.ui.board_results.clear();
+        if query.trim().is_empty() {
+            return Vec::new();
+        }
+        let matches = PDF_TEXT_INDEX.search(query);
+        let current_board = self.canvas.board.as_ref().map(|board| board.id.clone());
+        let mut results = Vec::new();
+
+        if let Some(ref board) = self.canvas.board {
+            for item in &board.items {
+                let ItemContent::Pdf { path, .. } = &item.content else {
+                    continue;
+                };
+                if found.iter().any(|(id, _)| *id == item.id) {
+                    continue;
+                }
+                if let Some(found) = matches.iter().find(|m| &m.path == path) {
+                    results.push((
+                        item.id,
+                        format!("{} — {}", item.content.display_name(), match_label(found)),
+                    ));
+                }
+            }
+        }
+
+        for found in &matches {
+            for board_id in &found.boards {
+                if Some(board_id) == current_board.as_ref()
+                    || self.ui.board_results.contains(board_id)
+                    || self.ui.board_results.len() >= MAX_BOARD_RESULTS
+                {
+                    continue;
+                }
+                let Some(metadata) = self
+                    .navigation
+                    .board_index
+                    .get_board(board_id)
+                    .filter(|metadata| !metadata.is_deleted())
+                else {
+                    continue;
+                };
+                let file_name = found
+                    .path
+                    .file_name()
+                    .map(|name| name.to_string_lossy().into_owned())
+                    .unwrap_or_default();
+                results.push((
+                    board_result_id(self.ui.board_results.len()),
+                    format!(
+                        "Board: {} — {}, {}",
+                        metadata.name,
+                        file_name,
+                        match_label(found)
+                    ),
+                ));
+                self.ui.board_results.push(board_id.clone());
+            }
+        }
+
+        results
+    }
+}
+
+/// Where a match is, e.g. `p. 3: …the quarterly figures…`
+fn match_label(found: &PdfTextMatch) -> String {
+    format!("p. {}: {}", found.page + 1, found.snippet)
+}
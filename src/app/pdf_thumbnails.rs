@@ -0,0 +1,95 @@
+//! PDF thumbnail methods - keeping the thumbnails of PDF cards in step with
+//! their files and regenerating one on request
+
+use crate::app::Humanboard;
+use crate::app::media_memory::release_image;
+use crate::notifications::Toast;
+use crate::types::{CanvasItem, ItemContent};
+use gpui::*;
+use std::path::PathBuf;
+
+impl Humanboard {
+    /// Give the visible PDF items the thumbnail of their file as it is now,
+    /// storing it on the board. A changed file also has its rendered pages
+    /// dropped so they are rendered again.
+    pub(crate) fn refresh_pdf_thumbnails(
+        &mut self,
+        visible_items: &mut [CanvasItem],
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        // Thumbnails regenerated in place must be decoded again
+        for thumbnail in self.system.pdf_thumbnails.take_refreshed() {
+            release_image(&thumbnail, window, cx);
+        }
+
+        let mut updated = Vec::new();
+        for item in visible_items.iter_mut() {
+            let ItemContent::Pdf {
+                path, thumbnail, ..
+            } = &mut item.content
+            else {
+                continue;
+            };
+            let Some(current) = self
+                .system
+                .pdf_thumbnails
+                .current(path, &self.system.background)
+            else {
+                continue;
+            };
+            if thumbnail.as_ref() == Some(&current) {
+                continue;
+            }
+            if thumbnail.is_some() {
+                for page in self.system.pdf_pages.forget(path) {
+                    release_image(&page, window, cx);
+                }
+            }
+            *thumbnail = Some(current.clone());
+            updated.push((item.id, current));
+        }
+
+        if let Some(ref mut board) = self.canvas.board {
+            for (id, thumbnail) in updated {
+                board.set_pdf_thumbnail(id, thumbnail);
+            }
+        }
+    }
+
+    /// The selected item's PDF, if the selection is a single PDF item
+    pub(crate) fn selected_pdf_item(&self) -> Option<(u64, PathBuf)> {
+        if self.canvas.selected_items.len() != 1 {
+            return None;
+        }
+        let id = *self.canvas.selected_items.iter().next()?;
+        match &self.canvas.board.as_ref()?.get_item(id)?.content {
+            ItemContent::Pdf { path, .. } => Some((id, path.clone())),
+            _ => None,
+        }
+    }
+
+    /// Regenerate the thumbnail and rendered pages of the selected PDF item
+    pub fn regenerate_selected_pdf_thumbnail(
+        &mut self,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some((_, path)) = self.selected_pdf_item() else {
+            return;
+        };
+        if !path.exists() {
+            self.show_toast(Toast::error(format!("Can't find {}", path.display())));
+            cx.notify();
+            return;
+        }
+        self.system
+            .pdf_thumbnails
+            .regenerate(&path, &self.system.background);
+        for page in self.system.pdf_pages.forget(&path) {
+            release_image(&page, window, cx);
+        }
+        self.show_toast(Toast::info("Regenerating thumbnail"));
+        cx.notify();
+    }
+}
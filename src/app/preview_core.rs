@@ -1,17 +1,46 @@
 //! Core preview panel methods - opening, PDF/code webviews, markdown/code editing.
 
-use super::{FocusedPane, Humanboard, PreviewPanel, PreviewTab, SplitDirection, TabMeta};
-use crate::constants::{DOCK_WIDTH, FOOTER_HEIGHT, HEADER_HEIGHT};
-use crate::data::DataSourceDelegate;
+use super::data_preview::new_data_view;
+use super::{Humanboard, PANE_SPLITTER_SIZE, PreviewPanel, PreviewTab, SplitDirection, TabMeta};
+use crate::data::{DATA_FILES, DataSourceDelegate, is_delimited_file};
 use crate::focus::FocusContext;
+use crate::image_cache::FileStamp;
+use crate::image_viewer::{IMAGE_PIXELS, ImageZoom};
+use crate::pdf::PDF_OUTLINES;
+use crate::render::pdf_controls::PDF_PREVIEW_FOOTER_HEIGHT;
+use crate::render::pdf_outline::outline_width;
+use crate::syntax_tokens::grammar_name;
+use crate::tab_zoom::TAB_ZOOM;
 use crate::webviews::PdfWebView;
 use gpui::*;
-use gpui_component::input::InputState;
+use gpui_component::input::{InputEvent, InputState, Position};
 use gpui_component::table::TableState;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use tracing::error;
 
+/// Editor for a markdown tab. Its rendered preview follows every edit.
+fn new_markdown_editor(
+    content: String,
+    window: &mut Window,
+    cx: &mut Context<Humanboard>,
+) -> Entity<InputState> {
+    let editor = cx.new(|cx| {
+        InputState::new(window, cx)
+            .code_editor("markdown")
+            .soft_wrap(true)
+            .line_number(true)
+            .default_value(content)
+    });
+    cx.subscribe(&editor, |_, _, event: &InputEvent, cx| {
+        if let InputEvent::Change { .. } = event {
+            cx.notify();
+        }
+    })
+    .detach();
+    editor
+}
+
 impl Humanboard {
     /// Open a file in the preview panel as a permanent tab
     pub fn open_preview(&mut self, path: PathBuf, window: &mut Window, cx: &mut Context<Self>) {
@@ -33,110 +62,131 @@ impl Humanboard {
         &mut self,
         path: PathBuf,
         as_preview: bool,
-        _window: &mut Window,
+        window: &mut Window,
         cx: &mut Context<Self>,
     ) {
-        // Determine tab type based on extension
-        let ext = path.extension().and_then(|e| e.to_str()).unwrap_or("");
-
         let meta = TabMeta {
             is_preview: as_preview,
             is_pinned: false,
+            zoom: None,
         };
+        let tab = self.new_preview_tab(&path, meta, window, cx);
+
+        if let Some(ref mut preview) = self.preview.panel {
+            // Check if file is already open in any pane
+            if let Some((pane, index)) = preview.tab_position(|t| t.path() == Some(&path)) {
+                // File already open - just switch to it and make permanent if not preview mode
+                if !as_preview {
+                    preview.panes[pane].tabs[index].make_permanent();
+                }
+                if index != preview.panes[pane].active_tab {
+                    preview.record_visit(pane, cx);
+                }
+                preview.panes[pane].active_tab = index;
+                preview.focused_pane = pane;
+                cx.notify();
+                return;
+            }
+            // File not open yet - add it
+            preview.record_visit(0, cx);
+            let pane = &mut preview.panes[0];
+            if as_preview {
+                // Preview mode: replace existing preview tab if one exists
+                if let Some(preview_idx) = pane.tabs.iter().position(|t| t.is_preview()) {
+                    pane.tabs[preview_idx] = tab;
+                    pane.active_tab = preview_idx;
+                } else {
+                    // No existing preview tab, add new one
+                    pane.tabs.push(tab);
+                    pane.active_tab = pane.tabs.len() - 1;
+                }
+            } else {
+                // Permanent mode: add new tab
+                pane.tabs.push(tab);
+                pane.active_tab = pane.tabs.len() - 1;
+            }
+        } else {
+            // Create new preview panel with first tab
+            let mut panel = PreviewPanel::new(SplitDirection::Vertical, 0.4);
+            panel.panes[0].tabs.push(tab);
+            self.preview.panel = Some(panel);
+        }
+        cx.notify();
+    }
+
+    /// A new tab for a file, of the kind its extension calls for
+    pub(crate) fn new_preview_tab(
+        &mut self,
+        path: &Path,
+        mut meta: TabMeta,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> PreviewTab {
+        // Open at the zoom the file was last viewed at
+        meta.zoom = TAB_ZOOM.get(path);
+
+        // Determine tab type based on extension
+        let ext = path.extension().and_then(|e| e.to_str()).unwrap_or("");
 
-        let tab = if ext == "md" {
+        if ext == "md" {
             // Load markdown content
-            let content = std::fs::read_to_string(&path).unwrap_or_default();
+            let content = std::fs::read_to_string(path).unwrap_or_default();
             // Create editor immediately for edit mode
-            let content_clone = content.clone();
-            let editor = Some(cx.new(|cx| {
-                InputState::new(_window, cx)
-                    .code_editor("markdown")
-                    .soft_wrap(true)
-                    .line_number(true)
-                    .default_value(content_clone)
-            }));
+            let editor = Some(new_markdown_editor(content.clone(), window, cx));
             PreviewTab::Markdown {
-                path: path.clone(),
+                path: path.to_path_buf(),
                 content,
                 editing: true, // Open in edit mode
                 editor,
                 meta,
             }
         } else if ext == "pdf" {
+            // Extract its text so it can be searched with find in file
+            self.index_preview_pdf(path);
             PreviewTab::Pdf {
-                path: path.clone(),
+                path: path.to_path_buf(),
                 webview: None,
                 meta,
             }
+        } else if is_delimited_file(path) {
+            DATA_FILES.load(path, &self.system.background);
+            PreviewTab::Data {
+                path: path.to_path_buf(),
+                view: new_data_view(0, window, cx),
+                meta,
+            }
+        } else if crate::image_viewer::is_viewable_image(path) {
+            IMAGE_PIXELS.load(path, &self.system.background);
+            // Images keep their zoom in the viewer
+            let zoom = meta.zoom.take();
+            let mut tab = PreviewTab::image(path.to_path_buf(), meta, cx);
+            if let (Some(scale), PreviewTab::Image { view, .. }) = (zoom, &mut tab) {
+                view.zoom = ImageZoom::Scale(scale);
+            }
+            tab
         } else if let Some(language) = crate::types::language_from_extension(ext) {
             // Code file - load content
-            let content = std::fs::read_to_string(&path).unwrap_or_default();
+            let content = std::fs::read_to_string(path).unwrap_or_default();
             PreviewTab::Code {
-                path: path.clone(),
+                path: path.to_path_buf(),
                 language: language.to_string(),
                 content,
                 editing: true, // Always editable
                 dirty: false,
                 editor: None,
+                disk_stamp: FileStamp::of(path),
+                conflict: false,
+                outline: None,
                 meta,
             }
         } else {
             // Default to PDF for unknown types (or could be Text)
             PreviewTab::Pdf {
-                path: path.clone(),
+                path: path.to_path_buf(),
                 webview: None,
                 meta,
             }
-        };
-
-        if let Some(ref mut preview) = self.preview.panel {
-            // Check if file is already open in left pane
-            if let Some(index) = preview.tabs.iter().position(|t| t.path() == Some(&path)) {
-                // File already open - just switch to it and make permanent if not preview mode
-                if !as_preview {
-                    preview.tabs[index].make_permanent();
-                }
-                preview.active_tab = index;
-                preview.focused_pane = FocusedPane::Left;
-                cx.notify();
-                return;
-            }
-            // Check if file is already open in right pane (when split)
-            if preview.is_pane_split {
-                if let Some(index) = preview.right_tabs.iter().position(|t| t.path() == Some(&path)) {
-                    if !as_preview {
-                        preview.right_tabs[index].make_permanent();
-                    }
-                    preview.right_active_tab = index;
-                    preview.focused_pane = FocusedPane::Right;
-                    cx.notify();
-                    return;
-                }
-            }
-            // File not open yet - add it
-            if as_preview {
-                // Preview mode: replace existing preview tab if one exists
-                if let Some(preview_idx) = preview.tabs.iter().position(|t| t.is_preview()) {
-                    preview.tabs[preview_idx] = tab;
-                    preview.active_tab = preview_idx;
-                } else {
-                    // No existing preview tab, add new one
-                    preview.tabs.push(tab);
-                    preview.active_tab = preview.tabs.len() - 1;
-                }
-            } else {
-                // Permanent mode: add new tab
-                preview.tabs.push(tab);
-                preview.active_tab = preview.tabs.len() - 1;
-            }
-        } else {
-            // Create new preview panel with first tab
-            let mut panel = PreviewPanel::new(SplitDirection::Vertical, 0.4);
-            panel.tabs.push(tab);
-            self.preview.panel = Some(panel);
         }
-        cx.notify();
     }
 
     /// Ensure PDF webviews are created for PDF tabs.
@@ -144,148 +194,38 @@ impl Humanboard {
     pub fn ensure_pdf_webview(&mut self, window: &mut Window, cx: &mut App) -> Vec<String> {
         let mut errors = Vec::new();
         if let Some(ref mut preview) = self.preview.panel {
-            let active_tab = preview.active_tab;
-            let right_active_tab = preview.right_active_tab;
-            let is_pane_split = preview.is_pane_split;
-            let is_horizontal_split = preview.pane_split_horizontal;
-
-            // Calculate preview panel bounds
-            let bounds = window.bounds();
-            let window_width = f32::from(bounds.size.width);
-            let window_height = f32::from(bounds.size.height);
-
-            // Preview panel position and size
-            let header_height = HEADER_HEIGHT;
-            let footer_height = FOOTER_HEIGHT;
-            let dock_width = DOCK_WIDTH;
             let tab_bar_height = 36.0;
-
-            let main_splitter_size = 8.0; // Splitter between canvas and preview panel
-            let (panel_x, panel_y, panel_width, panel_height) = match preview.split {
-                SplitDirection::Vertical => {
-                    // Account for splitter between canvas and preview
-                    let panel_x = dock_width + (window_width - dock_width) * (1.0 - preview.size) + main_splitter_size;
-                    let panel_y = header_height;
-                    let panel_width = (window_width - dock_width) * preview.size - main_splitter_size;
-                    let panel_height = window_height - header_height - footer_height;
-                    (panel_x, panel_y, panel_width, panel_height)
-                }
-                SplitDirection::Horizontal => {
-                    let panel_x = dock_width;
-                    // Account for splitter between canvas and preview
-                    let panel_y = header_height
-                        + (window_height - header_height - footer_height) * (1.0 - preview.size) + main_splitter_size;
-                    let panel_width = window_width - dock_width;
-                    let panel_height =
-                        (window_height - header_height - footer_height) * preview.size - main_splitter_size;
-                    (panel_x, panel_y, panel_width, panel_height)
-                }
-            };
-
-            // Calculate pane bounds (left/top pane and right/bottom pane)
-            let pane_ratio = preview.pane_ratio;
-            let splitter_size = 6.0; // Splitter handle size
-            let (
-                left_pane_x,
-                left_pane_y,
-                left_pane_w,
-                left_pane_h,
-                right_pane_x,
-                right_pane_y,
-                right_pane_w,
-                right_pane_h,
-            ) = if is_pane_split {
-                if is_horizontal_split {
-                    // Top/Bottom split - use pane_ratio for heights
-                    let available_height = panel_height - tab_bar_height * 2.0 - splitter_size;
-                    let first_pane_height = available_height * pane_ratio;
-                    let second_pane_height = available_height * (1.0 - pane_ratio);
-                    (
-                        panel_x,
-                        panel_y + tab_bar_height,
-                        panel_width,
-                        first_pane_height,
-                        panel_x,
-                        panel_y
-                            + tab_bar_height
-                            + first_pane_height
-                            + splitter_size
-                            + tab_bar_height,
-                        panel_width,
-                        second_pane_height,
-                    )
-                } else {
-                    // Left/Right split - use pane_ratio for widths
-                    let available_width = panel_width - splitter_size;
-                    let first_pane_width = available_width * pane_ratio;
-                    let second_pane_width = available_width * (1.0 - pane_ratio);
-                    (
-                        panel_x,
-                        panel_y + tab_bar_height,
-                        first_pane_width,
-                        panel_height - tab_bar_height,
-                        panel_x + first_pane_width + splitter_size,
-                        panel_y + tab_bar_height,
-                        second_pane_width,
-                        panel_height - tab_bar_height,
-                    )
-                }
-            } else {
-                // Single pane
-                (
-                    panel_x,
-                    panel_y + tab_bar_height,
-                    panel_width,
-                    panel_height - tab_bar_height,
-                    0.0,
-                    0.0,
-                    0.0,
-                    0.0, // Right pane not used
-                )
-            };
-
-            // Ensure all PDF tabs in left pane have their WebViews created and positioned
-            for (idx, tab) in preview.tabs.iter_mut().enumerate() {
-                if let PreviewTab::Pdf { path, webview, .. } = tab {
-                    if webview.is_none() {
-                        match PdfWebView::new(path.clone(), window, cx) {
-                            Ok(wv) => {
-                                if idx != active_tab {
-                                    wv.hide(cx);
-                                }
-                                *webview = Some(wv);
-                            }
-                            Err(e) => {
-                                let filename = path.file_name()
-                                    .and_then(|n| n.to_str())
-                                    .unwrap_or("PDF");
-                                errors.push(format!("Failed to open '{}': {}", filename, e));
-                                error!("Failed to create PDF WebView: {}", e);
-                            }
-                        }
-                    }
-
-                    if let Some(wv) = webview {
-                        if idx == active_tab {
-                            wv.set_bounds(left_pane_x, left_pane_y, left_pane_w, left_pane_h, cx);
-                            wv.show(cx);
-                        } else {
-                            wv.hide(cx);
-                        }
-                    }
-                }
-            }
-
-            // Ensure all PDF tabs in right pane have their WebViews created and positioned (when split)
-            if is_pane_split {
-                for (idx, tab) in preview.right_tabs.iter_mut().enumerate() {
-                    if let PreviewTab::Pdf { path, webview, .. } = tab {
+            let panel_bounds = preview.bounds_in_window(window);
+            let pane_bounds = preview.layout.pane_bounds(panel_bounds, PANE_SPLITTER_SIZE);
+
+            // Ensure all PDF tabs of each pane have their WebViews created and positioned
+            for (pane, (pane_x, pane_y, pane_w, pane_h)) in pane_bounds {
+                let Some(pane) = preview.panes.get_mut(pane) else {
+                    continue;
+                };
+                let active_tab = pane.active_tab;
+                for (idx, tab) in pane.tabs.iter_mut().enumerate() {
+                    if let PreviewTab::Pdf {
+                        path,
+                        webview,
+                        meta,
+                    } = tab
+                    {
                         if webview.is_none() {
+                            PDF_OUTLINES.load(path, &self.system.background);
                             match PdfWebView::new(path.clone(), window, cx) {
                                 Ok(wv) => {
-                                    if idx != right_active_tab {
+                                    if idx != active_tab {
                                         wv.hide(cx);
                                     }
+                                    // Opened from a highlight note or a restored
+                                    // session, at its page
+                                    if let Some(page) = self.preview.pending_pdf_pages.remove(path) {
+                                        wv.show_page(page, cx);
+                                    }
+                                    if let Some(zoom) = meta.zoom {
+                                        wv.set_zoom(zoom, cx);
+                                    }
                                     *webview = Some(wv);
                                 }
                                 Err(e) => {
@@ -293,18 +233,22 @@ impl Humanboard {
                                         .and_then(|n| n.to_str())
                                         .unwrap_or("PDF");
                                     errors.push(format!("Failed to open '{}': {}", filename, e));
-                                    error!("Failed to create PDF WebView for right pane: {}", e);
+                                    error!("Failed to create PDF WebView: {}", e);
                                 }
                             }
                         }
 
                         if let Some(wv) = webview {
-                            if idx == right_active_tab {
+                            if idx == active_tab {
+                                // Below the pane's tab bar, leaving room for the
+                                // outline beside the viewer and the page footer
+                                // below it
+                                let outline = outline_width(path, wv);
                                 wv.set_bounds(
-                                    right_pane_x,
-                                    right_pane_y,
-                                    right_pane_w,
-                                    right_pane_h,
+                                    pane_x + outline,
+                                    pane_y + tab_bar_height,
+                                    pane_w - outline,
+                                    pane_h - tab_bar_height - PDF_PREVIEW_FOOTER_HEIGHT,
                                     cx,
                                 );
                                 wv.show(cx);
@@ -322,11 +266,10 @@ impl Humanboard {
     /// Ensure code editors are created for code tabs (for syntax-highlighted viewing)
     pub fn ensure_code_editors(&mut self, _window: &mut Window, cx: &mut Context<Self>) {
         if let Some(ref mut preview) = self.preview.panel {
-            let is_split = preview.is_pane_split;
-
-            // Ensure code editors for left pane
-            for tab in preview.tabs.iter_mut() {
+            // Ensure code editors for every pane
+            for tab in preview.all_tabs_mut() {
                 if let PreviewTab::Code {
+                    path,
                     content,
                     language,
                     editor,
@@ -337,13 +280,20 @@ impl Humanboard {
                     if editor.is_none() {
                         // Create editor with syntax highlighting
                         let content_clone = content.clone();
-                        let lang = language.clone();
-                        *editor = Some(cx.new(|cx| {
+                        let lang = grammar_name(language).unwrap_or(language.as_str()).to_string();
+                        let new_editor = cx.new(|cx| {
                             InputState::new(_window, cx)
                                 .code_editor(lang)
                                 .line_number(true)
                                 .default_value(content_clone)
-                        }));
+                        });
+                        // Opened from a card of a line range: start at its first line
+                        if let Some(line) = self.preview.pending_code_lines.remove(path.as_path()) {
+                            new_editor.update(cx, |state, cx| {
+                                state.set_cursor_position(Position::new(line as u32, 0), _window, cx);
+                            });
+                        }
+                        *editor = Some(new_editor);
                     } else if let Some(ed) = editor {
                         // Check if content changed (for dirty indicator)
                         let editor_content = ed.read(cx).text().to_string();
@@ -351,34 +301,6 @@ impl Humanboard {
                     }
                 }
             }
-
-            // Ensure code editors for right pane (when split)
-            if is_split {
-                for tab in preview.right_tabs.iter_mut() {
-                    if let PreviewTab::Code {
-                        content,
-                        language,
-                        editor,
-                        dirty,
-                        ..
-                    } = tab
-                    {
-                        if editor.is_none() {
-                            let content_clone = content.clone();
-                            let lang = language.clone();
-                            *editor = Some(cx.new(|cx| {
-                                InputState::new(_window, cx)
-                                    .code_editor(lang)
-                                    .line_number(true)
-                                    .default_value(content_clone)
-                            }));
-                        } else if let Some(ed) = editor {
-                            let editor_content = ed.read(cx).text().to_string();
-                            *dirty = editor_content != *content;
-                        }
-                    }
-                }
-            }
         }
     }
 
@@ -389,18 +311,8 @@ impl Humanboard {
         if let Some(ref mut preview) = self.preview.panel {
             let mut dirty_updates: Vec<(u64, crate::types::DataSource)> = Vec::new();
 
-            // Check left pane tabs for dirty data
-            for tab in preview.tabs.iter() {
-                if let PreviewTab::Table { data_source_id, table_state: Some(state), .. } = tab {
-                    let delegate_ds = state.read(cx).delegate().data_source();
-                    if delegate_ds.is_dirty() {
-                        dirty_updates.push((*data_source_id, (**delegate_ds).clone()));
-                    }
-                }
-            }
-
-            // Check right pane tabs for dirty data
-            for tab in preview.right_tabs.iter() {
+            // Check the tabs of every pane for dirty data
+            for tab in preview.all_tabs() {
                 if let PreviewTab::Table { data_source_id, table_state: Some(state), .. } = tab {
                     let delegate_ds = state.read(cx).delegate().data_source();
                     if delegate_ds.is_dirty() {
@@ -415,7 +327,7 @@ impl Humanboard {
                     for (ds_id, updated_ds) in dirty_updates {
                         board.data_sources.insert(ds_id, updated_ds);
                     }
-                    board.push_history();
+                    board.push_labeled_history("Edited table");
                     let _ = board.flush_save();
                 }
             }
@@ -429,13 +341,12 @@ impl Humanboard {
         };
 
         if let Some(ref mut preview) = self.preview.panel {
-            let is_split = preview.is_pane_split;
-
-            // Ensure table states for left pane
-            for tab in preview.tabs.iter_mut() {
+            // Ensure table states for every pane
+            for tab in preview.all_tabs_mut() {
                 if let PreviewTab::Table {
                     data_source_id,
                     table_state,
+                    view,
                     ..
                 } = tab
                 {
@@ -445,26 +356,7 @@ impl Humanboard {
                             let delegate = DataSourceDelegate::with_width(Arc::new(ds.clone()), 800.0);
                             let state = cx.new(|cx| TableState::new(delegate, window, cx));
                             *table_state = Some(state);
-                        }
-                    }
-                }
-            }
-
-            // Ensure table states for right pane (when split)
-            if is_split {
-                for tab in preview.right_tabs.iter_mut() {
-                    if let PreviewTab::Table {
-                        data_source_id,
-                        table_state,
-                        ..
-                    } = tab
-                    {
-                        if table_state.is_none() {
-                            if let Some(ds) = data_sources_map.get(data_source_id) {
-                                let delegate = DataSourceDelegate::with_width(Arc::new(ds.clone()), 800.0);
-                                let state = cx.new(|cx| TableState::new(delegate, window, cx));
-                                *table_state = Some(state);
-                            }
+                            *view = Some(new_data_view(ds.rows.len(), window, cx));
                         }
                     }
                 }
@@ -474,7 +366,7 @@ impl Humanboard {
 
     pub fn toggle_markdown_edit(&mut self, window: &mut Window, cx: &mut Context<Self>) {
         if let Some(ref mut preview) = self.preview.panel {
-            if let Some(tab) = preview.tabs.get_mut(preview.active_tab) {
+            if let Some(tab) = preview.focused_mut().active_mut() {
                 if let PreviewTab::Markdown {
                     editing,
                     content,
@@ -488,15 +380,7 @@ impl Humanboard {
                         self.system.focus.focus(FocusContext::Preview, window);
 
                         if editor.is_none() {
-                            // Create editor with current content - use code_editor for multiline support
-                            let content_clone = content.clone();
-                            *editor = Some(cx.new(|cx| {
-                                InputState::new(window, cx)
-                                    .code_editor("markdown")
-                                    .soft_wrap(true)
-                                    .line_number(true)
-                                    .default_value(content_clone)
-                            }));
+                            *editor = Some(new_markdown_editor(content.clone(), window, cx));
                         }
                         // Focus the editor so user can type immediately
                         if let Some(ed) = editor {
@@ -514,86 +398,53 @@ impl Humanboard {
         cx.notify();
     }
 
+    /// Save the active markdown tab's editor text to its file, and show it
+    /// on the file's cards. The tab stays in the editor and preview split.
     pub fn save_markdown(&mut self, cx: &mut Context<Self>) {
-        if let Some(ref mut preview) = self.preview.panel {
-            if let Some(tab) = preview.tabs.get_mut(preview.active_tab) {
-                if let PreviewTab::Markdown {
-                    path,
-                    content,
-                    editor,
-                    editing,
-                    ..
-                } = tab
-                {
-                    if let Some(ed) = editor {
-                        let new_content = ed.read(cx).text().to_string();
-                        let _ = std::fs::write(path.as_path(), &new_content);
-                        if let Some(ref mut board) = self.canvas.board {
-                            for item in board.items.iter_mut() {
-                                if let crate::types::ItemContent::Markdown {
-                                    path: item_path,
-                                    content: item_content,
-                                    ..
-                                } = &mut item.content
-                                {
-                                    if item_path == path {
-                                        *item_content = new_content.clone();
-                                    }
-                                }
-                            }
-                        }
-                        *content = new_content;
-                        *editing = false;
-                    }
+        let Some(ref mut preview) = self.preview.panel else {
+            return;
+        };
+        let Some(PreviewTab::Markdown {
+            path,
+            content,
+            editor: Some(editor),
+            ..
+        }) = preview.focused_mut().active_mut()
+        else {
+            return;
+        };
+        let new_content = editor.read(cx).text().to_string();
+        let saved = match std::fs::write(path.as_path(), &new_content) {
+            Ok(()) => {
+                *content = new_content;
+                Ok((path.clone(), content.clone()))
+            }
+            Err(e) => Err(format!("Couldn't save {}: {}", path.display(), e)),
+        };
+        match saved {
+            Ok((path, content)) => {
+                if let Some(ref mut board) = self.canvas.board {
+                    board.set_markdown_content(&path, &content);
                 }
             }
+            Err(message) => {
+                error!("{}", message);
+                self.show_toast(crate::notifications::Toast::error(message));
+            }
         }
         cx.notify();
     }
 
     pub fn save_code(&mut self, cx: &mut Context<Self>) {
         if let Some(ref mut preview) = self.preview.panel {
-            if let Some(tab) = preview.tabs.get_mut(preview.active_tab) {
+            if let Some(tab) = preview.focused_mut().active_mut() {
                 match tab {
-                    PreviewTab::Code {
-                        path,
-                        content,
-                        editor,
-                        dirty,
-                        ..
-                    } => {
-                        if let Some(ed) = editor {
-                            let new_content = ed.read(cx).text().to_string();
-                            if let Err(e) = std::fs::write(path.as_path(), &new_content) {
-                                error!("Failed to save code file: {}", e);
-                            }
-                            *content = new_content;
-                            *dirty = false;
-                        }
+                    PreviewTab::Code { path, .. } => {
+                        let path = path.clone();
+                        return self.save_code_file(&path, false, cx);
                     }
-                    PreviewTab::Markdown { path, content, editor, editing, .. } => {
-                        if *editing {
-                            if let Some(ed) = editor {
-                                let new_content = ed.read(cx).text().to_string();
-                                let _ = std::fs::write(path.as_path(), &new_content);
-                                if let Some(ref mut board) = self.canvas.board {
-                                    for item in board.items.iter_mut() {
-                                        if let crate::types::ItemContent::Markdown {
-                                            path: item_path,
-                                            content: item_content,
-                                            ..
-                                        } = &mut item.content
-                                        {
-                                            if item_path == path {
-                                                *item_content = new_content.clone();
-                                            }
-                                        }
-                                    }
-                                }
-                                *content = new_content;
-                                *editing = false; // Exit edit mode to show preview
-                            }
-                        }
+                    PreviewTab::Markdown { editing: true, .. } => {
+                        return self.save_markdown(cx);
                     }
                     PreviewTab::Table { data_source_id, name, table_state, .. } => {
                         // Save the data source to file
@@ -640,43 +491,36 @@ impl Humanboard {
         let meta = TabMeta {
             is_preview: false,
             is_pinned: false,
+            zoom: None,
         };
 
         let tab = PreviewTab::Table {
             data_source_id,
             name: name.clone(),
             table_state: None, // Will be created when rendering
+            editing: false,
+            view: None,
             meta,
         };
 
         if let Some(ref mut preview) = self.preview.panel {
-            // Check if table is already open (by data_source_id)
-            if let Some(index) = preview.tabs.iter().position(|t| {
+            // Check if table is already open (by data_source_id) in any pane
+            if let Some((pane, index)) = preview.tab_position(|t| {
                 matches!(t, PreviewTab::Table { data_source_id: id, .. } if *id == data_source_id)
             }) {
-                preview.active_tab = index;
-                preview.focused_pane = FocusedPane::Left;
+                preview.panes[pane].active_tab = index;
+                preview.focused_pane = pane;
                 cx.notify();
                 return;
             }
-            // Check right pane too
-            if preview.is_pane_split {
-                if let Some(index) = preview.right_tabs.iter().position(|t| {
-                    matches!(t, PreviewTab::Table { data_source_id: id, .. } if *id == data_source_id)
-                }) {
-                    preview.right_active_tab = index;
-                    preview.focused_pane = FocusedPane::Right;
-                    cx.notify();
-                    return;
-                }
-            }
             // Add new tab
-            preview.tabs.push(tab);
-            preview.active_tab = preview.tabs.len() - 1;
+            let pane = &mut preview.panes[0];
+            pane.tabs.push(tab);
+            pane.active_tab = pane.tabs.len() - 1;
         } else {
             // Create new preview panel with first tab
             let mut panel = PreviewPanel::new(SplitDirection::Vertical, 0.4);
-            panel.tabs.push(tab);
+            panel.panes[0].tabs.push(tab);
             self.preview.panel = Some(panel);
         }
         cx.notify();
@@ -0,0 +1,189 @@
+//! Preview navigation methods - each pane's back/forward history of the
+//! tabs, PDF pages and code lines it moved between
+
+use crate::app::{Humanboard, PreviewPanel, PreviewTab, TabMeta};
+use crate::preview_history::{NavLocation, NavPlace, NavTarget};
+use gpui::*;
+use gpui_component::input::Position;
+use std::path::Path;
+
+impl PreviewPanel {
+    /// Where a pane is now: its active tab, and the page or line it shows
+    pub fn location(&self, pane: usize, cx: &App) -> Option<NavLocation> {
+        let tab = self.panes.get(pane)?.active()?;
+        let place = match tab {
+            PreviewTab::Pdf {
+                webview: Some(webview),
+                ..
+            } => Some(NavPlace::Page(webview.page())),
+            PreviewTab::Code {
+                editor: Some(editor),
+                ..
+            } => Some(NavPlace::Line(
+                editor.read(cx).cursor_position().line as usize,
+            )),
+            _ => None,
+        };
+        Some(NavLocation {
+            target: nav_target(tab),
+            place,
+        })
+    }
+
+    /// Remember where a pane is before it moves
+    pub fn record_visit(&mut self, pane: usize, cx: &App) {
+        if let Some(location) = self.location(pane, cx) {
+            self.panes[pane].history.visit(location);
+        }
+    }
+
+    /// The pane whose active tab shows `path`
+    fn pane_showing(&self, path: &Path) -> Option<usize> {
+        self.panes.iter().position(|pane| {
+            pane.active()
+                .and_then(|tab| tab.path())
+                .is_some_and(|p| p == path)
+        })
+    }
+}
+
+impl Humanboard {
+    /// Go back to where the focused pane was before its last move
+    pub fn go_back(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        if let Some(ref preview) = self.preview.panel {
+            let pane = preview.focused_pane;
+            self.navigate_pane(pane, true, window, cx);
+        }
+    }
+
+    /// Go forward again in the focused pane's history
+    pub fn go_forward(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        if let Some(ref preview) = self.preview.panel {
+            let pane = preview.focused_pane;
+            self.navigate_pane(pane, false, window, cx);
+        }
+    }
+
+    /// Go back or forward in a pane's history, passing over places that
+    /// can't be shown anymore, like closed tabs without a file
+    pub fn navigate_pane(
+        &mut self,
+        pane: usize,
+        back: bool,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(ref preview) = self.preview.panel else {
+            return;
+        };
+        let mut current = preview.location(pane, cx);
+        loop {
+            let Some(history) = self
+                .preview
+                .panel
+                .as_mut()
+                .and_then(|preview| preview.panes.get_mut(pane))
+                .map(|pane| &mut pane.history)
+            else {
+                return;
+            };
+            let location = if back {
+                history.back(current.take())
+            } else {
+                history.forward(current.take())
+            };
+            let Some(location) = location else {
+                break;
+            };
+            if self.show_nav_location(location, pane, window, cx) {
+                break;
+            }
+        }
+        cx.notify();
+    }
+
+    /// Remember where the pane showing `path` is, before it jumps to
+    /// another page or line of it
+    pub(crate) fn record_preview_jump(&mut self, path: &Path, cx: &App) {
+        if let Some(ref mut preview) = self.preview.panel {
+            if let Some(pane) = preview.pane_showing(path) {
+                preview.record_visit(pane, cx);
+            }
+        }
+    }
+
+    /// Show a place from a pane's history, opening its file again if its
+    /// tab was closed. Returns whether the place could be shown.
+    fn show_nav_location(
+        &mut self,
+        location: NavLocation,
+        pane: usize,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> bool {
+        let Some(ref preview) = self.preview.panel else {
+            return false;
+        };
+        let index = match preview.panes[pane]
+            .tabs
+            .iter()
+            .position(|tab| nav_target(tab) == location.target)
+        {
+            Some(index) => index,
+            None => {
+                let NavTarget::File(ref path) = location.target else {
+                    return false;
+                };
+                // A file moved to another pane is left there
+                if !path.exists() || preview.all_tabs().any(|tab| tab.path() == Some(path)) {
+                    return false;
+                }
+                let tab = self.new_preview_tab(path, TabMeta::default(), window, cx);
+                let Some(ref mut preview) = self.preview.panel else {
+                    return false;
+                };
+                let tabs = &mut preview.panes[pane].tabs;
+                tabs.push(tab);
+                tabs.len() - 1
+            }
+        };
+
+        let Some(ref mut preview) = self.preview.panel else {
+            return false;
+        };
+        preview.panes[pane].show_tab(index, cx);
+        preview.focused_pane = pane;
+        match (location.place, &preview.panes[pane].tabs[index]) {
+            (
+                Some(NavPlace::Page(page)),
+                PreviewTab::Pdf {
+                    webview: Some(webview),
+                    ..
+                },
+            ) => webview.show_page(page, cx),
+            (
+                Some(NavPlace::Line(line)),
+                PreviewTab::Code {
+                    editor: Some(editor),
+                    ..
+                },
+            ) => {
+                let editor = editor.clone();
+                editor.update(cx, |state, cx| {
+                    state.set_cursor_position(Position::new(line as u32, 0), window, cx);
+                });
+            }
+            _ => {}
+        }
+        true
+    }
+}
+
+/// The tab a history entry points at: its file, or its title for tabs
+/// without one
+fn nav_target(tab: &PreviewTab) -> NavTarget {
+    match tab.path() {
+        Some(path) => NavTarget::File(path.clone()),
+        None => NavTarget::Tab(tab.title()),
+    }
+}
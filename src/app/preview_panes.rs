@@ -1,241 +1,515 @@
 //! Pane management - tab switching, split panes, focus management.
-
-use super::{FocusedPane, Humanboard, PreviewTab, SplitDirection};
+//!
+//! The panel holds up to [`MAX_PREVIEW_PANES`] panes laid out by a
+//! [`PaneTree`]: every split divides part of the panel in two, side by
+//! side or stacked, and either part can be split again. Splitting a pane
+//! puts a new pane beside it, and a pane left without tabs is taken out,
+//! the part beside it filling its place.
+
+use super::preview_tabs::detach_webview;
+use super::{Humanboard, PreviewPane, PreviewPanel, PreviewTab, SplitDirection, SplitDropZone};
+use crate::constants::{DOCK_WIDTH, FOOTER_HEIGHT, HEADER_HEIGHT};
+use crate::preview::{insert_tab, toggle_tab_pinned};
 use gpui::*;
+use serde::{Deserialize, Serialize};
+
+/// Most panes the preview panel can be split into
+pub const MAX_PREVIEW_PANES: usize = 4;
+
+/// Width of the divider between the two parts of a split
+pub const PANE_SPLITTER_SIZE: f32 = 6.0;
+
+/// Bounds as (x, y, width, height)
+pub type PaneBounds = (f32, f32, f32, f32);
+
+/// How the panes of the preview panel are laid out
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum PaneTree {
+    /// A pane, by its index in the panel's panes
+    Pane(usize),
+    /// Two parts side by side, or stacked if `horizontal`, the first
+    /// taking `ratio` of the space
+    Split {
+        horizontal: bool,
+        ratio: f32,
+        first: Box<PaneTree>,
+        second: Box<PaneTree>,
+    },
+}
 
-impl Humanboard {
-    pub fn switch_tab(&mut self, tab_index: usize, cx: &mut Context<Self>) {
-        if let Some(ref mut preview) = self.preview.panel {
-            if tab_index < preview.tabs.len() && tab_index != preview.active_tab {
-                // Record in back stack for navigation history
-                preview.back_stack.push(preview.active_tab);
-                // Clear forward stack when user manually switches tabs
-                preview.forward_stack.clear();
-
-                // Hide/show PDF webviews based on active tab
-                for (idx, tab) in preview.tabs.iter().enumerate() {
-                    if let PreviewTab::Pdf {
-                        webview: Some(wv), ..
-                    } = tab
-                    {
-                        if idx == tab_index {
-                            wv.webview().update(cx, |view, _| view.show());
-                        } else {
-                            wv.webview().update(cx, |view, _| view.hide());
-                        }
-                    }
-                }
+impl Default for PaneTree {
+    fn default() -> Self {
+        PaneTree::Pane(0)
+    }
+}
 
-                preview.active_tab = tab_index;
-                cx.notify();
+impl PaneTree {
+    /// The panes in the order they appear, left to right and top to bottom
+    pub fn panes(&self) -> Vec<usize> {
+        match self {
+            PaneTree::Pane(pane) => vec![*pane],
+            PaneTree::Split { first, second, .. } => {
+                let mut panes = first.panes();
+                panes.extend(second.panes());
+                panes
             }
         }
     }
 
-    /// Switch to a specific tab in the specified pane (is_left_pane determines which pane)
-    pub fn switch_tab_in_pane(&mut self, tab_index: usize, is_left_pane: bool, cx: &mut Context<Self>) {
-        if let Some(ref mut preview) = self.preview.panel {
-            if is_left_pane {
-                // Switch in left pane
-                if tab_index < preview.tabs.len() && tab_index != preview.active_tab {
-                    preview.back_stack.push(preview.active_tab);
-                    preview.forward_stack.clear();
-
-                    // Hide/show PDF webviews based on active tab
-                    for (idx, tab) in preview.tabs.iter().enumerate() {
-                        if let PreviewTab::Pdf {
-                            webview: Some(wv), ..
-                        } = tab
-                        {
-                            if idx == tab_index {
-                                wv.webview().update(cx, |view, _| view.show());
-                            } else {
-                                wv.webview().update(cx, |view, _| view.hide());
-                            }
-                        }
-                    }
+    /// Split the part showing `pane` in two, `new_pane` going after it, or
+    /// before it if `before`. Returns whether `pane` was found.
+    pub fn split(&mut self, pane: usize, new_pane: usize, horizontal: bool, before: bool) -> bool {
+        match self {
+            PaneTree::Pane(p) if *p == pane => {
+                let (first, second) = if before {
+                    (new_pane, pane)
+                } else {
+                    (pane, new_pane)
+                };
+                *self = PaneTree::Split {
+                    horizontal,
+                    ratio: 0.5,
+                    first: Box::new(PaneTree::Pane(first)),
+                    second: Box::new(PaneTree::Pane(second)),
+                };
+                true
+            }
+            PaneTree::Pane(_) => false,
+            PaneTree::Split { first, second, .. } => {
+                first.split(pane, new_pane, horizontal, before)
+                    || second.split(pane, new_pane, horizontal, before)
+            }
+        }
+    }
 
-                    preview.active_tab = tab_index;
-                    preview.focused_pane = FocusedPane::Left;
-                    cx.notify();
+    /// Take `pane` out, the part beside it filling their split, and number
+    /// the panes after it one lower, as they are once it is removed from
+    /// the panel
+    pub fn remove(&mut self, pane: usize) {
+        self.take_out(pane);
+        self.renumber(pane);
+    }
+
+    fn take_out(&mut self, pane: usize) -> bool {
+        let PaneTree::Split { first, second, .. } = self else {
+            return false;
+        };
+        let rest = if **first == PaneTree::Pane(pane) {
+            Some(std::mem::take(&mut **second))
+        } else if **second == PaneTree::Pane(pane) {
+            Some(std::mem::take(&mut **first))
+        } else {
+            None
+        };
+        match rest {
+            Some(rest) => {
+                *self = rest;
+                true
+            }
+            None => first.take_out(pane) || second.take_out(pane),
+        }
+    }
+
+    fn renumber(&mut self, removed: usize) {
+        match self {
+            PaneTree::Pane(pane) => {
+                if *pane > removed {
+                    *pane -= 1;
                 }
-            } else {
-                // Switch in right pane
-                if tab_index < preview.right_tabs.len() && tab_index != preview.right_active_tab {
-                    preview.right_back_stack.push(preview.right_active_tab);
-                    preview.right_forward_stack.clear();
-
-                    // Hide/show PDF webviews based on active tab
-                    for (idx, tab) in preview.right_tabs.iter().enumerate() {
-                        if let PreviewTab::Pdf {
-                            webview: Some(wv), ..
-                        } = tab
-                        {
-                            if idx == tab_index {
-                                wv.webview().update(cx, |view, _| view.show());
-                            } else {
-                                wv.webview().update(cx, |view, _| view.hide());
-                            }
-                        }
-                    }
+            }
+            PaneTree::Split { first, second, .. } => {
+                first.renumber(removed);
+                second.renumber(removed);
+            }
+        }
+    }
 
-                    preview.right_active_tab = tab_index;
-                    preview.focused_pane = FocusedPane::Right;
-                    cx.notify();
+    /// The part at `path`, each step going into the second part of a split
+    /// if true and the first if false
+    pub fn part(&self, path: &[bool]) -> Option<&PaneTree> {
+        match (self, path.split_first()) {
+            (_, None) => Some(self),
+            (PaneTree::Split { first, second, .. }, Some((&into_second, rest))) => {
+                if into_second {
+                    second.part(rest)
+                } else {
+                    first.part(rest)
                 }
             }
+            (PaneTree::Pane(_), Some(_)) => None,
         }
     }
 
-    /// Make a tab permanent (convert from preview tab) in the specified pane
-    pub fn make_tab_permanent_in_pane(&mut self, tab_index: usize, is_left_pane: bool, cx: &mut Context<Self>) {
-        if let Some(ref mut preview) = self.preview.panel {
-            if is_left_pane {
-                if let Some(tab) = preview.tabs.get_mut(tab_index) {
-                    tab.make_permanent();
-                    cx.notify();
+    fn part_mut(&mut self, path: &[bool]) -> Option<&mut PaneTree> {
+        match path.split_first() {
+            None => Some(self),
+            Some((&into_second, rest)) => match self {
+                PaneTree::Split { first, second, .. } => {
+                    if into_second {
+                        second.part_mut(rest)
+                    } else {
+                        first.part_mut(rest)
+                    }
                 }
-            } else {
-                if let Some(tab) = preview.right_tabs.get_mut(tab_index) {
-                    tab.make_permanent();
-                    cx.notify();
+                PaneTree::Pane(_) => None,
+            },
+        }
+    }
+
+    /// Set the share of its first part of the split at `path`, kept so
+    /// neither part gets too small
+    pub fn set_ratio(&mut self, path: &[bool], new_ratio: f32) {
+        if let Some(PaneTree::Split { ratio, .. }) = self.part_mut(path) {
+            *ratio = new_ratio.clamp(0.2, 0.8);
+        }
+    }
+
+    /// Whether the split directly holding `pane` stacks its parts, if
+    /// the pane is in a split
+    pub fn parent_horizontal(&self, pane: usize) -> Option<bool> {
+        let PaneTree::Split {
+            horizontal,
+            first,
+            second,
+            ..
+        } = self
+        else {
+            return None;
+        };
+        if **first == PaneTree::Pane(pane) || **second == PaneTree::Pane(pane) {
+            Some(*horizontal)
+        } else {
+            first
+                .parent_horizontal(pane)
+                .or_else(|| second.parent_horizontal(pane))
+        }
+    }
+
+    /// The pane at the side of the panel `zone` is on, taking the first
+    /// part of splits across that side
+    pub fn edge_pane(&self, zone: SplitDropZone) -> usize {
+        match self {
+            PaneTree::Pane(pane) => *pane,
+            PaneTree::Split {
+                horizontal,
+                first,
+                second,
+                ..
+            } => {
+                let second_side = if *horizontal {
+                    zone == SplitDropZone::Bottom
+                } else {
+                    zone == SplitDropZone::Right
+                };
+                if second_side {
+                    second.edge_pane(zone)
+                } else {
+                    first.edge_pane(zone)
                 }
             }
         }
     }
 
-    /// Close a tab in the specified pane
-    pub fn close_tab_in_pane(&mut self, tab_index: usize, is_left_pane: bool, cx: &mut Context<Self>) {
-        let mut should_close_preview = false;
+    /// The bounds of each pane when the layout fills `bounds`, with `gap`
+    /// between the parts of every split
+    pub fn pane_bounds(&self, bounds: PaneBounds, gap: f32) -> Vec<(usize, PaneBounds)> {
+        match self {
+            PaneTree::Pane(pane) => vec![(*pane, bounds)],
+            PaneTree::Split {
+                horizontal,
+                ratio,
+                first,
+                second,
+            } => {
+                let (first_bounds, second_bounds) = divide(bounds, *horizontal, *ratio, gap);
+                let mut panes = first.pane_bounds(first_bounds, gap);
+                panes.extend(second.pane_bounds(second_bounds, gap));
+                panes
+            }
+        }
+    }
 
-        if let Some(ref mut preview) = self.preview.panel {
-            if is_left_pane {
-                // Close from left pane
-                if tab_index < preview.tabs.len() {
-                    if preview.tabs[tab_index].is_pinned() {
-                        return;
-                    }
+    /// The bounds the split at `path` divides when the layout fills
+    /// `bounds`
+    pub fn split_bounds(&self, path: &[bool], bounds: PaneBounds, gap: f32) -> Option<PaneBounds> {
+        let PaneTree::Split {
+            horizontal,
+            ratio,
+            first,
+            second,
+        } = self
+        else {
+            return None;
+        };
+        let Some((&into_second, rest)) = path.split_first() else {
+            return Some(bounds);
+        };
+        let (first_bounds, second_bounds) = divide(bounds, *horizontal, *ratio, gap);
+        if into_second {
+            second.split_bounds(rest, second_bounds, gap)
+        } else {
+            first.split_bounds(rest, first_bounds, gap)
+        }
+    }
+}
 
-                    let mut closed_tab = preview.tabs.remove(tab_index);
-                    // Immediately cleanup (hide PDF webviews, etc.) before storing
-                    closed_tab.cleanup(cx);
-                    preview.closed_tabs.push(closed_tab);
-                    if preview.closed_tabs.len() > 20 {
-                        preview.closed_tabs.remove(0);
-                    }
+/// The bounds of the two parts of a split
+fn divide(bounds: PaneBounds, horizontal: bool, ratio: f32, gap: f32) -> (PaneBounds, PaneBounds) {
+    let (x, y, width, height) = bounds;
+    if horizontal {
+        let available = (height - gap).max(0.0);
+        let first = available * ratio;
+        (
+            (x, y, width, first),
+            (x, y + first + gap, width, available - first),
+        )
+    } else {
+        let available = (width - gap).max(0.0);
+        let first = available * ratio;
+        (
+            (x, y, first, height),
+            (x + first + gap, y, available - first, height),
+        )
+    }
+}
 
-                    if preview.tabs.is_empty() {
-                        // Left pane empty - merge right into left if split exists
-                        if preview.is_pane_split && !preview.right_tabs.is_empty() {
-                            preview.tabs = std::mem::take(&mut preview.right_tabs);
-                            preview.active_tab = preview.right_active_tab;
-                            preview.back_stack = std::mem::take(&mut preview.right_back_stack);
-                            preview.forward_stack = std::mem::take(&mut preview.right_forward_stack);
-                            preview.is_pane_split = false;
-                            preview.focused_pane = FocusedPane::Left;
-                        } else {
-                            // No tabs left - close the preview panel entirely
-                            should_close_preview = true;
-                        }
-                    } else {
-                        if preview.active_tab >= preview.tabs.len() {
-                            preview.active_tab = preview.tabs.len() - 1;
-                        } else if tab_index < preview.active_tab {
-                            preview.active_tab -= 1;
-                        }
-                    }
-                    cx.notify();
-                }
-            } else {
-                // Close from right pane
-                if tab_index < preview.right_tabs.len() {
-                    if preview.right_tabs[tab_index].is_pinned() {
-                        return;
-                    }
+impl PreviewPane {
+    /// The tab the pane shows
+    pub fn active(&self) -> Option<&PreviewTab> {
+        self.tabs.get(self.active_tab)
+    }
 
-                    let mut closed_tab = preview.right_tabs.remove(tab_index);
-                    // Immediately cleanup (hide PDF webviews, etc.) before storing
-                    closed_tab.cleanup(cx);
-                    preview.closed_tabs.push(closed_tab);
-                    if preview.closed_tabs.len() > 20 {
-                        preview.closed_tabs.remove(0);
-                    }
+    pub fn active_mut(&mut self) -> Option<&mut PreviewTab> {
+        self.tabs.get_mut(self.active_tab)
+    }
 
-                    if preview.right_tabs.is_empty() {
-                        // Right pane empty, close the split
-                        preview.is_pane_split = false;
-                        preview.focused_pane = FocusedPane::Left;
-                    } else {
-                        if preview.right_active_tab >= preview.right_tabs.len() {
-                            preview.right_active_tab = preview.right_tabs.len() - 1;
-                        } else if tab_index < preview.right_active_tab {
-                            preview.right_active_tab -= 1;
-                        }
-                    }
-                    cx.notify();
+    /// Show the tab at `index`, hiding the PDF viewers of the others
+    pub fn show_tab(&mut self, index: usize, cx: &mut App) {
+        for (idx, tab) in self.tabs.iter().enumerate() {
+            if let PreviewTab::Pdf {
+                webview: Some(wv), ..
+            } = tab
+            {
+                if idx == index {
+                    wv.webview().update(cx, |view, _| view.show());
+                } else {
+                    wv.webview().update(cx, |view, _| view.hide());
                 }
             }
         }
+        self.active_tab = index;
+    }
 
-        // Close preview if all tabs are gone (after the borrow is released)
-        if should_close_preview {
-            self.preview.panel = None;
-            cx.notify();
+    /// Take the tab at `index` out of the pane, which keeps showing the
+    /// same tab, or the one before if it was that one
+    pub fn take_tab(&mut self, index: usize) -> PreviewTab {
+        let tab = self.tabs.remove(index);
+        if self.active_tab >= self.tabs.len() {
+            self.active_tab = self.tabs.len().saturating_sub(1);
+        } else if index < self.active_tab {
+            self.active_tab -= 1;
+        }
+        tab
+    }
+}
+
+impl PreviewPanel {
+    /// Whether the panel is split into more than one pane
+    pub fn is_split(&self) -> bool {
+        self.panes.len() > 1
+    }
+
+    pub fn focused(&self) -> &PreviewPane {
+        &self.panes[self.focused_pane]
+    }
+
+    pub fn focused_mut(&mut self) -> &mut PreviewPane {
+        &mut self.panes[self.focused_pane]
+    }
+
+    /// The tabs of every pane
+    pub fn all_tabs(&self) -> impl Iterator<Item = &PreviewTab> {
+        self.panes.iter().flat_map(|pane| &pane.tabs)
+    }
+
+    pub fn all_tabs_mut(&mut self) -> impl Iterator<Item = &mut PreviewTab> {
+        self.panes.iter_mut().flat_map(|pane| &mut pane.tabs)
+    }
+
+    /// The pane and index of the first tab `matches` accepts
+    pub fn tab_position(&self, matches: impl Fn(&PreviewTab) -> bool) -> Option<(usize, usize)> {
+        self.panes.iter().enumerate().find_map(|(pane, p)| {
+            p.tabs
+                .iter()
+                .position(|tab| matches(tab))
+                .map(|index| (pane, index))
+        })
+    }
+
+    /// Add an empty pane beside `pane`, after it or before it if `before`.
+    /// Returns the new pane, or None if the panel has all the panes it can.
+    pub fn add_pane(&mut self, pane: usize, horizontal: bool, before: bool) -> Option<usize> {
+        if self.panes.len() >= MAX_PREVIEW_PANES {
+            return None;
+        }
+        let new_pane = self.panes.len();
+        if !self.layout.split(pane, new_pane, horizontal, before) {
+            return None;
+        }
+        self.panes.push(PreviewPane::default());
+        Some(new_pane)
+    }
+
+    /// Take `pane` out of the panel if it has no tabs left, unless it is
+    /// the only pane
+    pub fn remove_pane_if_empty(&mut self, pane: usize) {
+        if !self.is_split() || self.panes.get(pane).is_none_or(|p| !p.tabs.is_empty()) {
+            return;
+        }
+        self.panes.remove(pane);
+        self.layout.remove(pane);
+        if self.focused_pane > pane || self.focused_pane >= self.panes.len() {
+            self.focused_pane -= 1;
+        }
+    }
+
+    /// Where the panel is in the window, as (x, y, width, height)
+    pub(crate) fn bounds_in_window(&self, window: &Window) -> PaneBounds {
+        let bounds = window.bounds();
+        let window_width = f32::from(bounds.size.width);
+        let window_height = f32::from(bounds.size.height);
+        let main_splitter_size = 8.0; // Splitter between canvas and preview panel
+        match self.split {
+            SplitDirection::Vertical => {
+                let panel_x = DOCK_WIDTH
+                    + (window_width - DOCK_WIDTH) * (1.0 - self.size)
+                    + main_splitter_size;
+                let panel_width = (window_width - DOCK_WIDTH) * self.size - main_splitter_size;
+                let panel_height = window_height - HEADER_HEIGHT - FOOTER_HEIGHT;
+                (panel_x, HEADER_HEIGHT, panel_width, panel_height)
+            }
+            SplitDirection::Horizontal => {
+                let available_height = window_height - HEADER_HEIGHT - FOOTER_HEIGHT;
+                let panel_y =
+                    HEADER_HEIGHT + available_height * (1.0 - self.size) + main_splitter_size;
+                let panel_height = available_height * self.size - main_splitter_size;
+                (DOCK_WIDTH, panel_y, window_width - DOCK_WIDTH, panel_height)
+            }
         }
     }
+}
 
-    /// Toggle tab pinned state in the specified pane
-    pub fn toggle_tab_pinned_in_pane(&mut self, tab_index: usize, is_left_pane: bool, cx: &mut Context<Self>) {
+impl Humanboard {
+    /// Switch to a tab of the primary pane
+    pub fn switch_tab(&mut self, tab_index: usize, cx: &mut Context<Self>) {
         if let Some(ref mut preview) = self.preview.panel {
-            let tabs = if is_left_pane {
-                &mut preview.tabs
-            } else {
-                &mut preview.right_tabs
-            };
-            let active_tab = if is_left_pane {
-                &mut preview.active_tab
-            } else {
-                &mut preview.right_active_tab
+            let pane = &preview.panes[0];
+            if tab_index < pane.tabs.len() && tab_index != pane.active_tab {
+                // Record in the pane's history for back/forward navigation
+                preview.record_visit(0, cx);
+                preview.panes[0].show_tab(tab_index, cx);
+                cx.notify();
+            }
+        }
+    }
+
+    /// Switch to a tab of a pane and focus the pane
+    pub fn switch_tab_in_pane(&mut self, tab_index: usize, pane: usize, cx: &mut Context<Self>) {
+        if let Some(ref mut preview) = self.preview.panel {
+            let Some(target) = preview.panes.get(pane) else {
+                return;
             };
+            if tab_index < target.tabs.len() && tab_index != target.active_tab {
+                preview.record_visit(pane, cx);
+                preview.panes[pane].show_tab(tab_index, cx);
+                preview.focused_pane = pane;
+                cx.notify();
+            }
+        }
+    }
 
-            if let Some(tab) = tabs.get_mut(tab_index) {
-                let was_pinned = tab.is_pinned();
-                tab.toggle_pinned();
-
-                // If we just pinned, move to end of pinned section
-                if !was_pinned && tab.is_pinned() {
-                    let pinned_count = tabs.iter().filter(|t| t.is_pinned()).count();
-                    if tab_index + 1 < pinned_count {
-                        // Already in correct position
-                    } else if pinned_count > 0 && tab_index >= pinned_count {
-                        // Move to end of pinned section
-                        let tab = tabs.remove(tab_index);
-                        let insert_pos = pinned_count - 1;
-                        tabs.insert(insert_pos, tab);
-
-                        // Adjust active_tab
-                        if tab_index == *active_tab {
-                            *active_tab = insert_pos;
-                        } else if tab_index > *active_tab && insert_pos <= *active_tab {
-                            *active_tab += 1;
-                        } else if tab_index < *active_tab && insert_pos >= *active_tab {
-                            *active_tab -= 1;
-                        }
-                    }
-                }
+    /// Make a tab permanent (convert from preview tab) in the specified pane
+    pub fn make_tab_permanent_in_pane(
+        &mut self,
+        tab_index: usize,
+        pane: usize,
+        cx: &mut Context<Self>,
+    ) {
+        if let Some(ref mut preview) = self.preview.panel {
+            if let Some(tab) = preview
+                .panes
+                .get_mut(pane)
+                .and_then(|p| p.tabs.get_mut(tab_index))
+            {
+                tab.make_permanent();
                 cx.notify();
             }
         }
     }
 
+    /// Close a tab in the specified pane. A pane left without tabs is
+    /// closed, and so is the panel once it has none.
+    pub fn close_tab_in_pane(&mut self, tab_index: usize, pane: usize, cx: &mut Context<Self>) {
+        let Some(ref mut preview) = self.preview.panel else {
+            return;
+        };
+        let Some(target) = preview.panes.get_mut(pane) else {
+            return;
+        };
+        if tab_index >= target.tabs.len() || target.tabs[tab_index].is_pinned() {
+            return;
+        }
+
+        let mut closed_tab = target.take_tab(tab_index);
+        // Immediately cleanup (hide PDF webviews, etc.) before storing
+        closed_tab.cleanup(cx);
+        preview.closed_tabs.push(closed_tab);
+        if preview.closed_tabs.len() > 20 {
+            preview.closed_tabs.remove(0);
+        }
+
+        if preview.is_split() {
+            preview.remove_pane_if_empty(pane);
+        } else if preview.panes[0].tabs.is_empty() {
+            // No tabs left - close the preview panel entirely
+            self.preview.panel = None;
+        }
+        cx.notify();
+    }
+
+    /// Toggle tab pinned state in the specified pane. Pinned tabs stay
+    /// left-most, so the tab moves to the edge of the pinned tabs.
+    pub fn toggle_tab_pinned_in_pane(
+        &mut self,
+        tab_index: usize,
+        pane: usize,
+        cx: &mut Context<Self>,
+    ) {
+        if let Some(ref mut preview) = self.preview.panel {
+            if let Some(target) = preview.panes.get_mut(pane) {
+                if tab_index < target.tabs.len() {
+                    toggle_tab_pinned(&mut target.tabs, &mut target.active_tab, tab_index);
+                    cx.notify();
+                }
+            }
+        }
+    }
+
     /// Start tab drag in the specified pane (sets pending state, actual drag starts after threshold)
-    pub fn start_tab_drag_in_pane(&mut self, tab_index: usize, position: Point<Pixels>, is_left_pane: bool, cx: &mut Context<Self>) {
+    pub fn start_tab_drag_in_pane(
+        &mut self,
+        tab_index: usize,
+        position: Point<Pixels>,
+        pane: usize,
+        cx: &mut Context<Self>,
+    ) {
         if let Some(ref mut preview) = self.preview.panel {
             // Set the focused pane to the one being dragged from
-            preview.focused_pane = if is_left_pane { FocusedPane::Left } else { FocusedPane::Right };
+            preview.focused_pane = pane;
         }
         // Set pending drag - actual drag starts after mouse moves beyond threshold
-        self.preview.tab_drag_pending = Some((tab_index, position, is_left_pane));
+        self.preview.tab_drag_pending = Some((tab_index, position, pane));
         // Don't set dragging_tab yet - wait for threshold
         cx.notify();
     }
@@ -248,8 +522,9 @@ impl Humanboard {
 
     pub fn next_tab(&mut self, cx: &mut Context<Self>) {
         if let Some(ref mut preview) = self.preview.panel {
-            if !preview.tabs.is_empty() {
-                preview.active_tab = (preview.active_tab + 1) % preview.tabs.len();
+            let pane = preview.focused_mut();
+            if !pane.tabs.is_empty() {
+                pane.active_tab = (pane.active_tab + 1) % pane.tabs.len();
                 cx.notify();
             }
         }
@@ -257,11 +532,12 @@ impl Humanboard {
 
     pub fn prev_tab(&mut self, cx: &mut Context<Self>) {
         if let Some(ref mut preview) = self.preview.panel {
-            if !preview.tabs.is_empty() {
-                preview.active_tab = if preview.active_tab == 0 {
-                    preview.tabs.len() - 1
+            let pane = preview.focused_mut();
+            if !pane.tabs.is_empty() {
+                pane.active_tab = if pane.active_tab == 0 {
+                    pane.tabs.len() - 1
                 } else {
-                    preview.active_tab - 1
+                    pane.active_tab - 1
                 };
                 cx.notify();
             }
@@ -270,11 +546,7 @@ impl Humanboard {
 
     pub fn close_current_tab(&mut self, cx: &mut Context<Self>) {
         if let Some(ref preview) = self.preview.panel {
-            let active = if preview.is_pane_split && preview.focused_pane == FocusedPane::Right {
-                preview.right_active_tab
-            } else {
-                preview.active_tab
-            };
+            let active = preview.focused().active_tab;
             self.close_tab(active, cx);
         }
     }
@@ -289,49 +561,43 @@ impl Humanboard {
         }
     }
 
-    /// Split the preview panel into two panes
+    /// Split the focused pane, putting an empty pane beside it. Splits
+    /// alternate between side by side and stacked, so splitting a pane
+    /// of a side-by-side split stacks the new pane below it.
     pub fn split_pane(&mut self, cx: &mut Context<Self>) {
         if let Some(ref mut preview) = self.preview.panel {
-            if !preview.is_pane_split {
-                preview.is_pane_split = true;
-                preview.pane_ratio = 0.5;
-                // Focus stays on left pane
+            let pane = preview.focused_pane;
+            let horizontal = preview.layout.parent_horizontal(pane) == Some(false);
+            // Focus stays on the pane that was split
+            if preview.add_pane(pane, horizontal, false).is_some() {
                 cx.notify();
             }
         }
     }
 
-    /// Close the split and merge into single pane
+    /// Close the split and merge every pane into the primary one
     pub fn close_split_pane(&mut self, cx: &mut Context<Self>) {
         if let Some(ref mut preview) = self.preview.panel {
-            if preview.is_pane_split {
-                // Move right pane tabs to left pane
-                let mut right_tabs: Vec<PreviewTab> = preview.right_tabs.drain(..).collect();
-                for tab in &mut right_tabs {
+            if preview.is_split() {
+                let merged: Vec<PreviewPane> = preview.panes.drain(1..).collect();
+                let primary = &mut preview.panes[0];
+                for mut tab in merged.into_iter().flat_map(|pane| pane.tabs) {
                     // Hide PDF webviews before move - they will be recreated in new position
-                    if let PreviewTab::Pdf { webview, .. } = tab {
-                        if let Some(wv) = webview.take() {
-                            wv.hide(cx);
-                        }
-                    }
-                }
-                for tab in right_tabs {
-                    preview.tabs.push(tab);
+                    detach_webview(&mut tab, cx);
+                    insert_tab(&mut primary.tabs, &mut primary.active_tab, tab);
                 }
-                preview.right_active_tab = 0;
-                preview.right_back_stack.clear();
-                preview.right_forward_stack.clear();
-                preview.is_pane_split = false;
-                preview.focused_pane = FocusedPane::Left;
+                preview.layout = PaneTree::default();
+                preview.focused_pane = 0;
                 cx.notify();
             }
         }
     }
 
-    /// Toggle split pane on/off
+    /// Split the focused pane, or merge the panes back into one once the
+    /// panel has all the panes it can
     pub fn toggle_pane_split(&mut self, cx: &mut Context<Self>) {
         if let Some(ref preview) = self.preview.panel {
-            if preview.is_pane_split {
+            if preview.panes.len() >= MAX_PREVIEW_PANES {
                 self.close_split_pane(cx);
             } else {
                 self.split_pane(cx);
@@ -339,86 +605,96 @@ impl Humanboard {
         }
     }
 
-    /// Focus the left pane
-    pub fn focus_left_pane(&mut self, cx: &mut Context<Self>) {
+    /// Focus a pane
+    pub fn focus_pane(&mut self, pane: usize, cx: &mut Context<Self>) {
         if let Some(ref mut preview) = self.preview.panel {
-            preview.focused_pane = FocusedPane::Left;
-            cx.notify();
-        }
-    }
-
-    /// Focus the right pane
-    pub fn focus_right_pane(&mut self, cx: &mut Context<Self>) {
-        if let Some(ref mut preview) = self.preview.panel {
-            if preview.is_pane_split {
-                preview.focused_pane = FocusedPane::Right;
+            if pane < preview.panes.len() && preview.focused_pane != pane {
+                preview.focused_pane = pane;
                 cx.notify();
             }
         }
     }
 
-    /// Move current tab to the other pane
+    /// Move the focused pane's tab to the next pane, splitting the panel
+    /// if it has a single pane
     pub fn move_tab_to_other_pane(&mut self, cx: &mut Context<Self>) {
-        if let Some(ref mut preview) = self.preview.panel {
-            if !preview.is_pane_split {
-                // Auto-split when moving tab
-                preview.is_pane_split = true;
-                preview.pane_ratio = 0.5;
-            }
-
-            match preview.focused_pane {
-                FocusedPane::Left => {
-                    if !preview.tabs.is_empty() && preview.active_tab < preview.tabs.len() {
-                        let mut tab = preview.tabs.remove(preview.active_tab);
-                        // Hide PDF webview before move - it will be recreated in new pane
-                        if let PreviewTab::Pdf { webview, .. } = &mut tab {
-                            if let Some(wv) = webview.take() {
-                                wv.hide(cx);
-                            }
-                        }
-                        preview.right_tabs.push(tab);
-                        preview.right_active_tab = preview.right_tabs.len() - 1;
-                        // Adjust left active tab
-                        if preview.active_tab >= preview.tabs.len() && !preview.tabs.is_empty() {
-                            preview.active_tab = preview.tabs.len() - 1;
-                        }
-                        // Focus the right pane where we moved the tab
-                        preview.focused_pane = FocusedPane::Right;
-                    }
-                }
-                FocusedPane::Right => {
-                    if !preview.right_tabs.is_empty()
-                        && preview.right_active_tab < preview.right_tabs.len()
-                    {
-                        let mut tab = preview.right_tabs.remove(preview.right_active_tab);
-                        // Hide PDF webview before move - it will be recreated in new pane
-                        if let PreviewTab::Pdf { webview, .. } = &mut tab {
-                            if let Some(wv) = webview.take() {
-                                wv.hide(cx);
-                            }
-                        }
-                        preview.tabs.push(tab);
-                        preview.active_tab = preview.tabs.len() - 1;
-                        // Adjust right active tab
-                        if preview.right_active_tab >= preview.right_tabs.len()
-                            && !preview.right_tabs.is_empty()
-                        {
-                            preview.right_active_tab = preview.right_tabs.len() - 1;
-                        }
-                        // Focus the left pane where we moved the tab
-                        preview.focused_pane = FocusedPane::Left;
-                    }
-                }
+        let Some(ref mut preview) = self.preview.panel else {
+            return;
+        };
+        let from = preview.focused_pane;
+        let tab_index = preview.panes[from].active_tab;
+        // A single pane's only tab has nowhere to go
+        if tab_index >= preview.panes[from].tabs.len()
+            || (!preview.is_split() && preview.panes[from].tabs.len() < 2)
+        {
+            return;
+        }
+        let to = if preview.is_split() {
+            let order = preview.layout.panes();
+            let position = order.iter().position(|&p| p == from).unwrap_or(0);
+            order[(position + 1) % order.len()]
+        } else {
+            // Auto-split when moving tab
+            match preview.add_pane(from, false, false) {
+                Some(pane) => pane,
+                None => return,
             }
+        };
+
+        let mut tab = preview.panes[from].take_tab(tab_index);
+        // Hide PDF webview before move - it will be recreated in new pane
+        detach_webview(&mut tab, cx);
+        let target = &mut preview.panes[to];
+        target.active_tab = insert_tab(&mut target.tabs, &mut target.active_tab, tab);
+        // Focus the pane where we moved the tab
+        preview.focused_pane = to;
+        preview.remove_pane_if_empty(from);
+        cx.notify();
+    }
 
-            // If right pane is empty after move, close split
-            if preview.right_tabs.is_empty() {
-                preview.is_pane_split = false;
-                preview.focused_pane = FocusedPane::Left;
-            }
+    /// Start dragging the divider of the split at `path`
+    pub fn start_pane_split_drag(
+        &mut self,
+        path: Vec<bool>,
+        position: Point<Pixels>,
+        cx: &mut Context<Self>,
+    ) {
+        let horizontal = matches!(
+            self.preview
+                .panel
+                .as_ref()
+                .and_then(|p| p.layout.part(&path)),
+            Some(PaneTree::Split {
+                horizontal: true,
+                ..
+            })
+        );
+        self.preview.dragged_pane_split = path;
+        self.canvas
+            .input_state
+            .start_pane_splitter_drag(position, horizontal);
+        cx.notify();
+    }
 
-            cx.notify();
-        }
+    /// Move the divider being dragged to `position`
+    pub(crate) fn drag_pane_split(&mut self, position: Point<Pixels>, window: &Window) {
+        let Some(ref mut preview) = self.preview.panel else {
+            return;
+        };
+        let path = &self.preview.dragged_pane_split;
+        let panel = preview.bounds_in_window(window);
+        let Some((x, y, width, height)) =
+            preview.layout.split_bounds(path, panel, PANE_SPLITTER_SIZE)
+        else {
+            return;
+        };
+        let ratio = match preview.layout.part(path) {
+            Some(PaneTree::Split {
+                horizontal: true, ..
+            }) => (f32::from(position.y) - y) / height,
+            _ => (f32::from(position.x) - x) / width,
+        };
+        preview.layout.set_ratio(path, ratio);
     }
 
     pub fn next_page(&mut self, _cx: &mut Context<Self>) {
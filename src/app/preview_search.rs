@@ -1,8 +1,19 @@
 //! Preview search functionality - find in file.
+//!
+//! Markdown and code tabs are searched line by line. PDF tabs are searched
+//! in their text from the PDF text index, with a page standing in for a
+//! line, and the viewer jumps to the page of the current match.
+//!
+//! Tabs being edited also get a replacement input, to replace the current
+//! match or every match in their editor. Each replace is a single edit, so
+//! it is undone in one step.
 
 use super::{Humanboard, PreviewTab};
+use crate::find_replace::{find_matches, line_and_column, replace_all_edit, utf16_range};
+use crate::notifications::Toast;
+use crate::pdf::PDF_TEXT_INDEX;
 use gpui::*;
-use gpui_component::input::InputState;
+use gpui_component::input::{InputEvent, InputState};
 
 impl Humanboard {
     /// Toggle the preview search bar
@@ -49,7 +60,20 @@ impl Humanboard {
         )
         .detach();
 
+        let replace = cx.new(|cx| InputState::new(window, cx).placeholder("Replace with..."));
+        cx.subscribe_in(
+            &replace,
+            window,
+            |this, _, event: &InputEvent, window, cx| {
+                if let InputEvent::PressEnter { .. } = event {
+                    this.replace_search_match(window, cx);
+                }
+            },
+        )
+        .detach();
+
         self.preview.search = Some(input);
+        self.preview.replace = Some(replace);
         self.preview.search_query.clear();
         self.preview.search_matches.clear();
         self.preview.search_current = 0;
@@ -59,6 +83,7 @@ impl Humanboard {
     /// Close the preview search bar
     pub fn close_preview_search(&mut self, cx: &mut Context<Self>) {
         self.preview.search = None;
+        self.preview.replace = None;
         self.preview.search_query.clear();
         self.preview.search_matches.clear();
         self.preview.search_current = 0;
@@ -76,41 +101,151 @@ impl Humanboard {
             return;
         }
 
-        // Get content from active tab
-        let content = if let Some(ref preview) = self.preview.panel {
-            if let Some(tab) = preview.tabs.get(preview.active_tab) {
-                match tab {
-                    PreviewTab::Markdown { content, .. } => Some(content.clone()),
-                    PreviewTab::Code { content, .. } => Some(content.clone()),
-                    _ => None,
-                }
-            } else {
-                None
+        // PDFs are searched in their extracted text, a page per line
+        let pdf_path = self
+            .preview
+            .panel
+            .as_ref()
+            .and_then(|preview| preview.panes[0].active())
+            .and_then(|tab| match tab {
+                PreviewTab::Pdf { path, .. } => Some(path.clone()),
+                _ => None,
+            });
+        if let Some(path) = pdf_path {
+            match PDF_TEXT_INDEX.find_in_pdf(&path, query) {
+                Some(matches) => self.preview.search_matches = matches,
+                // Not extracted yet; matches show up as the query changes
+                None => self.index_preview_pdf(&path),
             }
-        } else {
-            None
-        };
+            self.show_pdf_search_match(cx);
+            cx.notify();
+            return;
+        }
 
-        if let Some(content) = content {
-            let query_lower = query.to_lowercase();
-            for (line_idx, line) in content.lines().enumerate() {
-                let line_lower = line.to_lowercase();
-                let mut start = 0;
-                while let Some(col) = line_lower[start..].find(&query_lower) {
-                    self.preview.search_matches.push((line_idx, start + col));
-                    start += col + 1;
-                }
+        // Tabs being edited are searched as they are in the editor
+        let content = self.active_search_tab().and_then(|tab| match tab {
+            PreviewTab::Markdown {
+                editing: true,
+                editor: Some(editor),
+                ..
             }
+            | PreviewTab::Code {
+                editor: Some(editor),
+                ..
+            } => Some(editor.read(cx).text().to_string()),
+            PreviewTab::Markdown { content, .. } | PreviewTab::Code { content, .. } => {
+                Some(content.clone())
+            }
+            _ => None,
+        });
+
+        if let Some(content) = content {
+            self.preview.search_matches = find_matches(&content, query)
+                .into_iter()
+                .map(|range| line_and_column(&content, range.start))
+                .collect();
         }
 
         cx.notify();
     }
 
+    /// Replacement input, when the searched tab is being edited
+    pub fn preview_replace_input(&self) -> Option<&Entity<InputState>> {
+        self.active_search_editor()
+            .and(self.preview.replace.as_ref())
+    }
+
+    /// Replace the current match in the searched tab's editor, and move on
+    /// to the match after the replacement
+    pub fn replace_search_match(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(editor) = self.active_search_editor().cloned() else {
+            return;
+        };
+        let query = self.preview.search_query.clone();
+        let replacement = self.replacement_text(cx);
+        let text = editor.read(cx).text().to_string();
+        let Some(range) = find_matches(&text, &query)
+            .get(self.preview.search_current)
+            .cloned()
+        else {
+            return;
+        };
+        let next_from = range.start + replacement.len();
+        let range_utf16 = utf16_range(&text, range);
+        editor.update(cx, |state, cx| {
+            state.replace_text_in_range(Some(range_utf16), &replacement, window, cx);
+        });
+
+        self.update_preview_search(&query, cx);
+        let text = editor.read(cx).text().to_string();
+        self.preview.search_current = find_matches(&text, &query)
+            .iter()
+            .position(|range| range.start >= next_from)
+            .unwrap_or(0);
+        cx.notify();
+    }
+
+    /// Replace every match in the searched tab's editor
+    pub fn replace_all_search_matches(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(editor) = self.active_search_editor().cloned() else {
+            return;
+        };
+        let query = self.preview.search_query.clone();
+        let replacement = self.replacement_text(cx);
+        let text = editor.read(cx).text().to_string();
+        let count = find_matches(&text, &query).len();
+        let Some((span, replaced)) = replace_all_edit(&text, &query, &replacement) else {
+            return;
+        };
+        let span_utf16 = utf16_range(&text, span);
+        editor.update(cx, |state, cx| {
+            state.replace_text_in_range(Some(span_utf16), &replaced, window, cx);
+        });
+
+        self.update_preview_search(&query, cx);
+        self.show_toast(Toast::success(if count == 1 {
+            "Replaced 1 match".to_string()
+        } else {
+            format!("Replaced {} matches", count)
+        }));
+    }
+
+    /// The tab the find bar searches
+    fn active_search_tab(&self) -> Option<&PreviewTab> {
+        let preview = self.preview.panel.as_ref()?;
+        preview.panes[0].active()
+    }
+
+    /// Editor of the searched tab, when it is being edited
+    fn active_search_editor(&self) -> Option<&Entity<InputState>> {
+        match self.active_search_tab()? {
+            PreviewTab::Markdown {
+                editing: true,
+                editor: Some(editor),
+                ..
+            }
+            | PreviewTab::Code {
+                editor: Some(editor),
+                ..
+            } => Some(editor),
+            _ => None,
+        }
+    }
+
+    fn replacement_text(&self, cx: &App) -> String {
+        self.preview
+            .replace
+            .as_ref()
+            .map(|input| input.read(cx).text().to_string())
+            .unwrap_or_default()
+    }
+
     /// Go to next search match
     pub fn next_search_match(&mut self, cx: &mut Context<Self>) {
         if !self.preview.search_matches.is_empty() {
             self.preview.search_current =
                 (self.preview.search_current + 1) % self.preview.search_matches.len();
+            self.show_pdf_search_match(cx);
             cx.notify();
         }
     }
@@ -123,7 +258,35 @@ impl Humanboard {
             } else {
                 self.preview.search_current - 1
             };
+            self.show_pdf_search_match(cx);
             cx.notify();
         }
     }
+
+    /// Scroll the active PDF tab to the page of the current match
+    fn show_pdf_search_match(&mut self, cx: &mut Context<Self>) {
+        let Some(&(page, _)) = self.preview.search_matches.get(self.preview.search_current) else {
+            return;
+        };
+        if let Some(ref mut preview) = self.preview.panel {
+            let shown = match preview.panes[0].active() {
+                Some(PreviewTab::Pdf {
+                    webview: Some(webview),
+                    ..
+                }) => webview.page(),
+                _ => return,
+            };
+            // Jumps to another page are remembered for going back
+            if shown != page {
+                preview.record_visit(0, cx);
+            }
+            if let Some(PreviewTab::Pdf {
+                webview: Some(webview),
+                ..
+            }) = preview.panes[0].active()
+            {
+                webview.show_page(page, cx);
+            }
+        }
+    }
 }
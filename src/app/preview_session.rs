@@ -0,0 +1,174 @@
+//! Preview session methods - saving the preview panel with the board and
+//! reopening it when the board is opened again
+
+use crate::app::{Humanboard, PreviewPane, PreviewPanel, PreviewTab, SplitDirection, TabMeta};
+use crate::preview_session::{PreviewSession, SessionPane, SessionTab, SessionTabSource};
+use crate::text_diff::DiffBase;
+use gpui::*;
+use std::collections::HashSet;
+
+impl Humanboard {
+    /// The preview panel as it is now, or None if it is closed
+    pub(crate) fn capture_preview_session(&self) -> Option<PreviewSession> {
+        let preview = self.preview.panel.as_ref()?;
+        let panes = preview
+            .panes
+            .iter()
+            .enumerate()
+            .map(|(index, pane)| SessionPane {
+                tabs: self.session_tabs(&pane.tabs),
+                active_tab: pane.active_tab,
+                focused: index == preview.focused_pane,
+            })
+            .collect();
+        Some(PreviewSession::from_panes(
+            panes,
+            preview.layout.clone(),
+            preview.split == SplitDirection::Horizontal,
+            preview.size,
+        ))
+    }
+
+    fn session_tabs(&self, tabs: &[PreviewTab]) -> Vec<SessionTab> {
+        tabs.iter()
+            .map(|tab| {
+                let (source, page) = match tab {
+                    PreviewTab::Pdf { path, webview, .. } => {
+                        // Viewers not created yet are still waiting for their page
+                        let page = match webview {
+                            Some(webview) => Some(webview.page()),
+                            None => self.preview.pending_pdf_pages.get(path).copied(),
+                        };
+                        (SessionTabSource::File(path.clone()), page.filter(|&p| p > 0))
+                    }
+                    PreviewTab::Markdown { path, .. }
+                    | PreviewTab::Code { path, .. }
+                    | PreviewTab::Image { path, .. }
+                    | PreviewTab::Data { path, .. } => {
+                        (SessionTabSource::File(path.clone()), None)
+                    }
+                    PreviewTab::Table {
+                        data_source_id,
+                        name,
+                        ..
+                    } => (
+                        SessionTabSource::Table {
+                            data_source_id: *data_source_id,
+                            name: name.clone(),
+                        },
+                        None,
+                    ),
+                    PreviewTab::Diff { path, base, .. } => (
+                        SessionTabSource::Diff {
+                            path: path.clone(),
+                            base: base.clone(),
+                        },
+                        None,
+                    ),
+                };
+                SessionTab {
+                    source,
+                    pinned: tab.is_pinned(),
+                    preview: tab.is_preview(),
+                    page,
+                }
+            })
+            .collect()
+    }
+
+    /// Store the preview panel on the board so it is saved with it
+    pub(crate) fn persist_preview_session(&mut self) {
+        // Until the saved session is restored, it is still the one to keep
+        if self.preview.restore_session {
+            return;
+        }
+        let session = self.capture_preview_session();
+        if let Some(ref mut board) = self.canvas.board {
+            board.set_preview_session(session);
+        }
+    }
+
+    /// Reopen the preview panel the board was left with, once after the
+    /// board is opened. Tabs whose file or table is gone are left out.
+    pub(crate) fn restore_preview_session(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        if !std::mem::take(&mut self.preview.restore_session) || self.preview.panel.is_some() {
+            return;
+        }
+        let Some(board) = self.canvas.board.as_ref() else {
+            return;
+        };
+        let Some(session) = board.preview_session().cloned() else {
+            return;
+        };
+        let data_sources: HashSet<u64> = board.data_sources.keys().copied().collect();
+        let Some(session) = session.retain(|tab| match &tab.source {
+            SessionTabSource::File(path) => path.exists(),
+            SessionTabSource::Table { data_source_id, .. } => data_sources.contains(data_source_id),
+            SessionTabSource::Diff { path, base } => {
+                path.exists() && !matches!(base, DiffBase::File(other) if !other.exists())
+            }
+        }) else {
+            return;
+        };
+
+        let split = if session.below_canvas {
+            SplitDirection::Horizontal
+        } else {
+            SplitDirection::Vertical
+        };
+        let mut panel = PreviewPanel::new(split, session.size);
+        let panes = session.panes();
+        panel.panes = panes
+            .iter()
+            .map(|pane| PreviewPane {
+                tabs: pane
+                    .tabs
+                    .iter()
+                    .map(|tab| self.restored_tab(tab, window, cx))
+                    .collect(),
+                active_tab: pane.active_tab,
+                ..Default::default()
+            })
+            .collect();
+        panel.layout = session.layout();
+        panel.focused_pane = panes.iter().position(|pane| pane.focused).unwrap_or(0);
+        self.preview.panel = Some(panel);
+        cx.notify();
+    }
+
+    fn restored_tab(
+        &mut self,
+        tab: &SessionTab,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> PreviewTab {
+        let meta = TabMeta {
+            is_preview: tab.preview,
+            is_pinned: tab.pinned,
+            zoom: None,
+        };
+        match &tab.source {
+            SessionTabSource::File(path) => {
+                // The viewer opens at the page once it is created
+                if let Some(page) = tab.page {
+                    self.preview.pending_pdf_pages.insert(path.clone(), page);
+                }
+                self.new_preview_tab(path, meta, window, cx)
+            }
+            SessionTabSource::Table {
+                data_source_id,
+                name,
+            } => PreviewTab::Table {
+                data_source_id: *data_source_id,
+                name: name.clone(),
+                table_state: None, // Created when rendering
+                editing: false,
+                view: None,
+                meta,
+            },
+            SessionTabSource::Diff { path, base } => {
+                self.new_diff_tab(path.clone(), base.clone(), meta)
+            }
+        }
+    }
+}
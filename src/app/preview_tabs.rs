@@ -1,6 +1,12 @@
-//! Tab management - close, reopen, navigation history, and drag/drop reordering.
-
-use super::{FocusedPane, Humanboard, PreviewTab, SplitDropZone};
+//! Tab management - close, pin, reopen, and drag/drop reordering.
+//!
+//! Pinned tabs stay left-most in their pane and can't be closed, so "Close
+//! Others" and "Close All" leave them open.
+
+use super::{Humanboard, MAX_PREVIEW_PANES, PreviewPanel, PreviewTab, SplitDropZone};
+use crate::preview::{
+    canvas_edge_drop_zone, closable_tabs, in_preview_area, insert_tab, insert_tab_at, move_tab,
+};
 use gpui::*;
 
 impl Humanboard {
@@ -13,151 +19,68 @@ impl Humanboard {
         cx.notify();
     }
 
+    /// Close a tab of the focused pane
     pub fn close_tab(&mut self, tab_index: usize, cx: &mut Context<Self>) {
-        if let Some(ref mut preview) = self.preview.panel {
-            // Close tab in the focused pane
-            if preview.is_pane_split && preview.focused_pane == FocusedPane::Right {
-                // Closing from right pane
-                if tab_index < preview.right_tabs.len() {
-                    if preview.right_tabs[tab_index].is_pinned() {
-                        return;
-                    }
-
-                    let mut closed_tab = preview.right_tabs.remove(tab_index);
-                    // Immediately cleanup (hide PDF webviews, etc.) before storing
-                    closed_tab.cleanup(cx);
-                    preview.closed_tabs.push(closed_tab);
-                    if preview.closed_tabs.len() > 20 {
-                        preview.closed_tabs.remove(0);
-                    }
-
-                    if preview.right_tabs.is_empty() {
-                        // Right pane empty, close the split
-                        preview.is_pane_split = false;
-                        preview.focused_pane = FocusedPane::Left;
-                    } else {
-                        if preview.right_active_tab >= preview.right_tabs.len() {
-                            preview.right_active_tab = preview.right_tabs.len() - 1;
-                        } else if tab_index < preview.right_active_tab {
-                            preview.right_active_tab -= 1;
-                        }
-                    }
-                    cx.notify();
-                }
-            } else {
-                // Closing from left pane
-                if tab_index < preview.tabs.len() {
-                    if preview.tabs[tab_index].is_pinned() {
-                        return;
-                    }
-
-                    let mut closed_tab = preview.tabs.remove(tab_index);
-                    // Immediately cleanup (hide PDF webviews, etc.) before storing
-                    closed_tab.cleanup(cx);
-                    preview.closed_tabs.push(closed_tab);
-                    if preview.closed_tabs.len() > 20 {
-                        preview.closed_tabs.remove(0);
-                    }
-
-                    if preview.tabs.is_empty() {
-                        if preview.is_pane_split && !preview.right_tabs.is_empty() {
-                            // Left pane empty but right has tabs - move right to left
-                            preview.tabs = preview.right_tabs.drain(..).collect();
-                            preview.active_tab = preview.right_active_tab;
-                            preview.is_pane_split = false;
-                            preview.focused_pane = FocusedPane::Left;
-                        } else {
-                            // No tabs anywhere, clean up and close preview panel
-                            preview.cleanup(cx);
-                            self.preview.panel = None;
-                        }
-                    } else {
-                        if preview.active_tab >= preview.tabs.len() {
-                            preview.active_tab = preview.tabs.len() - 1;
-                        } else if tab_index < preview.active_tab {
-                            preview.active_tab -= 1;
-                        }
-                    }
-                    cx.notify();
-                }
-            }
+        if let Some(ref preview) = self.preview.panel {
+            let pane = preview.focused_pane;
+            self.close_tab_in_pane(tab_index, pane, cx);
         }
     }
 
     /// Convert a preview tab to a permanent tab
     pub fn make_tab_permanent(&mut self, tab_index: usize, cx: &mut Context<Self>) {
-        if let Some(ref mut preview) = self.preview.panel {
-            if let Some(tab) = preview.tabs.get_mut(tab_index) {
-                tab.make_permanent();
-                cx.notify();
-            }
-        }
+        self.make_tab_permanent_in_pane(tab_index, 0, cx);
     }
 
-    /// Toggle the pinned state of a tab
+    /// Toggle the pinned state of a tab in the primary pane
     pub fn toggle_tab_pinned(&mut self, tab_index: usize, cx: &mut Context<Self>) {
-        if let Some(ref mut preview) = self.preview.panel {
-            if let Some(tab) = preview.tabs.get_mut(tab_index) {
-                let was_pinned = tab.is_pinned();
-                tab.toggle_pinned();
-
-                // Reorder tabs: pinned tabs stay at the left
-                if !was_pinned && tab.is_pinned() {
-                    // Tab was just pinned - move it to after the last pinned tab
-                    let pinned_count = preview.tabs.iter().filter(|t| t.is_pinned()).count();
-                    if tab_index >= pinned_count {
-                        let tab = preview.tabs.remove(tab_index);
-                        preview.tabs.insert(pinned_count - 1, tab);
-                        // Adjust active tab index
-                        if preview.active_tab == tab_index {
-                            preview.active_tab = pinned_count - 1;
-                        } else if preview.active_tab < tab_index
-                            && preview.active_tab >= pinned_count - 1
-                        {
-                            preview.active_tab += 1;
-                        }
-                    }
-                }
-                cx.notify();
-            }
-        }
+        self.toggle_tab_pinned_in_pane(tab_index, 0, cx);
     }
 
-    /// Reopen the most recently closed tab
-    pub fn reopen_closed_tab(&mut self, cx: &mut Context<Self>) {
-        if let Some(ref mut preview) = self.preview.panel {
-            if let Some(tab) = preview.closed_tabs.pop() {
-                preview.tabs.push(tab);
-                preview.active_tab = preview.tabs.len() - 1;
-                cx.notify();
-            }
+    /// Close every unpinned tab of a pane except `keep`, which becomes the
+    /// pane's active tab
+    pub fn close_other_tabs_in_pane(&mut self, keep: usize, pane: usize, cx: &mut Context<Self>) {
+        let Some(target) = self
+            .preview
+            .panel
+            .as_mut()
+            .and_then(|preview| preview.panes.get_mut(pane))
+        else {
+            return;
+        };
+        if keep >= target.tabs.len() {
+            return;
+        }
+        target.active_tab = keep;
+        for index in closable_tabs(&target.tabs, Some(keep)) {
+            self.close_tab_in_pane(index, pane, cx);
         }
     }
 
-    /// Navigate back in tab history
-    pub fn go_back(&mut self, cx: &mut Context<Self>) {
-        if let Some(ref mut preview) = self.preview.panel {
-            if let Some(prev_index) = preview.back_stack.pop() {
-                if prev_index < preview.tabs.len() {
-                    // Push current to forward stack
-                    preview.forward_stack.push(preview.active_tab);
-                    preview.active_tab = prev_index;
-                    cx.notify();
-                }
-            }
+    /// Close every unpinned tab of a pane. A pane with no pinned tabs is
+    /// closed like when its last tab is.
+    pub fn close_all_tabs_in_pane(&mut self, pane: usize, cx: &mut Context<Self>) {
+        let Some(target) = self
+            .preview
+            .panel
+            .as_ref()
+            .and_then(|preview| preview.panes.get(pane))
+        else {
+            return;
+        };
+        for index in closable_tabs(&target.tabs, None) {
+            self.close_tab_in_pane(index, pane, cx);
         }
     }
 
-    /// Navigate forward in tab history
-    pub fn go_forward(&mut self, cx: &mut Context<Self>) {
+    /// Reopen the most recently closed tab in the focused pane
+    pub fn reopen_closed_tab(&mut self, cx: &mut Context<Self>) {
         if let Some(ref mut preview) = self.preview.panel {
-            if let Some(next_index) = preview.forward_stack.pop() {
-                if next_index < preview.tabs.len() {
-                    // Push current to back stack
-                    preview.back_stack.push(preview.active_tab);
-                    preview.active_tab = next_index;
-                    cx.notify();
-                }
+            if let Some(tab) = preview.closed_tabs.pop() {
+                let pane = preview.focused_mut();
+                pane.tabs.push(tab);
+                pane.active_tab = pane.tabs.len() - 1;
+                cx.notify();
             }
         }
     }
@@ -174,6 +97,8 @@ impl Humanboard {
         self.preview.tab_drag_target = Some(tab_index);
         self.preview.tab_drag_split_zone = None;
         self.preview.tab_drag_position = Some(position);
+        self.preview.tab_drag_from_pane = 0;
+        self.preview.tab_drag_target_pane = 0;
         cx.notify();
     }
 
@@ -181,7 +106,7 @@ impl Humanboard {
     /// If there's a pending drag, check if threshold is reached and promote to actual drag
     pub fn update_tab_drag_position(&mut self, position: Point<Pixels>, cx: &mut Context<Self>) {
         // Check if we should promote pending drag to actual drag
-        if let Some((tab_index, start_pos, _is_left_pane)) = self.preview.tab_drag_pending {
+        if let Some((tab_index, start_pos, pane)) = self.preview.tab_drag_pending {
             let dx = (f32::from(position.x) - f32::from(start_pos.x)).abs();
             let dy = (f32::from(position.y) - f32::from(start_pos.y)).abs();
             let distance = (dx * dx + dy * dy).sqrt();
@@ -193,6 +118,8 @@ impl Humanboard {
                 self.preview.tab_drag_target = Some(tab_index);
                 self.preview.tab_drag_split_zone = None;
                 self.preview.tab_drag_position = Some(position);
+                self.preview.tab_drag_from_pane = pane;
+                self.preview.tab_drag_target_pane = pane;
                 self.preview.tab_drag_pending = None;
                 tracing::debug!("Promoted pending drag to actual drag: index={}", tab_index);
                 cx.notify();
@@ -207,25 +134,27 @@ impl Humanboard {
         }
     }
 
-    /// Update the drag target position as mouse moves over tabs
-    pub fn update_tab_drag_target(&mut self, target_index: usize, cx: &mut Context<Self>) {
+    /// Update the drag target position as mouse moves over tabs of any pane
+    pub fn update_tab_drag_target(&mut self, target_index: usize, pane: usize, cx: &mut Context<Self>) {
         if self.preview.dragging_tab.is_some() {
             // Clear split zone when hovering over tabs
             if self.preview.tab_drag_split_zone.is_some() {
                 self.preview.tab_drag_split_zone = None;
             }
-            if self.preview.tab_drag_target != Some(target_index) {
-                self.preview.tab_drag_target = Some(target_index);
-            }
+            self.preview.tab_drag_target = Some(target_index);
+            self.preview.tab_drag_target_pane = pane;
             cx.notify();
         }
     }
 
-    /// Set the split drop zone when dragging to edges
-    pub fn set_tab_drag_split_zone(&mut self, zone: Option<SplitDropZone>, cx: &mut Context<Self>) {
-        if self.preview.dragging_tab.is_some() && self.preview.tab_drag_split_zone != zone {
-            tracing::debug!("set_tab_drag_split_zone: {:?}", zone);
+    /// Set the split drop zone of a pane when dragging to its edges
+    pub fn set_tab_drag_split_zone(&mut self, pane: usize, zone: Option<SplitDropZone>, cx: &mut Context<Self>) {
+        if self.preview.dragging_tab.is_some()
+            && (self.preview.tab_drag_split_zone != zone || self.preview.tab_drag_target_pane != pane)
+        {
+            tracing::debug!("set_tab_drag_split_zone: {} {:?}", pane, zone);
             self.preview.tab_drag_split_zone = zone;
+            self.preview.tab_drag_target_pane = pane;
             // Clear tab target when in split zone
             if zone.is_some() {
                 self.preview.tab_drag_target = None;
@@ -234,7 +163,55 @@ impl Humanboard {
         }
     }
 
-    /// Finish tab drag - either reorder or create split
+    /// Follow a tab dragged over the middle of a pane, where dropping it
+    /// moves it to the end of that pane's tabs
+    pub fn drag_tab_over_pane(&mut self, pane: usize, cx: &mut Context<Self>) {
+        let Some(from) = self.preview.dragging_tab else {
+            return;
+        };
+        let Some(ref preview) = self.preview.panel else {
+            return;
+        };
+        let target = if pane == self.preview.tab_drag_from_pane {
+            from
+        } else {
+            preview.panes.get(pane).map_or(0, |p| p.tabs.len())
+        };
+        if self.preview.tab_drag_split_zone.is_some()
+            || self.preview.tab_drag_target != Some(target)
+            || self.preview.tab_drag_target_pane != pane
+        {
+            self.preview.tab_drag_split_zone = None;
+            self.preview.tab_drag_target = Some(target);
+            self.preview.tab_drag_target_pane = pane;
+            cx.notify();
+        }
+    }
+
+    /// Follow a tab dragged out over the canvas. Dropping it on the edge of
+    /// the canvas beside the panel splits the panel, anywhere else on the
+    /// canvas does nothing. Over the panel its own drop targets take over.
+    pub fn update_tab_drag_over_canvas(&mut self, position: Point<Pixels>, window: &Window, cx: &mut Context<Self>) {
+        if self.preview.dragging_tab.is_none() {
+            return;
+        }
+        let Some(ref preview) = self.preview.panel else {
+            return;
+        };
+        let size = window.bounds().size;
+        let window_size = (f32::from(size.width), f32::from(size.height));
+        let position = (f32::from(position.x), f32::from(position.y));
+        if in_preview_area(position, window_size, preview.split, preview.size) {
+            return;
+        }
+        let zone = canvas_edge_drop_zone(position, window_size, preview.split, preview.size);
+        // The edge beside the panel splits the pane along that side of it
+        let pane = zone.map_or(0, |zone| preview.layout.edge_pane(zone));
+        self.preview.tab_drag_target = None;
+        self.set_tab_drag_split_zone(pane, zone, cx);
+    }
+
+    /// Finish tab drag - reorder, move to another pane or split a pane
     pub fn finish_tab_drag(&mut self, cx: &mut Context<Self>) {
         tracing::debug!(
             "finish_tab_drag: dragging={:?}, split_zone={:?}, target={:?}",
@@ -242,146 +219,65 @@ impl Humanboard {
             self.preview.tab_drag_split_zone,
             self.preview.tab_drag_target
         );
+        let from_pane = self.preview.tab_drag_from_pane;
+        let target_pane = self.preview.tab_drag_target_pane;
+
         // Check if dropping on a split zone
         if let (Some(from), Some(zone)) = (self.preview.dragging_tab, self.preview.tab_drag_split_zone) {
             if let Some(ref mut preview) = self.preview.panel {
-                // Determine which pane we're dragging from
-                let from_right_pane =
-                    preview.is_pane_split && preview.focused_pane == FocusedPane::Right;
-
-                // Get the source tabs list
-                let (source_tabs, source_active) = if from_right_pane {
-                    (&mut preview.right_tabs, &mut preview.right_active_tab)
-                } else {
-                    (&mut preview.tabs, &mut preview.active_tab)
-                };
-
-                if from < source_tabs.len() {
-                    let tab = source_tabs.remove(from);
-
-                    // Update source active tab
-                    if *source_active >= source_tabs.len() {
-                        *source_active = source_tabs.len().saturating_sub(1);
-                    } else if *source_active > from {
-                        *source_active -= 1;
-                    }
-
-                    // Hide webview before move
-                    if let PreviewTab::Pdf {
-                        webview: Some(wv), ..
-                    } = &tab
-                    {
-                        wv.hide(cx);
-                    }
-
-                    // Determine target based on zone
-                    let target_is_right =
-                        matches!(zone, SplitDropZone::Right | SplitDropZone::Bottom);
-
-                    if !preview.is_pane_split {
-                        // Create new split
-                        preview.is_pane_split = true;
-                        preview.pane_ratio = 0.5;
-                        preview.pane_split_horizontal =
-                            matches!(zone, SplitDropZone::Top | SplitDropZone::Bottom);
-
-                        if target_is_right {
-                            preview.right_tabs.push(tab);
-                            preview.right_active_tab = preview.right_tabs.len() - 1;
-                            preview.focused_pane = FocusedPane::Right;
-                        } else {
-                            // Move existing tabs to right, put dragged tab in left
-                            let left_tabs: Vec<PreviewTab> = preview.tabs.drain(..).collect();
-                            preview.tabs.push(tab);
-                            preview.active_tab = 0;
-                            for t in left_tabs {
-                                preview.right_tabs.push(t);
-                            }
-                            preview.right_active_tab = 0;
-                            preview.focused_pane = FocusedPane::Left;
+                let source_len = preview.panes.get(from_pane).map_or(0, |p| p.tabs.len());
+                // Splitting a pane off its only tab leaves it where it is
+                let own_tab = target_pane == from_pane && source_len == 1;
+                if from < source_len && target_pane < preview.panes.len() && !own_tab {
+                    if preview.panes.len() >= MAX_PREVIEW_PANES {
+                        // No room for another pane - move the tab into the target
+                        if target_pane != from_pane {
+                            move_to_pane(preview, from_pane, from, target_pane, None, cx);
                         }
                     } else {
-                        // Already split - move tab to target pane
-                        if target_is_right {
-                            preview.right_tabs.push(tab);
-                            preview.right_active_tab = preview.right_tabs.len() - 1;
-                            preview.focused_pane = FocusedPane::Right;
-                        } else {
-                            preview.tabs.push(tab);
-                            preview.active_tab = preview.tabs.len() - 1;
-                            preview.focused_pane = FocusedPane::Left;
-                        }
-
-                        // Check if source pane is now empty - close split
-                        if from_right_pane && preview.right_tabs.is_empty() {
-                            preview.is_pane_split = false;
-                            preview.focused_pane = FocusedPane::Left;
-                        } else if !from_right_pane && preview.tabs.is_empty() {
-                            // Move right tabs to left
-                            preview.tabs = preview.right_tabs.drain(..).collect();
-                            preview.active_tab = preview.right_active_tab;
-                            preview.is_pane_split = false;
-                            preview.focused_pane = FocusedPane::Left;
+                        let mut tab = preview.panes[from_pane].take_tab(from);
+                        // Hide webview before move - it is recreated in the new pane
+                        detach_webview(&mut tab, cx);
+                        let horizontal = matches!(zone, SplitDropZone::Top | SplitDropZone::Bottom);
+                        let before = matches!(zone, SplitDropZone::Left | SplitDropZone::Top);
+                        if let Some(new_pane) = preview.add_pane(target_pane, horizontal, before) {
+                            preview.panes[new_pane].tabs.push(tab);
+                            preview.focused_pane = new_pane;
+                            preview.remove_pane_if_empty(from_pane);
                         }
                     }
                 }
             }
-            self.preview.dragging_tab = None;
-            self.preview.tab_drag_target = None;
-            self.preview.tab_drag_split_zone = None;
-            self.preview.tab_drag_position = None;
-            cx.notify();
+            self.end_tab_drag(cx);
             return;
         }
 
-        // Normal tab reorder
         if let (Some(from), Some(to)) = (self.preview.dragging_tab, self.preview.tab_drag_target) {
-            if from != to {
-                if let Some(ref mut preview) = self.preview.panel {
-                    // Don't allow moving tabs before pinned tabs (unless the dragged tab is pinned)
-                    let pinned_count = preview.tabs.iter().filter(|t| t.is_pinned()).count();
-                    let is_dragged_pinned = preview
-                        .tabs
-                        .get(from)
-                        .map(|t| t.is_pinned())
-                        .unwrap_or(false);
-
-                    let effective_to = if !is_dragged_pinned && to < pinned_count {
-                        pinned_count // Can't move before pinned tabs
-                    } else {
-                        to
-                    };
-
-                    if from != effective_to && from < preview.tabs.len() {
-                        let tab = preview.tabs.remove(from);
-                        let insert_pos = if effective_to > from {
-                            effective_to.min(preview.tabs.len())
-                        } else {
-                            effective_to
-                        };
-                        preview.tabs.insert(insert_pos, tab);
-
-                        // Update active tab index
-                        if preview.active_tab == from {
-                            preview.active_tab = insert_pos;
-                        } else if from < preview.active_tab && insert_pos >= preview.active_tab {
-                            preview.active_tab -= 1;
-                        } else if from > preview.active_tab && insert_pos <= preview.active_tab {
-                            preview.active_tab += 1;
+            if let Some(ref mut preview) = self.preview.panel {
+                if target_pane == from_pane {
+                    // Normal tab reorder within the pane
+                    if let Some(pane) = preview.panes.get_mut(from_pane) {
+                        if from != to {
+                            move_tab(&mut pane.tabs, &mut pane.active_tab, from, to);
                         }
                     }
+                } else if target_pane < preview.panes.len()
+                    && preview.panes.get(from_pane).is_some_and(|p| from < p.tabs.len())
+                {
+                    // Dropped on another pane - move it there
+                    move_to_pane(preview, from_pane, from, target_pane, Some(to), cx);
                 }
             }
         }
-        self.preview.dragging_tab = None;
-        self.preview.tab_drag_target = None;
-        self.preview.tab_drag_split_zone = None;
-        self.preview.tab_drag_position = None;
-        cx.notify();
+        self.end_tab_drag(cx);
     }
 
     /// Cancel tab drag without reordering
     pub fn cancel_tab_drag(&mut self, cx: &mut Context<Self>) {
+        self.end_tab_drag(cx);
+    }
+
+    fn end_tab_drag(&mut self, cx: &mut Context<Self>) {
         self.preview.dragging_tab = None;
         self.preview.tab_drag_target = None;
         self.preview.tab_drag_split_zone = None;
@@ -389,3 +285,34 @@ impl Humanboard {
         cx.notify();
     }
 }
+
+/// Move a tab to another pane, at `index` or after its other tabs, and
+/// focus that pane, closing the pane it leaves if that was its last tab
+fn move_to_pane(
+    preview: &mut PreviewPanel,
+    from_pane: usize,
+    from: usize,
+    to_pane: usize,
+    index: Option<usize>,
+    cx: &mut App,
+) {
+    let mut tab = preview.panes[from_pane].take_tab(from);
+    detach_webview(&mut tab, cx);
+    let target = &mut preview.panes[to_pane];
+    target.active_tab = match index {
+        Some(index) => insert_tab_at(&mut target.tabs, &mut target.active_tab, tab, index),
+        None => insert_tab(&mut target.tabs, &mut target.active_tab, tab),
+    };
+    preview.focused_pane = to_pane;
+    preview.remove_pane_if_empty(from_pane);
+}
+
+/// Hide a PDF tab's viewer before the tab moves to another pane, where a
+/// new one is created in its place
+pub(super) fn detach_webview(tab: &mut PreviewTab, cx: &mut App) {
+    if let PreviewTab::Pdf { webview, .. } = tab {
+        if let Some(wv) = webview.take() {
+            wv.hide(cx);
+        }
+    }
+}
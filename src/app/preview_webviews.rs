@@ -1,18 +1,154 @@
-//! Webview management - YouTube, Audio, Video webviews and visibility updates.
+//! Webview management - YouTube, Vimeo, SoundCloud, Spotify, Audio, Video webviews and
+//! visibility updates.
 //!
 //! ## Memory Optimization
 //!
 //! Webviews are only created for items within WEBVIEW_PRELOAD_DISTANCE of the viewport.
 //! Webviews for items beyond WEBVIEW_UNLOAD_DISTANCE are destroyed after a delay
-//! to prevent rapid create/destroy cycles during fast panning.
+//! to prevent rapid create/destroy cycles during fast panning. New webviews
+//! go through the progressive loader, so a board full of videos creates them
+//! a few per frame instead of all at once.
 
 use super::{Humanboard, PreviewTab, SplitDirection};
 use crate::constants::{HEADER_HEIGHT, WEBVIEW_PRELOAD_DISTANCE, WEBVIEW_UNLOAD_DELAY_MS, WEBVIEW_UNLOAD_DISTANCE};
-use crate::webviews::{AudioWebView, VideoWebView, YouTubeWebView};
+use crate::progressive_load::LoadKind;
+use super::WebViewManager;
+use crate::types::{ItemContent, MediaVolume};
+use crate::webviews::{
+    AudioWebView, SoundCloudWebView, SpotifyWebView, VideoWebView, VimeoWebView, YouTubeWebView,
+};
 use gpui::*;
 use std::time::{Duration, Instant};
 use tracing::error;
 
+/// Embedded web players, each kept in its own map of the WebViewManager
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EmbedKind {
+    YouTube,
+    Vimeo,
+    SoundCloud,
+    Spotify,
+}
+
+impl EmbedKind {
+    pub const ALL: [EmbedKind; 4] = [
+        EmbedKind::YouTube,
+        EmbedKind::Vimeo,
+        EmbedKind::SoundCloud,
+        EmbedKind::Spotify,
+    ];
+
+    /// Video ID, track URL or embed path of `content`, if it is an embed
+    /// of this kind
+    fn source(self, content: &ItemContent) -> Option<&str> {
+        match (self, content) {
+            (EmbedKind::YouTube, ItemContent::YouTube(source))
+            | (EmbedKind::Vimeo, ItemContent::Vimeo(source))
+            | (EmbedKind::SoundCloud, ItemContent::SoundCloud(source))
+            | (EmbedKind::Spotify, ItemContent::Spotify(source)) => Some(source),
+            _ => None,
+        }
+    }
+
+    /// What failed to load, for error messages
+    fn player_name(self) -> &'static str {
+        match self {
+            EmbedKind::YouTube => "YouTube video",
+            EmbedKind::Vimeo => "Vimeo video",
+            EmbedKind::SoundCloud => "SoundCloud player",
+            EmbedKind::Spotify => "Spotify player",
+        }
+    }
+
+    fn is_loaded(self, webviews: &WebViewManager, item_id: u64) -> bool {
+        match self {
+            EmbedKind::YouTube => webviews.youtube.contains_key(&item_id),
+            EmbedKind::Vimeo => webviews.vimeo.contains_key(&item_id),
+            EmbedKind::SoundCloud => webviews.soundcloud.contains_key(&item_id),
+            EmbedKind::Spotify => webviews.spotify.contains_key(&item_id),
+        }
+    }
+
+    fn loaded_ids(self, webviews: &WebViewManager) -> Vec<u64> {
+        match self {
+            EmbedKind::YouTube => webviews.youtube.keys().copied().collect(),
+            EmbedKind::Vimeo => webviews.vimeo.keys().copied().collect(),
+            EmbedKind::SoundCloud => webviews.soundcloud.keys().copied().collect(),
+            EmbedKind::Spotify => webviews.spotify.keys().copied().collect(),
+        }
+    }
+
+    /// Create the webview of an item from its `source`. Only YouTube
+    /// resumes at `start`; Spotify has no volume to set.
+    #[allow(clippy::too_many_arguments)]
+    fn create(
+        self,
+        webviews: &mut WebViewManager,
+        item_id: u64,
+        source: String,
+        start: f64,
+        volume: MediaVolume,
+        window: &mut Window,
+        cx: &mut App,
+    ) -> Result<(), String> {
+        match self {
+            EmbedKind::YouTube => {
+                let webview = YouTubeWebView::new(source, start, volume, window, cx)?;
+                webviews.youtube.insert(item_id, webview);
+            }
+            EmbedKind::Vimeo => {
+                let webview = VimeoWebView::new(source, volume, window, cx)?;
+                webviews.vimeo.insert(item_id, webview);
+            }
+            EmbedKind::SoundCloud => {
+                let webview = SoundCloudWebView::new(source, volume, window, cx)?;
+                webviews.soundcloud.insert(item_id, webview);
+            }
+            EmbedKind::Spotify => {
+                let webview = SpotifyWebView::new(source, window, cx)?;
+                webviews.spotify.insert(item_id, webview);
+            }
+        }
+        Ok(())
+    }
+
+    /// Hide and drop the webview of an item
+    fn remove(self, webviews: &mut WebViewManager, item_id: u64, cx: &mut App) {
+        match self {
+            EmbedKind::YouTube => {
+                if let Some(webview) = webviews.youtube.remove(&item_id) {
+                    webview.hide(cx);
+                }
+            }
+            EmbedKind::Vimeo => {
+                if let Some(webview) = webviews.vimeo.remove(&item_id) {
+                    webview.hide(cx);
+                }
+            }
+            EmbedKind::SoundCloud => {
+                if let Some(webview) = webviews.soundcloud.remove(&item_id) {
+                    webview.hide(cx);
+                }
+            }
+            EmbedKind::Spotify => {
+                if let Some(webview) = webviews.spotify.remove(&item_id) {
+                    webview.hide(cx);
+                }
+            }
+        }
+    }
+
+    /// Drop all webviews of this kind
+    fn clear(self, webviews: &mut WebViewManager) {
+        match self {
+            EmbedKind::YouTube => webviews.youtube.clear(),
+            EmbedKind::Vimeo => webviews.vimeo.clear(),
+            EmbedKind::SoundCloud => webviews.soundcloud.clear(),
+            EmbedKind::Spotify => webviews.spotify.clear(),
+        }
+    }
+}
+
 impl Humanboard {
     /// Calculate viewport bounds in canvas coordinates
     fn get_viewport_bounds(&self, window: &Window) -> Option<(f32, f32, f32, f32)> {
@@ -64,30 +200,32 @@ impl Humanboard {
         dist_left.max(0.0).max(dist_right.max(0.0)).max(dist_top.max(0.0)).max(dist_bottom.max(0.0))
     }
 
-    /// Ensure YouTube webviews are created for items near viewport.
-    /// Destroys webviews for items far from viewport (with delay to prevent thrashing).
-    pub fn ensure_youtube_webviews(&mut self, window: &mut Window, cx: &mut App) -> Vec<String> {
-        use crate::types::ItemContent;
+    /// Ensure webviews of the embeds of `kind` are created for items near
+    /// the viewport. Destroys webviews for items far from the viewport
+    /// (with delay to prevent thrashing).
+    pub fn ensure_embed_webviews(
+        &mut self,
+        kind: EmbedKind,
+        window: &mut Window,
+        cx: &mut App,
+    ) -> Vec<String> {
         let mut errors = Vec::new();
 
         let Some(ref board) = self.canvas.board else {
-            self.webviews.youtube.clear();
+            kind.clear(&mut self.webviews);
             self.webviews.out_of_range_since.clear();
             return errors;
         };
 
         let viewport = self.get_viewport_bounds(window);
 
-        // Collect YouTube items with their distance from viewport
-        let youtube_items: Vec<(u64, String, (f32, f32), (f32, f32))> = board
+        // Collect the embeds with their position and size
+        let embed_items: Vec<(u64, String, (f32, f32), (f32, f32))> = board
             .items
             .iter()
             .filter_map(|item| {
-                if let ItemContent::YouTube(video_id) = &item.content {
-                    Some((item.id, video_id.clone(), item.position, item.size))
-                } else {
-                    None
-                }
+                kind.source(&item.content)
+                    .map(|source| (item.id, source.to_string(), item.position, item.size))
             })
             .collect();
 
@@ -95,7 +233,7 @@ impl Humanboard {
         let unload_delay = Duration::from_millis(WEBVIEW_UNLOAD_DELAY_MS);
 
         // Create WebViews for items within preload distance
-        for (item_id, video_id, pos, size) in &youtube_items {
+        for (item_id, source, pos, size) in &embed_items {
             let distance = viewport
                 .map(|vp| Self::item_distance_to_viewport(*pos, *size, vp))
                 .unwrap_or(0.0);
@@ -104,42 +242,55 @@ impl Humanboard {
                 // Clear out-of-range tracking since item is now in range
                 self.webviews.out_of_range_since.remove(item_id);
 
-                if !self.webviews.youtube.contains_key(item_id) {
-                    match YouTubeWebView::new(video_id.clone(), window, cx) {
-                        Ok(webview) => {
-                            self.webviews.youtube.insert(*item_id, webview);
-                        }
-                        Err(e) => {
-                            errors.push(format!("Failed to load YouTube video: {}", e));
-                            error!("Failed to create YouTube WebView for video {}: {}", video_id, e);
-                        }
+                if !kind.is_loaded(&self.webviews, *item_id)
+                    && !self.webviews.evicted.contains(item_id)
+                    && self.canvas.loader.try_load(*item_id, LoadKind::WebView)
+                {
+                    let started = Instant::now();
+                    let start = board.playback_position(*item_id).unwrap_or(0.0);
+                    let volume = board.media_volume(*item_id);
+                    let created = kind.create(
+                        &mut self.webviews,
+                        *item_id,
+                        source.clone(),
+                        start,
+                        volume,
+                        window,
+                        cx,
+                    );
+                    self.canvas.loader.record(
+                        LoadKind::WebView,
+                        started.elapsed().as_secs_f64() * 1000.0,
+                    );
+                    if let Err(e) = created {
+                        errors.push(format!("Failed to load {}: {}", kind.player_name(), e));
+                        error!("Failed to create {:?} WebView for {}: {}", kind, source, e);
                     }
                 }
             }
         }
 
         // Remove WebViews for items far from viewport (with delay)
-        let youtube_ids: std::collections::HashSet<u64> =
-            youtube_items.iter().map(|(id, _, _, _)| *id).collect();
+        let embed_ids: std::collections::HashSet<u64> =
+            embed_items.iter().map(|(id, _, _, _)| *id).collect();
 
-        let ids_to_remove: Vec<u64> = self
-            .webviews
-            .youtube
-            .keys()
+        let ids_to_remove: Vec<u64> = kind
+            .loaded_ids(&self.webviews)
+            .into_iter()
             .filter(|id| {
                 // Always remove if item was deleted
-                if !youtube_ids.contains(id) {
+                if !embed_ids.contains(id) {
                     return true;
                 }
 
                 // Check distance for existing items
                 if let Some(vp) = viewport {
-                    if let Some((_, _, pos, size)) = youtube_items.iter().find(|(i, _, _, _)| i == *id) {
+                    if let Some((_, _, pos, size)) = embed_items.iter().find(|(i, _, _, _)| i == id) {
                         let distance = Self::item_distance_to_viewport(*pos, *size, vp);
                         if distance > WEBVIEW_UNLOAD_DISTANCE {
                             // Track when item went out of range
                             let out_since = self.webviews.out_of_range_since
-                                .entry(**id)
+                                .entry(*id)
                                 .or_insert(now);
                             // Only remove if out of range for long enough
                             return now.duration_since(*out_since) >= unload_delay;
@@ -148,14 +299,13 @@ impl Humanboard {
                 }
                 false
             })
-            .copied()
             .collect();
 
         for id in ids_to_remove {
-            if let Some(webview) = self.webviews.youtube.remove(&id) {
-                webview.hide(cx);
-            }
+            self.persist_playback_position(id);
+            kind.remove(&mut self.webviews, id, cx);
             self.webviews.out_of_range_since.remove(&id);
+            self.canvas.loader.forget(id);
         }
 
         errors
@@ -164,7 +314,6 @@ impl Humanboard {
     /// Ensure Audio webviews are created for items near viewport.
     /// Destroys webviews for items far from viewport (with delay to prevent thrashing).
     pub fn ensure_audio_webviews(&mut self, window: &mut Window, cx: &mut App) -> Vec<String> {
-        use crate::types::ItemContent;
         let mut errors = Vec::new();
 
         let Some(ref board) = self.canvas.board else {
@@ -189,17 +338,46 @@ impl Humanboard {
             })
             .collect();
 
+        // The playing playlist item keeps its player wherever it is
+        let playlist_item = self.webviews.playlist.as_ref().map(|p| p.current());
+
         // Create WebViews for items within preload distance
         for (item_id, path, pos, size) in &audio_items {
-            let distance = viewport
-                .map(|vp| Self::item_distance_to_viewport(*pos, *size, vp))
-                .unwrap_or(0.0);
+            let distance = if playlist_item == Some(*item_id) {
+                0.0
+            } else {
+                viewport
+                    .map(|vp| Self::item_distance_to_viewport(*pos, *size, vp))
+                    .unwrap_or(0.0)
+            };
 
             if distance <= WEBVIEW_PRELOAD_DISTANCE {
                 self.webviews.out_of_range_since.remove(item_id);
 
-                if !self.webviews.audio.contains_key(item_id) {
-                    match AudioWebView::new(path.clone(), window, cx) {
+                // The player is labeled from the file's tags, so it waits
+                // for them to be read in the background
+                let metadata = self.system.audio_metadata.get(path, &self.system.background);
+                if !self.webviews.audio.contains_key(item_id)
+                    && !self.webviews.evicted.contains(item_id)
+                    && !self.system.audio_metadata.is_pending(path)
+                    && self.canvas.loader.try_load(*item_id, LoadKind::WebView)
+                {
+                    let started = Instant::now();
+                    let start = board.playback_position(*item_id).unwrap_or(0.0);
+                    let volume = board.media_volume(*item_id);
+                    let created = AudioWebView::new(
+                        path.clone(),
+                        metadata.as_deref(),
+                        start,
+                        volume,
+                        window,
+                        cx,
+                    );
+                    self.canvas.loader.record(
+                        LoadKind::WebView,
+                        started.elapsed().as_secs_f64() * 1000.0,
+                    );
+                    match created {
                         Ok(webview) => {
                             self.webviews.audio.insert(*item_id, webview);
                         }
@@ -226,6 +404,9 @@ impl Humanboard {
                 if !audio_ids.contains(id) {
                     return true;
                 }
+                if playlist_item == Some(**id) {
+                    return false;
+                }
                 if let Some(vp) = viewport {
                     if let Some((_, _, pos, size)) = audio_items.iter().find(|(i, _, _, _)| i == *id) {
                         let distance = Self::item_distance_to_viewport(*pos, *size, vp);
@@ -241,10 +422,12 @@ impl Humanboard {
             .collect();
 
         for id in ids_to_remove {
+            self.persist_playback_position(id);
             if let Some(webview) = self.webviews.audio.remove(&id) {
                 webview.hide(cx);
             }
             self.webviews.out_of_range_since.remove(&id);
+            self.canvas.loader.forget(id);
         }
         errors
     }
@@ -252,7 +435,6 @@ impl Humanboard {
     /// Ensure Video webviews are created for items near viewport.
     /// Destroys webviews for items far from viewport (with delay to prevent thrashing).
     pub fn ensure_video_webviews(&mut self, window: &mut Window, cx: &mut App) -> Vec<String> {
-        use crate::types::ItemContent;
         let mut errors = Vec::new();
 
         let Some(ref board) = self.canvas.board else {
@@ -286,8 +468,27 @@ impl Humanboard {
             if distance <= WEBVIEW_PRELOAD_DISTANCE {
                 self.webviews.out_of_range_since.remove(item_id);
 
-                if !self.webviews.video.contains_key(item_id) {
-                    match VideoWebView::new(path.clone(), window, cx) {
+                if !self.webviews.video.contains_key(item_id)
+                    && !self.webviews.evicted.contains(item_id)
+                    && self.canvas.loader.try_load(*item_id, LoadKind::WebView)
+                {
+                    let started = Instant::now();
+                    let start = board.playback_position(*item_id).unwrap_or(0.0);
+                    let volume = board.media_volume(*item_id);
+                    let show_subtitles = !self.webviews.hidden_subtitles.contains(item_id);
+                    let created = VideoWebView::new(
+                        path.clone(),
+                        start,
+                        volume,
+                        show_subtitles,
+                        window,
+                        cx,
+                    );
+                    self.canvas.loader.record(
+                        LoadKind::WebView,
+                        started.elapsed().as_secs_f64() * 1000.0,
+                    );
+                    match created {
                         Ok(webview) => {
                             self.webviews.video.insert(*item_id, webview);
                         }
@@ -329,10 +530,12 @@ impl Humanboard {
             .collect();
 
         for id in ids_to_remove {
+            self.persist_playback_position(id);
             if let Some(webview) = self.webviews.video.remove(&id) {
                 webview.hide(cx);
             }
             self.webviews.out_of_range_since.remove(&id);
+            self.canvas.loader.forget(id);
         }
         errors
     }
@@ -347,6 +550,15 @@ impl Humanboard {
             for (_, webview) in &self.webviews.youtube {
                 webview.webview().update(cx, |wv, _| wv.hide());
             }
+            for (_, webview) in &self.webviews.vimeo {
+                webview.webview().update(cx, |wv, _| wv.hide());
+            }
+            for (_, webview) in &self.webviews.soundcloud {
+                webview.webview().update(cx, |wv, _| wv.hide());
+            }
+            for (_, webview) in &self.webviews.spotify {
+                webview.webview().update(cx, |wv, _| wv.hide());
+            }
             for (_, webview) in &self.webviews.audio {
                 webview.webview_entity.update(cx, |wv, _| wv.hide());
             }
@@ -355,7 +567,7 @@ impl Humanboard {
             }
             // Also hide PDF webviews in preview panel
             if let Some(ref preview) = self.preview.panel {
-                for tab in &preview.tabs {
+                for tab in preview.all_tabs() {
                     if let PreviewTab::Pdf {
                         webview: Some(wv), ..
                     } = tab
@@ -420,6 +632,39 @@ impl Humanboard {
                 });
             }
 
+            // Update Vimeo webview visibility
+            if let Some(webview) = self.webviews.vimeo.get(&item.id) {
+                webview.webview().update(cx, |wv, _| {
+                    if is_visible {
+                        wv.show();
+                    } else {
+                        wv.hide();
+                    }
+                });
+            }
+
+            // Update SoundCloud webview visibility
+            if let Some(webview) = self.webviews.soundcloud.get(&item.id) {
+                webview.webview().update(cx, |wv, _| {
+                    if is_visible {
+                        wv.show();
+                    } else {
+                        wv.hide();
+                    }
+                });
+            }
+
+            // Update Spotify webview visibility
+            if let Some(webview) = self.webviews.spotify.get(&item.id) {
+                webview.webview().update(cx, |wv, _| {
+                    if is_visible {
+                        wv.show();
+                    } else {
+                        wv.hide();
+                    }
+                });
+            }
+
             // Update Audio webview visibility
             if let Some(webview) = self.webviews.audio.get(&item.id) {
                 webview.webview_entity.update(cx, |wv, _| {
@@ -443,17 +688,19 @@ impl Humanboard {
             }
         }
 
-        // Show PDF webviews in preview panel (active tab only)
+        // Show PDF webviews in preview panel (active tab of each pane only)
         if let Some(ref preview) = self.preview.panel {
-            for (idx, tab) in preview.tabs.iter().enumerate() {
-                if let PreviewTab::Pdf {
-                    webview: Some(wv), ..
-                } = tab
-                {
-                    if idx == preview.active_tab {
-                        wv.webview().update(cx, |view, _| view.show());
-                    } else {
-                        wv.webview().update(cx, |view, _| view.hide());
+            for pane in &preview.panes {
+                for (idx, tab) in pane.tabs.iter().enumerate() {
+                    if let PreviewTab::Pdf {
+                        webview: Some(wv), ..
+                    } = tab
+                    {
+                        if idx == pane.active_tab {
+                            wv.webview().update(cx, |view, _| view.show());
+                        } else {
+                            wv.webview().update(cx, |view, _| view.hide());
+                        }
                     }
                 }
             }
@@ -0,0 +1,125 @@
+//! Quick open methods - the palette's file mode, listing files under the
+//! quick-open folders and adding the chosen one to the board
+
+use super::{CmdPaletteMode, Humanboard};
+use crate::quick_open::{
+    MAX_FILE_RESULTS, MAX_INDEXED_FILES, matching_files, quick_open_folders, scan_folders,
+};
+use gpui::*;
+use std::path::PathBuf;
+use std::sync::mpsc;
+
+impl Humanboard {
+    /// Open the command palette in file mode
+    pub fn show_quick_open(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        if self.canvas.board.is_none() {
+            return;
+        }
+        if self.ui.command_palette.is_none() {
+            self.show_command_palette(window, cx);
+        }
+        self.ui.cmd_palette_mode = CmdPaletteMode::Files;
+        self.scan_quick_open_folders();
+        self.show_file_results("", cx);
+    }
+
+    /// Scan the quick-open folders in the background, unless the last
+    /// listing is still fresh
+    pub(crate) fn scan_quick_open_folders(&mut self) {
+        if !self.ui.file_index.needs_scan() {
+            return;
+        }
+        let folders = quick_open_folders(crate::settings::quick_open_folders());
+        let (tx, rx) = mpsc::channel();
+        self.system.background.spawn(
+            "quick_open_scan",
+            move || Ok(scan_folders(&folders, MAX_INDEXED_FILES)),
+            move |result| {
+                if let Ok(files) = result {
+                    let _ = tx.send(files);
+                }
+            },
+        );
+        self.ui.file_index.scan_rx = Some(rx);
+    }
+
+    /// List the files matching `filter`
+    pub(crate) fn show_file_results(&mut self, filter: &str, cx: &mut Context<Self>) {
+        let files = &self.ui.file_index.files;
+        self.ui.search_results = matching_files(files, filter, MAX_FILE_RESULTS)
+            .into_iter()
+            .map(|index| (index as u64, files[index].display.clone()))
+            .collect();
+        self.ui.selected_result = 0;
+        cx.notify();
+    }
+
+    /// Path of the file listed with `id` in file mode
+    pub(crate) fn file_result_path(&self, id: u64) -> Option<PathBuf> {
+        self.ui
+            .file_index
+            .files
+            .get(id as usize)
+            .map(|file| file.path.clone())
+    }
+
+    /// Take the files of a finished scan, listing them if the palette is
+    /// still in file mode
+    pub(crate) fn poll_quick_open_scan(&mut self, cx: &mut Context<Self>) {
+        if !self.ui.file_index.poll() || self.ui.cmd_palette_mode != CmdPaletteMode::Files {
+            return;
+        }
+        let Some(ref input) = self.ui.command_palette else {
+            return;
+        };
+        let text = input.read(cx).text().to_string();
+        let filter = text.trim();
+        let filter = filter.strip_prefix("file ").unwrap_or(filter).trim();
+        let filter = if filter.eq_ignore_ascii_case("file") {
+            ""
+        } else {
+            filter
+        };
+        self.show_file_results(filter, cx);
+    }
+
+    /// Add the file at `path` at the center of the view, as if dropped there
+    pub(crate) fn add_file_at_view_center(
+        &mut self,
+        path: PathBuf,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let center = self.canvas_center(window);
+        let Some(ref mut board) = self.canvas.board else {
+            return;
+        };
+        let name = path
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_else(|| path.display().to_string());
+
+        let count = board.items.len();
+        let errors = board.handle_file_drop(center, vec![path]);
+        // Select the new item, so it can be moved straight away
+        if board.items.len() > count {
+            if let Some(item) = board.items.last() {
+                self.canvas.selected_items.clear();
+                self.canvas.selected_items.insert(item.id);
+            }
+        }
+
+        if errors.is_empty() {
+            self.show_toast(crate::notifications::Toast::success(format!(
+                "Added {}",
+                name
+            )));
+        }
+        for error in errors {
+            self.ui
+                .toast_manager
+                .push(crate::notifications::Toast::error(error));
+        }
+        cx.notify();
+    }
+}
@@ -0,0 +1,69 @@
+//! Registered command methods - running commands of the command registry
+//! and listing them in the command palette
+
+use crate::app::Humanboard;
+use crate::command_palette::{MAX_COMMAND_RESULTS, command_result_id};
+use crate::command_registry::{COMMAND_REGISTRY, keystroke_label};
+use crate::frecency::PALETTE_FRECENCY;
+use crate::keymap::USER_KEYMAP;
+use gpui::*;
+use tracing::warn;
+
+impl Humanboard {
+    /// Run the registered command `id`, if it is enabled
+    pub fn run_registered_command(
+        &mut self,
+        id: &str,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        // Let go of the registry first, so the command can register others
+        let command = COMMAND_REGISTRY.read().get(id);
+        let Some(command) = command else {
+            warn!("No command registered as {}", id);
+            return;
+        };
+        if command.is_enabled(self) {
+            command.execute(self, window, cx);
+        }
+    }
+
+    /// Palette results for the enabled registered commands matching
+    /// `query`, each with its rank as (score, tiebreak). The commands are
+    /// remembered in `ui.command_results`.
+    pub(crate) fn registered_command_results(
+        &mut self,
+        query: &str,
+    ) -> Vec<((i32, i32), u64, String)> {
+        self.ui.command_results.clear();
+        let matching = COMMAND_REGISTRY.read().matching(query, self);
+        let loaded = USER_KEYMAP.read();
+
+        let mut results = Vec::new();
+        for ((score, tiebreak), command) in matching.into_iter().take(MAX_COMMAND_RESULTS) {
+            let detail = command.description().unwrap_or(command.category());
+            // Keys remapped in keymap.json read as they're bound
+            let hint = command
+                .shortcut_hint()
+                .filter(|_| !loaded.keymap.is_customized(command.id()));
+            let shortcut = hint.map(str::to_string).or_else(|| {
+                loaded
+                    .keymap
+                    .keybindings_for(command.as_ref())
+                    .first()
+                    .map(|key| keystroke_label(key))
+            });
+            let label = match shortcut {
+                Some(shortcut) => format!("{} - {} ({})", command.name(), detail, shortcut),
+                None => format!("{} - {}", command.name(), detail),
+            };
+            results.push((
+                (score + PALETTE_FRECENCY.bonus(command.name()), tiebreak),
+                command_result_id(self.ui.command_results.len()),
+                label,
+            ));
+            self.ui.command_results.push(command.id());
+        }
+        results
+    }
+}
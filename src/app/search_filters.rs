@@ -0,0 +1,134 @@
+//! Search filter methods - narrowing palette results with `type:`, `tag:`,
+//! `board:` and `modified:` operators
+
+use crate::app::Humanboard;
+use crate::command_palette::{MAX_BOARD_RESULTS, board_result_id, board_result_index};
+use crate::search_query::{SearchQuery, item_modified};
+use crate::types::ItemContent;
+use std::collections::HashSet;
+
+impl Humanboard {
+    /// Search results for a query with filters: items on the open board
+    /// that pass them, matched by name and then by their text, and other
+    /// boards, matched by name or by the text of their PDFs.
+    ///
+    /// Items on other boards aren't loaded, so other boards are only
+    /// found when no filter looks at items.
+    pub(crate) fn filtered_search_results(&mut self, query: &SearchQuery) -> Vec<(u64, String)> {
+        let text = query.text.as_str();
+        let filters_items =
+            !query.types.is_empty() || !query.tags.is_empty() || !query.modified.is_empty();
+
+        let mut found: Vec<(u64, String)> = Vec::new();
+        let mut allowed: HashSet<u64> = HashSet::new();
+        if let Some(ref board) = self.canvas.board {
+            let board_name = self
+                .navigation
+                .board_index
+                .get_board(&board.id)
+                .map(|metadata| metadata.name.clone())
+                .unwrap_or_default();
+            if query.matches_board(&board_name) {
+                allowed = board
+                    .items
+                    .iter()
+                    .filter(|item| query.matches_item(item, item_modified(item)))
+                    .map(|item| item.id)
+                    .collect();
+            }
+
+            if text.is_empty() {
+                found = board
+                    .items
+                    .iter()
+                    .filter(|item| allowed.contains(&item.id))
+                    .filter(|item| {
+                        item.content.is_searchable()
+                            || matches!(item.content, ItemContent::TextBox { .. })
+                    })
+                    .map(|item| {
+                        let name = match &item.content {
+                            ItemContent::TextBox { text, .. } => text
+                                .lines()
+                                .map(str::trim)
+                                .find(|line| !line.is_empty())
+                                .unwrap_or("Text")
+                                .to_string(),
+                            ItemContent::Table { data_source_id, .. } => board
+                                .data_sources
+                                .get(data_source_id)
+                                .map(|ds| ds.name.clone())
+                                .unwrap_or_else(|| "Table".to_string()),
+                            _ => item.content.display_name(),
+                        };
+                        (item.id, name)
+                    })
+                    .collect();
+            } else {
+                found = board
+                    .find_items(text)
+                    .into_iter()
+                    .filter(|(id, _)| allowed.contains(id))
+                    .collect();
+            }
+        }
+
+        if !text.is_empty() {
+            let content_results = self.content_results(text, &found);
+            found.extend(
+                content_results
+                    .into_iter()
+                    .filter(|(id, _)| allowed.contains(id)),
+            );
+        }
+
+        // PDFs matching the text, here and on other boards, then other
+        // boards by name when only boards are filtered
+        let pdf_results = self.pdf_text_results(text, &found);
+        for (id, label) in pdf_results {
+            let keep = match board_result_index(id) {
+                Some(index) => {
+                    !filters_items
+                        && self
+                            .ui
+                            .board_results
+                            .get(index)
+                            .and_then(|board_id| self.navigation.board_index.get_board(board_id))
+                            .is_some_and(|metadata| query.matches_board(&metadata.name))
+                }
+                None => allowed.contains(&id),
+            };
+            if keep {
+                found.push((id, label));
+            }
+        }
+        if !filters_items && query.board.is_some() {
+            let current_board = self.canvas.board.as_ref().map(|board| board.id.clone());
+            let boards: Vec<(String, String)> = self
+                .navigation
+                .board_index
+                .active_boards()
+                .into_iter()
+                .filter(|metadata| Some(&metadata.id) != current_board.as_ref())
+                .filter(|metadata| !self.ui.board_results.contains(&metadata.id))
+                .filter(|metadata| query.matches_board(&metadata.name))
+                .filter(|metadata| {
+                    text.is_empty() || metadata.name.to_lowercase().contains(&text.to_lowercase())
+                })
+                .map(|metadata| (metadata.id.clone(), metadata.name.clone()))
+                .collect();
+            for (board_id, name) in boards {
+                if self.ui.board_results.len() >= MAX_BOARD_RESULTS {
+                    break;
+                }
+                found.push((
+                    board_result_id(self.ui.board_results.len()),
+                    format!("Board: {}", name),
+                ));
+                self.ui.board_results.push(board_id);
+            }
+        }
+
+        found
+    }
+}
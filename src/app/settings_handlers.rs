@@ -1,13 +1,16 @@
-//! Settings-related methods - theme, font, dropdowns, shortcuts
+//! Settings-related methods - theme, font, dropdowns, shortcuts, board storage
 
 use super::{Humanboard, SettingsTab};
 use crate::focus::FocusContext;
+use crate::item_styles::{ItemStyles, StyledKind};
 use gpui::*;
 use gpui_component::ActiveTheme;
 
 impl Humanboard {
     pub fn toggle_settings(&mut self, window: &mut Window, cx: &mut Context<Self>) {
         if self.settings.show {
+            // An unsaved theme is dropped with the settings
+            self.cancel_theme_editor(cx);
             // Start fade-out animation
             self.ui.modal_animations.close_settings();
             // Force focus back to canvas when closing settings
@@ -74,11 +77,7 @@ impl Humanboard {
         self.settings.data.save();
 
         // Apply theme using the App context
-        let theme_name = gpui::SharedString::from(theme_name);
-        let config = gpui_component::theme::ThemeRegistry::global(cx)
-            .themes()
-            .get(&theme_name)
-            .cloned();
+        let config = crate::settings::Settings::theme_config(&theme_name, cx);
 
         if let Some(config) = config {
             let mode = config.mode;
@@ -158,6 +157,119 @@ impl Humanboard {
         crate::settings::is_high_contrast()
     }
 
+    /// Set how long trashed boards are kept (0 keeps them forever).
+    /// Boards already past the new retention period are purged right away.
+    pub fn set_trash_retention_days(&mut self, days: u32, cx: &mut Context<Self>) {
+        if let Err(e) = crate::settings::set_trash_retention_days(days) {
+            tracing::error!("Failed to set trash retention: {}", e);
+            return;
+        }
+
+        let purged = self.update_board_index(|index| index.purge_old_trash(days));
+        if purged > 0 {
+            self.show_toast(crate::notifications::Toast::info(format!(
+                "Permanently deleted {} board(s) from trash",
+                purged
+            )));
+        }
+        if self.navigation.board_index.trashed_boards().is_empty() {
+            self.navigation.show_trash = false;
+        }
+
+        cx.notify();
+    }
+
+    /// Set whether dropped files are copied into the board's assets folder
+    pub fn set_manage_assets(&mut self, enabled: bool, cx: &mut Context<Self>) {
+        if let Err(e) = crate::settings::set_manage_assets(enabled) {
+            tracing::error!("Failed to set managed assets: {}", e);
+            return;
+        }
+        cx.notify();
+    }
+
+    /// Turn snap-to-grid off (None) or on with the given grid size
+    pub fn set_snap_grid_size(&mut self, grid_size: Option<f32>, cx: &mut Context<Self>) {
+        if let Err(e) = crate::settings::set_snap_grid_size(grid_size) {
+            tracing::error!("Failed to set snap to grid: {}", e);
+            return;
+        }
+        cx.notify();
+    }
+
+    /// Set whether boards are saved in the git-friendly layout.
+    ///
+    /// The open board is re-saved right away so its files match the setting.
+    pub fn set_git_friendly_save(&mut self, enabled: bool, cx: &mut Context<Self>) {
+        if let Err(e) = crate::settings::set_git_friendly_save(enabled) {
+            tracing::error!("Failed to set board file format: {}", e);
+            return;
+        }
+        if let Some(ref mut board) = self.canvas.board {
+            board.mark_dirty();
+            if let Err(e) = board.flush_save() {
+                self.ui.toast_manager.push(
+                    crate::notifications::Toast::error(format!("Save failed: {}", e))
+                        .with_action(crate::notifications::ToastAction::retry()),
+                );
+            }
+        }
+        cx.notify();
+    }
+
+    /// Change the styles new text boxes, shapes, arrows and tables are made with
+    pub fn update_item_styles(
+        &mut self,
+        updater: impl FnOnce(&mut ItemStyles),
+        cx: &mut Context<Self>,
+    ) {
+        if let Err(e) = crate::settings::update_item_styles(updater) {
+            tracing::error!("Failed to set item styles: {}", e);
+            return;
+        }
+        cx.notify();
+    }
+
+    /// Make the style of the selected text boxes, shapes, arrows and tables
+    /// the default for new ones. Where several of a kind are selected the
+    /// topmost is used.
+    pub fn set_default_style_from_selection(&mut self, cx: &mut Context<Self>) {
+        let Some(ref board) = self.canvas.board else {
+            return;
+        };
+        let mut styles = crate::settings::item_styles();
+        let kinds = styles.set_from_items(
+            board
+                .items
+                .iter()
+                .filter(|item| self.canvas.selected_items.contains(&item.id))
+                .map(|item| &item.content),
+        );
+
+        if kinds.is_empty() {
+            self.show_toast(crate::notifications::Toast::info(
+                "Select a text box, shape, arrow or table to take its style",
+            ));
+        } else if let Err(e) = crate::settings::update_item_styles(|current| *current = styles) {
+            self.show_toast(crate::notifications::Toast::error(format!(
+                "Default style not saved: {}",
+                e
+            )));
+        } else {
+            let names: Vec<&str> = kinds.iter().map(StyledKind::plural).collect();
+            let names = match names.split_last() {
+                Some((last, [])) => last.to_string(),
+                Some((last, rest)) => format!("{} and {}", rest.join(", "), last),
+                None => String::new(),
+            };
+            self.show_toast(crate::notifications::Toast::success(format!(
+                "New {} will look like this",
+                names
+            )));
+        }
+        cx.notify();
+    }
+
     pub fn set_settings_tab(&mut self, tab: SettingsTab, cx: &mut Context<Self>) {
         self.settings.tab = tab;
         cx.notify();
@@ -0,0 +1,224 @@
+//! Settings profile methods - exporting the settings, custom themes, keymap
+//! and board overrides to one file, and importing such a file
+
+use crate::app::{Humanboard, ProfileImport};
+use crate::board::{Board, BoardState};
+use crate::board_index::BoardIndex;
+use crate::canvas_background::CanvasBackground;
+use crate::notifications::Toast;
+use crate::settings::{Settings, global_settings, reload_themes};
+use crate::settings_profile::{
+    BoardOverrides, PROFILE_FILE_NAME, ProfilePaths, SettingsProfile, collect_profile,
+    install_profile, match_boards, read_profile, write_profile,
+};
+use gpui::*;
+use std::collections::BTreeMap;
+use std::sync::mpsc;
+use tracing::warn;
+
+impl Humanboard {
+    /// Ask where to save a settings profile, then write it
+    pub fn export_settings_profile(&mut self, cx: &mut Context<Self>) {
+        // The profile reads board overrides from disk
+        if let Some(ref mut board) = self.canvas.board {
+            if let Err(e) = board.flush_save() {
+                warn!("Failed to save board before exporting profile: {}", e);
+            }
+        }
+
+        let directory = dirs::document_dir()
+            .or_else(dirs::home_dir)
+            .unwrap_or_default();
+        let path_rx = cx.prompt_for_new_path(&directory, Some(PROFILE_FILE_NAME));
+
+        // Same channel workaround as Open File - the result is polled in
+        // render. Every board is read for its overrides off the main thread.
+        let (tx, rx) = mpsc::channel();
+        cx.background_executor()
+            .spawn(async move {
+                if let Ok(Ok(Some(path))) = path_rx.await {
+                    let result = collect_profile(&ProfilePaths::user(), board_overrides())
+                        .and_then(|profile| write_profile(&profile, &path))
+                        .map(|()| path)
+                        .map_err(|e| e.to_string());
+                    let _ = tx.send(result);
+                }
+            })
+            .detach();
+
+        self.canvas.export_rx = Some(rx);
+    }
+
+    /// Ask for a settings profile and write its files, then apply it with
+    /// `poll_profile_import`
+    pub fn import_settings_profile(&mut self, cx: &mut Context<Self>) {
+        let paths_rx = cx.prompt_for_paths(PathPromptOptions {
+            files: true,
+            directories: false,
+            multiple: false,
+            prompt: None,
+        });
+
+        let (tx, rx) = mpsc::channel();
+        cx.background_executor()
+            .spawn(async move {
+                if let Ok(Ok(Some(paths))) = paths_rx.await {
+                    if let Some(path) = paths.into_iter().next() {
+                        let result = read_profile(&path)
+                            .and_then(|profile| {
+                                let installed = install_profile(&profile, &ProfilePaths::user())?;
+                                let (boards_updated, open_boards) = apply_board_overrides(&profile);
+                                Ok(ProfileImport {
+                                    installed,
+                                    boards_updated,
+                                    open_boards,
+                                })
+                            })
+                            .map_err(|e| e.to_string());
+                        let _ = tx.send(result);
+                    }
+                }
+            })
+            .detach();
+
+        self.settings.profile_rx = Some(rx);
+    }
+
+    /// Load the settings, themes and keymap of an imported profile and
+    /// apply its overrides to the board in this window (called from render)
+    pub(crate) fn poll_profile_import(&mut self, cx: &mut Context<Self>) {
+        let Some(ref rx) = self.settings.profile_rx else {
+            return;
+        };
+        let Ok(result) = rx.try_recv() else {
+            return;
+        };
+        self.settings.profile_rx = None;
+
+        let import = match result {
+            Ok(import) => import,
+            Err(e) => {
+                self.show_toast(Toast::error(format!("Profile not imported: {}", e)));
+                cx.notify();
+                return;
+            }
+        };
+
+        match global_settings().write() {
+            Ok(mut store) => {
+                if let Err(e) = store.load_user_settings() {
+                    self.show_toast(Toast::warning(format!("Settings not reloaded: {}", e)));
+                }
+            }
+            Err(_) => warn!("Settings store lock poisoned"),
+        }
+        reload_themes();
+        let settings = Settings::load();
+        self.settings.data.font = settings.font;
+        self.set_theme(settings.theme, cx);
+        cx.refresh_windows();
+
+        if import.installed.keymap {
+            if let Err(e) = crate::keymap::reload_user_keymap() {
+                self.show_toast(Toast::error(format!("Keymap not reloaded: {}", e)));
+            }
+            crate::keymap::bind_keymap(cx);
+            cx.set_menus(crate::command_registry::COMMAND_REGISTRY.read().menus());
+        }
+
+        let mut boards = import.boards_updated;
+        let mut elsewhere = 0;
+        for (id, background) in import.open_boards {
+            match self.canvas.board {
+                Some(ref mut board) if board.id == id => {
+                    board.set_background(background);
+                    boards += 1;
+                }
+                _ => elsewhere += 1,
+            }
+        }
+
+        let mut parts = Vec::new();
+        if import.installed.themes > 0 {
+            parts.push(count_label(import.installed.themes, "theme"));
+        }
+        if import.installed.keymap {
+            parts.push("the keymap".to_string());
+        }
+        if boards > 0 {
+            parts.push(count_label(boards, "board"));
+        }
+        let message = if parts.is_empty() {
+            "Imported settings profile".to_string()
+        } else {
+            format!("Imported settings profile with {}", parts.join(", "))
+        };
+        self.show_toast(Toast::success(message));
+        if elsewhere > 0 {
+            self.show_toast(Toast::warning(format!(
+                "{} open in other windows kept their settings",
+                count_label(elsewhere, "board")
+            )));
+        }
+        cx.notify();
+    }
+}
+
+/// Overrides of every board that has any, read from disk. Encrypted boards
+/// can't be read and are left out.
+fn board_overrides() -> BTreeMap<String, BoardOverrides> {
+    BoardIndex::load()
+        .active_boards()
+        .into_iter()
+        .filter_map(|meta| {
+            let state = BoardState::load_from_path(&meta.board_path()).ok()?;
+            BoardOverrides::from_state(&meta.name, &state).map(|o| (meta.id.clone(), o))
+        })
+        .collect()
+}
+
+/// Save the overrides of a profile to the boards they match that aren't
+/// open. Returns how many boards were saved, and the overrides of those
+/// that are open for their windows to apply.
+fn apply_board_overrides(
+    profile: &SettingsProfile,
+) -> (usize, Vec<(String, Option<CanvasBackground>)>) {
+    let index = BoardIndex::load();
+    let boards: Vec<(String, String)> = index
+        .active_boards()
+        .into_iter()
+        .map(|meta| (meta.id.clone(), meta.name.clone()))
+        .collect();
+
+    let mut updated = 0;
+    let mut open = Vec::new();
+    for (id, overrides) in match_boards(&profile.boards, &boards) {
+        if BoardIndex::is_board_open(&id) {
+            open.push((id, overrides.background.clone()));
+            continue;
+        }
+        // Boards that don't read, encrypted ones included, are left alone
+        // rather than loaded as empty boards and saved over
+        let Some(meta) = index.get_board(&id) else {
+            continue;
+        };
+        if BoardState::load_from_path(&meta.board_path()).is_err() {
+            continue;
+        }
+        let mut board = Board::load(id);
+        board.set_background(overrides.background.clone());
+        match board.flush_save() {
+            Ok(_) => updated += 1,
+            Err(e) => warn!("Failed to save board '{}': {}", board.id, e),
+        }
+    }
+    (updated, open)
+}
+
+fn count_label(count: usize, noun: &str) -> String {
+    if count == 1 {
+        format!("1 {}", noun)
+    } else {
+        format!("{} {}s", count, noun)
+    }
+}
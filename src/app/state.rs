@@ -1,26 +1,49 @@
 //! Application state - the Humanboard struct definition and sub-structs.
 
-use super::{CmdPaletteMode, CountdownState, PreviewPanel, SettingsTab, StorageLocation};
+use super::{
+    CmdPaletteMode, CountdownState, PassphraseMode, PreviewPanel, SettingsTab, StorageLocation,
+};
 use crate::animations::ModalAnimationState;
+use crate::audio_metadata::AudioMetadataCache;
 use crate::background::BackgroundExecutor;
-use crate::board::Board;
+use crate::board::{Board, BoardState};
+use crate::board_crypto::BoardKey;
 use crate::board_index::BoardIndex;
+use crate::canvas_find::FindMatches;
+use crate::card_refresh::CardFileWatch;
+use crate::code_excerpt::CodeExcerptCache;
+use crate::content_search::FileTextCache;
+use crate::custom_themes::{EditableColor, ThemeDraft};
 use crate::data::{DataSourceDelegate, VirtualScrollState};
+use crate::error::BoardError;
 use crate::focus::FocusManager;
 use crate::hit_testing::HitTester;
+use crate::image_cache::ImageThumbnailCache;
+use crate::memory::MemoryMonitor;
 use crate::notifications::ToastManager;
+use crate::pdf::{PdfPageCache, PdfThumbnailCache};
 use crate::perf::PerfMonitor;
+use crate::playlist::Playlist;
+use crate::progressive_load::ProgressiveLoader;
+use crate::quick_open::FileIndex;
+use crate::render::item_cache::ItemRenderCache;
+use crate::render::text_layout::TextLayoutCache;
 use crate::settings::Settings;
 use crate::settings_watcher::SettingsWatcher;
 use crate::types::ToolType;
-use crate::webviews::{AudioWebView, VideoWebView, YouTubeWebView};
+use crate::waveform::WaveformCache;
+use crate::webviews::{
+    AudioWebView, PlaybackPosition, SoundCloudWebView, SpotifyWebView, VideoWebView, VimeoWebView,
+    YouTubeWebView,
+};
+use crate::wiki_links::WikiLinks;
 use gpui::*;
 use crate::input::InputState as CanvasInputState;
 use gpui_component::input::InputState;
 use gpui_component::table::TableState;
 use std::collections::{HashMap, HashSet};
 use std::path::PathBuf;
-use std::sync::mpsc::Receiver;
+use std::sync::mpsc::{Receiver, Sender};
 use std::time::{Duration, Instant};
 
 use super::PanAnimation;
@@ -61,6 +84,198 @@ impl ChartConfigModal {
     }
 }
 
+/// State for the board version history browser
+pub struct VersionHistoryModal {
+    /// Versions available on disk, newest first
+    pub versions: Vec<crate::board_versions::BoardVersion>,
+    /// Index of the version being previewed
+    pub selected: Option<usize>,
+    /// Loaded state of the selected version (restored on confirm)
+    pub selected_state: Option<crate::board::BoardState>,
+    /// Summary of the selected version for the preview pane
+    pub preview: Option<crate::board_versions::VersionSummary>,
+    /// Error message if the selected version couldn't be loaded
+    pub preview_error: Option<String>,
+}
+
+impl VersionHistoryModal {
+    pub fn new(versions: Vec<crate::board_versions::BoardVersion>) -> Self {
+        Self {
+            versions,
+            selected: None,
+            selected_state: None,
+            preview: None,
+            preview_error: None,
+        }
+    }
+}
+
+/// A saved version compared with the board on the canvas
+pub struct VersionComparison {
+    /// How the version is named in the comparison bar, like "version from
+    /// 5 min ago"
+    pub title: String,
+    /// The version's state, restored on request
+    pub state: crate::board::BoardState,
+    /// Differences from the board, kept up to date with its edits
+    pub diff: crate::board_diff::BoardDiff,
+    /// Board change the diff was built from (rebuilt when this moves on)
+    pub built_at: Option<Instant>,
+}
+
+/// Chooser shown beside a freshly pasted URL, to make it something else
+/// than what it was detected as
+pub struct UrlPasteChooser {
+    /// The pasted item
+    pub item_id: u64,
+    pub url: String,
+    /// What the URL can become, the detected kind first
+    pub kinds: Vec<crate::url_paste::UrlPasteKind>,
+    /// What the item is now
+    pub current: crate::url_paste::UrlPasteKind,
+    /// When it was shown (it closes by itself after a few seconds)
+    pub opened_at: Instant,
+}
+
+/// Export dialog for a region of the canvas
+pub struct ExportDialog {
+    pub region: crate::region_export::ExportRegion,
+    pub format: crate::region_export::ExportFormat,
+    /// Pixels per canvas unit
+    pub scale: f32,
+    /// What the canvas showed when the dialog opened, in canvas units
+    pub visible: crate::input::guides::Bounds,
+    /// Rectangle dragged out on the canvas, in canvas units
+    pub drawn: Option<crate::input::guides::Bounds>,
+    /// Waiting for a rectangle to be dragged out on the canvas (the dialog
+    /// is hidden meanwhile)
+    pub picking: bool,
+}
+
+/// A page or image fetched in the background for a pasted URL
+pub struct UrlFetch {
+    /// Board the pasted item is on
+    pub board_id: String,
+    pub item_id: u64,
+    pub url: String,
+    /// What is fetched: the page of a bookmark, or an image
+    pub kind: crate::url_paste::UrlPasteKind,
+    /// Whether the item becomes the image as an undoable change, because
+    /// it was picked in the chooser, rather than being filled in
+    pub replace: bool,
+    pub result: Result<UrlFetched, String>,
+}
+
+/// What was fetched for a pasted URL
+pub enum UrlFetched {
+    /// Details of the page a bookmark points at
+    Page(crate::url_paste::PageDetails),
+    /// Data of the image the URL points at
+    Image(Vec<u8>),
+}
+
+/// Files dropped on a board being imported in the background. They are
+/// added to the board together once all of them are ready.
+pub struct FileImport {
+    /// Board the files were dropped on
+    pub board_id: String,
+    /// Where the files are copied to, if they are
+    pub import_dir: Option<PathBuf>,
+    /// Where they were dropped, in window coordinates
+    pub position: Point<Pixels>,
+    /// Number of files dropped
+    pub total: usize,
+    /// Number of files read so far
+    pub done: usize,
+    /// Files ready to add, in the order they were dropped
+    pub files: Vec<crate::file_import::ImportedFile>,
+    /// Problems with files read so far
+    pub errors: Vec<String>,
+    /// Receiver for each file once it has been read
+    pub rx: Receiver<(Option<crate::file_import::ImportedFile>, Vec<String>)>,
+    /// When the import started (progress shows once it takes a while)
+    pub started_at: Instant,
+}
+
+/// Items imported from another app, read in the background
+pub struct BoardImport {
+    /// Board the import was started on
+    pub board_id: String,
+    /// App the items come from, like "Miro"
+    pub source: &'static str,
+    pub items: crate::item_clipboard::CopiedItems,
+    /// What couldn't be imported, one note per part
+    pub skipped: Vec<String>,
+}
+
+/// Report listing what an import couldn't bring over
+pub struct ImportReport {
+    /// App the items came from
+    pub source: &'static str,
+    /// Number of items added to the board
+    pub imported: usize,
+    pub skipped: Vec<String>,
+}
+
+/// A settings profile imported in the background
+pub struct ProfileImport {
+    pub installed: crate::settings_profile::InstalledProfile,
+    /// Closed boards whose overrides were saved
+    pub boards_updated: usize,
+    /// Overrides of boards open in a window, left for the window to
+    /// apply: (board ID, background)
+    pub open_boards: Vec<(String, Option<crate::canvas_background::CanvasBackground>)>,
+}
+
+/// State for the Outline side panel
+#[derive(Default)]
+pub struct OutlinePanel {
+    /// Index of the current board's items
+    pub outline: crate::board_outline::BoardOutline,
+    /// Board change the index was built from (rebuilt when this moves on)
+    pub built_at: Option<std::time::Instant>,
+    /// Scroll handle for the item list
+    pub scroll: ScrollHandle,
+}
+
+/// State for the undo History side panel
+#[derive(Default)]
+pub struct UndoHistoryPanel {
+    /// Whether only the header is shown
+    pub collapsed: bool,
+    /// Scroll handle for the step list
+    pub scroll: ScrollHandle,
+}
+
+/// State for the board passphrase prompt
+pub struct PassphrasePrompt {
+    /// Board being unlocked or encrypted
+    pub board_id: String,
+    /// Whether the prompt unlocks or encrypts the board
+    pub mode: PassphraseMode,
+    /// Passphrase input (created on the next render, which has window access)
+    pub input: Option<Entity<InputState>>,
+    /// Confirmation input (encrypt mode only)
+    pub confirm_input: Option<Entity<InputState>>,
+    /// Error shown under the inputs (e.g. wrong passphrase)
+    pub error: Option<String>,
+    /// Pending unlock (the key is derived on the background executor)
+    pub unlock_rx: Option<Receiver<Result<(BoardState, BoardKey), BoardError>>>,
+}
+
+impl PassphrasePrompt {
+    pub fn new(board_id: String, mode: PassphraseMode) -> Self {
+        Self {
+            board_id,
+            mode,
+            input: None,
+            confirm_input: None,
+            error: None,
+            unlock_rx: None,
+        }
+    }
+}
+
 // =============================================================================
 // Sub-structs extracted from the god object Humanboard
 // =============================================================================
@@ -103,6 +318,73 @@ pub struct CanvasState {
     pub file_drop_rx: Option<Receiver<(Point<Pixels>, Vec<PathBuf>)>>,
     /// Last drop position
     pub last_drop_pos: Option<Point<Pixels>>,
+    /// When the board thumbnail was last regenerated
+    pub last_thumbnail_at: Option<Instant>,
+    /// Items whose backing file is missing (shown with a broken-link badge)
+    pub missing_files: HashSet<u64>,
+    /// When missing files were last checked
+    pub missing_files_checked_at: Option<Instant>,
+    /// Receiver for the items found missing by a check in the background
+    pub missing_files_rx: Option<Receiver<HashSet<u64>>>,
+    /// Receiver for a located file: (item_id, new path)
+    pub relink_rx: Option<Receiver<(u64, PathBuf)>>,
+    /// Receiver for an image picked as the canvas background
+    pub background_image_rx: Option<Receiver<PathBuf>>,
+    /// Receiver for a finished HTML export: saved path or error message
+    pub export_rx: Option<Receiver<Result<PathBuf, String>>>,
+    /// Receiver for the selection rendered as a PNG to copy: its data and
+    /// item count, or an error message
+    pub copy_png_rx: Option<Receiver<Result<(Vec<u8>, usize), String>>>,
+    /// Receiver for items imported from another app, or an error message
+    pub import_rx: Option<Receiver<Result<BoardImport, String>>>,
+    /// Receiver for a PDF page being placed on the canvas: board ID, where
+    /// it goes, and the saved image or an error message
+    pub placed_page_rx: Option<Receiver<(String, Point<Pixels>, Result<PathBuf, String>)>>,
+    /// Alignment guides shown while dragging items
+    pub alignment_guides: Vec<crate::input::guides::AlignmentGuide>,
+    /// When the selection was last nudged with the arrow keys
+    pub last_nudge_at: Option<Instant>,
+    /// Sender handed to image measuring tasks: (board_id, item_id, size)
+    pub image_size_tx: Sender<(String, u64, Option<(f32, f32)>)>,
+    /// Receiver for measured image sizes
+    pub image_size_rx: Receiver<(String, u64, Option<(f32, f32)>)>,
+    /// Number of images still being measured
+    pub images_measuring: usize,
+    /// Sender handed to tasks fetching pasted URLs
+    pub url_fetch_tx: Sender<UrlFetch>,
+    /// Receiver for fetched pages and images of pasted URLs
+    pub url_fetch_rx: Receiver<UrlFetch>,
+    /// Number of pasted URLs still being fetched
+    pub url_fetches: usize,
+    /// Pasted images made something else in the chooser before they were
+    /// downloaded
+    pub declined_url_images: HashSet<u64>,
+    /// Dropped files still being imported
+    pub file_imports: Vec<FileImport>,
+    /// Cached views for static items on screen
+    pub render_cache: ItemRenderCache,
+    /// Line layouts of visible text boxes
+    pub text_layouts: TextLayoutCache,
+    /// Excerpts shown on expanded code cards
+    pub code_excerpts: CodeExcerptCache,
+    /// Stamps of the files behind markdown and code cards
+    pub card_files: CardFileWatch,
+    /// When cards were last refreshed from their changed files, for their flash
+    pub updated_cards: HashMap<u64, Instant>,
+    /// Text of the code files searched from the command palette
+    pub search_texts: FileTextCache,
+    /// Card last chosen from the search results, and when, for its highlight
+    pub search_highlight: Option<(u64, Instant)>,
+    /// Input of the find bar, while it is open
+    pub find_input: Option<Entity<InputState>>,
+    /// Items matching the find bar's query
+    pub find: FindMatches,
+    /// Saved version the board is compared with on the canvas
+    pub comparison: Option<VersionComparison>,
+    /// Wiki links between the board's markdown cards
+    pub wiki_links: WikiLinks,
+    /// Which items' media has been allowed to load
+    pub loader: ProgressiveLoader,
 }
 
 /// Preview panel state - tabs, panes, search, scroll handles
@@ -117,24 +399,36 @@ pub struct PreviewState {
     pub tab_drag_split_zone: Option<super::SplitDropZone>,
     /// Current drag position for ghost
     pub tab_drag_position: Option<Point<Pixels>>,
-    /// Pending drag before threshold: (tab_index, start_pos, is_left_pane)
-    pub tab_drag_pending: Option<(usize, Point<Pixels>, bool)>,
+    /// Pane the dragged tab comes from
+    pub tab_drag_from_pane: usize,
+    /// Pane of the drop target tab or split zone
+    pub tab_drag_target_pane: usize,
+    /// Pending drag before threshold: (tab_index, start_pos, pane)
+    pub tab_drag_pending: Option<(usize, Point<Pixels>, usize)>,
     /// Search input for preview panel
     pub search: Option<Entity<InputState>>,
+    /// Replacement input below the search input, for editable tabs
+    pub replace: Option<Entity<InputState>>,
     /// Current search query
     pub search_query: String,
     /// Search match positions (line, column)
     pub search_matches: Vec<(usize, usize)>,
     /// Current match index
     pub search_current: usize,
-    /// Scroll handle for left pane tabs
-    pub left_tab_scroll: ScrollHandle,
-    /// Scroll handle for right pane tabs
-    pub right_tab_scroll: ScrollHandle,
+    /// PDFs to show at a page once their viewers are created
+    pub pending_pdf_pages: HashMap<PathBuf, usize>,
+    /// Code tabs to put at a line (counted from 0) once their editors are created
+    pub pending_code_lines: HashMap<PathBuf, usize>,
+    /// When the files of code tabs were last checked for changes
+    pub code_files_checked_at: Option<Instant>,
+    /// Whether the opened board's saved preview session is still to be restored
+    pub restore_session: bool,
+    /// Scroll handles for the tab bar of each pane
+    pub tab_scrolls: Vec<ScrollHandle>,
     /// Canvas/preview splitter dragging state
     pub dragging_splitter: bool,
-    /// Pane splitter dragging state (for split panes)
-    pub dragging_pane_splitter: bool,
+    /// Path in the pane layout of the split whose divider is dragged
+    pub dragged_pane_split: Vec<bool>,
     /// Splitter drag start position
     pub splitter_drag_start: Option<Point<Pixels>>,
 }
@@ -157,18 +451,58 @@ pub struct SettingsState {
     pub font_index: usize,
     /// Scroll handle for font list
     pub font_scroll: ScrollHandle,
+    /// Theme being made in the theme editor, if it is open
+    pub theme_editor: Option<ThemeEditor>,
+    /// Receiver for an imported settings profile, or an error message
+    pub profile_rx: Option<Receiver<Result<ProfileImport, String>>>,
+}
+
+/// The theme editor in the Appearance tab of settings
+pub struct ThemeEditor {
+    pub draft: ThemeDraft,
+    /// Theme to go back to if the draft isn't saved
+    pub original: String,
+    pub name_input: Entity<InputState>,
+    /// Hex input of each color, in the order they are listed
+    pub color_inputs: Vec<(EditableColor, Entity<InputState>)>,
 }
 
-/// WebView management - YouTube, Audio, Video webviews
+/// WebView management - YouTube, Vimeo, SoundCloud, Spotify, Audio, Video webviews.
+///
+/// Owned per window: webviews and their local media servers are dropped
+/// with the window that created them.
 pub struct WebViewManager {
     /// YouTube WebViews keyed by item ID
     pub youtube: HashMap<u64, YouTubeWebView>,
+    /// Vimeo WebViews keyed by item ID
+    pub vimeo: HashMap<u64, VimeoWebView>,
+    /// SoundCloud WebViews keyed by item ID
+    pub soundcloud: HashMap<u64, SoundCloudWebView>,
+    /// Spotify WebViews keyed by item ID
+    pub spotify: HashMap<u64, SpotifyWebView>,
     /// Audio WebViews keyed by item ID
     pub audio: HashMap<u64, AudioWebView>,
     /// Video WebViews keyed by item ID
     pub video: HashMap<u64, VideoWebView>,
     /// When items went out of viewport (for delayed unload)
     pub out_of_range_since: HashMap<u64, Instant>,
+    /// Items whose webview was evicted to stay within the memory budget;
+    /// not recreated until the item is on screen again
+    pub evicted: HashSet<u64>,
+    /// Last known playback position of each audio, video and YouTube player
+    pub media_positions: HashMap<u64, PlaybackPosition>,
+    /// Sender for positions reported by media players
+    pub media_position_tx: Sender<(u64, Option<PlaybackPosition>)>,
+    /// Receiver for positions reported by media players
+    pub media_position_rx: Receiver<(u64, Option<PlaybackPosition>)>,
+    /// When media players were last asked for their position
+    pub media_positions_polled_at: Option<Instant>,
+    /// Audio items being played one after another, if a playlist is running
+    pub playlist: Option<Playlist>,
+    /// When the current playlist item was last asked to play
+    pub playlist_play_sent_at: Option<Instant>,
+    /// Video items whose subtitles were turned off
+    pub hidden_subtitles: HashSet<u64>,
 }
 
 /// Tool state - selected tool and drawing state
@@ -193,7 +527,15 @@ pub struct UiState {
     pub search_results: Vec<(u64, String)>,
     /// Currently selected search result index
     pub selected_result: usize,
-    /// Current command palette mode: items or themes
+    /// IDs of the other boards listed in the search results, by position
+    /// (see `command_palette::board_result_id`)
+    pub board_results: Vec<String>,
+    /// IDs of the registered commands listed in the search results, by
+    /// position (see `command_palette::command_result_id`)
+    pub command_results: Vec<&'static str>,
+    /// Files under the quick-open folders, listed in the palette's file mode
+    pub file_index: FileIndex,
+    /// Current command palette mode: items, themes, symbols or files
     pub cmd_palette_mode: CmdPaletteMode,
     /// Command palette scroll handle
     pub cmd_palette_scroll: ScrollHandle,
@@ -205,6 +547,28 @@ pub struct UiState {
     pub pan_animation: Option<PanAnimation>,
     /// Modal animation states
     pub modal_animations: ModalAnimationState,
+    /// Board version history browser (open when Some)
+    pub version_history: Option<VersionHistoryModal>,
+    /// Passphrase prompt for encrypted boards (open when Some)
+    pub board_passphrase: Option<PassphrasePrompt>,
+    /// Outline side panel (open when Some)
+    pub outline: Option<OutlinePanel>,
+    /// Undo History side panel (open when Some)
+    pub undo_history: Option<UndoHistoryPanel>,
+    /// Name input of a checkpoint being created (open when Some)
+    pub checkpoint_name: Option<Entity<InputState>>,
+    /// Chooser for what a pasted URL becomes (open when Some)
+    pub url_paste: Option<UrlPasteChooser>,
+    /// Export dialog for a region of the canvas (open when Some)
+    pub export_dialog: Option<ExportDialog>,
+    /// What the last import skipped (open when Some)
+    pub import_report: Option<ImportReport>,
+    /// Right-click menu (open when Some)
+    pub context_menu: Option<crate::context_menu::ContextMenu>,
+    /// Item under the cursor, with its tooltip once the hover delay has passed
+    pub hover: Option<crate::item_tooltip::HoverState>,
+    /// Show the performance HUD
+    pub show_perf_hud: bool,
 }
 
 /// Performance and system state
@@ -223,8 +587,22 @@ pub struct SystemState {
     pub perf_monitor: PerfMonitor,
     /// Background task executor
     pub background: BackgroundExecutor,
+    /// Downscaled copies of canvas images
+    pub image_cache: ImageThumbnailCache,
+    /// Decoded peaks of audio items
+    pub waveforms: WaveformCache,
+    /// Tags and album art of audio items
+    pub audio_metadata: AudioMetadataCache,
+    /// Rendered pages of PDF items
+    pub pdf_pages: PdfPageCache,
+    /// Thumbnails of PDF items, regenerated when their files change
+    pub pdf_thumbnails: PdfThumbnailCache,
+    /// Memory held by decoded images, webviews and PDF thumbnails
+    pub memory: MemoryMonitor,
     /// Settings file watcher for hot-reload
     pub settings_watcher: Option<SettingsWatcher>,
+    /// ID of the window this view lives in
+    pub window_id: u64,
 }
 
 /// Textbox editing state
@@ -0,0 +1,116 @@
+//! Preview tab zoom methods - Cmd+/Cmd- over the preview panel
+
+use crate::app::{Humanboard, PreviewTab};
+use crate::focus::FocusContext;
+use crate::image_viewer::ImageZoom;
+use crate::preview::in_preview_area;
+use crate::tab_zoom::{IMAGE_ZOOM_STEP, TAB_ZOOM, step_tab_zoom};
+use gpui::*;
+
+impl Humanboard {
+    /// Whether zoom shortcuts go to the preview rather than the canvas: the
+    /// mouse is over the panel or one of its editors has focus
+    pub(crate) fn zooms_preview_tab(&self, window: &Window) -> bool {
+        let Some(ref preview) = self.preview.panel else {
+            return false;
+        };
+        if matches!(
+            self.system.focus.active_context(),
+            FocusContext::Preview | FocusContext::CodeEditor
+        ) {
+            return true;
+        }
+        let mouse = window.mouse_position();
+        let size = window.bounds().size;
+        in_preview_area(
+            (f32::from(mouse.x), f32::from(mouse.y)),
+            (f32::from(size.width), f32::from(size.height)),
+            preview.split,
+            preview.size,
+        )
+    }
+
+    /// Zoom the focused preview tab one step in or out and remember the
+    /// level for its file
+    pub fn zoom_preview_tab(&mut self, zoom_in: bool, cx: &mut Context<Self>) {
+        let Some(tab) = self
+            .preview
+            .panel
+            .as_ref()
+            .and_then(|preview| preview.focused().active())
+        else {
+            return;
+        };
+        let Some(path) = tab.path().cloned() else {
+            return;
+        };
+
+        if let PreviewTab::Image { .. } = tab {
+            let factor = if zoom_in {
+                IMAGE_ZOOM_STEP
+            } else {
+                1.0 / IMAGE_ZOOM_STEP
+            };
+            self.zoom_preview_image(&path, factor, None, cx);
+            if let Some(PreviewTab::Image { view, .. }) = self.preview_image_tab_mut(&path) {
+                if let ImageZoom::Scale(scale) = view.zoom {
+                    TAB_ZOOM.set(&path, Some(scale));
+                }
+            }
+            return;
+        }
+
+        let zoom = step_tab_zoom(tab.meta().zoom.unwrap_or(1.0), zoom_in);
+        self.set_preview_tab_zoom(zoom, cx);
+    }
+
+    /// Show the focused preview tab at 100% (images fitted to the pane) and
+    /// forget its file's zoom
+    pub fn reset_preview_tab_zoom(&mut self, cx: &mut Context<Self>) {
+        let Some(tab) = self
+            .preview
+            .panel
+            .as_ref()
+            .and_then(|preview| preview.focused().active())
+        else {
+            return;
+        };
+        let Some(path) = tab.path().cloned() else {
+            return;
+        };
+
+        if let PreviewTab::Image { .. } = tab {
+            self.fit_preview_image(&path, cx);
+            TAB_ZOOM.set(&path, None);
+            return;
+        }
+        self.set_preview_tab_zoom(1.0, cx);
+    }
+
+    /// Apply a zoom level to the focused tab. Only markdown, code and PDF
+    /// tabs have one.
+    fn set_preview_tab_zoom(&mut self, zoom: f32, cx: &mut Context<Self>) {
+        let Some(tab) = self
+            .preview
+            .panel
+            .as_mut()
+            .and_then(|preview| preview.focused_mut().active_mut())
+        else {
+            return;
+        };
+        let path = match tab {
+            PreviewTab::Markdown { path, .. } | PreviewTab::Code { path, .. } => path.clone(),
+            PreviewTab::Pdf { path, webview, .. } => {
+                if let Some(webview) = webview {
+                    webview.set_zoom(zoom, cx);
+                }
+                path.clone()
+            }
+            _ => return,
+        };
+        let zoom = Some(zoom).filter(|zoom| (zoom - 1.0).abs() > f32::EPSILON);
+        tab.meta_mut().zoom = zoom;
+        TAB_ZOOM.set(&path, zoom);
+        cx.notify();
+    }
+}
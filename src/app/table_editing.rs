@@ -142,7 +142,10 @@ impl Humanboard {
                 }
 
                 // Mark as modified
-                board.push_history();
+                board.push_labeled_history(format!(
+                    "Edited cell {}",
+                    crate::undo_history::cell_name(row, col)
+                ));
                 let _ = board.flush_save();
                 
                 updated_ds_id = Some(ds_id);
@@ -166,19 +169,8 @@ impl Humanboard {
         use crate::app::PreviewTab;
         
         if let Some(ref mut preview) = self.preview.panel {
-            // Sync to left pane tabs
-            for tab in preview.tabs.iter_mut() {
-                if let PreviewTab::Table { data_source_id: id, table_state: Some(state), .. } = tab {
-                    if *id == data_source_id {
-                        state.update(cx, |table_state, _cx| {
-                            table_state.delegate_mut().set_data_source(std::sync::Arc::new(data_source.clone()));
-                        });
-                    }
-                }
-            }
-            
-            // Sync to right pane tabs
-            for tab in preview.right_tabs.iter_mut() {
+            // Sync to the tabs of every pane
+            for tab in preview.all_tabs_mut() {
                 if let PreviewTab::Table { data_source_id: id, table_state: Some(state), .. } = tab {
                     if *id == data_source_id {
                         state.update(cx, |table_state, _cx| {
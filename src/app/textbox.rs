@@ -5,12 +5,15 @@ use crate::focus::FocusContext;
 use crate::types::ItemContent;
 use gpui::*;
 use gpui_component::input::InputState;
+use std::path::Path;
 use std::time::{Duration, Instant};
+use tracing::error;
 
 impl Humanboard {
     // ==================== TextBox Editing Methods ====================
 
-    /// Start editing a textbox inline on the canvas
+    /// Start editing a textbox, or an expanded markdown card's document,
+    /// inline on the canvas
     pub fn start_textbox_editing(
         &mut self,
         item_id: u64,
@@ -20,20 +23,21 @@ impl Humanboard {
         // Clear any pending drag/resize state from the first click of the double-click
         self.canvas.input_state.reset();
 
-        // Get the current text from the item
+        // Get the current text from the item: a text box, or the document
+        // of an expanded markdown card
         let current_text = if let Some(ref board) = self.canvas.board {
-            board.get_item(item_id).and_then(|item| {
-                if let ItemContent::TextBox { text, .. } = &item.content {
-                    Some(text.clone())
-                } else {
-                    None
+            board.get_item(item_id).and_then(|item| match &item.content {
+                ItemContent::TextBox { text, .. } => Some((text.clone(), "plaintext")),
+                ItemContent::Markdown { content, .. } if item.is_expanded_markdown() => {
+                    Some((content.clone(), "markdown"))
                 }
+                _ => None,
             })
         } else {
             None
         };
 
-        if let Some(text) = current_text {
+        if let Some((text, language)) = current_text {
             // Count lines to position cursor at end
             let lines: Vec<&str> = text.lines().collect();
             let last_line = lines.len().saturating_sub(1) as u32;
@@ -42,7 +46,7 @@ impl Humanboard {
             // Create the input with multiline support (code_editor enables multiline)
             let input = cx.new(|cx| {
                 InputState::new(window, cx)
-                    .code_editor("plaintext") // Enable multiline editing
+                    .code_editor(language) // Enable multiline editing
                     .line_number(false) // No line numbers for textbox
                     .soft_wrap(true) // Wrap long lines
                     .default_value(text)
@@ -109,22 +113,7 @@ impl Humanboard {
         if let Some(item_id) = self.textbox.editing_id.take() {
             if let Some(input) = self.textbox.input.take() {
                 let new_text = input.read(cx).text().to_string();
-
-                if let Some(ref mut board) = self.canvas.board {
-                    if let Some(item) = board.get_item_mut(item_id) {
-                        if let ItemContent::TextBox { text, .. } = &mut item.content {
-                            *text = new_text;
-                        }
-                    }
-                    board.push_history();
-                    if let Err(e) = board.flush_save() {
-                        self.ui.toast_manager
-                            .push(crate::notifications::Toast::error(format!(
-                                "Save failed: {}",
-                                e
-                            )).with_action(crate::notifications::ToastAction::retry()));
-                    }
-                }
+                self.commit_textbox_text(item_id, new_text);
             }
         }
 
@@ -142,22 +131,7 @@ impl Humanboard {
         if let Some(item_id) = self.textbox.editing_id.take() {
             if let Some(input) = self.textbox.input.take() {
                 let new_text = input.read(cx).text().to_string();
-
-                if let Some(ref mut board) = self.canvas.board {
-                    if let Some(item) = board.get_item_mut(item_id) {
-                        if let ItemContent::TextBox { text, .. } = &mut item.content {
-                            *text = new_text;
-                        }
-                    }
-                    board.push_history();
-                    if let Err(e) = board.flush_save() {
-                        self.ui.toast_manager
-                            .push(crate::notifications::Toast::error(format!(
-                                "Save failed: {}",
-                                e
-                            )).with_action(crate::notifications::ToastAction::retry()));
-                    }
-                }
+                self.commit_textbox_text(item_id, new_text);
             }
         }
 
@@ -166,6 +140,65 @@ impl Humanboard {
         cx.notify();
     }
 
+    /// Store the edited text: in the text box, or in the file behind a
+    /// markdown card
+    fn commit_textbox_text(&mut self, item_id: u64, new_text: String) {
+        let markdown_path = self.canvas.board.as_ref().and_then(|board| {
+            board.get_item(item_id).and_then(|item| match &item.content {
+                ItemContent::Markdown { path, .. } => Some(path.clone()),
+                _ => None,
+            })
+        });
+        if let Some(path) = markdown_path {
+            self.save_markdown_card(&path, &new_text);
+            return;
+        }
+
+        if let Some(ref mut board) = self.canvas.board {
+            if let Some(item) = board.get_item_mut(item_id) {
+                if let ItemContent::TextBox { text, .. } = &mut item.content {
+                    *text = new_text;
+                }
+            }
+            self.canvas.text_layouts.invalidate(item_id);
+            board.push_labeled_history("Edited text");
+            if let Err(e) = board.flush_save() {
+                self.ui.toast_manager
+                    .push(crate::notifications::Toast::error(format!(
+                        "Save failed: {}",
+                        e
+                    )).with_action(crate::notifications::ToastAction::retry()));
+            }
+        }
+    }
+
+    /// Write a markdown card's edited document to its file and show it on
+    /// every card of the file
+    fn save_markdown_card(&mut self, path: &Path, new_content: &str) {
+        let unchanged = self.canvas.board.as_ref().is_some_and(|board| {
+            board.items.iter().any(|item| {
+                matches!(&item.content, ItemContent::Markdown { path: p, content, .. }
+                    if p == path && content == new_content)
+            })
+        });
+        if unchanged {
+            return;
+        }
+
+        match std::fs::write(path, new_content) {
+            Ok(()) => {
+                if let Some(ref mut board) = self.canvas.board {
+                    board.set_markdown_content(path, new_content);
+                }
+            }
+            Err(e) => {
+                let message = format!("Couldn't save {}: {}", path.display(), e);
+                error!("{}", message);
+                self.show_toast(crate::notifications::Toast::error(message));
+            }
+        }
+    }
+
     /// Cancel textbox editing without saving
     pub fn cancel_textbox_editing(&mut self, cx: &mut Context<Self>) {
         self.textbox.editing_id = None;
@@ -217,15 +250,15 @@ impl Humanboard {
         }
     }
 
-    pub fn pdf_zoom_in(&mut self, _cx: &mut Context<Self>) {
-        // WebView handles PDF zoom internally
+    pub fn pdf_zoom_in(&mut self, cx: &mut Context<Self>) {
+        self.zoom_preview_tab(true, cx);
     }
 
-    pub fn pdf_zoom_out(&mut self, _cx: &mut Context<Self>) {
-        // WebView handles PDF zoom internally
+    pub fn pdf_zoom_out(&mut self, cx: &mut Context<Self>) {
+        self.zoom_preview_tab(false, cx);
     }
 
-    pub fn pdf_zoom_reset(&mut self, _cx: &mut Context<Self>) {
-        // WebView handles PDF zoom internally
+    pub fn pdf_zoom_reset(&mut self, cx: &mut Context<Self>) {
+        self.reset_preview_tab_zoom(cx);
     }
 }
@@ -0,0 +1,159 @@
+//! Theme editor methods - making a custom theme from the current one in
+//! settings, applying it as colors are changed and saving it to disk
+
+use crate::app::{Humanboard, ThemeEditor};
+use crate::custom_themes::{
+    EditableColor, ThemeDraft, custom_themes_dir, save_theme, set_content_palette,
+};
+use crate::notifications::Toast;
+use crate::render::canvas::ContentTypeColors;
+use crate::settings::reload_themes;
+use gpui::*;
+use gpui_component::ActiveTheme as _;
+use gpui_component::input::{InputEvent, InputState};
+use gpui_component::theme::{Colorize, Theme};
+use std::collections::BTreeMap;
+use std::rc::Rc;
+
+impl Humanboard {
+    /// Open the theme editor with the colors of the current theme
+    pub fn open_theme_editor(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        if self.settings.theme_editor.is_some() {
+            return;
+        }
+        let theme = cx.theme();
+        let base = if theme.mode.is_dark() {
+            theme.dark_theme.as_ref().clone()
+        } else {
+            theme.light_theme.as_ref().clone()
+        };
+        let content_colors = ContentTypeColors::from_theme(theme);
+        let colors: BTreeMap<EditableColor, String> = EditableColor::all()
+            .into_iter()
+            .map(|color| {
+                let value = match color {
+                    EditableColor::Background => theme.background,
+                    EditableColor::Foreground => theme.foreground,
+                    EditableColor::Accent => theme.primary,
+                    EditableColor::Content(content) => content_colors.color(content),
+                };
+                (color, value.to_hex().to_lowercase())
+            })
+            .collect();
+        let draft = ThemeDraft::new(base, colors);
+
+        let name_input = cx.new(|cx| {
+            InputState::new(window, cx)
+                .placeholder("Theme name...")
+                .default_value(draft.name.clone())
+        });
+        cx.subscribe_in(
+            &name_input,
+            window,
+            |this, input, event: &InputEvent, _window, cx| {
+                if let InputEvent::Change = event {
+                    let name = input.read(cx).text().to_string();
+                    if let Some(ref mut editor) = this.settings.theme_editor {
+                        editor.draft.name = name;
+                        this.preview_theme_draft(cx);
+                    }
+                }
+            },
+        )
+        .detach();
+
+        let color_inputs = EditableColor::all()
+            .into_iter()
+            .map(|color| {
+                let hex = draft.colors.get(&color).cloned().unwrap_or_default();
+                let input = cx.new(|cx| InputState::new(window, cx).default_value(hex));
+                cx.subscribe_in(
+                    &input,
+                    window,
+                    move |this, input, event: &InputEvent, _window, cx| {
+                        if let InputEvent::Change = event {
+                            let hex = input.read(cx).text().to_string();
+                            this.set_theme_editor_color(color, &hex, cx);
+                        }
+                    },
+                )
+                .detach();
+                (color, input)
+            })
+            .collect();
+
+        self.settings.theme_editor = Some(ThemeEditor {
+            draft,
+            original: self.settings.data.theme.clone(),
+            name_input,
+            color_inputs,
+        });
+        self.preview_theme_draft(cx);
+    }
+
+    /// Change a color of the theme being edited, if `hex` is a color
+    pub fn set_theme_editor_color(
+        &mut self,
+        color: EditableColor,
+        hex: &str,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(ref mut editor) = self.settings.theme_editor else {
+            return;
+        };
+        if editor.draft.set_color(color, hex) {
+            self.preview_theme_draft(cx);
+        }
+    }
+
+    /// Apply the theme being edited to every window without saving it
+    fn preview_theme_draft(&mut self, cx: &mut Context<Self>) {
+        let Some(ref editor) = self.settings.theme_editor else {
+            return;
+        };
+        let config = Rc::new(editor.draft.config());
+        set_content_palette(&config.name, editor.draft.content_palette());
+
+        let theme = Theme::global_mut(cx);
+        theme.mode = config.mode;
+        theme.apply_config(&config);
+        cx.refresh_windows();
+    }
+
+    /// Save the theme being edited into the custom themes folder and
+    /// switch to it
+    pub fn save_theme_draft(&mut self, cx: &mut Context<Self>) {
+        let Some(ref editor) = self.settings.theme_editor else {
+            return;
+        };
+        let Some(dir) = custom_themes_dir() else {
+            self.show_toast(Toast::error("No folder to save themes in"));
+            cx.notify();
+            return;
+        };
+        let name = editor.draft.theme_name();
+
+        match save_theme(&dir, &editor.draft) {
+            Ok(_) => {
+                self.settings.theme_editor = None;
+                reload_themes();
+                self.set_theme(name.clone(), cx);
+                self.show_toast(Toast::success(format!("Saved theme \"{}\"", name)));
+            }
+            Err(e) => {
+                self.show_toast(Toast::error(format!("Couldn't save theme: {}", e)));
+            }
+        }
+        cx.notify();
+    }
+
+    /// Close the theme editor, going back to the theme used before it
+    pub fn cancel_theme_editor(&mut self, cx: &mut Context<Self>) {
+        let Some(editor) = self.settings.theme_editor.take() else {
+            return;
+        };
+        reload_themes();
+        self.set_theme(editor.original, cx);
+        cx.refresh_windows();
+    }
+}
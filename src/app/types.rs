@@ -1,11 +1,23 @@
 //! Types and enums used by the Humanboard application.
 
+use crate::app::PaneTree;
+use crate::code_symbols::CodeSymbol;
+use crate::data::RowOrder;
+use crate::data_table::DataTableState;
+use crate::image_cache::FileStamp;
+use crate::image_viewer::ImageView;
+use crate::preview_history::NavHistory;
+use crate::syntax_highlight::DiffHighlights;
+use crate::text_diff::{DiffBase, TextDiff};
 use crate::webviews::PdfWebView;
 use gpui::Point;
 use gpui::Pixels;
+use gpui::{Bounds, Entity, FocusHandle};
 use gpui_component::input::InputState;
-use gpui::Entity;
+use std::cell::Cell;
+use std::collections::HashSet;
 use std::path::PathBuf;
+use std::rc::Rc;
 use std::time::{Duration, Instant};
 
 /// The current view state of the application
@@ -34,6 +46,8 @@ pub enum CmdPaletteMode {
     #[default]
     Items, // Searching canvas items (includes tables by CSV name)
     Themes, // Selecting theme
+    Symbols, // Going to a symbol of the active code tab
+    Files, // Adding a file from the quick-open folders
 }
 
 /// Tab in the settings modal
@@ -41,9 +55,20 @@ pub enum CmdPaletteMode {
 pub enum SettingsTab {
     #[default]
     Appearance,
+    Boards,
+    Keybindings,
     Integrations,
 }
 
+/// What the board passphrase prompt is asking for
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum PassphraseMode {
+    /// Unlock an encrypted board to open it
+    Unlock,
+    /// Choose a passphrase to encrypt the current board
+    Encrypt,
+}
+
 /// Storage location options for boards
 #[derive(Clone, Debug, PartialEq, Default)]
 pub enum StorageLocation {
@@ -111,6 +136,29 @@ pub struct TabMeta {
     pub is_preview: bool,
     /// Pinned tabs resist close operations and stay at the left
     pub is_pinned: bool,
+    /// Font scale of text tabs, or zoom of PDF tabs; `None` at 100%
+    pub zoom: Option<f32>,
+}
+
+/// Rows of a data or table tab, a page at a time, sorted and filtered
+pub struct DataView {
+    /// Page, page size and sort, and the filter typed in
+    pub table: DataTableState,
+    /// Rows matching the filter, in sorted order
+    pub order: RowOrder,
+    /// Filter input above the rows
+    pub filter: Entity<InputState>,
+}
+
+/// The symbol outline beside a code tab
+#[derive(Default)]
+pub struct CodeOutline {
+    /// Symbols of the editor's text
+    pub symbols: Vec<CodeSymbol>,
+    /// Symbols closed so those nested in them are hidden, by index
+    pub collapsed: HashSet<usize>,
+    /// Hash of the text the symbols were found in
+    pub text_hash: u64,
 }
 
 /// A tab in the preview panel
@@ -134,6 +182,12 @@ pub enum PreviewTab {
         editing: bool,
         dirty: bool,
         editor: Option<Entity<InputState>>,
+        /// Stamp of the file when `content` was read or last saved
+        disk_stamp: Option<FileStamp>,
+        /// The file changed on disk while the tab had unsaved edits
+        conflict: bool,
+        /// The symbol outline sidebar, while it is shown
+        outline: Option<CodeOutline>,
         meta: TabMeta,
     },
     Table {
@@ -143,17 +197,76 @@ pub enum PreviewTab {
         name: String,
         /// Table state for gpui-component Table
         table_state: Option<gpui::Entity<gpui_component::table::TableState<crate::data::DataSourceDelegate>>>,
+        /// Editing the cells in a grid, rather than viewing the rows
+        editing: bool,
+        /// Rows shown when not editing, created with the table state
+        view: Option<DataView>,
+        meta: TabMeta,
+    },
+    /// A CSV or TSV file, loaded in the background into a lazy data source
+    Data {
+        path: PathBuf,
+        view: DataView,
+        meta: TabMeta,
+    },
+    Image {
+        path: PathBuf,
+        /// Zoom and pan of the viewer
+        view: ImageView,
+        /// Where the image area was last drawn, to map the mouse onto the image
+        viewport: Rc<Cell<Bounds<Pixels>>>,
+        /// Image pixel under the mouse, shown in the footer
+        hovered_pixel: Option<(u32, u32)>,
+        /// Last mouse position while the image is dragged around
+        pan_from: Option<Point<Pixels>>,
+        /// Focus for the arrow keys stepping through the board's images
+        focus: FocusHandle,
+        meta: TabMeta,
+    },
+    /// A text file compared line by line with another file or its snapshot
+    Diff {
+        /// The file compared, shown as the new side
+        path: PathBuf,
+        /// What it is compared against, shown as the old side
+        base: DiffBase,
+        /// Name of the old side: the other file, or how old the snapshot is
+        base_label: String,
+        /// Language the lines are highlighted as
+        language: String,
+        /// The comparison, or why it couldn't be made
+        diff: Result<TextDiff, String>,
+        /// Syntax highlights of the compared lines
+        highlights: DiffHighlights,
+        /// Lines side by side rather than one after another
+        side_by_side: bool,
         meta: TabMeta,
     },
 }
 
 impl PreviewTab {
+    /// An image tab, fitted to the pane
+    pub fn image(path: PathBuf, meta: TabMeta, cx: &mut gpui::App) -> Self {
+        PreviewTab::Image {
+            path,
+            view: ImageView::default(),
+            viewport: Rc::new(Cell::new(Bounds::default())),
+            hovered_pixel: None,
+            pan_from: None,
+            focus: cx.focus_handle(),
+            meta,
+        }
+    }
+
     pub fn path(&self) -> Option<&PathBuf> {
         match self {
             PreviewTab::Pdf { path, .. } => Some(path),
             PreviewTab::Markdown { path, .. } => Some(path),
             PreviewTab::Code { path, .. } => Some(path),
+            PreviewTab::Image { path, .. } => Some(path),
+            PreviewTab::Data { path, .. } => Some(path),
             PreviewTab::Table { .. } => None, // Tables don't have file paths
+            // Diffs don't stand in for their file when it is opened
+            PreviewTab::Diff { .. } => None,
         }
     }
 
@@ -177,12 +290,20 @@ impl PreviewTab {
                 .and_then(|n| n.to_str())
                 .unwrap_or("Untitled")
                 .to_string(),
-            PreviewTab::Code { path, .. } => path
+            PreviewTab::Code { path, .. }
+            | PreviewTab::Image { path, .. }
+            | PreviewTab::Data { path, .. } => path
                 .file_name()
                 .and_then(|n| n.to_str())
                 .unwrap_or("Untitled")
                 .to_string(),
             PreviewTab::Table { name, .. } => name.clone(),
+            PreviewTab::Diff { path, .. } => format!(
+                "Diff: {}",
+                path.file_name()
+                    .and_then(|n| n.to_str())
+                    .unwrap_or("Untitled")
+            ),
         }
     }
 
@@ -201,6 +322,9 @@ impl PreviewTab {
             PreviewTab::Markdown { meta, .. } => meta,
             PreviewTab::Code { meta, .. } => meta,
             PreviewTab::Table { meta, .. } => meta,
+            PreviewTab::Image { meta, .. } => meta,
+            PreviewTab::Data { meta, .. } => meta,
+            PreviewTab::Diff { meta, .. } => meta,
         }
     }
 
@@ -211,6 +335,9 @@ impl PreviewTab {
             PreviewTab::Markdown { meta, .. } => meta,
             PreviewTab::Code { meta, .. } => meta,
             PreviewTab::Table { meta, .. } => meta,
+            PreviewTab::Image { meta, .. } => meta,
+            PreviewTab::Data { meta, .. } => meta,
+            PreviewTab::Diff { meta, .. } => meta,
         }
     }
 
@@ -243,10 +370,22 @@ impl PreviewTab {
                 // Clear the editor entity
                 *editor = None;
             }
-            PreviewTab::Table { table_state, .. } => {
+            PreviewTab::Table {
+                table_state, view, ..
+            } => {
                 // Clear the table state entity
                 *table_state = None;
+                *view = None;
+            }
+            PreviewTab::Data { path, .. } => {
+                // Drop the loaded file
+                crate::data::DATA_FILES.forget(path);
             }
+            PreviewTab::Image { path, .. } => {
+                // Drop the decoded pixels
+                crate::image_viewer::IMAGE_PIXELS.forget(path);
+            }
+            PreviewTab::Diff { .. } => {}
         }
     }
 
@@ -266,14 +405,6 @@ impl PreviewTab {
     }
 }
 
-/// Which pane is focused in split view
-#[derive(Clone, Copy, PartialEq, Default, Debug)]
-pub enum FocusedPane {
-    #[default]
-    Left,
-    Right,
-}
-
 /// Drop zone for dragging tabs to create splits
 #[derive(Clone, Copy, PartialEq, Debug)]
 pub enum SplitDropZone {
@@ -283,62 +414,45 @@ pub enum SplitDropZone {
     Bottom,
 }
 
-/// State for the preview panel with tab management
-pub struct PreviewPanel {
-    // Left/primary pane
+/// A pane of the preview panel: its tabs and its back/forward history
+#[derive(Default)]
+pub struct PreviewPane {
     pub tabs: Vec<PreviewTab>,
     pub active_tab: usize,
-    pub back_stack: Vec<usize>,
-    pub forward_stack: Vec<usize>,
-    pub closed_tabs: Vec<PreviewTab>,
+    pub history: NavHistory,
+}
 
-    // Right/secondary pane (only used when is_pane_split is true)
-    pub right_tabs: Vec<PreviewTab>,
-    pub right_active_tab: usize,
-    pub right_back_stack: Vec<usize>,
-    pub right_forward_stack: Vec<usize>,
+/// State for the preview panel with tab management
+pub struct PreviewPanel {
+    // Panes, never empty. The first is the primary pane new tabs open in.
+    pub panes: Vec<PreviewPane>,
+    pub layout: PaneTree,      // How the panes are split, see `preview_panes`
+    pub focused_pane: usize,   // Which pane has focus
+    pub closed_tabs: Vec<PreviewTab>,
 
     // Split state
     pub split: SplitDirection, // Split with canvas (vertical/horizontal)
     pub size: f32,             // Panel size (0.0 to 1.0)
-    pub is_pane_split: bool,   // Whether panel itself is split into two panes
-    pub pane_split_horizontal: bool, // True = top/bottom, False = left/right
-    pub focused_pane: FocusedPane, // Which pane has focus
-    pub pane_ratio: f32,       // Ratio between left/right panes (0.5 = equal)
 }
 
 impl PreviewPanel {
     pub fn new(split: SplitDirection, size: f32) -> Self {
         Self {
-            tabs: Vec::new(),
-            active_tab: 0,
-            back_stack: Vec::new(),
-            forward_stack: Vec::new(),
+            panes: vec![PreviewPane::default()],
+            layout: PaneTree::default(),
+            focused_pane: 0,
             closed_tabs: Vec::new(),
 
-            right_tabs: Vec::new(),
-            right_active_tab: 0,
-            right_back_stack: Vec::new(),
-            right_forward_stack: Vec::new(),
-
             split,
             size,
-            is_pane_split: false,
-            pane_split_horizontal: false,
-            focused_pane: FocusedPane::Left,
-            pane_ratio: 0.5,
         }
     }
 
     /// Clean up all resources before destroying the panel.
     /// This should be called before dropping the PreviewPanel to prevent memory leaks.
     pub fn cleanup(&mut self, cx: &mut gpui::App) {
-        // Clean up all tabs in the left pane
-        for tab in &mut self.tabs {
-            tab.cleanup(cx);
-        }
-        // Clean up all tabs in the right pane
-        for tab in &mut self.right_tabs {
+        // Clean up all tabs in every pane
+        for tab in self.all_tabs_mut() {
             tab.cleanup(cx);
         }
         // Clean up all closed tabs
@@ -352,6 +466,8 @@ impl PreviewPanel {
 pub struct PanAnimation {
     pub start_offset: Point<Pixels>,
     pub target_offset: Point<Pixels>,
+    pub start_zoom: f32,
+    pub target_zoom: f32,
     pub start_time: Instant,
     pub duration: Duration,
 }
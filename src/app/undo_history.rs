@@ -0,0 +1,48 @@
+//! Undo history panel methods - listing the board's undo steps and jumping
+//! back or forward to any of them
+
+use super::state::UndoHistoryPanel;
+use crate::app::Humanboard;
+use crate::notifications::Toast;
+use gpui::*;
+
+impl Humanboard {
+    /// Show or hide the History side panel
+    pub fn toggle_undo_history(&mut self, cx: &mut Context<Self>) {
+        if self.ui.undo_history.is_some() {
+            self.ui.undo_history = None;
+        } else if self.canvas.board.is_some() {
+            // Both panels take the right edge
+            self.ui.outline = None;
+            self.ui.undo_history = Some(UndoHistoryPanel::default());
+        }
+        cx.notify();
+    }
+
+    /// Fold the History panel down to its header, or unfold it
+    pub fn toggle_undo_history_collapsed(&mut self, cx: &mut Context<Self>) {
+        if let Some(panel) = self.ui.undo_history.as_mut() {
+            panel.collapsed = !panel.collapsed;
+            cx.notify();
+        }
+    }
+
+    /// Undo or redo until the board is at `position` of its history
+    pub fn jump_to_undo_step(&mut self, position: usize, cx: &mut Context<Self>) {
+        let Some(ref mut board) = self.canvas.board else {
+            return;
+        };
+        if board.history_position() == position {
+            return;
+        }
+
+        let reached = board.jump_to_history(position);
+        self.canvas.selected_items.clear();
+        if !reached {
+            self.show_toast(Toast::warning(
+                "Couldn't go back further - this change has no earlier state",
+            ));
+        }
+        cx.notify();
+    }
+}
@@ -0,0 +1,237 @@
+//! Smart URL paste methods - adding a pasted URL as what it points at,
+//! fetching its page or image in the background, and the chooser shown
+//! beside it to make it something else
+
+use super::state::{UrlFetch, UrlFetched, UrlPasteChooser};
+use crate::app::Humanboard;
+use crate::board_assets::pasted_image_extension;
+use crate::constants::{DOCK_WIDTH, HEADER_HEIGHT};
+use crate::notifications::Toast;
+use crate::types::ItemContent;
+use crate::url_paste::{self, UrlPasteKind};
+use gpui::*;
+use std::time::{Duration, Instant};
+use tracing::debug;
+
+/// How long the chooser stays beside a pasted URL
+const CHOOSER_TIMEOUT: Duration = Duration::from_secs(6);
+
+impl Humanboard {
+    /// Add a pasted URL at `position` on screen as what it was detected as,
+    /// and offer the other kinds beside it
+    pub(crate) fn paste_url(&mut self, url: &str, position: Point<Pixels>, cx: &mut Context<Self>) {
+        let Some(ref mut board) = self.canvas.board else {
+            return;
+        };
+        let kind = url_paste::detect(url);
+        let id = match kind {
+            UrlPasteKind::Embed | UrlPasteKind::Link => board.add_url(url, position),
+            // Images show as a bookmark until they have been downloaded
+            UrlPasteKind::Image | UrlPasteKind::Bookmark => board.add_bookmark(url, position),
+        };
+        self.fetch_pasted_url(id, url, kind, false, cx);
+
+        self.canvas.selected_items.clear();
+        self.canvas.selected_items.insert(id);
+        self.ui.url_paste = Some(UrlPasteChooser {
+            item_id: id,
+            url: url.to_string(),
+            kinds: url_paste::choices(url),
+            current: kind,
+            opened_at: Instant::now(),
+        });
+        cx.notify();
+    }
+
+    /// Make the pasted item `kind` instead (picked in the chooser). Images
+    /// replace the item once they have been downloaded.
+    pub fn choose_url_paste_kind(&mut self, kind: UrlPasteKind, cx: &mut Context<Self>) {
+        let Some(chooser) = self.ui.url_paste.take() else {
+            return;
+        };
+        cx.notify();
+        if kind == chooser.current {
+            return;
+        }
+        let Some(ref mut board) = self.canvas.board else {
+            return;
+        };
+
+        let (id, url) = (chooser.item_id, chooser.url);
+        let was_image = chooser.current == UrlPasteKind::Image;
+        if was_image {
+            self.canvas.declined_url_images.insert(id);
+        }
+        let content = match kind {
+            UrlPasteKind::Embed => url_paste::embed_content(&url),
+            // Images wait for their download as a bookmark already
+            UrlPasteKind::Bookmark if was_image && is_bookmark(board, id) => None,
+            UrlPasteKind::Bookmark => Some(url_paste::bare_bookmark(&url)),
+            UrlPasteKind::Link => Some(ItemContent::Link(url.clone())),
+            UrlPasteKind::Image => None,
+        };
+        if let Some(content) = content {
+            board.replace_item_content(id, content);
+        }
+        self.fetch_pasted_url(id, &url, kind, true, cx);
+    }
+
+    /// Close the chooser, leaving the pasted item as it is
+    pub fn dismiss_url_paste_chooser(&mut self, cx: &mut Context<Self>) {
+        if self.ui.url_paste.take().is_some() {
+            cx.notify();
+        }
+    }
+
+    /// The open chooser, with where it goes in the window: under the left
+    /// edge of the pasted item. None if the item is gone.
+    pub(crate) fn url_paste_chooser_anchor(&self) -> Option<(&UrlPasteChooser, Point<Pixels>)> {
+        let chooser = self.ui.url_paste.as_ref()?;
+        let board = self.canvas.board.as_ref()?;
+        let item = board.get_item(chooser.item_id)?;
+        let bottom_left = board.canvas_to_screen(point(
+            px(item.position.0),
+            px(item.position.1 + item.size.1),
+        ));
+        Some((
+            chooser,
+            point(
+                bottom_left.x + px(DOCK_WIDTH),
+                bottom_left.y + px(HEADER_HEIGHT),
+            ),
+        ))
+    }
+
+    /// Fetch what a pasted item of `kind` needs: the page of a bookmark or
+    /// the image of an image. Other kinds need nothing.
+    fn fetch_pasted_url(
+        &mut self,
+        item_id: u64,
+        url: &str,
+        kind: UrlPasteKind,
+        replace: bool,
+        cx: &mut Context<Self>,
+    ) {
+        if !matches!(kind, UrlPasteKind::Image | UrlPasteKind::Bookmark) {
+            return;
+        }
+        let Some(ref board) = self.canvas.board else {
+            return;
+        };
+
+        let fetch = UrlFetch {
+            board_id: board.id.clone(),
+            item_id,
+            url: url.to_string(),
+            kind,
+            replace,
+            result: Err(String::new()),
+        };
+        let tx = self.canvas.url_fetch_tx.clone();
+        self.canvas.url_fetches += 1;
+        cx.background_executor()
+            .spawn(async move {
+                let result = if fetch.kind == UrlPasteKind::Image {
+                    url_paste::download_image(&fetch.url).map(UrlFetched::Image)
+                } else {
+                    url_paste::fetch_page_details(&fetch.url).map(UrlFetched::Page)
+                };
+                let _ = tx.send(UrlFetch { result, ..fetch });
+            })
+            .detach();
+    }
+
+    /// Fill in pasted items whose page or image has arrived, and close the
+    /// chooser once it has been shown long enough (called from render).
+    ///
+    /// Returns true while fetches are running or the chooser is open, so the
+    /// caller keeps rendering.
+    pub(crate) fn poll_url_pastes(&mut self, cx: &mut Context<Self>) -> bool {
+        let mut changed = false;
+        while let Ok(fetch) = self.canvas.url_fetch_rx.try_recv() {
+            self.canvas.url_fetches = self.canvas.url_fetches.saturating_sub(1);
+            self.apply_url_fetch(fetch, cx);
+            changed = true;
+        }
+
+        if self
+            .ui
+            .url_paste
+            .as_ref()
+            .is_some_and(|chooser| chooser.opened_at.elapsed() >= CHOOSER_TIMEOUT)
+        {
+            self.ui.url_paste = None;
+            changed = true;
+        }
+
+        if changed {
+            cx.notify();
+        }
+        self.canvas.url_fetches > 0 || self.ui.url_paste.is_some()
+    }
+
+    fn apply_url_fetch(&mut self, fetch: UrlFetch, cx: &mut Context<Self>) {
+        let Some(ref mut board) = self.canvas.board else {
+            return;
+        };
+        // The user may have switched boards while this was running
+        if board.id != fetch.board_id {
+            return;
+        }
+
+        let (id, url) = (fetch.item_id, fetch.url.as_str());
+        if fetch.kind == UrlPasteKind::Image
+            && !fetch.replace
+            && self.canvas.declined_url_images.remove(&id)
+        {
+            return;
+        }
+        let error = match fetch.result {
+            Ok(UrlFetched::Page(details)) => {
+                board.fill_in_bookmark(id, url, details.bookmark(url));
+                return;
+            }
+            Ok(UrlFetched::Image(bytes)) => {
+                let stored = match pasted_image_extension(&bytes) {
+                    Some(extension) => board
+                        .store_pasted_file(extension, &bytes)
+                        .map_err(|e| e.to_string()),
+                    None => Err("it isn't an image".to_string()),
+                };
+                match stored {
+                    Ok(path) if fetch.replace => {
+                        board.replace_item_content(id, ItemContent::Image(path));
+                        return;
+                    }
+                    Ok(path) => {
+                        board.fill_in_bookmark(id, url, ItemContent::Image(path));
+                        return;
+                    }
+                    Err(e) => e,
+                }
+            }
+            // A bookmark without details still links to its page
+            Err(e) if fetch.kind == UrlPasteKind::Bookmark => {
+                debug!("Couldn't fetch details of {}: {}", url, e);
+                return;
+            }
+            Err(e) => e,
+        };
+
+        self.show_toast(Toast::warning(format!(
+            "Couldn't download the image: {}",
+            error
+        )));
+        // A detected image stays a bookmark, so give it the page's details
+        if !fetch.replace {
+            self.fetch_pasted_url(id, &fetch.url, UrlPasteKind::Bookmark, false, cx);
+        }
+    }
+}
+
+/// Whether the item with `id` is a bookmark card
+fn is_bookmark(board: &crate::board::Board, id: u64) -> bool {
+    board
+        .get_item(id)
+        .is_some_and(|item| matches!(item.content, ItemContent::Bookmark { .. }))
+}
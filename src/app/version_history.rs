@@ -0,0 +1,146 @@
+//! Version history methods - browsing, previewing, restoring and deleting board
+//! versions
+
+use super::state::VersionHistoryModal;
+use crate::app::Humanboard;
+use crate::board_versions::VersionSummary;
+use crate::notifications::Toast;
+use gpui::*;
+
+impl Humanboard {
+    /// Open the version history browser for the current board
+    pub fn show_version_history(&mut self, cx: &mut Context<Self>) {
+        let Some(ref board) = self.canvas.board else {
+            return;
+        };
+
+        let mut modal = VersionHistoryModal::new(board.list_versions());
+        if modal.versions.is_empty() {
+            self.show_toast(Toast::info("No saved versions of this board yet"));
+            cx.notify();
+            return;
+        }
+
+        // Preview the most recent version right away
+        modal.selected = Some(0);
+        self.ui.version_history = Some(modal);
+        self.load_version_preview(0);
+        cx.notify();
+    }
+
+    /// Close the version history browser
+    pub fn close_version_history(&mut self, cx: &mut Context<Self>) {
+        self.ui.version_history = None;
+        cx.notify();
+    }
+
+    /// Toggle the version history browser
+    pub fn toggle_version_history(&mut self, cx: &mut Context<Self>) {
+        if self.ui.version_history.is_some() {
+            self.close_version_history(cx);
+        } else {
+            self.show_version_history(cx);
+        }
+    }
+
+    /// Select a version in the browser and load its preview
+    pub fn select_version(&mut self, index: usize, cx: &mut Context<Self>) {
+        if let Some(ref mut modal) = self.ui.version_history {
+            if index < modal.versions.len() && modal.selected != Some(index) {
+                modal.selected = Some(index);
+                self.load_version_preview(index);
+                cx.notify();
+            }
+        }
+    }
+
+    fn load_version_preview(&mut self, index: usize) {
+        let Some(ref mut modal) = self.ui.version_history else {
+            return;
+        };
+        let Some(version) = modal.versions.get(index) else {
+            return;
+        };
+
+        match version.load() {
+            Ok(state) => {
+                modal.preview = Some(VersionSummary::from_state(&state));
+                modal.selected_state = Some(state);
+                modal.preview_error = None;
+            }
+            Err(e) => {
+                modal.preview = None;
+                modal.selected_state = None;
+                modal.preview_error = Some(e.to_string());
+            }
+        }
+    }
+
+    /// Restore the selected version into the current board (undoable)
+    pub fn restore_selected_version(&mut self, cx: &mut Context<Self>) {
+        let Some(modal) = self.ui.version_history.take() else {
+            return;
+        };
+        let Some(state) = modal.selected_state else {
+            self.ui.version_history = None;
+            cx.notify();
+            return;
+        };
+        let age = modal
+            .selected
+            .and_then(|i| modal.versions.get(i))
+            .map(|v| v.formatted_age())
+            .unwrap_or_default();
+
+        if let Some(ref mut board) = self.canvas.board {
+            board.restore_version(&state);
+            self.canvas.selected_items.clear();
+
+            if let Err(e) = board.flush_save() {
+                self.ui.toast_manager.push(
+                    Toast::error(format!("Save failed: {}", e))
+                        .with_action(crate::notifications::ToastAction::retry()),
+                );
+            } else {
+                self.ui
+                    .toast_manager
+                    .push(Toast::success(format!("Restored version from {}", age)));
+            }
+        }
+        cx.notify();
+    }
+
+    /// Delete the selected version from disk (checkpoints are only removed
+    /// this way)
+    pub fn delete_selected_version(&mut self, cx: &mut Context<Self>) {
+        let Some(ref mut modal) = self.ui.version_history else {
+            return;
+        };
+        let Some(index) = modal.selected.filter(|&i| i < modal.versions.len()) else {
+            return;
+        };
+
+        let version = &modal.versions[index];
+        let title = version.title();
+        if let Err(e) = version.delete() {
+            self.ui
+                .toast_manager
+                .push(Toast::error(format!("Couldn't delete version: {}", e)));
+            cx.notify();
+            return;
+        }
+        modal.versions.remove(index);
+        self.ui
+            .toast_manager
+            .push(Toast::success(format!("Deleted version {}", title)));
+
+        if modal.versions.is_empty() {
+            self.ui.version_history = None;
+        } else {
+            let index = index.min(modal.versions.len() - 1);
+            modal.selected = Some(index);
+            self.load_version_preview(index);
+        }
+        cx.notify();
+    }
+}
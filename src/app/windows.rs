@@ -0,0 +1,85 @@
+//! Window management - opening additional windows and scoping boards per window.
+//!
+//! Every window owns its own `Humanboard` entity, so webviews and their media
+//! servers live and die with the window that created them. Which board is
+//! open where is coordinated through the board index registry.
+
+use super::Humanboard;
+use crate::board_index::BoardIndex;
+use anyhow::Context as _;
+use gpui::*;
+
+/// Offset between cascaded windows
+const WINDOW_CASCADE_OFFSET: f32 = 30.0;
+
+/// Build window options, cascading from the default origin by `index` steps.
+pub fn window_options(index: usize) -> WindowOptions {
+    let offset = index as f32 * WINDOW_CASCADE_OFFSET;
+    WindowOptions {
+        window_bounds: Some(WindowBounds::Windowed(Bounds {
+            origin: Point::new(px(100.0 + offset), px(100.0 + offset)),
+            size: Size {
+                width: px(1400.0),
+                height: px(900.0),
+            },
+        })),
+        titlebar: Some(TitlebarOptions {
+            title: Some("Humanboard".into()),
+            appears_transparent: true,
+            ..Default::default()
+        }),
+        ..Default::default()
+    }
+}
+
+/// Open a new application window
+pub fn open_window(cx: &mut App) -> anyhow::Result<AnyWindowHandle> {
+    let options = window_options(cx.windows().len());
+    let handle = cx
+        .open_window(options, |window, cx| {
+            let app_view = cx.new(|cx| Humanboard::new(window, cx));
+            cx.new(|cx| gpui_component::Root::new(app_view, window, cx))
+        })
+        .context("Failed to open window")?;
+    Ok(handle.into())
+}
+
+impl Humanboard {
+    /// Claim a board for this window, or bring its existing window forward.
+    ///
+    /// Returns false if the board is already open in another window.
+    pub(crate) fn claim_board_for_window(
+        &mut self,
+        board_id: &str,
+        cx: &mut Context<Self>,
+    ) -> bool {
+        let window_id = self.system.window_id;
+        match BoardIndex::claim_open_board(board_id, window_id) {
+            Ok(()) => true,
+            Err(owner) => {
+                let handle = cx
+                    .windows()
+                    .into_iter()
+                    .find(|w| w.window_id().as_u64() == owner);
+                match handle {
+                    Some(handle) => {
+                        let _ = handle.update(cx, |_, window, _| window.activate_window());
+                        false
+                    }
+                    // Owner window is gone - take the board over
+                    None => {
+                        BoardIndex::release_open_board(board_id, owner);
+                        BoardIndex::claim_open_board(board_id, window_id).is_ok()
+                    }
+                }
+            }
+        }
+    }
+
+    /// Release the current board's claim (leaving the board or closing the window)
+    pub(crate) fn release_board_for_window(&mut self) {
+        if let Some(ref board) = self.canvas.board {
+            BoardIndex::release_open_board(&board.id, self.system.window_id);
+        }
+    }
+}
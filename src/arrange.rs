@@ -0,0 +1,238 @@
+//! Align, distribute and restack - arranging selected items relative to each other.
+//!
+//! Aligning lines up an edge or center of every item with the matching edge
+//! or center of the selection's bounding box. Distributing makes the gaps
+//! between neighboring items equal along an axis, keeping the overall extent
+//! of the selection. Restacking moves items up or down the z-order, which is
+//! the order of the board's item list (back to front).
+//!
+//! Laying out a grid places several new items (e.g. files dropped together)
+//! in rows near a point instead of stacking them on top of each other.
+//!
+//! Functions here only compute new positions or orders; callers apply them
+//! (e.g. with `Board::move_items`) so the whole arrangement is a single undo step.
+
+use crate::types::CanvasItem;
+use std::collections::HashSet;
+
+/// Edge or center to align items on
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AlignEdge {
+    Left,
+    /// Horizontal centers (items line up in a column)
+    Center,
+    Right,
+    Top,
+    /// Vertical centers (items line up in a row)
+    Middle,
+    Bottom,
+}
+
+impl AlignEdge {
+    pub fn label(&self) -> &'static str {
+        match self {
+            AlignEdge::Left => "Align Left",
+            AlignEdge::Center => "Align Center",
+            AlignEdge::Right => "Align Right",
+            AlignEdge::Top => "Align Top",
+            AlignEdge::Middle => "Align Middle",
+            AlignEdge::Bottom => "Align Bottom",
+        }
+    }
+}
+
+/// Axis to distribute items along
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DistributeAxis {
+    Horizontal,
+    Vertical,
+}
+
+impl DistributeAxis {
+    pub fn label(&self) -> &'static str {
+        match self {
+            DistributeAxis::Horizontal => "Distribute Horizontally",
+            DistributeAxis::Vertical => "Distribute Vertically",
+        }
+    }
+}
+
+/// Minimum number of items needed to align
+pub const MIN_ALIGN_ITEMS: usize = 2;
+
+/// Minimum number of items needed to distribute
+pub const MIN_DISTRIBUTE_ITEMS: usize = 3;
+
+/// New positions that align `items` on `edge`
+pub fn align_items(items: &[&CanvasItem], edge: AlignEdge) -> Vec<(u64, (f32, f32))> {
+    if items.len() < MIN_ALIGN_ITEMS {
+        return Vec::new();
+    }
+
+    let min_x = items
+        .iter()
+        .map(|i| i.position.0)
+        .fold(f32::INFINITY, f32::min);
+    let min_y = items
+        .iter()
+        .map(|i| i.position.1)
+        .fold(f32::INFINITY, f32::min);
+    let max_x = items
+        .iter()
+        .map(|i| i.position.0 + i.size.0)
+        .fold(f32::NEG_INFINITY, f32::max);
+    let max_y = items
+        .iter()
+        .map(|i| i.position.1 + i.size.1)
+        .fold(f32::NEG_INFINITY, f32::max);
+    let center_x = (min_x + max_x) / 2.0;
+    let center_y = (min_y + max_y) / 2.0;
+
+    items
+        .iter()
+        .map(|item| {
+            let (x, y) = item.position;
+            let (w, h) = item.size;
+            let position = match edge {
+                AlignEdge::Left => (min_x, y),
+                AlignEdge::Center => (center_x - w / 2.0, y),
+                AlignEdge::Right => (max_x - w, y),
+                AlignEdge::Top => (x, min_y),
+                AlignEdge::Middle => (x, center_y - h / 2.0),
+                AlignEdge::Bottom => (x, max_y - h),
+            };
+            (item.id, position)
+        })
+        .collect()
+}
+
+/// New positions that space `items` evenly along `axis`.
+///
+/// Items are ordered by their leading edge and laid out one after another
+/// with equal gaps, from the selection's leading edge to its trailing edge.
+pub fn distribute_items(items: &[&CanvasItem], axis: DistributeAxis) -> Vec<(u64, (f32, f32))> {
+    if items.len() < MIN_DISTRIBUTE_ITEMS {
+        return Vec::new();
+    }
+
+    let start = |item: &CanvasItem| match axis {
+        DistributeAxis::Horizontal => item.position.0,
+        DistributeAxis::Vertical => item.position.1,
+    };
+    let length = |item: &CanvasItem| match axis {
+        DistributeAxis::Horizontal => item.size.0,
+        DistributeAxis::Vertical => item.size.1,
+    };
+
+    let mut sorted: Vec<&CanvasItem> = items.to_vec();
+    sorted.sort_by(|a, b| start(a).total_cmp(&start(b)).then(a.id.cmp(&b.id)));
+
+    let first = sorted[0];
+    let span_start = start(first);
+    let span_end = sorted
+        .iter()
+        .map(|item| start(item) + length(item))
+        .fold(f32::NEG_INFINITY, f32::max);
+    let total_length: f32 = sorted.iter().map(|item| length(item)).sum();
+    let gap = (span_end - span_start - total_length) / (sorted.len() - 1) as f32;
+
+    let mut cursor = span_start;
+    sorted
+        .iter()
+        .map(|item| {
+            let position = match axis {
+                DistributeAxis::Horizontal => (cursor, item.position.1),
+                DistributeAxis::Vertical => (item.position.0, cursor),
+            };
+            cursor += length(item) + gap;
+            (item.id, position)
+        })
+        .collect()
+}
+
+/// Space between items laid out by [`grid_layout`]
+pub const GRID_GAP: f32 = 24.0;
+
+/// Number of columns [`grid_layout`] uses for `count` items: a single row
+/// for up to three, otherwise about as many columns as rows
+pub fn grid_columns(count: usize) -> usize {
+    if count <= 3 {
+        return count.max(1);
+    }
+    (count as f64).sqrt().ceil() as usize
+}
+
+/// Positions for items of the given sizes laid out in a grid whose top-left
+/// corner is `origin`, in the order given.
+///
+/// Each row is as tall as its tallest item, so items of different sizes
+/// never overlap; items are `GRID_GAP` apart.
+pub fn grid_layout(sizes: &[(f32, f32)], origin: (f32, f32)) -> Vec<(f32, f32)> {
+    let columns = grid_columns(sizes.len());
+    let mut positions = Vec::with_capacity(sizes.len());
+    let mut y = origin.1;
+    for row in sizes.chunks(columns) {
+        let mut x = origin.0;
+        for size in row {
+            positions.push((x, y));
+            x += size.0 + GRID_GAP;
+        }
+        let height = row.iter().map(|size| size.1).fold(0.0, f32::max);
+        y += height + GRID_GAP;
+    }
+    positions
+}
+
+/// Stacking change for the selected items
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ZOrder {
+    /// Above every other item
+    BringToFront,
+    /// One step up, past the next unselected item above
+    BringForward,
+    /// One step down, past the next unselected item below
+    SendBackward,
+    /// Below every other item
+    SendToBack,
+}
+
+impl ZOrder {
+    pub fn label(&self) -> &'static str {
+        match self {
+            ZOrder::BringToFront => "Bring to Front",
+            ZOrder::BringForward => "Bring Forward",
+            ZOrder::SendBackward => "Send Backward",
+            ZOrder::SendToBack => "Send to Back",
+        }
+    }
+}
+
+/// New back-to-front order of `order` after restacking the `selected` items.
+///
+/// Selected items keep their order relative to each other.
+pub fn reorder(order: &[u64], selected: &HashSet<u64>, z_order: ZOrder) -> Vec<u64> {
+    let (picked, rest): (Vec<u64>, Vec<u64>) = order.iter().partition(|id| selected.contains(id));
+    match z_order {
+        ZOrder::BringToFront => rest.into_iter().chain(picked).collect(),
+        ZOrder::SendToBack => picked.into_iter().chain(rest).collect(),
+        ZOrder::BringForward => {
+            // Walk from the top, moving each selected item above its unselected neighbor
+            let mut result = order.to_vec();
+            for i in (0..result.len().saturating_sub(1)).rev() {
+                if selected.contains(&result[i]) && !selected.contains(&result[i + 1]) {
+                    result.swap(i, i + 1);
+                }
+            }
+            result
+        }
+        ZOrder::SendBackward => {
+            let mut result = order.to_vec();
+            for i in 1..result.len() {
+                if selected.contains(&result[i]) && !selected.contains(&result[i - 1]) {
+                    result.swap(i, i - 1);
+                }
+            }
+            result
+        }
+    }
+}
@@ -0,0 +1,211 @@
+//! Tags and album art of audio files, for audio cards.
+//!
+//! Audio cards are labeled with the title and artist from the file's tags
+//! (ID3, Vorbis comments, MP4 atoms, ...) instead of its file name, with the
+//! embedded album art next to them. Tags are read once on the background
+//! executor. Album art is written to a temp directory keyed by a hash of
+//! the file's path, like PDF thumbnails, so cards show it as a plain image.
+//!
+//! Each window keeps an [`AudioMetadataCache`] next to its other media
+//! caches and hands the tags to whatever shows them: the cards, the audio
+//! players and item labels through [`ItemContent::display_name_with`].
+//!
+//! [`ItemContent::display_name_with`]: crate::types::ItemContent::display_name_with
+
+use crate::background::BackgroundExecutor;
+use crate::error::MediaError;
+use lofty::{Accessor, PictureType, Probe, TaggedFileExt};
+use parking_lot::Mutex;
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tempfile::NamedTempFile;
+use tracing::{debug, warn};
+
+/// Tags of an audio file
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct AudioMetadata {
+    pub title: Option<String>,
+    pub artist: Option<String>,
+    pub album: Option<String>,
+    /// Cached album art image, if the file has any
+    pub art: Option<PathBuf>,
+}
+
+impl AudioMetadata {
+    /// Label for the card, e.g. `Title — Artist`, or `None` without a title
+    pub fn label(&self) -> Option<String> {
+        let title = non_empty(&self.title)?;
+        match non_empty(&self.artist) {
+            Some(artist) => Some(format!("{} — {}", title, artist)),
+            None => Some(title.to_string()),
+        }
+    }
+}
+
+fn non_empty(tag: &Option<String>) -> Option<&str> {
+    tag.as_deref().map(str::trim).filter(|s| !s.is_empty())
+}
+
+/// Read the tags of an audio file, caching its album art
+pub fn read_audio_metadata(path: &Path) -> Result<AudioMetadata, MediaError> {
+    let tagged = Probe::open(path)
+        .and_then(|probe| probe.read())
+        .map_err(|e| MediaError::AudioDecode(e.to_string()))?;
+    let Some(tag) = tagged.primary_tag().or_else(|| tagged.first_tag()) else {
+        return Ok(AudioMetadata::default());
+    };
+
+    // Prefer the front cover, fall back to any picture
+    let art = tag
+        .pictures()
+        .iter()
+        .find(|p| p.pic_type() == PictureType::CoverFront)
+        .or_else(|| tag.pictures().first())
+        .and_then(|p| {
+            let mime = p.mime_type().map(|m| m.as_str()).unwrap_or("image/jpeg");
+            cache_album_art(path, art_extension(mime)?, p.data())
+        });
+
+    Ok(AudioMetadata {
+        title: tag.title().map(|s| s.to_string()),
+        artist: tag.artist().map(|s| s.to_string()),
+        album: tag.album().map(|s| s.to_string()),
+        art,
+    })
+}
+
+/// File extension for an album art MIME type, or `None` for types cards
+/// can't show
+pub fn art_extension(mime: &str) -> Option<&'static str> {
+    match mime {
+        "image/jpeg" | "image/jpg" => Some("jpg"),
+        "image/png" => Some("png"),
+        "image/gif" => Some("gif"),
+        "image/webp" => Some("webp"),
+        "image/bmp" => Some("bmp"),
+        _ => None,
+    }
+}
+
+/// Write album art to the temp directory, named after a hash of the audio
+/// file's canonical path
+fn cache_album_art(audio_path: &Path, ext: &str, data: &[u8]) -> Option<PathBuf> {
+    let art_dir = std::env::temp_dir().join("humanboard").join("album_art");
+    std::fs::create_dir_all(&art_dir).ok()?;
+
+    let canonical_path = audio_path
+        .canonicalize()
+        .unwrap_or_else(|_| audio_path.to_path_buf());
+    let mut hasher = Sha256::new();
+    hasher.update(canonical_path.to_string_lossy().as_bytes());
+    let path_hash = format!("{:x}", hasher.finalize());
+    let art_path = art_dir.join(format!("{}_art.{}", &path_hash[..16], ext));
+
+    // Atomic write, as for PDF thumbnails
+    let mut temp_file = NamedTempFile::new_in(&art_dir).ok()?;
+    if let Err(e) = temp_file.write_all(data) {
+        warn!("Failed to write album art for {:?}: {}", audio_path, e);
+        return None;
+    }
+    if let Err(e) = temp_file.persist(&art_path) {
+        warn!("Failed to persist album art to {:?}: {}", art_path, e);
+        return None;
+    }
+    debug!("Cached album art: {:?}", art_path);
+    Some(art_path)
+}
+
+#[derive(Debug, Clone)]
+enum MetadataState {
+    Pending,
+    Ready(Arc<AudioMetadata>),
+    Failed,
+}
+
+/// Tags of audio files, read on the background executor the first time
+/// each file is asked for
+#[derive(Clone, Default)]
+pub struct AudioMetadataCache {
+    entries: Arc<Mutex<HashMap<PathBuf, MetadataState>>>,
+}
+
+impl AudioMetadataCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The tags of an audio file, or `None` while they are being read or
+    /// if they couldn't be
+    pub fn get(&self, path: &Path, background: &BackgroundExecutor) -> Option<Arc<AudioMetadata>> {
+        let mut entries = self.entries.lock();
+        match entries.get(path) {
+            Some(MetadataState::Ready(metadata)) => Some(metadata.clone()),
+            Some(MetadataState::Pending | MetadataState::Failed) => None,
+            None => {
+                entries.insert(path.to_path_buf(), MetadataState::Pending);
+                drop(entries);
+                self.read(path.to_path_buf(), background);
+                None
+            }
+        }
+    }
+
+    /// The tags of an audio file if they have already been read, without
+    /// starting a read
+    pub fn cached(&self, path: &Path) -> Option<Arc<AudioMetadata>> {
+        match self.entries.lock().get(path) {
+            Some(MetadataState::Ready(metadata)) => Some(metadata.clone()),
+            _ => None,
+        }
+    }
+
+    /// Store tags read elsewhere
+    pub fn insert(&self, path: PathBuf, metadata: AudioMetadata) {
+        self.entries
+            .lock()
+            .insert(path, MetadataState::Ready(Arc::new(metadata)));
+    }
+
+    /// Whether the tags of an audio file are still being read
+    pub fn is_pending(&self, path: &Path) -> bool {
+        matches!(self.entries.lock().get(path), Some(MetadataState::Pending))
+    }
+
+    /// Whether any file's tags are still being read
+    pub fn has_pending(&self) -> bool {
+        self.entries
+            .lock()
+            .values()
+            .any(|state| matches!(state, MetadataState::Pending))
+    }
+
+    /// Drop every file's tags, e.g. when switching boards
+    pub fn clear(&self) {
+        self.entries
+            .lock()
+            .retain(|_, state| matches!(state, MetadataState::Pending));
+    }
+
+    fn read(&self, path: PathBuf, background: &BackgroundExecutor) {
+        let entries = self.entries.clone();
+        let source = path.clone();
+
+        background.spawn(
+            "audio_metadata",
+            move || read_audio_metadata(&source).map_err(|e| e.to_string()),
+            move |result| {
+                let state = match result {
+                    Ok(metadata) => MetadataState::Ready(Arc::new(metadata)),
+                    Err(e) => {
+                        debug!("Failed to read tags of {:?}: {}", path, e);
+                        MetadataState::Failed
+                    }
+                };
+                entries.lock().insert(path, state);
+            },
+        );
+    }
+}
@@ -13,22 +13,38 @@
 //!
 //! Enable profiling with `cargo build --features profiling` to see timing.
 
+use crate::arrange::{ZOrder, grid_layout, reorder};
+use crate::board_assets::{self, AssetMigration};
+use crate::board_crypto::{self, BoardKey};
+use crate::board_format;
 use crate::board_index::BoardIndex;
+use crate::board_journal::{self, BoardJournal, JournalEntry};
+use crate::board_thumbnail;
+use crate::board_versions::{self, BoardVersion, VersionTracker};
+use crate::canvas_background::CanvasBackground;
 use crate::constants::{DOCK_WIDTH, HEADER_HEIGHT};
 use crate::error::BoardError;
+use crate::file_import::{ImportedFile, import_file, table_content};
+use crate::file_links;
+use crate::fuzzy::fuzzy_score;
+use crate::item_clipboard::{CopiedItems, remap_items};
+use crate::pdf::PdfHighlight;
+use crate::preview_session::PreviewSession;
 use crate::profile_scope;
 use crate::spatial_index::SpatialIndex;
-use crate::data::{is_data_file, parse_csv_file, parse_json_file, write_csv_file, write_json_file, ChartData};
+use crate::data::{parse_csv_file, parse_json_file, write_csv_file, write_json_file, ChartData};
 use crate::types::ChartConfig;
 use crate::types::DataOrigin;
-use crate::types::{CanvasItem, DataSource, ItemContent};
+use crate::types::{CanvasItem, DataSource, ItemContent, MediaVolume, extract_youtube_start};
+use crate::undo_history::{DEFAULT_STEP_LABEL, UndoStep, items_label, operation_label};
+use crate::url_paste::{self, embed_content};
 use crate::validation::validate_items;
 use gpui::{point, px, Pixels, Point, Size};
 use serde::{Deserialize, Serialize};
-use std::collections::{HashMap, VecDeque};
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
 use std::fs;
-use std::path::PathBuf;
-use std::time::{Duration, Instant};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant, SystemTime};
 use tracing::{debug, error, info, info_span, trace, warn};
 
 /// Save debounce delay - saves are batched within this window
@@ -40,6 +56,10 @@ const MAX_HISTORY_OPERATIONS: usize = 100;
 /// How often to create full snapshots (every N operations)
 const SNAPSHOT_INTERVAL: usize = 20;
 
+/// Playback positions closer than this to the start, or to the stored
+/// position, aren't worth saving (seconds)
+const MIN_PLAYBACK_POSITION_SECS: f64 = 1.0;
+
 #[derive(Serialize, Deserialize, Clone)]
 pub struct BoardState {
     pub canvas_offset: (f32, f32),
@@ -51,10 +71,22 @@ pub struct BoardState {
     pub data_sources: HashMap<u64, DataSource>,
     #[serde(default)]
     pub next_data_source_id: u64,
+    /// Background style picked for this board (None follows the settings)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub background: Option<CanvasBackground>,
+    /// Where audio, video and YouTube items were left, in seconds
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub playback_positions: BTreeMap<u64, f64>,
+    /// Highlighted passages of the board's PDFs
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub pdf_highlights: Vec<PdfHighlight>,
+    /// Preview tabs and layout the board was left with
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub preview_session: Option<PreviewSession>,
 }
 
 /// A single undoable operation (delta-based)
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub enum UndoOperation {
     /// Add an item to the canvas
     AddItem(CanvasItem),
@@ -205,6 +237,36 @@ impl UndoOperation {
             }
         }
     }
+
+    /// Every copy of the item with `id` this operation holds
+    fn copies_of_item_mut(&mut self, id: u64) -> Vec<&mut CanvasItem> {
+        match self {
+            UndoOperation::AddItem(item) | UndoOperation::RemoveItem(item) if item.id == id => {
+                vec![item]
+            }
+            UndoOperation::ModifyItem { old_item, new_item } if new_item.id == id => {
+                vec![old_item, new_item]
+            }
+            UndoOperation::Batch(ops) => ops
+                .iter_mut()
+                .flat_map(|op| op.copies_of_item_mut(id))
+                .collect(),
+            _ => Vec::new(),
+        }
+    }
+
+    /// Set the size recorded for an added item (used once an image is measured)
+    fn set_added_item_size(&mut self, id: u64, size: (f32, f32)) {
+        match self {
+            UndoOperation::AddItem(item) if item.id == id => item.size = size,
+            UndoOperation::Batch(ops) => {
+                for op in ops {
+                    op.set_added_item_size(id, size);
+                }
+            }
+            _ => {}
+        }
+    }
 }
 
 /// A history entry - either an operation or a full snapshot
@@ -212,8 +274,48 @@ impl UndoOperation {
 enum HistoryEntry {
     /// A delta operation
     Operation(UndoOperation),
-    /// A full state snapshot (created periodically for efficiency)
+    /// A full state snapshot, taken after a change
     Snapshot(BoardState),
+    /// The state at a point of the history, not a change of its own.
+    /// Undo and redo step over checkpoints.
+    Checkpoint(BoardState),
+}
+
+impl HistoryEntry {
+    fn is_change(&self) -> bool {
+        !matches!(self, HistoryEntry::Checkpoint(_))
+    }
+}
+
+/// A history entry with what it did and when
+#[derive(Clone)]
+struct HistoryStep {
+    entry: HistoryEntry,
+    label: String,
+    at: SystemTime,
+}
+
+impl HistoryStep {
+    fn new(entry: HistoryEntry, label: String) -> Self {
+        Self {
+            entry,
+            label,
+            at: SystemTime::now(),
+        }
+    }
+}
+
+/// Changes collected between `begin_group` and `end_group`, recorded as
+/// one undo step when the outermost group ends
+struct HistoryGroup {
+    label: String,
+    /// Groups begun inside this one and not yet ended, plus this one
+    depth: usize,
+    /// State when the group began, to undo snapshot changes back to
+    before: BoardState,
+    ops: Vec<UndoOperation>,
+    /// Whether a change in the group was recorded as a snapshot
+    snapshot: bool,
 }
 
 impl BoardState {
@@ -239,20 +341,64 @@ impl BoardState {
         Ok(())
     }
 
+    /// Save board state encrypted with `key`.
+    pub fn save_encrypted(&self, path: &PathBuf, key: &BoardKey) -> Result<(), BoardError> {
+        let envelope = board_crypto::encrypt_state(self, key)?;
+        let json = serde_json::to_string_pretty(&envelope).map_err(BoardError::ParseError)?;
+
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).map_err(|e| BoardError::SaveFailed {
+                path: path.clone(),
+                source: e,
+            })?;
+        }
+
+        fs::write(path, json).map_err(|e| BoardError::SaveFailed {
+            path: path.clone(),
+            source: e,
+        })?;
+
+        trace!("Encrypted board state saved to {:?}", path);
+        Ok(())
+    }
+
     /// Load board state from a file path.
     ///
-    /// Returns the loaded state, or a BoardError if loading fails.
+    /// Returns the loaded state, or a BoardError if loading fails
+    /// (`BoardError::Encrypted` for encrypted boards).
     pub fn load_from_path(path: &PathBuf) -> Result<Self, BoardError> {
         let json = fs::read_to_string(path).map_err(|e| BoardError::LoadFailed {
             path: path.clone(),
             source: e,
         })?;
 
+        if board_crypto::parse_envelope(&json).is_some() {
+            return Err(BoardError::Encrypted);
+        }
+
         let state = serde_json::from_str(&json).map_err(BoardError::ParseError)?;
         trace!("Board state loaded from {:?}", path);
         Ok(state)
     }
 
+    /// Load and decrypt an encrypted board state.
+    ///
+    /// Returns the state together with the key, so later saves can
+    /// re-encrypt without deriving it again.
+    pub fn load_encrypted(path: &PathBuf, passphrase: &str) -> Result<(Self, BoardKey), BoardError> {
+        let json = fs::read_to_string(path).map_err(|e| BoardError::LoadFailed {
+            path: path.clone(),
+            source: e,
+        })?;
+
+        let envelope = board_crypto::parse_envelope(&json).ok_or_else(|| {
+            BoardError::InvalidData("board is not encrypted".into())
+        })?;
+        let result = board_crypto::decrypt_state(&envelope, passphrase)?;
+        trace!("Encrypted board state loaded from {:?}", path);
+        Ok(result)
+    }
+
     /// Try to load board state, returning None if the file doesn't exist
     /// or an error occurs.
     pub fn try_load(path: &PathBuf) -> Option<Self> {
@@ -309,21 +455,48 @@ pub struct Board {
     pub data_sources: HashMap<u64, DataSource>,
     pub next_data_source_id: u64,
 
+    /// Background style picked for this board (None follows the settings)
+    background: Option<CanvasBackground>,
+
+    /// Where media items were left, in seconds
+    playback_positions: BTreeMap<u64, f64>,
+
+    /// Highlighted passages of the board's PDFs
+    pdf_highlights: Vec<PdfHighlight>,
+
+    /// Preview tabs and layout the board was left with
+    preview_session: Option<PreviewSession>,
+
     // Delta-based history using VecDeque for O(1) front removal
-    history: VecDeque<HistoryEntry>,
+    history: VecDeque<HistoryStep>,
     history_index: usize,
     /// Counter for operations since last snapshot
     ops_since_snapshot: usize,
+    /// Undo group being collected (see `begin_group`)
+    history_group: Option<HistoryGroup>,
 
     // Debounced save tracking
     dirty: bool,
     last_change: Instant,
 
+    // Decides when a save should also write an on-disk version snapshot
+    versions: VersionTracker,
+
+    // Append-only log of mutations since the last full save
+    // (None in tests and for encrypted boards)
+    journal: Option<BoardJournal>,
+
+    // Key for encrypted boards (None for plain boards)
+    encryption: Option<BoardKey>,
+
     // Storage location for this board (used to determine if files should be copied)
     storage_location: crate::board_index::StoredLocation,
     
     // Chart data cache - transient (not serialized)
     chart_data_cache: HashMap<ChartCacheKey, ChartData>,
+
+    // Images added with a placeholder size, waiting to be measured
+    unmeasured_images: Vec<(u64, PathBuf)>,
 }
 
 impl Board {
@@ -334,52 +507,111 @@ impl Board {
     pub fn load(id: String) -> Self {
         let _span = info_span!("board_load", board_id = %id).entered();
 
-        // Try to get path and storage location from board index
-        let index = BoardIndex::load();
-        let (board_path, storage_location) = index.get_board(&id)
-            .map(|b| (b.board_path(), b.storage_location.clone()))
-            .unwrap_or_else(|| (BoardIndex::board_path(&id), crate::board_index::StoredLocation::Default));
-
-        if let Some(mut state) = BoardState::try_load(&board_path) {
-            info!(items = state.items.len(), "Loaded board");
+        let (board_path, storage_location) = Self::locate(&id);
 
-            // Validate and fix any invalid item properties
-            let fixed_count = validate_items(&mut state.items);
-            if fixed_count > 0 {
-                warn!(
-                    "Fixed {} items with invalid properties in board '{}'",
-                    fixed_count, id
-                );
+        // Keep any journal left over from a crash aside until the user recovers it
+        let journal = board_path.parent().map(|dir| BoardJournal::new(dir.to_path_buf()));
+        if let Some(ref journal) = journal {
+            if journal.stash_for_recovery() {
+                warn!("Board '{}' has unsaved changes from a previous session", id);
             }
+        }
 
-            let items_index = Self::build_items_index(&state.items);
-            let spatial_index = SpatialIndex::from_items(
-                state.items.iter().map(|item| (item.id, item.position, item.size))
-            );
-            Self {
-                id,
-                canvas_offset: point(px(state.canvas_offset.0), px(state.canvas_offset.1)),
-                zoom: state.zoom,
-                items: state.items,
-                items_index,
-                spatial_index,
-                next_item_id: state.next_item_id,
-                data_sources: state.data_sources,
-                next_data_source_id: state.next_data_source_id,
-                history: VecDeque::new(),
-                history_index: 0,
-                ops_since_snapshot: 0,
-                dirty: fixed_count > 0, // Mark dirty if we fixed anything
-                last_change: Instant::now(),
-                storage_location,
-                chart_data_cache: HashMap::new(),
-            }
+        if let Some(state) = BoardState::try_load(&board_path) {
+            info!(items = state.items.len(), "Loaded board");
+            Self::from_loaded_state(id, state, &board_path, storage_location, journal, None)
         } else {
             debug!("Creating new empty board '{}'", id);
             Self::new_empty_with_location(id, storage_location)
         }
     }
 
+    /// Load an encrypted board, decrypting it with `passphrase`.
+    ///
+    /// Returns `BoardError::WrongPassphrase` if the passphrase doesn't match.
+    pub fn load_encrypted(id: String, passphrase: &str) -> Result<Self, BoardError> {
+        let _span = info_span!("board_load", board_id = %id).entered();
+
+        let (board_path, _) = Self::locate(&id);
+        let (state, key) = BoardState::load_encrypted(&board_path, passphrase)?;
+        Ok(Self::from_decrypted(id, state, key))
+    }
+
+    /// Build an encrypted board from a state already decrypted with `key`
+    /// (e.g. by [`BoardState::load_encrypted`] on a background thread).
+    pub fn from_decrypted(id: String, state: BoardState, key: BoardKey) -> Self {
+        let (board_path, storage_location) = Self::locate(&id);
+        info!(board_id = %id, items = state.items.len(), "Loaded encrypted board");
+
+        Self::from_loaded_state(id, state, &board_path, storage_location, None, Some(key))
+    }
+
+    /// Find a board's file and storage location via the board index
+    fn locate(id: &str) -> (PathBuf, crate::board_index::StoredLocation) {
+        let index = BoardIndex::load();
+        index.get_board(id)
+            .map(|b| (b.board_path(), b.storage_location.clone()))
+            .unwrap_or_else(|| (BoardIndex::board_path(id), crate::board_index::StoredLocation::Default))
+    }
+
+    /// Build a board from a state loaded from `board_path`
+    fn from_loaded_state(
+        id: String,
+        mut state: BoardState,
+        board_path: &Path,
+        storage_location: crate::board_index::StoredLocation,
+        journal: Option<BoardJournal>,
+        encryption: Option<BoardKey>,
+    ) -> Self {
+        if let Some(dir) = board_path.parent() {
+            // Data sources may be split into separate files (git-friendly layout)
+            board_format::load_split(&mut state, dir);
+            // Paths to managed assets are stored relative to the board directory
+            board_assets::resolve_paths(&mut state.items, dir);
+        }
+
+        // Validate and fix any invalid item properties
+        let fixed_count = validate_items(&mut state.items);
+        if fixed_count > 0 {
+            warn!(
+                "Fixed {} items with invalid properties in board '{}'",
+                fixed_count, id
+            );
+        }
+
+        let items_index = Self::build_items_index(&state.items);
+        let spatial_index = SpatialIndex::from_items(
+            state.items.iter().map(|item| (item.id, item.position, item.size))
+        );
+        Self {
+            id,
+            canvas_offset: point(px(state.canvas_offset.0), px(state.canvas_offset.1)),
+            zoom: state.zoom,
+            items: state.items,
+            items_index,
+            spatial_index,
+            next_item_id: state.next_item_id,
+            data_sources: state.data_sources,
+            next_data_source_id: state.next_data_source_id,
+            background: state.background,
+            playback_positions: state.playback_positions,
+            pdf_highlights: state.pdf_highlights,
+            preview_session: state.preview_session,
+            history: VecDeque::new(),
+            history_index: 0,
+            ops_since_snapshot: 0,
+            history_group: None,
+            dirty: fixed_count > 0, // Mark dirty if we fixed anything
+            last_change: Instant::now(),
+            versions: VersionTracker::new(),
+            journal,
+            encryption,
+            storage_location,
+            chart_data_cache: HashMap::new(),
+            unmeasured_images: Vec::new(),
+        }
+    }
+
     /// Create a new empty board with the given ID and default storage location
     pub fn new_empty(id: String) -> Self {
         Self::new_empty_with_location(id, crate::board_index::StoredLocation::Default)
@@ -387,6 +619,7 @@ impl Board {
 
     /// Create a new empty board with the given ID and storage location
     pub fn new_empty_with_location(id: String, storage_location: crate::board_index::StoredLocation) -> Self {
+        let journal = Some(BoardJournal::new(storage_location.base_path().join(&id)));
         Self {
             id,
             canvas_offset: point(px(0.0), px(0.0)),
@@ -397,13 +630,22 @@ impl Board {
             next_item_id: 0,
             data_sources: HashMap::new(),
             next_data_source_id: 0,
+            background: None,
+            playback_positions: BTreeMap::new(),
+            pdf_highlights: Vec::new(),
+            preview_session: None,
             history: VecDeque::new(),
             history_index: 0,
             ops_since_snapshot: 0,
+            history_group: None,
             dirty: false,
             last_change: Instant::now(),
+            versions: VersionTracker::new(),
+            journal,
+            encryption: None,
             storage_location,
             chart_data_cache: HashMap::new(),
+            unmeasured_images: Vec::new(),
         }
     }
 
@@ -444,6 +686,46 @@ impl Board {
         self.spatial_index.query_point(x, y)
     }
 
+    /// Topmost item whose bounds contain the canvas point, if any
+    pub fn topmost_item_at(&self, x: f32, y: f32) -> Option<u64> {
+        let candidates = self.query_items_at_point(x, y);
+        self.items
+            .iter()
+            .rev()
+            .find(|item| candidates.contains(&item.id))
+            .map(|item| item.id)
+    }
+
+    /// Items intersecting a canvas rectangle, back to front.
+    /// Uses the spatial index, so it costs O(log n + k) rather than a scan of every item.
+    pub fn items_in_rect(
+        &self,
+        min_x: f32,
+        min_y: f32,
+        max_x: f32,
+        max_y: f32,
+    ) -> Vec<&CanvasItem> {
+        let mut indices: Vec<usize> = self
+            .query_items_in_rect(min_x, min_y, max_x, max_y)
+            .into_iter()
+            .filter_map(|id| self.items_index.get(&id).copied())
+            .collect();
+        indices.sort_unstable();
+        indices.into_iter().map(|idx| &self.items[idx]).collect()
+    }
+
+    /// Items within `margin` screen pixels of a viewport of `viewport` size, back to front
+    pub fn items_in_viewport(&self, viewport: (f32, f32), margin: f32) -> Vec<&CanvasItem> {
+        let offset_x = f32::from(self.canvas_offset.x);
+        let offset_y = f32::from(self.canvas_offset.y);
+        self.items_in_rect(
+            (-margin - offset_x) / self.zoom,
+            (-margin - offset_y) / self.zoom,
+            (viewport.0 + margin - offset_x) / self.zoom,
+            (viewport.1 + margin - offset_y) / self.zoom,
+        )
+    }
+
     /// Query the spatial index for items in a rectangle (for marquee selection).
     /// Returns item IDs that intersect the rectangle. O(log n + k) where k is results.
     pub fn query_items_in_rect(&self, min_x: f32, min_y: f32, max_x: f32, max_y: f32) -> Vec<u64> {
@@ -458,6 +740,214 @@ impl Board {
         }
     }
 
+    /// Move several items at once as a single undoable operation.
+    /// Returns false if no item actually moved.
+    pub fn move_items(&mut self, moves: &[(u64, (f32, f32))]) -> bool {
+        let mut ops = Vec::new();
+        for &(id, new_pos) in moves {
+            let Some(item) = self.get_item_mut(id) else {
+                continue;
+            };
+            if item.position == new_pos {
+                continue;
+            }
+            ops.push(UndoOperation::MoveItem {
+                id,
+                old_pos: item.position,
+                new_pos,
+            });
+            item.position = new_pos;
+            self.update_spatial_index(id);
+        }
+
+        if ops.is_empty() {
+            return false;
+        }
+        self.push_operation(UndoOperation::Batch(ops));
+        self.mark_dirty();
+        true
+    }
+
+    /// Move items by `delta` (arrow-key nudge).
+    ///
+    /// With `merge`, a nudge of the same items right after a previous one
+    /// extends that undo step instead of adding a new one, so holding an
+    /// arrow key undoes in one go. Returns false if nothing moved.
+    pub fn nudge_items(&mut self, ids: &[u64], delta: (f32, f32), merge: bool) -> bool {
+        let moves: Vec<(u64, (f32, f32))> = ids
+            .iter()
+            .filter_map(|&id| self.get_item(id))
+            .map(|item| {
+                let (x, y) = item.position;
+                (item.id, (x + delta.0, y + delta.1))
+            })
+            .collect();
+
+        if merge && self.extend_last_move(&moves) {
+            return true;
+        }
+        self.move_items(&moves)
+    }
+
+    /// Fold `moves` into the latest undo step if it moved exactly these items
+    fn extend_last_move(&mut self, moves: &[(u64, (f32, f32))]) -> bool {
+        if moves.is_empty() || self.history_index != self.history.len() {
+            return false;
+        }
+        let Some(HistoryStep {
+            entry: HistoryEntry::Operation(UndoOperation::Batch(ops)),
+            ..
+        }) = self.history.back()
+        else {
+            return false;
+        };
+        let same_items = ops.len() == moves.len()
+            && ops.iter().all(|op| {
+                matches!(op, UndoOperation::MoveItem { id, .. }
+                    if moves.iter().any(|(move_id, _)| move_id == id))
+            });
+        if !same_items {
+            return false;
+        }
+
+        let mut step = Vec::with_capacity(moves.len());
+        for &(id, new_pos) in moves {
+            let Some(item) = self.get_item_mut(id) else {
+                continue;
+            };
+            step.push(UndoOperation::MoveItem {
+                id,
+                old_pos: item.position,
+                new_pos,
+            });
+            item.position = new_pos;
+            self.update_spatial_index(id);
+        }
+
+        if let Some(last) = self.history.back_mut() {
+            last.at = SystemTime::now();
+        }
+        if let Some(HistoryStep {
+            entry: HistoryEntry::Operation(UndoOperation::Batch(ops)),
+            ..
+        }) = self.history.back_mut()
+        {
+            for op in ops.iter_mut() {
+                if let UndoOperation::MoveItem { id, new_pos, .. } = op {
+                    if let Some(&(_, pos)) = moves.iter().find(|(move_id, _)| move_id == id) {
+                        *new_pos = pos;
+                    }
+                }
+            }
+        }
+
+        // The journal replays operations, so it records just this step
+        self.append_journal(JournalEntry::Operation(UndoOperation::Batch(step)));
+        self.mark_dirty();
+        true
+    }
+
+    /// Copy items in place, stacking the copies above everything else.
+    /// Returns `(original, copy)` ID pairs. Does not record history; callers
+    /// push history once the copies are placed.
+    pub fn duplicate_items(&mut self, ids: &[u64]) -> Vec<(u64, u64)> {
+        let originals: Vec<CanvasItem> = ids
+            .iter()
+            .filter_map(|&id| self.get_item(id).cloned())
+            .collect();
+
+        let mut pairs = Vec::with_capacity(originals.len());
+        for mut item in originals {
+            let original_id = item.id;
+            item.id = self.next_item_id;
+            self.next_item_id += 1;
+            self.spatial_index.insert(item.id, item.position, item.size);
+            self.items_index.insert(item.id, self.items.len());
+            pairs.push((original_id, item.id));
+            self.items.push(item);
+        }
+
+        if !pairs.is_empty() {
+            self.mark_dirty();
+        }
+        pairs
+    }
+
+    /// Whether an item is locked against moving, resizing and deleting
+    pub fn is_locked(&self, id: u64) -> bool {
+        self.get_item(id).is_some_and(|item| item.locked)
+    }
+
+    /// The given item IDs minus any locked items
+    pub fn unlocked_ids(&self, ids: impl IntoIterator<Item = u64>) -> Vec<u64> {
+        ids.into_iter().filter(|&id| !self.is_locked(id)).collect()
+    }
+
+    /// Lock or unlock items as a single undoable operation.
+    /// Returns false if no item changed.
+    pub fn set_locked(&mut self, ids: &[u64], locked: bool) -> bool {
+        let mut ops = Vec::new();
+        for &id in ids {
+            let Some(item) = self.get_item_mut(id) else {
+                continue;
+            };
+            if item.locked == locked {
+                continue;
+            }
+            let old_item = item.clone();
+            item.locked = locked;
+            ops.push(UndoOperation::ModifyItem {
+                old_item,
+                new_item: item.clone(),
+            });
+        }
+
+        if ops.is_empty() {
+            return false;
+        }
+        self.push_operation(UndoOperation::Batch(ops));
+        self.mark_dirty();
+        true
+    }
+
+    /// Pin the lines an expanded code card shows, or with `None` show the
+    /// start of its file again, as an undoable operation. Returns false if
+    /// the item isn't a code card or already shows those lines.
+    pub fn set_code_lines(&mut self, id: u64, new_lines: Option<(usize, usize)>) -> bool {
+        let Some(item) = self.get_item_mut(id) else {
+            return false;
+        };
+        let old_item = item.clone();
+        let ItemContent::Code { lines, .. } = &mut item.content else {
+            return false;
+        };
+        if *lines == new_lines {
+            return false;
+        }
+        *lines = new_lines;
+        let new_item = item.clone();
+        self.push_operation(UndoOperation::ModifyItem { old_item, new_item });
+        self.mark_dirty();
+        true
+    }
+
+    /// Move items up or down the z-order. Returns false if the order is unchanged.
+    pub fn reorder_items(&mut self, ids: &HashSet<u64>, z_order: ZOrder) -> bool {
+        let order: Vec<u64> = self.items.iter().map(|item| item.id).collect();
+        let new_order = reorder(&order, ids, z_order);
+        if new_order == order {
+            return false;
+        }
+
+        let mut by_id: HashMap<u64, CanvasItem> =
+            self.items.drain(..).map(|item| (item.id, item)).collect();
+        self.items = new_order.iter().filter_map(|id| by_id.remove(id)).collect();
+        self.rebuild_index();
+        self.push_labeled_history("Reordered items");
+        self.mark_dirty();
+        true
+    }
+
     /// Add a single item (still triggers history + save for single operations)
     pub fn add_item(&mut self, position: Point<Pixels>, content: ItemContent) -> u64 {
         let id = self.add_item_internal(position, content);
@@ -472,14 +962,33 @@ impl Board {
     /// Internal add without history/save - used for batch operations
     fn add_item_internal(&mut self, position: Point<Pixels>, content: ItemContent) -> u64 {
         let size = content.default_size();
+        self.add_sized_item_internal(position, content, size)
+    }
+
+    /// Internal add with a known size. Images with the placeholder size are
+    /// measured later, see `take_unmeasured_images`.
+    fn add_sized_item_internal(
+        &mut self,
+        position: Point<Pixels>,
+        content: ItemContent,
+        size: (f32, f32),
+    ) -> u64 {
         let id = self.next_item_id;
         let pos = (f32::from(position.x), f32::from(position.y));
 
+        if let ItemContent::Image(ref path) = content {
+            if size == crate::constants::DEFAULT_IMAGE_SIZE {
+                self.unmeasured_images.push((id, path.clone()));
+            }
+        }
+
         self.items.push(CanvasItem {
             id,
             position: pos,
             size,
             content,
+            locked: false,
+            volume: None,
         });
         self.items_index.insert(id, self.items.len() - 1);
         self.spatial_index.insert(id, pos, size);
@@ -487,9 +996,53 @@ impl Board {
         id
     }
 
+    /// Take the images added since the last call, which still have the
+    /// placeholder size. The caller reads their dimensions off the UI thread
+    /// and reports back through `apply_image_size`.
+    pub fn take_unmeasured_images(&mut self) -> Vec<(u64, PathBuf)> {
+        std::mem::take(&mut self.unmeasured_images)
+    }
+
+    /// Give a newly added image its measured size.
+    ///
+    /// Does nothing if the item is gone or was resized in the meantime. The
+    /// size is also written into the item's add operation, so undo and redo
+    /// don't bring the placeholder back. Returns true if the item changed.
+    pub fn apply_image_size(&mut self, id: u64, size: (f32, f32)) -> bool {
+        let Some(&idx) = self.items_index.get(&id) else {
+            return false;
+        };
+        let item = &mut self.items[idx];
+        let placeholder = crate::constants::DEFAULT_IMAGE_SIZE;
+        if !matches!(item.content, ItemContent::Image(_))
+            || item.size != placeholder
+            || size == placeholder
+        {
+            return false;
+        }
+
+        item.size = size;
+        let position = item.position;
+        self.spatial_index.update(id, position, size);
+
+        for step in self.history.iter_mut() {
+            match &mut step.entry {
+                HistoryEntry::Operation(op) => op.set_added_item_size(id, size),
+                HistoryEntry::Snapshot(state) | HistoryEntry::Checkpoint(state) => {
+                    if let Some(item) = state.items.iter_mut().find(|item| item.id == id) {
+                        item.size = size;
+                    }
+                }
+            }
+        }
+        self.mark_dirty();
+        true
+    }
+
     /// Handle file drop - batched operation (single history push + save)
     /// For iCloud boards, files are copied to the board's files/ directory
-    /// so they sync across devices.
+    /// so they sync across devices. Other boards copy files into assets/
+    /// when managed assets are enabled.
     ///
     /// Returns a list of error messages for any files that failed to copy.
     /// The caller should display these to the user via toast notifications.
@@ -497,92 +1050,50 @@ impl Board {
     /// ## Performance
     /// This can be slow for many files or large files due to:
     /// - File I/O for copying (iCloud boards)
-    /// - Content type detection
+    /// - PDF thumbnails and image dimensions
+    ///
+    /// The app imports drops in the background with `import_file` and adds
+    /// them with `add_imported_files` instead.
     pub fn handle_file_drop(&mut self, position: Point<Pixels>, paths: Vec<PathBuf>) -> Vec<String> {
         profile_scope!("handle_file_drop");
 
         let mut errors = Vec::new();
+        let import_dir = self.import_dir();
+        let files = paths
+            .iter()
+            .filter_map(|path| import_file(import_dir.as_deref(), path, &mut errors))
+            .collect();
+        self.add_imported_files(position, files);
+        errors
+    }
 
-        if paths.is_empty() {
-            return errors;
+    /// Add imported files in a grid whose top-left corner is at `position`
+    /// on screen, as one undo step. Returns the IDs of the new items.
+    pub fn add_imported_files(&mut self, position: Point<Pixels>, files: Vec<ImportedFile>) -> Vec<u64> {
+        if files.is_empty() {
+            return Vec::new();
         }
 
-        // Stagger offset for multiple files so they don't overlap
-        const STAGGER_X: f32 = 30.0;
-        const STAGGER_Y: f32 = 30.0;
+        let origin = self.screen_to_canvas(position);
+        let sizes: Vec<(f32, f32)> = files.iter().map(ImportedFile::size).collect();
+        let positions = grid_layout(&sizes, (f32::from(origin.x), f32::from(origin.y)));
 
         let mut added_ids = Vec::new();
-
-        for (i, path) in paths.iter().enumerate() {
-            // For iCloud boards, copy the file to the board's files directory
-            let actual_path = if self.should_copy_files() {
-                match self.copy_file_to_board(path) {
-                    Ok(copied_path) => copied_path,
-                    Err(e) => {
-                        let filename = path.file_name()
-                            .and_then(|n| n.to_str())
-                            .unwrap_or("file");
-                        errors.push(format!("Failed to copy '{}': {}", filename, e));
-                        warn!("Failed to copy file to board storage: {}", e);
-                        path.clone()
-                    }
+        for ((file, size), (x, y)) in files.into_iter().zip(sizes).zip(positions) {
+            let content = match file {
+                ImportedFile::Item { content, .. } => content,
+                ImportedFile::Table(mut data_source) => {
+                    // Assign ID and store the data source
+                    data_source.id = self.next_data_source_id;
+                    self.next_data_source_id += 1;
+                    let ds_id = data_source.id;
+                    info!("Created table from data file: {}", data_source.name);
+                    self.data_sources.insert(ds_id, data_source);
+                    table_content(ds_id)
                 }
-            } else {
-                path.clone()
             };
-
-            let base_pos = self.screen_to_canvas(position);
-            let staggered_pos = point(
-                px(f32::from(base_pos.x) + (i as f32 * STAGGER_X)),
-                px(f32::from(base_pos.y) + (i as f32 * STAGGER_Y)),
-            );
-
-            // Check if this is a data file (CSV/TSV/JSON)
-            if is_data_file(&actual_path) {
-                let filename = actual_path.file_name()
-                    .and_then(|n| n.to_str())
-                    .unwrap_or("data");
-
-                // Parse the data file
-                let parse_result = if actual_path.extension()
-                    .and_then(|e| e.to_str())
-                    .map(|e| e.to_lowercase() == "json")
-                    .unwrap_or(false)
-                {
-                    parse_json_file(&actual_path)
-                } else {
-                    parse_csv_file(&actual_path)
-                };
-
-                match parse_result {
-                    Ok(mut data_source) => {
-                        // Assign ID and store the data source
-                        data_source.id = self.next_data_source_id;
-                        self.next_data_source_id += 1;
-                        let ds_id = data_source.id;
-                        self.data_sources.insert(ds_id, data_source);
-
-                        // Create a table item referencing this data source
-                        let content = ItemContent::Table {
-                            data_source_id: ds_id,
-                            show_headers: true,
-                            stripe: true,
-                        };
-                        let id = self.add_item_internal(staggered_pos, content);
-                        added_ids.push(id);
-                        info!("Created table from data file: {}", filename);
-                    }
-                    Err(e) => {
-                        errors.push(format!("Failed to parse '{}': {}", filename, e));
-                        warn!("Failed to parse data file '{}': {}", filename, e);
-                    }
-                }
-            } else {
-                // Handle as regular file (image, video, etc.)
-                let content = ItemContent::from_path(&actual_path);
-                let id = self.add_item_internal(staggered_pos, content);
-                added_ids.push(id);
-            }
+            let id = self.add_sized_item_internal(point(px(x), px(y)), content, size);
+            added_ids.push(id);
         }
 
         // Create batch operation for all added items
@@ -592,16 +1103,19 @@ impl Board {
             .map(UndoOperation::AddItem)
             .collect();
 
-        if !ops.is_empty() {
-            if ops.len() == 1 {
-                self.push_operation(ops.into_iter().next().unwrap());
-            } else {
-                self.push_operation(UndoOperation::Batch(ops));
+        if ops.len() == 1 {
+            self.push_operation(ops.into_iter().next().unwrap());
+        } else if !ops.is_empty() {
+            // One undo step for the whole drop
+            self.begin_group(items_label("Imported", ops.len()));
+            for op in ops {
+                self.push_operation(op);
             }
+            self.end_group();
         }
         self.mark_dirty();
 
-        errors
+        added_ids
     }
 
     /// Check if files should be copied to the board's storage
@@ -615,75 +1129,268 @@ impl Board {
         self.storage_location.base_path().join(&self.id).join("files")
     }
 
-    /// Copy a file to the board's files directory
-    /// Returns the new path to the copied file
-    fn copy_file_to_board(&self, source: &PathBuf) -> Result<PathBuf, std::io::Error> {
-        let files_dir = self.files_dir();
-        std::fs::create_dir_all(&files_dir)?;
-
-        // Get original filename and sanitize it to prevent path traversal attacks
-        let filename = source
-            .file_name()
-            .ok_or_else(|| std::io::Error::new(std::io::ErrorKind::InvalidInput, "No filename"))?
-            .to_str()
-            .ok_or_else(|| std::io::Error::new(std::io::ErrorKind::InvalidInput, "Invalid filename encoding"))?;
-
-        // Sanitize filename: remove path separators and reject dangerous names
-        let sanitized = sanitize_filename(filename)
-            .ok_or_else(|| std::io::Error::new(std::io::ErrorKind::InvalidInput, "Invalid filename"))?;
-
-        // Generate unique filename if it already exists
-        let mut dest = files_dir.join(&sanitized);
-        if dest.exists() {
-            let path = std::path::Path::new(&sanitized);
-            let stem = path.file_stem().and_then(|s| s.to_str()).unwrap_or("file");
-            let ext = path.extension().and_then(|s| s.to_str()).unwrap_or("");
-            let timestamp = std::time::SystemTime::now()
-                .duration_since(std::time::UNIX_EPOCH)
-                .unwrap_or_default()
-                .as_millis()
-                % 100000;
-
-            let new_name = if ext.is_empty() {
-                format!("{}_{}", stem, timestamp)
-            } else {
-                format!("{}_{}.{}", stem, timestamp, ext)
-            };
-            dest = files_dir.join(new_name);
+    /// Get the managed assets directory for this board
+    pub fn assets_dir(&self) -> PathBuf {
+        board_assets::assets_dir(&self.board_dir())
+    }
+
+    /// Directory dropped files are copied into, if any.
+    /// iCloud boards always copy into files/ so they sync; other boards
+    /// copy into assets/ when managed assets are enabled.
+    pub(crate) fn import_dir(&self) -> Option<PathBuf> {
+        if self.should_copy_files() {
+            Some(self.files_dir())
+        } else if crate::settings::is_manage_assets_enabled() {
+            Some(self.assets_dir())
+        } else {
+            None
         }
+    }
 
-        // Final safety check: verify destination is within files_dir
-        let canonical_files_dir = files_dir.canonicalize().unwrap_or(files_dir.clone());
-        let canonical_dest = dest.parent()
-            .and_then(|p| p.canonicalize().ok())
-            .map(|p| p.join(dest.file_name().unwrap_or_default()));
+    /// Directory pasted and downloaded files are saved in: the import
+    /// directory, or assets/ when files aren't otherwise copied
+    pub fn storage_dir(&self) -> PathBuf {
+        self.import_dir().unwrap_or_else(|| self.assets_dir())
+    }
 
-        if let Some(ref canonical) = canonical_dest {
-            if !canonical.starts_with(&canonical_files_dir) {
-                return Err(std::io::Error::new(
-                    std::io::ErrorKind::PermissionDenied,
-                    "Path traversal attempt detected",
-                ));
-            }
-        }
+    /// Save pasted image data into board storage (the import directory, or
+    /// assets/ when files aren't otherwise copied) and add it as an image
+    /// item at `position` on screen. Returns the new item's ID.
+    pub fn add_pasted_image(
+        &mut self,
+        position: Point<Pixels>,
+        extension: &str,
+        bytes: &[u8],
+    ) -> Result<u64, BoardError> {
+        let path = self.store_pasted_file(extension, bytes)?;
+        let canvas_pos = self.screen_to_canvas(position);
+        let id = self.add_item(canvas_pos, ItemContent::Image(path));
+        info!("Pasted image into board '{}'", self.id);
+        Ok(id)
+    }
 
-        std::fs::copy(source, &dest)?;
-        info!("Copied file to board storage: {:?} -> {:?}", source, dest);
-        Ok(dest)
+    /// Save pasted or downloaded file data into board storage, returning
+    /// the new file's path
+    pub fn store_pasted_file(&self, extension: &str, bytes: &[u8]) -> Result<PathBuf, BoardError> {
+        let dir = self.storage_dir();
+        board_assets::write_pasted_file(&dir, extension, bytes).map_err(|e| {
+            BoardError::SaveFailed {
+                path: dir.clone(),
+                source: e,
+            }
+        })
     }
 
-    /// Add URL (YouTube or generic link)
-    pub fn add_url(&mut self, url: &str, position: Point<Pixels>) {
-        use crate::types::extract_youtube_id;
+    /// Copy the items with the given IDs, back to front, along with the data
+    /// sources they show. None if none of them exist.
+    pub fn copy_items(&self, ids: &HashSet<u64>) -> Option<CopiedItems> {
+        let items: Vec<CanvasItem> = self
+            .items
+            .iter()
+            .filter(|item| ids.contains(&item.id))
+            .cloned()
+            .collect();
+        if items.is_empty() {
+            return None;
+        }
+        Some(CopiedItems::new(
+            &self.id,
+            self.board_dir(),
+            items,
+            &self.data_sources,
+        ))
+    }
 
-        let content = if let Some(video_id) = extract_youtube_id(url) {
-            ItemContent::YouTube(video_id)
-        } else {
-            ItemContent::Link(url.to_string())
-        };
+    /// Paste copied items with the top-left corner of their bounding box at
+    /// `position` on screen, as one undo step. Returns the new items' IDs.
+    ///
+    /// Items from another board get copies of their data sources, and files
+    /// from inside that board's directory are copied into this board's
+    /// storage; files that couldn't be copied are added to `errors`.
+    pub fn paste_items(
+        &mut self,
+        copied: &CopiedItems,
+        position: Point<Pixels>,
+        errors: &mut Vec<String>,
+    ) -> Vec<u64> {
+        self.add_copied_items(copied, position, "Pasted", errors)
+    }
 
-        let canvas_pos = self.screen_to_canvas(position);
-        self.add_item(canvas_pos, content);
+    /// Add items imported from another app, like pasted ones, as one undo
+    /// step labeled as an import. Returns the new items' IDs.
+    pub fn import_items(
+        &mut self,
+        imported: &CopiedItems,
+        position: Point<Pixels>,
+        errors: &mut Vec<String>,
+    ) -> Vec<u64> {
+        self.add_copied_items(imported, position, "Imported", errors)
+    }
+
+    fn add_copied_items(
+        &mut self,
+        copied: &CopiedItems,
+        position: Point<Pixels>,
+        verb: &str,
+        errors: &mut Vec<String>,
+    ) -> Vec<u64> {
+        let same_board = copied.board_id == self.id;
+
+        // Tables pasted on their own board keep sharing their data
+        let mut data_source_ids = HashMap::new();
+        for source in &copied.data_sources {
+            if same_board && self.data_sources.contains_key(&source.id) {
+                data_source_ids.insert(source.id, source.id);
+                continue;
+            }
+            let mut source = source.clone();
+            let id = self.next_data_source_id;
+            self.next_data_source_id += 1;
+            data_source_ids.insert(source.id, id);
+            source.id = id;
+            self.data_sources.insert(id, source);
+        }
+
+        let target = self.screen_to_canvas(position);
+        let origin = copied.origin();
+        let offset = (
+            f32::from(target.x) - origin.0,
+            f32::from(target.y) - origin.1,
+        );
+        let mut items = remap_items(
+            &copied.items,
+            self.next_item_id,
+            offset,
+            &data_source_ids,
+            same_board,
+        );
+        if !same_board {
+            let dir = self.storage_dir();
+            errors.extend(board_assets::copy_board_files(
+                &mut items,
+                &copied.board_dir,
+                &dir,
+            ));
+        }
+
+        let mut ops = Vec::with_capacity(items.len());
+        let mut ids = Vec::with_capacity(items.len());
+        for item in items {
+            self.next_item_id = self.next_item_id.max(item.id + 1);
+            self.items_index.insert(item.id, self.items.len());
+            self.spatial_index.insert(item.id, item.position, item.size);
+            ids.push(item.id);
+            ops.push(UndoOperation::AddItem(item.clone()));
+            self.items.push(item);
+        }
+
+        if ops.len() == 1 {
+            self.push_operation(ops.into_iter().next().unwrap());
+        } else if !ops.is_empty() {
+            self.begin_group(items_label(verb, ops.len()));
+            for op in ops {
+                self.push_operation(op);
+            }
+            self.end_group();
+        }
+        if !ids.is_empty() {
+            self.mark_dirty();
+        }
+        ids
+    }
+
+    /// Add URL (YouTube, Vimeo, SoundCloud, Spotify or generic link).
+    /// Returns the new item's ID.
+    pub fn add_url(&mut self, url: &str, position: Point<Pixels>) -> u64 {
+        let content = embed_content(url).unwrap_or_else(|| ItemContent::Link(url.to_string()));
+        let canvas_pos = self.screen_to_canvas(position);
+        self.add_url_content(url, canvas_pos, content)
+    }
+
+    /// Add a bookmark card for `url`, showing its host until the page has
+    /// been fetched (see `fill_in_bookmark`). Returns the new item's ID.
+    pub fn add_bookmark(&mut self, url: &str, position: Point<Pixels>) -> u64 {
+        let canvas_pos = self.screen_to_canvas(position);
+        self.add_url_content(url, canvas_pos, url_paste::bare_bookmark(url))
+    }
+
+    fn add_url_content(&mut self, url: &str, canvas_pos: Point<Pixels>, content: ItemContent) -> u64 {
+        let is_youtube = matches!(content, ItemContent::YouTube(_));
+        let id = self.add_item(canvas_pos, content);
+        // The embed starts where the link pointed, like a saved position
+        if let Some(seconds) = extract_youtube_start(url).filter(|_| is_youtube) {
+            self.set_playback_position(id, Some(seconds as f64));
+        }
+        id
+    }
+
+    /// Change what an item shows, keeping its place, as an undoable
+    /// operation. The item gets the new content's default size. Returns
+    /// false if the item doesn't exist.
+    pub fn replace_item_content(&mut self, id: u64, content: ItemContent) -> bool {
+        let Some(item) = self.get_item_mut(id) else {
+            return false;
+        };
+        let old_item = item.clone();
+        item.size = content.default_size();
+        item.content = content;
+        let new_item = item.clone();
+
+        if let ItemContent::Image(ref path) = new_item.content {
+            self.unmeasured_images.push((id, path.clone()));
+        }
+        self.spatial_index.update(id, new_item.position, new_item.size);
+        self.push_operation(UndoOperation::ModifyItem { old_item, new_item });
+        self.mark_dirty();
+        true
+    }
+
+    /// Fill in a pasted bookmark once its page or image has been fetched.
+    ///
+    /// Does nothing if the item is gone or no longer the bookmark of `url`.
+    /// Copies of the item in history get the content too, so undo and redo
+    /// don't bring the bare bookmark back. Returns true if the item changed.
+    pub fn fill_in_bookmark(&mut self, id: u64, url: &str, content: ItemContent) -> bool {
+        let is_bookmark_of_url =
+            |item: &CanvasItem| matches!(&item.content, ItemContent::Bookmark { url: u, .. } if u == url);
+        let Some(item) = self.get_item(id) else {
+            return false;
+        };
+        if !is_bookmark_of_url(item) {
+            return false;
+        }
+
+        // Cards still at the bookmark's size take the new content's size
+        let (old_size, new_size) = (item.content.default_size(), content.default_size());
+        let fill = |item: &mut CanvasItem| {
+            if item.id == id && is_bookmark_of_url(item) {
+                if item.size == old_size {
+                    item.size = new_size;
+                }
+                item.content = content.clone();
+            }
+        };
+
+        let Some(item) = self.get_item_mut(id) else {
+            return false;
+        };
+        fill(item);
+        let (position, size) = (item.position, item.size);
+        self.spatial_index.update(id, position, size);
+        if let ItemContent::Image(ref path) = content {
+            self.unmeasured_images.push((id, path.clone()));
+        }
+
+        for step in self.history.iter_mut() {
+            match &mut step.entry {
+                HistoryEntry::Operation(op) => op.copies_of_item_mut(id).into_iter().for_each(&fill),
+                HistoryEntry::Snapshot(state) | HistoryEntry::Checkpoint(state) => {
+                    state.items.iter_mut().for_each(&fill);
+                }
+            }
+        }
+        self.mark_dirty();
+        true
     }
 
     /// Remove an item by ID
@@ -795,10 +1502,20 @@ impl Board {
         }
     }
 
-    /// Find items matching a search query (searches display names)
+    /// Find items matching a search query (fuzzily matches display names),
+    /// best match first
     pub fn find_items(&self, query: &str) -> Vec<(u64, String)> {
-        let query_lower = query.to_lowercase();
-        self.items
+        self.ranked_items(query)
+            .into_iter()
+            .map(|(_, id, name)| (id, name))
+            .collect()
+    }
+
+    /// Items whose names match `query` with their score, best first, to
+    /// rank them together with other results
+    pub fn ranked_items(&self, query: &str) -> Vec<(i32, u64, String)> {
+        let mut found: Vec<(i32, u64, String)> = self
+            .items
             .iter()
             .filter_map(|item| {
                 if !item.content.is_searchable() {
@@ -816,13 +1533,13 @@ impl Board {
                     _ => item.content.display_name(),
                 };
 
-                if display_name.to_lowercase().contains(&query_lower) {
-                    Some((item.id, display_name))
-                } else {
-                    None
-                }
+                let score = fuzzy_score(query, &display_name)?;
+                Some((score, item.id, display_name))
             })
-            .collect()
+            .collect();
+        // Stable, so equally good matches keep board order
+        found.sort_by(|a, b| b.0.cmp(&a.0));
+        found
     }
 
     /// Mark the board as dirty (needing save)
@@ -836,6 +1553,11 @@ impl Board {
         self.dirty
     }
 
+    /// When the board was last changed
+    pub fn last_change(&self) -> Instant {
+        self.last_change
+    }
+
     /// Check if enough time has passed to save (debouncing)
     pub fn should_save(&self) -> bool {
         self.dirty && self.last_change.elapsed() >= Duration::from_millis(SAVE_DEBOUNCE_MS)
@@ -847,17 +1569,21 @@ impl Board {
         if self.dirty {
             self.try_save()?;
             self.dirty = false;
+            if let Some(ref journal) = self.journal {
+                journal.board_saved();
+            }
+            if self.versions.is_due() && !self.is_encrypted() {
+                self.capture_version();
+            }
             Ok(true)
         } else {
             Ok(false)
         }
     }
 
-    /// Try to save, returning any errors
-    pub fn try_save(&self) -> Result<(), BoardError> {
-        profile_scope!("board_save");
-
-        let state = BoardState {
+    /// Capture the current board contents as a serializable state
+    pub fn to_state(&self) -> BoardState {
+        BoardState {
             canvas_offset: (
                 f32::from(self.canvas_offset.x),
                 f32::from(self.canvas_offset.y),
@@ -867,14 +1593,296 @@ impl Board {
             next_item_id: self.next_item_id,
             data_sources: self.data_sources.clone(),
             next_data_source_id: self.next_data_source_id,
+            background: self.background.clone(),
+            // Positions of deleted items are dropped on save
+            playback_positions: self
+                .playback_positions
+                .iter()
+                .filter(|(id, _)| self.items_index.contains_key(id))
+                .map(|(&id, &seconds)| (id, seconds))
+                .collect(),
+            // Notes that were deleted are unlinked on save
+            pdf_highlights: self
+                .pdf_highlights
+                .iter()
+                .cloned()
+                .map(|mut highlight| {
+                    highlight.note_item = highlight
+                        .note_item
+                        .filter(|id| self.items_index.contains_key(id));
+                    highlight
+                })
+                .collect(),
+            preview_session: self.preview_session.clone(),
+        }
+    }
+
+    /// Background style of the canvas, falling back to the settings
+    pub fn background(&self) -> CanvasBackground {
+        self.background
+            .clone()
+            .unwrap_or_else(CanvasBackground::from_settings)
+    }
+
+    /// Pick the board's background style, or follow the settings with None.
+    ///
+    /// The background is a board preference rather than content, so the
+    /// change isn't recorded in undo history.
+    pub fn set_background(&mut self, background: Option<CanvasBackground>) {
+        if self.background != background {
+            self.background = background;
+            self.mark_dirty();
+        }
+    }
+
+    /// Preview tabs and layout the board was left with
+    pub fn preview_session(&self) -> Option<&PreviewSession> {
+        self.preview_session.as_ref()
+    }
+
+    /// Remember the preview panel as it is, or that it is closed with None.
+    ///
+    /// Like the background, this isn't recorded in undo history.
+    pub fn set_preview_session(&mut self, session: Option<PreviewSession>) {
+        if self.preview_session != session {
+            self.preview_session = session;
+            self.mark_dirty();
+        }
+    }
+
+    /// Where a media item was left, in seconds
+    pub fn playback_position(&self, id: u64) -> Option<f64> {
+        self.playback_positions.get(&id).copied()
+    }
+
+    /// Remember where a media item was left, or forget it with None to
+    /// start from the beginning next time. Positions within the first
+    /// second count as the beginning, and small moves don't mark the board
+    /// dirty so a playing item doesn't cause a save on every report.
+    ///
+    /// Like the background, this isn't recorded in undo history.
+    pub fn set_playback_position(&mut self, id: u64, seconds: Option<f64>) {
+        let seconds = seconds.filter(|s| s.is_finite() && *s >= MIN_PLAYBACK_POSITION_SECS);
+        let changed = match (self.playback_positions.get(&id), seconds) {
+            (Some(old), Some(new)) => (old - new).abs() >= MIN_PLAYBACK_POSITION_SECS,
+            (None, None) => false,
+            _ => true,
         };
+        if !changed {
+            return;
+        }
+        match seconds {
+            Some(seconds) => self.playback_positions.insert(id, seconds),
+            None => self.playback_positions.remove(&id),
+        };
+        self.mark_dirty();
+    }
+
+    /// Volume of a media item's player
+    pub fn media_volume(&self, id: u64) -> MediaVolume {
+        self.get_item(id)
+            .and_then(|item| item.volume)
+            .unwrap_or_default()
+    }
+
+    /// Set a media item's volume. Full, unmuted volume is stored as no
+    /// volume at all. Returns false if the item doesn't exist or the volume
+    /// is unchanged.
+    ///
+    /// Like playback positions, this isn't recorded in undo history.
+    pub fn set_media_volume(&mut self, id: u64, volume: MediaVolume) -> bool {
+        let volume = Some(volume).filter(|v| *v != MediaVolume::default());
+        let Some(item) = self.get_item_mut(id) else {
+            return false;
+        };
+        if item.volume == volume {
+            return false;
+        }
+        item.volume = volume;
+        self.mark_dirty();
+        true
+    }
+
+    /// Show another page of a PDF item. Returns false if the item isn't a
+    /// PDF or is already on that page.
+    ///
+    /// Like media volumes, this isn't recorded in undo history.
+    pub fn set_pdf_page(&mut self, id: u64, new_page: usize) -> bool {
+        let Some(item) = self.get_item_mut(id) else {
+            return false;
+        };
+        let ItemContent::Pdf { page, .. } = &mut item.content else {
+            return false;
+        };
+        if *page == new_page {
+            return false;
+        }
+        *page = new_page;
+        self.mark_dirty();
+        true
+    }
+
+    /// Point a PDF item at a new thumbnail, e.g. after its file changed.
+    /// Returns false if the item isn't a PDF or already uses it.
+    ///
+    /// Thumbnails are derived from the file, so this isn't recorded in
+    /// undo history.
+    pub fn set_pdf_thumbnail(&mut self, id: u64, new_thumbnail: PathBuf) -> bool {
+        let Some(item) = self.get_item_mut(id) else {
+            return false;
+        };
+        let ItemContent::Pdf { thumbnail, .. } = &mut item.content else {
+            return false;
+        };
+        if thumbnail.as_ref() == Some(&new_thumbnail) {
+            return false;
+        }
+        *thumbnail = Some(new_thumbnail);
+        self.mark_dirty();
+        true
+    }
+
+    /// Show the saved text of a markdown file on every card of it.
+    /// Returns the number of cards whose content changed.
+    ///
+    /// The file itself holds the text, so this isn't recorded in undo
+    /// history.
+    pub fn set_markdown_content(&mut self, path: &Path, new_content: &str) -> usize {
+        let mut changed = 0;
+        for item in &mut self.items {
+            if let ItemContent::Markdown {
+                path: item_path,
+                content,
+                ..
+            } = &mut item.content
+            {
+                if item_path == path && content != new_content {
+                    *content = new_content.to_string();
+                    changed += 1;
+                }
+            }
+        }
+        if changed > 0 {
+            self.mark_dirty();
+        }
+        changed
+    }
+
+    /// Highlighted passages of the board's PDFs, oldest first
+    pub fn pdf_highlights(&self) -> &[PdfHighlight] {
+        &self.pdf_highlights
+    }
+
+    /// Highlight a passage of a PDF, returning the highlight's ID. The same
+    /// passage is only highlighted once.
+    ///
+    /// Highlights aren't items, so like playback positions they aren't
+    /// recorded in undo history.
+    pub fn add_pdf_highlight(
+        &mut self,
+        path: PathBuf,
+        page: usize,
+        offset: usize,
+        text: String,
+    ) -> u64 {
+        if let Some(existing) = self
+            .pdf_highlights
+            .iter()
+            .find(|h| h.path == path && h.page == page && h.offset == offset && h.text == text)
+        {
+            return existing.id;
+        }
+        let id = self
+            .pdf_highlights
+            .iter()
+            .map(|h| h.id + 1)
+            .max()
+            .unwrap_or(0);
+        self.pdf_highlights.push(PdfHighlight {
+            id,
+            path,
+            page,
+            offset,
+            text,
+            note_item: None,
+        });
+        self.mark_dirty();
+        id
+    }
+
+    /// Make a text box the note of a highlight. Returns false if there is
+    /// no such highlight.
+    pub fn set_highlight_note(&mut self, highlight_id: u64, item_id: u64) -> bool {
+        let Some(highlight) = self
+            .pdf_highlights
+            .iter_mut()
+            .find(|h| h.id == highlight_id)
+        else {
+            return false;
+        };
+        highlight.note_item = Some(item_id);
+        self.mark_dirty();
+        true
+    }
+
+    /// The highlight an item is the note of
+    pub fn highlight_for_note(&self, item_id: u64) -> Option<&PdfHighlight> {
+        self.pdf_highlights
+            .iter()
+            .find(|h| h.note_item == Some(item_id))
+    }
+
+    /// Remove a highlight. Its note stays on the canvas as a plain text box.
+    pub fn remove_pdf_highlight(&mut self, highlight_id: u64) -> bool {
+        let before = self.pdf_highlights.len();
+        self.pdf_highlights.retain(|h| h.id != highlight_id);
+        let removed = self.pdf_highlights.len() != before;
+        if removed {
+            self.mark_dirty();
+        }
+        removed
+    }
+
+    /// Get the directory containing this board's board.json
+    pub fn board_dir(&self) -> PathBuf {
+        self.storage_location.base_path().join(&self.id)
+    }
+
+    /// Try to save, returning any errors
+    pub fn try_save(&self) -> Result<(), BoardError> {
+        profile_scope!("board_save");
+
+        let mut state = self.to_state();
 
         // Get path from board index (supports custom storage locations)
         let index = BoardIndex::load();
         let board_path = index.get_board_path(&self.id)
             .unwrap_or_else(|| BoardIndex::board_path(&self.id));
 
-        state.save_to_path(&board_path)?;
+        // Store paths to files inside the board directory relative to it
+        if let Some(dir) = board_path.parent() {
+            board_assets::relativize_paths(&mut state.items, dir);
+        }
+
+        let board_dir = board_path.parent().unwrap_or(Path::new("."));
+        match self.encryption {
+            Some(ref key) => {
+                state.save_encrypted(&board_path, key)?;
+                // Split data sources would be plain text
+                board_format::remove_split(board_dir);
+            }
+            // Never overwrite an encrypted board that wasn't unlocked
+            None if board_crypto::is_encrypted_file(&board_path) => {
+                return Err(BoardError::Encrypted);
+            }
+            None if crate::settings::is_git_friendly_save_enabled() => {
+                board_format::save_split(state, &board_path)?;
+            }
+            None => {
+                state.save_to_path(&board_path)?;
+                board_format::remove_split(board_dir);
+            }
+        }
         debug!("Board '{}' saved with {} items", self.id, self.items.len());
         Ok(())
     }
@@ -896,58 +1904,132 @@ impl Board {
 
     /// Push a delta operation to history (memory-efficient)
     pub fn push_operation(&mut self, op: UndoOperation) {
-        // Remove any operations after current index (for redo branch pruning)
-        while self.history.len() > self.history_index {
-            self.history.pop_back();
+        self.append_journal(JournalEntry::Operation(op.clone()));
+        self.versions.record_operation();
+
+        if let Some(group) = self.history_group.as_mut() {
+            group.ops.push(op);
+            return;
         }
 
-        self.history.push_back(HistoryEntry::Operation(op));
-        self.history_index = self.history.len();
+        let label = operation_label(&op, |id| {
+            self.get_item(id).map(crate::undo_history::item_name)
+        });
+        self.record_operation(op, label);
+    }
+
+    /// Add an operation to history as its own step
+    fn record_operation(&mut self, op: UndoOperation, label: String) {
+        self.push_step(HistoryEntry::Operation(op), label);
         self.ops_since_snapshot += 1;
 
-        // Create periodic snapshot for efficient reconstruction
+        // Create periodic checkpoint for efficient reconstruction
         if self.ops_since_snapshot >= SNAPSHOT_INTERVAL {
-            self.create_snapshot();
+            self.push_checkpoint();
         }
+    }
 
-        // Limit history - O(1) removal from front with VecDeque
-        while self.history.len() > MAX_HISTORY_OPERATIONS {
-            self.history.pop_front();
-            if self.history_index > 0 {
-                self.history_index -= 1;
-            }
+    /// Record the current state without counting it as a change, so that
+    /// a snapshot change pushed next can be undone back to it
+    pub fn push_checkpoint(&mut self) {
+        // An open group already holds the state it began with
+        if self.history_group.is_some() {
+            return;
         }
-    }
 
-    /// Create a full snapshot in history (for periodic checkpoints)
-    fn create_snapshot(&mut self) {
-        let state = BoardState {
-            canvas_offset: (
-                f32::from(self.canvas_offset.x),
-                f32::from(self.canvas_offset.y),
-            ),
-            zoom: self.zoom,
-            items: self.items.clone(),
-            next_item_id: self.next_item_id,
-            data_sources: self.data_sources.clone(),
-            next_data_source_id: self.next_data_source_id,
-        };
-        self.history.push_back(HistoryEntry::Snapshot(state));
-        self.history_index = self.history.len();
+        let state = self.to_state();
+        self.push_step(HistoryEntry::Checkpoint(state), String::new());
         self.ops_since_snapshot = 0;
     }
 
     /// Legacy push_history - creates a snapshot (backward compatibility)
     /// Prefer push_operation for new code
     pub fn push_history(&mut self) {
+        self.push_labeled_history(DEFAULT_STEP_LABEL);
+    }
+
+    /// Snapshot the board after a change, listed in the History panel as
+    /// `label`
+    pub fn push_labeled_history(&mut self, label: impl Into<String>) {
+        self.versions.record_operation();
+        self.append_journal(JournalEntry::State(self.to_state()));
+
+        if let Some(group) = self.history_group.as_mut() {
+            group.snapshot = true;
+            return;
+        }
+
+        let state = self.to_state();
+        self.push_step(HistoryEntry::Snapshot(state), label.into());
+        self.ops_since_snapshot = 0;
+    }
+
+    /// Start collecting changes into one undo step labelled `label`, so a
+    /// bulk operation undoes as a whole. Every call needs a matching
+    /// `end_group`; groups begun inside a group join it, under its label.
+    pub fn begin_group(&mut self, label: impl Into<String>) {
+        if let Some(group) = self.history_group.as_mut() {
+            group.depth += 1;
+            return;
+        }
+
+        self.history_group = Some(HistoryGroup {
+            label: label.into(),
+            depth: 1,
+            before: self.to_state(),
+            ops: Vec::new(),
+            snapshot: false,
+        });
+    }
+
+    /// End the group begun last. Ending the outermost group records its
+    /// changes as one undo step. Returns true if a step was recorded.
+    pub fn end_group(&mut self) -> bool {
+        let Some(group) = self.history_group.as_mut() else {
+            return false;
+        };
+        group.depth -= 1;
+        if group.depth > 0 {
+            return false;
+        }
+        let Some(group) = self.history_group.take() else {
+            return false;
+        };
+
+        if group.snapshot {
+            // Operations in the group are part of the snapshot too
+            self.push_step(HistoryEntry::Checkpoint(group.before), String::new());
+            let state = self.to_state();
+            self.push_step(HistoryEntry::Snapshot(state), group.label);
+            self.ops_since_snapshot = 0;
+            return true;
+        }
+
+        let mut ops = group.ops;
+        let op = match ops.len() {
+            0 => return false,
+            1 => ops.remove(0),
+            _ => UndoOperation::Batch(ops),
+        };
+        self.record_operation(op, group.label);
+        true
+    }
+
+    /// Add an entry after the current one, dropping the undone entries it
+    /// replaces
+    fn push_step(&mut self, entry: HistoryEntry, label: String) {
         // Remove any operations after current index (for redo branch pruning)
         while self.history.len() > self.history_index {
             self.history.pop_back();
         }
 
-        self.create_snapshot();
+        self.history.push_back(HistoryStep::new(entry, label));
+        self.history_index = self.history.len();
+        self.trim_history();
+    }
 
-        // Limit history
+    /// Limit history - O(1) removal from front with VecDeque
+    fn trim_history(&mut self) {
         while self.history.len() > MAX_HISTORY_OPERATIONS {
             self.history.pop_front();
             if self.history_index > 0 {
@@ -956,17 +2038,30 @@ impl Board {
         }
     }
 
+    /// `index` moved back over the checkpoints right before it
+    fn settled_index(&self, mut index: usize) -> usize {
+        while index > 0
+            && self
+                .history
+                .get(index - 1)
+                .is_some_and(|step| !step.entry.is_change())
+        {
+            index -= 1;
+        }
+        index
+    }
+
     pub fn undo(&mut self) -> bool {
         profile_scope!("board_undo");
 
-        if self.history_index == 0 {
+        let index = self.settled_index(self.history_index);
+        if index == 0 {
             return false;
         }
 
-        self.history_index -= 1;
-        let entry = self.history.get(self.history_index).cloned();
+        let entry = self.history.get(index - 1).map(|step| step.entry.clone());
 
-        match entry {
+        let undone = match entry {
             Some(HistoryEntry::Operation(op)) => {
                 // Reverse the operation
                 op.reverse(&mut self.items, &mut self.items_index);
@@ -975,34 +2070,54 @@ impl Board {
                     self.items.iter().map(|item| (item.id, item.position, item.size))
                 );
                 self.mark_dirty();
+                self.append_journal(JournalEntry::State(self.to_state()));
                 true
             }
             Some(HistoryEntry::Snapshot(_)) => {
                 // For snapshots, restore the PREVIOUS state (the one before this change)
                 // history[n] contains state AFTER change n, so we need history[n-1]
-                if self.history_index > 0 {
-                    if let Some(HistoryEntry::Snapshot(prev_state)) = self.history.get(self.history_index - 1).cloned() {
-                        self.restore_from_snapshot(&prev_state);
-                        return true;
+                let prev_state = index.checked_sub(2).and_then(|prev| {
+                    match self.history.get(prev).map(|step| &step.entry) {
+                        Some(HistoryEntry::Snapshot(state) | HistoryEntry::Checkpoint(state)) => {
+                            Some(state.clone())
+                        }
+                        _ => None,
                     }
-                }
+                });
                 // Can't undo if there's no previous snapshot
-                self.history_index += 1; // Restore index since we can't actually undo
-                false
+                let Some(prev_state) = prev_state else {
+                    return false;
+                };
+                self.restore_from_snapshot(&prev_state);
+                self.append_journal(JournalEntry::State(prev_state));
+                true
             }
-            None => false,
+            Some(HistoryEntry::Checkpoint(_)) | None => false,
+        };
+        if undone {
+            self.history_index = self.settled_index(index - 1);
         }
+        undone
     }
 
     pub fn redo(&mut self) -> bool {
         profile_scope!("board_redo");
 
-        if self.history_index >= self.history.len() {
+        // Step over checkpoints, which change nothing
+        let mut index = self.history_index;
+        while self
+            .history
+            .get(index)
+            .is_some_and(|step| !step.entry.is_change())
+        {
+            index += 1;
+        }
+        if index >= self.history.len() {
             return false;
         }
 
-        let entry = self.history.get(self.history_index).cloned();
-        self.history_index += 1;
+        let entry = self.history.get(index).map(|step| step.entry.clone());
+        self.history_index = index + 1;
 
         match entry {
             Some(HistoryEntry::Operation(op)) => {
@@ -1013,16 +2128,58 @@ impl Board {
                     self.items.iter().map(|item| (item.id, item.position, item.size))
                 );
                 self.mark_dirty();
+                self.append_journal(JournalEntry::State(self.to_state()));
                 true
             }
             Some(HistoryEntry::Snapshot(state)) => {
                 // For snapshots on redo, we need to apply operations until next snapshot
                 // For simplicity, just restore the snapshot
                 self.restore_from_snapshot(&state);
+                self.append_journal(JournalEntry::State(state));
                 true
             }
-            None => false,
+            Some(HistoryEntry::Checkpoint(_)) | None => false,
+        }
+    }
+
+    /// The changes in the undo history, oldest first, including the ones
+    /// undone and not yet redone
+    pub fn undo_steps(&self) -> Vec<UndoStep> {
+        self.history
+            .iter()
+            .enumerate()
+            .filter(|(_, step)| step.entry.is_change())
+            .map(|(index, step)| UndoStep {
+                label: step.label.clone(),
+                at: step.at,
+                position: index + 1,
+            })
+            .collect()
+    }
+
+    /// Where the board is in its undo history: the `position` of the last
+    /// step not undone, or 0 when every step is undone
+    pub fn history_position(&self) -> usize {
+        self.settled_index(self.history_index)
+    }
+
+    /// Undo or redo until the board is at `position` of its history.
+    /// Returns false if a step couldn't be undone, leaving the board at
+    /// the last step it reached.
+    pub fn jump_to_history(&mut self, position: usize) -> bool {
+        profile_scope!("board_jump_to_history");
+
+        while self.history_position() > position {
+            if !self.undo() {
+                return false;
+            }
+        }
+        while self.history_position() < position {
+            if !self.redo() {
+                return false;
+            }
         }
+        true
     }
 
     fn restore_from_snapshot(&mut self, state: &BoardState) {
@@ -1036,6 +2193,225 @@ impl Board {
         self.mark_dirty();
     }
 
+    // =========================================================================
+    // Version History
+    // =========================================================================
+
+    /// Write an on-disk version snapshot of the current state.
+    ///
+    /// Failures are logged rather than surfaced since versions are a
+    /// best-effort safety net on top of the regular save.
+    pub fn capture_version(&mut self) -> Option<BoardVersion> {
+        profile_scope!("board_capture_version");
+
+        // Versions are stored in plain text
+        if self.is_encrypted() {
+            return None;
+        }
+
+        match board_versions::save_version(&self.board_dir(), &self.to_state()) {
+            Ok(version) => {
+                self.versions.mark_captured();
+                info!("Captured version of board '{}'", self.id);
+                Some(version)
+            }
+            Err(e) => {
+                warn!("Failed to capture version of board '{}': {}", self.id, e);
+                None
+            }
+        }
+    }
+
+    /// Save the board as a version named `name`, kept until deleted.
+    /// Encrypted boards keep no versions, as they are stored in plain text.
+    pub fn create_checkpoint(&mut self, name: &str) -> Result<BoardVersion, BoardError> {
+        if self.is_encrypted() {
+            return Err(BoardError::Encrypted);
+        }
+        let version = board_versions::save_checkpoint(&self.board_dir(), &self.to_state(), name)?;
+        info!("Saved checkpoint '{}' of board '{}'", name, self.id);
+        Ok(version)
+    }
+
+    /// List on-disk versions of this board, newest first
+    pub fn list_versions(&self) -> Vec<BoardVersion> {
+        board_versions::list_versions(&self.board_dir())
+    }
+
+    /// Replace the board contents with an older version.
+    ///
+    /// The current state is snapshotted first so the restore can be undone,
+    /// and the camera is kept where it is.
+    pub fn restore_version(&mut self, state: &BoardState) {
+        self.push_checkpoint();
+
+        let mut state = state.clone();
+        validate_items(&mut state.items);
+
+        let (offset, zoom) = (self.canvas_offset, self.zoom);
+        self.restore_from_snapshot(&state);
+        self.canvas_offset = offset;
+        self.zoom = zoom;
+        self.chart_data_cache.clear();
+
+        self.push_labeled_history("Restored version");
+    }
+
+    // =========================================================================
+    // Crash Journal
+    // =========================================================================
+
+    /// Append an entry to the crash journal.
+    ///
+    /// Journal failures only lose crash protection, so they are logged
+    /// instead of interrupting the edit.
+    fn append_journal(&self, entry: JournalEntry) {
+        if let Some(ref journal) = self.journal {
+            if let Err(e) = journal.append(&entry) {
+                warn!("Failed to journal change to board '{}': {}", self.id, e);
+            }
+        }
+    }
+
+    /// Check if unsaved changes from a previous session can be recovered
+    pub fn has_pending_recovery(&self) -> bool {
+        self.journal
+            .as_ref()
+            .is_some_and(|journal| !journal.pending_entries().is_empty())
+    }
+
+    /// Replay the crash journal on top of the current board.
+    ///
+    /// The recovery is pushed to history so it can be undone. Returns the
+    /// number of replayed entries.
+    pub fn recover_from_journal(&mut self) -> usize {
+        let Some(journal) = self.journal.clone() else {
+            return 0;
+        };
+
+        let entries = journal.pending_entries();
+        if !entries.is_empty() {
+            self.push_checkpoint();
+
+            let mut state = self.to_state();
+            board_journal::replay(&mut state, &entries);
+            validate_items(&mut state.items);
+            self.restore_from_snapshot(&state);
+            self.chart_data_cache.clear();
+
+            self.push_labeled_history("Recovered changes");
+            info!("Recovered {} journaled changes for board '{}'", entries.len(), self.id);
+        }
+
+        journal.discard_recovered();
+        entries.len()
+    }
+
+    /// Drop the crash journal without applying it
+    pub fn discard_recovery(&mut self) {
+        if let Some(ref journal) = self.journal {
+            journal.discard_recovered();
+        }
+    }
+
+    // =========================================================================
+    // Encryption
+    // =========================================================================
+
+    /// Check if this board is encrypted at rest
+    pub fn is_encrypted(&self) -> bool {
+        self.encryption.is_some()
+    }
+
+    /// Encrypt this board with a passphrase and save it.
+    ///
+    /// Plain-text copies of the contents (crash journal, version snapshots,
+    /// thumbnail) are removed, and no new ones are written while encrypted.
+    pub fn enable_encryption(&mut self, passphrase: &str) -> Result<(), BoardError> {
+        let previous = self.encryption.replace(BoardKey::generate(passphrase)?);
+        self.mark_dirty();
+        if let Err(e) = self.flush_save() {
+            self.encryption = previous;
+            return Err(e);
+        }
+
+        if let Some(journal) = self.journal.take() {
+            journal.clear();
+            journal.discard_recovered();
+        }
+        let board_dir = self.board_dir();
+        board_versions::remove_versions(&board_dir);
+        board_thumbnail::remove_thumbnails(&board_dir);
+
+        info!("Encrypted board '{}'", self.id);
+        Ok(())
+    }
+
+    /// Remove encryption and save this board in plain text
+    pub fn disable_encryption(&mut self) -> Result<(), BoardError> {
+        let previous = self.encryption.take();
+        self.mark_dirty();
+        if let Err(e) = self.flush_save() {
+            self.encryption = previous;
+            return Err(e);
+        }
+
+        self.journal = Some(BoardJournal::new(self.board_dir()));
+        info!("Removed encryption from board '{}'", self.id);
+        Ok(())
+    }
+
+    // =========================================================================
+    // Managed Assets
+    // =========================================================================
+
+    /// Copy every file this board references from elsewhere on disk into
+    /// its assets directory, so the board no longer depends on the originals.
+    ///
+    /// The migration is pushed to history so it can be undone (the copies
+    /// stay on disk).
+    pub fn migrate_assets(&mut self) -> AssetMigration {
+        let mut items = self.items.clone();
+        let report = board_assets::migrate_items(&mut items, &self.board_dir());
+
+        if report.changed() {
+            self.push_checkpoint();
+            self.items = items;
+            self.rebuild_index();
+            self.push_labeled_history("Collected assets");
+            self.mark_dirty();
+        }
+
+        report
+    }
+
+    // =========================================================================
+    // File Relinking
+    // =========================================================================
+
+    /// The backing file of each item that has one, as (item ID, path)
+    pub fn linked_files(&self) -> Vec<(u64, PathBuf)> {
+        file_links::linked_files(&self.items)
+    }
+
+    /// Point a missing item at `new_path`, relinking other missing files
+    /// from the same folder as well (undoable).
+    ///
+    /// Returns the number of paths rebound.
+    pub fn relink_file(&mut self, item_id: u64, new_path: &Path) -> usize {
+        let mut items = self.items.clone();
+        let relinked = file_links::relink_items(&mut items, item_id, new_path);
+
+        if relinked > 0 {
+            self.push_checkpoint();
+            self.items = items;
+            self.push_labeled_history("Relinked files");
+            self.mark_dirty();
+        }
+
+        relinked
+    }
+
     // =========================================================================
     // Data Source File Operations
     // =========================================================================
@@ -1160,7 +2536,9 @@ impl Board {
 
     /// Create a fresh board for testing (doesn't load from disk)
     pub fn new_for_test() -> Self {
-        Self::new_empty("test-board".to_string())
+        let mut board = Self::new_empty("test-board".to_string());
+        board.journal = None;
+        board
     }
 
     /// Get the current history length (for testing)
@@ -1175,43 +2553,13 @@ impl Board {
 
     /// Check if there's history to undo
     pub fn can_undo(&self) -> bool {
-        self.history_index > 0
+        self.history_position() > 0
     }
 
     /// Check if there's history to redo
     pub fn can_redo(&self) -> bool {
-        self.history_index < self.history.len()
+        self.history
+            .range(self.history_index..)
+            .any(|step| step.entry.is_change())
     }
 }
-
-/// Sanitize a filename to prevent path traversal attacks.
-/// Returns None if the filename is invalid or dangerous.
-fn sanitize_filename(filename: &str) -> Option<String> {
-    // Reject empty filenames
-    if filename.is_empty() {
-        return None;
-    }
-
-    // Reject dangerous names
-    if filename == "." || filename == ".." {
-        return None;
-    }
-
-    // Remove any path separators (both Unix and Windows style)
-    let sanitized: String = filename
-        .chars()
-        .filter(|&c| c != '/' && c != '\\')
-        .collect();
-
-    // Reject if sanitization resulted in empty string or dangerous name
-    if sanitized.is_empty() || sanitized == "." || sanitized == ".." {
-        return None;
-    }
-
-    // Reject filenames that start with a dot followed by dot (hidden traversal)
-    if sanitized.starts_with("..") {
-        return None;
-    }
-
-    Some(sanitized)
-}
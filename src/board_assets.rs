@@ -0,0 +1,291 @@
+//! Managed board assets - files copied into the board directory.
+//!
+//! Items normally reference files by absolute path, which breaks as soon as
+//! the file moves. With managed assets enabled, dropped files are copied into
+//! `<board_dir>/assets/` instead, and existing boards can be migrated with
+//! [`migrate_items`].
+//!
+//! Paths inside the board directory are written to `board.json` relative to
+//! it ([`relativize_paths`]) and resolved back to absolute paths on load
+//! ([`resolve_paths`]), so a board folder can be moved or synced as a whole.
+//! Items pasted from another board bring their files from that board's
+//! folder along ([`copy_board_files`]).
+
+use crate::types::CanvasItem;
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+use tracing::{info, warn};
+
+/// Name of the managed assets directory inside a board directory
+pub const ASSETS_DIR: &str = "assets";
+
+/// Get the managed assets directory for a board directory
+pub fn assets_dir(board_dir: &Path) -> PathBuf {
+    board_dir.join(ASSETS_DIR)
+}
+
+/// Result of migrating a board's files into its assets directory
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct AssetMigration {
+    /// Number of files copied into the assets directory
+    pub copied: usize,
+    /// Number of referenced files that no longer exist
+    pub missing: usize,
+    /// Errors for files that exist but couldn't be copied
+    pub errors: Vec<String>,
+}
+
+impl AssetMigration {
+    /// Check if the migration changed any item paths
+    pub fn changed(&self) -> bool {
+        self.copied > 0
+    }
+}
+
+/// Copy a file into `dir` under a sanitized, unique file name.
+///
+/// Returns the path of the copy.
+pub fn copy_into_dir(dir: &Path, source: &Path) -> Result<PathBuf, io::Error> {
+    fs::create_dir_all(dir)?;
+
+    // Get original filename and sanitize it to prevent path traversal attacks
+    let filename = source
+        .file_name()
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "No filename"))?
+        .to_str()
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "Invalid filename encoding"))?;
+
+    // Sanitize filename: remove path separators and reject dangerous names
+    let sanitized = sanitize_filename(filename)
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "Invalid filename"))?;
+
+    // Generate unique filename if it already exists
+    let mut dest = dir.join(&sanitized);
+    if dest.exists() {
+        let path = Path::new(&sanitized);
+        let stem = path.file_stem().and_then(|s| s.to_str()).unwrap_or("file");
+        let ext = path.extension().and_then(|s| s.to_str()).unwrap_or("");
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_millis()
+            % 100000;
+
+        let new_name = if ext.is_empty() {
+            format!("{}_{}", stem, timestamp)
+        } else {
+            format!("{}_{}.{}", stem, timestamp, ext)
+        };
+        dest = dir.join(new_name);
+    }
+
+    // Final safety check: verify destination is within dir
+    let canonical_dir = dir.canonicalize().unwrap_or(dir.to_path_buf());
+    let canonical_dest = dest
+        .parent()
+        .and_then(|p| p.canonicalize().ok())
+        .map(|p| p.join(dest.file_name().unwrap_or_default()));
+
+    if let Some(ref canonical) = canonical_dest {
+        if !canonical.starts_with(&canonical_dir) {
+            return Err(io::Error::new(
+                io::ErrorKind::PermissionDenied,
+                "Path traversal attempt detected",
+            ));
+        }
+    }
+
+    fs::copy(source, &dest)?;
+    info!("Copied file to board storage: {:?} -> {:?}", source, dest);
+    Ok(dest)
+}
+
+/// File extension for pasted image data, from its contents. Returns `None`
+/// for data that isn't a known image format.
+pub fn pasted_image_extension(bytes: &[u8]) -> Option<&'static str> {
+    if let Ok(format) = image::guess_format(bytes) {
+        return format.extensions_str().first().copied();
+    }
+    let head = String::from_utf8_lossy(&bytes[..bytes.len().min(256)]);
+    let head = head.trim_start();
+    (head.starts_with("<svg") || (head.starts_with("<?xml") && head.contains("<svg")))
+        .then_some("svg")
+}
+
+/// Write pasted file data into `dir` as a new file named after the time of
+/// the paste, like `pasted-1718000000000.png`.
+///
+/// Returns the path of the new file.
+pub fn write_pasted_file(dir: &Path, extension: &str, bytes: &[u8]) -> Result<PathBuf, io::Error> {
+    let extension: String = extension
+        .chars()
+        .filter(|c| c.is_ascii_alphanumeric())
+        .collect();
+    if extension.is_empty() {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "Invalid file extension",
+        ));
+    }
+    fs::create_dir_all(dir)?;
+
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis();
+    let mut dest = dir.join(format!("pasted-{}.{}", timestamp, extension));
+    let mut n = 1;
+    while dest.exists() {
+        dest = dir.join(format!("pasted-{}-{}.{}", timestamp, n, extension));
+        n += 1;
+    }
+
+    fs::write(&dest, bytes)?;
+    info!("Saved pasted file to board storage: {:?}", dest);
+    Ok(dest)
+}
+
+/// Rewrite paths inside `board_dir` as paths relative to it (for saving)
+pub fn relativize_paths(items: &mut [CanvasItem], board_dir: &Path) {
+    for item in items {
+        for path in item.content.file_paths_mut() {
+            if let Ok(relative) = path.strip_prefix(board_dir) {
+                *path = relative.to_path_buf();
+            }
+        }
+    }
+}
+
+/// Resolve relative paths against `board_dir` (after loading)
+pub fn resolve_paths(items: &mut [CanvasItem], board_dir: &Path) {
+    for item in items {
+        for path in item.content.file_paths_mut() {
+            if path.is_relative() {
+                *path = board_dir.join(&*path);
+            }
+        }
+    }
+}
+
+/// Copy every file referenced from outside `board_dir` into its assets
+/// directory and point the items at the copies.
+///
+/// Files referenced by several items are copied once. Missing files and
+/// failed copies leave the original path untouched.
+pub fn migrate_items(items: &mut [CanvasItem], board_dir: &Path) -> AssetMigration {
+    let dir = assets_dir(board_dir);
+    let mut report = AssetMigration::default();
+    let mut copies: HashMap<PathBuf, PathBuf> = HashMap::new();
+
+    for item in items {
+        for path in item.content.file_paths_mut() {
+            if path.starts_with(board_dir) {
+                continue;
+            }
+
+            if let Some(copy) = copies.get(path.as_path()) {
+                *path = copy.clone();
+                continue;
+            }
+
+            if !path.exists() {
+                report.missing += 1;
+                continue;
+            }
+
+            match copy_into_dir(&dir, path) {
+                Ok(copy) => {
+                    copies.insert(path.clone(), copy.clone());
+                    *path = copy;
+                    report.copied += 1;
+                }
+                Err(e) => {
+                    let filename = path.file_name().and_then(|n| n.to_str()).unwrap_or("file");
+                    warn!("Failed to copy {:?} into board assets: {}", path, e);
+                    report
+                        .errors
+                        .push(format!("Failed to copy '{}': {}", filename, e));
+                }
+            }
+        }
+    }
+
+    report
+}
+
+/// Copy the files of items pasted from another board that live inside that
+/// board's directory `from_board_dir` into `dir`, and point the items at
+/// the copies.
+///
+/// Files referenced by several items are copied once; files elsewhere are
+/// left alone. Returns errors for files that couldn't be copied, which keep
+/// their original path.
+pub fn copy_board_files(
+    items: &mut [CanvasItem],
+    from_board_dir: &Path,
+    dir: &Path,
+) -> Vec<String> {
+    let mut errors = Vec::new();
+    let mut copies: HashMap<PathBuf, PathBuf> = HashMap::new();
+
+    for item in items {
+        for path in item.content.file_paths_mut() {
+            if !path.starts_with(from_board_dir) {
+                continue;
+            }
+            if let Some(copy) = copies.get(path.as_path()) {
+                *path = copy.clone();
+                continue;
+            }
+
+            match copy_into_dir(dir, path) {
+                Ok(copy) => {
+                    copies.insert(path.clone(), copy.clone());
+                    *path = copy;
+                }
+                Err(e) => {
+                    let filename = path.file_name().and_then(|n| n.to_str()).unwrap_or("file");
+                    warn!("Failed to copy {:?} into board storage: {}", path, e);
+                    errors.push(format!("Failed to copy '{}': {}", filename, e));
+                }
+            }
+        }
+    }
+
+    errors
+}
+
+/// Sanitize a filename to prevent path traversal attacks.
+/// Returns None if the filename is invalid or dangerous.
+fn sanitize_filename(filename: &str) -> Option<String> {
+    // Reject empty filenames
+    if filename.is_empty() {
+        return None;
+    }
+
+    // Reject dangerous names
+    if filename == "." || filename == ".." {
+        return None;
+    }
+
+    // Remove any path separators (both Unix and Windows style)
+    let sanitized: String = filename
+        .chars()
+        .filter(|&c| c != '/' && c != '\\')
+        .collect();
+
+    // Reject if sanitization resulted in empty string or dangerous name
+    if sanitized.is_empty() || sanitized == "." || sanitized == ".." {
+        return None;
+    }
+
+    // Reject filenames that start with a dot followed by dot (hidden traversal)
+    if sanitized.starts_with("..") {
+        return None;
+    }
+
+    Some(sanitized)
+}
@@ -0,0 +1,219 @@
+//! Board encryption - optional passphrase-based encryption at rest.
+//!
+//! An encrypted board's `board.json` holds an [`EncryptedBoard`] envelope
+//! instead of a plain [`BoardState`]: the state JSON sealed with AES-256-GCM
+//! under a key derived from the passphrase with PBKDF2-HMAC-SHA256.
+//!
+//! Only `board.json` is encrypted. While a board is encrypted its crash
+//! journal, version snapshots and thumbnail are not written, since they would
+//! hold the contents in plain text. Media files in the board folder (assets,
+//! iCloud files) are stored as-is.
+
+use crate::board::BoardState;
+use crate::error::BoardError;
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use ring::aead::{AES_256_GCM, Aad, LessSafeKey, NONCE_LEN, Nonce, UnboundKey};
+use ring::pbkdf2;
+use ring::rand::{SecureRandom, SystemRandom};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::fs;
+use std::num::NonZeroU32;
+use std::path::Path;
+
+/// PBKDF2 iterations for newly encrypted boards
+pub const PBKDF2_ITERATIONS: u32 = 600_000;
+
+/// Iteration counts accepted when deriving a key. The count comes from the
+/// board file, so an edited or corrupt envelope must not be able to make
+/// derivation free or make it run for hours.
+pub const PBKDF2_ITERATION_RANGE: std::ops::RangeInclusive<u32> = 1_000..=10_000_000;
+
+/// Shortest passphrase accepted when encrypting a board
+pub const MIN_PASSPHRASE_LEN: usize = 8;
+
+/// Envelope format version
+const FORMAT_VERSION: u32 = 1;
+
+/// Salt length in bytes
+const SALT_LEN: usize = 16;
+
+/// On-disk envelope for an encrypted board
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct EncryptedBoard {
+    /// Envelope format version (also marks the file as encrypted)
+    pub encrypted: u32,
+    /// PBKDF2 iteration count used to derive the key
+    pub iterations: u32,
+    /// Base64 PBKDF2 salt
+    pub salt: String,
+    /// Base64 AES-GCM nonce
+    pub nonce: String,
+    /// Base64 ciphertext (board state JSON + auth tag)
+    pub ciphertext: String,
+}
+
+/// Key derived from a board passphrase.
+///
+/// Kept in memory while an encrypted board is open so saves don't have to
+/// re-run the (deliberately slow) key derivation.
+#[derive(Clone)]
+pub struct BoardKey {
+    key: [u8; 32],
+    salt: Vec<u8>,
+    iterations: u32,
+}
+
+impl fmt::Debug for BoardKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BoardKey")
+            .field("iterations", &self.iterations)
+            .finish_non_exhaustive()
+    }
+}
+
+impl BoardKey {
+    /// Derive a key for a newly encrypted board (random salt)
+    pub fn generate(passphrase: &str) -> Result<Self, BoardError> {
+        let mut salt = vec![0u8; SALT_LEN];
+        SystemRandom::new()
+            .fill(&mut salt)
+            .map_err(|_| BoardError::Crypto("random number generator failed".into()))?;
+        Self::derive(passphrase, salt, PBKDF2_ITERATIONS)
+    }
+
+    /// Derive a key from a passphrase with the given salt and iteration count
+    pub fn derive(passphrase: &str, salt: Vec<u8>, iterations: u32) -> Result<Self, BoardError> {
+        let rounds = NonZeroU32::new(iterations)
+            .filter(|_| PBKDF2_ITERATION_RANGE.contains(&iterations))
+            .ok_or_else(|| {
+                BoardError::InvalidData(format!("invalid iteration count {}", iterations))
+            })?;
+        let mut key = [0u8; 32];
+        pbkdf2::derive(
+            pbkdf2::PBKDF2_HMAC_SHA256,
+            rounds,
+            &salt,
+            passphrase.as_bytes(),
+            &mut key,
+        );
+        Ok(Self {
+            key,
+            salt,
+            iterations,
+        })
+    }
+
+    /// Encrypt board state JSON into an envelope (fresh nonce every call)
+    pub fn seal(&self, plaintext: &[u8]) -> Result<EncryptedBoard, BoardError> {
+        let mut nonce = [0u8; NONCE_LEN];
+        SystemRandom::new()
+            .fill(&mut nonce)
+            .map_err(|_| BoardError::Crypto("random number generator failed".into()))?;
+
+        let mut in_out = plaintext.to_vec();
+        self.aead_key()?
+            .seal_in_place_append_tag(
+                Nonce::assume_unique_for_key(nonce),
+                Aad::empty(),
+                &mut in_out,
+            )
+            .map_err(|_| BoardError::Crypto("encryption failed".into()))?;
+
+        Ok(EncryptedBoard {
+            encrypted: FORMAT_VERSION,
+            iterations: self.iterations,
+            salt: BASE64.encode(&self.salt),
+            nonce: BASE64.encode(nonce),
+            ciphertext: BASE64.encode(in_out),
+        })
+    }
+
+    fn aead_key(&self) -> Result<LessSafeKey, BoardError> {
+        UnboundKey::new(&AES_256_GCM, &self.key)
+            .map(LessSafeKey::new)
+            .map_err(|_| BoardError::Crypto("invalid key".into()))
+    }
+}
+
+impl EncryptedBoard {
+    /// Decrypt the envelope, returning the plaintext and the key that opened it
+    pub fn open(&self, passphrase: &str) -> Result<(Vec<u8>, BoardKey), BoardError> {
+        if self.encrypted != FORMAT_VERSION {
+            return Err(BoardError::InvalidData(format!(
+                "unsupported encryption format {}",
+                self.encrypted
+            )));
+        }
+
+        let salt = decode(&self.salt)?;
+        let nonce: [u8; NONCE_LEN] = decode(&self.nonce)?
+            .try_into()
+            .map_err(|_| BoardError::InvalidData("invalid nonce".into()))?;
+        let mut in_out = decode(&self.ciphertext)?;
+
+        let key = BoardKey::derive(passphrase, salt, self.iterations)?;
+        let plaintext = key
+            .aead_key()?
+            .open_in_place(
+                Nonce::assume_unique_for_key(nonce),
+                Aad::empty(),
+                &mut in_out,
+            )
+            .map_err(|_| BoardError::WrongPassphrase)?
+            .to_vec();
+
+        Ok((plaintext, key))
+    }
+}
+
+/// Encrypt a board state
+pub fn encrypt_state(state: &BoardState, key: &BoardKey) -> Result<EncryptedBoard, BoardError> {
+    let json = serde_json::to_vec(state)?;
+    key.seal(&json)
+}
+
+/// Decrypt a board state, returning it with the key for later saves
+pub fn decrypt_state(
+    envelope: &EncryptedBoard,
+    passphrase: &str,
+) -> Result<(BoardState, BoardKey), BoardError> {
+    let (json, key) = envelope.open(passphrase)?;
+    let state = serde_json::from_slice(&json)?;
+    Ok((state, key))
+}
+
+/// Parse an encrypted envelope from board file contents, if it is one
+pub fn parse_envelope(json: &str) -> Option<EncryptedBoard> {
+    serde_json::from_str(json).ok()
+}
+
+/// Check if a board file holds an encrypted board
+pub fn is_encrypted_file(path: &Path) -> bool {
+    fs::read_to_string(path)
+        .ok()
+        .and_then(|json| parse_envelope(&json))
+        .is_some()
+}
+
+/// Check a new passphrase and its confirmation, returning a message for the
+/// user if it can't be used
+pub fn validate_new_passphrase(passphrase: &str, confirmation: &str) -> Result<(), String> {
+    if passphrase.chars().count() < MIN_PASSPHRASE_LEN {
+        return Err(format!(
+            "Passphrase must be at least {} characters",
+            MIN_PASSPHRASE_LEN
+        ));
+    }
+    if passphrase != confirmation {
+        return Err("Passphrases don't match".to_string());
+    }
+    Ok(())
+}
+
+fn decode(value: &str) -> Result<Vec<u8>, BoardError> {
+    BASE64
+        .decode(value)
+        .map_err(|e| BoardError::InvalidData(format!("invalid base64: {}", e)))
+}
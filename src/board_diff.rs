@@ -0,0 +1,146 @@
+//! Board diff - what changed on a board since a saved version of it.
+//!
+//! Comparing the board with a checkpoint marks its items on the canvas:
+//! items added since are outlined, items that moved or were resized show
+//! where they were, and items since removed are drawn as ghosts where they
+//! used to be. Items are matched by ID, so an item deleted and added again
+//! counts as removed and added.
+
+use crate::types::CanvasItem;
+use std::collections::{HashMap, HashSet};
+
+/// Position and size differences smaller than this are ignored, in canvas
+/// units
+const MOVE_TOLERANCE: f32 = 0.5;
+
+/// How an item on the board differs from the version it is compared with
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DiffMark {
+    /// Not in the version
+    Added,
+    /// Moved or resized since
+    Moved,
+    /// In the same place, with different content
+    Changed,
+}
+
+/// Differences between a saved version's items and the board's
+#[derive(Clone, Debug, Default)]
+pub struct BoardDiff {
+    added: HashSet<u64>,
+    /// Items in the version that are gone, as they were
+    removed: Vec<CanvasItem>,
+    /// Bounds the moved items had in the version, as (x, y, width, height)
+    moved: HashMap<u64, (f32, f32, f32, f32)>,
+    changed: HashSet<u64>,
+}
+
+impl BoardDiff {
+    /// Compare the items of a saved version with the board's current ones
+    pub fn between(version: &[CanvasItem], current: &[CanvasItem]) -> Self {
+        let old: HashMap<u64, &CanvasItem> = version.iter().map(|item| (item.id, item)).collect();
+        let current_ids: HashSet<u64> = current.iter().map(|item| item.id).collect();
+
+        let mut diff = Self::default();
+        for item in current {
+            let Some(before) = old.get(&item.id) else {
+                diff.added.insert(item.id);
+                continue;
+            };
+            if differs(before.position, item.position) || differs(before.size, item.size) {
+                diff.moved.insert(
+                    item.id,
+                    (
+                        before.position.0,
+                        before.position.1,
+                        before.size.0,
+                        before.size.1,
+                    ),
+                );
+            }
+            if content_changed(before, item) {
+                diff.changed.insert(item.id);
+            }
+        }
+        diff.removed = version
+            .iter()
+            .filter(|item| !current_ids.contains(&item.id))
+            .cloned()
+            .collect();
+        diff
+    }
+
+    /// How the item with `id` differs, if it does. Items that both moved
+    /// and changed are marked as moved.
+    pub fn mark(&self, id: u64) -> Option<DiffMark> {
+        if self.added.contains(&id) {
+            Some(DiffMark::Added)
+        } else if self.moved.contains_key(&id) {
+            Some(DiffMark::Moved)
+        } else if self.changed.contains(&id) {
+            Some(DiffMark::Changed)
+        } else {
+            None
+        }
+    }
+
+    /// Where a moved item was, as (x, y, width, height)
+    pub fn old_bounds(&self, id: u64) -> Option<(f32, f32, f32, f32)> {
+        self.moved.get(&id).copied()
+    }
+
+    /// Items since removed, as they were in the version
+    pub fn removed(&self) -> &[CanvasItem] {
+        &self.removed
+    }
+
+    pub fn added_count(&self) -> usize {
+        self.added.len()
+    }
+
+    pub fn moved_count(&self) -> usize {
+        self.moved.len()
+    }
+
+    pub fn changed_count(&self) -> usize {
+        self.changed.len()
+    }
+
+    /// Whether the board is the same as the version
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty()
+            && self.removed.is_empty()
+            && self.moved.is_empty()
+            && self.changed.is_empty()
+    }
+
+    /// One-line count of the differences, like "2 added · 1 moved"
+    pub fn summary(&self) -> String {
+        let parts: Vec<String> = [
+            (self.added.len(), "added"),
+            (self.removed.len(), "removed"),
+            (self.moved.len(), "moved"),
+            (self.changed.len(), "changed"),
+        ]
+        .into_iter()
+        .filter(|(count, _)| *count > 0)
+        .map(|(count, what)| format!("{} {}", count, what))
+        .collect();
+        if parts.is_empty() {
+            "No changes".to_string()
+        } else {
+            parts.join(" · ")
+        }
+    }
+}
+
+fn differs(a: (f32, f32), b: (f32, f32)) -> bool {
+    (a.0 - b.0).abs() > MOVE_TOLERANCE || (a.1 - b.1).abs() > MOVE_TOLERANCE
+}
+
+/// Whether an item's content or lock changed. Content has no equality of
+/// its own, so it is compared as saved.
+fn content_changed(before: &CanvasItem, after: &CanvasItem) -> bool {
+    before.locked != after.locked
+        || serde_json::to_value(&before.content).ok() != serde_json::to_value(&after.content).ok()
+}
@@ -0,0 +1,676 @@
+//! Static HTML export - a board as a standalone web page.
+//!
+//! The exported file has no external dependencies apart from YouTube
+//! thumbnails: images are embedded as data URIs, charts are drawn as inline
+//! SVG, and a small script provides pan (drag) and zoom (wheel) so the page
+//! behaves like the canvas. Video, audio and PDF items are shown as file
+//! cards since embedding them would make the page huge, and Vimeo,
+//! SoundCloud and Spotify embeds as links.
+
+use crate::board::BoardState;
+use crate::data::{ChartData, process_chart_data};
+use crate::error::BoardError;
+use crate::types::{ArrowHead, CanvasItem, ChartType, DataSource, ItemContent, ShapeType};
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use gpui::Hsla;
+use std::fmt::Write as _;
+use std::fs;
+use std::path::Path;
+use tracing::{debug, warn};
+
+/// Space around the board contents in pixels
+const PAGE_PADDING: f32 = 80.0;
+
+/// Code files larger than this are truncated in the export
+const MAX_CODE_BYTES: usize = 64 * 1024;
+
+/// Tables are cut off after this many rows
+const MAX_TABLE_ROWS: usize = 500;
+
+const STYLE: &str = r#"
+* { box-sizing: border-box; }
+html, body { margin: 0; height: 100%; overflow: hidden; background: #18181b; color: #e4e4e7;
+  font-family: -apple-system, BlinkMacSystemFont, "Segoe UI", sans-serif; }
+#viewport { position: fixed; inset: 0; cursor: grab; touch-action: none; }
+#viewport.dragging { cursor: grabbing; }
+#stage { position: absolute; left: 0; top: 0; transform-origin: 0 0; }
+.item { position: absolute; overflow: hidden; }
+.card { background: #27272a; border: 1px solid #3f3f46; border-radius: 8px; padding: 12px; }
+.card h3 { margin: 0 0 8px; font-size: 14px; }
+.note { white-space: pre-wrap; font-size: 14px; }
+.textbox { white-space: pre-wrap; overflow: visible; }
+.file { display: flex; flex-direction: column; align-items: center; justify-content: center;
+  gap: 6px; color: #a1a1aa; font-size: 13px; text-align: center; }
+.file .kind { font-size: 11px; text-transform: uppercase; letter-spacing: 0.08em; }
+img.media { width: 100%; height: 100%; object-fit: contain; display: block; }
+a { color: #38bdf8; word-break: break-all; }
+pre { margin: 0; white-space: pre-wrap; font-size: 12px; font-family: ui-monospace, Menlo, monospace; }
+table { border-collapse: collapse; width: 100%; font-size: 12px; }
+th, td { border: 1px solid #3f3f46; padding: 4px 8px; text-align: left; }
+th { background: #3f3f46; }
+table.stripe tr:nth-child(even) td { background: #2f2f33; }
+svg text { fill: #a1a1aa; font-size: 11px; }
+#hint { position: fixed; right: 12px; bottom: 12px; font-size: 12px; color: #71717a; }
+"#;
+
+const SCRIPT: &str = r#"
+const viewport = document.getElementById('viewport');
+const stage = document.getElementById('stage');
+let scale = 1, x = 0, y = 0, drag = null;
+function apply() { stage.style.transform = `translate(${x}px, ${y}px) scale(${scale})`; }
+function fit() {
+  const w = stage.offsetWidth, h = stage.offsetHeight;
+  scale = Math.min(innerWidth / w, innerHeight / h, 1);
+  x = (innerWidth - w * scale) / 2;
+  y = (innerHeight - h * scale) / 2;
+  apply();
+}
+viewport.addEventListener('wheel', e => {
+  e.preventDefault();
+  const next = Math.min(8, Math.max(0.05, scale * Math.exp(-e.deltaY * 0.0015)));
+  x = e.clientX - (e.clientX - x) * next / scale;
+  y = e.clientY - (e.clientY - y) * next / scale;
+  scale = next;
+  apply();
+}, { passive: false });
+viewport.addEventListener('pointerdown', e => {
+  if (e.target.closest('a')) return;
+  drag = { x: e.clientX - x, y: e.clientY - y };
+  viewport.classList.add('dragging');
+  viewport.setPointerCapture(e.pointerId);
+});
+viewport.addEventListener('pointermove', e => {
+  if (!drag) return;
+  x = e.clientX - drag.x;
+  y = e.clientY - drag.y;
+  apply();
+});
+viewport.addEventListener('pointerup', () => { drag = null; viewport.classList.remove('dragging'); });
+addEventListener('keydown', e => { if (e.key === '0') fit(); });
+addEventListener('resize', fit);
+fit();
+"#;
+
+/// Render a board as a standalone HTML page
+pub fn render_html(state: &BoardState, title: &str) -> String {
+    let (min_x, min_y, max_x, max_y) = content_bounds(&state.items).unwrap_or((0.0, 0.0, 0.0, 0.0));
+    let width = max_x - min_x + PAGE_PADDING * 2.0;
+    let height = max_y - min_y + PAGE_PADDING * 2.0;
+    let origin = (min_x - PAGE_PADDING, min_y - PAGE_PADDING);
+
+    let mut html = String::new();
+    html.push_str("<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n");
+    html.push_str("<meta name=\"viewport\" content=\"width=device-width, initial-scale=1\">\n");
+    let _ = writeln!(html, "<title>{}</title>", escape_html(title));
+    let _ = writeln!(html, "<style>{}</style>\n</head>\n<body>", STYLE);
+    html.push_str("<div id=\"viewport\">\n");
+    let _ = writeln!(
+        html,
+        "<div id=\"stage\" style=\"width:{:.0}px;height:{:.0}px\">",
+        width, height
+    );
+
+    for item in &state.items {
+        html.push_str(&render_item(item, state, origin));
+        html.push('\n');
+    }
+
+    html.push_str("</div>\n</div>\n");
+    html.push_str("<div id=\"hint\">Drag to pan · Scroll to zoom · 0 to fit</div>\n");
+    let _ = writeln!(html, "<script>{}</script>\n</body>\n</html>", SCRIPT);
+    html
+}
+
+/// Render a board as HTML and write it to `path`
+pub fn export_html(state: &BoardState, title: &str, path: &Path) -> Result<(), BoardError> {
+    let html = render_html(state, title);
+    fs::write(path, html).map_err(|e| BoardError::SaveFailed {
+        path: path.to_path_buf(),
+        source: e,
+    })?;
+    debug!("Exported board '{}' to {:?}", title, path);
+    Ok(())
+}
+
+/// Bounding box of all items (including arrow ends) as (min_x, min_y, max_x, max_y)
+fn content_bounds(items: &[CanvasItem]) -> Option<(f32, f32, f32, f32)> {
+    items.iter().fold(None, |acc, item| {
+        let (x, y) = item.position;
+        let (x2, y2) = match &item.content {
+            ItemContent::Arrow { end_offset, .. } => (x + end_offset.0, y + end_offset.1),
+            _ => (x + item.size.0, y + item.size.1),
+        };
+        let (lo_x, hi_x) = (x.min(x2), x.max(x2));
+        let (lo_y, hi_y) = (y.min(y2), y.max(y2));
+        Some(match acc {
+            None => (lo_x, lo_y, hi_x, hi_y),
+            Some((min_x, min_y, max_x, max_y)) => (
+                min_x.min(lo_x),
+                min_y.min(lo_y),
+                max_x.max(hi_x),
+                max_y.max(hi_y),
+            ),
+        })
+    })
+}
+
+fn render_item(item: &CanvasItem, state: &BoardState, origin: (f32, f32)) -> String {
+    let x = item.position.0 - origin.0;
+    let y = item.position.1 - origin.1;
+    let (w, h) = item.size;
+    let frame = |class: &str, extra_style: &str, body: &str| {
+        format!(
+            "<div class=\"item {}\" style=\"left:{:.1}px;top:{:.1}px;width:{:.1}px;height:{:.1}px;{}\">{}</div>",
+            class, x, y, w, h, extra_style, body
+        )
+    };
+
+    match &item.content {
+        ItemContent::Image(path) => match data_uri(path) {
+            Some(uri) => frame(
+                "",
+                "",
+                &format!(
+                    "<img class=\"media\" src=\"{}\" alt=\"{}\">",
+                    uri,
+                    escape_html(&item.content.display_name())
+                ),
+            ),
+            None => frame("card file", "", &file_card("Image", path)),
+        },
+        ItemContent::Text(text) => frame("card note", "", &escape_html(text)),
+        ItemContent::Video(path) => frame("card file", "", &file_card("Video", path)),
+        ItemContent::Audio(path) => frame("card file", "", &file_card("Audio", path)),
+        ItemContent::Pdf {
+            path, thumbnail, ..
+        } => match thumbnail.as_deref().and_then(data_uri) {
+            Some(uri) => frame(
+                "card",
+                "padding:0",
+                &format!(
+                    "<img class=\"media\" src=\"{}\" alt=\"{}\">",
+                    uri,
+                    escape_html(&item.content.display_name())
+                ),
+            ),
+            None => frame("card file", "", &file_card("PDF", path)),
+        },
+        ItemContent::Link(url) => {
+            let label = escape_html(url);
+            let body = match safe_href(url) {
+                Some(href) => format!(
+                    "<a href=\"{}\" target=\"_blank\" rel=\"noopener\">{}</a>",
+                    escape_html(href),
+                    label
+                ),
+                None => label,
+            };
+            frame("card", "", &body)
+        }
+        ItemContent::Bookmark {
+            url,
+            title,
+            description,
+            site,
+        } => {
+            let title = escape_html(title);
+            let heading = match safe_href(url) {
+                Some(href) => format!(
+                    "<a href=\"{}\" target=\"_blank\" rel=\"noopener\">{}</a>",
+                    escape_html(href),
+                    title
+                ),
+                None => title,
+            };
+            frame(
+                "card",
+                "",
+                &format!(
+                    "<h3>{}</h3><p>{}</p><small>{}</small>",
+                    heading,
+                    escape_html(description),
+                    escape_html(site)
+                ),
+            )
+        }
+        ItemContent::YouTube(id) => {
+            let id = escape_html(id);
+            frame(
+                "card",
+                "padding:0",
+                &format!(
+                    "<a href=\"https://www.youtube.com/watch?v={id}\" target=\"_blank\" rel=\"noopener\">\
+                     <img class=\"media\" src=\"https://img.youtube.com/vi/{id}/hqdefault.jpg\" alt=\"YouTube video\"></a>"
+                ),
+            )
+        }
+        ItemContent::Vimeo(id) => {
+            let id = escape_html(id);
+            frame(
+                "card",
+                "",
+                &format!(
+                    "<a href=\"https://vimeo.com/{id}\" target=\"_blank\" rel=\"noopener\">Vimeo video {id}</a>"
+                ),
+            )
+        }
+        ItemContent::SoundCloud(url) => {
+            let url = escape_html(url);
+            frame(
+                "card",
+                "",
+                &format!("<a href=\"{url}\" target=\"_blank\" rel=\"noopener\">{url}</a>"),
+            )
+        }
+        ItemContent::Spotify(path) => {
+            let path = escape_html(path);
+            frame(
+                "card",
+                "",
+                &format!(
+                    "<a href=\"https://open.spotify.com/{path}\" target=\"_blank\" rel=\"noopener\">Spotify {path}</a>"
+                ),
+            )
+        }
+        ItemContent::Markdown { title, content, .. } => frame(
+            "card",
+            "overflow:auto",
+            &format!(
+                "<h3>{}</h3><pre>{}</pre>",
+                escape_html(title),
+                escape_html(content)
+            ),
+        ),
+        ItemContent::Code { path, language, .. } => {
+            let code = read_code(path).unwrap_or_default();
+            frame(
+                "card",
+                "overflow:auto",
+                &format!(
+                    "<h3>{}</h3><pre><code class=\"language-{}\">{}</code></pre>",
+                    escape_html(&item.content.display_name()),
+                    escape_html(language),
+                    escape_html(&code)
+                ),
+            )
+        }
+        ItemContent::TextBox {
+            text,
+            font_size,
+            color,
+        } => frame(
+            "textbox",
+            &format!("font-size:{:.0}px;color:{}", font_size, css_hex(color)),
+            &escape_html(text),
+        ),
+        ItemContent::Arrow {
+            end_offset,
+            color,
+            thickness,
+            head_style,
+        } => render_arrow(
+            (x, y),
+            *end_offset,
+            &css_hex(color),
+            *thickness,
+            *head_style,
+        ),
+        ItemContent::Shape {
+            shape_type,
+            fill_color,
+            border_color,
+            border_width,
+        } => {
+            let radius = match shape_type {
+                ShapeType::Rectangle => "0",
+                ShapeType::RoundedRect => "12px",
+                ShapeType::Ellipse => "50%",
+            };
+            let fill = fill_color
+                .as_deref()
+                .map(css_hex)
+                .unwrap_or_else(|| "transparent".to_string());
+            frame(
+                "",
+                &format!(
+                    "background:{};border:{:.1}px solid {};border-radius:{}",
+                    fill,
+                    border_width,
+                    css_hex(border_color),
+                    radius
+                ),
+                "",
+            )
+        }
+        ItemContent::Table {
+            data_source_id,
+            show_headers,
+            stripe,
+        } => {
+            let body = state
+                .data_sources
+                .get(data_source_id)
+                .map(|ds| render_table(ds, *show_headers, *stripe))
+                .unwrap_or_default();
+            frame("card", "overflow:auto;padding:0", &body)
+        }
+        ItemContent::Chart {
+            data_source_id,
+            config,
+            ..
+        } => {
+            let svg = state
+                .data_sources
+                .get(data_source_id)
+                .and_then(|ds| process_chart_data(ds, config))
+                .map(|data| render_chart_svg(&data, config.chart_type, w, h))
+                .unwrap_or_default();
+            let title = config
+                .title
+                .as_deref()
+                .map(|t| format!("<h3>{}</h3>", escape_html(t)))
+                .unwrap_or_default();
+            frame("card", "padding:8px", &format!("{}{}", title, svg))
+        }
+    }
+}
+
+fn file_card(kind: &str, path: &Path) -> String {
+    let name = path
+        .file_name()
+        .and_then(|n| n.to_str())
+        .unwrap_or("Unknown");
+    format!(
+        "<span class=\"kind\">{}</span><span>{}</span>",
+        kind,
+        escape_html(name)
+    )
+}
+
+fn render_arrow(
+    start: (f32, f32),
+    end_offset: (f32, f32),
+    color: &str,
+    thickness: f32,
+    head: ArrowHead,
+) -> String {
+    let (dx, dy) = end_offset;
+    let mut svg = format!(
+        "<svg class=\"item\" style=\"left:{:.1}px;top:{:.1}px;overflow:visible\" width=\"1\" height=\"1\">\
+         <g stroke=\"{}\" stroke-width=\"{:.1}\" stroke-linecap=\"round\" fill=\"none\">\
+         <line x1=\"0\" y1=\"0\" x2=\"{:.1}\" y2=\"{:.1}\"/>",
+        start.0, start.1, color, thickness, dx, dy
+    );
+
+    // Same head geometry as the canvas
+    if head != ArrowHead::None {
+        let angle = dy.atan2(dx);
+        let head_size = (thickness * 4.0).max(8.0);
+        let head_angle = 0.5;
+        for side in [-head_angle, head_angle] {
+            let a = angle + std::f32::consts::PI + side;
+            let _ = write!(
+                svg,
+                "<line x1=\"{:.1}\" y1=\"{:.1}\" x2=\"{:.1}\" y2=\"{:.1}\"/>",
+                dx,
+                dy,
+                dx + head_size * a.cos(),
+                dy + head_size * a.sin()
+            );
+        }
+    }
+
+    svg.push_str("</g></svg>");
+    svg
+}
+
+fn render_table(ds: &DataSource, show_headers: bool, stripe: bool) -> String {
+    let mut html = String::new();
+    let _ = write!(
+        html,
+        "<table{}>",
+        if stripe { " class=\"stripe\"" } else { "" }
+    );
+    if show_headers {
+        html.push_str("<tr>");
+        for column in &ds.columns {
+            let _ = write!(html, "<th>{}</th>", escape_html(&column.name));
+        }
+        html.push_str("</tr>");
+    }
+    for row in ds.rows.iter().take(MAX_TABLE_ROWS) {
+        html.push_str("<tr>");
+        for cell in &row.cells {
+            let _ = write!(html, "<td>{}</td>", escape_html(&cell.to_string()));
+        }
+        html.push_str("</tr>");
+    }
+    html.push_str("</table>");
+    html
+}
+
+/// Render processed chart data as an inline SVG sized to the item
+fn render_chart_svg(data: &ChartData, chart_type: ChartType, width: f32, height: f32) -> String {
+    // Leave room for the card padding and labels
+    let w = (width - 16.0).max(40.0);
+    let h = (height - 16.0).max(40.0);
+    let mut svg = format!(
+        "<svg width=\"{:.0}\" height=\"{:.0}\" viewBox=\"0 0 {:.0} {:.0}\">",
+        w, h, w, h
+    );
+
+    let count = data.points.len().max(1) as f32;
+    let label_h = 16.0;
+    let plot_h = h - label_h;
+    let low = data.min_value.min(0.0);
+    let range = (data.max_value - low).max(f64::EPSILON);
+    let value_y = |value: f64| plot_h - ((value - low) / range) as f32 * plot_h;
+    let slot = w / count;
+    let center_x = |i: usize| slot * i as f32 + slot / 2.0;
+
+    match chart_type {
+        ChartType::Bar => {
+            for (i, point) in data.points.iter().enumerate() {
+                let top = value_y(point.value.max(low));
+                let base = value_y(0.0_f64.max(low));
+                let _ = write!(
+                    svg,
+                    "<rect x=\"{:.1}\" y=\"{:.1}\" width=\"{:.1}\" height=\"{:.1}\" fill=\"{}\"/>",
+                    slot * i as f32 + slot * 0.15,
+                    top.min(base),
+                    slot * 0.7,
+                    (base - top).abs(),
+                    css_color(point.color)
+                );
+            }
+        }
+        ChartType::Line | ChartType::Area | ChartType::Scatter => {
+            let points: Vec<(f32, f32)> = data
+                .points
+                .iter()
+                .enumerate()
+                .map(|(i, p)| (center_x(i), value_y(p.value)))
+                .collect();
+            let color = data
+                .points
+                .first()
+                .map(|p| css_color(p.color))
+                .unwrap_or_default();
+            let path: Vec<String> = points
+                .iter()
+                .map(|(x, y)| format!("{:.1},{:.1}", x, y))
+                .collect();
+
+            if chart_type == ChartType::Area {
+                let (first_x, last_x) = (points[0].0, points[points.len() - 1].0);
+                let _ = write!(
+                    svg,
+                    "<polygon points=\"{:.1},{:.1} {} {:.1},{:.1}\" fill=\"{}\" fill-opacity=\"0.3\"/>",
+                    first_x,
+                    plot_h,
+                    path.join(" "),
+                    last_x,
+                    plot_h,
+                    color
+                );
+            }
+            if chart_type != ChartType::Scatter {
+                let _ = write!(
+                    svg,
+                    "<polyline points=\"{}\" fill=\"none\" stroke=\"{}\" stroke-width=\"2\"/>",
+                    path.join(" "),
+                    color
+                );
+            }
+            for ((x, y), point) in points.iter().zip(&data.points) {
+                let _ = write!(
+                    svg,
+                    "<circle cx=\"{:.1}\" cy=\"{:.1}\" r=\"3.5\" fill=\"{}\"/>",
+                    x,
+                    y,
+                    css_color(point.color)
+                );
+            }
+        }
+        ChartType::Pie => {
+            let total: f64 = data.points.iter().map(|p| p.value.max(0.0)).sum();
+            let (cx, cy) = (w / 2.0, h / 2.0);
+            let r = w.min(h) / 2.0 - 4.0;
+            let mut angle = -std::f32::consts::FRAC_PI_2;
+            for point in &data.points {
+                if total <= 0.0 {
+                    break;
+                }
+                let sweep = (point.value.max(0.0) / total) as f32 * std::f32::consts::TAU;
+                if sweep >= std::f32::consts::TAU - 0.001 {
+                    let _ = write!(
+                        svg,
+                        "<circle cx=\"{:.1}\" cy=\"{:.1}\" r=\"{:.1}\" fill=\"{}\"/>",
+                        cx,
+                        cy,
+                        r,
+                        css_color(point.color)
+                    );
+                    break;
+                }
+                let end = angle + sweep;
+                let _ = write!(
+                    svg,
+                    "<path d=\"M{:.1},{:.1} L{:.1},{:.1} A{:.1},{:.1} 0 {} 1 {:.1},{:.1} Z\" fill=\"{}\"/>",
+                    cx,
+                    cy,
+                    cx + r * angle.cos(),
+                    cy + r * angle.sin(),
+                    r,
+                    r,
+                    if sweep > std::f32::consts::PI { 1 } else { 0 },
+                    cx + r * end.cos(),
+                    cy + r * end.sin(),
+                    css_color(point.color)
+                );
+                angle = end;
+            }
+            svg.push_str("</svg>");
+            return svg;
+        }
+    }
+
+    // X axis labels (not for pie charts)
+    for (i, point) in data.points.iter().enumerate() {
+        let _ = write!(
+            svg,
+            "<text x=\"{:.1}\" y=\"{:.1}\" text-anchor=\"middle\">{}</text>",
+            center_x(i),
+            h - 3.0,
+            escape_html(&point.label)
+        );
+    }
+
+    svg.push_str("</svg>");
+    svg
+}
+
+/// Escape text for use in HTML content and attribute values
+pub fn escape_html(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Only link to web and mail URLs (never `javascript:` and the like)
+fn safe_href(url: &str) -> Option<&str> {
+    let lower = url.trim_start().to_ascii_lowercase();
+    ["http://", "https://", "mailto:"]
+        .iter()
+        .any(|scheme| lower.starts_with(scheme))
+        .then_some(url)
+}
+
+/// Embed a file as a data URI
+pub(crate) fn data_uri(path: &Path) -> Option<String> {
+    let mime = image_mime_type(path)?;
+    match fs::read(path) {
+        Ok(bytes) => Some(format!("data:{};base64,{}", mime, BASE64.encode(bytes))),
+        Err(e) => {
+            warn!("Failed to embed {:?} in HTML export: {}", path, e);
+            None
+        }
+    }
+}
+
+fn image_mime_type(path: &Path) -> Option<&'static str> {
+    let ext = path.extension()?.to_str()?.to_lowercase();
+    Some(match ext.as_str() {
+        "png" => "image/png",
+        "jpg" | "jpeg" => "image/jpeg",
+        "gif" => "image/gif",
+        "webp" => "image/webp",
+        "svg" => "image/svg+xml",
+        "bmp" => "image/bmp",
+        "avif" => "image/avif",
+        _ => return None,
+    })
+}
+
+fn read_code(path: &Path) -> Option<String> {
+    let mut code = fs::read_to_string(path).ok()?;
+    if code.len() > MAX_CODE_BYTES {
+        let mut end = MAX_CODE_BYTES;
+        while !code.is_char_boundary(end) {
+            end -= 1;
+        }
+        code.truncate(end);
+        code.push_str("\n…");
+    }
+    Some(code)
+}
+
+/// Sanitize a stored "#rrggbb" color for CSS (falls back to white)
+fn css_hex(color: &str) -> String {
+    let hex = color.trim_start_matches('#');
+    if hex.len() == 6 && hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        format!("#{}", hex)
+    } else {
+        "#ffffff".to_string()
+    }
+}
+
+fn css_color(color: Hsla) -> String {
+    format!(
+        "hsla({:.0}, {:.0}%, {:.0}%, {:.2})",
+        color.h * 360.0,
+        color.s * 100.0,
+        color.l * 100.0,
+        color.a
+    )
+}
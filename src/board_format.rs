@@ -0,0 +1,126 @@
+//! Git-friendly board layout - board files that diff well in version control.
+//!
+//! In the default layout everything lives in `board.json`, and data sources
+//! are serialized from a `HashMap`, so their order changes from save to save.
+//! The git-friendly layout instead writes each data source to its own
+//! `data_sources/<id>.json` file and leaves them out of `board.json`. Every
+//! file is pretty-printed with a trailing newline, and items are written in
+//! their z-order, which only changes when the user reorders items.
+//!
+//! Loading merges the split files back in, so either layout opens no matter
+//! which one is currently selected in the settings.
+
+use crate::board::BoardState;
+use crate::error::BoardError;
+use crate::types::DataSource;
+use std::collections::{BTreeMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use tracing::{trace, warn};
+
+/// Directory (inside the board directory) holding split data source files
+pub const DATA_SOURCES_DIR: &str = "data_sources";
+
+/// Get the split data sources directory for a board directory
+pub fn data_sources_dir(board_dir: &Path) -> PathBuf {
+    board_dir.join(DATA_SOURCES_DIR)
+}
+
+/// Serialize a value as pretty JSON with a trailing newline
+pub fn to_stable_json<T: serde::Serialize>(value: &T) -> Result<String, BoardError> {
+    let mut json = serde_json::to_string_pretty(value)?;
+    json.push('\n');
+    Ok(json)
+}
+
+/// Save a board in the git-friendly layout.
+///
+/// Data source files that no longer belong to the board are removed.
+pub fn save_split(mut state: BoardState, path: &Path) -> Result<(), BoardError> {
+    let board_dir = path.parent().unwrap_or(Path::new("."));
+    let dir = data_sources_dir(board_dir);
+    let data_sources: BTreeMap<u64, DataSource> = std::mem::take(&mut state.data_sources)
+        .into_iter()
+        .collect();
+
+    let save_failed = |path: &Path, source: std::io::Error| BoardError::SaveFailed {
+        path: path.to_path_buf(),
+        source,
+    };
+
+    fs::create_dir_all(&dir).map_err(|e| save_failed(&dir, e))?;
+    for (id, data_source) in &data_sources {
+        let file = dir.join(format!("{}.json", id));
+        write_if_changed(&file, &to_stable_json(data_source)?)
+            .map_err(|e| save_failed(&file, e))?;
+    }
+
+    // Drop files for data sources that were deleted
+    let keep: HashSet<PathBuf> = data_sources
+        .keys()
+        .map(|id| dir.join(format!("{}.json", id)))
+        .collect();
+    for file in list_data_source_files(&dir) {
+        if !keep.contains(&file) {
+            if let Err(e) = fs::remove_file(&file) {
+                warn!("Failed to remove stale data source {:?}: {}", file, e);
+            }
+        }
+    }
+
+    write_if_changed(path, &to_stable_json(&state)?).map_err(|e| save_failed(path, e))?;
+    trace!("Board state saved to {:?} (git-friendly)", path);
+    Ok(())
+}
+
+/// Merge split data source files into a loaded state.
+///
+/// Data sources stored inline in `board.json` take precedence.
+pub fn load_split(state: &mut BoardState, board_dir: &Path) {
+    for file in list_data_source_files(&data_sources_dir(board_dir)) {
+        let data_source = fs::read_to_string(&file)
+            .map_err(|e| e.to_string())
+            .and_then(|json| serde_json::from_str::<DataSource>(&json).map_err(|e| e.to_string()));
+        match data_source {
+            Ok(data_source) => {
+                state
+                    .data_sources
+                    .entry(data_source.id)
+                    .or_insert(data_source);
+            }
+            Err(e) => warn!("Failed to load data source {:?}: {}", file, e),
+        }
+    }
+}
+
+/// Remove the split data source files (after saving in the default layout)
+pub fn remove_split(board_dir: &Path) {
+    let dir = data_sources_dir(board_dir);
+    if dir.exists() {
+        if let Err(e) = fs::remove_dir_all(&dir) {
+            warn!("Failed to remove {:?}: {}", dir, e);
+        }
+    }
+}
+
+fn list_data_source_files(dir: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+
+    let mut files: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+        .collect();
+    files.sort();
+    files
+}
+
+/// Skip rewriting unchanged files so their modification times stay put
+fn write_if_changed(path: &Path, contents: &str) -> std::io::Result<()> {
+    if fs::read_to_string(path).is_ok_and(|existing| existing == contents) {
+        return Ok(());
+    }
+    fs::write(path, contents)
+}
@@ -7,6 +7,7 @@
 //! - iCloud sync discovery across devices
 //! - Automatic purging of old trashed boards (30+ days)
 //! - Legacy board migration from single-board format
+//! - Tracking which boards are open in which window
 //!
 //! ## Storage Locations
 //!
@@ -16,7 +17,10 @@
 //! - **Custom**: User-specified directory
 
 use crate::app::StorageLocation;
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::fs;
 use std::path::PathBuf;
 use std::time::{SystemTime, UNIX_EPOCH};
@@ -94,6 +98,9 @@ pub struct BoardMetadata {
     /// Timestamp when board was moved to trash (None = not deleted)
     #[serde(default)]
     pub deleted_at: Option<u64>,
+    /// Whether the board is encrypted with a passphrase
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub encrypted: bool,
 }
 
 impl BoardMetadata {
@@ -114,6 +121,7 @@ impl BoardMetadata {
             updated_at: now,
             storage_location: location,
             deleted_at: None,
+            encrypted: false,
         }
     }
 
@@ -158,15 +166,19 @@ impl BoardMetadata {
         })
     }
 
-    /// Check if board should be permanently deleted (older than 30 days)
-    pub fn should_purge(&self) -> bool {
-        const THIRTY_DAYS: u64 = 30 * 24 * 60 * 60;
+    /// Check if board should be permanently deleted (in trash longer than
+    /// `retention_days`). A retention of 0 keeps trashed boards forever.
+    pub fn should_purge(&self, retention_days: u32) -> bool {
+        if retention_days == 0 {
+            return false;
+        }
+        let retention_secs = u64::from(retention_days) * 24 * 60 * 60;
         if let Some(deleted_at) = self.deleted_at {
             let now = SystemTime::now()
                 .duration_since(UNIX_EPOCH)
                 .unwrap_or_default()
                 .as_secs();
-            now.saturating_sub(deleted_at) > THIRTY_DAYS
+            now.saturating_sub(deleted_at) > retention_secs
         } else {
             false
         }
@@ -194,6 +206,11 @@ impl BoardMetadata {
         self.board_dir().join("files")
     }
 
+    /// Get the latest rendered thumbnail for this board, if any
+    pub fn thumbnail_path(&self) -> Option<PathBuf> {
+        crate::board_thumbnail::latest_thumbnail(&self.board_dir())
+    }
+
     pub fn formatted_date(&self) -> String {
         // Simple date formatting - just show relative time or date
         let now = SystemTime::now()
@@ -246,8 +263,8 @@ impl BoardIndex {
             index.save();
         }
 
-        // Auto-purge boards in trash for more than 30 days
-        index.purge_old_trash();
+        // Auto-purge boards that have been in the trash past the retention period
+        index.purge_old_trash(crate::settings::trash_retention_days());
 
         index
     }
@@ -304,6 +321,7 @@ impl BoardIndex {
                     updated_at,
                     storage_location: StoredLocation::ICloud,
                     deleted_at: None,
+                    encrypted: crate::board_crypto::is_encrypted_file(&board_json),
                 };
 
                 tracing::debug!("Discovered iCloud board: {} ({})", metadata.name, metadata.id);
@@ -355,6 +373,16 @@ impl BoardIndex {
         (created, modified)
     }
 
+    /// Read the index from disk again, picking up boards other windows
+    /// created, renamed or deleted since it was loaded
+    pub fn reload(&mut self) {
+        if let Ok(json) = fs::read_to_string(Self::index_path()) {
+            if let Ok(index) = serde_json::from_str(&json) {
+                *self = index;
+            }
+        }
+    }
+
     pub fn save(&self) {
         let index_path = Self::index_path();
 
@@ -400,6 +428,17 @@ impl BoardIndex {
         }
     }
 
+    /// Record whether a board is encrypted
+    pub fn set_board_encrypted(&mut self, id: &str, encrypted: bool) -> bool {
+        if let Some(board) = self.boards.iter_mut().find(|b| b.id == id) {
+            board.encrypted = encrypted;
+            self.save();
+            true
+        } else {
+            false
+        }
+    }
+
     /// Soft delete - moves board to trash (can be restored)
     pub fn delete_board(&mut self, id: &str) -> bool {
         if let Some(board) = self.boards.iter_mut().find(|b| b.id == id) {
@@ -467,10 +506,10 @@ impl BoardIndex {
         count
     }
 
-    /// Auto-purge boards deleted more than 30 days ago
-    pub fn purge_old_trash(&mut self) -> usize {
+    /// Auto-purge boards deleted more than `retention_days` ago (0 disables purging)
+    pub fn purge_old_trash(&mut self, retention_days: u32) -> usize {
         let to_purge: Vec<_> = self.boards.iter()
-            .filter(|b| b.should_purge())
+            .filter(|b| b.should_purge(retention_days))
             .map(|b| (b.id.clone(), b.storage_location.base_path().join(&b.id)))
             .collect();
 
@@ -480,10 +519,14 @@ impl BoardIndex {
             let _ = fs::remove_dir_all(dir);
         }
 
-        self.boards.retain(|b| !b.should_purge());
+        self.boards.retain(|b| !b.should_purge(retention_days));
 
         if count > 0 {
-            tracing::info!("Purged {} boards from trash (older than 30 days)", count);
+            tracing::info!(
+                "Purged {} boards from trash (older than {} days)",
+                count,
+                retention_days
+            );
             self.save();
         }
 
@@ -587,6 +630,52 @@ impl BoardIndex {
     }
 }
 
+// ============================================================================
+// Open Board Registry
+// ============================================================================
+
+/// Boards currently open in a window: board ID -> window ID.
+///
+/// This is runtime-only state shared by all windows of the process. A board is
+/// open in at most one window at a time so two windows never save over each
+/// other's changes.
+static OPEN_BOARDS: Lazy<Mutex<HashMap<String, u64>>> = Lazy::new(|| Mutex::new(HashMap::new()));
+
+impl BoardIndex {
+    /// Claim a board for a window.
+    ///
+    /// Returns `Err` with the owning window ID if the board is already open
+    /// in a different window.
+    pub fn claim_open_board(board_id: &str, window_id: u64) -> Result<(), u64> {
+        let mut open = OPEN_BOARDS.lock();
+        match open.get(board_id) {
+            Some(&owner) if owner != window_id => Err(owner),
+            _ => {
+                open.insert(board_id.to_string(), window_id);
+                Ok(())
+            }
+        }
+    }
+
+    /// Release a board claimed by a window
+    pub fn release_open_board(board_id: &str, window_id: u64) {
+        let mut open = OPEN_BOARDS.lock();
+        if open.get(board_id) == Some(&window_id) {
+            open.remove(board_id);
+        }
+    }
+
+    /// Get the window a board is open in, if any
+    pub fn window_for_board(board_id: &str) -> Option<u64> {
+        OPEN_BOARDS.lock().get(board_id).copied()
+    }
+
+    /// Check whether a board is open in any window
+    pub fn is_board_open(board_id: &str) -> bool {
+        OPEN_BOARDS.lock().contains_key(board_id)
+    }
+}
+
 /// Generate a cryptographically secure UUID v4.
 fn generate_uuid() -> String {
     uuid::Uuid::new_v4().to_string()
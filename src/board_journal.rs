@@ -0,0 +1,198 @@
+//! Board journal - append-only log of mutations between full saves.
+//!
+//! Every history entry pushed on a board is also appended as one JSON line to
+//! `journal.jsonl` in the board directory. A successful full save truncates
+//! the journal, so a non-empty journal on load means the app exited before
+//! the last changes reached `board.json`.
+//!
+//! ## Recovery Flow
+//!
+//! 1. [`Board::load`](crate::board::Board::load) moves a leftover journal to
+//!    `journal.recovered.jsonl` so new edits don't mix with it
+//! 2. On launch the app prompts for every board with pending entries
+//! 3. Accepting the prompt replays the entries on top of the loaded board
+//! 4. Saving the board first discards them, since they would replay onto
+//!    newer work
+
+use crate::board::{BoardState, UndoOperation};
+use crate::error::BoardError;
+use serde::{Deserialize, Serialize};
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use tracing::warn;
+
+/// Active journal file name inside a board directory
+const JOURNAL_FILE: &str = "journal.jsonl";
+
+/// Journal left over from a crash, waiting for the user to recover it
+const RECOVERED_JOURNAL_FILE: &str = "journal.recovered.jsonl";
+
+/// A single journaled mutation
+#[derive(Serialize, Deserialize, Clone)]
+pub enum JournalEntry {
+    /// A delta operation (from `push_operation`)
+    Operation(UndoOperation),
+    /// A full state (from snapshot-based `push_history`)
+    State(BoardState),
+}
+
+/// Append-only journal for a single board
+#[derive(Debug, Clone)]
+pub struct BoardJournal {
+    board_dir: PathBuf,
+}
+
+impl BoardJournal {
+    pub fn new(board_dir: PathBuf) -> Self {
+        Self { board_dir }
+    }
+
+    /// Path of the active journal
+    pub fn path(&self) -> PathBuf {
+        self.board_dir.join(JOURNAL_FILE)
+    }
+
+    /// Path of the journal awaiting recovery
+    pub fn recovered_path(&self) -> PathBuf {
+        self.board_dir.join(RECOVERED_JOURNAL_FILE)
+    }
+
+    /// Append an entry to the journal.
+    pub fn append(&self, entry: &JournalEntry) -> Result<(), BoardError> {
+        let path = self.path();
+        let line = serde_json::to_string(entry)?;
+
+        fs::create_dir_all(&self.board_dir).map_err(|e| BoardError::SaveFailed {
+            path: path.clone(),
+            source: e,
+        })?;
+
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&path)
+            .map_err(|e| BoardError::SaveFailed {
+                path: path.clone(),
+                source: e,
+            })?;
+
+        writeln!(file, "{}", line)
+            .and_then(|_| file.sync_data())
+            .map_err(|e| BoardError::SaveFailed { path, source: e })
+    }
+
+    /// Discard the active journal (called after a successful full save)
+    pub fn clear(&self) {
+        let path = self.path();
+        if path.exists() {
+            if let Err(e) = fs::remove_file(&path) {
+                warn!("Failed to clear board journal {:?}: {}", path, e);
+            }
+        }
+    }
+
+    /// Discard the active journal and any journal waiting to be recovered,
+    /// once the board has been saved. Recovered entries would replace the
+    /// saved board with the one before the crash.
+    pub fn board_saved(&self) {
+        self.clear();
+        self.discard_recovered();
+    }
+
+    /// Move a leftover journal aside for recovery.
+    ///
+    /// Entries are appended to any journal still waiting from an earlier
+    /// crash. Returns true if there are entries waiting to be recovered.
+    pub fn stash_for_recovery(&self) -> bool {
+        let path = self.path();
+        if has_entries(&path) {
+            let stashed = fs::read(&path).and_then(|content| {
+                OpenOptions::new()
+                    .create(true)
+                    .append(true)
+                    .open(self.recovered_path())?
+                    .write_all(&content)
+            });
+            match stashed {
+                Ok(()) => self.clear(),
+                Err(e) => warn!("Failed to stash board journal {:?}: {}", path, e),
+            }
+        }
+        self.has_pending_recovery()
+    }
+
+    /// Check if a crash journal is waiting to be recovered.
+    ///
+    /// Also true for a journal that hasn't been stashed yet, i.e. a board
+    /// that hasn't been opened since the crash.
+    pub fn has_pending_recovery(&self) -> bool {
+        has_entries(&self.recovered_path()) || has_entries(&self.path())
+    }
+
+    /// Read stashed entries waiting to be recovered.
+    ///
+    /// Lines that fail to parse (e.g. a write cut short by the crash) end the
+    /// replay, since later entries may depend on them.
+    pub fn pending_entries(&self) -> Vec<JournalEntry> {
+        read_entries(&self.recovered_path())
+    }
+
+    /// Delete the recovered journal once it has been applied or dismissed
+    pub fn discard_recovered(&self) {
+        let path = self.recovered_path();
+        if path.exists() {
+            if let Err(e) = fs::remove_file(&path) {
+                warn!("Failed to remove recovered journal {:?}: {}", path, e);
+            }
+        }
+    }
+}
+
+/// Replay journal entries on top of a board state
+pub fn replay(state: &mut BoardState, entries: &[JournalEntry]) {
+    for entry in entries {
+        match entry {
+            JournalEntry::Operation(op) => {
+                let mut items_index = state
+                    .items
+                    .iter()
+                    .enumerate()
+                    .map(|(idx, item)| (item.id, idx))
+                    .collect();
+                op.apply(&mut state.items, &mut items_index);
+                if let Some(max_id) = state.items.iter().map(|item| item.id).max() {
+                    state.next_item_id = state.next_item_id.max(max_id + 1);
+                }
+            }
+            JournalEntry::State(snapshot) => {
+                *state = snapshot.clone();
+            }
+        }
+    }
+}
+
+fn has_entries(path: &Path) -> bool {
+    fs::metadata(path).map(|m| m.len() > 0).unwrap_or(false)
+}
+
+fn read_entries(path: &Path) -> Vec<JournalEntry> {
+    let Ok(content) = fs::read_to_string(path) else {
+        return Vec::new();
+    };
+
+    let mut entries = Vec::new();
+    for line in content.lines().filter(|l| !l.trim().is_empty()) {
+        match serde_json::from_str(line) {
+            Ok(entry) => entries.push(entry),
+            Err(e) => {
+                warn!(
+                    "Stopping journal replay at corrupt entry in {:?}: {}",
+                    path, e
+                );
+                break;
+            }
+        }
+    }
+    entries
+}
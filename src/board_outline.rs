@@ -0,0 +1,164 @@
+//! Board outline - a lightweight index of a board's contents.
+//!
+//! Groups every item on the board by kind (images, notes, tables, ...) with
+//! per-group counts and the on-disk size of linked files. Backs the Outline
+//! side panel, which lists the groups and jumps to an item when clicked.
+//!
+//! File sizes are read from disk once per path and reused across rebuilds,
+//! so the index stays cheap to refresh while the board is being edited.
+
+use crate::types::{CanvasItem, DataSource, ItemContent};
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::PathBuf;
+
+/// Longest name shown for an entry before it is truncated
+const MAX_NAME_LEN: usize = 60;
+
+/// One item in the outline
+#[derive(Clone, Debug, PartialEq)]
+pub struct OutlineEntry {
+    pub item_id: u64,
+    /// Short name to show in the list
+    pub name: String,
+    /// Size of the item's file on disk (None for items without a file,
+    /// or when the file is missing)
+    pub file_size: Option<u64>,
+}
+
+/// All items of one kind
+#[derive(Clone, Debug, PartialEq)]
+pub struct OutlineGroup {
+    /// Group heading (e.g. "Images")
+    pub label: &'static str,
+    /// Items in z-order
+    pub entries: Vec<OutlineEntry>,
+    /// Combined size of the group's files
+    pub file_size: u64,
+}
+
+impl OutlineGroup {
+    pub fn count(&self) -> usize {
+        self.entries.len()
+    }
+}
+
+/// Items on a board grouped by kind
+#[derive(Clone, Debug, Default)]
+pub struct BoardOutline {
+    /// Groups sorted by label
+    pub groups: Vec<OutlineGroup>,
+    /// Total number of items
+    pub item_count: usize,
+    /// Combined size of every linked file
+    pub total_file_size: u64,
+    file_sizes: HashMap<PathBuf, Option<u64>>,
+}
+
+impl BoardOutline {
+    /// Index a board's items
+    pub fn build(items: &[CanvasItem], data_sources: &HashMap<u64, DataSource>) -> Self {
+        Self::build_with_sizes(items, data_sources, HashMap::new())
+    }
+
+    /// Re-index after the board changed, reusing file sizes already read
+    pub fn rebuild(&mut self, items: &[CanvasItem], data_sources: &HashMap<u64, DataSource>) {
+        let file_sizes = std::mem::take(&mut self.file_sizes);
+        *self = Self::build_with_sizes(items, data_sources, file_sizes);
+    }
+
+    fn build_with_sizes(
+        items: &[CanvasItem],
+        data_sources: &HashMap<u64, DataSource>,
+        mut file_sizes: HashMap<PathBuf, Option<u64>>,
+    ) -> Self {
+        let mut groups: BTreeMap<&'static str, OutlineGroup> = BTreeMap::new();
+        let mut total_file_size = 0;
+
+        for item in items {
+            let file_size = item.content.file_paths().first().and_then(|path| {
+                *file_sizes
+                    .entry((*path).clone())
+                    .or_insert_with(|| fs::metadata(path).ok().map(|m| m.len()))
+            });
+            total_file_size += file_size.unwrap_or(0);
+
+            let label = group_label(&item.content);
+            let group = groups.entry(label).or_insert_with(|| OutlineGroup {
+                label,
+                entries: Vec::new(),
+                file_size: 0,
+            });
+            group.file_size += file_size.unwrap_or(0);
+            group.entries.push(OutlineEntry {
+                item_id: item.id,
+                name: entry_name(&item.content, data_sources),
+                file_size,
+            });
+        }
+
+        Self {
+            groups: groups.into_values().collect(),
+            item_count: items.len(),
+            total_file_size,
+            file_sizes,
+        }
+    }
+}
+
+/// Heading of the outline group an item belongs to
+pub fn group_label(content: &ItemContent) -> &'static str {
+    match content {
+        ItemContent::Image(_) => "Images",
+        ItemContent::Video(_) | ItemContent::YouTube(_) | ItemContent::Vimeo(_) => "Videos",
+        ItemContent::Audio(_) | ItemContent::SoundCloud(_) | ItemContent::Spotify(_) => "Audio",
+        ItemContent::Pdf { .. } => "PDFs",
+        ItemContent::Markdown { .. } => "Documents",
+        ItemContent::Code { .. } => "Code",
+        ItemContent::Text(_) | ItemContent::TextBox { .. } => "Notes",
+        ItemContent::Link(_) | ItemContent::Bookmark { .. } => "Links",
+        ItemContent::Table { .. } => "Tables",
+        ItemContent::Chart { .. } => "Charts",
+        ItemContent::Arrow { .. } | ItemContent::Shape { .. } => "Shapes",
+    }
+}
+
+/// Format a byte count for display (e.g. "1.5 MB")
+pub fn format_file_size(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KB", "MB", "GB", "TB"];
+
+    if bytes < 1024 {
+        return format!("{} B", bytes);
+    }
+    let mut size = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+    format!("{:.1} {}", size, UNITS[unit])
+}
+
+fn entry_name(content: &ItemContent, data_sources: &HashMap<u64, DataSource>) -> String {
+    let name = match content {
+        ItemContent::TextBox { text, .. } => text.clone(),
+        ItemContent::Table { data_source_id, .. } => data_sources
+            .get(data_source_id)
+            .map(|ds| ds.name.clone())
+            .unwrap_or_else(|| content.display_name()),
+        _ => content.display_name(),
+    };
+
+    // Notes can span many lines - only the first one is shown
+    let first_line = name.lines().find(|l| !l.trim().is_empty()).unwrap_or("");
+    let first_line = first_line.trim();
+    if first_line.is_empty() {
+        return "Untitled".to_string();
+    }
+    if first_line.chars().count() > MAX_NAME_LEN {
+        let truncated: String = first_line.chars().take(MAX_NAME_LEN - 1).collect();
+        format!("{}…", truncated)
+    } else {
+        first_line.to_string()
+    }
+}
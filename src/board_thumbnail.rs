@@ -0,0 +1,95 @@
+//! Board thumbnails - small rendered previews for the board switcher.
+//!
+//! Thumbnails are offscreen snapshots (see [`crate::canvas_snapshot`]) of the
+//! whole board, so they can be produced on a background thread from a plain
+//! [`BoardState`].
+//!
+//! Each thumbnail is written as `thumbnail-<unix_secs>.png` inside the board
+//! directory. The changing file name means GPUI's image cache picks up the
+//! new preview instead of showing a stale one.
+
+use crate::board::BoardState;
+use crate::canvas_snapshot::{SnapshotOptions, render_snapshot};
+use crate::error::MediaError;
+use image::RgbaImage;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+use tracing::{debug, warn};
+
+/// Thumbnail width in pixels
+pub const THUMBNAIL_WIDTH: u32 = 480;
+
+/// Thumbnail height in pixels
+pub const THUMBNAIL_HEIGHT: u32 = 280;
+
+/// Maximum number of images decoded per thumbnail (keeps generation fast)
+const MAX_THUMBNAIL_IMAGES: usize = 12;
+
+const THUMBNAIL_PREFIX: &str = "thumbnail-";
+
+/// Snapshot options used for board thumbnails
+pub fn thumbnail_options() -> SnapshotOptions {
+    SnapshotOptions::fit(THUMBNAIL_WIDTH, THUMBNAIL_HEIGHT).with_max_images(MAX_THUMBNAIL_IMAGES)
+}
+
+/// Render a thumbnail image for a board state
+pub fn render_thumbnail(state: &BoardState) -> RgbaImage {
+    render_snapshot(state, &thumbnail_options())
+}
+
+/// Render and save a thumbnail, replacing any previous one.
+pub fn save_thumbnail(board_dir: &Path, state: &BoardState) -> Result<PathBuf, MediaError> {
+    let image = render_thumbnail(state);
+
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs();
+    let path = board_dir.join(format!("{}{}.png", THUMBNAIL_PREFIX, timestamp));
+
+    image.save(&path)?;
+
+    // Remove older thumbnails
+    for old in list_thumbnails(board_dir)
+        .into_iter()
+        .filter(|p| *p != path)
+    {
+        if let Err(e) = fs::remove_file(&old) {
+            warn!("Failed to remove old thumbnail {:?}: {}", old, e);
+        }
+    }
+
+    debug!("Saved board thumbnail {:?}", path);
+    Ok(path)
+}
+
+/// Delete all thumbnails in a board directory
+pub fn remove_thumbnails(board_dir: &Path) {
+    for path in list_thumbnails(board_dir) {
+        if let Err(e) = fs::remove_file(&path) {
+            warn!("Failed to remove thumbnail {:?}: {}", path, e);
+        }
+    }
+}
+
+/// Get the most recent thumbnail for a board directory
+pub fn latest_thumbnail(board_dir: &Path) -> Option<PathBuf> {
+    list_thumbnails(board_dir).into_iter().max()
+}
+
+fn list_thumbnails(board_dir: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = fs::read_dir(board_dir) else {
+        return Vec::new();
+    };
+
+    entries
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.file_name()
+                .and_then(|n| n.to_str())
+                .is_some_and(|n| n.starts_with(THUMBNAIL_PREFIX) && n.ends_with(".png"))
+        })
+        .collect()
+}
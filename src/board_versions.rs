@@ -0,0 +1,367 @@
+//! Board version history - periodic on-disk snapshots of a board.
+//!
+//! Versions are full [`BoardState`] copies stored next to `board.json` in a
+//! `versions/` directory, one file per snapshot named by its creation time
+//! in milliseconds. They are independent of the in-memory undo history and
+//! survive app restarts, so an older state can be restored at any time.
+//!
+//! ## Capture Policy
+//!
+//! [`VersionTracker`] decides when a save should also write a version: either
+//! [`VERSION_SNAPSHOT_INTERVAL_SECS`] have passed since the previous version,
+//! or [`VERSION_SNAPSHOT_OPERATIONS`] history operations were recorded.
+//! Only the newest [`MAX_BOARD_VERSIONS`] snapshots are kept.
+//!
+//! ## Named Checkpoints
+//!
+//! A version saved on request can be given a name, kept in a
+//! `<timestamp>.name` file beside it. Named versions are checkpoints: they
+//! are listed under their name and never pruned, only deleted from the
+//! version browser.
+//!
+//! ## File Texts
+//!
+//! Code and markdown cards point at files that keep changing, so each
+//! version also keeps a copy of their text in a `<timestamp>.files/`
+//! directory beside it, for diff tabs to compare the files against.
+
+use crate::board::BoardState;
+use crate::constants::{
+    MAX_BOARD_VERSIONS, MAX_SNAPSHOT_FILE_BYTES, VERSION_SNAPSHOT_INTERVAL_SECS,
+    VERSION_SNAPSHOT_OPERATIONS,
+};
+use crate::error::BoardError;
+use crate::types::ItemContent;
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use tracing::{debug, warn};
+
+/// Name of the directory holding version snapshots inside a board directory
+const VERSIONS_DIR: &str = "versions";
+
+/// A single version snapshot on disk
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BoardVersion {
+    /// Creation time in milliseconds since the Unix epoch
+    pub timestamp_ms: u64,
+    /// Path to the snapshot file
+    pub path: PathBuf,
+    /// Name given to a checkpoint; automatic versions have none
+    pub name: Option<String>,
+}
+
+impl BoardVersion {
+    /// Load the board state stored in this version
+    pub fn load(&self) -> Result<BoardState, BoardError> {
+        BoardState::load_from_path(&self.path)
+    }
+
+    /// Directory holding the file texts kept with this version
+    pub fn files_dir(&self) -> PathBuf {
+        self.path.with_extension("files")
+    }
+
+    /// Text of `file` as it was when this version was saved, if it was kept
+    pub fn file_text(&self, file: &Path) -> Option<String> {
+        fs::read_to_string(self.files_dir().join(file_text_name(file))).ok()
+    }
+
+    /// Delete the snapshot with its name and kept file texts
+    pub fn delete(&self) -> io::Result<()> {
+        fs::remove_file(&self.path)?;
+        let name = self.path.with_extension("name");
+        if name.exists() {
+            fs::remove_file(&name)?;
+        }
+        let files = self.files_dir();
+        if files.exists() {
+            fs::remove_dir_all(&files)?;
+        }
+        Ok(())
+    }
+
+    /// Whether this version is a named checkpoint
+    pub fn is_checkpoint(&self) -> bool {
+        self.name.is_some()
+    }
+
+    /// How the version is listed: its name, or its age
+    pub fn title(&self) -> String {
+        self.name.clone().unwrap_or_else(|| self.formatted_age())
+    }
+
+    /// Human-readable age of this version (e.g. "5 min ago")
+    pub fn formatted_age(&self) -> String {
+        let now = now_ms();
+        let diff = now.saturating_sub(self.timestamp_ms) / 1000;
+
+        if diff < 60 {
+            "Just now".to_string()
+        } else if diff < 3600 {
+            format!("{} min ago", diff / 60)
+        } else if diff < 86400 {
+            format!("{} hours ago", diff / 3600)
+        } else if diff < 604800 {
+            format!("{} days ago", diff / 86400)
+        } else {
+            format!("{} weeks ago", diff / 604800)
+        }
+    }
+}
+
+/// Summary of a version's contents, used by the history browser preview
+#[derive(Clone, Debug, Default)]
+pub struct VersionSummary {
+    /// Total number of items
+    pub item_count: usize,
+    /// Item counts keyed by type label (e.g. "Image" -> 3)
+    pub counts_by_type: BTreeMap<String, usize>,
+    /// Item bounds as (x, y, width, height) in canvas coordinates
+    pub item_bounds: Vec<(f32, f32, f32, f32)>,
+}
+
+impl VersionSummary {
+    /// Build a summary from a loaded board state
+    pub fn from_state(state: &BoardState) -> Self {
+        let mut counts_by_type = BTreeMap::new();
+        for item in &state.items {
+            *counts_by_type
+                .entry(item.content.type_label().to_string())
+                .or_insert(0) += 1;
+        }
+        Self {
+            item_count: state.items.len(),
+            counts_by_type,
+            item_bounds: state
+                .items
+                .iter()
+                .map(|item| (item.position.0, item.position.1, item.size.0, item.size.1))
+                .collect(),
+        }
+    }
+
+    /// Bounding box of all items as (min_x, min_y, max_x, max_y)
+    pub fn extent(&self) -> Option<(f32, f32, f32, f32)> {
+        self.item_bounds.iter().fold(None, |acc, &(x, y, w, h)| {
+            Some(match acc {
+                None => (x, y, x + w, y + h),
+                Some((min_x, min_y, max_x, max_y)) => (
+                    min_x.min(x),
+                    min_y.min(y),
+                    max_x.max(x + w),
+                    max_y.max(y + h),
+                ),
+            })
+        })
+    }
+}
+
+/// Tracks when the next automatic version snapshot is due
+#[derive(Debug)]
+pub struct VersionTracker {
+    last_version_at: Option<Instant>,
+    ops_since_version: usize,
+}
+
+impl Default for VersionTracker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl VersionTracker {
+    pub fn new() -> Self {
+        Self {
+            last_version_at: None,
+            ops_since_version: 0,
+        }
+    }
+
+    /// Record that a history operation happened
+    pub fn record_operation(&mut self) {
+        self.ops_since_version += 1;
+    }
+
+    /// Check whether a version should be captured on the next save
+    pub fn is_due(&self) -> bool {
+        if self.ops_since_version == 0 {
+            return false;
+        }
+        if self.ops_since_version >= VERSION_SNAPSHOT_OPERATIONS {
+            return true;
+        }
+        match self.last_version_at {
+            // First change after opening the board - keep the pre-session state
+            None => true,
+            Some(at) => at.elapsed() >= Duration::from_secs(VERSION_SNAPSHOT_INTERVAL_SECS),
+        }
+    }
+
+    /// Reset counters after a version was written
+    pub fn mark_captured(&mut self) {
+        self.last_version_at = Some(Instant::now());
+        self.ops_since_version = 0;
+    }
+}
+
+/// Get the versions directory for a board directory
+pub fn versions_dir(board_dir: &Path) -> PathBuf {
+    board_dir.join(VERSIONS_DIR)
+}
+
+/// Write a new version snapshot and prune old ones.
+pub fn save_version(board_dir: &Path, state: &BoardState) -> Result<BoardVersion, BoardError> {
+    write_version(board_dir, state, None)
+}
+
+/// Write a version snapshot named `name`, kept until deleted
+pub fn save_checkpoint(
+    board_dir: &Path,
+    state: &BoardState,
+    name: &str,
+) -> Result<BoardVersion, BoardError> {
+    write_version(board_dir, state, Some(name.trim().to_string()))
+}
+
+fn write_version(
+    board_dir: &Path,
+    state: &BoardState,
+    name: Option<String>,
+) -> Result<BoardVersion, BoardError> {
+    let dir = versions_dir(board_dir);
+    let mut timestamp_ms = now_ms();
+    // Never overwrite an existing snapshot taken within the same millisecond
+    while dir.join(format!("{}.json", timestamp_ms)).exists() {
+        timestamp_ms += 1;
+    }
+
+    let path = dir.join(format!("{}.json", timestamp_ms));
+    state.save_to_path(&path)?;
+    if let Some(ref name) = name {
+        let name_path = path.with_extension("name");
+        fs::write(&name_path, name).map_err(|e| BoardError::SaveFailed {
+            path: name_path,
+            source: e,
+        })?;
+    }
+    debug!("Saved board version {:?}", path);
+    let version = BoardVersion {
+        timestamp_ms,
+        path,
+        name,
+    };
+    save_file_texts(&version, state);
+
+    prune_versions(board_dir, MAX_BOARD_VERSIONS);
+    Ok(version)
+}
+
+/// Keep a copy of the text of every code and markdown file on the board.
+/// Files that are missing, too big or not text are left out.
+fn save_file_texts(version: &BoardVersion, state: &BoardState) {
+    let dir = version.files_dir();
+    for item in &state.items {
+        let (ItemContent::Code { path, .. } | ItemContent::Markdown { path, .. }) = &item.content
+        else {
+            continue;
+        };
+        let small = fs::metadata(path).is_ok_and(|m| m.len() <= MAX_SNAPSHOT_FILE_BYTES);
+        let Some(text) = small.then(|| fs::read_to_string(path).ok()).flatten() else {
+            continue;
+        };
+        let saved =
+            fs::create_dir_all(&dir).and_then(|()| fs::write(dir.join(file_text_name(path)), text));
+        if let Err(e) = saved {
+            warn!(
+                "Failed to keep text of {:?} with board version: {}",
+                path, e
+            );
+        }
+    }
+}
+
+/// Name a file's text is kept under, from a hash of its path
+fn file_text_name(file: &Path) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(file.to_string_lossy().as_bytes());
+    let hash = format!("{:x}", hasher.finalize());
+    format!("{}.txt", &hash[..16])
+}
+
+/// The newest version that kept the text of `file`, with that text
+pub fn snapshot_file_text(board_dir: &Path, file: &Path) -> Option<(BoardVersion, String)> {
+    list_versions(board_dir).into_iter().find_map(|version| {
+        let text = version.file_text(file)?;
+        Some((version, text))
+    })
+}
+
+/// List all versions for a board, newest first
+pub fn list_versions(board_dir: &Path) -> Vec<BoardVersion> {
+    let Ok(entries) = fs::read_dir(versions_dir(board_dir)) else {
+        return Vec::new();
+    };
+
+    let mut versions: Vec<BoardVersion> = entries
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| {
+            let path = entry.path();
+            if path.extension().and_then(|e| e.to_str()) != Some("json") {
+                return None;
+            }
+            let timestamp_ms = path.file_stem()?.to_str()?.parse().ok()?;
+            let name = fs::read_to_string(path.with_extension("name"))
+                .ok()
+                .map(|name| name.trim().to_string())
+                .filter(|name| !name.is_empty());
+            Some(BoardVersion {
+                timestamp_ms,
+                path,
+                name,
+            })
+        })
+        .collect();
+
+    versions.sort_by(|a, b| b.timestamp_ms.cmp(&a.timestamp_ms));
+    versions
+}
+
+/// Delete all but the newest `keep` automatic versions. Named checkpoints
+/// are kept. Returns the number removed.
+pub fn prune_versions(board_dir: &Path, keep: usize) -> usize {
+    let mut removed = 0;
+    let automatic = list_versions(board_dir)
+        .into_iter()
+        .filter(|version| !version.is_checkpoint());
+    for version in automatic.skip(keep) {
+        match version.delete() {
+            Ok(()) => removed += 1,
+            Err(e) => warn!(
+                "Failed to remove old board version {:?}: {}",
+                version.path, e
+            ),
+        }
+    }
+    removed
+}
+
+/// Delete every version snapshot of a board
+pub fn remove_versions(board_dir: &Path) {
+    let dir = versions_dir(board_dir);
+    if dir.exists() {
+        if let Err(e) = fs::remove_dir_all(&dir) {
+            warn!("Failed to remove board versions {:?}: {}", dir, e);
+        }
+    }
+}
+
+fn now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as u64
+}
@@ -0,0 +1,111 @@
+//! Canvas background styles.
+//!
+//! Each board can pick how the empty canvas behind its items looks: plain
+//! theme background, a dot grid, a line grid, a custom color or an image.
+//! Boards that haven't picked one follow the `show_grid` setting.
+//!
+//! Dot and line grids are spaced `grid_size` canvas units apart so they line
+//! up with snapping. When zoomed out far enough that the grid would get
+//! denser than `MIN_PATTERN_SPACING` on screen, every other line is dropped
+//! (repeatedly), which keeps the number of painted quads bounded at any zoom.
+
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+/// Smallest on-screen gap between grid dots or lines, in pixels
+pub const MIN_PATTERN_SPACING: f32 = 12.0;
+
+/// How the canvas behind the items is drawn
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "style", rename_all = "snake_case")]
+pub enum CanvasBackground {
+    /// Theme background color
+    Plain,
+    /// Dot at every grid intersection
+    Dots,
+    /// Horizontal and vertical grid lines
+    Lines,
+    /// Solid custom color (hex, e.g. "#1e1e2e")
+    Color { color: String },
+    /// Image stretched to cover the canvas
+    Image { path: PathBuf },
+}
+
+impl CanvasBackground {
+    /// Background for boards that haven't picked one
+    pub fn from_settings() -> Self {
+        if crate::settings::app_settings().show_grid {
+            CanvasBackground::Dots
+        } else {
+            CanvasBackground::Plain
+        }
+    }
+
+    /// Parse a style name or hex color as typed in the command palette
+    pub fn parse(text: &str) -> Option<Self> {
+        let text = text.trim();
+        match text.to_lowercase().as_str() {
+            "plain" | "none" => Some(CanvasBackground::Plain),
+            "dots" | "dot" => Some(CanvasBackground::Dots),
+            "lines" | "grid" => Some(CanvasBackground::Lines),
+            _ if is_hex_color(text) => Some(CanvasBackground::Color {
+                color: format!("#{}", text.trim_start_matches('#').to_lowercase()),
+            }),
+            _ => None,
+        }
+    }
+
+    /// The next of the built-in styles, for cycling through them
+    pub fn next(&self) -> Self {
+        match self {
+            CanvasBackground::Plain => CanvasBackground::Dots,
+            CanvasBackground::Dots => CanvasBackground::Lines,
+            _ => CanvasBackground::Plain,
+        }
+    }
+
+    pub fn label(&self) -> String {
+        match self {
+            CanvasBackground::Plain => "Plain".to_string(),
+            CanvasBackground::Dots => "Dot grid".to_string(),
+            CanvasBackground::Lines => "Line grid".to_string(),
+            CanvasBackground::Color { color } => format!("Color {}", color),
+            CanvasBackground::Image { path } => format!(
+                "Image {}",
+                path.file_name()
+                    .map(|name| name.to_string_lossy().into_owned())
+                    .unwrap_or_default()
+            ),
+        }
+    }
+}
+
+fn is_hex_color(text: &str) -> bool {
+    let hex = text.trim_start_matches('#');
+    hex.len() == 6 && hex.chars().all(|c| c.is_ascii_hexdigit())
+}
+
+/// On-screen gap between grid dots or lines for a grid size and zoom, or
+/// `None` if there is no grid to draw
+pub fn pattern_spacing(grid_size: f32, zoom: f32) -> Option<f32> {
+    let mut spacing = grid_size * zoom;
+    if !spacing.is_finite() || spacing <= 0.0 {
+        return None;
+    }
+    while spacing < MIN_PATTERN_SPACING {
+        spacing *= 2.0;
+    }
+    Some(spacing)
+}
+
+/// Screen positions of grid lines along one axis, from `start` to
+/// `start + length`, for a canvas panned by `offset`
+pub fn grid_positions(start: f32, length: f32, offset: f32, spacing: f32) -> Vec<f32> {
+    let mut positions = Vec::new();
+    let mut position = start + offset.rem_euclid(spacing);
+    while position <= start + length {
+        positions.push(position);
+        position += spacing;
+    }
+    positions
+}
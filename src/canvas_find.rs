@@ -0,0 +1,183 @@
+//! Find on the canvas - browser-style search of the board's items.
+//!
+//! The find bar (`Cmd+F` on the canvas) keeps the board in view while
+//! searching: items that don't match are dimmed, matches are outlined, and
+//! Enter and Shift+Enter step through them in reading order, panning the
+//! view to each one. Items are found by their name and the text they show,
+//! and the palette's operators (`type:`, `tag:`, `modified:`) narrow the
+//! matches the same way.
+
+use crate::search_query::{SearchQuery, item_modified};
+use crate::types::{CanvasItem, DataSource, ItemContent};
+use std::collections::{HashMap, HashSet};
+
+/// Opacity of the veil over items that don't match
+pub const FIND_DIM_OPACITY: f32 = 0.7;
+
+/// Text an item is found by: its name, and the text it shows
+fn item_text(item: &CanvasItem, data_sources: &HashMap<u64, DataSource>) -> String {
+    match &item.content {
+        ItemContent::TextBox { text, .. } => text.clone(),
+        ItemContent::Markdown { content, .. } => {
+            format!("{}\n{}", item.content.display_name(), content)
+        }
+        ItemContent::Table { data_source_id, .. } | ItemContent::Chart { data_source_id, .. } => {
+            data_sources
+                .get(data_source_id)
+                .map(|source| source.name.clone())
+                .unwrap_or_default()
+        }
+        ItemContent::Bookmark {
+            url,
+            title,
+            description,
+            ..
+        } => format!("{}\n{}\n{}", title, description, url),
+        ItemContent::Arrow { .. } | ItemContent::Shape { .. } => String::new(),
+        _ => item.content.display_name(),
+    }
+}
+
+/// IDs of the items matching `query`, in reading order: top to bottom,
+/// then left to right. Every word of the query's text has to appear in an
+/// item, in any case. A query with neither text nor filters finds nothing.
+pub fn find_on_canvas(
+    items: &[CanvasItem],
+    data_sources: &HashMap<u64, DataSource>,
+    query: &str,
+) -> Vec<u64> {
+    let query = SearchQuery::parse(query);
+    let words: Vec<String> = query
+        .text
+        .split_whitespace()
+        .map(str::to_lowercase)
+        .collect();
+    if words.is_empty() && !query.has_filters() {
+        return Vec::new();
+    }
+
+    let mut found: Vec<&CanvasItem> = items
+        .iter()
+        .filter(|item| {
+            // Only look up files' dates when they are filtered on
+            let modified = if query.modified.is_empty() {
+                None
+            } else {
+                item_modified(item)
+            };
+            query.matches_item(item, modified)
+        })
+        .filter(|item| {
+            if words.is_empty() {
+                return true;
+            }
+            let text = item_text(item, data_sources).to_lowercase();
+            words.iter().all(|word| text.contains(word.as_str()))
+        })
+        .collect();
+    found.sort_by(|a, b| {
+        a.position
+            .1
+            .total_cmp(&b.position.1)
+            .then(a.position.0.total_cmp(&b.position.0))
+    });
+    found.into_iter().map(|item| item.id).collect()
+}
+
+/// The matches of the find bar's query, and the one last stepped to
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FindMatches {
+    /// Matching items, in reading order
+    ids: Vec<u64>,
+    set: HashSet<u64>,
+    current: Option<usize>,
+    /// Whether there is a query; without one nothing is dimmed
+    active: bool,
+}
+
+impl FindMatches {
+    /// Matches of a new query. The item last stepped to stays current if
+    /// it still matches.
+    pub fn update(&mut self, ids: Vec<u64>, active: bool) {
+        let current_id = self.current_id();
+        self.current = current_id.and_then(|id| ids.iter().position(|&other| other == id));
+        self.set = ids.iter().copied().collect();
+        self.ids = ids;
+        self.active = active;
+    }
+
+    /// Forget the query and its matches
+    pub fn clear(&mut self) {
+        *self = Self::default();
+    }
+
+    /// Whether there is a query, so the canvas shows its matches
+    pub fn is_active(&self) -> bool {
+        self.active
+    }
+
+    /// Matching items, in reading order
+    pub fn ids(&self) -> &[u64] {
+        &self.ids
+    }
+
+    pub fn len(&self) -> usize {
+        self.ids.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ids.is_empty()
+    }
+
+    /// Whether the item with `id` matches
+    pub fn contains(&self, id: u64) -> bool {
+        self.set.contains(&id)
+    }
+
+    /// The match last stepped to
+    pub fn current_id(&self) -> Option<u64> {
+        self.current.and_then(|index| self.ids.get(index).copied())
+    }
+
+    /// Position of the current match, counted from 0
+    pub fn current_index(&self) -> Option<usize> {
+        self.current
+    }
+
+    /// Step to the next match, wrapping around to the first. Returns the
+    /// match stepped to.
+    pub fn next_match(&mut self) -> Option<u64> {
+        if self.ids.is_empty() {
+            return None;
+        }
+        let next = match self.current {
+            Some(index) => (index + 1) % self.ids.len(),
+            None => 0,
+        };
+        self.current = Some(next);
+        self.current_id()
+    }
+
+    /// Step to the previous match, wrapping around to the last. Returns the
+    /// match stepped to.
+    pub fn prev_match(&mut self) -> Option<u64> {
+        if self.ids.is_empty() {
+            return None;
+        }
+        let prev = match self.current {
+            Some(0) | None => self.ids.len() - 1,
+            Some(index) => index - 1,
+        };
+        self.current = Some(prev);
+        self.current_id()
+    }
+
+    /// Count shown in the find bar, like "2/5"
+    pub fn label(&self) -> String {
+        match (self.ids.len(), self.current) {
+            (0, _) => "No matches".to_string(),
+            (count, Some(index)) => format!("{}/{}", index + 1, count),
+            (count, None) => format!("{} found", count),
+        }
+    }
+}
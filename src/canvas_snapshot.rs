@@ -0,0 +1,514 @@
+//! Offscreen canvas snapshots.
+//!
+//! Draws a board into an image of any size, independent of the window, zoom
+//! and pan the board is currently shown with. Snapshots are rasterized with
+//! the `image` crate from a plain [`BoardState`], so they can be produced on
+//! a background thread. Board thumbnails, PNG export, handouts and copying
+//! the selection as an image all go through [`render_snapshot`] with
+//! different [`SnapshotOptions`].
+//!
+//! Shapes, arrows, text boxes and text items are drawn as vectors, through
+//! the same SVG the region export writes, rasterized with `resvg`. Image
+//! items get a scaled copy of the actual image and other cards are filled
+//! blocks colored by type. The board's canvas background (color, grid or
+//! image) is drawn behind them.
+
+use crate::board::BoardState;
+use crate::canvas_background::{CanvasBackground, grid_positions, pattern_spacing};
+use crate::error::MediaError;
+use crate::region_export::{SVG_FONT_FAMILY, item_extent, svg_item};
+use crate::types::{CanvasItem, ItemContent};
+use image::imageops::{self, FilterType};
+use image::{Rgba, RgbaImage};
+use resvg::{tiny_skia, usvg};
+use std::path::Path;
+use std::sync::{Arc, LazyLock};
+
+/// Largest width or height of a snapshot in pixels
+pub const MAX_SNAPSHOT_DIMENSION: u32 = 8192;
+
+/// Default canvas color, matching the dark theme
+pub const DEFAULT_BACKGROUND: Rgba<u8> = Rgba([24, 24, 27, 255]);
+
+/// Fully transparent pixel, behind the items of a transparent snapshot
+pub const TRANSPARENT: Rgba<u8> = Rgba([0, 0, 0, 0]);
+
+/// Grid color for dot and line backgrounds
+const GRID_COLOR: Rgba<u8> = Rgba([63, 63, 70, 255]);
+
+/// Part of the canvas a snapshot shows
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SnapshotViewport {
+    /// All items, scaled to fit with `padding` pixels around them. Content
+    /// is never scaled up past `max_scale`.
+    FitContent { padding: f32, max_scale: f32 },
+    /// A region of the canvas in canvas units, stretched to the image size
+    /// keeping its aspect ratio
+    Region {
+        x: f32,
+        y: f32,
+        width: f32,
+        height: f32,
+    },
+}
+
+/// Size and framing of a snapshot
+#[derive(Debug, Clone, PartialEq)]
+pub struct SnapshotOptions {
+    pub width: u32,
+    pub height: u32,
+    pub viewport: SnapshotViewport,
+    /// Canvas color behind the items (the board's own color wins)
+    pub background: Rgba<u8>,
+    /// Grid size in canvas units for dot and line backgrounds
+    pub grid_size: f32,
+    /// Most images decoded for one snapshot; the rest are drawn as blocks
+    pub max_images: usize,
+    /// Leave out the canvas and the board's background, drawing only items
+    pub transparent: bool,
+}
+
+impl SnapshotOptions {
+    /// A snapshot of all items at the given size
+    pub fn fit(width: u32, height: u32) -> Self {
+        Self {
+            width: width.clamp(1, MAX_SNAPSHOT_DIMENSION),
+            height: height.clamp(1, MAX_SNAPSHOT_DIMENSION),
+            viewport: SnapshotViewport::FitContent {
+                padding: 16.0,
+                max_scale: 1.0,
+            },
+            background: DEFAULT_BACKGROUND,
+            grid_size: 20.0,
+            max_images: usize::MAX,
+            transparent: false,
+        }
+    }
+
+    /// A snapshot of a canvas region at the given size
+    pub fn region(width: u32, height: u32, region: (f32, f32, f32, f32)) -> Self {
+        let (x, y, w, h) = region;
+        Self {
+            viewport: SnapshotViewport::Region {
+                x,
+                y,
+                width: w,
+                height: h,
+            },
+            ..Self::fit(width, height)
+        }
+    }
+
+    /// All items at `scale` pixels per canvas unit, sized to fit them (capped
+    /// at `MAX_SNAPSHOT_DIMENSION`, scaling down if needed)
+    pub fn full_board(state: &BoardState, scale: f32, padding: f32) -> Self {
+        let (width, height) = match content_bounds(state) {
+            Some((min_x, min_y, max_x, max_y)) => (
+                ((max_x - min_x) * scale + padding * 2.0).ceil(),
+                ((max_y - min_y) * scale + padding * 2.0).ceil(),
+            ),
+            None => (padding * 2.0, padding * 2.0),
+        };
+        // Keep the aspect ratio when the cap kicks in
+        let limit = MAX_SNAPSHOT_DIMENSION as f32;
+        let shrink = (limit / width.max(1.0))
+            .min(limit / height.max(1.0))
+            .min(1.0);
+        Self {
+            viewport: SnapshotViewport::FitContent {
+                padding: padding * shrink,
+                max_scale: scale,
+            },
+            ..Self::fit((width * shrink) as u32, (height * shrink) as u32)
+        }
+    }
+
+    pub fn with_background(mut self, background: Rgba<u8>) -> Self {
+        self.background = background;
+        self
+    }
+
+    pub fn with_grid_size(mut self, grid_size: f32) -> Self {
+        self.grid_size = grid_size;
+        self
+    }
+
+    pub fn with_max_images(mut self, max_images: usize) -> Self {
+        self.max_images = max_images;
+        self
+    }
+
+    /// Draw the items on a transparent image instead of the canvas
+    pub fn with_transparent_background(mut self) -> Self {
+        self.transparent = true;
+        self.background = TRANSPARENT;
+        self
+    }
+}
+
+/// Maps canvas coordinates to snapshot pixels
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SnapshotTransform {
+    pub scale: f32,
+    pub offset_x: f32,
+    pub offset_y: f32,
+}
+
+impl SnapshotTransform {
+    /// Where the canvas point `(x, y)` lands in the snapshot
+    pub fn apply(&self, x: f32, y: f32) -> (f32, f32) {
+        (
+            self.offset_x + x * self.scale,
+            self.offset_y + y * self.scale,
+        )
+    }
+}
+
+/// The canvas-to-pixel mapping a snapshot of `state` uses
+pub fn snapshot_transform(state: &BoardState, options: &SnapshotOptions) -> SnapshotTransform {
+    let (width, height) = (options.width as f32, options.height as f32);
+    let (x, y, w, h, padding, max_scale) = match options.viewport {
+        SnapshotViewport::Region {
+            x,
+            y,
+            width,
+            height,
+        } => (x, y, width, height, 0.0, f32::INFINITY),
+        SnapshotViewport::FitContent { padding, max_scale } => match content_bounds(state) {
+            Some((min_x, min_y, max_x, max_y)) => (
+                min_x,
+                min_y,
+                max_x - min_x,
+                max_y - min_y,
+                padding,
+                max_scale,
+            ),
+            None => (0.0, 0.0, 0.0, 0.0, padding, max_scale),
+        },
+    };
+
+    let avail_w = (width - padding * 2.0).max(1.0);
+    let avail_h = (height - padding * 2.0).max(1.0);
+    let scale = (avail_w / w.max(1.0))
+        .min(avail_h / h.max(1.0))
+        .min(max_scale);
+
+    // Center the viewport in the image
+    SnapshotTransform {
+        scale,
+        offset_x: (width - w * scale) / 2.0 - x * scale,
+        offset_y: (height - h * scale) / 2.0 - y * scale,
+    }
+}
+
+/// Draw a board into a new image
+pub fn render_snapshot(state: &BoardState, options: &SnapshotOptions) -> RgbaImage {
+    let background = match &state.background {
+        Some(CanvasBackground::Color { color }) if !options.transparent => {
+            parse_hex(color).unwrap_or(options.background)
+        }
+        _ => options.background,
+    };
+    let mut canvas = RgbaImage::from_pixel(options.width, options.height, background);
+    let transform = snapshot_transform(state, options);
+    if !options.transparent {
+        draw_background(&mut canvas, state.background.as_ref(), &transform, options);
+    }
+
+    let mut images_drawn = 0;
+    for item in &state.items {
+        let (x, y) = transform.apply(item.position.0, item.position.1);
+        let w = (item.size.0 * transform.scale).max(1.0);
+        let h = (item.size.1 * transform.scale).max(1.0);
+
+        // Skip items outside the image (arrows can reach in from outside)
+        let outside =
+            x + w < 0.0 || y + h < 0.0 || x > options.width as f32 || y > options.height as f32;
+        if outside && !matches!(item.content, ItemContent::Arrow { .. }) {
+            continue;
+        }
+
+        match &item.content {
+            ItemContent::Image(path) => {
+                if images_drawn < options.max_images && draw_image(&mut canvas, path, x, y, w, h) {
+                    images_drawn += 1;
+                    continue;
+                }
+            }
+            ItemContent::Shape { .. }
+            | ItemContent::Arrow { .. }
+            | ItemContent::TextBox { .. }
+            | ItemContent::Text(_)
+                if draw_vector_item(&mut canvas, item, &transform) =>
+            {
+                continue;
+            }
+            ItemContent::Arrow { end_offset, .. } => {
+                let color = item_color(&item.content);
+                let end = (
+                    x + end_offset.0 * transform.scale,
+                    y + end_offset.1 * transform.scale,
+                );
+                draw_line(&mut canvas, (x, y), end, color);
+                continue;
+            }
+            _ => {}
+        }
+
+        fill_rect(&mut canvas, x, y, w, h, item_color(&item.content));
+    }
+
+    canvas
+}
+
+/// Render a snapshot and save it as a PNG
+pub fn save_snapshot(
+    state: &BoardState,
+    options: &SnapshotOptions,
+    path: &Path,
+) -> Result<(), MediaError> {
+    render_snapshot(state, options).save_with_format(path, image::ImageFormat::Png)?;
+    Ok(())
+}
+
+/// Render a snapshot and encode it as PNG data, e.g. for the clipboard
+pub fn encode_snapshot_png(
+    state: &BoardState,
+    options: &SnapshotOptions,
+) -> Result<Vec<u8>, MediaError> {
+    let mut bytes = Vec::new();
+    render_snapshot(state, options).write_to(
+        &mut std::io::Cursor::new(&mut bytes),
+        image::ImageFormat::Png,
+    )?;
+    Ok(bytes)
+}
+
+/// Bounding box of all items as (min_x, min_y, max_x, max_y)
+pub fn content_bounds(state: &BoardState) -> Option<(f32, f32, f32, f32)> {
+    state.items.iter().fold(None, |acc, item| {
+        let (x, y) = item.position;
+        let (w, h) = item.size;
+        Some(match acc {
+            None => (x, y, x + w, y + h),
+            Some((min_x, min_y, max_x, max_y)) => (
+                min_x.min(x),
+                min_y.min(y),
+                max_x.max(x + w),
+                max_y.max(y + h),
+            ),
+        })
+    })
+}
+
+fn draw_background(
+    canvas: &mut RgbaImage,
+    background: Option<&CanvasBackground>,
+    transform: &SnapshotTransform,
+    options: &SnapshotOptions,
+) {
+    let (width, height) = (options.width as f32, options.height as f32);
+    match background {
+        Some(CanvasBackground::Image { path }) => {
+            draw_image(canvas, path, 0.0, 0.0, width, height);
+        }
+        Some(style @ (CanvasBackground::Dots | CanvasBackground::Lines)) => {
+            let Some(spacing) = pattern_spacing(options.grid_size, transform.scale) else {
+                return;
+            };
+            let xs = grid_positions(0.0, width, transform.offset_x, spacing);
+            let ys = grid_positions(0.0, height, transform.offset_y, spacing);
+            if matches!(style, CanvasBackground::Lines) {
+                for &x in &xs {
+                    fill_rect(canvas, x, 0.0, 1.0, height, GRID_COLOR);
+                }
+                for &y in &ys {
+                    fill_rect(canvas, 0.0, y, width, 1.0, GRID_COLOR);
+                }
+            } else {
+                for &y in &ys {
+                    for &x in &xs {
+                        fill_rect(canvas, x - 1.0, y - 1.0, 2.0, 2.0, GRID_COLOR);
+                    }
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Block color for an item type
+pub(crate) fn item_color(content: &ItemContent) -> Rgba<u8> {
+    match content {
+        ItemContent::Image(_) => Rgba([82, 82, 91, 255]),
+        ItemContent::Video(_) | ItemContent::YouTube(_) => Rgba([190, 60, 60, 255]),
+        ItemContent::Vimeo(_) => Rgba([40, 160, 210, 255]),
+        ItemContent::Audio(_) => Rgba([168, 85, 247, 255]),
+        ItemContent::SoundCloud(_) => Rgba([255, 85, 0, 255]),
+        ItemContent::Spotify(_) => Rgba([30, 215, 96, 255]),
+        ItemContent::Pdf { .. } => Rgba([220, 90, 70, 255]),
+        ItemContent::Markdown { .. } | ItemContent::Text(_) => Rgba([228, 228, 231, 255]),
+        ItemContent::Code { .. } => Rgba([59, 130, 246, 255]),
+        ItemContent::Link(_) | ItemContent::Bookmark { .. } => Rgba([14, 165, 233, 255]),
+        ItemContent::TextBox { color, .. } => {
+            parse_hex(color).unwrap_or(Rgba([255, 255, 255, 255]))
+        }
+        ItemContent::Arrow { color, .. } => parse_hex(color).unwrap_or(Rgba([255, 255, 255, 255])),
+        ItemContent::Shape {
+            fill_color,
+            border_color,
+            ..
+        } => fill_color
+            .as_deref()
+            .and_then(parse_hex)
+            .or_else(|| parse_hex(border_color))
+            .unwrap_or(Rgba([255, 255, 255, 255])),
+        ItemContent::Table { .. } => Rgba([34, 197, 94, 255]),
+        ItemContent::Chart { .. } => Rgba([234, 179, 8, 255]),
+    }
+}
+
+/// Parse a "#rrggbb" color
+pub(crate) fn parse_hex(hex: &str) -> Option<Rgba<u8>> {
+    let hex = hex.trim_start_matches('#');
+    if hex.len() != 6 {
+        return None;
+    }
+    let r = u8::from_str_radix(&hex[0..2], 16).ok()?;
+    let g = u8::from_str_radix(&hex[2..4], 16).ok()?;
+    let b = u8::from_str_radix(&hex[4..6], 16).ok()?;
+    Some(Rgba([r, g, b, 255]))
+}
+
+fn fill_rect(canvas: &mut RgbaImage, x: f32, y: f32, w: f32, h: f32, color: Rgba<u8>) {
+    let x0 = x.max(0.0) as u32;
+    let y0 = y.max(0.0) as u32;
+    let x1 = ((x + w).max(0.0) as u32).min(canvas.width());
+    let y1 = ((y + h).max(0.0) as u32).min(canvas.height());
+
+    for py in y0..y1 {
+        for px in x0..x1 {
+            canvas.put_pixel(px, py, color);
+        }
+    }
+}
+
+fn draw_line(canvas: &mut RgbaImage, from: (f32, f32), to: (f32, f32), color: Rgba<u8>) {
+    let steps = (to.0 - from.0)
+        .abs()
+        .max((to.1 - from.1).abs())
+        .ceil()
+        .max(1.0) as u32;
+    for i in 0..=steps {
+        let t = i as f32 / steps as f32;
+        let x = from.0 + (to.0 - from.0) * t;
+        let y = from.1 + (to.1 - from.1) * t;
+        if x >= 0.0 && y >= 0.0 && (x as u32) < canvas.width() && (y as u32) < canvas.height() {
+            canvas.put_pixel(x as u32, y as u32, color);
+        }
+    }
+}
+
+/// Draw a shape, arrow or text item from its SVG. Only the part inside the
+/// image is rasterized. Returns false if the SVG couldn't be drawn.
+fn draw_vector_item(
+    canvas: &mut RgbaImage,
+    item: &CanvasItem,
+    transform: &SnapshotTransform,
+) -> bool {
+    // Room for borders and arrow heads past the item's bounds
+    let margin = match &item.content {
+        ItemContent::Arrow { thickness, .. } => (thickness * 4.0).max(8.0),
+        ItemContent::Shape { border_width, .. } => *border_width,
+        _ => 0.0,
+    };
+    let (x, y, w, h) = item_extent(item);
+    let (left, top) = transform.apply(x - margin, y - margin);
+    let (right, bottom) = transform.apply(x + w + margin, y + h + margin);
+    let left = left.floor().max(0.0);
+    let top = top.floor().max(0.0);
+    let width = right.ceil().min(canvas.width() as f32) - left;
+    let height = bottom.ceil().min(canvas.height() as f32) - top;
+    if width < 1.0 || height < 1.0 {
+        return true;
+    }
+
+    // The visible part in canvas units
+    let view_x = (left - transform.offset_x) / transform.scale;
+    let view_y = (top - transform.offset_y) / transform.scale;
+    let svg = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{}\" height=\"{}\" \
+         viewBox=\"{} {} {} {}\" font-family=\"{}\">\n{}</svg>\n",
+        width,
+        height,
+        view_x,
+        view_y,
+        width / transform.scale,
+        height / transform.scale,
+        SVG_FONT_FAMILY,
+        svg_item(item)
+    );
+
+    let Ok(tree) = usvg::Tree::from_str(&svg, &svg_options()) else {
+        return false;
+    };
+    let Some(mut pixmap) = tiny_skia::Pixmap::new(width as u32, height as u32) else {
+        return false;
+    };
+    resvg::render(&tree, tiny_skia::Transform::default(), &mut pixmap.as_mut());
+
+    let layer = RgbaImage::from_fn(pixmap.width(), pixmap.height(), |px, py| {
+        // tiny-skia keeps premultiplied colors
+        let color = pixmap
+            .pixel(px, py)
+            .map(|pixel| pixel.demultiply())
+            .unwrap_or(tiny_skia::ColorU8::from_rgba(0, 0, 0, 0));
+        Rgba([color.red(), color.green(), color.blue(), color.alpha()])
+    });
+    imageops::overlay(canvas, &layer, left as i64, top as i64);
+    true
+}
+
+/// Options SVGs of items are parsed with, holding the system fonts, which
+/// are loaded on first use
+fn svg_options() -> usvg::Options<'static> {
+    static FONTS: LazyLock<Arc<usvg::fontdb::Database>> = LazyLock::new(|| {
+        let mut fonts = usvg::fontdb::Database::new();
+        fonts.load_system_fonts();
+        // Text falls back to sans-serif, which is Arial unless told otherwise
+        let query = usvg::fontdb::Query {
+            families: &[usvg::fontdb::Family::SansSerif],
+            ..Default::default()
+        };
+        if fonts.query(&query).is_none() {
+            let families: Vec<String> = fonts
+                .faces()
+                .flat_map(|face| face.families.iter().map(|(name, _)| name.clone()))
+                .collect();
+            if let Some(family) = families
+                .iter()
+                .find(|name| name.contains("Sans"))
+                .or(families.first())
+            {
+                fonts.set_sans_serif_family(family.clone());
+            }
+        }
+        Arc::new(fonts)
+    });
+    usvg::Options {
+        fontdb: FONTS.clone(),
+        ..Default::default()
+    }
+}
+
+/// Draw a scaled image into the given rect. Returns false if it couldn't be loaded.
+fn draw_image(canvas: &mut RgbaImage, path: &Path, x: f32, y: f32, w: f32, h: f32) -> bool {
+    let Ok(source) = image::open(path) else {
+        return false;
+    };
+
+    let (w, h) = (w.round().max(1.0) as u32, h.round().max(1.0) as u32);
+    let resized = source.resize_exact(w, h, FilterType::Triangle).to_rgba8();
+    imageops::overlay(canvas, &resized, x.round() as i64, y.round() as i64);
+    true
+}
@@ -0,0 +1,77 @@
+//! Keeping markdown and code cards in step with their files.
+//!
+//! Markdown cards hold a copy of their document and expanded code cards show
+//! an excerpt of theirs. [`CardFileWatch`] looks at the files behind the
+//! board's cards about once a second and reports the ones changed on disk
+//! since it last looked, so their cards and preview tabs can be read again.
+//! Cards refreshed this way flash briefly, fading out as
+//! [`update_flash_opacity`] describes.
+
+use crate::animations::ease_in_out_cubic;
+use crate::image_cache::FileStamp;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+/// How often the files behind cards are checked
+pub const CARD_FILE_CHECK_INTERVAL: Duration = Duration::from_secs(1);
+
+/// How long a refreshed card flashes
+pub const UPDATE_FLASH_DURATION: Duration = Duration::from_millis(1500);
+
+/// Opacity of a card's "updated" flash, `elapsed` after the refresh, or
+/// `None` once it is over
+pub fn update_flash_opacity(elapsed: Duration) -> Option<f32> {
+    if elapsed >= UPDATE_FLASH_DURATION {
+        return None;
+    }
+    let t = elapsed.as_secs_f32() / UPDATE_FLASH_DURATION.as_secs_f32();
+    Some(1.0 - ease_in_out_cubic(t))
+}
+
+/// Stamps of the files behind the board's cards, as last seen
+#[derive(Default)]
+pub struct CardFileWatch {
+    stamps: HashMap<PathBuf, Option<FileStamp>>,
+    checked_at: Option<Instant>,
+}
+
+impl CardFileWatch {
+    /// Whether the files are due to be checked again
+    pub fn is_due(&self) -> bool {
+        self.checked_at
+            .is_none_or(|at| at.elapsed() >= CARD_FILE_CHECK_INTERVAL)
+    }
+
+    /// Check the stamps of `paths` and return the files changed since the
+    /// last check. Files seen for the first time are only remembered, and
+    /// files that went missing aren't reported: missing files are badged
+    /// instead.
+    pub fn changed_files<'a>(&mut self, paths: impl IntoIterator<Item = &'a Path>) -> Vec<PathBuf> {
+        self.checked_at = Some(Instant::now());
+        let mut stamps = HashMap::new();
+        let mut changed = Vec::new();
+
+        for path in paths {
+            if stamps.contains_key(path) {
+                continue;
+            }
+            let stamp = FileStamp::of(path);
+            if let Some(previous) = self.stamps.get(path) {
+                if stamp.is_some() && stamp != *previous {
+                    changed.push(path.to_path_buf());
+                }
+            }
+            stamps.insert(path.to_path_buf(), stamp);
+        }
+
+        self.stamps = stamps;
+        changed
+    }
+
+    /// Forget every file, e.g. when switching boards
+    pub fn clear(&mut self) {
+        self.stamps.clear();
+        self.checked_at = None;
+    }
+}
@@ -0,0 +1,177 @@
+//! Excerpts of code files shown on expanded code cards.
+//!
+//! A code card resized tall shows part of its file in place: the first
+//! [`CODE_EXCERPT_LINES`] lines, or the range of lines pinned to the card
+//! with the `lines` command (`lines 10-40`). The card scrolls within the
+//! excerpt.
+//!
+//! A range can also come with the file as a line anchor, `path#L20-L45`,
+//! as GitHub writes them: pasting one adds a card showing those lines, and
+//! markdown links to one open the preview at them.
+//!
+//! [`CodeExcerptCache`] reads the excerpt of each expanded card on screen
+//! and keeps it until the file changes on disk or another range is pinned.
+
+use crate::types::{CanvasItem, ItemContent};
+use std::collections::HashMap;
+use std::fs;
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::SystemTime;
+
+/// Lines shown on an expanded card without a pinned range
+pub const CODE_EXCERPT_LINES: usize = 200;
+
+/// Parse a line range typed by the user: `10-40`, `10:40`, `L10-L40`,
+/// `#L10-L40` or a single line. Lines count from 1; the range is returned
+/// in order.
+pub fn parse_line_range(text: &str) -> Option<(usize, usize)> {
+    let line = |part: &str| -> Option<usize> {
+        let part = part.trim();
+        let part = part
+            .strip_prefix('L')
+            .or_else(|| part.strip_prefix('l'))
+            .unwrap_or(part);
+        part.parse::<usize>().ok().filter(|&line| line > 0)
+    };
+    let text = text.trim();
+    let text = text.strip_prefix('#').unwrap_or(text);
+    let (first, last) = match text.split_once(['-', ':', '–']) {
+        Some((first, last)) => (line(first)?, line(last)?),
+        None => {
+            let line = line(text)?;
+            (line, line)
+        }
+    };
+    Some((first.min(last), first.max(last)))
+}
+
+/// Split a line anchor (`#L20-L45` or `#L20`) off a file reference.
+/// References without one, or with another kind of fragment, are returned
+/// whole.
+pub fn split_line_anchor(reference: &str) -> (&str, Option<(usize, usize)>) {
+    let Some((path, anchor)) = reference.rsplit_once('#') else {
+        return (reference, None);
+    };
+    if !anchor.starts_with(['L', 'l']) {
+        return (reference, None);
+    }
+    match parse_line_range(anchor) {
+        Some(lines) => (path, Some(lines)),
+        None => (reference, None),
+    }
+}
+
+/// The line anchor for a range: `#L20-L45`, or `#L20` for a single line
+pub fn line_anchor((first, last): (usize, usize)) -> String {
+    if first == last {
+        format!("#L{}", first)
+    } else {
+        format!("#L{}-L{}", first, last)
+    }
+}
+
+/// Indices of the lines an excerpt shows, out of a file of `total_lines`.
+/// A pinned range is clamped to the file.
+pub fn excerpt_range(total_lines: usize, pinned: Option<(usize, usize)>) -> Range<usize> {
+    match pinned {
+        Some((first, last)) => {
+            let start = first.saturating_sub(1).min(total_lines);
+            start..last.min(total_lines).max(start)
+        }
+        None => 0..total_lines.min(CODE_EXCERPT_LINES),
+    }
+}
+
+/// Part of a code file, ready to draw
+#[derive(Debug, Clone, PartialEq)]
+pub struct CodeExcerpt {
+    /// Number of the first line shown, counted from 1
+    pub first_line: usize,
+    pub lines: Vec<String>,
+    /// Lines in the whole file
+    pub total_lines: usize,
+}
+
+impl CodeExcerpt {
+    pub fn new(text: &str, pinned: Option<(usize, usize)>) -> Self {
+        let all: Vec<&str> = text.lines().collect();
+        let range = excerpt_range(all.len(), pinned);
+        Self {
+            first_line: range.start + 1,
+            lines: all[range].iter().map(|line| line.to_string()).collect(),
+            total_lines: all.len(),
+        }
+    }
+
+    /// Number of the last line shown
+    pub fn last_line(&self) -> usize {
+        self.first_line + self.lines.len().saturating_sub(1)
+    }
+
+    /// Whether lines of the file are left out
+    pub fn is_partial(&self) -> bool {
+        self.lines.len() < self.total_lines
+    }
+}
+
+/// Everything an excerpt depends on
+#[derive(Debug, Clone, PartialEq)]
+struct ExcerptKey {
+    path: PathBuf,
+    pinned: Option<(usize, usize)>,
+    modified: Option<SystemTime>,
+}
+
+fn modified_time(path: &Path) -> Option<SystemTime> {
+    fs::metadata(path).and_then(|meta| meta.modified()).ok()
+}
+
+/// Excerpts of the expanded code cards currently on screen
+#[derive(Default)]
+pub struct CodeExcerptCache {
+    entries: HashMap<u64, (ExcerptKey, Arc<CodeExcerpt>)>,
+}
+
+impl CodeExcerptCache {
+    /// Bring the cache up to date with the visible items and return the
+    /// excerpt of each expanded code card. Files are read again when they
+    /// change on disk; unreadable files show no lines.
+    pub fn prepare(&mut self, items: &[CanvasItem]) -> HashMap<u64, Arc<CodeExcerpt>> {
+        let mut excerpts = HashMap::new();
+
+        for item in items {
+            if !item.is_expanded_code() {
+                continue;
+            }
+            let ItemContent::Code { path, lines, .. } = &item.content else {
+                continue;
+            };
+
+            let key = ExcerptKey {
+                path: path.clone(),
+                pinned: *lines,
+                modified: modified_time(path),
+            };
+            let excerpt = match self.entries.remove(&item.id) {
+                Some((cached_key, excerpt)) if cached_key == key => excerpt,
+                _ => {
+                    let text = fs::read_to_string(path).unwrap_or_default();
+                    Arc::new(CodeExcerpt::new(&text, *lines))
+                }
+            };
+
+            excerpts.insert(item.id, excerpt.clone());
+            self.entries.insert(item.id, (key, excerpt));
+        }
+
+        self.entries.retain(|id, _| excerpts.contains_key(id));
+        excerpts
+    }
+
+    /// Drop every excerpt, e.g. when switching boards
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+}
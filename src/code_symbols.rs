@@ -0,0 +1,330 @@
+//! Symbols defined in a code file, for the outline of code previews and
+//! "Go to symbol in file".
+//!
+//! Definitions are found line by line from the keywords that start them
+//! (`fn`, `def`, `class`, `struct`, ...) after any modifiers, plus
+//! JavaScript functions assigned to variables and, in C-like languages,
+//! functions declared without a keyword. A symbol's depth is how many other
+//! symbols enclose it: braces outside strings and comments are counted in
+//! brace languages, indentation in the others. Languages are the
+//! identifiers of
+//! [`language_from_extension`](crate::types::language_from_extension).
+
+use crate::syntax_tokens::{TokenKind, tokenize_line};
+use std::collections::HashSet;
+
+/// What a symbol defines
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SymbolKind {
+    Function,
+    Type,
+    Module,
+}
+
+impl SymbolKind {
+    /// Short marker shown beside the symbol's name
+    pub fn marker(&self) -> &'static str {
+        match self {
+            SymbolKind::Function => "ƒ",
+            SymbolKind::Type => "T",
+            SymbolKind::Module => "M",
+        }
+    }
+}
+
+/// A definition in a code file
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CodeSymbol {
+    pub name: String,
+    pub kind: SymbolKind,
+    /// Line the definition starts on, counted from 0
+    pub line: usize,
+    /// Number of symbols enclosing this one
+    pub depth: usize,
+}
+
+/// How a language nests definitions
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Nesting {
+    Braces,
+    Indent,
+}
+
+/// Keywords starting a definition, and what they define
+const DEFINITION_KEYWORDS: &[(&str, SymbolKind)] = &[
+    ("fn", SymbolKind::Function),
+    ("func", SymbolKind::Function),
+    ("function", SymbolKind::Function),
+    ("def", SymbolKind::Function),
+    ("defp", SymbolKind::Function),
+    ("fun", SymbolKind::Function),
+    ("proc", SymbolKind::Function),
+    ("struct", SymbolKind::Type),
+    ("enum", SymbolKind::Type),
+    ("trait", SymbolKind::Type),
+    ("impl", SymbolKind::Type),
+    ("class", SymbolKind::Type),
+    ("interface", SymbolKind::Type),
+    ("protocol", SymbolKind::Type),
+    ("extension", SymbolKind::Type),
+    ("object", SymbolKind::Type),
+    ("record", SymbolKind::Type),
+    ("union", SymbolKind::Type),
+    ("type", SymbolKind::Type),
+    ("mod", SymbolKind::Module),
+    ("module", SymbolKind::Module),
+    ("namespace", SymbolKind::Module),
+    ("defmodule", SymbolKind::Module),
+];
+
+/// Words that may come before a definition keyword
+const MODIFIERS: &str = "pub export default declare async static public private protected \
+    internal abstract final override open unsafe inline virtual sealed data inner partial \
+    extern";
+
+/// Words that start statements rather than definitions, in languages whose
+/// functions have no keyword
+const STATEMENT_WORDS: &str = "if else for while do switch case return catch try new delete \
+    throw sizeof typeof await yield goto";
+
+/// How definitions nest in a language, if its symbols are found at all
+fn nesting(language: &str) -> Option<Nesting> {
+    match language {
+        "rust" | "go" | "c" | "cpp" | "java" | "csharp" | "kotlin" | "swift" | "scala"
+        | "javascript" | "typescript" | "tsx" | "php" | "dart" | "zig" => Some(Nesting::Braces),
+        "python" | "ruby" | "elixir" | "nim" | "lua" => Some(Nesting::Indent),
+        _ => None,
+    }
+}
+
+/// Whether functions in a language are declared by their return type
+/// rather than a keyword, or are class methods written bare
+fn has_bare_functions(language: &str) -> bool {
+    matches!(
+        language,
+        "c" | "cpp" | "java" | "csharp" | "javascript" | "typescript" | "tsx" | "dart"
+    )
+}
+
+/// The symbols defined in `text`, in the order they appear
+pub fn code_symbols(text: &str, language: &str) -> Vec<CodeSymbol> {
+    let Some(nesting) = nesting(language) else {
+        return Vec::new();
+    };
+    let mut symbols = Vec::new();
+    // Scope levels of the symbols enclosing the current line
+    let mut open: Vec<usize> = Vec::new();
+    let mut braces = 0usize;
+    for (line, text) in text.lines().enumerate() {
+        let level = match nesting {
+            Nesting::Braces => braces,
+            Nesting::Indent => text.len() - text.trim_start().len(),
+        };
+        if nesting == Nesting::Braces {
+            braces = count_braces(text, language, braces);
+        }
+        if text.trim().is_empty() {
+            continue;
+        }
+        if nesting == Nesting::Indent {
+            while open.last().is_some_and(|&open| open >= level) {
+                open.pop();
+            }
+        }
+        let Some((name, kind)) = definition(text.trim(), language) else {
+            continue;
+        };
+        if nesting == Nesting::Braces {
+            while open.last().is_some_and(|&open| open >= level) {
+                open.pop();
+            }
+        }
+        symbols.push(CodeSymbol {
+            name,
+            kind,
+            line,
+            depth: open.len(),
+        });
+        open.push(level);
+    }
+    symbols
+}
+
+/// Brace depth after `line`, from the depth before it
+fn count_braces(line: &str, language: &str, depth: usize) -> usize {
+    let skipped: Vec<_> = tokenize_line(line, language)
+        .into_iter()
+        .filter(|(_, kind)| matches!(kind, TokenKind::String | TokenKind::Comment))
+        .map(|(range, _)| range)
+        .collect();
+    line.char_indices()
+        .filter(|(index, _)| !skipped.iter().any(|range| range.contains(index)))
+        .fold(depth, |depth, (_, c)| match c {
+            '{' => depth + 1,
+            '}' => depth.saturating_sub(1),
+            _ => depth,
+        })
+}
+
+/// The name and kind of the symbol a trimmed line defines, if it does
+fn definition(line: &str, language: &str) -> Option<(String, SymbolKind)> {
+    let mut rest = line;
+    while let Some((word, after)) = split_word(rest) {
+        if word.starts_with("pub(") || MODIFIERS.split_whitespace().any(|m| m == word) {
+            rest = after;
+        } else {
+            break;
+        }
+    }
+    if rest.starts_with("impl<") {
+        return impl_name(&rest[4..]).map(|name| (name, SymbolKind::Type));
+    }
+    let (word, after) = split_word(rest)?;
+
+    if matches!(word, "const" | "let" | "var") {
+        // `const fn` in Rust, or a JavaScript function held in a variable
+        if let Some(("fn", after)) = split_word(after) {
+            return Some((identifier(after)?, SymbolKind::Function));
+        }
+        let name = identifier(after)?;
+        let value = after[name.len()..]
+            .trim_start()
+            .strip_prefix('=')?
+            .trim_start();
+        let value = value.strip_prefix("async").unwrap_or(value).trim_start();
+        return (value.starts_with("function") || value.contains("=>"))
+            .then_some((name, SymbolKind::Function));
+    }
+
+    if let Some(&(_, kind)) = DEFINITION_KEYWORDS.iter().find(|(k, _)| *k == word) {
+        let name = match word {
+            "impl" => impl_name(after),
+            // Go methods name their receiver first
+            "func" if after.starts_with('(') => identifier(skip_parens(after)?),
+            "def" if after.starts_with("self.") => {
+                identifier(&after[5..]).map(|name| format!("self.{}", name))
+            }
+            _ => identifier(after),
+        };
+        return name.map(|name| (name, kind));
+    }
+
+    if has_bare_functions(language) {
+        return bare_function(rest).map(|name| (name, SymbolKind::Function));
+    }
+    None
+}
+
+/// A function declared without a keyword, like `int main(void) {` or a
+/// class method `render() {`: a name before parentheses, on a line opening
+/// its body
+fn bare_function(line: &str) -> Option<String> {
+    if !line.ends_with('{') || line.contains("=>") {
+        return None;
+    }
+    let (before, _) = line.split_once('(')?;
+    if before.replace("::", "").contains(['=', '.', ',', ':']) {
+        return None;
+    }
+    let first = before.split_whitespace().next()?;
+    if STATEMENT_WORDS.split_whitespace().any(|word| word == first) {
+        return None;
+    }
+    // C++ methods defined outside their class keep the class name
+    let name = before
+        .rsplit(|c: char| c.is_whitespace() || c == '*' || c == '&')
+        .next()?;
+    name.split("::")
+        .all(|part| identifier(part).as_deref() == Some(part))
+        .then(|| name.to_string())
+}
+
+/// Name of a Rust impl block: the type, or the trait and type, without
+/// generics on `impl` or the where clause
+fn impl_name(rest: &str) -> Option<String> {
+    let rest = if rest.starts_with('<') {
+        skip_brackets(rest, '<', '>')?
+    } else {
+        rest
+    };
+    let name = rest.split(" where").next()?.trim_end_matches('{').trim();
+    (!name.is_empty()).then(|| name.to_string())
+}
+
+/// The first word of `text` and what follows it, trimmed
+fn split_word(text: &str) -> Option<(&str, &str)> {
+    let text = text.trim_start();
+    if text.is_empty() {
+        return None;
+    }
+    let end = text.find(char::is_whitespace).unwrap_or(text.len());
+    Some((&text[..end], text[end..].trim_start()))
+}
+
+/// The identifier `text` starts with
+fn identifier(text: &str) -> Option<String> {
+    let text = text.trim_start();
+    let end = text
+        .find(|c: char| !(c.is_alphanumeric() || c == '_' || c == '$'))
+        .unwrap_or(text.len());
+    let name = &text[..end];
+    (!name.is_empty() && !name.starts_with(|c: char| c.is_ascii_digit())).then(|| name.to_string())
+}
+
+fn skip_parens(text: &str) -> Option<&str> {
+    skip_brackets(text, '(', ')')
+}
+
+/// What follows the bracketed group `text` starts with
+fn skip_brackets(text: &str, open: char, close: char) -> Option<&str> {
+    let mut depth = 0;
+    for (index, c) in text.char_indices() {
+        if c == open {
+            depth += 1;
+        } else if c == close {
+            depth -= 1;
+            if depth == 0 {
+                return Some(text[index + 1..].trim_start());
+            }
+        }
+    }
+    None
+}
+
+/// Indices of the symbols shown in an outline with the symbols in
+/// `collapsed` closed, so those nested in them are left out
+pub fn visible_symbols(symbols: &[CodeSymbol], collapsed: &HashSet<usize>) -> Vec<usize> {
+    let mut visible = Vec::new();
+    let mut hidden_below: Option<usize> = None;
+    for (index, symbol) in symbols.iter().enumerate() {
+        if let Some(depth) = hidden_below {
+            if symbol.depth > depth {
+                continue;
+            }
+            hidden_below = None;
+        }
+        visible.push(index);
+        if collapsed.contains(&index) {
+            hidden_below = Some(symbol.depth);
+        }
+    }
+    visible
+}
+
+/// Whether other symbols are nested in the symbol at `index`
+pub fn has_nested_symbols(symbols: &[CodeSymbol], index: usize) -> bool {
+    symbols
+        .get(index + 1)
+        .is_some_and(|next| next.depth > symbols[index].depth)
+}
+
+/// Symbols whose names contain `query`, ignoring case
+pub fn matching_symbols<'a>(
+    symbols: &'a [CodeSymbol],
+    query: &str,
+) -> impl Iterator<Item = &'a CodeSymbol> {
+    let query = query.to_lowercase();
+    symbols
+        .iter()
+        .filter(move |symbol| symbol.name.to_lowercase().contains(&query))
+}
@@ -7,8 +7,12 @@
 //!
 //! - **Item Search**: Search canvas items by name/content
 //! - **Theme Selection**: Quick theme switching
-//! - **Commands**: Execute various commands (md, theme, etc.)
-//! - **Fuzzy Matching**: Filter results as user types
+//! - **Quick Open**: Add a file from the quick-open folders by name (`Cmd+P`)
+//! - **Commands**: Run the commands of the
+//!   [`command_registry`](crate::command_registry) by name or keyword (`md`,
+//!   `theme`, `align left`, ...)
+//! - **Fuzzy Matching**: Filter results as user types (`ocb` finds "Open
+//!   Chart Builder"), ranking recently used commands first
 
 use crate::settings::Settings;
 use gpui::*;
@@ -50,33 +54,48 @@ impl SearchResult {
     }
 }
 
-/// Special command IDs that don't correspond to canvas items.
-/// Using high values to avoid collision with real item IDs.
-pub mod command_ids {
-    pub const THEME: u64 = u64::MAX - 1;
-    pub const CREATE_MARKDOWN: u64 = u64::MAX - 2;
+/// Results for other boards take IDs counting down from here, well clear of
+/// both command IDs and item IDs
+const BOARD_RESULT_BASE: u64 = u64::MAX - 1000;
+
+/// Most other boards listed in the search results
+pub const MAX_BOARD_RESULTS: usize = 20;
+
+/// Result ID of the `index`th board in the search results
+pub fn board_result_id(index: usize) -> u64 {
+    BOARD_RESULT_BASE - index as u64
 }
 
-/// Available commands in the palette.
-pub struct Command {
-    pub id: u64,
-    pub name: &'static str,
-    pub description: &'static str,
+/// Position among the boards in the search results of a result ID, or
+/// `None` for items and commands
+pub fn board_result_index(id: u64) -> Option<usize> {
+    let index = BOARD_RESULT_BASE.checked_sub(id)?;
+    (index < MAX_BOARD_RESULTS as u64).then_some(index as usize)
 }
 
-/// List of all available commands.
-pub const COMMANDS: &[Command] = &[
-    Command {
-        id: command_ids::THEME,
-        name: "theme",
-        description: "Change theme",
-    },
-    Command {
-        id: command_ids::CREATE_MARKDOWN,
-        name: "md",
-        description: "Create markdown note",
-    },
-];
+/// Results for registered commands take IDs counting down from here, above
+/// the boards
+const COMMAND_RESULT_BASE: u64 = u64::MAX - 100;
+
+/// Most registered commands listed in the search results
+pub const MAX_COMMAND_RESULTS: usize = 100;
+
+/// Result ID of the `index`th registered command in the search results
+pub fn command_result_id(index: usize) -> u64 {
+    COMMAND_RESULT_BASE - index as u64
+}
+
+/// Position among the registered commands in the search results of a
+/// result ID, or `None` for anything else
+pub fn command_result_index(id: u64) -> Option<usize> {
+    let index = COMMAND_RESULT_BASE.checked_sub(id)?;
+    (index < MAX_COMMAND_RESULTS as u64).then_some(index as usize)
+}
+
+/// Whether a result ID is a command's rather than an item's or a board's
+pub fn is_command_id(id: u64) -> bool {
+    id > BOARD_RESULT_BASE
+}
 
 /// Command palette state and logic.
 pub struct CommandPalette {
@@ -154,26 +173,8 @@ impl CommandPalette {
             return;
         }
 
-        // Check for command matches
-        if !query.is_empty() && query.len() <= 7 {
-            let query_lower = query.to_lowercase();
-            let matching: Vec<SearchResult> = COMMANDS
-                .iter()
-                .filter(|cmd| cmd.name.starts_with(&query_lower))
-                .map(|cmd| {
-                    SearchResult::new(cmd.id, format!("{} - {}", cmd.name, cmd.description))
-                })
-                .collect();
-
-            if !matching.is_empty() {
-                self.results = matching;
-                self.selected_index = 0;
-                return;
-            }
-        }
-
-        // For item search, we'll emit an event to let the parent handle it
-        // since it has access to the board data
+        // For commands and items, we'll emit an event to let the parent
+        // handle it since it has the command registry and the board data
         self.results.clear();
         self.selected_index = 0;
     }
@@ -268,8 +269,6 @@ pub enum PaletteAction {
     SetTheme(String),
     /// Create a markdown note with the given name
     CreateMarkdown(String),
-    /// Enter theme selection mode
-    EnterThemeMode,
 }
 
 /// Process the current selection and return the action to take.
@@ -282,23 +281,9 @@ pub fn process_selection(palette: &CommandPalette) -> PaletteAction {
         return PaletteAction::Close;
     }
 
-    // Check for command selection
     if let Some(result) = palette.selected_result() {
-        match result.id {
-            command_ids::THEME => PaletteAction::EnterThemeMode,
-            command_ids::CREATE_MARKDOWN => {
-                let name = palette
-                    .query
-                    .strip_prefix("md ")
-                    .unwrap_or("Untitled")
-                    .trim();
-                PaletteAction::CreateMarkdown(name.to_string())
-            }
-            _ => {
-                // Regular item - jump to it
-                PaletteAction::JumpToItem(result.id)
-            }
-        }
+        // Regular item - jump to it
+        PaletteAction::JumpToItem(result.id)
     } else {
         // No result selected, check if query is a command
         let query = palette.query.trim();
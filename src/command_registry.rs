@@ -1,22 +1,48 @@
 //! Command Registry - Extensible command system for the application.
 //!
-//! This module provides a registry-based command system that makes it easy
-//! to add new commands without modifying core palette logic.
+//! Every command the user can invoke by name lives in one registry: the
+//! command palette lists it, the application menus are built from it, and
+//! its keybindings go into the keymap. Modules (and later plugins) add
+//! commands by implementing [`Command`], or with a [`FnCommand`] built from
+//! an ID, a title and a handler closure.
 //!
 //! ## Usage
 //!
 //! ```ignore
-//! // Register a command
-//! registry.register(MyCommand::new());
+//! // Register a command before the windows open
+//! register_command(
+//!     FnCommand::new("board:tidy", "Tidy Board", |app, window, cx| app.tidy(window, cx))
+//!         .category("Board")
+//!         .keybinding("cmd-shift-y")
+//!         .menu("View", 2),
+//! );
 //!
 //! // Execute by ID
-//! if let Some(cmd) = registry.get("my_command") {
+//! if let Some(cmd) = COMMAND_REGISTRY.read().get("board:tidy") {
 //!     if cmd.is_enabled(app) {
-//!         cmd.execute(app, cx);
+//!         cmd.execute(app, window, cx);
 //!     }
 //! }
 //! ```
-
+//!
+//! Keybindings are written the way GPUI parses them. A `cmd-` binding is
+//! bound to Cmd on macOS and Ctrl on Linux and Windows, like the rest of the
+//! keymap; other bindings are taken as they are.
+
+use crate::actions::{
+    CollectBoardAssets, EncryptBoard, ExportBoardHtml, ExportBoardPng, NewWindow, OpenFile,
+    PauseAllMedia, Quit, RemoveBoardEncryption, RunCommand, ToggleOutline, ToggleUndoHistory,
+    ToggleVersionHistory,
+};
+use crate::app::{CmdPaletteMode, Humanboard};
+use crate::arrange::{AlignEdge, DistributeAxis, MIN_ALIGN_ITEMS, MIN_DISTRIBUTE_ITEMS};
+use crate::document_export::DocumentOrder;
+use crate::focus::FocusContext;
+use crate::fuzzy::command_rank;
+use crate::playlist::MIN_PLAYLIST_ITEMS;
+use gpui::{Action, Context, KeyBinding, Menu, MenuItem, Window};
+use once_cell::sync::Lazy;
+use parking_lot::RwLock;
 use std::collections::HashMap;
 use std::sync::Arc;
 
@@ -38,32 +64,307 @@ pub trait Command: Send + Sync {
         "General"
     }
 
-    /// Keyboard shortcut hint (for display only, actual binding is separate)
+    /// Keyboard shortcut hint, if it should read differently from the first
+    /// of [`Command::keybindings`]
     fn shortcut_hint(&self) -> Option<&str> {
         None
     }
 
+    /// Keystrokes that run the command, e.g. `cmd-shift-z`
+    fn keybindings(&self) -> &[&str] {
+        &[]
+    }
+
+    /// Key contexts the keybindings apply in; none means everywhere
+    fn key_contexts(&self) -> &[&str] {
+        &[]
+    }
+
+    /// Application menu the command is listed in, and its group there.
+    /// Groups are listed in order, with a separator between them.
+    fn menu(&self) -> Option<(&str, u32)> {
+        None
+    }
+
     /// Check if this command can be executed in the current state
-    fn is_enabled(&self, app: &crate::app::Humanboard) -> bool;
+    fn is_enabled(&self, app: &Humanboard) -> bool;
 
     /// Execute the command
-    fn execute(
-        &self,
-        app: &mut crate::app::Humanboard,
-        window: &mut gpui::Window,
-        cx: &mut gpui::Context<crate::app::Humanboard>,
-    );
+    fn execute(&self, app: &mut Humanboard, window: &mut Window, cx: &mut Context<Humanboard>);
 
     /// Keywords for fuzzy search (in addition to name)
     fn keywords(&self) -> &[&str] {
         &[]
     }
+
+    /// Key bindings running the command on `keybindings`. They dispatch
+    /// [`RunCommand`] unless the command has an action of its own.
+    fn key_bindings_for(&self, keybindings: &[&str]) -> Vec<KeyBinding> {
+        action_key_bindings(keybindings, self.key_contexts(), RunCommand::new(self.id()))
+    }
+
+    /// Key bindings of the command's own keybindings
+    fn key_bindings(&self) -> Vec<KeyBinding> {
+        self.key_bindings_for(self.keybindings())
+    }
+
+    /// Menu entry of the command
+    fn menu_item(&self) -> MenuItem {
+        MenuItem::action(self.name().to_string(), RunCommand::new(self.id()))
+    }
+}
+
+/// The keystrokes `binding` is bound to on each platform: `cmd-k` is bound
+/// as both `cmd-k` and `ctrl-k`, `cmd-k cmd-s` as `cmd-k cmd-s` and
+/// `ctrl-k ctrl-s`
+pub fn platform_keystrokes(binding: &str) -> Vec<String> {
+    if !binding.split_whitespace().any(|key| key.starts_with("cmd-")) {
+        return vec![binding.to_string()];
+    }
+    let ctrl = binding
+        .split_whitespace()
+        .map(|key| match key.strip_prefix("cmd-") {
+            Some(rest) => format!("ctrl-{}", rest),
+            None => key.to_string(),
+        })
+        .collect::<Vec<_>>()
+        .join(" ");
+    vec![binding.to_string(), ctrl]
+}
+
+/// How a keybinding reads in the palette, e.g. `Cmd+Shift+Z`, or
+/// `Cmd+K Cmd+S` for a sequence of keystrokes
+pub fn keystroke_label(binding: &str) -> String {
+    binding
+        .split_whitespace()
+        .map(single_keystroke_label)
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn single_keystroke_label(binding: &str) -> String {
+    // "cmd--" is Cmd and the minus key
+    let (modifiers, key) = match binding.strip_suffix("--") {
+        Some(modifiers) => (modifiers, "-"),
+        None => binding.rsplit_once('-').unwrap_or(("", binding)),
+    };
+    let mut chars = key.chars();
+    let key: String = match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    };
+    modifiers
+        .split('-')
+        .filter(|modifier| !modifier.is_empty())
+        .map(|modifier| match modifier {
+            "cmd" => "Cmd".to_string(),
+            "ctrl" => "Ctrl".to_string(),
+            "alt" => "Alt".to_string(),
+            "shift" => "Shift".to_string(),
+            other => other.to_string(),
+        })
+        .chain(std::iter::once(key))
+        .collect::<Vec<_>>()
+        .join("+")
+}
+
+/// Key bindings of `keybindings` to `action`, in each of `contexts`
+fn action_key_bindings<A: Action + Clone>(
+    keybindings: &[&str],
+    contexts: &[&str],
+    action: A,
+) -> Vec<KeyBinding> {
+    let contexts: Vec<Option<&str>> = if contexts.is_empty() {
+        vec![None]
+    } else {
+        contexts.iter().copied().map(Some).collect()
+    };
+    let mut bindings = Vec::new();
+    for binding in keybindings {
+        for keystrokes in platform_keystrokes(binding) {
+            for context in &contexts {
+                bindings.push(KeyBinding::new(&keystrokes, action.clone(), *context));
+            }
+        }
+    }
+    bindings
+}
+
+type Handler = Box<dyn Fn(&mut Humanboard, &mut Window, &mut Context<Humanboard>) + Send + Sync>;
+type EnabledCheck = Box<dyn Fn(&Humanboard) -> bool + Send + Sync>;
+type BindingsOf = Box<dyn Fn(&[&str], &[&str]) -> Vec<KeyBinding> + Send + Sync>;
+type MenuItemOf = Box<dyn Fn(&str) -> MenuItem + Send + Sync>;
+
+/// A command made of an ID, a title and a handler closure, set up with
+/// builder methods.
+///
+/// [`FnCommand::for_action`] makes a command that dispatches a GPUI action
+/// instead, which its keybindings and menu entry dispatch too. That keeps
+/// app-wide actions like Quit working with no window open.
+pub struct FnCommand {
+    id: &'static str,
+    name: String,
+    category: String,
+    description: Option<String>,
+    keywords: Vec<&'static str>,
+    keybindings: Vec<&'static str>,
+    key_contexts: Vec<&'static str>,
+    menu: Option<(&'static str, u32)>,
+    enabled: Option<EnabledCheck>,
+    handler: Handler,
+    /// Key bindings and menu entry for the action of an action command
+    action: Option<(BindingsOf, MenuItemOf)>,
+}
+
+impl FnCommand {
+    pub fn new(
+        id: &'static str,
+        name: impl Into<String>,
+        handler: impl Fn(&mut Humanboard, &mut Window, &mut Context<Humanboard>)
+        + Send
+        + Sync
+        + 'static,
+    ) -> Self {
+        Self {
+            id,
+            name: name.into(),
+            category: "General".to_string(),
+            description: None,
+            keywords: Vec::new(),
+            keybindings: Vec::new(),
+            key_contexts: Vec::new(),
+            menu: None,
+            enabled: None,
+            handler: Box::new(handler),
+            action: None,
+        }
+    }
+
+    /// A command dispatching `action`
+    pub fn for_action<A: Action + Clone + Sync>(
+        id: &'static str,
+        name: impl Into<String>,
+        action: A,
+    ) -> Self {
+        let dispatched = action.clone();
+        let bound = action.clone();
+        let mut command = Self::new(id, name, move |_, window, cx| {
+            window.dispatch_action(Box::new(dispatched.clone()), cx)
+        });
+        command.action = Some((
+            Box::new(move |keybindings, contexts| {
+                action_key_bindings(keybindings, contexts, bound.clone())
+            }),
+            Box::new(move |name| MenuItem::action(name.to_string(), action.clone())),
+        ));
+        command
+    }
+
+    pub fn category(mut self, category: impl Into<String>) -> Self {
+        self.category = category.into();
+        self
+    }
+
+    pub fn description(mut self, description: impl Into<String>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    pub fn keywords(mut self, keywords: &[&'static str]) -> Self {
+        self.keywords.extend_from_slice(keywords);
+        self
+    }
+
+    /// Add a keybinding; see the module docs for how it is written
+    pub fn keybinding(mut self, binding: &'static str) -> Self {
+        self.keybindings.push(binding);
+        self
+    }
+
+    /// Limit the keybindings to a key context; can be given more than once
+    pub fn key_context(mut self, context: &'static str) -> Self {
+        self.key_contexts.push(context);
+        self
+    }
+
+    /// List the command in an application menu, in a group of it
+    pub fn menu(mut self, menu: &'static str, group: u32) -> Self {
+        self.menu = Some((menu, group));
+        self
+    }
+
+    /// Only offer the command when `enabled` holds; it always is otherwise
+    pub fn enabled_when(
+        mut self,
+        enabled: impl Fn(&Humanboard) -> bool + Send + Sync + 'static,
+    ) -> Self {
+        self.enabled = Some(Box::new(enabled));
+        self
+    }
+}
+
+impl Command for FnCommand {
+    fn id(&self) -> &'static str {
+        self.id
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn description(&self) -> Option<&str> {
+        self.description.as_deref()
+    }
+
+    fn category(&self) -> &str {
+        &self.category
+    }
+
+    fn keybindings(&self) -> &[&str] {
+        &self.keybindings
+    }
+
+    fn key_contexts(&self) -> &[&str] {
+        &self.key_contexts
+    }
+
+    fn menu(&self) -> Option<(&str, u32)> {
+        self.menu
+    }
+
+    fn is_enabled(&self, app: &Humanboard) -> bool {
+        self.enabled.as_ref().is_none_or(|enabled| enabled(app))
+    }
+
+    fn execute(&self, app: &mut Humanboard, window: &mut Window, cx: &mut Context<Humanboard>) {
+        (self.handler)(app, window, cx);
+    }
+
+    fn keywords(&self) -> &[&str] {
+        &self.keywords
+    }
+
+    fn key_bindings_for(&self, keybindings: &[&str]) -> Vec<KeyBinding> {
+        match &self.action {
+            Some((bindings, _)) => bindings(keybindings, &self.key_contexts),
+            None => action_key_bindings(keybindings, &self.key_contexts, RunCommand::new(self.id)),
+        }
+    }
+
+    fn menu_item(&self) -> MenuItem {
+        match &self.action {
+            Some((_, menu_item)) => menu_item(&self.name),
+            None => MenuItem::action(self.name.clone(), RunCommand::new(self.id)),
+        }
+    }
 }
 
 /// Registry for application commands.
 #[derive(Default)]
 pub struct CommandRegistry {
     commands: HashMap<&'static str, Arc<dyn Command>>,
+    /// Command IDs in the order they were registered
+    order: Vec<&'static str>,
     /// Commands sorted by category for display
     by_category: HashMap<String, Vec<&'static str>>,
 }
@@ -73,16 +374,24 @@ impl CommandRegistry {
     pub fn new() -> Self {
         Self {
             commands: HashMap::new(),
+            order: Vec::new(),
             by_category: HashMap::new(),
         }
     }
 
-    /// Register a command.
+    /// Register a command, replacing any registered with the same ID.
     pub fn register<C: Command + 'static>(&mut self, cmd: C) {
         let id = cmd.id();
         let category = cmd.category().to_string();
 
-        self.commands.insert(id, Arc::new(cmd));
+        if self.commands.insert(id, Arc::new(cmd)).is_some() {
+            for ids in self.by_category.values_mut() {
+                ids.retain(|registered| *registered != id);
+            }
+            self.by_category.retain(|_, ids| !ids.is_empty());
+        } else {
+            self.order.push(id);
+        }
 
         self.by_category
             .entry(category)
@@ -95,16 +404,15 @@ impl CommandRegistry {
         self.commands.get(id).cloned()
     }
 
-    /// Get all registered commands.
+    /// Get all registered commands, in the order they were registered.
     pub fn all(&self) -> impl Iterator<Item = &Arc<dyn Command>> {
-        self.commands.values()
+        self.order.iter().filter_map(|id| self.commands.get(id))
     }
 
     /// Get all command IDs.
     pub fn ids(&self) -> impl Iterator<Item = &&'static str> {
-        self.commands.keys()
+        self.order.iter()
     }
-
     /// Get commands by category.
     pub fn by_category(&self, category: &str) -> Vec<Arc<dyn Command>> {
         self.by_category
@@ -158,6 +466,26 @@ impl CommandRegistry {
         matches
     }
 
+    /// Enabled commands fuzzily matching `query`, in registration order,
+    /// each ranked as (score, tiebreak): the best score of its name, ID,
+    /// category or keywords, and the score of its description, which only
+    /// orders commands that score the same
+    pub fn matching(
+        &self,
+        query: &str,
+        app: &Humanboard,
+    ) -> Vec<((i32, i32), Arc<dyn Command>)> {
+        self.all()
+            .filter(|cmd| cmd.is_enabled(app))
+            .filter_map(|cmd| {
+                let mut texts = vec![cmd.name(), cmd.id(), cmd.category()];
+                texts.extend_from_slice(cmd.keywords());
+                let rank = command_rank(query, &texts, cmd.description().unwrap_or_default())?;
+                Some((rank, cmd.clone()))
+            })
+            .collect()
+    }
+
     /// Get the number of registered commands.
     pub fn len(&self) -> usize {
         self.commands.len()
@@ -167,6 +495,80 @@ impl CommandRegistry {
     pub fn is_empty(&self) -> bool {
         self.commands.is_empty()
     }
+
+    /// Key bindings of every command, for the keymap
+    pub fn key_bindings(&self) -> Vec<KeyBinding> {
+        self.all().flat_map(|cmd| cmd.key_bindings()).collect()
+    }
+
+    /// Names of the menus commands are listed in, in the order they first
+    /// appear, with their commands by group and then registration order
+    pub fn menu_layout(&self) -> Vec<(String, Vec<Vec<Arc<dyn Command>>>)> {
+        let mut menus: Vec<(String, Vec<(u32, Arc<dyn Command>)>)> = Vec::new();
+        for cmd in self.all() {
+            let Some((menu, group)) = cmd.menu() else {
+                continue;
+            };
+            let index = match menus.iter().position(|(name, _)| name == menu) {
+                Some(index) => index,
+                None => {
+                    menus.push((menu.to_string(), Vec::new()));
+                    menus.len() - 1
+                }
+            };
+            menus[index].1.push((group, cmd.clone()));
+        }
+
+        menus
+            .into_iter()
+            .map(|(name, mut entries)| {
+                // Stable, so each group keeps registration order
+                entries.sort_by_key(|(group, _)| *group);
+                let mut groups: Vec<Vec<Arc<dyn Command>>> = Vec::new();
+                let mut current = None;
+                for (group, cmd) in entries {
+                    if current != Some(group) {
+                        groups.push(Vec::new());
+                        current = Some(group);
+                    }
+                    if let Some(last) = groups.last_mut() {
+                        last.push(cmd);
+                    }
+                }
+                (name, groups)
+            })
+            .collect()
+    }
+
+    /// The application menus, for the menu bar
+    pub fn menus(&self) -> Vec<Menu> {
+        self.menu_layout()
+            .into_iter()
+            .map(|(name, groups)| {
+                let mut items = Vec::new();
+                for (index, group) in groups.iter().enumerate() {
+                    if index > 0 {
+                        items.push(MenuItem::separator());
+                    }
+                    items.extend(group.iter().map(|cmd| cmd.menu_item()));
+                }
+                Menu {
+                    name: name.into(),
+                    items,
+                }
+            })
+            .collect()
+    }
+}
+
+/// The commands of the application, shared by every window. Register
+/// commands before the keymap and menus are set up at startup.
+pub static COMMAND_REGISTRY: Lazy<RwLock<CommandRegistry>> =
+    Lazy::new(|| RwLock::new(create_default_registry()));
+
+/// Register a command with the application's registry
+pub fn register_command<C: Command + 'static>(cmd: C) {
+    COMMAND_REGISTRY.write().register(cmd);
 }
 
 // ============================================================================
@@ -189,8 +591,8 @@ impl Command for GoHomeCommand {
         "Navigation"
     }
 
-    fn shortcut_hint(&self) -> Option<&str> {
-        Some("Cmd+Shift+H")
+    fn keybindings(&self) -> &[&str] {
+        &["cmd-h"]
     }
 
     fn is_enabled(&self, app: &crate::app::Humanboard) -> bool {
@@ -227,8 +629,12 @@ impl Command for NewBoardCommand {
         "Board"
     }
 
-    fn shortcut_hint(&self) -> Option<&str> {
-        Some("Cmd+N")
+    fn keybindings(&self) -> &[&str] {
+        &["cmd-n"]
+    }
+
+    fn menu(&self) -> Option<(&str, u32)> {
+        Some(("File", 0))
     }
 
     fn is_enabled(&self, _app: &crate::app::Humanboard) -> bool {
@@ -265,8 +671,12 @@ impl Command for OpenSettingsCommand {
         "Application"
     }
 
-    fn shortcut_hint(&self) -> Option<&str> {
-        Some("Cmd+,")
+    fn keybindings(&self) -> &[&str] {
+        &["cmd-,"]
+    }
+
+    fn menu(&self) -> Option<(&str, u32)> {
+        Some(("Humanboard", 0))
     }
 
     fn is_enabled(&self, _app: &crate::app::Humanboard) -> bool {
@@ -303,8 +713,8 @@ impl Command for ShowShortcutsCommand {
         "Help"
     }
 
-    fn shortcut_hint(&self) -> Option<&str> {
-        Some("?")
+    fn keybindings(&self) -> &[&str] {
+        &["cmd-/"]
     }
 
     fn is_enabled(&self, _app: &crate::app::Humanboard) -> bool {
@@ -341,8 +751,12 @@ impl Command for UndoCommand {
         "Edit"
     }
 
-    fn shortcut_hint(&self) -> Option<&str> {
-        Some("Cmd+Z")
+    fn keybindings(&self) -> &[&str] {
+        &["cmd-z"]
+    }
+
+    fn key_contexts(&self) -> &[&str] {
+        &[FocusContext::KEY_CANVAS]
     }
 
     fn is_enabled(&self, app: &crate::app::Humanboard) -> bool {
@@ -376,8 +790,13 @@ impl Command for RedoCommand {
         "Edit"
     }
 
-    fn shortcut_hint(&self) -> Option<&str> {
-        Some("Cmd+Shift+Z")
+    fn keybindings(&self) -> &[&str] {
+        // Ctrl+Y is how Windows redoes
+        &["cmd-shift-z", "ctrl-y"]
+    }
+
+    fn key_contexts(&self) -> &[&str] {
+        &[FocusContext::KEY_CANVAS]
     }
 
     fn is_enabled(&self, app: &crate::app::Humanboard) -> bool {
@@ -411,8 +830,8 @@ impl Command for ZoomInCommand {
         "View"
     }
 
-    fn shortcut_hint(&self) -> Option<&str> {
-        Some("Cmd++")
+    fn keybindings(&self) -> &[&str] {
+        &["cmd-=", "cmd-+"]
     }
 
     fn is_enabled(&self, app: &crate::app::Humanboard) -> bool {
@@ -425,7 +844,12 @@ impl Command for ZoomInCommand {
         window: &mut gpui::Window,
         cx: &mut gpui::Context<crate::app::Humanboard>,
     ) {
-        app.zoom_in(window, cx);
+        // The focused preview tab zooms instead of the canvas
+        if app.zooms_preview_tab(window) {
+            app.zoom_preview_tab(true, cx);
+        } else {
+            app.zoom_in(window, cx);
+        }
     }
 
     fn keywords(&self) -> &[&str] {
@@ -449,8 +873,8 @@ impl Command for ZoomOutCommand {
         "View"
     }
 
-    fn shortcut_hint(&self) -> Option<&str> {
-        Some("Cmd+-")
+    fn keybindings(&self) -> &[&str] {
+        &["cmd--"]
     }
 
     fn is_enabled(&self, app: &crate::app::Humanboard) -> bool {
@@ -463,7 +887,11 @@ impl Command for ZoomOutCommand {
         window: &mut gpui::Window,
         cx: &mut gpui::Context<crate::app::Humanboard>,
     ) {
-        app.zoom_out(window, cx);
+        if app.zooms_preview_tab(window) {
+            app.zoom_preview_tab(false, cx);
+        } else {
+            app.zoom_out(window, cx);
+        }
     }
 
     fn keywords(&self) -> &[&str] {
@@ -487,8 +915,8 @@ impl Command for ZoomResetCommand {
         "View"
     }
 
-    fn shortcut_hint(&self) -> Option<&str> {
-        Some("Cmd+0")
+    fn keybindings(&self) -> &[&str] {
+        &["cmd-0"]
     }
 
     fn is_enabled(&self, app: &crate::app::Humanboard) -> bool {
@@ -498,10 +926,14 @@ impl Command for ZoomResetCommand {
     fn execute(
         &self,
         app: &mut crate::app::Humanboard,
-        _window: &mut gpui::Window,
+        window: &mut gpui::Window,
         cx: &mut gpui::Context<crate::app::Humanboard>,
     ) {
-        app.zoom_reset(cx);
+        if app.zooms_preview_tab(window) {
+            app.reset_preview_tab_zoom(cx);
+        } else {
+            app.zoom_reset(cx);
+        }
     }
 
     fn keywords(&self) -> &[&str] {
@@ -509,28 +941,529 @@ impl Command for ZoomResetCommand {
     }
 }
 
+/// Fit all items in the view
+pub struct ZoomToFitCommand;
+
+impl Command for ZoomToFitCommand {
+    fn id(&self) -> &'static str {
+        "view:zoom_to_fit"
+    }
+
+    fn name(&self) -> &str {
+        "Zoom to Fit"
+    }
+
+    fn category(&self) -> &str {
+        "View"
+    }
+
+    fn keybindings(&self) -> &[&str] {
+        &["cmd-1"]
+    }
+
+    fn key_contexts(&self) -> &[&str] {
+        &[FocusContext::KEY_CANVAS]
+    }
+
+    fn menu(&self) -> Option<(&str, u32)> {
+        Some(("View", 0))
+    }
+
+    fn is_enabled(&self, app: &crate::app::Humanboard) -> bool {
+        app.canvas.board.as_ref().is_some_and(|board| !board.items.is_empty())
+    }
+
+    fn execute(
+        &self,
+        app: &mut crate::app::Humanboard,
+        window: &mut gpui::Window,
+        cx: &mut gpui::Context<crate::app::Humanboard>,
+    ) {
+        app.zoom_to_fit(window, cx);
+    }
+
+    fn keywords(&self) -> &[&str] {
+        &["all", "overview", "show everything"]
+    }
+}
+
+/// Fit the selected items in the view
+pub struct ZoomToSelectionCommand;
+
+impl Command for ZoomToSelectionCommand {
+    fn id(&self) -> &'static str {
+        "view:zoom_to_selection"
+    }
+
+    fn name(&self) -> &str {
+        "Zoom to Selection"
+    }
+
+    fn category(&self) -> &str {
+        "View"
+    }
+
+    fn keybindings(&self) -> &[&str] {
+        &["cmd-2"]
+    }
+
+    fn key_contexts(&self) -> &[&str] {
+        &[FocusContext::KEY_CANVAS]
+    }
+
+    fn menu(&self) -> Option<(&str, u32)> {
+        Some(("View", 0))
+    }
+
+    fn is_enabled(&self, app: &crate::app::Humanboard) -> bool {
+        app.canvas.board.is_some() && !app.canvas.selected_items.is_empty()
+    }
+
+    fn execute(
+        &self,
+        app: &mut crate::app::Humanboard,
+        window: &mut gpui::Window,
+        cx: &mut gpui::Context<crate::app::Humanboard>,
+    ) {
+        app.zoom_to_selection(window, cx);
+    }
+
+    fn keywords(&self) -> &[&str] {
+        &["focus", "selected", "frame"]
+    }
+}
+
 /// Create a command registry with all built-in commands.
 pub fn create_default_registry() -> CommandRegistry {
     let mut registry = CommandRegistry::new();
+    let has_board = |app: &Humanboard| app.canvas.board.is_some();
+
+    // Application - first, so its menu leads the menu bar as macOS expects
+    registry.register(OpenSettingsCommand);
+    registry.register(ShowShortcutsCommand);
+    registry.register(
+        FnCommand::new("app:change_theme", "Change Theme…", |app, window, cx| {
+            app.show_command_palette_in(CmdPaletteMode::Themes, window, cx)
+        })
+        .category("Application")
+        .description("Pick a theme from the palette")
+        .keywords(&["theme", "colors", "dark", "light", "appearance"]),
+    );
+    registry.register(
+        FnCommand::new(
+            "app:export_settings_profile",
+            "Export Settings Profile…",
+            |app, _window, cx| app.export_settings_profile(cx),
+        )
+        .category("Application")
+        .description("Save the settings, custom themes, keymap and board overrides to one file")
+        .menu("Humanboard", 0)
+        .keywords(&["preferences", "backup", "sync", "dotfiles", "keymap", "theme"]),
+    );
+    registry.register(
+        FnCommand::new(
+            "app:import_settings_profile",
+            "Import Settings Profile…",
+            |app, _window, cx| app.import_settings_profile(cx),
+        )
+        .category("Application")
+        .description("Apply the settings, custom themes, keymap and board overrides of a profile file")
+        .menu("Humanboard", 0)
+        .keywords(&["preferences", "restore", "sync", "dotfiles", "keymap", "theme"]),
+    );
+    registry.register(
+        FnCommand::for_action("app:quit", "Quit Humanboard", Quit)
+            .category("Application")
+            .keybinding("cmd-q")
+            .menu("Humanboard", 1)
+            .keywords(&["exit", "close"]),
+    );
+    registry.register(
+        FnCommand::for_action("app:new_window", "New Window", NewWindow)
+            .category("Application")
+            .keybinding("cmd-shift-n")
+            .menu("File", 0),
+    );
 
     // Navigation
     registry.register(GoHomeCommand);
 
     // Board
     registry.register(NewBoardCommand);
+    registry.register(
+        FnCommand::for_action("file:open", "Open File…", OpenFile)
+            .category("File")
+            .keybinding("cmd-o")
+            .menu("File", 1)
+            .enabled_when(has_board)
+            .keywords(&["add", "import"]),
+    );
+    registry.register(
+        FnCommand::new("file:quick_open", "Quick Open…", |app, window, cx| {
+            app.show_quick_open(window, cx)
+        })
+        .category("File")
+        .description("Add a file from your folders by name")
+        .keybinding("cmd-p")
+        .key_context(FocusContext::KEY_CANVAS)
+        .menu("File", 1)
+        .enabled_when(has_board)
+        .keywords(&["add", "find", "files", "go to file"]),
+    );
+    registry.register(
+        FnCommand::new(
+            "board:new_markdown_note",
+            "New Markdown Note",
+            |app, window, cx| app.create_markdown_note("Untitled".to_string(), window, cx),
+        )
+        .category("Board")
+        .description("Create a markdown note and open it to edit; type md <name> to name it")
+        .enabled_when(has_board)
+        .keywords(&["md", "note", "markdown", "create"]),
+    );
+    registry.register(
+        FnCommand::for_action(
+            "board:collect_assets",
+            "Copy Linked Files into Board",
+            CollectBoardAssets,
+        )
+        .category("Board")
+        .menu("File", 1)
+        .enabled_when(has_board),
+    );
+    registry.register(
+        FnCommand::new(
+            "board:import_obsidian_canvas",
+            "Import Obsidian Canvas…",
+            |app, _window, cx| app.import_obsidian_canvas(cx),
+        )
+        .category("Board")
+        .description("Add the notes, files, links and groups of a .canvas file to the board")
+        .menu("File", 1)
+        .enabled_when(has_board)
+        .keywords(&["obsidian", "canvas", "migrate", "open"]),
+    );
+    registry.register(
+        FnCommand::new(
+            "board:import_whiteboard",
+            "Import Miro or FigJam Board…",
+            |app, _window, cx| app.import_whiteboard_export(cx),
+        )
+        .category("Board")
+        .description("Add the sticky notes, shapes, images and connectors of a JSON or CSV export to the board")
+        .menu("File", 1)
+        .enabled_when(has_board)
+        .keywords(&["miro", "figjam", "figma", "sticky", "migrate", "csv"]),
+    );
+    registry.register(
+        FnCommand::for_action("board:export_html", "Export as Web Page…", ExportBoardHtml)
+            .category("Board")
+            .menu("File", 1)
+            .enabled_when(has_board)
+            .keywords(&["html", "share"]),
+    );
+    registry.register(
+        FnCommand::for_action("board:export_png", "Export as Image…", ExportBoardPng)
+            .category("Board")
+            .menu("File", 1)
+            .enabled_when(has_board)
+            .keywords(&["png", "screenshot"]),
+    );
+    registry.register(
+        FnCommand::new(
+            "board:export_markdown_outline",
+            "Export as Markdown Outline…",
+            |app, _window, cx| app.export_board_outline(cx),
+        )
+        .category("Board")
+        .description("Save the whole board as one Markdown document with a section per frame")
+        .menu("File", 1)
+        .enabled_when(has_board)
+        .keywords(&["markdown", "md", "outline", "notes", "document"]),
+    );
+    registry.register(
+        FnCommand::new(
+            "board:export_selection_markdown",
+            "Export Selection as Markdown",
+            |app, _window, cx| app.export_selection_markdown(DocumentOrder::Reading, cx),
+        )
+        .category("Board")
+        .description("Save the selected text, markdown, code and image cards as one document, in reading order")
+        .enabled_when(|app| app.has_exportable_selection())
+        .keywords(&["export", "md", "document"]),
+    );
+    registry.register(
+        FnCommand::new(
+            "board:export_selection_markdown_stacked",
+            "Export Selection as Markdown, Back to Front",
+            |app, _window, cx| app.export_selection_markdown(DocumentOrder::Stacking, cx),
+        )
+        .category("Board")
+        .description("Save the selected cards as one document, in the order they are stacked")
+        .enabled_when(|app| app.has_exportable_selection())
+        .keywords(&["export-stacked", "md", "document", "layers"]),
+    );
+    registry.register(
+        FnCommand::new("board:export_region", "Export Region…", |app, window, cx| {
+            app.open_export_dialog(window, cx)
+        })
+        .category("Board")
+        .description("Export all items, the selection or a drawn region as PNG, SVG or PDF")
+        .menu("File", 1)
+        .enabled_when(has_board)
+        .keywords(&["png", "svg", "pdf", "vector", "print", "area"]),
+    );
+    registry.register(
+        FnCommand::new("board:create_checkpoint", "Create Checkpoint…", |app, window, cx| {
+            app.prompt_checkpoint_name(window, cx)
+        })
+        .category("Board")
+        .description("Save a named version of the board to compare with or restore later")
+        .keybinding("cmd-alt-k")
+        .key_context(FocusContext::KEY_CANVAS)
+        .menu("File", 1)
+        .enabled_when(|app| {
+            app.canvas
+                .board
+                .as_ref()
+                .is_some_and(|board| !board.is_encrypted())
+        })
+        .keywords(&["snapshot", "save version", "compare", "diff"]),
+    );
+    registry.register(
+        FnCommand::for_action("board:encrypt", "Encrypt Board…", EncryptBoard)
+            .category("Board")
+            .menu("File", 2)
+            .enabled_when(|app| {
+                app.canvas
+                    .board
+                    .as_ref()
+                    .is_some_and(|board| !board.is_encrypted())
+            })
+            .keywords(&["passphrase", "password", "lock"]),
+    );
+    registry.register(
+        FnCommand::for_action(
+            "board:remove_encryption",
+            "Remove Board Encryption",
+            RemoveBoardEncryption,
+        )
+        .category("Board")
+        .menu("File", 2)
+        .enabled_when(|app| {
+            app.canvas
+                .board
+                .as_ref()
+                .is_some_and(|board| board.is_encrypted())
+        })
+        .keywords(&["decrypt", "unlock"]),
+    );
 
     // Edit
     registry.register(UndoCommand);
     registry.register(RedoCommand);
+    let has_selection = |app: &Humanboard| {
+        app.canvas.board.is_some() && !app.canvas.selected_items.is_empty()
+    };
+    registry.register(
+        FnCommand::new("edit:copy_as_png", "Copy as PNG", |app, _window, cx| {
+            app.copy_selection_as_png(false, cx)
+        })
+        .category("Edit")
+        .description("Copy the selected items as an image to paste into other apps")
+        .keybinding("cmd-shift-c")
+        .key_context(FocusContext::KEY_CANVAS)
+        .enabled_when(has_selection)
+        .keywords(&["image", "clipboard", "screenshot"]),
+    );
+    registry.register(
+        FnCommand::new(
+            "edit:copy_as_png_transparent",
+            "Copy as PNG with Transparent Background",
+            |app, _window, cx| app.copy_selection_as_png(true, cx),
+        )
+        .category("Edit")
+        .description("Copy the selected items as an image without the canvas behind them")
+        .enabled_when(has_selection)
+        .keywords(&["image", "clipboard", "alpha"]),
+    );
+    registry.register(
+        FnCommand::new(
+            "edit:set_default_style",
+            "Set as Default Style",
+            |app, _window, cx| app.set_default_style_from_selection(cx),
+        )
+        .category("Edit")
+        .description("New text boxes, shapes, arrows and tables take the style of the selected ones")
+        .enabled_when(has_selection)
+        .keywords(&["format", "font", "color", "fill", "border", "thickness", "preset"]),
+    );
+
+    // Arrange - offered once enough items are selected
+    let selected_at_least =
+        |count: usize| move |app: &Humanboard| app.canvas.selected_items.len() >= count;
+    for (id, edge) in [
+        ("arrange:align_left", AlignEdge::Left),
+        ("arrange:align_center", AlignEdge::Center),
+        ("arrange:align_right", AlignEdge::Right),
+        ("arrange:align_top", AlignEdge::Top),
+        ("arrange:align_middle", AlignEdge::Middle),
+        ("arrange:align_bottom", AlignEdge::Bottom),
+    ] {
+        registry.register(
+            FnCommand::new(id, edge.label(), move |app, _window, cx| {
+                app.align_selected(edge, cx)
+            })
+            .category("Arrange")
+            .enabled_when(selected_at_least(MIN_ALIGN_ITEMS))
+            .keywords(&["align", "line up"]),
+        );
+    }
+    for (id, axis) in [
+        ("arrange:distribute_horizontally", DistributeAxis::Horizontal),
+        ("arrange:distribute_vertically", DistributeAxis::Vertical),
+    ] {
+        registry.register(
+            FnCommand::new(id, axis.label(), move |app, _window, cx| {
+                app.distribute_selected(axis, cx)
+            })
+            .category("Arrange")
+            .enabled_when(selected_at_least(MIN_DISTRIBUTE_ITEMS))
+            .keywords(&["distribute", "space", "even"]),
+        );
+    }
 
     // View
     registry.register(ZoomInCommand);
     registry.register(ZoomOutCommand);
     registry.register(ZoomResetCommand);
+    registry.register(ZoomToFitCommand);
+    registry.register(ZoomToSelectionCommand);
+    registry.register(
+        FnCommand::for_action("view:outline", "Outline", ToggleOutline)
+            .category("View")
+            .keybinding("cmd-alt-o")
+            .key_context(FocusContext::KEY_CANVAS)
+            .menu("View", 1)
+            .enabled_when(has_board)
+            .keywords(&["contents", "panel", "list"]),
+    );
+    registry.register(
+        FnCommand::for_action("view:undo_history", "Undo History", ToggleUndoHistory)
+            .category("View")
+            .keybinding("cmd-alt-z")
+            .key_context(FocusContext::KEY_CANVAS)
+            .menu("View", 1)
+            .enabled_when(has_board)
+            .keywords(&["steps", "redo", "panel"]),
+    );
+    registry.register(
+        FnCommand::for_action("view:version_history", "Version History", ToggleVersionHistory)
+            .category("View")
+            .keybinding("cmd-alt-h")
+            .key_context(FocusContext::KEY_CANVAS)
+            .menu("View", 1)
+            .enabled_when(has_board)
+            .keywords(&["restore", "snapshots", "versions"]),
+    );
+    registry.register(
+        FnCommand::new(
+            "view:cycle_background",
+            "Change Canvas Background",
+            |app, _window, cx| app.run_background_command("", cx),
+        )
+        .category("View")
+        .description("Cycle through plain, dots and lines; type bg <style>, bg #hex or bg image for others")
+        .enabled_when(has_board)
+        .keywords(&["bg", "background", "dots", "lines", "plain", "grid"]),
+    );
 
-    // Application
-    registry.register(OpenSettingsCommand);
-    registry.register(ShowShortcutsCommand);
+    // Media
+    registry.register(
+        FnCommand::for_action("media:pause_all", "Pause All Media", PauseAllMedia)
+            .category("Media")
+            .keybinding("cmd-alt-p")
+            .key_context(FocusContext::KEY_CANVAS)
+            .enabled_when(has_board)
+            .keywords(&["pause", "mute", "audio", "video"]),
+    );
+    registry.register(
+        FnCommand::new("media:play_playlist", "Play Selection as Playlist", |app, _window, cx| {
+            app.start_playlist(false, cx)
+        })
+        .category("Media")
+        .description("Play the selected audio items in reading order")
+        .enabled_when(|app| app.selected_audio_count() >= MIN_PLAYLIST_ITEMS)
+        .keywords(&["playlist", "audio", "queue"]),
+    );
+    registry.register(
+        FnCommand::new(
+            "media:shuffle_playlist",
+            "Shuffle Selection as Playlist",
+            |app, _window, cx| app.start_playlist(true, cx),
+        )
+        .category("Media")
+        .description("Play the selected audio items shuffled")
+        .enabled_when(|app| app.selected_audio_count() >= MIN_PLAYLIST_ITEMS)
+        .keywords(&["shuffle", "playlist", "audio", "random"]),
+    );
+    registry.register(
+        FnCommand::new("media:stop_playlist", "Stop Playlist", |app, _window, cx| {
+            app.stop_playlist(cx)
+        })
+        .category("Media")
+        .enabled_when(|app| app.webviews.playlist.is_some())
+        .keywords(&["stop", "playlist"]),
+    );
+    registry.register(
+        FnCommand::new("media:toggle_subtitles", "Toggle Subtitles", |app, _window, cx| {
+            app.toggle_selected_subtitles(cx)
+        })
+        .category("Media")
+        .description("Turn the selected video's subtitles on or off")
+        .enabled_when(|app| app.selected_video_item().is_some())
+        .keywords(&["subtitles", "captions", "vtt", "srt"]),
+    );
+
+    // Code
+    registry.register(
+        FnCommand::new("code:go_to_symbol", "Go to Symbol…", |app, window, cx| {
+            app.show_command_palette_in(CmdPaletteMode::Symbols, window, cx)
+        })
+        .category("Code")
+        .description("Pick a symbol of the code tab being previewed")
+        .enabled_when(|app| app.has_active_code_tab())
+        .keywords(&["symbol", "function", "outline"]),
+    );
+    registry.register(
+        FnCommand::new("code:reset_lines", "Reset Code Card Lines", |app, _window, cx| {
+            app.run_lines_command("", cx)
+        })
+        .category("Code")
+        .description("Show the selected code card from the start of its file; type lines 10-40 to pin other lines")
+        .enabled_when(|app| app.selected_code_item().is_some())
+        .keywords(&["lines", "excerpt", "range"]),
+    );
+
+    // Debug
+    registry.register(
+        FnCommand::new(
+            "debug:export_trace",
+            "Export Performance Trace",
+            |app, _window, _cx| app.export_perf_trace(),
+        )
+        .category("Debug")
+        .description("Write recent frame and operation timings as a Chrome trace")
+        .keywords(&["trace", "perf", "profile"]),
+    );
+    registry.register(
+        FnCommand::new("debug:perf_hud", "Toggle Performance HUD", |app, _window, cx| {
+            app.ui.show_perf_hud = !app.ui.show_perf_hud;
+            cx.notify();
+        })
+        .category("Debug")
+        .keywords(&["perf", "hud", "fps", "frame"]),
+    );
 
     registry
 }
@@ -35,12 +35,23 @@ pub const MIN_FONT_SIZE: f32 = 8.0;
 /// Maximum font size for text boxes
 pub const MAX_FONT_SIZE: f32 = 200.0;
 
+/// Font size of code tabs at 100% zoom
+pub const CODE_TAB_FONT_SIZE: f32 = 14.0;
+
 /// Maximum dimension for images (scaled down if larger)
 pub const MAX_IMAGE_DIMENSION: f32 = 800.0;
 
 /// Default image fallback size
 pub const DEFAULT_IMAGE_SIZE: (f32, f32) = (800.0, 600.0);
 
+/// Markdown cards resized at least this tall show the rendered document
+/// rather than a title chip
+pub const MARKDOWN_EXPAND_HEIGHT: f32 = 96.0;
+
+/// Code cards resized at least this tall show an excerpt of their file
+/// rather than a file name chip
+pub const CODE_EXPAND_HEIGHT: f32 = 96.0;
+
 /// Default text box minimum size
 pub const MIN_TEXTBOX_SIZE: (f32, f32) = (100.0, 40.0);
 
@@ -60,6 +71,19 @@ pub const MAX_HISTORY_STATES: usize = 50;
 /// Pan animation duration in seconds
 pub const PAN_ANIMATION_DURATION: f64 = 0.3;
 
+/// Arrow-key nudges closer together than this are one undo step (covers key repeat)
+pub const NUDGE_BURST_MS: u64 = 500;
+
+// ============================================================================
+// Nudging
+// ============================================================================
+
+/// Distance an arrow key moves the selection, in canvas pixels
+pub const NUDGE_STEP: f32 = 1.0;
+
+/// Distance Shift+arrow moves the selection, in canvas pixels
+pub const NUDGE_STEP_LARGE: f32 = 10.0;
+
 // ============================================================================
 // Zoom & Pan
 // ============================================================================
@@ -76,6 +100,15 @@ pub const DEFAULT_ZOOM: f32 = 1.0;
 /// Zoom step for scroll wheel
 pub const ZOOM_STEP: f32 = 0.1;
 
+/// Margin in pixels kept around items when zooming to fit
+pub const ZOOM_TO_FIT_PADDING: f32 = 48.0;
+
+/// Highest zoom that zoom to fit/selection will use (keeps small items from filling the screen)
+pub const ZOOM_TO_FIT_MAX: f32 = 2.0;
+
+/// Zoom to fit/selection animation duration in milliseconds
+pub const ZOOM_TO_FIT_ANIMATION_MS: u64 = 300;
+
 // ============================================================================
 // Preview Panel
 // ============================================================================
@@ -148,6 +181,18 @@ pub const WEBVIEW_PRELOAD_DISTANCE: f32 = 500.0;
 /// 5 minutes - preserves playback state for reasonable pan-away durations
 pub const WEBVIEW_UNLOAD_DELAY_MS: u64 = 300_000;
 
+/// How often (in milliseconds) media players are asked for their position
+/// to move the waveform playhead and remember where they were left
+pub const PLAYHEAD_POLL_INTERVAL_MS: u64 = 200;
+
+/// How far (in seconds) a playing item moves before its position is saved
+/// on the board again
+pub const PLAYBACK_SAVE_INTERVAL_SECS: f64 = 10.0;
+
+/// How often (in milliseconds) a playlist item that hasn't started yet is
+/// asked to play again, while its player loads
+pub const PLAYLIST_PLAY_RETRY_MS: u64 = 1000;
+
 // ============================================================================
 // UI Spacing Constants (for visual consistency)
 // ============================================================================
@@ -246,3 +291,33 @@ pub const MAX_CSV_ROWS: usize = 100_000;
 
 /// Maximum CSV file size in MB
 pub const MAX_CSV_SIZE_MB: usize = 100;
+
+// ============================================================================
+// Version History
+// ============================================================================
+
+/// Minimum time between automatic board version snapshots (seconds)
+pub const VERSION_SNAPSHOT_INTERVAL_SECS: u64 = 300;
+
+/// Number of history operations that triggers a version snapshot on next save
+pub const VERSION_SNAPSHOT_OPERATIONS: usize = 50;
+
+/// Maximum number of version snapshots kept per board
+pub const MAX_BOARD_VERSIONS: usize = 50;
+
+/// Largest code or markdown file whose text is kept with a version snapshot
+pub const MAX_SNAPSHOT_FILE_BYTES: u64 = 1024 * 1024;
+
+// ============================================================================
+// Board Thumbnails
+// ============================================================================
+
+/// Minimum time between thumbnail refreshes while editing a board (seconds)
+pub const BOARD_THUMBNAIL_INTERVAL_SECS: u64 = 30;
+
+// ============================================================================
+// Missing Files
+// ============================================================================
+
+/// Minimum time between checks for items whose file has gone missing (seconds)
+pub const MISSING_FILE_CHECK_INTERVAL_SECS: u64 = 5;
@@ -0,0 +1,196 @@
+//! Full-text search of the current board's cards.
+//!
+//! Search finds items by name; this module finds them by what they hold:
+//! the text of text boxes and markdown cards, the contents of code files
+//! and the cell values of tables. Each match comes with where it is (a line
+//! or a cell) and a snippet of the text around it for the result list.
+//! Choosing a result highlights its card on the canvas for a moment, fading
+//! out as [`search_highlight_opacity`] describes.
+//!
+//! Code files are read once and kept until they change on disk, so typing
+//! a query doesn't read every file again at each keystroke.
+
+use crate::animations::ease_in_out_cubic;
+use crate::image_cache::FileStamp;
+use crate::pdf::snippet;
+use crate::types::{CanvasItem, DataSource, ItemContent};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Shortest query searched inside cards; shorter ones only match names
+pub const MIN_CONTENT_QUERY_CHARS: usize = 2;
+
+/// Code files larger than this aren't searched
+pub const MAX_SEARCHED_FILE_BYTES: u64 = 1024 * 1024;
+
+/// How long a card chosen from the search results stays highlighted
+pub const SEARCH_HIGHLIGHT_DURATION: Duration = Duration::from_millis(2000);
+
+/// Opacity of a chosen card's highlight, `elapsed` after it was chosen, or
+/// `None` once it is over. It holds for the first half, then fades.
+pub fn search_highlight_opacity(elapsed: Duration) -> Option<f32> {
+    if elapsed >= SEARCH_HIGHLIGHT_DURATION {
+        return None;
+    }
+    let t = elapsed.as_secs_f32() / SEARCH_HIGHLIGHT_DURATION.as_secs_f32();
+    let fade = ((t - 0.5) * 2.0).max(0.0);
+    Some(1.0 - ease_in_out_cubic(fade))
+}
+
+/// Where in a card a query was found
+#[derive(Debug, Clone, PartialEq)]
+pub enum MatchLocation {
+    /// Line of a text, counted from 1
+    Line(usize),
+    /// Cell of a table: its row, counted from 1, and its column's name
+    Cell { row: usize, column: String },
+}
+
+impl MatchLocation {
+    pub fn label(&self) -> String {
+        match self {
+            MatchLocation::Line(line) => format!("line {}", line),
+            MatchLocation::Cell { row, column } if column.is_empty() => format!("row {}", row),
+            MatchLocation::Cell { row, column } => format!("row {}, {}", row, column),
+        }
+    }
+}
+
+/// The first match of a query inside an item
+#[derive(Debug, Clone, PartialEq)]
+pub struct ContentMatch {
+    pub item_id: u64,
+    pub location: MatchLocation,
+    /// The matching text with some context around it, on one line
+    pub snippet: String,
+}
+
+impl ContentMatch {
+    /// Label of the match in the result list, e.g.
+    /// `main.rs — line 12: …let total = sum(values)…`
+    pub fn label(&self, item_name: &str) -> String {
+        format!(
+            "{} — {}: {}",
+            item_name,
+            self.location.label(),
+            self.snippet
+        )
+    }
+}
+
+/// The first case-insensitive match of `query` in `text`: its line,
+/// counted from 1, and a snippet of the line around it
+pub fn find_in_text(text: &str, query: &str) -> Option<(usize, String)> {
+    let query = query.trim().to_lowercase();
+    if query.is_empty() {
+        return None;
+    }
+    text.lines().enumerate().find_map(|(index, line)| {
+        let offset = line.to_lowercase().find(&query)?;
+        Some((index + 1, snippet(line, offset, query.chars().count())))
+    })
+}
+
+/// The first cell of `source` whose value contains `query`, ignoring case
+pub fn find_in_table(source: &DataSource, query: &str) -> Option<(MatchLocation, String)> {
+    let query = query.trim().to_lowercase();
+    if query.is_empty() {
+        return None;
+    }
+    for (row_index, row) in source.rows.iter().enumerate() {
+        for (column_index, cell) in row.cells.iter().enumerate() {
+            let value = cell.to_string();
+            let Some(offset) = value.to_lowercase().find(&query) else {
+                continue;
+            };
+            let column = source
+                .columns
+                .get(column_index)
+                .map(|column| column.name.clone())
+                .unwrap_or_default();
+            return Some((
+                MatchLocation::Cell {
+                    row: row_index + 1,
+                    column,
+                },
+                snippet(&value, offset, query.chars().count()),
+            ));
+        }
+    }
+    None
+}
+
+/// Text of the code files searched, as last read
+#[derive(Default)]
+pub struct FileTextCache {
+    entries: HashMap<PathBuf, (Option<FileStamp>, Arc<String>)>,
+}
+
+impl FileTextCache {
+    /// Text of the file at `path`, read again if it changed on disk since
+    /// it was last read. Unreadable and very large files have no text.
+    pub fn text(&mut self, path: &Path) -> Arc<String> {
+        let stamp = FileStamp::of(path);
+        if let Some((cached_stamp, text)) = self.entries.get(path) {
+            if *cached_stamp == stamp {
+                return text.clone();
+            }
+        }
+        let text = match stamp {
+            Some(ref stamp) if stamp.len <= MAX_SEARCHED_FILE_BYTES => {
+                fs::read_to_string(path).unwrap_or_default()
+            }
+            _ => String::new(),
+        };
+        let text = Arc::new(text);
+        self.entries
+            .insert(path.to_path_buf(), (stamp, text.clone()));
+        text
+    }
+
+    /// Forget every file, e.g. when switching boards
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+}
+
+/// The first match of `query` inside each of `items` that has one, in
+/// board order. `files` keeps the text of the code files read.
+pub fn search_content(
+    items: &[CanvasItem],
+    data_sources: &HashMap<u64, DataSource>,
+    query: &str,
+    files: &mut FileTextCache,
+) -> Vec<ContentMatch> {
+    if query.trim().chars().count() < MIN_CONTENT_QUERY_CHARS {
+        return Vec::new();
+    }
+
+    items
+        .iter()
+        .filter_map(|item| {
+            let (location, snippet) = match &item.content {
+                ItemContent::TextBox { text, .. } | ItemContent::Markdown { content: text, .. } => {
+                    let (line, snippet) = find_in_text(text, query)?;
+                    (MatchLocation::Line(line), snippet)
+                }
+                ItemContent::Code { path, .. } => {
+                    let (line, snippet) = find_in_text(&files.text(path), query)?;
+                    (MatchLocation::Line(line), snippet)
+                }
+                ItemContent::Table { data_source_id, .. } => {
+                    find_in_table(data_sources.get(data_source_id)?, query)?
+                }
+                _ => return None,
+            };
+            Some(ContentMatch {
+                item_id: item.id,
+                location,
+                snippet,
+            })
+        })
+        .collect()
+}
@@ -0,0 +1,256 @@
+//! Right-click context menus for canvas items and the empty canvas.
+//!
+//! Right-clicking an item (selecting it first if it isn't already) opens a
+//! menu of actions on the selection. Right-clicking empty canvas opens a menu
+//! for pasting and creating items at the clicked spot. Right-clicking a
+//! preview tab opens a menu for pinning and closing tabs.
+//!
+//! This module only describes menus: which entries they hold and where they
+//! open. `Humanboard` opens them and runs the chosen command, and
+//! `render::overlays::context_menu` draws them.
+
+use crate::arrange::ZOrder;
+use gpui::{Pixels, Point};
+
+/// Height of a command row in the menu
+pub const MENU_ENTRY_HEIGHT: f32 = 28.0;
+
+/// Height of a separator row in the menu
+pub const MENU_SEPARATOR_HEIGHT: f32 = 9.0;
+
+/// Width of the menu
+pub const MENU_WIDTH: f32 = 220.0;
+
+/// Vertical padding inside the menu, top and bottom
+pub const MENU_PADDING: f32 = 4.0;
+
+/// A command offered by a context menu
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ContextMenuCommand {
+    // Item menu
+    Copy,
+    CopyAsPng,
+    CopyAsPngTransparent,
+    Duplicate,
+    Delete,
+    Lock,
+    Unlock,
+    Restack(ZOrder),
+    OpenInPreview,
+    OpenHighlight,
+    CompareWithSnapshot,
+    CompareFiles,
+    RegenerateThumbnail,
+    CopyLinkAtTime,
+    SetDefaultStyle,
+    // Tab menu
+    PinTab,
+    UnpinTab,
+    CloseTab,
+    CloseOtherTabs,
+    CloseAllTabs,
+    // Canvas menu
+    Paste,
+    NewTextBox,
+    NewShape,
+    NewMarkdownNote,
+    ZoomToFit,
+}
+
+impl ContextMenuCommand {
+    pub fn label(&self) -> &'static str {
+        match self {
+            ContextMenuCommand::Copy => "Copy",
+            ContextMenuCommand::CopyAsPng => "Copy as PNG",
+            ContextMenuCommand::CopyAsPngTransparent => "Copy as PNG (Transparent)",
+            ContextMenuCommand::Duplicate => "Duplicate",
+            ContextMenuCommand::Delete => "Delete",
+            ContextMenuCommand::Lock => "Lock",
+            ContextMenuCommand::Unlock => "Unlock",
+            ContextMenuCommand::Restack(z_order) => z_order.label(),
+            ContextMenuCommand::OpenInPreview => "Open in Preview",
+            ContextMenuCommand::OpenHighlight => "Open Highlighted Page",
+            ContextMenuCommand::CompareWithSnapshot => "Compare with Last Snapshot",
+            ContextMenuCommand::CompareFiles => "Compare Files",
+            ContextMenuCommand::RegenerateThumbnail => "Regenerate Thumbnail",
+            ContextMenuCommand::CopyLinkAtTime => "Copy Link at Current Time",
+            ContextMenuCommand::SetDefaultStyle => "Set as Default Style",
+            ContextMenuCommand::PinTab => "Pin Tab",
+            ContextMenuCommand::UnpinTab => "Unpin Tab",
+            ContextMenuCommand::CloseTab => "Close Tab",
+            ContextMenuCommand::CloseOtherTabs => "Close Other Tabs",
+            ContextMenuCommand::CloseAllTabs => "Close All Tabs",
+            ContextMenuCommand::Paste => "Paste",
+            ContextMenuCommand::NewTextBox => "New Text Box",
+            ContextMenuCommand::NewShape => "New Shape",
+            ContextMenuCommand::NewMarkdownNote => "New Markdown Note",
+            ContextMenuCommand::ZoomToFit => "Zoom to Fit",
+        }
+    }
+
+    /// Keyboard shortcut shown next to the label
+    pub fn shortcut(&self) -> Option<&'static str> {
+        match self {
+            ContextMenuCommand::Copy => Some("Cmd+C"),
+            ContextMenuCommand::CopyAsPng => Some("Cmd+Shift+C"),
+            ContextMenuCommand::Duplicate => Some("Cmd+D"),
+            ContextMenuCommand::Delete => Some("Del"),
+            ContextMenuCommand::CloseTab => Some("Cmd+W"),
+            ContextMenuCommand::Paste => Some("Cmd+V"),
+            ContextMenuCommand::ZoomToFit => Some("Cmd+1"),
+            _ => None,
+        }
+    }
+}
+
+/// A row in a context menu
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ContextMenuEntry {
+    Command(ContextMenuCommand),
+    Separator,
+}
+
+impl ContextMenuEntry {
+    fn height(&self) -> f32 {
+        match self {
+            ContextMenuEntry::Command(_) => MENU_ENTRY_HEIGHT,
+            ContextMenuEntry::Separator => MENU_SEPARATOR_HEIGHT,
+        }
+    }
+}
+
+/// An open context menu
+#[derive(Debug, Clone)]
+pub struct ContextMenu {
+    /// Top-left corner of the menu in window coordinates
+    pub origin: Point<Pixels>,
+    /// Canvas position that was clicked (where new items are placed)
+    pub canvas_position: Point<Pixels>,
+    pub entries: Vec<ContextMenuEntry>,
+    /// For a tab menu, the tab's index and its pane
+    pub tab: Option<(usize, usize)>,
+}
+
+/// Entries for a right-click on the selection.
+///
+/// `all_locked` swaps Lock for Unlock and drops Delete; `can_preview` adds
+/// Open in Preview (a single file or table item); `is_highlight_note` adds
+/// Open Highlighted Page (a single note of a PDF highlight); `is_pdf` adds
+/// Regenerate Thumbnail (a single PDF item); `can_link_at_time` adds Copy
+/// Link at Current Time (a single YouTube item). `text_files` is how many
+/// code or markdown cards make up the selection: one adds Compare with
+/// Last Snapshot, two add Compare Files. `has_style` adds Set as Default
+/// Style (a text box, shape, arrow or table is selected).
+pub fn item_menu_entries(
+    all_locked: bool,
+    can_preview: bool,
+    is_highlight_note: bool,
+    is_pdf: bool,
+    can_link_at_time: bool,
+    text_files: usize,
+    has_style: bool,
+) -> Vec<ContextMenuEntry> {
+    use ContextMenuCommand::*;
+    use ContextMenuEntry::{Command, Separator};
+
+    let mut entries = Vec::new();
+    if can_preview {
+        entries.push(Command(OpenInPreview));
+    }
+    match text_files {
+        1 => entries.push(Command(CompareWithSnapshot)),
+        2 => entries.push(Command(CompareFiles)),
+        _ => {}
+    }
+    if is_pdf {
+        entries.push(Command(RegenerateThumbnail));
+    }
+    if can_preview || is_pdf || text_files == 2 {
+        entries.push(Separator);
+    }
+    if is_highlight_note {
+        entries.extend([Command(OpenHighlight), Separator]);
+    }
+    entries.push(Command(Copy));
+    if can_link_at_time {
+        entries.push(Command(CopyLinkAtTime));
+    }
+    entries.push(Command(Duplicate));
+    if !all_locked {
+        entries.push(Command(Delete));
+    }
+    entries.extend([
+        Separator,
+        Command(CopyAsPng),
+        Command(CopyAsPngTransparent),
+    ]);
+    if has_style {
+        entries.extend([Separator, Command(SetDefaultStyle)]);
+    }
+    entries.extend([
+        Separator,
+        Command(if all_locked { Unlock } else { Lock }),
+        Separator,
+        Command(Restack(ZOrder::BringToFront)),
+        Command(Restack(ZOrder::BringForward)),
+        Command(Restack(ZOrder::SendBackward)),
+        Command(Restack(ZOrder::SendToBack)),
+    ]);
+    entries
+}
+
+/// Entries for a right-click on empty canvas
+pub fn canvas_menu_entries() -> Vec<ContextMenuEntry> {
+    use ContextMenuCommand::*;
+    use ContextMenuEntry::{Command, Separator};
+
+    vec![
+        Command(Paste),
+        Separator,
+        Command(NewTextBox),
+        Command(NewShape),
+        Command(NewMarkdownNote),
+        Separator,
+        Command(ZoomToFit),
+    ]
+}
+
+/// Entries for a right-click on a preview tab. Pinned tabs can't be closed,
+/// and "Close Others" and "Close All" leave them open.
+pub fn tab_menu_entries(is_pinned: bool) -> Vec<ContextMenuEntry> {
+    use ContextMenuCommand::*;
+    use ContextMenuEntry::{Command, Separator};
+
+    let mut entries = vec![Command(if is_pinned { UnpinTab } else { PinTab }), Separator];
+    if !is_pinned {
+        entries.push(Command(CloseTab));
+    }
+    entries.extend([Command(CloseOtherTabs), Command(CloseAllTabs)]);
+    entries
+}
+
+/// Total height of a menu with `entries`
+pub fn menu_height(entries: &[ContextMenuEntry]) -> f32 {
+    entries.iter().map(ContextMenuEntry::height).sum::<f32>() + MENU_PADDING * 2.0
+}
+
+/// Where to open a menu of `size` clicked at `click`, flipped left/up as
+/// needed so it stays inside `bounds` (min x, min y, max x, max y).
+pub fn menu_origin(
+    click: (f32, f32),
+    size: (f32, f32),
+    bounds: (f32, f32, f32, f32),
+) -> (f32, f32) {
+    let (min_x, min_y, max_x, max_y) = bounds;
+    let x = if click.0 + size.0 > max_x {
+        click.0 - size.0
+    } else {
+        click.0
+    };
+    let y = if click.1 + size.1 > max_y {
+        click.1 - size.1
+    } else {
+        click.1
+    };
+    (x.max(min_x), y.max(min_y))
+}
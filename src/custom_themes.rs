@@ -0,0 +1,421 @@
+//! Custom themes - color themes made with the theme editor in settings.
+//!
+//! The editor starts from the current theme and changes its background,
+//! foreground and accent colors, and the colors content types are marked
+//! with on the canvas. Themes are saved as ordinary theme files in the
+//! `themes` folder next to `settings.json`, so they can be shared and
+//! edited by hand like the built-in ones:
+//!
+//! ```json
+//! {
+//!     "name": "Midnight",
+//!     "themes": [{
+//!         "name": "Midnight",
+//!         "mode": "dark",
+//!         "colors": {
+//!             "background": "#101820",
+//!             "primary.background": "#f2aa4c",
+//!             "content.video": "#b48ead"
+//!         }
+//!     }]
+//! }
+//! ```
+//!
+//! Any theme file may set `content.*` colors, built-in ones included.
+//! Content types a theme leaves out are drawn in its base colors.
+
+use crate::error::SettingsError;
+use gpui::{Hsla, SharedString};
+use gpui_component::theme::{Colorize, ThemeConfig, ThemeSet};
+use once_cell::sync::Lazy;
+use parking_lot::RwLock;
+use serde_json::Value;
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::{Path, PathBuf};
+use tracing::{debug, warn};
+
+/// Get the folder custom themes are saved in.
+pub fn custom_themes_dir() -> Option<PathBuf> {
+    dirs::config_dir().map(|p| p.join("humanboard").join("themes"))
+}
+
+/// A content type with its own color on the canvas
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum ContentColor {
+    Video,
+    Audio,
+    Text,
+    Pdf,
+    Link,
+    YouTube,
+    Vimeo,
+    SoundCloud,
+    Spotify,
+}
+
+impl ContentColor {
+    pub const ALL: [ContentColor; 9] = [
+        ContentColor::Video,
+        ContentColor::Audio,
+        ContentColor::Text,
+        ContentColor::Pdf,
+        ContentColor::Link,
+        ContentColor::YouTube,
+        ContentColor::Vimeo,
+        ContentColor::SoundCloud,
+        ContentColor::Spotify,
+    ];
+
+    /// Key of the color in a theme file
+    pub fn key(self) -> &'static str {
+        match self {
+            ContentColor::Video => "content.video",
+            ContentColor::Audio => "content.audio",
+            ContentColor::Text => "content.text",
+            ContentColor::Pdf => "content.pdf",
+            ContentColor::Link => "content.link",
+            ContentColor::YouTube => "content.youtube",
+            ContentColor::Vimeo => "content.vimeo",
+            ContentColor::SoundCloud => "content.soundcloud",
+            ContentColor::Spotify => "content.spotify",
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            ContentColor::Video => "Video",
+            ContentColor::Audio => "Audio",
+            ContentColor::Text => "Text",
+            ContentColor::Pdf => "PDF",
+            ContentColor::Link => "Links",
+            ContentColor::YouTube => "YouTube",
+            ContentColor::Vimeo => "Vimeo",
+            ContentColor::SoundCloud => "SoundCloud",
+            ContentColor::Spotify => "Spotify",
+        }
+    }
+
+    pub fn from_key(key: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|color| color.key() == key)
+    }
+}
+
+/// The content colors a theme sets
+pub type ContentPalette = HashMap<ContentColor, Hsla>;
+
+/// A color the theme editor changes
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum EditableColor {
+    Background,
+    Foreground,
+    Accent,
+    Content(ContentColor),
+}
+
+impl EditableColor {
+    /// Every editable color, in the order the editor lists them
+    pub fn all() -> Vec<EditableColor> {
+        let mut colors = vec![
+            EditableColor::Background,
+            EditableColor::Foreground,
+            EditableColor::Accent,
+        ];
+        colors.extend(ContentColor::ALL.into_iter().map(EditableColor::Content));
+        colors
+    }
+
+    /// Key of the color in a theme file
+    pub fn key(self) -> &'static str {
+        match self {
+            EditableColor::Background => "background",
+            EditableColor::Foreground => "foreground",
+            EditableColor::Accent => "primary.background",
+            EditableColor::Content(content) => content.key(),
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            EditableColor::Background => "Background",
+            EditableColor::Foreground => "Foreground",
+            EditableColor::Accent => "Accent",
+            EditableColor::Content(content) => content.label(),
+        }
+    }
+}
+
+/// Parse a `#rrggbb` or `#rrggbbaa` color
+pub fn parse_color(hex: &str) -> Option<Hsla> {
+    let hex = hex.trim();
+    if !hex.is_ascii() {
+        return None;
+    }
+    Hsla::parse_hex(hex).ok()
+}
+
+/// A theme being made in the theme editor
+#[derive(Debug, Clone)]
+pub struct ThemeDraft {
+    pub name: String,
+    /// Theme the draft started from; the colors the editor doesn't change
+    /// are kept from it
+    pub base: ThemeConfig,
+    /// Colors picked so far, as hex
+    pub colors: BTreeMap<EditableColor, String>,
+}
+
+impl ThemeDraft {
+    /// Start a theme from `base`, with the colors it is shown in now
+    pub fn new(base: ThemeConfig, colors: BTreeMap<EditableColor, String>) -> Self {
+        let name = if base.name.ends_with(" Custom") {
+            base.name.to_string()
+        } else {
+            format!("{} Custom", base.name)
+        };
+        Self { name, base, colors }
+    }
+
+    /// Set `color` to `hex`. Returns false, leaving the color as it was,
+    /// if `hex` isn't a color.
+    pub fn set_color(&mut self, color: EditableColor, hex: &str) -> bool {
+        if parse_color(hex).is_none() {
+            return false;
+        }
+        self.colors.insert(color, hex.trim().to_lowercase());
+        true
+    }
+
+    /// The name the theme is saved under
+    pub fn theme_name(&self) -> String {
+        match self.name.trim() {
+            "" => "Custom Theme".to_string(),
+            name => name.to_string(),
+        }
+    }
+
+    /// The theme as applied to the interface
+    pub fn config(&self) -> ThemeConfig {
+        let mut config = self.base.clone();
+        config.name = SharedString::from(self.theme_name());
+        config.is_default = false;
+        let color = |color: EditableColor| self.colors.get(&color).cloned().map(SharedString::from);
+        if let Some(background) = color(EditableColor::Background) {
+            config.colors.background = Some(background);
+        }
+        if let Some(foreground) = color(EditableColor::Foreground) {
+            config.colors.foreground = Some(foreground);
+        }
+        if let Some(accent) = color(EditableColor::Accent) {
+            config.colors.primary = Some(accent);
+        }
+        config
+    }
+
+    /// The content colors picked
+    pub fn content_palette(&self) -> ContentPalette {
+        self.colors
+            .iter()
+            .filter_map(|(color, hex)| match color {
+                EditableColor::Content(content) => Some((*content, parse_color(hex)?)),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// The theme file the draft is saved as
+    pub fn to_json(&self) -> Result<Value, serde_json::Error> {
+        let name = self.theme_name();
+        let mut theme = serde_json::to_value(self.config())?;
+        strip_nulls(&mut theme);
+        if let Some(colors) = theme.get_mut("colors").and_then(Value::as_object_mut) {
+            for (color, hex) in &self.colors {
+                if let EditableColor::Content(content) = color {
+                    colors.insert(content.key().to_string(), Value::String(hex.clone()));
+                }
+            }
+        }
+        Ok(serde_json::json!({
+            "name": name,
+            "themes": [theme],
+        }))
+    }
+}
+
+/// Drop the unset fields of a serialized theme, so the file only has the
+/// colors the theme sets
+fn strip_nulls(value: &mut Value) {
+    match value {
+        Value::Object(map) => {
+            map.retain(|_, value| !value.is_null());
+            map.values_mut().for_each(strip_nulls);
+        }
+        Value::Array(values) => values.iter_mut().for_each(strip_nulls),
+        _ => {}
+    }
+}
+
+/// Parse a theme file: each of its themes with the content colors it sets
+pub fn parse_theme_file(
+    json: &str,
+) -> Result<Vec<(ThemeConfig, ContentPalette)>, serde_json::Error> {
+    let value: Value = serde_json::from_str(json)?;
+    let set: ThemeSet = serde_json::from_value(value.clone())?;
+    let palettes = value
+        .get("themes")
+        .and_then(Value::as_array)
+        .map(|themes| themes.iter().map(theme_palette).collect())
+        .unwrap_or_else(Vec::new);
+    Ok(set
+        .themes
+        .into_iter()
+        .zip(
+            palettes
+                .into_iter()
+                .chain(std::iter::repeat_with(ContentPalette::new)),
+        )
+        .collect())
+}
+
+/// The `content.*` colors of one theme in a theme file
+fn theme_palette(theme: &Value) -> ContentPalette {
+    let Some(colors) = theme.get("colors").and_then(Value::as_object) else {
+        return ContentPalette::new();
+    };
+    colors
+        .iter()
+        .filter_map(|(key, value)| {
+            let content = ContentColor::from_key(key)?;
+            Some((content, parse_color(value.as_str()?)?))
+        })
+        .collect()
+}
+
+/// File name for a theme called `name`: `Ocean Breeze` is saved as
+/// `ocean-breeze.json`
+pub fn theme_file_name(name: &str) -> String {
+    let mut slug = String::new();
+    for c in name.trim().chars() {
+        if c.is_alphanumeric() {
+            slug.extend(c.to_lowercase());
+        } else if !slug.is_empty() && !slug.ends_with('-') {
+            slug.push('-');
+        }
+    }
+    let slug = slug.trim_end_matches('-');
+    if slug.is_empty() {
+        "custom-theme.json".to_string()
+    } else {
+        format!("{}.json", slug)
+    }
+}
+
+/// Write `draft` as a theme file in `dir`, replacing a theme saved under
+/// the same name. Returns the file written.
+pub fn save_theme(dir: &Path, draft: &ThemeDraft) -> Result<PathBuf, SettingsError> {
+    let path = dir.join(theme_file_name(&draft.theme_name()));
+    let json = draft
+        .to_json()
+        .and_then(|json| serde_json::to_string_pretty(&json))
+        .map_err(|e| SettingsError::InvalidValue(e.to_string()))?;
+    fs::create_dir_all(dir).map_err(|source| SettingsError::SaveFailed {
+        path: dir.to_path_buf(),
+        source,
+    })?;
+    fs::write(&path, json).map_err(|source| SettingsError::SaveFailed {
+        path: path.clone(),
+        source,
+    })?;
+    debug!("Saved theme {:?} to {:?}", draft.theme_name(), path);
+    Ok(path)
+}
+
+/// Custom themes, and the content colors of every theme file read
+#[derive(Default)]
+pub struct ThemeLibrary {
+    /// Custom themes by name
+    pub themes: BTreeMap<String, ThemeConfig>,
+    /// Content colors by theme name
+    pub palettes: HashMap<String, ContentPalette>,
+}
+
+impl ThemeLibrary {
+    /// Read the theme files in `dir`. Custom themes are kept whole; of
+    /// the others only the content colors are needed, as the theme
+    /// registry has the rest.
+    pub fn load_dir(&mut self, dir: &Path, custom: bool) {
+        let Ok(entries) = fs::read_dir(dir) else {
+            return;
+        };
+        for path in entries.flatten().map(|entry| entry.path()) {
+            if path.extension().and_then(|ext| ext.to_str()) != Some("json") {
+                continue;
+            }
+            let themes = match fs::read_to_string(&path)
+                .map_err(|e| e.to_string())
+                .and_then(|json| parse_theme_file(&json).map_err(|e| e.to_string()))
+            {
+                Ok(themes) => themes,
+                Err(e) => {
+                    warn!("Skipping theme file {:?}: {}", path, e);
+                    continue;
+                }
+            };
+            for (config, palette) in themes {
+                let name = config.name.to_string();
+                if !palette.is_empty() {
+                    self.palettes.insert(name.clone(), palette);
+                }
+                if custom {
+                    self.themes.insert(name, config);
+                }
+            }
+        }
+    }
+}
+
+/// The theme library shared by every window
+pub static THEME_LIBRARY: Lazy<RwLock<ThemeLibrary>> =
+    Lazy::new(|| RwLock::new(ThemeLibrary::default()));
+
+/// Read the custom themes, and the content colors of the built-in themes
+/// in `builtin_dir`, again
+pub fn reload_theme_library(builtin_dir: Option<&Path>) {
+    let mut library = ThemeLibrary::default();
+    if let Some(dir) = builtin_dir {
+        library.load_dir(dir, false);
+    }
+    if let Some(dir) = custom_themes_dir() {
+        library.load_dir(&dir, true);
+    }
+    *THEME_LIBRARY.write() = library;
+}
+
+/// The custom theme called `name`
+pub fn custom_theme(name: &str) -> Option<ThemeConfig> {
+    THEME_LIBRARY.read().themes.get(name).cloned()
+}
+
+/// Names of the custom themes, sorted
+pub fn custom_theme_names() -> Vec<String> {
+    THEME_LIBRARY.read().themes.keys().cloned().collect()
+}
+
+/// The content colors the theme called `name` sets
+pub fn content_palette(name: &str) -> ContentPalette {
+    THEME_LIBRARY
+        .read()
+        .palettes
+        .get(name)
+        .cloned()
+        .unwrap_or_default()
+}
+
+/// Use `palette` as the content colors of the theme called `name`, as the
+/// theme editor does while a theme is edited
+pub fn set_content_palette(name: &str, palette: ContentPalette) {
+    THEME_LIBRARY
+        .write()
+        .palettes
+        .insert(name.to_string(), palette);
+}
@@ -156,7 +156,7 @@ fn detect_delimiter(path: &PathBuf, content: &str) -> char {
 }
 
 /// Split a CSV line respecting quoted fields
-fn split_csv_line(line: &str, delimiter: char) -> Vec<&str> {
+pub(crate) fn split_csv_line(line: &str, delimiter: char) -> Vec<&str> {
     let mut result = Vec::new();
     let mut start = 0;
     let mut in_quotes = false;
@@ -0,0 +1,245 @@
+//! Rows shown by data tabs in the preview panel.
+//!
+//! Data tabs show a board table or a CSV file a page at a time. Tables are
+//! already in memory; CSV files are loaded into a [`LazyDataSource`] on the
+//! background executor and kept in [`DATA_FILES`], so big files are only
+//! turned into rows a page at a time. Sorting and filtering go through every
+//! row, so the resulting order is worked out in the background as well and
+//! kept in the tab's [`RowOrder`].
+
+use crate::background::BackgroundExecutor;
+use crate::data::lazy_source::LazyDataSource;
+use crate::data_table::{SortDirection, row_order};
+use crate::types::{DataColumn, DataRow, DataSource};
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tracing::warn;
+
+/// Whether a file opens in a data tab rather than as text
+pub fn is_delimited_file(path: &Path) -> bool {
+    path.extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(|ext| matches!(ext.to_lowercase().as_str(), "csv" | "tsv"))
+}
+
+/// The rows behind a data tab
+#[derive(Clone)]
+pub enum DataRows {
+    /// A board table
+    Memory(Arc<DataSource>),
+    /// A CSV file, turned into rows as they are needed
+    Lazy(Arc<LazyDataSource>),
+}
+
+impl DataRows {
+    pub fn columns(&self) -> &[DataColumn] {
+        match self {
+            DataRows::Memory(source) => &source.columns,
+            DataRows::Lazy(source) => &source.columns,
+        }
+    }
+
+    pub fn row_count(&self) -> usize {
+        match self {
+            DataRows::Memory(source) => source.rows.len(),
+            DataRows::Lazy(source) => source.row_count,
+        }
+    }
+
+    /// Up to `count` rows from `start`
+    pub fn rows(&self, start: usize, count: usize) -> Vec<DataRow> {
+        match self {
+            DataRows::Memory(source) => source
+                .rows
+                .iter()
+                .skip(start)
+                .take(count)
+                .cloned()
+                .collect(),
+            DataRows::Lazy(source) => source.get_rows(start, count),
+        }
+    }
+
+    /// The rows at `indices`, in that order
+    pub fn rows_at(&self, indices: &[usize]) -> Vec<DataRow> {
+        indices
+            .iter()
+            .filter_map(|&index| self.rows(index, 1).pop())
+            .collect()
+    }
+
+    /// Indices of the rows matching `filter`, sorted by `sort`
+    pub fn order(&self, filter: &str, sort: Option<(usize, SortDirection)>) -> Vec<usize> {
+        match self {
+            DataRows::Memory(source) => row_order(&source.rows, filter, sort),
+            DataRows::Lazy(source) => row_order(source.iter_rows(), filter, sort),
+        }
+    }
+}
+
+/// Rows a data tab shows, once filtered and sorted
+#[derive(Debug, Clone, PartialEq)]
+pub enum RowSelection {
+    /// Every row, in its own order
+    All,
+    /// Still being worked out
+    Pending,
+    /// Indices of the rows to show, in order
+    Rows(Arc<Vec<usize>>),
+}
+
+/// Order of the rows of one data tab, worked out in the background. Only
+/// the latest order asked for is kept, so a slow one finishing late can't
+/// replace it.
+#[derive(Clone)]
+pub struct RowOrder {
+    state: Arc<Mutex<(u64, RowSelection)>>,
+}
+
+impl Default for RowOrder {
+    fn default() -> Self {
+        Self {
+            state: Arc::new(Mutex::new((0, RowSelection::All))),
+        }
+    }
+}
+
+impl RowOrder {
+    pub fn selection(&self) -> RowSelection {
+        self.state.lock().1.clone()
+    }
+
+    pub fn is_pending(&self) -> bool {
+        self.state.lock().1 == RowSelection::Pending
+    }
+
+    /// Show the rows matching `filter`, sorted by `sort`. With neither,
+    /// every row is shown right away.
+    pub fn update(
+        &self,
+        rows: DataRows,
+        filter: &str,
+        sort: Option<(usize, SortDirection)>,
+        background: &BackgroundExecutor,
+    ) {
+        let generation = {
+            let mut state = self.state.lock();
+            state.0 += 1;
+            state.1 = if sort.is_none() && filter.trim().is_empty() {
+                RowSelection::All
+            } else {
+                RowSelection::Pending
+            };
+            if state.1 == RowSelection::All {
+                return;
+            }
+            state.0
+        };
+
+        let state = self.state.clone();
+        let filter = filter.to_string();
+        background.spawn(
+            "data_row_order",
+            move || Ok(rows.order(&filter, sort)),
+            move |result: Result<Vec<usize>, String>| {
+                let mut state = state.lock();
+                if state.0 == generation {
+                    if let Ok(order) = result {
+                        state.1 = RowSelection::Rows(Arc::new(order));
+                    }
+                }
+            },
+        );
+    }
+}
+
+/// CSV files shared by every window
+pub static DATA_FILES: Lazy<DataFileCache> = Lazy::new(DataFileCache::default);
+
+#[derive(Clone)]
+enum DataFileState {
+    Pending,
+    Ready(Arc<LazyDataSource>),
+    Failed(String),
+}
+
+/// CSV files open in data tabs, loaded on the background executor the
+/// first time each is opened
+#[derive(Clone, Default)]
+pub struct DataFileCache {
+    entries: Arc<Mutex<HashMap<PathBuf, DataFileState>>>,
+}
+
+impl DataFileCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Load a CSV file in the background, unless it has been already
+    pub fn load(&self, path: &Path, background: &BackgroundExecutor) {
+        {
+            let mut entries = self.entries.lock();
+            if entries.contains_key(path) {
+                return;
+            }
+            entries.insert(path.to_path_buf(), DataFileState::Pending);
+        }
+
+        let entries = self.entries.clone();
+        let source = path.to_path_buf();
+        let path = path.to_path_buf();
+        background.spawn(
+            "data_file",
+            move || LazyDataSource::from_csv(0, &source).map_err(|e| e.to_string()),
+            move |result| {
+                let state = match result {
+                    Ok(data) => DataFileState::Ready(Arc::new(data)),
+                    Err(e) => {
+                        warn!("Failed to load {:?}: {}", path, e);
+                        DataFileState::Failed(e)
+                    }
+                };
+                entries.lock().insert(path, state);
+            },
+        );
+    }
+
+    /// A file's rows, or `None` while it is loading or if it couldn't be
+    pub fn get(&self, path: &Path) -> Option<DataRows> {
+        match self.entries.lock().get(path) {
+            Some(DataFileState::Ready(data)) => Some(DataRows::Lazy(data.clone())),
+            _ => None,
+        }
+    }
+
+    /// Why a file couldn't be loaded, if it couldn't
+    pub fn error(&self, path: &Path) -> Option<String> {
+        match self.entries.lock().get(path) {
+            Some(DataFileState::Failed(e)) => Some(e.clone()),
+            _ => None,
+        }
+    }
+
+    /// Store a file loaded elsewhere
+    pub fn insert(&self, path: PathBuf, data: LazyDataSource) {
+        self.entries
+            .lock()
+            .insert(path, DataFileState::Ready(Arc::new(data)));
+    }
+
+    /// Forget a file, e.g. when its tab is closed, so it is loaded again
+    pub fn forget(&self, path: &Path) {
+        self.entries.lock().remove(path);
+    }
+
+    /// Whether any file is still loading
+    pub fn has_pending(&self) -> bool {
+        self.entries
+            .lock()
+            .values()
+            .any(|state| matches!(state, DataFileState::Pending))
+    }
+}
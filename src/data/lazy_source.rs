@@ -294,6 +294,16 @@ impl LazyDataSource {
         result
     }
 
+    /// Every row in order, read a chunk at a time past the chunk cache, e.g.
+    /// for filtering or sorting all of them
+    pub fn iter_rows(&self) -> impl Iterator<Item = DataRow> + '_ {
+        (0..self.row_count)
+            .step_by(CHUNK_SIZE)
+            .flat_map(move |start| {
+                self.load_rows_from_frame(start, CHUNK_SIZE.min(self.row_count - start))
+            })
+    }
+
     /// Get a single chunk, loading from cache or DataFrame
     fn get_chunk(&self, chunk_idx: usize) -> Vec<DataRow> {
         // Check cache first
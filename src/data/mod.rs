@@ -22,6 +22,7 @@
 
 mod chart_engine;
 mod csv_parser;
+mod data_preview;
 mod error;
 mod json_parser;
 mod lazy_source;
@@ -29,6 +30,7 @@ mod table_delegate;
 
 pub use chart_engine::*;
 pub use csv_parser::*;
+pub use data_preview::*;
 pub use error::*;
 pub use json_parser::*;
 pub use lazy_source::*;
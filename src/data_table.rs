@@ -3,9 +3,14 @@
 //! Features:
 //! - Collapse/expand toggle with row count badge
 //! - Pagination controls (first/prev/next/last + page size selector)
-//! - Sticky header row
+//! - Sticky header row, sorted by clicking a column
+//! - Filtering rows by text in any cell
 //! - Alternating row colors
 //!
+//! Clicks are reported as [`DataTableAction`]s through the `on_action`
+//! callback, which turns each into a click handler; the caller applies them
+//! to its [`DataTableState`] with [`DataTableState::apply`].
+//!
 //! ## Usage
 //!
 //! ```rust,ignore
@@ -14,7 +19,7 @@
 //!     "my-table",
 //!     "Users",
 //!     &["Name", "Email", "Status"],
-//!     &data,
+//!     &data[table_state.visible_range()],
 //!     &table_state,
 //!     |row, col_idx| match col_idx {
 //!         0 => row.name.clone(),
@@ -23,12 +28,16 @@
 //!         _ => String::new(),
 //!     },
 //!     theme_colors,
+//!     &|action| Box::new(cx.listener(move |this, _, _, cx| this.apply(action, cx))),
 //! )
 //! ```
 
+use crate::types::{DataCell, DataRow};
 use gpui::prelude::FluentBuilder;
 use gpui::*;
 use gpui_component::{Icon, IconName, h_flex, v_flex};
+use std::borrow::Borrow;
+use std::cmp::Ordering;
 
 // Re-export for external use
 pub use gpui::ElementId;
@@ -36,6 +45,30 @@ pub use gpui::ElementId;
 /// Page size options for pagination
 pub const PAGE_SIZE_OPTIONS: &[usize] = &[10, 25, 50, 100];
 
+/// Direction a column is sorted in
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SortDirection {
+    Ascending,
+    Descending,
+}
+
+/// Something a click in the table asks for
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DataTableAction {
+    ToggleCollapsed,
+    FirstPage,
+    PrevPage,
+    NextPage,
+    LastPage,
+    TogglePageSizeMenu,
+    SetPageSize(usize),
+    /// Sort by a column: ascending, then descending, then unsorted
+    SortBy(usize),
+}
+
+/// Click handler for one of the table's controls
+pub type ClickHandler = Box<dyn Fn(&ClickEvent, &mut Window, &mut App) + 'static>;
+
 /// State for a DataTable instance
 #[derive(Clone, Debug)]
 pub struct DataTableState {
@@ -51,6 +84,10 @@ pub struct DataTableState {
     pub page_size_dropdown_open: bool,
     /// Currently hovered row index (for hover states)
     pub hovered_row: Option<usize>,
+    /// Column the rows are sorted by
+    pub sort: Option<(usize, SortDirection)>,
+    /// Only rows with a cell containing this text are shown, ignoring case
+    pub filter: String,
 }
 
 impl DataTableState {
@@ -62,6 +99,8 @@ impl DataTableState {
             total_rows,
             page_size_dropdown_open: false,
             hovered_row: None,
+            sort: None,
+            filter: String::new(),
         }
     }
 
@@ -129,6 +168,119 @@ impl DataTableState {
         let end = (start + self.page_size).min(self.total_rows);
         start..end
     }
+
+    /// Update the row count, e.g. after filtering, keeping the page in range
+    pub fn set_total_rows(&mut self, total_rows: usize) {
+        self.total_rows = total_rows;
+        self.current_page = self.current_page.min(self.total_pages() - 1);
+    }
+
+    /// Sort by `column`: ascending first, then descending, then unsorted
+    pub fn sort_by(&mut self, column: usize) {
+        self.sort = match self.sort {
+            Some((c, SortDirection::Ascending)) if c == column => {
+                Some((column, SortDirection::Descending))
+            }
+            Some((c, SortDirection::Descending)) if c == column => None,
+            _ => Some((column, SortDirection::Ascending)),
+        };
+        self.current_page = 0;
+    }
+
+    /// Show only rows matching `filter`, from the first page
+    pub fn set_filter(&mut self, filter: &str) {
+        if self.filter != filter {
+            self.filter = filter.to_string();
+            self.current_page = 0;
+        }
+    }
+
+    /// Whether every row is shown in its own order
+    pub fn shows_all_rows(&self) -> bool {
+        self.sort.is_none() && self.filter.trim().is_empty()
+    }
+
+    /// Apply a click in the table
+    pub fn apply(&mut self, action: DataTableAction) {
+        match action {
+            DataTableAction::ToggleCollapsed => self.toggle_collapsed(),
+            DataTableAction::FirstPage => self.go_first(),
+            DataTableAction::PrevPage => self.go_prev(),
+            DataTableAction::NextPage => self.go_next(),
+            DataTableAction::LastPage => self.go_last(),
+            DataTableAction::TogglePageSizeMenu => self.toggle_page_size_dropdown(),
+            DataTableAction::SetPageSize(size) => {
+                self.set_page_size(size);
+                self.close_page_size_dropdown();
+            }
+            DataTableAction::SortBy(column) => self.sort_by(column),
+        }
+    }
+}
+
+/// Order of two cells when sorting: numbers by value, text ignoring case,
+/// and empty cells last
+pub fn compare_cells(a: Option<&DataCell>, b: Option<&DataCell>) -> Ordering {
+    let a = a.filter(|cell| !matches!(cell, DataCell::Empty));
+    let b = b.filter(|cell| !matches!(cell, DataCell::Empty));
+    match (a, b) {
+        (Some(DataCell::Number(na)), Some(DataCell::Number(nb))) => {
+            na.partial_cmp(nb).unwrap_or(Ordering::Equal)
+        }
+        (Some(DataCell::Text(ta)), Some(DataCell::Text(tb))) => {
+            ta.to_lowercase().cmp(&tb.to_lowercase())
+        }
+        (Some(DataCell::Boolean(ba)), Some(DataCell::Boolean(bb))) => ba.cmp(bb),
+        (Some(a), Some(b)) => a.to_string().cmp(&b.to_string()),
+        (Some(_), None) => Ordering::Less,
+        (None, Some(_)) => Ordering::Greater,
+        (None, None) => Ordering::Equal,
+    }
+}
+
+/// Indices of the rows matching `filter`, in the order `sort` puts them.
+/// Sorting is stable, and empty cells stay last in either direction.
+pub fn row_order<R: Borrow<DataRow>>(
+    rows: impl IntoIterator<Item = R>,
+    filter: &str,
+    sort: Option<(usize, SortDirection)>,
+) -> Vec<usize> {
+    let filter = filter.trim().to_lowercase();
+    let mut kept: Vec<(usize, Option<DataCell>)> = rows
+        .into_iter()
+        .enumerate()
+        .filter(|(_, row)| {
+            filter.is_empty()
+                || row
+                    .borrow()
+                    .cells
+                    .iter()
+                    .any(|cell| cell.to_string().to_lowercase().contains(&filter))
+        })
+        .map(|(index, row)| {
+            let key = sort.and_then(|(column, _)| row.borrow().cells.get(column).cloned());
+            (index, key)
+        })
+        .collect();
+
+    if let Some((_, direction)) = sort {
+        kept.sort_by(|(_, a), (_, b)| {
+            let empty = |cell: &Option<DataCell>| {
+                cell.as_ref().is_none_or(|cell| matches!(cell, DataCell::Empty))
+            };
+            match (empty(a), empty(b)) {
+                (false, false) => {
+                    let order = compare_cells(a.as_ref(), b.as_ref());
+                    match direction {
+                        SortDirection::Ascending => order,
+                        SortDirection::Descending => order.reverse(),
+                    }
+                }
+                (a_empty, b_empty) => a_empty.cmp(&b_empty),
+            }
+        });
+    }
+    kept.into_iter().map(|(index, _)| index).collect()
 }
 
 /// Theme colors for the data table
@@ -173,6 +325,7 @@ pub fn render_table_header(
     title: &str,
     state: &DataTableState,
     colors: &DataTableColors,
+    on_action: &dyn Fn(DataTableAction) -> ClickHandler,
 ) -> Div {
     let chevron_icon = if state.collapsed {
         IconName::ChevronRight
@@ -194,6 +347,7 @@ pub fn render_table_header(
         .child(
             // Left side: collapse toggle + title + row count badge
             h_flex()
+                .id("table-collapse-toggle")
                 .items_center()
                 .gap_2()
                 .cursor_pointer()
@@ -219,7 +373,8 @@ pub fn render_table_header(
                         .text_xs()
                         .text_color(colors.primary)
                         .child(row_count_text),
-                ),
+                )
+                .on_click(on_action(DataTableAction::ToggleCollapsed)),
         )
 }
 
@@ -227,6 +382,7 @@ pub fn render_table_header(
 pub fn render_pagination(
     state: &DataTableState,
     colors: &DataTableColors,
+    on_action: &dyn Fn(DataTableAction) -> ClickHandler,
 ) -> Div {
     let current_page = state.current_page + 1; // 1-indexed for display
     let total_pages = state.total_pages();
@@ -257,7 +413,7 @@ pub fn render_pagination(
                             showing_start, showing_end, state.total_rows
                         )),
                 )
-                .child(render_page_size_selector(state, colors)),
+                .child(render_page_size_selector(state, colors, on_action)),
         )
         .child(
             // Right: pagination buttons
@@ -274,7 +430,10 @@ pub fn render_pagination(
                         .rounded(px(4.0))
                         .cursor_pointer()
                         .when(!state.can_go_prev(), |d| d.opacity(0.3).cursor_default())
-                        .when(state.can_go_prev(), |d| d.hover(|s| s.bg(colors.alt_row_bg)))
+                        .when(state.can_go_prev(), |d| {
+                            d.hover(|s| s.bg(colors.alt_row_bg))
+                                .on_click(on_action(DataTableAction::FirstPage))
+                        })
                         .child(Icon::new(IconName::ChevronLeft).size(px(10.0)).text_color(colors.text_muted))
                         .child(Icon::new(IconName::ChevronLeft).size(px(10.0)).text_color(colors.text_muted).ml(px(-4.0))),
                 )
@@ -288,7 +447,10 @@ pub fn render_pagination(
                         .rounded(px(4.0))
                         .cursor_pointer()
                         .when(!state.can_go_prev(), |d| d.opacity(0.3).cursor_default())
-                        .when(state.can_go_prev(), |d| d.hover(|s| s.bg(colors.alt_row_bg)))
+                        .when(state.can_go_prev(), |d| {
+                            d.hover(|s| s.bg(colors.alt_row_bg))
+                                .on_click(on_action(DataTableAction::PrevPage))
+                        })
                         .child(Icon::new(IconName::ChevronLeft).size(px(14.0)).text_color(colors.text_muted)),
                 )
                 .child(
@@ -309,7 +471,10 @@ pub fn render_pagination(
                         .rounded(px(4.0))
                         .cursor_pointer()
                         .when(!state.can_go_next(), |d| d.opacity(0.3).cursor_default())
-                        .when(state.can_go_next(), |d| d.hover(|s| s.bg(colors.alt_row_bg)))
+                        .when(state.can_go_next(), |d| {
+                            d.hover(|s| s.bg(colors.alt_row_bg))
+                                .on_click(on_action(DataTableAction::NextPage))
+                        })
                         .child(Icon::new(IconName::ChevronRight).size(px(14.0)).text_color(colors.text_muted)),
                 )
                 .child(
@@ -322,7 +487,10 @@ pub fn render_pagination(
                         .rounded(px(4.0))
                         .cursor_pointer()
                         .when(!state.can_go_next(), |d| d.opacity(0.3).cursor_default())
-                        .when(state.can_go_next(), |d| d.hover(|s| s.bg(colors.alt_row_bg)))
+                        .when(state.can_go_next(), |d| {
+                            d.hover(|s| s.bg(colors.alt_row_bg))
+                                .on_click(on_action(DataTableAction::LastPage))
+                        })
                         .child(Icon::new(IconName::ChevronRight).size(px(10.0)).text_color(colors.text_muted))
                         .child(Icon::new(IconName::ChevronRight).size(px(10.0)).text_color(colors.text_muted).ml(px(-4.0))),
                 ),
@@ -333,6 +501,7 @@ pub fn render_pagination(
 pub fn render_page_size_selector(
     state: &DataTableState,
     colors: &DataTableColors,
+    on_action: &dyn Fn(DataTableAction) -> ClickHandler,
 ) -> Stateful<Div> {
     h_flex()
         .id("page-size-selector")
@@ -357,6 +526,7 @@ pub fn render_page_size_selector(
                 .rounded(px(4.0))
                 .cursor_pointer()
                 .hover(|s| s.border_color(colors.primary.opacity(0.5)))
+                .on_click(on_action(DataTableAction::TogglePageSizeMenu))
                 .child(
                     div()
                         .text_xs()
@@ -379,6 +549,7 @@ pub fn render_page_size_selector(
 pub fn render_page_size_dropdown_menu(
     state: &DataTableState,
     colors: &DataTableColors,
+    on_action: &dyn Fn(DataTableAction) -> ClickHandler,
 ) -> Stateful<Div> {
     let mut menu = div()
         .id("page-size-menu")
@@ -404,6 +575,7 @@ pub fn render_page_size_dropdown_menu(
                 .cursor_pointer()
                 .when(is_current, |d| d.bg(colors.primary.opacity(0.15)))
                 .when(!is_current, |d| d.hover(|s| s.bg(colors.alt_row_bg)))
+                .on_click(on_action(DataTableAction::SetPageSize(size)))
                 .items_center()
                 .justify_between()
                 .child(
@@ -425,10 +597,13 @@ pub fn render_page_size_dropdown_menu(
     menu
 }
 
-/// Render column headers row
+/// Render column headers row. Clicking a column sorts by it, and the
+/// sorted column shows its direction.
 pub fn render_column_headers(
     columns: &[&str],
+    sort: Option<(usize, SortDirection)>,
     colors: &DataTableColors,
+    on_action: &dyn Fn(DataTableAction) -> ClickHandler,
 ) -> Div {
     let mut header_row = h_flex()
         .w_full()
@@ -436,10 +611,17 @@ pub fn render_column_headers(
         .border_b_1()
         .border_color(colors.border);
 
-    for col in columns {
+    for (col_idx, col) in columns.iter().enumerate() {
+        let direction = sort
+            .filter(|&(column, _)| column == col_idx)
+            .map(|(_, direction)| direction);
         header_row = header_row.child(
-            div()
+            h_flex()
+                .id(ElementId::Name(format!("column-{}", col_idx).into()))
                 .flex_1()
+                .min_w_0()
+                .gap_1()
+                .items_center()
                 .px_3()
                 .py_2()
                 .text_xs()
@@ -447,7 +629,20 @@ pub fn render_column_headers(
                 .text_color(colors.text)
                 .border_r_1()
                 .border_color(colors.border)
-                .child(col.to_string()),
+                .cursor_pointer()
+                .hover(|s| s.bg(colors.alt_row_bg))
+                .on_click(on_action(DataTableAction::SortBy(col_idx)))
+                .child(div().overflow_hidden().child(col.to_string()))
+                .when_some(direction, |d, direction| {
+                    d.child(
+                        Icon::new(match direction {
+                            SortDirection::Ascending => IconName::ChevronUp,
+                            SortDirection::Descending => IconName::ChevronDown,
+                        })
+                        .size(px(10.0))
+                        .text_color(colors.primary),
+                    )
+                }),
         );
     }
 
@@ -498,11 +693,14 @@ pub fn render_data_row(
 ///
 /// This is a stateless render function. The caller is responsible for:
 /// - Storing the `DataTableState`
-/// - Handling click events on the header (to toggle collapse)
-/// - Handling click events on pagination buttons
-/// - Handling click events on page size selector
+/// - Applying the [`DataTableAction`]s `on_action` is given, for collapsing,
+///   pagination, the page size selector and sorting
+/// - Passing only the rows of the current page (`state.visible_range()`),
+///   already filtered and sorted as the state asks for, so big sources never
+///   have to be loaded whole
 ///
 /// Returns a Stateful<Div> that can have event handlers attached.
+#[allow(clippy::too_many_arguments)]
 pub fn render_data_table<T, F>(
     id: impl Into<ElementId>,
     title: &str,
@@ -511,6 +709,7 @@ pub fn render_data_table<T, F>(
     state: &DataTableState,
     render_cell: F,
     colors: &DataTableColors,
+    on_action: &dyn Fn(DataTableAction) -> ClickHandler,
 ) -> Stateful<Div>
 where
     F: Fn(&T, usize) -> String,
@@ -526,23 +725,16 @@ where
         .relative();
 
     // Header with collapse toggle
-    container = container.child(render_table_header(title, state, colors));
+    container = container.child(render_table_header(title, state, colors, on_action));
 
     // Table body (only when not collapsed)
     if !state.collapsed {
         // Column headers
-        container = container.child(render_column_headers(columns, colors));
-
-        // Data rows (paginated)
-        let range = state.visible_range();
-        let visible_rows: Vec<_> = rows
-            .iter()
-            .skip(range.start)
-            .take(range.end - range.start)
-            .collect();
+        container = container.child(render_column_headers(columns, state.sort, colors, on_action));
 
+        // Data rows of the current page
         let mut body = v_flex().w_full();
-        for (row_idx, row_data) in visible_rows.iter().enumerate() {
+        for (row_idx, row_data) in rows.iter().enumerate() {
             let cells: Vec<String> = (0..columns.len())
                 .map(|col_idx| render_cell(row_data, col_idx))
                 .collect();
@@ -555,9 +747,9 @@ where
         container = container.child(
             div()
                 .relative()
-                .child(render_pagination(state, colors))
+                .child(render_pagination(state, colors, on_action))
                 .when(state.page_size_dropdown_open, |d| {
-                    d.child(render_page_size_dropdown_menu(state, colors))
+                    d.child(render_page_size_dropdown_menu(state, colors, on_action))
                 }),
         );
     }
@@ -584,7 +776,17 @@ fn format_row_count(count: usize) -> String {
 
 #[cfg(test)]
 mod tests {
-    use super::{DataTableState, format_row_count};
+    use super::{DataTableAction, DataTableState, SortDirection, format_row_count, row_order};
+    use crate::types::{DataCell, DataRow};
+
+    fn rows() -> Vec<DataRow> {
+        vec![
+            DataRow::new(vec![DataCell::Text("Oslo".into()), DataCell::Number(3.0)]),
+            DataRow::new(vec![DataCell::Text("bergen".into()), DataCell::Empty]),
+            DataRow::new(vec![DataCell::Text("Arendal".into()), DataCell::Number(10.0)]),
+            DataRow::new(vec![DataCell::Text("Bodø".into()), DataCell::Number(1.0)]),
+        ]
+    }
 
     #[test]
     fn test_pagination_state() {
@@ -629,4 +831,50 @@ mod tests {
         assert_eq!(format_row_count(1234), "1.2K rows");
         assert_eq!(format_row_count(1000000), "1.0M rows");
     }
+
+    #[test]
+    fn test_sort_by_cycles_directions() {
+        let mut state = DataTableState::new(100);
+        state.current_page = 2;
+        state.apply(DataTableAction::SortBy(1));
+        assert_eq!(state.sort, Some((1, SortDirection::Ascending)));
+        assert_eq!(state.current_page, 0);
+        state.apply(DataTableAction::SortBy(1));
+        assert_eq!(state.sort, Some((1, SortDirection::Descending)));
+        state.apply(DataTableAction::SortBy(1));
+        assert_eq!(state.sort, None);
+        state.apply(DataTableAction::SortBy(0));
+        state.apply(DataTableAction::SortBy(1));
+        assert_eq!(state.sort, Some((1, SortDirection::Ascending)));
+    }
+
+    #[test]
+    fn test_set_total_rows_keeps_page_in_range() {
+        let mut state = DataTableState::new(100);
+        state.go_last();
+        state.set_total_rows(30);
+        assert_eq!(state.current_page, 1);
+        state.set_total_rows(0);
+        assert_eq!(state.current_page, 0);
+    }
+
+    #[test]
+    fn test_row_order_filters_ignoring_case() {
+        assert_eq!(row_order(rows(), "", None), vec![0, 1, 2, 3]);
+        assert_eq!(row_order(rows(), "B", None), vec![1, 3]);
+        assert_eq!(row_order(rows(), " 10 ", None), vec![2]);
+        assert!(row_order(rows(), "paris", None).is_empty());
+    }
+
+    #[test]
+    fn test_row_order_sorts_with_empty_cells_last() {
+        let ascending = Some((1, SortDirection::Ascending));
+        let descending = Some((1, SortDirection::Descending));
+        assert_eq!(row_order(rows(), "", ascending), vec![3, 0, 2, 1]);
+        assert_eq!(row_order(rows(), "", descending), vec![2, 0, 3, 1]);
+        assert_eq!(
+            row_order(&rows(), "", Some((0, SortDirection::Ascending))),
+            vec![2, 1, 3, 0]
+        );
+    }
 }
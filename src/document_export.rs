@@ -0,0 +1,239 @@
+//! Markdown export of a selection - turning cards into one document.
+//!
+//! The selected text boxes, markdown cards, code cards and images are
+//! written one after another into a single `.md` file, in reading order
+//! (top to bottom, then left to right) or in stacking order (back to front,
+//! as arranged with Bring Forward and Send Backward). Code is fenced with
+//! its language, showing the lines pinned to the card if any, and images
+//! are copied into a folder beside the document and referenced from it.
+
+use crate::code_excerpt::CodeExcerpt;
+use crate::error::BoardError;
+use crate::types::{CanvasItem, ItemContent};
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use tracing::{debug, warn};
+
+/// Order the cards of a selection are written in
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DocumentOrder {
+    /// Top to bottom, then left to right
+    Reading,
+    /// Back to front, as the cards are stacked on the board
+    Stacking,
+}
+
+/// Whether an item has something to write into a document
+pub fn is_exportable(content: &ItemContent) -> bool {
+    matches!(
+        content,
+        ItemContent::TextBox { .. }
+            | ItemContent::Markdown { .. }
+            | ItemContent::Code { .. }
+            | ItemContent::Image(_)
+    )
+}
+
+/// The exportable items among `selected`, in `order`. `items` is the
+/// board's items, back to front.
+pub fn document_items<'a>(
+    items: &'a [CanvasItem],
+    selected: &HashSet<u64>,
+    order: DocumentOrder,
+) -> Vec<&'a CanvasItem> {
+    let mut picked: Vec<&CanvasItem> = items
+        .iter()
+        .filter(|item| selected.contains(&item.id) && is_exportable(&item.content))
+        .collect();
+    if order == DocumentOrder::Reading {
+        picked.sort_by(|a, b| {
+            a.position
+                .1
+                .total_cmp(&b.position.1)
+                .then(a.position.0.total_cmp(&b.position.0))
+                .then(a.id.cmp(&b.id))
+        });
+    }
+    picked
+}
+
+/// A document made of cards, and the images it references
+#[derive(Debug, Clone, PartialEq)]
+pub struct SelectionDocument {
+    pub markdown: String,
+    /// Each image file and the name it is referenced by in the assets folder
+    pub assets: Vec<(PathBuf, String)>,
+}
+
+/// Folder the images of a document saved at `path` are copied into:
+/// `notes.md` keeps them in `notes_assets`
+pub fn assets_dir_name(path: &Path) -> String {
+    let stem = path
+        .file_stem()
+        .and_then(|stem| stem.to_str())
+        .unwrap_or("document");
+    format!("{}_assets", stem)
+}
+
+/// Write `items` into one markdown document, referencing images from
+/// `assets_dir`
+pub fn compose_document(items: &[&CanvasItem], assets_dir: &str) -> SelectionDocument {
+    let mut sections = Vec::new();
+    let mut assets: Vec<(PathBuf, String)> = Vec::new();
+
+    for item in items {
+        let section = match &item.content {
+            ItemContent::TextBox { text, .. } => text.trim().to_string(),
+            ItemContent::Markdown { content, .. } => content.trim().to_string(),
+            ItemContent::Code {
+                path,
+                language,
+                lines,
+            } => code_section(path, language, *lines),
+            ItemContent::Image(path) => image_section(path, assets_dir, &mut assets),
+            _ => continue,
+        };
+        if !section.is_empty() {
+            sections.push(section);
+        }
+    }
+
+    let mut markdown = sections.join("\n\n");
+    markdown.push('\n');
+    SelectionDocument { markdown, assets }
+}
+
+/// An image referenced from `assets_dir`, added to the `assets` to copy
+/// there unless it already is
+pub(crate) fn image_section(
+    path: &Path,
+    assets_dir: &str,
+    assets: &mut Vec<(PathBuf, String)>,
+) -> String {
+    let name = asset_name(path, assets);
+    let alt = path
+        .file_stem()
+        .and_then(|stem| stem.to_str())
+        .unwrap_or("image")
+        .to_string();
+    if !assets.iter().any(|(source, _)| source == path) {
+        assets.push((path.to_path_buf(), name.clone()));
+    }
+    format!(
+        "![{}]({})",
+        alt,
+        link_destination(&format!("{}/{}", assets_dir, name))
+    )
+}
+
+/// A code file as a fenced block under its file name, showing the pinned
+/// lines or the whole file
+pub(crate) fn code_section(path: &Path, language: &str, lines: Option<(usize, usize)>) -> String {
+    let name = path
+        .file_name()
+        .and_then(|name| name.to_str())
+        .unwrap_or("code");
+    let text = fs::read_to_string(path).unwrap_or_default();
+    let (caption, code) = match lines {
+        Some(range) => {
+            let excerpt = CodeExcerpt::new(&text, Some(range));
+            let caption = format!(
+                "**{}** (lines {}–{})",
+                name,
+                excerpt.first_line,
+                excerpt.last_line()
+            );
+            (caption, excerpt.lines.join("\n"))
+        }
+        None => (format!("**{}**", name), text.trim_end().to_string()),
+    };
+    let fence = code_fence(&code);
+    format!("{}\n\n{}{}\n{}\n{}", caption, fence, language, code, fence)
+}
+
+/// A backtick fence longer than any run of backticks in `code`
+fn code_fence(code: &str) -> String {
+    let longest = code.split(|c| c != '`').map(str::len).max().unwrap_or(0);
+    "`".repeat(longest.max(2) + 1)
+}
+
+/// Name an image is copied under, numbered if another image already took
+/// its file name
+fn asset_name(path: &Path, taken: &[(PathBuf, String)]) -> String {
+    if let Some((_, name)) = taken.iter().find(|(source, _)| source == path) {
+        return name.clone();
+    }
+    let stem = path
+        .file_stem()
+        .and_then(|stem| stem.to_str())
+        .unwrap_or("image");
+    let extension = path.extension().and_then(|ext| ext.to_str());
+    let named = |n: usize| {
+        let stem = if n == 1 {
+            stem.to_string()
+        } else {
+            format!("{}-{}", stem, n)
+        };
+        match extension {
+            Some(ext) => format!("{}.{}", stem, ext),
+            None => stem,
+        }
+    };
+    (1..)
+        .map(named)
+        .find(|name| !taken.iter().any(|(_, taken)| taken == name))
+        .unwrap_or_default()
+}
+
+/// A link destination, in angle brackets if it has spaces
+pub(crate) fn link_destination(target: &str) -> String {
+    if target.contains([' ', '(', ')']) {
+        format!("<{}>", target)
+    } else {
+        target.to_string()
+    }
+}
+
+/// Write `items` as a markdown document at `path`, copying their images
+/// into the assets folder beside it. Returns the number of cards written.
+///
+/// Images that can't be copied are referenced where they are instead.
+pub fn export_markdown(items: &[CanvasItem], path: &Path) -> Result<usize, BoardError> {
+    let refs: Vec<&CanvasItem> = items.iter().collect();
+    let document = compose_document(&refs, &assets_dir_name(path));
+    write_document(document, path)?;
+    debug!("Exported {} cards to {:?}", refs.len(), path);
+    Ok(refs.len())
+}
+
+/// Write a composed document at `path`, copying its images into the
+/// assets folder beside it (see `assets_dir_name`). Images that can't be
+/// copied are referenced where they are instead.
+pub(crate) fn write_document(
+    mut document: SelectionDocument,
+    path: &Path,
+) -> Result<(), BoardError> {
+    let save_failed = |e| BoardError::SaveFailed {
+        path: path.to_path_buf(),
+        source: e,
+    };
+    let dir_name = assets_dir_name(path);
+
+    if !document.assets.is_empty() {
+        let assets_dir = path.parent().unwrap_or(Path::new(".")).join(&dir_name);
+        fs::create_dir_all(&assets_dir).map_err(save_failed)?;
+        for (source, name) in &document.assets {
+            if let Err(e) = fs::copy(source, assets_dir.join(name)) {
+                warn!("Couldn't copy {:?} for the export: {}", source, e);
+                let copied = link_destination(&format!("{}/{}", dir_name, name));
+                let original = link_destination(&source.to_string_lossy());
+                document.markdown = document
+                    .markdown
+                    .replace(&format!("]({})", copied), &format!("]({})", original));
+            }
+        }
+    }
+
+    fs::write(path, &document.markdown).map_err(save_failed)
+}
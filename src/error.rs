@@ -31,6 +31,15 @@ pub enum BoardError {
 
     #[error("Failed to parse board JSON: {0}")]
     ParseError(#[from] serde_json::Error),
+
+    #[error("Board is encrypted")]
+    Encrypted,
+
+    #[error("Incorrect passphrase")]
+    WrongPassphrase,
+
+    #[error("Board encryption failed: {0}")]
+    Crypto(String),
 }
 
 /// Errors that can occur during settings operations
@@ -79,6 +88,13 @@ pub enum MediaError {
         source: std::io::Error,
     },
 
+    #[error("Failed to write file {path}: {source}")]
+    WriteFailed {
+        path: PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
+
     #[error("Failed to process image: {0}")]
     ImageError(#[from] image::ImageError),
 
@@ -87,6 +103,9 @@ pub enum MediaError {
 
     #[error("PDF rendering failed: {0}")]
     PdfError(String),
+
+    #[error("Failed to decode audio: {0}")]
+    AudioDecode(String),
 }
 
 /// Errors that can occur during UI operations
@@ -0,0 +1,121 @@
+//! File import - reading dropped files into what they become on the board.
+//!
+//! Importing a file copies it into the board's storage, parses data files
+//! into a data source, renders a PDF's thumbnail and measures an image.
+//! That can take a while for many or large files, so [`import_file`] needs
+//! no board and can run off the UI thread; the results are added to the
+//! board together with `Board::add_imported_files`.
+
+use crate::board_assets;
+use crate::data::{is_data_file, parse_csv_file, parse_json_file};
+use crate::types::{DataSource, ItemContent, image_item_size};
+use std::path::{Path, PathBuf};
+use tracing::warn;
+
+/// A dropped file, ready to be added to the board
+pub enum ImportedFile {
+    /// An item showing the file, with its size if it has been measured
+    Item {
+        content: ItemContent,
+        size: Option<(f32, f32)>,
+    },
+    /// A table showing the data in a CSV, TSV or JSON file
+    Table(DataSource),
+}
+
+impl ImportedFile {
+    /// Size of the item the file becomes
+    pub fn size(&self) -> (f32, f32) {
+        match self {
+            ImportedFile::Item { content, size } => size.unwrap_or_else(|| content.default_size()),
+            ImportedFile::Table(_) => table_content(0).default_size(),
+        }
+    }
+}
+
+/// Delete the copies in `import_dir` of files that won't be added after
+/// all, leaving files elsewhere - the user's own - alone. Returns how many
+/// were deleted.
+pub fn remove_copies(files: &[ImportedFile], import_dir: &Path) -> usize {
+    let paths = files.iter().flat_map(|file| match file {
+        ImportedFile::Item { content, .. } => content
+            .file_paths()
+            .into_iter()
+            .map(PathBuf::as_path)
+            .collect::<Vec<_>>(),
+        ImportedFile::Table(data_source) => data_source.file_path().into_iter().collect(),
+    });
+    paths
+        .filter(|path| path.starts_with(import_dir))
+        .filter(|path| match std::fs::remove_file(path) {
+            Ok(()) => true,
+            Err(e) => {
+                warn!("Failed to remove '{}': {}", path.display(), e);
+                false
+            }
+        })
+        .count()
+}
+
+/// Content of a table item showing the data source with `data_source_id`,
+/// styled as new tables are
+pub fn table_content(data_source_id: u64) -> ItemContent {
+    crate::settings::item_styles().table(data_source_id)
+}
+
+/// Import the file at `path`, copying it into `import_dir` first if given.
+///
+/// Problems are added to `errors`. A file that couldn't be copied is used
+/// from where it is; a data file that couldn't be parsed gives None.
+/// Slow for PDFs and large images, so call it off the UI thread.
+pub fn import_file(
+    import_dir: Option<&Path>,
+    path: &Path,
+    errors: &mut Vec<String>,
+) -> Option<ImportedFile> {
+    let filename = path
+        .file_name()
+        .and_then(|n| n.to_str())
+        .unwrap_or("file")
+        .to_string();
+
+    // Copy the file into board storage (iCloud files/ or managed assets/)
+    let path: PathBuf = match import_dir {
+        Some(dir) => match board_assets::copy_into_dir(dir, path) {
+            Ok(copied_path) => copied_path,
+            Err(e) => {
+                errors.push(format!("Failed to copy '{}': {}", filename, e));
+                warn!("Failed to copy file to board storage: {}", e);
+                path.to_path_buf()
+            }
+        },
+        None => path.to_path_buf(),
+    };
+
+    if !is_data_file(&path) {
+        let content = ItemContent::from_path(&path);
+        let size = match content {
+            ItemContent::Image(ref image) => image_item_size(image),
+            _ => None,
+        };
+        return Some(ImportedFile::Item { content, size });
+    }
+
+    let is_json = path
+        .extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(|e| e.eq_ignore_ascii_case("json"));
+    let parse_result = if is_json {
+        parse_json_file(&path)
+    } else {
+        parse_csv_file(&path)
+    };
+    match parse_result {
+        Ok(data_source) => Some(ImportedFile::Table(data_source)),
+        Err(e) => {
+            errors.push(format!("Failed to parse '{}': {}", filename, e));
+            warn!("Failed to parse data file '{}': {}", filename, e);
+            None
+        }
+    }
+}
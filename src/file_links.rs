@@ -0,0 +1,81 @@
+//! Missing file detection and relinking.
+//!
+//! Items that point at a file which no longer exists get a broken-link badge
+//! on the canvas. Locating the file rebinds that item's path, and every other
+//! missing file from the same folder is looked up in the new folder too, so a
+//! moved folder can be relinked in one step.
+
+use crate::types::CanvasItem;
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+/// IDs of items whose backing file doesn't exist
+pub fn missing_file_items(items: &[CanvasItem]) -> HashSet<u64> {
+    missing_files(&linked_files(items))
+}
+
+/// The backing file of each item that has one, as (item ID, path)
+pub fn linked_files(items: &[CanvasItem]) -> Vec<(u64, PathBuf)> {
+    items
+        .iter()
+        .filter_map(|item| {
+            let path = item.content.file_paths().into_iter().next()?;
+            Some((item.id, path.clone()))
+        })
+        .collect()
+}
+
+/// IDs of the items in `files` whose file doesn't exist.
+///
+/// Stats every file, which can block on network or cloud drives, so call
+/// it off the UI thread.
+pub fn missing_files(files: &[(u64, PathBuf)]) -> HashSet<u64> {
+    files
+        .iter()
+        .filter(|(_, path)| !path.exists())
+        .map(|(id, _)| *id)
+        .collect()
+}
+
+/// Point an item at `new_path` and relink other missing files that moved
+/// along with it.
+///
+/// Missing paths under the item's old folder are looked up at the same
+/// relative location under the new folder. Returns the number of paths
+/// rebound, including the item's own.
+pub fn relink_items(items: &mut [CanvasItem], item_id: u64, new_path: &Path) -> usize {
+    let Some(item) = items.iter_mut().find(|item| item.id == item_id) else {
+        return 0;
+    };
+    let Some(path) = item.content.file_paths_mut().into_iter().next() else {
+        return 0;
+    };
+
+    let old_path = std::mem::replace(path, new_path.to_path_buf());
+    let mut relinked = 1;
+
+    let (Some(old_dir), Some(new_dir)) = (old_path.parent(), new_path.parent()) else {
+        return relinked;
+    };
+    if old_dir == new_dir {
+        return relinked;
+    }
+
+    for item in items.iter_mut() {
+        for path in item.content.file_paths_mut() {
+            if path.exists() {
+                continue;
+            }
+            let Ok(relative) = path.strip_prefix(old_dir) else {
+                continue;
+            };
+            let candidate = new_dir.join(relative);
+            if candidate.exists() {
+                *path = candidate;
+                relinked += 1;
+            }
+        }
+    }
+
+    relinked
+}
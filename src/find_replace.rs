@@ -0,0 +1,74 @@
+//! Finding and replacing text in editable preview tabs.
+//!
+//! Matches ignore case and don't overlap, so each can be replaced on its
+//! own. Replacing every match is worked out as one edit over the span from
+//! the first match to the end of the last, so it is undone in one step.
+//! Editors address text in UTF-16 code units; [`utf16_range`] converts the
+//! byte ranges used here.
+
+use std::ops::Range;
+
+/// Byte ranges of the matches of `query` in `text`, ignoring case
+pub fn find_matches(text: &str, query: &str) -> Vec<Range<usize>> {
+    let mut matches = Vec::new();
+    if query.is_empty() {
+        return matches;
+    }
+    let mut start = 0;
+    while start < text.len() {
+        match match_len(&text[start..], query) {
+            Some(len) => {
+                matches.push(start..start + len);
+                start += len;
+            }
+            None => start += text[start..].chars().next().map_or(1, char::len_utf8),
+        }
+    }
+    matches
+}
+
+/// Length in bytes of the match of `query` at the start of `text`, if any
+fn match_len(text: &str, query: &str) -> Option<usize> {
+    let mut chars = text.char_indices();
+    for q in query.chars() {
+        let (_, c) = chars.next()?;
+        if !c.to_lowercase().eq(q.to_lowercase()) {
+            return None;
+        }
+    }
+    Some(chars.next().map_or(text.len(), |(index, _)| index))
+}
+
+/// Line and byte column of a byte offset, as the find bar lists matches
+pub fn line_and_column(text: &str, offset: usize) -> (usize, usize) {
+    let before = &text[..offset];
+    let line = before.matches('\n').count();
+    let column = offset - before.rfind('\n').map_or(0, |newline| newline + 1);
+    (line, column)
+}
+
+/// The one edit replacing every match of `query` with `replacement`: the
+/// span from the first match to the end of the last, and its new text.
+/// `None` when nothing matches.
+pub fn replace_all_edit(
+    text: &str,
+    query: &str,
+    replacement: &str,
+) -> Option<(Range<usize>, String)> {
+    let matches = find_matches(text, query);
+    let span = matches.first()?.start..matches.last()?.end;
+    let mut replaced = String::with_capacity(span.len());
+    let mut copied = span.start;
+    for range in &matches {
+        replaced.push_str(&text[copied..range.start]);
+        replaced.push_str(replacement);
+        copied = range.end;
+    }
+    Some((span, replaced))
+}
+
+/// A byte range of `text` in UTF-16 code units
+pub fn utf16_range(text: &str, range: Range<usize>) -> Range<usize> {
+    let start = text[..range.start].encode_utf16().count();
+    start..start + text[range].encode_utf16().count()
+}
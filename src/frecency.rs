@@ -0,0 +1,172 @@
+//! Frecency of command palette commands.
+//!
+//! Each time a command is run from the palette its use is counted, with
+//! the time. A command's frecency weighs how often it is used by how
+//! recently: uses in the last hour count the most, uses over a month ago
+//! the least. The palette ranks matching commands by how well they match
+//! plus a bonus for their frecency, so the commands used most lately float
+//! to the top. Uses are shared by every window and remembered across runs,
+//! saved on the background executor so running a command never waits on
+//! the disk.
+
+use crate::background::BackgroundExecutor;
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{SystemTime, UNIX_EPOCH};
+use tracing::warn;
+
+/// Largest ranking bonus frecency gives a command
+pub const MAX_FRECENCY_BONUS: i32 = 200;
+
+/// Uses counted per command; older uses stop adding up past this
+const MAX_COUNTED_USES: u32 = 20;
+
+/// Uses of one command
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Usage {
+    pub count: u32,
+    /// When it was last used, in seconds since the Unix epoch
+    pub last_used: u64,
+}
+
+/// Weight of a use `age` seconds ago
+fn recency_weight(age: u64) -> f32 {
+    const HOUR: u64 = 60 * 60;
+    const DAY: u64 = 24 * HOUR;
+    match age {
+        age if age < HOUR => 4.0,
+        age if age < DAY => 2.0,
+        age if age < 7 * DAY => 1.0,
+        age if age < 30 * DAY => 0.5,
+        _ => 0.25,
+    }
+}
+
+/// Frecency of `usage` at `now` (seconds since the Unix epoch)
+pub fn frecency(usage: &Usage, now: u64) -> f32 {
+    usage.count.min(MAX_COUNTED_USES) as f32 * recency_weight(now.saturating_sub(usage.last_used))
+}
+
+/// Ranking bonus for a frecency
+pub fn frecency_bonus(frecency: f32) -> i32 {
+    ((frecency * 8.0) as i32).min(MAX_FRECENCY_BONUS)
+}
+
+fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default()
+}
+
+/// Default file the uses are saved in
+pub fn default_frecency_file() -> PathBuf {
+    dirs::data_dir()
+        .unwrap_or_else(std::env::temp_dir)
+        .join("humanboard")
+        .join("palette_frecency.json")
+}
+
+/// The command uses shared by every window
+pub static PALETTE_FRECENCY: Lazy<FrecencyStore> =
+    Lazy::new(|| FrecencyStore::new(default_frecency_file()));
+
+/// Uses of each command, mirrored to disk
+pub struct FrecencyStore {
+    file: PathBuf,
+    uses: Arc<Mutex<HashMap<String, Usage>>>,
+    loaded: AtomicBool,
+    /// Held while the uses are written, so writes land in order
+    writing: Arc<Mutex<()>>,
+}
+
+impl FrecencyStore {
+    pub fn new(file: PathBuf) -> Self {
+        Self {
+            file,
+            uses: Arc::new(Mutex::new(HashMap::new())),
+            loaded: AtomicBool::new(false),
+            writing: Arc::new(Mutex::new(())),
+        }
+    }
+
+    /// Read the uses saved by earlier runs, once
+    fn ensure_loaded(&self) {
+        if self.loaded.swap(true, Ordering::SeqCst) {
+            return;
+        }
+        let saved: HashMap<String, Usage> = fs::read(&self.file)
+            .ok()
+            .and_then(|json| serde_json::from_slice(&json).ok())
+            .unwrap_or_default();
+        let mut uses = self.uses.lock();
+        for (command, usage) in saved {
+            uses.entry(command).or_insert(usage);
+        }
+    }
+
+    /// Count a use of `command` now, saving it on `background`
+    pub fn record(&self, command: &str, background: &BackgroundExecutor) {
+        self.record_at(command, now_secs(), background);
+    }
+
+    /// Count a use of `command` at `now` (seconds since the Unix epoch),
+    /// saving it on `background`
+    pub fn record_at(&self, command: &str, now: u64, background: &BackgroundExecutor) {
+        self.ensure_loaded();
+        {
+            let mut uses = self.uses.lock();
+            let usage = uses.entry(command.to_string()).or_insert(Usage {
+                count: 0,
+                last_used: now,
+            });
+            usage.count = usage.count.saturating_add(1);
+            usage.last_used = now;
+        }
+
+        let file = self.file.clone();
+        let uses = self.uses.clone();
+        let writing = self.writing.clone();
+        background.spawn(
+            "save_palette_frecency",
+            move || {
+                // Each write saves the uses as they are by then, so the
+                // last one to land has them all
+                let _writing = writing.lock();
+                let json = serde_json::to_vec_pretty(&*uses.lock()).map_err(|e| e.to_string())?;
+                file.parent()
+                    .map_or(Ok(()), fs::create_dir_all)
+                    .and_then(|_| fs::write(&file, json))
+                    .map_err(|e| format!("Failed to save command uses to {:?}: {}", file, e))
+            },
+            |result| {
+                if let Err(e) = result {
+                    warn!("{}", e);
+                }
+            },
+        );
+    }
+
+    /// Uses of `command` so far
+    pub fn usage(&self, command: &str) -> Option<Usage> {
+        self.ensure_loaded();
+        self.uses.lock().get(command).copied()
+    }
+
+    /// Ranking bonus of `command` now
+    pub fn bonus(&self, command: &str) -> i32 {
+        self.bonus_at(command, now_secs())
+    }
+
+    /// Ranking bonus of `command` at `now` (seconds since the Unix epoch)
+    pub fn bonus_at(&self, command: &str, now: u64) -> i32 {
+        self.usage(command)
+            .map_or(0, |usage| frecency_bonus(frecency(&usage, now)))
+    }
+}
@@ -0,0 +1,117 @@
+//! Fuzzy matching for the command palette.
+//!
+//! A query matches a text when its characters appear in the text in order,
+//! ignoring case and spaces in the query: `ocb` matches "Open Chart
+//! Builder". Matches are scored the way skim and nucleo score them:
+//! characters at the start of words and runs of consecutive characters
+//! count for more, and characters skipped between matches count against.
+//! Of all the ways a query can match, the best scoring one is taken.
+//!
+//! Matches so scattered that the skipped characters outweigh the bonuses
+//! are no match at all, so a long query doesn't match every long text.
+//! Plain substrings always match.
+
+/// Score of each matched character
+const MATCH: i32 = 16;
+/// Extra for a character at the start of the text
+const START_BONUS: i32 = 32;
+/// Extra for a character at the start of a word, or a capital in camelCase
+const WORD_BONUS: i32 = 24;
+/// Extra for a character right after the previous match
+const CONSECUTIVE_BONUS: i32 = 16;
+/// Cost of each character skipped between two matches
+const GAP_PENALTY: i32 = 1;
+
+/// Bonus for matching the character at `index`
+fn position_bonus(chars: &[char], index: usize) -> i32 {
+    if index == 0 {
+        return START_BONUS;
+    }
+    let (prev, current) = (chars[index - 1], chars[index]);
+    let after_separator = !prev.is_alphanumeric();
+    let camel_case = prev.is_lowercase() && current.is_uppercase();
+    let digit_start = !prev.is_ascii_digit() && current.is_ascii_digit();
+    if after_separator || camel_case || digit_start {
+        WORD_BONUS
+    } else {
+        0
+    }
+}
+
+/// Score of the best match of `query` in `text`, or `None` if it doesn't
+/// match. Higher is better; an empty query matches everything with 0.
+pub fn fuzzy_score(query: &str, text: &str) -> Option<i32> {
+    let query: Vec<char> = query
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .flat_map(char::to_lowercase)
+        .collect();
+    if query.is_empty() {
+        return Some(0);
+    }
+    let chars: Vec<char> = text.chars().collect();
+    let lower: Vec<char> = chars
+        .iter()
+        .map(|c| c.to_lowercase().next().unwrap_or(*c))
+        .collect();
+    if query.len() > lower.len() {
+        return None;
+    }
+
+    // best[j]: best score with the current query character matched at j
+    let mut best: Vec<Option<i32>> = lower
+        .iter()
+        .enumerate()
+        .map(|(j, &c)| (c == query[0]).then(|| MATCH + position_bonus(&chars, j)))
+        .collect();
+
+    for &q in &query[1..] {
+        let mut next = vec![None; lower.len()];
+        // Best of best[k] + k * GAP_PENALTY over k < j - 1, so a gap from
+        // k to j costs (j - k - 1) * GAP_PENALTY
+        let mut gapped: Option<i32> = None;
+        for j in 1..lower.len() {
+            if j >= 2 {
+                if let Some(score) = best[j - 2] {
+                    let candidate = score + (j as i32 - 2) * GAP_PENALTY;
+                    gapped = Some(gapped.map_or(candidate, |g| g.max(candidate)));
+                }
+            }
+            if lower[j] != q {
+                continue;
+            }
+            let consecutive = best[j - 1].map(|score| score + CONSECUTIVE_BONUS);
+            let after_gap = gapped.map(|g| g - (j as i32 - 1) * GAP_PENALTY);
+            let previous = match (consecutive, after_gap) {
+                (Some(a), Some(b)) => Some(a.max(b)),
+                (a, b) => a.or(b),
+            };
+            next[j] = previous.map(|score| score + MATCH + position_bonus(&chars, j));
+        }
+        best = next;
+    }
+
+    best.into_iter()
+        .flatten()
+        .max()
+        .filter(|&score| score >= query.len() as i32 * MATCH)
+}
+
+/// Best score of `query` against any of `texts`
+pub fn best_fuzzy_score(query: &str, texts: &[&str]) -> Option<i32> {
+    texts
+        .iter()
+        .filter_map(|text| fuzzy_score(query, text))
+        .max()
+}
+
+/// Rank of a command as (score, tiebreak): the best score of its `names`,
+/// on the same scale as item names, and the score of its `description`,
+/// which only orders commands whose names score the same. A command
+/// matching by its description alone scores 0, below every name that
+/// matches.
+pub fn command_rank(query: &str, names: &[&str], description: &str) -> Option<(i32, i32)> {
+    let tiebreak = fuzzy_score(query, description);
+    let score = best_fuzzy_score(query, names).or(tiebreak.map(|_| 0))?;
+    Some((score, tiebreak.unwrap_or(0)))
+}
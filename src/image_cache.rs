@@ -0,0 +1,284 @@
+//! Downscaled image thumbnails for the canvas.
+//!
+//! Photos straight from a camera are often 4000px or more across, but an
+//! image item is rarely more than a few hundred pixels on screen. Decoding
+//! and drawing the original at every frame wastes memory and GPU time, so
+//! each image gets a small set of mip levels (`MIP_LEVELS`, longest side in
+//! pixels) written to a cache directory on disk.
+//!
+//! The renderer asks [`ImageThumbnailCache::source_for`] which file to draw
+//! for an item's on-screen size. The smallest level that still covers the
+//! displayed size is used; the original is shown while levels are being
+//! generated, when the image is smaller than the level, and when zoomed in
+//! past the largest level.
+//!
+//! Cache files are named after a hash of the image's path, modification time
+//! and size, so editing the file produces a new set of levels. Files are
+//! re-checked at most once per `RESTAT_INTERVAL` and stale levels are deleted
+//! when they are replaced.
+
+use crate::background::BackgroundExecutor;
+use crate::error::MediaError;
+use image::imageops::FilterType;
+use image::{ImageError, ImageFormat};
+use parking_lot::Mutex;
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use tracing::{debug, warn};
+
+/// Longest side in pixels of each cached level, smallest first
+pub const MIP_LEVELS: [u32; 3] = [256, 512, 1024];
+
+/// How often a cached image's file is checked for changes
+const RESTAT_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Modification time and length of an image file, used to spot edits
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FileStamp {
+    pub modified: Option<SystemTime>,
+    pub len: u64,
+}
+
+impl FileStamp {
+    /// Read the stamp of a file, or `None` if it can't be read
+    pub fn of(path: &Path) -> Option<Self> {
+        let metadata = fs::metadata(path).ok()?;
+        Some(Self {
+            modified: metadata.modified().ok(),
+            len: metadata.len(),
+        })
+    }
+}
+
+/// The smallest level that covers `display_px` on screen, or `None` if the
+/// original should be drawn
+pub fn pick_level(display_px: f32) -> Option<u32> {
+    MIP_LEVELS
+        .iter()
+        .copied()
+        .find(|&level| level as f32 >= display_px)
+}
+
+/// Cache key for an image file in a given state
+pub fn cache_key(path: &Path, stamp: &FileStamp) -> String {
+    let canonical = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+    let modified = stamp
+        .modified
+        .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+        .map(|d| d.as_nanos())
+        .unwrap_or_default();
+
+    let mut hasher = Sha256::new();
+    hasher.update(canonical.to_string_lossy().as_bytes());
+    hasher.update(modified.to_le_bytes());
+    hasher.update(stamp.len.to_le_bytes());
+    let hash = format!("{:x}", hasher.finalize());
+    hash[..16].to_string()
+}
+
+/// Path of one cached level
+pub fn mip_path(dir: &Path, key: &str, level: u32) -> PathBuf {
+    dir.join(format!("{}_{}.png", key, level))
+}
+
+/// Levels worth generating for an image: those smaller than its longest side
+pub fn levels_for(dimensions: (u32, u32)) -> Vec<u32> {
+    let longest = dimensions.0.max(dimensions.1);
+    MIP_LEVELS
+        .iter()
+        .copied()
+        .filter(|&level| level < longest)
+        .collect()
+}
+
+/// Write any missing levels for `source` into `dir` and return the levels
+/// available. Levels already on disk from an earlier run are reused without
+/// decoding the image.
+pub fn ensure_mips(source: &Path, dir: &Path, key: &str) -> Result<Vec<u32>, MediaError> {
+    let levels = levels_for(image::image_dimensions(source)?);
+    let missing: Vec<u32> = levels
+        .iter()
+        .copied()
+        .filter(|&level| !mip_path(dir, key, level).exists())
+        .collect();
+    if missing.is_empty() {
+        return Ok(levels);
+    }
+
+    fs::create_dir_all(dir).map_err(ImageError::IoError)?;
+    let image = image::open(source)?;
+    for level in missing {
+        let path = mip_path(dir, key, level);
+        // Write under a temporary name so a half-written file is never drawn
+        let partial = path.with_extension("partial");
+        image
+            .resize(level, level, FilterType::Triangle)
+            .save_with_format(&partial, ImageFormat::Png)?;
+        fs::rename(&partial, &path).map_err(ImageError::IoError)?;
+    }
+
+    debug!("Generated image thumbnails for {:?}", source);
+    Ok(levels)
+}
+
+/// Delete every cached level for a key
+pub fn remove_mips(dir: &Path, key: &str) {
+    for level in MIP_LEVELS {
+        let path = mip_path(dir, key, level);
+        if path.exists() {
+            if let Err(e) = fs::remove_file(&path) {
+                warn!("Failed to remove image thumbnail {:?}: {}", path, e);
+            }
+        }
+    }
+}
+
+/// Default cache directory for image thumbnails
+pub fn default_cache_dir() -> PathBuf {
+    dirs::cache_dir()
+        .unwrap_or_else(std::env::temp_dir)
+        .join("humanboard")
+        .join("image_thumbnails")
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum MipState {
+    Pending,
+    Ready(Vec<u32>),
+    Failed,
+}
+
+#[derive(Debug)]
+struct CacheEntry {
+    stamp: FileStamp,
+    key: String,
+    checked_at: Instant,
+    state: MipState,
+}
+
+/// Tracks the cached levels of every image drawn on the canvas and
+/// generates missing ones on the background executor
+#[derive(Clone)]
+pub struct ImageThumbnailCache {
+    dir: PathBuf,
+    entries: Arc<Mutex<HashMap<PathBuf, CacheEntry>>>,
+}
+
+impl ImageThumbnailCache {
+    pub fn new(dir: PathBuf) -> Self {
+        Self {
+            dir,
+            entries: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    /// The file to draw for an image shown `display_px` pixels across.
+    ///
+    /// Starts generating levels the first time an image is seen or after
+    /// its file changes, and returns the original until they are ready.
+    pub fn source_for(
+        &self,
+        path: &Path,
+        display_px: f32,
+        background: &BackgroundExecutor,
+    ) -> PathBuf {
+        let Some(level) = pick_level(display_px) else {
+            return path.to_path_buf();
+        };
+
+        let mut entries = self.entries.lock();
+        let fresh = entries
+            .get(path)
+            .is_some_and(|entry| entry.checked_at.elapsed() < RESTAT_INTERVAL);
+
+        if !fresh {
+            let Some(stamp) = FileStamp::of(path) else {
+                entries.remove(path);
+                return path.to_path_buf();
+            };
+
+            match entries.get_mut(path) {
+                Some(entry) if entry.stamp == stamp => entry.checked_at = Instant::now(),
+                previous => {
+                    let stale_key = previous.map(|entry| entry.key.clone());
+                    let key = cache_key(path, &stamp);
+                    entries.insert(
+                        path.to_path_buf(),
+                        CacheEntry {
+                            stamp,
+                            key: key.clone(),
+                            checked_at: Instant::now(),
+                            state: MipState::Pending,
+                        },
+                    );
+                    drop(entries);
+                    self.generate(path.to_path_buf(), key, stale_key, background);
+                    return path.to_path_buf();
+                }
+            }
+        }
+
+        match entries.get(path) {
+            Some(CacheEntry {
+                key,
+                state: MipState::Ready(levels),
+                ..
+            }) if levels.contains(&level) => mip_path(&self.dir, key, level),
+            _ => path.to_path_buf(),
+        }
+    }
+
+    /// Whether any image is still waiting for its levels
+    pub fn has_pending(&self) -> bool {
+        self.entries
+            .lock()
+            .values()
+            .any(|entry| entry.state == MipState::Pending)
+    }
+
+    fn generate(
+        &self,
+        path: PathBuf,
+        key: String,
+        stale_key: Option<String>,
+        background: &BackgroundExecutor,
+    ) {
+        let dir = self.dir.clone();
+        let entries = self.entries.clone();
+        let (source, work_key) = (path.clone(), key.clone());
+
+        background.spawn(
+            "image_thumbnails",
+            move || {
+                if let Some(stale_key) = stale_key {
+                    remove_mips(&dir, &stale_key);
+                }
+                ensure_mips(&source, &dir, &work_key).map_err(|e| e.to_string())
+            },
+            move |result| {
+                let mut entries = entries.lock();
+                // The file may have changed again while this was running
+                let Some(entry) = entries.get_mut(&path).filter(|entry| entry.key == key) else {
+                    return;
+                };
+                entry.state = match result {
+                    Ok(levels) => MipState::Ready(levels),
+                    Err(e) => {
+                        warn!("Failed to generate image thumbnails for {:?}: {}", path, e);
+                        MipState::Failed
+                    }
+                };
+            },
+        );
+    }
+}
+
+impl Default for ImageThumbnailCache {
+    fn default() -> Self {
+        Self::new(default_cache_dir())
+    }
+}
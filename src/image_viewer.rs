@@ -0,0 +1,259 @@
+//! Image viewer for the preview panel.
+//!
+//! An image tab shows its image fitted to the pane or at a zoom of its own,
+//! and can be panned by dragging. [`ImageView`] holds that zoom and pan and
+//! maps points of the pane onto image pixels, so the pixel under the mouse
+//! can be inspected. Pixels are decoded once on the background executor and
+//! kept in [`IMAGE_PIXELS`], shared by every window.
+
+use crate::background::BackgroundExecutor;
+use image::RgbaImage;
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tracing::warn;
+
+/// Smallest zoom, as a fraction of the image's size
+pub const MIN_IMAGE_ZOOM: f32 = 0.05;
+
+/// Largest zoom, as a multiple of the image's size
+pub const MAX_IMAGE_ZOOM: f32 = 32.0;
+
+/// Zoom change of one step of the zoom buttons
+pub const IMAGE_ZOOM_STEP: f32 = 1.25;
+
+/// Whether a file is an image the viewer can show. Vector images have no
+/// pixels to inspect, so SVGs are left out.
+pub fn is_viewable_image(path: &Path) -> bool {
+    path.extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(|ext| {
+            matches!(
+                ext.to_lowercase().as_str(),
+                "jpg" | "jpeg" | "png" | "gif" | "webp" | "bmp"
+            )
+        })
+}
+
+/// How an image is scaled in the viewer
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ImageZoom {
+    /// Shrunk to fit the pane, but never enlarged past 100%
+    Fit,
+    /// A fixed scale, where 1.0 is one image pixel per screen pixel
+    Scale(f32),
+}
+
+/// Zoom and pan of an image tab
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ImageView {
+    pub zoom: ImageZoom,
+    /// Offset of the image's center from the pane's center
+    pub pan: (f32, f32),
+}
+
+impl Default for ImageView {
+    fn default() -> Self {
+        Self {
+            zoom: ImageZoom::Fit,
+            pan: (0.0, 0.0),
+        }
+    }
+}
+
+/// Scale that fits an image inside a viewport, at most 1.0
+pub fn fit_scale(image: (u32, u32), viewport: (f32, f32)) -> f32 {
+    if image.0 == 0 || image.1 == 0 {
+        return 1.0;
+    }
+    let scale = (viewport.0 / image.0 as f32).min(viewport.1 / image.1 as f32);
+    if scale > 0.0 { scale.min(1.0) } else { 1.0 }
+}
+
+impl ImageView {
+    /// Scale the image is drawn at
+    pub fn scale(&self, image: (u32, u32), viewport: (f32, f32)) -> f32 {
+        match self.zoom {
+            ImageZoom::Fit => fit_scale(image, viewport),
+            ImageZoom::Scale(scale) => scale,
+        }
+    }
+
+    /// Fit the image to the pane and center it
+    pub fn fit(&mut self) {
+        *self = Self::default();
+    }
+
+    /// Show the image at 100%, centered
+    pub fn actual_size(&mut self) {
+        self.zoom = ImageZoom::Scale(1.0);
+        self.pan = (0.0, 0.0);
+    }
+
+    /// Multiply the zoom by `factor`, keeping the point of the image under
+    /// `anchor` (relative to the viewport's top-left corner) in place
+    pub fn zoom_by(
+        &mut self,
+        factor: f32,
+        anchor: (f32, f32),
+        image: (u32, u32),
+        viewport: (f32, f32),
+    ) {
+        let old = self.scale(image, viewport);
+        let new = (old * factor).clamp(MIN_IMAGE_ZOOM, MAX_IMAGE_ZOOM);
+        let ratio = new / old;
+        let center = (viewport.0 / 2.0 + self.pan.0, viewport.1 / 2.0 + self.pan.1);
+        let new_center = (
+            anchor.0 - (anchor.0 - center.0) * ratio,
+            anchor.1 - (anchor.1 - center.1) * ratio,
+        );
+        self.zoom = ImageZoom::Scale(new);
+        self.pan = (
+            new_center.0 - viewport.0 / 2.0,
+            new_center.1 - viewport.1 / 2.0,
+        );
+    }
+
+    /// Move the image by a screen distance. A fitted image stays centered.
+    pub fn pan_by(&mut self, delta: (f32, f32)) {
+        if self.zoom == ImageZoom::Fit {
+            return;
+        }
+        self.pan.0 += delta.0;
+        self.pan.1 += delta.1;
+    }
+
+    /// Where the image is drawn as (x, y, width, height), relative to the
+    /// viewport's top-left corner
+    pub fn image_rect(&self, image: (u32, u32), viewport: (f32, f32)) -> (f32, f32, f32, f32) {
+        let scale = self.scale(image, viewport);
+        let width = image.0 as f32 * scale;
+        let height = image.1 as f32 * scale;
+        (
+            viewport.0 / 2.0 + self.pan.0 - width / 2.0,
+            viewport.1 / 2.0 + self.pan.1 - height / 2.0,
+            width,
+            height,
+        )
+    }
+
+    /// Image pixel at `position` (relative to the viewport's top-left
+    /// corner), or None outside the image
+    pub fn pixel_at(
+        &self,
+        position: (f32, f32),
+        image: (u32, u32),
+        viewport: (f32, f32),
+    ) -> Option<(u32, u32)> {
+        let (x, y, width, height) = self.image_rect(image, viewport);
+        let scale = self.scale(image, viewport);
+        if position.0 < x || position.1 < y || position.0 >= x + width || position.1 >= y + height {
+            return None;
+        }
+        let px = ((position.0 - x) / scale) as u32;
+        let py = ((position.1 - y) / scale) as u32;
+        Some((px.min(image.0 - 1), py.min(image.1 - 1)))
+    }
+}
+
+/// A color as `#RRGGBB`, or `#RRGGBBAA` when not fully opaque
+pub fn format_pixel_color(rgba: [u8; 4]) -> String {
+    let [r, g, b, a] = rgba;
+    if a == 255 {
+        format!("#{:02X}{:02X}{:02X}", r, g, b)
+    } else {
+        format!("#{:02X}{:02X}{:02X}{:02X}", r, g, b, a)
+    }
+}
+
+/// Decoded images shared by every window
+pub static IMAGE_PIXELS: Lazy<ImagePixelCache> = Lazy::new(ImagePixelCache::default);
+
+#[derive(Debug, Clone)]
+enum PixelState {
+    Pending,
+    Ready(Arc<RgbaImage>),
+    Failed,
+}
+
+/// Pixels of images open in the preview panel, decoded on the background
+/// executor the first time each image is shown
+#[derive(Clone, Default)]
+pub struct ImagePixelCache {
+    entries: Arc<Mutex<HashMap<PathBuf, PixelState>>>,
+}
+
+impl ImagePixelCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Decode an image in the background, unless it has been already
+    pub fn load(&self, path: &Path, background: &BackgroundExecutor) {
+        {
+            let mut entries = self.entries.lock();
+            if entries.contains_key(path) {
+                return;
+            }
+            entries.insert(path.to_path_buf(), PixelState::Pending);
+        }
+
+        let entries = self.entries.clone();
+        let source = path.to_path_buf();
+        let path = path.to_path_buf();
+        background.spawn(
+            "image_pixels",
+            move || {
+                image::open(&source)
+                    .map(|image| image.to_rgba8())
+                    .map_err(|e| e.to_string())
+            },
+            move |result| {
+                let state = match result {
+                    Ok(pixels) => PixelState::Ready(Arc::new(pixels)),
+                    Err(e) => {
+                        warn!("Failed to decode {:?}: {}", path, e);
+                        PixelState::Failed
+                    }
+                };
+                entries.lock().insert(path, state);
+            },
+        );
+    }
+
+    /// An image's pixels, or `None` while it is being decoded or if it
+    /// couldn't be
+    pub fn get(&self, path: &Path) -> Option<Arc<RgbaImage>> {
+        match self.entries.lock().get(path) {
+            Some(PixelState::Ready(pixels)) => Some(pixels.clone()),
+            _ => None,
+        }
+    }
+
+    /// Whether an image couldn't be decoded
+    pub fn failed(&self, path: &Path) -> bool {
+        matches!(self.entries.lock().get(path), Some(PixelState::Failed))
+    }
+
+    /// Store pixels decoded elsewhere
+    pub fn insert(&self, path: PathBuf, pixels: RgbaImage) {
+        self.entries
+            .lock()
+            .insert(path, PixelState::Ready(Arc::new(pixels)));
+    }
+
+    /// Forget an image's pixels, e.g. when its tab is closed
+    pub fn forget(&self, path: &Path) {
+        self.entries.lock().remove(path);
+    }
+
+    /// Whether any image is still being decoded
+    pub fn has_pending(&self) -> bool {
+        self.entries
+            .lock()
+            .values()
+            .any(|state| matches!(state, PixelState::Pending))
+    }
+}
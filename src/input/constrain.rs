@@ -0,0 +1,49 @@
+//! Shift-constrained dragging and resizing.
+//!
+//! Holding Shift while dragging keeps the items on the axis they have moved
+//! furthest along since the drag started. Holding Shift while resizing keeps
+//! the item's original aspect ratio, so images and videos don't stretch.
+//!
+//! All coordinates here are canvas coordinates.
+
+/// Axis a Shift-drag is constrained to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DragAxis {
+    /// Only x changes
+    Horizontal,
+    /// Only y changes
+    Vertical,
+}
+
+/// Axis with the larger movement in `delta` (horizontal on a tie)
+pub fn dominant_axis(delta: (f32, f32)) -> DragAxis {
+    if delta.1.abs() > delta.0.abs() {
+        DragAxis::Vertical
+    } else {
+        DragAxis::Horizontal
+    }
+}
+
+/// `target` with the coordinate across `axis` reset to `start`
+pub fn constrain_to_axis(start: (f32, f32), target: (f32, f32), axis: DragAxis) -> (f32, f32) {
+    match axis {
+        DragAxis::Horizontal => (target.0, start.1),
+        DragAxis::Vertical => (start.0, target.1),
+    }
+}
+
+/// Size with the aspect ratio of `start_size` for a resize to `size`.
+///
+/// The axis that grew more relative to the start wins, and neither side
+/// ends up smaller than `min_size`.
+pub fn keep_aspect_ratio(start_size: (f32, f32), size: (f32, f32), min_size: f32) -> (f32, f32) {
+    let (start_w, start_h) = start_size;
+    if start_w <= 0.0 || start_h <= 0.0 {
+        return size;
+    }
+    let scale = (size.0 / start_w)
+        .max(size.1 / start_h)
+        .max(min_size / start_w)
+        .max(min_size / start_h);
+    (start_w * scale, start_h * scale)
+}
@@ -11,8 +11,19 @@
 //! Enable profiling with `cargo build --features profiling` to see timing.
 
 use crate::app::{Humanboard, SplitDirection};
-use crate::constants::{DOCK_WIDTH, HEADER_HEIGHT, MAX_FONT_SIZE, MIN_ARROW_SIZE, MIN_FONT_SIZE, MIN_ITEM_SIZE};
+use crate::board::Board;
+use crate::constants::{
+    MARKDOWN_EXPAND_HEIGHT, MAX_FONT_SIZE, MIN_ARROW_SIZE, MIN_FONT_SIZE, MIN_ITEM_SIZE,
+};
+use crate::input::constrain::{DragAxis, constrain_to_axis, dominant_axis, keep_aspect_ratio};
 use crate::input::coords::{CoordinateContext, CoordinateConverter};
+use crate::input::group_resize::{MIN_GROUP_SCALE, group_scale, scale_bounds};
+use crate::input::guides::{
+    Alignment, Bounds, GUIDE_SEARCH_DISTANCE, GUIDE_SNAP_DISTANCE, GuideAxis, align, union_bounds,
+};
+use crate::input::lasso::LASSO_POINT_SPACING;
+use crate::input::resize::{ResizeHandle, snap_resized_length};
+use crate::input::snap::{active_grid_size, snap_to_grid, snapping_suppressed};
 use crate::profile_scope;
 use crate::types::{ArrowDirection, ItemContent};
 use gpui::*;
@@ -28,6 +39,22 @@ impl Humanboard {
 
         self.canvas.last_drop_pos = Some(event.position);
 
+        // A preview tab dragged out over the canvas
+        if self.preview.dragging_tab.is_some() || self.preview.tab_drag_pending.is_some() {
+            self.update_tab_drag_position(event.position, cx);
+            self.update_tab_drag_over_canvas(event.position, window, cx);
+            return;
+        }
+
+        self.update_hover(event.position, window, cx);
+
+        // Handle pane splitter dragging (between split panes)
+        if self.canvas.input_state.is_pane_splitter_dragging() {
+            self.drag_pane_split(event.position, window);
+            cx.notify();
+            return;
+        }
+
         // Handle splitter dragging (canvas/preview split)
         if self.canvas.input_state.is_splitter_dragging() {
             if let Some(ref mut preview) = self.preview.panel {
@@ -51,64 +78,19 @@ impl Humanboard {
             return;
         }
 
-        // Handle pane splitter dragging (between split panes)
-        if self.canvas.input_state.is_pane_splitter_dragging() {
-            if let Some(ref mut preview) = self.preview.panel {
-                if preview.is_pane_split {
-                    let bounds = window.bounds();
-                    let window_width = f32::from(bounds.size.width);
-                    let window_height = f32::from(bounds.size.height);
-                    let mouse_x = f32::from(event.position.x);
-                    let mouse_y = f32::from(event.position.y);
-
-                    let header_height = HEADER_HEIGHT;
-                    let footer_height = crate::constants::FOOTER_HEIGHT;
-                    let dock_width = DOCK_WIDTH;
-
-                    let (panel_start, panel_size) = match preview.split {
-                        SplitDirection::Vertical => {
-                            let panel_x =
-                                dock_width + (window_width - dock_width) * (1.0 - preview.size);
-                            let panel_width = (window_width - dock_width) * preview.size;
-                            if preview.pane_split_horizontal {
-                                let panel_y = header_height;
-                                let panel_height = window_height - header_height - footer_height;
-                                (panel_y, panel_height)
-                            } else {
-                                (panel_x, panel_width)
-                            }
-                        }
-                        SplitDirection::Horizontal => {
-                            let panel_y = header_height
-                                + (window_height - header_height - footer_height)
-                                    * (1.0 - preview.size);
-                            let panel_height =
-                                (window_height - header_height - footer_height) * preview.size;
-                            if preview.pane_split_horizontal {
-                                (panel_y, panel_height)
-                            } else {
-                                (dock_width, window_width - dock_width)
-                            }
-                        }
-                    };
-
-                    let new_ratio = if preview.pane_split_horizontal {
-                        ((mouse_y - panel_start) / panel_size).clamp(0.2, 0.8)
-                    } else {
-                        ((mouse_x - panel_start) / panel_size).clamp(0.2, 0.8)
-                    };
-
-                    preview.pane_ratio = new_ratio;
-                    cx.notify();
-                }
-            }
-            return;
-        }
-
         let Some(ref mut board) = self.canvas.board else {
             return;
         };
 
+        // Grid to snap dragged and resized items to (Cmd skips snapping)
+        let grid_size = if self.canvas.input_state.is_resizing()
+            || self.canvas.input_state.is_dragging_items()
+        {
+            active_grid_size(&event.modifiers)
+        } else {
+            None
+        };
+
         // Handle item resizing
         if let Some(item_id) = self.canvas.input_state.resizing_item() {
             profile_scope!("item_resize");
@@ -116,11 +98,25 @@ impl Humanboard {
             if let Some(start_size) = self.canvas.input_state.resize_start_size() {
                 if let Some(start_pos) = self.canvas.input_state.resize_start_pos() {
                     let zoom = board.zoom;
-                    let delta_x = f32::from(event.position.x - start_pos.x) / zoom;
-                    let delta_y = f32::from(event.position.y - start_pos.y) / zoom;
+                    let handle = self
+                        .canvas
+                        .input_state
+                        .resize_handle()
+                        .unwrap_or(ResizeHandle::BottomRight);
+                    // How much each side grows: left/top handles grow when dragged
+                    // left/up, and edge handles leave the other side alone
+                    let (sign_x, sign_y) = handle.signs();
+                    let delta_x = f32::from(event.position.x - start_pos.x) / zoom * sign_x;
+                    let delta_y = f32::from(event.position.y - start_pos.y) / zoom * sign_y;
 
                     let item_type = board.get_item(item_id).map(|item| match &item.content {
-                        ItemContent::Markdown { .. } => "markdown",
+                        // Dragged tall enough, a markdown chip expands to show its
+                        // document and resizes freely
+                        ItemContent::Markdown { .. }
+                            if start_size.1 + delta_y < MARKDOWN_EXPAND_HEIGHT =>
+                        {
+                            "markdown"
+                        }
                         ItemContent::TextBox { .. } => "textbox",
                         ItemContent::Arrow { end_offset, .. } => {
                             // Use ArrowDirection for type-safe quadrant encoding
@@ -132,10 +128,30 @@ impl Humanboard {
 
                     let original_font_size = self.canvas.input_state.resize_start_font_size();
 
+                    let start_position = self.canvas.input_state.resize_start_position();
+                    let start_bounds =
+                        start_position.map(|(x, y)| (x, y, start_size.0, start_size.1));
+
                     let (new_width, new_height) = match item_type.as_deref() {
                         Some("markdown") => {
                             const MD_ASPECT_RATIO: f32 = 200.0 / 36.0;
-                            let width = (start_size.0 + delta_x).max(100.0);
+                            // Top and bottom handles drive the width through the fixed ratio
+                            let growth = if sign_x == 0.0 {
+                                delta_y * MD_ASPECT_RATIO
+                            } else {
+                                delta_x
+                            };
+                            let mut width = (start_size.0 + growth).max(100.0);
+                            if let (Some(grid), Some((x, _))) = (grid_size, start_position) {
+                                width = snap_resized_length(
+                                    sign_x,
+                                    x,
+                                    start_size.0,
+                                    width,
+                                    grid,
+                                    100.0,
+                                );
+                            }
                             let height = width / MD_ASPECT_RATIO;
                             (width, height)
                         }
@@ -147,9 +163,34 @@ impl Humanboard {
                             let height = (start_size.1 * scale).max(MIN_ARROW_SIZE);
                             (width, height)
                         }
+                        // Shift keeps the aspect ratio (grid snapping would break it)
+                        _ if event.modifiers.shift => keep_aspect_ratio(
+                            start_size,
+                            (start_size.0 + delta_x, start_size.1 + delta_y),
+                            MIN_ITEM_SIZE,
+                        ),
                         _ => {
-                            let width = (start_size.0 + delta_x).max(MIN_ITEM_SIZE);
-                            let height = (start_size.1 + delta_y).max(MIN_ITEM_SIZE);
+                            let mut width = (start_size.0 + delta_x).max(MIN_ITEM_SIZE);
+                            let mut height = (start_size.1 + delta_y).max(MIN_ITEM_SIZE);
+                            // Keep the dragged edges on the grid
+                            if let (Some(grid), Some((x, y))) = (grid_size, start_position) {
+                                width = snap_resized_length(
+                                    sign_x,
+                                    x,
+                                    start_size.0,
+                                    width,
+                                    grid,
+                                    MIN_ITEM_SIZE,
+                                );
+                                height = snap_resized_length(
+                                    sign_y,
+                                    y,
+                                    start_size.1,
+                                    height,
+                                    grid,
+                                    MIN_ITEM_SIZE,
+                                );
+                            }
                             (width, height)
                         }
                     };
@@ -157,6 +198,10 @@ impl Humanboard {
                     if let Some(item) = board.get_item_mut(item_id) {
                         let scale = new_height / start_size.1;
                         item.size = (new_width, new_height);
+                        // Top and left handles move the item so the opposite edges stay put
+                        if let Some(bounds) = start_bounds {
+                            item.position = handle.anchored_position(bounds, item.size);
+                        }
 
                         if let ItemContent::Arrow { end_offset, .. } = &mut item.content {
                             // Use ArrowDirection for type-safe sign extraction
@@ -171,14 +216,67 @@ impl Humanboard {
                             }
                         }
                     }
+                    board.update_spatial_index(item_id);
                     board.mark_dirty();
                     cx.notify();
                 }
             }
-        } else if let Some(item_id) = self.canvas.input_state.dragging_item() {
+        } else if let Some((handle, start_bounds, start_pos, items)) =
+            self.canvas.input_state.group_resize()
+        {
+            // Scale every selected item about the opposite corner of the selection
+            profile_scope!("group_resize");
+
+            let delta = (
+                f32::from(event.position.x - start_pos.x) / board.zoom,
+                f32::from(event.position.y - start_pos.y) / board.zoom,
+            );
+            let scale = group_scale(start_bounds, handle, delta, MIN_GROUP_SCALE);
+            let anchor = handle.anchor(start_bounds);
+
+            for original in items {
+                let Some(item) = board.get_item_mut(original.id) else {
+                    continue;
+                };
+                let (x, y, w, h) = scale_bounds(original.bounds, anchor, scale);
+                item.position = (x, y);
+                item.size = (w, h);
+
+                match &mut item.content {
+                    ItemContent::Arrow { end_offset, .. } => {
+                        let (sign_x, sign_y) = ArrowDirection::from_offset(*end_offset).to_signs();
+                        *end_offset = (w * sign_x, h * sign_y);
+                    }
+                    ItemContent::TextBox { font_size, .. } => {
+                        if let Some(orig_size) = original.font_size {
+                            *font_size = (orig_size * scale).clamp(MIN_FONT_SIZE, MAX_FONT_SIZE);
+                        }
+                    }
+                    _ => {}
+                }
+                board.update_spatial_index(original.id);
+            }
+            board.mark_dirty();
+            cx.notify();
+        } else if let Some(mut item_id) = self.canvas.input_state.dragging_item() {
             // Handle item dragging
             profile_scope!("item_drag");
 
+            // Alt+drag: copy the items in place and drag the copies instead
+            if let Some(primary) = self.canvas.input_state.take_pending_duplicate() {
+                let originals: Vec<u64> = if self.canvas.selected_items.contains(&primary) {
+                    board.unlocked_ids(self.canvas.selected_items.iter().copied())
+                } else {
+                    vec![primary]
+                };
+                let copies = board.duplicate_items(&originals);
+                if let Some(&(_, copy)) = copies.iter().find(|(original, _)| *original == primary) {
+                    item_id = copy;
+                    self.canvas.input_state.set_dragged_item(copy);
+                }
+                self.canvas.selected_items = copies.into_iter().map(|(_, copy)| copy).collect();
+            }
+
             if let Some(offset) = self.canvas.input_state.drag_offset() {
                 let zoom = board.zoom;
                 let ctx = CoordinateContext::new(&board.canvas_offset, zoom);
@@ -186,29 +284,74 @@ impl Humanboard {
                 // Convert mouse position minus offset to canvas coordinates
                 let adjusted_pos = point(event.position.x - offset.x, event.position.y - offset.y);
                 let canvas_pos = CoordinateConverter::screen_to_canvas(adjusted_pos, &ctx);
-                let new_x = f32::from(canvas_pos.x);
-                let new_y = f32::from(canvas_pos.y);
+                let mut new_x = f32::from(canvas_pos.x);
+                let mut new_y = f32::from(canvas_pos.y);
+
+                // Shift keeps the drag on the axis it has moved furthest along
+                let axis = match (
+                    event.modifiers.shift,
+                    self.canvas.input_state.drag_start_position(),
+                ) {
+                    (true, Some(start)) => {
+                        let axis = dominant_axis((new_x - start.0, new_y - start.1));
+                        (new_x, new_y) = constrain_to_axis(start, (new_x, new_y), axis);
+                        Some(axis)
+                    }
+                    _ => None,
+                };
 
                 let old_pos = board.get_item(item_id).map(|i| i.position);
 
                 if let Some((old_x, old_y)) = old_pos {
-                    let delta_x = new_x - old_x;
-                    let delta_y = new_y - old_y;
-
-                    if self.canvas.selected_items.contains(&item_id) && self.canvas.selected_items.len() > 1 {
-                        // Group move
-                        let selected_ids: Vec<u64> = self.canvas.selected_items.iter().copied().collect();
-                        for id in selected_ids {
-                            if let Some(item) = board.get_item_mut(id) {
-                                item.position.0 += delta_x;
-                                item.position.1 += delta_y;
-                            }
-                        }
+                    let is_group_move = self.canvas.selected_items.contains(&item_id)
+                        && self.canvas.selected_items.len() > 1;
+                    // Locked items in the selection stay where they are
+                    let moving_ids: Vec<u64> = if is_group_move {
+                        board.unlocked_ids(self.canvas.selected_items.iter().copied())
+                    } else {
+                        vec![item_id]
+                    };
+
+                    // Align with nearby items first, then fall back to the grid.
+                    // Grid snapping uses the item under the cursor; the rest of a
+                    // group keeps its offsets from it.
+                    let mut alignment = if snapping_suppressed(&event.modifiers) {
+                        Alignment::default()
                     } else {
-                        // Single item move
-                        if let Some(item) = board.get_item_mut(item_id) {
-                            item.position = (new_x, new_y);
+                        align_with_neighbors(board, &moving_ids, (new_x - old_x, new_y - old_y), zoom)
+                    };
+                    // The locked axis stays exactly where the drag started
+                    let (snap_x, snap_y) = match axis {
+                        Some(DragAxis::Horizontal) => {
+                            alignment.dy = None;
+                            alignment.guides.retain(|g| g.axis != GuideAxis::Horizontal);
+                            (grid_size, None)
+                        }
+                        Some(DragAxis::Vertical) => {
+                            alignment.dx = None;
+                            alignment.guides.retain(|g| g.axis != GuideAxis::Vertical);
+                            (None, grid_size)
                         }
+                        None => (grid_size, grid_size),
+                    };
+                    let delta_x = match (alignment.dx, snap_x) {
+                        (Some(dx), _) => new_x - old_x + dx,
+                        (None, Some(grid)) => snap_to_grid(new_x, grid) - old_x,
+                        (None, None) => new_x - old_x,
+                    };
+                    let delta_y = match (alignment.dy, snap_y) {
+                        (Some(dy), _) => new_y - old_y + dy,
+                        (None, Some(grid)) => snap_to_grid(new_y, grid) - old_y,
+                        (None, None) => new_y - old_y,
+                    };
+                    self.canvas.alignment_guides = alignment.guides;
+
+                    for id in moving_ids {
+                        if let Some(item) = board.get_item_mut(id) {
+                            item.position.0 += delta_x;
+                            item.position.1 += delta_y;
+                        }
+                        board.update_spatial_index(id);
                     }
                 }
 
@@ -228,6 +371,10 @@ impl Humanboard {
             // Update marquee selection rectangle
             self.canvas.input_state.set_marquee_current(event.position);
             cx.notify();
+        } else if self.canvas.input_state.is_lasso_selecting() {
+            // Extend the lasso path
+            self.canvas.input_state.push_lasso_point(event.position, LASSO_POINT_SPACING);
+            cx.notify();
         } else if self.tools.drawing_start.is_some() {
             // Update drawing preview position
             self.tools.drawing_current = Some(event.position);
@@ -235,3 +382,32 @@ impl Humanboard {
         }
     }
 }
+
+/// Align dragged items (about to move by `delta`) with the items around them
+fn align_with_neighbors(board: &Board, moving_ids: &[u64], delta: (f32, f32), zoom: f32) -> Alignment {
+    let moving = moving_ids
+        .iter()
+        .filter_map(|&id| board.get_item(id))
+        .map(|item| (item.position.0 + delta.0, item.position.1 + delta.1, item.size.0, item.size.1));
+    let Some(bounds) = union_bounds(moving) else {
+        return Alignment::default();
+    };
+
+    let search = GUIDE_SEARCH_DISTANCE / zoom;
+    let neighbors: Vec<Bounds> = board
+        .query_items_in_rect(
+            bounds.0 - search,
+            bounds.1 - search,
+            bounds.0 + bounds.2 + search,
+            bounds.1 + bounds.3 + search,
+        )
+        .into_iter()
+        .filter(|id| !moving_ids.contains(id))
+        .filter_map(|id| board.get_item(id))
+        // Arrows connect items rather than sit next to them
+        .filter(|item| !matches!(item.content, ItemContent::Arrow { .. }))
+        .map(|item| (item.position.0, item.position.1, item.size.0, item.size.1))
+        .collect();
+
+    align(bounds, &neighbors, GUIDE_SNAP_DISTANCE / zoom)
+}
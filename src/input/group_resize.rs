@@ -0,0 +1,112 @@
+//! Group resize - scaling several selected items together.
+//!
+//! With two or more items selected, a bounding box with a handle on each
+//! corner is drawn around the selection. Dragging a handle scales the whole
+//! selection about the opposite corner: every item's position and size are
+//! multiplied by the same factor, so the arrangement keeps its proportions.
+//!
+//! All coordinates here are canvas coordinates.
+
+use crate::input::guides::Bounds;
+
+/// Size (in screen pixels) of the square handles on the selection box corners
+pub const GROUP_HANDLE_SIZE: f32 = 10.0;
+
+/// Smallest factor a group can be scaled down to
+pub const MIN_GROUP_SCALE: f32 = 0.1;
+
+/// Corner handle of the selection bounding box
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GroupHandle {
+    TopLeft,
+    TopRight,
+    BottomLeft,
+    BottomRight,
+}
+
+impl GroupHandle {
+    pub const ALL: [GroupHandle; 4] = [
+        GroupHandle::TopLeft,
+        GroupHandle::TopRight,
+        GroupHandle::BottomLeft,
+        GroupHandle::BottomRight,
+    ];
+
+    /// Position of this corner on `bounds`
+    pub fn corner(self, (x, y, w, h): Bounds) -> (f32, f32) {
+        match self {
+            GroupHandle::TopLeft => (x, y),
+            GroupHandle::TopRight => (x + w, y),
+            GroupHandle::BottomLeft => (x, y + h),
+            GroupHandle::BottomRight => (x + w, y + h),
+        }
+    }
+
+    /// The opposite corner, which stays fixed while scaling
+    pub fn anchor(self, bounds: Bounds) -> (f32, f32) {
+        let opposite = match self {
+            GroupHandle::TopLeft => GroupHandle::BottomRight,
+            GroupHandle::TopRight => GroupHandle::BottomLeft,
+            GroupHandle::BottomLeft => GroupHandle::TopRight,
+            GroupHandle::BottomRight => GroupHandle::TopLeft,
+        };
+        opposite.corner(bounds)
+    }
+
+    /// Whether dragging this handle to the right/down grows the box (+1) or shrinks it (-1)
+    fn signs(self) -> (f32, f32) {
+        match self {
+            GroupHandle::TopLeft => (-1.0, -1.0),
+            GroupHandle::TopRight => (1.0, -1.0),
+            GroupHandle::BottomLeft => (-1.0, 1.0),
+            GroupHandle::BottomRight => (1.0, 1.0),
+        }
+    }
+}
+
+/// Original state of one item at the start of a group resize
+#[derive(Debug, Clone, PartialEq)]
+pub struct GroupResizeItem {
+    pub id: u64,
+    pub bounds: Bounds,
+    /// Font size for text boxes, scaled along with the box
+    pub font_size: Option<f32>,
+}
+
+/// Handle of `bounds` within `hit_radius` of `point`, if any
+pub fn handle_at(bounds: Bounds, point: (f32, f32), hit_radius: f32) -> Option<GroupHandle> {
+    GroupHandle::ALL.into_iter().find(|handle| {
+        let (cx, cy) = handle.corner(bounds);
+        (point.0 - cx).abs() <= hit_radius && (point.1 - cy).abs() <= hit_radius
+    })
+}
+
+/// Uniform scale factor for dragging `handle` of `start` by `delta`.
+///
+/// The axis the cursor moved further along wins, so the dragged corner
+/// follows the cursor on at least one axis. Never below `min_scale`.
+pub fn group_scale(start: Bounds, handle: GroupHandle, delta: (f32, f32), min_scale: f32) -> f32 {
+    let (_, _, w, h) = start;
+    let (sign_x, sign_y) = handle.signs();
+    let scale_x = if w > 0.0 {
+        (w + delta.0 * sign_x) / w
+    } else {
+        1.0
+    };
+    let scale_y = if h > 0.0 {
+        (h + delta.1 * sign_y) / h
+    } else {
+        1.0
+    };
+    scale_x.max(scale_y).max(min_scale)
+}
+
+/// `bounds` scaled by `scale` about `anchor`
+pub fn scale_bounds((x, y, w, h): Bounds, anchor: (f32, f32), scale: f32) -> Bounds {
+    (
+        anchor.0 + (x - anchor.0) * scale,
+        anchor.1 + (y - anchor.1) * scale,
+        w * scale,
+        h * scale,
+    )
+}
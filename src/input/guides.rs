@@ -0,0 +1,167 @@
+//! Alignment guides (smart guides) for dragging items.
+//!
+//! While items are dragged, the edges and centers of the dragged bounds are
+//! compared with those of nearby items. When one comes within a few pixels of
+//! another, the drag snaps to it and a guide line is drawn along the shared
+//! edge or center, spanning both items.
+//!
+//! All coordinates here are canvas coordinates.
+
+/// Distance (in screen pixels) within which a drag snaps to an alignment
+pub const GUIDE_SNAP_DISTANCE: f32 = 6.0;
+
+/// How far (in screen pixels) around the dragged items to look for neighbors
+pub const GUIDE_SEARCH_DISTANCE: f32 = 800.0;
+
+/// Orientation of a guide line
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GuideAxis {
+    /// A vertical line at a shared x coordinate
+    Vertical,
+    /// A horizontal line at a shared y coordinate
+    Horizontal,
+}
+
+/// A guide line to draw on the canvas
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AlignmentGuide {
+    pub axis: GuideAxis,
+    /// x for vertical guides, y for horizontal guides
+    pub position: f32,
+    /// Where the line starts along its axis
+    pub start: f32,
+    /// Where the line ends along its axis
+    pub end: f32,
+}
+
+/// Axis-aligned bounds as (x, y, width, height)
+pub type Bounds = (f32, f32, f32, f32);
+
+/// Result of aligning dragged bounds against their neighbors
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Alignment {
+    /// Horizontal correction to apply, if the drag snapped on x
+    pub dx: Option<f32>,
+    /// Vertical correction to apply, if the drag snapped on y
+    pub dy: Option<f32>,
+    /// Guide lines for every alignment after snapping
+    pub guides: Vec<AlignmentGuide>,
+}
+
+/// Snap `moving` to the closest edge or center alignment with `neighbors`.
+///
+/// `threshold` is the snap distance in canvas units. Returns the corrections
+/// to apply and the guides to draw for the corrected bounds.
+pub fn align(moving: Bounds, neighbors: &[Bounds], threshold: f32) -> Alignment {
+    let dx = closest_offset(
+        x_anchors(moving),
+        neighbors.iter().map(|&b| x_anchors(b)),
+        threshold,
+    );
+    let dy = closest_offset(
+        y_anchors(moving),
+        neighbors.iter().map(|&b| y_anchors(b)),
+        threshold,
+    );
+
+    let snapped = (
+        moving.0 + dx.unwrap_or(0.0),
+        moving.1 + dy.unwrap_or(0.0),
+        moving.2,
+        moving.3,
+    );
+
+    let mut guides = Vec::new();
+    if dx.is_some() {
+        collect_guides(snapped, neighbors, GuideAxis::Vertical, &mut guides);
+    }
+    if dy.is_some() {
+        collect_guides(snapped, neighbors, GuideAxis::Horizontal, &mut guides);
+    }
+
+    Alignment { dx, dy, guides }
+}
+
+/// Bounding box of several bounds
+pub fn union_bounds(bounds: impl IntoIterator<Item = Bounds>) -> Option<Bounds> {
+    bounds
+        .into_iter()
+        .map(|(x, y, w, h)| (x, y, x + w, y + h))
+        .reduce(|a, b| (a.0.min(b.0), a.1.min(b.1), a.2.max(b.2), a.3.max(b.3)))
+        .map(|(min_x, min_y, max_x, max_y)| (min_x, min_y, max_x - min_x, max_y - min_y))
+}
+
+/// Left, center and right
+fn x_anchors((x, _, w, _): Bounds) -> [f32; 3] {
+    [x, x + w / 2.0, x + w]
+}
+
+/// Top, middle and bottom
+fn y_anchors((_, y, _, h): Bounds) -> [f32; 3] {
+    [y, y + h / 2.0, y + h]
+}
+
+fn closest_offset(
+    moving: [f32; 3],
+    neighbors: impl Iterator<Item = [f32; 3]>,
+    threshold: f32,
+) -> Option<f32> {
+    let mut best: Option<f32> = None;
+    for anchors in neighbors {
+        for target in anchors {
+            for source in moving {
+                let offset = target - source;
+                if offset.abs() <= threshold && best.is_none_or(|b| offset.abs() < b.abs()) {
+                    best = Some(offset);
+                }
+            }
+        }
+    }
+    best
+}
+
+fn collect_guides(
+    moving: Bounds,
+    neighbors: &[Bounds],
+    axis: GuideAxis,
+    guides: &mut Vec<AlignmentGuide>,
+) {
+    // Anchors closer than this count as aligned (absorbs float rounding)
+    const EPSILON: f32 = 0.5;
+
+    let (anchors, span): (fn(Bounds) -> [f32; 3], fn(Bounds) -> (f32, f32)) = match axis {
+        GuideAxis::Vertical => (x_anchors, |(_, y, _, h)| (y, y + h)),
+        GuideAxis::Horizontal => (y_anchors, |(x, _, w, _)| (x, x + w)),
+    };
+
+    for &neighbor in neighbors {
+        for target in anchors(neighbor) {
+            if !anchors(moving)
+                .iter()
+                .any(|a| (a - target).abs() <= EPSILON)
+            {
+                continue;
+            }
+            let (moving_start, moving_end) = span(moving);
+            let (neighbor_start, neighbor_end) = span(neighbor);
+            let start = moving_start.min(neighbor_start);
+            let end = moving_end.max(neighbor_end);
+
+            // Merge with an existing guide on the same line
+            if let Some(guide) = guides
+                .iter_mut()
+                .find(|g| g.axis == axis && (g.position - target).abs() <= EPSILON)
+            {
+                guide.start = guide.start.min(start);
+                guide.end = guide.end.max(end);
+            } else {
+                guides.push(AlignmentGuide {
+                    axis,
+                    position: target,
+                    start,
+                    end,
+                });
+            }
+        }
+    }
+}
@@ -0,0 +1,32 @@
+//! Canvas key handling that isn't a plain action binding.
+//!
+//! Holding Space switches the canvas to pan mode (see `InputState::SpacePanning`).
+//! Space has to be tracked through raw key down/up events because actions only
+//! fire on press. Tapping Space without dragging plays or pauses the selected
+//! media items instead.
+
+use crate::app::Humanboard;
+use gpui::*;
+
+impl Humanboard {
+    pub fn handle_key_down(&mut self, event: &KeyDownEvent, cx: &mut Context<Self>) {
+        // Space types a space while editing text
+        if event.keystroke.key != "space" || self.system.focus.is_input_active() {
+            return;
+        }
+        if self.canvas.board.is_some() && self.canvas.input_state.is_idle() {
+            self.canvas.input_state.start_space_pan();
+            cx.notify();
+        }
+        cx.stop_propagation();
+    }
+
+    pub fn handle_key_up(&mut self, event: &KeyUpEvent, cx: &mut Context<Self>) {
+        if event.keystroke.key == "space" && self.canvas.input_state.is_space_panning() {
+            if self.canvas.input_state.end_space_pan() {
+                self.toggle_selected_media(cx);
+            }
+            cx.notify();
+        }
+    }
+}
@@ -0,0 +1,86 @@
+//! Lasso selection geometry.
+//!
+//! A lasso is the freehand path traced while dragging, closed back to its
+//! first point. Items are selected when their bounds intersect the closed
+//! polygon: a corner inside it, a lasso point inside the item, or an edge
+//! crossing.
+//!
+//! All coordinates here are canvas coordinates.
+
+use crate::input::guides::Bounds;
+
+/// Minimum distance (in screen pixels) between recorded lasso points
+pub const LASSO_POINT_SPACING: f32 = 3.0;
+
+/// Whether a point lies inside a polygon (even-odd rule)
+pub fn point_in_polygon(point: (f32, f32), polygon: &[(f32, f32)]) -> bool {
+    let (px, py) = point;
+    let mut inside = false;
+    let mut j = polygon.len().wrapping_sub(1);
+    for i in 0..polygon.len() {
+        let (xi, yi) = polygon[i];
+        let (xj, yj) = polygon[j];
+        if (yi > py) != (yj > py) && px < (xj - xi) * (py - yi) / (yj - yi) + xi {
+            inside = !inside;
+        }
+        j = i;
+    }
+    inside
+}
+
+/// Whether `bounds` intersect the closed polygon
+pub fn polygon_intersects_bounds(polygon: &[(f32, f32)], bounds: Bounds) -> bool {
+    if polygon.len() < 3 {
+        return false;
+    }
+
+    let (x, y, w, h) = bounds;
+    let corners = [(x, y), (x + w, y), (x + w, y + h), (x, y + h)];
+
+    // Item inside the lasso
+    if corners.iter().any(|&c| point_in_polygon(c, polygon)) {
+        return true;
+    }
+    // Lasso inside the item
+    if polygon
+        .iter()
+        .any(|&(px, py)| px >= x && px <= x + w && py >= y && py <= y + h)
+    {
+        return true;
+    }
+    // Lasso crosses the item without a point or corner inside the other
+    let rect_edges = [
+        (corners[0], corners[1]),
+        (corners[1], corners[2]),
+        (corners[2], corners[3]),
+        (corners[3], corners[0]),
+    ];
+    polygon_edges(polygon).any(|(a, b)| {
+        rect_edges
+            .iter()
+            .any(|&(c, d)| segments_intersect(a, b, c, d))
+    })
+}
+
+/// Bounding box of the polygon
+pub fn polygon_bounds(polygon: &[(f32, f32)]) -> Option<Bounds> {
+    crate::input::guides::union_bounds(polygon.iter().map(|&(x, y)| (x, y, 0.0, 0.0)))
+}
+
+fn polygon_edges(polygon: &[(f32, f32)]) -> impl Iterator<Item = ((f32, f32), (f32, f32))> + '_ {
+    polygon
+        .iter()
+        .zip(polygon.iter().cycle().skip(1))
+        .map(|(&a, &b)| (a, b))
+}
+
+fn segments_intersect(a: (f32, f32), b: (f32, f32), c: (f32, f32), d: (f32, f32)) -> bool {
+    let cross = |o: (f32, f32), p: (f32, f32), q: (f32, f32)| {
+        (p.0 - o.0) * (q.1 - o.1) - (p.1 - o.1) * (q.0 - o.0)
+    };
+    let d1 = cross(c, d, a);
+    let d2 = cross(c, d, b);
+    let d3 = cross(a, b, c);
+    let d4 = cross(a, b, d);
+    ((d1 > 0.0) != (d2 > 0.0)) && ((d3 > 0.0) != (d4 > 0.0))
+}
@@ -15,13 +15,27 @@
 //! - `mouse_down` - Mouse down event handling (selection, drag/resize start)
 //! - `mouse_up` - Mouse up event handling (finalize operations, create items)
 //! - `drag` - Mouse move handling (drag, resize, pan operations)
+//! - `keyboard` - Raw key handling (Space to pan)
 //! - `transform` - Canvas transformations (scroll, zoom, coordinate conversion)
+//! - `snap` - Snap-to-grid helpers for drag and resize
+//! - `guides` - Alignment guides (smart guides) for drag
+//! - `constrain` - Shift-constrained drag axis and resize aspect ratio
+//! - `resize` - Corner and edge resize handles for a single item
+//! - `group_resize` - Scaling a multi-item selection from its bounding box corners
+//! - `lasso` - Freehand lasso selection geometry
 
+pub mod constrain;
 pub mod coords;
+pub mod group_resize;
+pub mod guides;
+pub mod lasso;
+pub mod resize;
+pub mod snap;
+pub mod transform;
 mod state;
 mod mouse_down;
 mod mouse_up;
 mod drag;
-mod transform;
+mod keyboard;
 
 pub use state::{InputState, SplitterDirection};
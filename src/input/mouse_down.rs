@@ -10,6 +10,10 @@
 
 use crate::app::{Humanboard, SplitDirection};
 use crate::constants::{DOCK_WIDTH, HEADER_HEIGHT, SPLITTER_WIDTH};
+use crate::image_viewer::is_viewable_image;
+use crate::input::group_resize::{GROUP_HANDLE_SIZE, GroupResizeItem, handle_at};
+use crate::input::guides::union_bounds;
+use crate::input::resize::{self, RESIZE_HANDLE_SIZE, ResizeHandle};
 use crate::profile_scope;
 use crate::types::{ItemContent, ToolType};
 use gpui::*;
@@ -23,9 +27,24 @@ impl Humanboard {
     ) {
         profile_scope!("handle_mouse_down");
 
+        // Pressing the button hides the tooltip so it doesn't follow a drag
+        self.clear_hover(cx);
+
+        // A click anywhere else dismisses an open context menu
+        if self.close_context_menu(cx) {
+            return;
+        }
+
         let Some(ref board) = self.canvas.board else { return };
         let mouse_pos = event.position;
 
+        // Space held: left-drag pans regardless of the active tool
+        if self.canvas.input_state.is_space_panning() {
+            self.canvas.input_state.press_space_pan(mouse_pos);
+            cx.notify();
+            return;
+        }
+
         // Check if clicking on splitter bar (canvas/preview split)
         if let Some(ref preview) = self.preview.panel {
             let bounds = window.bounds();
@@ -64,6 +83,13 @@ impl Humanboard {
             }
         }
 
+        // Picking the region to export: a drag outlines it, even over items
+        if self.is_picking_export_region() {
+            self.canvas.input_state.start_marquee(mouse_pos);
+            cx.notify();
+            return;
+        }
+
         let header_offset = HEADER_HEIGHT;
         let dock_offset = DOCK_WIDTH;
 
@@ -79,6 +105,17 @@ impl Humanboard {
             return;
         }
 
+        // The lasso tool traces a selection wherever the drag starts
+        if self.tools.selected == ToolType::Lasso {
+            self.system.focus.force_canvas_focus(window);
+            self.canvas.input_state.start_lasso(mouse_pos);
+            if !event.modifiers.shift {
+                self.canvas.selected_items.clear();
+            }
+            cx.notify();
+            return;
+        }
+
         // Check if clicking on an item using spatial index for O(log n) lookup
         profile_scope!("hit_test_items");
 
@@ -89,6 +126,67 @@ impl Humanboard {
         let canvas_x = (f32::from(mouse_pos.x) - dock_offset - f32::from(board.canvas_offset.x)) / board.zoom;
         let canvas_y = (f32::from(mouse_pos.y) - header_offset - f32::from(board.canvas_offset.y)) / board.zoom;
 
+        // Corner handles of the multi-selection box scale the whole selection
+        if self.canvas.selected_items.len() >= 2 {
+            let items: Vec<GroupResizeItem> = board
+                .items
+                .iter()
+                .filter(|item| self.canvas.selected_items.contains(&item.id) && !item.locked)
+                .map(|item| GroupResizeItem {
+                    id: item.id,
+                    bounds: (item.position.0, item.position.1, item.size.0, item.size.1),
+                    font_size: match &item.content {
+                        ItemContent::TextBox { font_size, .. } => Some(*font_size),
+                        _ => None,
+                    },
+                })
+                .collect();
+            let hit_radius = GROUP_HANDLE_SIZE / board.zoom;
+            let bounds = union_bounds(items.iter().map(|item| item.bounds));
+            let handle = bounds.and_then(|b| handle_at(b, (canvas_x, canvas_y), hit_radius));
+            if let (Some(bounds), Some(handle)) = (bounds, handle) {
+                self.system.focus.force_canvas_focus(window);
+                self.canvas.input_state.start_group_resizing(handle, bounds, mouse_pos, items);
+                cx.notify();
+                return;
+            }
+        }
+
+        // Corner and edge handles of a single selected item
+        if self.canvas.selected_items.len() == 1 {
+            let hit_radius = RESIZE_HANDLE_SIZE / board.zoom;
+            let hit = self
+                .canvas
+                .selected_items
+                .iter()
+                .next()
+                .and_then(|&id| board.get_item(id))
+                .filter(|item| !item.locked)
+                .and_then(|item| {
+                    let bounds = (item.position.0, item.position.1, item.size.0, item.size.1);
+                    let handle = resize::handle_at(bounds, (canvas_x, canvas_y), hit_radius)?;
+                    // Arrows are only resized from their end
+                    if matches!(item.content, ItemContent::Arrow { .. })
+                        && handle != ResizeHandle::BottomRight
+                    {
+                        return None;
+                    }
+                    let font_size = match &item.content {
+                        ItemContent::TextBox { font_size, .. } => Some(*font_size),
+                        _ => None,
+                    };
+                    Some((item.id, handle, bounds, font_size))
+                });
+            if let Some((item_id, handle, bounds, font_size)) = hit {
+                self.system.focus.force_canvas_focus(window);
+                self.canvas
+                    .input_state
+                    .start_resizing(item_id, handle, bounds, mouse_pos, font_size);
+                cx.notify();
+                return;
+            }
+        }
+
         // Get candidate items from spatial index (O(log n))
         let candidates: std::collections::HashSet<u64> = board
             .query_items_at_point(canvas_x, canvas_y)
@@ -141,14 +239,18 @@ impl Humanboard {
                 self.canvas.selected_items.insert(item_id);
             }
 
-            // Handle double-click for preview or TextBox editing
+            // Handle double-click for preview, or inline editing of text boxes
+            // and expanded markdown cards
             if event.click_count == 2 {
-                let is_textbox = board
+                let is_inline_editable = board
                     .get_item(item_id)
-                    .map(|item| matches!(&item.content, ItemContent::TextBox { .. }))
+                    .map(|item| {
+                        matches!(&item.content, ItemContent::TextBox { .. })
+                            || item.is_expanded_markdown()
+                    })
                     .unwrap_or(false);
 
-                if is_textbox {
+                if is_inline_editable {
                     self.start_textbox_editing(item_id, window, cx);
                     return;
                 }
@@ -171,18 +273,29 @@ impl Humanboard {
                 let content_path = board
                     .get_item(item_id)
                     .and_then(|item| match &item.content {
-                        ItemContent::Pdf { path, .. } => Some(path.clone()),
-                        ItemContent::Markdown { path, .. } => Some(path.clone()),
-                        ItemContent::Code { path, .. } => Some(path.clone()),
+                        ItemContent::Pdf { path, .. } => Some((path.clone(), None)),
+                        ItemContent::Markdown { path, .. } => Some((path.clone(), None)),
+                        // Code cards of a line range open at it
+                        ItemContent::Code { path, lines, .. } => Some((path.clone(), *lines)),
+                        ItemContent::Image(path) if is_viewable_image(path) => {
+                            Some((path.clone(), None))
+                        }
                         _ => None,
                     });
 
-                if let Some(path) = content_path {
-                    self.open_preview(path, window, cx);
+                if let Some((path, lines)) = content_path {
+                    self.open_preview_at_lines(path, lines, window, cx);
                     return;
                 }
             }
 
+            // Locked items can be selected but not dragged or resized
+            if board.is_locked(item_id) {
+                self.system.focus.force_canvas_focus(window);
+                cx.notify();
+                return;
+            }
+
             // Check if clicking on resize corner (bottom-right)
             let item_info = board
                 .get_item(item_id)
@@ -205,20 +318,33 @@ impl Humanboard {
                     && f32::from(mouse_pos.y) >= corner_y - corner_size
                     && f32::from(mouse_pos.y) <= corner_y + 5.0;
 
-                if in_corner {
+                // With several items selected, the group box handles resize instead
+                if in_corner && self.canvas.selected_items.len() == 1 {
                     let original_font_size =
                         if let ItemContent::TextBox { font_size, .. } = content {
                             Some(*font_size)
                         } else {
                             None
                         };
-                    self.canvas.input_state.start_resizing(item_id, size, mouse_pos, original_font_size);
+                    self.canvas.input_state.start_resizing(
+                        item_id,
+                        ResizeHandle::BottomRight,
+                        (position.0, position.1, size.0, size.1),
+                        mouse_pos,
+                        original_font_size,
+                    );
                 } else {
                     let drag_offset = point(
                         mouse_pos.x - px(scaled_x),
                         mouse_pos.y - px(scaled_y),
                     );
-                    self.canvas.input_state.start_dragging(item_id, drag_offset);
+                    if event.modifiers.alt {
+                        self.canvas
+                            .input_state
+                            .start_duplicate_dragging(item_id, drag_offset, position);
+                    } else {
+                        self.canvas.input_state.start_dragging(item_id, drag_offset, position);
+                    }
                 }
             }
             self.system.focus.force_canvas_focus(window);
@@ -227,8 +353,13 @@ impl Humanboard {
             self.system.focus.force_canvas_focus(window);
 
             match self.tools.selected {
-                ToolType::Select => {
-                    self.canvas.input_state.start_marquee(mouse_pos);
+                ToolType::Select | ToolType::Lasso => {
+                    // Alt switches the marquee to a freehand lasso
+                    if event.modifiers.alt {
+                        self.canvas.input_state.start_lasso(mouse_pos);
+                    } else {
+                        self.canvas.input_state.start_marquee(mouse_pos);
+                    }
                     if !event.modifiers.shift {
                         self.canvas.selected_items.clear();
                     }
@@ -242,4 +373,33 @@ impl Humanboard {
 
         cx.notify();
     }
+
+    /// Start panning the canvas with the middle mouse button
+    pub fn handle_middle_mouse_down(
+        &mut self,
+        event: &MouseDownEvent,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.clear_hover(cx);
+        self.close_context_menu(cx);
+        if self.canvas.board.is_none() || self.system.focus.is_input_active() {
+            return;
+        }
+
+        // Only the canvas area pans; the dock, header and preview panel keep their own clicks
+        let viewport = self.canvas_viewport_size(window);
+        let (x, y) = (f32::from(event.position.x), f32::from(event.position.y));
+        if x < DOCK_WIDTH
+            || y < HEADER_HEIGHT
+            || x > DOCK_WIDTH + f32::from(viewport.width)
+            || y > HEADER_HEIGHT + f32::from(viewport.height)
+        {
+            return;
+        }
+
+        self.canvas.input_state.start_panning(event.position);
+        cx.stop_propagation();
+        cx.notify();
+    }
 }
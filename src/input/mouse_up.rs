@@ -1,9 +1,12 @@
 //! Mouse up event handling - finalize operations, create drawn items.
 
 use crate::app::Humanboard;
-use crate::constants::{DEFAULT_FONT_SIZE, HEADER_HEIGHT, MIN_ARROW_SIZE, MIN_DRAW_DISTANCE, MIN_MARQUEE_SIZE};
+use crate::constants::{HEADER_HEIGHT, MIN_ARROW_SIZE, MIN_DRAW_DISTANCE, MIN_MARQUEE_SIZE};
 use crate::input::coords::{CoordinateContext, CoordinateConverter};
-use crate::types::{ArrowHead, DataSource, ItemContent, ShapeType, ToolType};
+use crate::input::lasso::{polygon_bounds, polygon_intersects_bounds};
+use crate::region_export::drawn_region;
+use crate::types::{DataSource, ShapeType, ToolType};
+use crate::undo_history::{DEFAULT_STEP_LABEL, items_label};
 use gpui::*;
 
 impl Humanboard {
@@ -13,22 +16,31 @@ impl Humanboard {
         window: &mut Window,
         cx: &mut Context<Self>,
     ) {
+        // Drop a preview tab released outside the panel's own drop targets
+        if self.preview.dragging_tab.is_some() {
+            self.finish_tab_drag(cx);
+            return;
+        } else if self.preview.tab_drag_pending.is_some() {
+            self.cancel_pending_drag(cx);
+        }
+
+        self.canvas.alignment_guides.clear();
+
         // Only push history on mouse up if we were dragging/resizing
         let was_modifying = self.canvas.input_state.is_dragging() || self.canvas.input_state.is_resizing();
 
         if was_modifying {
+            // The spatial index was kept up to date on every move
+            let input_state = &self.canvas.input_state;
+            let label = if input_state.is_dragging_items() {
+                items_label("Moved", self.canvas.selected_items.len())
+            } else if input_state.is_resizing() || input_state.is_resizing_group() {
+                items_label("Resized", self.canvas.selected_items.len())
+            } else {
+                DEFAULT_STEP_LABEL.to_string()
+            };
             if let Some(ref mut board) = self.canvas.board {
-                // Update spatial index for modified items
-                if let Some(resize_id) = self.canvas.input_state.resizing_item() {
-                    board.update_spatial_index(resize_id);
-                } else {
-                    // Update spatial index for all dragged items
-                    for &item_id in &self.canvas.selected_items {
-                        board.update_spatial_index(item_id);
-                    }
-                }
-
-                board.push_history();
+                board.push_labeled_history(label);
                 if let Err(e) = board.flush_save() {
                     self.ui.toast_manager
                         .push(crate::notifications::Toast::error(format!(
@@ -41,7 +53,22 @@ impl Humanboard {
 
         // Finalize marquee selection using spatial index for O(log n + k) query
         if let (Some(start), Some(end)) = (self.canvas.input_state.marquee_start(), self.canvas.input_state.marquee_current()) {
-            if let Some(ref board) = self.canvas.board {
+            if self.is_picking_export_region() {
+                let region = self.canvas.board.as_ref().and_then(|board| {
+                    let ctx = CoordinateContext::new(&board.canvas_offset, board.zoom);
+                    let from = CoordinateConverter::screen_to_canvas(start, &ctx);
+                    let to = CoordinateConverter::screen_to_canvas(end, &ctx);
+                    let width = f32::from(end.x - start.x).abs();
+                    let height = f32::from(end.y - start.y).abs();
+                    (width > MIN_MARQUEE_SIZE && height > MIN_MARQUEE_SIZE).then(|| {
+                        drawn_region(
+                            (f32::from(from.x), f32::from(from.y)),
+                            (f32::from(to.x), f32::from(to.y)),
+                        )
+                    })
+                });
+                self.finish_export_region_pick(region, cx);
+            } else if let Some(ref board) = self.canvas.board {
                 let min_x = f32::from(start.x).min(f32::from(end.x));
                 let max_x = f32::from(start.x).max(f32::from(end.x));
                 let min_y = f32::from(start.y).min(f32::from(end.y));
@@ -78,6 +105,44 @@ impl Humanboard {
             }
         }
 
+        // Finalize lasso selection
+        if let Some(points) = self.canvas.input_state.lasso_points() {
+            if let Some(ref board) = self.canvas.board {
+                let ctx = CoordinateContext::new(&board.canvas_offset, board.zoom);
+                let polygon: Vec<(f32, f32)> = points
+                    .iter()
+                    .map(|&p| {
+                        let canvas = CoordinateConverter::screen_to_canvas(p, &ctx);
+                        (f32::from(canvas.x), f32::from(canvas.y))
+                    })
+                    .collect();
+
+                // Narrow down with the spatial index, then test against the polygon
+                if let Some((x, y, w, h)) = polygon_bounds(&polygon) {
+                    let lassoed: Vec<u64> = board
+                        .query_items_in_rect(x, y, x + w, y + h)
+                        .into_iter()
+                        .filter(|&id| {
+                            board.get_item(id).is_some_and(|item| {
+                                polygon_intersects_bounds(
+                                    &polygon,
+                                    (item.position.0, item.position.1, item.size.0, item.size.1),
+                                )
+                            })
+                        })
+                        .collect();
+
+                    for item_id in lassoed {
+                        if event.modifiers.shift && self.canvas.selected_items.contains(&item_id) {
+                            self.canvas.selected_items.remove(&item_id);
+                        } else {
+                            self.canvas.selected_items.insert(item_id);
+                        }
+                    }
+                }
+            }
+        }
+
         // Finalize arrow/shape/text drawing
         if let Some(start) = self.tools.drawing_start {
             let end = event.position;
@@ -122,16 +187,12 @@ impl Humanboard {
 
                         let id = board.add_item(
                             point(px(box_x), px(box_y)),
-                            ItemContent::Arrow {
-                                end_offset,
-                                color: "".to_string(),
-                                thickness: 2.0,
-                                head_style: ArrowHead::Arrow,
-                            },
+                            crate::settings::item_styles().arrow(end_offset),
                         );
                         if let Some(item) = board.get_item_mut(id) {
                             item.size = (box_w, box_h);
                         }
+                        board.update_spatial_index(id);
                         self.canvas.selected_items.clear();
                         self.canvas.selected_items.insert(id);
                     }
@@ -140,16 +201,12 @@ impl Humanboard {
                     if let Some(ref mut board) = self.canvas.board {
                         let id = board.add_item(
                             point(px(pos_x), px(pos_y)),
-                            ItemContent::Shape {
-                                shape_type: ShapeType::Rectangle,
-                                fill_color: None,
-                                border_color: "".to_string(),
-                                border_width: 2.0,
-                            },
+                            crate::settings::item_styles().shape(ShapeType::Rectangle),
                         );
                         if let Some(item) = board.get_item_mut(id) {
                             item.size = (width, height);
                         }
+                        board.update_spatial_index(id);
                         self.canvas.selected_items.clear();
                         self.canvas.selected_items.insert(id);
                     }
@@ -158,15 +215,12 @@ impl Humanboard {
                     if let Some(ref mut board) = self.canvas.board {
                         let id = board.add_item(
                             point(px(pos_x), px(pos_y)),
-                            ItemContent::TextBox {
-                                text: "".to_string(),
-                                font_size: DEFAULT_FONT_SIZE,
-                                color: "".to_string(),
-                            },
+                            crate::settings::item_styles().text_box(),
                         );
                         if let Some(item) = board.get_item_mut(id) {
                             item.size = (width.max(100.0), height.max(40.0));
                         }
+                        board.update_spatial_index(id);
                         self.canvas.selected_items.clear();
                         self.canvas.selected_items.insert(id);
                         self.start_textbox_editing(id, window, cx);
@@ -186,15 +240,12 @@ impl Humanboard {
                         // Create the table item
                         let id = board.add_item(
                             point(px(pos_x), px(pos_y)),
-                            ItemContent::Table {
-                                data_source_id: ds_id,
-                                show_headers: true,
-                                stripe: true,
-                            },
+                            crate::settings::item_styles().table(ds_id),
                         );
                         if let Some(item) = board.get_item_mut(id) {
                             item.size = (width.max(300.0), height.max(200.0));
                         }
+                        board.update_spatial_index(id);
                         self.canvas.selected_items.clear();
                         self.canvas.selected_items.insert(id);
                     }
@@ -211,8 +262,23 @@ impl Humanboard {
             self.tools.drawing_current = None;
         }
 
-        // Reset all drag/resize state
-        self.canvas.input_state.reset();
+        // Reset all drag/resize state (Space-to-pan stays active while Space is held)
+        self.canvas.input_state.release_pointer();
         cx.notify();
     }
+
+    /// Finish a middle mouse pan
+    pub fn handle_middle_mouse_up(
+        &mut self,
+        _event: &MouseUpEvent,
+        _window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if self.canvas.input_state.is_canvas_panning()
+            && !self.canvas.input_state.is_space_panning()
+        {
+            self.canvas.input_state.end_panning();
+            cx.notify();
+        }
+    }
 }
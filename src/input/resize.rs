@@ -0,0 +1,122 @@
+//! Resize handles for a single selected item.
+//!
+//! A selected item has a handle on each corner and in the middle of each
+//! edge. Corner handles change both width and height; edge handles change
+//! one of them. Dragging a top or left handle moves the item as well, so the
+//! opposite edge stays where it was.
+//!
+//! All coordinates here are canvas coordinates.
+
+use crate::input::guides::Bounds;
+use crate::input::snap::snap_length;
+
+/// Size (in screen pixels) of the square resize handles
+pub const RESIZE_HANDLE_SIZE: f32 = 8.0;
+
+/// Handle on the edge or corner of a selected item
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ResizeHandle {
+    TopLeft,
+    Top,
+    TopRight,
+    Right,
+    BottomRight,
+    Bottom,
+    BottomLeft,
+    Left,
+}
+
+impl ResizeHandle {
+    pub const ALL: [ResizeHandle; 8] = [
+        ResizeHandle::TopLeft,
+        ResizeHandle::Top,
+        ResizeHandle::TopRight,
+        ResizeHandle::Right,
+        ResizeHandle::BottomRight,
+        ResizeHandle::Bottom,
+        ResizeHandle::BottomLeft,
+        ResizeHandle::Left,
+    ];
+
+    /// Which way each axis grows when the handle is dragged right/down:
+    /// +1 grows, -1 shrinks, 0 leaves that side alone
+    pub fn signs(self) -> (f32, f32) {
+        match self {
+            ResizeHandle::TopLeft => (-1.0, -1.0),
+            ResizeHandle::Top => (0.0, -1.0),
+            ResizeHandle::TopRight => (1.0, -1.0),
+            ResizeHandle::Right => (1.0, 0.0),
+            ResizeHandle::BottomRight => (1.0, 1.0),
+            ResizeHandle::Bottom => (0.0, 1.0),
+            ResizeHandle::BottomLeft => (-1.0, 1.0),
+            ResizeHandle::Left => (-1.0, 0.0),
+        }
+    }
+
+    /// Whether this handle sits on a corner
+    pub fn is_corner(self) -> bool {
+        let (sign_x, sign_y) = self.signs();
+        sign_x != 0.0 && sign_y != 0.0
+    }
+
+    /// Center of this handle on `bounds`
+    pub fn position(self, (x, y, w, h): Bounds) -> (f32, f32) {
+        let (sign_x, sign_y) = self.signs();
+        let along = |start: f32, length: f32, sign: f32| start + length * (sign + 1.0) / 2.0;
+        // Edge handles sit in the middle of their edge
+        let x = if sign_x == 0.0 {
+            x + w / 2.0
+        } else {
+            along(x, w, sign_x)
+        };
+        let y = if sign_y == 0.0 {
+            y + h / 2.0
+        } else {
+            along(y, h, sign_y)
+        };
+        (x, y)
+    }
+
+    /// Top-left corner of `start` resized to `size` with the opposite edges kept in place
+    pub fn anchored_position(self, (x, y, w, h): Bounds, size: (f32, f32)) -> (f32, f32) {
+        let (sign_x, sign_y) = self.signs();
+        let new_x = if sign_x < 0.0 { x + w - size.0 } else { x };
+        let new_y = if sign_y < 0.0 { y + h - size.1 } else { y };
+        (new_x, new_y)
+    }
+}
+
+/// Handle of `bounds` within `hit_radius` of `point`, corners first
+pub fn handle_at(bounds: Bounds, point: (f32, f32), hit_radius: f32) -> Option<ResizeHandle> {
+    let hit = |handle: &ResizeHandle| {
+        let (hx, hy) = handle.position(bounds);
+        (point.0 - hx).abs() <= hit_radius && (point.1 - hy).abs() <= hit_radius
+    };
+    let (corners, edges): (Vec<ResizeHandle>, Vec<ResizeHandle>) = ResizeHandle::ALL
+        .into_iter()
+        .partition(|handle| handle.is_corner());
+    corners.iter().chain(edges.iter()).copied().find(hit)
+}
+
+/// Snap the moving edge of a span resized from `sign`'s side to the grid.
+///
+/// `start` and `start_length` describe the span before the resize; the edge
+/// opposite the handle stays fixed. Spans the handle doesn't move are
+/// returned unchanged.
+pub fn snap_resized_length(
+    sign: f32,
+    start: f32,
+    start_length: f32,
+    length: f32,
+    grid_size: f32,
+    min_length: f32,
+) -> f32 {
+    if sign > 0.0 {
+        snap_length(start, length, grid_size, min_length)
+    } else if sign < 0.0 {
+        // Mirror the axis so the fixed far edge becomes the start
+        snap_length(-(start + start_length), length, grid_size, min_length)
+    } else {
+        length
+    }
+}
@@ -0,0 +1,41 @@
+//! Snap-to-grid helpers for dragging and resizing items.
+//!
+//! Snapping is controlled by the `snap_to_grid` and `grid_size` settings.
+//! Holding Cmd (Ctrl on Linux and Windows) while dragging or resizing turns
+//! it off for that move, along with alignment guides. Alt is left for
+//! duplicating the dragged items.
+
+use gpui::Modifiers;
+
+/// Whether snapping is held off for this mouse move
+pub fn snapping_suppressed(modifiers: &Modifiers) -> bool {
+    modifiers.platform || modifiers.control
+}
+
+/// Grid size to snap to for this mouse move, if snapping applies
+pub fn active_grid_size(modifiers: &Modifiers) -> Option<f32> {
+    if snapping_suppressed(modifiers) {
+        return None;
+    }
+    crate::settings::snap_grid_size()
+}
+
+/// Round a canvas coordinate to the nearest grid line
+#[inline]
+pub fn snap_to_grid(value: f32, grid_size: f32) -> f32 {
+    (value / grid_size).round() * grid_size
+}
+
+/// Snap the far edge of a span starting at `start` to the grid.
+///
+/// Returns the new length, never less than `min_length`.
+#[inline]
+pub fn snap_length(start: f32, length: f32, grid_size: f32, min_length: f32) -> f32 {
+    let snapped = snap_to_grid(start + length, grid_size) - start;
+    if snapped >= min_length {
+        return snapped;
+    }
+    // Use the first grid line that still leaves room for the minimum size
+    let first = ((start + min_length) / grid_size).ceil() * grid_size - start;
+    first.max(min_length)
+}
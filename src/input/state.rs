@@ -7,16 +7,25 @@
 //!
 //! ```text
 //! Idle -> Panning              (middle mouse down on canvas)
-//! Idle -> DraggingItems        (mouse down on selected item, not on resize corner)
-//! Idle -> ResizingItem         (mouse down on item resize corner)
+//! Idle -> SpacePanning         (Space pressed on the canvas; left-drag then pans)
+//! SpacePanning -> Idle         (Space released)
+//! Idle -> DraggingItems        (mouse down on selected item, not on resize corner;
+//!                               with Alt held the items are duplicated on the first move)
+//! Idle -> ResizingItem         (mouse down on a corner or edge handle of the selected item)
+//! Idle -> ResizingGroup        (mouse down on a corner of the multi-selection box)
 //! Idle -> MarqueeSelecting     (mouse down on empty canvas with select tool)
+//! Idle -> LassoSelecting       (mouse down on empty canvas with lasso tool, or Alt+select tool)
 //! Idle -> Drawing              (mouse down with arrow/shape/text tool)
 //! Idle -> SplitterDragging     (mouse down on preview splitter)
 //!
-//! Any -> Idle                  (mouse up - finalizes operation)
+//! Any -> Idle                  (mouse up - finalizes operation; SpacePanning stays
+//!                               until Space is released)
 //! ```
 
 use crate::app::SplitDirection;
+use crate::input::group_resize::{GroupHandle, GroupResizeItem};
+use crate::input::guides::Bounds;
+use crate::input::resize::ResizeHandle;
 use crate::types::ToolType;
 use gpui::{Point, Pixels};
 
@@ -41,18 +50,34 @@ pub enum InputState {
         last_pos: Point<Pixels>,
     },
 
+    /// Space held: the cursor is a hand and left-drag pans, whatever the tool
+    SpacePanning {
+        /// Last mouse position while the button is down, None while just hovering
+        last_pos: Option<Point<Pixels>>,
+        /// Whether the mouse was pressed since Space went down (a pan, not a tap)
+        pressed: bool,
+    },
+
     /// Dragging one or more items
     DraggingItems {
         /// Primary item being dragged (the one under the cursor)
         primary_item: u64,
         /// Offset from item top-left to cursor position
         drag_offset: Point<Pixels>,
+        /// Canvas position of the primary item when the drag started
+        start_position: (f32, f32),
+        /// Duplicate the items on the first move and drag the copies (Alt+drag)
+        duplicate: bool,
     },
 
     /// Resizing an item
     ResizingItem {
         /// Item being resized
         item_id: u64,
+        /// Handle being dragged
+        handle: ResizeHandle,
+        /// Original position at start of resize
+        start_position: (f32, f32),
         /// Original size at start of resize
         start_size: (f32, f32),
         /// Mouse position at start of resize
@@ -61,6 +86,18 @@ pub enum InputState {
         original_font_size: Option<f32>,
     },
 
+    /// Scaling all selected items from a corner of their bounding box
+    ResizingGroup {
+        /// Corner being dragged
+        handle: GroupHandle,
+        /// Selection bounds at start of resize (canvas coordinates)
+        start_bounds: Bounds,
+        /// Mouse position at start of resize
+        start_pos: Point<Pixels>,
+        /// Items as they were at start of resize
+        items: Vec<GroupResizeItem>,
+    },
+
     /// Marquee/box selection
     MarqueeSelecting {
         /// Selection box start position
@@ -69,6 +106,12 @@ pub enum InputState {
         current: Point<Pixels>,
     },
 
+    /// Freehand lasso selection
+    LassoSelecting {
+        /// Traced path in window coordinates
+        points: Vec<Point<Pixels>>,
+    },
+
     /// Drawing shapes, arrows, or text boxes
     Drawing {
         /// Tool being used
@@ -111,6 +154,7 @@ impl InputState {
             Self::Panning { .. }
                 | Self::DraggingItems { .. }
                 | Self::ResizingItem { .. }
+                | Self::ResizingGroup { .. }
                 | Self::SplitterDragging { .. }
         )
     }
@@ -120,6 +164,11 @@ impl InputState {
         matches!(self, Self::ResizingItem { .. })
     }
 
+    /// Returns true if currently scaling a multi-item selection
+    pub fn is_resizing_group(&self) -> bool {
+        matches!(self, Self::ResizingGroup { .. })
+    }
+
     /// Returns true if currently dragging items
     pub fn is_dragging_items(&self) -> bool {
         matches!(self, Self::DraggingItems { .. })
@@ -130,6 +179,11 @@ impl InputState {
         matches!(self, Self::MarqueeSelecting { .. })
     }
 
+    /// Returns true if currently tracing a lasso
+    pub fn is_lasso_selecting(&self) -> bool {
+        matches!(self, Self::LassoSelecting { .. })
+    }
+
     /// Returns true if currently drawing
     pub fn is_drawing(&self) -> bool {
         matches!(self, Self::Drawing { .. })
@@ -153,7 +207,15 @@ impl InputState {
 
     /// Returns true if currently panning the canvas
     pub fn is_panning(&self) -> bool {
-        matches!(self, Self::Panning { .. })
+        matches!(
+            self,
+            Self::Panning { .. } | Self::SpacePanning { last_pos: Some(_), .. }
+        )
+    }
+
+    /// Returns true while Space is held for panning (dragging or not)
+    pub fn is_space_panning(&self) -> bool {
+        matches!(self, Self::SpacePanning { .. })
     }
 
     /// Returns true if currently dragging any splitter
@@ -188,29 +250,91 @@ impl InputState {
     }
 
     /// Start dragging items
-    pub fn start_dragging(&mut self, item_id: u64, offset: Point<Pixels>) {
+    pub fn start_dragging(
+        &mut self,
+        item_id: u64,
+        offset: Point<Pixels>,
+        start_position: (f32, f32),
+    ) {
+        *self = Self::DraggingItems {
+            primary_item: item_id,
+            drag_offset: offset,
+            start_position,
+            duplicate: false,
+        };
+    }
+
+    /// Start dragging copies of the items (Alt+drag).
+    /// The copies are made on the first move, so an Alt+click leaves the board unchanged.
+    pub fn start_duplicate_dragging(
+        &mut self,
+        item_id: u64,
+        offset: Point<Pixels>,
+        start_position: (f32, f32),
+    ) {
         *self = Self::DraggingItems {
             primary_item: item_id,
             drag_offset: offset,
+            start_position,
+            duplicate: true,
         };
     }
 
+    /// Take a pending duplication, returning the primary item to duplicate.
+    /// Returns None once the copies have been made or when not duplicating.
+    pub fn take_pending_duplicate(&mut self) -> Option<u64> {
+        match self {
+            Self::DraggingItems { primary_item, duplicate, .. } if *duplicate => {
+                *duplicate = false;
+                Some(*primary_item)
+            }
+            _ => None,
+        }
+    }
+
+    /// Switch the drag to a different primary item (e.g. the copy of the original)
+    pub fn set_dragged_item(&mut self, item_id: u64) {
+        if let Self::DraggingItems { primary_item, .. } = self {
+            *primary_item = item_id;
+        }
+    }
+
     /// Start resizing an item
     pub fn start_resizing(
         &mut self,
         item_id: u64,
-        start_size: (f32, f32),
+        handle: ResizeHandle,
+        start_bounds: Bounds,
         start_pos: Point<Pixels>,
         original_font_size: Option<f32>,
     ) {
+        let (x, y, w, h) = start_bounds;
         *self = Self::ResizingItem {
             item_id,
-            start_size,
+            handle,
+            start_position: (x, y),
+            start_size: (w, h),
             start_pos,
             original_font_size,
         };
     }
 
+    /// Start scaling the selection from one corner of its bounding box
+    pub fn start_group_resizing(
+        &mut self,
+        handle: GroupHandle,
+        start_bounds: Bounds,
+        start_pos: Point<Pixels>,
+        items: Vec<GroupResizeItem>,
+    ) {
+        *self = Self::ResizingGroup {
+            handle,
+            start_bounds,
+            start_pos,
+            items,
+        };
+    }
+
     /// Start splitter dragging
     pub fn start_splitter_drag(&mut self, drag_start: Point<Pixels>, direction: SplitDirection) {
         *self = Self::SplitterDragging {
@@ -227,6 +351,57 @@ impl InputState {
         };
     }
 
+    /// Start a middle-mouse pan at `pos` (only from Idle, so other drags are not interrupted)
+    pub fn start_panning(&mut self, pos: Point<Pixels>) {
+        if self.is_idle() {
+            *self = Self::Panning { last_pos: pos };
+        }
+    }
+
+    /// Finish a middle-mouse pan
+    pub fn end_panning(&mut self) {
+        if matches!(self, Self::Panning { .. }) {
+            *self = Self::Idle;
+        }
+    }
+
+    /// Enter Space-to-pan mode (only from Idle, so key repeat and other drags are ignored)
+    pub fn start_space_pan(&mut self) {
+        if self.is_idle() {
+            *self = Self::SpacePanning {
+                last_pos: None,
+                pressed: false,
+            };
+        }
+    }
+
+    /// Leave Space-to-pan mode. Returns true if Space was only tapped, with
+    /// no mouse press while it was held.
+    pub fn end_space_pan(&mut self) -> bool {
+        let tapped = matches!(self, Self::SpacePanning { pressed: false, .. });
+        if self.is_space_panning() {
+            *self = Self::Idle;
+        }
+        tapped
+    }
+
+    /// Begin a Space+drag pan at `pos`
+    pub fn press_space_pan(&mut self, pos: Point<Pixels>) {
+        if let Self::SpacePanning { last_pos, pressed } = self {
+            *last_pos = Some(pos);
+            *pressed = true;
+        }
+    }
+
+    /// Finish the current pointer operation on mouse up.
+    /// Space-to-pan mode survives the mouse up while Space is still held.
+    pub fn release_pointer(&mut self) {
+        match self {
+            Self::SpacePanning { last_pos, .. } => *last_pos = None,
+            _ => *self = Self::Idle,
+        }
+    }
+
     /// Start marquee selection
     pub fn start_marquee(&mut self, start: Point<Pixels>) {
         *self = Self::MarqueeSelecting { start, current: start };
@@ -255,6 +430,33 @@ impl InputState {
         }
     }
 
+    /// Start lasso selection
+    pub fn start_lasso(&mut self, start: Point<Pixels>) {
+        *self = Self::LassoSelecting { points: vec![start] };
+    }
+
+    /// Extend the lasso path, skipping points closer than `min_spacing` to the last one
+    pub fn push_lasso_point(&mut self, point: Point<Pixels>, min_spacing: f32) {
+        if let Self::LassoSelecting { points } = self {
+            let far_enough = points.last().is_none_or(|last| {
+                let dx = f32::from(point.x - last.x);
+                let dy = f32::from(point.y - last.y);
+                dx * dx + dy * dy >= min_spacing * min_spacing
+            });
+            if far_enough {
+                points.push(point);
+            }
+        }
+    }
+
+    /// Get the traced lasso path
+    pub fn lasso_points(&self) -> Option<&[Point<Pixels>]> {
+        match self {
+            Self::LassoSelecting { points } => Some(points),
+            _ => None,
+        }
+    }
+
     /// Get the item ID being resized
     pub fn resizing_item(&self) -> Option<u64> {
         match self {
@@ -271,6 +473,22 @@ impl InputState {
         }
     }
 
+    /// Get the handle being dragged in a resize
+    pub fn resize_handle(&self) -> Option<ResizeHandle> {
+        match self {
+            Self::ResizingItem { handle, .. } => Some(*handle),
+            _ => None,
+        }
+    }
+
+    /// Get the item's position at the start of a resize
+    pub fn resize_start_position(&self) -> Option<(f32, f32)> {
+        match self {
+            Self::ResizingItem { start_position, .. } => Some(*start_position),
+            _ => None,
+        }
+    }
+
     /// Get resize start position
     pub fn resize_start_pos(&self) -> Option<Point<Pixels>> {
         match self {
@@ -287,6 +505,20 @@ impl InputState {
         }
     }
 
+    /// Get the group resize in progress: dragged handle, start bounds, start
+    /// mouse position and the items as they were at the start
+    pub fn group_resize(&self) -> Option<(GroupHandle, Bounds, Point<Pixels>, &[GroupResizeItem])> {
+        match self {
+            Self::ResizingGroup {
+                handle,
+                start_bounds,
+                start_pos,
+                items,
+            } => Some((*handle, *start_bounds, *start_pos, items)),
+            _ => None,
+        }
+    }
+
     /// Get drag offset
     pub fn drag_offset(&self) -> Option<Point<Pixels>> {
         match self {
@@ -295,6 +527,14 @@ impl InputState {
         }
     }
 
+    /// Canvas position of the primary item when the drag started
+    pub fn drag_start_position(&self) -> Option<(f32, f32)> {
+        match self {
+            Self::DraggingItems { start_position, .. } => Some(*start_position),
+            _ => None,
+        }
+    }
+
     /// Get dragging item ID (alias for dragged_item_id)
     pub fn dragging_item(&self) -> Option<u64> {
         self.dragged_item_id()
@@ -316,23 +556,29 @@ impl InputState {
         }
     }
 
-    /// Returns true if currently panning the canvas (middle mouse drag)
+    /// Returns true if currently panning the canvas (middle mouse or Space+drag)
     pub fn is_canvas_panning(&self) -> bool {
-        matches!(self, Self::Panning { .. })
+        self.is_panning()
     }
 
     /// Get last mouse position (for panning)
     pub fn last_mouse_pos(&self) -> Option<Point<Pixels>> {
         match self {
             Self::Panning { last_pos } => Some(*last_pos),
+            Self::SpacePanning { last_pos, .. } => *last_pos,
             _ => None,
         }
     }
 
     /// Update last mouse position (for panning)
     pub fn update_last_mouse_pos(&mut self, pos: Point<Pixels>) {
-        if let Self::Panning { last_pos } = self {
-            *last_pos = pos;
+        match self {
+            Self::Panning { last_pos } => *last_pos = pos,
+            Self::SpacePanning {
+                last_pos: Some(last_pos),
+                ..
+            } => *last_pos = pos,
+            _ => {}
         }
     }
 }
@@ -358,12 +604,16 @@ mod tests {
             InputState::DraggingItems {
                 primary_item: 1,
                 drag_offset: pos,
+                start_position: (0.0, 0.0),
+                duplicate: false,
             }
             .is_dragging()
         );
         assert!(
             InputState::ResizingItem {
                 item_id: 1,
+                handle: ResizeHandle::BottomRight,
+                start_position: (0.0, 0.0),
                 start_size: (100.0, 100.0),
                 start_pos: pos,
                 original_font_size: None,
@@ -382,6 +632,7 @@ mod tests {
 
         // Non-dragging states
         assert!(!InputState::MarqueeSelecting { start: pos, current: pos }.is_dragging());
+        assert!(!InputState::LassoSelecting { points: vec![pos] }.is_dragging());
         assert!(
             !InputState::Drawing {
                 tool: ToolType::Arrow,
@@ -401,12 +652,16 @@ mod tests {
             InputState::DraggingItems {
                 primary_item: 1,
                 drag_offset: pos,
+                start_position: (0.0, 0.0),
+                duplicate: false,
             }
             .is_dragging_items()
         );
         assert!(
             InputState::ResizingItem {
                 item_id: 1,
+                handle: ResizeHandle::BottomRight,
+                start_position: (0.0, 0.0),
                 start_size: (100.0, 100.0),
                 start_pos: pos,
                 original_font_size: None,
@@ -431,12 +686,16 @@ mod tests {
         let drag_state = InputState::DraggingItems {
             primary_item: 42,
             drag_offset: pos,
+            start_position: (0.0, 0.0),
+            duplicate: false,
         };
         assert_eq!(drag_state.dragged_item_id(), Some(42));
         assert_eq!(drag_state.resized_item_id(), None);
 
         let resize_state = InputState::ResizingItem {
             item_id: 99,
+            handle: ResizeHandle::BottomRight,
+            start_position: (0.0, 0.0),
             start_size: (100.0, 100.0),
             start_pos: pos,
             original_font_size: None,
@@ -445,6 +704,125 @@ mod tests {
         assert_eq!(resize_state.dragged_item_id(), None);
     }
 
+    #[test]
+    fn test_duplicate_drag_is_taken_once() {
+        let pos = Point::new(gpui::px(0.0), gpui::px(0.0));
+        let mut state = InputState::Idle;
+
+        state.start_duplicate_dragging(7, pos, (0.0, 0.0));
+        assert!(state.is_dragging_items());
+        assert_eq!(state.take_pending_duplicate(), Some(7));
+        assert_eq!(state.take_pending_duplicate(), None);
+
+        state.set_dragged_item(8);
+        assert_eq!(state.dragged_item_id(), Some(8));
+
+        state.start_dragging(7, pos, (0.0, 0.0));
+        assert_eq!(state.take_pending_duplicate(), None);
+    }
+
+    #[test]
+    fn test_lasso_skips_close_points() {
+        let mut state = InputState::Idle;
+        state.start_lasso(Point::new(gpui::px(0.0), gpui::px(0.0)));
+        state.push_lasso_point(Point::new(gpui::px(1.0), gpui::px(1.0)), 3.0);
+        state.push_lasso_point(Point::new(gpui::px(5.0), gpui::px(0.0)), 3.0);
+
+        assert!(state.is_lasso_selecting());
+        assert_eq!(state.lasso_points().map(|p| p.len()), Some(2));
+    }
+
+    #[test]
+    fn test_space_pan_cycle() {
+        let pos = Point::new(gpui::px(10.0), gpui::px(20.0));
+        let mut state = InputState::Idle;
+
+        state.start_space_pan();
+        assert!(state.is_space_panning());
+        assert!(!state.is_canvas_panning());
+
+        state.press_space_pan(pos);
+        assert!(state.is_canvas_panning());
+        assert_eq!(state.last_mouse_pos(), Some(pos));
+
+        // Mouse up keeps the mode while Space is held
+        state.release_pointer();
+        assert!(state.is_space_panning());
+        assert_eq!(state.last_mouse_pos(), None);
+
+        assert!(!state.end_space_pan());
+        assert!(state.is_idle());
+    }
+
+    #[test]
+    fn test_space_tap() {
+        let mut state = InputState::Idle;
+        state.start_space_pan();
+        assert!(state.end_space_pan());
+        assert!(state.is_idle());
+
+        // Not space panning at all
+        assert!(!state.end_space_pan());
+    }
+
+    #[test]
+    fn test_middle_mouse_pan_cycle() {
+        let pos = Point::new(gpui::px(10.0), gpui::px(20.0));
+        let mut state = InputState::Idle;
+
+        state.start_panning(pos);
+        assert!(state.is_canvas_panning());
+        assert!(!state.is_space_panning());
+        assert_eq!(state.last_mouse_pos(), Some(pos));
+
+        state.end_panning();
+        assert!(state.is_idle());
+    }
+
+    #[test]
+    fn test_middle_mouse_does_not_interrupt_drag() {
+        let pos = Point::new(gpui::px(0.0), gpui::px(0.0));
+        let mut state = InputState::Idle;
+        state.start_dragging(1, pos, (0.0, 0.0));
+
+        state.start_panning(pos);
+        assert!(state.is_dragging_items());
+        state.end_panning();
+        assert!(state.is_dragging_items());
+    }
+
+    #[test]
+    fn test_space_does_not_interrupt_drag() {
+        let pos = Point::new(gpui::px(0.0), gpui::px(0.0));
+        let mut state = InputState::Idle;
+        state.start_dragging(1, pos, (0.0, 0.0));
+
+        state.start_space_pan();
+        assert!(state.is_dragging_items());
+        state.end_space_pan();
+        assert!(state.is_dragging_items());
+    }
+
+    #[test]
+    fn test_group_resize_is_a_drag() {
+        let pos = Point::new(gpui::px(0.0), gpui::px(0.0));
+        let items = vec![GroupResizeItem {
+            id: 3,
+            bounds: (0.0, 0.0, 100.0, 50.0),
+            font_size: None,
+        }];
+        let mut state = InputState::Idle;
+        state.start_group_resizing(GroupHandle::TopLeft, (0.0, 0.0, 100.0, 50.0), pos, items);
+
+        assert!(state.is_resizing_group());
+        assert!(state.is_dragging());
+        assert!(!state.is_resizing());
+        assert_eq!(state.resizing_item(), None);
+        let (handle, _, _, items) = state.group_resize().unwrap();
+        assert_eq!(handle, GroupHandle::TopLeft);
+        assert_eq!(items[0].id, 3);
+    }
+
     #[test]
     fn test_reset() {
         let pos = Point::new(gpui::px(0.0), gpui::px(0.0));
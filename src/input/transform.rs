@@ -1,10 +1,111 @@
 //! Canvas transformations - scroll, zoom, coordinate conversion.
+//!
+//! Zooming with Cmd/Ctrl+scroll scales exponentially with the scroll delta
+//! and is applied as the events arrive, with no animation or momentum, so
+//! the canvas tracks the fingers exactly. The point under the cursor stays
+//! fixed. GPUI doesn't deliver trackpad magnify or rotate gestures, so a
+//! pinch zooms only where the platform sends it as Ctrl+scroll.
 
-use crate::app::Humanboard;
+use crate::app::{Humanboard, SplitDirection};
+use crate::board::Board;
+use crate::constants::{DOCK_WIDTH, FOOTER_HEIGHT, HEADER_HEIGHT, MIN_ZOOM};
 use crate::input::coords::{CoordinateContext, CoordinateConverter};
+use crate::input::guides::Bounds;
 use crate::types::ItemContent;
 use gpui::*;
 
+/// Scroll distance (in pixels) that zooms by a factor of e
+const SCROLL_ZOOM_DISTANCE: f32 = 500.0;
+
+/// Scroll lines that zoom by a factor of e
+const SCROLL_ZOOM_LINES: f32 = 50.0;
+
+/// Zoom factor for a scroll delta, always positive and symmetric in and out
+pub fn scroll_zoom_factor(delta: ScrollDelta) -> f32 {
+    match delta {
+        ScrollDelta::Pixels(delta) => (-f32::from(delta.y) / SCROLL_ZOOM_DISTANCE).exp(),
+        ScrollDelta::Lines(delta) => (-delta.y / SCROLL_ZOOM_LINES).exp(),
+    }
+}
+
+/// Canvas pan for a scroll delta. Shift turns a vertical-only wheel into a
+/// horizontal scroll, as most mice have no horizontal wheel.
+pub fn scroll_pan_delta(delta: (f32, f32), shift: bool) -> (f32, f32) {
+    if shift && delta.0 == 0.0 {
+        (delta.1, 0.0)
+    } else {
+        delta
+    }
+}
+
+/// Canvas offset and zoom that fit `bounds` (canvas coordinates) centered in a
+/// viewport of `viewport` size, leaving `padding` pixels on every side.
+///
+/// The zoom never exceeds `max_zoom`, so a single small item is not blown up.
+pub fn fit_bounds(
+    bounds: Bounds,
+    viewport: (f32, f32),
+    padding: f32,
+    max_zoom: f32,
+) -> (Point<Pixels>, f32) {
+    let (x, y, w, h) = bounds;
+    let available_w = (viewport.0 - padding * 2.0).max(1.0);
+    let available_h = (viewport.1 - padding * 2.0).max(1.0);
+
+    let zoom = (available_w / w.max(1.0))
+        .min(available_h / h.max(1.0))
+        .clamp(MIN_ZOOM, max_zoom);
+
+    let center_x = x + w / 2.0;
+    let center_y = y + h / 2.0;
+    let offset = point(
+        px(viewport.0 / 2.0 - center_x * zoom),
+        px(viewport.1 / 2.0 - center_y * zoom),
+    );
+    (offset, zoom)
+}
+
+/// Smallest canvas offset change that brings `bounds` (canvas coordinates) fully
+/// into a viewport of `viewport` size at `zoom`, keeping `padding` pixels clear
+/// on every side. Returns None when the bounds are already visible.
+///
+/// Bounds larger than the viewport are aligned to its top-left corner.
+pub fn reveal_offset(
+    bounds: Bounds,
+    offset: Point<Pixels>,
+    zoom: f32,
+    viewport: (f32, f32),
+    padding: f32,
+) -> Option<Point<Pixels>> {
+    let (x, y, w, h) = bounds;
+    let shift = |start: f32, length: f32, offset: f32, extent: f32| {
+        let screen_start = start * zoom + offset;
+        let screen_end = screen_start + length * zoom;
+        if screen_start < padding || screen_end - screen_start > extent - padding * 2.0 {
+            padding - screen_start
+        } else if screen_end > extent - padding {
+            extent - padding - screen_end
+        } else {
+            0.0
+        }
+    };
+
+    let dx = shift(x, w, f32::from(offset.x), viewport.0);
+    let dy = shift(y, h, f32::from(offset.y), viewport.1);
+    if dx == 0.0 && dy == 0.0 {
+        None
+    } else {
+        Some(point(offset.x + px(dx), offset.y + px(dy)))
+    }
+}
+
+/// Zoom around a window position, keeping the canvas point under it fixed
+fn zoom_at_cursor(board: &mut Board, factor: f32, position: Point<Pixels>) -> bool {
+    // Board offsets are relative to the canvas area, which starts after the dock and header
+    let center = point(position.x - px(DOCK_WIDTH), position.y - px(HEADER_HEIGHT));
+    board.zoom_around(factor, center)
+}
+
 impl Humanboard {
     /// Convert screen position to canvas position.
     pub fn screen_to_canvas(&self, pos: Point<Pixels>, _header_offset: f32) -> Point<Pixels> {
@@ -16,6 +117,25 @@ impl Humanboard {
         }
     }
 
+    /// Size of the visible canvas area (excludes dock, header, footer and preview panel)
+    pub fn canvas_viewport_size(&self, window: &Window) -> Size<Pixels> {
+        let window_size = window.bounds().size;
+        let available_width = f32::from(window_size.width) - DOCK_WIDTH;
+        let available_height = f32::from(window_size.height) - HEADER_HEIGHT - FOOTER_HEIGHT;
+
+        match self.preview.panel {
+            Some(ref preview) => match preview.split {
+                SplitDirection::Vertical => {
+                    size(px(available_width * (1.0 - preview.size)), px(available_height))
+                }
+                SplitDirection::Horizontal => {
+                    size(px(available_width), px(available_height * (1.0 - preview.size)))
+                }
+            },
+            None => size(px(available_width), px(available_height)),
+        }
+    }
+
     pub fn handle_scroll(
         &mut self,
         event: &ScrollWheelEvent,
@@ -45,21 +165,20 @@ impl Humanboard {
             }
         }
 
+        // The canvas moves under the cursor, so the hovered item is stale
+        self.clear_hover(cx);
+
         let Some(ref mut board) = self.canvas.board else {
             return;
         };
 
         // Zoom with Command (platform) or Control key
         if event.modifiers.platform || event.modifiers.control {
-            let zoom_factor = match event.delta {
-                ScrollDelta::Pixels(delta) => 1.0 - f32::from(delta.y) / 500.0,
-                ScrollDelta::Lines(delta) => 1.0 - delta.y / 50.0,
-            };
-
-            if (zoom_factor - 1.0).abs() > 0.001 {
-                if board.zoom_around(zoom_factor, event.position) {
-                    cx.notify();
-                }
+            let zoom_factor = scroll_zoom_factor(event.delta);
+            if (zoom_factor - 1.0).abs() > 0.001
+                && zoom_at_cursor(board, zoom_factor, event.position)
+            {
+                cx.notify();
             }
             return;
         }
@@ -70,9 +189,10 @@ impl Humanboard {
         let canvas_x = f32::from(canvas_pos.x);
         let canvas_y = f32::from(canvas_pos.y);
 
-        // Check if mouse is over a table - let the Table component handle its own scroll
+        // Check if mouse is over a table or an expanded code card - let them
+        // handle their own scroll
         let over_table = board.items.iter().any(|item| {
-            if !matches!(item.content, ItemContent::Table { .. }) {
+            if !matches!(item.content, ItemContent::Table { .. }) && !item.is_expanded_code() {
                 return false;
             }
             let (ix, iy) = item.position;
@@ -80,25 +200,20 @@ impl Humanboard {
             canvas_x >= ix && canvas_x <= ix + iw && canvas_y >= iy && canvas_y <= iy + ih
         });
 
-        // If over a table, don't handle scroll here - let gpui-component Table handle it
+        // If over a table or code excerpt, don't handle scroll here - they scroll themselves
         if over_table {
             return;
         }
 
-        // Default: Canvas panning
-        match event.delta {
-            ScrollDelta::Pixels(delta) => {
-                board.canvas_offset.x += delta.x;
-                board.canvas_offset.y += delta.y;
-                board.mark_dirty();
-                cx.notify();
-            }
-            ScrollDelta::Lines(delta) => {
-                board.canvas_offset.x += px(delta.x * 20.0);
-                board.canvas_offset.y += px(delta.y * 20.0);
-                board.mark_dirty();
-                cx.notify();
-            }
-        }
+        // Default: Canvas panning (Shift+wheel scrolls horizontally)
+        let delta = match event.delta {
+            ScrollDelta::Pixels(delta) => (f32::from(delta.x), f32::from(delta.y)),
+            ScrollDelta::Lines(delta) => (delta.x * 20.0, delta.y * 20.0),
+        };
+        let (dx, dy) = scroll_pan_delta(delta, event.modifiers.shift);
+        board.canvas_offset.x += px(dx);
+        board.canvas_offset.y += px(dy);
+        board.mark_dirty();
+        cx.notify();
     }
 }
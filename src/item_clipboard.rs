@@ -0,0 +1,143 @@
+//! Copying items between boards.
+//!
+//! Copying puts the selected items on the system clipboard as
+//! [`CopiedItems`]: the items, the data sources their tables and charts
+//! show, and the board they came from. Pasting gives every item a new ID on
+//! the target board, keeps the items where they were relative to each other
+//! and moves the group to the paste point.
+//!
+//! Files stored inside the source board's directory (managed assets, iCloud
+//! files) belong to that board, so pasting on another board copies them into
+//! its own storage, see `Board::paste_items`.
+
+use crate::types::{CanvasItem, DataSource, ItemContent};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+/// Items copied from a board, as put on the clipboard
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct CopiedItems {
+    /// Board the items were copied from
+    pub board_id: String,
+    /// Directory of that board, whose files are copied along when pasting
+    /// on another board
+    pub board_dir: PathBuf,
+    /// The items, back to front
+    pub items: Vec<CanvasItem>,
+    /// Data sources shown by the copied tables and charts
+    pub data_sources: Vec<DataSource>,
+}
+
+impl CopiedItems {
+    /// Copy `items` (back to front) of a board, along with the data sources
+    /// they show out of the board's `data_sources`
+    pub fn new(
+        board_id: &str,
+        board_dir: PathBuf,
+        items: Vec<CanvasItem>,
+        data_sources: &HashMap<u64, DataSource>,
+    ) -> Self {
+        let mut shown: Vec<u64> = Vec::new();
+        for id in items
+            .iter()
+            .filter_map(|item| data_source_of(&item.content))
+        {
+            if !shown.contains(&id) {
+                shown.push(id);
+            }
+        }
+        Self {
+            board_id: board_id.to_string(),
+            board_dir,
+            items,
+            data_sources: shown
+                .iter()
+                .filter_map(|id| data_sources.get(id).cloned())
+                .collect(),
+        }
+    }
+
+    /// Plain text put on the clipboard with the items, for pasting into
+    /// other apps: the name of each item on its own line
+    pub fn text(&self) -> String {
+        self.items
+            .iter()
+            .map(|item| item.content.display_name())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// Top-left corner of the items' bounding box
+    pub fn origin(&self) -> (f32, f32) {
+        self.items
+            .iter()
+            .map(|item| item.position)
+            .reduce(|a, b| (a.0.min(b.0), a.1.min(b.1)))
+            .unwrap_or((0.0, 0.0))
+    }
+}
+
+/// ID of the data source a table or chart shows
+pub fn data_source_of(content: &ItemContent) -> Option<u64> {
+    match content {
+        ItemContent::Table { data_source_id, .. } | ItemContent::Chart { data_source_id, .. } => {
+            Some(*data_source_id)
+        }
+        _ => None,
+    }
+}
+
+/// Copies of `items` to paste: numbered from `next_id` on in order, moved
+/// by `offset`, showing the data sources `data_source_ids` maps theirs to.
+///
+/// A chart drawn from a pasted table is drawn from the table's copy. One
+/// drawn from a table that wasn't copied keeps it if `same_board`, and
+/// loses the connection on another board.
+pub fn remap_items(
+    items: &[CanvasItem],
+    next_id: u64,
+    offset: (f32, f32),
+    data_source_ids: &HashMap<u64, u64>,
+    same_board: bool,
+) -> Vec<CanvasItem> {
+    let ids: HashMap<u64, u64> = items
+        .iter()
+        .zip(next_id..)
+        .map(|(item, id)| (item.id, id))
+        .collect();
+
+    items
+        .iter()
+        .map(|item| {
+            let mut item = item.clone();
+            item.id = ids[&item.id];
+            item.position = (item.position.0 + offset.0, item.position.1 + offset.1);
+            match &mut item.content {
+                ItemContent::Table { data_source_id, .. } => {
+                    *data_source_id = data_source_ids
+                        .get(data_source_id)
+                        .copied()
+                        .unwrap_or(*data_source_id);
+                }
+                ItemContent::Chart {
+                    data_source_id,
+                    source_item_id,
+                    ..
+                } => {
+                    *data_source_id = data_source_ids
+                        .get(data_source_id)
+                        .copied()
+                        .unwrap_or(*data_source_id);
+                    *source_item_id = match *source_item_id {
+                        Some(source) if ids.contains_key(&source) => Some(ids[&source]),
+                        Some(source) if same_board => Some(source),
+                        _ => None,
+                    };
+                }
+                _ => {}
+            }
+            item
+        })
+        .collect()
+}
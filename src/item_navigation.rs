@@ -0,0 +1,75 @@
+//! Keyboard navigation between items.
+//!
+//! Tab and Shift+Tab step through items in reading order: top to bottom,
+//! then left to right. Cmd/Ctrl+Alt+Arrow jumps to the nearest item in that
+//! direction, measured between item centers.
+//!
+//! Functions here only pick the next item; the caller selects it and scrolls
+//! it into view.
+
+use crate::input::guides::Bounds;
+
+/// Direction for spatial navigation
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NavDirection {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+/// How much sideways distance counts against a candidate compared to
+/// distance along the direction, so items straight ahead win over closer
+/// items off to the side
+const OFF_AXIS_WEIGHT: f32 = 2.0;
+
+/// Item after (or before, with `reverse`) `current` in reading order, wrapping around.
+///
+/// With nothing selected this is the first (or last) item.
+pub fn next_in_order(items: &[(u64, Bounds)], current: Option<u64>, reverse: bool) -> Option<u64> {
+    let mut ordered: Vec<&(u64, Bounds)> = items.iter().collect();
+    ordered.sort_by(|(a_id, a), (b_id, b)| {
+        a.1.total_cmp(&b.1)
+            .then(a.0.total_cmp(&b.0))
+            .then(a_id.cmp(b_id))
+    });
+    let ids: Vec<u64> = ordered.into_iter().map(|&(id, _)| id).collect();
+    if ids.is_empty() {
+        return None;
+    }
+
+    let index = match current.and_then(|id| ids.iter().position(|&i| i == id)) {
+        Some(i) if reverse => (i + ids.len() - 1) % ids.len(),
+        Some(i) => (i + 1) % ids.len(),
+        None if reverse => ids.len() - 1,
+        None => 0,
+    };
+    Some(ids[index])
+}
+
+/// Nearest item to `from` in `direction`, or None when nothing lies that way
+pub fn nearest_in_direction(
+    items: &[(u64, Bounds)],
+    from: u64,
+    direction: NavDirection,
+) -> Option<u64> {
+    let center = |(x, y, w, h): Bounds| (x + w / 2.0, y + h / 2.0);
+    let &(_, from_bounds) = items.iter().find(|(id, _)| *id == from)?;
+    let (fx, fy) = center(from_bounds);
+
+    items
+        .iter()
+        .filter(|(id, _)| *id != from)
+        .filter_map(|&(id, bounds)| {
+            let (cx, cy) = center(bounds);
+            let (along, across) = match direction {
+                NavDirection::Up => (fy - cy, cx - fx),
+                NavDirection::Down => (cy - fy, cx - fx),
+                NavDirection::Left => (fx - cx, cy - fy),
+                NavDirection::Right => (cx - fx, cy - fy),
+            };
+            (along > 0.0).then_some((id, along + across.abs() * OFF_AXIS_WEIGHT))
+        })
+        .min_by(|(a_id, a), (b_id, b)| a.total_cmp(b).then(a_id.cmp(b_id)))
+        .map(|(id, _)| id)
+}
@@ -0,0 +1,219 @@
+//! Default styles of new items - the font size and color of text boxes,
+//! the fill and border of shapes, the thickness and head of arrows and the
+//! options of tables, kept in settings so new items don't need restyling.
+//!
+//! An empty color follows the theme, as it does on items themselves.
+
+use crate::constants::{
+    DEFAULT_ARROW_THICKNESS, DEFAULT_BORDER_WIDTH, DEFAULT_FONT_SIZE, MAX_ARROW_THICKNESS,
+    MAX_BORDER_WIDTH, MAX_FONT_SIZE, MIN_ARROW_THICKNESS, MIN_BORDER_WIDTH, MIN_FONT_SIZE,
+};
+use crate::types::{ArrowHead, ItemContent, ShapeType};
+use crate::validation::is_valid_hex_color;
+use serde::{Deserialize, Serialize};
+
+/// Kinds of item with a default style
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum StyledKind {
+    TextBox,
+    Shape,
+    Arrow,
+    Table,
+}
+
+impl StyledKind {
+    /// Kind of `content`, if it has a default style
+    pub fn of(content: &ItemContent) -> Option<Self> {
+        match content {
+            ItemContent::TextBox { .. } => Some(StyledKind::TextBox),
+            ItemContent::Shape { .. } => Some(StyledKind::Shape),
+            ItemContent::Arrow { .. } => Some(StyledKind::Arrow),
+            ItemContent::Table { .. } => Some(StyledKind::Table),
+            _ => None,
+        }
+    }
+
+    /// Plural name for messages, like "text boxes"
+    pub fn plural(&self) -> &'static str {
+        match self {
+            StyledKind::TextBox => "text boxes",
+            StyledKind::Shape => "shapes",
+            StyledKind::Arrow => "arrows",
+            StyledKind::Table => "tables",
+        }
+    }
+}
+
+/// Styles new text boxes, shapes, arrows and tables are made with
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ItemStyles {
+    /// Font size of text boxes in points
+    pub text_font_size: f32,
+    /// Text color of text boxes as hex
+    pub text_color: String,
+    /// Fill color of shapes as hex; None leaves them unfilled
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub shape_fill: Option<String>,
+    /// Border color of shapes as hex
+    pub shape_border_color: String,
+    /// Border width of shapes in pixels
+    pub shape_border_width: f32,
+    /// Color of arrows as hex
+    pub arrow_color: String,
+    /// Line thickness of arrows in pixels
+    pub arrow_thickness: f32,
+    pub arrow_head: ArrowHead,
+    /// Whether tables show their column headers
+    pub table_headers: bool,
+    /// Whether tables stripe alternating rows
+    pub table_stripe: bool,
+}
+
+impl Default for ItemStyles {
+    fn default() -> Self {
+        Self {
+            text_font_size: DEFAULT_FONT_SIZE,
+            text_color: String::new(),
+            shape_fill: None,
+            shape_border_color: String::new(),
+            shape_border_width: DEFAULT_BORDER_WIDTH,
+            arrow_color: String::new(),
+            arrow_thickness: DEFAULT_ARROW_THICKNESS,
+            arrow_head: ArrowHead::Arrow,
+            table_headers: true,
+            table_stripe: true,
+        }
+    }
+}
+
+impl ItemStyles {
+    /// An empty text box
+    pub fn text_box(&self) -> ItemContent {
+        ItemContent::TextBox {
+            text: String::new(),
+            font_size: self.text_font_size,
+            color: self.text_color.clone(),
+        }
+    }
+
+    /// A shape of `shape_type`
+    pub fn shape(&self, shape_type: ShapeType) -> ItemContent {
+        ItemContent::Shape {
+            shape_type,
+            fill_color: self.shape_fill.clone(),
+            border_color: self.shape_border_color.clone(),
+            border_width: self.shape_border_width,
+        }
+    }
+
+    /// An arrow to `end_offset` from where it starts
+    pub fn arrow(&self, end_offset: (f32, f32)) -> ItemContent {
+        ItemContent::Arrow {
+            end_offset,
+            color: self.arrow_color.clone(),
+            thickness: self.arrow_thickness,
+            head_style: self.arrow_head,
+        }
+    }
+
+    /// A table showing the data source with `data_source_id`
+    pub fn table(&self, data_source_id: u64) -> ItemContent {
+        ItemContent::Table {
+            data_source_id,
+            show_headers: self.table_headers,
+            stripe: self.table_stripe,
+        }
+    }
+
+    /// Take the style of `content` as the default for items of its kind.
+    /// Returns the kind, or None if `content` has no style to take.
+    pub fn set_from(&mut self, content: &ItemContent) -> Option<StyledKind> {
+        match content {
+            ItemContent::TextBox {
+                font_size, color, ..
+            } => {
+                self.text_font_size = *font_size;
+                self.text_color = color.clone();
+            }
+            ItemContent::Shape {
+                fill_color,
+                border_color,
+                border_width,
+                ..
+            } => {
+                self.shape_fill = fill_color.clone();
+                self.shape_border_color = border_color.clone();
+                self.shape_border_width = *border_width;
+            }
+            ItemContent::Arrow {
+                color,
+                thickness,
+                head_style,
+                ..
+            } => {
+                self.arrow_color = color.clone();
+                self.arrow_thickness = *thickness;
+                self.arrow_head = *head_style;
+            }
+            ItemContent::Table {
+                show_headers,
+                stripe,
+                ..
+            } => {
+                self.table_headers = *show_headers;
+                self.table_stripe = *stripe;
+            }
+            _ => return None,
+        }
+        StyledKind::of(content)
+    }
+
+    /// Take the styles of `items`, listed bottom to top, as the defaults.
+    /// Where several items are of one kind the topmost is used. Returns the
+    /// kinds whose default changed, in order.
+    pub fn set_from_items<'a>(
+        &mut self,
+        items: impl DoubleEndedIterator<Item = &'a ItemContent>,
+    ) -> Vec<StyledKind> {
+        let mut kinds = Vec::new();
+        for content in items.rev() {
+            match StyledKind::of(content) {
+                Some(kind) if !kinds.contains(&kind) => {
+                    self.set_from(content);
+                    kinds.push(kind);
+                }
+                _ => {}
+            }
+        }
+        kinds.sort();
+        *self = std::mem::take(self).sanitized();
+        kinds
+    }
+
+    /// These styles with sizes kept in range and colors that aren't colors
+    /// replaced by the theme's
+    pub fn sanitized(self) -> Self {
+        let color = |color: String| {
+            if color.is_empty() || is_valid_hex_color(&color) {
+                color
+            } else {
+                String::new()
+            }
+        };
+        Self {
+            text_font_size: self.text_font_size.clamp(MIN_FONT_SIZE, MAX_FONT_SIZE),
+            text_color: color(self.text_color),
+            shape_fill: self.shape_fill.filter(|fill| is_valid_hex_color(fill)),
+            shape_border_color: color(self.shape_border_color),
+            shape_border_width: self
+                .shape_border_width
+                .clamp(MIN_BORDER_WIDTH, MAX_BORDER_WIDTH),
+            arrow_color: color(self.arrow_color),
+            arrow_thickness: self
+                .arrow_thickness
+                .clamp(MIN_ARROW_THICKNESS, MAX_ARROW_THICKNESS),
+            ..self
+        }
+    }
+}
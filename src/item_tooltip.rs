@@ -0,0 +1,152 @@
+//! Hover tooltips with item details.
+//!
+//! Resting the cursor on an item for `HOVER_TOOLTIP_DELAY` shows a small
+//! tooltip with the item's name, full path and a few facts about it: pixel
+//! dimensions for images, duration for audio and video, row and column
+//! counts for tables, and its size on the canvas.
+//!
+//! Details that need to read the file (image dimensions, media duration) are
+//! looked up once, when the tooltip appears. The tooltip never takes mouse
+//! events, so pressing the button still starts a drag as usual.
+
+use crate::types::{CanvasItem, DataSource, ItemContent};
+use gpui::{Pixels, Point};
+use std::collections::HashMap;
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+/// How long the cursor has to rest on an item before its tooltip appears
+pub const HOVER_TOOLTIP_DELAY: Duration = Duration::from_millis(600);
+
+/// Details shown in an item's tooltip
+#[derive(Debug, Clone, PartialEq)]
+pub struct ItemTooltip {
+    pub title: String,
+    /// Full path for file items
+    pub path: Option<String>,
+    /// One fact per line, e.g. "1920 × 1080 px"
+    pub details: Vec<String>,
+}
+
+/// The item under the cursor and its tooltip once the delay has passed
+#[derive(Debug, Clone)]
+pub struct HoverState {
+    pub item_id: u64,
+    /// When the cursor arrived on the item
+    pub since: Instant,
+    /// Cursor position in window coordinates
+    pub position: Point<Pixels>,
+    pub tooltip: Option<ItemTooltip>,
+}
+
+impl HoverState {
+    pub fn new(item_id: u64, position: Point<Pixels>) -> Self {
+        Self {
+            item_id,
+            since: Instant::now(),
+            position,
+            tooltip: None,
+        }
+    }
+
+    /// Whether the cursor has rested on the item long enough to show the tooltip
+    pub fn is_due(&self) -> bool {
+        self.since.elapsed() >= HOVER_TOOLTIP_DELAY
+    }
+}
+
+/// Tooltip details that come from the board alone, without reading files
+pub fn item_tooltip(item: &CanvasItem, data_sources: &HashMap<u64, DataSource>) -> ItemTooltip {
+    let path = item.content.file_paths().first().map(|p| p.display().to_string());
+    let mut details = Vec::new();
+
+    let title = match &item.content {
+        ItemContent::Table { data_source_id, .. } | ItemContent::Chart { data_source_id, .. } => {
+            match data_sources.get(data_source_id) {
+                Some(source) => {
+                    details.push(format!(
+                        "{} rows × {} columns",
+                        source.row_count(),
+                        source.column_count()
+                    ));
+                    match &item.content {
+                        ItemContent::Chart { .. } => {
+                            format!("{} ({})", item.content.display_name(), source.name)
+                        }
+                        _ => source.name.clone(),
+                    }
+                }
+                None => item.content.display_name(),
+            }
+        }
+        ItemContent::Code {
+            path,
+            language,
+            lines,
+        } => {
+            details.push(language.clone());
+            if let Some((first, last)) = lines {
+                details.push(format!("Lines {}–{}", first, last));
+            }
+            file_name(path)
+        }
+        ItemContent::Markdown { path, .. } => file_name(path),
+        ItemContent::TextBox { .. } => "Text box".to_string(),
+        _ => item.content.display_name(),
+    };
+
+    details.push(format!(
+        "{} × {} on canvas",
+        item.size.0.round(),
+        item.size.1.round()
+    ));
+    if item.locked {
+        details.push("Locked".to_string());
+    }
+
+    ItemTooltip {
+        title,
+        path,
+        details,
+    }
+}
+
+/// Details read from the item's file: image dimensions or media duration.
+/// Missing or unreadable files give no details.
+pub fn media_details(content: &ItemContent) -> Vec<String> {
+    match content {
+        // Only the header is read, not the whole image
+        ItemContent::Image(path) => image::image_dimensions(path)
+            .map(|(width, height)| vec![format!("{} × {} px", width, height)])
+            .unwrap_or_default(),
+        ItemContent::Audio(path) | ItemContent::Video(path) => media_duration(path)
+            .map(|duration| vec![format_duration(duration)])
+            .unwrap_or_default(),
+        _ => Vec::new(),
+    }
+}
+
+/// Format a duration as `m:ss`, or `h:mm:ss` from an hour up
+pub fn format_duration(duration: Duration) -> String {
+    let total = duration.as_secs();
+    let (hours, minutes, seconds) = (total / 3600, (total / 60) % 60, total % 60);
+    if hours > 0 {
+        format!("{}:{:02}:{:02}", hours, minutes, seconds)
+    } else {
+        format!("{}:{:02}", minutes, seconds)
+    }
+}
+
+fn media_duration(path: &Path) -> Option<Duration> {
+    use lofty::AudioFile;
+    let tagged = lofty::Probe::open(path).ok()?.read().ok()?;
+    let duration = tagged.properties().duration();
+    (!duration.is_zero()).then_some(duration)
+}
+
+fn file_name(path: &Path) -> String {
+    path.file_name()
+        .and_then(|n| n.to_str())
+        .unwrap_or("Unknown")
+        .to_string()
+}
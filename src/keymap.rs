@@ -0,0 +1,578 @@
+//! User keymap - keybindings customized in `keymap.json`.
+//!
+//! `keymap.json` sits next to `settings.json` and maps command IDs to the
+//! keybindings that should run them, in place of the command's own:
+//!
+//! ```json
+//! {
+//!     "view:outline": "cmd-shift-o",
+//!     "edit:redo": ["cmd-shift-z", "cmd-y"],
+//!     "app:new_window": null
+//! }
+//! ```
+//!
+//! `null` or `[]` unbinds a command. Keybindings are written like the
+//! built-in ones (see command_registry.rs), with a space between the
+//! keystrokes of a sequence. The file is watched like the settings, and the
+//! keymap is bound again whenever it changes.
+
+use crate::actions::{
+    CancelTextboxEdit, CloseCommandPalette, CloseTab, CmdPaletteDown, CmdPaletteUp, DeleteSelected,
+    DeselectAll, DuplicateSelected, GoBack, GoForward, ModalFocusNext, ModalFocusPrev,
+    MoveTabToOtherPane, NextImage, NextSearchMatch, NextTab, NudgeDown, NudgeDownLarge, NudgeLeft,
+    NudgeLeftLarge, NudgeRight, NudgeRightLarge, NudgeUp, NudgeUpLarge, OpenSettings, Paste,
+    PrevImage, PrevSearchMatch, PrevTab, ReopenClosedTab, SaveCode, SelectAll, SelectItemDown,
+    SelectItemLeft, SelectItemRight, SelectItemUp, SelectNextItem, SelectPrevItem,
+    ToggleCommandPalette, ToggleMediaPlayback, TogglePaneSplit, TogglePreviewSearch,
+};
+use crate::command_registry::{COMMAND_REGISTRY, Command, CommandRegistry, platform_keystrokes};
+use crate::error::SettingsError;
+use crate::focus::FocusContext;
+use crate::settings_watcher::{SettingsEvent, SettingsWatcher};
+use gpui::{App, KeyBinding, KeyBindingMetaIndex};
+use once_cell::sync::Lazy;
+use parking_lot::{Mutex, RwLock};
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use tracing::{debug, warn};
+
+/// Modifiers a keystroke can have, in the order they're written
+const MODIFIERS: [&str; 8] = [
+    "cmd",
+    "super",
+    "win",
+    "secondary",
+    "ctrl",
+    "alt",
+    "shift",
+    "fn",
+];
+
+/// Get the default keymap file path.
+pub fn default_keymap_path() -> Option<PathBuf> {
+    dirs::config_dir().map(|p| p.join("humanboard").join("keymap.json"))
+}
+
+/// The modifiers and key of one keystroke, e.g. `cmd-shift-z`
+fn split_keystroke(keystroke: &str) -> (&str, &str) {
+    // "cmd--" is Cmd and the minus key
+    if keystroke == "-" {
+        ("", "-")
+    } else if let Some(modifiers) = keystroke.strip_suffix("--") {
+        (modifiers, "-")
+    } else {
+        keystroke.rsplit_once('-').unwrap_or(("", keystroke))
+    }
+}
+
+/// `keystroke` lowercased, with its modifiers in the usual order, or None
+/// if it has no key or a modifier GPUI doesn't know
+fn normalize_keystroke(keystroke: &str) -> Option<String> {
+    let keystroke = keystroke.to_lowercase();
+    let (modifiers, key) = split_keystroke(&keystroke);
+    if key.is_empty() {
+        return None;
+    }
+
+    let mut order = Vec::new();
+    if !modifiers.is_empty() {
+        for modifier in modifiers.split('-') {
+            let index = MODIFIERS.iter().position(|known| *known == modifier)?;
+            if !order.contains(&index) {
+                order.push(index);
+            }
+        }
+    }
+    order.sort_unstable();
+
+    let mut normalized: Vec<&str> = order.into_iter().map(|index| MODIFIERS[index]).collect();
+    normalized.push(key);
+    Some(normalized.join("-"))
+}
+
+/// `chord` lowercased, with the modifiers of each keystroke in the usual
+/// order, or None if GPUI couldn't parse it
+pub fn normalize_chord(chord: &str) -> Option<String> {
+    let keystrokes = chord
+        .split_whitespace()
+        .map(normalize_keystroke)
+        .collect::<Option<Vec<_>>>()?;
+    if keystrokes.is_empty() {
+        return None;
+    }
+    Some(keystrokes.join(" "))
+}
+
+/// Whether `chord` can be bound. Binding one that can't panics in GPUI.
+pub fn is_valid_chord(chord: &str) -> bool {
+    normalize_chord(chord).is_some()
+}
+
+/// A command's keybindings as written in keymap.json
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum Chords {
+    One(String),
+    Many(Vec<String>),
+}
+
+/// Keybindings the user gave commands in keymap.json
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct UserKeymap {
+    /// Keybindings of each remapped command, by command ID. An empty list
+    /// unbinds the command.
+    pub bindings: BTreeMap<String, Vec<String>>,
+    /// Keybindings that couldn't be read, with their command ID
+    pub invalid: Vec<(String, String)>,
+}
+
+impl UserKeymap {
+    /// Parse the contents of a keymap file. An empty file remaps nothing.
+    pub fn parse(json: &str) -> Result<Self, serde_json::Error> {
+        let mut keymap = Self::default();
+        if json.trim().is_empty() {
+            return Ok(keymap);
+        }
+
+        let entries: BTreeMap<String, Option<Chords>> = serde_json::from_str(json)?;
+        for (id, chords) in entries {
+            let chords = match chords {
+                Some(Chords::One(chord)) => vec![chord],
+                Some(Chords::Many(chords)) => chords,
+                None => Vec::new(),
+            };
+            let mut valid = Vec::new();
+            for chord in chords {
+                match normalize_chord(&chord) {
+                    Some(chord) => {
+                        if !valid.contains(&chord) {
+                            valid.push(chord);
+                        }
+                    }
+                    None => keymap.invalid.push((id.clone(), chord)),
+                }
+            }
+            keymap.bindings.insert(id, valid);
+        }
+        Ok(keymap)
+    }
+
+    /// Load the keymap at `path`. A missing file remaps nothing.
+    pub fn load(path: &Path) -> Result<Self, SettingsError> {
+        if !path.exists() {
+            debug!("Keymap file not found: {:?}", path);
+            return Ok(Self::default());
+        }
+        let json = std::fs::read_to_string(path).map_err(|e| SettingsError::LoadFailed {
+            path: path.to_path_buf(),
+            source: e,
+        })?;
+        Self::parse(&json).map_err(|e| SettingsError::ParseFailed {
+            path: path.to_path_buf(),
+            message: e.to_string(),
+        })
+    }
+
+    /// Whether the user remapped the command `id`
+    pub fn is_customized(&self, id: &str) -> bool {
+        self.bindings.contains_key(id)
+    }
+
+    /// Keybindings that run `command`: the user's if they remapped it,
+    /// otherwise the command's own
+    pub fn keybindings_for(&self, command: &dyn Command) -> Vec<String> {
+        match self.bindings.get(command.id()) {
+            Some(chords) => chords.clone(),
+            None => command
+                .keybindings()
+                .iter()
+                .map(|key| key.to_string())
+                .collect(),
+        }
+    }
+
+    /// Remapped command IDs that aren't registered
+    pub fn unknown_commands(&self, registry: &CommandRegistry) -> Vec<String> {
+        self.bindings
+            .keys()
+            .filter(|id| registry.get(id).is_none())
+            .cloned()
+            .collect()
+    }
+}
+
+/// A command with the keybindings that run it
+#[derive(Debug, Clone, PartialEq)]
+pub struct CommandBinding {
+    pub id: &'static str,
+    pub name: String,
+    pub category: String,
+    pub keys: Vec<String>,
+    /// Key contexts the keybindings apply in; none means everywhere
+    pub contexts: Vec<String>,
+    /// Whether the keys come from keymap.json
+    pub customized: bool,
+}
+
+/// Every registered command with its keybindings under `keymap`, in
+/// registry order
+pub fn command_bindings(registry: &CommandRegistry, keymap: &UserKeymap) -> Vec<CommandBinding> {
+    registry
+        .all()
+        .map(|command| CommandBinding {
+            id: command.id(),
+            name: command.name().to_string(),
+            category: command.category().to_string(),
+            keys: keymap.keybindings_for(command.as_ref()),
+            contexts: command
+                .key_contexts()
+                .iter()
+                .map(|context| context.to_string())
+                .collect(),
+            customized: keymap.is_customized(command.id()),
+        })
+        .collect()
+}
+
+/// Commands bound to the same keystroke where they'd both apply
+#[derive(Debug, Clone, PartialEq)]
+pub struct KeyConflict {
+    pub keystroke: String,
+    pub commands: Vec<&'static str>,
+}
+
+/// Whether keybindings in `a` and `b` can apply at the same time
+fn contexts_overlap(a: &[String], b: &[String]) -> bool {
+    a.is_empty() || b.is_empty() || a.iter().any(|context| b.contains(context))
+}
+
+/// Keystrokes bound to more than one command. `cmd-` keybindings are
+/// compared on every platform, so `cmd-y` conflicts with `ctrl-y`.
+pub fn find_conflicts(bindings: &[CommandBinding]) -> Vec<KeyConflict> {
+    let mut by_keystroke: BTreeMap<String, Vec<&CommandBinding>> = BTreeMap::new();
+    for binding in bindings {
+        for key in &binding.keys {
+            let Some(key) = normalize_chord(key) else {
+                continue;
+            };
+            for keystroke in platform_keystrokes(&key) {
+                let commands = by_keystroke.entry(keystroke).or_default();
+                if !commands.iter().any(|other| other.id == binding.id) {
+                    commands.push(binding);
+                }
+            }
+        }
+    }
+
+    let mut conflicts = Vec::new();
+    for (keystroke, commands) in by_keystroke {
+        let clashing: Vec<&'static str> = commands
+            .iter()
+            .filter(|binding| {
+                commands.iter().any(|other| {
+                    other.id != binding.id && contexts_overlap(&binding.contexts, &other.contexts)
+                })
+            })
+            .map(|binding| binding.id)
+            .collect();
+        if clashing.len() > 1 {
+            conflicts.push(KeyConflict {
+                keystroke,
+                commands: clashing,
+            });
+        }
+    }
+    conflicts
+}
+
+/// The keymap loaded from keymap.json, and the error if it couldn't be read
+#[derive(Default)]
+pub struct LoadedKeymap {
+    pub keymap: UserKeymap,
+    pub error: Option<String>,
+}
+
+/// The user keymap shared by every window
+pub static USER_KEYMAP: Lazy<RwLock<LoadedKeymap>> =
+    Lazy::new(|| RwLock::new(LoadedKeymap::default()));
+
+/// Read keymap.json again. If it can't be read, the keymap loaded before
+/// stays in use.
+pub fn reload_user_keymap() -> Result<(), SettingsError> {
+    let Some(path) = default_keymap_path() else {
+        return Ok(());
+    };
+    match UserKeymap::load(&path) {
+        Ok(keymap) => {
+            for (id, chord) in &keymap.invalid {
+                warn!("Ignoring invalid keybinding {:?} for {}", chord, id);
+            }
+            *USER_KEYMAP.write() = LoadedKeymap {
+                keymap,
+                error: None,
+            };
+            Ok(())
+        }
+        Err(e) => {
+            USER_KEYMAP.write().error = Some(e.to_string());
+            Err(e)
+        }
+    }
+}
+
+/// Marks the keybindings Humanboard binds, to tell them from those of
+/// gpui_component's inputs, lists and dialogs
+const APP_BINDING: KeyBindingMetaIndex = KeyBindingMetaIndex(1);
+
+/// Bind the whole keymap, replacing the keybindings it bound before where
+/// they were. Other keybindings, like gpui_component's, are kept. Commands
+/// are bound last, so keybindings the user gave them win.
+pub fn bind_keymap(cx: &mut App) {
+    let mut bindings = fixed_key_bindings();
+    {
+        let loaded = USER_KEYMAP.read();
+        let registry = COMMAND_REGISTRY.read();
+        for command in registry.all() {
+            let keys = loaded.keymap.keybindings_for(command.as_ref());
+            let keys: Vec<&str> = keys.iter().map(String::as_str).collect();
+            bindings.extend(command.key_bindings_for(&keys));
+        }
+    }
+    let bindings = bindings
+        .into_iter()
+        .map(|binding| binding.with_meta(APP_BINDING));
+
+    // Later keybindings win, so the app's go back where they were
+    let bound: Vec<KeyBinding> = cx.key_bindings().borrow().bindings().cloned().collect();
+    let is_app = |binding: &KeyBinding| binding.meta() == Some(APP_BINDING);
+    let at = bound.iter().position(is_app).unwrap_or(bound.len());
+    let (before, after) = bound.split_at(at);
+    let others = |bindings: &[KeyBinding]| {
+        bindings
+            .iter()
+            .filter(|binding| !is_app(binding))
+            .cloned()
+            .collect::<Vec<_>>()
+    };
+    let (before, after) = (others(before), others(after));
+
+    cx.clear_key_bindings();
+    cx.bind_keys(before);
+    cx.bind_keys(bindings);
+    cx.bind_keys(after);
+    debug!("Keybindings registered");
+}
+
+/// Watcher of keymap.json, shared by every window so an edit rebinds the
+/// keys once
+static KEYMAP_WATCHER: Lazy<Mutex<Option<SettingsWatcher>>> = Lazy::new(|| {
+    Mutex::new(default_keymap_path().and_then(|path| SettingsWatcher::new(path).ok()))
+});
+
+/// The latest change to keymap.json since it was last asked for. Whichever
+/// window asks first gets it.
+pub fn poll_keymap_change() -> Option<SettingsEvent> {
+    KEYMAP_WATCHER.lock().as_mut()?.poll()
+}
+
+/// Keybindings of actions that aren't commands
+fn fixed_key_bindings() -> Vec<KeyBinding> {
+    let mut bindings = Vec::new();
+
+    // Preview panel shortcuts (always active, no context)
+    // Register both cmd- (macOS) and ctrl- (Linux/Windows) variants
+    bindings.extend([
+        // Tab navigation
+        KeyBinding::new("cmd-shift-]", NextTab, None),
+        KeyBinding::new("ctrl-shift-]", NextTab, None),
+        KeyBinding::new("ctrl-tab", NextTab, None),
+        KeyBinding::new("cmd-shift-[", PrevTab, None),
+        KeyBinding::new("ctrl-shift-[", PrevTab, None),
+        KeyBinding::new("ctrl-shift-tab", PrevTab, None),
+        KeyBinding::new("cmd-w", CloseTab, None),
+        KeyBinding::new("ctrl-w", CloseTab, None),
+        KeyBinding::new("cmd-shift-t", ReopenClosedTab, None),
+        KeyBinding::new("ctrl-shift-t", ReopenClosedTab, None),
+        // History navigation
+        KeyBinding::new("cmd-[", GoBack, None),
+        KeyBinding::new("ctrl-[", GoBack, None),
+        KeyBinding::new("alt-left", GoBack, None),
+        KeyBinding::new("cmd-]", GoForward, None),
+        KeyBinding::new("ctrl-]", GoForward, None),
+        KeyBinding::new("alt-right", GoForward, None),
+        // Search
+        KeyBinding::new("cmd-f", TogglePreviewSearch, None),
+        KeyBinding::new("ctrl-f", TogglePreviewSearch, None),
+        KeyBinding::new("cmd-g", NextSearchMatch, None),
+        KeyBinding::new("ctrl-g", NextSearchMatch, None),
+        KeyBinding::new("cmd-shift-g", PrevSearchMatch, None),
+        KeyBinding::new("ctrl-shift-g", PrevSearchMatch, None),
+        // Pane split
+        KeyBinding::new("cmd-shift-d", TogglePaneSplit, None),
+        KeyBinding::new("ctrl-shift-d", TogglePaneSplit, None),
+        KeyBinding::new("cmd-alt-shift-right", MoveTabToOtherPane, None),
+        KeyBinding::new("ctrl-alt-shift-right", MoveTabToOtherPane, None),
+        KeyBinding::new("cmd-alt-shift-left", MoveTabToOtherPane, None),
+        KeyBinding::new("ctrl-alt-shift-left", MoveTabToOtherPane, None),
+    ]);
+
+    // Save shortcuts for different contexts
+    bindings.extend([
+        KeyBinding::new("cmd-s", SaveCode, Some(FocusContext::KEY_CODE_EDITOR)),
+        KeyBinding::new("ctrl-s", SaveCode, Some(FocusContext::KEY_CODE_EDITOR)),
+        KeyBinding::new("cmd-s", SaveCode, Some(FocusContext::KEY_CANVAS)),
+        KeyBinding::new("ctrl-s", SaveCode, Some(FocusContext::KEY_CANVAS)),
+        KeyBinding::new(
+            "cmd-s",
+            SaveCode,
+            Some(FocusContext::KEY_CANVAS_INPUT_ACTIVE),
+        ),
+        KeyBinding::new(
+            "ctrl-s",
+            SaveCode,
+            Some(FocusContext::KEY_CANVAS_INPUT_ACTIVE),
+        ),
+        KeyBinding::new("cmd-s", SaveCode, Some(FocusContext::KEY_PREVIEW)),
+        KeyBinding::new("ctrl-s", SaveCode, Some(FocusContext::KEY_PREVIEW)),
+    ]);
+
+    // Image preview tabs step through the board's images with the arrow keys
+    bindings.extend([
+        KeyBinding::new("left", PrevImage, Some(FocusContext::KEY_PREVIEW)),
+        KeyBinding::new("right", NextImage, Some(FocusContext::KEY_PREVIEW)),
+    ]);
+
+    // Canvas-only shortcuts (not active when text input is focused)
+    bindings.extend([
+        // Selection actions
+        KeyBinding::new("backspace", DeleteSelected, Some(FocusContext::KEY_CANVAS)),
+        KeyBinding::new("delete", DeleteSelected, Some(FocusContext::KEY_CANVAS)),
+        KeyBinding::new("cmd-d", DuplicateSelected, Some(FocusContext::KEY_CANVAS)),
+        KeyBinding::new("ctrl-d", DuplicateSelected, Some(FocusContext::KEY_CANVAS)),
+        KeyBinding::new("cmd-a", SelectAll, Some(FocusContext::KEY_CANVAS)),
+        KeyBinding::new("ctrl-a", SelectAll, Some(FocusContext::KEY_CANVAS)),
+        KeyBinding::new("escape", DeselectAll, Some(FocusContext::KEY_CANVAS)),
+        // Copy items, to paste on this board or another one
+        KeyBinding::new("cmd-c", crate::actions::Copy, Some(FocusContext::KEY_CANVAS)),
+        KeyBinding::new("ctrl-c", crate::actions::Copy, Some(FocusContext::KEY_CANVAS)),
+        // Paste (handles URLs including YouTube)
+        KeyBinding::new("cmd-v", Paste, Some(FocusContext::KEY_CANVAS)),
+        KeyBinding::new("ctrl-v", Paste, Some(FocusContext::KEY_CANVAS)),
+        // Command palette (cmd-k / ctrl-k toggles open/close)
+        KeyBinding::new(
+            "cmd-k",
+            ToggleCommandPalette,
+            Some(FocusContext::KEY_CANVAS),
+        ),
+        KeyBinding::new(
+            "ctrl-k",
+            ToggleCommandPalette,
+            Some(FocusContext::KEY_CANVAS),
+        ),
+        // Media playback (tapping Space also toggles, see input/keyboard.rs).
+        // Hardware media keys aren't delivered as key events, so F8 - the
+        // play/pause key on Mac keyboards - stands in for them.
+        KeyBinding::new("f8", ToggleMediaPlayback, Some(FocusContext::KEY_CANVAS)),
+        // Arrow keys to nudge selected items
+        KeyBinding::new("up", NudgeUp, Some(FocusContext::KEY_CANVAS)),
+        KeyBinding::new("down", NudgeDown, Some(FocusContext::KEY_CANVAS)),
+        KeyBinding::new("left", NudgeLeft, Some(FocusContext::KEY_CANVAS)),
+        KeyBinding::new("right", NudgeRight, Some(FocusContext::KEY_CANVAS)),
+        KeyBinding::new("shift-up", NudgeUpLarge, Some(FocusContext::KEY_CANVAS)),
+        KeyBinding::new("shift-down", NudgeDownLarge, Some(FocusContext::KEY_CANVAS)),
+        KeyBinding::new("shift-left", NudgeLeftLarge, Some(FocusContext::KEY_CANVAS)),
+        KeyBinding::new(
+            "shift-right",
+            NudgeRightLarge,
+            Some(FocusContext::KEY_CANVAS),
+        ),
+        // Keyboard navigation between items
+        KeyBinding::new("tab", SelectNextItem, Some(FocusContext::KEY_CANVAS)),
+        KeyBinding::new("shift-tab", SelectPrevItem, Some(FocusContext::KEY_CANVAS)),
+        KeyBinding::new("cmd-alt-up", SelectItemUp, Some(FocusContext::KEY_CANVAS)),
+        KeyBinding::new("ctrl-alt-up", SelectItemUp, Some(FocusContext::KEY_CANVAS)),
+        KeyBinding::new(
+            "cmd-alt-down",
+            SelectItemDown,
+            Some(FocusContext::KEY_CANVAS),
+        ),
+        KeyBinding::new(
+            "ctrl-alt-down",
+            SelectItemDown,
+            Some(FocusContext::KEY_CANVAS),
+        ),
+        KeyBinding::new(
+            "cmd-alt-left",
+            SelectItemLeft,
+            Some(FocusContext::KEY_CANVAS),
+        ),
+        KeyBinding::new(
+            "ctrl-alt-left",
+            SelectItemLeft,
+            Some(FocusContext::KEY_CANVAS),
+        ),
+        KeyBinding::new(
+            "cmd-alt-right",
+            SelectItemRight,
+            Some(FocusContext::KEY_CANVAS),
+        ),
+        KeyBinding::new(
+            "ctrl-alt-right",
+            SelectItemRight,
+            Some(FocusContext::KEY_CANVAS),
+        ),
+    ]);
+
+    // Shortcuts that work even when input is active
+    bindings.extend([
+        KeyBinding::new(
+            "cmd-k",
+            ToggleCommandPalette,
+            Some(FocusContext::KEY_CANVAS_INPUT_ACTIVE),
+        ),
+        KeyBinding::new(
+            "ctrl-k",
+            ToggleCommandPalette,
+            Some(FocusContext::KEY_CANVAS_INPUT_ACTIVE),
+        ),
+        KeyBinding::new(
+            "escape",
+            CancelTextboxEdit,
+            Some(FocusContext::KEY_CANVAS_INPUT_ACTIVE),
+        ),
+    ]);
+
+    // Landing page shortcuts
+    bindings.extend([
+        KeyBinding::new(
+            "cmd-k",
+            ToggleCommandPalette,
+            Some(FocusContext::KEY_LANDING),
+        ),
+        KeyBinding::new(
+            "ctrl-k",
+            ToggleCommandPalette,
+            Some(FocusContext::KEY_LANDING),
+        ),
+    ]);
+
+    // Command palette navigation - use global bindings because when Input has focus,
+    // the CommandPalette key context is NOT in the focus path (Input's own focus takes over)
+    // The action handlers check if the palette is open before acting
+    bindings.extend([
+        KeyBinding::new("up", CmdPaletteUp, None),
+        KeyBinding::new("down", CmdPaletteDown, None),
+        KeyBinding::new("escape", CloseCommandPalette, None),
+    ]);
+
+    // Modal context shortcuts (settings, dialogs)
+    // Focus trap: Tab and Shift+Tab cycle focus within modal
+    bindings.extend([
+        KeyBinding::new("escape", OpenSettings, Some(FocusContext::KEY_MODAL)),
+        KeyBinding::new("tab", ModalFocusNext, Some(FocusContext::KEY_MODAL)),
+        KeyBinding::new("shift-tab", ModalFocusPrev, Some(FocusContext::KEY_MODAL)),
+    ]);
+
+    bindings
+}
@@ -6,8 +6,8 @@
 //! ## Components
 //!
 //! - **Header**: App title and "New Board" button
-//! - **Board Grid**: Cards for each board with edit/delete actions
-//! - **Trash Section**: Collapsible list of deleted boards (30-day retention)
+//! - **Board Grid**: Cards for each board with a rendered thumbnail and edit/delete actions
+//! - **Trash Section**: Collapsible list of deleted boards (purged after the configured retention period)
 //! - **Empty State**: Shown when no boards exist
 //! - **Delete Dialog**: Confirmation modal for board deletion
 
@@ -20,6 +20,15 @@ use gpui_component::button::{Button, ButtonVariants};
 use gpui_component::input::{Input, InputState};
 use gpui_component::{ActiveTheme as _, Icon, IconName, h_flex, v_flex};
 
+/// Summary shown next to the trash header, e.g. "(3) - auto-deleted after 30 days"
+fn trash_retention_label(count: usize) -> String {
+    match crate::settings::trash_retention_days() {
+        0 => format!("({}) - kept until emptied", count),
+        1 => format!("({}) - auto-deleted after 1 day", count),
+        days => format!("({}) - auto-deleted after {} days", count, days),
+    }
+}
+
 /// Render the landing page header bar
 pub fn render_landing_header(cx: &mut Context<crate::app::Humanboard>) -> Div {
     let bg = cx.theme().title_bar;
@@ -91,6 +100,9 @@ pub fn render_board_card(
     let board_id_for_click = metadata.id.clone();
     let board_id_for_edit = metadata.id.clone();
     let board_id_for_delete = metadata.id.clone();
+    let encrypted = metadata.encrypted;
+    // Encrypted boards never get a thumbnail, but an old one may be left over
+    let thumbnail = metadata.thumbnail_path().filter(|_| !encrypted);
 
     let bg = cx.theme().popover;
     let border = cx.theme().border;
@@ -122,6 +134,7 @@ pub fn render_board_card(
                 .flex()
                 .items_center()
                 .justify_center()
+                .overflow_hidden()
                 .when(!is_editing, |d| {
                     d.on_mouse_down(
                         MouseButton::Left,
@@ -130,11 +143,21 @@ pub fn render_board_card(
                         }),
                     )
                 })
-                .child(
-                    Icon::new(IconName::LayoutDashboard)
-                        .size(px(32.0))
-                        .text_color(muted_fg),
-                ),
+                .map(|d| match thumbnail {
+                    Some(path) => d.child(img(path).size_full().object_fit(ObjectFit::Cover)),
+                    None if encrypted => d.child(
+                        v_flex()
+                            .items_center()
+                            .gap_1()
+                            .child(Icon::new(IconName::EyeOff).size(px(28.0)).text_color(muted_fg))
+                            .child(div().text_xs().text_color(muted_fg).child("Encrypted")),
+                    ),
+                    None => d.child(
+                        Icon::new(IconName::LayoutDashboard)
+                            .size(px(32.0))
+                            .text_color(muted_fg),
+                    ),
+                }),
         )
         .child(
             // Info area
@@ -498,7 +521,7 @@ pub fn render_trash_section(
                             div()
                                 .text_xs()
                                 .text_color(muted_fg)
-                                .child(format!("({}) - auto-deleted after 30 days", trashed_boards.len())),
+                                .child(trash_retention_label(trashed_boards.len())),
                         ),
                 )
                 .child(
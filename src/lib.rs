@@ -20,36 +20,94 @@ pub use anyhow::{Context, Result};
 pub mod actions;
 pub mod animations;
 pub mod app;
+pub mod arrange;
+pub mod audio_metadata;
 pub mod background;
 pub mod board;
+pub mod board_assets;
+pub mod board_crypto;
+pub mod board_diff;
+pub mod board_export;
+pub mod board_format;
 pub mod board_index;
+pub mod board_journal;
+pub mod board_outline;
+pub mod board_thumbnail;
+pub mod board_versions;
+pub mod canvas_background;
+pub mod canvas_find;
+pub mod canvas_snapshot;
+pub mod card_refresh;
+pub mod code_excerpt;
+pub mod code_symbols;
 pub mod command_palette;
 pub mod command_registry;
 pub mod constants;
+pub mod content_search;
+pub mod context_menu;
+pub mod custom_themes;
 pub mod data;
 pub mod data_table;
+pub mod document_export;
 pub mod error;
+pub mod file_import;
+pub mod file_links;
+pub mod find_replace;
 pub mod focus;
 pub mod focus_ring;
+pub mod frecency;
+pub mod fuzzy;
 pub mod hit_testing;
 pub mod home;
+pub mod image_cache;
+pub mod image_viewer;
 pub mod input;
+pub mod item_clipboard;
+pub mod item_navigation;
+pub mod item_styles;
+pub mod item_tooltip;
+pub mod keymap;
 pub mod landing;
 pub mod loading;
 pub mod markdown_card;
+pub mod markdown_links;
+pub mod markdown_outline;
+pub mod memory;
 pub mod notifications;
+pub mod obsidian_canvas;
 pub mod onboarding;
 pub mod pdf;
 pub mod perf;
+pub mod playlist;
 pub mod preview;
+pub mod preview_history;
+pub mod preview_session;
+pub mod progressive_load;
+pub mod quick_open;
+pub mod region_export;
 pub mod render;
+pub mod search_query;
 pub mod selection;
 pub mod settings;
+pub mod settings_profile;
 pub mod settings_watcher;
 pub mod spatial_index;
+pub mod subtitles;
+pub mod syntax_highlight;
+pub mod syntax_tokens;
+pub mod tab_zoom;
+pub mod text_diff;
 pub mod types;
+pub mod undo_history;
+pub mod url_paste;
 pub mod validation;
+pub mod waveform;
 pub mod webviews;
+pub mod whiteboard_import;
+pub mod wiki_links;
 
 // Re-export webview types for backwards compatibility
-pub use webviews::{AudioWebView, PdfWebView, VideoWebView, YouTubeWebView};
+pub use webviews::{
+    AudioWebView, PdfWebView, SoundCloudWebView, SpotifyWebView, VideoWebView, VimeoWebView,
+    YouTubeWebView,
+};
@@ -5,16 +5,8 @@
 
 use anyhow::{Context, Result};
 use gpui::*;
-use humanboard::actions::{
-    CancelTextboxEdit, CloseCommandPalette, CloseTab, CmdPaletteDown, CmdPaletteUp, DeleteSelected,
-    DeselectAll, DuplicateSelected, GoBack, GoForward, GoHome, ModalFocusNext, ModalFocusPrev,
-    MoveTabToOtherPane, NewBoard, NextSearchMatch, NextTab, NudgeDown, NudgeLeft, NudgeRight,
-    NudgeUp, OpenFile, OpenSettings, Paste, PrevSearchMatch, PrevTab, Quit, Redo, ReopenClosedTab,
-    SaveCode, SelectAll, ShowShortcuts, ToggleCommandPalette, TogglePaneSplit, TogglePreviewSearch,
-    Undo, ZoomIn, ZoomOut, ZoomReset,
-};
-use humanboard::app::Humanboard;
-use humanboard::focus::FocusContext;
+use humanboard::actions::{NewWindow, Quit};
+use humanboard::command_registry::COMMAND_REGISTRY;
 use once_cell::sync::Lazy;
 use std::borrow::Cow;
 use std::time::Instant;
@@ -152,32 +144,9 @@ fn init_logging() {
     info!("Humanboard v{} starting up", env!("CARGO_PKG_VERSION"));
 }
 
-/// Build window options following Zed's patterns.
-fn build_window_options() -> WindowOptions {
-    WindowOptions {
-        window_bounds: Some(WindowBounds::Windowed(Bounds {
-            origin: Point::new(px(100.0), px(100.0)),
-            size: Size {
-                width: px(1400.0),
-                height: px(900.0),
-            },
-        })),
-        titlebar: Some(TitlebarOptions {
-            title: Some("Humanboard".into()),
-            appears_transparent: true,
-            ..Default::default()
-        }),
-        ..Default::default()
-    }
-}
-
 /// Open the main application window.
 fn open_main_window(cx: &mut App) -> Result<()> {
-    cx.open_window(build_window_options(), |window, cx| {
-        let app_view = cx.new(Humanboard::new);
-        cx.new(|cx| gpui_component::Root::new(app_view, window, cx))
-    })
-    .context("Failed to open main window")?;
+    humanboard::app::open_window(cx).context("Failed to open main window")?;
 
     info!("Main window opened in {:?}", STARTUP_TIME.elapsed());
     Ok(())
@@ -192,143 +161,19 @@ fn register_keybindings(cx: &mut App) {
         cx.quit();
     });
 
-    // Global shortcuts (always active, no context)
-    // Register both cmd- (macOS) and ctrl- (Linux/Windows) variants
-    cx.bind_keys([
-        // Quit - cmd-q on macOS, ctrl-q on Linux/Windows
-        KeyBinding::new("cmd-q", Quit, None),
-        KeyBinding::new("ctrl-q", Quit, None),
-        // Open file
-        KeyBinding::new("cmd-o", OpenFile, None),
-        KeyBinding::new("ctrl-o", OpenFile, None),
-        // Zoom
-        KeyBinding::new("cmd-=", ZoomIn, None),
-        KeyBinding::new("ctrl-=", ZoomIn, None),
-        KeyBinding::new("cmd-+", ZoomIn, None),
-        KeyBinding::new("ctrl-+", ZoomIn, None),
-        KeyBinding::new("cmd--", ZoomOut, None),
-        KeyBinding::new("ctrl--", ZoomOut, None),
-        KeyBinding::new("cmd-0", ZoomReset, None),
-        KeyBinding::new("ctrl-0", ZoomReset, None),
-        // Tab navigation
-        KeyBinding::new("cmd-shift-]", NextTab, None),
-        KeyBinding::new("ctrl-shift-]", NextTab, None),
-        KeyBinding::new("ctrl-tab", NextTab, None),
-        KeyBinding::new("cmd-shift-[", PrevTab, None),
-        KeyBinding::new("ctrl-shift-[", PrevTab, None),
-        KeyBinding::new("ctrl-shift-tab", PrevTab, None),
-        KeyBinding::new("cmd-w", CloseTab, None),
-        KeyBinding::new("ctrl-w", CloseTab, None),
-        KeyBinding::new("cmd-shift-t", ReopenClosedTab, None),
-        KeyBinding::new("ctrl-shift-t", ReopenClosedTab, None),
-        // History navigation
-        KeyBinding::new("cmd-[", GoBack, None),
-        KeyBinding::new("ctrl-[", GoBack, None),
-        KeyBinding::new("alt-left", GoBack, None),
-        KeyBinding::new("cmd-]", GoForward, None),
-        KeyBinding::new("ctrl-]", GoForward, None),
-        KeyBinding::new("alt-right", GoForward, None),
-        // Search
-        KeyBinding::new("cmd-f", TogglePreviewSearch, None),
-        KeyBinding::new("ctrl-f", TogglePreviewSearch, None),
-        KeyBinding::new("cmd-g", NextSearchMatch, None),
-        KeyBinding::new("ctrl-g", NextSearchMatch, None),
-        KeyBinding::new("cmd-shift-g", PrevSearchMatch, None),
-        KeyBinding::new("ctrl-shift-g", PrevSearchMatch, None),
-        // Pane split
-        KeyBinding::new("cmd-shift-d", TogglePaneSplit, None),
-        KeyBinding::new("ctrl-shift-d", TogglePaneSplit, None),
-        KeyBinding::new("cmd-alt-shift-right", MoveTabToOtherPane, None),
-        KeyBinding::new("ctrl-alt-shift-right", MoveTabToOtherPane, None),
-        KeyBinding::new("cmd-alt-shift-left", MoveTabToOtherPane, None),
-        KeyBinding::new("ctrl-alt-shift-left", MoveTabToOtherPane, None),
-        // Board management
-        KeyBinding::new("cmd-n", NewBoard, None),
-        KeyBinding::new("ctrl-n", NewBoard, None),
-        KeyBinding::new("cmd-h", GoHome, None),
-        KeyBinding::new("ctrl-h", GoHome, None),
-        // Help & settings
-        KeyBinding::new("cmd-/", ShowShortcuts, None),
-        KeyBinding::new("ctrl-/", ShowShortcuts, None),
-        KeyBinding::new("cmd-,", OpenSettings, None),
-        KeyBinding::new("ctrl-,", OpenSettings, None),
-    ]);
-
-    // Canvas-specific shortcuts (undo/redo - code editor handles its own)
-    cx.bind_keys([
-        KeyBinding::new("cmd-z", Undo, Some(FocusContext::KEY_CANVAS)),
-        KeyBinding::new("ctrl-z", Undo, Some(FocusContext::KEY_CANVAS)),
-        KeyBinding::new("cmd-shift-z", Redo, Some(FocusContext::KEY_CANVAS)),
-        KeyBinding::new("ctrl-shift-z", Redo, Some(FocusContext::KEY_CANVAS)),
-        KeyBinding::new("ctrl-y", Redo, Some(FocusContext::KEY_CANVAS)), // Windows-style redo
-    ]);
-
-    // Save shortcuts for different contexts
-    cx.bind_keys([
-        KeyBinding::new("cmd-s", SaveCode, Some(FocusContext::KEY_CODE_EDITOR)),
-        KeyBinding::new("ctrl-s", SaveCode, Some(FocusContext::KEY_CODE_EDITOR)),
-        KeyBinding::new("cmd-s", SaveCode, Some(FocusContext::KEY_CANVAS)),
-        KeyBinding::new("ctrl-s", SaveCode, Some(FocusContext::KEY_CANVAS)),
-        KeyBinding::new("cmd-s", SaveCode, Some(FocusContext::KEY_CANVAS_INPUT_ACTIVE)),
-        KeyBinding::new("ctrl-s", SaveCode, Some(FocusContext::KEY_CANVAS_INPUT_ACTIVE)),
-        KeyBinding::new("cmd-s", SaveCode, Some(FocusContext::KEY_PREVIEW)),
-        KeyBinding::new("ctrl-s", SaveCode, Some(FocusContext::KEY_PREVIEW)),
-    ]);
-
-    // Canvas-only shortcuts (not active when text input is focused)
-    cx.bind_keys([
-        // Selection actions
-        KeyBinding::new("backspace", DeleteSelected, Some(FocusContext::KEY_CANVAS)),
-        KeyBinding::new("delete", DeleteSelected, Some(FocusContext::KEY_CANVAS)),
-        KeyBinding::new("cmd-d", DuplicateSelected, Some(FocusContext::KEY_CANVAS)),
-        KeyBinding::new("ctrl-d", DuplicateSelected, Some(FocusContext::KEY_CANVAS)),
-        KeyBinding::new("cmd-a", SelectAll, Some(FocusContext::KEY_CANVAS)),
-        KeyBinding::new("ctrl-a", SelectAll, Some(FocusContext::KEY_CANVAS)),
-        KeyBinding::new("escape", DeselectAll, Some(FocusContext::KEY_CANVAS)),
-        // Paste (handles URLs including YouTube)
-        KeyBinding::new("cmd-v", Paste, Some(FocusContext::KEY_CANVAS)),
-        KeyBinding::new("ctrl-v", Paste, Some(FocusContext::KEY_CANVAS)),
-        // Command palette (cmd-k / ctrl-k toggles open/close)
-        KeyBinding::new("cmd-k", ToggleCommandPalette, Some(FocusContext::KEY_CANVAS)),
-        KeyBinding::new("ctrl-k", ToggleCommandPalette, Some(FocusContext::KEY_CANVAS)),
-        // Arrow keys to nudge selected items
-        KeyBinding::new("up", NudgeUp, Some(FocusContext::KEY_CANVAS)),
-        KeyBinding::new("down", NudgeDown, Some(FocusContext::KEY_CANVAS)),
-        KeyBinding::new("left", NudgeLeft, Some(FocusContext::KEY_CANVAS)),
-        KeyBinding::new("right", NudgeRight, Some(FocusContext::KEY_CANVAS)),
-    ]);
-
-    // Shortcuts that work even when input is active
-    cx.bind_keys([
-        KeyBinding::new("cmd-k", ToggleCommandPalette, Some(FocusContext::KEY_CANVAS_INPUT_ACTIVE)),
-        KeyBinding::new("ctrl-k", ToggleCommandPalette, Some(FocusContext::KEY_CANVAS_INPUT_ACTIVE)),
-        KeyBinding::new("escape", CancelTextboxEdit, Some(FocusContext::KEY_CANVAS_INPUT_ACTIVE)),
-    ]);
-
-    // Landing page shortcuts
-    cx.bind_keys([
-        KeyBinding::new("cmd-k", ToggleCommandPalette, Some(FocusContext::KEY_LANDING)),
-        KeyBinding::new("ctrl-k", ToggleCommandPalette, Some(FocusContext::KEY_LANDING)),
-    ]);
-
-    // Command palette navigation - use global bindings because when Input has focus,
-    // the CommandPalette key context is NOT in the focus path (Input's own focus takes over)
-    // The action handlers check if the palette is open before acting
-    cx.bind_keys([
-        KeyBinding::new("up", CmdPaletteUp, None),      // Global - handler checks if palette open
-        KeyBinding::new("down", CmdPaletteDown, None),  // Global - handler checks if palette open
-        KeyBinding::new("escape", CloseCommandPalette, None), // Global - handler checks if palette open
-    ]);
-
-    // Modal context shortcuts (settings, dialogs)
-    // Focus trap: Tab and Shift+Tab cycle focus within modal
-    cx.bind_keys([
-        KeyBinding::new("escape", OpenSettings, Some(FocusContext::KEY_MODAL)),
-        KeyBinding::new("tab", ModalFocusNext, Some(FocusContext::KEY_MODAL)),
-        KeyBinding::new("shift-tab", ModalFocusPrev, Some(FocusContext::KEY_MODAL)),
-    ]);
-
-    debug!("Keybindings registered");
+    // New windows can be opened even when no window is focused
+    cx.on_action(|_: &NewWindow, cx| {
+        if let Err(e) = humanboard::app::open_window(cx) {
+            error!("Failed to open new window: {}", e);
+        }
+    });
+
+    // The keymap: fixed keybindings, then the commands' own as remapped in
+    // keymap.json, see keymap.rs
+    if let Err(e) = humanboard::keymap::reload_user_keymap() {
+        error!("Failed to load keymap: {}", e);
+    }
+    humanboard::keymap::bind_keymap(cx);
 }
 
 /// Initialize global services and subsystems.
@@ -344,6 +189,14 @@ fn initialize_subsystems(cx: &mut App) {
 
     // 3. Register keybindings
     register_keybindings(cx);
+
+    // 4. Application menus
+    register_menus(cx);
+}
+
+/// Register the application menu bar, built from the commands.
+fn register_menus(cx: &mut App) {
+    cx.set_menus(COMMAND_REGISTRY.read().menus());
 }
 
 fn main() {
@@ -7,7 +7,9 @@
 //! ## Features
 //!
 //! - **Collapsed Cards**: Compact file cards for canvas items (markdown, code)
-//! - **Rich Markdown**: Headings, lists, code blocks, tables, blockquotes
+//! - **Expanded Cards**: Markdown cards resized tall render their document in place,
+//!   code cards a highlighted excerpt of their file
+//! - **Rich Markdown**: Headings, lists, code blocks, tables, blockquotes, images
 //! - **Inline Styles**: Bold, italic, strikethrough, inline code
 //! - **Theme Support**: Light/dark mode aware color schemes
 
@@ -15,7 +17,15 @@ use gpui::prelude::FluentBuilder;
 use gpui::*;
 use gpui_component::{Icon, IconName};
 use pulldown_cmark::{Event, Options, Parser, Tag, TagEnd};
-use std::path::PathBuf;
+use crate::code_excerpt::CodeExcerpt;
+use crate::markdown_links::{LinkTarget, resolve_link};
+use crate::syntax_highlight::LineHighlighter;
+use crate::syntax_tokens::TokenKind;
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+
+/// Called with a link's destination when it is clicked in rendered markdown
+pub type LinkHandler = Rc<dyn Fn(&str, &mut Window, &mut App)>;
 
 /// Render a collapsed code file card (similar to markdown)
 /// Takes theme colors as parameters for proper theming support
@@ -252,6 +262,8 @@ struct TextSegment {
     italic: bool,
     code: bool,
     strikethrough: bool,
+    /// Link index (unique within the document) and destination
+    link: Option<(usize, String)>,
 }
 
 impl TextSegment {
@@ -262,11 +274,13 @@ impl TextSegment {
             italic,
             code,
             strikethrough,
+            link: None,
         }
     }
 }
 
 /// Render a paragraph with mixed inline styles
+#[allow(clippy::too_many_arguments)]
 fn render_styled_paragraph(
     segments: Vec<TextSegment>,
     zoom: f32,
@@ -276,9 +290,16 @@ fn render_styled_paragraph(
     text_muted: Hsla,
     code_bg: Hsla,
     code_text: Hsla,
+    link_color: Hsla,
+    on_link: Option<&LinkHandler>,
 ) -> Div {
     // For simple paragraphs without mixed styles, just render as text
-    if segments.len() == 1 && !segments[0].code && !segments[0].bold && !segments[0].italic {
+    if segments.len() == 1
+        && !segments[0].code
+        && !segments[0].bold
+        && !segments[0].italic
+        && segments[0].link.is_none()
+    {
         return div()
             .text_size(px(13.0 * zoom))
             .text_color(text_color)
@@ -296,7 +317,7 @@ fn render_styled_paragraph(
     // Build combined text for simple cases
     let all_plain = segments
         .iter()
-        .all(|s| !s.code && !s.bold && !s.italic && !s.strikethrough);
+        .all(|s| !s.code && !s.bold && !s.italic && !s.strikethrough && s.link.is_none());
 
     if all_plain {
         let combined: String = segments.iter().map(|s| s.text.as_str()).collect();
@@ -339,6 +360,20 @@ fn render_styled_paragraph(
             if segment.strikethrough {
                 span = span.text_color(text_muted);
             }
+            if let Some((index, dest)) = segment.link {
+                let span = span.text_color(link_color).underline().child(text);
+                let Some(on_link) = on_link.cloned() else {
+                    inline_container = inline_container.child(span);
+                    continue;
+                };
+                inline_container = inline_container.child(
+                    span.id(("md-link", index))
+                        .cursor_pointer()
+                        .hover(|s| s.opacity(0.8))
+                        .on_click(move |_, window, cx| on_link(&dest, window, cx)),
+                );
+                continue;
+            }
             span.child(text)
         };
         inline_container = inline_container.child(span);
@@ -347,8 +382,261 @@ fn render_styled_paragraph(
     container.child(inline_container)
 }
 
+/// Render an image of a markdown document, or its alt text if it can't be
+/// found
+fn render_markdown_image(
+    dest: &str,
+    alt: &str,
+    base_dir: Option<&Path>,
+    zoom: f32,
+    text_muted: Hsla,
+) -> Div {
+    let source: Option<ImageSource> = match resolve_link(dest, base_dir) {
+        LinkTarget::File(path) if path.exists() => Some(path.into()),
+        LinkTarget::External(url) if url.starts_with("http") => Some(url.into()),
+        _ => None,
+    };
+    match source {
+        Some(source) => div()
+            .w_full()
+            .child(img(source).max_w_full().object_fit(ObjectFit::Contain)),
+        None => div()
+            .text_size(px(12.0 * zoom))
+            .text_color(text_muted)
+            .italic()
+            .child(if alt.is_empty() {
+                format!("[image: {}]", dest)
+            } else {
+                format!("[image: {}]", alt)
+            }),
+    }
+}
+
+/// Render a markdown card expanded in place on the canvas: a title bar over
+/// the rendered document, clipped to the card, and the cards linking to it
+/// below
+pub fn render_expanded_markdown<V: 'static>(
+    title: &str,
+    content: &str,
+    base_dir: Option<&Path>,
+    backlinks: &[String],
+    zoom: f32,
+    cx: &mut Context<V>,
+) -> Div {
+    use gpui_component::ActiveTheme as _;
+
+    let display_title = extract_title_from_content(content, title);
+    let bg = cx.theme().background;
+    let border = cx.theme().border;
+    let title_bg = cx.theme().muted;
+    let fg = cx.theme().foreground;
+    let icon_color = cx.theme().muted_foreground;
+
+    div()
+        .size_full()
+        .flex()
+        .flex_col()
+        .bg(bg)
+        .rounded(px(6.0 * zoom))
+        .border(px(1.0 * zoom))
+        .border_color(border)
+        .overflow_hidden()
+        .child(
+            div()
+                .flex()
+                .items_center()
+                .gap(px(6.0 * zoom))
+                .px(px(10.0 * zoom))
+                .py(px(6.0 * zoom))
+                .bg(title_bg)
+                .border_b(px(1.0 * zoom))
+                .border_color(border)
+                .child(
+                    Icon::new(IconName::File)
+                        .size(px(14.0 * zoom))
+                        .text_color(icon_color),
+                )
+                .child(
+                    div()
+                        .flex_1()
+                        .text_size(px(12.0 * zoom))
+                        .font_weight(FontWeight::MEDIUM)
+                        .text_color(fg)
+                        .overflow_hidden()
+                        .whitespace_nowrap()
+                        .child(display_title),
+                ),
+        )
+        .child(
+            div()
+                .flex_1()
+                .min_h_0()
+                .overflow_hidden()
+                .child(render_markdown_with_links(content, zoom, base_dir, None, cx)),
+        )
+        .when(!backlinks.is_empty(), |d| {
+            d.child(
+                div()
+                    .flex()
+                    .items_center()
+                    .gap(px(6.0 * zoom))
+                    .px(px(10.0 * zoom))
+                    .py(px(4.0 * zoom))
+                    .bg(title_bg)
+                    .border_t(px(1.0 * zoom))
+                    .border_color(border)
+                    .text_size(px(11.0 * zoom))
+                    .text_color(icon_color)
+                    .overflow_hidden()
+                    .whitespace_nowrap()
+                    .child(
+                        Icon::new(IconName::ArrowLeft)
+                            .size(px(12.0 * zoom))
+                            .text_color(icon_color),
+                    )
+                    .child(format!("Linked from {}", backlinks.join(", "))),
+            )
+        })
+}
+
+/// Render a code card expanded in place on the canvas: a title bar naming
+/// the file and the lines shown over the highlighted excerpt, which scrolls
+/// within the card
+pub fn render_expanded_code<V: 'static>(
+    item_id: u64,
+    filename: &str,
+    language: &str,
+    excerpt: &CodeExcerpt,
+    zoom: f32,
+    cx: &mut Context<V>,
+) -> Div {
+    use gpui_component::ActiveTheme as _;
+
+    let theme = cx.theme();
+    let (bg, border, title_bg) = (theme.background, theme.border, theme.muted);
+    let (fg, muted_fg) = (theme.foreground, theme.muted_foreground);
+    let (keyword, string, number) = (theme.primary, theme.success, theme.warning);
+    let syntax_theme = theme.highlight_theme.clone();
+
+    let highlighter = LineHighlighter::new(excerpt.lines.iter().map(String::as_str), language);
+    let number_width = excerpt.last_line().to_string().len() as f32 * 7.5 + 8.0;
+    let range_label = if excerpt.lines.is_empty() {
+        "Empty".to_string()
+    } else if excerpt.is_partial() {
+        format!(
+            "Lines {}–{} of {}",
+            excerpt.first_line,
+            excerpt.last_line(),
+            excerpt.total_lines
+        )
+    } else {
+        format!("{} lines", excerpt.total_lines)
+    };
+
+    let lines = excerpt.lines.iter().enumerate().map(|(index, line)| {
+        let highlights = highlighter.line_styles(index, line, &syntax_theme, |kind| {
+            let color = match kind {
+                TokenKind::Keyword => keyword,
+                TokenKind::String => string,
+                TokenKind::Comment => muted_fg,
+                TokenKind::Number => number,
+            };
+            HighlightStyle {
+                color: Some(color),
+                ..Default::default()
+            }
+        });
+        div()
+            .flex()
+            .child(
+                div()
+                    .w(px(number_width * zoom))
+                    .flex_shrink_0()
+                    .pr(px(8.0 * zoom))
+                    .flex()
+                    .justify_end()
+                    .text_color(muted_fg)
+                    .child((excerpt.first_line + index).to_string()),
+            )
+            .child(
+                div()
+                    .whitespace_nowrap()
+                    .text_color(fg)
+                    .child(StyledText::new(line.clone()).with_highlights(highlights)),
+            )
+    });
+
+    div()
+        .size_full()
+        .flex()
+        .flex_col()
+        .bg(bg)
+        .rounded(px(6.0 * zoom))
+        .border(px(1.0 * zoom))
+        .border_color(border)
+        .overflow_hidden()
+        .child(
+            div()
+                .flex()
+                .items_center()
+                .gap(px(6.0 * zoom))
+                .px(px(10.0 * zoom))
+                .py(px(6.0 * zoom))
+                .bg(title_bg)
+                .border_b(px(1.0 * zoom))
+                .border_color(border)
+                .child(
+                    Icon::new(IconName::SquareTerminal)
+                        .size(px(14.0 * zoom))
+                        .text_color(muted_fg),
+                )
+                .child(
+                    div()
+                        .flex_1()
+                        .text_size(px(12.0 * zoom))
+                        .font_weight(FontWeight::MEDIUM)
+                        .text_color(fg)
+                        .overflow_hidden()
+                        .whitespace_nowrap()
+                        .child(filename.to_string()),
+                )
+                .child(
+                    div()
+                        .text_size(px(10.0 * zoom))
+                        .text_color(muted_fg)
+                        .whitespace_nowrap()
+                        .child(range_label),
+                ),
+        )
+        .child(
+            div()
+                .id(("code-excerpt", item_id as usize))
+                .flex_1()
+                .min_h_0()
+                .overflow_scroll()
+                .py(px(6.0 * zoom))
+                .font_family(crate::constants::DEFAULT_CODE_FONT)
+                .text_size(px(11.0 * zoom))
+                .line_height(px(16.0 * zoom))
+                .children(lines),
+        )
+}
+
 /// Render parsed markdown with rich styling
 pub fn render_markdown_content<V: 'static>(content: &str, zoom: f32, cx: &mut Context<V>) -> Div {
+    render_markdown_with_links(content, zoom, None, None, cx)
+}
+
+/// Render parsed markdown, calling `on_link` with the destination of
+/// clicked links. Without a handler links are styled but inert. Relative
+/// image paths are resolved against `base_dir`, the markdown file's folder.
+pub fn render_markdown_with_links<V: 'static>(
+    content: &str,
+    zoom: f32,
+    base_dir: Option<&Path>,
+    on_link: Option<LinkHandler>,
+    cx: &mut Context<V>,
+) -> Div {
     use gpui_component::ActiveTheme as _;
 
     // Get theme colors
@@ -357,7 +645,7 @@ pub fn render_markdown_content<V: 'static>(content: &str, zoom: f32, cx: &mut Co
     let bg = cx.theme().background;
     let muted_bg = cx.theme().muted;
     let border = cx.theme().border;
-    let _primary = cx.theme().primary;
+    let link_color = cx.theme().link;
     let success = cx.theme().success;
     let danger = cx.theme().danger;
 
@@ -423,6 +711,13 @@ pub fn render_markdown_content<V: 'static>(content: &str, zoom: f32, cx: &mut Co
     let mut paragraph_segments: Vec<TextSegment> = Vec::new();
     let mut current_text = String::new();
 
+    // Link tracking
+    let mut current_link: Option<String> = None;
+    let mut link_count = 0;
+
+    // Image tracking: destination, with the alt text collected in current_text
+    let mut current_image: Option<String> = None;
+
     // Helper to flush current text into segments
     let flush_text = |text: &mut String,
                       segments: &mut Vec<TextSegment>,
@@ -513,6 +808,8 @@ pub fn render_markdown_content<V: 'static>(content: &str, zoom: f32, cx: &mut Co
                             text_muted,
                             code_bg,
                             code_text,
+                            link_color,
+                            on_link.as_ref(),
                         ));
                     }
                 }
@@ -652,6 +949,8 @@ pub fn render_markdown_content<V: 'static>(content: &str, zoom: f32, cx: &mut Co
                                 text_muted,
                                 code_bg,
                                 code_text,
+                                link_color,
+                                on_link.as_ref(),
                             )
                             .italic(),
                         ),
@@ -717,6 +1016,8 @@ pub fn render_markdown_content<V: 'static>(content: &str, zoom: f32, cx: &mut Co
                             text_muted,
                             code_bg,
                             code_text,
+                            link_color,
+                            on_link.as_ref(),
                         ))),
                 );
             }
@@ -812,7 +1113,7 @@ pub fn render_markdown_content<V: 'static>(content: &str, zoom: f32, cx: &mut Co
             }
 
             // Links
-            Event::Start(Tag::Link { .. }) => {
+            Event::Start(Tag::Link { dest_url, .. }) => {
                 flush_text(
                     &mut current_text,
                     &mut paragraph_segments,
@@ -820,18 +1121,57 @@ pub fn render_markdown_content<V: 'static>(content: &str, zoom: f32, cx: &mut Co
                     in_italic,
                     in_strikethrough,
                 );
+                current_link = Some(dest_url.to_string());
             }
             Event::End(TagEnd::Link) => {
                 // Link text is in current_text, style it as a link
                 let text = std::mem::take(&mut current_text);
+                let dest = current_link.take();
                 if !text.is_empty() {
                     paragraph_segments.push(TextSegment {
                         text,
-                        bold: false,
-                        italic: false,
+                        bold: in_bold,
+                        italic: in_italic,
                         code: false,
-                        strikethrough: false,
+                        strikethrough: in_strikethrough,
+                        link: dest.map(|dest| (link_count, dest)),
                     });
+                    link_count += 1;
+                }
+            }
+
+            // Images, shown as blocks between the text around them
+            Event::Start(Tag::Image { dest_url, .. }) => {
+                flush_text(
+                    &mut current_text,
+                    &mut paragraph_segments,
+                    in_bold,
+                    in_italic,
+                    in_strikethrough,
+                );
+                if !paragraph_segments.is_empty() && !in_blockquote {
+                    let segments = std::mem::take(&mut paragraph_segments);
+                    container = container.child(render_styled_paragraph(
+                        segments,
+                        zoom,
+                        text_color,
+                        text_bold,
+                        text_italic,
+                        text_muted,
+                        code_bg,
+                        code_text,
+                        link_color,
+                        on_link.as_ref(),
+                    ));
+                }
+                current_image = Some(dest_url.to_string());
+            }
+            Event::End(TagEnd::Image) => {
+                let alt = std::mem::take(&mut current_text);
+                if let Some(dest) = current_image.take() {
+                    container = container.child(render_markdown_image(
+                        &dest, &alt, base_dir, zoom, text_muted,
+                    ));
                 }
             }
 
@@ -890,6 +1230,8 @@ pub fn render_markdown_content<V: 'static>(content: &str, zoom: f32, cx: &mut Co
             text_muted,
             code_bg,
             code_text,
+            link_color,
+            on_link.as_ref(),
         ));
     }
 
@@ -0,0 +1,101 @@
+//! Link resolution for rendered markdown.
+//!
+//! Links in a markdown preview either point outside the app (web pages,
+//! mail addresses), at another file relative to the markdown file, or at a
+//! heading in the same document. Relative targets are resolved against the
+//! directory of the markdown file and normalized so they compare equal to
+//! the paths stored on canvas items.
+
+use std::path::{Component, Path, PathBuf};
+
+/// Where a markdown link points
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LinkTarget {
+    /// URL to open in the default browser
+    External(String),
+    /// Local file, resolved to an absolute path where possible
+    File(PathBuf),
+    /// Fragment within the current document, or an empty link
+    Anchor,
+}
+
+/// Classify a link destination and resolve file links against `base_dir`.
+pub fn resolve_link(dest: &str, base_dir: Option<&Path>) -> LinkTarget {
+    let dest = dest.trim();
+    if dest.is_empty() || dest.starts_with('#') {
+        return LinkTarget::Anchor;
+    }
+
+    if let Some(scheme) = url_scheme(dest) {
+        if !scheme.eq_ignore_ascii_case("file") {
+            return LinkTarget::External(dest.to_string());
+        }
+        let path = strip_fragment(&dest[scheme.len() + 1..]);
+        let path = path.strip_prefix("//").unwrap_or(path);
+        return LinkTarget::File(normalize_path(&PathBuf::from(percent_decode(path))));
+    }
+
+    let path = PathBuf::from(percent_decode(strip_fragment(dest)));
+    let path = match base_dir {
+        Some(base) if path.is_relative() => base.join(path),
+        _ => path,
+    };
+    LinkTarget::File(normalize_path(&path))
+}
+
+/// URL scheme of `dest`, if it has one.
+///
+/// Single letters are treated as Windows drive letters rather than schemes.
+fn url_scheme(dest: &str) -> Option<&str> {
+    let (scheme, _) = dest.split_once(':')?;
+    let mut chars = scheme.chars();
+    let starts_alpha = chars.next().is_some_and(|c| c.is_ascii_alphabetic());
+    let valid = chars.all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '-' | '.'));
+    (starts_alpha && valid && scheme.len() > 1).then_some(scheme)
+}
+
+/// Drop a trailing `#fragment` and `?query` from a file link
+fn strip_fragment(dest: &str) -> &str {
+    let end = dest.find(['#', '?']).unwrap_or(dest.len());
+    &dest[..end]
+}
+
+/// Decode `%XX` escapes, leaving malformed escapes untouched
+fn percent_decode(text: &str) -> String {
+    let bytes = text.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%' && i + 2 < bytes.len() {
+            let hex = std::str::from_utf8(&bytes[i + 1..i + 3]).ok();
+            if let Some(byte) = hex.and_then(|h| u8::from_str_radix(h, 16).ok()) {
+                decoded.push(byte);
+                i += 3;
+                continue;
+            }
+        }
+        decoded.push(bytes[i]);
+        i += 1;
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+/// Resolve `.` and `..` components without touching the filesystem
+pub fn normalize_path(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => match normalized.components().next_back() {
+                Some(Component::Normal(_)) => {
+                    normalized.pop();
+                }
+                // `..` at the root stays at the root
+                Some(Component::RootDir | Component::Prefix(_)) => {}
+                _ => normalized.push(".."),
+            },
+            other => normalized.push(other),
+        }
+    }
+    normalized
+}
@@ -0,0 +1,439 @@
+//! Markdown outline export - the whole board as one structured document.
+//!
+//! Frames become headings. A frame is a rectangle with other items inside
+//! it: at least two, or anything that isn't a text box (a rectangle around a
+//! single text box is a sticky note, not a frame). Frames can nest, and
+//! each item belongs to the smallest frame around it. A frame's title is a
+//! one-line text box just above it, as imported Obsidian groups and Miro
+//! frames have.
+//!
+//! Under the board's title come the items outside any frame, then each
+//! frame as a section with its own items and then its inner frames. Items
+//! are in reading order: row by row, left to right within a row.
+//!
+//! Text and markdown cards are written as they are (markdown headings
+//! moved under the section's), code is fenced, images are copied into a
+//! folder beside the document, tables become Markdown tables, and links,
+//! embeds and files become links. Shapes and arrows are left out.
+
+use crate::board::BoardState;
+use crate::document_export::{
+    SelectionDocument, assets_dir_name, code_section, image_section, link_destination,
+    write_document,
+};
+use crate::error::BoardError;
+use crate::types::{CanvasItem, DataSource, ItemContent, ShapeType};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use tracing::debug;
+
+/// How far above a frame its title may sit, in canvas units
+const TITLE_GAP: f32 = 40.0;
+
+/// Rows of a table written before the rest are counted instead
+pub const MAX_TABLE_ROWS: usize = 1000;
+
+/// Deepest Markdown heading level
+const MAX_HEADING_LEVEL: usize = 6;
+
+/// A frame and what is inside it
+#[derive(Debug, Clone, PartialEq)]
+pub struct FrameNode {
+    /// ID of the frame's rectangle
+    pub id: u64,
+    pub title: String,
+    /// Items directly inside, in reading order
+    pub items: Vec<u64>,
+    /// Frames directly inside, in reading order
+    pub frames: Vec<FrameNode>,
+}
+
+/// The board's items arranged under their frames
+#[derive(Debug, Clone, PartialEq)]
+pub struct FrameTree {
+    /// Items outside any frame, in reading order
+    pub items: Vec<u64>,
+    /// Outermost frames, in reading order
+    pub frames: Vec<FrameNode>,
+}
+
+/// Arrange `items` (the board's, back to front) under their frames
+pub fn frame_tree(items: &[CanvasItem]) -> FrameTree {
+    let frame_ids: Vec<u64> = items
+        .iter()
+        .filter(|frame| is_frame(frame, items))
+        .map(|frame| frame.id)
+        .collect();
+    let by_id: HashMap<u64, &CanvasItem> = items.iter().map(|item| (item.id, item)).collect();
+
+    // Titles are taken out of the items they would otherwise be listed with
+    let mut titles: HashMap<u64, (u64, String)> = HashMap::new();
+    for &id in &frame_ids {
+        if let Some(title) = frame_title(by_id[&id], items, &titles) {
+            titles.insert(id, title);
+        }
+    }
+
+    // Each item's parent is the smallest frame around it
+    let mut children: HashMap<Option<u64>, Vec<&CanvasItem>> = HashMap::new();
+    for item in items {
+        if titles.values().any(|(title_id, _)| *title_id == item.id) {
+            continue;
+        }
+        let parent = frame_ids
+            .iter()
+            .map(|id| by_id[id])
+            .filter(|frame| frame.id != item.id && contains(frame, item))
+            .filter(|frame| area(frame) > area(item) || !frame_ids.contains(&item.id))
+            .min_by(|a, b| area(a).total_cmp(&area(b)))
+            .map(|frame| frame.id);
+        children.entry(parent).or_default().push(item);
+    }
+
+    let mut untitled = 0;
+    let (items, frames) = arrange(None, &children, &frame_ids, &titles, &mut untitled);
+    FrameTree { items, frames }
+}
+
+/// The items and frames directly inside `parent`, in reading order
+fn arrange(
+    parent: Option<u64>,
+    children: &HashMap<Option<u64>, Vec<&CanvasItem>>,
+    frame_ids: &[u64],
+    titles: &HashMap<u64, (u64, String)>,
+    untitled: &mut usize,
+) -> (Vec<u64>, Vec<FrameNode>) {
+    let mut inside = children.get(&parent).cloned().unwrap_or_default();
+    reading_order(&mut inside);
+
+    let mut items = Vec::new();
+    let mut frames = Vec::new();
+    for item in inside {
+        if !frame_ids.contains(&item.id) {
+            items.push(item.id);
+            continue;
+        }
+        let title = match titles.get(&item.id) {
+            Some((_, title)) => title.clone(),
+            None => {
+                *untitled += 1;
+                format!("Frame {}", untitled)
+            }
+        };
+        let (items, inner) = arrange(Some(item.id), children, frame_ids, titles, untitled);
+        frames.push(FrameNode {
+            id: item.id,
+            title,
+            items,
+            frames: inner,
+        });
+    }
+    (items, frames)
+}
+
+/// Sort `items` row by row, left to right within a row. An item starts a
+/// new row when its top is below the middle of the row's first item.
+pub fn reading_order(items: &mut Vec<&CanvasItem>) {
+    items.sort_by(|a, b| a.position.1.total_cmp(&b.position.1));
+    let mut rows: Vec<Vec<&CanvasItem>> = Vec::new();
+    for item in items.drain(..) {
+        match rows.last_mut() {
+            Some(row) if item.position.1 < row[0].position.1 + row[0].size.1 / 2.0 => {
+                row.push(item)
+            }
+            _ => rows.push(vec![item]),
+        }
+    }
+    for mut row in rows {
+        row.sort_by(|a, b| a.position.0.total_cmp(&b.position.0).then(a.id.cmp(&b.id)));
+        items.extend(row);
+    }
+}
+
+/// Whether a rectangle has at least two items, or anything but a text box,
+/// inside it
+fn is_frame(frame: &CanvasItem, items: &[CanvasItem]) -> bool {
+    if !matches!(
+        frame.content,
+        ItemContent::Shape {
+            shape_type: ShapeType::Rectangle | ShapeType::RoundedRect,
+            ..
+        }
+    ) {
+        return false;
+    }
+    let inside: Vec<&CanvasItem> = items
+        .iter()
+        .filter(|item| {
+            item.id != frame.id
+                && !matches!(item.content, ItemContent::Arrow { .. })
+                && area(item) < area(frame)
+                && contains(frame, item)
+        })
+        .collect();
+    inside.len() >= 2
+        || inside
+            .iter()
+            .any(|item| !matches!(item.content, ItemContent::TextBox { .. }))
+}
+
+/// The one-line text box closest above `frame` that overlaps it
+/// horizontally and isn't another frame's title: its ID and text
+fn frame_title(
+    frame: &CanvasItem,
+    items: &[CanvasItem],
+    taken: &HashMap<u64, (u64, String)>,
+) -> Option<(u64, String)> {
+    let (x, y, w, _) = bounds(frame);
+    items
+        .iter()
+        .filter(|item| !taken.values().any(|(id, _)| *id == item.id))
+        .filter_map(|item| match &item.content {
+            ItemContent::TextBox { text, .. } if !text.trim().contains('\n') => {
+                let (tx, ty, tw, th) = bounds(item);
+                let gap = y - (ty + th);
+                let overlaps = tx < x + w && tx + tw > x;
+                (overlaps && (-1.0..=TITLE_GAP).contains(&gap) && !text.trim().is_empty())
+                    .then(|| (gap, item.id, text.trim().to_string()))
+            }
+            _ => None,
+        })
+        .min_by(|a, b| a.0.total_cmp(&b.0))
+        .map(|(_, id, text)| (id, text))
+}
+
+fn bounds(item: &CanvasItem) -> (f32, f32, f32, f32) {
+    (item.position.0, item.position.1, item.size.0, item.size.1)
+}
+
+fn area(item: &CanvasItem) -> f32 {
+    item.size.0 * item.size.1
+}
+
+/// Whether `item` lies entirely within `frame`
+fn contains(frame: &CanvasItem, item: &CanvasItem) -> bool {
+    let (fx, fy, fw, fh) = bounds(frame);
+    let (x, y, w, h) = bounds(item);
+    x >= fx && y >= fy && x + w <= fx + fw && y + h <= fy + fh
+}
+
+/// Write the board as an outline under `title`, referencing images from
+/// `assets_dir`
+pub fn compose_outline(state: &BoardState, title: &str, assets_dir: &str) -> SelectionDocument {
+    let tree = frame_tree(&state.items);
+    let by_id: HashMap<u64, &CanvasItem> = state.items.iter().map(|item| (item.id, item)).collect();
+    let mut writer = OutlineWriter {
+        by_id,
+        data_sources: &state.data_sources,
+        assets_dir,
+        sections: vec![format!("# {}", title.trim())],
+        assets: Vec::new(),
+    };
+
+    writer.items(&tree.items, 1);
+    for frame in &tree.frames {
+        writer.frame(frame, 2);
+    }
+
+    let mut markdown = writer.sections.join("\n\n");
+    markdown.push('\n');
+    SelectionDocument {
+        markdown,
+        assets: writer.assets,
+    }
+}
+
+struct OutlineWriter<'a> {
+    by_id: HashMap<u64, &'a CanvasItem>,
+    data_sources: &'a HashMap<u64, DataSource>,
+    assets_dir: &'a str,
+    sections: Vec<String>,
+    assets: Vec<(PathBuf, String)>,
+}
+
+impl OutlineWriter<'_> {
+    fn frame(&mut self, frame: &FrameNode, level: usize) {
+        self.sections.push(format!(
+            "{} {}",
+            "#".repeat(level.min(MAX_HEADING_LEVEL)),
+            frame.title
+        ));
+        self.items(&frame.items, level);
+        for inner in &frame.frames {
+            self.frame(inner, level + 1);
+        }
+    }
+
+    /// Write items under a heading of `level`
+    fn items(&mut self, ids: &[u64], level: usize) {
+        for id in ids {
+            let Some(item) = self.by_id.get(id).copied() else {
+                continue;
+            };
+            let section = self.item_section(item, level);
+            if !section.is_empty() {
+                self.sections.push(section);
+            }
+        }
+    }
+
+    fn item_section(&mut self, item: &CanvasItem, level: usize) -> String {
+        match &item.content {
+            ItemContent::TextBox { text, .. } | ItemContent::Text(text) => text.trim().to_string(),
+            ItemContent::Markdown { content, .. } => demote_headings(content.trim(), level),
+            ItemContent::Code {
+                path,
+                language,
+                lines,
+            } => code_section(path, language, *lines),
+            ItemContent::Image(path) => image_section(path, self.assets_dir, &mut self.assets),
+            ItemContent::Table { data_source_id, .. } => self
+                .data_sources
+                .get(data_source_id)
+                .map(|source| format!("**{}**\n\n{}", source.name, markdown_table(source)))
+                .unwrap_or_default(),
+            ItemContent::Chart {
+                data_source_id,
+                config,
+                ..
+            } => {
+                let name = config
+                    .title
+                    .clone()
+                    .or_else(|| {
+                        self.data_sources
+                            .get(data_source_id)
+                            .map(|source| source.name.clone())
+                    })
+                    .unwrap_or_else(|| "Chart".to_string());
+                format!("*Chart: {}*", name)
+            }
+            ItemContent::Link(url) => link(url, url),
+            ItemContent::Bookmark {
+                url,
+                title,
+                description,
+                ..
+            } => {
+                if description.trim().is_empty() {
+                    link(title, url)
+                } else {
+                    format!("{} — {}", link(title, url), description.trim())
+                }
+            }
+            ItemContent::YouTube(id) => link(
+                "YouTube video",
+                &format!("https://www.youtube.com/watch?v={}", id),
+            ),
+            ItemContent::Vimeo(id) => link("Vimeo video", &format!("https://vimeo.com/{}", id)),
+            ItemContent::SoundCloud(url) => link("SoundCloud", url),
+            ItemContent::Spotify(path) => {
+                link("Spotify", &format!("https://open.spotify.com/{}", path))
+            }
+            ItemContent::Pdf { path, .. } | ItemContent::Video(path) | ItemContent::Audio(path) => {
+                file_link(path)
+            }
+            ItemContent::Shape { .. } | ItemContent::Arrow { .. } => String::new(),
+        }
+    }
+}
+
+/// `[text](url)`, with brackets in the text escaped
+fn link(text: &str, url: &str) -> String {
+    format!(
+        "[{}]({})",
+        text.replace('[', "\\[").replace(']', "\\]"),
+        link_destination(url)
+    )
+}
+
+fn file_link(path: &Path) -> String {
+    let name = path
+        .file_name()
+        .and_then(|name| name.to_str())
+        .unwrap_or("file");
+    link(name, &path.to_string_lossy())
+}
+
+/// Move the headings of a markdown card `levels` deeper, so they sit under
+/// the section the card is in. Headings inside code blocks are left alone.
+pub fn demote_headings(markdown: &str, levels: usize) -> String {
+    let mut in_code = false;
+    markdown
+        .lines()
+        .map(|line| {
+            let trimmed = line.trim_start();
+            if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+                in_code = !in_code;
+            }
+            let hashes = line.len() - line.trim_start_matches('#').len();
+            let is_heading = !in_code
+                && (1..=MAX_HEADING_LEVEL).contains(&hashes)
+                && line[hashes..].starts_with(' ');
+            if is_heading {
+                let level = (hashes + levels).min(MAX_HEADING_LEVEL);
+                format!("{}{}", "#".repeat(level), &line[hashes..])
+            } else {
+                line.to_string()
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// A data source as a Markdown table with its columns as the header
+pub fn markdown_table(source: &DataSource) -> String {
+    let cell = |text: &str| {
+        text.replace('|', "\\|")
+            .replace("\r\n", "<br>")
+            .replace('\n', "<br>")
+    };
+    let row = |cells: Vec<String>| format!("| {} |", cells.join(" | "));
+
+    let columns = source.columns.len().max(1);
+    let mut lines = vec![
+        row(source
+            .columns
+            .iter()
+            .map(|column| cell(&column.name))
+            .collect()),
+        row(vec!["---".to_string(); columns]),
+    ];
+    for data_row in source.rows.iter().take(MAX_TABLE_ROWS) {
+        let mut cells: Vec<String> = data_row
+            .cells
+            .iter()
+            .take(columns)
+            .map(|value| cell(&value.to_string()))
+            .collect();
+        cells.resize(columns, String::new());
+        lines.push(row(cells));
+    }
+    if source.rows.len() > MAX_TABLE_ROWS {
+        lines.push(String::new());
+        lines.push(format!(
+            "*{} more rows*",
+            source.rows.len() - MAX_TABLE_ROWS
+        ));
+    }
+    lines.join("\n")
+}
+
+/// Write the board as a Markdown outline at `path`, copying its images into
+/// the assets folder beside it. Returns the number of items written.
+pub fn export_outline(state: &BoardState, title: &str, path: &Path) -> Result<usize, BoardError> {
+    let document = compose_outline(state, title, &assets_dir_name(path));
+    let written = state
+        .items
+        .iter()
+        .filter(|item| {
+            !matches!(
+                item.content,
+                ItemContent::Shape { .. } | ItemContent::Arrow { .. }
+            )
+        })
+        .count();
+    write_document(document, path)?;
+    debug!("Exported the board outline to {:?}", path);
+    Ok(written)
+}
@@ -0,0 +1,245 @@
+//! Memory budget for media.
+//!
+//! Boards full of photos, videos and PDFs can hold far more decoded pixels
+//! and embedded webviews than fit comfortably in memory. The
+//! [`MemoryMonitor`] keeps an estimate of what the canvas is holding on to:
+//!
+//! - decoded images, at four bytes per pixel of the file actually drawn
+//!   (usually a downscaled level from `crate::image_cache`)
+//! - webviews for YouTube, Vimeo, SoundCloud, Spotify, audio and video
+//!   items, at a fixed estimate each
+//! - rendered PDF thumbnails, like images
+//!
+//! Every frame the renderer marks what is on screen. When the total goes
+//! over the budget, resources are evicted least-recently-visible first until
+//! it fits again. Anything visible in the current frame is never evicted, so
+//! a board that doesn't fit on screen within the budget simply runs over it.
+//! Evicted resources come back the next time they scroll into view.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+/// Default media memory budget in megabytes
+pub const DEFAULT_MEDIA_BUDGET_MB: u64 = 1024;
+
+/// Rough memory held by one embedded webview (its web process and surface)
+pub const WEBVIEW_ESTIMATE_BYTES: u64 = 64 * 1024 * 1024;
+
+const BYTES_PER_MB: u64 = 1024 * 1024;
+
+/// A media resource held in memory
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum MediaResource {
+    /// A decoded image file
+    Image(PathBuf),
+    /// The webview of an item, by item ID
+    WebView(u64),
+    /// A decoded PDF thumbnail file
+    PdfThumbnail(PathBuf),
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Tracked {
+    bytes: u64,
+    last_visible: u64,
+}
+
+/// Memory held by each kind of media, for the perf HUD
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct MemoryStats {
+    pub image_count: usize,
+    pub image_bytes: u64,
+    pub webview_count: usize,
+    pub webview_bytes: u64,
+    pub pdf_count: usize,
+    pub pdf_bytes: u64,
+    pub budget_bytes: u64,
+    /// Resources evicted since the monitor was created
+    pub evictions: u64,
+}
+
+impl MemoryStats {
+    pub fn total_bytes(&self) -> u64 {
+        self.image_bytes + self.webview_bytes + self.pdf_bytes
+    }
+}
+
+/// Tracks media memory and picks what to evict when over budget
+#[derive(Debug)]
+pub struct MemoryMonitor {
+    budget_bytes: u64,
+    frame: u64,
+    resources: HashMap<MediaResource, Tracked>,
+    evictions: u64,
+}
+
+impl MemoryMonitor {
+    pub fn new(budget_bytes: u64) -> Self {
+        Self {
+            budget_bytes,
+            frame: 0,
+            resources: HashMap::new(),
+            evictions: 0,
+        }
+    }
+
+    /// Create a monitor with a budget in megabytes
+    pub fn with_budget_mb(budget_mb: u64) -> Self {
+        Self::new(budget_mb.saturating_mul(BYTES_PER_MB))
+    }
+
+    pub fn budget_bytes(&self) -> u64 {
+        self.budget_bytes
+    }
+
+    pub fn set_budget_mb(&mut self, budget_mb: u64) {
+        self.budget_bytes = budget_mb.saturating_mul(BYTES_PER_MB);
+    }
+
+    /// Start a new frame. Resources marked visible after this are safe from
+    /// eviction until the next frame.
+    pub fn begin_frame(&mut self) {
+        self.frame += 1;
+    }
+
+    /// Mark a resource as visible this frame, tracking it with `bytes` if new
+    pub fn mark_visible(&mut self, resource: MediaResource, bytes: u64) {
+        self.mark_visible_with(resource, || bytes);
+    }
+
+    /// Like [`Self::mark_visible`], but only works out the size for new resources
+    pub fn mark_visible_with(&mut self, resource: MediaResource, bytes: impl FnOnce() -> u64) {
+        let frame = self.frame;
+        self.resources
+            .entry(resource)
+            .and_modify(|tracked| tracked.last_visible = frame)
+            .or_insert_with(|| Tracked {
+                bytes: bytes(),
+                last_visible: frame,
+            });
+    }
+
+    /// Track a resource that is loaded but not on screen, such as a webview
+    /// preloaded near the viewport. Its last visible frame is left alone.
+    pub fn mark_loaded(&mut self, resource: MediaResource, bytes: u64) {
+        let frame = self.frame;
+        self.resources.entry(resource).or_insert(Tracked {
+            bytes,
+            last_visible: frame,
+        });
+    }
+
+    /// Stop tracking a resource that was freed elsewhere
+    pub fn forget(&mut self, resource: &MediaResource) {
+        self.resources.remove(resource);
+    }
+
+    /// Stop tracking every webview not in `alive`
+    pub fn retain_webviews(&mut self, alive: impl Fn(u64) -> bool) {
+        self.resources.retain(|resource, _| match resource {
+            MediaResource::WebView(id) => alive(*id),
+            _ => true,
+        });
+    }
+
+    pub fn is_tracked(&self, resource: &MediaResource) -> bool {
+        self.resources.contains_key(resource)
+    }
+
+    pub fn total_bytes(&self) -> u64 {
+        self.resources.values().map(|tracked| tracked.bytes).sum()
+    }
+
+    pub fn is_over_budget(&self) -> bool {
+        self.total_bytes() > self.budget_bytes
+    }
+
+    /// Drop resources not visible this frame, least recently visible first,
+    /// until the total fits the budget. Returns what the caller must free.
+    pub fn evict(&mut self) -> Vec<MediaResource> {
+        let mut total = self.total_bytes();
+        if total <= self.budget_bytes {
+            return Vec::new();
+        }
+
+        let mut candidates: Vec<(MediaResource, Tracked)> = self
+            .resources
+            .iter()
+            .filter(|(_, tracked)| tracked.last_visible < self.frame)
+            .map(|(resource, tracked)| (resource.clone(), *tracked))
+            .collect();
+        // Oldest first; larger resources first among equally old ones
+        candidates.sort_by(|a, b| {
+            a.1.last_visible
+                .cmp(&b.1.last_visible)
+                .then(b.1.bytes.cmp(&a.1.bytes))
+        });
+
+        let mut evicted = Vec::new();
+        for (resource, tracked) in candidates {
+            if total <= self.budget_bytes {
+                break;
+            }
+            total -= tracked.bytes;
+            self.resources.remove(&resource);
+            evicted.push(resource);
+        }
+
+        self.evictions += evicted.len() as u64;
+        evicted
+    }
+
+    pub fn stats(&self) -> MemoryStats {
+        let mut stats = MemoryStats {
+            budget_bytes: self.budget_bytes,
+            evictions: self.evictions,
+            ..Default::default()
+        };
+        for (resource, tracked) in &self.resources {
+            match resource {
+                MediaResource::Image(_) => {
+                    stats.image_count += 1;
+                    stats.image_bytes += tracked.bytes;
+                }
+                MediaResource::WebView(_) => {
+                    stats.webview_count += 1;
+                    stats.webview_bytes += tracked.bytes;
+                }
+                MediaResource::PdfThumbnail(_) => {
+                    stats.pdf_count += 1;
+                    stats.pdf_bytes += tracked.bytes;
+                }
+            }
+        }
+        stats
+    }
+}
+
+impl Default for MemoryMonitor {
+    fn default() -> Self {
+        Self::with_budget_mb(DEFAULT_MEDIA_BUDGET_MB)
+    }
+}
+
+/// Estimated size of an image once decoded (RGBA), read from its header.
+/// Unreadable files count as zero.
+pub fn decoded_image_bytes(path: &Path) -> u64 {
+    image::image_dimensions(path)
+        .map(|(width, height)| width as u64 * height as u64 * 4)
+        .unwrap_or(0)
+}
+
+/// Format a byte count for display, e.g. "12.5 MB"
+pub fn format_bytes(bytes: u64) -> String {
+    const KB: f64 = 1024.0;
+    let bytes = bytes as f64;
+    if bytes >= KB * KB * KB {
+        format!("{:.1} GB", bytes / (KB * KB * KB))
+    } else if bytes >= KB * KB {
+        format!("{:.1} MB", bytes / (KB * KB))
+    } else if bytes >= KB {
+        format!("{:.1} KB", bytes / KB)
+    } else {
+        format!("{} B", bytes)
+    }
+}
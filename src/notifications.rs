@@ -28,6 +28,10 @@ pub enum ToastActionType {
     ResetSettings,
     /// Reload webview
     ReloadWebview,
+    /// Replay the crash journal of the board with this ID
+    RecoverBoard(String),
+    /// Delete the crash journal of the board with this ID unapplied
+    DiscardRecovery(String),
     /// Dismiss the toast (no action)
     Dismiss,
 }
@@ -64,6 +68,22 @@ impl ToastAction {
             action_type: ToastActionType::ReloadWebview,
         }
     }
+
+    /// Create a Recover action for a board with unsaved journaled changes
+    pub fn recover_board(board_id: impl Into<String>) -> Self {
+        Self {
+            label: "Recover".to_string(),
+            action_type: ToastActionType::RecoverBoard(board_id.into()),
+        }
+    }
+
+    /// Create a Discard action for a board with unsaved journaled changes
+    pub fn discard_recovery(board_id: impl Into<String>) -> Self {
+        Self {
+            label: "Discard".to_string(),
+            action_type: ToastActionType::DiscardRecovery(board_id.into()),
+        }
+    }
 }
 
 /// Visual variant for toast notifications
@@ -137,6 +157,8 @@ pub struct Toast {
     pub duration: Duration,
     /// Optional action button for error recovery
     pub action: Option<ToastAction>,
+    /// Optional second button, shown after the action
+    pub secondary_action: Option<ToastAction>,
 }
 
 impl std::fmt::Debug for Toast {
@@ -147,6 +169,7 @@ impl std::fmt::Debug for Toast {
             .field("variant", &self.variant)
             .field("duration", &self.duration)
             .field("has_action", &self.action.is_some())
+            .field("has_secondary_action", &self.secondary_action.is_some())
             .finish()
     }
 }
@@ -162,6 +185,7 @@ impl Toast {
             created_at: std::time::Instant::now(),
             duration,
             action: None,
+            secondary_action: None,
         }
     }
 
@@ -201,6 +225,12 @@ impl Toast {
         self
     }
 
+    /// Attach a second action button, like a way to decline the first
+    pub fn with_secondary_action(mut self, action: ToastAction) -> Self {
+        self.secondary_action = Some(action);
+        self
+    }
+
     /// Check if this toast has expired
     pub fn is_expired(&self) -> bool {
         self.created_at.elapsed() >= self.duration
@@ -333,12 +363,19 @@ pub fn render_toast(
                 .child(toast.message.clone()),
         );
 
-    // Add action button if present
-    if let Some(ref action) = toast.action {
+    // Add action buttons if present
+    let actions = [
+        ("toast-action", &toast.action),
+        ("toast-secondary-action", &toast.secondary_action),
+    ];
+    for (id_prefix, action) in actions {
+        let Some(action) = action else {
+            continue;
+        };
         let action_type = action.action_type.clone();
         let label = action.label.clone();
         base = base.child(
-            Button::new(SharedString::from(format!("toast-action-{}", toast_id)))
+            Button::new(SharedString::from(format!("{}-{}", id_prefix, toast_id)))
                 .xsmall()
                 .ghost()
                 .label(label.clone())
@@ -0,0 +1,327 @@
+//! Obsidian Canvas import - turning a `.canvas` file into board items.
+//!
+//! A canvas is JSON with `nodes` and `edges`. Nodes become:
+//!
+//! - **text** a text box with the note's markdown
+//! - **file** the item the file would become when dropped (image, PDF,
+//!   markdown card, ...), found relative to the vault the canvas is in
+//! - **link** an embedded player for sites that have one, a bookmark
+//!   otherwise
+//! - **group** a rounded rectangle behind the other items, with its label
+//!   as a text box above it
+//!
+//! Edges become arrows between the sides of the nodes they connect, with
+//! their label as a text box at the middle. Obsidian's color presets are
+//! mapped to the colors of its default theme.
+//!
+//! Anything that can't be brought over (unknown node types, missing files,
+//! edges to nodes that don't exist) is listed in the import's `skipped`
+//! notes instead.
+
+use crate::constants::{
+    DEFAULT_ARROW_COLOR, DEFAULT_BORDER_COLOR, DEFAULT_FONT_SIZE, DEFAULT_TEXT_COLOR,
+};
+use crate::error::BoardError;
+use crate::item_clipboard::CopiedItems;
+use crate::types::{ArrowHead, CanvasItem, ItemContent, ShapeType};
+use crate::url_paste::{bare_bookmark, embed_content};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Height of the label above a group
+const GROUP_LABEL_HEIGHT: f32 = 28.0;
+
+/// Size of an edge's label
+const EDGE_LABEL_SIZE: (f32, f32) = (160.0, 28.0);
+
+/// Board ID the items of an import are "copied" from, so pasting them
+/// treats them as coming from another board
+const IMPORT_BOARD_ID: &str = "obsidian-canvas";
+
+#[derive(Deserialize)]
+struct CanvasFile {
+    #[serde(default)]
+    nodes: Vec<CanvasNode>,
+    #[serde(default)]
+    edges: Vec<CanvasEdge>,
+}
+
+#[derive(Deserialize)]
+struct CanvasNode {
+    id: String,
+    #[serde(rename = "type")]
+    kind: String,
+    x: f32,
+    y: f32,
+    width: f32,
+    height: f32,
+    color: Option<String>,
+    text: Option<String>,
+    file: Option<String>,
+    url: Option<String>,
+    label: Option<String>,
+}
+
+impl CanvasNode {
+    fn bounds(&self) -> (f32, f32, f32, f32) {
+        (self.x, self.y, self.width, self.height)
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CanvasEdge {
+    from_node: String,
+    to_node: String,
+    from_side: Option<String>,
+    to_side: Option<String>,
+    from_end: Option<String>,
+    to_end: Option<String>,
+    color: Option<String>,
+    label: Option<String>,
+}
+
+/// Items converted from a canvas, back to front
+#[derive(Debug, Default)]
+pub struct ConvertedCanvas {
+    pub items: Vec<CanvasItem>,
+    /// What couldn't be imported, one note per node or edge
+    pub skipped: Vec<String>,
+}
+
+/// Convert the JSON of a `.canvas` file. File nodes are looked up relative
+/// to `vault_dir`.
+pub fn convert_canvas(json: &str, vault_dir: &Path) -> Result<ConvertedCanvas, BoardError> {
+    let canvas: CanvasFile = serde_json::from_str(json)?;
+    let mut converted = ConvertedCanvas::default();
+    let mut next_id = 1;
+    let mut push = |items: &mut Vec<CanvasItem>, position, size, content| {
+        items.push(CanvasItem {
+            id: next_id,
+            position,
+            size,
+            content,
+            locked: false,
+            volume: None,
+        });
+        next_id += 1;
+    };
+
+    // Groups go behind everything else
+    let (groups, nodes): (Vec<_>, Vec<_>) =
+        canvas.nodes.iter().partition(|node| node.kind == "group");
+    for group in &groups {
+        let border = group
+            .color
+            .as_deref()
+            .and_then(canvas_color)
+            .unwrap_or_else(|| DEFAULT_BORDER_COLOR.to_string());
+        push(
+            &mut converted.items,
+            (group.x, group.y),
+            (group.width, group.height),
+            ItemContent::Shape {
+                shape_type: ShapeType::RoundedRect,
+                fill_color: None,
+                border_color: border.clone(),
+                border_width: 2.0,
+            },
+        );
+        if let Some(label) = group.label.as_deref().filter(|l| !l.trim().is_empty()) {
+            push(
+                &mut converted.items,
+                (group.x, group.y - GROUP_LABEL_HEIGHT),
+                (group.width, GROUP_LABEL_HEIGHT),
+                text_box(label, Some(border)),
+            );
+        }
+    }
+
+    for node in &nodes {
+        let color = node.color.as_deref().and_then(canvas_color);
+        let content = match node.kind.as_str() {
+            "text" => text_box(node.text.as_deref().unwrap_or_default(), color),
+            "file" => {
+                let Some(file) = node.file.as_deref() else {
+                    converted
+                        .skipped
+                        .push(format!("File node '{}' has no file", node.id));
+                    continue;
+                };
+                let path = vault_dir.join(file);
+                if !path.exists() {
+                    converted.skipped.push(format!("Missing file '{}'", file));
+                    continue;
+                }
+                ItemContent::from_path(&path)
+            }
+            "link" => {
+                let url = node.url.as_deref().unwrap_or_default();
+                embed_content(url).unwrap_or_else(|| bare_bookmark(url))
+            }
+            other => {
+                converted
+                    .skipped
+                    .push(format!("Node '{}' of unknown type '{}'", node.id, other));
+                continue;
+            }
+        };
+        push(
+            &mut converted.items,
+            (node.x, node.y),
+            (node.width, node.height),
+            content,
+        );
+    }
+
+    let by_id: HashMap<&str, &CanvasNode> = canvas
+        .nodes
+        .iter()
+        .map(|node| (node.id.as_str(), node))
+        .collect();
+    for edge in &canvas.edges {
+        let (Some(from), Some(to)) = (
+            by_id.get(edge.from_node.as_str()),
+            by_id.get(edge.to_node.as_str()),
+        ) else {
+            converted.skipped.push(format!(
+                "Connection from '{}' to '{}' between missing nodes",
+                edge.from_node, edge.to_node
+            ));
+            continue;
+        };
+
+        let start = side_anchor(from.bounds(), to.bounds(), edge.from_side.as_deref());
+        let end = side_anchor(to.bounds(), from.bounds(), edge.to_side.as_deref());
+        let head_at_start = edge.from_end.as_deref() == Some("arrow");
+        let head_at_end = edge.to_end.as_deref() != Some("none");
+        // An arrow has one head; point it at the end that has one
+        let (start, end) = if head_at_start && !head_at_end {
+            (end, start)
+        } else {
+            (start, end)
+        };
+        let color = edge
+            .color
+            .as_deref()
+            .and_then(canvas_color)
+            .unwrap_or_else(|| DEFAULT_ARROW_COLOR.to_string());
+        let end_offset = (end.0 - start.0, end.1 - start.1);
+
+        push(
+            &mut converted.items,
+            start,
+            (end_offset.0.abs(), end_offset.1.abs()),
+            ItemContent::Arrow {
+                end_offset,
+                color: color.clone(),
+                thickness: 2.0,
+                head_style: if head_at_start || head_at_end {
+                    ArrowHead::Arrow
+                } else {
+                    ArrowHead::None
+                },
+            },
+        );
+        if let Some(label) = edge.label.as_deref().filter(|l| !l.trim().is_empty()) {
+            let middle = ((start.0 + end.0) / 2.0, (start.1 + end.1) / 2.0);
+            push(
+                &mut converted.items,
+                (
+                    middle.0 - EDGE_LABEL_SIZE.0 / 2.0,
+                    middle.1 - EDGE_LABEL_SIZE.1 / 2.0,
+                ),
+                EDGE_LABEL_SIZE,
+                text_box(label, Some(color)),
+            );
+        }
+    }
+
+    Ok(converted)
+}
+
+/// Read a `.canvas` file and convert it into items to paste on a board,
+/// with notes on what was skipped.
+///
+/// Files the canvas shows that live in its vault are copied into the board
+/// when the items are pasted, like files from another board.
+pub fn import_canvas(path: &Path) -> Result<(CopiedItems, Vec<String>), BoardError> {
+    let json = fs::read_to_string(path).map_err(|e| BoardError::LoadFailed {
+        path: path.to_path_buf(),
+        source: e,
+    })?;
+    let vault_dir = vault_root(path);
+    let converted = convert_canvas(&json, &vault_dir)?;
+    let copied = CopiedItems {
+        board_id: IMPORT_BOARD_ID.to_string(),
+        board_dir: vault_dir,
+        items: converted.items,
+        data_sources: Vec::new(),
+    };
+    Ok((copied, converted.skipped))
+}
+
+/// The vault a canvas is in: the closest folder above it with an
+/// `.obsidian` folder, or the canvas's own folder
+pub fn vault_root(canvas_path: &Path) -> PathBuf {
+    let folder = canvas_path.parent().unwrap_or(Path::new("."));
+    folder
+        .ancestors()
+        .find(|dir| dir.join(".obsidian").is_dir())
+        .unwrap_or(folder)
+        .to_path_buf()
+}
+
+/// Hex color for an Obsidian color: a preset number "1" to "6" or a hex
+/// color
+pub fn canvas_color(color: &str) -> Option<String> {
+    let preset = match color {
+        "1" => "#fb464c",
+        "2" => "#e9973f",
+        "3" => "#e0de71",
+        "4" => "#44cf6e",
+        "5" => "#53dfdd",
+        "6" => "#a882ff",
+        hex => {
+            let digits = hex.strip_prefix('#')?;
+            return (digits.len() == 6 && digits.chars().all(|c| c.is_ascii_hexdigit()))
+                .then(|| format!("#{}", digits.to_lowercase()));
+        }
+    };
+    Some(preset.to_string())
+}
+
+fn text_box(text: &str, color: Option<String>) -> ItemContent {
+    ItemContent::TextBox {
+        text: text.to_string(),
+        font_size: DEFAULT_FONT_SIZE,
+        color: color.unwrap_or_else(|| DEFAULT_TEXT_COLOR.to_string()),
+    }
+}
+
+/// Middle of the named side of `bounds`, or of the side facing `other`
+pub(crate) fn side_anchor(
+    bounds: (f32, f32, f32, f32),
+    other: (f32, f32, f32, f32),
+    side: Option<&str>,
+) -> (f32, f32) {
+    let (x, y, w, h) = bounds;
+    let side = side.unwrap_or_else(|| {
+        let dx = (other.0 + other.2 / 2.0) - (x + w / 2.0);
+        let dy = (other.1 + other.3 / 2.0) - (y + h / 2.0);
+        match (dx.abs() > dy.abs(), dx > 0.0, dy > 0.0) {
+            (true, true, _) => "right",
+            (true, false, _) => "left",
+            (false, _, true) => "bottom",
+            (false, _, false) => "top",
+        }
+    });
+    match side {
+        "top" => (x + w / 2.0, y),
+        "bottom" => (x + w / 2.0, y + h),
+        "left" => (x, y + h / 2.0),
+        _ => (x + w, y + h / 2.0),
+    }
+}
@@ -0,0 +1,129 @@
+//! First-page thumbnails without pdfium.
+//!
+//! When the pdfium library can't be loaded, PDFs still get a thumbnail so
+//! their cards are never blank. Pages can't be rendered without it, so the
+//! PDF's bytes are scanned for its first embedded JPEG (a `DCTDecode`
+//! stream), which for scans and image-heavy documents is usually the first
+//! page. Anything else gets a plain page placeholder.
+
+use image::{DynamicImage, ImageFormat, Rgba, RgbaImage};
+use std::path::Path;
+
+/// Largest PDF read for an embedded image
+const MAX_SCAN_BYTES: u64 = 64 * 1024 * 1024;
+
+/// Smallest embedded image used as a thumbnail, skipping icons and logos
+const MIN_IMAGE_SIDE: u32 = 64;
+
+/// Size of the placeholder page, in the proportions of an A4 page
+const PLACEHOLDER_WIDTH: u32 = 400;
+const PLACEHOLDER_HEIGHT: u32 = 566;
+
+/// A first-page thumbnail of a PDF made without pdfium: its first embedded
+/// JPEG if it has a usable one, otherwise a placeholder page
+pub fn fallback_thumbnail(path: &Path) -> DynamicImage {
+    let embedded = std::fs::metadata(path)
+        .ok()
+        .filter(|meta| meta.len() <= MAX_SCAN_BYTES)
+        .and_then(|_| std::fs::read(path).ok())
+        .and_then(|bytes| first_embedded_jpeg(&bytes));
+    embedded.unwrap_or_else(|| DynamicImage::ImageRgba8(placeholder_page()))
+}
+
+/// The first JPEG stream of a PDF that decodes to an image of a useful size
+pub fn first_embedded_jpeg(bytes: &[u8]) -> Option<DynamicImage> {
+    let mut from = 0;
+    while let Some(found) = find(&bytes[from..], b"/DCTDecode") {
+        let filter_at = from + found;
+        from = filter_at + 1;
+
+        let Some(data) = stream_after(bytes, filter_at) else {
+            continue;
+        };
+        let Ok(image) = image::load_from_memory_with_format(data, ImageFormat::Jpeg) else {
+            continue;
+        };
+        if image.width() >= MIN_IMAGE_SIDE && image.height() >= MIN_IMAGE_SIDE {
+            return Some(image);
+        }
+    }
+    None
+}
+
+/// The data of the stream whose dictionary has a filter at `filter_at`.
+/// Streams that are also compressed (e.g. `[/FlateDecode /DCTDecode]`)
+/// aren't plain JPEG data and are skipped.
+fn stream_after(bytes: &[u8], filter_at: usize) -> Option<&[u8]> {
+    let keyword = filter_at + find(&bytes[filter_at..], b"stream")?;
+    let dictionary = &bytes[dictionary_start(bytes, filter_at)..keyword];
+    if find(dictionary, b"/FlateDecode").is_some() {
+        return None;
+    }
+
+    // The data starts after the end of line following the keyword
+    let mut start = keyword + b"stream".len();
+    if bytes.get(start) == Some(&b'\r') {
+        start += 1;
+    }
+    if bytes.get(start) == Some(&b'\n') {
+        start += 1;
+    }
+    let end = start + find(&bytes[start..], b"endstream")?;
+    Some(&bytes[start..end])
+}
+
+/// Start of the innermost dictionary open at `at`
+fn dictionary_start(bytes: &[u8], at: usize) -> usize {
+    bytes[..at]
+        .windows(2)
+        .rposition(|pair| pair == b"<<")
+        .unwrap_or(0)
+}
+
+fn find(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    haystack
+        .windows(needle.len())
+        .position(|window| window == needle)
+}
+
+/// A blank page with a folded corner and grey lines standing in for text
+pub fn placeholder_page() -> RgbaImage {
+    let paper = Rgba([250, 250, 248, 255]);
+    let fold = Rgba([222, 222, 218, 255]);
+    let line = Rgba([214, 214, 210, 255]);
+    let corner = 56;
+
+    let mut page = RgbaImage::from_pixel(PLACEHOLDER_WIDTH, PLACEHOLDER_HEIGHT, paper);
+    // The corner is cut off and folded down into the square below it
+    for y in 0..corner {
+        for x in PLACEHOLDER_WIDTH - corner..PLACEHOLDER_WIDTH {
+            let from_corner = (PLACEHOLDER_WIDTH - 1 - x) + y;
+            let pixel = if from_corner < corner {
+                Rgba([0, 0, 0, 0])
+            } else {
+                fold
+            };
+            page.put_pixel(x, y, pixel);
+        }
+    }
+
+    let margin = 40;
+    let mut y = 110;
+    let mut row = 0;
+    while y + 8 < PLACEHOLDER_HEIGHT - margin {
+        // Every fifth line ends short, like the end of a paragraph
+        let right = if row % 5 == 4 {
+            PLACEHOLDER_WIDTH / 2
+        } else {
+            PLACEHOLDER_WIDTH - margin
+        };
+        for line_y in y..y + 8 {
+            for x in margin..right {
+                page.put_pixel(x, line_y, line);
+            }
+        }
+        y += 22;
+        row += 1;
+    }
+    page
+}
@@ -0,0 +1,51 @@
+//! Highlights of passages in PDFs, stored with the board.
+//!
+//! The native PDF viewer doesn't expose its text selection to the app, so a
+//! passage is picked with find in file: the current match in a PDF tab can
+//! be highlighted, and its place is kept as a page and a byte offset into
+//! that page's indexed text. A highlight can have a text box on the canvas
+//! as its note, which opens the PDF at the highlighted page again.
+
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+/// A highlighted passage of a PDF
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PdfHighlight {
+    pub id: u64,
+    pub path: PathBuf,
+    /// Page of the passage, counted from 0
+    pub page: usize,
+    /// Byte offset of the passage in the page's text
+    pub offset: usize,
+    /// The highlighted text
+    pub text: String,
+    /// Text box on the canvas holding a note about the passage
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub note_item: Option<u64>,
+}
+
+impl PdfHighlight {
+    /// Where the passage is, e.g. `report.pdf, p. 3`
+    pub fn location(&self) -> String {
+        let file_name = self
+            .path
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        format!("{}, p. {}", file_name, self.page + 1)
+    }
+
+    /// Starting text of a note text box: the quoted passage and where it is
+    pub fn note_text(&self) -> String {
+        let quote = self.text.split_whitespace().collect::<Vec<_>>().join(" ");
+        format!("“{}”\n— {}", quote, self.location())
+    }
+}
+
+/// The text at byte `offset` of `page_text` that a search for `query`
+/// matched, as it is written in the PDF
+pub fn matched_text(page_text: &str, offset: usize, query: &str) -> Option<String> {
+    let len = query.to_lowercase().len();
+    page_text.get(offset..offset + len).map(str::to_string)
+}
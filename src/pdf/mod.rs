@@ -1,17 +1,46 @@
 //! PDF rendering and thumbnail generation using pdfium.
 //!
-//! This module provides PDF handling for the canvas (not the preview panel):
+//! This module provides PDF handling for the canvas and search:
 //!
 //! - `document` - PdfDocument for rendering pages on canvas items
-//! - `thumbnail` - First-page thumbnail generation for canvas cards
+//! - `fallback` - First-page thumbnails when pdfium isn't available
+//! - `highlights` - Highlighted passages stored with the board
+//! - `outline` - Bookmark trees shown beside PDF previews
+//! - `pages` - Background-rendered pages shown on PDF cards
+//! - `text` - Text extraction
+//! - `text_index` - Local index of PDF text for search
+//! - `thumbnail` - First-page thumbnails for canvas cards, kept up to date
 //! - `pdfium_loader` - Shared PDFium library loading logic
 //!
 //! For the preview panel PDF viewer, see `webviews::PdfWebView`.
 
 mod document;
+mod fallback;
+mod highlights;
+mod outline;
+mod pages;
 mod pdfium_loader;
+mod text;
+mod text_index;
 mod thumbnail;
 
 pub use document::PdfDocument;
-pub use pdfium_loader::PdfiumLoader;
-pub use thumbnail::generate_pdf_thumbnail;
+pub use fallback::{fallback_thumbnail, first_embedded_jpeg, placeholder_page};
+pub use highlights::{PdfHighlight, matched_text};
+pub use outline::{
+    OutlineRow, PDF_OUTLINES, PdfOutlineCache, PdfOutlineEntry, active_outline_row, outline_rows,
+    read_pdf_outline,
+};
+pub use pages::{
+    PdfPage, PdfPageCache, export_pdf_page, placed_page_path, render_pdf_page, turn_page,
+};
+pub use pdfium_loader::{PDFIUM_LIBRARY_ENV, PdfiumLoader};
+pub use text::extract_pdf_text;
+pub use text_index::{
+    PDF_TEXT_INDEX, PdfTextEntry, PdfTextIndex, PdfTextMatch, default_index_dir, entry_path,
+    find_in_pages, snippet,
+};
+pub use thumbnail::{
+    PdfThumbnailCache, generate_pdf_thumbnail, regenerate_pdf_thumbnail, thumbnail_dir,
+    thumbnail_path,
+};
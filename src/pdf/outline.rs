@@ -0,0 +1,219 @@
+//! PDF outlines (bookmark trees) for the preview panel.
+//!
+//! A PDF's outline is read once through pdfium on the background executor
+//! and kept in [`PDF_OUTLINES`], shared by every window. The preview panel
+//! shows it as a sidebar of rows, one per visible entry, where entries with
+//! children can be collapsed.
+
+use crate::background::BackgroundExecutor;
+use crate::pdf::pdfium_loader::PdfiumLoader;
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+use pdfium_render::prelude::*;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tracing::{info_span, warn};
+
+/// Deepest outline level read, guarding against malformed trees
+const MAX_OUTLINE_DEPTH: usize = 16;
+
+/// Most entries read from one outline, guarding against cyclic trees
+const MAX_OUTLINE_ENTRIES: usize = 5000;
+
+/// An entry of a PDF's outline
+#[derive(Debug, Clone, PartialEq)]
+pub struct PdfOutlineEntry {
+    pub title: String,
+    /// Page the entry points at, counted from 0, if it points at one
+    pub page: Option<usize>,
+    pub children: Vec<PdfOutlineEntry>,
+}
+
+/// Read the outline of a PDF. Documents without bookmarks have an empty
+/// outline.
+pub fn read_pdf_outline(path: &Path) -> Result<Vec<PdfOutlineEntry>, String> {
+    let _span = info_span!("read_pdf_outline", path = ?path).entered();
+    let pdfium = PdfiumLoader::load()?;
+    let document = pdfium
+        .load_pdf_from_file(path, None)
+        .map_err(|e| format!("Failed to load PDF: {:?}", e))?;
+
+    let mut budget = MAX_OUTLINE_ENTRIES;
+    Ok(read_siblings(document.bookmarks().root(), 0, &mut budget))
+}
+
+fn read_siblings(
+    first: Option<PdfBookmark<'_>>,
+    depth: usize,
+    budget: &mut usize,
+) -> Vec<PdfOutlineEntry> {
+    let mut entries = Vec::new();
+    let mut next = first;
+    while let Some(bookmark) = next {
+        if *budget == 0 {
+            break;
+        }
+        *budget -= 1;
+        let children = if depth + 1 < MAX_OUTLINE_DEPTH {
+            read_siblings(bookmark.first_child(), depth + 1, budget)
+        } else {
+            Vec::new()
+        };
+        entries.push(PdfOutlineEntry {
+            title: bookmark.title().unwrap_or_default().trim().to_string(),
+            page: bookmark
+                .destination()
+                .and_then(|destination| destination.page_index().ok())
+                .map(|index| index as usize),
+            children,
+        });
+        next = bookmark.next_sibling();
+    }
+    entries
+}
+
+/// A visible row of an outline sidebar
+#[derive(Debug, Clone, PartialEq)]
+pub struct OutlineRow {
+    /// Position of the entry in the tree: its index among its siblings,
+    /// after those of its ancestors
+    pub index_path: Vec<usize>,
+    pub depth: usize,
+    pub title: String,
+    pub page: Option<usize>,
+    pub has_children: bool,
+    pub expanded: bool,
+}
+
+/// The rows of an outline with the entries in `collapsed` closed, so their
+/// children are left out
+pub fn outline_rows(
+    entries: &[PdfOutlineEntry],
+    collapsed: &HashSet<Vec<usize>>,
+) -> Vec<OutlineRow> {
+    let mut rows = Vec::new();
+    push_rows(entries, collapsed, &mut Vec::new(), &mut rows);
+    rows
+}
+
+fn push_rows(
+    entries: &[PdfOutlineEntry],
+    collapsed: &HashSet<Vec<usize>>,
+    index_path: &mut Vec<usize>,
+    rows: &mut Vec<OutlineRow>,
+) {
+    for (index, entry) in entries.iter().enumerate() {
+        index_path.push(index);
+        let has_children = !entry.children.is_empty();
+        let expanded = has_children && !collapsed.contains(index_path);
+        rows.push(OutlineRow {
+            index_path: index_path.clone(),
+            depth: index_path.len() - 1,
+            title: entry.title.clone(),
+            page: entry.page,
+            has_children,
+            expanded,
+        });
+        if expanded {
+            push_rows(&entry.children, collapsed, index_path, rows);
+        }
+        index_path.pop();
+    }
+}
+
+/// The row for the section `page` is in: the last row pointing at or
+/// before it
+pub fn active_outline_row(rows: &[OutlineRow], page: usize) -> Option<usize> {
+    rows.iter()
+        .enumerate()
+        .filter(|(_, row)| row.page.is_some_and(|p| p <= page))
+        .max_by_key(|&(index, row)| (row.page, index))
+        .map(|(index, _)| index)
+}
+
+/// Outlines shared by every window
+pub static PDF_OUTLINES: Lazy<PdfOutlineCache> = Lazy::new(PdfOutlineCache::default);
+
+#[derive(Debug, Clone)]
+enum OutlineState {
+    Pending,
+    Ready(Arc<Vec<PdfOutlineEntry>>),
+    Failed,
+}
+
+/// Outlines of PDFs, read on the background executor the first time each
+/// PDF is opened in the preview panel
+#[derive(Clone, Default)]
+pub struct PdfOutlineCache {
+    entries: Arc<Mutex<HashMap<PathBuf, OutlineState>>>,
+}
+
+impl PdfOutlineCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Read a PDF's outline in the background, unless it has been already
+    pub fn load(&self, path: &Path, background: &BackgroundExecutor) {
+        {
+            let mut entries = self.entries.lock();
+            if entries.contains_key(path) {
+                return;
+            }
+            entries.insert(path.to_path_buf(), OutlineState::Pending);
+        }
+
+        let entries = self.entries.clone();
+        let source = path.to_path_buf();
+        let path = path.to_path_buf();
+        background.spawn(
+            "pdf_outline",
+            move || read_pdf_outline(&source),
+            move |result| {
+                let state = match result {
+                    Ok(outline) => OutlineState::Ready(Arc::new(outline)),
+                    Err(e) => {
+                        warn!("Failed to read the outline of {:?}: {}", path, e);
+                        OutlineState::Failed
+                    }
+                };
+                entries.lock().insert(path, state);
+            },
+        );
+    }
+
+    /// A PDF's outline, or `None` while it is being read or if it couldn't be
+    pub fn get(&self, path: &Path) -> Option<Arc<Vec<PdfOutlineEntry>>> {
+        match self.entries.lock().get(path) {
+            Some(OutlineState::Ready(outline)) => Some(outline.clone()),
+            _ => None,
+        }
+    }
+
+    /// Whether a PDF has an outline with at least one entry
+    pub fn has_outline(&self, path: &Path) -> bool {
+        self.get(path).is_some_and(|outline| !outline.is_empty())
+    }
+
+    /// Store an outline read elsewhere
+    pub fn insert(&self, path: PathBuf, outline: Vec<PdfOutlineEntry>) {
+        self.entries
+            .lock()
+            .insert(path, OutlineState::Ready(Arc::new(outline)));
+    }
+
+    /// Forget a PDF's outline so it is read again, e.g. after the file
+    /// changed
+    pub fn forget(&self, path: &Path) {
+        self.entries.lock().remove(path);
+    }
+
+    /// Whether any outline is still being read
+    pub fn has_pending(&self) -> bool {
+        self.entries
+            .lock()
+            .values()
+            .any(|state| matches!(state, OutlineState::Pending))
+    }
+}
@@ -0,0 +1,205 @@
+//! Pages of PDF canvas items.
+//!
+//! A PDF card shows the page stored in its item, starting at the first. Each
+//! page is rendered once through [`PdfDocument`] on the background executor
+//! and cached as a PNG in the temp directory, so the card can draw it as a
+//! plain image. Until a page is ready, a card on the first page shows its
+//! thumbnail instead.
+//!
+//! A page can also be rendered at full quality and saved with the board, to
+//! place it on the canvas as an image of its own.
+
+use crate::background::BackgroundExecutor;
+use crate::pdf::PdfDocument;
+use parking_lot::Mutex;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tracing::warn;
+
+/// Document zoom pages are rendered at, giving images 1200px wide
+const PAGE_RENDER_ZOOM: f32 = 0.5;
+
+/// Document zoom pages placed on the canvas are rendered at, giving images
+/// 2400px wide
+const PLACED_PAGE_ZOOM: f32 = 1.0;
+
+/// A rendered page of a PDF
+#[derive(Debug, Clone, PartialEq)]
+pub struct PdfPage {
+    /// Rendered page image
+    pub image: PathBuf,
+    /// Number of pages in the document
+    pub page_count: usize,
+}
+
+/// Render one page of a PDF
+pub fn render_pdf_page(path: &Path, page: usize) -> Result<PdfPage, String> {
+    let (image, page_count) = render_page_at(path, page, PAGE_RENDER_ZOOM)?;
+    Ok(PdfPage { image, page_count })
+}
+
+/// Render a page at a document zoom, returning the image and the number of
+/// pages in the document
+fn render_page_at(path: &Path, page: usize, zoom: f32) -> Result<(PathBuf, usize), String> {
+    let mut document = PdfDocument::open(path)?;
+    if !document.go_to_page(page) {
+        return Err(format!(
+            "Page {} is out of range, the document has {} pages",
+            page + 1,
+            document.page_count
+        ));
+    }
+    document.set_zoom(zoom);
+    let image = document
+        .get_current_page_image()
+        .ok_or_else(|| format!("Failed to render page {}", page + 1))?;
+    Ok((image, document.page_count))
+}
+
+/// Render one page of a PDF at full quality and save it as a PNG at `dest`
+pub fn export_pdf_page(path: &Path, page: usize, dest: &Path) -> Result<(), String> {
+    let (image, _) = render_page_at(path, page, PLACED_PAGE_ZOOM)?;
+    if let Some(dir) = dest.parent() {
+        fs::create_dir_all(dir).map_err(|e| format!("Failed to create {:?}: {}", dir, e))?;
+    }
+    fs::copy(&image, dest).map_err(|e| format!("Failed to save {:?}: {}", dest, e))?;
+    Ok(())
+}
+
+/// Where to save a page placed on the canvas: `report-p3.png` in `dir`, or
+/// `report-p3-2.png` and so on if that is taken
+pub fn placed_page_path(dir: &Path, pdf_path: &Path, page: usize) -> PathBuf {
+    let stem = pdf_path
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().into_owned())
+        .unwrap_or_else(|| "page".to_string());
+    let base = format!("{}-p{}", stem, page + 1);
+    let mut path = dir.join(format!("{}.png", base));
+    let mut copy = 2;
+    while path.exists() {
+        path = dir.join(format!("{}-{}.png", base, copy));
+        copy += 1;
+    }
+    path
+}
+
+/// The page `delta` pages away from `page`, kept within the document
+pub fn turn_page(page: usize, delta: isize, page_count: usize) -> usize {
+    page.saturating_add_signed(delta)
+        .min(page_count.saturating_sub(1))
+}
+
+#[derive(Debug, Clone)]
+enum PageState {
+    Pending,
+    Ready(Arc<PdfPage>),
+    Failed,
+}
+
+/// Rendered pages of PDF items, rendered on the background executor the
+/// first time each page is asked for
+#[derive(Clone, Default)]
+pub struct PdfPageCache {
+    entries: Arc<Mutex<HashMap<(PathBuf, usize), PageState>>>,
+}
+
+impl PdfPageCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// A page of a PDF, or `None` while it is being rendered or if it
+    /// couldn't be
+    pub fn get(
+        &self,
+        path: &Path,
+        page: usize,
+        background: &BackgroundExecutor,
+    ) -> Option<Arc<PdfPage>> {
+        let key = (path.to_path_buf(), page);
+        let mut entries = self.entries.lock();
+        match entries.get(&key) {
+            Some(PageState::Ready(rendered)) => Some(rendered.clone()),
+            Some(PageState::Pending | PageState::Failed) => None,
+            None => {
+                entries.insert(key, PageState::Pending);
+                drop(entries);
+                self.render(path.to_path_buf(), page, background);
+                None
+            }
+        }
+    }
+
+    /// Number of pages in a PDF, known once any of its pages has rendered
+    pub fn page_count(&self, path: &Path) -> Option<usize> {
+        self.entries
+            .lock()
+            .iter()
+            .find_map(|((entry_path, _), state)| match state {
+                PageState::Ready(rendered) if entry_path == path => Some(rendered.page_count),
+                _ => None,
+            })
+    }
+
+    /// Store a page rendered elsewhere
+    pub fn insert(&self, path: PathBuf, page: usize, rendered: PdfPage) {
+        self.entries
+            .lock()
+            .insert((path, page), PageState::Ready(Arc::new(rendered)));
+    }
+
+    /// Drop the rendered pages of a PDF so they are rendered again, e.g.
+    /// after the file changed. Returns the images of the dropped pages.
+    pub fn forget(&self, path: &Path) -> Vec<PathBuf> {
+        let mut images = Vec::new();
+        self.entries
+            .lock()
+            .retain(|(entry_path, _), state| match state {
+                PageState::Pending => true,
+                _ if entry_path != path => true,
+                PageState::Ready(rendered) => {
+                    images.push(rendered.image.clone());
+                    false
+                }
+                PageState::Failed => false,
+            });
+        images
+    }
+
+    /// Whether any page is still being rendered
+    pub fn has_pending(&self) -> bool {
+        self.entries
+            .lock()
+            .values()
+            .any(|state| matches!(state, PageState::Pending))
+    }
+
+    /// Drop every rendered page, e.g. when switching boards
+    pub fn clear(&self) {
+        self.entries
+            .lock()
+            .retain(|_, state| matches!(state, PageState::Pending));
+    }
+
+    fn render(&self, path: PathBuf, page: usize, background: &BackgroundExecutor) {
+        let entries = self.entries.clone();
+        let source = path.clone();
+
+        background.spawn(
+            "pdf_page",
+            move || render_pdf_page(&source, page),
+            move |result| {
+                let state = match result {
+                    Ok(rendered) => PageState::Ready(Arc::new(rendered)),
+                    Err(e) => {
+                        warn!("Failed to render page {} of {:?}: {}", page + 1, path, e);
+                        PageState::Failed
+                    }
+                };
+                entries.lock().insert((path, page), state);
+            },
+        );
+    }
+}
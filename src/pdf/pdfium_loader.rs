@@ -1,21 +1,35 @@
 //! PDFium library loader with platform-specific search paths.
 //!
 //! This module centralizes the logic for locating and loading the PDFium
-//! dynamic library across different deployment scenarios.
+//! dynamic library across different deployment scenarios. Whether the
+//! library can be loaded at all is checked once at startup, see
+//! [`PdfiumLoader::availability`], so the app can tell the user how to fix
+//! it instead of PDFs silently losing their thumbnails.
 
+use once_cell::sync::Lazy;
 use pdfium_render::prelude::*;
 use std::path::PathBuf;
 
+/// Environment variable naming the PDFium library, or a directory holding it
+pub const PDFIUM_LIBRARY_ENV: &str = "PDFIUM_DYNAMIC_LIB_PATH";
+
+/// Whether PDFium could be loaded, checked the first time it is asked for
+static AVAILABILITY: Lazy<Result<(), String>> = Lazy::new(|| PdfiumLoader::load().map(|_| ()));
+
 pub struct PdfiumLoader;
 
 impl PdfiumLoader {
     /// Load the PDFium library from known search paths or system library.
     ///
     /// Search order:
-    /// 1. `lib/libpdfium.dylib` in current working directory (development)
-    /// 2. `lib/libpdfium.dylib` relative to executable
-    /// 3. `Resources/lib/libpdfium.dylib` in macOS bundle
-    /// 4. System library fallback
+    /// 1. The file or directory named by `PDFIUM_DYNAMIC_LIB_PATH`
+    /// 2. `lib/` in current working directory (development)
+    /// 3. `lib/` relative to executable
+    /// 4. `Resources/lib/` in macOS bundle
+    /// 5. System library fallback
+    ///
+    /// Each directory is searched for the platform's library name
+    /// (`libpdfium.dylib`, `libpdfium.so` or `pdfium.dll`).
     pub fn load() -> Result<Pdfium, String> {
         let paths = Self::search_paths();
         for path in paths {
@@ -30,26 +44,69 @@ impl PdfiumLoader {
             .map_err(|e| format!("Failed to load pdfium: {:?}", e))
     }
 
+    /// Whether PDFium can be loaded, with the error if not. The library is
+    /// looked for once; later calls return the same answer.
+    pub fn availability() -> &'static Result<(), String> {
+        &AVAILABILITY
+    }
+
+    /// Whether PDFium can be loaded
+    pub fn is_available() -> bool {
+        Self::availability().is_ok()
+    }
+
+    /// What the user can do when PDFium can't be loaded
+    pub fn remediation() -> String {
+        format!(
+            "PDF previews are limited: {} wasn't found. Put it in a lib folder next to the app, \
+             install it system-wide, or set {} to its path.",
+            Self::library_name(),
+            PDFIUM_LIBRARY_ENV
+        )
+    }
+
+    /// File name of the PDFium library on this platform
+    pub fn library_name() -> String {
+        Pdfium::pdfium_platform_library_name()
+            .to_string_lossy()
+            .into_owned()
+    }
+
     fn search_paths() -> Vec<PathBuf> {
         let mut paths = Vec::new();
-        
+
+        // Explicit override, either the library itself or its directory
+        if let Some(path) = std::env::var_os(PDFIUM_LIBRARY_ENV).map(PathBuf::from) {
+            if path.is_dir() {
+                paths.push(Pdfium::pdfium_platform_library_name_at_path(&path));
+            } else {
+                paths.push(path);
+            }
+        }
+
         // Current working directory (development)
         if let Ok(cwd) = std::env::current_dir() {
-            paths.push(cwd.join("lib/libpdfium.dylib"));
+            paths.push(Pdfium::pdfium_platform_library_name_at_path(
+                &cwd.join("lib"),
+            ));
         }
-        
+
         // Executable-relative path
         if let Ok(exe) = std::env::current_exe() {
             if let Some(parent) = exe.parent() {
-                paths.push(parent.join("lib/libpdfium.dylib"));
-                
+                paths.push(Pdfium::pdfium_platform_library_name_at_path(
+                    &parent.join("lib"),
+                ));
+
                 // macOS bundle path
                 if let Some(grandparent) = parent.parent() {
-                    paths.push(grandparent.join("Resources/lib/libpdfium.dylib"));
+                    paths.push(Pdfium::pdfium_platform_library_name_at_path(
+                        &grandparent.join("Resources/lib"),
+                    ));
                 }
             }
         }
-        
+
         paths
     }
 }
@@ -0,0 +1,21 @@
+//! Text extraction from PDF documents using pdfium.
+
+use crate::pdf::pdfium_loader::PdfiumLoader;
+use std::path::Path;
+use tracing::info_span;
+
+/// Extract the text of every page of a PDF, one string per page. Scanned
+/// pages without a text layer come back empty.
+pub fn extract_pdf_text(path: &Path) -> Result<Vec<String>, String> {
+    let _span = info_span!("extract_pdf_text", path = ?path).entered();
+    let pdfium = PdfiumLoader::load()?;
+    let document = pdfium
+        .load_pdf_from_file(path, None)
+        .map_err(|e| format!("Failed to load PDF: {:?}", e))?;
+
+    Ok(document
+        .pages()
+        .iter()
+        .map(|page| page.text().map(|text| text.all()).unwrap_or_default())
+        .collect())
+}
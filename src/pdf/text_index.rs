@@ -0,0 +1,346 @@
+//! Local index of the text inside PDFs.
+//!
+//! Each PDF on a board has its text extracted once on the background
+//! executor and written to a cache directory as JSON, one file per PDF named
+//! after a hash of its path. The index remembers which boards each PDF is on,
+//! so the command palette can find items on the open board and other boards
+//! by what their PDFs say, and the preview panel can find in a PDF's pages.
+//! One index is shared by every window through [`PDF_TEXT_INDEX`].
+//!
+//! Entries carry the file's cache key from [`cache_key`], so an edited PDF
+//! is extracted again the next time its board is indexed. PDFs on encrypted
+//! boards are never indexed, as their text would be written out in the clear.
+
+use crate::background::BackgroundExecutor;
+use crate::image_cache::{FileStamp, cache_key};
+use crate::pdf::extract_pdf_text;
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use tracing::{debug, warn};
+
+/// Characters of context kept on each side of a match in a snippet
+const SNIPPET_CONTEXT: usize = 30;
+
+/// Extracted text of one PDF
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PdfTextEntry {
+    pub path: PathBuf,
+    /// Cache key of the file the text was extracted from
+    pub key: String,
+    /// Text of each page
+    pub pages: Vec<String>,
+    /// IDs of the boards the PDF is on
+    #[serde(default)]
+    pub boards: BTreeSet<String>,
+}
+
+/// A PDF whose text matches a search
+#[derive(Debug, Clone, PartialEq)]
+pub struct PdfTextMatch {
+    pub path: PathBuf,
+    /// Page of the first match, counted from 0
+    pub page: usize,
+    /// The first match with some text around it
+    pub snippet: String,
+    pub boards: BTreeSet<String>,
+}
+
+/// Every case-insensitive match of `query` in `pages`, as (page, byte
+/// offset) pairs in reading order
+pub fn find_in_pages(pages: &[String], query: &str) -> Vec<(usize, usize)> {
+    let query = query.to_lowercase();
+    if query.is_empty() {
+        return Vec::new();
+    }
+    let mut matches = Vec::new();
+    for (page_idx, page) in pages.iter().enumerate() {
+        let page = page.to_lowercase();
+        let mut start = 0;
+        while let Some(offset) = page[start..].find(&query) {
+            matches.push((page_idx, start + offset));
+            start += offset + query.len();
+        }
+    }
+    matches
+}
+
+/// The text around a match at byte `offset` in `text`, on one line, with
+/// ellipses where it was cut
+pub fn snippet(text: &str, offset: usize, match_chars: usize) -> String {
+    let offset = floor_char_boundary(text, offset);
+    let mut before: Vec<char> = text[..offset].chars().rev().take(SNIPPET_CONTEXT).collect();
+    before.reverse();
+    let before: String = before.into_iter().collect();
+    let after: String = text[offset..]
+        .chars()
+        .take(match_chars + SNIPPET_CONTEXT)
+        .collect();
+
+    let mut snippet = format!("{}{}", before, after)
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ");
+    if before.len() < offset {
+        snippet.insert(0, '…');
+    }
+    if offset + after.len() < text.len() {
+        snippet.push('…');
+    }
+    snippet
+}
+
+fn floor_char_boundary(text: &str, index: usize) -> usize {
+    let mut index = index.min(text.len());
+    while !text.is_char_boundary(index) {
+        index -= 1;
+    }
+    index
+}
+
+/// Default directory for the index
+pub fn default_index_dir() -> PathBuf {
+    dirs::cache_dir()
+        .unwrap_or_else(std::env::temp_dir)
+        .join("humanboard")
+        .join("pdf_text")
+}
+
+/// Index file of a PDF, named after a hash of its path
+pub fn entry_path(dir: &Path, pdf_path: &Path) -> PathBuf {
+    let mut hasher = Sha256::new();
+    hasher.update(pdf_path.to_string_lossy().as_bytes());
+    let hash = format!("{:x}", hasher.finalize());
+    dir.join(format!("{}.json", &hash[..16]))
+}
+
+fn write_entry(dir: &Path, entry: &PdfTextEntry) {
+    let path = entry_path(dir, &entry.path);
+    let result = fs::create_dir_all(dir)
+        .and_then(|_| serde_json::to_vec(entry).map_err(std::io::Error::other))
+        .and_then(|json| {
+            // Write under a temporary name so a half-written entry is never read
+            let partial = path.with_extension("partial");
+            fs::write(&partial, json)?;
+            fs::rename(&partial, &path)
+        });
+    if let Err(e) = result {
+        warn!("Failed to save PDF text index entry {:?}: {}", path, e);
+    }
+}
+
+fn read_entries(dir: &Path) -> Vec<PdfTextEntry> {
+    let Ok(files) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    files
+        .filter_map(Result::ok)
+        .map(|file| file.path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+        .filter_map(|path| {
+            let entry = fs::read(&path)
+                .ok()
+                .and_then(|json| serde_json::from_slice(&json).ok());
+            if entry.is_none() {
+                debug!("Skipping unreadable PDF text index entry {:?}", path);
+            }
+            entry
+        })
+        .collect()
+}
+
+/// The index shared by every window
+pub static PDF_TEXT_INDEX: Lazy<PdfTextIndex> = Lazy::new(PdfTextIndex::default);
+
+/// Text of every indexed PDF, kept in memory and mirrored to disk
+#[derive(Clone)]
+pub struct PdfTextIndex {
+    dir: PathBuf,
+    entries: Arc<Mutex<HashMap<PathBuf, PdfTextEntry>>>,
+    pending: Arc<Mutex<HashSet<PathBuf>>>,
+    loaded: Arc<AtomicBool>,
+}
+
+impl PdfTextIndex {
+    pub fn new(dir: PathBuf) -> Self {
+        Self {
+            dir,
+            entries: Arc::new(Mutex::new(HashMap::new())),
+            pending: Arc::new(Mutex::new(HashSet::new())),
+            loaded: Arc::new(AtomicBool::new(false)),
+        }
+    }
+
+    /// Read the index saved by earlier runs in the background, once.
+    /// Entries indexed in the meantime are kept.
+    pub fn load(&self, background: &BackgroundExecutor) {
+        if self.loaded.swap(true, Ordering::SeqCst) {
+            return;
+        }
+        let dir = self.dir.clone();
+        let entries = self.entries.clone();
+        background.spawn(
+            "pdf_text_index_load",
+            move || Ok(read_entries(&dir)),
+            move |result| {
+                let Ok(loaded) = result else {
+                    return;
+                };
+                let mut entries = entries.lock();
+                for entry in loaded {
+                    entries.entry(entry.path.clone()).or_insert(entry);
+                }
+            },
+        );
+    }
+
+    /// Add an entry, e.g. one extracted elsewhere, saving it to disk
+    pub fn insert(&self, entry: PdfTextEntry) {
+        write_entry(&self.dir, &entry);
+        self.entries.lock().insert(entry.path.clone(), entry);
+    }
+
+    /// Index the PDFs of a board, extracting any that are new or changed,
+    /// and forget the board on PDFs that are no longer on it. PDFs left on
+    /// no board at all are dropped from the index.
+    pub fn index_board(&self, board_id: &str, pdfs: &[PathBuf], background: &BackgroundExecutor) {
+        self.retain_board(board_id, pdfs);
+        for pdf in pdfs {
+            self.index_pdf(pdf, Some(board_id), background);
+        }
+    }
+
+    /// Drop a board from every entry, e.g. once it is encrypted
+    pub fn forget_board(&self, board_id: &str) {
+        self.retain_board(board_id, &[]);
+    }
+
+    fn retain_board(&self, board_id: &str, pdfs: &[PathBuf]) {
+        self.entries.lock().retain(|path, entry| {
+            if pdfs.contains(path) || !entry.boards.remove(board_id) {
+                return true;
+            }
+            if entry.boards.is_empty() {
+                let _ = fs::remove_file(entry_path(&self.dir, path));
+                return false;
+            }
+            write_entry(&self.dir, entry);
+            true
+        });
+    }
+
+    /// Index one PDF, on the given board if any. Up-to-date entries only
+    /// have the board added; others are extracted in the background.
+    pub fn index_pdf(&self, path: &Path, board_id: Option<&str>, background: &BackgroundExecutor) {
+        let Some(stamp) = FileStamp::of(path) else {
+            return;
+        };
+        let key = cache_key(path, &stamp);
+
+        {
+            let mut entries = self.entries.lock();
+            if let Some(entry) = entries.get_mut(path).filter(|entry| entry.key == key) {
+                if let Some(board_id) = board_id {
+                    if entry.boards.insert(board_id.to_string()) {
+                        write_entry(&self.dir, entry);
+                    }
+                }
+                return;
+            }
+        }
+        if !self.pending.lock().insert(path.to_path_buf()) {
+            return;
+        }
+
+        let dir = self.dir.clone();
+        let entries = self.entries.clone();
+        let pending = self.pending.clone();
+        let source = path.to_path_buf();
+        let path = path.to_path_buf();
+        let board_id = board_id.map(str::to_string);
+
+        background.spawn(
+            "pdf_text_index",
+            move || extract_pdf_text(&source),
+            move |result| {
+                pending.lock().remove(&path);
+                let pages = match result {
+                    Ok(pages) => pages,
+                    Err(e) => {
+                        warn!("Failed to extract text from {:?}: {}", path, e);
+                        return;
+                    }
+                };
+                let mut entries = entries.lock();
+                // Keep the boards an older entry was on
+                let mut boards = entries
+                    .remove(&path)
+                    .map(|entry| entry.boards)
+                    .unwrap_or_default();
+                boards.extend(board_id);
+                let entry = PdfTextEntry {
+                    path: path.clone(),
+                    key,
+                    pages,
+                    boards,
+                };
+                write_entry(&dir, &entry);
+                entries.insert(path, entry);
+            },
+        );
+    }
+
+    /// Whether any PDF is still being extracted
+    pub fn has_pending(&self) -> bool {
+        !self.pending.lock().is_empty()
+    }
+
+    /// Matches of `query` in a PDF's pages, or `None` if it isn't indexed
+    pub fn find_in_pdf(&self, path: &Path, query: &str) -> Option<Vec<(usize, usize)>> {
+        let entries = self.entries.lock();
+        let entry = entries.get(path)?;
+        Some(find_in_pages(&entry.pages, query))
+    }
+
+    /// Number of pages of an indexed PDF
+    pub fn page_count(&self, path: &Path) -> Option<usize> {
+        Some(self.entries.lock().get(path)?.pages.len())
+    }
+
+    /// Text of one page of an indexed PDF
+    pub fn page_text(&self, path: &Path, page: usize) -> Option<String> {
+        self.entries.lock().get(path)?.pages.get(page).cloned()
+    }
+
+    /// Every indexed PDF whose text contains `query`, by path
+    pub fn search(&self, query: &str) -> Vec<PdfTextMatch> {
+        let query = query.trim();
+        let entries = self.entries.lock();
+        let mut matches: Vec<PdfTextMatch> = entries
+            .values()
+            .filter_map(|entry| {
+                let (page, offset) = *find_in_pages(&entry.pages, query).first()?;
+                Some(PdfTextMatch {
+                    path: entry.path.clone(),
+                    page,
+                    snippet: snippet(&entry.pages[page], offset, query.chars().count()),
+                    boards: entry.boards.clone(),
+                })
+            })
+            .collect();
+        matches.sort_by(|a, b| a.path.cmp(&b.path));
+        matches
+    }
+}
+
+impl Default for PdfTextIndex {
+    fn default() -> Self {
+        Self::new(default_index_dir())
+    }
+}
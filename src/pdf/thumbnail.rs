@@ -1,8 +1,11 @@
 //! PDF thumbnail generation using pdfium.
 //!
 //! This module renders the first page of a PDF document as a PNG thumbnail
-//! for display on the canvas. Thumbnails are cached in a temp directory
-//! with content-based naming to avoid regeneration.
+//! for display on the canvas. Thumbnails are cached in a temp directory,
+//! named after a hash of the PDF's path, modification time and size, so an
+//! unchanged file reuses its thumbnail and an edited one gets a new one.
+//! [`PdfThumbnailCache`] notices edits while a board is open and
+//! regenerates stale thumbnails in the background.
 //!
 //! ## Security
 //!
@@ -12,53 +15,82 @@
 //! ## Library Loading
 //!
 //! Pdfium is loaded dynamically from (in order):
-//! 1. `lib/libpdfium.dylib` in working directory
-//! 2. `lib/libpdfium.dylib` relative to executable
-//! 3. `Resources/lib/libpdfium.dylib` in macOS bundle
-//! 4. System library fallback
+//! 1. The path in `PDFIUM_DYNAMIC_LIB_PATH`
+//! 2. `lib/` in working directory
+//! 3. `lib/` relative to executable
+//! 4. `Resources/lib/` in macOS bundle
+//! 5. System library fallback
+//!
+//! If pdfium can't be loaded or fails on a document, a thumbnail is made
+//! without it, see the `fallback` module.
 
+use crate::background::BackgroundExecutor;
+use crate::image_cache::{FileStamp, cache_key};
+use crate::pdf::fallback::fallback_thumbnail;
 use crate::pdf::pdfium_loader::PdfiumLoader;
+use image::DynamicImage;
+use parking_lot::Mutex;
 use pdfium_render::prelude::*;
-use sha2::{Digest, Sha256};
+use std::collections::HashMap;
 use std::io::Write;
 use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 use tempfile::NamedTempFile;
 use tracing::{debug, info_span, warn};
 
-/// Generate a thumbnail image for a PDF's first page
+/// How often a PDF with a thumbnail is checked for changes
+const RESTAT_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Directory PDF thumbnails are written to
+pub fn thumbnail_dir() -> PathBuf {
+    std::env::temp_dir()
+        .join("humanboard")
+        .join("pdf_thumbnails")
+}
+
+/// Path of the thumbnail of a PDF in a given state, so an edited file gets
+/// a new thumbnail
+pub fn thumbnail_path(dir: &Path, pdf_path: &Path, stamp: &FileStamp) -> PathBuf {
+    dir.join(format!("{}_thumb.png", cache_key(pdf_path, stamp)))
+}
+
+/// Generate a thumbnail image for a PDF's first page. A thumbnail already
+/// made for the file as it is now is reused.
 pub fn generate_pdf_thumbnail<P: AsRef<Path>>(pdf_path: P) -> Option<PathBuf> {
     let pdf_path = pdf_path.as_ref();
     let _span = info_span!("generate_pdf_thumbnail", path = ?pdf_path).entered();
 
-    // Load pdfium
-    let pdfium = match PdfiumLoader::load() {
-        Ok(p) => p,
-        Err(e) => {
-            warn!("Failed to load pdfium library: {}", e);
-            return None;
-        }
-    };
+    let stamp = FileStamp::of(pdf_path)?;
+    let thumbnail_path = thumbnail_path(&thumbnail_dir(), pdf_path, &stamp);
+    if thumbnail_path.exists() {
+        return Some(thumbnail_path);
+    }
+    write_thumbnail(pdf_path, &thumbnail_path)
+}
 
-    // Load PDF document
-    let document = match pdfium.load_pdf_from_file(pdf_path, None) {
-        Ok(d) => d,
+/// Generate a PDF's thumbnail again even if one exists, e.g. when it
+/// looks wrong or pdfium has been installed since
+pub fn regenerate_pdf_thumbnail(pdf_path: &Path) -> Option<PathBuf> {
+    let _span = info_span!("regenerate_pdf_thumbnail", path = ?pdf_path).entered();
+
+    let stamp = FileStamp::of(pdf_path)?;
+    let thumbnail_path = thumbnail_path(&thumbnail_dir(), pdf_path, &stamp);
+    write_thumbnail(pdf_path, &thumbnail_path)
+}
+
+/// Render a PDF's first page and write it to `thumbnail_path`
+fn write_thumbnail(pdf_path: &Path, thumbnail_path: &Path) -> Option<PathBuf> {
+    // Without pdfium, fall back to a thumbnail made from the PDF's bytes so
+    // the card is never blank
+    let image = match render_first_page(pdf_path) {
+        Ok(image) => image,
         Err(e) => {
-            warn!("Failed to load PDF {:?}: {:?}", pdf_path, e);
-            return None;
+            warn!("Using fallback PDF thumbnail for {:?}: {}", pdf_path, e);
+            fallback_thumbnail(pdf_path)
         }
     };
 
-    // Get first page
-    let page = document.pages().get(0).ok()?;
-
-    // Render at a reasonable thumbnail size (400px width)
-    let render_config = PdfRenderConfig::new()
-        .set_target_width(400)
-        .set_maximum_height(600);
-
-    let bitmap = page.render_with_config(&render_config).ok()?;
-    let image = bitmap.as_image();
-
     // Save as PNG data first
     let mut png_data = Vec::new();
     if let Err(e) = image.write_to(
@@ -70,22 +102,12 @@ pub fn generate_pdf_thumbnail<P: AsRef<Path>>(pdf_path: P) -> Option<PathBuf> {
     }
 
     // Create secure temp directory with restricted permissions
-    let temp_dir = std::env::temp_dir()
-        .join("humanboard")
-        .join("pdf_thumbnails");
-    std::fs::create_dir_all(&temp_dir).ok()?;
-
-    // Generate cryptographic hash of the full canonical path for unique filename
-    let canonical_path = pdf_path.canonicalize().unwrap_or_else(|_| pdf_path.to_path_buf());
-    let mut hasher = Sha256::new();
-    hasher.update(canonical_path.to_string_lossy().as_bytes());
-    let path_hash = format!("{:x}", hasher.finalize());
-    let thumbnail_filename = format!("{}_thumb.png", &path_hash[..16]);
-    let thumbnail_path = temp_dir.join(&thumbnail_filename);
+    let temp_dir = thumbnail_path.parent()?;
+    std::fs::create_dir_all(temp_dir).ok()?;
 
     // Use atomic write: create temp file, write, then persist
     // This prevents TOCTOU race conditions and symlink attacks
-    let mut temp_file = match NamedTempFile::new_in(&temp_dir) {
+    let mut temp_file = match NamedTempFile::new_in(temp_dir) {
         Ok(f) => f,
         Err(e) => {
             warn!("Failed to create temp file for thumbnail: {:?}", e);
@@ -99,13 +121,224 @@ pub fn generate_pdf_thumbnail<P: AsRef<Path>>(pdf_path: P) -> Option<PathBuf> {
     }
 
     // Atomically persist to final location
-    if let Err(e) = temp_file.persist(&thumbnail_path) {
+    if let Err(e) = temp_file.persist(thumbnail_path) {
         warn!("Failed to persist PDF thumbnail to {:?}: {:?}", thumbnail_path, e);
         return None;
     }
 
     debug!("Generated PDF thumbnail: {:?}", thumbnail_path);
-    Some(thumbnail_path)
+    Some(thumbnail_path.to_path_buf())
+}
+
+/// Render a PDF's first page at thumbnail size with pdfium
+fn render_first_page(pdf_path: &Path) -> Result<DynamicImage, String> {
+    let pdfium = PdfiumLoader::load()?;
+    let document = pdfium
+        .load_pdf_from_file(pdf_path, None)
+        .map_err(|e| format!("Failed to load PDF: {:?}", e))?;
+    let page = document
+        .pages()
+        .get(0)
+        .map_err(|e| format!("Failed to get first page: {:?}", e))?;
+
+    // Render at a reasonable thumbnail size (400px width)
+    let render_config = PdfRenderConfig::new()
+        .set_target_width(400)
+        .set_maximum_height(600);
+
+    let bitmap = page
+        .render_with_config(&render_config)
+        .map_err(|e| format!("Failed to render first page: {:?}", e))?;
+    Ok(bitmap.as_image())
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum ThumbnailState {
+    Pending,
+    Ready(PathBuf),
+    Failed,
+}
+
+#[derive(Debug)]
+struct ThumbnailEntry {
+    stamp: FileStamp,
+    checked_at: Instant,
+    state: ThumbnailState,
 }
 
+/// Keeps the thumbnails of PDF items in step with their files. Each PDF
+/// shown on the canvas is re-checked at most once per `RESTAT_INTERVAL`,
+/// and when it has changed a new thumbnail is generated on the background
+/// executor and the old one deleted.
+#[derive(Clone)]
+pub struct PdfThumbnailCache {
+    dir: PathBuf,
+    entries: Arc<Mutex<HashMap<PathBuf, ThumbnailEntry>>>,
+    /// Thumbnails regenerated in place since last asked for, which must be
+    /// decoded again
+    refreshed: Arc<Mutex<Vec<PathBuf>>>,
+}
+
+impl PdfThumbnailCache {
+    pub fn new(dir: PathBuf) -> Self {
+        Self {
+            dir,
+            entries: Arc::new(Mutex::new(HashMap::new())),
+            refreshed: Arc::new(Mutex::new(Vec::new())),
+        }
+    }
 
+    /// The thumbnail of a PDF as it is now, or `None` while it is being
+    /// generated, if it couldn't be or if the file is missing. Starts
+    /// generating one the first time a PDF is seen or after it changes.
+    pub fn current(&self, path: &Path, background: &BackgroundExecutor) -> Option<PathBuf> {
+        let mut entries = self.entries.lock();
+        let fresh = entries
+            .get(path)
+            .is_some_and(|entry| entry.checked_at.elapsed() < RESTAT_INTERVAL);
+
+        if !fresh {
+            let Some(stamp) = FileStamp::of(path) else {
+                entries.remove(path);
+                return None;
+            };
+
+            match entries.get_mut(path) {
+                Some(entry) if entry.stamp == stamp => entry.checked_at = Instant::now(),
+                previous => {
+                    let stale = previous.and_then(|entry| match &entry.state {
+                        ThumbnailState::Ready(thumbnail) => Some(thumbnail.clone()),
+                        _ => None,
+                    });
+                    entries.insert(
+                        path.to_path_buf(),
+                        ThumbnailEntry {
+                            stamp,
+                            checked_at: Instant::now(),
+                            state: ThumbnailState::Pending,
+                        },
+                    );
+                    drop(entries);
+                    self.generate(path.to_path_buf(), stamp, stale, false, background);
+                    return None;
+                }
+            }
+        }
+
+        match entries.get(path) {
+            Some(ThumbnailEntry {
+                state: ThumbnailState::Ready(thumbnail),
+                ..
+            }) => Some(thumbnail.clone()),
+            _ => None,
+        }
+    }
+
+    /// Generate a PDF's thumbnail again in the background, replacing the
+    /// one on disk
+    pub fn regenerate(&self, path: &Path, background: &BackgroundExecutor) {
+        let Some(stamp) = FileStamp::of(path) else {
+            return;
+        };
+        self.entries.lock().insert(
+            path.to_path_buf(),
+            ThumbnailEntry {
+                stamp,
+                checked_at: Instant::now(),
+                state: ThumbnailState::Pending,
+            },
+        );
+        self.generate(path.to_path_buf(), stamp, None, true, background);
+    }
+
+    /// Store a thumbnail generated elsewhere for a PDF as it is now
+    pub fn insert(&self, path: PathBuf, thumbnail: PathBuf) {
+        let Some(stamp) = FileStamp::of(&path) else {
+            return;
+        };
+        self.entries.lock().insert(
+            path,
+            ThumbnailEntry {
+                stamp,
+                checked_at: Instant::now(),
+                state: ThumbnailState::Ready(thumbnail),
+            },
+        );
+    }
+
+    /// Thumbnails regenerated in place since the last call. Images decoded
+    /// from them before are out of date.
+    pub fn take_refreshed(&self) -> Vec<PathBuf> {
+        std::mem::take(&mut *self.refreshed.lock())
+    }
+
+    /// Whether any thumbnail is still being generated
+    pub fn has_pending(&self) -> bool {
+        self.entries
+            .lock()
+            .values()
+            .any(|entry| entry.state == ThumbnailState::Pending)
+    }
+
+    /// Forget every PDF, e.g. when switching boards
+    pub fn clear(&self) {
+        self.entries
+            .lock()
+            .retain(|_, entry| entry.state == ThumbnailState::Pending);
+    }
+
+    fn generate(
+        &self,
+        path: PathBuf,
+        stamp: FileStamp,
+        stale: Option<PathBuf>,
+        force: bool,
+        background: &BackgroundExecutor,
+    ) {
+        let entries = self.entries.clone();
+        let refreshed = self.refreshed.clone();
+        let dir = self.dir.clone();
+        let source = path.clone();
+
+        background.spawn(
+            "pdf_thumbnail",
+            move || {
+                let thumbnail = thumbnail_path(&dir, &source, &stamp);
+                if let Some(stale) = stale.filter(|stale| *stale != thumbnail) {
+                    let _ = std::fs::remove_file(stale);
+                }
+                if !force && thumbnail.exists() {
+                    return Ok(thumbnail);
+                }
+                write_thumbnail(&source, &thumbnail)
+                    .ok_or_else(|| "Failed to write thumbnail".to_string())
+            },
+            move |result| {
+                let mut entries = entries.lock();
+                // The file may have changed again while this was running
+                let Some(entry) = entries.get_mut(&path).filter(|entry| entry.stamp == stamp)
+                else {
+                    return;
+                };
+                entry.state = match result {
+                    Ok(thumbnail) => {
+                        if force {
+                            refreshed.lock().push(thumbnail.clone());
+                        }
+                        ThumbnailState::Ready(thumbnail)
+                    }
+                    Err(e) => {
+                        warn!("Failed to generate PDF thumbnail for {:?}: {}", path, e);
+                        ThumbnailState::Failed
+                    }
+                };
+            },
+        );
+    }
+}
+
+impl Default for PdfThumbnailCache {
+    fn default() -> Self {
+        Self::new(thumbnail_dir())
+    }
+}
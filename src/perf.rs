@@ -34,6 +34,9 @@
 //! ```
 
 use std::collections::{HashMap, VecDeque};
+use std::fs::File;
+use std::io::BufWriter;
+use std::path::Path;
 use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use std::time::Instant;
 use tracing::{debug, warn};
@@ -56,6 +59,9 @@ const WARN_THRESHOLD: f64 = 2.0;
 /// Number of samples to keep for operation statistics
 const STATS_SAMPLE_COUNT: usize = 100;
 
+/// Number of timings kept for trace export
+const MAX_TRACE_EVENTS: usize = 20_000;
+
 /// Global flag to enable/disable profiling at runtime
 static PROFILING_ENABLED: AtomicBool = AtomicBool::new(cfg!(feature = "profiling"));
 
@@ -163,6 +169,10 @@ pub struct PerfMonitor {
     operation_stats: HashMap<&'static str, OperationStats>,
     /// Current frame's operation timings (for hierarchical display)
     current_frame_ops: Vec<OperationTiming>,
+    /// When the monitor was created; trace timestamps count from here
+    epoch: Instant,
+    /// Recent frame and operation timings for trace export
+    trace: VecDeque<OperationTiming>,
 }
 
 /// Statistics for a specific operation type.
@@ -232,6 +242,8 @@ impl OperationStats {
 #[derive(Debug, Clone)]
 pub struct OperationTiming {
     pub name: &'static str,
+    /// Start time in milliseconds since the monitor was created
+    pub start_ms: f64,
     pub elapsed_ms: f64,
     pub depth: usize,
 }
@@ -252,6 +264,8 @@ impl PerfMonitor {
             total_frames: 0,
             operation_stats: HashMap::new(),
             current_frame_ops: Vec::new(),
+            epoch: Instant::now(),
+            trace: VecDeque::new(),
         }
     }
 
@@ -274,6 +288,15 @@ impl PerfMonitor {
         }
         self.frame_times.push_back(ms);
         self.total_frames += 1;
+        self.push_trace(OperationTiming {
+            name: "frame",
+            start_ms: self.ms_since_epoch(start),
+            elapsed_ms: ms,
+            depth: 0,
+        });
+
+        #[cfg(feature = "profiling")]
+        self.collect_scoped_timings();
 
         // Check for slow frame
         if ms > TARGET_FRAME_MS * WARN_THRESHOLD {
@@ -292,20 +315,95 @@ impl PerfMonitor {
         Some(ms)
     }
 
-    /// Record an operation timing.
+    /// Record an operation timing. The operation is taken to have just ended.
     pub fn record_operation(&mut self, name: &'static str, elapsed_ms: f64, depth: usize) {
+        let start_ms = (self.ms_since_epoch(Instant::now()) - elapsed_ms).max(0.0);
+        self.record_timing(OperationTiming {
+            name,
+            start_ms,
+            elapsed_ms,
+            depth,
+        });
+    }
+
+    fn record_timing(&mut self, timing: OperationTiming) {
         // Update per-operation statistics
         self.operation_stats
-            .entry(name)
+            .entry(timing.name)
             .or_default()
-            .record(elapsed_ms);
+            .record(timing.elapsed_ms);
 
-        // Record for current frame breakdown
-        self.current_frame_ops.push(OperationTiming {
-            name,
-            elapsed_ms,
-            depth,
+        // Record for current frame breakdown and the trace
+        self.current_frame_ops.push(timing.clone());
+        self.push_trace(timing);
+    }
+
+    fn push_trace(&mut self, timing: OperationTiming) {
+        if self.trace.len() >= MAX_TRACE_EVENTS {
+            self.trace.pop_front();
+        }
+        self.trace.push_back(timing);
+    }
+
+    fn ms_since_epoch(&self, at: Instant) -> f64 {
+        at.saturating_duration_since(self.epoch).as_secs_f64() * 1000.0
+    }
+
+    /// Record timings from `profile_scope!` timers that finished on this thread
+    #[cfg(feature = "profiling")]
+    fn collect_scoped_timings(&mut self) {
+        for (name, start, elapsed_ms, depth) in take_scoped_timings() {
+            let start_ms = self.ms_since_epoch(start);
+            self.record_timing(OperationTiming {
+                name,
+                start_ms,
+                elapsed_ms,
+                depth,
+            });
+        }
+    }
+
+    /// Recent frame and operation timings as a Chrome trace, which can be
+    /// opened in about:tracing or the Perfetto UI.
+    pub fn chrome_trace(&self) -> serde_json::Value {
+        let mut timings: Vec<&OperationTiming> = self.trace.iter().collect();
+        // Parents before children when they start together
+        timings.sort_by(|a, b| {
+            a.start_ms
+                .partial_cmp(&b.start_ms)
+                .unwrap_or(std::cmp::Ordering::Equal)
+                .then(a.depth.cmp(&b.depth))
         });
+
+        let events: Vec<serde_json::Value> = timings
+            .into_iter()
+            .map(|timing| {
+                let category = if timing.name == "frame" { "frame" } else { "operation" };
+                serde_json::json!({
+                    "name": timing.name,
+                    "cat": category,
+                    "ph": "X",
+                    "ts": timing.start_ms * 1000.0,
+                    "dur": timing.elapsed_ms * 1000.0,
+                    "pid": 1,
+                    "tid": 1,
+                    "args": { "depth": timing.depth },
+                })
+            })
+            .collect();
+
+        serde_json::json!({
+            "traceEvents": events,
+            "displayTimeUnit": "ms",
+        })
+    }
+
+    /// Write `chrome_trace` to a JSON file. Returns the number of events written.
+    pub fn export_trace(&self, path: &Path) -> std::io::Result<usize> {
+        let trace = self.chrome_trace();
+        let writer = BufWriter::new(File::create(path)?);
+        serde_json::to_writer(writer, &trace)?;
+        Ok(self.trace.len())
     }
 
     /// Log a breakdown of the current frame's operations.
@@ -411,6 +509,7 @@ impl PerfMonitor {
         self.total_frames = 0;
         self.operation_stats.clear();
         self.current_frame_ops.clear();
+        self.trace.clear();
     }
 }
 
@@ -432,10 +531,19 @@ pub struct ScopedTimer {
     depth: usize,
 }
 
-// Thread-local depth tracking for hierarchical profiling
+// Thread-local depth tracking for hierarchical profiling, and finished
+// timers waiting to be collected by the `PerfMonitor` for trace export
 #[cfg(feature = "profiling")]
 thread_local! {
     static CURRENT_DEPTH: std::cell::Cell<usize> = const { std::cell::Cell::new(0) };
+    static SCOPED_TIMINGS: std::cell::RefCell<Vec<(&'static str, Instant, f64, usize)>> =
+        const { std::cell::RefCell::new(Vec::new()) };
+}
+
+/// Take the timers that finished on this thread since the last call
+#[cfg(feature = "profiling")]
+fn take_scoped_timings() -> Vec<(&'static str, Instant, f64, usize)> {
+    SCOPED_TIMINGS.with(|timings| std::mem::take(&mut *timings.borrow_mut()))
 }
 
 impl ScopedTimer {
@@ -504,6 +612,14 @@ impl Drop for ScopedTimer {
             // Decrement depth
             CURRENT_DEPTH.with(|d| d.set(d.get().saturating_sub(1)));
 
+            // Keep for trace export (timers on threads without a monitor are capped)
+            SCOPED_TIMINGS.with(|timings| {
+                let mut timings = timings.borrow_mut();
+                if timings.len() < MAX_TRACE_EVENTS {
+                    timings.push((self.name, self.start, elapsed_ms, self.depth));
+                }
+            });
+
             // Log with hierarchy indication
             if elapsed_ms > self.threshold_ms {
                 let indent = "  ".repeat(self.depth);
@@ -712,6 +828,12 @@ impl RenderBudget {
         let _ = operation;
     }
 
+    /// Time recorded so far.
+    #[inline]
+    pub fn spent_ms(&self) -> f64 {
+        self.spent_ms
+    }
+
     /// Check if we should skip a non-critical operation to stay in budget.
     #[inline]
     #[allow(dead_code)]
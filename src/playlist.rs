@@ -0,0 +1,119 @@
+//! Playlists of audio items.
+//!
+//! Selected audio items can be played one after another, in reading order
+//! (top to bottom, then left to right) or shuffled. The playlist only keeps
+//! the order and which item is current; `Humanboard` starts each player and
+//! feeds back the positions the players report, and the canvas highlights
+//! the current item.
+
+use crate::input::guides::Bounds;
+use crate::webviews::PlaybackPosition;
+use rand::seq::SliceRandom;
+
+/// Fewest selected audio items offered as a playlist
+pub const MIN_PLAYLIST_ITEMS: usize = 2;
+
+/// Audio items played one after another
+#[derive(Debug, Clone, PartialEq)]
+pub struct Playlist {
+    order: Vec<u64>,
+    index: usize,
+    started: bool,
+    shuffled: bool,
+}
+
+impl Playlist {
+    /// Playlist of `items` in reading order, or shuffled. `None` if there
+    /// are no items.
+    pub fn new(items: &[(u64, Bounds)], shuffle: bool) -> Option<Self> {
+        let mut ordered: Vec<&(u64, Bounds)> = items.iter().collect();
+        ordered.sort_by(|(a_id, a), (b_id, b)| {
+            a.1.total_cmp(&b.1)
+                .then(a.0.total_cmp(&b.0))
+                .then(a_id.cmp(b_id))
+        });
+        let mut order: Vec<u64> = ordered.into_iter().map(|&(id, _)| id).collect();
+        if order.is_empty() {
+            return None;
+        }
+        if shuffle {
+            order.shuffle(&mut rand::thread_rng());
+        }
+        Some(Self {
+            order,
+            index: 0,
+            started: false,
+            shuffled: shuffle,
+        })
+    }
+
+    /// Items in the order they play
+    pub fn order(&self) -> &[u64] {
+        &self.order
+    }
+
+    /// Item playing now, or about to start
+    pub fn current(&self) -> u64 {
+        self.order[self.index]
+    }
+
+    /// Position of the current item, counting from 1, and the number of items
+    pub fn progress(&self) -> (usize, usize) {
+        (self.index + 1, self.order.len())
+    }
+
+    pub fn is_shuffled(&self) -> bool {
+        self.shuffled
+    }
+
+    /// Whether the current item's player has reported playing yet. Until
+    /// then it is asked to play again at every poll, since its page may
+    /// still be loading.
+    pub fn is_started(&self) -> bool {
+        self.started
+    }
+
+    /// Take a position reported by a player. Returns true once the current
+    /// item has played to the end, and the next item should start.
+    ///
+    /// Pausing the current item part way through leaves the playlist
+    /// waiting on it.
+    pub fn track_position(&mut self, item_id: u64, position: &PlaybackPosition) -> bool {
+        if item_id != self.current() {
+            return false;
+        }
+        if position.playing {
+            self.started = true;
+            return false;
+        }
+        self.started && position.resume_seconds().is_none()
+    }
+
+    /// Move to the next item. Returns it, or `None` once the playlist has
+    /// played through.
+    pub fn advance(&mut self) -> Option<u64> {
+        if self.index + 1 >= self.order.len() {
+            return None;
+        }
+        self.index += 1;
+        self.started = false;
+        Some(self.current())
+    }
+
+    /// Drop items that no longer exist, e.g. after a delete. If the current
+    /// item goes the one after it becomes current. Returns false if no items
+    /// are left.
+    pub fn retain(&mut self, keep: impl Fn(u64) -> bool) -> bool {
+        let current = self.order.get(self.index).copied();
+        let removed_before = self.order[..self.index]
+            .iter()
+            .filter(|&&id| !keep(id))
+            .count();
+        self.order.retain(|&id| keep(id));
+        self.index -= removed_before;
+        if current.is_some_and(|id| !keep(id)) {
+            self.started = false;
+        }
+        self.index < self.order.len()
+    }
+}
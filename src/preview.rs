@@ -6,14 +6,19 @@
 //!
 //! ## Features
 //!
-//! - **Tab Management**: Multiple file tabs with switching
-//! - **Split Views**: Horizontal/vertical split with canvas
+//! - **Tab Management**: Multiple file tabs with switching, and pinned tabs
+//!   that stay left-most and survive "Close Others" and "Close All"
+//! - **Split Views**: Horizontal/vertical split with canvas, and up to four
+//!   panes split side by side or stacked, that tabs can be dragged between
 //! - **File Types**: PDF, Markdown, Code files
-//! - **Editing**: Inline editing for markdown and code
+//! - **Editing**: Inline editing for markdown and code, with code files
+//!   reloaded when they change on disk and conflicts flagged when they
+//!   change under unsaved edits
 
 // Re-export types from app module
-pub use crate::app::{PreviewPanel, PreviewTab, SplitDirection};
+pub use crate::app::{PreviewPanel, PreviewTab, SplitDirection, SplitDropZone};
 
+use crate::image_cache::FileStamp;
 use std::path::PathBuf;
 use tracing::debug;
 
@@ -25,17 +30,17 @@ pub trait PreviewPanelExt {
     /// Get the currently active tab.
     fn active_tab(&self) -> Option<&PreviewTab>;
 
-    /// Find a tab by file path, returning its index.
+    /// Find a tab of the primary pane by file path, returning its index.
     fn find_tab(&self, path: &PathBuf) -> Option<usize>;
 
-    /// Switch to the next tab (wrapping around).
+    /// Switch to the next tab of the focused pane (wrapping around).
     fn next_tab(&mut self);
 
-    /// Switch to the previous tab (wrapping around).
+    /// Switch to the previous tab of the focused pane (wrapping around).
     fn prev_tab(&mut self);
 
-    /// Close a specific tab by index.
-    /// Returns true if the panel should be closed (no tabs left).
+    /// Close a specific tab of the primary pane by index.
+    /// Returns true if the pane has no tabs left.
     fn close_tab(&mut self, index: usize) -> bool;
 
     /// Calculate the canvas bounds given window size.
@@ -47,50 +52,49 @@ pub trait PreviewPanelExt {
 
 impl PreviewPanelExt for PreviewPanel {
     fn is_empty(&self) -> bool {
-        self.tabs.is_empty()
+        self.all_tabs().next().is_none()
     }
 
     fn active_tab(&self) -> Option<&PreviewTab> {
-        self.tabs.get(self.active_tab)
+        self.focused().active()
     }
 
     fn find_tab(&self, path: &PathBuf) -> Option<usize> {
-        self.tabs.iter().position(|t| t.path() == Some(path))
+        self.panes[0]
+            .tabs
+            .iter()
+            .position(|t| t.path() == Some(path))
     }
 
     fn next_tab(&mut self) {
-        if !self.tabs.is_empty() {
-            self.active_tab = (self.active_tab + 1) % self.tabs.len();
-            debug!("Switched to next tab: {}", self.active_tab);
+        let pane = self.focused_mut();
+        if !pane.tabs.is_empty() {
+            pane.active_tab = (pane.active_tab + 1) % pane.tabs.len();
+            debug!("Switched to next tab: {}", pane.active_tab);
         }
     }
 
     fn prev_tab(&mut self) {
-        if !self.tabs.is_empty() {
-            self.active_tab = if self.active_tab == 0 {
-                self.tabs.len() - 1
+        let pane = self.focused_mut();
+        if !pane.tabs.is_empty() {
+            pane.active_tab = if pane.active_tab == 0 {
+                pane.tabs.len() - 1
             } else {
-                self.active_tab - 1
+                pane.active_tab - 1
             };
-            debug!("Switched to previous tab: {}", self.active_tab);
+            debug!("Switched to previous tab: {}", pane.active_tab);
         }
     }
 
     fn close_tab(&mut self, index: usize) -> bool {
-        if index < self.tabs.len() {
+        let pane = &mut self.panes[0];
+        if index < pane.tabs.len() {
             debug!("Closing tab {}", index);
-            self.tabs.remove(index);
+            pane.take_tab(index);
 
-            if self.tabs.is_empty() {
+            if pane.tabs.is_empty() {
                 return true;
             }
-
-            // Adjust active tab if needed
-            if self.active_tab >= self.tabs.len() {
-                self.active_tab = self.tabs.len() - 1;
-            } else if index < self.active_tab {
-                self.active_tab -= 1;
-            }
         }
         false
     }
@@ -140,6 +144,9 @@ impl PreviewTabExt for PreviewTab {
             PreviewTab::Markdown { .. } => Some("markdown"),
             PreviewTab::Pdf { .. } => None,
             PreviewTab::Table { .. } => None,
+            PreviewTab::Image { .. } => None,
+            PreviewTab::Data { .. } => None,
+            PreviewTab::Diff { language, .. } => Some(language),
         }
     }
 
@@ -181,12 +188,15 @@ pub fn tab_from_path(path: PathBuf) -> Option<PreviewTab> {
             if let Some(language) = crate::types::language_from_extension(ext) {
                 let content = std::fs::read_to_string(&path).unwrap_or_default();
                 Some(PreviewTab::Code {
+                    disk_stamp: FileStamp::of(&path),
                     path,
                     language: language.to_string(),
                     content,
                     editing: true,
                     dirty: false,
                     editor: None,
+                    conflict: false,
+                    outline: None,
                     meta: crate::app::TabMeta::default(),
                 })
             } else {
@@ -196,6 +206,31 @@ pub fn tab_from_path(path: PathBuf) -> Option<PreviewTab> {
     }
 }
 
+/// What a code tab should do about its file, checked on disk
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DiskChange {
+    Unchanged,
+    /// Changed with no unsaved edits in the tab: read it again
+    Reload,
+    /// Changed under unsaved edits: ask whether to reload or overwrite
+    Conflict,
+}
+
+/// Compare the stamp a code tab read its file at with its stamp now. A
+/// file that can no longer be read is left alone, as saving writes it again.
+pub fn disk_change(read: Option<FileStamp>, now: Option<FileStamp>, dirty: bool) -> DiskChange {
+    match now {
+        Some(now) if read != Some(now) => {
+            if dirty {
+                DiskChange::Conflict
+            } else {
+                DiskChange::Reload
+            }
+        }
+        _ => DiskChange::Unchanged,
+    }
+}
+
 /// Toggle split direction helper.
 pub fn toggle_split(direction: SplitDirection) -> SplitDirection {
     match direction {
@@ -203,3 +238,146 @@ pub fn toggle_split(direction: SplitDirection) -> SplitDirection {
         SplitDirection::Horizontal => SplitDirection::Vertical,
     }
 }
+
+/// Pin or unpin the tab at `index`, moving it so pinned tabs stay
+/// left-most: a newly pinned tab goes after the other pinned tabs, a newly
+/// unpinned one before the other unpinned tabs. `active` keeps pointing at
+/// the same tab.
+pub fn toggle_tab_pinned(tabs: &mut Vec<PreviewTab>, active: &mut usize, index: usize) {
+    if index >= tabs.len() {
+        return;
+    }
+    let mut tab = tabs.remove(index);
+    tab.toggle_pinned();
+    let target = tabs.iter().filter(|t| t.is_pinned()).count();
+    tabs.insert(target, tab);
+    *active = index_after_move(*active, index, target);
+}
+
+/// Add a tab to a pane's tabs, after the other pinned tabs if it is pinned
+/// and at the end otherwise. Returns the index it went to; `active` keeps
+/// pointing at the same tab.
+pub fn insert_tab(tabs: &mut Vec<PreviewTab>, active: &mut usize, tab: PreviewTab) -> usize {
+    let index = if tab.is_pinned() {
+        tabs.iter().filter(|t| t.is_pinned()).count()
+    } else {
+        tabs.len()
+    };
+    tabs.insert(index, tab);
+    if tabs.len() > 1 && index <= *active {
+        *active += 1;
+    }
+    index
+}
+
+/// Where the tab that was at `index` ends up after the tab at `from` is
+/// moved to `to`
+pub fn index_after_move(index: usize, from: usize, to: usize) -> usize {
+    if index == from {
+        to
+    } else if from < index && to >= index {
+        index - 1
+    } else if from > index && to <= index {
+        index + 1
+    } else {
+        index
+    }
+}
+
+/// Indices of the tabs "Close Others" (keeping `keep`) or "Close All"
+/// (`keep` of `None`) closes: every unpinned tab but the kept one, last
+/// first so they can be removed in turn
+pub fn closable_tabs(tabs: &[PreviewTab], keep: Option<usize>) -> Vec<usize> {
+    (0..tabs.len())
+        .rev()
+        .filter(|&index| Some(index) != keep && !tabs[index].is_pinned())
+        .collect()
+}
+
+/// Add a tab to a pane's tabs in front of the tab at `index` (or at the
+/// end for `tabs.len()`), kept among the pinned tabs if it is pinned and
+/// after them otherwise. Returns the index it went to; `active` keeps
+/// pointing at the same tab.
+pub fn insert_tab_at(
+    tabs: &mut Vec<PreviewTab>,
+    active: &mut usize,
+    tab: PreviewTab,
+    index: usize,
+) -> usize {
+    let pinned_count = tabs.iter().filter(|t| t.is_pinned()).count();
+    let index = if tab.is_pinned() {
+        index.min(pinned_count)
+    } else {
+        index.clamp(pinned_count, tabs.len())
+    };
+    tabs.insert(index, tab);
+    if tabs.len() > 1 && index <= *active {
+        *active += 1;
+    }
+    index
+}
+
+/// Move the tab at `from` to where the tab at `to` is, within one pane.
+/// Pinned tabs stay among the pinned tabs and unpinned ones after them.
+/// Returns the index it went to; `active` keeps pointing at the same tab.
+pub fn move_tab(tabs: &mut Vec<PreviewTab>, active: &mut usize, from: usize, to: usize) -> usize {
+    if from >= tabs.len() {
+        return from;
+    }
+    let pinned_count = tabs.iter().filter(|t| t.is_pinned()).count();
+    let to = if tabs[from].is_pinned() {
+        to.min(pinned_count - 1)
+    } else {
+        to.max(pinned_count)
+    };
+    let tab = tabs.remove(from);
+    let to = to.min(tabs.len());
+    tabs.insert(to, tab);
+    *active = index_after_move(*active, from, to);
+    to
+}
+
+/// Width of the strip of canvas beside the preview panel that a dragged
+/// tab can be dropped on to split the panel
+pub const CANVAS_EDGE_DROP_SIZE: f32 = 80.0;
+
+/// Where the preview panel starts along its split axis, for a window of
+/// `window_size` with the panel taking `size` of it
+fn preview_start(window_size: (f32, f32), split: SplitDirection, size: f32) -> f32 {
+    match split {
+        SplitDirection::Vertical => window_size.0 * (1.0 - size),
+        SplitDirection::Horizontal => window_size.1 * (1.0 - size),
+    }
+}
+
+/// Whether `position` is over the preview panel, which sits right of the
+/// canvas for a vertical split and below it for a horizontal one
+pub fn in_preview_area(
+    position: (f32, f32),
+    window_size: (f32, f32),
+    split: SplitDirection,
+    size: f32,
+) -> bool {
+    let start = preview_start(window_size, split, size);
+    match split {
+        SplitDirection::Vertical => position.0 > start,
+        SplitDirection::Horizontal => position.1 > start,
+    }
+}
+
+/// The split a tab dragged to `position` makes, if it is over the edge of
+/// the canvas beside the preview panel: the tab goes to the pane nearest
+/// the canvas
+pub fn canvas_edge_drop_zone(
+    position: (f32, f32),
+    window_size: (f32, f32),
+    split: SplitDirection,
+    size: f32,
+) -> Option<SplitDropZone> {
+    let start = preview_start(window_size, split, size);
+    let (along, zone) = match split {
+        SplitDirection::Vertical => (position.0, SplitDropZone::Left),
+        SplitDirection::Horizontal => (position.1, SplitDropZone::Top),
+    };
+    (along <= start && along >= start - CANVAS_EDGE_DROP_SIZE).then_some(zone)
+}
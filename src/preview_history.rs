@@ -0,0 +1,108 @@
+//! Back/forward history of a preview pane.
+//!
+//! A pane remembers where it was before each move: to another tab, a file
+//! opened in it, or a PDF page or code line jumped to from an outline,
+//! search or the command palette. Going back returns to the last place
+//! left and going forward undoes that, until a new move drops the places
+//! ahead. Turning pages and scrolling are not moves.
+
+use std::path::PathBuf;
+
+/// Most places a pane remembers in each direction
+pub const MAX_NAV_HISTORY: usize = 100;
+
+/// The tab a place is in
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum NavTarget {
+    /// A file's tab, opened again if it was closed since
+    File(PathBuf),
+    /// A tab without a file, by its title
+    Tab(String),
+}
+
+/// Where in a tab a place is
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum NavPlace {
+    /// A PDF page, counted from 0
+    Page(usize),
+    /// A code line, counted from 0
+    Line(usize),
+}
+
+/// A place in a pane's history
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct NavLocation {
+    pub target: NavTarget,
+    /// Where in the tab, for tabs with pages or lines
+    pub place: Option<NavPlace>,
+}
+
+/// The places behind and ahead of a pane
+#[derive(Clone, Debug, Default)]
+pub struct NavHistory {
+    back: Vec<NavLocation>,
+    forward: Vec<NavLocation>,
+}
+
+impl NavHistory {
+    /// Remember `from` as the place left by a move, dropping the places
+    /// ahead
+    pub fn visit(&mut self, from: NavLocation) {
+        if self.back.last() != Some(&from) {
+            push_capped(&mut self.back, from);
+        }
+        self.forward.clear();
+    }
+
+    /// The last place left, skipping any that are `current`, which is
+    /// remembered ahead instead
+    pub fn back(&mut self, current: Option<NavLocation>) -> Option<NavLocation> {
+        step(&mut self.back, &mut self.forward, current)
+    }
+
+    /// The last place gone back from, skipping any that are `current`,
+    /// which is remembered behind instead
+    pub fn forward(&mut self, current: Option<NavLocation>) -> Option<NavLocation> {
+        step(&mut self.forward, &mut self.back, current)
+    }
+
+    pub fn can_go_back(&self) -> bool {
+        !self.back.is_empty()
+    }
+
+    pub fn can_go_forward(&self) -> bool {
+        !self.forward.is_empty()
+    }
+
+    /// Forget every place
+    pub fn clear(&mut self) {
+        self.back.clear();
+        self.forward.clear();
+    }
+}
+
+/// Take the next place from `from`, remembering `current` in `to`
+fn step(
+    from: &mut Vec<NavLocation>,
+    to: &mut Vec<NavLocation>,
+    current: Option<NavLocation>,
+) -> Option<NavLocation> {
+    while from
+        .last()
+        .is_some_and(|last| Some(last) == current.as_ref())
+    {
+        from.pop();
+    }
+    let location = from.pop()?;
+    if let Some(current) = current {
+        push_capped(to, current);
+    }
+    Some(location)
+}
+
+fn push_capped(stack: &mut Vec<NavLocation>, location: NavLocation) {
+    stack.push(location);
+    if stack.len() > MAX_NAV_HISTORY {
+        stack.remove(0);
+    }
+}
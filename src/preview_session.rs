@@ -0,0 +1,256 @@
+//! Preview sessions - the preview panel's layout saved with a board.
+//!
+//! When a board is left or saved, its open preview tabs are recorded in a
+//! [`PreviewSession`]: which files, tables and diffs are open in which
+//! pane, the active tabs, how the panel is split, and the page each PDF was
+//! left at. The first two panes are saved as the left and right pane, so
+//! older versions still open them; further panes and the split tree laying
+//! them out are saved beside.
+//! Opening the board again rebuilds the panel from it, leaving out tabs
+//! whose files or tables are gone.
+
+use crate::app::PaneTree;
+use crate::text_diff::DiffBase;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+/// What a saved tab shows
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum SessionTabSource {
+    /// A PDF, markdown, code or image file
+    File(PathBuf),
+    /// A table of one of the board's data sources
+    Table { data_source_id: u64, name: String },
+    /// A file compared with another file or its snapshot
+    Diff { path: PathBuf, base: DiffBase },
+}
+
+/// A saved preview tab
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct SessionTab {
+    pub source: SessionTabSource,
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub pinned: bool,
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub preview: bool,
+    /// Page a PDF was left at, counted from 0
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub page: Option<usize>,
+}
+
+/// A saved pane. Those past the first two are saved as such.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct SessionPane {
+    pub tabs: Vec<SessionTab>,
+    #[serde(default)]
+    pub active_tab: usize,
+    /// Whether the pane had focus
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub focused: bool,
+}
+
+/// The preview panel of a board as it was left
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct PreviewSession {
+    /// Tabs of the left (or only) pane
+    pub tabs: Vec<SessionTab>,
+    #[serde(default)]
+    pub active_tab: usize,
+    /// Tabs of the right pane, when the panel is split in two
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub right_tabs: Vec<SessionTab>,
+    #[serde(default)]
+    pub right_active_tab: usize,
+    /// Whether the panel is below the canvas rather than beside it
+    #[serde(default)]
+    pub below_canvas: bool,
+    /// Share of the window the panel takes
+    pub size: f32,
+    /// Whether the two panes are stacked rather than side by side
+    #[serde(default)]
+    pub panes_stacked: bool,
+    /// Share of the panel the left (or top) pane takes
+    #[serde(default = "default_pane_ratio")]
+    pub pane_ratio: f32,
+    /// Whether the right pane had focus
+    #[serde(default)]
+    pub right_focused: bool,
+    /// Panes after the left and right ones, when the panel is split in more
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub more_panes: Vec<SessionPane>,
+    /// How the panes are laid out, when there are more than two
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub layout: Option<PaneTree>,
+}
+
+fn is_false(value: &bool) -> bool {
+    !value
+}
+
+fn default_pane_ratio() -> f32 {
+    0.5
+}
+
+impl PreviewSession {
+    /// A session of `panes` laid out by `layout`, the first pane being the
+    /// left (or only) one
+    pub fn from_panes(
+        panes: Vec<SessionPane>,
+        layout: PaneTree,
+        below_canvas: bool,
+        size: f32,
+    ) -> Self {
+        let mut session = PreviewSession {
+            tabs: Vec::new(),
+            active_tab: 0,
+            right_tabs: Vec::new(),
+            right_active_tab: 0,
+            below_canvas,
+            size,
+            panes_stacked: false,
+            pane_ratio: default_pane_ratio(),
+            right_focused: false,
+            more_panes: Vec::new(),
+            layout: None,
+        };
+        session.set_panes(panes, layout);
+        session
+    }
+
+    /// Whether the panel is split into more than one pane
+    pub fn is_split(&self) -> bool {
+        !self.right_tabs.is_empty()
+    }
+
+    /// Every pane, the left (or only) one first. Panes past the first two
+    /// are left out if the saved layout doesn't place them.
+    pub fn panes(&self) -> Vec<SessionPane> {
+        let mut panes = vec![SessionPane {
+            tabs: self.tabs.clone(),
+            active_tab: self.active_tab,
+            focused: false,
+        }];
+        if self.is_split() {
+            panes.push(SessionPane {
+                tabs: self.right_tabs.clone(),
+                active_tab: self.right_active_tab,
+                focused: self.right_focused,
+            });
+            if self.saved_layout().is_some() {
+                panes.extend(self.more_panes.iter().cloned());
+            }
+        }
+        panes
+    }
+
+    /// How the panes are laid out
+    pub fn layout(&self) -> PaneTree {
+        if let Some(layout) = self.saved_layout() {
+            return layout.clone();
+        }
+        if !self.is_split() {
+            return PaneTree::default();
+        }
+        PaneTree::Split {
+            horizontal: self.panes_stacked,
+            ratio: self.pane_ratio,
+            first: Box::new(PaneTree::Pane(0)),
+            second: Box::new(PaneTree::Pane(1)),
+        }
+    }
+
+    /// The saved layout, if it places every pane
+    fn saved_layout(&self) -> Option<&PaneTree> {
+        let layout = self.layout.as_ref()?;
+        let mut panes = layout.panes();
+        panes.sort_unstable();
+        let expected: Vec<usize> = (0..self.more_panes.len() + 2).collect();
+        (self.is_split() && panes == expected).then_some(layout)
+    }
+
+    fn set_panes(&mut self, panes: Vec<SessionPane>, layout: PaneTree) {
+        let mut panes = panes.into_iter();
+        let first = panes.next().unwrap_or_default();
+        let second = panes.next().unwrap_or_default();
+        self.more_panes = panes.collect();
+        self.tabs = first.tabs;
+        self.active_tab = first.active_tab;
+        self.right_tabs = second.tabs;
+        self.right_active_tab = second.active_tab;
+        self.right_focused = second.focused;
+        // Older versions split the panel in two as the root split does
+        if let PaneTree::Split {
+            horizontal, ratio, ..
+        } = layout
+        {
+            self.panes_stacked = horizontal;
+            self.pane_ratio = ratio;
+        }
+        self.layout = (!self.more_panes.is_empty()).then_some(layout);
+    }
+
+    /// The session with only the tabs `keep` accepts, e.g. those whose
+    /// files still exist. Active tabs stay on the same tab where it is
+    /// kept, a pane left empty is closed, and `None` is returned if no
+    /// tab is left. Sizes are brought back into range.
+    pub fn retain(mut self, keep: impl Fn(&SessionTab) -> bool) -> Option<Self> {
+        let mut layout = self.layout();
+        let mut panes = self.panes();
+        for pane in &mut panes {
+            pane.active_tab = retain_tabs(&mut pane.tabs, pane.active_tab, &keep);
+        }
+        for index in (0..panes.len()).rev() {
+            if panes[index].tabs.is_empty() && panes.len() > 1 {
+                panes.remove(index);
+                layout.remove(index);
+            }
+        }
+        if panes[0].tabs.is_empty() {
+            return None;
+        }
+        clamp_ratios(&mut layout);
+        self.set_panes(panes, layout);
+        self.size = self.size.clamp(0.2, 0.8);
+        self.pane_ratio = self.pane_ratio.clamp(0.2, 0.8);
+        Some(self)
+    }
+}
+
+fn clamp_ratios(layout: &mut PaneTree) {
+    if let PaneTree::Split {
+        ratio,
+        first,
+        second,
+        ..
+    } = layout
+    {
+        *ratio = ratio.clamp(0.2, 0.8);
+        clamp_ratios(first);
+        clamp_ratios(second);
+    }
+}
+
+/// Drop the tabs `keep` rejects, returning where the active tab went: its
+/// new index, or that of the nearest kept tab before it if it was dropped
+fn retain_tabs(
+    tabs: &mut Vec<SessionTab>,
+    active: usize,
+    keep: &impl Fn(&SessionTab) -> bool,
+) -> usize {
+    let mut new_active = 0;
+    let mut kept = 0;
+    let mut index = 0;
+    tabs.retain(|tab| {
+        let keep_tab = keep(tab);
+        if keep_tab {
+            if index <= active {
+                new_active = kept;
+            }
+            kept += 1;
+        }
+        index += 1;
+        keep_tab
+    });
+    new_active
+}
@@ -0,0 +1,163 @@
+//! Progressive loading of heavy media.
+//!
+//! Opening a board with hundreds of images, PDFs and videos would otherwise
+//! start everything in the first frame: image thumbnail generation, decoding
+//! every PDF thumbnail and creating every webview near the viewport. The
+//! board stays frozen until all of it is done.
+//!
+//! [`ProgressiveLoader`] spreads that work across frames. Each frame gets a
+//! [`RenderBudget`]; an item is only started while the budget has room for
+//! its estimated cost, and items that haven't started yet draw a lightweight
+//! placeholder. One item is always started per frame so loading keeps moving
+//! even when the frame is already over budget.
+//!
+//! Webview costs are measured as they are created and the estimate follows
+//! them. Image and PDF thumbnail costs are paid later by GPUI's decoder and
+//! can't be timed here, so they use fixed estimates.
+
+use crate::perf::RenderBudget;
+use std::collections::HashSet;
+
+/// Frame time available for starting new loads, in milliseconds
+pub const LOAD_BUDGET_MS: f64 = 8.0;
+
+/// Weight of a new measurement in the running cost estimate
+const ESTIMATE_SMOOTHING: f64 = 0.2;
+
+/// Kinds of media loaded progressively
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LoadKind {
+    /// Canvas image, including its thumbnail generation
+    Image,
+    /// Rendered first page of a PDF item
+    PdfThumbnail,
+    /// Embedded player, audio or video webview
+    WebView,
+}
+
+impl LoadKind {
+    /// Estimated cost before anything has been measured, in milliseconds
+    pub fn default_estimate_ms(self) -> f64 {
+        match self {
+            LoadKind::Image => 2.0,
+            LoadKind::PdfThumbnail => 2.0,
+            LoadKind::WebView => 15.0,
+        }
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            LoadKind::Image => "load_image",
+            LoadKind::PdfThumbnail => "load_pdf_thumbnail",
+            LoadKind::WebView => "load_webview",
+        }
+    }
+}
+
+/// Decides which items may start loading this frame
+pub struct ProgressiveLoader {
+    budget_ms: f64,
+    budget: RenderBudget,
+    loaded: HashSet<u64>,
+    webview_estimate_ms: f64,
+    started_this_frame: usize,
+    deferred_this_frame: usize,
+}
+
+impl ProgressiveLoader {
+    pub fn new() -> Self {
+        Self::with_budget_ms(LOAD_BUDGET_MS)
+    }
+
+    pub fn with_budget_ms(budget_ms: f64) -> Self {
+        Self {
+            budget_ms,
+            budget: RenderBudget::with_budget(budget_ms),
+            loaded: HashSet::new(),
+            webview_estimate_ms: LoadKind::WebView.default_estimate_ms(),
+            started_this_frame: 0,
+            deferred_this_frame: 0,
+        }
+    }
+
+    /// Start a new frame's budget. Call once at the start of each render.
+    pub fn begin_frame(&mut self) {
+        self.budget = RenderBudget::with_budget(self.budget_ms);
+        self.started_this_frame = 0;
+        self.deferred_this_frame = 0;
+    }
+
+    /// Whether an item has been allowed to load
+    pub fn is_loaded(&self, item_id: u64) -> bool {
+        self.loaded.contains(&item_id)
+    }
+
+    /// Whether an item may load now. Items that already loaded always may;
+    /// others are started if this frame's budget has room for them, and
+    /// otherwise wait for a later frame.
+    pub fn try_load(&mut self, item_id: u64, kind: LoadKind) -> bool {
+        if self.loaded.contains(&item_id) {
+            return true;
+        }
+
+        let estimate = self.estimate_ms(kind);
+        if self.started_this_frame > 0 && self.budget.should_skip(self.budget.spent_ms() + estimate)
+        {
+            self.deferred_this_frame += 1;
+            return false;
+        }
+
+        self.budget.record(kind.label(), estimate);
+        self.started_this_frame += 1;
+        self.loaded.insert(item_id);
+        true
+    }
+
+    /// Feed back how long starting an item actually took
+    pub fn record(&mut self, kind: LoadKind, elapsed_ms: f64) {
+        if kind == LoadKind::WebView {
+            self.webview_estimate_ms +=
+                (elapsed_ms - self.webview_estimate_ms) * ESTIMATE_SMOOTHING;
+        }
+    }
+
+    /// Expected cost of starting an item of this kind, in milliseconds
+    pub fn estimate_ms(&self, kind: LoadKind) -> f64 {
+        match kind {
+            LoadKind::WebView => self.webview_estimate_ms,
+            _ => kind.default_estimate_ms(),
+        }
+    }
+
+    /// Whether any item had to wait this frame (another frame is needed)
+    pub fn has_deferred(&self) -> bool {
+        self.deferred_this_frame > 0
+    }
+
+    /// Items started this frame
+    pub fn started_this_frame(&self) -> usize {
+        self.started_this_frame
+    }
+
+    /// Let an item's media go through the budget again, e.g. after its
+    /// webview was destroyed
+    pub fn forget(&mut self, item_id: u64) {
+        self.loaded.remove(&item_id);
+    }
+
+    /// Forget every item, e.g. when switching boards
+    pub fn clear(&mut self) {
+        self.loaded.clear();
+    }
+
+    /// Number of items allowed to load so far
+    pub fn loaded_count(&self) -> usize {
+        self.loaded.len()
+    }
+}
+
+impl Default for ProgressiveLoader {
+    fn default() -> Self {
+        Self::new()
+    }
+}
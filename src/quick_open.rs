@@ -0,0 +1,207 @@
+//! Quick open - adding files to the board by name.
+//!
+//! The palette's file mode (`Cmd+P`) lists the files under the quick-open
+//! folders, matched fuzzily against their names and paths, and the chosen
+//! file is added at the center of the view as if it had been dropped
+//! there. The folders are the `quick_open_folders` setting, or the user's
+//! Desktop, Documents, Downloads and Pictures when it isn't set. They're
+//! scanned off the UI thread, and scanned again once the listing is a
+//! minute old.
+
+use crate::fuzzy::fuzzy_score;
+use std::collections::VecDeque;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::mpsc::{Receiver, TryRecvError};
+use std::time::{Duration, Instant};
+
+/// Most files listed, so a huge folder can't hold up the scan for long
+pub const MAX_INDEXED_FILES: usize = 50_000;
+
+/// Deepest a scan goes below each folder
+pub const MAX_SCAN_DEPTH: usize = 8;
+
+/// Most files shown in the palette
+pub const MAX_FILE_RESULTS: usize = 50;
+
+/// How long a listing is used before the folders are scanned again
+pub const RESCAN_AFTER: Duration = Duration::from_secs(60);
+
+/// Extra score for matching the file name rather than its folders
+const NAME_BONUS: i32 = 32;
+
+/// Folders never worth listing: dependencies and build output
+const SKIPPED_DIRS: [&str; 5] = ["node_modules", "target", "__pycache__", "venv", "Library"];
+
+/// A file under one of the quick-open folders
+#[derive(Debug, Clone, PartialEq)]
+pub struct IndexedFile {
+    pub path: PathBuf,
+    /// The path shown in the palette: the folder's name, then the path
+    /// below it
+    pub display: String,
+}
+
+/// The folders to list: those configured, with `~` standing for the home
+/// folder, or the usual folders for documents when none are
+pub fn quick_open_folders(configured: Option<Vec<String>>) -> Vec<PathBuf> {
+    match configured {
+        Some(folders) => folders.iter().map(|folder| expand_home(folder)).collect(),
+        None => [
+            dirs::desktop_dir(),
+            dirs::document_dir(),
+            dirs::download_dir(),
+            dirs::picture_dir(),
+        ]
+        .into_iter()
+        .flatten()
+        .filter(|folder| folder.is_dir())
+        .collect(),
+    }
+}
+
+/// `folder` with a leading `~` replaced by the home folder
+fn expand_home(folder: &str) -> PathBuf {
+    let home = dirs::home_dir();
+    match (folder.strip_prefix('~'), home) {
+        (Some(rest), Some(home)) => home.join(rest.trim_start_matches(['/', '\\'])),
+        _ => PathBuf::from(folder),
+    }
+}
+
+/// Whether an entry named `name` is left out of the listing
+fn is_skipped(name: &str) -> bool {
+    name.starts_with('.') || SKIPPED_DIRS.contains(&name)
+}
+
+/// List the files under `folders`, breadth first so the files nearest
+/// the top are listed if there are more than `max_files`. Hidden files and
+/// folders are left out, and symlinked folders aren't followed.
+pub fn scan_folders(folders: &[PathBuf], max_files: usize) -> Vec<IndexedFile> {
+    let mut files = Vec::new();
+    for folder in folders {
+        // A folder inside another one is listed with it
+        if folders
+            .iter()
+            .any(|other| other != folder && folder.starts_with(other))
+        {
+            continue;
+        }
+        let root = folder
+            .file_name()
+            .map(PathBuf::from)
+            .unwrap_or_else(|| folder.clone());
+
+        let mut queue = VecDeque::from([(folder.clone(), 0)]);
+        while let Some((dir, depth)) = queue.pop_front() {
+            let Ok(entries) = fs::read_dir(&dir) else {
+                continue;
+            };
+            let mut entries: Vec<_> = entries.flatten().collect();
+            entries.sort_by_key(|entry| entry.file_name());
+
+            for entry in entries {
+                if is_skipped(&entry.file_name().to_string_lossy()) {
+                    continue;
+                }
+                let Ok(file_type) = entry.file_type() else {
+                    continue;
+                };
+                let path = entry.path();
+                if file_type.is_dir() {
+                    if depth < MAX_SCAN_DEPTH {
+                        queue.push_back((path, depth + 1));
+                    }
+                } else if file_type.is_file() || path.is_file() {
+                    let relative = path.strip_prefix(folder).unwrap_or(&path);
+                    files.push(IndexedFile {
+                        display: root.join(relative).display().to_string(),
+                        path,
+                    });
+                    if files.len() >= max_files {
+                        return files;
+                    }
+                }
+            }
+        }
+    }
+    files
+}
+
+/// Indices of the files matching `query`, best first, at most `limit`.
+/// A match in the file name beats one in its folders, and shorter paths
+/// win ties. An empty query lists the files in order.
+pub fn matching_files(files: &[IndexedFile], query: &str, limit: usize) -> Vec<usize> {
+    if query.trim().is_empty() {
+        return (0..files.len().min(limit)).collect();
+    }
+
+    let mut ranked: Vec<(i32, usize, usize)> = files
+        .iter()
+        .enumerate()
+        .filter_map(|(index, file)| {
+            let name = file
+                .path
+                .file_name()
+                .map(|name| name.to_string_lossy())
+                .unwrap_or_default();
+            let in_name = fuzzy_score(query, &name).map(|score| score + NAME_BONUS);
+            let in_path = fuzzy_score(query, &file.display);
+            let score = in_name.max(in_path)?;
+            Some((score, file.display.len(), index))
+        })
+        .collect();
+    ranked.sort_by(|a, b| b.0.cmp(&a.0).then(a.1.cmp(&b.1)));
+    ranked
+        .into_iter()
+        .take(limit)
+        .map(|(_, _, index)| index)
+        .collect()
+}
+
+/// The files under the quick-open folders, as of the last scan
+#[derive(Default)]
+pub struct FileIndex {
+    pub files: Vec<IndexedFile>,
+    /// When the last scan finished
+    pub scanned_at: Option<Instant>,
+    /// Files of the scan under way
+    pub scan_rx: Option<Receiver<Vec<IndexedFile>>>,
+}
+
+impl FileIndex {
+    /// Whether a scan is under way
+    pub fn is_scanning(&self) -> bool {
+        self.scan_rx.is_some()
+    }
+
+    /// Whether the folders should be scanned (again)
+    pub fn needs_scan(&self) -> bool {
+        !self.is_scanning()
+            && self
+                .scanned_at
+                .is_none_or(|scanned_at| scanned_at.elapsed() >= RESCAN_AFTER)
+    }
+
+    /// Take the files of a finished scan. Returns true if the listing
+    /// changed.
+    pub fn poll(&mut self) -> bool {
+        let Some(ref rx) = self.scan_rx else {
+            return false;
+        };
+        match rx.try_recv() {
+            Ok(files) => {
+                self.files = files;
+                self.scanned_at = Some(Instant::now());
+                self.scan_rx = None;
+                true
+            }
+            Err(TryRecvError::Empty) => false,
+            Err(TryRecvError::Disconnected) => {
+                self.scanned_at = Some(Instant::now());
+                self.scan_rx = None;
+                false
+            }
+        }
+    }
+}
@@ -0,0 +1,611 @@
+//! Region export - a part of the canvas as a PNG, an SVG or a PDF.
+//!
+//! The region is all items, the selection, the visible part of the canvas
+//! or a rectangle dragged out on it, always in canvas units. It is exported
+//! at 1x, 2x or 4x pixels per canvas unit:
+//!
+//! - **PNG** goes through the offscreen snapshot renderer
+//! - **SVG** draws the items as vectors: shapes, arrows, text boxes and text as
+//!   themselves, images embedded as data URIs and other cards as labeled
+//!   blocks in their type's color
+//! - **PDF** splits the region into landscape A4 pages, one point per
+//!   canvas unit, each page a snapshot embedded as a JPEG
+
+use crate::board::BoardState;
+use crate::board_export::{data_uri, escape_html};
+use crate::canvas_background::CanvasBackground;
+use crate::canvas_snapshot::{
+    DEFAULT_BACKGROUND, MAX_SNAPSHOT_DIMENSION, SnapshotOptions, item_color, parse_hex,
+    render_snapshot, save_snapshot,
+};
+use crate::error::MediaError;
+use crate::input::guides::{Bounds, union_bounds};
+use crate::types::{ArrowHead, CanvasItem, ItemContent, ShapeType};
+use image::codecs::jpeg::JpegEncoder;
+use image::{DynamicImage, Rgba};
+use std::collections::HashSet;
+use std::fmt::Write as _;
+use std::fs;
+use std::path::Path;
+
+/// Pixels per canvas unit offered in the export dialog
+pub const EXPORT_SCALES: [f32; 3] = [1.0, 2.0, 4.0];
+
+/// Space around exported items, in canvas units
+pub const REGION_PADDING: f32 = 32.0;
+
+/// Size of a PDF page in points (landscape A4)
+pub const PDF_PAGE_SIZE: (f32, f32) = (842.0, 595.0);
+
+/// JPEG quality of the pages of a PDF
+const PDF_JPEG_QUALITY: u8 = 90;
+
+/// Fonts text in SVGs is drawn with
+pub(crate) const SVG_FONT_FAMILY: &str = "-apple-system, Helvetica, Arial, sans-serif";
+
+/// Width of an average character as a part of the font size, for wrapping
+/// text without measuring it
+const AVERAGE_CHAR_WIDTH: f32 = 0.55;
+
+/// Height of a line of text as a part of the font size
+const LINE_HEIGHT: f32 = 1.2;
+
+/// Part of the canvas to export
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportRegion {
+    AllItems,
+    Selection,
+    /// What the canvas shows in the window
+    Visible,
+    /// A rectangle dragged out on the canvas
+    Drawn,
+}
+
+impl ExportRegion {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::AllItems => "All items",
+            Self::Selection => "Selection",
+            Self::Visible => "Visible area",
+            Self::Drawn => "Drawn region",
+        }
+    }
+}
+
+/// File format of an export
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+    Png,
+    Svg,
+    Pdf,
+}
+
+impl ExportFormat {
+    pub const ALL: [ExportFormat; 3] = [Self::Png, Self::Svg, Self::Pdf];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Png => "PNG",
+            Self::Svg => "SVG",
+            Self::Pdf => "PDF",
+        }
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Self::Png => "png",
+            Self::Svg => "svg",
+            Self::Pdf => "pdf",
+        }
+    }
+}
+
+/// Bounds of an item, reaching to the end of an arrow
+pub fn item_extent(item: &CanvasItem) -> Bounds {
+    let (x, y) = item.position;
+    match &item.content {
+        ItemContent::Arrow { end_offset, .. } => (
+            x.min(x + end_offset.0),
+            y.min(y + end_offset.1),
+            end_offset.0.abs(),
+            end_offset.1.abs(),
+        ),
+        _ => (x, y, item.size.0, item.size.1),
+    }
+}
+
+/// Region around the items whose IDs are in `ids` (all items if None),
+/// with `padding` on every side
+pub fn items_region(
+    items: &[CanvasItem],
+    ids: Option<&HashSet<u64>>,
+    padding: f32,
+) -> Option<Bounds> {
+    let bounds = union_bounds(
+        items
+            .iter()
+            .filter(|item| ids.is_none_or(|ids| ids.contains(&item.id)))
+            .map(item_extent),
+    )?;
+    let (x, y, w, h) = bounds;
+    Some((
+        x - padding,
+        y - padding,
+        w + padding * 2.0,
+        h + padding * 2.0,
+    ))
+}
+
+/// Rectangle between two corners dragged out on the canvas
+pub fn drawn_region(start: (f32, f32), end: (f32, f32)) -> Bounds {
+    (
+        start.0.min(end.0),
+        start.1.min(end.1),
+        (end.0 - start.0).abs(),
+        (end.1 - start.1).abs(),
+    )
+}
+
+/// Whether `item` shows inside `region`
+pub fn overlaps_region(item: &CanvasItem, region: Bounds) -> bool {
+    let (x, y, w, h) = item_extent(item);
+    let (rx, ry, rw, rh) = region;
+    x <= rx + rw && x + w >= rx && y <= ry + rh && y + h >= ry
+}
+
+/// Size in pixels of `region` exported at `scale`, shrunk to fit
+/// `MAX_SNAPSHOT_DIMENSION` keeping the aspect ratio
+pub fn export_size(region: Bounds, scale: f32) -> (u32, u32) {
+    let (_, _, w, h) = region;
+    let (width, height) = ((w * scale).max(1.0), (h * scale).max(1.0));
+    let limit = MAX_SNAPSHOT_DIMENSION as f32;
+    let shrink = (limit / width).min(limit / height).min(1.0);
+    (
+        (width * shrink).round().max(1.0) as u32,
+        (height * shrink).round().max(1.0) as u32,
+    )
+}
+
+/// Snapshot options for `region` at `scale`
+pub fn region_snapshot_options(region: Bounds, scale: f32, grid_size: f32) -> SnapshotOptions {
+    let (width, height) = export_size(region, scale);
+    SnapshotOptions::region(width, height, region).with_grid_size(grid_size)
+}
+
+/// Split `region` into pages of `page` canvas units, row by row. Pages on
+/// the right and bottom edges are cut to the region.
+pub fn page_regions(region: Bounds, page: (f32, f32)) -> Vec<Bounds> {
+    let (x, y, w, h) = region;
+    let columns = (w / page.0).ceil().max(1.0) as usize;
+    let rows = (h / page.1).ceil().max(1.0) as usize;
+
+    let mut pages = Vec::with_capacity(columns * rows);
+    for row in 0..rows {
+        for column in 0..columns {
+            let left = x + column as f32 * page.0;
+            let top = y + row as f32 * page.1;
+            pages.push((
+                left,
+                top,
+                page.0.min(x + w - left).max(1.0),
+                page.1.min(y + h - top).max(1.0),
+            ));
+        }
+    }
+    pages
+}
+
+/// Draw the items of `state` inside `region` as an SVG document `scale`
+/// pixels per canvas unit in size
+pub fn render_svg(state: &BoardState, region: Bounds, scale: f32) -> String {
+    let (x, y, w, h) = region;
+    let background = match &state.background {
+        Some(CanvasBackground::Color { color }) => parse_hex(color).unwrap_or(DEFAULT_BACKGROUND),
+        _ => DEFAULT_BACKGROUND,
+    };
+
+    let mut svg = String::new();
+    let _ = writeln!(
+        svg,
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{:.0}\" height=\"{:.0}\" \
+         viewBox=\"{} {} {} {}\" font-family=\"{}\">",
+        w * scale,
+        h * scale,
+        x,
+        y,
+        w,
+        h,
+        SVG_FONT_FAMILY
+    );
+    let _ = writeln!(
+        svg,
+        "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"{}\"/>",
+        x,
+        y,
+        w,
+        h,
+        svg_color(background)
+    );
+    for item in state
+        .items
+        .iter()
+        .filter(|item| overlaps_region(item, region))
+    {
+        svg.push_str(&svg_item(item));
+    }
+    svg.push_str("</svg>\n");
+    svg
+}
+
+/// Render `region` of the board into one JPEG per page and put them in a
+/// PDF document
+pub fn render_pdf(
+    state: &BoardState,
+    region: Bounds,
+    scale: f32,
+    grid_size: f32,
+) -> Result<Vec<u8>, MediaError> {
+    let mut pages = Vec::new();
+    for page in page_regions(region, PDF_PAGE_SIZE) {
+        let options = region_snapshot_options(page, scale, grid_size);
+        let image = DynamicImage::ImageRgba8(render_snapshot(state, &options)).to_rgb8();
+        let mut jpeg = Vec::new();
+        JpegEncoder::new_with_quality(&mut jpeg, PDF_JPEG_QUALITY).encode_image(&image)?;
+        pages.push(PdfPage {
+            jpeg,
+            pixels: (image.width(), image.height()),
+            size: (page.2, page.3),
+        });
+    }
+    Ok(write_pdf(&pages))
+}
+
+/// Export `region` of the board to `path` in `format`
+pub fn export_region(
+    state: &BoardState,
+    region: Bounds,
+    format: ExportFormat,
+    scale: f32,
+    grid_size: f32,
+    path: &Path,
+) -> Result<(), MediaError> {
+    let bytes = match format {
+        ExportFormat::Png => {
+            return save_snapshot(
+                state,
+                &region_snapshot_options(region, scale, grid_size),
+                path,
+            );
+        }
+        ExportFormat::Svg => render_svg(state, region, scale).into_bytes(),
+        ExportFormat::Pdf => render_pdf(state, region, scale, grid_size)?,
+    };
+    fs::write(path, bytes).map_err(|e| MediaError::WriteFailed {
+        path: path.to_path_buf(),
+        source: e,
+    })
+}
+
+/// One page of a PDF: a JPEG drawn at the top-left, `size` points large
+struct PdfPage {
+    jpeg: Vec<u8>,
+    pixels: (u32, u32),
+    size: (f32, f32),
+}
+
+/// Write a PDF with one image per page
+fn write_pdf(pages: &[PdfPage]) -> Vec<u8> {
+    let mut pdf: Vec<u8> = b"%PDF-1.4\n%\xE2\xE3\xCF\xD3\n".to_vec();
+    let mut offsets = Vec::new();
+    let mut object = |pdf: &mut Vec<u8>, body: &[u8]| {
+        offsets.push(pdf.len());
+        pdf.extend_from_slice(format!("{} 0 obj\n", offsets.len()).as_bytes());
+        pdf.extend_from_slice(body);
+        pdf.extend_from_slice(b"\nendobj\n");
+    };
+
+    // Objects 1 and 2 are the catalog and page tree, then three per page:
+    // the page, its content stream and its image
+    let kids: Vec<String> = (0..pages.len())
+        .map(|i| format!("{} 0 R", 3 + i * 3))
+        .collect();
+    object(&mut pdf, b"<< /Type /Catalog /Pages 2 0 R >>");
+    object(
+        &mut pdf,
+        format!(
+            "<< /Type /Pages /Kids [{}] /Count {} >>",
+            kids.join(" "),
+            pages.len()
+        )
+        .as_bytes(),
+    );
+    for (i, page) in pages.iter().enumerate() {
+        let id = 3 + i * 3;
+        let (page_w, page_h) = PDF_PAGE_SIZE;
+        object(
+            &mut pdf,
+            format!(
+                "<< /Type /Page /Parent 2 0 R /MediaBox [0 0 {} {}] \
+                 /Resources << /XObject << /Im0 {} 0 R >> >> /Contents {} 0 R >>",
+                page_w,
+                page_h,
+                id + 2,
+                id + 1
+            )
+            .as_bytes(),
+        );
+        let content = format!(
+            "q {:.2} 0 0 {:.2} 0 {:.2} cm /Im0 Do Q",
+            page.size.0,
+            page.size.1,
+            page_h - page.size.1
+        );
+        object(
+            &mut pdf,
+            format!(
+                "<< /Length {} >>\nstream\n{}\nendstream",
+                content.len(),
+                content
+            )
+            .as_bytes(),
+        );
+        let mut image = format!(
+            "<< /Type /XObject /Subtype /Image /Width {} /Height {} /ColorSpace /DeviceRGB \
+             /BitsPerComponent 8 /Filter /DCTDecode /Length {} >>\nstream\n",
+            page.pixels.0,
+            page.pixels.1,
+            page.jpeg.len()
+        )
+        .into_bytes();
+        image.extend_from_slice(&page.jpeg);
+        image.extend_from_slice(b"\nendstream");
+        object(&mut pdf, &image);
+    }
+
+    let xref = pdf.len();
+    let mut trailer = format!("xref\n0 {}\n0000000000 65535 f \n", offsets.len() + 1);
+    for offset in &offsets {
+        let _ = writeln!(trailer, "{:010} 00000 n ", offset);
+    }
+    let _ = write!(
+        trailer,
+        "trailer\n<< /Size {} /Root 1 0 R >>\nstartxref\n{}\n%%EOF\n",
+        offsets.len() + 1,
+        xref
+    );
+    pdf.extend_from_slice(trailer.as_bytes());
+    pdf
+}
+
+/// An item as SVG elements in canvas units
+pub(crate) fn svg_item(item: &CanvasItem) -> String {
+    let (x, y) = item.position;
+    let (w, h) = item.size;
+    match &item.content {
+        ItemContent::Image(path) => match data_uri(path) {
+            Some(uri) => format!(
+                "<image x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" \
+                 preserveAspectRatio=\"none\" href=\"{}\"/>\n",
+                x, y, w, h, uri
+            ),
+            None => svg_card(item),
+        },
+        ItemContent::Shape {
+            shape_type,
+            fill_color,
+            border_color,
+            border_width,
+        } => {
+            let fill = fill_color
+                .as_deref()
+                .and_then(parse_hex)
+                .map(svg_color)
+                .unwrap_or_else(|| "none".to_string());
+            let stroke = parse_hex(border_color)
+                .map(svg_color)
+                .unwrap_or_else(|| "#ffffff".to_string());
+            let paint = format!(
+                "fill=\"{}\" stroke=\"{}\" stroke-width=\"{}\"",
+                fill, stroke, border_width
+            );
+            match shape_type {
+                ShapeType::Ellipse => format!(
+                    "<ellipse cx=\"{}\" cy=\"{}\" rx=\"{}\" ry=\"{}\" {}/>\n",
+                    x + w / 2.0,
+                    y + h / 2.0,
+                    w / 2.0,
+                    h / 2.0,
+                    paint
+                ),
+                ShapeType::Rectangle | ShapeType::RoundedRect => {
+                    let radius = if *shape_type == ShapeType::RoundedRect {
+                        (w.min(h) * 0.1).min(16.0)
+                    } else {
+                        0.0
+                    };
+                    format!(
+                        "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" rx=\"{}\" {}/>\n",
+                        x, y, w, h, radius, paint
+                    )
+                }
+            }
+        }
+        ItemContent::Arrow {
+            end_offset,
+            thickness,
+            head_style,
+            ..
+        } => {
+            let color = svg_color(item_color(&item.content));
+            let end = (x + end_offset.0, y + end_offset.1);
+            let mut svg = format!(
+                "<line x1=\"{}\" y1=\"{}\" x2=\"{}\" y2=\"{}\" stroke=\"{}\" \
+                 stroke-width=\"{}\" stroke-linecap=\"round\"/>\n",
+                x, y, end.0, end.1, color, thickness
+            );
+            svg.push_str(&svg_arrow_head(
+                (x, y),
+                end,
+                *thickness,
+                *head_style,
+                &color,
+            ));
+            svg
+        }
+        ItemContent::TextBox {
+            text,
+            font_size,
+            color,
+        } => {
+            let fill = parse_hex(color)
+                .map(svg_color)
+                .unwrap_or_else(|| "#ffffff".to_string());
+            // Padded like the text box on the canvas
+            let lines = wrap_text(text, w - 16.0, *font_size);
+            svg_text(&lines, x + 8.0, y + 8.0, *font_size, &fill, "start")
+        }
+        ItemContent::Text(text) => {
+            let font_size = 14.0;
+            let lines = wrap_text(text, w - 24.0, font_size);
+            // Centered in the card, as on the canvas
+            let top = y + (h - lines.len() as f32 * font_size * LINE_HEIGHT) / 2.0;
+            let mut svg = format!(
+                "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" rx=\"8\" fill=\"{}\"/>\n",
+                x,
+                y,
+                w,
+                h,
+                svg_color(item_color(&item.content))
+            );
+            svg.push_str(&svg_text(
+                &lines,
+                x + w / 2.0,
+                top.max(y + 12.0),
+                font_size,
+                "#18181b",
+                "middle",
+            ));
+            svg
+        }
+        _ => svg_card(item),
+    }
+}
+
+/// Lines of text from `top`, anchored at `x` ("start" or "middle")
+fn svg_text(
+    lines: &[String],
+    x: f32,
+    top: f32,
+    font_size: f32,
+    fill: &str,
+    anchor: &str,
+) -> String {
+    let mut svg = format!(
+        "<text x=\"{}\" y=\"{}\" font-size=\"{}\" fill=\"{}\" text-anchor=\"{}\">",
+        x, top, font_size, fill, anchor
+    );
+    for line in lines {
+        let _ = write!(
+            svg,
+            "<tspan x=\"{}\" dy=\"{}em\">{}</tspan>",
+            x,
+            LINE_HEIGHT,
+            escape_html(line)
+        );
+    }
+    svg.push_str("</text>\n");
+    svg
+}
+
+/// `text` broken into lines at word boundaries so they fit in `width` at
+/// `font_size`, going by the average width of a character
+fn wrap_text(text: &str, width: f32, font_size: f32) -> Vec<String> {
+    let max_chars = ((width / (font_size * AVERAGE_CHAR_WIDTH)) as usize).max(1);
+    let mut lines = Vec::new();
+    for paragraph in text.lines() {
+        let mut line = String::new();
+        for word in paragraph.split_whitespace() {
+            if !line.is_empty() && line.chars().count() + 1 + word.chars().count() > max_chars {
+                lines.push(std::mem::take(&mut line));
+            }
+            if !line.is_empty() {
+                line.push(' ');
+            }
+            line.push_str(word);
+        }
+        lines.push(line);
+    }
+    lines
+}
+
+/// A block in the item type's color with the item's name in it
+fn svg_card(item: &CanvasItem) -> String {
+    let (x, y) = item.position;
+    let (w, h) = item.size;
+    format!(
+        "<g><rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" rx=\"8\" fill=\"{}\"/>\
+         <text x=\"{}\" y=\"{}\" font-size=\"14\" fill=\"#18181b\">{}</text></g>\n",
+        x,
+        y,
+        w,
+        h,
+        svg_color(item_color(&item.content)),
+        x + 12.0,
+        y + 24.0,
+        escape_html(&item.content.display_name())
+    )
+}
+
+/// Head drawn at the `end` of an arrow coming from `start`
+fn svg_arrow_head(
+    start: (f32, f32),
+    end: (f32, f32),
+    thickness: f32,
+    head: ArrowHead,
+    color: &str,
+) -> String {
+    let size = (thickness * 4.0).max(8.0);
+    let (dx, dy) = (end.0 - start.0, end.1 - start.1);
+    let length = (dx * dx + dy * dy).sqrt().max(f32::EPSILON);
+    let (ux, uy) = (dx / length, dy / length);
+    // Points back along the arrow and to either side of it
+    let back = |d: f32| (end.0 - ux * d, end.1 - uy * d);
+    let side = |p: (f32, f32), d: f32| (p.0 - uy * d, p.1 + ux * d);
+
+    let polygon = |points: &[(f32, f32)]| {
+        let points: Vec<String> = points.iter().map(|(x, y)| format!("{},{}", x, y)).collect();
+        format!(
+            "<polygon points=\"{}\" fill=\"{}\"/>\n",
+            points.join(" "),
+            color
+        )
+    };
+    match head {
+        ArrowHead::None => String::new(),
+        ArrowHead::Arrow => {
+            let base = back(size);
+            polygon(&[end, side(base, size / 2.0), side(base, -size / 2.0)])
+        }
+        ArrowHead::Diamond => {
+            let middle = back(size / 2.0);
+            polygon(&[
+                end,
+                side(middle, size / 3.0),
+                back(size),
+                side(middle, -size / 3.0),
+            ])
+        }
+        ArrowHead::Circle => format!(
+            "<circle cx=\"{}\" cy=\"{}\" r=\"{}\" fill=\"{}\"/>\n",
+            end.0,
+            end.1,
+            size / 3.0,
+            color
+        ),
+    }
+}
+
+fn svg_color(color: Rgba<u8>) -> String {
+    let [r, g, b, _] = color.0;
+    format!("#{:02x}{:02x}{:02x}", r, g, b)
+}
@@ -0,0 +1,133 @@
+//! Batched item backgrounds
+//!
+//! Items without their own content element (text, links, PDFs and media)
+//! get a colored rounded background painted straight to the GPU. Rather than
+//! painting each item as it is found, the visible backgrounds are collected
+//! during prepaint, with consecutive items of the same fill and border color
+//! grouped, then painted one group at a time in paint.
+//!
+//! Groups only merge runs of neighbors in item order, so overlapping items
+//! of different colors still paint bottom to top. All background quads are
+//! emitted back to back, before any path, so GPUI's renderer submits them
+//! as a single instanced quad draw however many items the board has.
+//! Grouping by style also means a group's colors, corner radius and border
+//! width are worked out once instead of per item.
+
+use super::canvas::ContentTypeColors;
+use crate::constants::CULLING_MARGIN;
+use crate::profile_scope;
+use crate::types::{CanvasItem, ItemContent};
+use gpui::*;
+
+/// Background quads sharing a fill and border color
+#[derive(Debug, Clone, PartialEq)]
+pub struct QuadBatch {
+    pub background: Hsla,
+    pub border: Hsla,
+    pub bounds: Vec<Bounds<Pixels>>,
+}
+
+/// Whether an item gets a painted background. Items that draw their own
+/// content element (images, cards, shapes, arrows, text boxes, tables and
+/// charts) don't.
+pub fn has_painted_background(content: &ItemContent) -> bool {
+    !matches!(
+        content,
+        ItemContent::Image(_)
+            | ItemContent::Markdown { .. }
+            | ItemContent::Code { .. }
+            | ItemContent::TextBox { .. }
+            | ItemContent::Arrow { .. }
+            | ItemContent::Shape { .. }
+            | ItemContent::Table { .. }
+            | ItemContent::Chart { .. }
+    )
+}
+
+/// Collect the backgrounds of items visible in `bounds`, in item order,
+/// grouping runs of the same color
+pub fn batch_backgrounds(
+    bounds: Bounds<Pixels>,
+    items: &[CanvasItem],
+    canvas_offset: Point<Pixels>,
+    zoom: f32,
+    colors: &ContentTypeColors,
+) -> Vec<QuadBatch> {
+    profile_scope!("batch_backgrounds");
+
+    // Viewport bounds with margin for culling (prevents pop-in at edges)
+    let vp_left = f32::from(bounds.origin.x) - CULLING_MARGIN;
+    let vp_top = f32::from(bounds.origin.y) - CULLING_MARGIN;
+    let vp_right = f32::from(bounds.origin.x) + f32::from(bounds.size.width) + CULLING_MARGIN;
+    let vp_bottom = f32::from(bounds.origin.y) + f32::from(bounds.size.height) + CULLING_MARGIN;
+
+    let mut batches: Vec<QuadBatch> = Vec::new();
+    for item in items {
+        if !has_painted_background(&item.content) {
+            continue;
+        }
+
+        let item_x =
+            f32::from(bounds.origin.x) + item.position.0 * zoom + f32::from(canvas_offset.x);
+        let item_y =
+            f32::from(bounds.origin.y) + item.position.1 * zoom + f32::from(canvas_offset.y);
+        let item_w = item.size.0 * zoom;
+        let item_h = item.size.1 * zoom;
+
+        // Skip items completely outside the visible area
+        if item_x + item_w < vp_left
+            || item_x > vp_right
+            || item_y + item_h < vp_top
+            || item_y > vp_bottom
+        {
+            continue;
+        }
+
+        let item_bounds = Bounds {
+            origin: point(px(item_x), px(item_y)),
+            size: size(px(item_w), px(item_h)),
+        };
+
+        // Only the last group can take the item, or it would paint out of order
+        let background = colors.for_content(&item.content);
+        match batches
+            .last_mut()
+            .filter(|batch| batch.background == background && batch.border == colors.border)
+        {
+            Some(batch) => batch.bounds.push(item_bounds),
+            None => batches.push(QuadBatch {
+                background,
+                border: colors.border,
+                bounds: vec![item_bounds],
+            }),
+        }
+    }
+
+    batches
+}
+
+/// Paint batched backgrounds, one group after another in item order
+pub fn paint_batches(window: &mut Window, batches: &[QuadBatch], zoom: f32) {
+    profile_scope!("paint_batches");
+
+    let corner_radius = px(8.0 * zoom);
+    let border_width = px(2.0 * zoom);
+    for batch in batches {
+        for &bounds in &batch.bounds {
+            window.paint_quad(quad(
+                bounds,
+                corner_radius,
+                batch.background,
+                border_width,
+                batch.border,
+                Default::default(),
+            ));
+        }
+    }
+
+    #[cfg(feature = "profiling")]
+    if !batches.is_empty() {
+        let painted: usize = batches.iter().map(|batch| batch.bounds.len()).sum();
+        tracing::trace!(painted, batches = batches.len(), "Item backgrounds");
+    }
+}
@@ -2,33 +2,57 @@
 //!
 //! This module handles all canvas-related rendering including:
 //! - The infinite canvas background with grid
-//! - Item background shapes (painted via GPU)
+//! - Item background shapes (batched by color, see `backgrounds`)
 //! - Individual item content rendering
-//! - Item selection and resize handles
+//! - Item selection and resize handles (per item, or one group box for multi-selections)
 //!
 //! ## Performance Notes
 //!
 //! This is a hot path - rendering happens every frame. Key optimizations:
 //! - Early culling of off-screen items (viewport culling)
-//! - Batched GPU paint operations for backgrounds
+//! - Background quads collected in prepaint and painted in color batches
 //! - Minimal allocations in render loop
 //!
 //! Enable profiling with `cargo build --features profiling` to see timing.
 
+use super::backgrounds::{batch_backgrounds, paint_batches};
+use super::patterns::GridPattern;
+use super::pdf_controls::render_pdf_page_controls;
+use super::text_layout::TextLayout;
+use super::volume::{VOLUME_CONTROL_WIDTH, render_volume_control};
+use super::waveform::{
+    AUDIO_DRAG_BAR_HEIGHT, AudioWaveform, render_waveform_seek_target, render_waveform_strip,
+};
 use crate::app::Humanboard;
+use crate::audio_metadata::AudioMetadata;
+use crate::board_diff::{BoardDiff, DiffMark};
+use crate::canvas_background::CanvasBackground;
+use crate::canvas_find::{FIND_DIM_OPACITY, FindMatches};
+use crate::code_excerpt::line_anchor;
 use crate::constants::HEADER_HEIGHT;
+use crate::custom_themes::{ContentColor, content_palette};
 use crate::data::{DataSourceDelegate, VirtualScrollState};
+use crate::input::group_resize::{GROUP_HANDLE_SIZE, GroupHandle};
+use crate::input::guides::{AlignmentGuide, Bounds as ItemBounds, GuideAxis, union_bounds};
+use crate::input::resize::{RESIZE_HANDLE_SIZE, ResizeHandle};
 use crate::markdown_card::{render_collapsed_code, render_collapsed_markdown};
+use crate::playlist::Playlist;
 use crate::profile_scope;
 use crate::types::{CanvasItem, DataSource, ItemContent};
-use crate::webviews::{AudioWebView, VideoWebView, YouTubeWebView};
+use crate::webviews::{
+    AudioWebView, SoundCloudWebView, SpotifyWebView, VideoWebView, VimeoWebView, YouTubeWebView,
+};
+use crate::wiki_links::connector_endpoints;
 use gpui::prelude::FluentBuilder;
 use gpui::{PathBuilder, *};
 use gpui_component::chart::{BarChart, LineChart, PieChart};
 use gpui_component::input::{Input, InputState};
 use gpui_component::table::TableState;
+use gpui_component::webview::WebView;
 use gpui_component::{ActiveTheme as _, Icon, IconName, h_flex, v_flex};
 use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::Arc;
 
 /// Theme-aware colors for different content types
 #[derive(Clone, Copy)]
@@ -39,33 +63,60 @@ pub struct ContentTypeColors {
     pub pdf: Hsla,
     pub link: Hsla,
     pub youtube: Hsla,
+    pub vimeo: Hsla,
+    pub soundcloud: Hsla,
+    pub spotify: Hsla,
     pub unknown: Hsla,
     pub border: Hsla,
 }
 
 impl ContentTypeColors {
-    /// Create content type colors from the current theme
+    /// Create content type colors from the current theme: the `content.*`
+    /// colors its theme file sets, and its base colors for the rest
     pub fn from_theme(theme: &gpui_component::theme::Theme) -> Self {
-        let is_dark = theme.mode.is_dark();
-
-        // Use theme-aware colors with good contrast in both light and dark modes
-        // Colors are chosen to be visually distinct while respecting theme luminance
-        let base_saturation = if is_dark { 0.5 } else { 0.6 };
-        let base_lightness = if is_dark { 0.4 } else { 0.55 };
-        let alpha = 0.9;
+        let config = if theme.mode.is_dark() {
+            &theme.dark_theme
+        } else {
+            &theme.light_theme
+        };
+        let palette = content_palette(&config.name);
+        let color = |content: ContentColor, base: Hsla| {
+            palette
+                .get(&content)
+                .copied()
+                .unwrap_or_else(|| base.opacity(0.9))
+        };
 
         Self {
-            video: hsla(280.0 / 360.0, base_saturation, base_lightness, alpha),    // Purple for video
-            audio: hsla(320.0 / 360.0, base_saturation, base_lightness, alpha),    // Pink for audio
-            text: hsla(210.0 / 360.0, base_saturation, base_lightness, alpha),     // Blue for text
-            pdf: hsla(25.0 / 360.0, base_saturation + 0.1, base_lightness, alpha), // Orange for PDF
-            link: hsla(180.0 / 360.0, base_saturation, base_lightness, alpha),     // Cyan for links
-            youtube: hsla(0.0, 0.7, if is_dark { 0.45 } else { 0.5 }, alpha),      // Red for YouTube
+            video: color(ContentColor::Video, theme.magenta),
+            audio: color(ContentColor::Audio, theme.red_light),
+            text: color(ContentColor::Text, theme.blue),
+            pdf: color(ContentColor::Pdf, theme.yellow),
+            link: color(ContentColor::Link, theme.cyan),
+            youtube: color(ContentColor::YouTube, theme.red),
+            vimeo: color(ContentColor::Vimeo, theme.blue_light),
+            soundcloud: color(ContentColor::SoundCloud, theme.yellow_light),
+            spotify: color(ContentColor::Spotify, theme.green),
             unknown: theme.muted,
             border: theme.border.opacity(0.5),
         }
     }
 
+    /// Get the color of a content type the theme can set
+    pub fn color(&self, content: ContentColor) -> Hsla {
+        match content {
+            ContentColor::Video => self.video,
+            ContentColor::Audio => self.audio,
+            ContentColor::Text => self.text,
+            ContentColor::Pdf => self.pdf,
+            ContentColor::Link => self.link,
+            ContentColor::YouTube => self.youtube,
+            ContentColor::Vimeo => self.vimeo,
+            ContentColor::SoundCloud => self.soundcloud,
+            ContentColor::Spotify => self.spotify,
+        }
+    }
+
     /// Get color for a specific content type
     pub fn for_content(&self, content: &ItemContent) -> Hsla {
         match content {
@@ -73,25 +124,37 @@ impl ContentTypeColors {
             ItemContent::Audio(_) => self.audio,
             ItemContent::Text(_) => self.text,
             ItemContent::Pdf { .. } => self.pdf,
-            ItemContent::Link(_) => self.link,
+            ItemContent::Link(_) | ItemContent::Bookmark { .. } => self.link,
             ItemContent::YouTube(_) => self.youtube,
+            ItemContent::Vimeo(_) => self.vimeo,
+            ItemContent::SoundCloud(_) => self.soundcloud,
+            ItemContent::Spotify(_) => self.spotify,
             _ => self.unknown,
         }
     }
 }
 
-/// Render the main canvas with item backgrounds and connection lines
+/// Render the main canvas with its grid, item backgrounds and connection lines
 pub fn render_canvas(
     canvas_offset: Point<Pixels>,
     zoom: f32,
     items: Vec<CanvasItem>,
     colors: ContentTypeColors,
+    grid: Option<GridPattern>,
+    wiki_links: Vec<(u64, u64)>,
 ) -> impl IntoElement {
     canvas(
-        move |_bounds, _window, _cx| (),
-        move |bounds, _data, window, _cx| {
-            render_item_backgrounds(bounds, window, &items, canvas_offset, zoom, colors);
+        move |bounds, _window, _cx| {
+            let batches = batch_backgrounds(bounds, &items, canvas_offset, zoom, &colors);
+            (batches, items)
+        },
+        move |bounds, (batches, items), window, _cx| {
+            if let Some(grid) = grid {
+                grid.paint(window, bounds, canvas_offset);
+            }
+            paint_batches(window, &batches, zoom);
             render_connection_lines(bounds, window, &items, canvas_offset, zoom);
+            render_wiki_link_lines(bounds, window, &items, &wiki_links, canvas_offset, zoom);
         },
     )
     .absolute()
@@ -165,104 +228,111 @@ fn render_connection_lines(
     }
 }
 
-/// Paint item background shapes directly to GPU
-fn render_item_backgrounds(
+/// Draw wiki links between markdown cards, with an arrowhead at the linked
+/// card. Cards linking to each other share one line without arrowheads.
+fn render_wiki_link_lines(
     bounds: Bounds<Pixels>,
     window: &mut Window,
     items: &[CanvasItem],
+    links: &[(u64, u64)],
     canvas_offset: Point<Pixels>,
     zoom: f32,
-    colors: ContentTypeColors,
 ) {
-    profile_scope!("render_item_backgrounds");
-
-    // Early exit if no items
-    if items.is_empty() {
+    if links.is_empty() {
         return;
     }
+    let item_map: std::collections::HashMap<u64, ((f32, f32), (f32, f32))> = items
+        .iter()
+        .map(|item| (item.id, (item.position, item.size)))
+        .collect();
 
-    // Viewport bounds with margin for culling (prevents pop-in at edges)
-    use crate::constants::CULLING_MARGIN;
-    let vp_left = f32::from(bounds.origin.x) - CULLING_MARGIN;
-    let vp_top = f32::from(bounds.origin.y) - CULLING_MARGIN;
-    let vp_right = f32::from(bounds.origin.x) + f32::from(bounds.size.width) + CULLING_MARGIN;
-    let vp_bottom = f32::from(bounds.origin.y) + f32::from(bounds.size.height) + CULLING_MARGIN;
-
-    // Count items for profiling
-    #[cfg(feature = "profiling")]
-    let mut painted_count = 0usize;
-    #[cfg(feature = "profiling")]
-    let mut culled_count = 0usize;
+    // Line style: soft purple, apart from the blue chart lines
+    let line_color = hsla(270.0 / 360.0, 0.5, 0.6, 0.6);
+    let origin_x = f32::from(bounds.origin.x) + f32::from(canvas_offset.x);
+    let origin_y = f32::from(bounds.origin.y) + f32::from(canvas_offset.y);
+    let to_screen = |(x, y): (f32, f32)| (origin_x + x * zoom, origin_y + y * zoom);
 
-    for item in items {
-        // Skip items that render themselves (images, markdown cards, code files, shapes, arrows, textboxes, tables, charts)
-        if matches!(
-            &item.content,
-            ItemContent::Image(_)
-                | ItemContent::Markdown { .. }
-                | ItemContent::Code { .. }
-                | ItemContent::TextBox { .. }
-                | ItemContent::Arrow { .. }
-                | ItemContent::Shape { .. }
-                | ItemContent::Table { .. }
-                | ItemContent::Chart { .. }
-        ) {
+    for &(from, to) in links {
+        let mutual = links.contains(&(to, from));
+        if mutual && from > to {
             continue;
         }
-
-        // Calculate screen-space position for culling check
-        let item_x = f32::from(bounds.origin.x) + item.position.0 * zoom + f32::from(canvas_offset.x);
-        let item_y = f32::from(bounds.origin.y) + item.position.1 * zoom + f32::from(canvas_offset.y);
-        let item_w = item.size.0 * zoom;
-        let item_h = item.size.1 * zoom;
-
-        // VIEWPORT CULLING: Skip items completely outside visible area
-        if item_x + item_w < vp_left || item_x > vp_right ||
-           item_y + item_h < vp_top || item_y > vp_bottom {
-            #[cfg(feature = "profiling")]
-            {
-                culled_count += 1;
-            }
+        let (Some(&from_rect), Some(&to_rect)) = (item_map.get(&from), item_map.get(&to)) else {
             continue;
+        };
+        let (start, end) = connector_endpoints(from_rect, to_rect);
+        let (start_x, start_y) = to_screen(start);
+        let (end_x, end_y) = to_screen(end);
+
+        let mut path = PathBuilder::stroke(px(1.5 * zoom));
+        path.move_to(point(px(start_x), px(start_y)));
+        path.line_to(point(px(end_x), px(end_y)));
+        if let Ok(built_path) = path.build() {
+            window.paint_path(built_path, line_color);
         }
 
-        let item_bounds = Bounds {
-            origin: point(px(item_x), px(item_y)),
-            size: size(px(item_w), px(item_h)),
-        };
+        if mutual {
+            continue;
+        }
 
-        // Use theme-aware colors for content types
-        let bg_color = colors.for_content(&item.content);
-
-        window.paint_quad(quad(
-            item_bounds,
-            px(8.0 * zoom),
-            bg_color,
-            px(2.0 * zoom),
-            colors.border,
-            Default::default(),
-        ));
-
-        #[cfg(feature = "profiling")]
-        {
-            painted_count += 1;
+        // Arrowhead pointing into the linked card
+        let (dx, dy) = (end_x - start_x, end_y - start_y);
+        let length = (dx * dx + dy * dy).sqrt();
+        if length < f32::EPSILON {
+            continue;
+        }
+        let (ux, uy) = (dx / length, dy / length);
+        let size = 7.0 * zoom;
+        let (base_x, base_y) = (end_x - ux * size, end_y - uy * size);
+        let mut arrow = PathBuilder::fill();
+        arrow.move_to(point(px(end_x), px(end_y)));
+        arrow.line_to(point(px(base_x - uy * size / 2.0), px(base_y + ux * size / 2.0)));
+        arrow.line_to(point(px(base_x + uy * size / 2.0), px(base_y - ux * size / 2.0)));
+        arrow.close();
+        if let Ok(built_arrow) = arrow.build() {
+            window.paint_path(built_arrow, line_color);
         }
     }
+}
 
-    #[cfg(feature = "profiling")]
-    if painted_count > 0 || culled_count > 0 {
-        tracing::trace!(painted = painted_count, culled = culled_count, "Item backgrounds");
-    }
+/// Editor over an expanded markdown card while its document is edited
+fn render_markdown_card_editor(
+    input: &Entity<InputState>,
+    zoom: f32,
+    muted_fg: Hsla,
+    muted_bg: Hsla,
+) -> Div {
+    div()
+        .size_full()
+        .bg(muted_bg)
+        .rounded(px(6.0 * zoom))
+        .border(px(1.0 * zoom))
+        .border_color(muted_fg.opacity(0.4))
+        .p(px(8.0 * zoom))
+        .overflow_hidden()
+        .child(
+            Input::new(input)
+                .appearance(false)
+                .size_full()
+                .text_size(px(13.0 * zoom)),
+        )
 }
 
 /// Render a single canvas item based on its content type
-fn render_item_content(
+pub(super) fn render_item_content(
     item: &CanvasItem,
     zoom: f32,
     youtube_webviews: &HashMap<u64, YouTubeWebView>,
+    vimeo_webviews: &HashMap<u64, VimeoWebView>,
+    soundcloud_webviews: &HashMap<u64, SoundCloudWebView>,
+    spotify_webviews: &HashMap<u64, SpotifyWebView>,
     audio_webviews: &HashMap<u64, AudioWebView>,
     video_webviews: &HashMap<u64, VideoWebView>,
     data_sources: &HashMap<u64, DataSource>,
+    image_sources: &HashMap<u64, PathBuf>,
+    audio_waveforms: &HashMap<u64, AudioWaveform>,
+    audio_metadata: &HashMap<u64, Arc<AudioMetadata>>,
+    text_layout: Option<&TextLayout>,
     _table_scroll_states: &HashMap<u64, VirtualScrollState>,
     _table_states: &HashMap<u64, Entity<TableState<DataSourceDelegate>>>,
     editing_textbox_id: Option<u64>,
@@ -277,11 +347,15 @@ fn render_item_content(
     let corner_radius = px(8.0 * zoom);
 
     match &item.content {
-        ItemContent::Image(path) => div()
-            .size_full()
-            .overflow_hidden()
-            .rounded(corner_radius)
-            .child(img(path.clone()).size_full().object_fit(ObjectFit::Contain)),
+        ItemContent::Image(_) => match image_sources.get(&item.id) {
+            Some(source) => div()
+                .size_full()
+                .overflow_hidden()
+                .rounded(corner_radius)
+                .child(img(source.clone()).size_full().object_fit(ObjectFit::Contain)),
+            // Still waiting for its turn to load
+            None => div().size_full().rounded(corner_radius).bg(muted_bg),
+        },
 
         ItemContent::Pdf {
             thumbnail: Some(thumb_path),
@@ -392,20 +466,27 @@ fn render_item_content(
                     .child(
                         div()
                             .w_full()
-                            .h(px(24.0 * zoom))
+                            .h(px(AUDIO_DRAG_BAR_HEIGHT * zoom))
                             .bg(hsla(0.0, 0.0, 0.1, 1.0))
                             .border_b_1()
                             .border_color(hsla(0.0, 0.0, 0.2, 1.0))
                             .flex()
                             .items_center()
                             .justify_center()
-                            .child(
-                                div()
-                                    .text_size(px(12.0 * zoom))
-                                    .text_color(hsla(0.0, 0.0, 0.4, 1.0))
-                                    .child("≡"),
-                            ),
+                            .child(render_audio_header(
+                                audio_metadata.get(&item.id).map(|m| m.as_ref()),
+                                zoom,
+                            )),
                     )
+                    // Waveform between the drag bar and the player, once decoded
+                    .when_some(audio_waveforms.get(&item.id), |d, audio| {
+                        d.child(render_waveform_strip(
+                            audio,
+                            zoom,
+                            hsla(320.0 / 360.0, 0.7, 0.65, 1.0),
+                            hsla(0.0, 0.0, 0.45, 1.0),
+                        ))
+                    })
                     // WebView takes remaining space
                     .child(
                         div()
@@ -415,14 +496,39 @@ fn render_item_content(
                             .child(webview.webview_entity.clone()),
                     )
             } else {
-                // Placeholder while loading
+                // Placeholder while loading, with the album art and title
+                // once the tags have been read
+                let metadata = audio_metadata.get(&item.id);
                 div()
                     .size_full()
                     .bg(hsla(0.0, 0.0, 0.1, 1.0))
                     .rounded(corner_radius)
                     .flex()
+                    .flex_col()
+                    .gap(px(8.0 * zoom))
                     .items_center()
                     .justify_center()
+                    .when_some(metadata.and_then(|m| m.art.clone()), |d, art| {
+                        d.child(
+                            img(art)
+                                .size(px(48.0 * zoom))
+                                .rounded(px(4.0 * zoom))
+                                .object_fit(ObjectFit::Cover),
+                        )
+                    })
+                    .when_some(metadata.and_then(|m| m.label()), |d, label| {
+                        d.child(
+                            div()
+                                .max_w_full()
+                                .px(px(12.0 * zoom))
+                                .text_size(px(13.0 * zoom))
+                                .text_color(fg)
+                                .overflow_hidden()
+                                .text_ellipsis()
+                                .whitespace_nowrap()
+                                .child(label),
+                        )
+                    })
                     .child(
                         div()
                             .text_size(px(14.0 * zoom))
@@ -472,65 +578,90 @@ fn render_item_content(
                     .child(url.clone()),
             ),
 
-        ItemContent::YouTube(video_id) => {
-            // Render YouTube WebView if available, otherwise placeholder
-            if let Some(webview) = youtube_webviews.get(&item.id) {
-                // Use vertical flex with drag bar ABOVE the webview
-                // (overlays don't work on webviews - they render on top layer)
-                v_flex()
-                    .size_full()
-                    // Drag handle bar at top - OUTSIDE the webview
-                    .child(
-                        div()
-                            .w_full()
-                            .h(px(24.0 * zoom))
-                            .bg(hsla(0.0, 0.0, 0.15, 1.0))
-                            .border_b_1()
-                            .border_color(hsla(0.0, 0.0, 0.3, 1.0))
-                            .rounded_t(corner_radius)
-                            .flex()
-                            .items_center()
-                            .justify_center()
-                            .child(
-                                div()
-                                    .text_size(px(14.0 * zoom))
-                                    .text_color(hsla(0.0, 0.0, 0.5, 1.0))
-                                    .child("≡"),
-                            ),
-                    )
-                    // WebView takes remaining space
+        ItemContent::Bookmark {
+            title,
+            description,
+            site,
+            ..
+        } => div()
+            .size_full()
+            .p(px(12.0 * zoom))
+            .flex()
+            .flex_col()
+            .gap(px(6.0 * zoom))
+            .overflow_hidden()
+            .child(
+                h_flex()
+                    .gap(px(6.0 * zoom))
+                    .child(div().text_size(px(12.0 * zoom)).child("🔗"))
                     .child(
                         div()
-                            .flex_1()
-                            .w_full()
+                            .text_size(px(10.0 * zoom))
+                            .text_color(muted_fg)
                             .overflow_hidden()
-                            .rounded_b(corner_radius)
-                            .child(webview.webview().clone()),
-                    )
-            } else {
+                            .child(site.clone()),
+                    ),
+            )
+            .child(
                 div()
-                    .size_full()
-                    .flex()
-                    .items_center()
-                    .justify_center()
-                    .bg(muted_bg)
-                    .rounded(corner_radius)
-                    .child(
-                        v_flex()
-                            .items_center()
-                            .gap(px(8.0 * zoom))
-                            .child(div().text_size(px(48.0 * zoom)).child("▶️"))
-                            .child(
-                                div()
-                                    .text_size(px(12.0 * zoom))
-                                    .text_color(muted_fg)
-                                    .child(format!("YouTube: {}", video_id)),
-                            ),
-                    )
-            }
-        }
+                    .text_size(px(14.0 * zoom))
+                    .text_color(fg)
+                    .font_weight(FontWeight::SEMIBOLD)
+                    .line_clamp(2)
+                    .child(title.clone()),
+            )
+            .when(!description.is_empty(), |d| {
+                d.child(
+                    div()
+                        .text_size(px(11.0 * zoom))
+                        .text_color(muted_fg)
+                        .line_clamp(3)
+                        .child(description.clone()),
+                )
+            }),
+
+        ItemContent::YouTube(video_id) => render_embed_player(
+            youtube_webviews.get(&item.id).map(|webview| webview.webview()),
+            format!("YouTube: {}", video_id),
+            zoom,
+            corner_radius,
+            muted_fg,
+            muted_bg,
+        ),
+
+        ItemContent::Vimeo(video_id) => render_embed_player(
+            vimeo_webviews.get(&item.id).map(|webview| webview.webview()),
+            format!("Vimeo: {}", video_id),
+            zoom,
+            corner_radius,
+            muted_fg,
+            muted_bg,
+        ),
+
+        ItemContent::SoundCloud(_) => render_embed_player(
+            soundcloud_webviews.get(&item.id).map(|webview| webview.webview()),
+            item.content.display_name(),
+            zoom,
+            corner_radius,
+            muted_fg,
+            muted_bg,
+        ),
+
+        ItemContent::Spotify(_) => render_embed_player(
+            spotify_webviews.get(&item.id).map(|webview| webview.webview()),
+            item.content.display_name(),
+            zoom,
+            corner_radius,
+            muted_fg,
+            muted_bg,
+        ),
 
         ItemContent::Markdown { title, content, .. } => {
+            // An expanded card being edited in place
+            if let Some(input) = textbox_input.filter(|_| editing_textbox_id == Some(item.id)) {
+                return render_markdown_card_editor(input, zoom, muted_fg, muted_bg);
+            }
+
             // Use theme colors for markdown cards
             let popover_bg = hsla(220.0 / 360.0, 0.15, 0.18, 1.0); // Subtle dark bg
             let border = hsla(240.0 / 360.0, 0.2, 0.35, 1.0); // Muted border
@@ -552,7 +683,11 @@ fn render_item_content(
             )
         }
 
-        ItemContent::Code { path, language } => {
+        ItemContent::Code {
+            path,
+            language,
+            lines,
+        } => {
             // Use theme colors for code file cards
             let popover_bg = hsla(220.0 / 360.0, 0.15, 0.14, 1.0); // Darker bg for code
             let border = hsla(200.0 / 360.0, 0.3, 0.35, 1.0); // Cyan-ish border
@@ -567,9 +702,14 @@ fn render_item_content(
                 .file_name()
                 .and_then(|n| n.to_str())
                 .unwrap_or("Unknown");
+            // Cards of a line range name it after the file
+            let label = match lines {
+                Some(lines) => format!("{}{}", filename, line_anchor(*lines)),
+                None => filename.to_string(),
+            };
 
             render_collapsed_code(
-                filename,
+                &label,
                 language,
                 zoom,
                 popover_bg,
@@ -624,7 +764,12 @@ fn render_item_content(
                         }))
                 }
             } else {
-                // Normal display mode - just text, no background
+                // Normal display mode - just text, no background. Lines come
+                // from the layout cache when the caller has one.
+                let layout = match text_layout {
+                    Some(layout) => std::borrow::Cow::Borrowed(layout),
+                    None => std::borrow::Cow::Owned(TextLayout::new(text, *font_size, zoom)),
+                };
                 div()
                     .size_full()
                     .rounded(px(4.0 * zoom))
@@ -632,15 +777,11 @@ fn render_item_content(
                     .overflow_hidden()
                     .flex()
                     .flex_col()
-                    .children(text.lines().map(|line| {
+                    .children(layout.lines.iter().map(|line| {
                         div()
-                            .text_size(px(scaled_font))
+                            .text_size(px(layout.font_size))
                             .text_color(text_color)
-                            .child(if line.is_empty() {
-                                " ".to_string() // Preserve empty lines
-                            } else {
-                                line.to_string()
-                            })
+                            .child(line.clone())
                     }))
             }
         }
@@ -748,85 +889,15 @@ fn render_item_content(
                 .when_some(fill, |d, c| d.bg(c))
         }
 
-        ItemContent::Table { data_source_id, .. } => {
-            // Render table as a compact file card (like code files)
-            // Colors tailored for data/CSV files
-            let bg = hsla(220.0 / 360.0, 0.15, 0.14, 1.0); // Darker bg like code
-            let border = hsla(140.0 / 360.0, 0.3, 0.35, 1.0); // Green-ish border for data
-            let hover_bg = hsla(220.0 / 360.0, 0.15, 0.18, 1.0);
-            let hover_border = hsla(140.0 / 360.0, 0.5, 0.5, 1.0); // Brighter green on hover
-            let icon_color = hsla(140.0 / 360.0, 0.6, 0.5, 1.0); // Green icon for data
-            let text_color = hsla(0.0, 0.0, 0.85, 1.0);
-            let badge_bg = hsla(140.0 / 360.0, 0.4, 0.25, 1.0); // Green badge bg
-            let badge_text = hsla(140.0 / 360.0, 0.6, 0.8, 1.0); // Green badge text
-
-            if let Some(data_source) = data_sources.get(data_source_id) {
-                let row_count = data_source.rows.len();
-                let col_count = data_source.column_count();
-                let filename = data_source.file_path()
-                    .and_then(|p| p.file_name())
-                    .and_then(|n| n.to_str())
-                    .unwrap_or("data.csv");
-
-                // Compact file card style (like code files)
-                div()
-                    .size_full()
-                    .bg(bg)
-                    .rounded(px(6.0 * zoom))
-                    .border(px(1.0 * zoom))
-                    .border_color(border)
-                    .cursor(CursorStyle::PointingHand)
-                    .hover(move |s| s.bg(hover_bg).border_color(hover_border))
-                    .flex()
-                    .items_center()
-                    .gap(px(8.0 * zoom))
-                    .px(px(12.0 * zoom))
-                    .child(
-                        Icon::new(IconName::LayoutDashboard)
-                            .size(px(16.0 * zoom))
-                            .text_color(icon_color),
-                    )
-                    .child(
-                        div()
-                            .flex_1()
-                            .text_size(px(12.0 * zoom))
-                            .font_weight(FontWeight::MEDIUM)
-                            .text_color(text_color)
-                            .overflow_hidden()
-                            .whitespace_nowrap()
-                            .child(filename.to_string()),
-                    )
-                    .child(
-                        // Stats badge showing rows × cols
-                        div()
-                            .px(px(6.0 * zoom))
-                            .py(px(2.0 * zoom))
-                            .bg(badge_bg)
-                            .rounded(px(3.0 * zoom))
-                            .text_size(px(9.0 * zoom))
-                            .font_weight(FontWeight::MEDIUM)
-                            .text_color(badge_text)
-                            .child(format!("{}×{}", row_count, col_count)),
-                    )
-            } else {
-                // Data source not found - show placeholder
-                div()
-                    .size_full()
-                    .bg(muted_bg)
-                    .rounded(corner_radius)
-                    .border_1()
-                    .border_color(muted_fg.opacity(0.3))
-                    .flex()
-                    .items_center()
-                    .justify_center()
-                    .child(
-                        div()
-                            .text_size(px(14.0 * zoom))
-                            .text_color(muted_fg)
-                            .child("Data source not found")
-                    )
-            }
-        }
+        ItemContent::Table { data_source_id, .. } => render_table_card(
+            data_sources
+                .get(data_source_id)
+                .map(TableCard::from_source)
+                .as_ref(),
+            zoom,
+            muted_fg,
+            muted_bg,
+        ),
 
         ItemContent::Chart { data_source_id, config, .. } => {
             let border_color = muted_fg.opacity(0.3);
@@ -1174,6 +1245,176 @@ fn render_item_content(
     }
 }
 
+/// What a table item's file card shows
+#[derive(Debug, Clone, PartialEq, Hash)]
+pub struct TableCard {
+    pub filename: String,
+    pub row_count: usize,
+    pub col_count: usize,
+}
+
+impl TableCard {
+    pub fn from_source(data_source: &DataSource) -> Self {
+        Self {
+            filename: data_source
+                .file_path()
+                .and_then(|p| p.file_name())
+                .and_then(|n| n.to_str())
+                .unwrap_or("data.csv")
+                .to_string(),
+            row_count: data_source.rows.len(),
+            col_count: data_source.column_count(),
+        }
+    }
+}
+
+/// Render an embedded player webview below a drag bar, or a placeholder
+/// labelled with the item while the webview is still being created
+fn render_embed_player(
+    webview: Option<Entity<WebView>>,
+    label: String,
+    zoom: f32,
+    corner_radius: Pixels,
+    muted_fg: Hsla,
+    muted_bg: Hsla,
+) -> Div {
+    if let Some(webview) = webview {
+        // Use vertical flex with drag bar ABOVE the webview
+        // (overlays don't work on webviews - they render on top layer)
+        v_flex()
+            .size_full()
+            // Drag handle bar at top - OUTSIDE the webview
+            .child(
+                div()
+                    .w_full()
+                    .h(px(24.0 * zoom))
+                    .bg(hsla(0.0, 0.0, 0.15, 1.0))
+                    .border_b_1()
+                    .border_color(hsla(0.0, 0.0, 0.3, 1.0))
+                    .rounded_t(corner_radius)
+                    .flex()
+                    .items_center()
+                    .justify_center()
+                    .child(
+                        div()
+                            .text_size(px(14.0 * zoom))
+                            .text_color(hsla(0.0, 0.0, 0.5, 1.0))
+                            .child("≡"),
+                    ),
+            )
+            // WebView takes remaining space
+            .child(
+                div()
+                    .flex_1()
+                    .w_full()
+                    .overflow_hidden()
+                    .rounded_b(corner_radius)
+                    .child(webview),
+            )
+    } else {
+        div()
+            .size_full()
+            .flex()
+            .items_center()
+            .justify_center()
+            .bg(muted_bg)
+            .rounded(corner_radius)
+            .child(
+                v_flex()
+                    .items_center()
+                    .gap(px(8.0 * zoom))
+                    .child(div().text_size(px(48.0 * zoom)).child("▶️"))
+                    .child(
+                        div()
+                            .text_size(px(12.0 * zoom))
+                            .text_color(muted_fg)
+                            .child(label),
+                    ),
+            )
+    }
+}
+
+/// Render a table item as a compact file card, or a placeholder if its data
+/// source is gone
+pub(super) fn render_table_card(
+    card: Option<&TableCard>,
+    zoom: f32,
+    muted_fg: Hsla,
+    muted_bg: Hsla,
+) -> Div {
+    // Render table as a compact file card (like code files)
+    // Colors tailored for data/CSV files
+    let bg = hsla(220.0 / 360.0, 0.15, 0.14, 1.0); // Darker bg like code
+    let border = hsla(140.0 / 360.0, 0.3, 0.35, 1.0); // Green-ish border for data
+    let hover_bg = hsla(220.0 / 360.0, 0.15, 0.18, 1.0);
+    let hover_border = hsla(140.0 / 360.0, 0.5, 0.5, 1.0); // Brighter green on hover
+    let icon_color = hsla(140.0 / 360.0, 0.6, 0.5, 1.0); // Green icon for data
+    let text_color = hsla(0.0, 0.0, 0.85, 1.0);
+    let badge_bg = hsla(140.0 / 360.0, 0.4, 0.25, 1.0); // Green badge bg
+    let badge_text = hsla(140.0 / 360.0, 0.6, 0.8, 1.0); // Green badge text
+
+    if let Some(card) = card {
+
+        // Compact file card style (like code files)
+        div()
+            .size_full()
+            .bg(bg)
+            .rounded(px(6.0 * zoom))
+            .border(px(1.0 * zoom))
+            .border_color(border)
+            .cursor(CursorStyle::PointingHand)
+            .hover(move |s| s.bg(hover_bg).border_color(hover_border))
+            .flex()
+            .items_center()
+            .gap(px(8.0 * zoom))
+            .px(px(12.0 * zoom))
+            .child(
+                Icon::new(IconName::LayoutDashboard)
+                    .size(px(16.0 * zoom))
+                    .text_color(icon_color),
+            )
+            .child(
+                div()
+                    .flex_1()
+                    .text_size(px(12.0 * zoom))
+                    .font_weight(FontWeight::MEDIUM)
+                    .text_color(text_color)
+                    .overflow_hidden()
+                    .whitespace_nowrap()
+                    .child(card.filename.clone()),
+            )
+            .child(
+                // Stats badge showing rows × cols
+                div()
+                    .px(px(6.0 * zoom))
+                    .py(px(2.0 * zoom))
+                    .bg(badge_bg)
+                    .rounded(px(3.0 * zoom))
+                    .text_size(px(9.0 * zoom))
+                    .font_weight(FontWeight::MEDIUM)
+                    .text_color(badge_text)
+                    .child(format!("{}×{}", card.row_count, card.col_count)),
+            )
+    } else {
+        // Data source not found - show placeholder
+        div()
+            .size_full()
+            .bg(muted_bg)
+            .rounded(px(8.0 * zoom))
+            .border_1()
+            .border_color(muted_fg.opacity(0.3))
+            .flex()
+            .items_center()
+            .justify_center()
+            .child(
+                div()
+                    .text_size(px(14.0 * zoom))
+                    .text_color(muted_fg)
+                    .child("Data source not found")
+            )
+    }
+}
+
 /// Parse a hex color string like "#ffffff" into an Hsla color
 fn parse_hex_color(hex: &str) -> Option<Hsla> {
     let hex = hex.trim_start_matches('#');
@@ -1216,7 +1457,8 @@ fn parse_hex_color(hex: &str) -> Option<Hsla> {
 ///
 /// This is a key hot path - called every frame for all visible items.
 /// Performance optimizations:
-/// - Viewport culling: Only renders items within the visible viewport
+/// - Viewport culling: Callers pass only the items the spatial index finds
+///   near the viewport (see `Board::items_in_viewport`); anything else is skipped
 /// - Pre-computed bounds: Calculates screen positions once per item
 /// - Early exit for items outside viewport
 pub fn render_items(
@@ -1224,10 +1466,24 @@ pub fn render_items(
     canvas_offset: Point<Pixels>,
     zoom: f32,
     selected_items: &std::collections::HashSet<u64>,
+    missing_files: &std::collections::HashSet<u64>,
+    updated_cards: &HashMap<u64, f32>,
+    search_highlight: Option<(u64, f32)>,
+    find: &FindMatches,
     youtube_webviews: &HashMap<u64, YouTubeWebView>,
+    vimeo_webviews: &HashMap<u64, VimeoWebView>,
+    soundcloud_webviews: &HashMap<u64, SoundCloudWebView>,
+    spotify_webviews: &HashMap<u64, SpotifyWebView>,
     audio_webviews: &HashMap<u64, AudioWebView>,
     video_webviews: &HashMap<u64, VideoWebView>,
     data_sources: &HashMap<u64, DataSource>,
+    image_sources: &HashMap<u64, PathBuf>,
+    audio_waveforms: &HashMap<u64, AudioWaveform>,
+    audio_metadata: &HashMap<u64, Arc<AudioMetadata>>,
+    pdf_page_counts: &HashMap<u64, usize>,
+    playlist: Option<&Playlist>,
+    item_views: &HashMap<u64, AnyView>,
+    text_layouts: &HashMap<u64, Arc<TextLayout>>,
     table_scroll_states: &HashMap<u64, VirtualScrollState>,
     table_states: &HashMap<u64, Entity<TableState<DataSourceDelegate>>>,
     editing_textbox_id: Option<u64>,
@@ -1254,6 +1510,7 @@ pub fn render_items(
     let muted_bg = cx.theme().muted;
     let danger = cx.theme().danger;
     let primary = cx.theme().primary;
+    let success = cx.theme().success;
 
     // Pre-allocate with estimated visible items to reduce allocations
     let mut result = Vec::with_capacity(items.len().min(100));
@@ -1275,10 +1532,56 @@ pub fn render_items(
         let is_editing_this = editing_textbox_id == Some(item.id);
         // Don't show selection border while editing textbox (it has its own editing border)
         let show_selection = is_selected && !is_editing_this;
+        // Multi-selections are resized from the group box instead
+        let show_resize_handle = selected_items.len() == 1 && !item.locked;
+        let is_locked = item.locked;
+        // Arrows are only resized from their end
+        let resize_handles: &[ResizeHandle] = if matches!(item.content, ItemContent::Arrow { .. }) {
+            &[ResizeHandle::BottomRight]
+        } else {
+            &ResizeHandle::ALL
+        };
+        let handle_fill = cx.theme().background;
 
         // Check if this is a table item (for chart creation button)
         let is_table = matches!(&item.content, ItemContent::Table { .. });
         let item_id = item.id;
+        let is_missing = missing_files.contains(&item.id);
+        // Cards refreshed from their changed file flash for a moment
+        let update_flash = updated_cards.get(&item.id).copied();
+        // The card chosen from the search results is ringed for a moment
+        let search_ring = search_highlight
+            .filter(|(id, _)| *id == item.id)
+            .map(|(_, opacity)| opacity);
+        // While finding, matches are outlined and everything else dimmed
+        let find_match = find
+            .is_active()
+            .then(|| find.contains(item.id))
+            .map(|matched| (matched, find.current_id() == Some(item.id)));
+        // Audio cards with a loaded player can be scrubbed from their waveform
+        let has_seekable_waveform =
+            audio_waveforms.contains_key(&item.id) && audio_webviews.contains_key(&item.id);
+        // Media cards with a loaded player get volume controls in their drag bar
+        let has_volume_control = match &item.content {
+            ItemContent::Audio(_) => audio_webviews.contains_key(&item.id),
+            ItemContent::Video(_) => video_webviews.contains_key(&item.id),
+            ItemContent::YouTube(_) => youtube_webviews.contains_key(&item.id),
+            ItemContent::Vimeo(_) => vimeo_webviews.contains_key(&item.id),
+            ItemContent::SoundCloud(_) => soundcloud_webviews.contains_key(&item.id),
+            _ => false,
+        };
+        // PDFs with more than one page can be paged through from their card
+        let pdf_pager = match &item.content {
+            ItemContent::Pdf { page, .. } => pdf_page_counts
+                .get(&item.id)
+                .filter(|&&page_count| page_count > 1)
+                .map(|&page_count| (*page, page_count)),
+            _ => None,
+        };
+        // The playing playlist item is outlined and tagged with its place
+        let playlist_progress = playlist
+            .filter(|playlist| playlist.current() == item.id)
+            .map(|playlist| playlist.progress());
 
         result.push(
             div()
@@ -1287,24 +1590,71 @@ pub fn render_items(
                 .top(px(y))
                 .w(px(w))
                 .h(px(h))
-                .child(render_item_content(
-                    item,
-                    zoom,
-                    youtube_webviews,
-                    audio_webviews,
-                    video_webviews,
-                    data_sources,
-                    table_scroll_states,
-                    table_states,
-                    editing_textbox_id,
-                    textbox_input,
-                    _editing_table_cell,
-                    table_cell_input,
-                    fg,
-                    muted_fg,
-                    muted_bg,
-                    danger,
-                ))
+                .child(match item_views.get(&item.id) {
+                    Some(view) => div().size_full().child(view.clone()),
+                    None => render_item_content(
+                        item,
+                        zoom,
+                        youtube_webviews,
+                        vimeo_webviews,
+                        soundcloud_webviews,
+                        spotify_webviews,
+                        audio_webviews,
+                        video_webviews,
+                        data_sources,
+                        image_sources,
+                        audio_waveforms,
+                        audio_metadata,
+                        text_layouts.get(&item.id).map(|layout| layout.as_ref()),
+                        table_scroll_states,
+                        table_states,
+                        editing_textbox_id,
+                        textbox_input,
+                        _editing_table_cell,
+                        table_cell_input,
+                        fg,
+                        muted_fg,
+                        muted_bg,
+                        danger,
+                    ),
+                })
+                .when(has_seekable_waveform, |d| {
+                    d.child(render_waveform_seek_target(item_id, zoom, cx))
+                })
+                .when(has_volume_control, |d| {
+                    d.child(render_volume_control(
+                        item_id,
+                        item.volume.unwrap_or_default(),
+                        zoom,
+                        cx,
+                    ))
+                })
+                .when_some(pdf_pager, |d, (page, page_count)| {
+                    d.child(render_pdf_page_controls(
+                        item_id, page, page_count, zoom, cx,
+                    ))
+                })
+                // Broken-link badge for items whose file is gone
+                .when(is_missing, |d| {
+                    d.child(render_missing_file_badge(item_id, zoom, cx))
+                })
+                .when_some(update_flash, |d, opacity| {
+                    d.child(render_update_flash(opacity, zoom, cx))
+                })
+                .when_some(search_ring, |d, opacity| {
+                    d.child(render_search_highlight(opacity, zoom, cx))
+                })
+                .when_some(find_match, |d, (matched, current)| {
+                    d.child(render_find_match(matched, current, zoom, cx))
+                })
+                .when_some(playlist_progress, |d, progress| {
+                    d.when(!show_selection, |d| {
+                        d.border_2()
+                            .border_color(success)
+                            .rounded(px(8.0 * zoom))
+                    })
+                    .child(render_now_playing_badge(progress, zoom, cx))
+                })
                 // NOTE: Table cell editing temporarily disabled
                 // The double-click to edit feature was causing focus issues.
                 // To edit table data, open the table in the preview panel.
@@ -1315,18 +1665,12 @@ pub fn render_items(
                         .border_2()
                         .border_color(primary)
                         .rounded(px(8.0 * zoom))
-                        .child(
-                            // Resize handle - small corner indicator
-                            div()
-                                .absolute()
-                                .right(px(-2.0))
-                                .bottom(px(-2.0))
-                                .w(px(10.0 * zoom))
-                                .h(px(10.0 * zoom))
-                                .bg(primary)
-                                .rounded(px(2.0 * zoom))
-                                .cursor(CursorStyle::ResizeUpLeftDownRight),
-                        )
+                        .when(show_resize_handle, |d| {
+                            d.children(resize_handles.iter().map(|&handle| {
+                                render_resize_handle(handle, (w, h), primary, handle_fill)
+                            }))
+                        })
+                        .when(is_locked, |d| d.child(render_locked_badge(zoom, cx)))
                 }),
         );
 
@@ -1432,21 +1776,215 @@ pub fn render_items(
     result
 }
 
+/// Render the "Missing file" badge that starts the locate/relink flow
+fn render_missing_file_badge(item_id: u64, zoom: f32, cx: &Context<Humanboard>) -> Stateful<Div> {
+    let danger = cx.theme().danger;
+    let danger_fg = cx.theme().danger_foreground;
+
+    div()
+        .id(ElementId::Name(format!("locate-file-btn-{}", item_id).into()))
+        .absolute()
+        .top(px(6.0 * zoom))
+        .left(px(6.0 * zoom))
+        .px(px(8.0 * zoom))
+        .py(px(4.0 * zoom))
+        .bg(danger)
+        .rounded(px(6.0 * zoom))
+        .cursor_pointer()
+        .flex()
+        .flex_row()
+        .items_center()
+        .gap(px(4.0 * zoom))
+        .shadow_md()
+        .hover(|s| s.opacity(0.85))
+        .on_mouse_down(MouseButton::Left, |_, _, cx| {
+            cx.stop_propagation();
+        })
+        .on_click(cx.listener(move |this, _, _, cx| {
+            this.locate_missing_file(item_id, cx);
+        }))
+        .child(
+            Icon::new(IconName::TriangleAlert)
+                .size(px(12.0 * zoom))
+                .text_color(danger_fg),
+        )
+        .child(
+            div()
+                .text_size(px(11.0 * zoom))
+                .font_weight(FontWeight::MEDIUM)
+                .text_color(danger_fg)
+                .child("Missing file · Locate…"),
+        )
+}
+
+/// Border and "Updated" tag over a card refreshed from its changed file,
+/// fading out with `opacity`
+fn render_update_flash(opacity: f32, zoom: f32, cx: &Context<Humanboard>) -> Div {
+    let primary = cx.theme().primary;
+
+    div()
+        .absolute()
+        .inset_0()
+        .border_2()
+        .border_color(primary.opacity(opacity))
+        .rounded(px(8.0 * zoom))
+        .child(
+            div()
+                .absolute()
+                .top(px(-8.0 * zoom))
+                .right(px(6.0 * zoom))
+                .px(px(6.0 * zoom))
+                .py(px(1.0 * zoom))
+                .bg(primary.opacity(opacity))
+                .rounded(px(4.0 * zoom))
+                .text_size(px(9.0 * zoom))
+                .font_weight(FontWeight::MEDIUM)
+                .text_color(cx.theme().primary_foreground.opacity(opacity))
+                .child("Updated"),
+        )
+}
+
+/// Ring around the card chosen from the search results, fading out with
+/// `opacity`
+fn render_search_highlight(opacity: f32, zoom: f32, cx: &Context<Humanboard>) -> Div {
+    let warning = cx.theme().warning;
+
+    div()
+        .absolute()
+        .inset_0()
+        .border(px(3.0 * zoom))
+        .border_color(warning.opacity(opacity))
+        .rounded(px(10.0 * zoom))
+        .bg(warning.opacity(0.12 * opacity))
+}
+
+/// Outline over an item matching the find bar's query, thicker on the
+/// current match, or a veil dimming an item that doesn't match
+fn render_find_match(
+    matched: bool,
+    current: bool,
+    zoom: f32,
+    cx: &Context<Humanboard>,
+) -> Div {
+    let overlay = div().absolute().inset_0().rounded(px(8.0 * zoom));
+    if !matched {
+        return overlay.bg(cx.theme().background.opacity(FIND_DIM_OPACITY));
+    }
+    let width = if current { 3.0 } else { 2.0 };
+    overlay
+        .border(px(width * zoom))
+        .border_color(cx.theme().warning)
+}
+
+/// Small "Locked" tag in the top-right corner of a selected locked item
+fn render_locked_badge(zoom: f32, cx: &Context<Humanboard>) -> Div {
+    div()
+        .absolute()
+        .top(px(6.0 * zoom))
+        .right(px(6.0 * zoom))
+        .px(px(6.0 * zoom))
+        .py(px(2.0 * zoom))
+        .bg(cx.theme().secondary)
+        .rounded(px(4.0 * zoom))
+        .text_size(px(10.0 * zoom))
+        .font_weight(FontWeight::MEDIUM)
+        .text_color(cx.theme().muted_foreground)
+        .child("Locked")
+}
+
+/// Drag bar contents of an audio card: album art and title from the file's
+/// tags, or a grip until they have been read
+fn render_audio_header(metadata: Option<&AudioMetadata>, zoom: f32) -> Div {
+    let art = metadata.and_then(|m| m.art.clone());
+    let label = metadata.and_then(|m| m.label());
+    if art.is_none() && label.is_none() {
+        return div()
+            .text_size(px(12.0 * zoom))
+            .text_color(hsla(0.0, 0.0, 0.4, 1.0))
+            .child("≡");
+    }
+    h_flex()
+        .w_full()
+        .pl(px(6.0 * zoom))
+        .pr(px(VOLUME_CONTROL_WIDTH * zoom))
+        .gap(px(6.0 * zoom))
+        .when_some(art, |d, art| {
+            d.child(
+                img(art)
+                    .flex_none()
+                    .size(px(16.0 * zoom))
+                    .rounded(px(2.0 * zoom))
+                    .object_fit(ObjectFit::Cover),
+            )
+        })
+        .when_some(label, |d, label| {
+            d.child(
+                div()
+                    .flex_1()
+                    .min_w_0()
+                    .text_size(px(11.0 * zoom))
+                    .text_color(hsla(0.0, 0.0, 0.7, 1.0))
+                    .overflow_hidden()
+                    .text_ellipsis()
+                    .whitespace_nowrap()
+                    .child(label),
+            )
+        })
+}
+
+/// "▶ 2/5" tag above the playing playlist item
+fn render_now_playing_badge(
+    (position, len): (usize, usize),
+    zoom: f32,
+    cx: &Context<Humanboard>,
+) -> Div {
+    div()
+        .absolute()
+        .top(px(-20.0 * zoom))
+        .left_0()
+        .px(px(6.0 * zoom))
+        .py(px(2.0 * zoom))
+        .bg(cx.theme().success)
+        .rounded(px(4.0 * zoom))
+        .text_size(px(10.0 * zoom))
+        .font_weight(FontWeight::MEDIUM)
+        .text_color(cx.theme().success_foreground)
+        .child(format!("▶ {}/{}", position, len))
+}
+
 /// Render the canvas area container
 ///
 /// This is the main entry point for canvas rendering. It composes:
 /// 1. Background canvas with item backgrounds (GPU painted)
 /// 2. Individual item content elements
-/// 3. Selection overlays (marquee, drawing preview)
+/// 3. Selection overlays (group box, marquee, lasso, drawing preview, alignment guides)
 pub fn render_canvas_area(
     canvas_offset: Point<Pixels>,
     zoom: f32,
+    background: &CanvasBackground,
     items: &[CanvasItem],
+    visible_items: &[CanvasItem],
     selected_items: &std::collections::HashSet<u64>,
+    missing_files: &std::collections::HashSet<u64>,
+    updated_cards: &HashMap<u64, f32>,
+    search_highlight: Option<(u64, f32)>,
+    find: &FindMatches,
+    comparison: Option<&BoardDiff>,
     youtube_webviews: &HashMap<u64, YouTubeWebView>,
+    vimeo_webviews: &HashMap<u64, VimeoWebView>,
+    soundcloud_webviews: &HashMap<u64, SoundCloudWebView>,
+    spotify_webviews: &HashMap<u64, SpotifyWebView>,
     audio_webviews: &HashMap<u64, AudioWebView>,
     video_webviews: &HashMap<u64, VideoWebView>,
     data_sources: &HashMap<u64, DataSource>,
+    image_sources: &HashMap<u64, PathBuf>,
+    audio_waveforms: &HashMap<u64, AudioWaveform>,
+    audio_metadata: &HashMap<u64, Arc<AudioMetadata>>,
+    pdf_page_counts: &HashMap<u64, usize>,
+    playlist: Option<&Playlist>,
+    item_views: &HashMap<u64, AnyView>,
+    text_layouts: &HashMap<u64, Arc<TextLayout>>,
+    wiki_links: &[(u64, u64)],
     table_scroll_states: &HashMap<u64, VirtualScrollState>,
     table_states: &HashMap<u64, Entity<TableState<DataSourceDelegate>>>,
     editing_textbox_id: Option<u64>,
@@ -1454,7 +1992,9 @@ pub fn render_canvas_area(
     _editing_table_cell: Option<(u64, usize, usize)>,
     table_cell_input: Option<&Entity<InputState>>,
     marquee: Option<(Point<Pixels>, Point<Pixels>)>,
+    lasso: &[Point<Pixels>],
     drawing_preview: Option<(Point<Pixels>, Point<Pixels>, crate::types::ToolType)>,
+    alignment_guides: &[AlignmentGuide],
     viewport_size: Size<Pixels>,
     cx: &Context<Humanboard>,
 ) -> Div {
@@ -1464,22 +2004,67 @@ pub fn render_canvas_area(
     let primary = cx.theme().primary;
     let fg = cx.theme().foreground;
     let content_colors = ContentTypeColors::from_theme(cx.theme());
+    let grid = GridPattern::for_background(
+        background,
+        crate::settings::app_settings().grid_size,
+        zoom,
+        cx.theme().border,
+    );
+    let canvas_bg = match background {
+        CanvasBackground::Color { color } => parse_hex_color(color).unwrap_or(bg),
+        _ => bg,
+    };
 
     div()
         .size_full()
-        .bg(bg)
+        .bg(canvas_bg)
         .overflow_hidden()
         .relative()
-        .child(render_canvas(canvas_offset, zoom, items.to_vec(), content_colors))
+        .when_some(
+            match background {
+                CanvasBackground::Image { path } => Some(path.clone()),
+                _ => None,
+            },
+            |d, path| {
+                d.child(
+                    img(path)
+                        .absolute()
+                        .size_full()
+                        .object_fit(ObjectFit::Cover),
+                )
+            },
+        )
+        .child(render_canvas(
+            canvas_offset,
+            zoom,
+            items.to_vec(),
+            content_colors,
+            grid,
+            wiki_links.to_vec(),
+        ))
         .children(render_items(
-            items,
+            visible_items,
             canvas_offset,
             zoom,
             selected_items,
+            missing_files,
+            updated_cards,
+            search_highlight,
+            find,
             youtube_webviews,
+            vimeo_webviews,
+            soundcloud_webviews,
+            spotify_webviews,
             audio_webviews,
             video_webviews,
             data_sources,
+            image_sources,
+            audio_waveforms,
+            audio_metadata,
+            pdf_page_counts,
+            playlist,
+            item_views,
+            text_layouts,
             table_scroll_states,
             table_states,
             editing_textbox_id,
@@ -1489,6 +2074,14 @@ pub fn render_canvas_area(
             viewport_size,
             cx,
         ))
+        // Mark how the board differs from the version it is compared with
+        .when_some(comparison, |d, diff| {
+            d.children(render_diff_marks(diff, visible_items, canvas_offset, zoom, cx))
+        })
+        // Render the group box with scale handles around a multi-selection
+        .when_some(selection_group_bounds(items, selected_items), |d, bounds| {
+            d.child(render_group_selection(bounds, canvas_offset, zoom, primary, bg))
+        })
         // Render marquee selection rectangle
         .when_some(marquee, |d, (start, current)| {
             // Account for dock width since mouse coords are in window space
@@ -1521,6 +2114,12 @@ pub fn render_canvas_area(
                 d
             }
         })
+        // Render lasso selection path
+        .when(lasso.len() > 1, |d| d.child(render_lasso(lasso, primary)))
+        // Render alignment guides while dragging
+        .children(alignment_guides.iter().map(|guide| {
+            render_alignment_guide(guide, canvas_offset, zoom)
+        }))
         // Render drawing preview (for TextBox, Shape, Arrow while dragging)
         .when_some(drawing_preview, |d, (start, current, tool)| {
             // Account for dock width since mouse coords are in window space
@@ -1633,3 +2232,204 @@ pub fn render_canvas_area(
             }
         })
 }
+
+/// Bounds of the unlocked selected items when two or more items are selected
+fn selection_group_bounds(
+    items: &[CanvasItem],
+    selected_items: &std::collections::HashSet<u64>,
+) -> Option<ItemBounds> {
+    if selected_items.len() < 2 {
+        return None;
+    }
+    union_bounds(
+        items
+            .iter()
+            .filter(|item| selected_items.contains(&item.id) && !item.locked)
+            .map(|item| (item.position.0, item.position.1, item.size.0, item.size.1)),
+    )
+}
+
+/// Render one resize handle of a selected item, relative to the item's `size` on screen
+fn render_resize_handle(handle: ResizeHandle, size: (f32, f32), color: Hsla, fill: Hsla) -> Div {
+    let (x, y) = handle.position((0.0, 0.0, size.0, size.1));
+    let cursor = match handle {
+        ResizeHandle::TopLeft | ResizeHandle::BottomRight => CursorStyle::ResizeUpLeftDownRight,
+        ResizeHandle::TopRight | ResizeHandle::BottomLeft => CursorStyle::ResizeUpRightDownLeft,
+        ResizeHandle::Top | ResizeHandle::Bottom => CursorStyle::ResizeUpDown,
+        ResizeHandle::Left | ResizeHandle::Right => CursorStyle::ResizeLeftRight,
+    };
+    div()
+        .absolute()
+        .left(px(x - RESIZE_HANDLE_SIZE / 2.0))
+        .top(px(y - RESIZE_HANDLE_SIZE / 2.0))
+        .size(px(RESIZE_HANDLE_SIZE))
+        .bg(fill)
+        .border_1()
+        .border_color(color)
+        .rounded(px(2.0))
+        .cursor(cursor)
+}
+
+/// Render the selection box and its corner handles in canvas-area coordinates
+fn render_group_selection(
+    (x, y, w, h): ItemBounds,
+    canvas_offset: Point<Pixels>,
+    zoom: f32,
+    color: Hsla,
+    handle_fill: Hsla,
+) -> Div {
+    let left = x * zoom + f32::from(canvas_offset.x);
+    let top = y * zoom + f32::from(canvas_offset.y);
+    let bounds = (left, top, w * zoom, h * zoom);
+
+    div()
+        .absolute()
+        .left(px(left))
+        .top(px(top))
+        .w(px(w * zoom))
+        .h(px(h * zoom))
+        .border_1()
+        .border_color(color.opacity(0.6))
+        .children(GroupHandle::ALL.into_iter().map(|handle| {
+            let (corner_x, corner_y) = handle.corner(bounds);
+            let cursor = match handle {
+                GroupHandle::TopLeft | GroupHandle::BottomRight => {
+                    CursorStyle::ResizeUpLeftDownRight
+                }
+                GroupHandle::TopRight | GroupHandle::BottomLeft => {
+                    CursorStyle::ResizeUpRightDownLeft
+                }
+            };
+            div()
+                .absolute()
+                .left(px(corner_x - left - GROUP_HANDLE_SIZE / 2.0))
+                .top(px(corner_y - top - GROUP_HANDLE_SIZE / 2.0))
+                .size(px(GROUP_HANDLE_SIZE))
+                .bg(handle_fill)
+                .border_1()
+                .border_color(color)
+                .rounded(px(2.0))
+                .cursor(cursor)
+        }))
+}
+
+/// Render the lasso path, closed back to its start, with a light fill
+fn render_lasso(points: &[Point<Pixels>], color: Hsla) -> Div {
+    // Mouse coords are in window space; the canvas starts after the dock and header
+    let origin = point(px(crate::constants::DOCK_WIDTH), px(HEADER_HEIGHT));
+    let points: Vec<Point<Pixels>> = points.iter().map(|&p| p - origin).collect();
+
+    div().absolute().left(px(0.0)).top(px(0.0)).size_full().child(
+        canvas(
+            move |_, _, _| {},
+            move |bounds, _, window, _| {
+                let at = |p: Point<Pixels>| bounds.origin + p;
+
+                let mut fill = PathBuilder::fill();
+                fill.move_to(at(points[0]));
+                for &p in &points[1..] {
+                    fill.line_to(at(p));
+                }
+                fill.close();
+                if let Ok(built_fill) = fill.build() {
+                    window.paint_path(built_fill, color.opacity(0.1));
+                }
+
+                let mut outline = PathBuilder::stroke(px(1.0));
+                outline.move_to(at(points[0]));
+                for &p in points[1..].iter().chain(std::iter::once(&points[0])) {
+                    outline.line_to(at(p));
+                }
+                if let Ok(built_outline) = outline.build() {
+                    window.paint_path(built_outline, color);
+                }
+            },
+        )
+        .size_full(),
+    )
+}
+
+/// Marks of how the board differs from the version it is compared with:
+/// outlines on added, moved and changed items, the place moved items came
+/// from, and ghosts of removed items
+fn render_diff_marks(
+    diff: &BoardDiff,
+    items: &[CanvasItem],
+    canvas_offset: Point<Pixels>,
+    zoom: f32,
+    cx: &Context<Humanboard>,
+) -> Vec<Div> {
+    let offset_x = f32::from(canvas_offset.x);
+    let offset_y = f32::from(canvas_offset.y);
+    let frame = |(x, y, w, h): (f32, f32, f32, f32)| {
+        div()
+            .absolute()
+            .left(px(x * zoom + offset_x))
+            .top(px(y * zoom + offset_y))
+            .w(px(w * zoom))
+            .h(px(h * zoom))
+            .rounded(px(8.0 * zoom))
+    };
+
+    let mut marks = Vec::new();
+    for item in items {
+        let Some(mark) = diff.mark(item.id) else {
+            continue;
+        };
+        let color = match mark {
+            DiffMark::Added => cx.theme().success,
+            DiffMark::Moved => cx.theme().warning,
+            DiffMark::Changed => cx.theme().primary,
+        };
+        if let Some(old) = diff.old_bounds(item.id) {
+            marks.push(
+                frame(old)
+                    .border_1()
+                    .border_color(color.opacity(0.6))
+                    .bg(color.opacity(0.08)),
+            );
+        }
+        marks.push(
+            frame((item.position.0, item.position.1, item.size.0, item.size.1))
+                .border(px(2.0 * zoom))
+                .border_color(color),
+        );
+    }
+
+    let danger = cx.theme().danger;
+    for item in diff.removed() {
+        marks.push(
+            frame((item.position.0, item.position.1, item.size.0, item.size.1))
+                .border_1()
+                .border_color(danger)
+                .bg(danger.opacity(0.12))
+                .p(px(6.0 * zoom))
+                .overflow_hidden()
+                .text_size(px(12.0 * zoom))
+                .text_color(danger)
+                .child(format!("Removed: {}", crate::undo_history::item_name(item))),
+        );
+    }
+    marks
+}
+
+/// Render one alignment guide as a 1px line in canvas-area coordinates
+fn render_alignment_guide(guide: &AlignmentGuide, canvas_offset: Point<Pixels>, zoom: f32) -> Div {
+    let offset_x = f32::from(canvas_offset.x);
+    let offset_y = f32::from(canvas_offset.y);
+    // Magenta stands out against any theme and item color
+    let line = div().absolute().bg(hsla(300.0 / 360.0, 1.0, 0.5, 1.0));
+
+    match guide.axis {
+        GuideAxis::Vertical => line
+            .left(px(guide.position * zoom + offset_x))
+            .top(px(guide.start * zoom + offset_y))
+            .w(px(1.0))
+            .h(px((guide.end - guide.start) * zoom)),
+        GuideAxis::Horizontal => line
+            .left(px(guide.start * zoom + offset_x))
+            .top(px(guide.position * zoom + offset_y))
+            .w(px((guide.end - guide.start) * zoom))
+            .h(px(1.0)),
+    }
+}
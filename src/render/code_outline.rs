@@ -0,0 +1,133 @@
+//! Outline sidebar of code tabs.
+//!
+//! Code tabs with their outline shown get a sidebar to the left of the
+//! editor listing the functions, types and modules their file defines,
+//! nested as they are in the code. Clicking a symbol puts the editor's
+//! cursor on its line, and symbols with others nested in them have a
+//! chevron that collapses them.
+
+use crate::app::{CodeOutline, Humanboard};
+use crate::code_symbols::{CodeSymbol, has_nested_symbols, visible_symbols};
+use gpui::prelude::FluentBuilder;
+use gpui::*;
+use gpui_component::{ActiveTheme as _, h_flex, v_flex};
+use std::path::Path;
+
+/// Width of the outline sidebar
+pub const CODE_OUTLINE_WIDTH: f32 = 200.0;
+
+/// Indent per nesting level
+const OUTLINE_INDENT: f32 = 12.0;
+
+/// The outline sidebar of a code tab
+pub fn render_code_outline_sidebar(
+    path: &Path,
+    outline: &CodeOutline,
+    cx: &mut Context<Humanboard>,
+) -> Stateful<Div> {
+    let muted_fg = cx.theme().muted_foreground;
+    let rows: Vec<_> = visible_symbols(&outline.symbols, &outline.collapsed)
+        .into_iter()
+        .map(|index| {
+            let has_nested = has_nested_symbols(&outline.symbols, index);
+            let expanded = has_nested && !outline.collapsed.contains(&index);
+            render_symbol_row(
+                path,
+                index,
+                &outline.symbols[index],
+                has_nested,
+                expanded,
+                cx,
+            )
+        })
+        .collect();
+
+    v_flex()
+        .id("code-outline")
+        .w(px(CODE_OUTLINE_WIDTH))
+        .h_full()
+        .flex_shrink_0()
+        .py_1()
+        .bg(cx.theme().title_bar)
+        .border_r_1()
+        .border_color(cx.theme().border)
+        .overflow_y_scroll()
+        .when(rows.is_empty(), |d| {
+            d.child(
+                div()
+                    .px_3()
+                    .py_2()
+                    .text_xs()
+                    .text_color(muted_fg)
+                    .child("No symbols found"),
+            )
+        })
+        .children(rows)
+}
+
+fn render_symbol_row(
+    path: &Path,
+    index: usize,
+    symbol: &CodeSymbol,
+    has_nested: bool,
+    expanded: bool,
+    cx: &mut Context<Humanboard>,
+) -> Stateful<Div> {
+    let fg = cx.theme().foreground;
+    let muted_fg = cx.theme().muted_foreground;
+    let primary = cx.theme().primary;
+    let list_hover = cx.theme().list_hover;
+
+    let chevron = if expanded { "▾" } else { "▸" };
+    let toggle_path = path.to_path_buf();
+    let jump_path = path.to_path_buf();
+    let line = symbol.line;
+
+    h_flex()
+        .id(ElementId::Name(format!("code-outline-{}", index).into()))
+        .w_full()
+        .h(px(24.0))
+        .pl(px(4.0 + symbol.depth as f32 * OUTLINE_INDENT))
+        .pr_2()
+        .gap_1()
+        .items_center()
+        .text_xs()
+        .text_color(fg)
+        .hover(|s| s.bg(list_hover))
+        .cursor_pointer()
+        .child(
+            div()
+                .w(px(12.0))
+                .flex_shrink_0()
+                .text_color(muted_fg)
+                .when(has_nested, |d| {
+                    d.child(chevron).on_mouse_down(
+                        MouseButton::Left,
+                        cx.listener(move |this, _, _, cx| {
+                            cx.stop_propagation();
+                            this.toggle_code_symbol(&toggle_path, index, cx);
+                        }),
+                    )
+                }),
+        )
+        .child(
+            div()
+                .w(px(12.0))
+                .flex_shrink_0()
+                .text_color(primary)
+                .child(symbol.kind.marker()),
+        )
+        .child(
+            div()
+                .flex_1()
+                .min_w_0()
+                .overflow_hidden()
+                .text_ellipsis()
+                .whitespace_nowrap()
+                .font_family("monospace")
+                .child(symbol.name.clone()),
+        )
+        .on_click(cx.listener(move |this, _, window, cx| {
+            this.go_to_code_line(&jump_path, line, window, cx);
+        }))
+}
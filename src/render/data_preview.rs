@@ -0,0 +1,133 @@
+//! Data preview tabs - the rows of CSV files and board tables, a page at a
+//! time, below a filter input.
+//!
+//! Rows are drawn with the [`render_data_table`] component. Only the rows of
+//! the current page are taken from the source, in the order the tab's
+//! [`RowOrder`](crate::data::RowOrder) worked out, so big files stay lazy.
+
+use crate::app::{DataView, Humanboard};
+use crate::data::{DataRows, RowSelection};
+use crate::data_table::{ClickHandler, DataTableAction, DataTableColors, render_data_table};
+use crate::loading::render_loading_spinner;
+use crate::types::DataRow;
+use gpui::*;
+use gpui_component::input::Input;
+use gpui_component::{ActiveTheme as _, Sizable as _, h_flex, v_flex};
+
+/// A data view: the filter input above the current page of rows
+pub fn render_data_view(
+    title: &str,
+    rows: Option<DataRows>,
+    error: Option<String>,
+    view: &DataView,
+    cx: &mut Context<Humanboard>,
+) -> Div {
+    let theme = cx.theme();
+    let colors = DataTableColors::from_theme(
+        theme.background,
+        theme.muted,
+        theme.border,
+        theme.foreground,
+        theme.muted_foreground,
+        theme.primary,
+        theme.title_bar,
+    );
+    let selection = view.order.selection();
+
+    let filter_bar = h_flex()
+        .w_full()
+        .h(px(32.0))
+        .px_2()
+        .py_1()
+        .flex_shrink_0()
+        .bg(colors.title_bar)
+        .border_b_1()
+        .border_color(colors.border)
+        .gap_2()
+        .items_center()
+        .child(div().flex_1().child(Input::new(&view.filter).xsmall()));
+
+    let content = match (rows, error) {
+        (_, Some(error)) => div()
+            .p_4()
+            .text_sm()
+            .text_color(colors.text_muted)
+            .child(format!("Couldn't open this file: {}", error))
+            .into_any_element(),
+        (Some(_), None) if selection == RowSelection::Pending => div()
+            .p_4()
+            .child(render_loading_spinner(
+                "Sorting rows...",
+                colors.primary,
+                colors.text_muted,
+            ))
+            .into_any_element(),
+        (Some(rows), None) => {
+            let page = page_rows(&rows, &selection, view);
+            let columns: Vec<&str> = rows
+                .columns()
+                .iter()
+                .map(|column| column.name.as_str())
+                .collect();
+            let view_id = view.filter.entity_id();
+            let on_action = |action: DataTableAction| -> ClickHandler {
+                Box::new(cx.listener(move |this, _: &ClickEvent, _, cx| {
+                    this.apply_data_table_action(view_id, action, cx);
+                }))
+            };
+            render_data_table(
+                "data-preview-table",
+                title,
+                &columns,
+                &page,
+                &view.table,
+                |row: &DataRow, col_idx| {
+                    row.cells
+                        .get(col_idx)
+                        .map(|cell| cell.to_string())
+                        .unwrap_or_default()
+                },
+                &colors,
+                &on_action,
+            )
+            .into_any_element()
+        }
+        (None, None) => div()
+            .p_4()
+            .child(render_loading_spinner(
+                "Loading rows...",
+                colors.primary,
+                colors.text_muted,
+            ))
+            .into_any_element(),
+    };
+
+    v_flex()
+        .flex_1()
+        .w_full()
+        .min_h_0()
+        .bg(colors.background)
+        .child(filter_bar)
+        .child(
+            div()
+                .id("data-preview-scroll")
+                .flex_1()
+                .min_h_0()
+                .p_2()
+                .overflow_y_scroll()
+                .child(content),
+        )
+}
+
+/// Rows of the current page, in the order the tab shows them
+fn page_rows(rows: &DataRows, selection: &RowSelection, view: &DataView) -> Vec<DataRow> {
+    let range = view.table.visible_range();
+    match selection {
+        RowSelection::Rows(order) => {
+            let end = range.end.min(order.len());
+            let start = range.start.min(end);
+            rows.rows_at(&order[start..end])
+        }
+        _ => rows.rows(range.start, range.len()),
+    }
+}
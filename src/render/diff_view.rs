@@ -0,0 +1,344 @@
+//! Diff tabs - a file's lines compared with another file or its snapshot,
+//! above a footer.
+//!
+//! Lines are shown unified, one after another with both line numbers, or
+//! side by side with the old text on the left. Removed and added lines are
+//! tinted, their code highlighted with [`DiffHighlights`], and long runs
+//! of unchanged lines are folded down to a row saying how many were hidden.
+//! The footer counts the changes and switches the layout.
+
+use crate::app::Humanboard;
+use crate::syntax_highlight::DiffHighlights;
+use crate::syntax_tokens::TokenKind;
+use crate::text_diff::{
+    DIFF_CONTEXT_LINES, DiffBase, DiffFold, DiffKind, DiffLine, TextDiff, fold_unchanged,
+    side_by_side_rows,
+};
+use gpui::prelude::FluentBuilder;
+use gpui::*;
+use gpui_component::button::{Button, ButtonVariants};
+use gpui_component::highlighter::HighlightTheme;
+use gpui_component::{ActiveTheme as _, Sizable as _, h_flex, v_flex};
+use std::path::Path;
+
+/// Height of a diff line
+const DIFF_LINE_HEIGHT: f32 = 20.0;
+
+/// Width of a line number column
+const LINE_NUMBER_WIDTH: f32 = 44.0;
+
+/// Most lines drawn in one diff, so very different files stay responsive
+const MAX_DIFF_ROWS: usize = 5000;
+
+/// Colors of a diff, taken from the theme
+#[derive(Clone, Copy)]
+struct DiffColors {
+    text: Hsla,
+    muted: Hsla,
+    border: Hsla,
+    added: Hsla,
+    removed: Hsla,
+    keyword: Hsla,
+    string: Hsla,
+    number: Hsla,
+}
+
+/// Highlights of a diff's lines, and the theme coloring them
+#[derive(Clone, Copy)]
+struct Syntax<'a> {
+    highlights: &'a DiffHighlights,
+    theme: &'a HighlightTheme,
+}
+
+/// A diff tab: the compared lines above the footer
+pub fn render_diff_view(
+    path: &Path,
+    base: &DiffBase,
+    base_label: &str,
+    diff: &Result<TextDiff, String>,
+    highlights: &DiffHighlights,
+    side_by_side: bool,
+    cx: &mut Context<Humanboard>,
+) -> Div {
+    let theme = cx.theme();
+    let colors = DiffColors {
+        text: theme.foreground,
+        muted: theme.muted_foreground,
+        border: theme.border,
+        added: theme.success,
+        removed: theme.danger,
+        keyword: theme.primary,
+        string: theme.success,
+        number: theme.warning,
+    };
+    let (bg, title_bar, primary) = (theme.background, theme.title_bar, theme.primary);
+    let syntax_theme = theme.highlight_theme.clone();
+    let syntax = Syntax {
+        highlights,
+        theme: &syntax_theme,
+    };
+
+    let content = match diff {
+        Err(error) => div()
+            .p_4()
+            .text_sm()
+            .text_color(colors.muted)
+            .child(error.clone())
+            .into_any_element(),
+        Ok(diff) if diff.is_identical() => div()
+            .p_4()
+            .text_sm()
+            .text_color(colors.muted)
+            .child(format!("No changes since {}", base_label))
+            .into_any_element(),
+        Ok(diff) if side_by_side => {
+            render_side_by_side(&diff.lines, syntax, colors).into_any_element()
+        }
+        Ok(diff) => render_unified(&diff.lines, syntax, colors).into_any_element(),
+    };
+
+    let file = path
+        .file_name()
+        .and_then(|n| n.to_str())
+        .unwrap_or("Untitled")
+        .to_string();
+    let stats = diff.as_ref().ok().map(|diff| (diff.added, diff.removed));
+    let (toggle_path, toggle_base) = (path.to_path_buf(), base.clone());
+    let (refresh_path, refresh_base) = (path.to_path_buf(), base.clone());
+
+    v_flex()
+        .flex_1()
+        .w_full()
+        .min_h_0()
+        .bg(bg)
+        .child(
+            div()
+                .id("diff-content-scroll")
+                .flex_1()
+                .min_h_0()
+                .overflow_y_scroll()
+                .font_family("monospace")
+                .text_xs()
+                .child(content),
+        )
+        .child(
+            h_flex()
+                .h(px(40.0))
+                .bg(title_bar)
+                .border_t_1()
+                .border_color(colors.border)
+                .items_center()
+                .justify_between()
+                .px_3()
+                .child(
+                    h_flex()
+                        .gap_3()
+                        .child(
+                            div()
+                                .px(px(6.0))
+                                .py(px(2.0))
+                                .bg(primary.opacity(0.2))
+                                .rounded(px(3.0))
+                                .text_xs()
+                                .font_weight(FontWeight::MEDIUM)
+                                .text_color(primary)
+                                .child("DIFF"),
+                        )
+                        .child(
+                            div()
+                                .text_xs()
+                                .text_color(colors.muted)
+                                .child(format!("{} → {}", base_label, file)),
+                        )
+                        .when_some(stats, |d, (added, removed)| {
+                            d.child(
+                                div()
+                                    .text_xs()
+                                    .text_color(colors.added)
+                                    .child(format!("+{}", added)),
+                            )
+                            .child(
+                                div()
+                                    .text_xs()
+                                    .text_color(colors.removed)
+                                    .child(format!("−{}", removed)),
+                            )
+                        }),
+                )
+                .child(
+                    h_flex()
+                        .gap_1()
+                        .child(
+                            Button::new("diff-layout-toggle")
+                                .ghost()
+                                .xsmall()
+                                .label(if side_by_side {
+                                    "Unified"
+                                } else {
+                                    "Side by Side"
+                                })
+                                .on_click(cx.listener(move |this, _, _, cx| {
+                                    this.toggle_diff_layout(&toggle_path, &toggle_base, cx);
+                                })),
+                        )
+                        .child(
+                            Button::new("diff-refresh")
+                                .ghost()
+                                .xsmall()
+                                .label("Refresh")
+                                .on_click(cx.listener(move |this, _, _, cx| {
+                                    this.refresh_diff(&refresh_path, &refresh_base, cx);
+                                })),
+                        ),
+                ),
+        )
+}
+
+/// Lines one after another, with their old and new line numbers
+fn render_unified(lines: &[DiffLine], syntax: Syntax, colors: DiffColors) -> Div {
+    let changed: Vec<bool> = lines
+        .iter()
+        .map(|line| line.kind != DiffKind::Unchanged)
+        .collect();
+    render_folds(&changed, colors, |index| {
+        let line = &lines[index];
+        h_flex()
+            .w_full()
+            .bg(line_tint(line.kind, colors))
+            .child(line_number(line.old_number, colors))
+            .child(line_number(line.new_number, colors))
+            .child(line_marker(line.kind, colors))
+            .child(line_text(line, syntax, colors))
+    })
+}
+
+/// Old lines on the left and new lines on the right, changes paired up
+fn render_side_by_side(lines: &[DiffLine], syntax: Syntax, colors: DiffColors) -> Div {
+    let rows = side_by_side_rows(lines);
+    let changed: Vec<bool> = rows.iter().map(|row| row.is_change(lines)).collect();
+    let side = |index: Option<usize>, old: bool| {
+        let line = index.map(|index| &lines[index]);
+        let kind = line.map_or(DiffKind::Unchanged, |line| line.kind);
+        let number = line.and_then(|line| {
+            if old {
+                line.old_number
+            } else {
+                line.new_number
+            }
+        });
+        h_flex()
+            .flex_1()
+            .min_w_0()
+            .overflow_hidden()
+            .bg(line_tint(kind, colors))
+            .child(line_number(number, colors))
+            .child(line_marker(kind, colors))
+            .when_some(line, |d, line| d.child(line_text(line, syntax, colors)))
+    };
+    render_folds(&changed, colors, |index| {
+        let row = rows[index];
+        h_flex()
+            .w_full()
+            .child(side(row.old, true))
+            .child(div().w(px(1.0)).h_full().bg(colors.border))
+            .child(side(row.new, false))
+    })
+}
+
+/// The entries `changed` describes, with unchanged runs away from changes
+/// folded into one row each
+fn render_folds(changed: &[bool], colors: DiffColors, row: impl Fn(usize) -> Div) -> Div {
+    let mut list = v_flex().w_full().py_1();
+    let mut drawn = 0;
+    for fold in fold_unchanged(changed, DIFF_CONTEXT_LINES) {
+        if drawn >= MAX_DIFF_ROWS {
+            list = list.child(
+                div()
+                    .px_3()
+                    .py_2()
+                    .text_color(colors.muted)
+                    .child("Diff too long to show in full"),
+            );
+            break;
+        }
+        match fold {
+            DiffFold::Shown(range) => {
+                let end = range.end.min(range.start + MAX_DIFF_ROWS - drawn);
+                drawn += end - range.start;
+                list = list
+                    .children((range.start..end).map(|index| row(index).h(px(DIFF_LINE_HEIGHT))));
+            }
+            DiffFold::Hidden(range) => {
+                drawn += 1;
+                list = list.child(
+                    div()
+                        .w_full()
+                        .h(px(DIFF_LINE_HEIGHT))
+                        .px_3()
+                        .flex()
+                        .items_center()
+                        .border_y_1()
+                        .border_color(colors.border)
+                        .text_color(colors.muted)
+                        .child(format!("⋯ {} unchanged lines", range.len())),
+                );
+            }
+        }
+    }
+    list
+}
+
+fn line_tint(kind: DiffKind, colors: DiffColors) -> Hsla {
+    match kind {
+        DiffKind::Unchanged => transparent_black(),
+        DiffKind::Added => colors.added.opacity(0.15),
+        DiffKind::Removed => colors.removed.opacity(0.15),
+    }
+}
+
+fn line_number(number: Option<usize>, colors: DiffColors) -> Div {
+    div()
+        .w(px(LINE_NUMBER_WIDTH))
+        .flex_shrink_0()
+        .pr_2()
+        .flex()
+        .justify_end()
+        .text_color(colors.muted)
+        .children(number.map(|number| number.to_string()))
+}
+
+fn line_marker(kind: DiffKind, colors: DiffColors) -> Div {
+    let (marker, color) = match kind {
+        DiffKind::Unchanged => (" ", colors.muted),
+        DiffKind::Added => ("+", colors.added),
+        DiffKind::Removed => ("−", colors.removed),
+    };
+    div()
+        .w(px(16.0))
+        .flex_shrink_0()
+        .text_color(color)
+        .child(marker)
+}
+
+/// A line of code, syntax highlighted
+fn line_text(line: &DiffLine, syntax: Syntax, colors: DiffColors) -> Div {
+    let highlights = syntax.highlights.line_styles(line, syntax.theme, |kind| {
+        let color = match kind {
+            TokenKind::Keyword => colors.keyword,
+            TokenKind::String => colors.string,
+            TokenKind::Comment => colors.muted,
+            TokenKind::Number => colors.number,
+        };
+        HighlightStyle {
+            color: Some(color),
+            ..Default::default()
+        }
+    });
+    div()
+        .flex_1()
+        .min_w_0()
+        .whitespace_nowrap()
+        .overflow_hidden()
+        .text_color(colors.text)
+        .child(StyledText::new(line.text.clone()).with_highlights(highlights))
+}
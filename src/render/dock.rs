@@ -63,6 +63,7 @@ where
     let on_select2 = on_select.clone();
     let on_select3 = on_select.clone();
     let on_select4 = on_select.clone();
+    let on_select5 = on_select.clone();
 
     let border_color = cx.theme().border;
 
@@ -84,6 +85,13 @@ where
                     on_select1(this, ToolType::Select, window, cx);
                 })),
         )
+        // Lasso selection tool
+        .child(
+            render_tool_button(ToolType::Lasso, selected_tool == ToolType::Lasso, "L", cx)
+                .on_click(cx.listener(move |this, _, window, cx| {
+                    on_select5(this, ToolType::Lasso, window, cx);
+                })),
+        )
         // Text tool
         .child(
             render_tool_button(ToolType::Text, selected_tool == ToolType::Text, "T", cx).on_click(
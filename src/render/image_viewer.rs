@@ -0,0 +1,271 @@
+//! Image preview tabs - the image and the footer below it.
+//!
+//! The image is drawn at the zoom and pan of the tab's [`ImageView`], clipped
+//! to the tab. Scrolling zooms about the mouse and dragging moves the image.
+//! The area the image is drawn in is recorded while painting, so mouse
+//! positions can be mapped onto image pixels. The footer has Fit, 100% and
+//! zoom buttons, the zoom level and the position and color of the pixel
+//! under the mouse.
+
+use crate::app::Humanboard;
+use crate::focus::FocusContext;
+use crate::image_viewer::{IMAGE_PIXELS, IMAGE_ZOOM_STEP, ImageView, format_pixel_color};
+use crate::input::transform::scroll_zoom_factor;
+use crate::loading::render_loading_spinner;
+use gpui::prelude::FluentBuilder;
+use gpui::*;
+use gpui_component::button::{Button, ButtonVariants};
+use gpui_component::{ActiveTheme as _, Sizable as _, h_flex, v_flex};
+use std::cell::Cell;
+use std::path::Path;
+use std::rc::Rc;
+
+/// Height of the footer below image viewers
+pub const IMAGE_PREVIEW_FOOTER_HEIGHT: f32 = 40.0;
+
+/// An image tab: the image, zoomed and panned, above its footer
+pub fn render_image_viewer(
+    path: &Path,
+    view: ImageView,
+    viewport: &Rc<Cell<Bounds<Pixels>>>,
+    hovered_pixel: Option<(u32, u32)>,
+    panning: bool,
+    focus: &FocusHandle,
+    cx: &mut Context<Humanboard>,
+) -> Div {
+    let pixels = IMAGE_PIXELS.get(path);
+    let size = pixels.as_ref().map(|pixels| pixels.dimensions());
+    let bounds = viewport.get();
+    let viewport_size = (f32::from(bounds.size.width), f32::from(bounds.size.height));
+    let scale = size.map(|size| view.scale(size, viewport_size));
+    let hovered_color = pixels.as_ref().and_then(|pixels| {
+        let (x, y) = hovered_pixel?;
+        pixels.get_pixel_checked(x, y).map(|pixel| pixel.0)
+    });
+
+    let image = match size {
+        Some(size) => {
+            let (x, y, width, height) = view.image_rect(size, viewport_size);
+            img(path.to_path_buf())
+                .absolute()
+                .left(px(x))
+                .top(px(y))
+                .w(px(width))
+                .h(px(height))
+                .into_any_element()
+        }
+        None if IMAGE_PIXELS.failed(path) => div()
+            .size_full()
+            .flex()
+            .items_center()
+            .justify_center()
+            .text_sm()
+            .text_color(cx.theme().muted_foreground)
+            .child("Couldn't open this image")
+            .into_any_element(),
+        None => div()
+            .p_4()
+            .child(render_loading_spinner(
+                "Loading image...",
+                cx.theme().primary,
+                cx.theme().muted_foreground,
+            ))
+            .into_any_element(),
+    };
+
+    let record_viewport = viewport.clone();
+    let focus_handle = focus.clone();
+    let down_path = path.to_path_buf();
+    let move_path = path.to_path_buf();
+    let up_path = path.to_path_buf();
+    let hover_path = path.to_path_buf();
+    let scroll_path = path.to_path_buf();
+
+    let content = div()
+        .id("image-viewer")
+        .flex_1()
+        .w_full()
+        .min_h_0()
+        .relative()
+        .overflow_hidden()
+        .bg(cx.theme().muted)
+        .track_focus(focus)
+        .key_context(FocusContext::KEY_PREVIEW)
+        .cursor(if panning {
+            CursorStyle::ClosedHand
+        } else {
+            CursorStyle::OpenHand
+        })
+        // Record where the image area is drawn for mapping the mouse onto it
+        .child(
+            canvas(
+                move |bounds, window, _| {
+                    // Draw again at the new size when the pane was resized
+                    if record_viewport.replace(bounds) != bounds {
+                        window.refresh();
+                    }
+                },
+                |_, _, _, _| {},
+            )
+            .absolute()
+            .size_full(),
+        )
+        .child(image)
+        .on_mouse_down(
+            MouseButton::Left,
+            cx.listener(move |this, event: &MouseDownEvent, window, cx| {
+                // Take the arrow keys for stepping through the board's images
+                this.system.focus.focus(FocusContext::Preview, window);
+                focus_handle.focus(window);
+                this.start_preview_image_pan(&down_path, event.position);
+                cx.notify();
+            }),
+        )
+        .on_mouse_move(cx.listener(move |this, event: &MouseMoveEvent, _, cx| {
+            this.preview_image_mouse_move(&move_path, event.position, cx);
+        }))
+        .on_mouse_up(
+            MouseButton::Left,
+            cx.listener(move |this, _, _, cx| {
+                this.end_preview_image_pan(&up_path);
+                cx.notify();
+            }),
+        )
+        .on_hover(cx.listener(move |this, hovered: &bool, _, cx| {
+            if !*hovered {
+                this.clear_preview_image_hover(&hover_path, cx);
+            }
+        }))
+        .on_scroll_wheel(cx.listener(move |this, event: &ScrollWheelEvent, _, cx| {
+            let factor = scroll_zoom_factor(event.delta);
+            if (factor - 1.0).abs() > 0.001 {
+                this.zoom_preview_image(&scroll_path, factor, Some(event.position), cx);
+            }
+        }));
+
+    v_flex()
+        .flex_1()
+        .w_full()
+        .min_h_0()
+        .child(content)
+        .child(render_image_preview_footer(
+            path,
+            size,
+            scale,
+            hovered_pixel.zip(hovered_color),
+            cx,
+        ))
+}
+
+/// Footer below an image: zoom buttons and level, then the image's size or
+/// the pixel under the mouse with its color
+fn render_image_preview_footer(
+    path: &Path,
+    size: Option<(u32, u32)>,
+    scale: Option<f32>,
+    hovered: Option<((u32, u32), [u8; 4])>,
+    cx: &mut Context<Humanboard>,
+) -> Div {
+    let muted_fg = cx.theme().muted_foreground;
+    let border = cx.theme().border;
+    let zoom = |factor: f32| {
+        let path = path.to_path_buf();
+        cx.listener(move |this, _, _, cx| this.zoom_preview_image(&path, factor, None, cx))
+    };
+    let zoom_out = zoom(1.0 / IMAGE_ZOOM_STEP);
+    let zoom_in = zoom(IMAGE_ZOOM_STEP);
+    let fit_path = path.to_path_buf();
+    let actual_path = path.to_path_buf();
+
+    let info = match (hovered, size) {
+        (Some(((x, y), rgba)), _) => {
+            let color = format_pixel_color(rgba);
+            h_flex()
+                .gap_2()
+                .items_center()
+                .child(
+                    div()
+                        .w(px(12.0))
+                        .h(px(12.0))
+                        .rounded(px(2.0))
+                        .border_1()
+                        .border_color(border)
+                        .bg(rgba_color(rgba)),
+                )
+                .child(format!("{}, {}  {}", x, y, color))
+        }
+        (None, Some((width, height))) => h_flex().child(format!("{} × {}", width, height)),
+        (None, None) => h_flex(),
+    };
+
+    h_flex()
+        .h(px(IMAGE_PREVIEW_FOOTER_HEIGHT))
+        .flex_shrink_0()
+        .bg(cx.theme().title_bar)
+        .border_t_1()
+        .border_color(border)
+        .items_center()
+        .justify_between()
+        .px_3()
+        .child(
+            h_flex()
+                .gap_1()
+                .items_center()
+                .child(
+                    Button::new("image-preview-fit")
+                        .ghost()
+                        .small()
+                        .label("Fit")
+                        .tooltip("Fit to pane")
+                        .on_click(cx.listener(move |this, _, _, cx| {
+                            this.fit_preview_image(&fit_path, cx);
+                        })),
+                )
+                .child(
+                    Button::new("image-preview-actual")
+                        .ghost()
+                        .small()
+                        .label("100%")
+                        .tooltip("Actual size")
+                        .on_click(cx.listener(move |this, _, _, cx| {
+                            this.preview_image_actual_size(&actual_path, cx);
+                        })),
+                )
+                .child(
+                    Button::new("image-preview-zoom-out")
+                        .ghost()
+                        .small()
+                        .label("−")
+                        .tooltip("Zoom out")
+                        .on_click(zoom_out),
+                )
+                .child(
+                    h_flex()
+                        .min_w(px(44.0))
+                        .justify_center()
+                        .text_xs()
+                        .text_color(muted_fg)
+                        .when_some(scale, |d, scale| {
+                            d.child(format!("{}%", (scale * 100.0).round()))
+                        }),
+                )
+                .child(
+                    Button::new("image-preview-zoom-in")
+                        .ghost()
+                        .small()
+                        .label("+")
+                        .tooltip("Zoom in")
+                        .on_click(zoom_in),
+                ),
+        )
+        .child(info.text_xs().text_color(muted_fg))
+}
+
+fn rgba_color([r, g, b, a]: [u8; 4]) -> Rgba {
+    Rgba {
+        r: r as f32 / 255.0,
+        g: g as f32 / 255.0,
+        b: b as f32 / 255.0,
+        a: a as f32 / 255.0,
+    }
+}
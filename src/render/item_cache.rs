@@ -0,0 +1,320 @@
+//! Render caching for static items.
+//!
+//! Most items draw exactly the same thing frame after frame. Markdown and
+//! code cards, table cards, shapes, text boxes, text and links are each
+//! wrapped in a small view and drawn through GPUI's view cache, so their
+//! element trees are only rebuilt when something they show changes.
+//!
+//! Every cached view remembers the [`RenderKey`] it was built for: a hash of
+//! the item's content, the zoom rounded to a bucket, whether the item is
+//! selected, whether a markdown or code card is expanded, the cards linking
+//! to a markdown card, the excerpt shown on a code card and the theme
+//! colors. A new
+//! key updates the view and notifies it; an unchanged key lets GPUI reuse the
+//! previous frame's layout and paint.
+//! Items that scroll out of view drop their cached view.
+
+use super::canvas::{TableCard, render_item_content, render_table_card};
+use super::text_layout::TextLayout;
+use crate::code_excerpt::CodeExcerpt;
+use crate::markdown_card::{render_expanded_code, render_expanded_markdown};
+use crate::types::{CanvasItem, DataSource, ItemContent};
+use gpui::*;
+use gpui_component::theme::Theme;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{HashMap, HashSet};
+use std::hash::{Hash, Hasher};
+use std::sync::Arc;
+
+/// Zoom buckets per 1.0 of zoom. Cached items are drawn at the bucket's
+/// zoom, which is at most half a percent off the real one.
+pub const ZOOM_BUCKETS_PER_UNIT: f32 = 200.0;
+
+/// Round a zoom level to its cache bucket
+pub fn zoom_bucket(zoom: f32) -> i32 {
+    (zoom * ZOOM_BUCKETS_PER_UNIT).round() as i32
+}
+
+/// The zoom level a bucket is drawn at
+pub fn bucket_zoom(bucket: i32) -> f32 {
+    bucket as f32 / ZOOM_BUCKETS_PER_UNIT
+}
+
+/// Whether an item's content can be drawn from the cache. Media with live
+/// webviews, charts and the text box or markdown card being edited are
+/// always rebuilt.
+pub fn is_cacheable(item: &CanvasItem, editing_textbox_id: Option<u64>) -> bool {
+    match item.content {
+        ItemContent::Text(_)
+        | ItemContent::Link(_)
+        | ItemContent::Bookmark { .. }
+        | ItemContent::Pdf { .. }
+        | ItemContent::Code { .. }
+        | ItemContent::Shape { .. }
+        | ItemContent::Table { .. } => true,
+        ItemContent::TextBox { .. } | ItemContent::Markdown { .. } => {
+            editing_textbox_id != Some(item.id)
+        }
+        _ => false,
+    }
+}
+
+/// Theme colors item content is drawn with
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ItemColors {
+    pub fg: Hsla,
+    pub muted_fg: Hsla,
+    pub muted_bg: Hsla,
+    pub danger: Hsla,
+}
+
+impl ItemColors {
+    pub fn from_theme(theme: &Theme) -> Self {
+        Self {
+            fg: theme.foreground,
+            muted_fg: theme.muted_foreground,
+            muted_bg: theme.muted,
+            danger: theme.danger,
+        }
+    }
+
+    fn hash_into(&self, hasher: &mut impl Hasher) {
+        for color in [self.fg, self.muted_fg, self.muted_bg, self.danger] {
+            for channel in [color.h, color.s, color.l, color.a] {
+                channel.to_bits().hash(hasher);
+            }
+        }
+    }
+}
+
+/// Everything a cached item's rendering depends on
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct RenderKey {
+    content: u64,
+    zoom_bucket: i32,
+    selected: bool,
+    /// Markdown and code cards switch between a chip and their file as they
+    /// resize
+    expanded: bool,
+    theme: u64,
+}
+
+impl RenderKey {
+    pub fn new(
+        item: &CanvasItem,
+        table: Option<&TableCard>,
+        zoom: f32,
+        selected: bool,
+        colors: &ItemColors,
+    ) -> Self {
+        let mut hasher = DefaultHasher::new();
+        item.content.hash(&mut hasher);
+        table.hash(&mut hasher);
+        let content = hasher.finish();
+
+        let mut hasher = DefaultHasher::new();
+        colors.hash_into(&mut hasher);
+        let theme = hasher.finish();
+
+        Self {
+            content,
+            zoom_bucket: zoom_bucket(zoom),
+            selected,
+            expanded: item.is_expanded_markdown() || item.is_expanded_code(),
+            theme,
+        }
+    }
+
+    /// Include the titles of the cards linking to a markdown card, which
+    /// expanded cards list below their document
+    pub fn with_backlinks(mut self, backlinks: &[String]) -> Self {
+        if !backlinks.is_empty() {
+            let mut hasher = DefaultHasher::new();
+            self.content.hash(&mut hasher);
+            backlinks.hash(&mut hasher);
+            self.content = hasher.finish();
+        }
+        self
+    }
+
+    /// Include the excerpt of its file an expanded code card shows
+    pub fn with_code_excerpt(mut self, excerpt: Option<&CodeExcerpt>) -> Self {
+        if let Some(excerpt) = excerpt {
+            let mut hasher = DefaultHasher::new();
+            self.content.hash(&mut hasher);
+            excerpt.first_line.hash(&mut hasher);
+            excerpt.lines.hash(&mut hasher);
+            excerpt.total_lines.hash(&mut hasher);
+            self.content = hasher.finish();
+        }
+        self
+    }
+}
+
+/// A single item's content, drawn through the view cache
+pub struct CachedItemView {
+    item: CanvasItem,
+    table: Option<TableCard>,
+    text_layout: Option<Arc<TextLayout>>,
+    /// Titles of the cards linking to this markdown card
+    backlinks: Vec<String>,
+    /// Part of the file an expanded code card shows
+    code_excerpt: Option<Arc<CodeExcerpt>>,
+    zoom: f32,
+    colors: ItemColors,
+}
+
+impl Render for CachedItemView {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let colors = self.colors;
+        if self.item.is_expanded_markdown() {
+            if let ItemContent::Markdown {
+                path,
+                title,
+                content,
+            } = &self.item.content
+            {
+                return render_expanded_markdown(
+                    title,
+                    content,
+                    path.parent(),
+                    &self.backlinks,
+                    self.zoom,
+                    cx,
+                );
+            }
+        }
+        if let (ItemContent::Code { path, language, .. }, Some(excerpt)) =
+            (&self.item.content, &self.code_excerpt)
+        {
+            let filename = path
+                .file_name()
+                .and_then(|name| name.to_str())
+                .unwrap_or("Unknown");
+            return render_expanded_code(self.item.id, filename, language, excerpt, self.zoom, cx);
+        }
+        if let ItemContent::Table { .. } = self.item.content {
+            return render_table_card(
+                self.table.as_ref(),
+                self.zoom,
+                colors.muted_fg,
+                colors.muted_bg,
+            );
+        }
+
+        render_item_content(
+            &self.item,
+            self.zoom,
+            &HashMap::new(),
+            &HashMap::new(),
+            &HashMap::new(),
+            &HashMap::new(),
+            &HashMap::new(),
+            &HashMap::new(),
+            &HashMap::new(),
+            &HashMap::new(),
+            &HashMap::new(),
+            &HashMap::new(),
+            self.text_layout.as_deref(),
+            &HashMap::new(),
+            &HashMap::new(),
+            None,
+            None,
+            None,
+            None,
+            colors.fg,
+            colors.muted_fg,
+            colors.muted_bg,
+            colors.danger,
+        )
+    }
+}
+
+/// Cached views for the items currently on screen
+#[derive(Default)]
+pub struct ItemRenderCache {
+    views: HashMap<u64, (RenderKey, Entity<CachedItemView>)>,
+}
+
+impl ItemRenderCache {
+    /// Bring the cache up to date with the visible items and return a view
+    /// for each cacheable one. Views whose key changed are re-rendered; the
+    /// rest are reused as they are.
+    pub fn prepare(
+        &mut self,
+        items: &[CanvasItem],
+        data_sources: &HashMap<u64, DataSource>,
+        text_layouts: &HashMap<u64, Arc<TextLayout>>,
+        code_excerpts: &HashMap<u64, Arc<CodeExcerpt>>,
+        backlinks: &HashMap<u64, Vec<String>>,
+        zoom: f32,
+        selected_items: &HashSet<u64>,
+        editing_textbox_id: Option<u64>,
+        colors: ItemColors,
+        cx: &mut App,
+    ) -> HashMap<u64, AnyView> {
+        let cached_zoom = bucket_zoom(zoom_bucket(zoom));
+        let mut views = HashMap::new();
+
+        for item in items {
+            if !is_cacheable(item, editing_textbox_id) {
+                continue;
+            }
+
+            let table = match item.content {
+                ItemContent::Table { data_source_id, .. } => data_sources
+                    .get(&data_source_id)
+                    .map(TableCard::from_source),
+                _ => None,
+            };
+            let text_layout = text_layouts.get(&item.id).cloned();
+            let selected = selected_items.contains(&item.id);
+            let item_backlinks = backlinks.get(&item.id).cloned().unwrap_or_default();
+            let code_excerpt = code_excerpts.get(&item.id).cloned();
+            let key = RenderKey::new(item, table.as_ref(), zoom, selected, &colors)
+                .with_backlinks(&item_backlinks)
+                .with_code_excerpt(code_excerpt.as_deref());
+
+            let entity = match self.views.remove(&item.id) {
+                Some((cached_key, entity)) if cached_key == key => entity,
+                Some((_, entity)) => {
+                    entity.update(cx, |view, cx| {
+                        view.item = item.clone();
+                        view.table = table;
+                        view.text_layout = text_layout;
+                        view.backlinks = item_backlinks;
+                        view.code_excerpt = code_excerpt;
+                        view.zoom = cached_zoom;
+                        view.colors = colors;
+                        cx.notify();
+                    });
+                    entity
+                }
+                None => cx.new(|_| CachedItemView {
+                    item: item.clone(),
+                    table,
+                    text_layout,
+                    backlinks: item_backlinks,
+                    code_excerpt,
+                    zoom: cached_zoom,
+                    colors,
+                }),
+            };
+
+            views.insert(
+                item.id,
+                AnyView::from(entity.clone()).cached(StyleRefinement::default().size_full()),
+            );
+            self.views.insert(item.id, (key, entity));
+        }
+
+        // Anything left over is off screen, deleted or no longer cacheable
+        self.views.retain(|id, _| views.contains_key(id));
+        views
+    }
+
+    /// Drop every cached view, e.g. when switching boards
+    pub fn clear(&mut self) {
+        self.views.clear();
+    }
+}
@@ -2,47 +2,87 @@
 //!
 //! This module is split into submodules for maintainability:
 //! - `canvas`: Canvas and item rendering
+//! - `backgrounds`: Item background quads, batched by color
+//! - `patterns`: Dot and line grids behind the canvas
 //! - `dock`: Tool dock (left sidebar)
 //! - `preview`: Preview panel, tabs, splitter
+//! - `data_preview`: Paginated rows of CSV and table tabs
+//! - `diff_view`: Unified and side-by-side diffs of files
 //! - `overlays`: Header, footer, shortcuts, command palette
+//! - `item_cache`: Cached views for items that don't change between frames
+//! - `text_layout`: Cached line layouts for text boxes
 
+pub mod backgrounds;
 pub mod canvas;
+pub mod code_outline;
+pub mod data_preview;
+pub mod diff_view;
 pub mod dock;
+pub mod image_viewer;
+pub mod item_cache;
 pub mod overlays;
+pub mod patterns;
+pub mod pdf_controls;
+pub mod pdf_outline;
 pub mod preview;
+pub mod text_layout;
+pub mod volume;
+pub mod waveform;
 
 // Re-export commonly used items
 pub use canvas::{render_canvas, render_canvas_area, render_items};
 pub use dock::render_tool_dock;
 pub use overlays::{
-    render_chart_config_modal, render_command_palette, render_create_board_modal,
-    render_footer_bar, render_header_bar, render_settings_modal, render_shortcuts_overlay,
+    render_arrange_toolbar, render_board_passphrase_modal, render_canvas_find_bar,
+    render_chart_config_modal, render_checkpoint_name_bar, render_command_palette,
+    render_comparison_bar, render_context_menu,
+    render_create_board_modal, render_export_dialog, render_footer_bar, render_header_bar, render_import_progress,
+    render_import_report,
+    render_item_tooltip,
+    render_outline_panel, render_perf_hud, render_settings_modal, render_shortcuts_overlay,
+    render_undo_history_panel, render_url_paste_chooser, render_version_history_modal,
 };
 pub use preview::{
-    render_drag_ghost, render_preview_panel, render_search_bar, render_selected_item_label,
-    render_split_drop_zones, render_split_panes, render_splitter, render_tab_bar,
-    render_tab_content,
+    render_canvas_edge_drop_zone, render_drag_ghost, render_preview_panel, render_search_bar,
+    render_selected_item_label, render_split_drop_zones, render_split_panes, render_splitter,
+    render_tab_bar, render_tab_content,
 };
 
 use crate::actions::{
     CancelTextboxEdit, CloseCommandPalette, ClosePreview, CloseTab, CmdPaletteDown, CmdPaletteUp,
-    CommandPalette, DeleteSelected, DeselectAll, DuplicateSelected, GoBack, GoForward, GoHome,
-    MoveTabToOtherPane, NewBoard, NextPage, NextSearchMatch, NextTab, NudgeDown, NudgeLeft,
-    NudgeRight, NudgeUp, OpenFile, OpenSettings, Paste, PdfZoomIn, PdfZoomOut, PdfZoomReset,
-    PrevPage, PrevSearchMatch, PrevTab, Redo, ReopenClosedTab, SaveCode, SelectAll, ShowShortcuts,
-    ToggleCommandPalette, TogglePaneSplit, TogglePreviewSearch, ToggleSplit, ToolArrow, ToolSelect,
-    ToolShape, ToolText, Undo, ZoomIn, ZoomOut, ZoomReset,
+    CollectBoardAssets, CommandPalette, DeleteSelected, DeselectAll, DuplicateSelected,
+    EncryptBoard, ExportBoardHtml, ExportBoardPng, GoBack, GoForward, MoveTabToOtherPane,
+    NextImage, NextPage, NextSearchMatch, NextTab, NudgeDown, NudgeDownLarge, NudgeLeft,
+    NudgeLeftLarge, NudgeRight, NudgeRightLarge, NudgeUp, NudgeUpLarge, OpenFile, OpenSettings,
+    Paste, PauseAllMedia, PdfZoomIn, PdfZoomOut, PdfZoomReset, PrevImage, PrevPage,
+    PrevSearchMatch, PrevTab, RemoveBoardEncryption, ReopenClosedTab, RunCommand, SaveCode,
+    SelectAll, SelectItemDown, SelectItemLeft, SelectItemRight, SelectItemUp, SelectNextItem,
+    SelectPrevItem, ToggleCommandPalette, ToggleMediaPlayback, ToggleOutline, TogglePaneSplit,
+    TogglePreviewSearch, ToggleSplit, ToggleUndoHistory, ToggleVersionHistory, ToolArrow,
+    ToolSelect, ToolShape, ToolText,
 };
 use crate::app::{AppView, Humanboard, SplitDirection};
+use crate::audio_metadata::AudioMetadata;
+use crate::canvas_background::CanvasBackground;
+use crate::constants::CULLING_MARGIN;
+use item_cache::ItemColors;
+use waveform::AudioWaveform;
 use crate::focus::FocusContext;
 use crate::home::render_home_screen;
+use crate::item_navigation::NavDirection;
 use crate::landing::render_landing_page;
 use crate::notifications::render_toast_container;
 use crate::onboarding::render_onboarding_page;
+use crate::image_viewer::IMAGE_PIXELS;
+use crate::pdf::PDF_OUTLINES;
+use crate::preview::in_preview_area;
+use crate::progressive_load::LoadKind;
+use crate::types::{CanvasItem, ItemContent};
 use gpui::DefiniteLength::Fraction;
 use gpui::prelude::FluentBuilder;
 use gpui::*;
 use gpui_component::ActiveTheme as _;
+use std::sync::Arc;
 
 /// UI Font used throughout the application
 pub const UI_FONT: &str = "Iosevka Nerd Font";
@@ -66,6 +106,11 @@ impl Render for Humanboard {
             window.request_animation_frame();
         }
 
+        // Keep rendering until the hover tooltip is due, then fill it in
+        if self.update_hover_tooltip() {
+            window.request_animation_frame();
+        }
+
         // Update modal animations and request next frame if still animating
         if self.ui.modal_animations.update() {
             window.request_animation_frame();
@@ -89,6 +134,14 @@ impl Render for Humanboard {
             self.ui.modal_animations.command_palette = None;
         }
 
+        // Create passphrase prompt inputs (needs window access)
+        self.ensure_passphrase_inputs(window, cx);
+
+        // Open an encrypted board once its key has been derived
+        if self.poll_board_unlock(cx) {
+            window.request_animation_frame();
+        }
+
         // Restore focus to canvas if needed (e.g., after closing command palette via blur)
         self.system.focus.restore_focus_if_needed(window);
 
@@ -106,19 +159,40 @@ impl Render for Humanboard {
         // Process completed background tasks
         self.system.background.process_results();
 
+        // Report a finished HTML export
+        self.poll_export_result(cx);
+
+        // Put the selection on the clipboard once it has been rendered
+        if self.poll_copied_png(cx) {
+            window.request_animation_frame();
+        }
+
+        // Keep the outline in step with board edits
+        self.refresh_outline();
+
+        // Keep the version comparison in step with board edits
+        self.refresh_comparison();
+
         // Check for settings file changes
         self.check_settings_reload(cx);
 
-        // Check for debounced save
+        // Check for debounced save, keeping the preview panel with the board
+        if self.canvas.board.as_ref().is_some_and(|board| board.should_save()) {
+            self.persist_preview_session();
+        }
         if let Some(ref mut board) = self.canvas.board {
             if board.should_save() {
-                if let Err(e) = board.flush_save() {
-                    // Show error toast for save failures with retry option
-                    self.ui.toast_manager
-                        .push(crate::notifications::Toast::error(format!(
-                            "Save failed: {}",
-                            e
-                        )).with_action(crate::notifications::ToastAction::retry()));
+                match board.flush_save() {
+                    Ok(true) => self.refresh_board_thumbnail(false),
+                    Ok(false) => {}
+                    Err(e) => {
+                        // Show error toast for save failures with retry option
+                        self.ui.toast_manager
+                            .push(crate::notifications::Toast::error(format!(
+                                "Save failed: {}",
+                                e
+                            )).with_action(crate::notifications::ToastAction::retry()));
+                    }
                 }
             }
         }
@@ -147,6 +221,7 @@ impl Render for Humanboard {
                     self.settings.theme_index,
                     &self.settings.theme_scroll,
                     self.settings.tab,
+                    self.settings.theme_editor.as_ref(),
                     &self.system.focus.modal,
                     self.ui.modal_animations.settings_opacity(),
                     cx,
@@ -170,6 +245,102 @@ impl Render for Humanboard {
             .when_some(self.chart_config_modal.as_ref(), |d, modal| {
                 d.child(render_chart_config_modal(modal, cx))
             })
+            // Version history browser
+            .when_some(self.ui.version_history.as_ref(), |d, modal| {
+                d.child(render_version_history_modal(modal, cx))
+            })
+            // Align/distribute toolbar for multi-selection
+            .when(
+                self.canvas.board.is_some()
+                    && self.canvas.selected_items.len() >= crate::arrange::MIN_ALIGN_ITEMS,
+                |d| d.child(render_arrange_toolbar(self.canvas.selected_items.len(), cx)),
+            )
+            // Find bar over the canvas
+            .when_some(
+                self.canvas.find_input.as_ref().filter(|_| self.canvas.board.is_some()),
+                |d, input| d.child(render_canvas_find_bar(input, &self.canvas.find, cx)),
+            )
+            // Checkpoint name bar
+            .when_some(
+                self.ui.checkpoint_name.as_ref().filter(|_| self.canvas.board.is_some()),
+                |d, input| d.child(render_checkpoint_name_bar(input, cx)),
+            )
+            // Export dialog for a region of the canvas
+            .when_some(
+                self.ui.export_dialog.as_ref().filter(|_| self.canvas.board.is_some()),
+                |d, dialog| {
+                    d.child(render_export_dialog(
+                        dialog,
+                        self.export_dialog_region(),
+                        !self.canvas.selected_items.is_empty(),
+                        cx,
+                    ))
+                },
+            )
+            // What the last import skipped
+            .when_some(
+                self.ui.import_report.as_ref().filter(|_| self.canvas.board.is_some()),
+                |d, report| d.child(render_import_report(report, cx)),
+            )
+            // Progress of slow file imports
+            .when_some(self.file_import_progress(), |d, (done, total)| {
+                d.child(render_import_progress(done, total, cx))
+            })
+            // Chooser under a pasted URL
+            .when_some(self.url_paste_chooser_anchor(), |d, (chooser, anchor)| {
+                d.child(render_url_paste_chooser(chooser, anchor, cx))
+            })
+            // Comparison with a saved version
+            .when_some(
+                self.canvas.comparison.as_ref().filter(|_| self.canvas.board.is_some()),
+                |d, comparison| d.child(render_comparison_bar(comparison, cx)),
+            )
+            // Outline side panel
+            .when_some(
+                self.ui.outline.as_ref().filter(|_| self.canvas.board.is_some()),
+                |d, panel| d.child(render_outline_panel(panel, &self.canvas.selected_items, cx)),
+            )
+            // Undo history side panel
+            .when_some(
+                self.ui
+                    .undo_history
+                    .as_ref()
+                    .zip(self.canvas.board.as_ref()),
+                |d, (panel, board)| {
+                    d.child(render_undo_history_panel(
+                        panel,
+                        board.undo_steps(),
+                        board.history_position(),
+                        cx,
+                    ))
+                },
+            )
+            // Hover tooltip with item details
+            .when_some(
+                self.ui
+                    .hover
+                    .as_ref()
+                    .and_then(|hover| Some((hover.tooltip.as_ref()?, hover.position))),
+                |d, (tooltip, position)| d.child(render_item_tooltip(tooltip, position, cx)),
+            )
+            // Frame rate and media memory readout
+            .when(self.ui.show_perf_hud && self.canvas.board.is_some(), |d| {
+                d.child(render_perf_hud(
+                    self.calculate_fps(),
+                    &self.system.perf_monitor,
+                    &self.system.memory.stats(),
+                    cx,
+                ))
+            })
+            // Right-click menu
+            .when_some(
+                self.ui.context_menu.as_ref().filter(|_| self.canvas.board.is_some()),
+                |d, menu| d.child(render_context_menu(menu, cx)),
+            )
+            // Passphrase prompt for encrypted boards
+            .when_some(self.ui.board_passphrase.as_ref(), |d, prompt| {
+                d.child(render_board_passphrase_modal(prompt, cx))
+            })
             // Toast notifications
             .when(!toasts.is_empty(), |d| {
                 d.child(render_toast_container(&toasts, reduce_motion, cx))
@@ -215,10 +386,9 @@ impl Humanboard {
                     }),
                 )
             })
-            .on_action(
-                cx.listener(|this, _: &NewBoard, window, cx| this.create_new_board(window, cx)),
-            )
-            .on_action(cx.listener(|this, _: &ShowShortcuts, _, cx| this.toggle_shortcuts(cx)))
+            .on_action(cx.listener(|this, action: &RunCommand, window, cx| {
+                this.run_registered_command(&action.id, window, cx)
+            }))
             .on_action(
                 cx.listener(|this, _: &OpenSettings, window, cx| this.toggle_settings(window, cx)),
             )
@@ -258,19 +428,58 @@ impl Humanboard {
         // Poll for file picker results (from Cmd+O)
         if let Some(rx) = &self.canvas.file_drop_rx {
             if let Ok((pos, paths)) = rx.try_recv() {
-                if let Some(ref mut board) = self.canvas.board {
-                    let errors = board.handle_file_drop(pos, paths);
-                    // Show toast notifications for any file copy errors
-                    for error in errors {
-                        self.ui.toast_manager.push(crate::notifications::Toast::error(error)
-                            .with_action(crate::notifications::ToastAction::retry()));
-                    }
-                }
+                self.import_dropped_files(pos, paths, cx);
                 self.canvas.file_drop_rx = None;
                 cx.notify();
             }
         }
 
+        // List quick-open files once the folders have been scanned
+        self.poll_quick_open_scan(cx);
+
+        // Poll for a located file (from a missing-file badge) and refresh badges
+        self.poll_relink_result(cx);
+        self.refresh_missing_files(false, cx);
+        if self.poll_missing_files(cx) {
+            window.request_animation_frame();
+        }
+
+        // Add the items of a finished import
+        self.poll_board_import(window, cx);
+
+        // Apply a settings profile once its files are written
+        self.poll_profile_import(cx);
+
+        // Apply an image picked as the canvas background
+        self.poll_background_image(cx);
+
+        // Add a PDF page placed from the preview once it has been rendered
+        if self.poll_placed_pdf_page(cx) {
+            window.request_animation_frame();
+        }
+
+        // Give newly added images their real size once it has been read
+        self.measure_new_images(cx);
+        if self.poll_image_sizes(cx) {
+            window.request_animation_frame();
+        }
+
+        // Add dropped files once they have been read
+        if self.poll_file_imports(cx) {
+            window.request_animation_frame();
+        }
+
+        // Fill in pasted URLs once their page or image has been fetched
+        if self.poll_url_pastes(cx) {
+            window.request_animation_frame();
+        }
+
+        // New media may only start loading while this frame has time left
+        self.canvas.loader.begin_frame();
+
+        // Reopen the preview panel the board was left with
+        self.restore_preview_session(window, cx);
+
         // Ensure WebViews and editors are created if preview is active
         if self.preview.panel.is_some() {
             let pdf_errors = self.ensure_pdf_webview(window, cx);
@@ -279,14 +488,23 @@ impl Humanboard {
                     .with_action(crate::notifications::ToastAction::reload_webview()));
             }
             self.ensure_code_editors(window, cx);
+            self.check_code_files(window, cx);
+            self.ensure_code_outlines(cx);
             self.ensure_preview_table_states(window, cx);
+            self.ensure_image_pixels();
+            self.ensure_data_views(cx);
         }
 
-        // Ensure YouTube WebViews are created for any YouTube items
-        let youtube_errors = self.ensure_youtube_webviews(window, cx);
-        for error in youtube_errors {
-            self.ui.toast_manager.push(crate::notifications::Toast::error(error)
-                .with_action(crate::notifications::ToastAction::reload_webview()));
+        // Refresh cards whose files changed on disk
+        self.refresh_card_files(window, cx);
+
+        // Ensure YouTube, Vimeo, SoundCloud and Spotify WebViews are created
+        for kind in crate::app::EmbedKind::ALL {
+            let embed_errors = self.ensure_embed_webviews(kind, window, cx);
+            for error in embed_errors {
+                self.ui.toast_manager.push(crate::notifications::Toast::error(error)
+                    .with_action(crate::notifications::ToastAction::reload_webview()));
+            }
         }
 
         // Ensure Audio WebViews are created for any Audio items
@@ -327,12 +545,15 @@ impl Humanboard {
         let fps = self.calculate_fps();
         let frame_count = self.system.frame_count;
         let selected_items = self.canvas.selected_items.clone();
+        let missing_files = self.canvas.missing_files.clone();
+        let updated_cards = self.updated_card_flashes();
+        let search_highlight = self.search_highlight();
         let selected_item_name = if self.canvas.selected_items.len() == 1 {
             self.canvas.selected_items.iter().next().and_then(|&id| {
                 self.canvas.board
                     .as_ref()
                     .and_then(|b| b.items.iter().find(|i| i.id == id))
-                    .map(|i| i.content.display_name())
+                    .map(|i| i.content.display_name_with(&self.system.audio_metadata))
             })
         } else if self.canvas.selected_items.len() > 1 {
             Some(format!("{} items selected", self.canvas.selected_items.len()))
@@ -346,6 +567,14 @@ impl Humanboard {
             _ => None,
         };
 
+        // Lasso selection path
+        let lasso: Vec<Point<Pixels>> = self
+            .canvas
+            .input_state
+            .lasso_points()
+            .map(<[_]>::to_vec)
+            .unwrap_or_default();
+
         // Drawing preview state (for TextBox, Shape, Arrow while dragging)
         let drawing_preview = match (self.canvas.input_state.drawing_start(), self.canvas.input_state.drawing_current()) {
             (Some(start), Some(current)) => Some((start, current, self.tools.selected)),
@@ -359,34 +588,184 @@ impl Humanboard {
             None
         };
 
-        // Extract preview info
-        let preview_info = self
-            .preview.panel
+        // Compute canvas viewport size for culling (accounts for dock, header, footer, preview)
+        let canvas_viewport_size = self.canvas_viewport_size(window);
+
+        // Only items near the viewport get elements; the spatial index finds them
+        let mut visible_items: Vec<CanvasItem> = self
+            .canvas
+            .board
             .as_ref()
-            .map(|p| (p, p.split, p.size, &p.tabs, p.active_tab, p.is_pane_split));
+            .map(|board| {
+                let viewport = (
+                    f32::from(canvas_viewport_size.width),
+                    f32::from(canvas_viewport_size.height),
+                );
+                board
+                    .items_in_viewport(viewport, CULLING_MARGIN)
+                    .into_iter()
+                    .cloned()
+                    .collect()
+            })
+            .unwrap_or_default();
 
-        // Compute canvas viewport size for culling (accounts for dock, header, footer, preview)
-        let window_bounds = window.bounds();
-        let canvas_viewport_size = {
-            use crate::constants::{DOCK_WIDTH, HEADER_HEIGHT, FOOTER_HEIGHT};
-            let available_width = f32::from(window_bounds.size.width) - DOCK_WIDTH;
-            let available_height = f32::from(window_bounds.size.height) - HEADER_HEIGHT - FOOTER_HEIGHT;
-
-            if let Some(ref preview) = self.preview.panel {
-                match preview.split {
-                    SplitDirection::Vertical => {
-                        let canvas_width = available_width * (1.0 - preview.size);
-                        gpui::size(gpui::px(canvas_width), gpui::px(available_height))
-                    }
-                    SplitDirection::Horizontal => {
-                        let canvas_height = available_height * (1.0 - preview.size);
-                        gpui::size(gpui::px(available_width), gpui::px(canvas_height))
+        // Large images are drawn from a cached copy sized for the screen.
+        // Images that haven't had their turn to load get no source and draw
+        // a placeholder.
+        let scale_factor = window.scale_factor();
+        let image_sources: std::collections::HashMap<u64, std::path::PathBuf> = visible_items
+            .iter()
+            .filter_map(|item| match &item.content {
+                ItemContent::Image(path) => {
+                    if !self.canvas.loader.try_load(item.id, LoadKind::Image) {
+                        return None;
                     }
+                    let display_px = item.size.0.max(item.size.1) * zoom * scale_factor;
+                    let source = self.system.image_cache.source_for(
+                        path,
+                        display_px,
+                        &self.system.background,
+                    );
+                    Some((item.id, source))
+                }
+                _ => None,
+            })
+            .collect();
+
+        // Audio cards draw their waveform once the file has been decoded,
+        // with the playhead wherever the player last said it was
+        let audio_playing = self.poll_media_positions(cx);
+        let audio_waveforms: std::collections::HashMap<u64, AudioWaveform> = visible_items
+            .iter()
+            .filter_map(|item| match &item.content {
+                ItemContent::Audio(path) => {
+                    let waveform = self.system.waveforms.get(path, &self.system.background)?;
+                    let playhead = self
+                        .webviews
+                        .media_positions
+                        .get(&item.id)
+                        .map(|position| position.fraction);
+                    Some((item.id, AudioWaveform { waveform, playhead }))
+                }
+                _ => None,
+            })
+            .collect();
+
+        // Audio cards are labeled from their tags once they have been read
+        let audio_metadata: std::collections::HashMap<u64, Arc<AudioMetadata>> = visible_items
+            .iter()
+            .filter_map(|item| match &item.content {
+                ItemContent::Audio(path) => Some((
+                    item.id,
+                    self.system.audio_metadata.get(path, &self.system.background)?,
+                )),
+                _ => None,
+            })
+            .collect();
+
+        // PDFs whose files changed get a new thumbnail
+        self.refresh_pdf_thumbnails(&mut visible_items, window, cx);
+
+        // PDFs show their current page once it has been rendered. Cards on
+        // the first page show their thumbnail meanwhile, later pages nothing.
+        let mut pdf_page_counts: std::collections::HashMap<u64, usize> =
+            std::collections::HashMap::new();
+        let pdf_pages = &self.system.pdf_pages;
+        for item in &mut visible_items {
+            if let ItemContent::Pdf {
+                path,
+                thumbnail,
+                page,
+            } = &mut item.content
+            {
+                match pdf_pages.get(path, *page, &self.system.background) {
+                    Some(rendered) => *thumbnail = Some(rendered.image.clone()),
+                    None if *page > 0 => *thumbnail = None,
+                    None => {}
+                }
+                // Known from any rendered page, so the controls stay put
+                // while the next page renders
+                if let Some(page_count) = pdf_pages.page_count(path) {
+                    pdf_page_counts.insert(item.id, page_count);
                 }
-            } else {
-                gpui::size(gpui::px(available_width), gpui::px(available_height))
             }
-        };
+        }
+
+        // PDFs show their generic card until their thumbnail's turn comes
+        for item in &mut visible_items {
+            if let ItemContent::Pdf { thumbnail, .. } = &mut item.content {
+                if thumbnail.is_some() && !self.canvas.loader.try_load(item.id, LoadKind::PdfThumbnail) {
+                    *thumbnail = None;
+                }
+            }
+        }
+
+        // Keep rendering until generated thumbnails can replace the originals,
+        // every deferred item has loaded, waveforms, tags and PDF outlines are
+        // read, PDF pages and thumbnails are rendered, preview images are
+        // decoded and data tabs have their rows, and while audio plays so its
+        // playhead moves
+        if self.system.image_cache.has_pending()
+            || self.canvas.loader.has_deferred()
+            || self.system.waveforms.has_pending()
+            || self.system.audio_metadata.has_pending()
+            || self.system.pdf_pages.has_pending()
+            || self.system.pdf_thumbnails.has_pending()
+            || PDF_OUTLINES.has_pending()
+            || IMAGE_PIXELS.has_pending()
+            || self.has_pending_data_rows()
+            || audio_playing
+            || !updated_cards.is_empty()
+            || search_highlight.is_some()
+        {
+            window.request_animation_frame();
+        }
+
+        // Canvas background style picked for this board
+        let background = self
+            .canvas
+            .board
+            .as_ref()
+            .map(|board| board.background())
+            .unwrap_or(CanvasBackground::Plain);
+
+        // Free off-screen media when over the memory budget
+        self.track_media_memory(&visible_items, &image_sources, window, cx);
+
+        // Extract preview info
+        let preview_info = self
+            .preview.panel
+            .as_ref()
+            .map(|p| (p, p.split, p.size, &p.panes[0].tabs, p.panes[0].active_tab, p.is_split()));
+
+        // Wiki links between markdown cards, re-parsed only for edited cards
+        self.canvas.wiki_links.update(&items);
+        let wiki_links = self.canvas.wiki_links.edges().to_vec();
+        let backlinks: std::collections::HashMap<u64, Vec<String>> = visible_items
+            .iter()
+            .filter(|item| item.is_expanded_markdown())
+            .map(|item| (item.id, self.canvas.wiki_links.backlink_titles(item.id, &items)))
+            .filter(|(_, titles)| !titles.is_empty())
+            .collect();
+
+        // Static items are drawn from cached views until they change
+        // Text box lines are split once and reused until the text or zoom changes
+        let text_layouts = self.canvas.text_layouts.prepare(&visible_items, zoom);
+        // Expanded code cards read their excerpt again only when the file changes
+        let code_excerpts = self.canvas.code_excerpts.prepare(&visible_items);
+
+        let item_views = self.canvas.render_cache.prepare(
+            &visible_items,
+            &data_sources,
+            &text_layouts,
+            &code_excerpts,
+            &backlinks,
+            zoom,
+            &selected_items,
+            self.textbox.editing_id,
+            ItemColors::from_theme(cx.theme()),
+            cx,
+        );
 
         // Check if we should block canvas keyboard shortcuts
         // When input is active, we use a different key context to avoid shortcut conflicts
@@ -406,19 +785,13 @@ impl Humanboard {
                 cx.listener(|this, event: &MouseDownEvent, window, cx| {
                     // Check if we're clicking in the preview panel area
                     let in_preview_area = if let Some(ref preview) = this.preview.panel {
-                        let bounds = window.bounds();
-                        match preview.split {
-                            SplitDirection::Vertical => {
-                                let preview_start_x =
-                                    f32::from(bounds.size.width) * (1.0 - preview.size);
-                                f32::from(event.position.x) > preview_start_x
-                            }
-                            SplitDirection::Horizontal => {
-                                let preview_start_y =
-                                    f32::from(bounds.size.height) * (1.0 - preview.size);
-                                f32::from(event.position.y) > preview_start_y
-                            }
-                        }
+                        let size = window.bounds().size;
+                        in_preview_area(
+                            (f32::from(event.position.x), f32::from(event.position.y)),
+                            (f32::from(size.width), f32::from(size.height)),
+                            preview.split,
+                            preview.size,
+                        )
                     } else {
                         false
                     };
@@ -434,26 +807,93 @@ impl Humanboard {
                 }),
             )
             .on_mouse_up(MouseButton::Left, cx.listener(Humanboard::handle_mouse_up))
+            .on_mouse_down(
+                MouseButton::Middle,
+                cx.listener(Humanboard::handle_middle_mouse_down),
+            )
+            .on_mouse_up(
+                MouseButton::Middle,
+                cx.listener(Humanboard::handle_middle_mouse_up),
+            )
+            .on_mouse_down(
+                MouseButton::Right,
+                cx.listener(Humanboard::handle_right_mouse_down),
+            )
             .on_mouse_move(cx.listener(Humanboard::handle_mouse_move))
+            .on_key_down(cx.listener(|this, event: &KeyDownEvent, _, cx| {
+                this.handle_key_down(event, cx)
+            }))
+            .on_key_up(cx.listener(|this, event: &KeyUpEvent, _, cx| this.handle_key_up(event, cx)))
+            // Hand cursor while Space is held, closed while panning
+            .when(self.canvas.input_state.is_space_panning(), |d| {
+                d.cursor(CursorStyle::OpenHand)
+            })
+            .when(self.canvas.input_state.is_canvas_panning(), |d| {
+                d.cursor(CursorStyle::ClosedHand)
+            })
             .on_scroll_wheel(cx.listener(Humanboard::handle_scroll))
-            .on_action(cx.listener(|this, _: &GoHome, _, cx| this.go_home(cx)))
+            .on_action(cx.listener(|this, action: &RunCommand, window, cx| {
+                this.run_registered_command(&action.id, window, cx)
+            }))
             .on_action(cx.listener(|this, _: &OpenFile, window, cx| this.open_file(window, cx)))
-            .on_action(cx.listener(|this, _: &ZoomIn, window, cx| this.zoom_in(window, cx)))
-            .on_action(cx.listener(|this, _: &ZoomOut, window, cx| this.zoom_out(window, cx)))
-            .on_action(cx.listener(|this, _: &ZoomReset, _, cx| this.zoom_reset(cx)))
             .on_action(cx.listener(|this, _: &DeleteSelected, _, cx| this.delete_selected(cx)))
             .on_action(
                 cx.listener(|this, _: &DuplicateSelected, _, cx| this.duplicate_selected(cx)),
             )
             .on_action(cx.listener(|this, _: &SelectAll, _, cx| this.select_all(cx)))
             .on_action(cx.listener(|this, _: &DeselectAll, _, cx| this.deselect_all(cx)))
+            .on_action(cx.listener(|this, _: &crate::actions::Copy, _, cx| this.copy_selected(cx)))
             .on_action(cx.listener(|this, _: &Paste, window, cx| this.paste(window, cx)))
-            .on_action(cx.listener(|this, _: &NudgeUp, _, cx| this.nudge_up(cx)))
-            .on_action(cx.listener(|this, _: &NudgeDown, _, cx| this.nudge_down(cx)))
-            .on_action(cx.listener(|this, _: &NudgeLeft, _, cx| this.nudge_left(cx)))
-            .on_action(cx.listener(|this, _: &NudgeRight, _, cx| this.nudge_right(cx)))
-            .on_action(cx.listener(|this, _: &Undo, _, cx| this.undo(cx)))
-            .on_action(cx.listener(|this, _: &Redo, _, cx| this.redo(cx)))
+            .on_action(cx.listener(|this, _: &SelectNextItem, window, cx| {
+                this.select_next_item(false, window, cx)
+            }))
+            .on_action(cx.listener(|this, _: &SelectPrevItem, window, cx| {
+                this.select_next_item(true, window, cx)
+            }))
+            .on_action(cx.listener(|this, _: &SelectItemUp, window, cx| {
+                this.select_item_in_direction(NavDirection::Up, window, cx)
+            }))
+            .on_action(cx.listener(|this, _: &SelectItemDown, window, cx| {
+                this.select_item_in_direction(NavDirection::Down, window, cx)
+            }))
+            .on_action(cx.listener(|this, _: &SelectItemLeft, window, cx| {
+                this.select_item_in_direction(NavDirection::Left, window, cx)
+            }))
+            .on_action(cx.listener(|this, _: &SelectItemRight, window, cx| {
+                this.select_item_in_direction(NavDirection::Right, window, cx)
+            }))
+            .on_action(cx.listener(|this, _: &NudgeUp, _, cx| this.nudge_up(false, cx)))
+            .on_action(cx.listener(|this, _: &NudgeDown, _, cx| this.nudge_down(false, cx)))
+            .on_action(cx.listener(|this, _: &NudgeLeft, _, cx| this.nudge_left(false, cx)))
+            .on_action(cx.listener(|this, _: &NudgeRight, _, cx| this.nudge_right(false, cx)))
+            .on_action(cx.listener(|this, _: &NudgeUpLarge, _, cx| this.nudge_up(true, cx)))
+            .on_action(cx.listener(|this, _: &NudgeDownLarge, _, cx| this.nudge_down(true, cx)))
+            .on_action(cx.listener(|this, _: &NudgeLeftLarge, _, cx| this.nudge_left(true, cx)))
+            .on_action(cx.listener(|this, _: &NudgeRightLarge, _, cx| this.nudge_right(true, cx)))
+            .on_action(cx.listener(|this, _: &ToggleVersionHistory, _, cx| {
+                this.toggle_version_history(cx)
+            }))
+            .on_action(cx.listener(|this, _: &ToggleOutline, _, cx| this.toggle_outline(cx)))
+            .on_action(cx.listener(|this, _: &ToggleUndoHistory, _, cx| {
+                this.toggle_undo_history(cx)
+            }))
+            .on_action(cx.listener(|this, _: &ToggleMediaPlayback, _, cx| {
+                this.toggle_selected_media(cx);
+            }))
+            .on_action(cx.listener(|this, _: &PauseAllMedia, _, cx| this.pause_all_media(cx)))
+            .on_action(cx.listener(|this, _: &CollectBoardAssets, _, cx| {
+                this.collect_board_assets(cx)
+            }))
+            .on_action(cx.listener(|this, _: &EncryptBoard, _, cx| {
+                this.show_encrypt_board_prompt(cx)
+            }))
+            .on_action(cx.listener(|this, _: &RemoveBoardEncryption, _, cx| {
+                this.remove_board_encryption(cx)
+            }))
+            .on_action(cx.listener(|this, _: &ExportBoardHtml, _, cx| {
+                this.export_board_html(cx)
+            }))
+            .on_action(cx.listener(|this, _: &ExportBoardPng, _, cx| this.export_board_png(cx)))
             .on_action(cx.listener(|this, _: &SaveCode, _, cx| this.save_code(cx)))
             .on_action(cx.listener(|this, _: &ClosePreview, _, cx| this.close_preview(cx)))
             .on_action(cx.listener(|this, _: &ToggleSplit, _, cx| this.toggle_split_direction(cx)))
@@ -462,22 +902,31 @@ impl Humanboard {
             .on_action(cx.listener(|this, _: &PdfZoomIn, _, cx| this.pdf_zoom_in(cx)))
             .on_action(cx.listener(|this, _: &PdfZoomOut, _, cx| this.pdf_zoom_out(cx)))
             .on_action(cx.listener(|this, _: &PdfZoomReset, _, cx| this.pdf_zoom_reset(cx)))
+            .on_action(cx.listener(|this, _: &NextImage, window, cx| {
+                this.step_preview_image(false, window, cx)
+            }))
+            .on_action(cx.listener(|this, _: &PrevImage, window, cx| {
+                this.step_preview_image(true, window, cx)
+            }))
             .on_action(cx.listener(|this, _: &NextTab, _, cx| this.next_tab(cx)))
             .on_action(cx.listener(|this, _: &PrevTab, _, cx| this.prev_tab(cx)))
             .on_action(cx.listener(|this, _: &CloseTab, _, cx| this.close_current_tab(cx)))
             .on_action(cx.listener(|this, _: &ReopenClosedTab, _, cx| this.reopen_closed_tab(cx)))
-            .on_action(cx.listener(|this, _: &GoBack, _, cx| this.go_back(cx)))
-            .on_action(cx.listener(|this, _: &GoForward, _, cx| this.go_forward(cx)))
+            .on_action(cx.listener(|this, _: &GoBack, window, cx| this.go_back(window, cx)))
+            .on_action(cx.listener(|this, _: &GoForward, window, cx| this.go_forward(window, cx)))
             .on_action(cx.listener(|this, _: &TogglePreviewSearch, window, cx| {
-                this.toggle_preview_search(window, cx)
+                this.toggle_find(window, cx)
+            }))
+            .on_action(cx.listener(|this, _: &NextSearchMatch, window, cx| {
+                this.next_find_match(window, cx)
+            }))
+            .on_action(cx.listener(|this, _: &PrevSearchMatch, window, cx| {
+                this.prev_find_match(window, cx)
             }))
-            .on_action(cx.listener(|this, _: &NextSearchMatch, _, cx| this.next_search_match(cx)))
-            .on_action(cx.listener(|this, _: &PrevSearchMatch, _, cx| this.prev_search_match(cx)))
             .on_action(cx.listener(|this, _: &TogglePaneSplit, _, cx| this.toggle_pane_split(cx)))
             .on_action(
                 cx.listener(|this, _: &MoveTabToOtherPane, _, cx| this.move_tab_to_other_pane(cx)),
             )
-            .on_action(cx.listener(|this, _: &ShowShortcuts, _, cx| this.toggle_shortcuts(cx)))
             .on_action(cx.listener(|this, _: &CommandPalette, window, cx| {
                 this.show_command_palette(window, cx)
             }))
@@ -560,23 +1009,12 @@ impl Humanboard {
                     point(px(canvas_center_x), px(canvas_center_y))
                 };
 
-                let count = all_paths.len();
-                if let Some(ref mut board) = this.canvas.board {
-                    board.handle_file_drop(drop_pos, all_paths);
-                }
-                // Show toast notification
-                let msg = if count == 1 {
-                    "Added 1 item".to_string()
-                } else {
-                    format!("Added {} items", count)
-                };
-                this.show_toast(crate::notifications::Toast::success(msg));
-                cx.notify();
+                this.import_dropped_files(drop_pos, all_paths, cx);
             }));
 
         let selected_tool = self.tools.selected;
         let content = match preview_info {
-            Some((preview_ref, split, size, tabs, active_tab, is_pane_split)) => {
+            Some((preview_ref, split, size, tabs, active_tab, is_split)) => {
                 let canvas_size = 1.0 - size;
                 let preview_size = size;
 
@@ -608,15 +1046,34 @@ impl Humanboard {
                                         .flex_shrink_0()
                                         .w(Fraction(canvas_size))
                                         .h_full()
+                                        .relative()
                                         .child(render_canvas_area(
                                             canvas_offset,
                                             zoom,
+                                            &background,
                                             &items,
+                                            &visible_items,
                                             &selected_items,
+                                            &missing_files,
+                                            &updated_cards,
+                                            search_highlight,
+                                            &self.canvas.find,
+                                            self.canvas.comparison.as_ref().map(|c| &c.diff),
                                             &self.webviews.youtube,
+                                            &self.webviews.vimeo,
+                                            &self.webviews.soundcloud,
+                                            &self.webviews.spotify,
                                             &self.webviews.audio,
                                             &self.webviews.video,
                                             &data_sources,
+                                            &image_sources,
+                                            &audio_waveforms,
+                                            &audio_metadata,
+                                            &pdf_page_counts,
+                                            self.webviews.playlist.as_ref(),
+                                            &item_views,
+                                            &text_layouts,
+                                            &wiki_links,
                                             &self.table.scroll_states,
                                             &self.table.table_states,
                                             self.textbox.editing_id,
@@ -624,10 +1081,20 @@ impl Humanboard {
                                             self.table.editing_cell,
                                             self.table.cell_input.as_ref(),
                                             marquee,
+                                            &lasso,
                                             drawing_preview,
+                                            &self.canvas.alignment_guides,
                                             canvas_viewport_size,
                                             cx,
-                                        )),
+                                        ))
+                                        // Drop strip for splitting the panel while dragging a tab
+                                        .when(self.preview.dragging_tab.is_some(), |d| {
+                                            d.child(render_canvas_edge_drop_zone(
+                                                SplitDirection::Vertical,
+                                                self.preview.tab_drag_split_zone,
+                                                cx,
+                                            ))
+                                        }),
                                 )
                                 .child(render_splitter(SplitDirection::Vertical, cx))
                                 .child({
@@ -641,17 +1108,20 @@ impl Humanboard {
                                         .flex_col()
                                         .overflow_hidden();
 
-                                    if is_pane_split {
+                                    if is_split {
                                         // Render split panes
                                         container.child(render_split_panes(
                                             preview_ref,
-                                            &self.preview.left_tab_scroll,
-                                            &self.preview.right_tab_scroll,
+                                            &self.preview.tab_scrolls,
                                             self.preview.dragging_tab,
                                             self.preview.tab_drag_target,
+                                            self.preview.tab_drag_from_pane,
+                                            self.preview.tab_drag_target_pane,
                                             self.preview.search.as_ref(),
+                                            self.preview_replace_input(),
                                             self.preview.search_matches.len(),
                                             self.preview.search_current,
+                                            self.pdf_search_selection().is_some(),
                                             self.preview.tab_drag_split_zone,
                                             cx,
                                         ))
@@ -664,10 +1134,11 @@ impl Humanboard {
                                             .child(render_tab_bar(
                                                 tabs,
                                                 active_tab,
-                                                &self.preview.left_tab_scroll,
+                                                &self.preview.tab_scrolls[0],
                                                 self.preview.dragging_tab,
                                                 self.preview.tab_drag_target,
-                                                true, // is_left_pane (single pane = left)
+                                                &preview_ref.panes[0].history,
+                                                0, // pane (single pane = primary)
                                                 cx,
                                             ))
                                             // Search bar (when active)
@@ -676,8 +1147,10 @@ impl Humanboard {
                                                 |d, search_input| {
                                                     d.child(render_search_bar(
                                                         search_input,
+                                                        self.preview_replace_input(),
                                                         self.preview.search_matches.len(),
                                                         self.preview.search_current,
+                                                        self.pdf_search_selection().is_some(),
                                                         cx,
                                                     ))
                                                 },
@@ -709,7 +1182,7 @@ impl Humanboard {
                                                     // Show drop zones when dragging a tab
                                                     .when(dragging.is_some(), |d| {
                                                         d.child(render_split_drop_zones(
-                                                            split_zone, cx,
+                                                            0, split_zone, cx,
                                                         ))
                                                     }),
                                             )
@@ -740,15 +1213,34 @@ impl Humanboard {
                                         .flex_shrink_0()
                                         .h(Fraction(canvas_size))
                                         .w_full()
+                                        .relative()
                                         .child(render_canvas_area(
                                             canvas_offset,
                                             zoom,
+                                            &background,
                                             &items,
+                                            &visible_items,
                                             &selected_items,
+                                            &missing_files,
+                                            &updated_cards,
+                                            search_highlight,
+                                            &self.canvas.find,
+                                            self.canvas.comparison.as_ref().map(|c| &c.diff),
                                             &self.webviews.youtube,
+                                            &self.webviews.vimeo,
+                                            &self.webviews.soundcloud,
+                                            &self.webviews.spotify,
                                             &self.webviews.audio,
                                             &self.webviews.video,
                                             &data_sources,
+                                            &image_sources,
+                                            &audio_waveforms,
+                                            &audio_metadata,
+                                            &pdf_page_counts,
+                                            self.webviews.playlist.as_ref(),
+                                            &item_views,
+                                            &text_layouts,
+                                            &wiki_links,
                                             &self.table.scroll_states,
                                             &self.table.table_states,
                                             self.textbox.editing_id,
@@ -756,10 +1248,20 @@ impl Humanboard {
                                             self.table.editing_cell,
                                             self.table.cell_input.as_ref(),
                                             marquee,
+                                            &lasso,
                                             drawing_preview,
+                                            &self.canvas.alignment_guides,
                                             canvas_viewport_size,
                                             cx,
-                                        )),
+                                        ))
+                                        // Drop strip for splitting the panel while dragging a tab
+                                        .when(self.preview.dragging_tab.is_some(), |d| {
+                                            d.child(render_canvas_edge_drop_zone(
+                                                SplitDirection::Horizontal,
+                                                self.preview.tab_drag_split_zone,
+                                                cx,
+                                            ))
+                                        }),
                                 )
                                 .child(render_splitter(SplitDirection::Horizontal, cx))
                                 .child({
@@ -773,17 +1275,20 @@ impl Humanboard {
                                         .flex_col()
                                         .overflow_hidden();
 
-                                    if is_pane_split {
+                                    if is_split {
                                         // Render split panes
                                         container.child(render_split_panes(
                                             preview_ref,
-                                            &self.preview.left_tab_scroll,
-                                            &self.preview.right_tab_scroll,
+                                            &self.preview.tab_scrolls,
                                             self.preview.dragging_tab,
                                             self.preview.tab_drag_target,
+                                            self.preview.tab_drag_from_pane,
+                                            self.preview.tab_drag_target_pane,
                                             self.preview.search.as_ref(),
+                                            self.preview_replace_input(),
                                             self.preview.search_matches.len(),
                                             self.preview.search_current,
+                                            self.pdf_search_selection().is_some(),
                                             self.preview.tab_drag_split_zone,
                                             cx,
                                         ))
@@ -796,10 +1301,11 @@ impl Humanboard {
                                             .child(render_tab_bar(
                                                 tabs,
                                                 active_tab,
-                                                &self.preview.left_tab_scroll,
+                                                &self.preview.tab_scrolls[0],
                                                 self.preview.dragging_tab,
                                                 self.preview.tab_drag_target,
-                                                true, // is_left_pane (single pane = left)
+                                                &preview_ref.panes[0].history,
+                                                0, // pane (single pane = primary)
                                                 cx,
                                             ))
                                             // Search bar (when active)
@@ -808,8 +1314,10 @@ impl Humanboard {
                                                 |d, search_input| {
                                                     d.child(render_search_bar(
                                                         search_input,
+                                                        self.preview_replace_input(),
                                                         self.preview.search_matches.len(),
                                                         self.preview.search_current,
+                                                        self.pdf_search_selection().is_some(),
                                                         cx,
                                                     ))
                                                 },
@@ -841,7 +1349,7 @@ impl Humanboard {
                                                     // Show drop zones when dragging a tab
                                                     .when(dragging.is_some(), |d| {
                                                         d.child(render_split_drop_zones(
-                                                            split_zone, cx,
+                                                            0, split_zone, cx,
                                                         ))
                                                     }),
                                             )
@@ -866,12 +1374,30 @@ impl Humanboard {
                 .child(div().flex_1().h_full().child(render_canvas_area(
                     canvas_offset,
                     zoom,
+                    &background,
                     &items,
+                    &visible_items,
                     &selected_items,
+                    &missing_files,
+                    &updated_cards,
+                    search_highlight,
+                    &self.canvas.find,
+                    self.canvas.comparison.as_ref().map(|c| &c.diff),
                     &self.webviews.youtube,
+                    &self.webviews.vimeo,
+                    &self.webviews.soundcloud,
+                    &self.webviews.spotify,
                     &self.webviews.audio,
                     &self.webviews.video,
                     &data_sources,
+                    &image_sources,
+                    &audio_waveforms,
+                    &audio_metadata,
+                    &pdf_page_counts,
+                    self.webviews.playlist.as_ref(),
+                    &item_views,
+                    &text_layouts,
+                    &wiki_links,
                     &self.table.scroll_states,
                     &self.table.table_states,
                     self.textbox.editing_id,
@@ -879,7 +1405,9 @@ impl Humanboard {
                     self.table.editing_cell,
                     self.table.cell_input.as_ref(),
                     marquee,
+                    &lasso,
                     drawing_preview,
+                    &self.canvas.alignment_guides,
                     canvas_viewport_size,
                     cx,
                 ))),
@@ -910,7 +1438,11 @@ impl Humanboard {
         let content =
             if let (Some(drag_idx), Some(drag_pos)) = (self.preview.dragging_tab, self.preview.tab_drag_position) {
                 if let Some(ref preview) = self.preview.panel {
-                    if let Some(tab) = preview.tabs.get(drag_idx) {
+                    let source_tab = preview
+                        .panes
+                        .get(self.preview.tab_drag_from_pane)
+                        .and_then(|pane| pane.tabs.get(drag_idx));
+                    if let Some(tab) = source_tab {
                         content.child(render_drag_ghost(tab, drag_pos, cx))
                     } else {
                         content
@@ -0,0 +1,108 @@
+//! Arrange toolbar.
+//!
+//! Floating bar above the canvas shown while several items are selected:
+//! - Align left, center, right, top, middle and bottom
+//! - Distribute horizontally and vertically (three or more items)
+
+use crate::app::Humanboard;
+use crate::arrange::{AlignEdge, DistributeAxis, MIN_DISTRIBUTE_ITEMS};
+use crate::constants::{DOCK_WIDTH, HEADER_HEIGHT};
+use gpui::prelude::FluentBuilder;
+use gpui::*;
+use gpui_component::button::{Button, ButtonVariants};
+use gpui_component::{ActiveTheme as _, Sizable, h_flex};
+
+/// Align buttons with their short labels
+const ALIGN_BUTTONS: [(AlignEdge, &str); 6] = [
+    (AlignEdge::Left, "Left"),
+    (AlignEdge::Center, "Center"),
+    (AlignEdge::Right, "Right"),
+    (AlignEdge::Top, "Top"),
+    (AlignEdge::Middle, "Middle"),
+    (AlignEdge::Bottom, "Bottom"),
+];
+
+/// Distribute buttons with their short labels
+const DISTRIBUTE_BUTTONS: [(DistributeAxis, &str); 2] = [
+    (DistributeAxis::Horizontal, "Horizontal"),
+    (DistributeAxis::Vertical, "Vertical"),
+];
+
+/// Render the arrange toolbar for a selection of `selected_count` items
+pub fn render_arrange_toolbar(
+    selected_count: usize,
+    cx: &mut Context<Humanboard>,
+) -> impl IntoElement {
+    let bg = cx.theme().background;
+    let border = cx.theme().border;
+    let muted_fg = cx.theme().muted_foreground;
+
+    let section_label = |label: &'static str| {
+        div()
+            .px(px(6.0))
+            .text_xs()
+            .text_color(muted_fg)
+            .child(label)
+    };
+
+    let align_buttons: Vec<Button> = ALIGN_BUTTONS
+        .iter()
+        .map(|&(edge, label)| {
+            Button::new(ElementId::Name(format!("align-{:?}", edge).into()))
+                .ghost()
+                .small()
+                .label(label)
+                .tooltip(edge.label())
+                .on_click(cx.listener(move |this, _, _window, cx| {
+                    this.align_selected(edge, cx);
+                }))
+        })
+        .collect();
+
+    let distribute_buttons: Vec<Button> = DISTRIBUTE_BUTTONS
+        .iter()
+        .map(|&(axis, label)| {
+            Button::new(ElementId::Name(format!("distribute-{:?}", axis).into()))
+                .ghost()
+                .small()
+                .label(label)
+                .tooltip(axis.label())
+                .on_click(cx.listener(move |this, _, _window, cx| {
+                    this.distribute_selected(axis, cx);
+                }))
+        })
+        .collect();
+
+    deferred(
+        h_flex()
+            .absolute()
+            .top(px(HEADER_HEIGHT + 12.0))
+            .left(px(DOCK_WIDTH))
+            .right_0()
+            .justify_center()
+            .child(
+                h_flex()
+                    .id("arrange-toolbar")
+                    .occlude()
+                    .gap_1()
+                    .px(px(6.0))
+                    .py(px(4.0))
+                    .bg(bg)
+                    .border_1()
+                    .border_color(border)
+                    .rounded(px(8.0))
+                    .shadow_md()
+                    .on_mouse_down(MouseButton::Left, |_, _, cx| {
+                        cx.stop_propagation();
+                    })
+                    .child(section_label("Align"))
+                    .children(align_buttons)
+                    .when(selected_count >= MIN_DISTRIBUTE_ITEMS, |d| {
+                        d.child(div().w(px(1.0)).h(px(16.0)).mx(px(4.0)).bg(border))
+                            .child(section_label("Distribute"))
+                            .children(distribute_buttons)
+                    }),
+            ),
+    )
+    .with_priority(500)
+}
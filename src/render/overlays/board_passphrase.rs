@@ -0,0 +1,156 @@
+//! Board passphrase prompt.
+//!
+//! Asks for the passphrase of an encrypted board before it opens, or for a
+//! new passphrase (entered twice) when encrypting the current board.
+
+use crate::app::{Humanboard, PassphraseMode, PassphrasePrompt};
+use crate::constants::{MODAL_BACKDROP_OPACITY, MODAL_WIDTH_SM};
+use gpui::prelude::FluentBuilder;
+use gpui::*;
+use gpui_component::button::{Button, ButtonVariants};
+use gpui_component::input::Input;
+use gpui_component::{ActiveTheme as _, Icon, IconName, h_flex, v_flex};
+
+/// Render the passphrase prompt
+pub fn render_board_passphrase_modal(
+    prompt: &PassphrasePrompt,
+    cx: &mut Context<Humanboard>,
+) -> impl IntoElement {
+    let bg = cx.theme().background;
+    let border = cx.theme().border;
+    let fg = cx.theme().foreground;
+    let muted_fg = cx.theme().muted_foreground;
+    let danger = cx.theme().danger;
+    let list_hover = cx.theme().list_hover;
+
+    let (title, description, submit_label) = match prompt.mode {
+        PassphraseMode::Unlock => (
+            "Unlock Board",
+            "This board is encrypted. Enter its passphrase to open it.",
+            "Unlock",
+        ),
+        PassphraseMode::Encrypt => (
+            "Encrypt Board",
+            "The board will be saved encrypted. Version history and the board \
+             thumbnail are turned off while it is encrypted. A forgotten passphrase \
+             can't be recovered.",
+            "Encrypt",
+        ),
+    };
+
+    deferred(
+        div()
+            .id("board-passphrase-backdrop")
+            .absolute()
+            .top_0()
+            .left_0()
+            .size_full()
+            .bg(hsla(0.0, 0.0, 0.0, MODAL_BACKDROP_OPACITY))
+            .flex()
+            .items_center()
+            .justify_center()
+            .on_mouse_down(
+                MouseButton::Left,
+                cx.listener(|this, _, _, cx| {
+                    this.cancel_board_passphrase(cx);
+                }),
+            )
+            .child(
+                v_flex()
+                    .id("board-passphrase-modal")
+                    .on_mouse_down(MouseButton::Left, |_, _, cx| {
+                        cx.stop_propagation();
+                    })
+                    .w(px(MODAL_WIDTH_SM))
+                    .bg(bg)
+                    .border_1()
+                    .border_color(border)
+                    .rounded(px(12.0))
+                    .overflow_hidden()
+                    .shadow_lg()
+                    // Header
+                    .child(
+                        h_flex()
+                            .w_full()
+                            .px(px(20.0))
+                            .py(px(16.0))
+                            .border_b_1()
+                            .border_color(border)
+                            .justify_between()
+                            .child(
+                                div()
+                                    .text_size(px(16.0))
+                                    .font_weight(FontWeight::SEMIBOLD)
+                                    .text_color(fg)
+                                    .child(title),
+                            )
+                            .child(
+                                div()
+                                    .id("close-board-passphrase")
+                                    .cursor_pointer()
+                                    .p(px(4.0))
+                                    .rounded(px(4.0))
+                                    .hover(|s| s.bg(list_hover))
+                                    .on_click(cx.listener(|this, _, _, cx| {
+                                        this.cancel_board_passphrase(cx);
+                                    }))
+                                    .child(
+                                        Icon::new(IconName::Close)
+                                            .size(px(16.0))
+                                            .text_color(muted_fg),
+                                    ),
+                            ),
+                    )
+                    // Content
+                    .child(
+                        v_flex()
+                            .w_full()
+                            .p(px(20.0))
+                            .gap(px(12.0))
+                            .child(
+                                div()
+                                    .text_size(px(13.0))
+                                    .text_color(muted_fg)
+                                    .child(description),
+                            )
+                            .when_some(prompt.input.as_ref(), |d, input| {
+                                d.child(Input::new(input).w_full())
+                            })
+                            .when_some(prompt.confirm_input.as_ref(), |d, input| {
+                                d.child(Input::new(input).w_full())
+                            })
+                            .when_some(prompt.error.clone(), |d, error| {
+                                d.child(div().text_size(px(12.0)).text_color(danger).child(error))
+                            }),
+                    )
+                    // Footer with buttons
+                    .child(
+                        h_flex()
+                            .w_full()
+                            .px(px(20.0))
+                            .py(px(16.0))
+                            .border_t_1()
+                            .border_color(border)
+                            .justify_end()
+                            .gap(px(12.0))
+                            .child(
+                                Button::new("cancel-board-passphrase")
+                                    .label("Cancel")
+                                    .ghost()
+                                    .on_click(cx.listener(|this, _, _, cx| {
+                                        this.cancel_board_passphrase(cx);
+                                    })),
+                            )
+                            .child(
+                                Button::new("submit-board-passphrase")
+                                    .label(submit_label)
+                                    .primary()
+                                    .loading(prompt.unlock_rx.is_some())
+                                    .on_click(cx.listener(|this, _, _, cx| {
+                                        this.submit_board_passphrase(cx);
+                                    })),
+                            ),
+                    ),
+            ),
+    )
+}
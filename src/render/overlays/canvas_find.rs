@@ -0,0 +1,92 @@
+//! Canvas find bar.
+//!
+//! Floating bar in the top-left corner of the canvas while finding on the
+//! board: the query, the count of matches and buttons stepping through
+//! them. Enter and Shift+Enter in the input step too.
+
+use crate::app::Humanboard;
+use crate::canvas_find::FindMatches;
+use crate::constants::{DOCK_WIDTH, HEADER_HEIGHT};
+use gpui::prelude::FluentBuilder;
+use gpui::*;
+use gpui_component::button::{Button, ButtonVariants};
+use gpui_component::input::{Escape, Input, InputState};
+use gpui_component::{ActiveTheme as _, Disableable as _, IconName, Sizable, h_flex};
+
+/// Render the find bar for `input`, with the matches found so far
+pub fn render_canvas_find_bar(
+    input: &Entity<InputState>,
+    find: &FindMatches,
+    cx: &mut Context<Humanboard>,
+) -> impl IntoElement {
+    let bg = cx.theme().background;
+    let border = cx.theme().border;
+    let muted_fg = cx.theme().muted_foreground;
+    let no_matches = find.is_empty();
+
+    deferred(
+        h_flex()
+            .id("canvas-find-bar")
+            .occlude()
+            .absolute()
+            .top(px(HEADER_HEIGHT + 12.0))
+            .left(px(DOCK_WIDTH + 12.0))
+            .w(px(340.0))
+            .gap_2()
+            .px(px(6.0))
+            .py(px(4.0))
+            .items_center()
+            .bg(bg)
+            .border_1()
+            .border_color(border)
+            .rounded(px(8.0))
+            .shadow_md()
+            .on_mouse_down(MouseButton::Left, |_, _, cx| {
+                cx.stop_propagation();
+            })
+            .on_action(cx.listener(|this, _: &Escape, window, cx| {
+                this.close_canvas_find(window, cx);
+            }))
+            .child(div().flex_1().child(Input::new(input).xsmall()))
+            .child(
+                div()
+                    .flex_shrink_0()
+                    .text_xs()
+                    .text_color(muted_fg)
+                    .when(find.is_active(), |d| d.child(find.label())),
+            )
+            .child(
+                Button::new("canvas-find-prev")
+                    .icon(IconName::ChevronUp)
+                    .xsmall()
+                    .ghost()
+                    .tooltip("Previous match (Shift+Enter)")
+                    .disabled(no_matches)
+                    .on_click(cx.listener(|this, _, window, cx| {
+                        this.prev_canvas_find_match(window, cx);
+                    })),
+            )
+            .child(
+                Button::new("canvas-find-next")
+                    .icon(IconName::ChevronDown)
+                    .xsmall()
+                    .ghost()
+                    .tooltip("Next match (Enter)")
+                    .disabled(no_matches)
+                    .on_click(cx.listener(|this, _, window, cx| {
+                        this.next_canvas_find_match(window, cx);
+                    })),
+            )
+            .child(
+                Button::new("canvas-find-close")
+                    .icon(IconName::Close)
+                    .xsmall()
+                    .ghost()
+                    .tooltip("Close find")
+                    .on_click(cx.listener(|this, _, window, cx| {
+                        this.close_canvas_find(window, cx);
+                    })),
+            ),
+    )
+    .with_priority(500)
+}
@@ -0,0 +1,153 @@
+//! Checkpoint bars.
+//!
+//! - Name bar in the top-left corner of the canvas while a checkpoint of
+//!   the board is being created: Enter saves it, Escape cancels
+//! - Comparison bar along the bottom of the canvas while the board is
+//!   compared with a saved version: what changed since, a legend of the
+//!   marks on the canvas, and buttons to restore the version or stop
+
+use crate::app::{Humanboard, VersionComparison};
+use crate::constants::{DOCK_WIDTH, FOOTER_HEIGHT, HEADER_HEIGHT};
+use gpui::*;
+use gpui_component::button::{Button, ButtonVariants};
+use gpui_component::input::{Escape, Input, InputState};
+use gpui_component::{ActiveTheme as _, Sizable, h_flex};
+
+/// Render the name bar of a new checkpoint
+pub fn render_checkpoint_name_bar(
+    input: &Entity<InputState>,
+    cx: &mut Context<Humanboard>,
+) -> impl IntoElement {
+    let bg = cx.theme().background;
+    let border = cx.theme().border;
+    let muted_fg = cx.theme().muted_foreground;
+
+    deferred(
+        h_flex()
+            .id("checkpoint-name-bar")
+            .occlude()
+            .absolute()
+            .top(px(HEADER_HEIGHT + 12.0))
+            .left(px(DOCK_WIDTH + 12.0))
+            .w(px(340.0))
+            .gap_2()
+            .px(px(6.0))
+            .py(px(4.0))
+            .items_center()
+            .bg(bg)
+            .border_1()
+            .border_color(border)
+            .rounded(px(8.0))
+            .shadow_md()
+            .on_mouse_down(MouseButton::Left, |_, _, cx| {
+                cx.stop_propagation();
+            })
+            .on_action(cx.listener(|this, _: &Escape, window, cx| {
+                this.cancel_checkpoint_name(window, cx);
+            }))
+            .child(
+                div()
+                    .flex_shrink_0()
+                    .pl(px(4.0))
+                    .text_xs()
+                    .text_color(muted_fg)
+                    .child("Checkpoint"),
+            )
+            .child(div().flex_1().child(Input::new(input).xsmall()))
+            .child(
+                Button::new("save-checkpoint")
+                    .label("Save")
+                    .xsmall()
+                    .primary()
+                    .on_click(cx.listener(|this, _, window, cx| {
+                        let name = this
+                            .ui
+                            .checkpoint_name
+                            .as_ref()
+                            .map(|input| input.read(cx).text().to_string())
+                            .unwrap_or_default();
+                        this.create_checkpoint(&name, cx);
+                        this.cancel_checkpoint_name(window, cx);
+                    })),
+            ),
+    )
+    .with_priority(500)
+}
+
+/// Render the bar shown while the board is compared with a saved version
+pub fn render_comparison_bar(
+    comparison: &VersionComparison,
+    cx: &mut Context<Humanboard>,
+) -> impl IntoElement {
+    let bg = cx.theme().background;
+    let border = cx.theme().border;
+    let fg = cx.theme().foreground;
+    let muted_fg = cx.theme().muted_foreground;
+
+    let legend_entry = |color: Hsla, label: &'static str| {
+        h_flex()
+            .gap(px(4.0))
+            .child(div().size(px(8.0)).rounded(px(2.0)).bg(color))
+            .child(label)
+    };
+
+    deferred(
+        h_flex()
+            .absolute()
+            .bottom(px(FOOTER_HEIGHT + 16.0))
+            .left(px(DOCK_WIDTH))
+            .right_0()
+            .justify_center()
+            .child(
+                h_flex()
+                    .id("comparison-bar")
+                    .occlude()
+                    .gap_3()
+                    .px(px(12.0))
+                    .py(px(6.0))
+                    .items_center()
+                    .bg(bg)
+                    .border_1()
+                    .border_color(border)
+                    .rounded(px(8.0))
+                    .shadow_md()
+                    .on_mouse_down(MouseButton::Left, |_, _, cx| {
+                        cx.stop_propagation();
+                    })
+                    .child(div().text_sm().text_color(fg).child(format!(
+                        "Since {}: {}",
+                        comparison.title,
+                        comparison.diff.summary()
+                    )))
+                    .child(
+                        h_flex()
+                            .gap_2()
+                            .text_xs()
+                            .text_color(muted_fg)
+                            .child(legend_entry(cx.theme().success, "Added"))
+                            .child(legend_entry(cx.theme().danger, "Removed"))
+                            .child(legend_entry(cx.theme().warning, "Moved"))
+                            .child(legend_entry(cx.theme().primary, "Changed")),
+                    )
+                    .child(
+                        Button::new("restore-compared-version")
+                            .label("Restore")
+                            .small()
+                            .primary()
+                            .on_click(cx.listener(|this, _, _, cx| {
+                                this.restore_compared_version(cx);
+                            })),
+                    )
+                    .child(
+                        Button::new("close-comparison")
+                            .label("Done")
+                            .small()
+                            .ghost()
+                            .on_click(cx.listener(|this, _, _, cx| {
+                                this.close_comparison(cx);
+                            })),
+                    ),
+            ),
+    )
+    .with_priority(500)
+}
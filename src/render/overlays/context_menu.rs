@@ -0,0 +1,80 @@
+//! Context menu.
+//!
+//! The right-click menu for items and the empty canvas, drawn at the spot
+//! that was clicked. See `crate::context_menu` for the entries it holds.
+
+use crate::app::Humanboard;
+use crate::context_menu::{
+    ContextMenu, ContextMenuEntry, MENU_ENTRY_HEIGHT, MENU_PADDING, MENU_SEPARATOR_HEIGHT,
+    MENU_WIDTH,
+};
+use gpui::prelude::FluentBuilder;
+use gpui::*;
+use gpui_component::{ActiveTheme as _, h_flex, v_flex};
+
+/// Render an open context menu
+pub fn render_context_menu(menu: &ContextMenu, cx: &mut Context<Humanboard>) -> impl IntoElement {
+    let bg = cx.theme().popover;
+    let border = cx.theme().border;
+    let fg = cx.theme().popover_foreground;
+    let muted_fg = cx.theme().muted_foreground;
+    let hover = cx.theme().list_hover;
+
+    let rows: Vec<AnyElement> = menu
+        .entries
+        .iter()
+        .enumerate()
+        .map(|(index, entry)| match *entry {
+            ContextMenuEntry::Separator => div()
+                .h(px(MENU_SEPARATOR_HEIGHT))
+                .flex()
+                .items_center()
+                .child(div().w_full().h(px(1.0)).bg(border))
+                .into_any_element(),
+            ContextMenuEntry::Command(command) => h_flex()
+                .id(ElementId::NamedInteger(
+                    "context-menu-entry".into(),
+                    index as u64,
+                ))
+                .h(px(MENU_ENTRY_HEIGHT))
+                .px_3()
+                .mx_1()
+                .rounded(px(4.0))
+                .justify_between()
+                .cursor_pointer()
+                .hover(|s| s.bg(hover))
+                .on_mouse_down(
+                    MouseButton::Left,
+                    cx.listener(move |this, _, window, cx| {
+                        cx.stop_propagation();
+                        this.run_context_menu_command(command, window, cx);
+                    }),
+                )
+                .child(div().text_sm().text_color(fg).child(command.label()))
+                .when_some(command.shortcut(), |d, shortcut| {
+                    d.child(div().text_xs().text_color(muted_fg).child(shortcut))
+                })
+                .into_any_element(),
+        })
+        .collect();
+
+    deferred(
+        v_flex()
+            .id("context-menu")
+            .occlude()
+            .absolute()
+            .left(menu.origin.x)
+            .top(menu.origin.y)
+            .w(px(MENU_WIDTH))
+            .py(px(MENU_PADDING))
+            .bg(bg)
+            .border_1()
+            .border_color(border)
+            .rounded(px(6.0))
+            .shadow_lg()
+            .on_mouse_down(MouseButton::Left, |_, _, cx| cx.stop_propagation())
+            .on_mouse_down(MouseButton::Right, |_, _, cx| cx.stop_propagation())
+            .children(rows),
+    )
+    .with_priority(1200)
+}
@@ -0,0 +1,211 @@
+//! Export dialog for a region of the canvas.
+//!
+//! A panel in the top-left corner of the canvas with rows of buttons for
+//! the region (all items, the selection, the visible area or a drawn
+//! rectangle), the scale and the file format, and the size the export will
+//! have. While a region is being drawn it shrinks to a hint with a Cancel
+//! button.
+
+use crate::app::{ExportDialog, Humanboard};
+use crate::constants::{DOCK_WIDTH, HEADER_HEIGHT};
+use crate::input::guides::Bounds as ItemBounds;
+use crate::region_export::{
+    EXPORT_SCALES, ExportFormat, ExportRegion, PDF_PAGE_SIZE, export_size, page_regions,
+};
+use gpui::*;
+use gpui_component::button::{Button, ButtonVariants};
+use gpui_component::{ActiveTheme as _, Disableable as _, Sizable, h_flex, v_flex};
+
+/// Width of the dialog
+const DIALOG_WIDTH: f32 = 380.0;
+
+/// Render the export dialog; `region` is the canvas region it is set to
+pub fn render_export_dialog(
+    dialog: &ExportDialog,
+    region: Option<ItemBounds>,
+    has_selection: bool,
+    cx: &mut Context<Humanboard>,
+) -> impl IntoElement {
+    let bg = cx.theme().background;
+    let border = cx.theme().border;
+    let fg = cx.theme().foreground;
+    let muted_fg = cx.theme().muted_foreground;
+
+    let panel = v_flex()
+        .id("export-dialog")
+        .occlude()
+        .absolute()
+        .top(px(HEADER_HEIGHT + 12.0))
+        .left(px(DOCK_WIDTH + 12.0))
+        .gap_2()
+        .p(px(10.0))
+        .bg(bg)
+        .border_1()
+        .border_color(border)
+        .rounded(px(8.0))
+        .shadow_md()
+        .on_mouse_down(MouseButton::Left, |_, _, cx| {
+            cx.stop_propagation();
+        });
+
+    if dialog.picking {
+        return deferred(
+            panel.child(
+                h_flex()
+                    .gap_2()
+                    .items_center()
+                    .child(
+                        div()
+                            .text_xs()
+                            .text_color(muted_fg)
+                            .child("Drag over the canvas to choose the region to export"),
+                    )
+                    .child(
+                        Button::new("cancel-export-region-pick")
+                            .label("Cancel")
+                            .xsmall()
+                            .ghost()
+                            .on_click(cx.listener(|this, _, _, cx| {
+                                this.finish_export_region_pick(None, cx);
+                            })),
+                    ),
+            ),
+        )
+        .with_priority(500);
+    }
+
+    let row = |label: &'static str| {
+        h_flex().gap_1().items_center().child(
+            div()
+                .w(px(56.0))
+                .flex_shrink_0()
+                .text_xs()
+                .text_color(muted_fg)
+                .child(label),
+        )
+    };
+    let choice = |button: Button, selected: bool| {
+        if selected {
+            button.xsmall().primary()
+        } else {
+            button.xsmall().ghost()
+        }
+    };
+
+    let mut regions = vec![
+        ExportRegion::AllItems,
+        ExportRegion::Selection,
+        ExportRegion::Visible,
+    ];
+    if dialog.drawn.is_some() {
+        regions.push(ExportRegion::Drawn);
+    }
+    let region_row = row("Region")
+        .flex_wrap()
+        .children(regions.into_iter().map(|region| {
+            choice(
+                Button::new(SharedString::from(format!("export-region-{:?}", region)))
+                    .label(region.label())
+                    .disabled(region == ExportRegion::Selection && !has_selection)
+                    .on_click(cx.listener(move |this, _, _, cx| {
+                        this.update_export_dialog(cx, |dialog| dialog.region = region);
+                    })),
+                dialog.region == region,
+            )
+        }))
+        .child(
+            Button::new("draw-export-region")
+                .label("Draw…")
+                .xsmall()
+                .ghost()
+                .on_click(cx.listener(|this, _, _, cx| {
+                    this.start_export_region_pick(cx);
+                })),
+        );
+
+    let scale_row = row("Scale").children(EXPORT_SCALES.iter().map(|&scale| {
+        choice(
+            Button::new(SharedString::from(format!("export-scale-{}", scale)))
+                .label(format!("{}x", scale))
+                .on_click(cx.listener(move |this, _, _, cx| {
+                    this.update_export_dialog(cx, |dialog| dialog.scale = scale);
+                })),
+            dialog.scale == scale,
+        )
+    }));
+
+    let format_row = row("Format").children(ExportFormat::ALL.iter().map(|&format| {
+        choice(
+            Button::new(SharedString::from(format!("export-format-{:?}", format)))
+                .label(format.label())
+                .on_click(cx.listener(move |this, _, _, cx| {
+                    this.update_export_dialog(cx, |dialog| dialog.format = format);
+                })),
+            dialog.format == format,
+        )
+    }));
+
+    let size_label = match region {
+        Some(region) if dialog.format == ExportFormat::Pdf => {
+            let pages = page_regions(region, PDF_PAGE_SIZE).len();
+            if pages == 1 {
+                "1 page".to_string()
+            } else {
+                format!("{} pages", pages)
+            }
+        }
+        Some(region) => {
+            let (width, height) = export_size(region, dialog.scale);
+            format!("{} × {} px", width, height)
+        }
+        None => "Nothing to export".to_string(),
+    };
+
+    deferred(
+        panel
+            .w(px(DIALOG_WIDTH))
+            .child(
+                div()
+                    .text_sm()
+                    .font_weight(FontWeight::SEMIBOLD)
+                    .text_color(fg)
+                    .child("Export Region"),
+            )
+            .child(region_row)
+            .child(scale_row)
+            .child(format_row)
+            .child(
+                h_flex()
+                    .pt(px(4.0))
+                    .gap_2()
+                    .items_center()
+                    .child(
+                        div()
+                            .flex_1()
+                            .text_xs()
+                            .text_color(muted_fg)
+                            .child(size_label),
+                    )
+                    .child(
+                        Button::new("cancel-export-region")
+                            .label("Cancel")
+                            .small()
+                            .ghost()
+                            .on_click(cx.listener(|this, _, _, cx| {
+                                this.close_export_dialog(cx);
+                            })),
+                    )
+                    .child(
+                        Button::new("export-region")
+                            .label("Export…")
+                            .small()
+                            .primary()
+                            .disabled(region.is_none())
+                            .on_click(cx.listener(|this, _, _, cx| {
+                                this.export_dialog_region_to_file(cx);
+                            })),
+                    ),
+            ),
+    )
+    .with_priority(500)
+}
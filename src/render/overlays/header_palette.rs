@@ -219,6 +219,18 @@ fn render_dropdown_results(
                     cx.listener(move |this, _, _, cx| {
                         if this.ui.cmd_palette_mode == crate::app::CmdPaletteMode::Themes {
                             this.ui.pending_command = Some(format!("__theme:{}", name_clone));
+                        } else if this.ui.cmd_palette_mode == crate::app::CmdPaletteMode::Symbols {
+                            this.ui.pending_command = Some(format!("__symbol:{}", item_id));
+                        } else if this.ui.cmd_palette_mode == crate::app::CmdPaletteMode::Files {
+                            if let Some(path) = this.file_result_path(item_id) {
+                                this.ui.pending_command =
+                                    Some(format!("__file:{}", path.display()));
+                            }
+                        } else if let Some(id) =
+                            crate::command_palette::command_result_index(item_id)
+                                .and_then(|index| this.ui.command_results.get(index))
+                        {
+                            this.ui.pending_command = Some(format!("__command:{}", id));
                         } else {
                             this.ui.pending_command = Some(format!("__jump:{}", item_id));
                         }
@@ -0,0 +1,74 @@
+//! Progress of dropped files being imported.
+//!
+//! A small bar under the header while slow imports (many files, PDFs whose
+//! thumbnails are rendered, large images) are read, showing how many of the
+//! dropped files are done.
+
+use crate::app::Humanboard;
+use crate::constants::{DOCK_WIDTH, HEADER_HEIGHT};
+use gpui::*;
+use gpui_component::{ActiveTheme as _, h_flex, v_flex};
+
+/// Width of the progress track
+const TRACK_WIDTH: f32 = 160.0;
+
+/// Render the progress of `done` out of `total` dropped files
+pub fn render_import_progress(
+    done: usize,
+    total: usize,
+    cx: &mut Context<Humanboard>,
+) -> impl IntoElement {
+    let bg = cx.theme().background;
+    let border = cx.theme().border;
+    let fg = cx.theme().foreground;
+    let muted = cx.theme().muted;
+    let primary = cx.theme().primary;
+
+    let fraction = if total == 0 {
+        0.0
+    } else {
+        done as f32 / total as f32
+    };
+    let label = if total == 1 {
+        "Importing file…".to_string()
+    } else {
+        format!("Importing files… {} of {}", done, total)
+    };
+
+    deferred(
+        h_flex()
+            .absolute()
+            .top(px(HEADER_HEIGHT + 12.0))
+            .left(px(DOCK_WIDTH))
+            .right_0()
+            .justify_center()
+            .child(
+                v_flex()
+                    .id("import-progress")
+                    .gap(px(6.0))
+                    .px(px(12.0))
+                    .py(px(8.0))
+                    .bg(bg)
+                    .border_1()
+                    .border_color(border)
+                    .rounded(px(8.0))
+                    .shadow_md()
+                    .child(div().text_xs().text_color(fg).child(label))
+                    .child(
+                        div()
+                            .w(px(TRACK_WIDTH))
+                            .h(px(4.0))
+                            .rounded(px(2.0))
+                            .bg(muted)
+                            .child(
+                                div()
+                                    .h_full()
+                                    .w(px(TRACK_WIDTH * fraction))
+                                    .rounded(px(2.0))
+                                    .bg(primary),
+                            ),
+                    ),
+            ),
+    )
+    .with_priority(500)
+}
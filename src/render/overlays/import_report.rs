@@ -0,0 +1,88 @@
+//! Report of an import that couldn't bring everything over.
+//!
+//! A panel in the top-left corner of the canvas saying how many items were
+//! imported, with a scrolling list of every node, connector or image that
+//! was skipped and why.
+
+use crate::app::{Humanboard, ImportReport};
+use crate::constants::{DOCK_WIDTH, HEADER_HEIGHT};
+use crate::undo_history::items_label;
+use gpui::*;
+use gpui_component::button::{Button, ButtonVariants};
+use gpui_component::{ActiveTheme as _, Sizable, h_flex, v_flex};
+
+/// Width of the report
+const REPORT_WIDTH: f32 = 380.0;
+
+/// Height of the list of skipped parts before it scrolls
+const LIST_MAX_HEIGHT: f32 = 240.0;
+
+/// Render the report of the last import
+pub fn render_import_report(
+    report: &ImportReport,
+    cx: &mut Context<Humanboard>,
+) -> impl IntoElement {
+    let bg = cx.theme().background;
+    let border = cx.theme().border;
+    let fg = cx.theme().foreground;
+    let muted_fg = cx.theme().muted_foreground;
+
+    let summary = format!(
+        "{} · {} skipped",
+        items_label("Imported", report.imported),
+        report.skipped.len()
+    );
+
+    deferred(
+        v_flex()
+            .id("import-report")
+            .occlude()
+            .absolute()
+            .top(px(HEADER_HEIGHT + 12.0))
+            .left(px(DOCK_WIDTH + 12.0))
+            .w(px(REPORT_WIDTH))
+            .gap_2()
+            .p(px(10.0))
+            .bg(bg)
+            .border_1()
+            .border_color(border)
+            .rounded(px(8.0))
+            .shadow_md()
+            .on_mouse_down(MouseButton::Left, |_, _, cx| {
+                cx.stop_propagation();
+            })
+            .child(
+                div()
+                    .text_sm()
+                    .font_weight(FontWeight::SEMIBOLD)
+                    .text_color(fg)
+                    .child(format!("Imported from {}", report.source)),
+            )
+            .child(div().text_xs().text_color(muted_fg).child(summary))
+            .child(
+                v_flex()
+                    .id("import-report-skipped")
+                    .max_h(px(LIST_MAX_HEIGHT))
+                    .overflow_y_scroll()
+                    .gap_1()
+                    .children(
+                        report
+                            .skipped
+                            .iter()
+                            .map(|note| div().text_xs().text_color(fg).child(note.clone())),
+                    ),
+            )
+            .child(
+                h_flex().justify_end().child(
+                    Button::new("close-import-report")
+                        .label("Close")
+                        .small()
+                        .ghost()
+                        .on_click(cx.listener(|this, _, _, cx| {
+                            this.close_import_report(cx);
+                        })),
+                ),
+            ),
+    )
+    .with_priority(500)
+}
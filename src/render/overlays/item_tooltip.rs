@@ -0,0 +1,60 @@
+//! Item tooltip.
+//!
+//! Details of the item under the cursor, drawn just below and to the right
+//! of it. The tooltip doesn't take mouse events, so it never gets in the
+//! way of clicking or dragging. See `crate::item_tooltip` for its contents.
+
+use crate::app::Humanboard;
+use crate::item_tooltip::ItemTooltip;
+use gpui::prelude::FluentBuilder;
+use gpui::*;
+use gpui_component::{ActiveTheme as _, v_flex};
+
+/// Offset of the tooltip from the cursor, clear of the pointer arrow
+const TOOLTIP_OFFSET: (f32, f32) = (12.0, 18.0);
+
+/// Render the hover tooltip for an item
+pub fn render_item_tooltip(
+    tooltip: &ItemTooltip,
+    position: Point<Pixels>,
+    cx: &mut Context<Humanboard>,
+) -> impl IntoElement {
+    let bg = cx.theme().popover;
+    let border = cx.theme().border;
+    let fg = cx.theme().popover_foreground;
+    let muted_fg = cx.theme().muted_foreground;
+
+    deferred(
+        v_flex()
+            .absolute()
+            .left(position.x + px(TOOLTIP_OFFSET.0))
+            .top(position.y + px(TOOLTIP_OFFSET.1))
+            .max_w(px(360.0))
+            .px_2()
+            .py_1p5()
+            .gap_0p5()
+            .bg(bg)
+            .border_1()
+            .border_color(border)
+            .rounded(px(6.0))
+            .shadow_md()
+            .text_xs()
+            .child(
+                div()
+                    .text_sm()
+                    .font_weight(FontWeight::SEMIBOLD)
+                    .text_color(fg)
+                    .child(tooltip.title.clone()),
+            )
+            .when_some(tooltip.path.clone(), |d, path| {
+                d.child(div().text_color(muted_fg).child(path))
+            })
+            .children(
+                tooltip
+                    .details
+                    .iter()
+                    .map(|detail| div().text_color(fg).child(detail.clone())),
+            ),
+    )
+    .with_priority(1100)
+}
@@ -5,25 +5,70 @@
 //! - Footer bar with status info
 //! - Keyboard shortcuts modal
 //! - Command palette popup
-//! - Settings modal
+//! - Settings modal, with the keybindings list and new item styles
 //! - Create board modal
 //! - Chart configuration modal
+//! - Version history browser
+//! - Board passphrase prompt
+//! - Outline side panel
+//! - Undo history side panel
+//! - Arrange toolbar for multi-selection
+//! - Find bar for finding items on the canvas
+//! - Checkpoint name bar and version comparison bar
+//! - Export dialog for a region of the canvas
+//! - Chooser for what a pasted URL becomes
+//! - Progress of dropped files being imported
+//! - Report of what an import skipped
+//! - Right-click context menu
+//! - Hover tooltip with item details
+//! - Performance HUD
 
+mod arrange_toolbar;
+mod board_passphrase;
+mod canvas_find;
 mod chart_config;
+mod checkpoint;
 mod command_palette;
+mod context_menu;
+mod item_tooltip;
 mod create_board;
+mod export_dialog;
 mod header;
 mod header_palette;
+mod import_progress;
+mod import_report;
 mod modal_base;
+mod outline;
+mod perf_hud;
 mod settings;
 mod settings_dropdowns;
+mod settings_item_styles;
+mod settings_keymap;
+mod settings_theme_editor;
 mod shortcuts;
+mod undo_history;
+mod url_paste_chooser;
+mod version_history;
 
 // Re-export all public items
+pub use arrange_toolbar::render_arrange_toolbar;
+pub use board_passphrase::render_board_passphrase_modal;
+pub use canvas_find::render_canvas_find_bar;
 pub use chart_config::render_chart_config_modal;
+pub use checkpoint::{render_checkpoint_name_bar, render_comparison_bar};
 pub use command_palette::render_command_palette;
+pub use context_menu::render_context_menu;
+pub use item_tooltip::render_item_tooltip;
 pub use create_board::render_create_board_modal;
+pub use export_dialog::render_export_dialog;
 pub use header::{render_footer_bar, render_header_bar};
+pub use import_progress::render_import_progress;
+pub use import_report::render_import_report;
 pub use modal_base::{FontDropdownOpen, SettingsDropdown, ThemeDropdownOpen};
+pub use outline::render_outline_panel;
+pub use perf_hud::render_perf_hud;
 pub use settings::render_settings_modal;
 pub use shortcuts::render_shortcuts_overlay;
+pub use undo_history::render_undo_history_panel;
+pub use url_paste_chooser::render_url_paste_chooser;
+pub use version_history::render_version_history_modal;
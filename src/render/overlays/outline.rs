@@ -0,0 +1,210 @@
+//! Outline side panel.
+//!
+//! Lists every item on the current board grouped by kind:
+//! - Item count and combined file size for the board and each group
+//! - File size next to items that link to a file
+//! - Click an item to pan to it and select it
+
+use crate::app::{Humanboard, OutlinePanel};
+use crate::board_outline::{OutlineEntry, OutlineGroup, format_file_size};
+use crate::constants::{FOOTER_HEIGHT, HEADER_HEIGHT};
+use gpui::prelude::FluentBuilder;
+use gpui::*;
+use gpui_component::{ActiveTheme as _, Icon, IconName, h_flex, v_flex};
+use std::collections::HashSet;
+
+/// Width of the outline panel
+const OUTLINE_WIDTH: f32 = 280.0;
+
+/// Render the Outline side panel along the right edge of the canvas
+pub fn render_outline_panel(
+    panel: &OutlinePanel,
+    selected_items: &HashSet<u64>,
+    cx: &mut Context<Humanboard>,
+) -> impl IntoElement {
+    let bg = cx.theme().background;
+    let border = cx.theme().border;
+    let fg = cx.theme().foreground;
+    let muted_fg = cx.theme().muted_foreground;
+    let list_hover = cx.theme().list_hover;
+
+    let outline = &panel.outline;
+    let summary = if outline.total_file_size > 0 {
+        format!(
+            "{} items · {}",
+            outline.item_count,
+            format_file_size(outline.total_file_size)
+        )
+    } else {
+        format!("{} items", outline.item_count)
+    };
+
+    deferred(
+        v_flex()
+            .id("outline-panel")
+            .occlude()
+            .absolute()
+            .top(px(HEADER_HEIGHT))
+            .bottom(px(FOOTER_HEIGHT))
+            .right_0()
+            .w(px(OUTLINE_WIDTH))
+            .bg(bg)
+            .border_l_1()
+            .border_color(border)
+            .shadow_lg()
+            .on_mouse_down(MouseButton::Left, |_, _, cx| {
+                cx.stop_propagation();
+            })
+            .on_scroll_wheel(|_, _, cx| {
+                cx.stop_propagation();
+            })
+            // Header
+            .child(
+                h_flex()
+                    .w_full()
+                    .px(px(16.0))
+                    .py(px(12.0))
+                    .border_b_1()
+                    .border_color(border)
+                    .justify_between()
+                    .child(
+                        v_flex()
+                            .child(
+                                div()
+                                    .text_size(px(14.0))
+                                    .font_weight(FontWeight::SEMIBOLD)
+                                    .text_color(fg)
+                                    .child("Outline"),
+                            )
+                            .child(
+                                div()
+                                    .text_size(px(12.0))
+                                    .text_color(muted_fg)
+                                    .child(summary),
+                            ),
+                    )
+                    .child(
+                        div()
+                            .id("close-outline")
+                            .cursor_pointer()
+                            .p(px(4.0))
+                            .rounded(px(4.0))
+                            .hover(|s| s.bg(list_hover))
+                            .on_click(cx.listener(|this, _, _, cx| {
+                                this.toggle_outline(cx);
+                            }))
+                            .child(
+                                Icon::new(IconName::Close)
+                                    .size(px(16.0))
+                                    .text_color(muted_fg),
+                            ),
+                    ),
+            )
+            // Groups
+            .child(
+                v_flex()
+                    .id("outline-list")
+                    .flex_1()
+                    .min_h_0()
+                    .overflow_y_scroll()
+                    .track_scroll(&panel.scroll)
+                    .p(px(8.0))
+                    .gap(px(12.0))
+                    .when(outline.groups.is_empty(), |d| {
+                        d.child(
+                            div()
+                                .p(px(8.0))
+                                .text_size(px(13.0))
+                                .text_color(muted_fg)
+                                .child("This board is empty"),
+                        )
+                    })
+                    .children(
+                        outline
+                            .groups
+                            .iter()
+                            .map(|group| render_group(group, selected_items, cx)),
+                    ),
+            ),
+    )
+    .with_priority(500)
+}
+
+/// Render one group heading and its items
+fn render_group(
+    group: &OutlineGroup,
+    selected_items: &HashSet<u64>,
+    cx: &mut Context<Humanboard>,
+) -> Div {
+    let muted_fg = cx.theme().muted_foreground;
+
+    let mut heading = format!("{} · {}", group.label, group.count());
+    if group.file_size > 0 {
+        heading.push_str(&format!(" · {}", format_file_size(group.file_size)));
+    }
+
+    v_flex()
+        .gap(px(2.0))
+        .child(
+            div()
+                .px(px(8.0))
+                .pb(px(2.0))
+                .text_size(px(11.0))
+                .font_weight(FontWeight::SEMIBOLD)
+                .text_color(muted_fg)
+                .child(heading.to_uppercase()),
+        )
+        .children(
+            group
+                .entries
+                .iter()
+                .map(|entry| render_entry(entry, selected_items.contains(&entry.item_id), cx)),
+        )
+}
+
+/// Render a clickable item row
+fn render_entry(
+    entry: &OutlineEntry,
+    is_selected: bool,
+    cx: &mut Context<Humanboard>,
+) -> Stateful<Div> {
+    let fg = cx.theme().foreground;
+    let muted_fg = cx.theme().muted_foreground;
+    let list_hover = cx.theme().list_hover;
+    let list_active = cx.theme().list_active;
+    let item_id = entry.item_id;
+
+    h_flex()
+        .id(ElementId::Name(format!("outline-item-{}", item_id).into()))
+        .w_full()
+        .px(px(8.0))
+        .py(px(5.0))
+        .gap(px(8.0))
+        .rounded(px(6.0))
+        .cursor_pointer()
+        .when(is_selected, |d| d.bg(list_active))
+        .when(!is_selected, |d| d.hover(|s| s.bg(list_hover)))
+        .on_click(cx.listener(move |this, _, window, cx| {
+            this.jump_to_item(item_id, window, cx);
+        }))
+        .child(
+            div()
+                .flex_1()
+                .min_w_0()
+                .text_size(px(13.0))
+                .text_color(fg)
+                .overflow_hidden()
+                .text_ellipsis()
+                .whitespace_nowrap()
+                .child(entry.name.clone()),
+        )
+        .when_some(entry.file_size, |d, size| {
+            d.child(
+                div()
+                    .flex_shrink_0()
+                    .text_size(px(11.0))
+                    .text_color(muted_fg)
+                    .child(format_file_size(size)),
+            )
+        })
+}
@@ -0,0 +1,102 @@
+//! Performance HUD.
+//!
+//! A small readout in the top-right corner of the canvas with the frame rate,
+//! frame times and the media memory tracked by `crate::memory`. Toggled from
+//! the command palette; it doesn't take mouse events.
+
+use crate::app::Humanboard;
+use crate::constants::HEADER_HEIGHT;
+use crate::memory::{MemoryStats, format_bytes};
+use crate::perf::PerfMonitor;
+use gpui::*;
+use gpui_component::{ActiveTheme as _, h_flex, v_flex};
+
+/// Render the performance HUD
+pub fn render_perf_hud(
+    fps: f32,
+    perf: &PerfMonitor,
+    memory: &MemoryStats,
+    cx: &mut Context<Humanboard>,
+) -> impl IntoElement {
+    let bg = cx.theme().popover;
+    let border = cx.theme().border;
+    let fg = cx.theme().popover_foreground;
+    let muted_fg = cx.theme().muted_foreground;
+    let danger = cx.theme().danger;
+
+    let total = memory.total_bytes();
+    let row = |label: &'static str, value: String| {
+        h_flex()
+            .justify_between()
+            .gap_4()
+            .child(div().text_color(muted_fg).child(label))
+            .child(div().text_color(fg).child(value))
+    };
+
+    deferred(
+        v_flex()
+            .absolute()
+            .top(px(HEADER_HEIGHT + 8.0))
+            .right(px(12.0))
+            .w(px(220.0))
+            .px_2()
+            .py_1p5()
+            .gap_0p5()
+            .bg(bg.opacity(0.9))
+            .border_1()
+            .border_color(border)
+            .rounded(px(6.0))
+            .text_xs()
+            .font_family("monospace")
+            .child(row("FPS", format!("{:.0}", fps)))
+            .child(row(
+                "Frame",
+                format!(
+                    "{:.1} ms avg, {:.1} max",
+                    perf.average_frame_time(),
+                    perf.max_frame_time()
+                ),
+            ))
+            .child(row(
+                "Images",
+                format!(
+                    "{} · {}",
+                    memory.image_count,
+                    format_bytes(memory.image_bytes)
+                ),
+            ))
+            .child(row(
+                "Webviews",
+                format!(
+                    "{} · {}",
+                    memory.webview_count,
+                    format_bytes(memory.webview_bytes)
+                ),
+            ))
+            .child(row(
+                "PDF thumbnails",
+                format!("{} · {}", memory.pdf_count, format_bytes(memory.pdf_bytes)),
+            ))
+            .child(
+                h_flex()
+                    .justify_between()
+                    .gap_4()
+                    .child(div().text_color(muted_fg).child("Media"))
+                    .child(
+                        div()
+                            .text_color(if total > memory.budget_bytes {
+                                danger
+                            } else {
+                                fg
+                            })
+                            .child(format!(
+                                "{} / {}",
+                                format_bytes(total),
+                                format_bytes(memory.budget_bytes)
+                            )),
+                    ),
+            )
+            .child(row("Evicted", memory.evictions.to_string())),
+    )
+    .with_priority(1100)
+}
@@ -1,7 +1,7 @@
 //! Settings modal component.
 
 use crate::actions::{ModalFocusNext, ModalFocusPrev, OpenSettings};
-use crate::app::{Humanboard, SettingsTab};
+use crate::app::{Humanboard, SettingsTab, ThemeEditor};
 use crate::constants::{MODAL_HEIGHT_MD, MODAL_WIDTH_LG};
 use crate::focus::FocusContext;
 use crate::settings::Settings;
@@ -17,6 +17,9 @@ use super::settings_dropdowns::{
     render_font_dropdown, render_font_dropdown_menu, render_theme_dropdown,
     render_theme_dropdown_menu,
 };
+use super::settings_item_styles::render_item_styles_section;
+use super::settings_keymap::render_keybindings_tab;
+use super::settings_theme_editor::render_theme_editor_section;
 
 /// Render a settings sidebar tab button
 fn render_settings_tab_button(
@@ -68,12 +71,14 @@ fn render_settings_tab_button(
 }
 
 /// Render the settings modal
+#[allow(clippy::too_many_arguments)]
 pub fn render_settings_modal(
     current_theme: &str,
     current_font: &str,
     _theme_index: usize,
     _theme_scroll: &ScrollHandle,
     active_tab: SettingsTab,
+    theme_editor: Option<&ThemeEditor>,
     modal_focus: &FocusHandle,
     opacity: f32,
     cx: &mut Context<Humanboard>,
@@ -111,6 +116,7 @@ pub fn render_settings_modal(
     // Build content after creating handlers
     let content = render_settings_content(
         active_tab,
+        theme_editor,
         &current_theme_display,
         &current_font_display,
         &themes,
@@ -168,6 +174,7 @@ pub fn render_settings_modal(
         // Backdrop mouse up: close if flag is set
         |this, _, window, cx| {
             if this.settings.backdrop_clicked {
+                this.cancel_theme_editor(cx);
                 this.settings.show = false;
                 this.system.focus.force_canvas_focus(window);
             }
@@ -210,6 +217,32 @@ fn render_settings_sidebar(
             list_hover,
             cx,
         ))
+        // Boards tab
+        .child(render_settings_tab_button(
+            "tab-boards",
+            SettingsTab::Boards,
+            active_tab,
+            IconName::LayoutDashboard,
+            "Boards",
+            fg,
+            muted_fg,
+            list_active,
+            list_hover,
+            cx,
+        ))
+        // Keybindings tab
+        .child(render_settings_tab_button(
+            "tab-keybindings",
+            SettingsTab::Keybindings,
+            active_tab,
+            IconName::SquareTerminal,
+            "Keybindings",
+            fg,
+            muted_fg,
+            list_active,
+            list_hover,
+            cx,
+        ))
         // Integrations tab
         .child(render_settings_tab_button(
             "tab-integrations",
@@ -225,9 +258,67 @@ fn render_settings_sidebar(
         ))
 }
 
+/// Trash retention choices as (days, label); 0 keeps boards until the trash is emptied
+const TRASH_RETENTION_OPTIONS: [(u32, &str); 4] =
+    [(7, "7 days"), (30, "30 days"), (90, "90 days"), (0, "Never")];
+
+/// Managed assets choices as (enabled, label)
+const MANAGE_ASSETS_OPTIONS: [(bool, &str); 2] = [(false, "Link"), (true, "Copy")];
+
+/// Board file layout choices as (git-friendly, label)
+const SAVE_FORMAT_OPTIONS: [(bool, &str); 2] = [(false, "Standard"), (true, "Git-friendly")];
+
+/// Snap-to-grid choices as (grid size, label); None turns snapping off
+const SNAP_GRID_OPTIONS: [(Option<f32>, &str); 4] = [
+    (None, "Off"),
+    (Some(10.0), "10 px"),
+    (Some(20.0), "20 px"),
+    (Some(40.0), "40 px"),
+];
+
+/// Render a segmented control that calls `on_select` with the chosen value
+pub(super) fn render_segmented_control<T: Copy + PartialEq + 'static>(
+    id_prefix: &'static str,
+    options: &[(T, &'static str)],
+    current: T,
+    on_select: fn(&mut Humanboard, T, &mut Context<Humanboard>),
+    cx: &mut Context<Humanboard>,
+) -> Div {
+    let fg = cx.theme().foreground;
+    let muted_fg = cx.theme().muted_foreground;
+    let border = cx.theme().border;
+    let list_active = cx.theme().list_active;
+    let list_hover = cx.theme().list_hover;
+
+    h_flex()
+        .gap_1()
+        .p(px(2.0))
+        .border_1()
+        .border_color(border)
+        .rounded(px(6.0))
+        .children(options.iter().enumerate().map(|(index, &(value, label))| {
+            let is_active = current == value;
+            div()
+                .id(ElementId::Name(format!("{}-{}", id_prefix, index).into()))
+                .px_2()
+                .py_1()
+                .rounded(px(4.0))
+                .text_xs()
+                .cursor(CursorStyle::PointingHand)
+                .text_color(if is_active { fg } else { muted_fg })
+                .when(is_active, |d| d.bg(list_active))
+                .when(!is_active, |d| d.hover(|s| s.bg(list_hover)))
+                .on_click(cx.listener(move |this, _, _, cx| {
+                    on_select(this, value, cx);
+                }))
+                .child(label)
+        }))
+}
+
 #[allow(clippy::too_many_arguments)]
 fn render_settings_content(
     active_tab: SettingsTab,
+    theme_editor: Option<&ThemeEditor>,
     current_theme: &str,
     current_font: &str,
     themes: &[String],
@@ -257,6 +348,9 @@ fn render_settings_content(
         .when(active_tab == SettingsTab::Appearance, |d| {
             d.child(
                 v_flex()
+                    .id("appearance-settings")
+                    .size_full()
+                    .overflow_y_scroll()
                     .gap_4()
                     .child(render_section_header("Theme", cx))
                     .child(render_setting_row(
@@ -275,9 +369,76 @@ fn render_settings_content(
                             &current_font_clone, fg, muted_fg, input_bg, border, cx,
                         ),
                         cx,
-                    )),
+                    ))
+                    .child(render_theme_editor_section(theme_editor, cx)),
+            )
+        })
+        // Content - Boards tab
+        .when(active_tab == SettingsTab::Boards, |d| {
+            d.child(
+                v_flex()
+                    .id("boards-settings")
+                    .size_full()
+                    .overflow_y_scroll()
+                    .gap_4()
+                    .child(render_section_header("Canvas", cx))
+                    .child(render_setting_row(
+                        "Snap to Grid",
+                        "Snap items to the grid while dragging and resizing. Hold Cmd to move freely",
+                        render_segmented_control(
+                            "snap-grid",
+                            &SNAP_GRID_OPTIONS,
+                            crate::settings::snap_grid_size(),
+                            Humanboard::set_snap_grid_size,
+                            cx,
+                        ),
+                        cx,
+                    ))
+                    .child(render_section_header("Trash", cx))
+                    .child(render_setting_row(
+                        "Keep Deleted Boards",
+                        "Boards in the trash are permanently deleted after this period",
+                        render_segmented_control(
+                            "trash-retention",
+                            &TRASH_RETENTION_OPTIONS,
+                            crate::settings::trash_retention_days(),
+                            Humanboard::set_trash_retention_days,
+                            cx,
+                        ),
+                        cx,
+                    ))
+                    .child(render_section_header("Files", cx))
+                    .child(render_setting_row(
+                        "Dropped Files",
+                        "Link to files where they are, or copy them into the board's assets folder",
+                        render_segmented_control(
+                            "manage-assets",
+                            &MANAGE_ASSETS_OPTIONS,
+                            crate::settings::is_manage_assets_enabled(),
+                            Humanboard::set_manage_assets,
+                            cx,
+                        ),
+                        cx,
+                    ))
+                    .child(render_setting_row(
+                        "Board File Format",
+                        "Git-friendly stores data sources in separate files so boards diff cleanly",
+                        render_segmented_control(
+                            "save-format",
+                            &SAVE_FORMAT_OPTIONS,
+                            crate::settings::is_git_friendly_save_enabled(),
+                            Humanboard::set_git_friendly_save,
+                            cx,
+                        ),
+                        cx,
+                    ))
+                    .child(render_item_styles_section(cx)),
             )
         })
+        // Content - Keybindings tab
+        .when(active_tab == SettingsTab::Keybindings, |d| {
+            d.child(render_keybindings_tab(cx))
+        })
         // Content - Integrations tab
         .when(active_tab == SettingsTab::Integrations, |d| {
             d.child(
@@ -0,0 +1,230 @@
+//! New Items section in the Boards tab of the settings modal - the styles
+//! new text boxes, shapes, arrows and tables are made with.
+
+use crate::app::Humanboard;
+use crate::custom_themes::parse_color;
+use crate::types::ArrowHead;
+use gpui::prelude::FluentBuilder;
+use gpui::*;
+use gpui_component::{ActiveTheme as _, h_flex, v_flex};
+
+use super::modal_base::{render_section_header, render_setting_row};
+use super::settings::render_segmented_control;
+
+/// Colors offered for new items, besides the theme's
+const STYLE_COLORS: [&str; 8] = [
+    "#ffffff", "#1f2328", "#e5484d", "#f76b15", "#ffc53d", "#46a758", "#0090ff", "#8e4ec6",
+];
+
+/// Text box font size choices as (points, label)
+const TEXT_SIZE_OPTIONS: [(f32, &str); 4] =
+    [(12.0, "12"), (16.0, "16"), (24.0, "24"), (32.0, "32")];
+
+/// Shape border width choices as (pixels, label)
+const BORDER_WIDTH_OPTIONS: [(f32, &str); 4] =
+    [(0.0, "None"), (1.0, "1 px"), (2.0, "2 px"), (4.0, "4 px")];
+
+/// Arrow thickness choices as (pixels, label)
+const ARROW_THICKNESS_OPTIONS: [(f32, &str); 4] =
+    [(1.0, "1 px"), (2.0, "2 px"), (4.0, "4 px"), (8.0, "8 px")];
+
+/// Arrow head choices
+const ARROW_HEAD_OPTIONS: [(ArrowHead, &str); 4] = [
+    (ArrowHead::Arrow, "Arrow"),
+    (ArrowHead::Diamond, "Diamond"),
+    (ArrowHead::Circle, "Circle"),
+    (ArrowHead::None, "None"),
+];
+
+/// Show/hide choices as (shown, label)
+const SHOW_OPTIONS: [(bool, &str); 2] = [(true, "Show"), (false, "Hide")];
+
+/// Render the New Items section
+pub fn render_item_styles_section(cx: &mut Context<Humanboard>) -> Div {
+    let styles = crate::settings::item_styles();
+    let muted_fg = cx.theme().muted_foreground;
+
+    v_flex()
+        .gap_4()
+        .child(render_section_header("New Items", cx))
+        .child(div().text_xs().text_color(muted_fg).child(
+            "For other colors and sizes, style an item and use Set as Default Style from its menu.",
+        ))
+        .child(render_setting_row(
+            "Text Size",
+            "Font size of new text boxes, in points",
+            render_segmented_control(
+                "item-text-size",
+                &TEXT_SIZE_OPTIONS,
+                styles.text_font_size,
+                |this, size, cx| this.update_item_styles(|s| s.text_font_size = size, cx),
+                cx,
+            ),
+            cx,
+        ))
+        .child(render_setting_row(
+            "Text Color",
+            "Color of the text in new text boxes",
+            render_color_choice(
+                "item-text-color",
+                "Theme",
+                &styles.text_color,
+                |this, color, cx| this.update_item_styles(|s| s.text_color = color.into(), cx),
+                cx,
+            ),
+            cx,
+        ))
+        .child(render_setting_row(
+            "Shape Fill",
+            "Color new shapes are filled with",
+            render_color_choice(
+                "item-shape-fill",
+                "None",
+                styles.shape_fill.as_deref().unwrap_or_default(),
+                |this, color, cx| {
+                    this.update_item_styles(
+                        |s| s.shape_fill = (!color.is_empty()).then(|| color.into()),
+                        cx,
+                    )
+                },
+                cx,
+            ),
+            cx,
+        ))
+        .child(render_setting_row(
+            "Shape Border",
+            "Border color of new shapes",
+            render_color_choice(
+                "item-shape-border",
+                "Theme",
+                &styles.shape_border_color,
+                |this, color, cx| {
+                    this.update_item_styles(|s| s.shape_border_color = color.into(), cx)
+                },
+                cx,
+            ),
+            cx,
+        ))
+        .child(render_setting_row(
+            "Shape Border Width",
+            "Border width of new shapes",
+            render_segmented_control(
+                "item-border-width",
+                &BORDER_WIDTH_OPTIONS,
+                styles.shape_border_width,
+                |this, width, cx| this.update_item_styles(|s| s.shape_border_width = width, cx),
+                cx,
+            ),
+            cx,
+        ))
+        .child(render_setting_row(
+            "Arrow Color",
+            "Color of new arrows",
+            render_color_choice(
+                "item-arrow-color",
+                "Theme",
+                &styles.arrow_color,
+                |this, color, cx| this.update_item_styles(|s| s.arrow_color = color.into(), cx),
+                cx,
+            ),
+            cx,
+        ))
+        .child(render_setting_row(
+            "Arrow Thickness",
+            "Line thickness of new arrows",
+            render_segmented_control(
+                "item-arrow-thickness",
+                &ARROW_THICKNESS_OPTIONS,
+                styles.arrow_thickness,
+                |this, thickness, cx| {
+                    this.update_item_styles(|s| s.arrow_thickness = thickness, cx)
+                },
+                cx,
+            ),
+            cx,
+        ))
+        .child(render_setting_row(
+            "Arrow Head",
+            "Head at the end of new arrows",
+            render_segmented_control(
+                "item-arrow-head",
+                &ARROW_HEAD_OPTIONS,
+                styles.arrow_head,
+                |this, head, cx| this.update_item_styles(|s| s.arrow_head = head, cx),
+                cx,
+            ),
+            cx,
+        ))
+        .child(render_setting_row(
+            "Table Headers",
+            "Whether new tables show their column headers",
+            render_segmented_control(
+                "item-table-headers",
+                &SHOW_OPTIONS,
+                styles.table_headers,
+                |this, shown, cx| this.update_item_styles(|s| s.table_headers = shown, cx),
+                cx,
+            ),
+            cx,
+        ))
+        .child(render_setting_row(
+            "Table Stripes",
+            "Whether new tables stripe alternating rows",
+            render_segmented_control(
+                "item-table-stripe",
+                &SHOW_OPTIONS,
+                styles.table_stripe,
+                |this, shown, cx| this.update_item_styles(|s| s.table_stripe = shown, cx),
+                cx,
+            ),
+            cx,
+        ))
+}
+
+/// Render a row of color swatches after a chip for no color, labeled
+/// `none_label`; `on_select` gets the chosen hex, or "" for the chip
+fn render_color_choice(
+    id_prefix: &'static str,
+    none_label: &'static str,
+    current: &str,
+    on_select: fn(&mut Humanboard, &'static str, &mut Context<Humanboard>),
+    cx: &mut Context<Humanboard>,
+) -> Div {
+    let fg = cx.theme().foreground;
+    let muted_fg = cx.theme().muted_foreground;
+    let border = cx.theme().border;
+    let list_active = cx.theme().list_active;
+    let list_hover = cx.theme().list_hover;
+    let current = current.to_lowercase();
+
+    let none_active = current.is_empty();
+    let none_chip = div()
+        .id(ElementId::Name(format!("{}-none", id_prefix).into()))
+        .px_2()
+        .py_1()
+        .rounded(px(4.0))
+        .text_xs()
+        .cursor(CursorStyle::PointingHand)
+        .text_color(if none_active { fg } else { muted_fg })
+        .when(none_active, |d| d.bg(list_active))
+        .when(!none_active, |d| d.hover(|s| s.bg(list_hover)))
+        .on_click(cx.listener(move |this, _, _, cx| on_select(this, "", cx)))
+        .child(none_label);
+
+    h_flex()
+        .gap_1()
+        .items_center()
+        .child(none_chip)
+        .children(STYLE_COLORS.iter().enumerate().map(|(index, &hex)| {
+            let is_active = current == hex;
+            div()
+                .id(ElementId::Name(format!("{}-{}", id_prefix, index).into()))
+                .size(px(18.0))
+                .rounded(px(4.0))
+                .border_2()
+                .border_color(if is_active { fg } else { border })
+                .cursor(CursorStyle::PointingHand)
+                .when_some(parse_color(hex), |d, color| d.bg(color))
+                .on_click(cx.listener(move |this, _, _, cx| on_select(this, hex, cx)))
+        }))
+}
@@ -0,0 +1,135 @@
+//! Keybindings tab of the settings modal - every command with the keys that
+//! run it, and the keys bound to more than one command.
+
+use crate::app::Humanboard;
+use crate::command_registry::{COMMAND_REGISTRY, keystroke_label};
+use crate::keymap::{USER_KEYMAP, command_bindings, default_keymap_path, find_conflicts};
+use gpui::prelude::FluentBuilder;
+use gpui::*;
+use gpui_component::{ActiveTheme as _, h_flex, v_flex};
+
+use super::modal_base::{render_kbd, render_section_header};
+
+/// Render the keybindings tab
+pub fn render_keybindings_tab(cx: &mut Context<Humanboard>) -> impl IntoElement {
+    let fg = cx.theme().foreground;
+    let muted_fg = cx.theme().muted_foreground;
+    let border = cx.theme().border;
+    let danger = cx.theme().danger;
+    let primary = cx.theme().primary;
+
+    let (bindings, unknown, invalid, error) = {
+        let loaded = USER_KEYMAP.read();
+        let registry = COMMAND_REGISTRY.read();
+        (
+            command_bindings(&registry, &loaded.keymap),
+            loaded.keymap.unknown_commands(&registry),
+            loaded.keymap.invalid.clone(),
+            loaded.error.clone(),
+        )
+    };
+    let conflicts = find_conflicts(&bindings);
+    let keymap_path = default_keymap_path()
+        .map(|p| p.display().to_string())
+        .unwrap_or_else(|| "keymap.json".to_string());
+
+    let mut problems: Vec<String> = Vec::new();
+    if let Some(error) = error {
+        problems.push(error);
+    }
+    for (id, chord) in &invalid {
+        problems.push(format!("\"{}\" for {} isn't a keybinding", chord, id));
+    }
+    for id in &unknown {
+        problems.push(format!("No command is called {}", id));
+    }
+    for conflict in &conflicts {
+        problems.push(format!(
+            "{} is bound to {}",
+            keystroke_label(&conflict.keystroke),
+            conflict.commands.join(" and ")
+        ));
+    }
+
+    let rows: Vec<AnyElement> = bindings
+        .into_iter()
+        .map(|binding| {
+            let conflicted = conflicts
+                .iter()
+                .any(|conflict| conflict.commands.contains(&binding.id));
+            h_flex()
+                .py_1p5()
+                .gap_3()
+                .items_center()
+                .justify_between()
+                .border_b_1()
+                .border_color(border)
+                .child(
+                    v_flex()
+                        .flex_1()
+                        .overflow_hidden()
+                        .child(
+                            h_flex()
+                                .gap_2()
+                                .child(div().text_sm().text_color(fg).child(binding.name))
+                                .when(binding.customized, |d| {
+                                    d.child(div().text_xs().text_color(primary).child("Custom"))
+                                }),
+                        )
+                        .child(
+                            div()
+                                .text_xs()
+                                .text_color(muted_fg)
+                                .child(format!("{} - {}", binding.category, binding.id)),
+                        ),
+                )
+                .child(
+                    h_flex()
+                        .gap_1()
+                        .when(binding.keys.is_empty(), |d| {
+                            d.child(div().text_xs().text_color(muted_fg).child("Unbound"))
+                        })
+                        .children(
+                            binding
+                                .keys
+                                .iter()
+                                .map(|key| render_kbd(&keystroke_label(key), cx)),
+                        )
+                        .when(conflicted, |d| {
+                            d.child(div().text_xs().text_color(danger).child("Conflict"))
+                        }),
+                )
+                .into_any_element()
+        })
+        .collect();
+
+    v_flex()
+        .size_full()
+        .gap_4()
+        .child(render_section_header("Keybindings", cx))
+        .child(
+            div()
+                .text_xs()
+                .text_color(muted_fg)
+                .child(format!(
+                    "Remap commands by their ID in {}, e.g. {{\"view:outline\": \"cmd-shift-o\"}}. Changes apply when the file is saved.",
+                    keymap_path
+                )),
+        )
+        .when(!problems.is_empty(), |d| {
+            d.child(
+                v_flex().gap_1().children(
+                    problems
+                        .into_iter()
+                        .map(|problem| div().text_xs().text_color(danger).child(problem)),
+                ),
+            )
+        })
+        .child(
+            v_flex()
+                .id("keybindings-list")
+                .flex_1()
+                .overflow_y_scroll()
+                .children(rows),
+        )
+}
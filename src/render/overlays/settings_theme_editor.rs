@@ -0,0 +1,140 @@
+//! Theme editor in the Appearance tab of the settings modal - a hex input
+//! and swatch for each color of the theme being made, applied as it is
+//! typed, with buttons to save the theme or go back to the one before.
+
+use crate::app::{Humanboard, ThemeEditor};
+use crate::custom_themes::{EditableColor, custom_themes_dir, parse_color};
+use gpui::prelude::FluentBuilder;
+use gpui::*;
+use gpui_component::button::{Button, ButtonVariants};
+use gpui_component::input::{Input, InputState};
+use gpui_component::{ActiveTheme as _, Sizable, h_flex, v_flex};
+
+use super::modal_base::{render_section_header, render_setting_row};
+
+/// Width of the hex inputs
+const HEX_INPUT_WIDTH: f32 = 110.0;
+
+/// Render the custom theme section: a button to start a theme, or the
+/// editor while one is being made
+pub fn render_theme_editor_section(
+    editor: Option<&ThemeEditor>,
+    cx: &mut Context<Humanboard>,
+) -> Div {
+    let section = v_flex()
+        .gap_4()
+        .child(render_section_header("Custom Theme", cx));
+    match editor {
+        None => section.child(render_setting_row(
+            "Theme Editor",
+            "Change the colors of the current theme and save it as a theme of your own",
+            Button::new("open-theme-editor")
+                .label("Customize…")
+                .small()
+                .on_click(cx.listener(|this, _, window, cx| {
+                    this.open_theme_editor(window, cx);
+                })),
+            cx,
+        )),
+        Some(editor) => section.child(render_theme_editor(editor, cx)),
+    }
+}
+
+fn render_theme_editor(editor: &ThemeEditor, cx: &mut Context<Humanboard>) -> Div {
+    let fg = cx.theme().foreground;
+    let muted_fg = cx.theme().muted_foreground;
+    let border = cx.theme().border;
+    let danger = cx.theme().danger;
+
+    let themes_dir = custom_themes_dir()
+        .map(|p| p.display().to_string())
+        .unwrap_or_else(|| "themes".to_string());
+
+    let row = |color: EditableColor, input: &Entity<InputState>, cx: &App| {
+        let text = input.read(cx).text().to_string();
+        let parsed = parse_color(&text);
+        h_flex()
+            .py_1()
+            .gap_3()
+            .items_center()
+            .child(div().flex_1().text_sm().text_color(fg).child(color.label()))
+            .child(
+                div()
+                    .size(px(20.0))
+                    .rounded(px(4.0))
+                    .border_1()
+                    .border_color(border)
+                    .when_some(parsed, |d, color| d.bg(color)),
+            )
+            .child(
+                div()
+                    .w(px(HEX_INPUT_WIDTH))
+                    .child(Input::new(input).xsmall()),
+            )
+            .when(parsed.is_none(), |d| {
+                d.child(div().text_xs().text_color(danger).child("Not a color"))
+            })
+    };
+
+    let (interface, content): (Vec<_>, Vec<_>) = editor
+        .color_inputs
+        .iter()
+        .partition(|(color, _)| !matches!(color, EditableColor::Content(_)));
+
+    v_flex()
+        .gap_3()
+        .child(div().text_xs().text_color(muted_fg).child(format!(
+            "Colors apply as you type them, as #rrggbb or #rrggbbaa. Saved themes go in {}.",
+            themes_dir
+        )))
+        .child(
+            h_flex()
+                .gap_3()
+                .items_center()
+                .child(div().flex_1().text_sm().text_color(fg).child("Name"))
+                .child(
+                    div()
+                        .w(px(HEX_INPUT_WIDTH * 2.0))
+                        .child(Input::new(&editor.name_input).xsmall()),
+                ),
+        )
+        .child(div().text_xs().text_color(muted_fg).child("Interface"))
+        .child(
+            v_flex().children(
+                interface
+                    .into_iter()
+                    .map(|(color, input)| row(*color, input, cx)),
+            ),
+        )
+        .child(div().text_xs().text_color(muted_fg).child("Content Types"))
+        .child(
+            v_flex().children(
+                content
+                    .into_iter()
+                    .map(|(color, input)| row(*color, input, cx)),
+            ),
+        )
+        .child(
+            h_flex()
+                .gap_2()
+                .justify_end()
+                .child(
+                    Button::new("cancel-theme-editor")
+                        .label("Cancel")
+                        .small()
+                        .ghost()
+                        .on_click(cx.listener(|this, _, _, cx| {
+                            this.cancel_theme_editor(cx);
+                        })),
+                )
+                .child(
+                    Button::new("save-theme-draft")
+                        .label("Save Theme")
+                        .small()
+                        .primary()
+                        .on_click(cx.listener(|this, _, _, cx| {
+                            this.save_theme_draft(cx);
+                        })),
+                ),
+        )
+}
@@ -67,8 +67,10 @@ pub fn render_shortcuts_overlay(cx: &mut Context<Humanboard>) -> impl IntoElemen
                                 vec![
                                     ("Cmd+K", "Command palette"),
                                     ("Cmd+N", "New board"),
+                                    ("Cmd+Shift+N", "New window"),
                                     ("Cmd+H", "Go home"),
                                     ("Cmd+O", "Open file"),
+                                    ("Cmd+P", "Quick open a file"),
                                     ("Cmd+,", "Settings"),
                                     ("Cmd+Q", "Quit"),
                                 ],
@@ -80,10 +82,34 @@ pub fn render_shortcuts_overlay(cx: &mut Context<Humanboard>) -> impl IntoElemen
                                     ("Cmd+=", "Zoom in"),
                                     ("Cmd+-", "Zoom out"),
                                     ("Cmd+0", "Reset zoom"),
+                                    ("Cmd+1", "Zoom to fit"),
+                                    ("Cmd+2", "Zoom to selection"),
                                     ("Cmd+D", "Duplicate selected"),
+                                    ("Cmd+C / Cmd+V", "Copy / paste items, across boards"),
+                                    ("Cmd+Shift+C", "Copy selected as PNG"),
                                     ("Del", "Delete selected"),
+                                    ("Arrows", "Nudge 1px (Shift: 10px)"),
+                                    ("Tab / Shift+Tab", "Select next/previous item"),
+                                    ("Cmd+Alt+Arrows", "Select nearest item in direction"),
                                     ("Cmd+Z", "Undo"),
                                     ("Cmd+Shift+Z", "Redo"),
+                                    ("Cmd+Alt+H", "Version history"),
+                                    ("Cmd+Alt+K", "Create checkpoint"),
+                                    ("Cmd+Alt+O", "Outline"),
+                                    ("Cmd+Alt+Z", "Undo history"),
+                                    ("Cmd+F", "Find on board"),
+                                    ("Enter / Shift+Enter", "Next/previous match"),
+                                    ("Alt+Drag", "Duplicate and move"),
+                                    ("Shift+Drag", "Move along one axis"),
+                                    ("Shift+Resize", "Keep aspect ratio"),
+                                    ("Hold Cmd", "Move without snapping"),
+                                    ("Alt+Drag canvas", "Lasso select"),
+                                    ("Space+Drag", "Pan canvas"),
+                                    ("Space / F8", "Play/pause selected media"),
+                                    ("Cmd+Alt+P", "Pause all media"),
+                                    ("Middle-drag", "Pan canvas"),
+                                    ("Shift+Scroll", "Scroll horizontally"),
+                                    ("Right-click", "Context menu"),
                                     ("Esc", "Close palette/preview"),
                                 ],
                                 cx,
@@ -94,7 +120,8 @@ pub fn render_shortcuts_overlay(cx: &mut Context<Humanboard>) -> impl IntoElemen
                                     ("T", "Toggle split"),
                                     ("←  →", "Prev / Next page"),
                                     ("+ - 0", "Zoom PDF"),
-                                    ("Cmd+]  [", "Next / Prev tab"),
+                                    ("Cmd+Shift+]  [", "Next / Prev tab"),
+                                    ("Cmd+[  ]", "Back / Forward"),
                                     ("Cmd+W", "Close tab"),
                                     ("Esc", "Close preview"),
                                 ],
@@ -0,0 +1,200 @@
+//! Undo History side panel.
+//!
+//! Lists the undo steps of the current board, oldest first:
+//! - What each step did and how long ago
+//! - Steps that were undone are dimmed until redone or replaced
+//! - Click a step to undo or redo up to it; click the first row to undo all
+//! - The chevron folds the panel down to its header
+
+use crate::app::{Humanboard, UndoHistoryPanel};
+use crate::constants::{FOOTER_HEIGHT, HEADER_HEIGHT};
+use crate::undo_history::{UndoStep, format_step_time};
+use gpui::prelude::FluentBuilder;
+use gpui::*;
+use gpui_component::{ActiveTheme as _, Icon, IconName, h_flex, v_flex};
+use std::time::SystemTime;
+
+/// Width of the history panel
+const UNDO_HISTORY_WIDTH: f32 = 260.0;
+
+/// Render the History side panel along the right edge of the canvas
+pub fn render_undo_history_panel(
+    panel: &UndoHistoryPanel,
+    steps: Vec<UndoStep>,
+    position: usize,
+    cx: &mut Context<Humanboard>,
+) -> impl IntoElement {
+    let bg = cx.theme().background;
+    let border = cx.theme().border;
+    let fg = cx.theme().foreground;
+    let muted_fg = cx.theme().muted_foreground;
+    let list_hover = cx.theme().list_hover;
+
+    let undone = steps.iter().filter(|step| step.position > position).count();
+    let summary = match (steps.len(), undone) {
+        (1, 0) => "1 step".to_string(),
+        (count, 0) => format!("{} steps", count),
+        (count, undone) => format!("{} steps · {} undone", count, undone),
+    };
+    let now = SystemTime::now();
+    let collapsed = panel.collapsed;
+
+    deferred(
+        v_flex()
+            .id("undo-history-panel")
+            .occlude()
+            .absolute()
+            .top(px(HEADER_HEIGHT))
+            .when(!collapsed, |d| d.bottom(px(FOOTER_HEIGHT)))
+            .right_0()
+            .w(px(UNDO_HISTORY_WIDTH))
+            .bg(bg)
+            .border_l_1()
+            .when(collapsed, |d| d.border_b_1())
+            .border_color(border)
+            .shadow_lg()
+            .on_mouse_down(MouseButton::Left, |_, _, cx| {
+                cx.stop_propagation();
+            })
+            .on_scroll_wheel(|_, _, cx| {
+                cx.stop_propagation();
+            })
+            // Header
+            .child(
+                h_flex()
+                    .w_full()
+                    .px(px(16.0))
+                    .py(px(12.0))
+                    .when(!collapsed, |d| d.border_b_1())
+                    .border_color(border)
+                    .justify_between()
+                    .child(
+                        h_flex()
+                            .id("collapse-undo-history")
+                            .gap(px(6.0))
+                            .cursor_pointer()
+                            .on_click(cx.listener(|this, _, _, cx| {
+                                this.toggle_undo_history_collapsed(cx);
+                            }))
+                            .child(
+                                Icon::new(if collapsed {
+                                    IconName::ChevronRight
+                                } else {
+                                    IconName::ChevronDown
+                                })
+                                .size(px(14.0))
+                                .text_color(muted_fg),
+                            )
+                            .child(
+                                v_flex()
+                                    .child(
+                                        div()
+                                            .text_size(px(14.0))
+                                            .font_weight(FontWeight::SEMIBOLD)
+                                            .text_color(fg)
+                                            .child("History"),
+                                    )
+                                    .child(
+                                        div()
+                                            .text_size(px(12.0))
+                                            .text_color(muted_fg)
+                                            .child(summary),
+                                    ),
+                            ),
+                    )
+                    .child(
+                        div()
+                            .id("close-undo-history")
+                            .cursor_pointer()
+                            .p(px(4.0))
+                            .rounded(px(4.0))
+                            .hover(|s| s.bg(list_hover))
+                            .on_click(cx.listener(|this, _, _, cx| {
+                                this.toggle_undo_history(cx);
+                            }))
+                            .child(
+                                Icon::new(IconName::Close)
+                                    .size(px(16.0))
+                                    .text_color(muted_fg),
+                            ),
+                    ),
+            )
+            // Steps
+            .when(!collapsed, |d| {
+                d.child(
+                    v_flex()
+                        .id("undo-history-list")
+                        .flex_1()
+                        .min_h_0()
+                        .overflow_y_scroll()
+                        .track_scroll(&panel.scroll)
+                        .p(px(8.0))
+                        .gap(px(2.0))
+                        .child(render_step_row("Start of history", None, 0, position, cx))
+                        .children(steps.iter().map(|step| {
+                            render_step_row(
+                                &step.label,
+                                Some(format_step_time(step.at, now)),
+                                step.position,
+                                position,
+                                cx,
+                            )
+                        })),
+                )
+            }),
+    )
+    .with_priority(500)
+}
+
+/// Render a clickable step row. The row at `current` is highlighted and
+/// rows after it are dimmed, as they were undone.
+fn render_step_row(
+    label: &str,
+    time: Option<String>,
+    step_position: usize,
+    current: usize,
+    cx: &mut Context<Humanboard>,
+) -> Stateful<Div> {
+    let fg = cx.theme().foreground;
+    let muted_fg = cx.theme().muted_foreground;
+    let list_hover = cx.theme().list_hover;
+    let list_active = cx.theme().list_active;
+    let is_current = step_position == current;
+    let is_undone = step_position > current;
+
+    h_flex()
+        .id(ElementId::Name(
+            format!("undo-step-{}", step_position).into(),
+        ))
+        .w_full()
+        .px(px(8.0))
+        .py(px(5.0))
+        .gap(px(8.0))
+        .rounded(px(6.0))
+        .cursor_pointer()
+        .when(is_current, |d| d.bg(list_active))
+        .when(!is_current, |d| d.hover(|s| s.bg(list_hover)))
+        .on_click(cx.listener(move |this, _, _, cx| {
+            this.jump_to_undo_step(step_position, cx);
+        }))
+        .child(
+            div()
+                .flex_1()
+                .min_w_0()
+                .text_size(px(13.0))
+                .text_color(if is_undone { muted_fg } else { fg })
+                .overflow_hidden()
+                .text_ellipsis()
+                .whitespace_nowrap()
+                .child(label.to_string()),
+        )
+        .when_some(time, |d, time| {
+            d.child(
+                div()
+                    .flex_shrink_0()
+                    .text_size(px(11.0))
+                    .text_color(muted_fg)
+                    .child(time),
+            )
+        })
+}
@@ -0,0 +1,76 @@
+//! Chooser shown under a freshly pasted URL.
+//!
+//! A row of buttons for what the URL can become - embed, image, bookmark
+//! or plain link - with the current kind highlighted. Picking another one
+//! changes the item; the chooser closes by itself after a few seconds.
+
+use crate::app::{Humanboard, UrlPasteChooser};
+use gpui::*;
+use gpui_component::button::{Button, ButtonVariants};
+use gpui_component::{ActiveTheme as _, IconName, Sizable, h_flex};
+
+/// Gap between the pasted item and the chooser
+const CHOOSER_GAP: f32 = 8.0;
+
+/// Render the chooser with its top-left corner at `anchor` (window
+/// coordinates), under the pasted item
+pub fn render_url_paste_chooser(
+    chooser: &UrlPasteChooser,
+    anchor: Point<Pixels>,
+    cx: &mut Context<Humanboard>,
+) -> impl IntoElement {
+    let bg = cx.theme().background;
+    let border = cx.theme().border;
+    let muted_fg = cx.theme().muted_foreground;
+
+    deferred(
+        h_flex()
+            .id("url-paste-chooser")
+            .occlude()
+            .absolute()
+            .left(anchor.x)
+            .top(anchor.y + px(CHOOSER_GAP))
+            .gap_1()
+            .px(px(6.0))
+            .py(px(4.0))
+            .items_center()
+            .bg(bg)
+            .border_1()
+            .border_color(border)
+            .rounded(px(8.0))
+            .shadow_md()
+            .on_mouse_down(MouseButton::Left, |_, _, cx| {
+                cx.stop_propagation();
+            })
+            .child(
+                div()
+                    .px(px(4.0))
+                    .text_xs()
+                    .text_color(muted_fg)
+                    .child("Paste as"),
+            )
+            .children(chooser.kinds.iter().map(|&kind| {
+                let button = Button::new(SharedString::from(format!("paste-as-{:?}", kind)))
+                    .label(kind.label())
+                    .xsmall()
+                    .on_click(cx.listener(move |this, _, _, cx| {
+                        this.choose_url_paste_kind(kind, cx);
+                    }));
+                if kind == chooser.current {
+                    button.primary()
+                } else {
+                    button.ghost()
+                }
+            }))
+            .child(
+                Button::new("dismiss-url-paste-chooser")
+                    .icon(IconName::Close)
+                    .xsmall()
+                    .ghost()
+                    .on_click(cx.listener(|this, _, _, cx| {
+                        this.dismiss_url_paste_chooser(cx);
+                    })),
+            ),
+    )
+    .with_priority(500)
+}
@@ -0,0 +1,287 @@
+//! Version history browser modal.
+//!
+//! Lists the on-disk versions of the current board on the left, named
+//! checkpoints by name, and shows a preview of the selected version on the
+//! right:
+//! - Minimap of item positions
+//! - Item counts by type
+//! - Compare button, marking the differences on the canvas
+//! - Restore button (undoable)
+//! - Delete button, removing the version and its kept file texts from disk
+
+use crate::app::{Humanboard, VersionHistoryModal};
+use crate::board_versions::VersionSummary;
+use crate::constants::{MODAL_BACKDROP_OPACITY, MODAL_HEIGHT_MD, MODAL_WIDTH_LG};
+use gpui::prelude::FluentBuilder;
+use gpui::*;
+use gpui_component::button::{Button, ButtonVariants};
+use gpui_component::{ActiveTheme as _, Disableable as _, Icon, IconName, h_flex, v_flex};
+
+/// Size of the minimap preview area
+const MINIMAP_WIDTH: f32 = 360.0;
+const MINIMAP_HEIGHT: f32 = 220.0;
+
+/// Render the version history modal
+pub fn render_version_history_modal(
+    modal: &VersionHistoryModal,
+    cx: &mut Context<Humanboard>,
+) -> impl IntoElement {
+    let bg = cx.theme().background;
+    let border = cx.theme().border;
+    let fg = cx.theme().foreground;
+    let muted_fg = cx.theme().muted_foreground;
+    let list_hover = cx.theme().list_hover;
+    let list_active = cx.theme().list_active;
+
+    let selected = modal.selected;
+    let can_restore = modal.selected_state.is_some();
+
+    deferred(
+        div()
+            .id("version-history-backdrop")
+            .absolute()
+            .top_0()
+            .left_0()
+            .size_full()
+            .bg(hsla(0.0, 0.0, 0.0, MODAL_BACKDROP_OPACITY))
+            .flex()
+            .items_center()
+            .justify_center()
+            .on_mouse_down(
+                MouseButton::Left,
+                cx.listener(|this, _, _, cx| {
+                    this.close_version_history(cx);
+                }),
+            )
+            .child(
+                v_flex()
+                    .id("version-history-modal")
+                    .on_mouse_down(MouseButton::Left, |_, _, cx| {
+                        cx.stop_propagation();
+                    })
+                    .w(px(MODAL_WIDTH_LG))
+                    .bg(bg)
+                    .border_1()
+                    .border_color(border)
+                    .rounded(px(12.0))
+                    .overflow_hidden()
+                    .shadow_lg()
+                    // Header
+                    .child(
+                        h_flex()
+                            .w_full()
+                            .px(px(20.0))
+                            .py(px(16.0))
+                            .border_b_1()
+                            .border_color(border)
+                            .justify_between()
+                            .child(
+                                div()
+                                    .text_size(px(16.0))
+                                    .font_weight(FontWeight::SEMIBOLD)
+                                    .text_color(fg)
+                                    .child("Version History"),
+                            )
+                            .child(
+                                div()
+                                    .id("close-version-history")
+                                    .cursor_pointer()
+                                    .p(px(4.0))
+                                    .rounded(px(4.0))
+                                    .hover(|s| s.bg(list_hover))
+                                    .on_click(cx.listener(|this, _, _, cx| {
+                                        this.close_version_history(cx);
+                                    }))
+                                    .child(
+                                        Icon::new(IconName::Close)
+                                            .size(px(16.0))
+                                            .text_color(muted_fg),
+                                    ),
+                            ),
+                    )
+                    // Content: version list + preview
+                    .child(
+                        h_flex()
+                            .w_full()
+                            .h(px(MODAL_HEIGHT_MD - 120.0))
+                            .child(
+                                v_flex()
+                                    .id("version-list")
+                                    .w(px(220.0))
+                                    .h_full()
+                                    .overflow_y_scroll()
+                                    .border_r_1()
+                                    .border_color(border)
+                                    .p(px(8.0))
+                                    .gap(px(2.0))
+                                    .children(modal.versions.iter().enumerate().map(
+                                        |(index, version)| {
+                                            let is_selected = selected == Some(index);
+                                            div()
+                                                .id(ElementId::Name(
+                                                    format!("version-{}", version.timestamp_ms)
+                                                        .into(),
+                                                ))
+                                                .px(px(10.0))
+                                                .py(px(8.0))
+                                                .rounded(px(6.0))
+                                                .text_size(px(13.0))
+                                                .text_color(fg)
+                                                .when(is_selected, |d| d.bg(list_active))
+                                                .when(!is_selected, |d| {
+                                                    d.hover(|s| s.bg(list_hover))
+                                                })
+                                                .cursor_pointer()
+                                                .on_click(cx.listener(move |this, _, _, cx| {
+                                                    this.select_version(index, cx);
+                                                }))
+                                                .child(version.title())
+                                                .when(version.is_checkpoint(), |d| {
+                                                    d.child(
+                                                        div()
+                                                            .text_size(px(11.0))
+                                                            .text_color(muted_fg)
+                                                            .child(version.formatted_age()),
+                                                    )
+                                                })
+                                        },
+                                    )),
+                            )
+                            .child(
+                                v_flex()
+                                    .flex_1()
+                                    .h_full()
+                                    .p(px(20.0))
+                                    .gap(px(16.0))
+                                    .when_some(modal.preview.as_ref(), |d, preview| {
+                                        d.child(render_minimap(preview, cx))
+                                            .child(render_type_counts(preview, cx))
+                                    })
+                                    .when_some(modal.preview_error.clone(), |d, error| {
+                                        d.child(
+                                            div()
+                                                .text_size(px(13.0))
+                                                .text_color(cx.theme().danger)
+                                                .child(format!(
+                                                    "Couldn't load this version: {}",
+                                                    error
+                                                )),
+                                        )
+                                    }),
+                            ),
+                    )
+                    // Footer with buttons
+                    .child(
+                        h_flex()
+                            .w_full()
+                            .px(px(20.0))
+                            .py(px(16.0))
+                            .border_t_1()
+                            .border_color(border)
+                            .justify_end()
+                            .gap(px(12.0))
+                            .child(
+                                Button::new("delete-version")
+                                    .label("Delete")
+                                    .ghost()
+                                    .disabled(selected.is_none())
+                                    .on_click(cx.listener(|this, _, _, cx| {
+                                        this.delete_selected_version(cx);
+                                    })),
+                            )
+                            .child(
+                                Button::new("cancel-version-restore")
+                                    .label("Cancel")
+                                    .ghost()
+                                    .on_click(cx.listener(|this, _, _, cx| {
+                                        this.close_version_history(cx);
+                                    })),
+                            )
+                            .child(
+                                Button::new("compare-version")
+                                    .label("Compare on Canvas")
+                                    .ghost()
+                                    .disabled(!can_restore)
+                                    .on_click(cx.listener(|this, _, _, cx| {
+                                        this.compare_selected_version(cx);
+                                    })),
+                            )
+                            .child(
+                                Button::new("confirm-version-restore")
+                                    .label("Restore Version")
+                                    .primary()
+                                    .disabled(!can_restore)
+                                    .on_click(cx.listener(|this, _, _, cx| {
+                                        this.restore_selected_version(cx);
+                                    })),
+                            ),
+                    ),
+            ),
+    )
+    .with_priority(1600)
+}
+
+/// Render a scaled-down map of item positions in the selected version
+fn render_minimap(preview: &VersionSummary, cx: &Context<Humanboard>) -> Div {
+    let muted = cx.theme().muted;
+    let primary = cx.theme().primary;
+    let muted_fg = cx.theme().muted_foreground;
+
+    let map = div()
+        .relative()
+        .w(px(MINIMAP_WIDTH))
+        .h(px(MINIMAP_HEIGHT))
+        .bg(muted)
+        .rounded(px(8.0))
+        .overflow_hidden();
+
+    let Some((min_x, min_y, max_x, max_y)) = preview.extent() else {
+        return map.flex().items_center().justify_center().child(
+            div()
+                .text_size(px(12.0))
+                .text_color(muted_fg)
+                .child("Empty board"),
+        );
+    };
+
+    let padding = 8.0;
+    let scale = ((MINIMAP_WIDTH - padding * 2.0) / (max_x - min_x).max(1.0))
+        .min((MINIMAP_HEIGHT - padding * 2.0) / (max_y - min_y).max(1.0));
+
+    map.children(preview.item_bounds.iter().map(|&(x, y, w, h)| {
+        div()
+            .absolute()
+            .left(px(padding + (x - min_x) * scale))
+            .top(px(padding + (y - min_y) * scale))
+            .w(px((w * scale).max(2.0)))
+            .h(px((h * scale).max(2.0)))
+            .bg(primary.opacity(0.35))
+            .border_1()
+            .border_color(primary)
+            .rounded(px(2.0))
+    }))
+}
+
+/// Render item counts by type for the selected version
+fn render_type_counts(preview: &VersionSummary, cx: &Context<Humanboard>) -> Div {
+    let fg = cx.theme().foreground;
+    let muted_fg = cx.theme().muted_foreground;
+
+    v_flex()
+        .gap(px(4.0))
+        .child(
+            div()
+                .text_size(px(13.0))
+                .font_weight(FontWeight::MEDIUM)
+                .text_color(fg)
+                .child(format!("{} items", preview.item_count)),
+        )
+        .children(preview.counts_by_type.iter().map(|(label, count)| {
+            h_flex()
+                .justify_between()
+                .text_size(px(12.0))
+                .text_color(muted_fg)
+                .child(label.clone())
+                .child(count.to_string())
+        }))
+}
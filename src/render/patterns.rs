@@ -0,0 +1,96 @@
+//! Canvas background patterns
+//!
+//! Dot and line grids are painted straight to the GPU at the start of the
+//! canvas paint, under the item backgrounds. Only the part of the grid inside
+//! the canvas bounds is painted, and `pattern_spacing` thins the grid out when
+//! zoomed out, so the quad count depends on the window size rather than the
+//! board. Solid colors and images are plain elements behind the canvas and
+//! don't go through here.
+
+use crate::canvas_background::{CanvasBackground, grid_positions, pattern_spacing};
+use crate::profile_scope;
+use gpui::*;
+
+/// Side of a grid dot in pixels
+const DOT_SIZE: f32 = 2.0;
+
+/// Width of a grid line in pixels
+const LINE_WIDTH: f32 = 1.0;
+
+/// A dot or line grid ready to paint
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GridPattern {
+    /// Lines rather than dots
+    pub lines: bool,
+    /// On-screen gap between dots or lines
+    pub spacing: f32,
+    pub color: Hsla,
+}
+
+impl GridPattern {
+    /// The grid for a background style, or `None` for styles without one
+    pub fn for_background(
+        background: &CanvasBackground,
+        grid_size: f32,
+        zoom: f32,
+        color: Hsla,
+    ) -> Option<Self> {
+        let lines = match background {
+            CanvasBackground::Dots => false,
+            CanvasBackground::Lines => true,
+            _ => return None,
+        };
+        Some(Self {
+            lines,
+            spacing: pattern_spacing(grid_size, zoom)?,
+            color,
+        })
+    }
+
+    /// Paint the grid over `bounds` for a canvas panned by `canvas_offset`
+    pub fn paint(&self, window: &mut Window, bounds: Bounds<Pixels>, canvas_offset: Point<Pixels>) {
+        profile_scope!("paint_grid_pattern");
+
+        let (left, top) = (f32::from(bounds.origin.x), f32::from(bounds.origin.y));
+        let (width, height) = (f32::from(bounds.size.width), f32::from(bounds.size.height));
+        let xs = grid_positions(left, width, f32::from(canvas_offset.x), self.spacing);
+        let ys = grid_positions(top, height, f32::from(canvas_offset.y), self.spacing);
+
+        if self.lines {
+            for &x in &xs {
+                window.paint_quad(fill(
+                    Bounds::new(
+                        point(px(x), bounds.origin.y),
+                        size(px(LINE_WIDTH), bounds.size.height),
+                    ),
+                    self.color,
+                ));
+            }
+            for &y in &ys {
+                window.paint_quad(fill(
+                    Bounds::new(
+                        point(bounds.origin.x, px(y)),
+                        size(bounds.size.width, px(LINE_WIDTH)),
+                    ),
+                    self.color,
+                ));
+            }
+        } else {
+            let half = DOT_SIZE / 2.0;
+            for &y in &ys {
+                for &x in &xs {
+                    window.paint_quad(
+                        fill(
+                            Bounds::new(
+                                point(px(x - half), px(y - half)),
+                                size(px(DOT_SIZE), px(DOT_SIZE)),
+                            ),
+                            self.color,
+                        )
+                        .corner_radii(px(half)),
+                    );
+                }
+            }
+        }
+    }
+}
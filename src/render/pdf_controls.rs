@@ -0,0 +1,178 @@
+//! Page controls on PDF cards and PDF preview tabs.
+//!
+//! PDFs with more than one page get a small bar along the bottom of their
+//! card with previous and next buttons around a `3 / 12` page indicator.
+//! Like the volume controls they are laid over the card in `render_items`,
+//! so the card's content itself can stay in the render cache.
+//!
+//! PDF tabs in the preview panel get a footer below the viewer with the same
+//! buttons, a button showing the outline if the PDF has one, and a Place on
+//! Canvas button for the page being shown.
+
+use crate::app::Humanboard;
+use gpui::prelude::FluentBuilder;
+use gpui::*;
+use gpui_component::button::{Button, ButtonVariants};
+use gpui_component::{ActiveTheme as _, Disableable as _, Sizable as _, h_flex};
+use std::path::Path;
+
+/// Height of the page controls, in canvas units
+pub const PDF_CONTROLS_HEIGHT: f32 = 22.0;
+
+/// Space between the page controls and the card's bottom edge, in canvas units
+const PDF_CONTROLS_INSET: f32 = 8.0;
+
+/// Previous/next page buttons and the page indicator along the bottom of a
+/// PDF card. `page` counts from 0.
+pub fn render_pdf_page_controls(
+    item_id: u64,
+    page: usize,
+    page_count: usize,
+    zoom: f32,
+    cx: &Context<Humanboard>,
+) -> Div {
+    let enabled = hsla(0.0, 0.0, 0.9, 1.0);
+    let disabled = hsla(0.0, 0.0, 0.45, 1.0);
+    let has_prev = page > 0;
+    let has_next = page + 1 < page_count;
+
+    let button = |id: &str, label: &'static str, active: bool, delta: isize| {
+        div()
+            .id(ElementId::Name(format!("pdf-{}-{}", id, item_id).into()))
+            .w(px(20.0 * zoom))
+            .h_full()
+            .flex()
+            .items_center()
+            .justify_center()
+            .text_size(px(13.0 * zoom))
+            .text_color(if active { enabled } else { disabled })
+            .when(active, |d| d.cursor_pointer())
+            .child(label)
+            .on_mouse_down(
+                MouseButton::Left,
+                cx.listener(move |this, _, _, cx| {
+                    cx.stop_propagation();
+                    this.turn_pdf_page(item_id, delta, cx);
+                }),
+            )
+    };
+
+    div()
+        .absolute()
+        .left_0()
+        .bottom(px(PDF_CONTROLS_INSET * zoom))
+        .w_full()
+        .flex()
+        .justify_center()
+        .child(
+            div()
+                .h(px(PDF_CONTROLS_HEIGHT * zoom))
+                .px(px(4.0 * zoom))
+                .rounded(px(PDF_CONTROLS_HEIGHT / 2.0 * zoom))
+                .bg(hsla(0.0, 0.0, 0.1, 0.8))
+                .flex()
+                .items_center()
+                .child(button("prev", "‹", has_prev, -1))
+                .child(
+                    div()
+                        .px(px(4.0 * zoom))
+                        .text_size(px(11.0 * zoom))
+                        .text_color(enabled)
+                        .child(page_indicator(page, page_count)),
+                )
+                .child(button("next", "›", has_next, 1)),
+        )
+}
+
+/// Height of the footer below PDF viewers in the preview panel
+pub const PDF_PREVIEW_FOOTER_HEIGHT: f32 = 40.0;
+
+/// Footer below a PDF viewer: previous/next page buttons, the page shown,
+/// an outline toggle and a button placing the page on the canvas.
+/// `page_count` is known once the PDF's text has been indexed, and
+/// `outline_open` once its outline has been read, if it has one.
+pub fn render_pdf_preview_footer(
+    path: &Path,
+    page: usize,
+    page_count: Option<usize>,
+    outline_open: Option<bool>,
+    cx: &mut Context<Humanboard>,
+) -> Div {
+    let has_next = page_count.is_none_or(|count| page + 1 < count);
+    let label = match page_count {
+        Some(count) => format!("Page {} of {}", page + 1, count),
+        None => format!("Page {}", page + 1),
+    };
+    let turn = |delta: isize| {
+        let path = path.to_path_buf();
+        cx.listener(move |this, _, _, cx| this.turn_preview_pdf_page(&path, delta, cx))
+    };
+    let place_path = path.to_path_buf();
+    let outline_path = path.to_path_buf();
+
+    h_flex()
+        .h(px(PDF_PREVIEW_FOOTER_HEIGHT))
+        .flex_shrink_0()
+        .bg(cx.theme().title_bar)
+        .border_t_1()
+        .border_color(cx.theme().border)
+        .items_center()
+        .justify_between()
+        .px_3()
+        .child(
+            h_flex()
+                .gap_1()
+                .items_center()
+                .when_some(outline_open, |d, open| {
+                    d.child(
+                        Button::new("pdf-preview-outline")
+                            .ghost()
+                            .small()
+                            .label("Outline")
+                            .tooltip(if open { "Hide outline" } else { "Show outline" })
+                            .on_click(cx.listener(move |this, _, _, cx| {
+                                this.toggle_pdf_outline(&outline_path, cx);
+                            })),
+                    )
+                })
+                .child(
+                    Button::new("pdf-preview-prev")
+                        .ghost()
+                        .small()
+                        .label("‹")
+                        .tooltip("Previous page")
+                        .disabled(page == 0)
+                        .on_click(turn(-1)),
+                )
+                .child(
+                    div()
+                        .text_xs()
+                        .text_color(cx.theme().muted_foreground)
+                        .child(label),
+                )
+                .child(
+                    Button::new("pdf-preview-next")
+                        .ghost()
+                        .small()
+                        .label("›")
+                        .tooltip("Next page")
+                        .disabled(!has_next)
+                        .on_click(turn(1)),
+                ),
+        )
+        .child(
+            Button::new("pdf-preview-place")
+                .primary()
+                .small()
+                .label("Place Page on Canvas")
+                .tooltip("Add this page to the board as an image")
+                .on_click(cx.listener(move |this, _, window, cx| {
+                    this.place_pdf_page_on_canvas(&place_path, window, cx);
+                })),
+        )
+}
+
+/// Page indicator text, e.g. `3 / 12` for the third of twelve pages
+pub fn page_indicator(page: usize, page_count: usize) -> String {
+    format!("{} / {}", page + 1, page_count)
+}
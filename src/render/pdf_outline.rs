@@ -0,0 +1,133 @@
+//! Outline sidebar of PDF preview tabs.
+//!
+//! PDFs with bookmarks get a sidebar to the left of the viewer listing their
+//! outline. Clicking an entry shows its page, and entries with children have
+//! a chevron that collapses them. The native viewer is moved right to make
+//! room, see `ensure_pdf_webview`.
+
+use crate::app::Humanboard;
+use crate::pdf::{OutlineRow, PDF_OUTLINES, active_outline_row, outline_rows};
+use crate::webviews::PdfWebView;
+use gpui::prelude::FluentBuilder;
+use gpui::*;
+use gpui_component::{ActiveTheme as _, h_flex, v_flex};
+use std::path::Path;
+
+/// Width of the outline sidebar
+pub const PDF_OUTLINE_WIDTH: f32 = 220.0;
+
+/// Indent per outline level
+const OUTLINE_INDENT: f32 = 12.0;
+
+/// Width the outline sidebar takes beside a PDF viewer: none if it is
+/// closed or the PDF has no outline
+pub fn outline_width(path: &Path, webview: &PdfWebView) -> f32 {
+    if webview.outline_open() && PDF_OUTLINES.has_outline(path) {
+        PDF_OUTLINE_WIDTH
+    } else {
+        0.0
+    }
+}
+
+/// The outline sidebar of a PDF viewer, if it is shown
+pub fn render_pdf_outline_sidebar(
+    path: &Path,
+    webview: &PdfWebView,
+    cx: &mut Context<Humanboard>,
+) -> Option<Stateful<Div>> {
+    if outline_width(path, webview) == 0.0 {
+        return None;
+    }
+    let outline = PDF_OUTLINES.get(path)?;
+    let rows = outline_rows(&outline, webview.collapsed_outline());
+    let active = active_outline_row(&rows, webview.page());
+
+    Some(
+        v_flex()
+            .id("pdf-outline")
+            .w(px(PDF_OUTLINE_WIDTH))
+            .h_full()
+            .flex_shrink_0()
+            .py_1()
+            .bg(cx.theme().title_bar)
+            .border_r_1()
+            .border_color(cx.theme().border)
+            .overflow_y_scroll()
+            .children(
+                rows.into_iter()
+                    .enumerate()
+                    .map(|(index, row)| render_outline_row(path, row, active == Some(index), cx)),
+            ),
+    )
+}
+
+fn render_outline_row(
+    path: &Path,
+    row: OutlineRow,
+    active: bool,
+    cx: &mut Context<Humanboard>,
+) -> Stateful<Div> {
+    let fg = cx.theme().foreground;
+    let muted_fg = cx.theme().muted_foreground;
+    let list_active = cx.theme().list_active;
+    let list_hover = cx.theme().list_hover;
+
+    let id = row
+        .index_path
+        .iter()
+        .map(|index| index.to_string())
+        .collect::<Vec<_>>()
+        .join("-");
+    let title = if row.title.is_empty() {
+        "Untitled".to_string()
+    } else {
+        row.title
+    };
+    let chevron = if row.expanded { "▾" } else { "▸" };
+    let toggle_path = path.to_path_buf();
+    let index_path = row.index_path;
+    let page = row.page;
+    let jump_path = path.to_path_buf();
+
+    h_flex()
+        .id(ElementId::Name(format!("pdf-outline-{}", id).into()))
+        .w_full()
+        .h(px(24.0))
+        .pl(px(4.0 + row.depth as f32 * OUTLINE_INDENT))
+        .pr_2()
+        .gap_1()
+        .items_center()
+        .text_xs()
+        .text_color(if active { fg } else { muted_fg })
+        .when(active, |d| d.bg(list_active))
+        .hover(|s| s.bg(list_hover))
+        .when(page.is_some(), |d| d.cursor_pointer())
+        .child(
+            div()
+                .w(px(12.0))
+                .flex_shrink_0()
+                .when(row.has_children, |d| {
+                    d.child(chevron).on_mouse_down(
+                        MouseButton::Left,
+                        cx.listener(move |this, _, _, cx| {
+                            cx.stop_propagation();
+                            this.toggle_pdf_outline_entry(&toggle_path, index_path.clone(), cx);
+                        }),
+                    )
+                }),
+        )
+        .child(
+            div()
+                .flex_1()
+                .min_w_0()
+                .overflow_hidden()
+                .text_ellipsis()
+                .whitespace_nowrap()
+                .child(title),
+        )
+        .when_some(page, |d, page| {
+            d.on_click(cx.listener(move |this, _, _, cx| {
+                this.show_pdf_outline_page(&jump_path, page, cx);
+            }))
+        })
+}
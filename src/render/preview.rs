@@ -3,13 +3,25 @@
 //! This module handles the preview panel for PDFs and Markdown files including:
 //! - Tab bar with multiple file support
 //! - PDF viewer with navigation
-//! - Markdown preview and editing
+//! - Markdown editing beside a live rendered preview
+//! - Image viewer with zoom and pan
+//! - Diffs of code and markdown files
 //! - Resizable splitter
 
-use crate::app::{Humanboard, PreviewTab, SplitDirection};
+use crate::app::{Humanboard, MAX_PREVIEW_PANES, PANE_SPLITTER_SIZE, PreviewTab, SplitDirection};
+use crate::constants::CODE_TAB_FONT_SIZE;
 use crate::focus::FocusContext;
 use crate::focus_ring::focus_ring_shadow;
 use crate::loading::render_loading_spinner;
+use crate::pdf::{PDF_OUTLINES, PDF_TEXT_INDEX};
+use crate::preview_history::NavHistory;
+use crate::data::{DATA_FILES, format_row_count};
+use crate::render::code_outline::render_code_outline_sidebar;
+use crate::render::data_preview::render_data_view;
+use crate::render::diff_view::render_diff_view;
+use crate::render::image_viewer::render_image_viewer;
+use crate::render::pdf_controls::render_pdf_preview_footer;
+use crate::render::pdf_outline::render_pdf_outline_sidebar;
 use gpui::prelude::FluentBuilder;
 use gpui::*;
 use gpui_component::Disableable as _;
@@ -17,17 +29,25 @@ use gpui_component::InteractiveElementExt as _;
 use gpui_component::button::{Button, ButtonVariants};
 use gpui_component::input::Input;
 use gpui_component::{ActiveTheme as _, Icon, IconName, Sizable, h_flex, v_flex};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+
+/// Height of a pane's tab bar
+const TAB_BAR_HEIGHT: f32 = 36.0;
 
 /// Render the tab bar for the preview panel
-/// `is_left_pane` indicates which pane this tab bar belongs to for proper event routing
+/// `pane` is the pane this tab bar belongs to, for proper event routing.
+/// `dragging_tab` is only given to the pane the tab is dragged from, and
+/// `drag_target` only to the pane it would be dropped in. The pane's
+/// `history` enables its back and forward buttons.
+#[allow(clippy::too_many_arguments)]
 pub fn render_tab_bar(
     tabs: &Vec<PreviewTab>,
     active_tab: usize,
     scroll_handle: &ScrollHandle,
     dragging_tab: Option<usize>,
     drag_target: Option<usize>,
-    is_left_pane: bool,
+    history: &NavHistory,
+    pane: usize,
     cx: &mut Context<Humanboard>,
 ) -> Stateful<Div> {
     let bg = cx.theme().title_bar;
@@ -38,10 +58,12 @@ pub fn render_tab_bar(
     let list_hover = cx.theme().list_hover;
     let primary = cx.theme().primary;
     let danger = cx.theme().danger;
+    let success = cx.theme().success;
+    let tab_count = tabs.len();
 
     div()
         .id("preview-tab-bar")
-        .h(px(36.0))
+        .h(px(TAB_BAR_HEIGHT))
         .w_full()
         .bg(bg)
         .border_b_1()
@@ -61,6 +83,7 @@ pub fn render_tab_bar(
                 }
             }),
         )
+        .child(render_history_buttons(history, pane, cx))
         .child(
             h_flex()
                 .flex_shrink_0()
@@ -69,10 +92,15 @@ pub fn render_tab_bar(
                     let filename = tab.title();
                     let is_markdown = matches!(tab, PreviewTab::Markdown { .. });
                     let is_code = matches!(tab, PreviewTab::Code { .. });
-                    let is_table = matches!(tab, PreviewTab::Table { .. });
+                    let is_table = matches!(tab, PreviewTab::Table { .. } | PreviewTab::Data { .. });
+                    let is_image = matches!(tab, PreviewTab::Image { .. });
+                    let is_diff = matches!(tab, PreviewTab::Diff { .. });
                     // Check dirty state - for tables, check the delegate's data source
                     let is_dirty = match tab {
                         PreviewTab::Code { dirty: true, .. } => true,
+                        PreviewTab::Markdown { editor: Some(editor), content, .. } => {
+                            editor.read(cx).text().to_string() != *content
+                        }
                         PreviewTab::Table { table_state: Some(state), .. } => {
                             state.read(cx).delegate().is_dirty()
                         }
@@ -81,9 +109,8 @@ pub fn render_tab_bar(
                     let is_preview = tab.is_preview();
                     let is_pinned = tab.is_pinned();
                     let is_being_dragged = dragging_tab == Some(index);
-                    let is_drag_target = drag_target == Some(index)
-                        && dragging_tab.is_some()
-                        && dragging_tab != Some(index);
+                    let is_drag_target =
+                        drag_target == Some(index) && dragging_tab != Some(index);
 
                     let display_name = if filename.len() > 20 {
                         format!("{}...", &filename[..17])
@@ -93,14 +120,16 @@ pub fn render_tab_bar(
 
                     let tab_index = index;
                     let tab_index_close = index;
-                    let tab_index_pin = index;
+                    let tab_index_menu = index;
                     let tab_index_drag = index;
 
                     h_flex()
                         .id(ElementId::Name(format!("tab-{}", index).into()))
                         .flex_shrink_0()
                         .gap_2()
-                        .px_3()
+                        // Pinned tabs shrink to their icon
+                        .when(is_pinned, |d| d.px_2())
+                        .when(!is_pinned, |d| d.px_3())
                         .py_1()
                         .bg(if is_active { list_active } else { bg })
                         .border_r_1()
@@ -112,25 +141,33 @@ pub fn render_tab_bar(
                         .hover(|style| style.bg(list_hover))
                         // Focus ring for keyboard navigation (WCAG compliance)
                         .focus(|s| s.shadow(focus_ring_shadow(primary)))
-                        .cursor(if dragging_tab.is_some() {
+                        .cursor(if dragging_tab.is_some() || drag_target.is_some() {
                             CursorStyle::ClosedHand
                         } else {
                             CursorStyle::PointingHand
                         })
                         .on_click(cx.listener(move |this, _event, _window, cx| {
                             if this.preview.dragging_tab.is_none() {
-                                this.switch_tab_in_pane(tab_index, is_left_pane, cx);
+                                this.switch_tab_in_pane(tab_index, pane, cx);
                             }
                         }))
                         // Double-click converts preview to permanent
                         .on_double_click(cx.listener(move |this, _event, _window, cx| {
-                            this.make_tab_permanent_in_pane(tab_index, is_left_pane, cx);
+                            this.make_tab_permanent_in_pane(tab_index, pane, cx);
                         }))
                         // Start drag on mouse down
                         .on_mouse_down(
                             MouseButton::Left,
                             cx.listener(move |this, event: &MouseDownEvent, _window, cx| {
-                                this.start_tab_drag_in_pane(tab_index_drag, event.position, is_left_pane, cx);
+                                this.close_context_menu(cx);
+                                this.start_tab_drag_in_pane(tab_index_drag, event.position, pane, cx);
+                            }),
+                        )
+                        // Right-click opens the tab menu for pinning and closing
+                        .on_mouse_down(
+                            MouseButton::Right,
+                            cx.listener(move |this, event: &MouseDownEvent, window, cx| {
+                                this.open_tab_menu(tab_index_menu, pane, event.position, window, cx);
                             }),
                         )
                         // Update drag target and position on mouse move
@@ -140,7 +177,7 @@ pub fn render_tab_bar(
                                 if this.preview.tab_drag_pending.is_some() || this.preview.dragging_tab.is_some() {
                                     this.update_tab_drag_position(event.position, cx);
                                     if this.preview.dragging_tab.is_some() {
-                                        this.update_tab_drag_target(tab_index_drag, cx);
+                                        this.update_tab_drag_target(tab_index_drag, pane, cx);
                                     }
                                 }
                             },
@@ -157,75 +194,148 @@ pub fn render_tab_bar(
                                 }
                             }),
                         )
-                        // Pin icon for pinned tabs
-                        .when(is_pinned, |d| {
-                            d.child(div().text_xs().text_color(muted_fg).mr_1().child("📌"))
-                        })
                         .child(if is_table {
                             Icon::new(IconName::LayoutDashboard)
                                 .xsmall()
                                 .text_color(primary)
+                        } else if is_diff {
+                            Icon::new(IconName::SquareTerminal)
+                                .xsmall()
+                                .text_color(success)
                         } else if is_code {
                             Icon::new(IconName::SquareTerminal)
                                 .xsmall()
                                 .text_color(primary) // Use theme primary for code
                         } else if is_markdown {
                             Icon::new(IconName::File).xsmall().text_color(primary)
+                        } else if is_image {
+                            Icon::new(IconName::File).xsmall().text_color(success)
                         } else {
                             Icon::new(IconName::File).xsmall().text_color(danger) // PDF
                         })
-                        .child(
-                            div()
-                                .text_xs()
-                                .whitespace_nowrap()
-                                .text_color(if is_active { fg } else { muted_fg })
-                                // Italicize preview tabs
-                                .when(is_preview, |d| d.italic())
-                                .child(display_name),
-                        )
-                        .child(
-                            div()
-                                .w(px(14.0))
-                                .h(px(14.0))
-                                .flex()
-                                .items_center()
-                                .justify_center()
-                                .rounded(px(2.0))
-                                .when(is_dirty, |d| {
-                                    // Show dot indicator when dirty
-                                    d.child(div().w(px(8.0)).h(px(8.0)).rounded_full().bg(primary))
-                                })
-                                .when(!is_dirty && !is_pinned, |d| {
-                                    // Show close button when not dirty and not pinned
-                                    d.text_xs()
-                                        .text_color(muted_fg)
-                                        .hover(|style| style.bg(list_hover).text_color(fg))
-                                        .on_mouse_down(
-                                            MouseButton::Left,
-                                            cx.listener(move |this, _event, _window, cx| {
-                                                this.close_tab_in_pane(tab_index_close, is_left_pane, cx);
-                                            }),
-                                        )
-                                        .child("×")
-                                })
-                                .when(is_pinned && !is_dirty, |d| {
-                                    // Show unpin option on hover for pinned tabs
-                                    d.text_xs()
-                                        .text_color(muted_fg)
-                                        .hover(|style| style.text_color(fg))
-                                        .on_mouse_down(
-                                            MouseButton::Left,
-                                            cx.listener(move |this, _event, _window, cx| {
-                                                this.toggle_tab_pinned_in_pane(tab_index_pin, is_left_pane, cx);
-                                            }),
-                                        )
-                                        .child("×")
-                                }),
-                        )
+                        .when(!is_pinned, |d| {
+                            d.child(
+                                div()
+                                    .text_xs()
+                                    .whitespace_nowrap()
+                                    .text_color(if is_active { fg } else { muted_fg })
+                                    // Italicize preview tabs
+                                    .when(is_preview, |d| d.italic())
+                                    .child(display_name),
+                            )
+                        })
+                        // Pinned tabs have no close button, only the dirty dot
+                        .when(!is_pinned || is_dirty, |d| {
+                            d.child(
+                                div()
+                                    .w(px(14.0))
+                                    .h(px(14.0))
+                                    .flex()
+                                    .items_center()
+                                    .justify_center()
+                                    .rounded(px(2.0))
+                                    .when(is_dirty, |d| {
+                                        // Show dot indicator when dirty
+                                        d.child(div().w(px(8.0)).h(px(8.0)).rounded_full().bg(primary))
+                                    })
+                                    .when(!is_dirty && !is_pinned, |d| {
+                                        // Show close button when not dirty and not pinned
+                                        d.text_xs()
+                                            .text_color(muted_fg)
+                                            .hover(|style| style.bg(list_hover).text_color(fg))
+                                            .on_mouse_down(
+                                                MouseButton::Left,
+                                                cx.listener(move |this, _event, _window, cx| {
+                                                    this.close_tab_in_pane(tab_index_close, pane, cx);
+                                                }),
+                                            )
+                                            .child("×")
+                                    })
+                            )
+                        })
+                })),
+        )
+        // Space after the last tab, dropping here puts the tab at the end
+        .child(
+            div()
+                .id("tab-bar-end")
+                .flex_1()
+                .min_w(px(24.0))
+                .h_full()
+                .when(drag_target == Some(tabs.len()), |d| {
+                    d.border_l_2().border_color(primary)
+                })
+                .on_mouse_move(cx.listener(move |this, event: &MouseMoveEvent, _window, cx| {
+                    if this.preview.dragging_tab.is_some() {
+                        this.update_tab_drag_position(event.position, cx);
+                        this.update_tab_drag_target(tab_count, pane, cx);
+                    }
                 })),
         )
 }
 
+/// Back and forward buttons at the start of a pane's tab bar
+fn render_history_buttons(
+    history: &NavHistory,
+    pane: usize,
+    cx: &mut Context<Humanboard>,
+) -> Div {
+    let border = cx.theme().border;
+    let fg = cx.theme().foreground;
+    let muted_fg = cx.theme().muted_foreground;
+    let list_hover = cx.theme().list_hover;
+
+    let button = |id: &'static str,
+                  icon: IconName,
+                  enabled: bool,
+                  back: bool,
+                  cx: &mut Context<Humanboard>| {
+        div()
+            .id(id)
+            .w(px(22.0))
+            .h(px(22.0))
+            .flex()
+            .items_center()
+            .justify_center()
+            .rounded(px(4.0))
+            .child(
+                Icon::new(icon)
+                    .xsmall()
+                    .text_color(if enabled { fg } else { muted_fg }),
+            )
+            .when(!enabled, |d| d.opacity(0.5))
+            .when(enabled, |d| {
+                d.cursor_pointer()
+                    .hover(|s| s.bg(list_hover))
+                    .on_click(cx.listener(move |this, _, window, cx| {
+                        this.navigate_pane(pane, back, window, cx);
+                    }))
+            })
+    };
+
+    h_flex()
+        .flex_shrink_0()
+        .h_full()
+        .px_1()
+        .gap_0p5()
+        .border_r_1()
+        .border_color(border)
+        .child(button(
+            "preview-back",
+            IconName::ChevronLeft,
+            history.can_go_back(),
+            true,
+            cx,
+        ))
+        .child(button(
+            "preview-forward",
+            IconName::ChevronRight,
+            history.can_go_forward(),
+            false,
+            cx,
+        ))
+}
+
 /// Render a ghost tab that follows the cursor during drag
 pub fn render_drag_ghost(
     tab: &PreviewTab,
@@ -310,16 +420,19 @@ fn render_drop_zone_indicator(
         )
 }
 
-/// Render split drop zones when dragging a tab - shows 4 directional zones
+/// Render split drop zones of a pane when dragging a tab - shows 4
+/// directional zones that split the pane, around its middle, where the tab
+/// is moved into the pane
 pub fn render_split_drop_zones(
+    pane: usize,
     active_zone: Option<crate::app::SplitDropZone>,
     cx: &mut Context<Humanboard>,
 ) -> Div {
     use crate::app::SplitDropZone;
 
-    // Zone strip dimensions
-    let side_width = px(120.0);
-    let top_bottom_height = px(100.0);
+    // Zone strip dimensions, relative so the zones fit small panes
+    let side_width = relative(0.25);
+    let top_bottom_height = relative(0.25);
 
     div()
         .absolute()
@@ -341,9 +454,9 @@ pub fn render_split_drop_zones(
                 .bottom(top_bottom_height)
                 .left(side_width)
                 .right(side_width)
-                .on_mouse_move(cx.listener(|this, event: &MouseMoveEvent, _window, cx| {
+                .on_mouse_move(cx.listener(move |this, event: &MouseMoveEvent, _window, cx| {
                     this.update_tab_drag_position(event.position, cx);
-                    this.set_tab_drag_split_zone(None, cx);
+                    this.drag_tab_over_pane(pane, cx);
                 }))
                 .on_mouse_up(
                     MouseButton::Left,
@@ -364,9 +477,9 @@ pub fn render_split_drop_zones(
                 .flex()
                 .items_center()
                 .justify_center()
-                .on_mouse_move(cx.listener(|this, event: &MouseMoveEvent, _window, cx| {
+                .on_mouse_move(cx.listener(move |this, event: &MouseMoveEvent, _window, cx| {
                     this.update_tab_drag_position(event.position, cx);
-                    this.set_tab_drag_split_zone(Some(SplitDropZone::Left), cx);
+                    this.set_tab_drag_split_zone(pane, Some(SplitDropZone::Left), cx);
                 }))
                 .on_mouse_up(
                     MouseButton::Left,
@@ -398,9 +511,9 @@ pub fn render_split_drop_zones(
                 .flex()
                 .items_center()
                 .justify_center()
-                .on_mouse_move(cx.listener(|this, event: &MouseMoveEvent, _window, cx| {
+                .on_mouse_move(cx.listener(move |this, event: &MouseMoveEvent, _window, cx| {
                     this.update_tab_drag_position(event.position, cx);
-                    this.set_tab_drag_split_zone(Some(SplitDropZone::Right), cx);
+                    this.set_tab_drag_split_zone(pane, Some(SplitDropZone::Right), cx);
                 }))
                 .on_mouse_up(
                     MouseButton::Left,
@@ -432,9 +545,9 @@ pub fn render_split_drop_zones(
                 .flex()
                 .items_center()
                 .justify_center()
-                .on_mouse_move(cx.listener(|this, event: &MouseMoveEvent, _window, cx| {
+                .on_mouse_move(cx.listener(move |this, event: &MouseMoveEvent, _window, cx| {
                     this.update_tab_drag_position(event.position, cx);
-                    this.set_tab_drag_split_zone(Some(SplitDropZone::Top), cx);
+                    this.set_tab_drag_split_zone(pane, Some(SplitDropZone::Top), cx);
                 }))
                 .on_mouse_up(
                     MouseButton::Left,
@@ -466,9 +579,9 @@ pub fn render_split_drop_zones(
                 .flex()
                 .items_center()
                 .justify_center()
-                .on_mouse_move(cx.listener(|this, event: &MouseMoveEvent, _window, cx| {
+                .on_mouse_move(cx.listener(move |this, event: &MouseMoveEvent, _window, cx| {
                     this.update_tab_drag_position(event.position, cx);
-                    this.set_tab_drag_split_zone(Some(SplitDropZone::Bottom), cx);
+                    this.set_tab_drag_split_zone(pane, Some(SplitDropZone::Bottom), cx);
                 }))
                 .on_mouse_up(
                     MouseButton::Left,
@@ -490,20 +603,71 @@ pub fn render_split_drop_zones(
         )
 }
 
-/// Render a single pane with tab bar and content
+/// Render the strip along the canvas edge beside the panel that a dragged
+/// tab can be dropped on to split the panel (visual only - the canvas
+/// handles the events, see `update_tab_drag_over_canvas`)
+pub fn render_canvas_edge_drop_zone(
+    split: SplitDirection,
+    active_zone: Option<crate::app::SplitDropZone>,
+    cx: &Context<Humanboard>,
+) -> Div {
+    use crate::app::SplitDropZone;
+    use crate::preview::CANVAS_EDGE_DROP_SIZE;
+
+    let primary = cx.theme().primary;
+    let (zone, label) = match split {
+        SplitDirection::Vertical => (SplitDropZone::Left, "Split →"),
+        SplitDirection::Horizontal => (SplitDropZone::Top, "Split ↓"),
+    };
+    let is_active = active_zone == Some(zone);
+
+    div()
+        .absolute()
+        .when(split == SplitDirection::Vertical, |d| {
+            d.top_0().bottom_0().right_0().w(px(CANVAS_EDGE_DROP_SIZE)).border_r_2()
+        })
+        .when(split == SplitDirection::Horizontal, |d| {
+            d.left_0().right_0().bottom_0().h(px(CANVAS_EDGE_DROP_SIZE)).border_b_2()
+        })
+        .border_color(if is_active {
+            primary
+        } else {
+            primary.opacity(0.4)
+        })
+        .bg(primary.opacity(if is_active { 0.25 } else { 0.08 }))
+        .flex()
+        .items_center()
+        .justify_center()
+        .child(
+            div()
+                .text_xs()
+                .font_weight(FontWeight::SEMIBOLD)
+                .text_color(if is_active {
+                    primary
+                } else {
+                    primary.opacity(0.7)
+                })
+                .child(label),
+        )
+}
+
+/// Render a single pane with tab bar and content. The drag state is only
+/// given to the panes a tab is dragged from and to, see [`render_tab_bar`].
+#[allow(clippy::too_many_arguments)]
 fn render_pane(
-    id: &'static str,
-    content_id: &'static str,
+    pane: usize,
     tabs: &Vec<PreviewTab>,
     active_tab: usize,
     scroll: &ScrollHandle,
     is_focused: bool,
     dragging_tab: Option<usize>,
     drag_target: Option<usize>,
+    history: &NavHistory,
     search_input: Option<&Entity<gpui_component::input::InputState>>,
+    replace_input: Option<&Entity<gpui_component::input::InputState>>,
     search_match_count: usize,
     search_current: usize,
-    is_left_pane: bool,
+    can_highlight: bool,
     cx: &mut Context<Humanboard>,
 ) -> Stateful<Div> {
     let bg = cx.theme().background;
@@ -511,7 +675,7 @@ fn render_pane(
     let primary = cx.theme().primary;
 
     v_flex()
-        .id(id)
+        .id(("preview-pane", pane))
         .flex_1()
         .min_h_0()
         .min_w_0()
@@ -523,35 +687,34 @@ fn render_pane(
         .on_mouse_down(
             MouseButton::Left,
             cx.listener(move |this, _event, _window, cx| {
-                if is_left_pane {
-                    this.focus_left_pane(cx);
-                } else {
-                    this.focus_right_pane(cx);
-                }
+                this.focus_pane(pane, cx);
             }),
         )
         .child(render_tab_bar(
             tabs,
             active_tab,
             scroll,
-            if is_focused { dragging_tab } else { None },
-            if is_focused { drag_target } else { None },
-            is_left_pane,
+            dragging_tab,
+            drag_target,
+            history,
+            pane,
             cx,
         ))
         .when(is_focused, |d| {
             d.when_some(search_input, |d, input| {
                 d.child(render_search_bar(
                     input,
+                    replace_input,
                     search_match_count,
                     search_current,
+                    can_highlight,
                     cx,
                 ))
             })
         })
         .child(
             div()
-                .id(content_id)
+                .id(("preview-pane-content", pane))
                 .flex_1()
                 .min_h_0()
                 .min_w_0()
@@ -565,254 +728,260 @@ fn render_pane(
         )
 }
 
-/// Render the split pane container when panel is split
+/// What every pane of a split panel is rendered with
+struct SplitPanes<'a> {
+    preview: &'a crate::app::PreviewPanel,
+    scrolls: &'a [ScrollHandle],
+    dragging_tab: Option<usize>,
+    drag_target: Option<usize>,
+    drag_from_pane: usize,
+    drag_target_pane: usize,
+    search_input: Option<&'a Entity<gpui_component::input::InputState>>,
+    replace_input: Option<&'a Entity<gpui_component::input::InputState>>,
+    search_match_count: usize,
+    search_current: usize,
+    can_highlight: bool,
+    split_zone: Option<crate::app::SplitDropZone>,
+}
+
+/// Render the split pane container when panel is split, following its
+/// split tree. A dragged tab can be dropped on a tab of any pane, on the
+/// edges of a pane to split it, or in the middle of a pane to add it there.
+#[allow(clippy::too_many_arguments)]
 pub fn render_split_panes(
     preview: &crate::app::PreviewPanel,
-    left_scroll: &ScrollHandle,
-    right_scroll: &ScrollHandle,
+    scrolls: &[ScrollHandle],
     dragging_tab: Option<usize>,
     drag_target: Option<usize>,
+    drag_from_pane: usize,
+    drag_target_pane: usize,
     search_input: Option<&Entity<gpui_component::input::InputState>>,
+    replace_input: Option<&Entity<gpui_component::input::InputState>>,
     search_match_count: usize,
     search_current: usize,
+    can_highlight: bool,
     split_zone: Option<crate::app::SplitDropZone>,
     cx: &mut Context<Humanboard>,
 ) -> Div {
-    use crate::app::{FocusedPane, SplitDropZone};
-    use gpui::DefiniteLength::Fraction;
-
-    let left_focused = preview.focused_pane == FocusedPane::Left;
-    let right_focused = preview.focused_pane == FocusedPane::Right;
-    let is_horizontal = preview.pane_split_horizontal;
-    let is_dragging = dragging_tab.is_some();
-    let pane_ratio = preview.pane_ratio;
-
-    let first_pane = render_pane(
-        "first-pane",
-        "first-pane-content",
-        &preview.tabs,
-        preview.active_tab,
-        left_scroll,
-        left_focused,
-        dragging_tab,
-        drag_target,
-        search_input,
-        search_match_count,
-        search_current,
-        true,
-        cx,
-    );
-
-    let second_pane = render_pane(
-        "second-pane",
-        "second-pane-content",
-        &preview.right_tabs,
-        preview.right_active_tab,
-        right_scroll,
-        right_focused,
+    let panes = SplitPanes {
+        preview,
+        scrolls,
         dragging_tab,
         drag_target,
+        drag_from_pane,
+        drag_target_pane,
         search_input,
+        replace_input,
         search_match_count,
         search_current,
-        false,
-        cx,
-    );
+        can_highlight,
+        split_zone,
+    };
+    render_pane_tree(&preview.layout, &mut Vec::new(), &panes, cx)
+}
 
-    // Wrap panes with drop zone overlays when dragging
-    // Use Fraction for sizing based on pane_ratio
-    let first_pane_wrapped = if is_dragging {
-        let target_zone = if is_horizontal {
-            SplitDropZone::Top
-        } else {
-            SplitDropZone::Left
-        };
-        let is_active = split_zone == Some(target_zone);
-        let primary = cx.theme().primary;
+/// Render a part of the split tree: a pane, or the two parts of a split
+/// with a draggable divider between them. `path` leads to the part.
+fn render_pane_tree(
+    tree: &crate::app::PaneTree,
+    path: &mut Vec<bool>,
+    panes: &SplitPanes,
+    cx: &mut Context<Humanboard>,
+) -> Div {
+    use crate::app::PaneTree;
+    use gpui::DefiniteLength::Fraction;
 
-        div()
-            .id("first-pane-drop")
-            .when(is_horizontal, |d| d.h(Fraction(pane_ratio)).w_full())
-            .when(!is_horizontal, |d| d.w(Fraction(pane_ratio)).h_full())
-            .min_h_0()
-            .min_w_0()
-            .flex()
-            .flex_col()
-            .relative()
-            .child(first_pane)
-            .child(
-                // Drop zone overlay
-                div()
-                    .id("first-pane-overlay")
-                    .absolute()
-                    .inset_0()
-                    .bg(if is_active {
-                        primary.opacity(0.2)
-                    } else {
-                        gpui::transparent_black()
-                    })
-                    .border_2()
-                    .border_color(if is_active {
-                        primary
-                    } else {
-                        gpui::transparent_black()
-                    })
-                    .rounded(px(4.0))
-                    .on_mouse_move(
-                        cx.listener(move |this, event: &MouseMoveEvent, _window, cx| {
-                            this.update_tab_drag_position(event.position, cx);
-                            this.set_tab_drag_split_zone(Some(target_zone), cx);
-                        }),
-                    )
-                    .on_mouse_up(
-                        MouseButton::Left,
-                        cx.listener(|this, _event, _window, cx| {
-                            this.finish_tab_drag(cx);
-                        }),
-                    ),
-            )
-    } else {
-        div()
-            .id("first-pane-wrap")
-            .when(is_horizontal, |d| d.h(Fraction(pane_ratio)).w_full())
-            .when(!is_horizontal, |d| d.w(Fraction(pane_ratio)).h_full())
-            .min_h_0()
-            .min_w_0()
-            .flex()
-            .flex_col()
-            .child(first_pane)
+    let (horizontal, ratio, first, second) = match tree {
+        PaneTree::Pane(pane) => return render_split_pane(*pane, panes, cx),
+        PaneTree::Split {
+            horizontal,
+            ratio,
+            first,
+            second,
+        } => (*horizontal, *ratio, first, second),
     };
 
-    let second_pane_wrapped = if is_dragging {
-        let target_zone = if is_horizontal {
-            SplitDropZone::Bottom
-        } else {
-            SplitDropZone::Right
-        };
-        let is_active = split_zone == Some(target_zone);
-        let primary = cx.theme().primary;
-
-        div()
-            .id("second-pane-drop")
-            .when(is_horizontal, |d| d.h(Fraction(1.0 - pane_ratio)).w_full())
-            .when(!is_horizontal, |d| d.w(Fraction(1.0 - pane_ratio)).h_full())
-            .min_h_0()
-            .min_w_0()
-            .flex()
-            .flex_col()
-            .relative()
-            .child(second_pane)
-            .child(
-                // Drop zone overlay
-                div()
-                    .id("second-pane-overlay")
-                    .absolute()
-                    .inset_0()
-                    .bg(if is_active {
-                        primary.opacity(0.2)
-                    } else {
-                        gpui::transparent_black()
-                    })
-                    .border_2()
-                    .border_color(if is_active {
-                        primary
-                    } else {
-                        gpui::transparent_black()
-                    })
-                    .rounded(px(4.0))
-                    .on_mouse_move(
-                        cx.listener(move |this, event: &MouseMoveEvent, _window, cx| {
-                            this.update_tab_drag_position(event.position, cx);
-                            this.set_tab_drag_split_zone(Some(target_zone), cx);
-                        }),
-                    )
-                    .on_mouse_up(
-                        MouseButton::Left,
-                        cx.listener(|this, _event, _window, cx| {
-                            this.finish_tab_drag(cx);
-                        }),
-                    ),
-            )
-    } else {
+    // Use Fraction for sizing based on the split's ratio
+    let part = |content: Div, share: f32| {
         div()
-            .id("second-pane-wrap")
-            .when(is_horizontal, |d| d.h(Fraction(1.0 - pane_ratio)).w_full())
-            .when(!is_horizontal, |d| d.w(Fraction(1.0 - pane_ratio)).h_full())
+            .when(horizontal, |d| d.h(Fraction(share)).w_full())
+            .when(!horizontal, |d| d.w(Fraction(share)).h_full())
             .min_h_0()
             .min_w_0()
             .flex()
             .flex_col()
-            .child(second_pane)
+            .child(content)
     };
+    path.push(false);
+    let first_part = part(render_pane_tree(first, path, panes, cx), ratio);
+    path.pop();
+    path.push(true);
+    let second_part = part(render_pane_tree(second, path, panes, cx), 1.0 - ratio);
+    path.pop();
 
     // Pane splitter (draggable divider)
-    let pane_splitter = render_pane_splitter(is_horizontal, cx);
+    let pane_splitter = render_pane_splitter(path.clone(), horizontal, cx);
 
-    if is_horizontal {
+    if horizontal {
         // Top/Bottom split
         v_flex()
             .flex_1()
             .min_h_0()
             .w_full()
-            .child(first_pane_wrapped)
+            .child(first_part)
             .child(pane_splitter)
-            .child(second_pane_wrapped)
+            .child(second_part)
     } else {
         // Left/Right split
         h_flex()
             .flex_1()
             .min_w_0()
             .h_full()
-            .child(first_pane_wrapped)
+            .child(first_part)
             .child(pane_splitter)
-            .child(second_pane_wrapped)
+            .child(second_part)
     }
 }
 
-/// Render the draggable splitter between split panes
-fn render_pane_splitter(is_horizontal: bool, cx: &mut Context<Humanboard>) -> Stateful<Div> {
+/// Render a pane of a split panel, with drop zones over it while a tab is
+/// dragged. Once the panel has all the panes it can, a pane can only take
+/// the tab in.
+fn render_split_pane(pane: usize, panes: &SplitPanes, cx: &mut Context<Humanboard>) -> Div {
+    let preview = panes.preview;
+    let Some(state) = preview.panes.get(pane) else {
+        return div();
+    };
+    let is_dragging = panes.dragging_tab.is_some();
+    let is_target = is_dragging && panes.drag_target_pane == pane;
+
+    // Each pane only sees the dragged tab if it comes from there, and the
+    // drop target if it is there
+    let pane_view = render_pane(
+        pane,
+        &state.tabs,
+        state.active_tab,
+        &panes.scrolls[pane % panes.scrolls.len()],
+        preview.focused_pane == pane,
+        panes.dragging_tab.filter(|_| panes.drag_from_pane == pane),
+        panes.drag_target.filter(|_| is_target),
+        &state.history,
+        panes.search_input,
+        panes.replace_input,
+        panes.search_match_count,
+        panes.search_current,
+        panes.can_highlight,
+        cx,
+    );
+
+    let overlay = if !is_dragging {
+        None
+    } else if preview.panes.len() < MAX_PREVIEW_PANES {
+        Some(render_split_drop_zones(
+            pane,
+            panes.split_zone.filter(|_| is_target),
+            cx,
+        ))
+    } else {
+        // Dropping on the pane the tab came from does nothing
+        let is_active =
+            is_target && panes.split_zone.is_none() && panes.drag_from_pane != pane;
+        let primary = cx.theme().primary;
+        Some(
+            div()
+                .absolute()
+                .inset_0()
+                .bg(if is_active {
+                    primary.opacity(0.2)
+                } else {
+                    gpui::transparent_black()
+                })
+                .border_2()
+                .border_color(if is_active {
+                    primary
+                } else {
+                    gpui::transparent_black()
+                })
+                .rounded(px(4.0))
+                .on_mouse_move(cx.listener(move |this, event: &MouseMoveEvent, _window, cx| {
+                    this.update_tab_drag_position(event.position, cx);
+                    this.drag_tab_over_pane(pane, cx);
+                }))
+                .on_mouse_up(
+                    MouseButton::Left,
+                    cx.listener(|this, _event, _window, cx| {
+                        this.finish_tab_drag(cx);
+                    }),
+                ),
+        )
+    };
+
+    div()
+        .size_full()
+        .min_h_0()
+        .min_w_0()
+        .flex()
+        .flex_col()
+        .relative()
+        .child(pane_view)
+        .when_some(overlay, |d, overlay| {
+            // Drop zone overlay, below the tab bar so tabs can be dropped between tabs
+            d.child(
+                div()
+                    .absolute()
+                    .top(px(TAB_BAR_HEIGHT))
+                    .left_0()
+                    .right_0()
+                    .bottom_0()
+                    .child(overlay),
+            )
+        })
+}
+
+/// Render the draggable splitter of the split at `path`
+fn render_pane_splitter(
+    path: Vec<bool>,
+    is_horizontal: bool,
+    cx: &mut Context<Humanboard>,
+) -> Stateful<Div> {
     let border = cx.theme().border;
     let list_hover = cx.theme().list_hover;
+    // Number the splitters by their path, with a leading 1 so that paths of
+    // different lengths differ
+    let id = path
+        .iter()
+        .fold(1usize, |id, &second| id * 2 + usize::from(second));
+
+    let splitter = div()
+        .id(("pane-splitter", id))
+        .cursor(if is_horizontal {
+            CursorStyle::ResizeUpDown
+        } else {
+            CursorStyle::ResizeLeftRight
+        })
+        .bg(gpui::transparent_black())
+        .hover(|s| s.bg(list_hover))
+        .flex()
+        .items_center()
+        .justify_center()
+        .on_mouse_down(
+            MouseButton::Left,
+            cx.listener(move |this, event: &MouseDownEvent, _window, cx| {
+                this.start_pane_split_drag(path.clone(), event.position, cx);
+                cx.stop_propagation();
+            }),
+        );
 
     if is_horizontal {
         // Horizontal splitter (for top/bottom split)
-        div()
-            .id("pane-splitter")
-            .h(px(6.0))
+        splitter
+            .h(px(PANE_SPLITTER_SIZE))
             .w_full()
-            .cursor(CursorStyle::ResizeUpDown)
-            .bg(gpui::transparent_black())
-            .hover(|s| s.bg(list_hover))
-            .flex()
-            .items_center()
-            .justify_center()
-            .on_mouse_down(
-                MouseButton::Left,
-                cx.listener(|this, _event: &MouseDownEvent, _window, cx| {
-                    this.preview.dragging_pane_splitter = true;
-                    cx.notify();
-                }),
-            )
             .child(div().h(px(2.0)).w(px(40.0)).bg(border).rounded(px(1.0)))
     } else {
         // Vertical splitter (for left/right split)
-        div()
-            .id("pane-splitter")
-            .w(px(6.0))
+        splitter
+            .w(px(PANE_SPLITTER_SIZE))
             .h_full()
-            .cursor(CursorStyle::ResizeLeftRight)
-            .bg(gpui::transparent_black())
-            .hover(|s| s.bg(list_hover))
-            .flex()
-            .items_center()
-            .justify_center()
-            .on_mouse_down(
-                MouseButton::Left,
-                cx.listener(|this, _event: &MouseDownEvent, _window, cx| {
-                    this.preview.dragging_pane_splitter = true;
-                    cx.notify();
-                }),
-            )
             .child(div().w(px(2.0)).h(px(40.0)).bg(border).rounded(px(1.0)))
     }
 }
@@ -831,50 +1000,112 @@ pub fn render_tab_content(
     let primary = cx.theme().primary;
 
     match tab {
-        PreviewTab::Pdf { .. } => {
+        PreviewTab::Pdf { path, webview, .. } => {
             // PDF webviews are positioned explicitly via set_bounds in ensure_pdf_webview
             // We just need an empty container - the webview renders as a native overlay
-            div().flex_1().w_full().min_h_0()
+            let page = webview.as_ref().map_or(0, |webview| webview.page());
+            let outline_open = webview
+                .as_ref()
+                .filter(|_| PDF_OUTLINES.has_outline(path))
+                .map(|webview| webview.outline_open());
+            let sidebar = webview
+                .as_ref()
+                .and_then(|webview| render_pdf_outline_sidebar(path, webview, cx));
+            v_flex()
+                .flex_1()
+                .w_full()
+                .min_h_0()
+                .child(
+                    h_flex()
+                        .flex_1()
+                        .w_full()
+                        .min_h_0()
+                        .children(sidebar)
+                        .child(div().flex_1().h_full()),
+                )
+                .child(render_pdf_preview_footer(
+                    path,
+                    page,
+                    PDF_TEXT_INDEX.page_count(path),
+                    outline_open,
+                    cx,
+                ))
         }
         PreviewTab::Markdown {
+            path,
             content,
             editing,
             editor,
-            ..
+            meta,
         } => {
             let is_editing = *editing;
+            let editor_text = editor.as_ref().map(|ed| ed.read(cx).text().to_string());
+            let is_dirty = editor_text.as_ref().is_some_and(|text| text != content);
+            // While editing, the preview shows the editor's text as it is typed
+            let text = editor_text.filter(|_| is_editing);
+            let char_count = text.as_ref().unwrap_or(content).len();
+            let on_link = Humanboard::markdown_link_handler(path.clone(), cx);
+            let zoom = meta.zoom;
+
+            let rendered = div()
+                .id("md-content-scroll")
+                .flex_1()
+                .min_w_0()
+                .overflow_y_scroll()
+                .bg(bg)
+                .child(crate::markdown_card::render_markdown_with_links(
+                    text.as_deref().unwrap_or(content),
+                    zoom.unwrap_or(1.0),
+                    path.parent(),
+                    Some(on_link),
+                    cx,
+                ));
 
             v_flex()
                 .flex_1()
                 .w_full()
                 .min_h_0()
                 .bg(bg)
-                .child(
-                    // Content area
-                    div()
-                        .id("md-content-scroll")
+                .child(if is_editing {
+                    // Edit mode - markdown editor on the left, rendered preview on the right
+                    h_flex()
                         .flex_1()
-                        .overflow_y_scroll()
-                        .bg(bg)
-                        .when(!is_editing, |d| {
-                            // Preview mode - show rendered markdown (scrollable)
-                            d.child(crate::markdown_card::render_markdown_content(
-                                content, 1.0, cx,
-                            ))
-                        })
-                        .when(is_editing, |d| {
-                            // Edit mode - code editor with markdown syntax highlighting
-                            if let Some(ed) = editor {
-                                d.child(Input::new(ed).size_full().appearance(false))
-                            } else {
-                                d.child(div().p_4().child(render_loading_spinner(
-                                    "Loading editor...",
-                                    cx.theme().primary,
-                                    cx.theme().muted_foreground,
-                                )))
-                            }
-                        }),
-                )
+                        .w_full()
+                        .min_h_0()
+                        .child(
+                            div()
+                                .id("md-editor-scroll")
+                                .flex_1()
+                                .min_w_0()
+                                .h_full()
+                                .overflow_y_scroll()
+                                .border_r_1()
+                                .border_color(border)
+                                .child(if let Some(ed) = editor {
+                                    Input::new(ed)
+                                        .size_full()
+                                        .appearance(false)
+                                        .when_some(zoom, |input, zoom| {
+                                            input.text_size(px(CODE_TAB_FONT_SIZE * zoom))
+                                        })
+                                        .into_any_element()
+                                } else {
+                                    div()
+                                        .p_4()
+                                        .child(render_loading_spinner(
+                                            "Loading editor...",
+                                            cx.theme().primary,
+                                            cx.theme().muted_foreground,
+                                        ))
+                                        .into_any_element()
+                                }),
+                        )
+                        .child(rendered)
+                        .into_any_element()
+                } else {
+                    // Preview mode - show rendered markdown (scrollable)
+                    rendered.into_any_element()
+                })
                 .child(
                     // Footer with action buttons
                     h_flex()
@@ -886,10 +1117,17 @@ pub fn render_tab_content(
                         .justify_between()
                         .px_3()
                         .child(
-                            div()
-                                .text_xs()
-                                .text_color(muted_fg)
-                                .child(format!("{} chars", content.len())),
+                            h_flex()
+                                .gap_3()
+                                .child(
+                                    div()
+                                        .text_xs()
+                                        .text_color(muted_fg)
+                                        .child(format!("{} chars", char_count)),
+                                )
+                                .when(is_dirty, |d| {
+                                    d.child(div().text_xs().text_color(muted_fg).child("⌘S to save"))
+                                }),
                         )
                         .child(
                             h_flex()
@@ -901,6 +1139,7 @@ pub fn render_tab_content(
                                             .small()
                                             .label("Save")
                                             .tooltip("Save markdown changes")
+                                            .disabled(!is_dirty)
                                             .on_click(cx.listener(|this, _, _window, cx| {
                                                 this.save_markdown(cx);
                                             })),
@@ -910,8 +1149,8 @@ pub fn render_tab_content(
                                     Button::new("toggle-edit")
                                         .ghost()
                                         .small()
-                                        .label("Cancel")
-                                        .tooltip("Cancel editing")
+                                        .label("Done")
+                                        .tooltip("Close the editor")
                                         .on_click(cx.listener(|this, _, window, cx| {
                                             this.toggle_markdown_edit(window, cx);
                                         }))
@@ -929,56 +1168,83 @@ pub fn render_tab_content(
                 )
         }
         PreviewTab::Code {
+            path,
             content,
             language,
             dirty,
             editor,
+            conflict,
+            outline,
+            meta,
             ..
         } => {
             let is_dirty = *dirty;
+            let zoom = meta.zoom;
             let lang = language.clone();
             let line_count = content.lines().count();
+            let outline_open = outline.is_some();
+            let outline_path = path.clone();
 
             v_flex()
                 .flex_1()
                 .w_full()
                 .min_h_0()
                 .bg(bg)
-                .child({
-                    // Content area - always editable
-                    let editor_entity = editor.clone();
-                    let code_editor_focus = cx.focus_handle();
-                    div()
-                        .id("code-content-scroll")
+                .when(*conflict, |d| {
+                    d.child(render_code_conflict_banner(path, cx))
+                })
+                .child(
+                    h_flex()
                         .flex_1()
-                        .overflow_y_scroll()
-                        .bg(bg)
-                        .track_focus(&code_editor_focus)
-                        .key_context(FocusContext::KEY_CODE_EDITOR)
-                        .on_click(cx.listener(move |this, _event, window, cx| {
-                            // Set focus context to CodeEditor and focus the editor
-                            this.system.focus
-                                .focus(crate::focus::FocusContext::CodeEditor, window);
-                            code_editor_focus.focus(window);
-                            if let Some(ref ed) = editor_entity {
-                                ed.update(cx, |state, cx| {
-                                    state.focus(window, cx);
-                                });
-                            }
-                        }))
-                        .child(if let Some(ed) = editor {
-                            Input::new(ed).size_full().appearance(false).into_any_element()
-                        } else {
-                            div()
-                                .p_4()
-                                .child(render_loading_spinner(
-                                    "Loading code...",
-                                    cx.theme().primary,
-                                    cx.theme().muted_foreground,
-                                ))
-                                .into_any_element()
+                        .w_full()
+                        .min_h_0()
+                        .when_some(outline.as_ref(), |d, outline| {
+                            d.child(render_code_outline_sidebar(path, outline, cx))
                         })
-                })
+                        .child({
+                            // Content area - always editable
+                            let editor_entity = editor.clone();
+                            let code_editor_focus = cx.focus_handle();
+                            div()
+                                .id("code-content-scroll")
+                                .flex_1()
+                                .h_full()
+                                .min_w_0()
+                                .overflow_y_scroll()
+                                .bg(bg)
+                                .track_focus(&code_editor_focus)
+                                .key_context(FocusContext::KEY_CODE_EDITOR)
+                                .on_click(cx.listener(move |this, _event, window, cx| {
+                                    // Set focus context to CodeEditor and focus the editor
+                                    this.system.focus
+                                        .focus(crate::focus::FocusContext::CodeEditor, window);
+                                    code_editor_focus.focus(window);
+                                    if let Some(ref ed) = editor_entity {
+                                        ed.update(cx, |state, cx| {
+                                            state.focus(window, cx);
+                                        });
+                                    }
+                                }))
+                                .child(if let Some(ed) = editor {
+                                    Input::new(ed)
+                                        .size_full()
+                                        .appearance(false)
+                                        .when_some(zoom, |input, zoom| {
+                                            input.text_size(px(CODE_TAB_FONT_SIZE * zoom))
+                                        })
+                                        .into_any_element()
+                                } else {
+                                    div()
+                                        .p_4()
+                                        .child(render_loading_spinner(
+                                            "Loading code...",
+                                            cx.theme().primary,
+                                            cx.theme().muted_foreground,
+                                        ))
+                                        .into_any_element()
+                                })
+                        }),
+                )
                 .child(
                     // Footer with action buttons
                     h_flex()
@@ -1010,67 +1276,76 @@ pub fn render_tab_content(
                                         .child(format!("{} lines", line_count)),
                                 ),
                         )
-                        .when(is_dirty, |d| {
-                            d.child(div().text_xs().text_color(muted_fg).child("⌘S to save"))
-                        }),
+                        .child(
+                            h_flex()
+                                .gap_2()
+                                .items_center()
+                                .when(is_dirty, |d| {
+                                    d.child(
+                                        div().text_xs().text_color(muted_fg).child("⌘S to save"),
+                                    )
+                                })
+                                .child(
+                                    Button::new("code-outline-toggle")
+                                        .ghost()
+                                        .xsmall()
+                                        .label(if outline_open { "Hide Outline" } else { "Outline" })
+                                        .on_click(cx.listener(move |this, _, _, cx| {
+                                            this.toggle_code_outline(&outline_path, cx);
+                                        })),
+                                ),
+                        ),
                 )
         }
 
+        PreviewTab::Image {
+            path,
+            view,
+            viewport,
+            hovered_pixel,
+            pan_from,
+            focus,
+            ..
+        } => render_image_viewer(
+            path,
+            *view,
+            viewport,
+            *hovered_pixel,
+            pan_from.is_some(),
+            focus,
+            cx,
+        ),
+
         PreviewTab::Table {
+            data_source_id,
             name,
             table_state,
+            editing,
+            view,
             ..
         } => {
             let table_name = name.clone();
+            let ds_id = *data_source_id;
             // Check if table has unsaved changes
             let is_dirty = table_state
                 .as_ref()
                 .map(|state| state.read(cx).delegate().is_dirty())
                 .unwrap_or(false);
 
-            // Focus handle for key context
-            let table_focus = cx.focus_handle();
+            // Rows are viewed a page at a time unless the cells are edited
+            let content = match view.as_ref().filter(|_| !*editing) {
+                Some(view) => {
+                    render_data_view(name, tab.data_rows(cx), None, view, cx).into_any_element()
+                }
+                None => render_table_grid(table_state.as_ref(), bg, cx).into_any_element(),
+            };
 
             v_flex()
                 .flex_1()
                 .w_full()
                 .min_h_0()
                 .bg(bg)
-                .child(
-                    // Table content area
-                    div()
-                        .id("table-content-scroll")
-                        .flex_1()
-                        .overflow_hidden()
-                        .bg(bg)
-                        .track_focus(&table_focus)
-                        .key_context(FocusContext::KEY_PREVIEW)
-                        .on_click(cx.listener(move |this, _event, window, cx| {
-                            // Set focus context to Preview and focus the table
-                            this.system.focus.focus(crate::focus::FocusContext::Preview, window);
-                            table_focus.focus(window);
-                            cx.notify();
-                        }))
-                        .when_some(table_state.as_ref(), |d, state| {
-                            use gpui_component::table::Table;
-                            d.child(
-                                Table::new(state)
-                                    .bordered(true)
-                                    .stripe(true)
-                            )
-                        })
-                        .when(table_state.is_none(), |d| {
-                            d.child(
-                                div()
-                                    .p_4()
-                                    .child(render_loading_spinner(
-                                        "Loading table...",
-                                        cx.theme().primary,
-                                        cx.theme().muted_foreground,
-                                    ))
-                            )
-                        }),
-                )
+                .child(content)
                 .child(
                     // Footer with table info
                     h_flex()
@@ -1102,34 +1377,156 @@ pub fn render_tab_content(
                                         .child(table_name),
                                 ),
                         )
-                        .when(is_dirty, |d| {
-                            d.child(div().text_xs().text_color(muted_fg).child("⌘S to save"))
+                        .child(
+                            h_flex()
+                                .gap_3()
+                                .items_center()
+                                .when(is_dirty, |d| {
+                                    d.child(div().text_xs().text_color(muted_fg).child("⌘S to save"))
+                                })
+                                .when(table_state.is_some(), |d| {
+                                    d.child(
+                                        Button::new("table-edit-toggle")
+                                            .ghost()
+                                            .xsmall()
+                                            .label(if *editing { "Done" } else { "Edit" })
+                                            .on_click(cx.listener(move |this, _, _, cx| {
+                                                this.toggle_table_editing(ds_id, cx);
+                                            })),
+                                    )
+                                }),
+                        ),
+                )
+        }
+
+        PreviewTab::Data { path, view, .. } => {
+            let name = tab.title();
+            let kind = path
+                .extension()
+                .and_then(|e| e.to_str())
+                .unwrap_or("csv")
+                .to_uppercase();
+            let row_count = DATA_FILES
+                .get(path)
+                .map(|rows| format_row_count(rows.row_count()));
+
+            v_flex()
+                .flex_1()
+                .w_full()
+                .min_h_0()
+                .bg(bg)
+                .child(render_data_view(
+                    &name,
+                    DATA_FILES.get(path),
+                    DATA_FILES.error(path),
+                    view,
+                    cx,
+                ))
+                .child(
+                    h_flex()
+                        .h(px(40.0))
+                        .bg(title_bar)
+                        .border_t_1()
+                        .border_color(border)
+                        .items_center()
+                        .justify_between()
+                        .px_3()
+                        .child(
+                            h_flex()
+                                .gap_3()
+                                .child(
+                                    div()
+                                        .px(px(6.0))
+                                        .py(px(2.0))
+                                        .bg(primary.opacity(0.2))
+                                        .rounded(px(3.0))
+                                        .text_xs()
+                                        .font_weight(FontWeight::MEDIUM)
+                                        .text_color(primary)
+                                        .child(kind),
+                                )
+                                .child(div().text_xs().text_color(muted_fg).child(name)),
+                        )
+                        .when_some(row_count, |d, count| {
+                            d.child(div().text_xs().text_color(muted_fg).child(count))
                         }),
                 )
         }
+
+        PreviewTab::Diff {
+            path,
+            base,
+            base_label,
+            diff,
+            highlights,
+            side_by_side,
+            ..
+        } => render_diff_view(path, base, base_label, diff, highlights, *side_by_side, cx),
     }
 }
 
+/// The editable grid of a table tab
+fn render_table_grid(
+    table_state: Option<&Entity<gpui_component::table::TableState<crate::data::DataSourceDelegate>>>,
+    bg: Hsla,
+    cx: &mut Context<Humanboard>,
+) -> Stateful<Div> {
+    // Focus handle for key context
+    let table_focus = cx.focus_handle();
+
+    div()
+        .id("table-content-scroll")
+        .flex_1()
+        .overflow_hidden()
+        .bg(bg)
+        .track_focus(&table_focus)
+        .key_context(FocusContext::KEY_PREVIEW)
+        .on_click(cx.listener(move |this, _event, window, cx| {
+            // Set focus context to Preview and focus the table
+            this.system.focus.focus(crate::focus::FocusContext::Preview, window);
+            table_focus.focus(window);
+            cx.notify();
+        }))
+        .when_some(table_state, |d, state| {
+            use gpui_component::table::Table;
+            d.child(
+                Table::new(state)
+                    .bordered(true)
+                    .stripe(true)
+            )
+        })
+        .when(table_state.is_none(), |d| {
+            d.child(
+                div()
+                    .p_4()
+                    .child(render_loading_spinner(
+                        "Loading table...",
+                        cx.theme().primary,
+                        cx.theme().muted_foreground,
+                    ))
+            )
+        })
+}
+
 /// Render the legacy preview panel (for PDF pages)
 /// Render the search bar for the preview panel
 pub fn render_search_bar(
     search_input: &Entity<gpui_component::input::InputState>,
+    replace_input: Option<&Entity<gpui_component::input::InputState>>,
     match_count: usize,
     current_match: usize,
+    can_highlight: bool,
     cx: &mut Context<Humanboard>,
 ) -> Div {
     let bg = cx.theme().title_bar;
     let border = cx.theme().border;
     let muted_fg = cx.theme().muted_foreground;
 
-    h_flex()
+    let find_row = h_flex()
         .w_full()
         .h(px(32.0))
         .px_2()
         .py_1()
-        .bg(bg)
-        .border_b_1()
-        .border_color(border)
         .gap_2()
         .items_center()
         .child(div().flex_1().child(Input::new(search_input).xsmall()))
@@ -1143,6 +1540,34 @@ pub fn render_search_bar(
                     "No matches".to_string()
                 }),
         )
+        // The current match of a PDF can be highlighted, as the viewer's
+        // own selection isn't visible to the app
+        .when(can_highlight, |d| {
+            d.child(
+                h_flex()
+                    .gap_1()
+                    .child(
+                        Button::new("search-highlight")
+                            .label("Highlight")
+                            .xsmall()
+                            .ghost()
+                            .tooltip("Highlight this match")
+                            .on_click(cx.listener(|this, _, window, cx| {
+                                this.highlight_pdf_search_match(false, window, cx);
+                            })),
+                    )
+                    .child(
+                        Button::new("search-highlight-note")
+                            .label("Add Note")
+                            .xsmall()
+                            .ghost()
+                            .tooltip("Highlight this match and add a note to the canvas")
+                            .on_click(cx.listener(|this, _, window, cx| {
+                                this.highlight_pdf_search_match(true, window, cx);
+                            })),
+                    ),
+            )
+        })
         .child(
             h_flex()
                 .gap_1()
@@ -1178,7 +1603,53 @@ pub fn render_search_bar(
                             this.close_preview_search(cx);
                         })),
                 ),
-        )
+        );
+
+    v_flex()
+        .w_full()
+        .bg(bg)
+        .border_b_1()
+        .border_color(border)
+        .child(find_row)
+        // Tabs being edited can have their matches replaced
+        .when_some(replace_input, |d, replace_input| {
+            d.child(
+                h_flex()
+                    .w_full()
+                    .h(px(32.0))
+                    .px_2()
+                    .py_1()
+                    .gap_2()
+                    .items_center()
+                    .child(div().flex_1().child(Input::new(replace_input).xsmall()))
+                    .child(
+                        h_flex()
+                            .gap_1()
+                            .child(
+                                Button::new("search-replace")
+                                    .label("Replace")
+                                    .xsmall()
+                                    .ghost()
+                                    .tooltip("Replace this match")
+                                    .disabled(match_count == 0)
+                                    .on_click(cx.listener(|this, _, window, cx| {
+                                        this.replace_search_match(window, cx);
+                                    })),
+                            )
+                            .child(
+                                Button::new("search-replace-all")
+                                    .label("All")
+                                    .xsmall()
+                                    .ghost()
+                                    .tooltip("Replace every match")
+                                    .disabled(match_count == 0)
+                                    .on_click(cx.listener(|this, _, window, cx| {
+                                        this.replace_all_search_matches(window, cx);
+                                    })),
+                            ),
+                    ),
+            )
+        })
 }
 
 pub fn render_preview_panel(
@@ -1348,3 +1819,54 @@ pub fn render_splitter(direction: SplitDirection, cx: &mut Context<Humanboard>)
 pub fn render_selected_item_label(_name: String) -> Div {
     div().size_0()
 }
+
+/// Banner above a code tab whose file changed on disk under unsaved edits,
+/// offering to reload the file or overwrite it with the edits
+fn render_code_conflict_banner(path: &Path, cx: &mut Context<Humanboard>) -> Div {
+    let warning = cx.theme().warning;
+    let file = path
+        .file_name()
+        .and_then(|n| n.to_str())
+        .unwrap_or("File")
+        .to_string();
+    let (reload_path, overwrite_path) = (path.to_path_buf(), path.to_path_buf());
+
+    h_flex()
+        .w_full()
+        .px_3()
+        .py_1()
+        .gap_2()
+        .items_center()
+        .justify_between()
+        .bg(warning.opacity(0.15))
+        .border_b_1()
+        .border_color(warning.opacity(0.4))
+        .child(
+            div()
+                .text_xs()
+                .text_color(cx.theme().foreground)
+                .child(format!("{} changed on disk since it was opened", file)),
+        )
+        .child(
+            h_flex()
+                .gap_1()
+                .child(
+                    Button::new("code-conflict-reload")
+                        .ghost()
+                        .xsmall()
+                        .label("Reload")
+                        .on_click(cx.listener(move |this, _, window, cx| {
+                            this.reload_code_file(&reload_path, window, cx);
+                        })),
+                )
+                .child(
+                    Button::new("code-conflict-overwrite")
+                        .ghost()
+                        .xsmall()
+                        .label("Overwrite")
+                        .on_click(cx.listener(move |this, _, _, cx| {
+                            this.save_code_file(&overwrite_path, true, cx);
+                        })),
+                ),
+        )
+}
@@ -0,0 +1,123 @@
+//! Line layout caching for text boxes.
+//!
+//! A text box is drawn as one text element per line. Splitting its text
+//! into lines and building each line's string every frame adds up on boards
+//! with hundreds of notes, and drawing them at the exact zoom means GPUI has
+//! to shape every line again whenever the zoom moves slightly.
+//!
+//! [`TextLayoutCache`] keeps each visible text box's lines as shared strings,
+//! keyed by a hash of its text, its font size and the zoom bucket (see
+//! `item_cache`). Lines are laid out at the bucket's zoom, so a layout stays
+//! valid, and GPUI's shaped-line cache keeps hitting, until the zoom moves to
+//! another bucket. Editing a text box invalidates its entry.
+
+use super::item_cache::{bucket_zoom, zoom_bucket};
+use crate::types::{CanvasItem, ItemContent};
+use gpui::SharedString;
+use std::collections::HashMap;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::sync::Arc;
+
+/// A text box's text split into lines, ready to draw
+#[derive(Debug, Clone, PartialEq)]
+pub struct TextLayout {
+    /// One entry per line; empty lines hold a space so they keep their height
+    pub lines: Vec<SharedString>,
+    /// Font size on screen, at the zoom bucket's zoom
+    pub font_size: f32,
+}
+
+impl TextLayout {
+    pub fn new(text: &str, font_size: f32, zoom: f32) -> Self {
+        let lines = text
+            .lines()
+            .map(|line| {
+                if line.is_empty() {
+                    SharedString::from(" ")
+                } else {
+                    SharedString::from(line.to_string())
+                }
+            })
+            .collect();
+        Self {
+            lines,
+            font_size: font_size * bucket_zoom(zoom_bucket(zoom)),
+        }
+    }
+}
+
+/// Everything a text box's layout depends on
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TextLayoutKey {
+    text: u64,
+    font_size: u32,
+    zoom_bucket: i32,
+}
+
+impl TextLayoutKey {
+    pub fn new(text: &str, font_size: f32, zoom: f32) -> Self {
+        let mut hasher = DefaultHasher::new();
+        text.hash(&mut hasher);
+        Self {
+            text: hasher.finish(),
+            font_size: font_size.to_bits(),
+            zoom_bucket: zoom_bucket(zoom),
+        }
+    }
+}
+
+/// Line layouts for the text boxes currently on screen
+#[derive(Default)]
+pub struct TextLayoutCache {
+    entries: HashMap<u64, (TextLayoutKey, Arc<TextLayout>)>,
+}
+
+impl TextLayoutCache {
+    /// Bring the cache up to date with the visible items and return the
+    /// layout of each text box. Layouts whose key changed are rebuilt; text
+    /// boxes that left the screen are dropped.
+    pub fn prepare(&mut self, items: &[CanvasItem], zoom: f32) -> HashMap<u64, Arc<TextLayout>> {
+        let mut layouts = HashMap::new();
+
+        for item in items {
+            let ItemContent::TextBox {
+                text, font_size, ..
+            } = &item.content
+            else {
+                continue;
+            };
+
+            let key = TextLayoutKey::new(text, *font_size, zoom);
+            let layout = match self.entries.remove(&item.id) {
+                Some((cached_key, layout)) if cached_key == key => layout,
+                _ => Arc::new(TextLayout::new(text, *font_size, zoom)),
+            };
+
+            layouts.insert(item.id, layout.clone());
+            self.entries.insert(item.id, (key, layout));
+        }
+
+        self.entries.retain(|id, _| layouts.contains_key(id));
+        layouts
+    }
+
+    /// Forget a text box's layout after its text was edited
+    pub fn invalidate(&mut self, item_id: u64) {
+        self.entries.remove(&item_id);
+    }
+
+    /// Drop every layout, e.g. when switching boards
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+    /// Number of cached layouts
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
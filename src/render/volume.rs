@@ -0,0 +1,105 @@
+//! Volume controls on media item headers.
+//!
+//! Media cards keep a drag bar above their webview, and the controls sit
+//! at its right end: a mute toggle and a volume bar that sets the level to
+//! wherever it is clicked. Like the waveform seek target they are laid over
+//! the card in `render_items`, which has access to the app context.
+
+use crate::app::Humanboard;
+use crate::types::MediaVolume;
+use gpui::prelude::FluentBuilder;
+use gpui::*;
+
+/// Height of a media card's drag bar, in canvas units
+pub const MEDIA_HEADER_HEIGHT: f32 = 24.0;
+
+/// Width of the volume bar, in canvas units
+pub const VOLUME_BAR_WIDTH: f32 = 60.0;
+
+/// Space between the volume bar and the card's right edge, in canvas units
+pub const VOLUME_CONTROL_INSET: f32 = 8.0;
+
+/// Width of the mute toggle, in canvas units
+const MUTE_BUTTON_WIDTH: f32 = 18.0;
+
+/// Width kept clear for the controls at the right of the drag bar
+pub const VOLUME_CONTROL_WIDTH: f32 = VOLUME_BAR_WIDTH + MUTE_BUTTON_WIDTH + VOLUME_CONTROL_INSET;
+
+/// Fraction of the volume bar at `canvas_x`, for a card at `item_x` that is
+/// `item_width` wide
+pub fn volume_bar_fraction(canvas_x: f32, item_x: f32, item_width: f32) -> f32 {
+    let bar_left = item_x + item_width - VOLUME_CONTROL_INSET - VOLUME_BAR_WIDTH;
+    ((canvas_x - bar_left) / VOLUME_BAR_WIDTH).clamp(0.0, 1.0)
+}
+
+/// Mute toggle and volume bar at the right end of a media card's drag bar
+pub fn render_volume_control(
+    item_id: u64,
+    volume: MediaVolume,
+    zoom: f32,
+    cx: &Context<Humanboard>,
+) -> Div {
+    let level = if volume.muted { 0.0 } else { volume.level };
+    let track = hsla(0.0, 0.0, 0.3, 1.0);
+    let fill = hsla(0.0, 0.0, 0.75, 1.0);
+
+    div()
+        .absolute()
+        .top_0()
+        .right(px(VOLUME_CONTROL_INSET * zoom))
+        .h(px(MEDIA_HEADER_HEIGHT * zoom))
+        .flex()
+        .items_center()
+        .child(
+            div()
+                .id(ElementId::Name(format!("mute-{}", item_id).into()))
+                .w(px(MUTE_BUTTON_WIDTH * zoom))
+                .h_full()
+                .flex()
+                .items_center()
+                .justify_center()
+                .cursor_pointer()
+                .text_size(px(11.0 * zoom))
+                .text_color(hsla(0.0, 0.0, 0.6, 1.0))
+                .child(if volume.is_silent() { "🔇" } else { "🔊" })
+                .on_mouse_down(
+                    MouseButton::Left,
+                    cx.listener(move |this, _, _, cx| {
+                        cx.stop_propagation();
+                        this.toggle_item_mute(item_id, cx);
+                    }),
+                ),
+        )
+        .child(
+            div()
+                .id(ElementId::Name(format!("volume-{}", item_id).into()))
+                .w(px(VOLUME_BAR_WIDTH * zoom))
+                .h_full()
+                .flex()
+                .items_center()
+                .cursor_pointer()
+                .child(
+                    div()
+                        .w_full()
+                        .h(px(4.0 * zoom))
+                        .rounded(px(2.0 * zoom))
+                        .bg(track)
+                        .when(level > 0.0, |d| {
+                            d.child(
+                                div()
+                                    .h_full()
+                                    .w(relative(level))
+                                    .rounded(px(2.0 * zoom))
+                                    .bg(fill),
+                            )
+                        }),
+                )
+                .on_mouse_down(
+                    MouseButton::Left,
+                    cx.listener(move |this, event: &MouseDownEvent, _, cx| {
+                        cx.stop_propagation();
+                        this.set_item_volume_at(item_id, event.position, cx);
+                    }),
+                ),
+        )
+}
@@ -0,0 +1,134 @@
+//! Waveform strip on audio cards.
+//!
+//! The strip sits between the card's drag bar and the player webview, since
+//! webviews draw over anything placed on top of them. Bars are painted
+//! straight to the GPU at a fixed on-screen width, so zooming changes how
+//! many peaks are shown rather than how thick they are. The part before the
+//! playhead is drawn in the played color.
+//!
+//! Clicks on the strip are handled by a separate seek target laid over the
+//! same area in `render_items`, which has access to the app context.
+
+use crate::app::Humanboard;
+use crate::waveform::Waveform;
+use gpui::*;
+use std::sync::Arc;
+
+/// Height of the drag bar above the waveform, in canvas units
+pub const AUDIO_DRAG_BAR_HEIGHT: f32 = 24.0;
+
+/// Height of the waveform strip, in canvas units
+pub const WAVEFORM_STRIP_HEIGHT: f32 = 40.0;
+
+/// On-screen width of one waveform bar in pixels
+const BAR_WIDTH: f32 = 2.0;
+
+/// On-screen gap between waveform bars in pixels
+const BAR_GAP: f32 = 1.0;
+
+/// Space kept clear above and below the tallest bar in pixels
+const BAR_PADDING: f32 = 4.0;
+
+/// A decoded waveform and the player's position, ready to draw
+#[derive(Clone)]
+pub struct AudioWaveform {
+    pub waveform: Arc<Waveform>,
+    /// Playback position as a fraction of the duration, once the player
+    /// has reported one
+    pub playhead: Option<f32>,
+}
+
+/// Render the waveform strip for an audio card
+pub fn render_waveform_strip(
+    audio: &AudioWaveform,
+    zoom: f32,
+    played: Hsla,
+    unplayed: Hsla,
+) -> Div {
+    let waveform = audio.waveform.clone();
+    let playhead = audio.playhead;
+
+    div()
+        .w_full()
+        .h(px(WAVEFORM_STRIP_HEIGHT * zoom))
+        .flex_shrink_0()
+        .bg(hsla(0.0, 0.0, 0.08, 1.0))
+        .border_b_1()
+        .border_color(hsla(0.0, 0.0, 0.2, 1.0))
+        .child(
+            canvas(
+                move |_, _, _| {},
+                move |bounds, _, window, _| {
+                    paint_waveform(&waveform, playhead, bounds, played, unplayed, window);
+                },
+            )
+            .size_full(),
+        )
+}
+
+/// Invisible click target over an audio card's waveform strip that moves
+/// playback to the clicked point
+pub fn render_waveform_seek_target(
+    item_id: u64,
+    zoom: f32,
+    cx: &Context<Humanboard>,
+) -> Stateful<Div> {
+    div()
+        .id(ElementId::Name(format!("waveform-seek-{}", item_id).into()))
+        .absolute()
+        .left_0()
+        .top(px(AUDIO_DRAG_BAR_HEIGHT * zoom))
+        .w_full()
+        .h(px(WAVEFORM_STRIP_HEIGHT * zoom))
+        .cursor_pointer()
+        .on_mouse_down(
+            MouseButton::Left,
+            cx.listener(move |this, event: &MouseDownEvent, _, cx| {
+                cx.stop_propagation();
+                this.seek_audio_at(item_id, event.position, cx);
+            }),
+        )
+}
+
+fn paint_waveform(
+    waveform: &Waveform,
+    playhead: Option<f32>,
+    bounds: Bounds<Pixels>,
+    played: Hsla,
+    unplayed: Hsla,
+    window: &mut Window,
+) {
+    let (left, top) = (f32::from(bounds.origin.x), f32::from(bounds.origin.y));
+    let (width, height) = (f32::from(bounds.size.width), f32::from(bounds.size.height));
+    let bar_count = ((width + BAR_GAP) / (BAR_WIDTH + BAR_GAP)).floor().max(0.0) as usize;
+    let max_bar = (height - 2.0 * BAR_PADDING).max(1.0);
+    let middle = top + height / 2.0;
+    let playhead_x = playhead.map(|fraction| left + fraction * width);
+
+    for (i, peak) in waveform.bars(bar_count).into_iter().enumerate() {
+        let x = left + i as f32 * (BAR_WIDTH + BAR_GAP);
+        let bar_height = (peak * max_bar).max(1.0);
+        let color = if playhead_x.is_some_and(|playhead_x| x < playhead_x) {
+            played
+        } else {
+            unplayed
+        };
+        window.paint_quad(fill(
+            Bounds::new(
+                point(px(x), px(middle - bar_height / 2.0)),
+                size(px(BAR_WIDTH), px(bar_height)),
+            ),
+            color,
+        ));
+    }
+
+    if let Some(x) = playhead_x {
+        window.paint_quad(fill(
+            Bounds::new(
+                point(px(x), bounds.origin.y),
+                size(px(1.0), bounds.size.height),
+            ),
+            played,
+        ));
+    }
+}
@@ -0,0 +1,272 @@
+//! Search operators for the command palette.
+//!
+//! Besides plain text, a search can hold filters written as `name:value`:
+//!
+//! - `type:image` keeps items of a kind: `image`, `video`, `audio`, `pdf`,
+//!   `markdown` (or `md`), `code`, `text`, `link`, `table`, `chart`, ... A
+//!   code language (`type:rust`) keeps code cards in it. Kinds can be listed
+//!   together, `type:image,pdf`, to keep items of either.
+//! - `tag:research` keeps text boxes and markdown cards tagged `#research`.
+//!   Every tag given must be there.
+//! - `board:Q3` searches boards whose name contains `Q3`. On its own it
+//!   lists those boards.
+//! - `modified:>2024-01-01` keeps items whose file was changed after a day.
+//!   `>=`, `<`, `<=` compare the same way; a bare date keeps that day.
+//!
+//! Filter names and values ignore case. Anything that isn't a known filter,
+//! or a filter whose value can't be read, is searched as text.
+
+use crate::types::{CanvasItem, ItemContent};
+use std::fs;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Days from 1970-01-01 to a date in the proleptic Gregorian calendar
+fn days_from_civil(year: i64, month: u32, day: u32) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let month = month as i64;
+    let day_of_year =
+        (153 * (if month > 2 { month - 3 } else { month + 9 }) + 2) / 5 + day as i64 - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+/// Days since 1970-01-01 of a `YYYY-MM-DD` date
+pub fn parse_date(text: &str) -> Option<i64> {
+    let mut parts = text.splitn(3, '-');
+    let year: i64 = parts.next()?.parse().ok()?;
+    let month: u32 = parts.next()?.parse().ok()?;
+    let day: u32 = parts.next()?.parse().ok()?;
+    if !(1..=12).contains(&month) || !(1..=31).contains(&day) {
+        return None;
+    }
+    Some(days_from_civil(year, month, day))
+}
+
+/// Day (since 1970-01-01, in UTC) of a time
+fn day_of(time: SystemTime) -> i64 {
+    match time.duration_since(UNIX_EPOCH) {
+        Ok(since) => (since.as_secs() / 86_400) as i64,
+        Err(before) => -(before.duration().as_secs().div_ceil(86_400) as i64),
+    }
+}
+
+/// How a `modified:` filter compares with its day
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DateComparison {
+    Before,
+    OnOrBefore,
+    On,
+    OnOrAfter,
+    After,
+}
+
+/// A `modified:` filter
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DateFilter {
+    pub comparison: DateComparison,
+    /// Days since 1970-01-01
+    pub day: i64,
+}
+
+impl DateFilter {
+    /// Parse a filter value: a date, optionally after `>`, `>=`, `<` or `<=`
+    pub fn parse(value: &str) -> Option<Self> {
+        let (comparison, date) = if let Some(date) = value.strip_prefix(">=") {
+            (DateComparison::OnOrAfter, date)
+        } else if let Some(date) = value.strip_prefix("<=") {
+            (DateComparison::OnOrBefore, date)
+        } else if let Some(date) = value.strip_prefix('>') {
+            (DateComparison::After, date)
+        } else if let Some(date) = value.strip_prefix('<') {
+            (DateComparison::Before, date)
+        } else {
+            (DateComparison::On, value.strip_prefix('=').unwrap_or(value))
+        };
+        Some(Self {
+            comparison,
+            day: parse_date(date)?,
+        })
+    }
+
+    pub fn matches(&self, time: SystemTime) -> bool {
+        let day = day_of(time);
+        match self.comparison {
+            DateComparison::Before => day < self.day,
+            DateComparison::OnOrBefore => day <= self.day,
+            DateComparison::On => day == self.day,
+            DateComparison::OnOrAfter => day >= self.day,
+            DateComparison::After => day > self.day,
+        }
+    }
+}
+
+/// A search split into its text and its filters
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SearchQuery {
+    /// What is left to search for as text
+    pub text: String,
+    /// Kinds of items kept, lowercase; any of them will do
+    pub types: Vec<String>,
+    /// Tags every item kept has, lowercase and without `#`
+    pub tags: Vec<String>,
+    /// Part of the name of the boards searched, lowercase
+    pub board: Option<String>,
+    pub modified: Vec<DateFilter>,
+}
+
+impl SearchQuery {
+    /// Split a search into text and filters
+    pub fn parse(input: &str) -> Self {
+        let mut query = Self::default();
+        let mut words = Vec::new();
+
+        for word in input.split_whitespace() {
+            let Some((name, value)) = word.split_once(':') else {
+                words.push(word);
+                continue;
+            };
+            let value_lower = value.to_lowercase();
+            let parsed = match name.to_lowercase().as_str() {
+                _ if value.is_empty() => false,
+                "type" | "is" => {
+                    query.types.extend(
+                        value_lower
+                            .split(',')
+                            .filter(|kind| !kind.is_empty())
+                            .map(str::to_string),
+                    );
+                    true
+                }
+                "tag" => {
+                    query
+                        .tags
+                        .push(value_lower.trim_start_matches('#').to_string());
+                    true
+                }
+                "board" => {
+                    query.board = Some(value_lower);
+                    true
+                }
+                "modified" => match DateFilter::parse(value) {
+                    Some(filter) => {
+                        query.modified.push(filter);
+                        true
+                    }
+                    None => false,
+                },
+                _ => false,
+            };
+            if !parsed {
+                words.push(word);
+            }
+        }
+
+        query.text = words.join(" ");
+        query
+    }
+
+    /// Whether the search has any filter
+    pub fn has_filters(&self) -> bool {
+        !self.types.is_empty()
+            || !self.tags.is_empty()
+            || self.board.is_some()
+            || !self.modified.is_empty()
+    }
+
+    /// Whether items on a board named `name` are searched
+    pub fn matches_board(&self, name: &str) -> bool {
+        self.board
+            .as_ref()
+            .is_none_or(|board| name.to_lowercase().contains(board))
+    }
+
+    /// Whether an item passes the type, tag and date filters. `modified`
+    /// is when the item's file was last changed, if it has one.
+    pub fn matches_item(&self, item: &CanvasItem, modified: Option<SystemTime>) -> bool {
+        let kinds = item_kinds(&item.content);
+        if !self.types.is_empty() && !self.types.iter().any(|kind| kinds.contains(kind)) {
+            return false;
+        }
+        if !self.tags.is_empty() {
+            let tags = item_tags(&item.content);
+            if !self.tags.iter().all(|tag| tags.contains(tag)) {
+                return false;
+            }
+        }
+        if !self.modified.is_empty() {
+            let Some(modified) = modified else {
+                return false;
+            };
+            if !self.modified.iter().all(|filter| filter.matches(modified)) {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+/// Names a `type:` filter knows an item's kind by, lowercase
+pub fn item_kinds(content: &ItemContent) -> Vec<String> {
+    let kinds: &[&str] = match content {
+        ItemContent::Image(_) => &["image", "img"],
+        ItemContent::Video(_) => &["video", "media"],
+        ItemContent::Audio(_) => &["audio", "media"],
+        ItemContent::Pdf { .. } => &["pdf"],
+        ItemContent::Text(_) => &["note"],
+        ItemContent::Link(_) => &["link", "url"],
+        ItemContent::Bookmark { .. } => &["bookmark", "link", "url"],
+        ItemContent::YouTube(_) => &["youtube", "video", "embed"],
+        ItemContent::Vimeo(_) => &["vimeo", "video", "embed"],
+        ItemContent::SoundCloud(_) => &["soundcloud", "audio", "embed"],
+        ItemContent::Spotify(_) => &["spotify", "audio", "embed"],
+        ItemContent::Markdown { .. } => &["markdown", "md"],
+        ItemContent::Code { .. } => &["code"],
+        ItemContent::TextBox { .. } => &["text", "textbox"],
+        ItemContent::Arrow { .. } => &["arrow"],
+        ItemContent::Shape { .. } => &["shape"],
+        ItemContent::Table { .. } => &["table"],
+        ItemContent::Chart { .. } => &["chart"],
+    };
+    let mut kinds: Vec<String> = kinds.iter().map(|kind| kind.to_string()).collect();
+    if let ItemContent::Code { language, .. } = content {
+        kinds.push(language.to_lowercase());
+    }
+    kinds
+}
+
+/// Hashtags written in a text box or markdown card, lowercase and without
+/// `#`. A tag starts a word and isn't only digits, so headings and issue
+/// numbers aren't tags.
+pub fn item_tags(content: &ItemContent) -> Vec<String> {
+    let text = match content {
+        ItemContent::TextBox { text, .. } | ItemContent::Markdown { content: text, .. } => text,
+        ItemContent::Text(text) => text,
+        _ => return Vec::new(),
+    };
+    let mut tags: Vec<String> = Vec::new();
+    for word in text.split_whitespace() {
+        let Some(tag) = word.strip_prefix('#') else {
+            continue;
+        };
+        let tag: String = tag
+            .chars()
+            .take_while(|c| c.is_alphanumeric() || matches!(c, '_' | '-' | '/'))
+            .collect();
+        let tag = tag.trim_end_matches(['-', '/']).to_lowercase();
+        if tag.is_empty() || tag.chars().all(|c| c.is_ascii_digit()) {
+            continue;
+        }
+        if !tags.contains(&tag) {
+            tags.push(tag);
+        }
+    }
+    tags
+}
+
+/// When the file behind an item was last changed, for items with a file
+pub fn item_modified(item: &CanvasItem) -> Option<SystemTime> {
+    let path = item.content.file_paths().into_iter().next()?;
+    fs::metadata(path).and_then(|meta| meta.modified()).ok()
+}
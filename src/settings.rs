@@ -7,11 +7,14 @@
 //! 2. User settings (~/.config/humanboard/settings.json)
 //! 3. Project settings (.humanboard/settings.json) (highest priority)
 
+use crate::custom_themes::{custom_theme, custom_theme_names, reload_theme_library};
 use crate::error::SettingsError;
+use crate::item_styles::ItemStyles;
 use gpui::*;
-use gpui_component::theme::{Theme, ThemeMode, ThemeRegistry};
+use gpui_component::theme::{Theme, ThemeConfig, ThemeMode, ThemeRegistry};
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
+use std::rc::Rc;
 
 use tracing::{debug, error, info, warn};
 
@@ -99,6 +102,30 @@ pub struct SettingsContent {
     /// Whether high contrast mode is enabled (accessibility)
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub high_contrast: Option<bool>,
+
+    /// Days a deleted board stays in the trash before it is purged (0 to keep forever)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub trash_retention_days: Option<u32>,
+
+    /// Whether dropped files are copied into the board's assets folder
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub manage_assets: Option<bool>,
+
+    /// Whether boards are saved in a diff-friendly layout for version control
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub git_friendly_save: Option<bool>,
+
+    /// Memory budget for decoded images, webviews and PDF thumbnails, in megabytes
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub media_memory_budget_mb: Option<u64>,
+
+    /// Folders quick open lists files from ("~" is the home folder)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub quick_open_folders: Option<Vec<String>>,
+
+    /// Styles new text boxes, shapes, arrows and tables are made with
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub item_styles: Option<ItemStyles>,
 }
 
 impl SettingsContent {
@@ -147,6 +174,24 @@ impl SettingsContent {
         if other.high_contrast.is_some() {
             self.high_contrast = other.high_contrast;
         }
+        if other.trash_retention_days.is_some() {
+            self.trash_retention_days = other.trash_retention_days;
+        }
+        if other.manage_assets.is_some() {
+            self.manage_assets = other.manage_assets;
+        }
+        if other.git_friendly_save.is_some() {
+            self.git_friendly_save = other.git_friendly_save;
+        }
+        if other.media_memory_budget_mb.is_some() {
+            self.media_memory_budget_mb = other.media_memory_budget_mb;
+        }
+        if other.quick_open_folders.is_some() {
+            self.quick_open_folders = other.quick_open_folders.clone();
+        }
+        if other.item_styles.is_some() {
+            self.item_styles = other.item_styles.clone();
+        }
     }
 }
 
@@ -171,6 +216,16 @@ pub struct AppSettings {
     /// Reduce motion preference: "system", "on", or "off"
     pub reduce_motion: String,
     pub high_contrast: bool,
+    /// Days before trashed boards are purged (0 keeps them forever)
+    pub trash_retention_days: u32,
+    /// Copy dropped files into the board's assets folder
+    pub manage_assets: bool,
+    /// Save boards with data sources split into separate files
+    pub git_friendly_save: bool,
+    /// Memory budget for media in megabytes
+    pub media_memory_budget_mb: u64,
+    /// Styles new items are made with
+    pub item_styles: ItemStyles,
 }
 
 impl Default for AppSettings {
@@ -189,6 +244,11 @@ impl Default for AppSettings {
             pan_sensitivity: 1.0,
             reduce_motion: "system".to_string(),
             high_contrast: false,
+            trash_retention_days: 30,
+            manage_assets: false,
+            git_friendly_save: false,
+            media_memory_budget_mb: crate::memory::DEFAULT_MEDIA_BUDGET_MB,
+            item_styles: ItemStyles::default(),
         }
     }
 }
@@ -228,6 +288,21 @@ impl Setting for AppSettings {
                 .clone()
                 .unwrap_or(defaults.reduce_motion),
             high_contrast: content.high_contrast.unwrap_or(defaults.high_contrast),
+            trash_retention_days: content
+                .trash_retention_days
+                .unwrap_or(defaults.trash_retention_days),
+            manage_assets: content.manage_assets.unwrap_or(defaults.manage_assets),
+            git_friendly_save: content
+                .git_friendly_save
+                .unwrap_or(defaults.git_friendly_save),
+            media_memory_budget_mb: content
+                .media_memory_budget_mb
+                .unwrap_or(defaults.media_memory_budget_mb),
+            item_styles: content
+                .item_styles
+                .clone()
+                .map(ItemStyles::sanitized)
+                .unwrap_or(defaults.item_styles),
         }
     }
 
@@ -403,6 +478,12 @@ impl SettingsStore {
             onboarding_completed: Some(false),
             reduce_motion: Some(defaults.reduce_motion),
             high_contrast: Some(defaults.high_contrast),
+            trash_retention_days: Some(defaults.trash_retention_days),
+            manage_assets: Some(defaults.manage_assets),
+            git_friendly_save: Some(defaults.git_friendly_save),
+            media_memory_budget_mb: Some(defaults.media_memory_budget_mb),
+            quick_open_folders: None,
+            item_styles: Some(defaults.item_styles),
         }
     }
 
@@ -678,6 +759,106 @@ pub fn set_high_contrast(enabled: bool) -> Result<(), SettingsError> {
     })
 }
 
+/// Get how many days trashed boards are kept (0 means never purge).
+pub fn trash_retention_days() -> u32 {
+    let store = global_settings();
+    let guard = store.read().unwrap_or_else(|poisoned| poisoned.into_inner());
+    guard
+        .merged_content()
+        .trash_retention_days
+        .unwrap_or(AppSettings::default().trash_retention_days)
+}
+
+/// Set how many days trashed boards are kept.
+pub fn set_trash_retention_days(days: u32) -> Result<(), SettingsError> {
+    update_setting(SettingsSource::User, |content| {
+        content.trash_retention_days = Some(days);
+    })
+}
+
+/// Check if dropped files should be copied into the board's assets folder.
+pub fn is_manage_assets_enabled() -> bool {
+    let store = global_settings();
+    let guard = store.read().unwrap_or_else(|poisoned| poisoned.into_inner());
+    guard.merged_content().manage_assets.unwrap_or(false)
+}
+
+/// Set whether dropped files are copied into the board's assets folder.
+pub fn set_manage_assets(enabled: bool) -> Result<(), SettingsError> {
+    update_setting(SettingsSource::User, |content| {
+        content.manage_assets = Some(enabled);
+    })
+}
+
+/// Check if boards should be saved in the git-friendly layout.
+pub fn is_git_friendly_save_enabled() -> bool {
+    let store = global_settings();
+    let guard = store.read().unwrap_or_else(|poisoned| poisoned.into_inner());
+    guard.merged_content().git_friendly_save.unwrap_or(false)
+}
+
+/// Set whether boards are saved in the git-friendly layout.
+pub fn set_git_friendly_save(enabled: bool) -> Result<(), SettingsError> {
+    update_setting(SettingsSource::User, |content| {
+        content.git_friendly_save = Some(enabled);
+    })
+}
+
+/// Get the memory budget for media in megabytes.
+pub fn media_memory_budget_mb() -> u64 {
+    let store = global_settings();
+    let guard = store.read().unwrap_or_else(|poisoned| poisoned.into_inner());
+    guard
+        .merged_content()
+        .media_memory_budget_mb
+        .unwrap_or(AppSettings::default().media_memory_budget_mb)
+}
+
+/// Get the folders quick open lists files from, if the user chose them.
+pub fn quick_open_folders() -> Option<Vec<String>> {
+    let store = global_settings();
+    let guard = store.read().unwrap_or_else(|poisoned| poisoned.into_inner());
+    guard.merged_content().quick_open_folders.clone()
+}
+
+/// Get the grid size items snap to, or None when snap-to-grid is off.
+pub fn snap_grid_size() -> Option<f32> {
+    let store = global_settings();
+    let guard = store.read().unwrap_or_else(|poisoned| poisoned.into_inner());
+    let settings = guard.app_settings();
+    (settings.snap_to_grid && settings.grid_size > 0.0).then_some(settings.grid_size)
+}
+
+/// Turn snap-to-grid off (None) or on with the given grid size.
+pub fn set_snap_grid_size(grid_size: Option<f32>) -> Result<(), SettingsError> {
+    update_setting(SettingsSource::User, |content| {
+        content.snap_to_grid = Some(grid_size.is_some());
+        if let Some(size) = grid_size {
+            content.grid_size = Some(size);
+        }
+    })
+}
+
+/// Get the styles new text boxes, shapes, arrows and tables are made with.
+pub fn item_styles() -> ItemStyles {
+    let store = global_settings();
+    let guard = store.read().unwrap_or_else(|poisoned| poisoned.into_inner());
+    guard.app_settings().item_styles.clone()
+}
+
+/// Change the styles new items are made with.
+pub fn update_item_styles<F>(updater: F) -> Result<(), SettingsError>
+where
+    F: FnOnce(&mut ItemStyles),
+{
+    let mut styles = item_styles();
+    updater(&mut styles);
+    let styles = styles.sanitized();
+    update_setting(SettingsSource::User, |content| {
+        content.item_styles = Some(styles);
+    })
+}
+
 // ============================================================================
 // Legacy Settings struct - Backwards compatibility
 // ============================================================================
@@ -734,10 +915,10 @@ impl Settings {
 
     /// Apply the current theme
     pub fn apply_theme(&self, cx: &mut App) {
-        let theme_name = SharedString::from(self.theme.clone());
+        let theme_name = self.theme.clone();
 
-        // Get config clone from registry first
-        let config = ThemeRegistry::global(cx).themes().get(&theme_name).cloned();
+        // Get config clone from registry first, then the custom themes
+        let config = Self::theme_config(&theme_name, cx);
 
         if let Some(config) = config {
             // Set theme mode based on the config
@@ -765,17 +946,31 @@ impl Settings {
         }
     }
 
-    /// Get available themes from the registry
+    /// Get available themes from the registry, followed by the custom themes
     pub fn available_themes(cx: &App) -> Vec<String> {
         let registry = ThemeRegistry::global(cx);
-        let themes: Vec<String> = registry
+        let mut themes: Vec<String> = registry
             .sorted_themes()
             .iter()
             .map(|t| t.name.to_string())
             .collect();
+        for name in custom_theme_names() {
+            if !themes.contains(&name) {
+                themes.push(name);
+            }
+        }
 
         themes
     }
+
+    /// Look up a theme by name in the registry, or among the custom themes
+    pub fn theme_config(name: &str, cx: &App) -> Option<Rc<ThemeConfig>> {
+        ThemeRegistry::global(cx)
+            .themes()
+            .get(&SharedString::from(name.to_string()))
+            .cloned()
+            .or_else(|| custom_theme(name).map(Rc::new))
+    }
 }
 
 // ============================================================================
@@ -828,14 +1023,16 @@ pub fn init_themes(cx: &mut App) {
     // Try multiple locations for themes directory
     let themes_dir = find_themes_dir();
 
+    // Custom themes, and the content colors of every theme
+    reload_theme_library(themes_dir.as_deref());
+
     if let Some(themes_dir) = themes_dir {
         let saved_theme = app_settings().theme;
         let saved_theme_clone = saved_theme.clone();
 
         if let Err(_err) = ThemeRegistry::watch_dir(themes_dir, cx, move |cx| {
             // Apply saved theme after themes are loaded
-            let theme_name = SharedString::from(saved_theme_clone.clone());
-            if let Some(config) = ThemeRegistry::global(cx).themes().get(&theme_name).cloned() {
+            if let Some(config) = Settings::theme_config(&saved_theme_clone, cx) {
                 let mode = config.mode;
                 if mode.is_dark() {
                     Theme::global_mut(cx).dark_theme = config.clone();
@@ -849,5 +1046,13 @@ pub fn init_themes(cx: &mut App) {
         }) {
             warn!("Theme directory watch failed");
         }
+    } else if custom_theme(&app_settings().theme).is_some() {
+        Settings::load().apply_theme(cx);
     }
 }
+
+/// Read the custom themes and the content colors of the built-in themes
+/// again, after a theme is saved in the theme editor
+pub fn reload_themes() {
+    reload_theme_library(find_themes_dir().as_deref());
+}
@@ -0,0 +1,250 @@
+//! Settings profiles - the user settings, custom themes, keymap and
+//! per-board overrides in one JSON file, to carry a setup to another
+//! machine or keep it in dotfiles.
+//!
+//! A profile holds the files as they are on disk, so importing one writes
+//! them back where they came from; settings are merged into the ones
+//! already there rather than replacing them.
+
+use crate::board::BoardState;
+use crate::canvas_background::CanvasBackground;
+use crate::custom_themes::custom_themes_dir;
+use crate::error::SettingsError;
+use crate::keymap::default_keymap_path;
+use crate::settings::{SettingsContent, SettingsStore};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Version written into new profiles. Profiles from a newer version are
+/// refused rather than half read.
+pub const PROFILE_VERSION: u32 = 1;
+
+/// File name suggested when exporting a profile
+pub const PROFILE_FILE_NAME: &str = "humanboard-profile.json";
+
+/// Everything a profile carries
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SettingsProfile {
+    pub version: u32,
+    /// User settings: theme, font, grid, snapping, new item styles and the
+    /// other tool defaults
+    #[serde(default)]
+    pub settings: SettingsContent,
+    /// Custom theme files by file name
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub themes: BTreeMap<String, Value>,
+    /// keymap.json, if there is one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub keymap: Option<Value>,
+    /// Settings boards override, by board ID
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub boards: BTreeMap<String, BoardOverrides>,
+}
+
+/// Settings one board overrides
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BoardOverrides {
+    /// Board name, to find the board when its ID differs on another machine
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub background: Option<CanvasBackground>,
+}
+
+impl BoardOverrides {
+    /// The overrides of a board, or None if it follows the settings
+    pub fn from_state(name: &str, state: &BoardState) -> Option<Self> {
+        state.background.as_ref().map(|background| Self {
+            name: name.to_string(),
+            background: Some(background.clone()),
+        })
+    }
+}
+
+/// Where the files a profile carries live
+#[derive(Debug, Clone)]
+pub struct ProfilePaths {
+    pub settings: PathBuf,
+    pub keymap: Option<PathBuf>,
+    pub themes: Option<PathBuf>,
+}
+
+impl ProfilePaths {
+    /// The files of the current user
+    pub fn user() -> Self {
+        Self {
+            settings: SettingsStore::user_settings_path(),
+            keymap: default_keymap_path(),
+            themes: custom_themes_dir(),
+        }
+    }
+}
+
+/// What importing a profile wrote
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct InstalledProfile {
+    /// Custom themes written
+    pub themes: usize,
+    /// Whether keymap.json was written
+    pub keymap: bool,
+}
+
+/// Read the settings, custom themes and keymap at `paths` into a profile,
+/// with `boards` as its board overrides
+pub fn collect_profile(
+    paths: &ProfilePaths,
+    boards: BTreeMap<String, BoardOverrides>,
+) -> Result<SettingsProfile, SettingsError> {
+    let mut settings: SettingsContent = read_json(&paths.settings)?.unwrap_or_default();
+    // Whether this machine has seen the welcome screen isn't a preference
+    settings.onboarding_completed = None;
+
+    let keymap = match paths.keymap {
+        Some(ref path) => read_json(path)?,
+        None => None,
+    };
+
+    let mut themes = BTreeMap::new();
+    if let Some(ref dir) = paths.themes {
+        if let Ok(entries) = fs::read_dir(dir) {
+            for entry in entries.flatten() {
+                let path = entry.path();
+                let Some(name) = theme_entry_name(&path) else {
+                    continue;
+                };
+                // A theme file that doesn't parse isn't loaded here either
+                if let Ok(Some(theme)) = read_json::<Value>(&path) {
+                    themes.insert(name, theme);
+                }
+            }
+        }
+    }
+
+    Ok(SettingsProfile {
+        version: PROFILE_VERSION,
+        settings,
+        themes,
+        keymap,
+        boards,
+    })
+}
+
+/// Write a profile to `path` as pretty-printed JSON
+pub fn write_profile(profile: &SettingsProfile, path: &Path) -> Result<(), SettingsError> {
+    write_json(profile, path)
+}
+
+/// Read a profile from `path`
+pub fn read_profile(path: &Path) -> Result<SettingsProfile, SettingsError> {
+    let profile: SettingsProfile =
+        read_json(path)?.ok_or_else(|| SettingsError::NotFound(path.to_path_buf()))?;
+    if profile.version > PROFILE_VERSION {
+        return Err(SettingsError::InvalidValue(format!(
+            "profile version {} is newer than this version of Humanboard reads",
+            profile.version
+        )));
+    }
+    Ok(profile)
+}
+
+/// Write the settings, themes and keymap of a profile to `paths`. Its
+/// settings are merged into the ones there; themes and the keymap replace
+/// files of the same name.
+pub fn install_profile(
+    profile: &SettingsProfile,
+    paths: &ProfilePaths,
+) -> Result<InstalledProfile, SettingsError> {
+    let mut settings: SettingsContent = read_json(&paths.settings)?.unwrap_or_default();
+    settings.merge_from(&profile.settings);
+    write_json(&settings, &paths.settings)?;
+
+    let mut installed = InstalledProfile::default();
+    if let Some(ref dir) = paths.themes {
+        for (name, theme) in &profile.themes {
+            // Only plain theme file names - a profile can't write elsewhere
+            if theme_entry_name(Path::new(name)).as_deref() != Some(name.as_str()) {
+                continue;
+            }
+            write_json(theme, &dir.join(name))?;
+            installed.themes += 1;
+        }
+    }
+
+    if let (Some(keymap), Some(path)) = (&profile.keymap, &paths.keymap) {
+        write_json(keymap, path)?;
+        installed.keymap = true;
+    }
+
+    Ok(installed)
+}
+
+/// Match the board overrides of a profile with boards here, given as
+/// (ID, name) pairs: by ID, or else by a name only one board has.
+/// Returns the ID of each matched board with its overrides.
+pub fn match_boards<'a>(
+    overrides: &'a BTreeMap<String, BoardOverrides>,
+    boards: &[(String, String)],
+) -> Vec<(String, &'a BoardOverrides)> {
+    let mut by_name: HashMap<&str, Vec<&str>> = HashMap::new();
+    for (id, name) in boards {
+        by_name.entry(name.as_str()).or_default().push(id.as_str());
+    }
+
+    overrides
+        .iter()
+        .filter_map(|(id, board)| {
+            if boards.iter().any(|(board_id, _)| board_id == id) {
+                return Some((id.clone(), board));
+            }
+            match by_name.get(board.name.as_str()).map(Vec::as_slice) {
+                Some([only]) => Some((only.to_string(), board)),
+                _ => None,
+            }
+        })
+        .collect()
+}
+
+/// File name of a theme file, if `path` is one
+fn theme_entry_name(path: &Path) -> Option<String> {
+    if path.extension().and_then(|e| e.to_str()) != Some("json") {
+        return None;
+    }
+    let name = path.file_name()?.to_str()?;
+    (!name.starts_with('.')).then(|| name.to_string())
+}
+
+/// Read a JSON file, or None if there is no file
+fn read_json<T: serde::de::DeserializeOwned>(path: &Path) -> Result<Option<T>, SettingsError> {
+    if !path.exists() {
+        return Ok(None);
+    }
+    let json = fs::read_to_string(path).map_err(|e| SettingsError::LoadFailed {
+        path: path.to_path_buf(),
+        source: e,
+    })?;
+    serde_json::from_str(&json)
+        .map(Some)
+        .map_err(|e| SettingsError::ParseFailed {
+            path: path.to_path_buf(),
+            message: e.to_string(),
+        })
+}
+
+fn write_json<T: Serialize>(value: &T, path: &Path) -> Result<(), SettingsError> {
+    let json = serde_json::to_string_pretty(value).map_err(|e| SettingsError::ParseFailed {
+        path: path.to_path_buf(),
+        message: e.to_string(),
+    })?;
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| SettingsError::SaveFailed {
+            path: path.to_path_buf(),
+            source: e,
+        })?;
+    }
+    fs::write(path, json).map_err(|e| SettingsError::SaveFailed {
+        path: path.to_path_buf(),
+        source: e,
+    })
+}
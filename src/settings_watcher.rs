@@ -39,8 +39,15 @@ impl SettingsWatcher {
     /// Create a new settings watcher for the given path.
     ///
     /// The path can be a file or directory. If a directory, all files
-    /// within it will be watched.
+    /// within it will be watched. A file is watched through its directory,
+    /// so it is noticed when it's created, and changes to the other files
+    /// there are ignored.
     pub fn new(path: PathBuf) -> Result<Self, notify::Error> {
+        let watched_file = if path.is_dir() {
+            None
+        } else {
+            path.file_name().map(|name| name.to_os_string())
+        };
         let (tx, rx) = mpsc::channel();
         let (event_tx, event_rx) = mpsc::channel();
 
@@ -73,6 +80,12 @@ impl SettingsWatcher {
             move |res: Result<Event, notify::Error>| {
                 match res {
                     Ok(event) => {
+                        if let Some(ref name) = watched_file {
+                            if !event.paths.iter().any(|p| p.file_name() == Some(name.as_os_str())) {
+                                return;
+                            }
+                        }
+
                         let settings_event = match event.kind {
                             EventKind::Modify(_) => Some(SettingsEvent::Modified),
                             EventKind::Create(_) => Some(SettingsEvent::Created),
@@ -95,10 +108,10 @@ impl SettingsWatcher {
         )?;
 
         // Start watching
-        let watch_path = if path.is_file() {
-            path.parent().unwrap_or(&path).to_path_buf()
-        } else {
+        let watch_path = if path.is_dir() {
             path.clone()
+        } else {
+            path.parent().unwrap_or(&path).to_path_buf()
         };
 
         watcher.watch(&watch_path, RecursiveMode::NonRecursive)?;
@@ -0,0 +1,71 @@
+//! Sidecar subtitle files for video items.
+//!
+//! A video's subtitles are a `.vtt` or `.srt` file next to it sharing its
+//! name, like `lecture.vtt` or `lecture.en.srt` for `lecture.mp4`. Players
+//! only understand WebVTT, so SubRip files are converted when loaded.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Subtitle file extensions, in order of preference
+const SUBTITLE_EXTENSIONS: [&str; 2] = ["vtt", "srt"];
+
+/// Find the subtitle file next to `video_path`. An exact name match like
+/// `lecture.vtt` wins over a tagged one like `lecture.en.vtt`, and WebVTT
+/// wins over SubRip.
+pub fn find_sidecar(video_path: &Path) -> Option<PathBuf> {
+    let stem = video_path.file_stem()?.to_str()?;
+    let dir = video_path.parent()?;
+
+    for ext in SUBTITLE_EXTENSIONS {
+        let exact = dir.join(format!("{}.{}", stem, ext));
+        if exact.is_file() {
+            return Some(exact);
+        }
+    }
+
+    let tagged_prefix = format!("{}.", stem);
+    let mut tagged: Vec<PathBuf> = fs::read_dir(dir)
+        .ok()?
+        .filter_map(Result::ok)
+        .map(|entry| entry.path())
+        .filter(|path| {
+            let name = path.file_name().and_then(|n| n.to_str()).unwrap_or("");
+            name.starts_with(&tagged_prefix) && subtitle_rank(path).is_some()
+        })
+        .collect();
+    tagged.sort_by_key(|path| (subtitle_rank(path), path.clone()));
+    tagged.into_iter().next()
+}
+
+fn subtitle_rank(path: &Path) -> Option<usize> {
+    let ext = path.extension()?.to_str()?.to_lowercase();
+    SUBTITLE_EXTENSIONS.iter().position(|e| *e == ext)
+}
+
+/// Read a subtitle file as WebVTT
+pub fn load_vtt(path: &Path) -> Result<String, String> {
+    let text = fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read subtitles {}: {}", path.display(), e))?;
+    let is_srt = path
+        .extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(|e| e.eq_ignore_ascii_case("srt"));
+    Ok(if is_srt { srt_to_vtt(&text) } else { text })
+}
+
+/// Convert SubRip subtitles to WebVTT. Cue numbers are kept as cue IDs;
+/// only the header and the decimal commas in timings differ.
+pub fn srt_to_vtt(srt: &str) -> String {
+    let srt = srt.trim_start_matches('\u{feff}');
+    let mut vtt = String::from("WEBVTT\n\n");
+    for line in srt.lines() {
+        if line.contains("-->") {
+            vtt.push_str(&line.replace(',', "."));
+        } else {
+            vtt.push_str(line);
+        }
+        vtt.push('\n');
+    }
+    vtt
+}
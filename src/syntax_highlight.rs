@@ -0,0 +1,120 @@
+//! Tree-sitter highlighting of text drawn line by line, outside the code
+//! editor.
+//!
+//! The text is parsed whole with the grammar the editor uses for its
+//! language, so strings and comments spanning lines are coloured on every
+//! line, and each line's highlights are then taken from the parse when it
+//! is drawn. Languages without a [`grammar_name`] fall back to
+//! [`tokenize_line`].
+//!
+//! A diff is highlighted as its two texts, [`DiffHighlights`] parsing the
+//! old lines and the new lines apart.
+
+use crate::syntax_tokens::{TokenKind, grammar_name, tokenize_line};
+use crate::text_diff::{DiffKind, DiffLine, TextDiff};
+use gpui::HighlightStyle;
+use gpui_component::highlighter::{HighlightTheme, SyntaxHighlighter};
+use gpui_component::input::Rope;
+use std::ops::Range;
+
+/// Highlights for the lines of one text
+pub struct LineHighlighter {
+    /// The parsed text, unless its language has no grammar
+    syntax: Option<SyntaxHighlighter>,
+    language: String,
+    /// Byte offset of each line in the parsed text
+    line_starts: Vec<usize>,
+}
+
+impl LineHighlighter {
+    /// Parse `lines`, joined by newlines, as `language`
+    pub fn new<'a>(lines: impl IntoIterator<Item = &'a str>, language: &str) -> Self {
+        let mut text = String::new();
+        let mut line_starts = Vec::new();
+        for line in lines {
+            if !line_starts.is_empty() {
+                text.push('\n');
+            }
+            line_starts.push(text.len());
+            text.push_str(line);
+        }
+        let syntax = grammar_name(language).map(|grammar| {
+            let mut syntax = SyntaxHighlighter::new(grammar);
+            syntax.update(None, &Rope::from(text.as_str()));
+            syntax
+        });
+        Self {
+            syntax,
+            language: language.to_string(),
+            line_starts,
+        }
+    }
+
+    /// Highlighted stretches of line `index`, whose text is `line`, as byte
+    /// ranges within it. `fallback` colors lines of languages without a
+    /// grammar.
+    pub fn line_styles(
+        &self,
+        index: usize,
+        line: &str,
+        theme: &HighlightTheme,
+        fallback: impl Fn(TokenKind) -> HighlightStyle,
+    ) -> Vec<(Range<usize>, HighlightStyle)> {
+        let (Some(syntax), Some(&start)) = (&self.syntax, self.line_starts.get(index)) else {
+            return tokenize_line(line, &self.language)
+                .into_iter()
+                .map(|(range, kind)| (range, fallback(kind)))
+                .collect();
+        };
+        let end = start + line.len();
+        syntax
+            .styles(&(start..end), theme)
+            .into_iter()
+            .filter(|(range, _)| range.start < end && range.end > start)
+            .map(|(range, style)| {
+                let clipped = range.start.max(start) - start..range.end.min(end) - start;
+                (clipped, style)
+            })
+            .collect()
+    }
+}
+
+/// Highlights for the lines of a diff, old and new texts parsed apart
+pub struct DiffHighlights {
+    old: LineHighlighter,
+    new: LineHighlighter,
+}
+
+impl DiffHighlights {
+    pub fn new(diff: &TextDiff, language: &str) -> Self {
+        let side = |old: bool| {
+            let lines = diff.lines.iter().filter(move |line| {
+                if old {
+                    line.kind != DiffKind::Added
+                } else {
+                    line.kind != DiffKind::Removed
+                }
+            });
+            LineHighlighter::new(lines.map(|line| line.text.as_str()), language)
+        };
+        Self {
+            old: side(true),
+            new: side(false),
+        }
+    }
+
+    /// Highlighted stretches of a line of the diff, from the text it is in
+    pub fn line_styles(
+        &self,
+        line: &DiffLine,
+        theme: &HighlightTheme,
+        fallback: impl Fn(TokenKind) -> HighlightStyle,
+    ) -> Vec<(Range<usize>, HighlightStyle)> {
+        let (highlighter, number) = match line.kind {
+            DiffKind::Removed => (&self.old, line.old_number),
+            DiffKind::Unchanged | DiffKind::Added => (&self.new, line.new_number),
+        };
+        let index = number.unwrap_or(0).saturating_sub(1);
+        highlighter.line_styles(index, &line.text, theme, fallback)
+    }
+}
@@ -0,0 +1,155 @@
+//! Light syntax highlighting of single lines of code, and the tree-sitter
+//! grammars used in its place.
+//!
+//! Languages with a grammar bundled with the code editor, named by
+//! [`grammar_name`], are highlighted with it everywhere. Lines of other
+//! languages are split into keywords, strings, comments and numbers
+//! without state carried between them: block comments and strings spanning
+//! lines are only coloured on the line they start. Languages are the
+//! identifiers of
+//! [`language_from_extension`](crate::types::language_from_extension).
+
+use std::ops::Range;
+
+/// What a highlighted stretch of a line is
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TokenKind {
+    Keyword,
+    String,
+    Comment,
+    Number,
+}
+
+/// Words coloured as keywords, common to most languages
+const KEYWORDS: &str = "abstract as async await break case catch class const continue def \
+    default defer del do elif else end enum except export extends extern false False final \
+    finally fn for from func function go if impl implements import in interface is lambda let \
+    local loop match mod module mut new nil None not null package pass private protected pub \
+    public raise return self Self static struct super switch then this throw trait true True \
+    try type typeof undefined unsafe use val var void where while with yield";
+
+/// Name of the tree-sitter grammar a language is highlighted with, if the
+/// code editor bundles one
+pub fn grammar_name(language: &str) -> Option<&'static str> {
+    Some(match language {
+        "rust" => "rust",
+        "python" => "python",
+        "javascript" => "javascript",
+        "typescript" => "typescript",
+        "tsx" => "tsx",
+        "go" => "go",
+        "c" => "c",
+        "cpp" => "cpp",
+        "java" => "java",
+        "csharp" => "csharp",
+        "swift" => "swift",
+        "scala" => "scala",
+        "ruby" => "ruby",
+        "elixir" => "elixir",
+        "zig" => "zig",
+        "bash" => "bash",
+        "sql" => "sql",
+        "html" => "html",
+        "css" => "css",
+        "json" => "json",
+        "yaml" => "yaml",
+        "toml" => "toml",
+        "markdown" => "markdown",
+        "makefile" => "make",
+        _ => return None,
+    })
+}
+
+/// Highlighted stretches of `line`, as byte ranges in order
+pub fn tokenize_line(line: &str, language: &str) -> Vec<(Range<usize>, TokenKind)> {
+    let mut tokens = Vec::new();
+    if !has_code_syntax(language) {
+        return tokens;
+    }
+    let comment = line_comment(language);
+    let bytes = line.as_bytes();
+    let mut index = 0;
+    while index < bytes.len() {
+        let rest = &line[index..];
+        let byte = bytes[index];
+        if comment.is_some_and(|prefix| rest.starts_with(prefix)) {
+            tokens.push((index..line.len(), TokenKind::Comment));
+            break;
+        }
+        if comment == Some("//") && rest.starts_with("/*") {
+            let end = rest[2..]
+                .find("*/")
+                .map_or(line.len(), |end| index + end + 4);
+            tokens.push((index..end, TokenKind::Comment));
+            index = end;
+        } else if byte == b'"' || byte == b'`' || (byte == b'\'' && language != "rust") {
+            let end = string_end(bytes, index);
+            tokens.push((index..end, TokenKind::String));
+            index = end;
+        } else if byte.is_ascii_digit() {
+            let end = word_end(bytes, index);
+            tokens.push((index..end, TokenKind::Number));
+            index = end;
+        } else if byte.is_ascii_alphabetic() || byte == b'_' {
+            let end = word_end(bytes, index);
+            if KEYWORDS
+                .split_whitespace()
+                .any(|word| word == &line[index..end])
+            {
+                tokens.push((index..end, TokenKind::Keyword));
+            }
+            index = end;
+        } else {
+            index += rest.chars().next().map_or(1, char::len_utf8);
+        }
+    }
+    tokens
+}
+
+/// Whether a language is code with keywords and strings, rather than
+/// prose or markup
+fn has_code_syntax(language: &str) -> bool {
+    !matches!(
+        language,
+        "" | "markdown" | "text" | "html" | "xml" | "css" | "scss" | "less" | "json" | "csv"
+    )
+}
+
+/// What starts a comment running to the end of the line
+fn line_comment(language: &str) -> Option<&'static str> {
+    match language {
+        "python" | "ruby" | "bash" | "powershell" | "yaml" | "toml" | "elixir" | "nim" | "r"
+        | "perl" => Some("#"),
+        "sql" | "lua" | "haskell" => Some("--"),
+        "erlang" => Some("%"),
+        "ocaml" => None,
+        _ => Some("//"),
+    }
+}
+
+/// End of the string starting at `start`, just past its closing quote or
+/// at the end of the line
+fn string_end(bytes: &[u8], start: usize) -> usize {
+    let quote = bytes[start];
+    let mut index = start + 1;
+    while index < bytes.len() {
+        match bytes[index] {
+            b'\\' => index += 2,
+            byte if byte == quote => return index + 1,
+            _ => index += 1,
+        }
+    }
+    bytes.len()
+}
+
+/// End of the word or number starting at `start`. Numbers run on through
+/// dots, so `1.5` is one token.
+fn word_end(bytes: &[u8], start: usize) -> usize {
+    let number = bytes[start].is_ascii_digit();
+    bytes[start..]
+        .iter()
+        .position(|&byte| {
+            !(byte.is_ascii_alphanumeric() || byte == b'_' || (number && byte == b'.'))
+        })
+        .map_or(bytes.len(), |len| start + len)
+}
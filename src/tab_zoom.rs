@@ -0,0 +1,116 @@
+//! Per-file zoom of preview tabs.
+//!
+//! Cmd+ and Cmd- over the preview panel zoom the focused tab: text, code
+//! and markdown tabs scale their font, PDF and image tabs zoom their
+//! content. The level is remembered per file, so a file opens at the zoom
+//! it was last viewed at, in any window and on any board.
+
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use tracing::warn;
+
+/// Zoom levels stepped through by Cmd+ and Cmd-
+pub const TAB_ZOOM_LEVELS: [f32; 13] = [
+    0.5, 0.67, 0.75, 0.8, 0.9, 1.0, 1.1, 1.25, 1.5, 1.75, 2.0, 2.5, 3.0,
+];
+
+/// Factor an image tab is zoomed by per step
+pub const IMAGE_ZOOM_STEP: f32 = 1.25;
+
+/// The next zoom level above (or below, when `zoom_in` is false) `zoom`,
+/// staying at the ends of [`TAB_ZOOM_LEVELS`]
+pub fn step_tab_zoom(zoom: f32, zoom_in: bool) -> f32 {
+    const EPSILON: f32 = 0.001;
+    let next = if zoom_in {
+        TAB_ZOOM_LEVELS
+            .iter()
+            .find(|&&level| level > zoom + EPSILON)
+    } else {
+        TAB_ZOOM_LEVELS
+            .iter()
+            .rev()
+            .find(|&&level| level < zoom - EPSILON)
+    };
+    next.copied().unwrap_or(zoom).clamp(
+        TAB_ZOOM_LEVELS[0],
+        TAB_ZOOM_LEVELS[TAB_ZOOM_LEVELS.len() - 1],
+    )
+}
+
+/// Default file the zoom levels are saved in
+pub fn default_zoom_file() -> PathBuf {
+    dirs::data_dir()
+        .unwrap_or_else(std::env::temp_dir)
+        .join("humanboard")
+        .join("tab_zoom.json")
+}
+
+/// The zoom levels shared by every window
+pub static TAB_ZOOM: Lazy<TabZoomStore> = Lazy::new(|| TabZoomStore::new(default_zoom_file()));
+
+/// Zoom level of each file viewed at other than 100%, mirrored to disk
+pub struct TabZoomStore {
+    file: PathBuf,
+    levels: Mutex<HashMap<PathBuf, f32>>,
+    loaded: AtomicBool,
+}
+
+impl TabZoomStore {
+    pub fn new(file: PathBuf) -> Self {
+        Self {
+            file,
+            levels: Mutex::new(HashMap::new()),
+            loaded: AtomicBool::new(false),
+        }
+    }
+
+    /// Read the levels saved by earlier runs, once
+    fn ensure_loaded(&self) {
+        if self.loaded.swap(true, Ordering::SeqCst) {
+            return;
+        }
+        let saved: HashMap<PathBuf, f32> = fs::read(&self.file)
+            .ok()
+            .and_then(|json| serde_json::from_slice(&json).ok())
+            .unwrap_or_default();
+        let mut levels = self.levels.lock();
+        for (path, zoom) in saved {
+            levels.entry(path).or_insert(zoom);
+        }
+    }
+
+    /// Zoom the file was last viewed at, if not 100%
+    pub fn get(&self, path: &Path) -> Option<f32> {
+        self.ensure_loaded();
+        self.levels.lock().get(path).copied()
+    }
+
+    /// Remember a file's zoom. `None` (or 100%) forgets it.
+    pub fn set(&self, path: &Path, zoom: Option<f32>) {
+        self.ensure_loaded();
+        let mut levels = self.levels.lock();
+        let changed = match zoom.filter(|zoom| (zoom - 1.0).abs() > f32::EPSILON) {
+            Some(zoom) => levels.insert(path.to_path_buf(), zoom) != Some(zoom),
+            None => levels.remove(path).is_some(),
+        };
+        if changed {
+            self.write(&levels);
+        }
+    }
+
+    fn write(&self, levels: &HashMap<PathBuf, f32>) {
+        let result = self
+            .file
+            .parent()
+            .map_or(Ok(()), fs::create_dir_all)
+            .and_then(|_| serde_json::to_vec_pretty(levels).map_err(std::io::Error::other))
+            .and_then(|json| fs::write(&self.file, json));
+        if let Err(e) = result {
+            warn!("Failed to save tab zoom levels to {:?}: {}", self.file, e);
+        }
+    }
+}
@@ -0,0 +1,260 @@
+//! Line diffs of two texts, for the preview panel's diff tabs.
+//!
+//! A diff is worked out from the lines the two texts share, longest common
+//! subsequence first, after setting aside the lines they start and end
+//! with. Texts too different to compare line by line within
+//! [`MAX_DIFF_CELLS`] show every changed line as removed and added.
+//!
+//! The same lines are laid out two ways: unified, one after another, or
+//! side by side, where [`side_by_side_rows`] pairs removed lines with the
+//! added lines that replaced them. [`fold_unchanged`] hides long runs of
+//! unchanged lines in either layout.
+
+use serde::{Deserialize, Serialize};
+use std::ops::Range;
+use std::path::PathBuf;
+
+/// Largest number of line pairs compared when working out a diff
+pub const MAX_DIFF_CELLS: usize = 4_000_000;
+
+/// Unchanged lines kept around each change when the rest are folded
+pub const DIFF_CONTEXT_LINES: usize = 3;
+
+/// What a file is compared against
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum DiffBase {
+    /// Another file
+    File(PathBuf),
+    /// The file's text in the newest board version that stored it
+    Snapshot,
+}
+
+/// How a line differs between the two texts
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DiffKind {
+    Unchanged,
+    Removed,
+    Added,
+}
+
+/// A line of a diff, with its line numbers counted from 1
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DiffLine {
+    pub kind: DiffKind,
+    /// Line number in the old text, unless the line was added
+    pub old_number: Option<usize>,
+    /// Line number in the new text, unless the line was removed
+    pub new_number: Option<usize>,
+    pub text: String,
+}
+
+/// A row of a side-by-side diff: indices of the lines shown on the left
+/// (old) and right (new), either of which may be blank
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DiffRow {
+    pub old: Option<usize>,
+    pub new: Option<usize>,
+}
+
+impl DiffRow {
+    /// Whether the row shows a change rather than the same line twice
+    pub fn is_change(&self, lines: &[DiffLine]) -> bool {
+        self.old
+            .or(self.new)
+            .is_some_and(|index| lines[index].kind != DiffKind::Unchanged)
+    }
+}
+
+/// A stretch of diff lines or rows, shown or folded away
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum DiffFold {
+    Shown(Range<usize>),
+    Hidden(Range<usize>),
+}
+
+/// The lines of `old` and `new`, each marked unchanged, removed or added
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct TextDiff {
+    pub lines: Vec<DiffLine>,
+    pub added: usize,
+    pub removed: usize,
+}
+
+impl TextDiff {
+    pub fn new(old: &str, new: &str) -> Self {
+        let lines = diff_lines(old, new);
+        let added = lines.iter().filter(|l| l.kind == DiffKind::Added).count();
+        let removed = lines.iter().filter(|l| l.kind == DiffKind::Removed).count();
+        Self {
+            lines,
+            added,
+            removed,
+        }
+    }
+
+    /// Whether the two texts have the same lines
+    pub fn is_identical(&self) -> bool {
+        self.added == 0 && self.removed == 0
+    }
+}
+
+/// Diff `old` against `new` line by line
+pub fn diff_lines(old: &str, new: &str) -> Vec<DiffLine> {
+    let old: Vec<&str> = old.lines().collect();
+    let new: Vec<&str> = new.lines().collect();
+
+    let prefix = old.iter().zip(&new).take_while(|(a, b)| a == b).count();
+    let suffix = old[prefix..]
+        .iter()
+        .rev()
+        .zip(new[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+    let old_middle = &old[prefix..old.len() - suffix];
+    let new_middle = &new[prefix..new.len() - suffix];
+
+    let mut builder = DiffBuilder::default();
+    for line in &old[..prefix] {
+        builder.push(DiffKind::Unchanged, line);
+    }
+    if old_middle.len().saturating_mul(new_middle.len()) > MAX_DIFF_CELLS {
+        for line in old_middle {
+            builder.push(DiffKind::Removed, line);
+        }
+        for line in new_middle {
+            builder.push(DiffKind::Added, line);
+        }
+    } else {
+        diff_middle(old_middle, new_middle, &mut builder);
+    }
+    for line in &old[old.len() - suffix..] {
+        builder.push(DiffKind::Unchanged, line);
+    }
+    builder.lines
+}
+
+/// Numbers lines as they are added to a diff
+#[derive(Default)]
+struct DiffBuilder {
+    lines: Vec<DiffLine>,
+    old_number: usize,
+    new_number: usize,
+}
+
+impl DiffBuilder {
+    fn push(&mut self, kind: DiffKind, text: &str) {
+        let old_number = (kind != DiffKind::Added).then(|| {
+            self.old_number += 1;
+            self.old_number
+        });
+        let new_number = (kind != DiffKind::Removed).then(|| {
+            self.new_number += 1;
+            self.new_number
+        });
+        self.lines.push(DiffLine {
+            kind,
+            old_number,
+            new_number,
+            text: text.to_string(),
+        });
+    }
+}
+
+/// Diff lines with nothing in common at either end, from the longest
+/// common subsequence. Removed lines come before the added lines they sit
+/// next to.
+fn diff_middle(old: &[&str], new: &[&str], builder: &mut DiffBuilder) {
+    let width = new.len() + 1;
+    // common[i * width + j]: length of the common subsequence of old[i..] and new[j..]
+    let mut common = vec![0u32; (old.len() + 1) * width];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            common[i * width + j] = if old[i] == new[j] {
+                common[(i + 1) * width + j + 1] + 1
+            } else {
+                common[(i + 1) * width + j].max(common[i * width + j + 1])
+            };
+        }
+    }
+
+    let (mut i, mut j) = (0, 0);
+    while i < old.len() || j < new.len() {
+        if i < old.len() && j < new.len() && old[i] == new[j] {
+            builder.push(DiffKind::Unchanged, old[i]);
+            i += 1;
+            j += 1;
+        } else if j == new.len()
+            || (i < old.len() && common[(i + 1) * width + j] >= common[i * width + j + 1])
+        {
+            builder.push(DiffKind::Removed, old[i]);
+            i += 1;
+        } else {
+            builder.push(DiffKind::Added, new[j]);
+            j += 1;
+        }
+    }
+}
+
+/// Rows of a side-by-side diff. Each run of removed lines is paired with
+/// the run of added lines after it, row by row.
+pub fn side_by_side_rows(lines: &[DiffLine]) -> Vec<DiffRow> {
+    let mut rows = Vec::new();
+    let mut index = 0;
+    while index < lines.len() {
+        if lines[index].kind == DiffKind::Unchanged {
+            rows.push(DiffRow {
+                old: Some(index),
+                new: Some(index),
+            });
+            index += 1;
+            continue;
+        }
+        let removed_start = index;
+        while index < lines.len() && lines[index].kind == DiffKind::Removed {
+            index += 1;
+        }
+        let added_start = index;
+        while index < lines.len() && lines[index].kind == DiffKind::Added {
+            index += 1;
+        }
+        let removed = removed_start..added_start;
+        let added = added_start..index;
+        for offset in 0..removed.len().max(added.len()) {
+            rows.push(DiffRow {
+                old: Some(removed.start + offset).filter(|i| removed.contains(i)),
+                new: Some(added.start + offset).filter(|i| added.contains(i)),
+            });
+        }
+    }
+    rows
+}
+
+/// Split lines or rows into stretches to show and to fold away: each
+/// change is shown with `context` unchanged entries either side of it, and
+/// the unchanged entries further away are hidden
+pub fn fold_unchanged(changed: &[bool], context: usize) -> Vec<DiffFold> {
+    let mut shown = vec![false; changed.len()];
+    for (index, _) in changed.iter().enumerate().filter(|(_, c)| **c) {
+        let start = index.saturating_sub(context);
+        let end = (index + context + 1).min(changed.len());
+        shown[start..end].iter_mut().for_each(|s| *s = true);
+    }
+
+    let mut folds = Vec::new();
+    let mut start = 0;
+    while start < shown.len() {
+        let visible = shown[start];
+        let end = shown[start..]
+            .iter()
+            .position(|&s| s != visible)
+            .map_or(shown.len(), |len| start + len);
+        folds.push(if visible {
+            DiffFold::Shown(start..end)
+        } else {
+            DiffFold::Hidden(start..end)
+        });
+        start = end;
+    }
+    folds
+}
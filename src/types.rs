@@ -3,10 +3,14 @@
 //! This module defines the fundamental data structures used throughout the application,
 //! including canvas items, content types, and helper functions for content detection.
 
+use crate::audio_metadata::AudioMetadataCache;
+use crate::constants::{
+    CODE_EXPAND_HEIGHT, DEFAULT_IMAGE_SIZE, MARKDOWN_EXPAND_HEIGHT, MAX_IMAGE_DIMENSION,
+};
 use crate::pdf::generate_pdf_thumbnail;
-use image::GenericImageView;
 use serde::{Deserialize, Serialize};
-use std::path::PathBuf;
+use std::hash::{Hash, Hasher};
+use std::path::{Path, PathBuf};
 
 // ============================================================================
 // Data Visualization Types
@@ -447,6 +451,74 @@ pub struct CanvasItem {
     pub size: (f32, f32),
     /// The content this item displays
     pub content: ItemContent,
+    /// Locked items can be selected but not moved, resized or deleted
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub locked: bool,
+    /// Volume of a media item's player; `None` plays at full volume
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub volume: Option<MediaVolume>,
+}
+
+impl CanvasItem {
+    /// Whether this is a markdown card resized tall enough to render its
+    /// document in place
+    pub fn is_expanded_markdown(&self) -> bool {
+        matches!(self.content, ItemContent::Markdown { .. })
+            && self.size.1 >= MARKDOWN_EXPAND_HEIGHT
+    }
+
+    /// Whether this is a code card resized tall enough to show an excerpt
+    /// of its file in place
+    pub fn is_expanded_code(&self) -> bool {
+        matches!(self.content, ItemContent::Code { .. }) && self.size.1 >= CODE_EXPAND_HEIGHT
+    }
+}
+
+/// Volume and mute state of a media item's player
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct MediaVolume {
+    /// Volume from 0 (silent) to 1 (full)
+    pub level: f32,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub muted: bool,
+}
+
+impl Default for MediaVolume {
+    fn default() -> Self {
+        Self {
+            level: 1.0,
+            muted: false,
+        }
+    }
+}
+
+impl MediaVolume {
+    /// This volume at `level`, clamped to 0..=1. Turning the volume up
+    /// unmutes.
+    pub fn with_level(self, level: f32) -> Self {
+        let level = if level.is_finite() {
+            level.clamp(0.0, 1.0)
+        } else {
+            self.level
+        };
+        Self {
+            level,
+            muted: self.muted && level <= 0.0,
+        }
+    }
+
+    /// This volume muted or unmuted, keeping its level
+    pub fn toggled_mute(self) -> Self {
+        Self {
+            muted: !self.muted,
+            ..self
+        }
+    }
+
+    /// Whether the player is silent, muted or turned all the way down
+    pub fn is_silent(&self) -> bool {
+        self.muted || self.level <= 0.0
+    }
 }
 
 /// Tool types for the Miro-style tool dock
@@ -459,10 +531,11 @@ pub enum ToolType {
     Shape,
     Table,
     Chart,
+    Lasso,
 }
 
 /// Shape types for the Shape tool
-#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Clone, Copy, Debug, Default, Hash, PartialEq, Eq, Serialize, Deserialize)]
 pub enum ShapeType {
     #[default]
     Rectangle,
@@ -471,7 +544,7 @@ pub enum ShapeType {
 }
 
 /// Arrow head styles
-#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Clone, Copy, Debug, Default, Hash, PartialEq, Eq, Serialize, Deserialize)]
 pub enum ArrowHead {
     None,
     #[default]
@@ -536,11 +609,31 @@ pub enum ItemContent {
         path: PathBuf,
         /// Path to generated thumbnail image
         thumbnail: Option<PathBuf>,
+        /// Page shown on the card, counted from 0
+        #[serde(default, skip_serializing_if = "is_first_page")]
+        page: usize,
     },
     /// A web link/URL
     Link(String),
+    /// A web page shown with its title and description
+    Bookmark {
+        url: String,
+        /// Page title, or the site's host until the page has been fetched
+        title: String,
+        #[serde(default, skip_serializing_if = "String::is_empty")]
+        description: String,
+        /// Site name, or the host
+        #[serde(default)]
+        site: String,
+    },
     /// An embedded YouTube video (stores video ID)
     YouTube(String),
+    /// An embedded Vimeo video (stores video ID)
+    Vimeo(String),
+    /// An embedded SoundCloud track or playlist (stores its soundcloud.com URL)
+    SoundCloud(String),
+    /// An embedded Spotify player (stores the embed path, e.g. `track/ID`)
+    Spotify(String),
     /// A markdown document
     Markdown {
         /// Path to the markdown file
@@ -556,6 +649,10 @@ pub enum ItemContent {
         path: PathBuf,
         /// Language identifier for syntax highlighting (e.g., "rust", "python")
         language: String,
+        /// Lines shown when the card is expanded, counted from 1 and
+        /// inclusive; `None` shows the start of the file
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        lines: Option<(usize, usize)>,
     },
     /// Editable text box (Miro-style)
     TextBox {
@@ -608,6 +705,117 @@ pub enum ItemContent {
     },
 }
 
+fn is_first_page(page: &usize) -> bool {
+    *page == 0
+}
+
+/// Hashed field by field, with sizes by their bits, so render caches can key
+/// on content without formatting it
+impl Hash for ItemContent {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        std::mem::discriminant(self).hash(state);
+        match self {
+            ItemContent::Image(path) | ItemContent::Video(path) | ItemContent::Audio(path) => {
+                path.hash(state)
+            }
+            ItemContent::Text(text)
+            | ItemContent::Link(text)
+            | ItemContent::YouTube(text)
+            | ItemContent::Vimeo(text)
+            | ItemContent::SoundCloud(text)
+            | ItemContent::Spotify(text) => text.hash(state),
+            ItemContent::Pdf {
+                path,
+                thumbnail,
+                page,
+            } => {
+                path.hash(state);
+                thumbnail.hash(state);
+                page.hash(state);
+            }
+            ItemContent::Bookmark {
+                url,
+                title,
+                description,
+                site,
+            } => {
+                url.hash(state);
+                title.hash(state);
+                description.hash(state);
+                site.hash(state);
+            }
+            ItemContent::Markdown {
+                path,
+                title,
+                content,
+            } => {
+                path.hash(state);
+                title.hash(state);
+                content.hash(state);
+            }
+            ItemContent::Code {
+                path,
+                language,
+                lines,
+            } => {
+                path.hash(state);
+                language.hash(state);
+                lines.hash(state);
+            }
+            ItemContent::TextBox {
+                text,
+                font_size,
+                color,
+            } => {
+                text.hash(state);
+                font_size.to_bits().hash(state);
+                color.hash(state);
+            }
+            ItemContent::Arrow {
+                end_offset,
+                color,
+                thickness,
+                head_style,
+            } => {
+                end_offset.0.to_bits().hash(state);
+                end_offset.1.to_bits().hash(state);
+                color.hash(state);
+                thickness.to_bits().hash(state);
+                head_style.hash(state);
+            }
+            ItemContent::Shape {
+                shape_type,
+                fill_color,
+                border_color,
+                border_width,
+            } => {
+                shape_type.hash(state);
+                fill_color.hash(state);
+                border_color.hash(state);
+                border_width.to_bits().hash(state);
+            }
+            ItemContent::Table {
+                data_source_id,
+                show_headers,
+                stripe,
+            } => {
+                data_source_id.hash(state);
+                show_headers.hash(state);
+                stripe.hash(state);
+            }
+            ItemContent::Chart {
+                data_source_id,
+                source_item_id,
+                config,
+            } => {
+                data_source_id.hash(state);
+                source_item_id.hash(state);
+                config.hash(state);
+            }
+        }
+    }
+}
+
 /// Get the language identifier for syntax highlighting from file extension
 pub fn language_from_extension(ext: &str) -> Option<&'static str> {
     match ext.to_lowercase().as_str() {
@@ -616,7 +824,7 @@ pub fn language_from_extension(ext: &str) -> Option<&'static str> {
         "js" => Some("javascript"),
         "ts" => Some("typescript"),
         "jsx" => Some("javascript"),
-        "tsx" => Some("typescript"),
+        "tsx" => Some("tsx"),
         "go" => Some("go"),
         "c" => Some("c"),
         "h" => Some("c"),
@@ -695,41 +903,161 @@ pub fn extract_youtube_id(url: &str) -> Option<String> {
     None
 }
 
+/// Start time in seconds from a YouTube URL's `t=` or `start=` parameter,
+/// written as `90`, `90s` or `1h2m3s`
+pub fn extract_youtube_start(url: &str) -> Option<u64> {
+    let (_, params) = url.split_once(['?', '#'])?;
+    params
+        .split(['&', '?', '#'])
+        .filter_map(|param| param.split_once('='))
+        .find(|(key, _)| *key == "t" || *key == "start")
+        .and_then(|(_, value)| parse_youtube_timestamp(value))
+}
+
+fn parse_youtube_timestamp(value: &str) -> Option<u64> {
+    let mut total = 0u64;
+    let mut digits = String::new();
+    for c in value.chars() {
+        if c.is_ascii_digit() {
+            digits.push(c);
+            continue;
+        }
+        let unit = match c {
+            'h' => 3600,
+            'm' => 60,
+            's' => 1,
+            _ => return None,
+        };
+        total += digits.parse::<u64>().ok()? * unit;
+        digits.clear();
+    }
+    // Bare trailing digits are seconds
+    if !digits.is_empty() {
+        total += digits.parse::<u64>().ok()?;
+    }
+    (total > 0).then_some(total)
+}
+
+/// Link to a YouTube video that starts `seconds` in
+pub fn youtube_link(video_id: &str, seconds: u64) -> String {
+    if seconds == 0 {
+        format!("https://youtu.be/{}", video_id)
+    } else {
+        format!("https://youtu.be/{}?t={}", video_id, seconds)
+    }
+}
+
+/// Extract Vimeo video ID from vimeo.com and player.vimeo.com URLs
+pub fn extract_vimeo_id(url: &str) -> Option<String> {
+    let without_scheme = url.split("://").nth(1).unwrap_or(url);
+    let (host, path) = without_scheme.split_once('/')?;
+    if host != "vimeo.com" && !host.ends_with(".vimeo.com") {
+        return None;
+    }
+
+    // vimeo.com/ID, player.vimeo.com/video/ID, vimeo.com/channels/NAME/ID, ...
+    // The ID is the first all-digit path segment
+    path.split(['?', '#'])
+        .next()?
+        .split('/')
+        .find(|segment| !segment.is_empty() && segment.chars().all(|c| c.is_ascii_digit()))
+        .map(|segment| segment.to_string())
+}
+
+/// Extract a canonical SoundCloud URL (`https://soundcloud.com/artist/track`)
+/// from soundcloud.com links. Profile pages and other single-segment paths
+/// are left as plain links.
+pub fn extract_soundcloud_url(url: &str) -> Option<String> {
+    let without_scheme = url.split("://").nth(1).unwrap_or(url);
+    let (host, path) = without_scheme.split_once('/')?;
+    if !matches!(host, "soundcloud.com" | "www.soundcloud.com" | "m.soundcloud.com") {
+        return None;
+    }
+
+    let segments: Vec<&str> = path
+        .split(['?', '#'])
+        .next()?
+        .split('/')
+        .filter(|segment| !segment.is_empty())
+        .collect();
+    if segments.len() < 2 {
+        return None;
+    }
+    Some(format!("https://soundcloud.com/{}", segments.join("/")))
+}
+
+/// Spotify content that can be embedded
+const SPOTIFY_EMBED_KINDS: &[&str] = &["track", "album", "playlist", "artist", "episode", "show"];
+
+/// Extract a Spotify embed path (`kind/ID`) from open.spotify.com links and
+/// `spotify:kind:ID` URIs
+pub fn extract_spotify_embed(url: &str) -> Option<String> {
+    let (kind, id) = if let Some(uri) = url.strip_prefix("spotify:") {
+        uri.split_once(':')?
+    } else {
+        let without_scheme = url.split("://").nth(1).unwrap_or(url);
+        let (host, path) = without_scheme.split_once('/')?;
+        if host != "open.spotify.com" {
+            return None;
+        }
+        // open.spotify.com/track/ID, /intl-de/track/ID, /embed/track/ID
+        let mut segments = path
+            .split(['?', '#'])
+            .next()?
+            .split('/')
+            .filter(|segment| {
+                !segment.is_empty() && *segment != "embed" && !segment.starts_with("intl-")
+            });
+        (segments.next()?, segments.next()?)
+    };
+
+    if !SPOTIFY_EMBED_KINDS.contains(&kind)
+        || id.is_empty()
+        || !id.chars().all(|c| c.is_ascii_alphanumeric())
+    {
+        return None;
+    }
+    Some(format!("{}/{}", kind, id))
+}
+
+/// Canvas size for an image of the given pixel dimensions, scaled down so
+/// the longer side is at most `MAX_IMAGE_DIMENSION`
+pub fn fit_image_size(width: u32, height: u32) -> (f32, f32) {
+    let (width, height) = (width.max(1) as f32, height.max(1) as f32);
+    let scale = (MAX_IMAGE_DIMENSION / width.max(height)).min(1.0);
+    (width * scale, height * scale)
+}
+
+/// Canvas size for an image file, read from its header.
+/// Slow for large or remote files, so call it off the UI thread.
+pub fn image_item_size(path: &Path) -> Option<(f32, f32)> {
+    let (width, height) = image::image_dimensions(path).ok()?;
+    Some(fit_image_size(width, height))
+}
+
 impl ItemContent {
     pub fn default_size(&self) -> (f32, f32) {
         match self {
-            ItemContent::Image(path) => {
-                // Try to load the image and get its actual dimensions, scaled to max 800px
-                if let Ok(img) = image::open(path) {
-                    let (width, height) = img.dimensions();
-                    let max_dimension = 800.0;
-
-                    let aspect_ratio = width as f32 / height as f32;
-
-                    if width > height {
-                        if width as f32 > max_dimension {
-                            (max_dimension, max_dimension / aspect_ratio)
-                        } else {
-                            (width as f32, height as f32)
-                        }
-                    } else {
-                        if height as f32 > max_dimension {
-                            (max_dimension * aspect_ratio, max_dimension)
-                        } else {
-                            (width as f32, height as f32)
-                        }
-                    }
-                } else {
-                    (800.0, 600.0)
-                }
-            }
+            // Real dimensions are read in the background, see `image_item_size`
+            ItemContent::Image(_) => DEFAULT_IMAGE_SIZE,
             ItemContent::Text(_) => (300.0, 100.0),
             ItemContent::Video(_) => (400.0, 300.0),
             ItemContent::Audio(_) => (320.0, 160.0), // Compact audio player
             ItemContent::Pdf { .. } => (180.0, 240.0),
             ItemContent::Link(_) => (300.0, 150.0),
+            ItemContent::Bookmark { .. } => (320.0, 140.0),
             ItemContent::YouTube(_) => (560.0, 315.0), // 16:9 aspect ratio
+            ItemContent::Vimeo(_) => (560.0, 315.0),
+            // Heights of the providers' compact single-track and list players
+            ItemContent::SoundCloud(url) if url.contains("/sets/") => (400.0, 450.0),
+            ItemContent::SoundCloud(_) => (400.0, 166.0),
+            ItemContent::Spotify(path) => match path.split('/').next() {
+                Some("track" | "episode") => (400.0, 152.0),
+                _ => (400.0, 352.0),
+            },
             ItemContent::Markdown { .. } => (200.0, 36.0), // Simple filename button
+            // Cards of a line range open expanded, showing the lines
+            ItemContent::Code { lines: Some(_), .. } => (480.0, 320.0),
             ItemContent::Code { .. } => (200.0, 36.0), // Simple filename button like markdown
             ItemContent::TextBox { .. } => (200.0, 100.0), // Default text box size
             ItemContent::Arrow { end_offset, .. } => {
@@ -744,6 +1072,42 @@ impl ItemContent {
         }
     }
 
+    /// Every file path this content points at
+    pub fn file_paths(&self) -> Vec<&PathBuf> {
+        match self {
+            ItemContent::Image(path) | ItemContent::Video(path) | ItemContent::Audio(path) => {
+                vec![path]
+            }
+            ItemContent::Pdf {
+                path, thumbnail, ..
+            } => {
+                let mut paths = vec![path];
+                paths.extend(thumbnail.as_ref());
+                paths
+            }
+            ItemContent::Markdown { path, .. } | ItemContent::Code { path, .. } => vec![path],
+            _ => Vec::new(),
+        }
+    }
+
+    /// Mutable references to every file path this content points at
+    pub fn file_paths_mut(&mut self) -> Vec<&mut PathBuf> {
+        match self {
+            ItemContent::Image(path) | ItemContent::Video(path) | ItemContent::Audio(path) => {
+                vec![path]
+            }
+            ItemContent::Pdf {
+                path, thumbnail, ..
+            } => {
+                let mut paths = vec![path];
+                paths.extend(thumbnail.as_mut());
+                paths
+            }
+            ItemContent::Markdown { path, .. } | ItemContent::Code { path, .. } => vec![path],
+            _ => Vec::new(),
+        }
+    }
+
     pub fn display_name(&self) -> String {
         match self {
             ItemContent::Image(path) | ItemContent::Video(path) | ItemContent::Audio(path) => path
@@ -758,7 +1122,14 @@ impl ItemContent {
                 .to_string(),
             ItemContent::Text(text) => text.clone(),
             ItemContent::Link(url) => url.clone(),
+            ItemContent::Bookmark { title, .. } => title.clone(),
             ItemContent::YouTube(id) => format!("YouTube: {}", id),
+            ItemContent::Vimeo(id) => format!("Vimeo: {}", id),
+            ItemContent::SoundCloud(url) => format!(
+                "SoundCloud: {}",
+                url.trim_start_matches("https://soundcloud.com/")
+            ),
+            ItemContent::Spotify(path) => format!("Spotify: {}", path),
             ItemContent::Markdown { title, .. } => title.clone(),
             ItemContent::Code { path, .. } => path
                 .file_name()
@@ -777,6 +1148,31 @@ impl ItemContent {
         }
     }
 
+    /// Display name, with audio labeled by the title and artist from its
+    /// tags once `audio_metadata` has read them
+    pub fn display_name_with(&self, audio_metadata: &AudioMetadataCache) -> String {
+        match self {
+            ItemContent::Audio(path) => audio_metadata
+                .cached(path)
+                .and_then(|metadata| metadata.label())
+                .unwrap_or_else(|| self.display_name()),
+            _ => self.display_name(),
+        }
+    }
+
+    /// Returns true if this item's player has a volume that can be set.
+    /// Spotify's embed doesn't allow it.
+    pub fn has_volume(&self) -> bool {
+        matches!(
+            self,
+            ItemContent::Audio(_)
+                | ItemContent::Video(_)
+                | ItemContent::YouTube(_)
+                | ItemContent::Vimeo(_)
+                | ItemContent::SoundCloud(_)
+        )
+    }
+
     /// Returns true if this item should appear in search results
     pub fn is_searchable(&self) -> bool {
         !matches!(
@@ -797,12 +1193,16 @@ impl ItemContent {
             ItemContent::Pdf { .. } => "PDF",
             ItemContent::Text(_) => "TEXT",
             ItemContent::Link(_) => "LINK",
+            ItemContent::Bookmark { .. } => "BOOKMARK",
             ItemContent::YouTube(_) => "YOUTUBE",
+            ItemContent::Vimeo(_) => "VIMEO",
+            ItemContent::SoundCloud(_) => "SOUNDCLOUD",
+            ItemContent::Spotify(_) => "SPOTIFY",
             ItemContent::Markdown { .. } => "MARKDOWN",
             ItemContent::Code { language, .. } => match language.as_str() {
                 "rust" => "RUST",
                 "python" => "PYTHON",
-                "javascript" | "typescript" => "JS/TS",
+                "javascript" | "typescript" | "tsx" => "JS/TS",
                 "go" => "GO",
                 "c" | "cpp" => "C/C++",
                 "java" => "JAVA",
@@ -848,6 +1248,7 @@ impl ItemContent {
                     ItemContent::Pdf {
                         path: path.clone(),
                         thumbnail,
+                        page: 0,
                     }
                 }
                 "md" => {
@@ -871,6 +1272,7 @@ impl ItemContent {
                     ItemContent::Code {
                         path: path.clone(),
                         language,
+                        lines: None,
                     }
                 }
                 "txt" => {
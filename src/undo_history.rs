@@ -0,0 +1,135 @@
+//! Undo history labels - what each step of a board's undo history did.
+//!
+//! Every change pushed to a board's history gets a short label, like
+//! "Moved 3 items" or "Edited cell B4", and the time it was made. Changes
+//! recorded as operations are labelled from the operation itself; changes
+//! recorded as whole-board snapshots are labelled by the code making them.
+//! The History panel lists the steps and jumps to any of them by undoing
+//! or redoing up to it.
+
+use crate::board::UndoOperation;
+use crate::types::{CanvasItem, ItemContent};
+use std::time::{Duration, SystemTime};
+
+/// Label of snapshot changes whose code doesn't name them
+pub const DEFAULT_STEP_LABEL: &str = "Edited board";
+
+/// Longest item name put in a label, in characters
+const MAX_NAME_CHARS: usize = 32;
+
+/// A step of the undo history, as listed in the History panel
+#[derive(Debug, Clone, PartialEq)]
+pub struct UndoStep {
+    pub label: String,
+    /// When the change was made
+    pub at: SystemTime,
+    /// History position right after this step; jumping there undoes every
+    /// later step and redoes every earlier one
+    pub position: usize,
+}
+
+/// How an item is named in labels
+pub fn item_name(item: &CanvasItem) -> String {
+    let name = match &item.content {
+        ItemContent::TextBox { .. } => return "text box".to_string(),
+        ItemContent::Arrow { .. } => return "arrow".to_string(),
+        ItemContent::Table { .. } => return "table".to_string(),
+        ItemContent::Shape { .. } | ItemContent::Chart { .. } => {
+            return item.content.display_name().to_lowercase();
+        }
+        _ => item.content.display_name(),
+    };
+    let name = name.lines().next().unwrap_or_default().trim();
+    if name.chars().count() > MAX_NAME_CHARS {
+        let short: String = name.chars().take(MAX_NAME_CHARS - 1).collect();
+        format!("{}…", short.trim_end())
+    } else {
+        name.to_string()
+    }
+}
+
+/// Label of a change to `count` items, like "Deleted 3 items"
+pub fn items_label(verb: &str, count: usize) -> String {
+    if count == 1 {
+        format!("{} 1 item", verb)
+    } else {
+        format!("{} {} items", verb, count)
+    }
+}
+
+/// Spreadsheet name of a table cell, counted from 0: column letters, then
+/// the row number ("B4" for row 3, column 1)
+pub fn cell_name(row: usize, col: usize) -> String {
+    let mut letters = Vec::new();
+    let mut n = col + 1;
+    while n > 0 {
+        let rem = (n - 1) % 26;
+        letters.push((b'A' + rem as u8) as char);
+        n = (n - 1) / 26;
+    }
+    letters.iter().rev().collect::<String>() + &(row + 1).to_string()
+}
+
+/// Verb of an operation, with the item it applies to
+fn describe(op: &UndoOperation) -> Option<(&'static str, Option<u64>, Option<&CanvasItem>)> {
+    match op {
+        UndoOperation::AddItem(item) => Some(("Added", None, Some(item))),
+        UndoOperation::RemoveItem(item) => Some(("Deleted", None, Some(item))),
+        UndoOperation::MoveItem { id, .. } => Some(("Moved", Some(*id), None)),
+        UndoOperation::ResizeItem { id, .. } | UndoOperation::TransformItem { id, .. } => {
+            Some(("Resized", Some(*id), None))
+        }
+        UndoOperation::ModifyItem { old_item, new_item } => {
+            let verb = match (old_item.locked, new_item.locked) {
+                (false, true) => "Locked",
+                (true, false) => "Unlocked",
+                _ => "Edited",
+            };
+            Some((verb, None, Some(new_item)))
+        }
+        UndoOperation::Batch(_) => None,
+    }
+}
+
+/// Label of an operation. `name_of` names the items operations only know
+/// by ID.
+pub fn operation_label(op: &UndoOperation, name_of: impl Fn(u64) -> Option<String>) -> String {
+    let single = |op: &UndoOperation| {
+        let (verb, id, item) = describe(op)?;
+        let name = match item {
+            Some(item) => item_name(item),
+            None => id.and_then(&name_of).unwrap_or_else(|| "item".to_string()),
+        };
+        Some(format!("{} {}", verb, name))
+    };
+
+    let UndoOperation::Batch(ops) = op else {
+        return single(op).unwrap_or_else(|| DEFAULT_STEP_LABEL.to_string());
+    };
+    match ops.as_slice() {
+        [] => DEFAULT_STEP_LABEL.to_string(),
+        [op] => operation_label(op, name_of),
+        [first, rest @ ..] => {
+            let verb = describe(first).map(|(verb, _, _)| verb);
+            let same = rest
+                .iter()
+                .all(|op| describe(op).map(|(verb, _, _)| verb) == verb);
+            match verb {
+                Some(verb) if same => format!("{} {} items", verb, ops.len()),
+                _ => format!("Changed {} items", ops.len()),
+            }
+        }
+    }
+}
+
+/// When a step was made, as shown in the History panel
+pub fn format_step_time(at: SystemTime, now: SystemTime) -> String {
+    let secs = now.duration_since(at).unwrap_or(Duration::ZERO).as_secs();
+    if secs < 60 {
+        "Just now".to_string()
+    } else if secs < 3600 {
+        format!("{} min ago", secs / 60)
+    } else {
+        format!("{} hours ago", secs / 3600)
+    }
+}
@@ -0,0 +1,337 @@
+//! Smart URL paste - what a pasted URL becomes on the board.
+//!
+//! Links to YouTube, Vimeo, SoundCloud and Spotify become their embedded
+//! players, links to image files are downloaded into the board as images,
+//! and any other page becomes a bookmark card showing the page's title and
+//! description. The detected kind can be overridden right after pasting.
+//!
+//! Pages and images are fetched off the UI thread with [`fetch_page_details`]
+//! and [`download_image`].
+
+use crate::types::{
+    ItemContent, extract_soundcloud_url, extract_spotify_embed, extract_vimeo_id,
+    extract_youtube_id,
+};
+use std::io::Read;
+use std::time::Duration;
+
+/// How long to wait for a page or image before giving up
+const FETCH_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Most of a page read when looking for its title and description. They
+/// are in the head, near the start.
+const MAX_PAGE_BYTES: u64 = 512 * 1024;
+
+/// Largest image downloaded for a pasted image URL
+const MAX_IMAGE_BYTES: u64 = 25 * 1024 * 1024;
+
+/// Longest description kept on a bookmark card, in characters
+const MAX_DESCRIPTION_CHARS: usize = 300;
+
+/// File extensions of image URLs
+const IMAGE_EXTENSIONS: [&str; 8] = ["png", "jpg", "jpeg", "gif", "webp", "bmp", "svg", "avif"];
+
+/// What a pasted URL can become
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum UrlPasteKind {
+    /// An embedded player (YouTube, Vimeo, SoundCloud, Spotify)
+    Embed,
+    /// The image at the URL, downloaded into the board
+    Image,
+    /// A card with the page's title and description
+    Bookmark,
+    /// A card with just the URL
+    Link,
+}
+
+impl UrlPasteKind {
+    /// Name shown in the chooser
+    pub fn label(self) -> &'static str {
+        match self {
+            UrlPasteKind::Embed => "Embed",
+            UrlPasteKind::Image => "Image",
+            UrlPasteKind::Bookmark => "Bookmark",
+            UrlPasteKind::Link => "Plain link",
+        }
+    }
+}
+
+/// The embedded player for a URL, if its site has one
+pub fn embed_content(url: &str) -> Option<ItemContent> {
+    if let Some(video_id) = extract_youtube_id(url) {
+        Some(ItemContent::YouTube(video_id))
+    } else if let Some(video_id) = extract_vimeo_id(url) {
+        Some(ItemContent::Vimeo(video_id))
+    } else if let Some(track_url) = extract_soundcloud_url(url) {
+        Some(ItemContent::SoundCloud(track_url))
+    } else {
+        extract_spotify_embed(url).map(ItemContent::Spotify)
+    }
+}
+
+/// Whether a URL points at an image file, going by its extension
+pub fn is_image_url(url: &str) -> bool {
+    let path = url.split(['?', '#']).next().unwrap_or(url);
+    let Some((_, extension)) = path.rsplit_once('.') else {
+        return false;
+    };
+    // "example.png" alone is a host, not a file
+    path.trim_start_matches("https://")
+        .trim_start_matches("http://")
+        .contains('/')
+        && IMAGE_EXTENSIONS.contains(&extension.to_lowercase().as_str())
+}
+
+/// What a pasted URL becomes unless the user picks otherwise
+pub fn detect(url: &str) -> UrlPasteKind {
+    if embed_content(url).is_some() {
+        UrlPasteKind::Embed
+    } else if is_image_url(url) {
+        UrlPasteKind::Image
+    } else {
+        UrlPasteKind::Bookmark
+    }
+}
+
+/// Everything a pasted URL can become, the detected kind first
+pub fn choices(url: &str) -> Vec<UrlPasteKind> {
+    let detected = detect(url);
+    let mut kinds = vec![detected];
+    if detected != UrlPasteKind::Embed && embed_content(url).is_some() {
+        kinds.push(UrlPasteKind::Embed);
+    }
+    if detected != UrlPasteKind::Image {
+        kinds.push(UrlPasteKind::Image);
+    }
+    if detected != UrlPasteKind::Bookmark {
+        kinds.push(UrlPasteKind::Bookmark);
+    }
+    kinds.push(UrlPasteKind::Link);
+    kinds
+}
+
+/// Host of a URL without "www.", like "example.com"
+pub fn url_host(url: &str) -> String {
+    let rest = url.split_once("://").map_or(url, |(_, rest)| rest);
+    let host = rest.split(['/', '?', '#']).next().unwrap_or(rest);
+    let host = host.rsplit_once('@').map_or(host, |(_, host)| host);
+    host.trim_start_matches("www.").to_lowercase()
+}
+
+/// A bookmark card for a URL, before its page has been fetched
+pub fn bare_bookmark(url: &str) -> ItemContent {
+    let host = url_host(url);
+    ItemContent::Bookmark {
+        url: url.to_string(),
+        title: host.clone(),
+        description: String::new(),
+        site: host,
+    }
+}
+
+/// Title, description and site name of a web page
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct PageDetails {
+    pub title: Option<String>,
+    pub description: Option<String>,
+    pub site_name: Option<String>,
+}
+
+impl PageDetails {
+    /// The bookmark card for the page at `url` with these details
+    pub fn bookmark(&self, url: &str) -> ItemContent {
+        let host = url_host(url);
+        ItemContent::Bookmark {
+            url: url.to_string(),
+            title: self.title.clone().unwrap_or_else(|| host.clone()),
+            description: self.description.clone().unwrap_or_default(),
+            site: self.site_name.clone().unwrap_or(host),
+        }
+    }
+}
+
+/// Read a page's title, description and site name from its HTML, preferring
+/// Open Graph tags over the plain ones
+pub fn parse_page_details(html: &str) -> PageDetails {
+    let mut og_title = None;
+    let mut description = None;
+    let mut og_description = None;
+    let mut site_name = None;
+
+    for tag in tags(html, "meta") {
+        let key = attribute(tag, "property").or_else(|| attribute(tag, "name"));
+        let (Some(key), Some(content)) = (key, attribute(tag, "content")) else {
+            continue;
+        };
+        let content = clean_text(&content);
+        if content.is_empty() {
+            continue;
+        }
+        let slot = match key.to_lowercase().as_str() {
+            "og:title" | "twitter:title" => &mut og_title,
+            "og:description" | "twitter:description" => &mut og_description,
+            "description" => &mut description,
+            "og:site_name" => &mut site_name,
+            _ => continue,
+        };
+        slot.get_or_insert(content);
+    }
+
+    let title = og_title.or_else(|| title_element(html));
+    let description = og_description.or(description).map(|description| {
+        if description.chars().count() > MAX_DESCRIPTION_CHARS {
+            let cut: String = description
+                .chars()
+                .take(MAX_DESCRIPTION_CHARS - 1)
+                .collect();
+            format!("{}…", cut.trim_end())
+        } else {
+            description
+        }
+    });
+    PageDetails {
+        title,
+        description,
+        site_name,
+    }
+}
+
+/// Fetch the page at `url` and read its details (blocking)
+pub fn fetch_page_details(url: &str) -> Result<PageDetails, String> {
+    let bytes = fetch(url, MAX_PAGE_BYTES)?;
+    Ok(parse_page_details(&String::from_utf8_lossy(&bytes)))
+}
+
+/// Download the image at `url` (blocking)
+pub fn download_image(url: &str) -> Result<Vec<u8>, String> {
+    let bytes = fetch(url, MAX_IMAGE_BYTES + 1)?;
+    if bytes.len() as u64 > MAX_IMAGE_BYTES {
+        return Err("the image is too large".to_string());
+    }
+    Ok(bytes)
+}
+
+/// GET `url`, reading at most `limit` bytes of the body
+fn fetch(url: &str, limit: u64) -> Result<Vec<u8>, String> {
+    let response = ureq::get(url)
+        .timeout(FETCH_TIMEOUT)
+        .set(
+            "User-Agent",
+            concat!("Humanboard/", env!("CARGO_PKG_VERSION")),
+        )
+        .call()
+        .map_err(|e| e.to_string())?;
+    let mut bytes = Vec::new();
+    response
+        .into_reader()
+        .take(limit)
+        .read_to_end(&mut bytes)
+        .map_err(|e| e.to_string())?;
+    Ok(bytes)
+}
+
+/// Text inside every `<name ...>` tag of the HTML, case-insensitively
+fn tags<'a>(html: &'a str, name: &str) -> Vec<&'a str> {
+    let lower = html.to_ascii_lowercase();
+    let open = format!("<{}", name);
+    let mut found = Vec::new();
+    let mut from = 0;
+    while let Some(start) = lower[from..].find(&open).map(|i| from + i + open.len()) {
+        let Some(end) = lower[start..].find('>').map(|i| start + i) else {
+            break;
+        };
+        // "<metadata" isn't a meta tag
+        if html[start..].starts_with(|c: char| c.is_ascii_whitespace() || c == '/') {
+            found.push(&html[start..end]);
+        }
+        from = end;
+    }
+    found
+}
+
+/// Value of an attribute inside a tag, quoted or not
+fn attribute(tag: &str, name: &str) -> Option<String> {
+    let lower = tag.to_ascii_lowercase();
+    let mut from = 0;
+    while let Some(at) = lower[from..].find(name).map(|i| from + i) {
+        from = at + name.len();
+        // Part of a longer attribute name, like "data-name"
+        let before = lower[..at].chars().next_back();
+        if before.is_some_and(|c| !c.is_ascii_whitespace()) {
+            continue;
+        }
+        let rest = tag[from..].trim_start();
+        let Some(rest) = rest.strip_prefix('=') else {
+            continue;
+        };
+        let rest = rest.trim_start();
+        let value = match rest.chars().next() {
+            Some(quote @ ('"' | '\'')) => rest[1..].split(quote).next().unwrap_or(""),
+            _ => rest
+                .split(|c: char| c.is_ascii_whitespace() || c == '/')
+                .next()
+                .unwrap_or(""),
+        };
+        return Some(decode_entities(value));
+    }
+    None
+}
+
+/// Text of the page's `<title>` element
+fn title_element(html: &str) -> Option<String> {
+    let lower = html.to_ascii_lowercase();
+    let start = lower.find("<title")?;
+    let start = start + lower[start..].find('>')? + 1;
+    let end = start + lower[start..].find("</title")?;
+    let title = clean_text(&decode_entities(&html[start..end]));
+    (!title.is_empty()).then_some(title)
+}
+
+/// Collapse runs of whitespace into single spaces
+fn clean_text(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Decode the HTML entities common in titles and descriptions
+fn decode_entities(text: &str) -> String {
+    if !text.contains('&') {
+        return text.to_string();
+    }
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(amp) = rest.find('&') {
+        out.push_str(&rest[..amp]);
+        rest = &rest[amp..];
+        let decoded = rest.find(';').filter(|&end| end <= 10).and_then(|end| {
+            let entity = &rest[1..end];
+            let c = match entity {
+                "amp" => Some('&'),
+                "lt" => Some('<'),
+                "gt" => Some('>'),
+                "quot" => Some('"'),
+                "apos" => Some('\''),
+                "nbsp" => Some(' '),
+                _ => match entity.strip_prefix('#') {
+                    Some(hex) if hex.starts_with(['x', 'X']) => u32::from_str_radix(&hex[1..], 16)
+                        .ok()
+                        .and_then(char::from_u32),
+                    Some(decimal) => decimal.parse().ok().and_then(char::from_u32),
+                    None => None,
+                },
+            };
+            c.map(|c| (c, end))
+        });
+        match decoded {
+            Some((c, end)) => {
+                out.push(c);
+                rest = &rest[end + 1..];
+            }
+            None => {
+                out.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
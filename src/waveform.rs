@@ -0,0 +1,273 @@
+//! Audio waveforms for audio cards.
+//!
+//! An audio file is decoded once on the background executor and reduced to
+//! a short list of peaks (`WAVEFORM_BUCKETS` values between 0 and 1, with
+//! the loudest part of the file at 1). The card draws the peaks as bars
+//! and a playhead at the player's position, so clips can be told apart and
+//! scrubbed without opening the full player.
+//!
+//! Decoding goes through symphonia; files it can't decode simply get no
+//! waveform and the card falls back to the player alone.
+
+use crate::background::BackgroundExecutor;
+use crate::error::MediaError;
+use parking_lot::Mutex;
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use symphonia::core::audio::SampleBuffer;
+use symphonia::core::codecs::{CODEC_TYPE_NULL, DecoderOptions};
+use symphonia::core::errors::Error as DecodeError;
+use symphonia::core::formats::FormatOptions;
+use symphonia::core::io::MediaSourceStream;
+use symphonia::core::meta::MetadataOptions;
+use symphonia::core::probe::Hint;
+use tracing::{debug, warn};
+
+/// Number of peaks kept per waveform
+pub const WAVEFORM_BUCKETS: usize = 256;
+
+/// Frames folded into one peak while decoding
+const BLOCK_FRAMES: usize = 1024;
+
+/// Peaks of a decoded audio file
+#[derive(Debug, Clone, PartialEq)]
+pub struct Waveform {
+    /// Loudness from start to end, normalized so the loudest peak is 1
+    pub peaks: Vec<f32>,
+    /// Length of the audio in seconds
+    pub duration_secs: f64,
+}
+
+impl Waveform {
+    /// Peaks resampled to `bars` values for drawing. Each bar takes the
+    /// loudest peak it covers, so short transients stay visible.
+    pub fn bars(&self, bars: usize) -> Vec<f32> {
+        resample_peaks(&self.peaks, bars)
+    }
+}
+
+/// Folds decoded samples into per-block peaks
+#[derive(Debug, Clone)]
+pub struct PeakAccumulator {
+    block_frames: usize,
+    blocks: Vec<f32>,
+    current: f32,
+    current_frames: usize,
+    frames: u64,
+}
+
+impl PeakAccumulator {
+    pub fn new(block_frames: usize) -> Self {
+        Self {
+            block_frames: block_frames.max(1),
+            blocks: Vec::new(),
+            current: 0.0,
+            current_frames: 0,
+            frames: 0,
+        }
+    }
+
+    /// Add interleaved samples with `channels` samples per frame
+    pub fn push_interleaved(&mut self, samples: &[f32], channels: usize) {
+        for frame in samples.chunks(channels.max(1)) {
+            let peak = frame.iter().fold(0.0f32, |peak, s| peak.max(s.abs()));
+            self.current = self.current.max(peak);
+            self.current_frames += 1;
+            self.frames += 1;
+            if self.current_frames == self.block_frames {
+                self.blocks.push(self.current);
+                self.current = 0.0;
+                self.current_frames = 0;
+            }
+        }
+    }
+
+    /// Frames seen so far
+    pub fn frames(&self) -> u64 {
+        self.frames
+    }
+
+    /// Normalized peaks in at most `buckets` values
+    pub fn finish(mut self, buckets: usize) -> Vec<f32> {
+        if self.current_frames > 0 {
+            self.blocks.push(self.current);
+        }
+        bucket_peaks(&self.blocks, buckets)
+    }
+}
+
+/// Reduce block peaks to at most `buckets` values and scale them so the
+/// loudest is 1. Silence stays at 0.
+pub fn bucket_peaks(block_peaks: &[f32], buckets: usize) -> Vec<f32> {
+    let mut peaks = resample_peaks(block_peaks, buckets.min(block_peaks.len()));
+    let loudest = peaks.iter().copied().fold(0.0f32, f32::max);
+    if loudest > 0.0 {
+        for peak in &mut peaks {
+            *peak /= loudest;
+        }
+    }
+    peaks
+}
+
+/// Resample peaks to exactly `count` values, taking the loudest peak each
+/// value covers when shrinking and repeating peaks when stretching
+pub fn resample_peaks(peaks: &[f32], count: usize) -> Vec<f32> {
+    if peaks.is_empty() || count == 0 {
+        return Vec::new();
+    }
+    let len = peaks.len();
+    (0..count)
+        .map(|i| {
+            let start = (i * len / count).min(len - 1);
+            let end = ((i + 1) * len / count).clamp(start + 1, len);
+            peaks[start..end].iter().copied().fold(0.0f32, f32::max)
+        })
+        .collect()
+}
+
+/// Decode an audio file into a waveform of at most `buckets` peaks
+pub fn decode_waveform(path: &Path, buckets: usize) -> Result<Waveform, MediaError> {
+    let file = File::open(path).map_err(|source| MediaError::ReadFailed {
+        path: path.to_path_buf(),
+        source,
+    })?;
+    let stream = MediaSourceStream::new(Box::new(file), Default::default());
+
+    let mut hint = Hint::new();
+    if let Some(extension) = path.extension().and_then(|e| e.to_str()) {
+        hint.with_extension(extension);
+    }
+    let probed = symphonia::default::get_probe()
+        .format(
+            &hint,
+            stream,
+            &FormatOptions::default(),
+            &MetadataOptions::default(),
+        )
+        .map_err(|e| MediaError::AudioDecode(e.to_string()))?;
+    let mut format = probed.format;
+
+    let track = format
+        .tracks()
+        .iter()
+        .find(|track| track.codec_params.codec != CODEC_TYPE_NULL)
+        .ok_or_else(|| MediaError::AudioDecode("no audio track".to_string()))?;
+    let track_id = track.id;
+    let sample_rate = track.codec_params.sample_rate;
+    let mut decoder = symphonia::default::get_codecs()
+        .make(&track.codec_params, &DecoderOptions::default())
+        .map_err(|e| MediaError::AudioDecode(e.to_string()))?;
+
+    let mut accumulator = PeakAccumulator::new(BLOCK_FRAMES);
+    let mut rate = sample_rate.unwrap_or(0);
+    loop {
+        let packet = match format.next_packet() {
+            Ok(packet) => packet,
+            Err(DecodeError::IoError(e)) if e.kind() == ErrorKind::UnexpectedEof => break,
+            Err(DecodeError::ResetRequired) => break,
+            Err(e) => return Err(MediaError::AudioDecode(e.to_string())),
+        };
+        if packet.track_id() != track_id {
+            continue;
+        }
+
+        let decoded = match decoder.decode(&packet) {
+            Ok(decoded) => decoded,
+            // A corrupt packet only leaves a gap in the waveform
+            Err(DecodeError::DecodeError(e)) => {
+                debug!("Skipping undecodable audio packet in {:?}: {}", path, e);
+                continue;
+            }
+            Err(e) => return Err(MediaError::AudioDecode(e.to_string())),
+        };
+        let spec = *decoded.spec();
+        rate = spec.rate;
+        let mut samples = SampleBuffer::<f32>::new(decoded.capacity() as u64, spec);
+        samples.copy_interleaved_ref(decoded);
+        accumulator.push_interleaved(samples.samples(), spec.channels.count());
+    }
+
+    let duration_secs = if rate > 0 {
+        accumulator.frames() as f64 / rate as f64
+    } else {
+        0.0
+    };
+    Ok(Waveform {
+        peaks: accumulator.finish(buckets),
+        duration_secs,
+    })
+}
+
+#[derive(Debug, Clone)]
+enum WaveformState {
+    Pending,
+    Ready(Arc<Waveform>),
+    Failed,
+}
+
+/// Waveforms of every audio file shown on the canvas, decoded on the
+/// background executor the first time each file is asked for
+#[derive(Clone, Default)]
+pub struct WaveformCache {
+    entries: Arc<Mutex<HashMap<PathBuf, WaveformState>>>,
+}
+
+impl WaveformCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The waveform for an audio file, or `None` while it is being decoded
+    /// or if it couldn't be
+    pub fn get(&self, path: &Path, background: &BackgroundExecutor) -> Option<Arc<Waveform>> {
+        let mut entries = self.entries.lock();
+        match entries.get(path) {
+            Some(WaveformState::Ready(waveform)) => Some(waveform.clone()),
+            Some(WaveformState::Pending | WaveformState::Failed) => None,
+            None => {
+                entries.insert(path.to_path_buf(), WaveformState::Pending);
+                drop(entries);
+                self.decode(path.to_path_buf(), background);
+                None
+            }
+        }
+    }
+
+    /// Whether any waveform is still being decoded
+    pub fn has_pending(&self) -> bool {
+        self.entries
+            .lock()
+            .values()
+            .any(|state| matches!(state, WaveformState::Pending))
+    }
+
+    /// Drop every waveform, e.g. when switching boards
+    pub fn clear(&self) {
+        self.entries
+            .lock()
+            .retain(|_, state| matches!(state, WaveformState::Pending));
+    }
+
+    fn decode(&self, path: PathBuf, background: &BackgroundExecutor) {
+        let entries = self.entries.clone();
+        let source = path.clone();
+
+        background.spawn(
+            "audio_waveform",
+            move || decode_waveform(&source, WAVEFORM_BUCKETS).map_err(|e| e.to_string()),
+            move |result| {
+                let state = match result {
+                    Ok(waveform) => WaveformState::Ready(Arc::new(waveform)),
+                    Err(e) => {
+                        warn!("Failed to decode waveform for {:?}: {}", path, e);
+                        WaveformState::Failed
+                    }
+                };
+                entries.lock().insert(path, state);
+            },
+        );
+    }
+}
@@ -1,85 +1,63 @@
 //! WebView-based audio player with metadata display.
 //!
-//! This module provides an audio player implemented as a WebView with a local
-//! HTTP server that supports range requests for seeking. It extracts and displays
-//! metadata (title, artist, album art) from audio files.
+//! This module provides an audio player implemented as a WebView over the
+//! local media server, which supports range requests for seeking. It shows
+//! the file's tags (title, artist, album art) as read in the background by
+//! [`crate::audio_metadata`], with the album art served next to the file.
 //!
 //! ## Architecture
 //!
-//! Each audio player spawns a local HTTP server on a unique port that serves:
+//! Each audio player registers a route on the shared media server that serves:
 //! - HTML/CSS/JS for the player UI
 //! - Audio data with HTTP range request support for seeking
+//! - The cached album art, if the file has any
 //!
 //! ## Supported Formats
 //!
 //! MP3, WAV, OGG, M4A, AAC, FLAC
 
-use base64::Engine;
+use super::media_control::{
+    MediaCommand, PlaybackPosition, query_playback_position, seek_script, send_media_command,
+    set_media_volume, start_fragment, volume_script,
+};
+use super::media_server::{self, MediaRoute};
+use crate::audio_metadata::AudioMetadata;
+use crate::types::MediaVolume;
 use gpui::*;
 use gpui_component::webview::WebView;
-use lofty::{Accessor, PictureType, Probe, TaggedFileExt};
-use std::io::{Read, Seek, SeekFrom};
 use std::path::PathBuf;
-use std::sync::Arc;
-use std::sync::atomic::{AtomicBool, AtomicU16, Ordering};
-use std::sync::mpsc;
-use std::thread::{self, JoinHandle};
-use std::time::Duration;
-use tiny_http::{Header, Response, Server, StatusCode};
-use tracing::error;
 use wry::WebViewBuilder;
 
-// Global port counter for unique server ports
-static PORT_COUNTER: AtomicU16 = AtomicU16::new(19900);
-
-/// Helper to create HTTP headers, returning None if the bytes are invalid
-fn create_header(name: &[u8], value: &[u8]) -> Option<Header> {
-    Header::from_bytes(name, value).ok()
-}
-
-/// WebView-based audio player with local HTTP server supporting range requests
+/// WebView-based audio player streamed from the shared media server
 pub struct AudioWebView {
     pub webview_entity: Entity<WebView>,
     pub audio_path: PathBuf,
-    shutdown_flag: Arc<AtomicBool>,
-    server_thread: Option<JoinHandle<()>>,
+    _route: MediaRoute,
 }
 
 impl AudioWebView {
-    pub fn new(audio_path: PathBuf, window: &mut Window, cx: &mut App) -> Result<Self, String> {
-        let port = PORT_COUNTER.fetch_add(1, Ordering::SeqCst);
-        let audio_path_clone = audio_path.clone();
+    /// Create an audio player that starts at `start_seconds` into the file,
+    /// playing at `volume` and labeled with the file's `metadata`
+    pub fn new(
+        audio_path: PathBuf,
+        metadata: Option<&AudioMetadata>,
+        start_seconds: f64,
+        volume: MediaVolume,
+        window: &mut Window,
+        cx: &mut App,
+    ) -> Result<Self, String> {
+        let start = start_fragment(start_seconds);
         let file_name = audio_path
             .file_name()
             .and_then(|n| n.to_str())
             .unwrap_or("Audio")
             .to_string();
-        let shutdown_flag = Arc::new(AtomicBool::new(false));
-        let shutdown_flag_clone = shutdown_flag.clone();
-
-        // Extract metadata from audio file
-        let (title, artist, album_art_base64) = extract_audio_metadata(&audio_path);
-        let display_title = title.unwrap_or_else(|| file_name.clone());
-        let display_artist = artist.unwrap_or_else(|| "Audio File".to_string());
-
-        // Channel for server startup synchronization
-        let (tx, rx) = mpsc::channel();
 
-        let server_thread = thread::spawn(move || {
-            let addr = format!("127.0.0.1:{}", port);
-            let server = match Server::http(&addr) {
-                Ok(s) => {
-                    let _ = tx.send(Ok(()));
-                    s
-                }
-                Err(e) => {
-                    error!("Failed to start audio server on port {}: {}", port, e);
-                    let _ = tx.send(Err(e.to_string()));
-                    return;
-                }
-            };
+        let metadata = metadata.cloned().unwrap_or_default();
+        let display_title = metadata.title.unwrap_or_else(|| file_name.clone());
+        let display_artist = metadata.artist.unwrap_or_else(|| "Audio File".to_string());
 
-            let html = format!(r##"<!DOCTYPE html>
+        let html = format!(r##"<!DOCTYPE html>
 <html>
 <head>
     <meta charset="UTF-8">
@@ -220,7 +198,7 @@ impl AudioWebView {
         </div>
     </div>
     <audio id="audio" preload="metadata">
-        <source src="/audio" type="audio/mpeg">
+        <source src="media{start}" type="audio/mpeg">
     </audio>
     <script>
         const audio = document.getElementById('audio');
@@ -248,20 +226,20 @@ impl AudioWebView {
             cur.textContent = fmt(audio.currentTime);
         }});
 
-        audio.addEventListener('ended', () => {{
-            playIcon.classList.remove('hidden');
-            pauseIcon.classList.add('hidden');
-        }});
+        // Icons follow the element, so play/pause sent from the canvas shows too
+        const showPaused = paused => {{
+            playIcon.classList.toggle('hidden', !paused);
+            pauseIcon.classList.toggle('hidden', paused);
+        }};
+        audio.addEventListener('play', () => showPaused(false));
+        audio.addEventListener('pause', () => showPaused(true));
+        audio.addEventListener('ended', () => showPaused(true));
 
         playBtn.onclick = () => {{
             if (audio.paused) {{
                 audio.play();
-                playIcon.classList.add('hidden');
-                pauseIcon.classList.remove('hidden');
             }} else {{
                 audio.pause();
-                playIcon.classList.remove('hidden');
-                pauseIcon.classList.add('hidden');
             }}
         }};
 
@@ -271,58 +249,21 @@ impl AudioWebView {
             audio.currentTime = pct * audio.duration;
         }};
     </script>
+    <script>{volume}</script>
 </body>
 </html>"##,
-                title = html_escape(&display_title),
-                artist = html_escape(&display_artist),
-                album_art = if let Some(ref art_data) = album_art_base64 {
-                    // Format is "mime_type|base64_data"
-                    let parts: Vec<&str> = art_data.splitn(2, '|').collect();
-                    // Validate MIME type and base64 data to prevent XSS (CWE-79)
-                    if parts.len() == 2 && is_valid_image_mime(parts[0]) && is_valid_base64(parts[1]) {
-                        format!(r#"<img src="data:{};base64,{}" alt="">"#, parts[0], parts[1])
-                    } else {
-                        r#"<svg viewBox="0 0 24 24"><path d="M12 3v10.55c-.59-.34-1.27-.55-2-.55-2.21 0-4 1.79-4 4s1.79 4 4 4 4-1.79 4-4V7h4V3h-6z"/></svg>"#.to_string()
-                    }
-                } else {
-                    r#"<svg viewBox="0 0 24 24"><path d="M12 3v10.55c-.59-.34-1.27-.55-2-.55-2.21 0-4 1.79-4 4s1.79 4 4 4 4-1.79 4-4V7h4V3h-6z"/></svg>"#.to_string()
-                }
-            );
-
-            loop {
-                if shutdown_flag_clone.load(Ordering::Relaxed) {
-                    break;
-                }
-
-                match server.recv_timeout(std::time::Duration::from_millis(100)) {
-                    Ok(Some(request)) => {
-                        let url = request.url();
-                        if url.starts_with("/audio") {
-                            Self::serve_audio_file(&audio_path_clone, request);
-                        } else {
-                            let mut response = Response::from_string(&html);
-                            if let Some(header) =
-                                create_header(&b"Content-Type"[..], &b"text/html"[..])
-                            {
-                                response = response.with_header(header);
-                            }
-                            let _ = request.respond(response);
-                        }
-                    }
-                    Ok(None) => {}
-                    Err(_) => break,
-                }
+            start = start,
+            volume = volume_script(volume),
+            title = html_escape(&display_title),
+            artist = html_escape(&display_artist),
+            album_art = if metadata.art.is_some() {
+                r#"<img src="art" alt="">"#
+            } else {
+                r#"<svg viewBox="0 0 24 24"><path d="M12 3v10.55c-.59-.34-1.27-.55-2-.55-2.21 0-4 1.79-4 4s1.79 4 4 4 4-1.79 4-4V7h4V3h-6z"/></svg>"#
             }
-        });
+        );
 
-        // Wait for server to start with timeout
-        match rx.recv_timeout(Duration::from_secs(5)) {
-            Ok(Ok(())) => {}
-            Ok(Err(e)) => return Err(format!("Failed to start server: {}", e)),
-            Err(_) => return Err("Server startup timeout".to_string()),
-        }
-
-        let url = format!("http://127.0.0.1:{}/", port);
+        let route = media_server::serve_with_art(html, audio_path.clone(), metadata.art)?;
 
         #[cfg(any(
             target_os = "macos",
@@ -331,7 +272,7 @@ impl AudioWebView {
             target_os = "android"
         ))]
         let webview = WebViewBuilder::new()
-            .with_url(&url)
+            .with_url(route.url())
             .with_autoplay(true)
             .build_as_child(window)
             .map_err(|e| format!("Failed to create WebView: {:?}", e))?;
@@ -349,8 +290,7 @@ impl AudioWebView {
         Ok(Self {
             webview_entity,
             audio_path,
-            shutdown_flag,
-            server_thread: Some(server_thread),
+            _route: route,
         })
     }
 
@@ -359,117 +299,34 @@ impl AudioWebView {
         self.webview_entity.update(cx, |wv, _| wv.hide());
     }
 
-    fn serve_audio_file(path: &PathBuf, request: tiny_http::Request) {
-        let mut file = match std::fs::File::open(path) {
-            Ok(f) => f,
-            Err(_) => {
-                let _ = request.respond(Response::empty(StatusCode(404)));
-                return;
-            }
-        };
-
-        let file_size = match file.metadata() {
-            Ok(m) => m.len(),
-            Err(_) => {
-                let _ = request.respond(Response::empty(StatusCode(500)));
-                return;
-            }
-        };
-
-        let mime = match path
-            .extension()
-            .and_then(|e| e.to_str())
-            .map(|e| e.to_lowercase())
-            .as_deref()
-        {
-            Some("mp3") => "audio/mpeg",
-            Some("wav") => "audio/wav",
-            Some("ogg") => "audio/ogg",
-            Some("m4a") => "audio/mp4",
-            Some("aac") => "audio/aac",
-            Some("flac") => "audio/flac",
-            _ => "audio/mpeg",
-        };
-
-        // Check for Range header
-        let range_header = request
-            .headers()
-            .iter()
-            .find(|h| h.field.as_str() == "Range" || h.field.as_str() == "range")
-            .map(|h| h.value.as_str().to_string());
-
-        if let Some(range) = range_header {
-            use crate::webviews::ByteRange;
-            
-            if let Some(byte_range) = ByteRange::parse_header(&range, file_size) {
-                // Seek to start position
-                if file.seek(SeekFrom::Start(byte_range.start)).is_err() {
-                    let _ = request.respond(Response::empty(StatusCode(500)));
-                    return;
-                }
-
-                // Read the requested range with safe buffer size
-                let length = byte_range.length();
-                let mut buffer = vec![0u8; length as usize];
-                if file.read_exact(&mut buffer).is_err() {
-                    let _ = file.seek(SeekFrom::Start(byte_range.start));
-                    buffer.clear();
-                    let _ = file.take(length).read_to_end(&mut buffer);
-                }
-
-                let mut response = Response::from_data(buffer).with_status_code(StatusCode(206));
-                if let Some(h) = create_header(&b"Content-Type"[..], mime.as_bytes()) {
-                    response = response.with_header(h);
-                }
-                if let Some(h) = create_header(
-                    &b"Content-Range"[..],
-                    byte_range.format_content_range().as_bytes(),
-                ) {
-                    response = response.with_header(h);
-                }
-                if let Some(h) = create_header(&b"Accept-Ranges"[..], &b"bytes"[..]) {
-                    response = response.with_header(h);
-                }
-                if let Some(h) =
-                    create_header(&b"Content-Length"[..], length.to_string().as_bytes())
-                {
-                    response = response.with_header(h);
-                }
-
-                let _ = request.respond(response);
-                return;
-            }
-            // If range parsing fails, fall through to serve entire file (HTTP 200)
-        }
-
-        // No range request - serve entire file
-        let mut buffer = Vec::new();
-        if file.read_to_end(&mut buffer).is_err() {
-            let _ = request.respond(Response::empty(StatusCode(500)));
-            return;
-        }
+    /// Set the player's volume
+    pub fn set_volume(&self, volume: MediaVolume, cx: &mut App) -> Result<(), String> {
+        set_media_volume(&self.webview_entity, volume, cx)
+    }
 
-        let mut response = Response::from_data(buffer);
-        if let Some(h) = create_header(&b"Content-Type"[..], mime.as_bytes()) {
-            response = response.with_header(h);
-        }
-        if let Some(h) = create_header(&b"Accept-Ranges"[..], &b"bytes"[..]) {
-            response = response.with_header(h);
-        }
-        if let Some(h) = create_header(&b"Content-Length"[..], file_size.to_string().as_bytes()) {
-            response = response.with_header(h);
-        }
+    /// Play or pause the player
+    pub fn send_command(&self, command: MediaCommand, cx: &mut App) -> Result<(), String> {
+        send_media_command(&self.webview_entity, command, cx)
+    }
 
-        let _ = request.respond(response);
+    /// Ask the player where it is; `on_position` runs off the UI thread with
+    /// the answer, or `None` before the audio has loaded
+    pub fn query_position(
+        &self,
+        on_position: impl Fn(Option<PlaybackPosition>) + Send + 'static,
+        cx: &mut App,
+    ) -> Result<(), String> {
+        query_playback_position(&self.webview_entity, on_position, cx)
     }
-}
 
-impl Drop for AudioWebView {
-    fn drop(&mut self) {
-        self.shutdown_flag.store(true, Ordering::SeqCst);
-        if let Some(handle) = self.server_thread.take() {
-            let _ = handle.join();
-        }
+    /// Move playback to a fraction of the track's length
+    pub fn seek(&self, fraction: f32, cx: &mut App) -> Result<(), String> {
+        let script = seek_script(fraction);
+        self.webview_entity.update(cx, |view, _| {
+            view.raw()
+                .evaluate_script(&script)
+                .map_err(|e| format!("Failed to seek audio: {}", e))
+        })
     }
 }
 
@@ -480,48 +337,3 @@ fn html_escape(s: &str) -> String {
         .replace('"', "&quot;")
         .replace('\'', "&#39;")
 }
-
-/// Validate that a MIME type is a safe image type to prevent XSS (CWE-79)
-fn is_valid_image_mime(mime: &str) -> bool {
-    matches!(
-        mime,
-        "image/jpeg" | "image/png" | "image/gif" | "image/webp" | "image/bmp" | "image/svg+xml"
-    )
-}
-
-/// Validate that a string contains only valid base64 characters to prevent XSS (CWE-79)
-fn is_valid_base64(s: &str) -> bool {
-    s.chars()
-        .all(|c| c.is_ascii_alphanumeric() || c == '+' || c == '/' || c == '=')
-}
-
-/// Extract title, artist, and album art from audio file metadata
-fn extract_audio_metadata(path: &PathBuf) -> (Option<String>, Option<String>, Option<String>) {
-    let tagged_file = match Probe::open(path).and_then(|p| p.read()) {
-        Ok(f) => f,
-        Err(_) => return (None, None, None),
-    };
-
-    let tag = tagged_file.primary_tag().or_else(|| tagged_file.first_tag());
-
-    let Some(tag) = tag else {
-        return (None, None, None);
-    };
-
-    let title = tag.title().map(|s| s.to_string());
-    let artist = tag.artist().map(|s| s.to_string());
-
-    // Try to get album art - prefer front cover, fall back to any picture
-    let album_art = tag
-        .pictures()
-        .iter()
-        .find(|p| p.pic_type() == PictureType::CoverFront)
-        .or_else(|| tag.pictures().first())
-        .map(|p| {
-            let mime = p.mime_type().map(|m| m.as_str()).unwrap_or("image/jpeg");
-            let data = base64::engine::general_purpose::STANDARD.encode(p.data());
-            format!("{}|{}", mime, data)
-        });
-
-    (title, artist, album_art)
-}
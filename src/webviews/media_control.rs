@@ -0,0 +1,331 @@
+//! Play/pause commands for media webviews.
+//!
+//! Commands are sent by evaluating a small script in the player page. Audio
+//! and video pages control their `<audio>`/`<video>` element directly. The
+//! YouTube, Vimeo, SoundCloud and Spotify pages can't reach into their
+//! cross-origin iframe, so they expose a `humanboardMedia` object that
+//! forwards commands through the player's postMessage API. Players without a
+//! toggle command remember the last state they were told about.
+//!
+//! Audio, video and YouTube pages can also be asked for their playback
+//! position, which the canvas uses for the waveform playhead and to resume
+//! players where they were left. Audio pages can be moved to a point in
+//! the file, and video pages can show or hide their subtitles.
+//!
+//! Every player but Spotify's takes a volume. Bridges hold on to the last
+//! volume they were given and apply it again once their player is ready, so
+//! a page can set it as soon as it loads.
+
+use crate::types::MediaVolume;
+use gpui::*;
+use gpui_component::webview::WebView;
+
+/// A playback command for a media item
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MediaCommand {
+    /// Play if paused, pause if playing
+    Toggle,
+    Play,
+    Pause,
+}
+
+impl MediaCommand {
+    fn method(self) -> &'static str {
+        match self {
+            MediaCommand::Toggle => "toggle",
+            MediaCommand::Play => "play",
+            MediaCommand::Pause => "pause",
+        }
+    }
+
+    /// Script that applies the command inside a player page
+    pub fn script(self) -> String {
+        let action = match self {
+            MediaCommand::Toggle => "if (media.paused) { media.play(); } else { media.pause(); }",
+            MediaCommand::Play => "media.play();",
+            MediaCommand::Pause => "media.pause();",
+        };
+        format!(
+            "(function() {{ \
+                if (window.humanboardMedia) {{ window.humanboardMedia.{method}(); return; }} \
+                var media = document.querySelector('video, audio'); \
+                if (!media) {{ return; }} \
+                {action} \
+            }})();",
+            method = self.method(),
+            action = action,
+        )
+    }
+}
+
+/// Fraction of the duration past which media counts as finished, and
+/// starts from the beginning next time
+const FINISHED_FRACTION: f32 = 0.98;
+
+/// Playback position reported by a media page
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PlaybackPosition {
+    /// Position in seconds from the start
+    pub seconds: f64,
+    /// Position as a fraction of the duration, from 0 to 1
+    pub fraction: f32,
+    pub playing: bool,
+}
+
+impl PlaybackPosition {
+    /// Parse the result of `POSITION_SCRIPT`. Webviews return script results
+    /// as JSON, and some platforms wrap that in a JSON string.
+    pub fn parse(result: &str) -> Option<Self> {
+        let value: serde_json::Value = serde_json::from_str(result.trim()).ok()?;
+        let value = match value {
+            serde_json::Value::String(inner) => serde_json::from_str(&inner).ok()?,
+            value => value,
+        };
+        let (seconds, duration, playing): (f64, f64, bool) = serde_json::from_value(value).ok()?;
+        if !seconds.is_finite() || !duration.is_finite() || duration <= 0.0 {
+            return None;
+        }
+        let seconds = seconds.clamp(0.0, duration);
+        Some(Self {
+            seconds,
+            fraction: (seconds / duration) as f32,
+            playing,
+        })
+    }
+
+    /// Where to resume from next time, or `None` to start from the
+    /// beginning because the media was played to the end
+    pub fn resume_seconds(&self) -> Option<f64> {
+        (self.fraction < FINISHED_FRACTION).then_some(self.seconds)
+    }
+}
+
+/// Script returning `[seconds, duration, playing]` for the page's player,
+/// or `null` until its duration is known
+pub(crate) const POSITION_SCRIPT: &str = "(function() { \
+    if (window.humanboardMedia && window.humanboardMedia.position) { \
+        return window.humanboardMedia.position(); \
+    } \
+    var media = document.querySelector('video, audio'); \
+    if (!media || !isFinite(media.duration) || media.duration <= 0) { return null; } \
+    return [media.currentTime, media.duration, !media.paused]; \
+})()";
+
+/// Media fragment that makes an `<audio>` or `<video>` source start at
+/// `start_seconds`, or an empty string to start from the beginning
+pub fn start_fragment(start_seconds: f64) -> String {
+    if start_seconds.is_finite() && start_seconds > 0.0 {
+        format!("#t={:.2}", start_seconds)
+    } else {
+        String::new()
+    }
+}
+
+/// Script that moves the page's media element to a fraction of its duration
+pub fn seek_script(fraction: f32) -> String {
+    format!(
+        "(function() {{ \
+            var media = document.querySelector('video, audio'); \
+            if (!media || !isFinite(media.duration)) {{ return; }} \
+            media.currentTime = {} * media.duration; \
+        }})();",
+        fraction.clamp(0.0, 1.0)
+    )
+}
+
+/// Script that shows or hides the subtitles of the page's video
+pub fn subtitles_script(shown: bool) -> String {
+    format!(
+        "(function() {{ \
+            var video = document.querySelector('video'); \
+            if (!video || !video.textTracks.length) {{ return; }} \
+            video.textTracks[0].mode = '{}'; \
+        }})();",
+        if shown { "showing" } else { "hidden" }
+    )
+}
+
+/// Script that sets the volume of the page's player
+pub fn volume_script(volume: MediaVolume) -> String {
+    let level = volume.level.clamp(0.0, 1.0);
+    format!(
+        "(function() {{ \
+            if (window.humanboardMedia) {{ \
+                if (window.humanboardMedia.volume) {{ window.humanboardMedia.volume({level}, {muted}); }} \
+                return; \
+            }} \
+            var media = document.querySelector('video, audio'); \
+            if (!media) {{ return; }} \
+            media.volume = {level}; \
+            media.muted = {muted}; \
+        }})();",
+        level = level,
+        muted = volume.muted,
+    )
+}
+
+/// Bridge script for the YouTube page, defining `window.humanboardMedia`
+pub(crate) const YOUTUBE_BRIDGE_SCRIPT: &str = r#"
+    (function() {
+        var frame = document.querySelector('iframe');
+        var state = -1;
+        var time = 0;
+        var duration = 0;
+        var volume = null;
+        function send(func, args) {
+            frame.contentWindow.postMessage(JSON.stringify({ event: 'command', func: func, args: args || [] }), '*');
+        }
+        function applyVolume() {
+            if (!volume) { return; }
+            send('setVolume', [Math.round(volume.level * 100)]);
+            send(volume.muted ? 'mute' : 'unMute');
+        }
+        window.addEventListener('message', function(e) {
+            try {
+                var data = typeof e.data === 'string' ? JSON.parse(e.data) : e.data;
+                if (data && data.event === 'onReady') { applyVolume(); }
+                if (data && data.info) {
+                    if (data.info.playerState !== undefined) { state = data.info.playerState; }
+                    if (data.info.currentTime !== undefined) { time = data.info.currentTime; }
+                    if (data.info.duration !== undefined) { duration = data.info.duration; }
+                }
+            } catch (_) {}
+        });
+        frame.addEventListener('load', function() {
+            frame.contentWindow.postMessage(JSON.stringify({ event: 'listening' }), '*');
+        });
+        window.humanboardMedia = {
+            play: function() { send('playVideo'); },
+            pause: function() { send('pauseVideo'); },
+            toggle: function() { send(state === 1 ? 'pauseVideo' : 'playVideo'); },
+            position: function() { return duration > 0 ? [time, duration, state === 1] : null; },
+            volume: function(level, muted) { volume = { level: level, muted: muted }; applyVolume(); }
+        };
+    })();
+"#;
+
+/// Bridge script for the Vimeo page, defining `window.humanboardMedia`
+pub(crate) const VIMEO_BRIDGE_SCRIPT: &str = r#"
+    (function() {
+        var frame = document.querySelector('iframe');
+        var playing = false;
+        var volume = null;
+        function applyVolume() {
+            if (volume) { send('setVolume', volume.muted ? 0 : volume.level); }
+        }
+        function send(method, value) {
+            var message = { method: method };
+            if (value !== undefined) { message.value = value; }
+            frame.contentWindow.postMessage(JSON.stringify(message), 'https://player.vimeo.com');
+        }
+        window.addEventListener('message', function(e) {
+            try {
+                var data = typeof e.data === 'string' ? JSON.parse(e.data) : e.data;
+                if (!data) { return; }
+                if (data.event === 'ready') {
+                    send('addEventListener', 'play');
+                    send('addEventListener', 'pause');
+                    send('addEventListener', 'ended');
+                    applyVolume();
+                } else if (data.event === 'play') {
+                    playing = true;
+                } else if (data.event === 'pause' || data.event === 'ended') {
+                    playing = false;
+                }
+            } catch (_) {}
+        });
+        window.humanboardMedia = {
+            play: function() { send('play'); },
+            pause: function() { send('pause'); },
+            toggle: function() { send(playing ? 'pause' : 'play'); },
+            volume: function(level, muted) { volume = { level: level, muted: muted }; applyVolume(); }
+        };
+    })();
+"#;
+
+/// Bridge script for the SoundCloud widget page, defining `window.humanboardMedia`
+pub(crate) const SOUNDCLOUD_BRIDGE_SCRIPT: &str = r#"
+    (function() {
+        var frame = document.querySelector('iframe');
+        var volume = null;
+        function send(method, value) {
+            var message = { method: method };
+            if (value !== undefined) { message.value = value; }
+            frame.contentWindow.postMessage(JSON.stringify(message), 'https://w.soundcloud.com');
+        }
+        function applyVolume() {
+            if (volume) { send('setVolume', volume.muted ? 0 : Math.round(volume.level * 100)); }
+        }
+        window.addEventListener('message', function(e) {
+            try {
+                var data = typeof e.data === 'string' ? JSON.parse(e.data) : e.data;
+                if (data && data.method === 'ready') { applyVolume(); }
+            } catch (_) {}
+        });
+        window.humanboardMedia = {
+            play: function() { send('play'); },
+            pause: function() { send('pause'); },
+            toggle: function() { send('toggle'); },
+            volume: function(level, muted) { volume = { level: level, muted: muted }; applyVolume(); }
+        };
+    })();
+"#;
+
+/// Bridge script for the Spotify embed page, defining `window.humanboardMedia`
+pub(crate) const SPOTIFY_BRIDGE_SCRIPT: &str = r#"
+    (function() {
+        var frame = document.querySelector('iframe');
+        function send(command) {
+            frame.contentWindow.postMessage({ command: command }, 'https://open.spotify.com');
+        }
+        window.humanboardMedia = {
+            play: function() { send('resume'); },
+            pause: function() { send('pause'); },
+            toggle: function() { send('toggle'); }
+        };
+    })();
+"#;
+
+/// Ask a player webview for its playback position. `on_position` runs once
+/// the page answers, off the UI thread.
+pub(crate) fn query_playback_position(
+    webview: &Entity<WebView>,
+    on_position: impl Fn(Option<PlaybackPosition>) + Send + 'static,
+    cx: &mut App,
+) -> Result<(), String> {
+    webview.update(cx, |view, _| {
+        view.raw()
+            .evaluate_script_with_callback(POSITION_SCRIPT, move |result| {
+                on_position(PlaybackPosition::parse(&result))
+            })
+            .map_err(|e| format!("Failed to query media position: {}", e))
+    })
+}
+
+/// Set the volume of a player webview
+pub(crate) fn set_media_volume(
+    webview: &Entity<WebView>,
+    volume: MediaVolume,
+    cx: &mut App,
+) -> Result<(), String> {
+    let script = volume_script(volume);
+    webview.update(cx, |view, _| {
+        view.raw()
+            .evaluate_script(&script)
+            .map_err(|e| format!("Failed to set media volume: {}", e))
+    })
+}
+
+/// Run a command in a player webview
+pub(crate) fn send_media_command(
+    webview: &Entity<WebView>,
+    command: MediaCommand,
+    cx: &mut App,
+) -> Result<(), String> {
+    let script = command.script();
+    webview.update(cx, |view, _| {
+        view.raw()
+            .evaluate_script(&script)
+            .map_err(|e| format!("Failed to send {:?} to media player: {}", command, e))
+    })
+}
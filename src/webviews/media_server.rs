@@ -0,0 +1,338 @@
+//! Local HTTP server shared by every media webview.
+//!
+//! Players are served from `http://127.0.0.1` because embeds and `<video>`
+//! elements don't work from `file://` or HTML strings. One server on an
+//! ephemeral port handles every player: each webview registers a route with
+//! its page (and the local file it plays, if any) and gets back a
+//! [`MediaRoute`] that removes the route again when dropped.
+//!
+//! Routes are numbered, so a player's page lives at `/{id}/` and its file at
+//! `/{id}/media`, with any subtitles at `/{id}/subtitles` and album art at
+//! `/{id}/art`. Pages refer to these with the relative URLs `media`,
+//! `subtitles` and `art`.
+//! Files are streamed from disk with HTTP range support for seeking, each on
+//! its own thread so a stalled or paused player never holds up the others.
+
+use super::ByteRange;
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::thread;
+use tiny_http::{Header, Request, Response, Server, StatusCode};
+use tracing::{debug, error};
+
+/// The shared server, started by the first player that needs it
+static SERVER: Lazy<Mutex<Option<Arc<MediaServer>>>> = Lazy::new(|| Mutex::new(None));
+
+/// What a player's route serves
+struct RouteContent {
+    html: String,
+    file: Option<PathBuf>,
+    subtitles: Option<String>,
+    art: Option<PathBuf>,
+}
+
+type Routes = Arc<Mutex<HashMap<u64, Arc<RouteContent>>>>;
+
+struct MediaServer {
+    port: u16,
+    routes: Routes,
+    next_id: AtomicU64,
+}
+
+/// Part of a player's route a request asks for
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RouteTarget {
+    /// The player page
+    Page,
+    /// The local file the page plays
+    Media,
+    /// WebVTT subtitles for the file
+    Subtitles,
+    /// Album art shown with the file
+    Art,
+}
+
+/// A player's route on the shared server, removed when dropped
+pub struct MediaRoute {
+    id: u64,
+    url: String,
+    routes: Routes,
+}
+
+impl MediaRoute {
+    /// URL of the player page
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+}
+
+impl Drop for MediaRoute {
+    fn drop(&mut self) {
+        self.routes.lock().remove(&self.id);
+    }
+}
+
+/// Serve a player page, and optionally the local file it plays at the
+/// page-relative URL `media`. Starts the shared server on first use.
+pub fn serve(html: String, file: Option<PathBuf>) -> Result<MediaRoute, String> {
+    register(RouteContent {
+        html,
+        file,
+        subtitles: None,
+        art: None,
+    })
+}
+
+/// Serve a player page and the local file it plays, along with WebVTT
+/// subtitles at the page-relative URL `subtitles`
+pub fn serve_with_subtitles(
+    html: String,
+    file: PathBuf,
+    subtitles: String,
+) -> Result<MediaRoute, String> {
+    register(RouteContent {
+        html,
+        file: Some(file),
+        subtitles: Some(subtitles),
+        art: None,
+    })
+}
+
+/// Serve a player page and the audio file it plays, along with its album
+/// art, if any, at the page-relative URL `art`
+pub fn serve_with_art(
+    html: String,
+    file: PathBuf,
+    art: Option<PathBuf>,
+) -> Result<MediaRoute, String> {
+    register(RouteContent {
+        html,
+        file: Some(file),
+        subtitles: None,
+        art,
+    })
+}
+
+fn register(content: RouteContent) -> Result<MediaRoute, String> {
+    let server = shared_server()?;
+    let id = server.next_id.fetch_add(1, Ordering::Relaxed);
+    server.routes.lock().insert(id, Arc::new(content));
+    Ok(MediaRoute {
+        id,
+        url: format!("http://127.0.0.1:{}/{}/", server.port, id),
+        routes: server.routes.clone(),
+    })
+}
+
+/// Split a request URL like `/12/media?x#t=3` into its route ID and target
+pub fn parse_route(url: &str) -> Option<(u64, RouteTarget)> {
+    let path = url.split(['?', '#']).next()?;
+    let mut segments = path.trim_start_matches('/').splitn(2, '/');
+    let id = segments.next()?.parse().ok()?;
+    match segments.next() {
+        None | Some("") => Some((id, RouteTarget::Page)),
+        Some("media") => Some((id, RouteTarget::Media)),
+        Some("subtitles") => Some((id, RouteTarget::Subtitles)),
+        Some("art") => Some((id, RouteTarget::Art)),
+        Some(_) => None,
+    }
+}
+
+/// MIME type for an audio, video or album art file, from its extension
+pub fn media_mime(path: &Path) -> &'static str {
+    match path
+        .extension()
+        .and_then(|e| e.to_str())
+        .map(|e| e.to_lowercase())
+        .as_deref()
+    {
+        Some("mp3") => "audio/mpeg",
+        Some("wav") => "audio/wav",
+        Some("ogg") => "audio/ogg",
+        Some("m4a") => "audio/mp4",
+        Some("aac") => "audio/aac",
+        Some("flac") => "audio/flac",
+        Some("mp4") => "video/mp4",
+        Some("webm") => "video/webm",
+        Some("mov") => "video/quicktime",
+        Some("avi") => "video/x-msvideo",
+        Some("mkv") => "video/x-matroska",
+        Some("jpg" | "jpeg") => "image/jpeg",
+        Some("png") => "image/png",
+        Some("gif") => "image/gif",
+        Some("webp") => "image/webp",
+        Some("bmp") => "image/bmp",
+        _ => "application/octet-stream",
+    }
+}
+
+fn shared_server() -> Result<Arc<MediaServer>, String> {
+    let mut shared = SERVER.lock();
+    if let Some(server) = shared.as_ref() {
+        return Ok(server.clone());
+    }
+
+    let http =
+        Server::http("127.0.0.1:0").map_err(|e| format!("Failed to start media server: {}", e))?;
+    let port = http
+        .server_addr()
+        .to_ip()
+        .map(|addr| addr.port())
+        .ok_or_else(|| "Media server has no TCP port".to_string())?;
+    let routes: Routes = Arc::new(Mutex::new(HashMap::new()));
+
+    let server_routes = routes.clone();
+    thread::Builder::new()
+        .name("media-server".to_string())
+        .spawn(move || {
+            loop {
+                match http.recv() {
+                    Ok(request) => handle_request(request, &server_routes),
+                    Err(e) => {
+                        error!("Media server stopped: {}", e);
+                        break;
+                    }
+                }
+            }
+        })
+        .map_err(|e| format!("Failed to start media server thread: {}", e))?;
+    debug!("Media server listening on port {}", port);
+
+    let server = Arc::new(MediaServer {
+        port,
+        routes,
+        next_id: AtomicU64::new(1),
+    });
+    *shared = Some(server.clone());
+    Ok(server)
+}
+
+fn handle_request(request: Request, routes: &Routes) {
+    let route = parse_route(request.url()).and_then(|(id, target)| {
+        let content = routes.lock().get(&id).cloned()?;
+        Some((content, target))
+    });
+    match route {
+        Some((content, RouteTarget::Page)) => {
+            let mut response = Response::from_string(content.html.as_str());
+            if let Some(header) = create_header(&b"Content-Type"[..], &b"text/html"[..]) {
+                response = response.with_header(header);
+            }
+            let _ = request.respond(response);
+        }
+        Some((content, RouteTarget::Subtitles)) => match &content.subtitles {
+            Some(vtt) => {
+                let mut response = Response::from_string(vtt.as_str());
+                if let Some(header) = create_header(&b"Content-Type"[..], &b"text/vtt"[..]) {
+                    response = response.with_header(header);
+                }
+                let _ = request.respond(response);
+            }
+            None => {
+                let _ = request.respond(Response::empty(StatusCode(404)));
+            }
+        },
+        Some((content, RouteTarget::Media)) => match content.file.clone() {
+            Some(path) => stream_file(path, request),
+            None => {
+                let _ = request.respond(Response::empty(StatusCode(404)));
+            }
+        },
+        Some((content, RouteTarget::Art)) => match content.art.clone() {
+            Some(path) => stream_file(path, request),
+            None => {
+                let _ = request.respond(Response::empty(StatusCode(404)));
+            }
+        },
+        None => {
+            let _ = request.respond(Response::empty(StatusCode(404)));
+        }
+    }
+}
+
+/// Helper to create HTTP headers, returning None if the bytes are invalid
+fn create_header(name: &[u8], value: &[u8]) -> Option<Header> {
+    Header::from_bytes(name, value).ok()
+}
+
+/// Serve a file on its own thread; a stream lasts as long as the player
+/// reads it
+fn stream_file(path: PathBuf, request: Request) {
+    let spawned = thread::Builder::new()
+        .name("media-stream".to_string())
+        .spawn(move || serve_file(&path, request));
+    if let Err(e) = spawned {
+        error!("Failed to start media stream thread: {}", e);
+    }
+}
+
+/// Stream a file, or the byte range asked for, straight from disk
+fn serve_file(path: &Path, request: Request) {
+    let mut file = match File::open(path) {
+        Ok(f) => f,
+        Err(_) => {
+            let _ = request.respond(Response::empty(StatusCode(404)));
+            return;
+        }
+    };
+    let file_size = match file.metadata() {
+        Ok(m) => m.len(),
+        Err(_) => {
+            let _ = request.respond(Response::empty(StatusCode(500)));
+            return;
+        }
+    };
+
+    let mut headers: Vec<Header> = [
+        create_header(&b"Content-Type"[..], media_mime(path).as_bytes()),
+        create_header(&b"Accept-Ranges"[..], &b"bytes"[..]),
+    ]
+    .into_iter()
+    .flatten()
+    .collect();
+
+    // Unparseable ranges get the whole file (HTTP 200)
+    let range = request
+        .headers()
+        .iter()
+        .find(|h| h.field.equiv("Range"))
+        .and_then(|h| ByteRange::parse_header(h.value.as_str(), file_size));
+
+    let result = match range {
+        Some(range) => {
+            if file.seek(SeekFrom::Start(range.start)).is_err() {
+                let _ = request.respond(Response::empty(StatusCode(500)));
+                return;
+            }
+            headers.extend(create_header(
+                &b"Content-Range"[..],
+                range.format_content_range().as_bytes(),
+            ));
+            let length = range.length();
+            request.respond(Response::new(
+                StatusCode(206),
+                headers,
+                file.take(length),
+                Some(length as usize),
+                None,
+            ))
+        }
+        None => request.respond(Response::new(
+            StatusCode(200),
+            headers,
+            file,
+            Some(file_size as usize),
+            None,
+        )),
+    };
+    // Players cancel requests all the time while seeking
+    if let Err(e) = result {
+        debug!("Media request for {:?} ended early: {}", path, e);
+    }
+}
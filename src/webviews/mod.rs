@@ -1,23 +1,41 @@
 //! WebView-based media players and viewers.
 //!
 //! This module provides embedded web content viewers for various media types,
-//! each implemented using GPUI's WebView component with local HTTP servers.
+//! each implemented using GPUI's WebView component and served from one
+//! shared local HTTP server.
 //!
 //! ## Modules
 //!
 //! - `audio` - Audio player with metadata display (MP3, WAV, OGG, etc.)
-//! - `video` - Video player with streaming (MP4, WebM, MOV, etc.)
+//! - `video` - Video player with streaming and subtitles (MP4, WebM, MOV, etc.)
 //! - `youtube` - YouTube iframe embed player
+//! - `vimeo` - Vimeo iframe embed player
+//! - `soundcloud` - SoundCloud widget player
+//! - `spotify` - Spotify embed player
 //! - `pdf` - Native PDF viewer using platform rendering
+//! - `media_control` - Play/pause and volume commands for the media players
+//! - `media_server` - The local HTTP server behind every media player
 
 mod audio;
+mod media_control;
+mod media_server;
 mod pdf;
 mod range_utils;
+mod soundcloud;
+mod spotify;
 mod video;
+mod vimeo;
 mod youtube;
 
 pub use audio::AudioWebView;
+pub use media_control::{
+    MediaCommand, PlaybackPosition, seek_script, start_fragment, subtitles_script, volume_script,
+};
+pub use media_server::{MediaRoute, RouteTarget, media_mime, parse_route};
 pub use pdf::PdfWebView;
 pub use range_utils::*;
+pub use soundcloud::SoundCloudWebView;
+pub use spotify::SpotifyWebView;
 pub use video::VideoWebView;
+pub use vimeo::VimeoWebView;
 pub use youtube::YouTubeWebView;
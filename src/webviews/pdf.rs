@@ -11,11 +11,15 @@
 //! ## Features
 //!
 //! - Native PDF rendering with zoom and scroll
+//! - Jumping to a page, for find in file and the page controls
+//! - An outline sidebar, opened and collapsed per viewer
 //! - Bounds control for positioning within the canvas
 //! - Show/hide for visibility management
 
 use gpui::*;
 use gpui_component::webview::WebView;
+use std::cell::Cell;
+use std::collections::HashSet;
 use std::path::{Path, PathBuf};
 use wry::dpi::{LogicalPosition, LogicalSize};
 use wry::{Rect, WebViewBuilder};
@@ -56,6 +60,13 @@ fn path_to_file_url(path: &Path) -> String {
 pub struct PdfWebView {
     webview_entity: Entity<WebView>,
     path: PathBuf,
+    /// Page last shown with `show_page`. The native viewer doesn't report
+    /// pages scrolled to by hand.
+    page: Cell<usize>,
+    /// Whether the outline sidebar is shown
+    outline_open: bool,
+    /// Outline entries closed in the sidebar, by index path
+    collapsed_outline: HashSet<Vec<usize>>,
 }
 
 impl PdfWebView {
@@ -94,6 +105,9 @@ impl PdfWebView {
         Ok(Self {
             webview_entity,
             path,
+            page: Cell::new(0),
+            outline_open: true,
+            collapsed_outline: HashSet::new(),
         })
     }
 
@@ -107,14 +121,58 @@ impl PdfWebView {
         self.webview_entity.clone()
     }
 
+    /// Page last shown with `show_page`, counted from 0
+    pub fn page(&self) -> usize {
+        self.page.get()
+    }
+
+    /// Whether the outline sidebar is shown (if the PDF has an outline)
+    pub fn outline_open(&self) -> bool {
+        self.outline_open
+    }
+
+    /// Show or hide the outline sidebar
+    pub fn toggle_outline(&mut self) {
+        self.outline_open = !self.outline_open;
+    }
+
+    /// Outline entries closed in the sidebar
+    pub fn collapsed_outline(&self) -> &HashSet<Vec<usize>> {
+        &self.collapsed_outline
+    }
+
+    /// Open or close an outline entry in the sidebar
+    pub fn toggle_outline_entry(&mut self, index_path: Vec<usize>) {
+        if !self.collapsed_outline.remove(&index_path) {
+            self.collapsed_outline.insert(index_path);
+        }
+    }
+
     /// Reload the PDF in the WebView
     pub fn reload(&self, cx: &mut App) {
+        self.page.set(0);
         let file_url = path_to_file_url(&self.path);
         self.webview_entity.update(cx, |view, _| {
             view.load_url(&file_url);
         });
     }
 
+    /// Scroll the viewer to a page, counted from 0
+    pub fn show_page(&self, page: usize, cx: &mut App) {
+        self.page.set(page);
+        let file_url = format!("{}#page={}", path_to_file_url(&self.path), page + 1);
+        self.webview_entity.update(cx, |view, _| {
+            view.load_url(&file_url);
+        });
+    }
+
+    /// Zoom the viewer's content, where 1.0 is 100%
+    pub fn set_zoom(&self, zoom: f32, cx: &mut App) {
+        self.webview_entity.update(cx, |view, _| {
+            let _ = view.raw().zoom(zoom as f64);
+        });
+    }
+
     /// Set the bounds of the webview explicitly (x, y, width, height in logical pixels)
     pub fn set_bounds(&self, x: f32, y: f32, width: f32, height: f32, cx: &mut App) {
         self.webview_entity.update(cx, |view, _| {
@@ -0,0 +1,123 @@
+//! WebView-based SoundCloud player.
+//!
+//! Works like the YouTube player: the shared media server serves a page
+//! with the SoundCloud widget iframe, which the native WebView then loads.
+//!
+//! ## Features
+//!
+//! - SoundCloud widget for tracks and playlists
+//! - Autoplay disabled by default
+//! - Compact player without artwork, comments or reposts
+//! - Play/pause from the canvas through the widget's postMessage API
+//! - Volume and mute set from the canvas
+use super::media_control::{
+    MediaCommand, SOUNDCLOUD_BRIDGE_SCRIPT, send_media_command, set_media_volume, volume_script,
+};
+use super::media_server::{self, MediaRoute};
+use crate::types::MediaVolume;
+use gpui::*;
+use gpui_component::webview::WebView;
+use wry::WebViewBuilder;
+
+/// WebView-based SoundCloud player served from the shared media server
+pub struct SoundCloudWebView {
+    webview_entity: Entity<WebView>,
+    track_url: String,
+    _route: MediaRoute,
+}
+
+impl SoundCloudWebView {
+    /// Create a new SoundCloud WebView served from the shared media server,
+    /// playing at `volume`
+    pub fn new(
+        track_url: String,
+        volume: MediaVolume,
+        window: &mut Window,
+        cx: &mut App,
+    ) -> Result<Self, String> {
+        // HTML with SoundCloud embed
+        let html = format!(
+            r#"<!DOCTYPE html>
+<html>
+<head>
+    <meta charset="UTF-8">
+    <meta name="viewport" content="width=device-width, initial-scale=1.0">
+    <style>
+        * {{ margin: 0; padding: 0; box-sizing: border-box; }}
+        html, body {{ width: 100%; height: 100%; overflow: hidden; background: #fff; }}
+        iframe {{ width: 100%; height: 100%; border: none; }}
+    </style>
+</head>
+<body>
+    <iframe
+        src="https://w.soundcloud.com/player/?url={track_url}&auto_play=false&visual=false&show_comments=false&show_reposts=false"
+        title="SoundCloud player"
+        frameborder="0"
+        allow="autoplay"
+        scrolling="no">
+    </iframe>
+    <script>{bridge}</script>
+    <script>{volume}</script>
+</body>
+</html>"#,
+            track_url = urlencoding::encode(&track_url),
+            bridge = SOUNDCLOUD_BRIDGE_SCRIPT,
+            volume = volume_script(volume)
+        );
+
+        let route = media_server::serve(html, None)?;
+
+        // Create WebView entity pointing to the player page
+        #[cfg(any(
+            target_os = "macos",
+            target_os = "windows",
+            target_os = "ios",
+            target_os = "android"
+        ))]
+        let webview = WebViewBuilder::new()
+            .with_url(route.url())
+            .build_as_child(window)
+            .map_err(|e| format!("Failed to create WebView: {:?}", e))?;
+
+        #[cfg(not(any(
+            target_os = "macos",
+            target_os = "windows",
+            target_os = "ios",
+            target_os = "android"
+        )))]
+        return Err("WebView not supported on this platform".to_string());
+
+        let webview_entity = cx.new(|cx| WebView::new(webview, window, cx));
+
+        Ok(Self {
+            webview_entity,
+            track_url,
+            _route: route,
+        })
+    }
+
+    /// Get the SoundCloud URL of the track or playlist
+    pub fn track_url(&self) -> &str {
+        &self.track_url
+    }
+
+    /// Get the WebView entity for rendering
+    pub fn webview(&self) -> Entity<WebView> {
+        self.webview_entity.clone()
+    }
+
+    /// Hide the webview (should be called before dropping to prevent orphaned UI)
+    pub fn hide(&self, cx: &mut App) {
+        self.webview_entity.update(cx, |wv, _| wv.hide());
+    }
+
+    /// Set the player's volume
+    pub fn set_volume(&self, volume: MediaVolume, cx: &mut App) -> Result<(), String> {
+        set_media_volume(&self.webview_entity, volume, cx)
+    }
+
+    /// Play or pause the player
+    pub fn send_command(&self, command: MediaCommand, cx: &mut App) -> Result<(), String> {
+        send_media_command(&self.webview_entity, command, cx)
+    }
+}
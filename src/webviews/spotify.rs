@@ -0,0 +1,103 @@
+//! WebView-based Spotify player.
+//!
+//! Works like the YouTube player: the shared media server serves a page
+//! with the Spotify embed iframe, which the native WebView then loads.
+//!
+//! ## Features
+//!
+//! - Spotify embed for tracks, albums, playlists, artists and podcasts
+//! - Previews for listeners who aren't logged in, full playback otherwise
+//! - Play/pause from the canvas through the embed's postMessage API
+use super::media_control::{MediaCommand, SPOTIFY_BRIDGE_SCRIPT, send_media_command};
+use super::media_server::{self, MediaRoute};
+use gpui::*;
+use gpui_component::webview::WebView;
+use wry::WebViewBuilder;
+
+/// WebView-based Spotify player served from the shared media server
+pub struct SpotifyWebView {
+    webview_entity: Entity<WebView>,
+    embed_path: String,
+    _route: MediaRoute,
+}
+
+impl SpotifyWebView {
+    /// Create a new Spotify WebView served from the shared media server
+    pub fn new(embed_path: String, window: &mut Window, cx: &mut App) -> Result<Self, String> {
+        // HTML with Spotify embed
+        let html = format!(
+            r#"<!DOCTYPE html>
+<html>
+<head>
+    <meta charset="UTF-8">
+    <meta name="viewport" content="width=device-width, initial-scale=1.0">
+    <style>
+        * {{ margin: 0; padding: 0; box-sizing: border-box; }}
+        html, body {{ width: 100%; height: 100%; overflow: hidden; background: #121212; }}
+        iframe {{ width: 100%; height: 100%; border: none; }}
+    </style>
+</head>
+<body>
+    <iframe
+        src="https://open.spotify.com/embed/{embed_path}"
+        title="Spotify player"
+        frameborder="0"
+        allow="autoplay; clipboard-write; encrypted-media; fullscreen; picture-in-picture">
+    </iframe>
+    <script>{bridge}</script>
+</body>
+</html>"#,
+            bridge = SPOTIFY_BRIDGE_SCRIPT
+        );
+
+        let route = media_server::serve(html, None)?;
+
+        // Create WebView entity pointing to the player page
+        #[cfg(any(
+            target_os = "macos",
+            target_os = "windows",
+            target_os = "ios",
+            target_os = "android"
+        ))]
+        let webview = WebViewBuilder::new()
+            .with_url(route.url())
+            .build_as_child(window)
+            .map_err(|e| format!("Failed to create WebView: {:?}", e))?;
+
+        #[cfg(not(any(
+            target_os = "macos",
+            target_os = "windows",
+            target_os = "ios",
+            target_os = "android"
+        )))]
+        return Err("WebView not supported on this platform".to_string());
+
+        let webview_entity = cx.new(|cx| WebView::new(webview, window, cx));
+
+        Ok(Self {
+            webview_entity,
+            embed_path,
+            _route: route,
+        })
+    }
+
+    /// Get the embed path (`kind/ID`)
+    pub fn embed_path(&self) -> &str {
+        &self.embed_path
+    }
+
+    /// Get the WebView entity for rendering
+    pub fn webview(&self) -> Entity<WebView> {
+        self.webview_entity.clone()
+    }
+
+    /// Hide the webview (should be called before dropping to prevent orphaned UI)
+    pub fn hide(&self, cx: &mut App) {
+        self.webview_entity.update(cx, |wv, _| wv.hide());
+    }
+
+    /// Play or pause the player
+    pub fn send_command(&self, command: MediaCommand, cx: &mut App) -> Result<(), String> {
+        send_media_command(&self.webview_entity, command, cx)
+    }
+}
@@ -0,0 +1,124 @@
+//! WebView-based Vimeo video player.
+//!
+//! Works like the YouTube player: the shared media server serves a page
+//! with the Vimeo player iframe, which the native WebView then loads.
+//!
+//! ## Features
+//!
+//! - Vimeo player embed with full playback controls
+//! - Autoplay disabled by default
+//! - Title, byline and portrait hidden
+//! - Play/pause from the canvas through the player's postMessage API
+//! - Volume and mute set from the canvas
+
+use super::media_control::{
+    MediaCommand, VIMEO_BRIDGE_SCRIPT, send_media_command, set_media_volume, volume_script,
+};
+use super::media_server::{self, MediaRoute};
+use crate::types::MediaVolume;
+use gpui::*;
+use gpui_component::webview::WebView;
+use wry::WebViewBuilder;
+
+/// WebView-based Vimeo player served from the shared media server
+pub struct VimeoWebView {
+    webview_entity: Entity<WebView>,
+    video_id: String,
+    _route: MediaRoute,
+}
+
+impl VimeoWebView {
+    /// Create a new Vimeo WebView served from the shared media server,
+    /// playing at `volume`
+    pub fn new(
+        video_id: String,
+        volume: MediaVolume,
+        window: &mut Window,
+        cx: &mut App,
+    ) -> Result<Self, String> {
+        // HTML with Vimeo embed
+        let html = format!(
+            r#"<!DOCTYPE html>
+<html>
+<head>
+    <meta charset="UTF-8">
+    <meta name="viewport" content="width=device-width, initial-scale=1.0">
+    <style>
+        * {{ margin: 0; padding: 0; box-sizing: border-box; }}
+        html, body {{ width: 100%; height: 100%; overflow: hidden; background: #000; }}
+        iframe {{ width: 100%; height: 100%; border: none; }}
+    </style>
+</head>
+<body>
+    <iframe
+        src="https://player.vimeo.com/video/{video_id}?autoplay=0&title=0&byline=0&portrait=0&playsinline=1"
+        title="Vimeo video player"
+        frameborder="0"
+        allow="autoplay; fullscreen; picture-in-picture; clipboard-write"
+        referrerpolicy="strict-origin-when-cross-origin"
+        allowfullscreen>
+    </iframe>
+    <script>{bridge}</script>
+    <script>{volume}</script>
+</body>
+</html>"#,
+            bridge = VIMEO_BRIDGE_SCRIPT,
+            volume = volume_script(volume)
+        );
+
+        let route = media_server::serve(html, None)?;
+
+        // Create WebView entity pointing to the player page
+        #[cfg(any(
+            target_os = "macos",
+            target_os = "windows",
+            target_os = "ios",
+            target_os = "android"
+        ))]
+        let webview = WebViewBuilder::new()
+            .with_url(route.url())
+            .build_as_child(window)
+            .map_err(|e| format!("Failed to create WebView: {:?}", e))?;
+
+        #[cfg(not(any(
+            target_os = "macos",
+            target_os = "windows",
+            target_os = "ios",
+            target_os = "android"
+        )))]
+        return Err("WebView not supported on this platform".to_string());
+
+        let webview_entity = cx.new(|cx| WebView::new(webview, window, cx));
+
+        Ok(Self {
+            webview_entity,
+            video_id,
+            _route: route,
+        })
+    }
+
+    /// Get the video ID
+    pub fn video_id(&self) -> &str {
+        &self.video_id
+    }
+
+    /// Get the WebView entity for rendering
+    pub fn webview(&self) -> Entity<WebView> {
+        self.webview_entity.clone()
+    }
+
+    /// Hide the webview (should be called before dropping to prevent orphaned UI)
+    pub fn hide(&self, cx: &mut App) {
+        self.webview_entity.update(cx, |wv, _| wv.hide());
+    }
+
+    /// Set the player's volume
+    pub fn set_volume(&self, volume: MediaVolume, cx: &mut App) -> Result<(), String> {
+        set_media_volume(&self.webview_entity, volume, cx)
+    }
+
+    /// Play or pause the player
+    pub fn send_command(&self, command: MediaCommand, cx: &mut App) -> Result<(), String> {
+        send_media_command(&self.webview_entity, command, cx)
+    }
+}
@@ -1,78 +1,57 @@
 //! WebView-based YouTube video player.
 //!
 //! This module embeds YouTube videos using their official iframe API,
-//! served through a local HTTP server to work around WebView security restrictions.
+//! served through the local media server to work around WebView security
+//! restrictions.
 //!
 //! ## Architecture
 //!
-//! Each YouTube player spawns a local HTTP server on a unique port that serves
-//! an HTML page containing the YouTube iframe embed. This approach allows the
-//! embedded player to function correctly within the native WebView.
+//! Each YouTube player registers an HTML page containing the YouTube iframe
+//! embed with the shared media server (see `media_server`). This approach
+//! allows the embedded player to function correctly within the native WebView.
 //!
 //! ## Features
 //!
 //! - YouTube iframe embed with full playback controls
 //! - Autoplay disabled by default
 //! - Modest branding (reduced YouTube UI)
-
+//! - Play/pause from the canvas through the iframe API
+//! - Starts where the video was left when the player is recreated
+//! - Volume and mute set from the canvas
+
+use super::media_control::{
+    MediaCommand, PlaybackPosition, YOUTUBE_BRIDGE_SCRIPT, query_playback_position,
+    send_media_command, set_media_volume, volume_script,
+};
+use super::media_server::{self, MediaRoute};
+use crate::types::MediaVolume;
 use gpui::*;
 use gpui_component::webview::WebView;
-use std::sync::Arc;
-use std::sync::atomic::{AtomicBool, AtomicU16, Ordering};
-use std::sync::mpsc;
-use std::thread::{self, JoinHandle};
-use std::time::Duration;
-use tiny_http::{Response, Server};
-use tracing::error;
 use wry::WebViewBuilder;
 
-// Global port counter for unique server ports
-static PORT_COUNTER: AtomicU16 = AtomicU16::new(19800);
-
-/// Helper to create HTTP headers, returning None if the bytes are invalid
-fn create_header(name: &[u8], value: &[u8]) -> Option<tiny_http::Header> {
-    tiny_http::Header::from_bytes(name, value).ok()
-}
-
-/// WebView-based YouTube player with local HTTP server
+/// WebView-based YouTube player served from the shared media server
 pub struct YouTubeWebView {
     webview_entity: Entity<WebView>,
     video_id: String,
-    port: u16,
-    shutdown_flag: Arc<AtomicBool>,
-    server_thread: Option<JoinHandle<()>>,
+    _route: MediaRoute,
 }
 
 impl YouTubeWebView {
-    /// Create a new YouTube WebView with a local HTTP server
-    pub fn new(video_id: String, window: &mut Window, cx: &mut App) -> Result<Self, String> {
-        // Get a unique port for this instance
-        let port = PORT_COUNTER.fetch_add(1, Ordering::SeqCst);
-        let video_id_clone = video_id.clone();
-        let shutdown_flag = Arc::new(AtomicBool::new(false));
-        let shutdown_flag_clone = shutdown_flag.clone();
-
-        // Channel for server startup synchronization
-        let (tx, rx) = mpsc::channel();
-
-        // Start a local HTTP server in a background thread
-        let server_thread = thread::spawn(move || {
-            let addr = format!("127.0.0.1:{}", port);
-            let server = match Server::http(&addr) {
-                Ok(s) => {
-                    let _ = tx.send(Ok(()));
-                    s
-                }
-                Err(e) => {
-                    error!("Failed to start YouTube embed server on port {}: {}", port, e);
-                    let _ = tx.send(Err(e.to_string()));
-                    return;
-                }
-            };
-
-            // HTML with YouTube embed
-            let html = format!(
-                r#"<!DOCTYPE html>
+    /// Create a new YouTube WebView served from the shared media server,
+    /// starting `start_seconds` into the video at `volume`
+    pub fn new(
+        video_id: String,
+        start_seconds: f64,
+        volume: MediaVolume,
+        window: &mut Window,
+        cx: &mut App,
+    ) -> Result<Self, String> {
+        // The embed only takes whole seconds
+        let start = start_seconds.max(0.0) as u64;
+
+        // HTML with YouTube embed
+        let html = format!(
+            r#"<!DOCTYPE html>
 <html>
 <head>
     <meta charset="UTF-8">
@@ -85,59 +64,25 @@ impl YouTubeWebView {
 </head>
 <body>
     <iframe
-        src="https://www.youtube.com/embed/{video_id}?autoplay=0&rel=0&modestbranding=1&playsinline=1"
+        src="https://www.youtube.com/embed/{video_id}?autoplay=0&rel=0&modestbranding=1&playsinline=1&enablejsapi=1&start={start}"
         title="YouTube video player"
         frameborder="0"
         allow="accelerometer; autoplay; clipboard-write; encrypted-media; gyroscope; picture-in-picture; web-share"
         referrerpolicy="strict-origin-when-cross-origin"
         allowfullscreen>
     </iframe>
+    <script>{bridge}</script>
+    <script>{volume}</script>
 </body>
 </html>"#,
-                video_id = video_id_clone
-            );
-
-            // Serve requests with non-blocking check for shutdown
-            // tiny_http doesn't have non-blocking recv, but we can use try_recv with timeout
-            loop {
-                // Check shutdown flag
-                if shutdown_flag_clone.load(Ordering::Relaxed) {
-                    break;
-                }
-
-                // Use recv_timeout to periodically check shutdown flag
-                match server.recv_timeout(std::time::Duration::from_millis(100)) {
-                    Ok(Some(request)) => {
-                        let mut response = Response::from_string(&html);
-                        if let Some(header) =
-                            create_header(&b"Content-Type"[..], &b"text/html"[..])
-                        {
-                            response = response.with_header(header);
-                        }
-                        let _ = request.respond(response);
-                    }
-                    Ok(None) => {
-                        // Timeout, loop continues to check shutdown
-                    }
-                    Err(_) => {
-                        // Server error, exit
-                        break;
-                    }
-                }
-            }
-        });
-
-        // Wait for server to start with timeout
-        match rx.recv_timeout(Duration::from_secs(5)) {
-            Ok(Ok(())) => {}
-            Ok(Err(e)) => return Err(format!("Failed to start server: {}", e)),
-            Err(_) => return Err("Server startup timeout".to_string()),
-        }
+            start = start,
+            bridge = YOUTUBE_BRIDGE_SCRIPT,
+            volume = volume_script(volume)
+        );
 
-        // URL to our local server
-        let url = format!("http://127.0.0.1:{}/", port);
+        let route = media_server::serve(html, None)?;
 
-        // Create WebView entity pointing to local server
+        // Create WebView entity pointing to the player page
         #[cfg(any(
             target_os = "macos",
             target_os = "windows",
@@ -145,7 +90,7 @@ impl YouTubeWebView {
             target_os = "android"
         ))]
         let webview = WebViewBuilder::new()
-            .with_url(&url)
+            .with_url(route.url())
             .build_as_child(window)
             .map_err(|e| format!("Failed to create WebView: {:?}", e))?;
 
@@ -162,9 +107,7 @@ impl YouTubeWebView {
         Ok(Self {
             webview_entity,
             video_id,
-            port,
-            shutdown_flag,
-            server_thread: Some(server_thread),
+            _route: route,
         })
     }
 
@@ -173,33 +116,33 @@ impl YouTubeWebView {
         &self.video_id
     }
 
-    /// Get the port this server is running on
-    pub fn port(&self) -> u16 {
-        self.port
-    }
-
     /// Get the WebView entity for rendering
     pub fn webview(&self) -> Entity<WebView> {
         self.webview_entity.clone()
     }
 
-    /// Shutdown the HTTP server
-    pub fn shutdown(&self) {
-        self.shutdown_flag.store(true, Ordering::SeqCst);
-    }
-
     /// Hide the webview (should be called before dropping to prevent orphaned UI)
     pub fn hide(&self, cx: &mut App) {
         self.webview_entity.update(cx, |wv, _| wv.hide());
     }
-}
 
-impl Drop for YouTubeWebView {
-    fn drop(&mut self) {
-        // Signal server to shutdown and join the thread
-        self.shutdown_flag.store(true, Ordering::SeqCst);
-        if let Some(handle) = self.server_thread.take() {
-            let _ = handle.join();
-        }
+    /// Set the player's volume
+    pub fn set_volume(&self, volume: MediaVolume, cx: &mut App) -> Result<(), String> {
+        set_media_volume(&self.webview_entity, volume, cx)
+    }
+
+    /// Play or pause the player
+    pub fn send_command(&self, command: MediaCommand, cx: &mut App) -> Result<(), String> {
+        send_media_command(&self.webview_entity, command, cx)
+    }
+
+    /// Ask the player where it is; `on_position` runs off the UI thread with
+    /// the answer, or `None` before the video has started loading
+    pub fn query_position(
+        &self,
+        on_position: impl Fn(Option<PlaybackPosition>) + Send + 'static,
+        cx: &mut App,
+    ) -> Result<(), String> {
+        query_playback_position(&self.webview_entity, on_position, cx)
     }
 }
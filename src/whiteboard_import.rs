@@ -0,0 +1,881 @@
+//! Miro and FigJam import - turning a board exported from another
+//! whiteboard app into board items.
+//!
+//! Both importers are best-effort. They understand:
+//!
+//! - **Miro JSON**, the item list of Miro's REST API (`{"data": [...]}` or
+//!   a bare array): sticky notes, shapes, text, images, frames and
+//!   connectors. Positions are item centers, relative to their frame.
+//! - **Miro CSV**, as exported from a selection of sticky notes: every
+//!   non-empty cell becomes a sticky note, laid out like the table.
+//! - **FigJam JSON**, the file document of Figma's REST API: stickies,
+//!   shapes with text, text, sections, images and connectors.
+//!
+//! Sticky notes become a filled rectangle with a text box on top, and text
+//! inside shapes a text box over the shape. Frames and sections become a
+//! rounded rectangle behind everything else with their name above it, like
+//! Obsidian groups. Connectors become arrows between the sides of the items
+//! they connect.
+//!
+//! Images are downloaded into the board's storage, or taken from files
+//! next to the export. Anything that can't be brought over is listed in the
+//! import's `skipped` notes.
+
+use crate::board_assets::{pasted_image_extension, write_pasted_file};
+use crate::canvas_snapshot::parse_hex;
+use crate::constants::{
+    DEFAULT_ARROW_COLOR, DEFAULT_ARROW_THICKNESS, DEFAULT_BORDER_COLOR, DEFAULT_BORDER_WIDTH,
+    DEFAULT_FONT_SIZE, DEFAULT_TEXT_COLOR,
+};
+use crate::data::split_csv_line;
+use crate::error::BoardError;
+use crate::item_clipboard::CopiedItems;
+use crate::obsidian_canvas::side_anchor;
+use crate::types::{ArrowHead, CanvasItem, ItemContent, ShapeType};
+use crate::url_paste::download_image;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Size of a sticky note made from a CSV cell
+const CSV_STICKY_SIZE: f32 = 200.0;
+
+/// Space between sticky notes made from CSV cells
+const CSV_STICKY_GAP: f32 = 20.0;
+
+/// Space between a sticky note's edge and its text
+const STICKY_PADDING: f32 = 12.0;
+
+/// Height of the label above a frame
+const FRAME_LABEL_HEIGHT: f32 = 28.0;
+
+/// Size of a connector's label
+const CONNECTOR_LABEL_SIZE: (f32, f32) = (160.0, 28.0);
+
+/// Color of sticky notes that don't say theirs (Miro's light yellow)
+const DEFAULT_STICKY_COLOR: &str = "#fff9b1";
+
+/// Text color on light sticky notes and shapes
+const DARK_TEXT_COLOR: &str = "#1a1a1a";
+
+/// Board ID the items of an import are "copied" from, so pasting them
+/// treats them as coming from another board
+const IMPORT_BOARD_ID: &str = "whiteboard-import";
+
+/// App a board export came from
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum WhiteboardApp {
+    Miro,
+    FigJam,
+}
+
+impl WhiteboardApp {
+    /// Name of the app
+    pub fn label(self) -> &'static str {
+        match self {
+            WhiteboardApp::Miro => "Miro",
+            WhiteboardApp::FigJam => "FigJam",
+        }
+    }
+}
+
+/// Items converted from a board export, back to front
+#[derive(Debug)]
+pub struct ConvertedWhiteboard {
+    pub app: WhiteboardApp,
+    pub items: Vec<CanvasItem>,
+    /// What couldn't be imported, one note per item
+    pub skipped: Vec<String>,
+}
+
+/// Finds the file of an image in an export: downloads a URL or looks up a
+/// path next to the export, returning where the image is on disk
+pub type ImageResolver<'a> = dyn FnMut(&str) -> Result<PathBuf, String> + 'a;
+
+/// Converted items in the making
+struct Converter<'a> {
+    app: WhiteboardApp,
+    items: Vec<CanvasItem>,
+    skipped: Vec<String>,
+    next_id: u64,
+    resolve_image: &'a mut ImageResolver<'a>,
+}
+
+impl<'a> Converter<'a> {
+    fn new(app: WhiteboardApp, resolve_image: &'a mut ImageResolver<'a>) -> Self {
+        Self {
+            app,
+            items: Vec::new(),
+            skipped: Vec::new(),
+            next_id: 1,
+            resolve_image,
+        }
+    }
+
+    fn finish(self) -> ConvertedWhiteboard {
+        ConvertedWhiteboard {
+            app: self.app,
+            items: self.items,
+            skipped: self.skipped,
+        }
+    }
+
+    fn push(&mut self, bounds: Bounds, content: ItemContent) {
+        self.items.push(CanvasItem {
+            id: self.next_id,
+            position: (bounds.0, bounds.1),
+            size: (bounds.2, bounds.3),
+            content,
+            locked: false,
+            volume: None,
+        });
+        self.next_id += 1;
+    }
+
+    fn text(&mut self, bounds: Bounds, text: &str, font_size: f32, color: &str) {
+        if text.trim().is_empty() {
+            return;
+        }
+        self.push(
+            bounds,
+            ItemContent::TextBox {
+                text: text.to_string(),
+                font_size,
+                color: color.to_string(),
+            },
+        );
+    }
+
+    fn sticky(&mut self, bounds: Bounds, text: &str, fill: Option<String>) {
+        let fill = fill.unwrap_or_else(|| DEFAULT_STICKY_COLOR.to_string());
+        let text_color = readable_text_color(&fill);
+        self.push(
+            bounds,
+            ItemContent::Shape {
+                shape_type: ShapeType::Rectangle,
+                fill_color: Some(fill.clone()),
+                border_color: fill,
+                border_width: 1.0,
+            },
+        );
+        let (x, y, w, h) = bounds;
+        self.text(
+            (
+                x + STICKY_PADDING,
+                y + STICKY_PADDING,
+                (w - 2.0 * STICKY_PADDING).max(1.0),
+                (h - 2.0 * STICKY_PADDING).max(1.0),
+            ),
+            text,
+            DEFAULT_FONT_SIZE,
+            text_color,
+        );
+    }
+
+    fn frame(&mut self, bounds: Bounds, title: &str, fill: Option<String>) {
+        self.push(
+            bounds,
+            ItemContent::Shape {
+                shape_type: ShapeType::RoundedRect,
+                fill_color: fill,
+                border_color: DEFAULT_BORDER_COLOR.to_string(),
+                border_width: DEFAULT_BORDER_WIDTH,
+            },
+        );
+        let (x, y, w, _) = bounds;
+        self.text(
+            (x, y - FRAME_LABEL_HEIGHT, w, FRAME_LABEL_HEIGHT),
+            title,
+            DEFAULT_FONT_SIZE,
+            DEFAULT_TEXT_COLOR,
+        );
+    }
+
+    fn image(&mut self, bounds: Bounds, source: &str, name: &str) {
+        match (self.resolve_image)(source) {
+            Ok(path) => self.push(bounds, ItemContent::Image(path)),
+            Err(e) => self.skipped.push(format!("Image '{}': {}", name, e)),
+        }
+    }
+
+    fn connector(&mut self, connector: Connector) {
+        let (start, end) = (connector.start, connector.end);
+        let (start, end) = match (start, end) {
+            (Endpoint::Item(from), Endpoint::Item(to)) => {
+                (side_anchor(from, to, None), side_anchor(to, from, None))
+            }
+            (Endpoint::Item(from), Endpoint::Point(to)) => (nearest_side(from, to), to),
+            (Endpoint::Point(from), Endpoint::Item(to)) => (from, nearest_side(to, from)),
+            (Endpoint::Point(from), Endpoint::Point(to)) => (from, to),
+        };
+        // An arrow has one head; point it at the end that has one
+        let (start, end) = if connector.head_at_start && !connector.head_at_end {
+            (end, start)
+        } else {
+            (start, end)
+        };
+        let end_offset = (end.0 - start.0, end.1 - start.1);
+        let color = connector
+            .color
+            .unwrap_or_else(|| DEFAULT_ARROW_COLOR.to_string());
+
+        self.push(
+            (start.0, start.1, end_offset.0.abs(), end_offset.1.abs()),
+            ItemContent::Arrow {
+                end_offset,
+                color: color.clone(),
+                thickness: connector.thickness.unwrap_or(DEFAULT_ARROW_THICKNESS),
+                head_style: if connector.head_at_start || connector.head_at_end {
+                    ArrowHead::Arrow
+                } else {
+                    ArrowHead::None
+                },
+            },
+        );
+        let middle = ((start.0 + end.0) / 2.0, (start.1 + end.1) / 2.0);
+        self.text(
+            (
+                middle.0 - CONNECTOR_LABEL_SIZE.0 / 2.0,
+                middle.1 - CONNECTOR_LABEL_SIZE.1 / 2.0,
+                CONNECTOR_LABEL_SIZE.0,
+                CONNECTOR_LABEL_SIZE.1,
+            ),
+            &connector.label,
+            DEFAULT_FONT_SIZE,
+            &color,
+        );
+    }
+}
+
+/// Canvas rectangle: x, y, width, height
+type Bounds = (f32, f32, f32, f32);
+
+/// End of a connector: an item's bounds or a loose point
+#[derive(Clone, Copy)]
+enum Endpoint {
+    Item(Bounds),
+    Point((f32, f32)),
+}
+
+struct Connector {
+    start: Endpoint,
+    end: Endpoint,
+    head_at_start: bool,
+    head_at_end: bool,
+    color: Option<String>,
+    thickness: Option<f32>,
+    label: String,
+}
+
+/// Convert an export, telling CSV from JSON by `is_csv` and Miro from
+/// FigJam JSON by its contents
+pub fn convert_whiteboard<'a>(
+    contents: &str,
+    is_csv: bool,
+    resolve_image: &'a mut ImageResolver<'a>,
+) -> Result<ConvertedWhiteboard, BoardError> {
+    if is_csv {
+        return convert_miro_csv(contents);
+    }
+    let value: Value = serde_json::from_str(contents)?;
+    if value.get("document").is_some() {
+        convert_figjam(&value, resolve_image)
+    } else {
+        convert_miro(&value, resolve_image)
+    }
+}
+
+/// Convert the item list of a Miro board
+pub fn convert_miro<'a>(
+    value: &Value,
+    resolve_image: &'a mut ImageResolver<'a>,
+) -> Result<ConvertedWhiteboard, BoardError> {
+    let items = value
+        .as_array()
+        .or_else(|| value.get("data")?.as_array())
+        .ok_or_else(|| BoardError::InvalidData("not a Miro board export".to_string()))?;
+    let mut converter = Converter::new(WhiteboardApp::Miro, resolve_image);
+
+    // Frames first: they go behind everything else, and the other items'
+    // positions are relative to them
+    let mut bounds_by_id: HashMap<String, Bounds> = HashMap::new();
+    let (frames, others): (Vec<&Value>, Vec<&Value>) = items
+        .iter()
+        .partition(|item| str_at(item, &["type"]) == Some("frame"));
+    for frame in &frames {
+        let bounds = miro_bounds(frame, None);
+        if let Some(id) = id_at(frame, &["id"]) {
+            bounds_by_id.insert(id, bounds);
+        }
+        converter.frame(
+            bounds,
+            str_at(frame, &["data", "title"]).unwrap_or_default(),
+            str_at(frame, &["style", "fillColor"]).and_then(hex_color),
+        );
+    }
+
+    let mut connectors = Vec::new();
+    for item in &others {
+        let kind = str_at(item, &["type"]).unwrap_or_default();
+        if kind == "connector" {
+            connectors.push(*item);
+            continue;
+        }
+        let parent = id_at(item, &["parent", "id"]).and_then(|id| bounds_by_id.get(&id).copied());
+        let bounds = miro_bounds(item, parent);
+        if let Some(id) = id_at(item, &["id"]) {
+            bounds_by_id.insert(id, bounds);
+        }
+        let content = str_at(item, &["data", "content"])
+            .map(html_text)
+            .unwrap_or_default();
+
+        match kind {
+            "sticky_note" => converter.sticky(
+                bounds,
+                &content,
+                str_at(item, &["style", "fillColor"]).and_then(miro_color),
+            ),
+            "shape" => {
+                let fill = str_at(item, &["style", "fillColor"]).and_then(hex_color);
+                let text_color = str_at(item, &["style", "color"])
+                    .and_then(hex_color)
+                    .unwrap_or_else(|| {
+                        fill.as_deref()
+                            .map(readable_text_color)
+                            .unwrap_or(DEFAULT_TEXT_COLOR)
+                            .to_string()
+                    });
+                converter.push(
+                    bounds,
+                    ItemContent::Shape {
+                        shape_type: shape_type(
+                            str_at(item, &["data", "shape"]).unwrap_or_default(),
+                        ),
+                        fill_color: fill,
+                        border_color: str_at(item, &["style", "borderColor"])
+                            .and_then(hex_color)
+                            .unwrap_or_else(|| DEFAULT_BORDER_COLOR.to_string()),
+                        border_width: number_at(item, &["style", "borderWidth"])
+                            .unwrap_or(DEFAULT_BORDER_WIDTH),
+                    },
+                );
+                converter.text(
+                    bounds,
+                    &content,
+                    number_at(item, &["style", "fontSize"]).unwrap_or(DEFAULT_FONT_SIZE),
+                    &text_color,
+                );
+            }
+            "text" => converter.text(
+                bounds,
+                &content,
+                number_at(item, &["style", "fontSize"]).unwrap_or(DEFAULT_FONT_SIZE),
+                &str_at(item, &["style", "color"])
+                    .and_then(hex_color)
+                    .unwrap_or_else(|| DEFAULT_TEXT_COLOR.to_string()),
+            ),
+            "image" => {
+                let name = str_at(item, &["data", "title"])
+                    .filter(|title| !title.is_empty())
+                    .or_else(|| str_at(item, &["id"]))
+                    .unwrap_or("image")
+                    .to_string();
+                match str_at(item, &["data", "imageUrl"]).or_else(|| str_at(item, &["data", "url"]))
+                {
+                    Some(source) => converter.image(bounds, source, &name),
+                    None => converter
+                        .skipped
+                        .push(format!("Image '{}' has no address", name)),
+                }
+            }
+            other => converter.skipped.push(format!(
+                "{} '{}'",
+                item_kind_label(other),
+                str_at(item, &["id"]).unwrap_or_default()
+            )),
+        }
+    }
+
+    for connector in connectors {
+        let endpoint = |end: &str| {
+            id_at(connector, &[end, "id"])
+                .and_then(|id| bounds_by_id.get(&id).copied())
+                .map(Endpoint::Item)
+                .or_else(|| {
+                    Some(Endpoint::Point((
+                        number_at(connector, &[end, "position", "x"])?,
+                        number_at(connector, &[end, "position", "y"])?,
+                    )))
+                })
+        };
+        let (Some(start), Some(end)) = (endpoint("startItem"), endpoint("endItem")) else {
+            converter.skipped.push(format!(
+                "Connector '{}' between items that weren't imported",
+                str_at(connector, &["id"]).unwrap_or_default()
+            ));
+            continue;
+        };
+        let cap = |name: &str| str_at(connector, &["style", name]);
+        let label = connector
+            .get("captions")
+            .and_then(Value::as_array)
+            .map(|captions| {
+                captions
+                    .iter()
+                    .filter_map(|caption| str_at(caption, &["content"]).map(html_text))
+                    .collect::<Vec<_>>()
+                    .join(" ")
+            })
+            .unwrap_or_default();
+        converter.connector(Connector {
+            start,
+            end,
+            head_at_start: cap("startStrokeCap").is_some_and(|cap| cap != "none"),
+            head_at_end: cap("endStrokeCap") != Some("none"),
+            color: cap("strokeColor").and_then(hex_color),
+            thickness: number_at(connector, &["style", "strokeWidth"]),
+            label,
+        });
+    }
+
+    Ok(converter.finish())
+}
+
+/// Convert sticky notes exported from Miro as CSV, one per non-empty cell
+pub fn convert_miro_csv(csv: &str) -> Result<ConvertedWhiteboard, BoardError> {
+    let mut no_images = |_: &str| -> Result<PathBuf, String> { Err(String::new()) };
+    let mut converter = Converter::new(WhiteboardApp::Miro, &mut no_images);
+
+    let step = CSV_STICKY_SIZE + CSV_STICKY_GAP;
+    for (row, line) in csv.lines().enumerate() {
+        for (column, cell) in split_csv_line(line, ',').into_iter().enumerate() {
+            if cell.is_empty() {
+                continue;
+            }
+            converter.sticky(
+                (
+                    column as f32 * step,
+                    row as f32 * step,
+                    CSV_STICKY_SIZE,
+                    CSV_STICKY_SIZE,
+                ),
+                cell,
+                None,
+            );
+        }
+    }
+
+    Ok(converter.finish())
+}
+
+/// Convert a FigJam file document
+pub fn convert_figjam<'a>(
+    value: &Value,
+    resolve_image: &'a mut ImageResolver<'a>,
+) -> Result<ConvertedWhiteboard, BoardError> {
+    let document = value
+        .get("document")
+        .ok_or_else(|| BoardError::InvalidData("not a FigJam file".to_string()))?;
+    // Image fills name their image; the export may list where each is
+    let images: HashMap<String, String> = value
+        .get("images")
+        .or_else(|| value.get("meta")?.get("images"))
+        .and_then(Value::as_object)
+        .map(|images| {
+            images
+                .iter()
+                .filter_map(|(image, url)| Some((image.clone(), url.as_str()?.to_string())))
+                .collect()
+        })
+        .unwrap_or_default();
+
+    let mut converter = Converter::new(WhiteboardApp::FigJam, resolve_image);
+    let mut bounds_by_id: HashMap<String, Bounds> = HashMap::new();
+    let mut connectors = Vec::new();
+    figjam_node(
+        document,
+        &images,
+        &mut converter,
+        &mut bounds_by_id,
+        &mut connectors,
+    );
+
+    for connector in connectors {
+        let endpoint = |end: &str| {
+            id_at(connector, &[end, "endpointNodeId"])
+                .and_then(|id| bounds_by_id.get(&id).copied())
+                .map(Endpoint::Item)
+                .or_else(|| {
+                    Some(Endpoint::Point((
+                        number_at(connector, &[end, "position", "x"])?,
+                        number_at(connector, &[end, "position", "y"])?,
+                    )))
+                })
+        };
+        let name = str_at(connector, &["name"]).unwrap_or_default();
+        let (Some(start), Some(end)) = (endpoint("connectorStart"), endpoint("connectorEnd"))
+        else {
+            converter.skipped.push(format!(
+                "Connector '{}' between items that weren't imported",
+                name
+            ));
+            continue;
+        };
+        let cap = |name: &str| str_at(connector, &[name]);
+        converter.connector(Connector {
+            start,
+            end,
+            head_at_start: cap("connectorStartStrokeCap").is_some_and(|cap| cap != "NONE"),
+            head_at_end: cap("connectorEndStrokeCap").is_some_and(|cap| cap != "NONE"),
+            color: paint_color(connector, "strokes"),
+            thickness: number_at(connector, &["strokeWeight"]),
+            label: str_at(connector, &["characters"])
+                .unwrap_or_default()
+                .to_string(),
+        });
+    }
+
+    Ok(converter.finish())
+}
+
+/// Convert a FigJam node and its children, setting connectors aside for
+/// when every item they may connect is known
+fn figjam_node<'v>(
+    node: &'v Value,
+    images: &HashMap<String, String>,
+    converter: &mut Converter,
+    bounds_by_id: &mut HashMap<String, Bounds>,
+    connectors: &mut Vec<&'v Value>,
+) {
+    let kind = str_at(node, &["type"]).unwrap_or_default();
+    let name = str_at(node, &["name"]).unwrap_or_default();
+    let text = str_at(node, &["characters"]).unwrap_or_default();
+    let bounds = figjam_bounds(node);
+    if let (Some(id), Some(bounds)) = (id_at(node, &["id"]), bounds) {
+        bounds_by_id.insert(id, bounds);
+    }
+
+    match (kind, bounds) {
+        ("DOCUMENT" | "CANVAS" | "GROUP", _) => {}
+        ("CONNECTOR", _) => {
+            connectors.push(node);
+            return;
+        }
+        ("SECTION" | "FRAME", Some(bounds)) => {
+            converter.frame(bounds, name, paint_color(node, "fills"));
+        }
+        ("STICKY", Some(bounds)) => converter.sticky(bounds, text, paint_color(node, "fills")),
+        ("TEXT", Some(bounds)) => converter.text(
+            bounds,
+            text,
+            number_at(node, &["style", "fontSize"]).unwrap_or(DEFAULT_FONT_SIZE),
+            &paint_color(node, "fills").unwrap_or_else(|| DEFAULT_TEXT_COLOR.to_string()),
+        ),
+        ("SHAPE_WITH_TEXT" | "RECTANGLE" | "ELLIPSE", Some(bounds)) => {
+            if let Some(image) = image_fill(node) {
+                match images.get(image) {
+                    Some(url) => converter.image(bounds, url, name),
+                    None => converter
+                        .skipped
+                        .push(format!("Image '{}' isn't included in the export", name)),
+                }
+                return;
+            }
+            let fill = paint_color(node, "fills");
+            let text_color = fill
+                .as_deref()
+                .map(readable_text_color)
+                .unwrap_or(DEFAULT_TEXT_COLOR)
+                .to_string();
+            let shape = if kind == "SHAPE_WITH_TEXT" {
+                str_at(node, &["shapeType"]).unwrap_or_default()
+            } else {
+                kind
+            };
+            converter.push(
+                bounds,
+                ItemContent::Shape {
+                    shape_type: shape_type(shape),
+                    fill_color: fill,
+                    border_color: paint_color(node, "strokes")
+                        .unwrap_or_else(|| DEFAULT_BORDER_COLOR.to_string()),
+                    border_width: number_at(node, &["strokeWeight"])
+                        .unwrap_or(DEFAULT_BORDER_WIDTH),
+                },
+            );
+            converter.text(bounds, text, DEFAULT_FONT_SIZE, &text_color);
+        }
+        (other, _) => {
+            converter
+                .skipped
+                .push(format!("{} '{}'", item_kind_label(other), name));
+            return;
+        }
+    }
+
+    if let Some(children) = node.get("children").and_then(Value::as_array) {
+        for child in children {
+            figjam_node(child, images, converter, bounds_by_id, connectors);
+        }
+    }
+}
+
+/// Read an export file and convert it into items to paste on a board, with
+/// notes on what was skipped.
+///
+/// Images at URLs are downloaded into `storage_dir`, the board's file
+/// storage; images next to the export are copied into the board when the
+/// items are pasted, like files from another board.
+pub fn import_whiteboard(
+    path: &Path,
+    storage_dir: &Path,
+) -> Result<(ConvertedWhiteboard, CopiedItems), BoardError> {
+    let contents = fs::read_to_string(path).map_err(|e| BoardError::LoadFailed {
+        path: path.to_path_buf(),
+        source: e,
+    })?;
+    let export_dir = path.parent().unwrap_or(Path::new(".")).to_path_buf();
+    let is_csv = path
+        .extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(|e| e.eq_ignore_ascii_case("csv"));
+
+    let mut resolve_image = |source: &str| -> Result<PathBuf, String> {
+        if source.starts_with("http://") || source.starts_with("https://") {
+            let bytes = download_image(source)?;
+            let extension =
+                pasted_image_extension(&bytes).ok_or_else(|| "it isn't an image".to_string())?;
+            write_pasted_file(storage_dir, extension, &bytes).map_err(|e| e.to_string())
+        } else {
+            let file = export_dir.join(source);
+            if file.is_file() {
+                Ok(file)
+            } else {
+                Err("the file is missing".to_string())
+            }
+        }
+    };
+    let mut converted = convert_whiteboard(&contents, is_csv, &mut resolve_image)?;
+
+    let copied = CopiedItems {
+        board_id: IMPORT_BOARD_ID.to_string(),
+        board_dir: export_dir,
+        items: std::mem::take(&mut converted.items),
+        data_sources: Vec::new(),
+    };
+    Ok((converted, copied))
+}
+
+/// Plain text of Miro's rich text: tags dropped, paragraphs and line
+/// breaks kept as newlines, entities decoded
+pub fn html_text(html: &str) -> String {
+    let mut text = String::new();
+    let mut tag: Option<String> = None;
+    for c in html.chars() {
+        let Some(name) = tag.as_mut() else {
+            if c == '<' {
+                tag = Some(String::new());
+            } else {
+                text.push(c);
+            }
+            continue;
+        };
+        if c != '>' {
+            name.push(c);
+            continue;
+        }
+        let closing = name.starts_with('/');
+        let name = name
+            .trim_start_matches('/')
+            .split(|c: char| c.is_whitespace() || c == '/')
+            .next()
+            .unwrap_or_default()
+            .to_ascii_lowercase();
+        if name == "br" || (closing && matches!(name.as_str(), "p" | "div" | "li")) {
+            text.push('\n');
+        }
+        tag = None;
+    }
+    text.replace("&nbsp;", " ")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+        .trim_end()
+        .to_string()
+}
+
+/// Hex color for one of Miro's named sticky note colors, or a hex color
+pub fn miro_color(color: &str) -> Option<String> {
+    let named = match color {
+        "gray" => "#f5f6f8",
+        "light_yellow" => "#fff9b1",
+        "yellow" => "#f5d128",
+        "orange" => "#ff9d48",
+        "light_green" => "#d5f692",
+        "green" => "#c9df56",
+        "dark_green" => "#93d275",
+        "cyan" => "#67c6c0",
+        "light_pink" => "#ffcee0",
+        "pink" => "#ea94bb",
+        "violet" => "#c6a2d2",
+        "red" => "#f0939d",
+        "light_blue" => "#a6ccf5",
+        "blue" => "#6cd8fa",
+        "dark_blue" => "#9ea9ff",
+        "black" => "#000000",
+        other => return hex_color(other),
+    };
+    Some(named.to_string())
+}
+
+/// A `#rrggbb` or `#rgb` color, lowercased and in the long form
+fn hex_color(color: &str) -> Option<String> {
+    let digits = color.trim().strip_prefix('#')?;
+    if !digits.chars().all(|c| c.is_ascii_hexdigit()) {
+        return None;
+    }
+    match digits.len() {
+        6 => Some(format!("#{}", digits.to_lowercase())),
+        3 => Some(
+            digits
+                .chars()
+                .fold(String::from("#"), |mut hex, c| {
+                    hex.push(c);
+                    hex.push(c);
+                    hex
+                })
+                .to_lowercase(),
+        ),
+        _ => None,
+    }
+}
+
+/// Dark text on light fills, white text on dark ones
+fn readable_text_color(fill: &str) -> &'static str {
+    match parse_hex(fill) {
+        Some(rgb) => {
+            let [r, g, b, _] = rgb.0;
+            let luma = 0.299 * r as f32 + 0.587 * g as f32 + 0.114 * b as f32;
+            if luma < 128.0 {
+                DEFAULT_TEXT_COLOR
+            } else {
+                DARK_TEXT_COLOR
+            }
+        }
+        None => DARK_TEXT_COLOR,
+    }
+}
+
+/// Shape for Miro's and FigJam's shape names; ones without a match here
+/// (triangles, stars, ...) become rectangles
+fn shape_type(shape: &str) -> ShapeType {
+    match shape.to_ascii_lowercase().as_str() {
+        "round_rectangle" | "rounded_rectangle" => ShapeType::RoundedRect,
+        "circle" | "ellipse" => ShapeType::Ellipse,
+        _ => ShapeType::Rectangle,
+    }
+}
+
+/// "Card", "Embed", ... for skipped items of a type like `app_card`
+fn item_kind_label(kind: &str) -> String {
+    let words = kind.to_lowercase().replace('_', " ");
+    let mut chars = words.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => "Item".to_string(),
+    }
+}
+
+/// Top-left bounds of a Miro item from its center position and size,
+/// offset by the top-left of the frame it is in
+fn miro_bounds(item: &Value, parent: Option<Bounds>) -> Bounds {
+    let width = number_at(item, &["geometry", "width"]).unwrap_or(CSV_STICKY_SIZE);
+    let height = number_at(item, &["geometry", "height"]).unwrap_or(width);
+    let mut x = number_at(item, &["position", "x"]).unwrap_or_default();
+    let mut y = number_at(item, &["position", "y"]).unwrap_or_default();
+    if str_at(item, &["position", "origin"]).is_none_or(|origin| origin == "center") {
+        x -= width / 2.0;
+        y -= height / 2.0;
+    }
+    if let Some((px, py, _, _)) = parent {
+        x += px;
+        y += py;
+    }
+    (x, y, width, height)
+}
+
+fn figjam_bounds(node: &Value) -> Option<Bounds> {
+    Some((
+        number_at(node, &["absoluteBoundingBox", "x"])?,
+        number_at(node, &["absoluteBoundingBox", "y"])?,
+        number_at(node, &["absoluteBoundingBox", "width"])?,
+        number_at(node, &["absoluteBoundingBox", "height"])?,
+    ))
+}
+
+/// Hex color of the first visible solid paint in a FigJam node's `fills`
+/// or `strokes`
+fn paint_color(node: &Value, paints: &str) -> Option<String> {
+    node.get(paints)?
+        .as_array()?
+        .iter()
+        .filter(|paint| paint.get("visible").and_then(Value::as_bool) != Some(false))
+        .find(|paint| str_at(paint, &["type"]) == Some("SOLID"))
+        .and_then(|paint| {
+            let channel = |name: &str| {
+                number_at(paint, &["color", name])
+                    .map(|c| (c.clamp(0.0, 1.0) * 255.0).round() as u8)
+            };
+            Some(format!(
+                "#{:02x}{:02x}{:02x}",
+                channel("r")?,
+                channel("g")?,
+                channel("b")?
+            ))
+        })
+}
+
+/// Image shown by a FigJam node's image fill
+fn image_fill(node: &Value) -> Option<&str> {
+    node.get("fills")?
+        .as_array()?
+        .iter()
+        .find(|paint| str_at(paint, &["type"]) == Some("IMAGE"))
+        .and_then(|paint| str_at(paint, &["imageRef"]))
+}
+
+/// Nearest side middle of `bounds` to `point`
+fn nearest_side(bounds: Bounds, point: (f32, f32)) -> (f32, f32) {
+    side_anchor(bounds, (point.0, point.1, 0.0, 0.0), None)
+}
+
+fn at<'v>(value: &'v Value, path: &[&str]) -> Option<&'v Value> {
+    path.iter().try_fold(value, |value, key| value.get(key))
+}
+
+fn str_at<'v>(value: &'v Value, path: &[&str]) -> Option<&'v str> {
+    at(value, path)?.as_str()
+}
+
+/// A number, which Miro sometimes writes as a string
+fn number_at(value: &Value, path: &[&str]) -> Option<f32> {
+    let value = at(value, path)?;
+    value
+        .as_f64()
+        .map(|n| n as f32)
+        .or_else(|| value.as_str()?.trim().parse().ok())
+}
+
+/// An ID, which may be a string or a number
+fn id_at(value: &Value, path: &[&str]) -> Option<String> {
+    let value = at(value, path)?;
+    value
+        .as_str()
+        .map(str::to_string)
+        .or_else(|| value.as_u64().map(|id| id.to_string()))
+}
@@ -0,0 +1,191 @@
+//! Wiki links between markdown cards.
+//!
+//! A markdown card links to another with `[[Name]]`, where the name is the
+//! other card's title or file name (without `.md`), matched ignoring case.
+//! `[[Name|label]]` and `[[Name#heading]]` link to the same card. Links in
+//! code blocks and inline code are ignored.
+//!
+//! [`WikiLinks`] keeps the links of a board's cards up to date: the canvas
+//! draws a connector for each link and cards list the cards linking to them
+//! as backlinks.
+
+use crate::types::{CanvasItem, ItemContent};
+use std::collections::HashMap;
+
+/// Link targets written in markdown text, in order, without duplicates
+pub fn parse_wiki_links(content: &str) -> Vec<String> {
+    let mut targets: Vec<String> = Vec::new();
+    let mut in_fence = false;
+
+    for line in content.lines() {
+        let trimmed = line.trim_start();
+        if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            in_fence = !in_fence;
+            continue;
+        }
+        if in_fence {
+            continue;
+        }
+
+        // Inline code spans alternate with text at each backtick
+        for (i, part) in line.split('`').enumerate() {
+            if i % 2 == 1 {
+                continue;
+            }
+            let mut rest = part;
+            while let Some(start) = rest.find("[[") {
+                let after = &rest[start + 2..];
+                let Some(end) = after.find("]]") else {
+                    break;
+                };
+                let target = link_target(&after[..end]);
+                if !target.is_empty() && !targets.contains(&target) {
+                    targets.push(target);
+                }
+                rest = &after[end + 2..];
+            }
+        }
+    }
+    targets
+}
+
+/// The card name of a link's inner text, dropping any label or heading
+fn link_target(inner: &str) -> String {
+    let end = inner.find(['|', '#']).unwrap_or(inner.len());
+    inner[..end].trim().to_string()
+}
+
+/// Normalized form of a card name, for matching links to cards
+pub fn link_key(name: &str) -> String {
+    let name = name.trim();
+    let name = name
+        .strip_suffix(".md")
+        .or_else(|| name.strip_suffix(".MD"))
+        .unwrap_or(name);
+    name.to_lowercase()
+}
+
+/// Names a markdown card can be linked by: its title and file name
+fn card_keys(item: &CanvasItem) -> Vec<String> {
+    let ItemContent::Markdown { path, title, .. } = &item.content else {
+        return Vec::new();
+    };
+    let mut keys = vec![link_key(title)];
+    if let Some(stem) = path.file_stem().and_then(|stem| stem.to_str()) {
+        let key = link_key(stem);
+        if !keys.contains(&key) {
+            keys.push(key);
+        }
+    }
+    keys
+}
+
+/// The wiki links between a board's markdown cards
+#[derive(Default)]
+pub struct WikiLinks {
+    /// Content each card was last parsed from, and the targets found in it
+    parsed: HashMap<u64, (String, Vec<String>)>,
+    /// (from, to) card pairs, in board order
+    edges: Vec<(u64, u64)>,
+}
+
+impl WikiLinks {
+    /// Bring the links up to date with the board's items. Only cards whose
+    /// text changed are parsed again.
+    pub fn update(&mut self, items: &[CanvasItem]) {
+        let mut parsed = HashMap::new();
+        let mut cards_by_key: HashMap<String, Vec<u64>> = HashMap::new();
+
+        for item in items {
+            let ItemContent::Markdown { content, .. } = &item.content else {
+                continue;
+            };
+            let entry = match self.parsed.remove(&item.id) {
+                Some(entry) if entry.0 == *content => entry,
+                _ => (content.clone(), parse_wiki_links(content)),
+            };
+            parsed.insert(item.id, entry);
+            for key in card_keys(item) {
+                cards_by_key.entry(key).or_default().push(item.id);
+            }
+        }
+
+        let mut edges = Vec::new();
+        for item in items {
+            let Some((_, targets)) = parsed.get(&item.id) else {
+                continue;
+            };
+            for target in targets {
+                for &to in cards_by_key.get(&link_key(target)).into_iter().flatten() {
+                    if to != item.id && !edges.contains(&(item.id, to)) {
+                        edges.push((item.id, to));
+                    }
+                }
+            }
+        }
+
+        self.parsed = parsed;
+        self.edges = edges;
+    }
+
+    /// (from, to) card pairs of every link
+    pub fn edges(&self) -> &[(u64, u64)] {
+        &self.edges
+    }
+
+    /// Cards linking to `id`
+    pub fn backlinks(&self, id: u64) -> Vec<u64> {
+        self.edges
+            .iter()
+            .filter(|(_, to)| *to == id)
+            .map(|(from, _)| *from)
+            .collect()
+    }
+
+    /// Titles of the cards linking to `id`, in board order
+    pub fn backlink_titles(&self, id: u64, items: &[CanvasItem]) -> Vec<String> {
+        let backlinks = self.backlinks(id);
+        items
+            .iter()
+            .filter(|item| backlinks.contains(&item.id))
+            .filter_map(|item| match &item.content {
+                ItemContent::Markdown { title, .. } => Some(title.clone()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Whether `to` also links back to `from`
+    pub fn is_mutual(&self, from: u64, to: u64) -> bool {
+        self.edges.contains(&(to, from))
+    }
+}
+
+/// Where a connector between two items starts and ends: the midpoints of
+/// their facing sides. Items are given as ((x, y), (width, height)).
+pub fn connector_endpoints(
+    from: ((f32, f32), (f32, f32)),
+    to: ((f32, f32), (f32, f32)),
+) -> ((f32, f32), (f32, f32)) {
+    let center = |((x, y), (w, h)): ((f32, f32), (f32, f32))| (x + w / 2.0, y + h / 2.0);
+    let (from_center, to_center) = (center(from), center(to));
+    let dx = to_center.0 - from_center.0;
+    let dy = to_center.1 - from_center.1;
+
+    // Side facing the other item: left/right when they are further apart
+    // horizontally, top/bottom otherwise
+    let side = |((x, y), (w, h)): ((f32, f32), (f32, f32)), toward: f32, horizontal: bool| {
+        let (cx, cy) = (x + w / 2.0, y + h / 2.0);
+        if horizontal {
+            (cx + toward.signum() * w / 2.0, cy)
+        } else {
+            (cx, cy + toward.signum() * h / 2.0)
+        }
+    };
+    let horizontal = dx.abs() >= dy.abs();
+    let (toward_from, toward_to) = if horizontal { (dx, -dx) } else { (dy, -dy) };
+    (
+        side(from, toward_from, horizontal),
+        side(to, toward_to, horizontal),
+    )
+}
@@ -7,8 +7,14 @@ use crate::helpers::{
     TestBoardBuilder,
 };
 use humanboard::board::{Board, UndoOperation};
-use humanboard::types::ItemContent;
+use humanboard::canvas_background::CanvasBackground;
+use humanboard::file_import::ImportedFile;
+use humanboard::item_clipboard::data_source_of;
+use humanboard::preview_session::{PreviewSession, SessionTab, SessionTabSource};
+use humanboard::types::{DataSource, ItemContent, MediaVolume};
+use humanboard::url_paste::{self, PageDetails};
 use gpui::{point, px};
+use std::path::PathBuf;
 
 // Match the constant from board.rs for delta-based history
 const MAX_HISTORY_OPERATIONS: usize = 100;
@@ -192,6 +198,22 @@ fn test_find_items_by_name() {
     assert_eq!(results.len(), 0);
 }
 
+#[test]
+fn test_ranked_items_score_best_first() {
+    let board = board_with_texts(&["Chart notes", "chart"]);
+
+    let ranked = board.ranked_items("chart");
+    assert_eq!(ranked.len(), 2);
+    assert!(ranked[0].0 >= ranked[1].0);
+    assert_eq!(
+        board.find_items("chart"),
+        ranked
+            .into_iter()
+            .map(|(_, id, name)| (id, name))
+            .collect::<Vec<_>>()
+    );
+}
+
 #[test]
 fn test_find_items_case_insensitive() {
     let board = board_with_text("Hello World");
@@ -748,3 +770,780 @@ fn test_undo_redo_preserves_item_id() {
     board.redo();
     assert_eq!(board.items[0].id, original_id);
 }
+
+#[test]
+fn test_duplicate_items_copies_in_place() {
+    let mut board = TestBoardBuilder::new()
+        .with_text_item("First", (10.0, 20.0))
+        .with_text_item("Second", (300.0, 20.0))
+        .build();
+
+    let pairs = board.duplicate_items(&[1, 999]);
+    assert_eq!(pairs, vec![(1, 2)]);
+    assert_item_count(&board, 3);
+
+    let copy = board.get_item(2).unwrap();
+    assert_eq!(copy.position, (300.0, 20.0));
+    assert!(matches!(&copy.content, ItemContent::Text(text) if text == "Second"));
+    // Copies stack on top and are found by hit testing
+    assert_eq!(board.items.last().unwrap().id, 2);
+    assert!(board.query_items_at_point(310.0, 30.0).contains(&2));
+    assert_eq!(board.next_item_id, 3);
+}
+
+#[test]
+fn test_nudge_burst_is_one_undo_step() {
+    let mut board = TestBoardBuilder::new()
+        .with_text_item("First", (0.0, 0.0))
+        .with_text_item("Second", (100.0, 0.0))
+        .build();
+    let history_before = board.history_len();
+
+    assert!(board.nudge_items(&[0, 1], (1.0, 0.0), false));
+    for _ in 0..9 {
+        assert!(board.nudge_items(&[0, 1], (1.0, 0.0), true));
+    }
+    assert_eq!(board.history_len(), history_before + 1);
+    assert_eq!(board.get_item(0).unwrap().position, (10.0, 0.0));
+    assert_eq!(board.get_item(1).unwrap().position, (110.0, 0.0));
+
+    assert!(board.undo());
+    assert_eq!(board.get_item(0).unwrap().position, (0.0, 0.0));
+    assert_eq!(board.get_item(1).unwrap().position, (100.0, 0.0));
+
+    assert!(board.redo());
+    assert_eq!(board.get_item(0).unwrap().position, (10.0, 0.0));
+}
+
+#[test]
+fn test_nudge_of_other_items_starts_new_undo_step() {
+    let mut board = TestBoardBuilder::new()
+        .with_text_item("First", (0.0, 0.0))
+        .with_text_item("Second", (100.0, 0.0))
+        .build();
+    let history_before = board.history_len();
+
+    board.nudge_items(&[0], (0.0, 10.0), false);
+    board.nudge_items(&[1], (0.0, 10.0), true);
+    assert_eq!(board.history_len(), history_before + 2);
+
+    board.undo();
+    assert_eq!(board.get_item(0).unwrap().position, (0.0, 10.0));
+    assert_eq!(board.get_item(1).unwrap().position, (100.0, 0.0));
+}
+
+#[test]
+fn test_set_locked_is_undoable() {
+    let mut board = TestBoardBuilder::new()
+        .with_text_item("First", (0.0, 0.0))
+        .with_text_item("Second", (100.0, 0.0))
+        .build();
+
+    assert!(board.set_locked(&[0, 1], true));
+    assert!(board.is_locked(0) && board.is_locked(1));
+    assert!(board.unlocked_ids([0, 1]).is_empty());
+    assert!(!board.set_locked(&[0], true));
+
+    assert!(board.undo());
+    assert!(!board.is_locked(0) && !board.is_locked(1));
+}
+
+#[test]
+fn test_set_code_lines_is_undoable() {
+    let mut board = TestBoardBuilder::new()
+        .with_code_item("/src/main.rs", "rust", (0.0, 0.0))
+        .with_text_item("Note", (300.0, 0.0))
+        .build();
+    let lines = |board: &Board| match &board.get_item(0).unwrap().content {
+        ItemContent::Code { lines, .. } => *lines,
+        _ => panic!("expected a code item"),
+    };
+
+    assert!(board.set_code_lines(0, Some((10, 40))));
+    assert_eq!(lines(&board), Some((10, 40)));
+    assert!(!board.set_code_lines(0, Some((10, 40))));
+    assert!(!board.set_code_lines(1, Some((1, 2))));
+
+    assert!(board.undo());
+    assert_eq!(lines(&board), None);
+}
+
+#[test]
+fn test_reorder_items_changes_stacking() {
+    use humanboard::arrange::ZOrder;
+    use std::collections::HashSet;
+
+    let mut board = TestBoardBuilder::new()
+        .with_text_item("First", (0.0, 0.0))
+        .with_text_item("Second", (100.0, 0.0))
+        .with_text_item("Third", (200.0, 0.0))
+        .build();
+    let ids = |board: &Board| board.items.iter().map(|item| item.id).collect::<Vec<_>>();
+
+    let selected: HashSet<u64> = [0].into_iter().collect();
+    assert!(board.reorder_items(&selected, ZOrder::BringToFront));
+    assert_eq!(ids(&board), vec![1, 2, 0]);
+    assert!(!board.reorder_items(&selected, ZOrder::BringForward));
+
+    assert!(board.reorder_items(&selected, ZOrder::SendToBack));
+    assert_eq!(ids(&board), vec![0, 1, 2]);
+}
+
+#[test]
+fn test_items_in_rect_are_back_to_front() {
+    let board = TestBoardBuilder::new()
+        .with_text_item("First", (0.0, 0.0))
+        .with_text_item("Far away", (1000.0, 0.0))
+        .with_text_item("On top", (50.0, 50.0))
+        .build();
+
+    let ids: Vec<u64> = board
+        .items_in_rect(0.0, 0.0, 400.0, 200.0)
+        .iter()
+        .map(|item| item.id)
+        .collect();
+    assert_eq!(ids, vec![0, 2]);
+    assert_eq!(board.topmost_item_at(60.0, 60.0), Some(2));
+    assert_eq!(board.topmost_item_at(10.0, 10.0), Some(0));
+    assert_eq!(board.topmost_item_at(600.0, 10.0), None);
+}
+
+#[test]
+fn test_items_in_viewport_follow_pan_and_zoom() {
+    let board = TestBoardBuilder::new()
+        .with_text_item("First", (0.0, 0.0))
+        .with_text_item("Far away", (1000.0, 0.0))
+        .with_offset(-900.0, 0.0)
+        .build();
+
+    let ids: Vec<u64> = board
+        .items_in_viewport((400.0, 300.0), 0.0)
+        .iter()
+        .map(|item| item.id)
+        .collect();
+    assert_eq!(ids, vec![1]);
+}
+
+#[test]
+fn test_spatial_index_follows_moved_items() {
+    let mut board = TestBoardBuilder::new()
+        .with_text_item("Moving", (0.0, 0.0))
+        .build();
+
+    board.get_item_mut(0).unwrap().position = (0.0, 500.0);
+    board.update_spatial_index(0);
+
+    assert_eq!(board.topmost_item_at(10.0, 10.0), None);
+    assert_eq!(board.topmost_item_at(10.0, 510.0), Some(0));
+}
+
+fn add_image(board: &mut Board, path: &str) -> u64 {
+    board.add_item(point(px(0.0), px(0.0)), ItemContent::Image(path.into()))
+}
+
+#[test]
+fn test_added_image_is_queued_for_measuring() {
+    let mut board = empty_board();
+    let id = add_image(&mut board, "/photos/a.png");
+    board.add_item(point(px(0.0), px(0.0)), ItemContent::Text("note".into()));
+
+    let images = board.take_unmeasured_images();
+    assert_eq!(images, vec![(id, "/photos/a.png".into())]);
+    assert!(board.take_unmeasured_images().is_empty());
+}
+
+#[test]
+fn test_apply_image_size_survives_undo_redo() {
+    let mut board = empty_board();
+    let id = add_image(&mut board, "/photos/a.png");
+
+    assert!(board.apply_image_size(id, (320.0, 240.0)));
+    assert_eq!(board.get_item(id).unwrap().size, (320.0, 240.0));
+    assert_eq!(board.topmost_item_at(300.0, 200.0), Some(id));
+
+    board.undo();
+    board.redo();
+    assert_eq!(board.get_item(id).unwrap().size, (320.0, 240.0));
+}
+
+#[test]
+fn test_apply_image_size_skips_resized_items() {
+    let mut board = empty_board();
+    let id = add_image(&mut board, "/photos/a.png");
+    board.get_item_mut(id).unwrap().size = (100.0, 100.0);
+
+    assert!(!board.apply_image_size(id, (320.0, 240.0)));
+    assert_eq!(board.get_item(id).unwrap().size, (100.0, 100.0));
+}
+
+#[test]
+fn test_set_background_is_saved_but_not_undoable() {
+    let mut board = board_with_text("note");
+    board.set_background(Some(CanvasBackground::Lines));
+
+    assert_eq!(board.background(), CanvasBackground::Lines);
+    assert_eq!(board.to_state().background, Some(CanvasBackground::Lines));
+    assert!(board.is_dirty());
+
+    board.undo();
+    assert!(board.items.is_empty());
+    assert_eq!(board.background(), CanvasBackground::Lines);
+}
+
+#[test]
+fn test_playback_position_ignores_start_and_small_moves() {
+    let mut board = board_with_text("song");
+    board.set_playback_position(0, Some(0.5));
+    assert_eq!(board.playback_position(0), None);
+
+    board.set_playback_position(0, Some(42.0));
+    board.set_playback_position(0, Some(42.4));
+    assert_eq!(board.playback_position(0), Some(42.0));
+
+    board.set_playback_position(0, None);
+    assert_eq!(board.playback_position(0), None);
+}
+
+#[test]
+fn test_playback_positions_saved_for_existing_items() {
+    let mut board = board_with_texts(&["a", "b"]);
+    board.set_playback_position(0, Some(12.0));
+    board.set_playback_position(1, Some(30.0));
+    assert!(board.remove_item(1));
+
+    let state = board.to_state();
+    assert_eq!(state.playback_positions.get(&0), Some(&12.0));
+    assert!(!state.playback_positions.contains_key(&1));
+}
+
+#[test]
+fn test_add_youtube_url_starts_at_timestamp() {
+    let mut board = empty_board();
+    let origin = point(px(0.0), px(0.0));
+    board.add_url("https://youtu.be/dQw4w9WgXcQ?t=1m30s", origin);
+    board.add_url("https://www.youtube.com/watch?v=dQw4w9WgXcQ", origin);
+
+    assert!(matches!(&board.items[0].content, ItemContent::YouTube(id) if id == "dQw4w9WgXcQ"));
+    assert_eq!(board.playback_position(0), Some(90.0));
+    assert_eq!(board.playback_position(1), None);
+}
+
+#[test]
+fn test_media_volume_stored_on_item() {
+    let mut board = board_with_text("song");
+    assert_eq!(board.media_volume(0), MediaVolume::default());
+
+    let quiet = MediaVolume::default().with_level(0.25);
+    assert!(board.set_media_volume(0, quiet));
+    assert!(!board.set_media_volume(0, quiet));
+    assert_eq!(board.media_volume(0), quiet);
+    assert_eq!(board.to_state().items[0].volume, Some(quiet));
+    assert!(board.is_dirty());
+
+    // Full volume is the default and isn't stored
+    assert!(board.set_media_volume(0, MediaVolume::default()));
+    assert_eq!(board.items[0].volume, None);
+    assert!(!board.set_media_volume(99, quiet));
+}
+
+#[test]
+fn test_set_pdf_page_stored_on_item() {
+    let mut board = TestBoardBuilder::new()
+        .with_pdf_item("/docs/report.pdf", (0.0, 0.0))
+        .with_text_item("note", (400.0, 0.0))
+        .build();
+
+    assert!(board.set_pdf_page(0, 3));
+    assert!(!board.set_pdf_page(0, 3));
+    assert!(matches!(board.items[0].content, ItemContent::Pdf { page: 3, .. }));
+    assert!(board.is_dirty());
+
+    // Only PDFs have pages
+    assert!(!board.set_pdf_page(1, 1));
+    assert!(!board.set_pdf_page(99, 1));
+}
+
+#[test]
+fn test_set_pdf_thumbnail_stored_on_item() {
+    let mut board = TestBoardBuilder::new()
+        .with_pdf_item("/docs/report.pdf", (0.0, 0.0))
+        .with_text_item("note", (400.0, 0.0))
+        .build();
+    let thumbnail = PathBuf::from("/tmp/humanboard/pdf_thumbnails/abc_thumb.png");
+
+    assert!(board.set_pdf_thumbnail(0, thumbnail.clone()));
+    assert!(!board.set_pdf_thumbnail(0, thumbnail.clone()));
+    assert!(matches!(
+        &board.items[0].content,
+        ItemContent::Pdf { thumbnail: Some(t), .. } if *t == thumbnail
+    ));
+    assert!(board.is_dirty());
+
+    // Only PDFs have thumbnails
+    assert!(!board.set_pdf_thumbnail(1, thumbnail.clone()));
+    assert!(!board.set_pdf_thumbnail(99, thumbnail));
+}
+
+#[test]
+fn test_set_markdown_content_updates_every_card_of_file() {
+    let mut board = TestBoardBuilder::new()
+        .with_markdown_item("/notes/plan.md", "plan", "# Plan", (0.0, 0.0))
+        .with_markdown_item("/notes/plan.md", "plan", "# Plan", (300.0, 0.0))
+        .with_markdown_item("/notes/other.md", "other", "# Other", (600.0, 0.0))
+        .build();
+    let history_len = board.history_len();
+
+    let path = PathBuf::from("/notes/plan.md");
+    assert_eq!(board.set_markdown_content(&path, "# Plan v2"), 2);
+    assert_eq!(board.set_markdown_content(&path, "# Plan v2"), 0);
+    assert!(matches!(
+        &board.items[1].content,
+        ItemContent::Markdown { content, .. } if content == "# Plan v2"
+    ));
+    assert!(matches!(
+        &board.items[2].content,
+        ItemContent::Markdown { content, .. } if content == "# Other"
+    ));
+    assert!(board.is_dirty());
+    assert_eq!(board.history_len(), history_len);
+}
+
+#[test]
+fn test_preview_session_saved_but_not_undoable() {
+    let mut board = board_with_text("note");
+    let session = PreviewSession {
+        tabs: vec![SessionTab {
+            source: SessionTabSource::File(PathBuf::from("/docs/report.pdf")),
+            pinned: false,
+            preview: false,
+            page: Some(4),
+        }],
+        active_tab: 0,
+        right_tabs: Vec::new(),
+        right_active_tab: 0,
+        below_canvas: false,
+        size: 0.4,
+        panes_stacked: false,
+        pane_ratio: 0.5,
+        right_focused: false,
+        more_panes: Vec::new(),
+        layout: None,
+    };
+
+    board.set_preview_session(Some(session.clone()));
+    assert!(board.is_dirty());
+    assert_eq!(board.preview_session(), Some(&session));
+    assert_eq!(board.to_state().preview_session.as_ref(), Some(&session));
+
+    board.undo();
+    assert!(board.items.is_empty());
+    assert_eq!(board.preview_session(), Some(&session));
+
+    board.set_preview_session(None);
+    assert!(board.to_state().preview_session.is_none());
+}
+
+#[test]
+fn test_pdf_highlight_added_once_per_passage() {
+    let mut board = board_with_texts(&["note"]);
+    let path = PathBuf::from("/docs/report.pdf");
+
+    let first = board.add_pdf_highlight(path.clone(), 2, 40, "Revenue".to_string());
+    let again = board.add_pdf_highlight(path.clone(), 2, 40, "Revenue".to_string());
+    let other = board.add_pdf_highlight(path, 3, 0, "Costs".to_string());
+
+    assert_eq!(first, again);
+    assert_ne!(first, other);
+    assert_eq!(board.pdf_highlights().len(), 2);
+    assert!(board.is_dirty());
+
+    assert!(board.remove_pdf_highlight(other));
+    assert!(!board.remove_pdf_highlight(other));
+    assert_eq!(board.pdf_highlights().len(), 1);
+}
+
+#[test]
+fn test_pdf_highlight_note_unlinked_once_deleted() {
+    let mut board = board_with_texts(&["note"]);
+    let path = PathBuf::from("/docs/report.pdf");
+    let id = board.add_pdf_highlight(path, 0, 0, "Q3".to_string());
+    assert!(board.set_highlight_note(id, 0));
+    assert!(!board.set_highlight_note(id + 1, 0));
+    assert_eq!(board.highlight_for_note(0).map(|h| h.id), Some(id));
+
+    assert!(board.remove_item(0));
+    let state = board.to_state();
+    assert_eq!(state.pdf_highlights.len(), 1);
+    assert_eq!(state.pdf_highlights[0].note_item, None);
+}
+
+#[test]
+fn test_undo_steps_are_labelled() {
+    let mut board = board_with_texts(&["Alpha", "Beta"]);
+    assert!(board.move_items(&[(0, (50.0, 50.0)), (1, (80.0, 80.0))]));
+    board.push_checkpoint();
+    board.items[0].content = ItemContent::Text("Alpha 2".to_string());
+    board.push_labeled_history("Edited text");
+
+    let labels: Vec<String> = board.undo_steps().into_iter().map(|s| s.label).collect();
+    assert_eq!(
+        labels,
+        vec!["Added Alpha", "Added Beta", "Moved 2 items", "Edited text"]
+    );
+    // The checkpoint isn't a step of its own
+    assert_eq!(board.undo_steps()[3].position, 5);
+    assert_eq!(board.history_position(), 5);
+
+    assert!(board.undo());
+    assert!(matches!(&board.items[0].content, ItemContent::Text(t) if t == "Alpha"));
+    assert_eq!(board.history_position(), 3);
+    // Undone steps stay listed until replaced
+    assert_eq!(board.undo_steps().len(), 4);
+
+    board.add_item(point(px(0.0), px(0.0)), ItemContent::Text("Gamma".to_string()));
+    let labels: Vec<String> = board.undo_steps().into_iter().map(|s| s.label).collect();
+    assert_eq!(labels.last().map(String::as_str), Some("Added Gamma"));
+    assert_eq!(labels.len(), 4);
+}
+
+#[test]
+fn test_jump_to_history() {
+    let mut board = board_with_texts(&["A", "B", "C"]);
+
+    assert!(board.jump_to_history(1));
+    assert_item_count(&board, 1);
+    assert_eq!(board.history_position(), 1);
+    assert!(board.can_redo());
+
+    assert!(board.jump_to_history(3));
+    assert_item_count(&board, 3);
+    assert!(!board.can_redo());
+
+    assert!(board.jump_to_history(0));
+    assert_item_count(&board, 0);
+    assert!(!board.can_undo());
+}
+
+#[test]
+fn test_undo_steps_over_periodic_checkpoints() {
+    let mut board = Board::new_for_test();
+    for i in 0..25 {
+        board.add_item(
+            point(px(i as f32 * 10.0), px(0.0)),
+            ItemContent::Text(format!("Item {}", i)),
+        );
+    }
+    let steps = board.undo_steps();
+    assert_eq!(steps.len(), 25);
+    assert_eq!(board.history_position(), steps.last().unwrap().position);
+
+    for _ in 0..6 {
+        assert!(board.undo());
+    }
+    assert_item_count(&board, 19);
+
+    assert!(board.jump_to_history(0));
+    assert_item_count(&board, 0);
+    assert!(board.jump_to_history(steps[21].position));
+    assert_item_count(&board, 22);
+}
+
+#[test]
+fn test_restore_version_is_one_undo_step() {
+    let mut board = board_with_text("Kept");
+    let version = board_to_state(&board);
+    board.add_item(point(px(0.0), px(0.0)), ItemContent::Text("Added".to_string()));
+    let steps_before = board.undo_steps().len();
+
+    board.restore_version(&version);
+    assert_item_count(&board, 1);
+    let steps = board.undo_steps();
+    assert_eq!(steps.len(), steps_before + 1);
+    assert_eq!(steps.last().unwrap().label, "Restored version");
+
+    assert!(board.undo());
+    assert_item_count(&board, 2);
+    assert!(board.redo());
+    assert_item_count(&board, 1);
+}
+
+#[test]
+fn test_group_undoes_as_one_step() {
+    let mut board = board_with_texts(&["A", "B", "C"]);
+    let steps_before = board.undo_steps().len();
+
+    board.begin_group("Align items");
+    board.move_items(&[(0, (0.0, 500.0))]);
+    board.move_items(&[(1, (100.0, 500.0))]);
+    board.remove_item(2);
+    assert!(board.end_group());
+
+    let steps = board.undo_steps();
+    assert_eq!(steps.len(), steps_before + 1);
+    assert_eq!(steps.last().unwrap().label, "Align items");
+
+    assert!(board.undo());
+    assert_item_count(&board, 3);
+    assert_eq!(board.get_item(0).unwrap().position.1, 0.0);
+    assert_eq!(board.get_item(1).unwrap().position.1, 0.0);
+
+    assert!(board.redo());
+    assert_item_count(&board, 2);
+    assert_eq!(board.get_item(1).unwrap().position, (100.0, 500.0));
+}
+
+#[test]
+fn test_nested_groups_join_the_outer_one() {
+    let mut board = board_with_texts(&["A", "B"]);
+    let steps_before = board.undo_steps().len();
+
+    board.begin_group("Tidy up");
+    board.begin_group("Inner");
+    board.move_items(&[(0, (10.0, 10.0))]);
+    assert!(!board.end_group());
+    board.move_items(&[(1, (20.0, 20.0))]);
+    assert!(board.end_group());
+
+    let steps = board.undo_steps();
+    assert_eq!(steps.len(), steps_before + 1);
+    assert_eq!(steps.last().unwrap().label, "Tidy up");
+}
+
+#[test]
+fn test_group_with_snapshot_change_undoes_to_its_start() {
+    let mut board = board_with_text("Table");
+    let steps_before = board.undo_steps().len();
+
+    board.begin_group("Added chart");
+    let id = board.add_item(point(px(400.0), px(0.0)), ItemContent::Text("Chart".to_string()));
+    board.get_item_mut(id).unwrap().size = (400.0, 300.0);
+    board.push_history();
+    assert!(board.end_group());
+    assert_eq!(board.undo_steps().len(), steps_before + 1);
+
+    assert!(board.undo());
+    assert_item_count(&board, 1);
+    assert!(board.redo());
+    assert_eq!(board.get_item(id).unwrap().size, (400.0, 300.0));
+}
+
+#[test]
+fn test_empty_group_records_nothing() {
+    let mut board = board_with_text("A");
+    let history_before = board.history_len();
+
+    board.begin_group("Nothing");
+    assert!(!board.end_group());
+    assert!(!board.end_group());
+    assert_eq!(board.history_len(), history_before);
+}
+
+#[test]
+fn test_replace_item_content_is_undoable() {
+    let mut board = empty_board();
+    let url = "https://example.com/post";
+    let id = board.add_item(point(px(0.0), px(0.0)), ItemContent::Link(url.to_string()));
+
+    assert!(board.replace_item_content(id, url_paste::bare_bookmark(url)));
+    assert!(matches!(
+        board.get_item(id).unwrap().content,
+        ItemContent::Bookmark { .. }
+    ));
+
+    assert!(board.undo());
+    assert!(matches!(board.get_item(id).unwrap().content, ItemContent::Link(_)));
+    assert!(board.redo());
+    assert!(matches!(
+        board.get_item(id).unwrap().content,
+        ItemContent::Bookmark { .. }
+    ));
+    assert!(!board.replace_item_content(999, ItemContent::Link(url.to_string())));
+}
+
+#[test]
+fn test_fill_in_bookmark_survives_undo_and_redo() {
+    let mut board = empty_board();
+    let url = "https://example.com/post";
+    let id = board.add_bookmark(url, point(px(300.0), px(300.0)));
+    let details = PageDetails {
+        title: Some("A post".to_string()),
+        description: Some("About things".to_string()),
+        site_name: None,
+    };
+
+    assert!(!board.fill_in_bookmark(id, "https://example.com/other", details.bookmark(url)));
+    assert!(board.fill_in_bookmark(id, url, details.bookmark(url)));
+    assert_eq!(board.get_item(id).unwrap().content.display_name(), "A post");
+
+    assert!(board.undo());
+    assert_item_count(&board, 0);
+    assert!(board.redo());
+    assert_eq!(board.get_item(id).unwrap().content.display_name(), "A post");
+}
+
+#[test]
+fn test_fill_in_bookmark_skips_items_made_something_else() {
+    let mut board = empty_board();
+    let url = "https://example.com/post";
+    let id = board.add_bookmark(url, point(px(300.0), px(300.0)));
+    board.replace_item_content(id, ItemContent::Link(url.to_string()));
+
+    assert!(!board.fill_in_bookmark(id, url, PageDetails::default().bookmark(url)));
+    assert!(matches!(board.get_item(id).unwrap().content, ItemContent::Link(_)));
+}
+
+#[test]
+fn test_imported_files_are_laid_out_without_overlap() {
+    let mut board = empty_board();
+    let files = (0..10)
+        .map(|i| ImportedFile::Item {
+            content: ItemContent::Video(PathBuf::from(format!("/tmp/clip{}.mp4", i))),
+            size: None,
+        })
+        .collect();
+    let ids = board.add_imported_files(point(px(300.0), px(300.0)), files);
+    assert_eq!(ids.len(), 10);
+
+    let items: Vec<_> = ids.iter().map(|&id| board.get_item(id).unwrap()).collect();
+    for (i, a) in items.iter().enumerate() {
+        for b in &items[i + 1..] {
+            let apart = a.position.0 + a.size.0 <= b.position.0
+                || b.position.0 + b.size.0 <= a.position.0
+                || a.position.1 + a.size.1 <= b.position.1
+                || b.position.1 + b.size.1 <= a.position.1;
+            assert!(apart, "items {} and {} overlap", a.id, b.id);
+        }
+    }
+
+    // One undo removes the whole drop
+    assert!(board.undo());
+    assert_item_count(&board, 0);
+    assert!(board.redo());
+    assert_item_count(&board, 10);
+}
+
+#[test]
+fn test_imported_images_keep_their_measured_size() {
+    let mut board = empty_board();
+    let files = vec![
+        ImportedFile::Item {
+            content: ItemContent::Image(PathBuf::from("/tmp/wide.png")),
+            size: Some((400.0, 100.0)),
+        },
+        ImportedFile::Item {
+            content: ItemContent::Image(PathBuf::from("/tmp/unreadable.png")),
+            size: None,
+        },
+    ];
+    let ids = board.add_imported_files(point(px(300.0), px(300.0)), files);
+
+    assert_eq!(board.get_item(ids[0]).unwrap().size, (400.0, 100.0));
+    // Only the image without a size is measured later
+    let unmeasured: Vec<u64> = board
+        .take_unmeasured_images()
+        .into_iter()
+        .map(|(id, _)| id)
+        .collect();
+    assert_eq!(unmeasured, vec![ids[1]]);
+}
+
+fn board_with_table() -> (Board, u64) {
+    let mut board = empty_board();
+    board
+        .data_sources
+        .insert(1, DataSource::new_empty(1, "Sales".to_string()));
+    board.next_data_source_id = 2;
+    let table = board.add_item(
+        point(px(100.0), px(100.0)),
+        ItemContent::Table {
+            data_source_id: 1,
+            show_headers: true,
+            stripe: true,
+        },
+    );
+    board.add_item(point(px(400.0), px(150.0)), ItemContent::Text("note".to_string()));
+    (board, table)
+}
+
+#[test]
+fn test_paste_items_onto_another_board() {
+    let (source, _) = board_with_table();
+    let ids = source.items.iter().map(|item| item.id).collect();
+    let copied = source.copy_items(&ids).unwrap();
+
+    let mut target = empty_board();
+    target.id = "other-board".to_string();
+    target.add_item(point(px(0.0), px(0.0)), ItemContent::Text("existing".to_string()));
+    let mut errors = Vec::new();
+    let pasted = target.paste_items(&copied, point(px(500.0), px(500.0)), &mut errors);
+
+    assert!(errors.is_empty());
+    assert_eq!(pasted.len(), 2);
+    assert_item_count(&target, 3);
+    // New IDs that don't collide with the board's own items
+    let all_ids: std::collections::HashSet<u64> = target.items.iter().map(|item| item.id).collect();
+    assert_eq!(all_ids.len(), 3);
+
+    // The table shows its own copy of the data
+    let table = target.get_item(pasted[0]).unwrap();
+    let data_source_id = data_source_of(&table.content).unwrap();
+    assert_eq!(target.data_sources[&data_source_id].name, "Sales");
+
+    // Positions relative to each other are kept
+    let (a, b) = (target.get_item(pasted[0]).unwrap(), target.get_item(pasted[1]).unwrap());
+    assert_eq!(b.position.0 - a.position.0, 300.0);
+    assert_eq!(b.position.1 - a.position.1, 50.0);
+
+    // One undo removes the pasted items
+    assert!(target.undo());
+    assert_item_count(&target, 1);
+}
+
+#[test]
+fn test_paste_items_on_same_board_shares_data() {
+    let (mut board, table) = board_with_table();
+    let copied = board.copy_items(&[table].into_iter().collect()).unwrap();
+
+    let mut errors = Vec::new();
+    let pasted = board.paste_items(&copied, point(px(600.0), px(600.0)), &mut errors);
+
+    assert_eq!(pasted.len(), 1);
+    assert_ne!(pasted[0], table);
+    assert_eq!(board.data_sources.len(), 1);
+    assert_eq!(
+        data_source_of(&board.get_item(pasted[0]).unwrap().content),
+        Some(1)
+    );
+}
+
+#[test]
+fn test_import_items_is_one_labeled_step() {
+    let json = r#"{
+        "nodes": [
+            {"id": "a", "type": "text", "text": "One", "x": 0, "y": 0, "width": 200, "height": 100},
+            {"id": "b", "type": "text", "text": "Two", "x": 400, "y": 0, "width": 200, "height": 100}
+        ],
+        "edges": [{"id": "e", "fromNode": "a", "toNode": "b"}]
+    }"#;
+    let converted =
+        humanboard::obsidian_canvas::convert_canvas(json, std::path::Path::new(".")).unwrap();
+    let imported = humanboard::item_clipboard::CopiedItems {
+        board_id: "obsidian-canvas".to_string(),
+        board_dir: PathBuf::from("/nonexistent-vault"),
+        items: converted.items,
+        data_sources: Vec::new(),
+    };
+
+    let mut board = empty_board();
+    let mut errors = Vec::new();
+    let ids = board.import_items(&imported, point(px(100.0), px(100.0)), &mut errors);
+
+    assert!(errors.is_empty());
+    assert_eq!(ids.len(), 3);
+    assert_item_count(&board, 3);
+    let labels: Vec<String> = board.undo_steps().into_iter().map(|s| s.label).collect();
+    assert_eq!(labels.last().map(String::as_str), Some("Imported 3 items"));
+
+    assert!(board.undo());
+    assert_item_count(&board, 0);
+}
@@ -10,6 +10,7 @@ use humanboard::selection::SelectionManager;
 use humanboard::types::{CanvasItem, ItemContent};
 use gpui::{point, px, Point, Pixels};
 use std::path::PathBuf;
+use std::collections::BTreeMap;
 
 // ============================================================================
 // TestBoardBuilder - Builder pattern for creating test boards
@@ -122,6 +123,7 @@ impl TestBoardBuilder {
             ItemContent::Code {
                 path: path.into(),
                 language: language.into(),
+                lines: None,
             },
         ));
         self
@@ -134,6 +136,7 @@ impl TestBoardBuilder {
             ItemContent::Pdf {
                 path: path.into(),
                 thumbnail: None,
+                page: 0,
             },
         ));
         self
@@ -265,11 +268,17 @@ pub fn board_to_state(board: &Board) -> BoardState {
                 position: item.position,
                 size: item.size,
                 content: item.content.clone(),
+                locked: item.locked,
+                volume: item.volume,
             })
             .collect(),
         next_item_id: board.next_item_id,
         data_sources: board.data_sources.clone(),
         next_data_source_id: board.next_data_source_id,
+        background: None,
+        playback_positions: BTreeMap::new(),
+        pdf_highlights: Vec::new(),
+        preview_session: None,
     }
 }
 
@@ -280,16 +289,55 @@ pub fn test_canvas_item(id: u64, text: &str) -> CanvasItem {
         position: (0.0, 0.0),
         size: (300.0, 100.0),
         content: ItemContent::Text(text.to_string()),
+        locked: false,
+        volume: None,
     }
 }
 
 /// Create a CanvasItem with custom position and size.
 pub fn test_canvas_item_at(id: u64, text: &str, pos: (f32, f32), size: (f32, f32)) -> CanvasItem {
+    test_content_item_at(id, text_content(text), pos, size)
+}
+
+/// Create a 200x100 CanvasItem with any content at the origin.
+pub fn test_content_item(id: u64, content: ItemContent) -> CanvasItem {
+    test_content_item_at(id, content, (0.0, 0.0), (200.0, 100.0))
+}
+
+/// Create a CanvasItem with any content, position and size.
+pub fn test_content_item_at(
+    id: u64,
+    content: ItemContent,
+    pos: (f32, f32),
+    size: (f32, f32),
+) -> CanvasItem {
     CanvasItem {
         id,
         position: pos,
         size,
-        content: ItemContent::Text(text.to_string()),
+        content,
+        locked: false,
+        volume: None,
+    }
+}
+
+/// Create an image CanvasItem showing the file at `path`.
+pub fn test_image_item(id: u64, path: PathBuf) -> CanvasItem {
+    CanvasItem {
+        id,
+        position: (0.0, 0.0),
+        size: (100.0, 100.0),
+        content: ItemContent::Image(path),
+        locked: false,
+        volume: None,
+    }
+}
+
+/// Get the file path of an image item.
+pub fn image_item_path(item: &CanvasItem) -> &PathBuf {
+    match &item.content {
+        ItemContent::Image(path) => path,
+        _ => panic!("expected image item"),
     }
 }
 
@@ -302,6 +350,15 @@ pub fn text_content(text: &str) -> ItemContent {
     ItemContent::Text(text.to_string())
 }
 
+/// Create text box content (16px, white).
+pub fn text_box_content(text: &str) -> ItemContent {
+    ItemContent::TextBox {
+        text: text.to_string(),
+        font_size: 16.0,
+        color: "#ffffff".to_string(),
+    }
+}
+
 /// Create markdown content.
 pub fn markdown_content(path: &str, title: &str, content: &str) -> ItemContent {
     ItemContent::Markdown {
@@ -316,6 +373,7 @@ pub fn code_content(path: &str, language: &str) -> ItemContent {
     ItemContent::Code {
         path: PathBuf::from(path),
         language: language.to_string(),
+        lines: None,
     }
 }
 
@@ -329,6 +387,7 @@ pub fn pdf_content(path: &str) -> ItemContent {
     ItemContent::Pdf {
         path: PathBuf::from(path),
         thumbnail: None,
+        page: 0,
     }
 }
 
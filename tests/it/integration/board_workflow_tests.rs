@@ -5,6 +5,7 @@ use humanboard::board::{Board, BoardState};
 use humanboard::board_index::{BoardIndex, BoardMetadata};
 use humanboard::types::{CanvasItem, ItemContent};
 use gpui::{point, px};
+use std::collections::BTreeMap;
 
 #[test]
 fn test_new_board_workflow() {
@@ -133,10 +134,16 @@ fn test_complete_board_lifecycle() {
         zoom: board.zoom,
         items: board.items.iter().map(|item| CanvasItem {
             id: item.id, position: item.position, size: item.size, content: item.content.clone(),
+            locked: item.locked,
+            volume: item.volume,
         }).collect(),
         next_item_id: board.next_item_id,
         data_sources: board.data_sources.clone(),
         next_data_source_id: board.next_data_source_id,
+        background: None,
+        playback_positions: BTreeMap::new(),
+        pdf_highlights: Vec::new(),
+        preview_session: None,
     };
 
     let json = serde_json::to_string(&state).unwrap();
@@ -5,7 +5,7 @@ use humanboard::board_index::{BoardIndex, BoardMetadata};
 use humanboard::notifications::ToastManager;
 use humanboard::types::{CanvasItem, ItemContent};
 use gpui::{point, px};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
 #[test]
 fn test_board_state_serialization() {
@@ -18,11 +18,17 @@ fn test_board_state_serialization() {
                 position: (50.0, 50.0),
                 size: (200.0, 150.0),
                 content: ItemContent::Text("Test".to_string()),
+                locked: false,
+                volume: None,
             },
         ],
         next_item_id: 1,
         data_sources: HashMap::new(),
         next_data_source_id: 0,
+        background: None,
+        playback_positions: BTreeMap::new(),
+        pdf_highlights: Vec::new(),
+        preview_session: None,
     };
 
     let json = serde_json::to_string(&state).unwrap();
@@ -106,10 +112,16 @@ fn test_canvas_state_persistence() {
         zoom: board.zoom,
         items: board.items.iter().map(|i| CanvasItem {
             id: i.id, position: i.position, size: i.size, content: i.content.clone(),
+            locked: i.locked,
+            volume: i.volume,
         }).collect(),
         next_item_id: board.next_item_id,
         data_sources: board.data_sources.clone(),
         next_data_source_id: board.next_data_source_id,
+        background: None,
+        playback_positions: BTreeMap::new(),
+        pdf_highlights: Vec::new(),
+        preview_session: None,
     };
 
     assert_eq!(state.canvas_offset, (150.0, 250.0));
@@ -120,12 +132,12 @@ fn test_canvas_state_persistence() {
 fn test_item_content_variants() {
     let text = ItemContent::Text("Hello".to_string());
     let image = ItemContent::Image("/path/image.png".into());
-    let pdf = ItemContent::Pdf { path: "/doc.pdf".into(), thumbnail: None };
+    let pdf = ItemContent::Pdf { path: "/doc.pdf".into(), thumbnail: None, page: 0 };
     let video = ItemContent::Video("/video.mp4".into());
     let audio = ItemContent::Audio("/audio.mp3".into());
     let youtube = ItemContent::YouTube("123".to_string());
     let markdown = ItemContent::Markdown { path: "/notes.md".into(), title: "Notes".to_string(), content: "# Title".to_string() };
-    let code = ItemContent::Code { path: "/main.rs".into(), language: "rust".to_string() };
+    let code = ItemContent::Code { path: "/main.rs".into(), language: "rust".to_string(), lines: None };
 
     let items = vec![text, image, pdf, video, audio, youtube, markdown, code];
     for item in items {
@@ -153,15 +165,19 @@ fn test_board_state_with_all_content_types() {
         canvas_offset: (0.0, 0.0),
         zoom: 1.0,
         items: vec![
-            CanvasItem { id: 0, position: (0.0, 0.0), size: (200.0, 100.0), content: ItemContent::Text("Text".to_string()) },
-            CanvasItem { id: 1, position: (250.0, 0.0), size: (200.0, 200.0), content: ItemContent::Image("/img.png".into()) },
-            CanvasItem { id: 2, position: (500.0, 0.0), size: (200.0, 300.0), content: ItemContent::Pdf { path: "/doc.pdf".into(), thumbnail: None } },
-            CanvasItem { id: 3, position: (0.0, 350.0), size: (320.0, 180.0), content: ItemContent::Video("/vid.mp4".into()) },
-            CanvasItem { id: 4, position: (350.0, 350.0), size: (200.0, 50.0), content: ItemContent::Audio("/audio.mp3".into()) },
+            CanvasItem { id: 0, position: (0.0, 0.0), size: (200.0, 100.0), content: ItemContent::Text("Text".to_string()), locked: false, volume: None },
+            CanvasItem { id: 1, position: (250.0, 0.0), size: (200.0, 200.0), content: ItemContent::Image("/img.png".into()), locked: false, volume: None },
+            CanvasItem { id: 2, position: (500.0, 0.0), size: (200.0, 300.0), content: ItemContent::Pdf { path: "/doc.pdf".into(), thumbnail: None, page: 0 }, locked: false, volume: None },
+            CanvasItem { id: 3, position: (0.0, 350.0), size: (320.0, 180.0), content: ItemContent::Video("/vid.mp4".into()), locked: false, volume: None },
+            CanvasItem { id: 4, position: (350.0, 350.0), size: (200.0, 50.0), content: ItemContent::Audio("/audio.mp3".into()), locked: false, volume: None },
         ],
         next_item_id: 5,
         data_sources: HashMap::new(),
         next_data_source_id: 0,
+        background: None,
+        playback_positions: BTreeMap::new(),
+        pdf_highlights: Vec::new(),
+        preview_session: None,
     };
 
     let json = serde_json::to_string_pretty(&state).unwrap();
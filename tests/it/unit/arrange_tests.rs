@@ -0,0 +1,218 @@
+//! Unit tests for arrange module.
+
+use crate::helpers::{TestBoardBuilder, assert_item_position, test_canvas_item_at};
+use humanboard::arrange::{
+    AlignEdge, DistributeAxis, GRID_GAP, ZOrder, align_items, distribute_items, grid_columns,
+    grid_layout, reorder,
+};
+use humanboard::types::CanvasItem;
+use std::collections::HashSet;
+
+fn items() -> Vec<CanvasItem> {
+    vec![
+        test_canvas_item_at(1, "a", (10.0, 50.0), (100.0, 40.0)),
+        test_canvas_item_at(2, "b", (200.0, 20.0), (50.0, 100.0)),
+        test_canvas_item_at(3, "c", (80.0, 300.0), (200.0, 60.0)),
+    ]
+}
+
+fn positions(moves: &[(u64, (f32, f32))]) -> Vec<(f32, f32)> {
+    let mut moves = moves.to_vec();
+    moves.sort_by_key(|(id, _)| *id);
+    moves.into_iter().map(|(_, pos)| pos).collect()
+}
+
+#[test]
+fn test_align_edges() {
+    let items = items();
+    let refs: Vec<&CanvasItem> = items.iter().collect();
+
+    assert_eq!(
+        positions(&align_items(&refs, AlignEdge::Left)),
+        vec![(10.0, 50.0), (10.0, 20.0), (10.0, 300.0)]
+    );
+    assert_eq!(
+        positions(&align_items(&refs, AlignEdge::Right)),
+        vec![(180.0, 50.0), (230.0, 20.0), (80.0, 300.0)]
+    );
+    assert_eq!(
+        positions(&align_items(&refs, AlignEdge::Top)),
+        vec![(10.0, 20.0), (200.0, 20.0), (80.0, 20.0)]
+    );
+    assert_eq!(
+        positions(&align_items(&refs, AlignEdge::Bottom)),
+        vec![(10.0, 320.0), (200.0, 260.0), (80.0, 300.0)]
+    );
+}
+
+#[test]
+fn test_align_centers() {
+    let items = items();
+    let refs: Vec<&CanvasItem> = items.iter().collect();
+
+    // Selection spans x 10..280 and y 20..360
+    assert_eq!(
+        positions(&align_items(&refs, AlignEdge::Center)),
+        vec![(95.0, 50.0), (120.0, 20.0), (45.0, 300.0)]
+    );
+    assert_eq!(
+        positions(&align_items(&refs, AlignEdge::Middle)),
+        vec![(10.0, 170.0), (200.0, 140.0), (80.0, 160.0)]
+    );
+}
+
+#[test]
+fn test_align_needs_two_items() {
+    let item = test_canvas_item_at(1, "a", (10.0, 50.0), (100.0, 40.0));
+    assert!(align_items(&[&item], AlignEdge::Left).is_empty());
+}
+
+#[test]
+fn test_distribute_horizontally_equalizes_gaps() {
+    let items = vec![
+        test_canvas_item_at(1, "a", (0.0, 0.0), (100.0, 50.0)),
+        test_canvas_item_at(2, "b", (400.0, 10.0), (100.0, 50.0)),
+        test_canvas_item_at(3, "c", (120.0, 20.0), (50.0, 50.0)),
+    ];
+    let refs: Vec<&CanvasItem> = items.iter().collect();
+
+    // Extent 0..500 holds 250 of items, leaving two 125 gaps
+    assert_eq!(
+        positions(&distribute_items(&refs, DistributeAxis::Horizontal)),
+        vec![(0.0, 0.0), (400.0, 10.0), (225.0, 20.0)]
+    );
+}
+
+#[test]
+fn test_distribute_vertically_keeps_order() {
+    let items = vec![
+        test_canvas_item_at(1, "a", (0.0, 300.0), (50.0, 100.0)),
+        test_canvas_item_at(2, "b", (10.0, 0.0), (50.0, 100.0)),
+        test_canvas_item_at(3, "c", (20.0, 110.0), (50.0, 100.0)),
+    ];
+    let refs: Vec<&CanvasItem> = items.iter().collect();
+
+    assert_eq!(
+        positions(&distribute_items(&refs, DistributeAxis::Vertical)),
+        vec![(0.0, 300.0), (10.0, 0.0), (20.0, 150.0)]
+    );
+}
+
+#[test]
+fn test_distribute_needs_three_items() {
+    let items = vec![
+        test_canvas_item_at(1, "a", (0.0, 0.0), (100.0, 50.0)),
+        test_canvas_item_at(2, "b", (400.0, 0.0), (100.0, 50.0)),
+    ];
+    let refs: Vec<&CanvasItem> = items.iter().collect();
+    assert!(distribute_items(&refs, DistributeAxis::Horizontal).is_empty());
+}
+
+#[test]
+fn test_move_items_is_a_single_undo_step() {
+    let mut board = TestBoardBuilder::new()
+        .with_text_item("a", (10.0, 50.0))
+        .with_text_item("b", (200.0, 20.0))
+        .build();
+    let ids: Vec<u64> = board.items.iter().map(|item| item.id).collect();
+
+    let refs: Vec<&CanvasItem> = board.items.iter().collect();
+    let moves = align_items(&refs, AlignEdge::Top);
+    assert!(board.move_items(&moves));
+    assert_item_position(&board, ids[0], (10.0, 20.0));
+    assert_item_position(&board, ids[1], (200.0, 20.0));
+
+    assert!(board.undo());
+    assert_item_position(&board, ids[0], (10.0, 50.0));
+    assert_item_position(&board, ids[1], (200.0, 20.0));
+}
+
+#[test]
+fn test_move_items_without_changes() {
+    let mut board = TestBoardBuilder::new()
+        .with_text_item("a", (10.0, 20.0))
+        .with_text_item("b", (200.0, 20.0))
+        .build();
+
+    let refs: Vec<&CanvasItem> = board.items.iter().collect();
+    let moves = align_items(&refs, AlignEdge::Top);
+    assert!(!board.move_items(&moves));
+}
+
+#[test]
+fn test_reorder_to_front_and_back() {
+    let order = [1, 2, 3, 4];
+    let selected: HashSet<u64> = [1, 3].into_iter().collect();
+    assert_eq!(
+        reorder(&order, &selected, ZOrder::BringToFront),
+        vec![2, 4, 1, 3]
+    );
+    assert_eq!(
+        reorder(&order, &selected, ZOrder::SendToBack),
+        vec![1, 3, 2, 4]
+    );
+}
+
+#[test]
+fn test_reorder_one_step() {
+    let order = [1, 2, 3, 4];
+    let selected: HashSet<u64> = [1, 2].into_iter().collect();
+    assert_eq!(
+        reorder(&order, &selected, ZOrder::BringForward),
+        vec![3, 1, 2, 4]
+    );
+
+    let selected: HashSet<u64> = [3, 4].into_iter().collect();
+    assert_eq!(
+        reorder(&order, &selected, ZOrder::SendBackward),
+        vec![1, 3, 4, 2]
+    );
+}
+
+#[test]
+fn test_reorder_already_at_front_is_unchanged() {
+    let order = [1, 2, 3];
+    let selected: HashSet<u64> = [3].into_iter().collect();
+    assert_eq!(
+        reorder(&order, &selected, ZOrder::BringForward),
+        vec![1, 2, 3]
+    );
+    assert_eq!(
+        reorder(&order, &selected, ZOrder::BringToFront),
+        vec![1, 2, 3]
+    );
+}
+
+#[test]
+fn test_grid_columns() {
+    assert_eq!(grid_columns(0), 1);
+    assert_eq!(grid_columns(1), 1);
+    assert_eq!(grid_columns(3), 3);
+    assert_eq!(grid_columns(4), 2);
+    assert_eq!(grid_columns(10), 4);
+    assert_eq!(grid_columns(16), 4);
+}
+
+#[test]
+fn test_grid_layout_rows() {
+    let sizes = [(100.0, 50.0), (200.0, 80.0), (100.0, 50.0), (50.0, 20.0)];
+    assert_eq!(
+        grid_layout(&sizes, (10.0, 20.0)),
+        vec![
+            (10.0, 20.0),
+            (10.0 + 100.0 + GRID_GAP, 20.0),
+            // The second row starts below the taller item of the first
+            (10.0, 20.0 + 80.0 + GRID_GAP),
+            (10.0 + 100.0 + GRID_GAP, 20.0 + 80.0 + GRID_GAP),
+        ]
+    );
+}
+
+#[test]
+fn test_grid_layout_single_row() {
+    let sizes = [(100.0, 50.0), (60.0, 90.0), (30.0, 30.0)];
+    let positions = grid_layout(&sizes, (0.0, 0.0));
+    assert!(positions.iter().all(|&(_, y)| y == 0.0));
+    assert_eq!(positions[2].0, 160.0 + 2.0 * GRID_GAP);
+    assert!(grid_layout(&[], (0.0, 0.0)).is_empty());
+}
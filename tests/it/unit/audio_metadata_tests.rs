@@ -0,0 +1,77 @@
+//! Unit tests for audio_metadata module.
+
+use humanboard::audio_metadata::{
+    AudioMetadata, AudioMetadataCache, art_extension, read_audio_metadata,
+};
+use humanboard::types::ItemContent;
+use std::fs;
+use std::path::{Path, PathBuf};
+use tempfile::tempdir;
+
+fn tagged(title: Option<&str>, artist: Option<&str>) -> AudioMetadata {
+    AudioMetadata {
+        title: title.map(str::to_string),
+        artist: artist.map(str::to_string),
+        ..AudioMetadata::default()
+    }
+}
+
+#[test]
+fn test_label_joins_title_and_artist() {
+    assert_eq!(
+        tagged(Some("Blue in Green"), Some("Miles Davis")).label(),
+        Some("Blue in Green — Miles Davis".to_string())
+    );
+    assert_eq!(
+        tagged(Some("Blue in Green"), Some("  ")).label(),
+        Some("Blue in Green".to_string())
+    );
+}
+
+#[test]
+fn test_label_needs_a_title() {
+    assert_eq!(tagged(None, Some("Miles Davis")).label(), None);
+    assert_eq!(tagged(Some(" "), None).label(), None);
+}
+
+#[test]
+fn test_art_extension() {
+    assert_eq!(art_extension("image/jpeg"), Some("jpg"));
+    assert_eq!(art_extension("image/png"), Some("png"));
+    assert_eq!(art_extension("image/svg+xml"), None);
+}
+
+#[test]
+fn test_read_audio_metadata_rejects_non_audio() {
+    let dir = tempdir().unwrap();
+    let path = dir.path().join("notes.mp3");
+    fs::write(&path, b"not really audio").unwrap();
+    assert!(read_audio_metadata(&path).is_err());
+    assert!(read_audio_metadata(&dir.path().join("missing.mp3")).is_err());
+}
+
+#[test]
+fn test_cache_insert_and_clear() {
+    let cache = AudioMetadataCache::new();
+    let path = Path::new("/music/song.mp3");
+    assert!(cache.cached(path).is_none());
+
+    cache.insert(path.to_path_buf(), tagged(Some("Song"), None));
+    assert_eq!(cache.cached(path).unwrap().title.as_deref(), Some("Song"));
+    assert!(!cache.has_pending());
+
+    cache.clear();
+    assert!(cache.cached(path).is_none());
+}
+
+#[test]
+fn test_display_name_with_uses_tags_once_read() {
+    let path = PathBuf::from("/music/display_name_test/track01.mp3");
+    let content = ItemContent::Audio(path.clone());
+    let cache = AudioMetadataCache::new();
+    assert_eq!(content.display_name_with(&cache), "track01.mp3");
+
+    cache.insert(path, tagged(Some("So What"), Some("Miles Davis")));
+    assert_eq!(content.display_name_with(&cache), "So What — Miles Davis");
+    assert_eq!(content.display_name(), "track01.mp3");
+}
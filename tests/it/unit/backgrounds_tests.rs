@@ -0,0 +1,92 @@
+//! Unit tests for batched item backgrounds.
+
+use crate::helpers::{image_content, test_canvas_item_at};
+use gpui::{Bounds, hsla, point, px, size};
+use humanboard::render::backgrounds::{batch_backgrounds, has_painted_background};
+use humanboard::render::canvas::ContentTypeColors;
+use humanboard::types::ItemContent;
+use std::path::PathBuf;
+
+fn colors() -> ContentTypeColors {
+    ContentTypeColors {
+        video: hsla(0.78, 0.5, 0.4, 0.9),
+        audio: hsla(0.89, 0.5, 0.4, 0.9),
+        text: hsla(0.58, 0.5, 0.4, 0.9),
+        pdf: hsla(0.07, 0.6, 0.4, 0.9),
+        link: hsla(0.5, 0.5, 0.4, 0.9),
+        youtube: hsla(0.0, 0.7, 0.45, 0.9),
+        unknown: hsla(0.0, 0.0, 0.3, 1.0),
+        border: hsla(0.0, 0.0, 0.5, 0.5),
+    }
+}
+
+fn viewport() -> Bounds<gpui::Pixels> {
+    Bounds {
+        origin: point(px(0.0), px(0.0)),
+        size: size(px(800.0), px(600.0)),
+    }
+}
+
+#[test]
+fn test_items_with_own_content_have_no_background() {
+    assert!(has_painted_background(&ItemContent::Text("note".into())));
+    assert!(has_painted_background(&ItemContent::Video(PathBuf::new())));
+    assert!(!has_painted_background(&image_content("/photos/a.png")));
+}
+
+#[test]
+fn test_consecutive_backgrounds_are_grouped_by_color() {
+    let mut video = test_canvas_item_at(3, "", (0.0, 200.0), (100.0, 100.0));
+    video.content = ItemContent::Video(PathBuf::new());
+    let items = vec![
+        test_canvas_item_at(1, "a", (0.0, 0.0), (100.0, 50.0)),
+        test_canvas_item_at(2, "b", (200.0, 0.0), (100.0, 50.0)),
+        video,
+    ];
+
+    let batches = batch_backgrounds(viewport(), &items, point(px(0.0), px(0.0)), 1.0, &colors());
+    assert_eq!(batches.len(), 2);
+    assert_eq!(batches[0].background, colors().text);
+    assert_eq!(batches[0].bounds.len(), 2);
+    assert_eq!(batches[1].background, colors().video);
+    assert_eq!(batches[1].bounds.len(), 1);
+}
+
+#[test]
+fn test_backgrounds_keep_item_order() {
+    // The video sits between two text items, so the top text item must
+    // still paint over it
+    let mut video = test_canvas_item_at(3, "", (0.0, 0.0), (100.0, 100.0));
+    video.content = ItemContent::Video(PathBuf::new());
+    let items = vec![
+        test_canvas_item_at(1, "a", (0.0, 0.0), (100.0, 50.0)),
+        video,
+        test_canvas_item_at(2, "b", (50.0, 50.0), (100.0, 50.0)),
+    ];
+
+    let batches = batch_backgrounds(viewport(), &items, point(px(0.0), px(0.0)), 1.0, &colors());
+    let order: Vec<_> = batches.iter().map(|batch| batch.background).collect();
+    assert_eq!(order, vec![colors().text, colors().video, colors().text]);
+}
+
+#[test]
+fn test_off_screen_backgrounds_are_culled() {
+    let items = vec![
+        test_canvas_item_at(1, "near", (10.0, 10.0), (100.0, 50.0)),
+        test_canvas_item_at(2, "far", (5000.0, 5000.0), (100.0, 50.0)),
+    ];
+
+    let batches = batch_backgrounds(viewport(), &items, point(px(0.0), px(0.0)), 1.0, &colors());
+    assert_eq!(batches.len(), 1);
+    assert_eq!(batches[0].bounds.len(), 1);
+}
+
+#[test]
+fn test_background_bounds_follow_pan_and_zoom() {
+    let items = vec![test_canvas_item_at(1, "a", (10.0, 20.0), (100.0, 50.0))];
+
+    let batches = batch_backgrounds(viewport(), &items, point(px(5.0), px(-5.0)), 2.0, &colors());
+    let bounds = batches[0].bounds[0];
+    assert_eq!(bounds.origin, point(px(25.0), px(35.0)));
+    assert_eq!(bounds.size, size(px(200.0), px(100.0)));
+}
@@ -0,0 +1,144 @@
+//! Unit tests for board_assets module.
+
+use crate::helpers::{image_item_path, test_image_item};
+use humanboard::board_assets::{
+    assets_dir, copy_board_files, migrate_items, pasted_image_extension, relativize_paths,
+    resolve_paths, write_pasted_file,
+};
+use std::fs;
+use std::path::PathBuf;
+use tempfile::tempdir;
+
+#[test]
+fn test_relativize_and_resolve_round_trip() {
+    let board_dir = PathBuf::from("/boards/abc");
+    let inside = board_dir.join("assets").join("photo.png");
+    let outside = PathBuf::from("/elsewhere/photo.png");
+    let mut items = vec![
+        test_image_item(0, inside.clone()),
+        test_image_item(1, outside.clone()),
+    ];
+
+    relativize_paths(&mut items, &board_dir);
+    assert_eq!(
+        image_item_path(&items[0]),
+        &PathBuf::from("assets/photo.png")
+    );
+    assert_eq!(image_item_path(&items[1]), &outside);
+
+    resolve_paths(&mut items, &board_dir);
+    assert_eq!(image_item_path(&items[0]), &inside);
+    assert_eq!(image_item_path(&items[1]), &outside);
+}
+
+#[test]
+fn test_migrate_copies_external_files_once() {
+    let source_dir = tempdir().unwrap();
+    let board_dir = tempdir().unwrap();
+    let source = source_dir.path().join("photo.png");
+    fs::write(&source, b"not really a png").unwrap();
+
+    let mut items = vec![
+        test_image_item(0, source.clone()),
+        test_image_item(1, source.clone()),
+    ];
+    let report = migrate_items(&mut items, board_dir.path());
+
+    assert_eq!(report.copied, 1);
+    assert_eq!(report.missing, 0);
+    assert!(report.errors.is_empty());
+
+    let copy = image_item_path(&items[0]).clone();
+    assert!(copy.starts_with(assets_dir(board_dir.path())));
+    assert!(copy.exists());
+    assert_eq!(image_item_path(&items[1]), &copy);
+    // Original is left in place
+    assert!(source.exists());
+}
+
+#[test]
+fn test_migrate_reports_missing_files() {
+    let source_dir = tempdir().unwrap();
+    let board_dir = tempdir().unwrap();
+    let missing = source_dir.path().join("does-not-exist.png");
+    let mut items = vec![test_image_item(0, missing.clone())];
+
+    let report = migrate_items(&mut items, board_dir.path());
+
+    assert_eq!(report.copied, 0);
+    assert_eq!(report.missing, 1);
+    assert!(!report.changed());
+    assert_eq!(image_item_path(&items[0]), &missing);
+}
+
+#[test]
+fn test_pasted_image_extension_from_contents() {
+    let png = [0x89, b'P', b'N', b'G', 0x0D, 0x0A, 0x1A, 0x0A, 0, 0, 0, 0];
+    assert_eq!(pasted_image_extension(&png), Some("png"));
+    let jpeg = [0xFF, 0xD8, 0xFF, 0xE0, 0, 0];
+    assert_eq!(pasted_image_extension(&jpeg), Some("jpg"));
+    assert_eq!(
+        pasted_image_extension(b"<svg xmlns=\"http://www.w3.org/2000/svg\"/>"),
+        Some("svg")
+    );
+    assert_eq!(pasted_image_extension(b"just some text"), None);
+}
+
+#[test]
+fn test_write_pasted_file_never_overwrites() {
+    let dir = tempdir().unwrap();
+    let assets = assets_dir(dir.path());
+
+    let first = write_pasted_file(&assets, "png", b"one").unwrap();
+    let second = write_pasted_file(&assets, "png", b"two").unwrap();
+
+    assert_ne!(first, second);
+    assert!(first.starts_with(&assets));
+    assert_eq!(first.extension().unwrap(), "png");
+    assert_eq!(fs::read(&first).unwrap(), b"one");
+    assert_eq!(fs::read(&second).unwrap(), b"two");
+}
+
+#[test]
+fn test_write_pasted_file_rejects_bad_extension() {
+    let dir = tempdir().unwrap();
+    assert!(write_pasted_file(dir.path(), "../", b"data").is_err());
+}
+
+#[test]
+fn test_copy_board_files_copies_only_the_source_boards_files() {
+    let source_board = tempdir().unwrap();
+    let target_board = tempdir().unwrap();
+    let asset = assets_dir(source_board.path()).join("photo.png");
+    fs::create_dir_all(asset.parent().unwrap()).unwrap();
+    fs::write(&asset, b"not really a png").unwrap();
+    let outside = PathBuf::from("/elsewhere/photo.png");
+
+    let mut items = vec![
+        test_image_item(0, asset.clone()),
+        test_image_item(1, asset.clone()),
+        test_image_item(2, outside.clone()),
+    ];
+    let target_dir = assets_dir(target_board.path());
+    let errors = copy_board_files(&mut items, source_board.path(), &target_dir);
+
+    assert!(errors.is_empty());
+    let copy = image_item_path(&items[0]).clone();
+    assert!(copy.starts_with(&target_dir));
+    assert!(copy.exists());
+    assert_eq!(image_item_path(&items[1]), &copy);
+    assert_eq!(image_item_path(&items[2]), &outside);
+}
+
+#[test]
+fn test_copy_board_files_keeps_path_of_missing_file() {
+    let source_board = tempdir().unwrap();
+    let target_board = tempdir().unwrap();
+    let missing = source_board.path().join("files").join("gone.png");
+
+    let mut items = vec![test_image_item(0, missing.clone())];
+    let errors = copy_board_files(&mut items, source_board.path(), target_board.path());
+
+    assert_eq!(errors.len(), 1);
+    assert_eq!(image_item_path(&items[0]), &missing);
+}
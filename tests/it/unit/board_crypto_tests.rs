@@ -0,0 +1,117 @@
+//! Unit tests for board_crypto module.
+
+use crate::helpers::{board_to_state, board_with_texts};
+use humanboard::board::BoardState;
+use humanboard::board_crypto::{
+    BoardKey, decrypt_state, encrypt_state, is_encrypted_file, validate_new_passphrase,
+};
+use humanboard::error::BoardError;
+use std::fs;
+use tempfile::tempdir;
+
+/// Few iterations keep the tests fast; real boards use `PBKDF2_ITERATIONS`
+fn test_key(passphrase: &str) -> BoardKey {
+    BoardKey::derive(passphrase, vec![7; 16], 1_000).unwrap()
+}
+
+#[test]
+fn test_seal_and_open_round_trip() {
+    let key = test_key("correct horse");
+    let envelope = key.seal(b"board contents").unwrap();
+
+    let (plaintext, _) = envelope.open("correct horse").unwrap();
+    assert_eq!(plaintext, b"board contents");
+}
+
+#[test]
+fn test_ciphertext_hides_contents() {
+    let state = board_to_state(&board_with_texts(&["top secret note"]));
+    let envelope = encrypt_state(&state, &test_key("correct horse")).unwrap();
+
+    let json = serde_json::to_string(&envelope).unwrap();
+    assert!(!json.contains("top secret note"));
+}
+
+#[test]
+fn test_wrong_passphrase_is_rejected() {
+    let envelope = test_key("correct horse").seal(b"board contents").unwrap();
+
+    let result = envelope.open("battery staple");
+    assert!(matches!(result, Err(BoardError::WrongPassphrase)));
+}
+
+#[test]
+fn test_returned_key_reencrypts() {
+    let state = board_to_state(&board_with_texts(&["a", "b"]));
+    let envelope = encrypt_state(&state, &test_key("correct horse")).unwrap();
+
+    let (decrypted, key) = decrypt_state(&envelope, "correct horse").unwrap();
+    let resealed = encrypt_state(&decrypted, &key).unwrap();
+    let (reopened, _) = decrypt_state(&resealed, "correct horse").unwrap();
+
+    assert_eq!(reopened.items.len(), 2);
+    assert_ne!(resealed.nonce, envelope.nonce);
+}
+
+#[test]
+fn test_encrypted_save_and_load() {
+    let dir = tempdir().unwrap();
+    let path = dir.path().join("board.json");
+    let state = board_to_state(&board_with_texts(&["one", "two", "three"]));
+
+    state
+        .save_encrypted(&path, &test_key("correct horse"))
+        .unwrap();
+    assert!(is_encrypted_file(&path));
+
+    let (loaded, _) = BoardState::load_encrypted(&path, "correct horse").unwrap();
+    assert_eq!(loaded.items.len(), 3);
+    assert_eq!(loaded.next_item_id, state.next_item_id);
+}
+
+#[test]
+fn test_plain_load_refuses_encrypted_board() {
+    let dir = tempdir().unwrap();
+    let path = dir.path().join("board.json");
+    let state = board_to_state(&board_with_texts(&["a"]));
+    state
+        .save_encrypted(&path, &test_key("correct horse"))
+        .unwrap();
+
+    assert!(matches!(
+        BoardState::load_from_path(&path),
+        Err(BoardError::Encrypted)
+    ));
+    assert!(BoardState::try_load(&path).is_none());
+}
+
+#[test]
+fn test_plain_board_is_not_encrypted() {
+    let dir = tempdir().unwrap();
+    let path = dir.path().join("board.json");
+    board_to_state(&board_with_texts(&["a"]))
+        .save_to_path(&path)
+        .unwrap();
+
+    assert!(!is_encrypted_file(&path));
+    assert!(fs::read_to_string(&path).unwrap().contains("\"a\""));
+}
+
+#[test]
+fn test_validate_new_passphrase() {
+    assert!(validate_new_passphrase("long enough", "long enough").is_ok());
+    assert!(validate_new_passphrase("short", "short").is_err());
+    assert!(validate_new_passphrase("long enough", "long enougH").is_err());
+}
+
+#[test]
+fn test_unreasonable_iteration_counts_are_rejected() {
+    let mut envelope = test_key("correct horse").seal(b"board contents").unwrap();
+
+    envelope.iterations = u32::MAX;
+    assert!(matches!(
+        envelope.open("correct horse"),
+        Err(BoardError::InvalidData(_))
+    ));
+    assert!(BoardKey::derive("correct horse", vec![7; 16], 1).is_err());
+}
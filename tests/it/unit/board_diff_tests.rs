@@ -0,0 +1,81 @@
+//! Unit tests for board_diff module.
+
+use humanboard::board_diff::{BoardDiff, DiffMark};
+use humanboard::types::{CanvasItem, ItemContent};
+
+fn text(id: u64, x: f32, y: f32, body: &str) -> CanvasItem {
+    CanvasItem {
+        id,
+        position: (x, y),
+        size: (200.0, 100.0),
+        content: ItemContent::Text(body.to_string()),
+        locked: false,
+        volume: None,
+    }
+}
+
+#[test]
+fn test_same_items_have_no_differences() {
+    let items = vec![text(1, 0.0, 0.0, "a"), text(2, 300.0, 0.0, "b")];
+    let diff = BoardDiff::between(&items, &items);
+
+    assert!(diff.is_empty());
+    assert_eq!(diff.mark(1), None);
+    assert_eq!(diff.summary(), "No changes");
+}
+
+#[test]
+fn test_added_and_removed_items() {
+    let version = vec![text(1, 0.0, 0.0, "a"), text(2, 300.0, 0.0, "b")];
+    let current = vec![text(1, 0.0, 0.0, "a"), text(3, 600.0, 0.0, "c")];
+    let diff = BoardDiff::between(&version, &current);
+
+    assert_eq!(diff.mark(3), Some(DiffMark::Added));
+    assert_eq!(diff.mark(1), None);
+    assert_eq!(diff.removed().len(), 1);
+    assert_eq!(diff.removed()[0].id, 2);
+    assert_eq!(diff.added_count(), 1);
+    assert_eq!(diff.summary(), "1 added · 1 removed");
+}
+
+#[test]
+fn test_moved_items_keep_old_bounds() {
+    let version = vec![text(1, 0.0, 0.0, "a")];
+    let mut moved = text(1, 40.0, 20.0, "a");
+    moved.size = (250.0, 100.0);
+    let diff = BoardDiff::between(&version, &[moved]);
+
+    assert_eq!(diff.mark(1), Some(DiffMark::Moved));
+    assert_eq!(diff.old_bounds(1), Some((0.0, 0.0, 200.0, 100.0)));
+    assert_eq!(diff.moved_count(), 1);
+}
+
+#[test]
+fn test_tiny_moves_are_ignored() {
+    let version = vec![text(1, 0.0, 0.0, "a")];
+    let diff = BoardDiff::between(&version, &[text(1, 0.2, -0.3, "a")]);
+
+    assert!(diff.is_empty());
+}
+
+#[test]
+fn test_changed_content_and_lock() {
+    let version = vec![text(1, 0.0, 0.0, "a"), text(2, 300.0, 0.0, "b")];
+    let mut locked = text(2, 300.0, 0.0, "b");
+    locked.locked = true;
+    let diff = BoardDiff::between(&version, &[text(1, 0.0, 0.0, "edited"), locked]);
+
+    assert_eq!(diff.mark(1), Some(DiffMark::Changed));
+    assert_eq!(diff.mark(2), Some(DiffMark::Changed));
+    assert_eq!(diff.changed_count(), 2);
+    assert_eq!(diff.summary(), "2 changed");
+}
+
+#[test]
+fn test_moved_and_changed_is_marked_moved() {
+    let version = vec![text(1, 0.0, 0.0, "a")];
+    let diff = BoardDiff::between(&version, &[text(1, 100.0, 0.0, "b")]);
+
+    assert_eq!(diff.mark(1), Some(DiffMark::Moved));
+    assert_eq!(diff.summary(), "1 moved · 1 changed");
+}
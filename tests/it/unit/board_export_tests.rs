@@ -0,0 +1,137 @@
+//! Unit tests for board_export module.
+
+use crate::helpers::{board_to_state, board_with_texts};
+use humanboard::board::BoardState;
+use humanboard::board_export::{escape_html, export_html, render_html};
+use humanboard::types::{
+    CanvasItem, ChartConfig, DataCell, DataColumn, DataOrigin, DataRow, DataSource, DataType,
+    ItemContent,
+};
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use tempfile::tempdir;
+
+fn state_with(contents: Vec<ItemContent>) -> BoardState {
+    let items: Vec<CanvasItem> = contents
+        .into_iter()
+        .enumerate()
+        .map(|(i, content)| CanvasItem {
+            id: i as u64,
+            position: (i as f32 * 300.0, 0.0),
+            size: (200.0, 150.0),
+            content,
+            locked: false,
+            volume: None,
+        })
+        .collect();
+    BoardState {
+        canvas_offset: (0.0, 0.0),
+        zoom: 1.0,
+        next_item_id: items.len() as u64,
+        items,
+        data_sources: HashMap::new(),
+        next_data_source_id: 0,
+        background: None,
+        playback_positions: BTreeMap::new(),
+        pdf_highlights: Vec::new(),
+        preview_session: None,
+    }
+}
+
+#[test]
+fn test_escape_html() {
+    assert_eq!(
+        escape_html("<b>\"Tom\" & 'Jerry'</b>"),
+        "&lt;b&gt;&quot;Tom&quot; &amp; &#39;Jerry&#39;&lt;/b&gt;"
+    );
+}
+
+#[test]
+fn test_export_contains_escaped_text_and_title() {
+    let state = board_to_state(&board_with_texts(&["<script>alert(1)</script>", "plain"]));
+    let html = render_html(&state, "Ideas & Notes");
+
+    assert!(html.starts_with("<!DOCTYPE html>"));
+    assert!(html.contains("<title>Ideas &amp; Notes</title>"));
+    assert!(html.contains("&lt;script&gt;alert(1)&lt;/script&gt;"));
+    assert!(html.contains("plain"));
+}
+
+#[test]
+fn test_links_only_allow_web_schemes() {
+    let state = state_with(vec![
+        ItemContent::Link("https://example.com".to_string()),
+        ItemContent::Link("javascript:alert(1)".to_string()),
+    ]);
+    let html = render_html(&state, "Links");
+
+    assert!(html.contains("href=\"https://example.com\""));
+    assert!(!html.contains("href=\"javascript:"));
+}
+
+#[test]
+fn test_images_are_embedded() {
+    let dir = tempdir().unwrap();
+    let path = dir.path().join("pixel.png");
+    image::RgbaImage::from_pixel(1, 1, image::Rgba([255, 0, 0, 255]))
+        .save(&path)
+        .unwrap();
+
+    let state = state_with(vec![ItemContent::Image(path)]);
+    let html = render_html(&state, "Images");
+
+    assert!(html.contains("src=\"data:image/png;base64,"));
+}
+
+#[test]
+fn test_missing_image_becomes_file_card() {
+    let state = state_with(vec![ItemContent::Image("/nonexistent/photo.png".into())]);
+    let html = render_html(&state, "Images");
+
+    assert!(!html.contains("data:image"));
+    assert!(html.contains("photo.png"));
+}
+
+#[test]
+fn test_charts_render_as_svg() {
+    let mut state = state_with(vec![ItemContent::Chart {
+        data_source_id: 1,
+        source_item_id: None,
+        config: ChartConfig::default(),
+    }]);
+    state.data_sources.insert(
+        1,
+        DataSource {
+            id: 1,
+            name: "Sales".to_string(),
+            columns: vec![
+                DataColumn::new("Month", DataType::Text),
+                DataColumn::new("Total", DataType::Number),
+            ],
+            rows: vec![
+                DataRow::new(vec![DataCell::Text("Jan".into()), DataCell::Number(3.0)]),
+                DataRow::new(vec![DataCell::Text("Feb".into()), DataCell::Number(5.0)]),
+            ],
+            origin: DataOrigin::Manual,
+            dirty: false,
+        },
+    );
+
+    let html = render_html(&state, "Charts");
+    assert!(html.contains("<svg"));
+    assert_eq!(html.matches("<rect").count(), 2);
+    assert!(html.contains(">Feb</text>"));
+}
+
+#[test]
+fn test_export_writes_file() {
+    let dir = tempdir().unwrap();
+    let path = dir.path().join("board.html");
+    let state = board_to_state(&board_with_texts(&["hello"]));
+
+    export_html(&state, "Board", &path).unwrap();
+
+    let html = fs::read_to_string(&path).unwrap();
+    assert!(html.contains("hello"));
+    assert!(html.contains("<script>"));
+}
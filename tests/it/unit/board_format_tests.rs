@@ -0,0 +1,99 @@
+//! Unit tests for board_format module.
+
+use crate::helpers::{board_to_state, board_with_texts};
+use humanboard::board::BoardState;
+use humanboard::board_format::{data_sources_dir, load_split, remove_split, save_split};
+use humanboard::types::DataSource;
+use std::fs;
+use tempfile::tempdir;
+
+fn state_with_data_sources(ids: &[u64]) -> BoardState {
+    let mut state = board_to_state(&board_with_texts(&["a", "b"]));
+    for &id in ids {
+        state
+            .data_sources
+            .insert(id, DataSource::new_empty(id, format!("Data {}", id)));
+    }
+    state
+}
+
+#[test]
+fn test_save_split_writes_data_sources_separately() {
+    let dir = tempdir().unwrap();
+    let path = dir.path().join("board.json");
+
+    save_split(state_with_data_sources(&[1, 2]), &path).unwrap();
+
+    let board_json = fs::read_to_string(&path).unwrap();
+    assert!(board_json.ends_with('\n'));
+    assert!(!board_json.contains("Data 1"));
+    assert!(data_sources_dir(dir.path()).join("1.json").exists());
+    assert!(data_sources_dir(dir.path()).join("2.json").exists());
+}
+
+#[test]
+fn test_split_round_trip() {
+    let dir = tempdir().unwrap();
+    let path = dir.path().join("board.json");
+    save_split(state_with_data_sources(&[1, 2]), &path).unwrap();
+
+    let mut loaded = BoardState::load_from_path(&path).unwrap();
+    assert!(loaded.data_sources.is_empty());
+    load_split(&mut loaded, dir.path());
+
+    assert_eq!(loaded.items.len(), 2);
+    assert_eq!(loaded.data_sources.len(), 2);
+    assert_eq!(loaded.data_sources[&2].name, "Data 2");
+}
+
+#[test]
+fn test_save_split_is_deterministic() {
+    let dir = tempdir().unwrap();
+    let path = dir.path().join("board.json");
+
+    save_split(state_with_data_sources(&[3, 1, 2]), &path).unwrap();
+    let first = fs::read_to_string(&path).unwrap();
+    save_split(state_with_data_sources(&[2, 3, 1]), &path).unwrap();
+    let second = fs::read_to_string(&path).unwrap();
+
+    assert_eq!(first, second);
+}
+
+#[test]
+fn test_save_split_removes_deleted_data_sources() {
+    let dir = tempdir().unwrap();
+    let path = dir.path().join("board.json");
+
+    save_split(state_with_data_sources(&[1, 2]), &path).unwrap();
+    save_split(state_with_data_sources(&[1]), &path).unwrap();
+
+    assert!(data_sources_dir(dir.path()).join("1.json").exists());
+    assert!(!data_sources_dir(dir.path()).join("2.json").exists());
+}
+
+#[test]
+fn test_inline_data_sources_take_precedence() {
+    let dir = tempdir().unwrap();
+    let path = dir.path().join("board.json");
+    save_split(state_with_data_sources(&[1]), &path).unwrap();
+
+    let mut state = board_to_state(&board_with_texts(&["a"]));
+    state
+        .data_sources
+        .insert(1, DataSource::new_empty(1, "Inline".to_string()));
+    load_split(&mut state, dir.path());
+
+    assert_eq!(state.data_sources[&1].name, "Inline");
+}
+
+#[test]
+fn test_remove_split() {
+    let dir = tempdir().unwrap();
+    let path = dir.path().join("board.json");
+    save_split(state_with_data_sources(&[1]), &path).unwrap();
+
+    remove_split(dir.path());
+
+    assert!(!data_sources_dir(dir.path()).exists());
+    assert!(path.exists());
+}
@@ -66,3 +66,49 @@ fn test_board_metadata_trash_and_restore() {
     assert!(!meta.is_deleted());
     assert!(meta.deleted_at.is_none());
 }
+
+#[test]
+fn test_board_metadata_should_purge_respects_retention() {
+    let mut meta = BoardMetadata::new("Test".to_string());
+    assert!(!meta.should_purge(30));
+
+    meta.move_to_trash();
+    assert!(!meta.should_purge(30));
+
+    // Pretend the board was trashed 10 days ago
+    let ten_days = 10 * 24 * 60 * 60;
+    meta.deleted_at = meta.deleted_at.map(|t| t - ten_days);
+    assert!(meta.should_purge(7));
+    assert!(!meta.should_purge(30));
+
+    // Zero retention keeps trashed boards forever
+    assert!(!meta.should_purge(0));
+}
+
+#[test]
+fn test_open_board_registry_claim_and_release() {
+    let id = "registry-claim-test";
+
+    assert!(BoardIndex::claim_open_board(id, 1).is_ok());
+    assert!(BoardIndex::is_board_open(id));
+    assert_eq!(BoardIndex::window_for_board(id), Some(1));
+
+    // Same window can re-claim, other windows are refused
+    assert!(BoardIndex::claim_open_board(id, 1).is_ok());
+    assert_eq!(BoardIndex::claim_open_board(id, 2), Err(1));
+
+    BoardIndex::release_open_board(id, 1);
+    assert!(!BoardIndex::is_board_open(id));
+    assert!(BoardIndex::claim_open_board(id, 2).is_ok());
+    BoardIndex::release_open_board(id, 2);
+}
+
+#[test]
+fn test_open_board_registry_ignores_foreign_release() {
+    let id = "registry-foreign-release-test";
+
+    assert!(BoardIndex::claim_open_board(id, 7).is_ok());
+    BoardIndex::release_open_board(id, 8);
+    assert_eq!(BoardIndex::window_for_board(id), Some(7));
+    BoardIndex::release_open_board(id, 7);
+}
@@ -0,0 +1,131 @@
+//! Unit tests for board_journal module.
+
+use crate::helpers::{board_to_state, board_with_texts, empty_board};
+use humanboard::board::UndoOperation;
+use humanboard::board_journal::{BoardJournal, JournalEntry, replay};
+use humanboard::types::{CanvasItem, ItemContent};
+use tempfile::tempdir;
+
+fn text_item(id: u64, text: &str) -> CanvasItem {
+    CanvasItem {
+        id,
+        position: (10.0, 20.0),
+        size: (200.0, 100.0),
+        content: ItemContent::Text(text.to_string()),
+        locked: false,
+        volume: None,
+    }
+}
+
+#[test]
+fn test_empty_journal_has_nothing_pending() {
+    let dir = tempdir().unwrap();
+    let journal = BoardJournal::new(dir.path().to_path_buf());
+
+    assert!(!journal.has_pending_recovery());
+    assert!(!journal.stash_for_recovery());
+    assert!(journal.pending_entries().is_empty());
+}
+
+#[test]
+fn test_stash_moves_entries_for_recovery() {
+    let dir = tempdir().unwrap();
+    let journal = BoardJournal::new(dir.path().to_path_buf());
+
+    journal
+        .append(&JournalEntry::Operation(UndoOperation::AddItem(text_item(
+            0, "a",
+        ))))
+        .unwrap();
+    assert!(journal.has_pending_recovery());
+
+    assert!(journal.stash_for_recovery());
+    assert!(!journal.path().exists());
+    assert_eq!(journal.pending_entries().len(), 1);
+
+    journal.discard_recovered();
+    assert!(!journal.has_pending_recovery());
+}
+
+#[test]
+fn test_clear_removes_active_journal() {
+    let dir = tempdir().unwrap();
+    let journal = BoardJournal::new(dir.path().to_path_buf());
+
+    journal
+        .append(&JournalEntry::State(board_to_state(&empty_board())))
+        .unwrap();
+    journal.clear();
+
+    assert!(!journal.has_pending_recovery());
+}
+
+#[test]
+fn test_saving_the_board_discards_the_recovered_journal() {
+    let dir = tempdir().unwrap();
+    let journal = BoardJournal::new(dir.path().to_path_buf());
+
+    journal
+        .append(&JournalEntry::State(board_to_state(&empty_board())))
+        .unwrap();
+    assert!(journal.stash_for_recovery());
+    journal
+        .append(&JournalEntry::Operation(UndoOperation::AddItem(text_item(
+            0, "a",
+        ))))
+        .unwrap();
+
+    journal.board_saved();
+    assert!(!journal.has_pending_recovery());
+    assert!(journal.pending_entries().is_empty());
+}
+
+#[test]
+fn test_corrupt_tail_is_ignored() {
+    let dir = tempdir().unwrap();
+    let journal = BoardJournal::new(dir.path().to_path_buf());
+
+    journal
+        .append(&JournalEntry::Operation(UndoOperation::AddItem(text_item(
+            0, "a",
+        ))))
+        .unwrap();
+    // Simulate a write cut short by a crash
+    let mut content = std::fs::read_to_string(journal.path()).unwrap();
+    content.push_str("{\"Operation\":{\"AddIt");
+    std::fs::write(journal.path(), content).unwrap();
+
+    journal.stash_for_recovery();
+    assert_eq!(journal.pending_entries().len(), 1);
+}
+
+#[test]
+fn test_replay_operations() {
+    let mut state = board_to_state(&empty_board());
+
+    replay(
+        &mut state,
+        &[
+            JournalEntry::Operation(UndoOperation::AddItem(text_item(0, "a"))),
+            JournalEntry::Operation(UndoOperation::MoveItem {
+                id: 0,
+                old_pos: (10.0, 20.0),
+                new_pos: (50.0, 60.0),
+            }),
+        ],
+    );
+
+    assert_eq!(state.items.len(), 1);
+    assert_eq!(state.items[0].position, (50.0, 60.0));
+    assert_eq!(state.next_item_id, 1);
+}
+
+#[test]
+fn test_replay_state_replaces_everything() {
+    let mut state = board_to_state(&empty_board());
+    let snapshot = board_to_state(&board_with_texts(&["a", "b", "c"]));
+
+    replay(&mut state, &[JournalEntry::State(snapshot)]);
+
+    assert_eq!(state.items.len(), 3);
+}
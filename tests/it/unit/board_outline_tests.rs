@@ -0,0 +1,122 @@
+//! Unit tests for board_outline module.
+
+use crate::helpers::{TestBoardBuilder, empty_board};
+use humanboard::board_outline::{BoardOutline, format_file_size};
+use humanboard::types::{DataSource, ItemContent};
+use std::collections::HashMap;
+use std::fs;
+use tempfile::tempdir;
+
+#[test]
+fn test_outline_groups_items_by_kind() {
+    let board = TestBoardBuilder::new()
+        .with_text_items(&["one", "two"])
+        .with_image_item("/nonexistent/a.png", (0.0, 100.0))
+        .with_link_item("https://example.com", (0.0, 200.0))
+        .build();
+
+    let outline = BoardOutline::build(&board.items, &board.data_sources);
+
+    assert_eq!(outline.item_count, 4);
+    let groups: Vec<(&str, usize)> = outline
+        .groups
+        .iter()
+        .map(|g| (g.label, g.count()))
+        .collect();
+    assert_eq!(groups, vec![("Images", 1), ("Links", 1), ("Notes", 2)]);
+}
+
+#[test]
+fn test_outline_of_empty_board() {
+    let board = empty_board();
+    let outline = BoardOutline::build(&board.items, &board.data_sources);
+
+    assert_eq!(outline.item_count, 0);
+    assert!(outline.groups.is_empty());
+    assert_eq!(outline.total_file_size, 0);
+}
+
+#[test]
+fn test_outline_reads_file_sizes() {
+    let dir = tempdir().unwrap();
+    let path = dir.path().join("photo.png");
+    fs::write(&path, vec![0u8; 2048]).unwrap();
+
+    let board = TestBoardBuilder::new()
+        .with_image_item(&path, (0.0, 0.0))
+        .with_image_item("/nonexistent/missing.png", (100.0, 0.0))
+        .build();
+    let outline = BoardOutline::build(&board.items, &board.data_sources);
+
+    let images = &outline.groups[0];
+    assert_eq!(images.entries[0].name, "photo.png");
+    assert_eq!(images.entries[0].file_size, Some(2048));
+    assert_eq!(images.entries[1].file_size, None);
+    assert_eq!(images.file_size, 2048);
+    assert_eq!(outline.total_file_size, 2048);
+}
+
+#[test]
+fn test_rebuild_reuses_known_file_sizes() {
+    let dir = tempdir().unwrap();
+    let path = dir.path().join("clip.mp4");
+    fs::write(&path, vec![0u8; 100]).unwrap();
+
+    let board = TestBoardBuilder::new()
+        .with_video_item(&path, (0.0, 0.0))
+        .build();
+    let mut outline = BoardOutline::build(&board.items, &board.data_sources);
+
+    // Sizes already read are not looked up again on rebuild
+    fs::write(&path, vec![0u8; 500]).unwrap();
+    outline.rebuild(&board.items, &board.data_sources);
+    assert_eq!(outline.total_file_size, 100);
+}
+
+#[test]
+fn test_entry_names_are_single_line() {
+    let long_note = "x".repeat(200);
+    let board = TestBoardBuilder::new()
+        .with_text_items(&["\n  Shopping list\nmilk\neggs", &long_note, "   "])
+        .build();
+    let outline = BoardOutline::build(&board.items, &board.data_sources);
+
+    let names: Vec<&str> = outline.groups[0]
+        .entries
+        .iter()
+        .map(|e| e.name.as_str())
+        .collect();
+    assert_eq!(names[0], "Shopping list");
+    assert!(names[1].ends_with('…'));
+    assert_eq!(names[1].chars().count(), 60);
+    assert_eq!(names[2], "Untitled");
+}
+
+#[test]
+fn test_tables_are_named_after_their_data_source() {
+    let board = TestBoardBuilder::new()
+        .with_item(
+            ItemContent::Table {
+                data_source_id: 7,
+                show_headers: true,
+                stripe: true,
+            },
+            (0.0, 0.0),
+        )
+        .build();
+    let mut data_sources = HashMap::new();
+    data_sources.insert(7, DataSource::new_empty(7, "Budget".to_string()));
+
+    let outline = BoardOutline::build(&board.items, &data_sources);
+    assert_eq!(outline.groups[0].label, "Tables");
+    assert_eq!(outline.groups[0].entries[0].name, "Budget");
+}
+
+#[test]
+fn test_format_file_size() {
+    assert_eq!(format_file_size(0), "0 B");
+    assert_eq!(format_file_size(1023), "1023 B");
+    assert_eq!(format_file_size(1536), "1.5 KB");
+    assert_eq!(format_file_size(5 * 1024 * 1024), "5.0 MB");
+    assert_eq!(format_file_size(3 * 1024 * 1024 * 1024), "3.0 GB");
+}
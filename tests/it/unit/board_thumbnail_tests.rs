@@ -0,0 +1,52 @@
+//! Unit tests for board_thumbnail module.
+
+use crate::helpers::{board_to_state, board_with_texts, empty_board};
+use humanboard::board_thumbnail::{
+    THUMBNAIL_HEIGHT, THUMBNAIL_WIDTH, latest_thumbnail, render_thumbnail, save_thumbnail,
+};
+use tempfile::tempdir;
+
+#[test]
+fn test_thumbnail_dimensions() {
+    let image = render_thumbnail(&board_to_state(&board_with_texts(&["a", "b"])));
+    assert_eq!(image.width(), THUMBNAIL_WIDTH);
+    assert_eq!(image.height(), THUMBNAIL_HEIGHT);
+}
+
+#[test]
+fn test_empty_board_thumbnail_is_plain_background() {
+    let image = render_thumbnail(&board_to_state(&empty_board()));
+    let first = *image.get_pixel(0, 0);
+    assert!(image.pixels().all(|p| *p == first));
+}
+
+#[test]
+fn test_items_are_drawn() {
+    let image = render_thumbnail(&board_to_state(&board_with_texts(&["a"])));
+    let center = image.get_pixel(THUMBNAIL_WIDTH / 2, THUMBNAIL_HEIGHT / 2);
+    assert_ne!(center, image.get_pixel(0, 0));
+}
+
+#[test]
+fn test_save_and_find_latest_thumbnail() {
+    let dir = tempdir().unwrap();
+    assert!(latest_thumbnail(dir.path()).is_none());
+
+    let state = board_to_state(&board_with_texts(&["a"]));
+    let path = save_thumbnail(dir.path(), &state).unwrap();
+
+    assert!(path.exists());
+    assert_eq!(latest_thumbnail(dir.path()), Some(path));
+}
+
+#[test]
+fn test_save_replaces_older_thumbnails() {
+    let dir = tempdir().unwrap();
+    std::fs::write(dir.path().join("thumbnail-1.png"), b"stale").unwrap();
+
+    let state = board_to_state(&board_with_texts(&["a"]));
+    let path = save_thumbnail(dir.path(), &state).unwrap();
+
+    assert!(!dir.path().join("thumbnail-1.png").exists());
+    assert_eq!(latest_thumbnail(dir.path()), Some(path));
+}
@@ -0,0 +1,200 @@
+//! Unit tests for board_versions module.
+
+use crate::helpers::{TestBoardBuilder, board_to_state, board_with_texts};
+use humanboard::board_versions::{
+    VersionSummary, VersionTracker, list_versions, prune_versions, save_checkpoint, save_version,
+    snapshot_file_text,
+};
+use tempfile::tempdir;
+
+#[test]
+fn test_list_versions_empty_dir() {
+    let dir = tempdir().unwrap();
+    assert!(list_versions(dir.path()).is_empty());
+}
+
+#[test]
+fn test_save_and_list_versions_newest_first() {
+    let dir = tempdir().unwrap();
+    let state = board_to_state(&board_with_texts(&["a", "b"]));
+
+    let first = save_version(dir.path(), &state).unwrap();
+    let second = save_version(dir.path(), &state).unwrap();
+
+    let versions = list_versions(dir.path());
+    assert_eq!(versions.len(), 2);
+    assert_eq!(versions[0], second);
+    assert_eq!(versions[1], first);
+}
+
+#[test]
+fn test_version_roundtrip() {
+    let dir = tempdir().unwrap();
+    let state = board_to_state(&board_with_texts(&["one", "two", "three"]));
+
+    let version = save_version(dir.path(), &state).unwrap();
+    let loaded = version.load().unwrap();
+
+    assert_eq!(loaded.items.len(), 3);
+    assert_eq!(loaded.next_item_id, state.next_item_id);
+}
+
+#[test]
+fn test_prune_keeps_newest() {
+    let dir = tempdir().unwrap();
+    let state = board_to_state(&board_with_texts(&["a"]));
+
+    for _ in 0..5 {
+        save_version(dir.path(), &state).unwrap();
+    }
+    let newest = list_versions(dir.path())[0].clone();
+
+    assert_eq!(prune_versions(dir.path(), 2), 3);
+    let remaining = list_versions(dir.path());
+    assert_eq!(remaining.len(), 2);
+    assert_eq!(remaining[0], newest);
+}
+
+#[test]
+fn test_checkpoint_keeps_name() {
+    let dir = tempdir().unwrap();
+    let state = board_to_state(&board_with_texts(&["a"]));
+
+    save_version(dir.path(), &state).unwrap();
+    let checkpoint = save_checkpoint(dir.path(), &state, "Before redesign").unwrap();
+
+    let versions = list_versions(dir.path());
+    assert_eq!(versions[0], checkpoint);
+    assert!(versions[0].is_checkpoint());
+    assert_eq!(versions[0].title(), "Before redesign");
+    assert!(!versions[1].is_checkpoint());
+    assert_eq!(versions[0].load().unwrap().items.len(), 1);
+}
+
+#[test]
+fn test_prune_keeps_checkpoints() {
+    let dir = tempdir().unwrap();
+    let state = board_to_state(&board_with_texts(&["a"]));
+
+    save_checkpoint(dir.path(), &state, "Kept").unwrap();
+    for _ in 0..4 {
+        save_version(dir.path(), &state).unwrap();
+    }
+
+    assert_eq!(prune_versions(dir.path(), 2), 2);
+    let remaining = list_versions(dir.path());
+    assert_eq!(remaining.len(), 3);
+    assert!(remaining.iter().any(|version| version.title() == "Kept"));
+}
+
+#[test]
+fn test_versions_keep_code_file_text() {
+    let dir = tempdir().unwrap();
+    let file = dir.path().join("main.rs");
+    std::fs::write(&file, "fn main() {}\n").unwrap();
+    let board = TestBoardBuilder::new()
+        .with_code_item(&file, "rust", (0.0, 0.0))
+        .build();
+    let state = board_to_state(&board);
+
+    let first = save_version(dir.path(), &state).unwrap();
+    std::fs::write(&file, "fn main() { run() }\n").unwrap();
+    assert_eq!(
+        snapshot_file_text(dir.path(), &file),
+        Some((first, "fn main() {}\n".to_string()))
+    );
+
+    let second = save_version(dir.path(), &state).unwrap();
+    let (newest, text) = snapshot_file_text(dir.path(), &file).unwrap();
+    assert_eq!(newest, second);
+    assert_eq!(text, "fn main() { run() }\n");
+    assert!(snapshot_file_text(dir.path(), &dir.path().join("other.rs")).is_none());
+}
+
+#[test]
+fn test_prune_removes_kept_file_texts() {
+    let dir = tempdir().unwrap();
+    let file = dir.path().join("notes.py");
+    std::fs::write(&file, "# Notes").unwrap();
+    let board = TestBoardBuilder::new()
+        .with_code_item(&file, "python", (0.0, 0.0))
+        .build();
+    let state = board_to_state(&board);
+
+    let oldest = save_version(dir.path(), &state).unwrap();
+    save_version(dir.path(), &state).unwrap();
+    assert!(oldest.files_dir().exists());
+
+    prune_versions(dir.path(), 1);
+    assert!(!oldest.files_dir().exists());
+}
+
+#[test]
+fn test_delete_removes_checkpoint_name_and_file_texts() {
+    let dir = tempdir().unwrap();
+    let file = dir.path().join("notes.py");
+    std::fs::write(&file, "# Notes").unwrap();
+    let board = TestBoardBuilder::new()
+        .with_code_item(&file, "python", (0.0, 0.0))
+        .build();
+    let state = board_to_state(&board);
+
+    let kept = save_version(dir.path(), &state).unwrap();
+    let checkpoint = save_checkpoint(dir.path(), &state, "Before redesign").unwrap();
+    assert!(checkpoint.files_dir().exists());
+
+    checkpoint.delete().unwrap();
+    assert!(!checkpoint.path.exists());
+    assert!(!checkpoint.path.with_extension("name").exists());
+    assert!(!checkpoint.files_dir().exists());
+    assert_eq!(list_versions(dir.path()), vec![kept]);
+}
+
+#[test]
+fn test_list_versions_ignores_unrelated_files() {
+    let dir = tempdir().unwrap();
+    let versions_dir = dir.path().join("versions");
+    std::fs::create_dir_all(&versions_dir).unwrap();
+    std::fs::write(versions_dir.join("notes.txt"), "hi").unwrap();
+    std::fs::write(versions_dir.join("not-a-time.json"), "{}").unwrap();
+
+    assert!(list_versions(dir.path()).is_empty());
+}
+
+#[test]
+fn test_tracker_not_due_without_changes() {
+    let tracker = VersionTracker::new();
+    assert!(!tracker.is_due());
+}
+
+#[test]
+fn test_tracker_due_on_first_change_then_waits() {
+    let mut tracker = VersionTracker::new();
+    tracker.record_operation();
+    assert!(tracker.is_due());
+
+    tracker.mark_captured();
+    tracker.record_operation();
+    assert!(!tracker.is_due(), "interval hasn't elapsed yet");
+}
+
+#[test]
+fn test_tracker_due_after_many_operations() {
+    let mut tracker = VersionTracker::new();
+    tracker.mark_captured();
+    for _ in 0..humanboard::constants::VERSION_SNAPSHOT_OPERATIONS {
+        tracker.record_operation();
+    }
+    assert!(tracker.is_due());
+}
+
+#[test]
+fn test_summary_counts_and_extent() {
+    let state = board_to_state(&board_with_texts(&["a", "b"]));
+    let summary = VersionSummary::from_state(&state);
+
+    assert_eq!(summary.item_count, 2);
+    assert_eq!(summary.counts_by_type.values().sum::<usize>(), 2);
+    assert!(summary.extent().is_some());
+    assert!(VersionSummary::default().extent().is_none());
+}
@@ -0,0 +1,87 @@
+//! Unit tests for canvas background styles.
+
+use humanboard::canvas_background::{
+    CanvasBackground, MIN_PATTERN_SPACING, grid_positions, pattern_spacing,
+};
+use std::path::PathBuf;
+
+#[test]
+fn test_parse_style_names() {
+    assert_eq!(
+        CanvasBackground::parse("dots"),
+        Some(CanvasBackground::Dots)
+    );
+    assert_eq!(
+        CanvasBackground::parse(" Grid "),
+        Some(CanvasBackground::Lines)
+    );
+    assert_eq!(
+        CanvasBackground::parse("none"),
+        Some(CanvasBackground::Plain)
+    );
+    assert_eq!(CanvasBackground::parse("stripes"), None);
+}
+
+#[test]
+fn test_parse_hex_colors() {
+    assert_eq!(
+        CanvasBackground::parse("#1E1E2E"),
+        Some(CanvasBackground::Color {
+            color: "#1e1e2e".to_string()
+        })
+    );
+    assert_eq!(
+        CanvasBackground::parse("336699"),
+        Some(CanvasBackground::Color {
+            color: "#336699".to_string()
+        })
+    );
+    assert_eq!(CanvasBackground::parse("#12345"), None);
+    assert_eq!(CanvasBackground::parse("#zzzzzz"), None);
+}
+
+#[test]
+fn test_next_cycles_builtin_styles() {
+    assert_eq!(CanvasBackground::Plain.next(), CanvasBackground::Dots);
+    assert_eq!(CanvasBackground::Dots.next(), CanvasBackground::Lines);
+    assert_eq!(CanvasBackground::Lines.next(), CanvasBackground::Plain);
+    let image = CanvasBackground::Image {
+        path: PathBuf::from("/tmp/paper.png"),
+    };
+    assert_eq!(image.next(), CanvasBackground::Plain);
+}
+
+#[test]
+fn test_serializes_with_style_tag() {
+    let color = CanvasBackground::Color {
+        color: "#102030".to_string(),
+    };
+    let json = serde_json::to_string(&color).unwrap();
+    assert_eq!(json, r##"{"style":"color","color":"#102030"}"##);
+    assert_eq!(
+        serde_json::from_str::<CanvasBackground>(r#"{"style":"dots"}"#).unwrap(),
+        CanvasBackground::Dots
+    );
+}
+
+#[test]
+fn test_pattern_spacing_follows_grid_and_zoom() {
+    assert_eq!(pattern_spacing(20.0, 1.0), Some(20.0));
+    assert_eq!(pattern_spacing(20.0, 2.0), Some(40.0));
+    assert_eq!(pattern_spacing(0.0, 1.0), None);
+}
+
+#[test]
+fn test_pattern_spacing_thins_out_when_zoomed_out() {
+    // 20 * 0.1 = 2px, doubled until it reaches the minimum
+    let spacing = pattern_spacing(20.0, 0.1).unwrap();
+    assert_eq!(spacing, 16.0);
+    assert!(spacing >= MIN_PATTERN_SPACING);
+}
+
+#[test]
+fn test_grid_positions_follow_pan_offset() {
+    assert_eq!(grid_positions(0.0, 50.0, 0.0, 20.0), vec![0.0, 20.0, 40.0]);
+    assert_eq!(grid_positions(0.0, 50.0, 25.0, 20.0), vec![5.0, 25.0, 45.0]);
+    assert_eq!(grid_positions(100.0, 30.0, -5.0, 20.0), vec![115.0]);
+}
@@ -0,0 +1,136 @@
+//! Unit tests for canvas_find module.
+
+use crate::helpers::{test_content_item_at, text_box_content};
+use humanboard::canvas_find::{FindMatches, find_on_canvas};
+use humanboard::types::{CanvasItem, DataSource, ItemContent};
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+fn board() -> Vec<CanvasItem> {
+    vec![
+        test_content_item_at(
+            1,
+            text_box_content("Budget for Q3"),
+            (400.0, 0.0),
+            (200.0, 100.0),
+        ),
+        test_content_item_at(
+            2,
+            ItemContent::Image(PathBuf::from("/img/budget.png")),
+            (0.0, 300.0),
+            (200.0, 100.0),
+        ),
+        test_content_item_at(
+            3,
+            text_box_content("Travel plans"),
+            (0.0, 0.0),
+            (200.0, 100.0),
+        ),
+        test_content_item_at(
+            4,
+            ItemContent::Markdown {
+                path: PathBuf::from("/notes/plan.md"),
+                title: "Plan".to_string(),
+                content: "# Plan\nKeep the BUDGET small #finance".to_string(),
+            },
+            (100.0, 600.0),
+            (200.0, 100.0),
+        ),
+        test_content_item_at(
+            5,
+            ItemContent::Table {
+                data_source_id: 7,
+                show_headers: true,
+                stripe: false,
+            },
+            (0.0, 900.0),
+            (200.0, 100.0),
+        ),
+    ]
+}
+
+#[test]
+fn test_find_matches_names_and_text_in_reading_order() {
+    let items = board();
+    let sources = HashMap::new();
+    assert_eq!(find_on_canvas(&items, &sources, "budget"), vec![1, 2, 4]);
+    assert_eq!(find_on_canvas(&items, &sources, "PLAN"), vec![3, 4]);
+    assert!(find_on_canvas(&items, &sources, "zebra").is_empty());
+}
+
+#[test]
+fn test_find_needs_every_word() {
+    let items = board();
+    let sources = HashMap::new();
+    assert_eq!(find_on_canvas(&items, &sources, "budget q3"), vec![1]);
+    assert_eq!(find_on_canvas(&items, &sources, "small budget"), vec![4]);
+}
+
+#[test]
+fn test_find_applies_search_operators() {
+    let items = board();
+    let sources = HashMap::new();
+    assert_eq!(
+        find_on_canvas(&items, &sources, "budget type:image"),
+        vec![2]
+    );
+    assert_eq!(find_on_canvas(&items, &sources, "tag:finance"), vec![4]);
+}
+
+#[test]
+fn test_find_matches_tables_by_their_source_name() {
+    let items = board();
+    let mut sources = HashMap::new();
+    sources.insert(7, DataSource::new_empty(7, "Expenses".to_string()));
+    assert_eq!(find_on_canvas(&items, &sources, "expenses"), vec![5]);
+}
+
+#[test]
+fn test_empty_query_finds_nothing() {
+    let items = board();
+    assert!(find_on_canvas(&items, &HashMap::new(), "   ").is_empty());
+}
+
+#[test]
+fn test_stepping_wraps_both_ways() {
+    let mut find = FindMatches::default();
+    assert_eq!(find.next_match(), None);
+    assert_eq!(find.label(), "No matches");
+
+    find.update(vec![10, 20, 30], true);
+    assert!(find.is_active());
+    assert!(find.contains(20));
+    assert!(!find.contains(40));
+    assert_eq!(find.label(), "3 found");
+
+    assert_eq!(find.next_match(), Some(10));
+    assert_eq!(find.next_match(), Some(20));
+    assert_eq!(find.label(), "2/3");
+    assert_eq!(find.next_match(), Some(30));
+    assert_eq!(find.next_match(), Some(10));
+    assert_eq!(find.prev_match(), Some(30));
+
+    let mut find = FindMatches::default();
+    find.update(vec![10, 20, 30], true);
+    assert_eq!(find.prev_match(), Some(30));
+}
+
+#[test]
+fn test_update_keeps_the_current_match_when_it_still_matches() {
+    let mut find = FindMatches::default();
+    find.update(vec![10, 20, 30], true);
+    find.next_match();
+    find.next_match();
+
+    find.update(vec![5, 20], true);
+    assert_eq!(find.current_id(), Some(20));
+    assert_eq!(find.current_index(), Some(1));
+
+    find.update(vec![5, 30], true);
+    assert_eq!(find.current_id(), None);
+    assert_eq!(find.next_match(), Some(5));
+
+    find.clear();
+    assert!(!find.is_active());
+    assert!(find.is_empty());
+}
@@ -0,0 +1,163 @@
+//! Unit tests for offscreen canvas snapshots.
+
+use crate::helpers::{board_to_state, empty_board, test_canvas_item_at};
+use humanboard::canvas_background::CanvasBackground;
+use humanboard::canvas_snapshot::{
+    DEFAULT_BACKGROUND, MAX_SNAPSHOT_DIMENSION, SnapshotOptions, TRANSPARENT, encode_snapshot_png,
+    render_snapshot, save_snapshot, snapshot_transform,
+};
+use humanboard::types::{ArrowHead, CanvasItem, ItemContent, ShapeType};
+use image::Rgba;
+use tempfile::tempdir;
+
+fn state_with_item_at(pos: (f32, f32), size: (f32, f32)) -> humanboard::board::BoardState {
+    let mut state = board_to_state(&empty_board());
+    state.items.push(test_canvas_item_at(1, "a", pos, size));
+    state
+}
+
+#[test]
+fn test_snapshot_uses_requested_size() {
+    let state = state_with_item_at((0.0, 0.0), (100.0, 100.0));
+    let image = render_snapshot(&state, &SnapshotOptions::fit(1920, 1080));
+    assert_eq!((image.width(), image.height()), (1920, 1080));
+}
+
+#[test]
+fn test_fit_centers_content() {
+    let state = state_with_item_at((500.0, 500.0), (100.0, 100.0));
+    let transform = snapshot_transform(&state, &SnapshotOptions::fit(200, 200));
+    assert_eq!(transform.scale, 1.0);
+    assert_eq!(transform.apply(500.0, 500.0), (50.0, 50.0));
+}
+
+#[test]
+fn test_region_maps_to_whole_image() {
+    let state = state_with_item_at((0.0, 0.0), (10.0, 10.0));
+    let options = SnapshotOptions::region(400, 400, (100.0, 100.0, 200.0, 200.0));
+    let transform = snapshot_transform(&state, &options);
+    assert_eq!(transform.scale, 2.0);
+    assert_eq!(transform.apply(100.0, 100.0), (0.0, 0.0));
+    assert_eq!(transform.apply(300.0, 300.0), (400.0, 400.0));
+}
+
+#[test]
+fn test_region_skips_items_outside() {
+    let state = state_with_item_at((0.0, 0.0), (10.0, 10.0));
+    let options = SnapshotOptions::region(100, 100, (1000.0, 1000.0, 100.0, 100.0));
+    let image = render_snapshot(&state, &options);
+    assert!(image.pixels().all(|p| *p == DEFAULT_BACKGROUND));
+}
+
+#[test]
+fn test_full_board_is_sized_to_content() {
+    let state = state_with_item_at((0.0, 0.0), (300.0, 100.0));
+    let options = SnapshotOptions::full_board(&state, 2.0, 10.0);
+    assert_eq!((options.width, options.height), (620, 220));
+}
+
+#[test]
+fn test_full_board_is_capped() {
+    let state = state_with_item_at((0.0, 0.0), (100_000.0, 1000.0));
+    let options = SnapshotOptions::full_board(&state, 1.0, 0.0);
+    assert!(options.width <= MAX_SNAPSHOT_DIMENSION);
+    assert!(options.width >= MAX_SNAPSHOT_DIMENSION - 1);
+    assert!(options.height < 1000);
+}
+
+#[test]
+fn test_board_color_background_is_drawn() {
+    let mut state = board_to_state(&empty_board());
+    state.background = Some(CanvasBackground::Color {
+        color: "#102030".to_string(),
+    });
+    let image = render_snapshot(&state, &SnapshotOptions::fit(50, 50));
+    assert_eq!(*image.get_pixel(0, 0), Rgba([16, 32, 48, 255]));
+}
+
+#[test]
+fn test_dot_background_is_drawn() {
+    let mut state = board_to_state(&empty_board());
+    state.background = Some(CanvasBackground::Dots);
+    let image = render_snapshot(&state, &SnapshotOptions::fit(100, 100));
+    assert!(image.pixels().any(|p| *p != DEFAULT_BACKGROUND));
+}
+
+#[test]
+fn test_save_snapshot_writes_png() {
+    let dir = tempdir().unwrap();
+    let path = dir.path().join("board.png");
+    let state = state_with_item_at((0.0, 0.0), (100.0, 100.0));
+
+    save_snapshot(&state, &SnapshotOptions::fit(64, 32), &path).unwrap();
+
+    let saved = image::open(&path).unwrap();
+    assert_eq!((saved.width(), saved.height()), (64, 32));
+}
+
+#[test]
+fn test_transparent_snapshot_leaves_out_background() {
+    let mut state = state_with_item_at((0.0, 0.0), (100.0, 100.0));
+    state.background = Some(CanvasBackground::Color {
+        color: "#102030".to_string(),
+    });
+    let options = SnapshotOptions::fit(200, 200).with_transparent_background();
+    let image = render_snapshot(&state, &options);
+
+    // Corners are outside the centered item
+    assert_eq!(*image.get_pixel(0, 0), TRANSPARENT);
+    assert_ne!(*image.get_pixel(100, 100), TRANSPARENT);
+}
+
+#[test]
+fn test_encode_snapshot_png() {
+    let state = state_with_item_at((0.0, 0.0), (100.0, 50.0));
+    let options = SnapshotOptions::full_board(&state, 2.0, 16.0).with_transparent_background();
+    let bytes = encode_snapshot_png(&state, &options).unwrap();
+
+    let decoded = image::load_from_memory(&bytes).unwrap().to_rgba8();
+    assert_eq!((decoded.width(), decoded.height()), (232, 132));
+    assert_eq!(*decoded.get_pixel(0, 0), TRANSPARENT);
+}
+
+#[test]
+fn test_shapes_and_arrows_are_drawn_as_themselves() {
+    let mut state = board_to_state(&empty_board());
+    state.items.push(CanvasItem {
+        id: 1,
+        position: (0.0, 0.0),
+        size: (100.0, 100.0),
+        content: ItemContent::Shape {
+            shape_type: ShapeType::Ellipse,
+            fill_color: Some("#00ff00".to_string()),
+            border_color: "#0000ff".to_string(),
+            border_width: 4.0,
+        },
+        locked: false,
+        volume: None,
+    });
+    state.items.push(CanvasItem {
+        id: 2,
+        position: (0.0, 150.0),
+        size: (100.0, 0.0),
+        content: ItemContent::Arrow {
+            end_offset: (100.0, 0.0),
+            color: "#ff0000".to_string(),
+            thickness: 4.0,
+            head_style: ArrowHead::Arrow,
+        },
+        locked: false,
+        volume: None,
+    });
+    let options = SnapshotOptions::region(200, 200, (0.0, 0.0, 200.0, 200.0));
+    let image = render_snapshot(&state, &options);
+
+    assert_eq!(*image.get_pixel(50, 50), Rgba([0, 255, 0, 255]));
+    assert_eq!(*image.get_pixel(50, 1), Rgba([0, 0, 255, 255]));
+    // The ellipse leaves the corner of its bounds empty
+    assert_eq!(*image.get_pixel(3, 3), DEFAULT_BACKGROUND);
+    assert_eq!(*image.get_pixel(50, 150), Rgba([255, 0, 0, 255]));
+    // The head is wider than the line
+    assert_eq!(*image.get_pixel(88, 153), Rgba([255, 0, 0, 255]));
+    assert_eq!(*image.get_pixel(50, 153), DEFAULT_BACKGROUND);
+}
@@ -0,0 +1,70 @@
+//! Unit tests for card_refresh module.
+
+use humanboard::card_refresh::{CardFileWatch, UPDATE_FLASH_DURATION, update_flash_opacity};
+use std::fs;
+use std::time::Duration;
+use tempfile::tempdir;
+
+#[test]
+fn test_files_seen_first_are_not_reported() {
+    let dir = tempdir().unwrap();
+    let path = dir.path().join("notes.md");
+    fs::write(&path, "# Notes").unwrap();
+
+    let mut watch = CardFileWatch::default();
+    assert!(watch.is_due());
+    assert!(watch.changed_files([path.as_path()]).is_empty());
+    assert!(!watch.is_due());
+    assert!(watch.changed_files([path.as_path()]).is_empty());
+}
+
+#[test]
+fn test_rewritten_file_is_reported_once() {
+    let dir = tempdir().unwrap();
+    let path = dir.path().join("main.rs");
+    fs::write(&path, "fn main() {}").unwrap();
+
+    let mut watch = CardFileWatch::default();
+    watch.changed_files([path.as_path(), path.as_path()]);
+    fs::write(&path, "fn main() {\n    println!(\"hi\");\n}\n").unwrap();
+
+    assert_eq!(
+        watch.changed_files([path.as_path(), path.as_path()]),
+        vec![path.clone()]
+    );
+    assert!(watch.changed_files([path.as_path()]).is_empty());
+}
+
+#[test]
+fn test_deleted_file_is_not_reported() {
+    let dir = tempdir().unwrap();
+    let path = dir.path().join("gone.md");
+    fs::write(&path, "bye").unwrap();
+
+    let mut watch = CardFileWatch::default();
+    watch.changed_files([path.as_path()]);
+    fs::remove_file(&path).unwrap();
+    assert!(watch.changed_files([path.as_path()]).is_empty());
+}
+
+#[test]
+fn test_cleared_watch_forgets_files() {
+    let dir = tempdir().unwrap();
+    let path = dir.path().join("notes.md");
+    fs::write(&path, "one").unwrap();
+
+    let mut watch = CardFileWatch::default();
+    watch.changed_files([path.as_path()]);
+    watch.clear();
+    assert!(watch.is_due());
+    fs::write(&path, "one two").unwrap();
+    assert!(watch.changed_files([path.as_path()]).is_empty());
+}
+
+#[test]
+fn test_update_flash_fades_out() {
+    assert_eq!(update_flash_opacity(Duration::ZERO), Some(1.0));
+    let halfway = update_flash_opacity(UPDATE_FLASH_DURATION / 2).unwrap();
+    assert!(halfway > 0.0 && halfway < 1.0);
+    assert_eq!(update_flash_opacity(UPDATE_FLASH_DURATION), None);
+}
@@ -0,0 +1,101 @@
+//! Unit tests for code card excerpts.
+
+use crate::helpers::test_canvas_item;
+use humanboard::code_excerpt::{
+    CODE_EXCERPT_LINES, CodeExcerpt, CodeExcerptCache, excerpt_range, line_anchor,
+    parse_line_range, split_line_anchor,
+};
+use humanboard::types::ItemContent;
+use std::fs;
+use std::sync::Arc;
+
+#[test]
+fn test_parses_line_ranges() {
+    assert_eq!(parse_line_range("10-40"), Some((10, 40)));
+    assert_eq!(parse_line_range(" L10:L40 "), Some((10, 40)));
+    assert_eq!(parse_line_range("40-10"), Some((10, 40)));
+    assert_eq!(parse_line_range("7"), Some((7, 7)));
+    assert_eq!(parse_line_range("0-5"), None);
+    assert_eq!(parse_line_range("ten"), None);
+}
+
+#[test]
+fn test_splits_line_anchors_off_references() {
+    assert_eq!(
+        split_line_anchor("/src/main.rs#L20-L45"),
+        ("/src/main.rs", Some((20, 45)))
+    );
+    assert_eq!(
+        split_line_anchor("src/lib.rs#L7"),
+        ("src/lib.rs", Some((7, 7)))
+    );
+    assert_eq!(split_line_anchor("/src/main.rs"), ("/src/main.rs", None));
+    assert_eq!(
+        split_line_anchor("notes.md#usage"),
+        ("notes.md#usage", None)
+    );
+}
+
+#[test]
+fn test_line_anchor_round_trips() {
+    assert_eq!(line_anchor((20, 45)), "#L20-L45");
+    assert_eq!(line_anchor((7, 7)), "#L7");
+    assert_eq!(parse_line_range(&line_anchor((20, 45))), Some((20, 45)));
+}
+
+#[test]
+fn test_excerpt_range_defaults_to_start_of_file() {
+    assert_eq!(excerpt_range(30, None), 0..30);
+    assert_eq!(excerpt_range(10_000, None), 0..CODE_EXCERPT_LINES);
+}
+
+#[test]
+fn test_pinned_range_is_clamped_to_file() {
+    assert_eq!(excerpt_range(100, Some((10, 40))), 9..40);
+    assert_eq!(excerpt_range(20, Some((10, 40))), 9..20);
+    assert_eq!(excerpt_range(5, Some((10, 40))), 5..5);
+}
+
+#[test]
+fn test_excerpt_numbers_its_lines() {
+    let text = "fn main() {\n    let a = 1;\n    let b = 2;\n}\n";
+    let excerpt = CodeExcerpt::new(text, Some((2, 3)));
+
+    assert_eq!(excerpt.first_line, 2);
+    assert_eq!(excerpt.last_line(), 3);
+    assert_eq!(excerpt.lines, vec!["    let a = 1;", "    let b = 2;"]);
+    assert!(excerpt.is_partial());
+    assert!(!CodeExcerpt::new(text, None).is_partial());
+}
+
+#[test]
+fn test_cache_reads_expanded_cards_only() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("main.rs");
+    fs::write(&path, "fn main() {}\n").unwrap();
+
+    let mut card = test_canvas_item(1, "");
+    card.content = ItemContent::Code {
+        path: path.clone(),
+        language: "rust".to_string(),
+        lines: None,
+    };
+    card.size = (200.0, 36.0);
+
+    let mut cache = CodeExcerptCache::default();
+    assert!(cache.prepare(std::slice::from_ref(&card)).is_empty());
+
+    card.size = (400.0, 300.0);
+    let first = cache.prepare(std::slice::from_ref(&card));
+    let second = cache.prepare(std::slice::from_ref(&card));
+    assert_eq!(first[&1].lines, vec!["fn main() {}"]);
+    assert!(Arc::ptr_eq(&first[&1], &second[&1]));
+
+    card.content = ItemContent::Code {
+        path,
+        language: "rust".to_string(),
+        lines: Some((1, 1)),
+    };
+    let pinned = cache.prepare(std::slice::from_ref(&card));
+    assert!(!Arc::ptr_eq(&first[&1], &pinned[&1]));
+}
@@ -0,0 +1,138 @@
+//! Unit tests for code symbols module.
+
+use humanboard::code_symbols::{
+    CodeSymbol, SymbolKind, code_symbols, has_nested_symbols, matching_symbols, visible_symbols,
+};
+use std::collections::HashSet;
+
+fn names(symbols: &[CodeSymbol]) -> Vec<(&str, SymbolKind, usize, usize)> {
+    symbols
+        .iter()
+        .map(|s| (s.name.as_str(), s.kind, s.line, s.depth))
+        .collect()
+}
+
+#[test]
+fn test_rust_symbols_nest_by_braces() {
+    let text = "\
+use std::fmt;
+
+pub struct Board {
+    items: Vec<u64>, // { not counted
+}
+
+impl<T> Display for Wrapper<T> where T: Clone {
+    pub(crate) fn fmt(&self) -> String {
+        format!(\"{\")
+    }
+
+    const fn size() -> usize { 0 }
+}
+
+mod tests {
+    fn helper() {}
+}
+";
+    assert_eq!(
+        names(&code_symbols(text, "rust")),
+        [
+            ("Board", SymbolKind::Type, 2, 0),
+            ("Display for Wrapper<T>", SymbolKind::Type, 6, 0),
+            ("fmt", SymbolKind::Function, 7, 1),
+            ("size", SymbolKind::Function, 11, 1),
+            ("tests", SymbolKind::Module, 14, 0),
+            ("helper", SymbolKind::Function, 15, 1),
+        ]
+    );
+}
+
+#[test]
+fn test_python_symbols_nest_by_indent() {
+    let text = "\
+import os
+
+class Board:
+    def __init__(self):
+        pass
+
+    async def load(self):
+        pass
+
+def main():
+    pass
+";
+    assert_eq!(
+        names(&code_symbols(text, "python")),
+        [
+            ("Board", SymbolKind::Type, 2, 0),
+            ("__init__", SymbolKind::Function, 3, 1),
+            ("load", SymbolKind::Function, 6, 1),
+            ("main", SymbolKind::Function, 9, 0),
+        ]
+    );
+}
+
+#[test]
+fn test_javascript_functions_without_keywords() {
+    let text = "\
+export default class App {
+  render() {
+    if (ready) {
+    }
+  }
+}
+const handler = async (event) => {
+};
+items.forEach((item) => {
+});
+";
+    assert_eq!(
+        names(&code_symbols(text, "javascript")),
+        [
+            ("App", SymbolKind::Type, 0, 0),
+            ("render", SymbolKind::Function, 1, 1),
+            ("handler", SymbolKind::Function, 6, 0),
+        ]
+    );
+}
+
+#[test]
+fn test_c_and_go_functions() {
+    assert_eq!(
+        names(&code_symbols("static int main(void) {\n}\n", "c")),
+        [("main", SymbolKind::Function, 0, 0)]
+    );
+    assert_eq!(
+        names(&code_symbols("void Board::save() {\n}\n", "cpp")),
+        [("Board::save", SymbolKind::Function, 0, 0)]
+    );
+    assert_eq!(
+        names(&code_symbols("func (s *Server) Start() error {\n}\n", "go")),
+        [("Start", SymbolKind::Function, 0, 0)]
+    );
+}
+
+#[test]
+fn test_unknown_language_has_no_symbols() {
+    assert!(code_symbols("fn main() {}", "markdown").is_empty());
+}
+
+#[test]
+fn test_collapsed_symbols_hide_nested_ones() {
+    let symbols = code_symbols(
+        "struct A {}\nimpl A {\n    fn a() {}\n    fn b() {}\n}\nfn c() {}\n",
+        "rust",
+    );
+    assert_eq!(visible_symbols(&symbols, &HashSet::new()), [0, 1, 2, 3, 4]);
+    assert_eq!(visible_symbols(&symbols, &HashSet::from([1])), [0, 1, 4]);
+    assert!(has_nested_symbols(&symbols, 1));
+    assert!(!has_nested_symbols(&symbols, 2));
+    assert!(!has_nested_symbols(&symbols, 4));
+}
+
+#[test]
+fn test_matching_symbols_ignore_case() {
+    let symbols = code_symbols("fn load_board() {}\nfn save() {}\n", "rust");
+    let found: Vec<_> = matching_symbols(&symbols, "BOARD").map(|s| &s.name).collect();
+    assert_eq!(found, ["load_board"]);
+}
@@ -0,0 +1,45 @@
+//! Unit tests for command palette module.
+
+use humanboard::command_palette::{
+    MAX_BOARD_RESULTS, MAX_COMMAND_RESULTS, board_result_id, board_result_index, command_result_id,
+    command_result_index, is_command_id,
+};
+
+#[test]
+fn test_board_result_ids_round_trip() {
+    for index in [0, 1, MAX_BOARD_RESULTS - 1] {
+        assert_eq!(board_result_index(board_result_id(index)), Some(index));
+    }
+}
+
+#[test]
+fn test_board_result_ids_clear_of_items_and_commands() {
+    assert_eq!(board_result_index(0), None);
+    assert_eq!(board_result_index(42), None);
+    assert_eq!(board_result_index(command_result_id(0)), None);
+    assert_eq!(
+        board_result_index(command_result_id(MAX_COMMAND_RESULTS - 1)),
+        None
+    );
+    assert_eq!(board_result_index(board_result_id(MAX_BOARD_RESULTS)), None);
+}
+
+#[test]
+fn test_command_ids_are_told_apart_from_results() {
+    assert!(is_command_id(command_result_id(0)));
+    assert!(!is_command_id(42));
+    assert!(!is_command_id(board_result_id(0)));
+}
+
+#[test]
+fn test_registered_command_ids_round_trip_and_stay_apart() {
+    for index in [0, 1, MAX_COMMAND_RESULTS - 1] {
+        let id = command_result_id(index);
+        assert_eq!(command_result_index(id), Some(index));
+        assert!(is_command_id(id));
+        assert_eq!(board_result_index(id), None);
+    }
+    assert_eq!(command_result_index(command_result_id(0) + 1), None);
+    assert_eq!(command_result_index(board_result_id(0)), None);
+    assert_eq!(command_result_index(42), None);
+}
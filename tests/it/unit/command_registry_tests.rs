@@ -1,6 +1,9 @@
 //! Unit tests for command_registry module.
 
-use humanboard::command_registry::{create_default_registry, CommandRegistry, GoHomeCommand};
+use humanboard::command_registry::{
+    Command, CommandRegistry, FnCommand, GoHomeCommand, create_default_registry, keystroke_label,
+    platform_keystrokes,
+};
 
 #[test]
 fn test_registry_creation() {
@@ -25,6 +28,32 @@ fn test_default_registry() {
     assert!(registry.get("navigation:home").is_some());
     assert!(registry.get("edit:undo").is_some());
     assert!(registry.get("view:zoom_in").is_some());
+    assert!(registry.get("file:quick_open").is_some());
+}
+
+#[test]
+fn test_default_registry_has_the_palette_commands() {
+    let registry = create_default_registry();
+    for id in [
+        "app:change_theme",
+        "board:new_markdown_note",
+        "view:cycle_background",
+        "arrange:align_left",
+        "arrange:distribute_vertically",
+        "media:pause_all",
+        "media:play_playlist",
+        "code:go_to_symbol",
+        "debug:perf_hud",
+    ] {
+        assert!(registry.get(id).is_some(), "{} isn't registered", id);
+    }
+    // The words typed in the palette before still find them
+    let background = registry.get("view:cycle_background").unwrap();
+    assert!(background.keywords().contains(&"bg"));
+    assert_eq!(
+        registry.get("media:pause_all").unwrap().keybindings(),
+        &["cmd-alt-p"]
+    );
 }
 
 #[test]
@@ -35,3 +64,104 @@ fn test_categories() {
     assert!(categories.contains(&&"Edit".to_string()));
     assert!(categories.contains(&&"View".to_string()));
 }
+
+fn noop_command(id: &'static str, name: &str) -> FnCommand {
+    FnCommand::new(id, name, |_, _, _| {})
+}
+
+#[test]
+fn test_fn_command_builder() {
+    let cmd = noop_command("board:tidy", "Tidy Board")
+        .category("Board")
+        .description("Line items up")
+        .keybinding("cmd-shift-y")
+        .key_context("Canvas")
+        .menu("View", 2)
+        .keywords(&["arrange"]);
+
+    assert_eq!(cmd.id(), "board:tidy");
+    assert_eq!(cmd.name(), "Tidy Board");
+    assert_eq!(cmd.category(), "Board");
+    assert_eq!(cmd.description(), Some("Line items up"));
+    assert_eq!(cmd.keybindings(), &["cmd-shift-y"]);
+    assert_eq!(cmd.key_contexts(), &["Canvas"]);
+    assert_eq!(cmd.menu(), Some(("View", 2)));
+    assert_eq!(cmd.keywords(), &["arrange"]);
+}
+
+#[test]
+fn test_register_keeps_order_and_replaces_same_id() {
+    let mut registry = CommandRegistry::new();
+    registry.register(noop_command("b:first", "First").category("One"));
+    registry.register(noop_command("a:second", "Second"));
+    registry.register(noop_command("b:first", "First Again").category("Two"));
+
+    let ids: Vec<_> = registry.ids().copied().collect();
+    assert_eq!(ids, vec!["b:first", "a:second"]);
+    assert_eq!(registry.len(), 2);
+    assert_eq!(registry.get("b:first").unwrap().name(), "First Again");
+    assert!(registry.by_category("One").is_empty());
+    assert_eq!(registry.by_category("Two").len(), 1);
+}
+
+#[test]
+fn test_platform_keystrokes() {
+    assert_eq!(platform_keystrokes("cmd-k"), vec!["cmd-k", "ctrl-k"]);
+    assert_eq!(platform_keystrokes("ctrl-y"), vec!["ctrl-y"]);
+    assert_eq!(platform_keystrokes("f8"), vec!["f8"]);
+    assert_eq!(
+        platform_keystrokes("cmd-k cmd-s"),
+        vec!["cmd-k cmd-s", "ctrl-k ctrl-s"]
+    );
+}
+
+#[test]
+fn test_keystroke_label() {
+    assert_eq!(keystroke_label("cmd-shift-z"), "Cmd+Shift+Z");
+    assert_eq!(keystroke_label("cmd--"), "Cmd+-");
+    assert_eq!(keystroke_label("cmd-alt-o"), "Cmd+Alt+O");
+    assert_eq!(keystroke_label("f8"), "F8");
+    assert_eq!(keystroke_label("cmd-k cmd-s"), "Cmd+K Cmd+S");
+}
+
+#[test]
+fn test_menu_layout_groups_commands() {
+    let mut registry = CommandRegistry::new();
+    registry.register(noop_command("app:settings", "Settings").menu("App", 0));
+    registry.register(noop_command("file:close", "Close").menu("File", 1));
+    registry.register(noop_command("file:new", "New").menu("File", 0));
+    registry.register(noop_command("file:open", "Open").menu("File", 0));
+    registry.register(noop_command("view:hidden", "Not in a menu"));
+
+    let layout = registry.menu_layout();
+    let names: Vec<Vec<Vec<&str>>> = layout
+        .iter()
+        .map(|(_, groups)| {
+            groups
+                .iter()
+                .map(|group| group.iter().map(|cmd| cmd.id()).collect())
+                .collect()
+        })
+        .collect();
+    assert_eq!(layout[0].0, "App");
+    assert_eq!(layout[1].0, "File");
+    assert_eq!(
+        names,
+        vec![
+            vec![vec!["app:settings"]],
+            vec![vec!["file:new", "file:open"], vec!["file:close"]],
+        ]
+    );
+}
+
+#[test]
+fn test_default_menus_start_with_application_menu() {
+    let registry = create_default_registry();
+    let menus: Vec<String> = registry
+        .menu_layout()
+        .into_iter()
+        .map(|(name, _)| name)
+        .collect();
+    assert_eq!(menus, vec!["Humanboard", "File", "View"]);
+    assert!(registry.get("app:quit").is_some());
+}
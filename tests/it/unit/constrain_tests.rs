@@ -0,0 +1,40 @@
+//! Unit tests for Shift-constrained dragging and resizing.
+
+use humanboard::input::constrain::{DragAxis, constrain_to_axis, dominant_axis, keep_aspect_ratio};
+
+#[test]
+fn test_dominant_axis() {
+    assert_eq!(dominant_axis((30.0, -10.0)), DragAxis::Horizontal);
+    assert_eq!(dominant_axis((5.0, -40.0)), DragAxis::Vertical);
+    assert_eq!(dominant_axis((10.0, 10.0)), DragAxis::Horizontal);
+}
+
+#[test]
+fn test_constrain_to_axis() {
+    let start = (100.0, 200.0);
+    assert_eq!(
+        constrain_to_axis(start, (180.0, 215.0), DragAxis::Horizontal),
+        (180.0, 200.0)
+    );
+    assert_eq!(
+        constrain_to_axis(start, (90.0, 50.0), DragAxis::Vertical),
+        (100.0, 50.0)
+    );
+}
+
+#[test]
+fn test_keep_aspect_ratio_follows_larger_change() {
+    // 16:9 video dragged mostly wider
+    let size = keep_aspect_ratio((160.0, 90.0), (320.0, 100.0), 20.0);
+    assert_eq!(size, (320.0, 180.0));
+
+    // Dragged mostly taller
+    let size = keep_aspect_ratio((160.0, 90.0), (170.0, 180.0), 20.0);
+    assert_eq!(size, (320.0, 180.0));
+}
+
+#[test]
+fn test_keep_aspect_ratio_respects_min_size() {
+    let size = keep_aspect_ratio((200.0, 100.0), (10.0, 5.0), 20.0);
+    assert_eq!(size, (40.0, 20.0));
+}
@@ -0,0 +1,172 @@
+//! Unit tests for content_search module.
+
+use crate::helpers::test_content_item;
+use humanboard::content_search::{
+    ContentMatch, FileTextCache, MatchLocation, SEARCH_HIGHLIGHT_DURATION, find_in_table,
+    find_in_text, search_content, search_highlight_opacity,
+};
+use humanboard::types::{DataCell, DataColumn, DataRow, DataSource, DataType, ItemContent};
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+use std::time::Duration;
+use tempfile::tempdir;
+
+fn sales_table() -> DataSource {
+    let mut source = DataSource::new_empty(7, "Sales".to_string());
+    source.columns = vec![
+        DataColumn::new("Region", DataType::Text),
+        DataColumn::new("Total", DataType::Number),
+    ];
+    source.rows = vec![
+        DataRow::new(vec![
+            DataCell::Text("South".to_string()),
+            DataCell::Number(12.0),
+        ]),
+        DataRow::new(vec![
+            DataCell::Text("North America".to_string()),
+            DataCell::Number(40.5),
+        ]),
+    ];
+    source
+}
+
+#[test]
+fn test_find_in_text_reports_line_and_snippet() {
+    let text = "# Plan\n\nShip the Quarterly report on Friday\n";
+    assert_eq!(
+        find_in_text(text, "quarterly"),
+        Some((3, "Ship the Quarterly report on Friday".to_string()))
+    );
+    assert_eq!(find_in_text(text, "monthly"), None);
+    assert_eq!(find_in_text(text, "  "), None);
+}
+
+#[test]
+fn test_find_in_table_reports_row_and_column() {
+    let source = sales_table();
+    assert_eq!(
+        find_in_table(&source, "america"),
+        Some((
+            MatchLocation::Cell {
+                row: 2,
+                column: "Region".to_string()
+            },
+            "North America".to_string()
+        ))
+    );
+    assert_eq!(
+        find_in_table(&source, "40.5").map(|(location, _)| location.label()),
+        Some("row 2, Total".to_string())
+    );
+}
+
+#[test]
+fn test_search_content_covers_text_code_and_tables() {
+    let dir = tempdir().unwrap();
+    let code = dir.path().join("lib.rs");
+    fs::write(&code, "mod a;\n\npub fn parse_invoice() {}\n").unwrap();
+
+    let items = vec![
+        test_content_item(
+            1,
+            ItemContent::TextBox {
+                text: "Call about the invoice".to_string(),
+                font_size: 16.0,
+                color: "#ffffff".to_string(),
+            },
+        ),
+        test_content_item(
+            2,
+            ItemContent::Markdown {
+                path: PathBuf::from("/notes/todo.md"),
+                title: "todo".to_string(),
+                content: "- nothing here".to_string(),
+            },
+        ),
+        test_content_item(
+            3,
+            ItemContent::Code {
+                path: code,
+                language: "rust".to_string(),
+                lines: None,
+            },
+        ),
+        test_content_item(
+            4,
+            ItemContent::Table {
+                data_source_id: 7,
+                show_headers: true,
+                stripe: false,
+            },
+        ),
+    ];
+    let data_sources = HashMap::from([(7, sales_table())]);
+    let mut files = FileTextCache::default();
+
+    let matches = search_content(&items, &data_sources, "invoice", &mut files);
+    let found: Vec<(u64, String)> = matches
+        .iter()
+        .map(|m| (m.item_id, m.location.label()))
+        .collect();
+    assert_eq!(
+        found,
+        vec![(1, "line 1".to_string()), (3, "line 3".to_string())]
+    );
+
+    let matches = search_content(&items, &data_sources, "south", &mut files);
+    assert_eq!(matches.len(), 1);
+    assert_eq!(matches[0].item_id, 4);
+}
+
+#[test]
+fn test_short_queries_only_match_names() {
+    let items = vec![test_content_item(
+        1,
+        ItemContent::TextBox {
+            text: "a b c".to_string(),
+            font_size: 16.0,
+            color: "#ffffff".to_string(),
+        },
+    )];
+    let mut files = FileTextCache::default();
+    assert!(search_content(&items, &HashMap::new(), "a", &mut files).is_empty());
+}
+
+#[test]
+fn test_file_text_is_read_again_after_a_change() {
+    let dir = tempdir().unwrap();
+    let path = dir.path().join("main.py");
+    fs::write(&path, "print('hi')").unwrap();
+
+    let mut files = FileTextCache::default();
+    assert_eq!(files.text(&path).as_str(), "print('hi')");
+    fs::write(&path, "print('hello there')").unwrap();
+    assert_eq!(files.text(&path).as_str(), "print('hello there')");
+    assert_eq!(files.text(&dir.path().join("missing.py")).as_str(), "");
+}
+
+#[test]
+fn test_match_label_names_the_card() {
+    let found = ContentMatch {
+        item_id: 3,
+        location: MatchLocation::Line(12),
+        snippet: "let total = sum(values)".to_string(),
+    };
+    assert_eq!(
+        found.label("main.rs"),
+        "main.rs — line 12: let total = sum(values)"
+    );
+}
+
+#[test]
+fn test_search_highlight_holds_then_fades() {
+    assert_eq!(search_highlight_opacity(Duration::ZERO), Some(1.0));
+    assert_eq!(
+        search_highlight_opacity(SEARCH_HIGHLIGHT_DURATION / 4),
+        Some(1.0)
+    );
+    let fading = search_highlight_opacity(SEARCH_HIGHLIGHT_DURATION * 3 / 4).unwrap();
+    assert!(fading > 0.0 && fading < 1.0);
+    assert_eq!(search_highlight_opacity(SEARCH_HIGHLIGHT_DURATION), None);
+}
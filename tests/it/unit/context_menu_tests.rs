@@ -0,0 +1,214 @@
+//! Unit tests for context_menu module.
+
+use humanboard::arrange::ZOrder;
+use humanboard::context_menu::{
+    ContextMenuCommand, ContextMenuEntry, MENU_ENTRY_HEIGHT, MENU_PADDING, MENU_SEPARATOR_HEIGHT,
+    canvas_menu_entries, item_menu_entries, menu_height, menu_origin, tab_menu_entries,
+};
+
+fn commands(entries: &[ContextMenuEntry]) -> Vec<ContextMenuCommand> {
+    entries
+        .iter()
+        .filter_map(|entry| match entry {
+            ContextMenuEntry::Command(command) => Some(*command),
+            ContextMenuEntry::Separator => None,
+        })
+        .collect()
+}
+
+#[test]
+fn test_item_menu_for_unlocked_selection() {
+    let commands = commands(&item_menu_entries(
+        false, false, false, false, false, 0, false,
+    ));
+    assert!(commands.contains(&ContextMenuCommand::Delete));
+    assert!(commands.contains(&ContextMenuCommand::Lock));
+    assert!(!commands.contains(&ContextMenuCommand::Unlock));
+    assert!(!commands.contains(&ContextMenuCommand::OpenInPreview));
+    assert!(!commands.contains(&ContextMenuCommand::CopyLinkAtTime));
+    assert!(!commands.contains(&ContextMenuCommand::OpenHighlight));
+    assert!(commands.contains(&ContextMenuCommand::Restack(ZOrder::BringToFront)));
+    assert!(commands.contains(&ContextMenuCommand::Restack(ZOrder::SendToBack)));
+}
+
+#[test]
+fn test_item_menu_offers_copy_as_png() {
+    for all_locked in [false, true] {
+        let commands = commands(&item_menu_entries(
+            all_locked, false, false, false, false, 0, false,
+        ));
+        assert!(commands.contains(&ContextMenuCommand::CopyAsPng));
+        assert!(commands.contains(&ContextMenuCommand::CopyAsPngTransparent));
+    }
+    assert_eq!(
+        ContextMenuCommand::CopyAsPng.shortcut(),
+        Some("Cmd+Shift+C")
+    );
+}
+
+#[test]
+fn test_item_menu_for_locked_selection() {
+    let commands = commands(&item_menu_entries(
+        true, false, false, false, false, 0, false,
+    ));
+    assert!(!commands.contains(&ContextMenuCommand::Delete));
+    assert!(commands.contains(&ContextMenuCommand::Unlock));
+    assert!(!commands.contains(&ContextMenuCommand::Lock));
+}
+
+#[test]
+fn test_item_menu_with_preview_starts_with_open() {
+    let entries = item_menu_entries(false, true, false, false, false, 0, false);
+    assert_eq!(
+        entries[0],
+        ContextMenuEntry::Command(ContextMenuCommand::OpenInPreview)
+    );
+}
+
+#[test]
+fn test_item_menu_for_highlight_note_starts_with_open_highlight() {
+    let entries = item_menu_entries(false, false, true, false, false, 0, false);
+    assert_eq!(
+        entries[..2],
+        [
+            ContextMenuEntry::Command(ContextMenuCommand::OpenHighlight),
+            ContextMenuEntry::Separator,
+        ]
+    );
+}
+
+#[test]
+fn test_item_menu_for_pdf_offers_regenerate_thumbnail() {
+    let entries = item_menu_entries(false, true, false, true, false, 0, false);
+    assert_eq!(
+        entries[..3],
+        [
+            ContextMenuEntry::Command(ContextMenuCommand::OpenInPreview),
+            ContextMenuEntry::Command(ContextMenuCommand::RegenerateThumbnail),
+            ContextMenuEntry::Separator,
+        ]
+    );
+    let commands = commands(&item_menu_entries(
+        false, true, false, false, false, 0, false,
+    ));
+    assert!(!commands.contains(&ContextMenuCommand::RegenerateThumbnail));
+}
+
+#[test]
+fn test_item_menu_link_at_time_follows_copy() {
+    let commands = commands(&item_menu_entries(
+        false, false, false, false, true, 0, false,
+    ));
+    assert_eq!(
+        &commands[..3],
+        &[
+            ContextMenuCommand::Copy,
+            ContextMenuCommand::CopyLinkAtTime,
+            ContextMenuCommand::Duplicate,
+        ]
+    );
+}
+
+#[test]
+fn test_item_menu_compares_text_files() {
+    let entries = item_menu_entries(false, true, false, false, false, 1, false);
+    assert_eq!(
+        entries[..3],
+        [
+            ContextMenuEntry::Command(ContextMenuCommand::OpenInPreview),
+            ContextMenuEntry::Command(ContextMenuCommand::CompareWithSnapshot),
+            ContextMenuEntry::Separator,
+        ]
+    );
+
+    let entries = item_menu_entries(false, false, false, false, false, 2, false);
+    assert_eq!(
+        entries[..2],
+        [
+            ContextMenuEntry::Command(ContextMenuCommand::CompareFiles),
+            ContextMenuEntry::Separator,
+        ]
+    );
+}
+
+#[test]
+fn test_item_menu_with_style_offers_set_as_default() {
+    let with_style = commands(&item_menu_entries(
+        false, false, false, false, false, 0, true,
+    ));
+    let at = with_style
+        .iter()
+        .position(|c| *c == ContextMenuCommand::SetDefaultStyle)
+        .unwrap();
+    assert_eq!(with_style[at - 1], ContextMenuCommand::CopyAsPngTransparent);
+    assert_eq!(with_style[at + 1], ContextMenuCommand::Lock);
+
+    let without = commands(&item_menu_entries(
+        false, false, false, false, false, 0, false,
+    ));
+    assert!(!without.contains(&ContextMenuCommand::SetDefaultStyle));
+}
+
+#[test]
+fn test_tab_menu_for_pinned_tab_has_no_close() {
+    let unpinned = commands(&tab_menu_entries(false));
+    assert_eq!(
+        unpinned,
+        vec![
+            ContextMenuCommand::PinTab,
+            ContextMenuCommand::CloseTab,
+            ContextMenuCommand::CloseOtherTabs,
+            ContextMenuCommand::CloseAllTabs,
+        ]
+    );
+
+    let pinned = commands(&tab_menu_entries(true));
+    assert_eq!(pinned[0], ContextMenuCommand::UnpinTab);
+    assert!(!pinned.contains(&ContextMenuCommand::CloseTab));
+    assert!(pinned.contains(&ContextMenuCommand::CloseAllTabs));
+}
+
+#[test]
+fn test_canvas_menu_entries() {
+    assert_eq!(
+        commands(&canvas_menu_entries()),
+        vec![
+            ContextMenuCommand::Paste,
+            ContextMenuCommand::NewTextBox,
+            ContextMenuCommand::NewShape,
+            ContextMenuCommand::NewMarkdownNote,
+            ContextMenuCommand::ZoomToFit,
+        ]
+    );
+}
+
+#[test]
+fn test_menu_height() {
+    let entries = [
+        ContextMenuEntry::Command(ContextMenuCommand::Copy),
+        ContextMenuEntry::Separator,
+        ContextMenuEntry::Command(ContextMenuCommand::Paste),
+    ];
+    assert_eq!(
+        menu_height(&entries),
+        MENU_ENTRY_HEIGHT * 2.0 + MENU_SEPARATOR_HEIGHT + MENU_PADDING * 2.0
+    );
+}
+
+#[test]
+fn test_menu_origin_opens_at_click_when_it_fits() {
+    let origin = menu_origin((100.0, 100.0), (200.0, 150.0), (0.0, 0.0, 800.0, 600.0));
+    assert_eq!(origin, (100.0, 100.0));
+}
+
+#[test]
+fn test_menu_origin_flips_near_edges() {
+    let origin = menu_origin((700.0, 550.0), (200.0, 150.0), (0.0, 0.0, 800.0, 600.0));
+    assert_eq!(origin, (500.0, 400.0));
+}
+
+#[test]
+fn test_menu_origin_stays_inside_min_bounds() {
+    let origin = menu_origin((150.0, 100.0), (200.0, 150.0), (56.0, 48.0, 300.0, 200.0));
+    assert_eq!(origin, (56.0, 48.0));
+}
@@ -0,0 +1,135 @@
+//! Unit tests for custom_themes module.
+
+use gpui_component::theme::{Colorize, ThemeConfig, ThemeMode};
+use humanboard::custom_themes::{
+    ContentColor, EditableColor, ThemeDraft, ThemeLibrary, parse_color, parse_theme_file,
+    save_theme, theme_file_name,
+};
+use std::collections::BTreeMap;
+use std::fs;
+use tempfile::tempdir;
+
+fn base_theme() -> ThemeConfig {
+    ThemeConfig {
+        name: "Default Dark".into(),
+        mode: ThemeMode::Dark,
+        is_default: true,
+        ..ThemeConfig::default()
+    }
+}
+
+fn draft() -> ThemeDraft {
+    let colors = BTreeMap::from([
+        (EditableColor::Background, "#101820".to_string()),
+        (EditableColor::Accent, "#f2aa4c".to_string()),
+        (
+            EditableColor::Content(ContentColor::Video),
+            "#b48ead".to_string(),
+        ),
+    ]);
+    ThemeDraft::new(base_theme(), colors)
+}
+
+#[test]
+fn test_draft_is_named_after_its_base() {
+    let draft = draft();
+    assert_eq!(draft.name, "Default Dark Custom");
+
+    let again = ThemeDraft::new(draft.config(), BTreeMap::new());
+    assert_eq!(again.name, "Default Dark Custom");
+}
+
+#[test]
+fn test_set_color_ignores_what_isnt_a_color() {
+    let mut draft = draft();
+    assert!(!draft.set_color(EditableColor::Foreground, "#12"));
+    assert!(!draft.set_color(EditableColor::Foreground, "#ééé"));
+    assert!(!draft.colors.contains_key(&EditableColor::Foreground));
+
+    assert!(draft.set_color(EditableColor::Foreground, " #ABCDEF "));
+    assert_eq!(draft.colors[&EditableColor::Foreground], "#abcdef");
+}
+
+#[test]
+fn test_config_has_the_picked_colors() {
+    let mut draft = draft();
+    draft.name = "  ".to_string();
+    let config = draft.config();
+
+    assert_eq!(config.name.as_ref(), "Custom Theme");
+    assert!(!config.is_default);
+    assert_eq!(config.mode, ThemeMode::Dark);
+    assert_eq!(config.colors.background.as_deref(), Some("#101820"));
+    assert_eq!(config.colors.primary.as_deref(), Some("#f2aa4c"));
+    assert_eq!(config.colors.foreground, None);
+
+    let palette = draft.content_palette();
+    assert_eq!(palette.len(), 1);
+    assert_eq!(
+        palette[&ContentColor::Video].to_hex(),
+        parse_color("#b48ead").unwrap().to_hex()
+    );
+}
+
+#[test]
+fn test_saved_theme_reads_back() {
+    let dir = tempdir().unwrap();
+    let mut draft = draft();
+    draft.name = "Ocean Breeze".to_string();
+
+    let path = save_theme(dir.path(), &draft).unwrap();
+    assert_eq!(path, dir.path().join("ocean-breeze.json"));
+
+    let json = fs::read_to_string(&path).unwrap();
+    assert!(!json.contains("null"));
+    let themes = parse_theme_file(&json).unwrap();
+    assert_eq!(themes.len(), 1);
+    let (config, palette) = &themes[0];
+    assert_eq!(config.name.as_ref(), "Ocean Breeze");
+    assert_eq!(config.colors.background.as_deref(), Some("#101820"));
+    assert!(palette.contains_key(&ContentColor::Video));
+}
+
+#[test]
+fn test_library_keeps_custom_themes_and_every_palette() {
+    let builtin = tempdir().unwrap();
+    fs::write(
+        builtin.path().join("sunset.json"),
+        r##"{"name": "Sunset", "themes": [
+            {"name": "Sunset Light", "mode": "light", "colors": {"content.pdf": "#ff8800"}},
+            {"name": "Sunset Dark", "mode": "dark", "colors": {"background": "#000000"}}
+        ]}"##,
+    )
+    .unwrap();
+    fs::write(builtin.path().join("broken.json"), "{").unwrap();
+    let custom = tempdir().unwrap();
+    save_theme(custom.path(), &draft()).unwrap();
+
+    let mut library = ThemeLibrary::default();
+    library.load_dir(builtin.path(), false);
+    library.load_dir(custom.path(), true);
+
+    assert_eq!(
+        library.themes.keys().collect::<Vec<_>>(),
+        vec!["Default Dark Custom"]
+    );
+    assert!(library.palettes["Sunset Light"].contains_key(&ContentColor::Pdf));
+    assert!(!library.palettes.contains_key("Sunset Dark"));
+    assert!(library.palettes["Default Dark Custom"].contains_key(&ContentColor::Video));
+}
+
+#[test]
+fn test_theme_file_name() {
+    assert_eq!(theme_file_name("Ocean Breeze"), "ocean-breeze.json");
+    assert_eq!(theme_file_name(" Dark / Blue! "), "dark-blue.json");
+    assert_eq!(theme_file_name("..."), "custom-theme.json");
+}
+
+#[test]
+fn test_editable_color_keys() {
+    assert_eq!(EditableColor::all().len(), 3 + ContentColor::ALL.len());
+    assert_eq!(EditableColor::Accent.key(), "primary.background");
+    for content in ContentColor::ALL {
+        assert_eq!(ContentColor::from_key(content.key()), Some(content));
+    }
+}
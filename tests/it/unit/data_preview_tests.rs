@@ -0,0 +1,95 @@
+//! Unit tests for data preview module.
+
+use humanboard::data::{DataFileCache, DataRows, LazyDataSource, is_delimited_file};
+use humanboard::data_table::SortDirection;
+use humanboard::types::{DataCell, DataColumn, DataOrigin, DataRow, DataSource, DataType};
+use std::fs;
+use std::path::Path;
+use std::sync::Arc;
+use tempfile::tempdir;
+
+fn cities() -> DataRows {
+    DataRows::Memory(Arc::new(DataSource {
+        id: 1,
+        name: "Cities".to_string(),
+        columns: vec![
+            DataColumn::new("City", DataType::Text),
+            DataColumn::new("People", DataType::Number),
+        ],
+        rows: vec![
+            DataRow::new(vec![DataCell::Text("Oslo".into()), DataCell::Number(0.7)]),
+            DataRow::new(vec![DataCell::Text("Lagos".into()), DataCell::Number(15.4)]),
+            DataRow::new(vec![DataCell::Text("Lima".into()), DataCell::Empty]),
+        ],
+        origin: DataOrigin::Manual,
+        dirty: false,
+    }))
+}
+
+fn city(row: &DataRow) -> String {
+    row.cells[0].to_string()
+}
+
+#[test]
+fn test_delimited_files() {
+    assert!(is_delimited_file(Path::new("sales.csv")));
+    assert!(is_delimited_file(Path::new("/a/b.TSV")));
+    assert!(!is_delimited_file(Path::new("data.json")));
+    assert!(!is_delimited_file(Path::new("no_extension")));
+}
+
+#[test]
+fn test_memory_rows_page() {
+    let rows = cities();
+    assert_eq!(rows.row_count(), 3);
+    assert_eq!(rows.columns()[1].name, "People");
+
+    let page: Vec<String> = rows.rows(1, 5).iter().map(city).collect();
+    assert_eq!(page, ["Lagos", "Lima"]);
+}
+
+#[test]
+fn test_memory_rows_order() {
+    let rows = cities();
+    assert_eq!(
+        rows.order("", Some((1, SortDirection::Descending))),
+        [1, 0, 2]
+    );
+    assert_eq!(rows.order("LA", None), [1]);
+
+    let sorted: Vec<String> = rows.rows_at(&[2, 0]).iter().map(city).collect();
+    assert_eq!(sorted, ["Lima", "Oslo"]);
+}
+
+#[test]
+fn test_lazy_rows_order() {
+    let dir = tempdir().unwrap();
+    let path = dir.path().join("scores.csv");
+    fs::write(&path, "name,score\nada,3\nbob,1\ncy,2\n").unwrap();
+
+    let rows = DataRows::Lazy(Arc::new(LazyDataSource::from_csv(0, &path).unwrap()));
+    assert_eq!(rows.row_count(), 3);
+    assert_eq!(
+        rows.order("", Some((1, SortDirection::Ascending))),
+        [1, 2, 0]
+    );
+    assert_eq!(rows.order("BOB", None), [1]);
+}
+
+#[test]
+fn test_data_file_cache_insert_and_forget() {
+    let dir = tempdir().unwrap();
+    let path = dir.path().join("scores.csv");
+    fs::write(&path, "name,score\nada,3\n").unwrap();
+
+    let cache = DataFileCache::new();
+    assert!(cache.get(&path).is_none());
+
+    cache.insert(path.clone(), LazyDataSource::from_csv(0, &path).unwrap());
+    assert_eq!(cache.get(&path).map(|rows| rows.row_count()), Some(1));
+    assert!(cache.error(&path).is_none());
+    assert!(!cache.has_pending());
+
+    cache.forget(&path);
+    assert!(cache.get(&path).is_none());
+}
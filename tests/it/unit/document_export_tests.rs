@@ -0,0 +1,158 @@
+//! Unit tests for document_export module.
+
+use crate::helpers::{test_content_item_at, text_box_content};
+use humanboard::document_export::{
+    DocumentOrder, assets_dir_name, compose_document, document_items, export_markdown,
+};
+use humanboard::types::{CanvasItem, ItemContent};
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use tempfile::tempdir;
+
+fn ids(items: &[&CanvasItem]) -> Vec<u64> {
+    items.iter().map(|item| item.id).collect()
+}
+
+#[test]
+fn test_reading_order_goes_down_then_across() {
+    let items = vec![
+        test_content_item_at(
+            1,
+            text_box_content("top right"),
+            (300.0, 0.0),
+            (200.0, 100.0),
+        ),
+        test_content_item_at(2, text_box_content("below"), (0.0, 200.0), (200.0, 100.0)),
+        test_content_item_at(3, text_box_content("top left"), (0.0, 0.0), (200.0, 100.0)),
+    ];
+    let selected: HashSet<u64> = [1, 2, 3].into();
+
+    let reading = document_items(&items, &selected, DocumentOrder::Reading);
+    assert_eq!(ids(&reading), vec![3, 1, 2]);
+    let stacking = document_items(&items, &selected, DocumentOrder::Stacking);
+    assert_eq!(ids(&stacking), vec![1, 2, 3]);
+}
+
+#[test]
+fn test_only_selected_text_cards_are_exported() {
+    let items = vec![
+        test_content_item_at(1, text_box_content("kept"), (0.0, 0.0), (200.0, 100.0)),
+        test_content_item_at(
+            2,
+            text_box_content("not selected"),
+            (0.0, 100.0),
+            (200.0, 100.0),
+        ),
+        test_content_item_at(
+            3,
+            ItemContent::Video(PathBuf::from("/clip.mp4")),
+            (0.0, 200.0),
+            (200.0, 100.0),
+        ),
+    ];
+    let selected: HashSet<u64> = [1, 3].into();
+
+    let picked = document_items(&items, &selected, DocumentOrder::Reading);
+    assert_eq!(ids(&picked), vec![1]);
+}
+
+#[test]
+fn test_document_joins_cards_and_references_images() {
+    let items = vec![
+        test_content_item_at(
+            1,
+            text_box_content("Intro paragraph"),
+            (0.0, 0.0),
+            (200.0, 100.0),
+        ),
+        test_content_item_at(
+            2,
+            ItemContent::Markdown {
+                path: PathBuf::from("/notes/idea.md"),
+                title: "idea".to_string(),
+                content: "# Idea\n\nDetails\n".to_string(),
+            },
+            (0.0, 100.0),
+            (200.0, 100.0),
+        ),
+        test_content_item_at(
+            3,
+            ItemContent::Image(PathBuf::from("/a/photo.png")),
+            (0.0, 200.0),
+            (200.0, 100.0),
+        ),
+        test_content_item_at(
+            4,
+            ItemContent::Image(PathBuf::from("/b/photo.png")),
+            (0.0, 300.0),
+            (200.0, 100.0),
+        ),
+    ];
+    let refs: Vec<&CanvasItem> = items.iter().collect();
+
+    let document = compose_document(&refs, "draft_assets");
+    assert_eq!(
+        document.markdown,
+        "Intro paragraph\n\n# Idea\n\nDetails\n\n\
+         ![photo](draft_assets/photo.png)\n\n![photo](draft_assets/photo-2.png)\n"
+    );
+    assert_eq!(
+        document.assets,
+        vec![
+            (PathBuf::from("/a/photo.png"), "photo.png".to_string()),
+            (PathBuf::from("/b/photo.png"), "photo-2.png".to_string()),
+        ]
+    );
+}
+
+#[test]
+fn test_code_cards_are_fenced_with_their_pinned_lines() {
+    let dir = tempdir().unwrap();
+    let path = dir.path().join("main.rs");
+    fs::write(&path, "fn one() {}\nfn two() {}\nfn three() {}\n").unwrap();
+    let card = test_content_item_at(
+        1,
+        ItemContent::Code {
+            path,
+            language: "rust".to_string(),
+            lines: Some((2, 3)),
+        },
+        (0.0, 0.0),
+        (200.0, 100.0),
+    );
+
+    let document = compose_document(&[&card], "assets");
+    assert_eq!(
+        document.markdown,
+        "**main.rs** (lines 2–3)\n\n```rust\nfn two() {}\nfn three() {}\n```\n"
+    );
+}
+
+#[test]
+fn test_assets_dir_is_named_after_the_document() {
+    assert_eq!(assets_dir_name(Path::new("/out/draft.md")), "draft_assets");
+}
+
+#[test]
+fn test_export_writes_document_and_copies_images() {
+    let dir = tempdir().unwrap();
+    let image = dir.path().join("chart.png");
+    fs::write(&image, b"png").unwrap();
+    let items = vec![
+        test_content_item_at(1, text_box_content("Summary"), (0.0, 0.0), (200.0, 100.0)),
+        test_content_item_at(2, ItemContent::Image(image), (0.0, 100.0), (200.0, 100.0)),
+    ];
+    let out = dir.path().join("out").join("draft.md");
+    fs::create_dir_all(out.parent().unwrap()).unwrap();
+
+    assert_eq!(export_markdown(&items, &out).unwrap(), 2);
+    assert_eq!(
+        fs::read_to_string(&out).unwrap(),
+        "Summary\n\n![chart](draft_assets/chart.png)\n"
+    );
+    assert_eq!(
+        fs::read(dir.path().join("out/draft_assets/chart.png")).unwrap(),
+        b"png"
+    );
+}
@@ -0,0 +1,31 @@
+//! Unit tests for file_import module.
+
+use humanboard::file_import::{ImportedFile, remove_copies};
+use humanboard::types::ItemContent;
+use std::fs;
+use tempfile::tempdir;
+
+#[test]
+fn test_remove_copies_only_deletes_files_in_the_import_dir() {
+    let dir = tempdir().unwrap();
+    let import_dir = dir.path().join("files");
+    fs::create_dir_all(&import_dir).unwrap();
+    let copy = import_dir.join("cat.png");
+    let original = dir.path().join("dog.png");
+    fs::write(&copy, "png").unwrap();
+    fs::write(&original, "png").unwrap();
+
+    let files = vec![
+        ImportedFile::Item {
+            content: ItemContent::Image(copy.clone()),
+            size: None,
+        },
+        ImportedFile::Item {
+            content: ItemContent::Image(original.clone()),
+            size: None,
+        },
+    ];
+    assert_eq!(remove_copies(&files, &import_dir), 1);
+    assert!(!copy.exists());
+    assert!(original.exists());
+}
@@ -0,0 +1,75 @@
+//! Unit tests for file_links module.
+
+use crate::helpers::{image_item_path, test_canvas_item, test_image_item};
+use humanboard::file_links::{linked_files, missing_file_items, missing_files, relink_items};
+use std::fs;
+use std::path::PathBuf;
+use tempfile::tempdir;
+
+#[test]
+fn test_missing_file_items() {
+    let dir = tempdir().unwrap();
+    let present = dir.path().join("present.png");
+    fs::write(&present, b"").unwrap();
+
+    let items = vec![
+        test_image_item(0, present),
+        test_image_item(1, dir.path().join("gone.png")),
+        test_canvas_item(2, "no file"),
+    ];
+
+    let missing = missing_file_items(&items);
+    assert_eq!(missing.len(), 1);
+    assert!(missing.contains(&1));
+}
+
+#[test]
+fn test_linked_files_are_checked_apart_from_the_items() {
+    let dir = tempdir().unwrap();
+    let present = dir.path().join("present.png");
+    fs::write(&present, b"").unwrap();
+    let gone = dir.path().join("gone.png");
+
+    let files = linked_files(&[
+        test_image_item(0, present.clone()),
+        test_image_item(1, gone.clone()),
+    ]);
+    assert_eq!(files, vec![(0, present), (1, gone)]);
+
+    let missing = missing_files(&files);
+    assert_eq!(missing.len(), 1);
+    assert!(missing.contains(&1));
+}
+
+#[test]
+fn test_relink_moved_folder() {
+    let old_dir = tempdir().unwrap();
+    let new_dir = tempdir().unwrap();
+    for name in ["a.png", "b.png"] {
+        fs::write(new_dir.path().join(name), b"").unwrap();
+    }
+
+    let mut items = vec![
+        test_image_item(0, old_dir.path().join("a.png")),
+        test_image_item(1, old_dir.path().join("b.png")),
+        test_image_item(2, old_dir.path().join("c.png")),
+    ];
+
+    let relinked = relink_items(&mut items, 0, &new_dir.path().join("a.png"));
+
+    assert_eq!(relinked, 2);
+    assert_eq!(image_item_path(&items[0]), &new_dir.path().join("a.png"));
+    assert_eq!(image_item_path(&items[1]), &new_dir.path().join("b.png"));
+    // Not in the new folder either - left for the user to locate
+    assert_eq!(image_item_path(&items[2]), &old_dir.path().join("c.png"));
+}
+
+#[test]
+fn test_relink_unknown_item() {
+    let mut items = vec![test_image_item(0, PathBuf::from("/missing/a.png"))];
+    assert_eq!(
+        relink_items(&mut items, 42, &PathBuf::from("/found/a.png")),
+        0
+    );
+    assert_eq!(image_item_path(&items[0]), &PathBuf::from("/missing/a.png"));
+}
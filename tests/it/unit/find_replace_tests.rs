@@ -0,0 +1,52 @@
+//! Unit tests for find replace module.
+
+use humanboard::find_replace::{find_matches, line_and_column, replace_all_edit, utf16_range};
+
+#[test]
+fn test_find_matches_ignores_case() {
+    assert_eq!(find_matches("Foo foo FOO", "foo"), [0..3, 4..7, 8..11]);
+    assert!(find_matches("bar", "foo").is_empty());
+    assert!(find_matches("foo", "").is_empty());
+}
+
+#[test]
+fn test_find_matches_dont_overlap() {
+    assert_eq!(find_matches("aaaa", "aa"), [0..2, 2..4]);
+}
+
+#[test]
+fn test_find_matches_in_multibyte_text() {
+    let text = "café CAFÉ";
+    assert_eq!(find_matches(text, "café"), [0..5, 6..11]);
+}
+
+#[test]
+fn test_line_and_column() {
+    let text = "one\ntwo three\nfour";
+    assert_eq!(line_and_column(text, 0), (0, 0));
+    assert_eq!(line_and_column(text, 8), (1, 4));
+    assert_eq!(line_and_column(text, 14), (2, 0));
+}
+
+#[test]
+fn test_replace_all_is_one_edit() {
+    let text = "let a = 1;\nlet b = a + a;\n";
+    let (span, replaced) = replace_all_edit(text, "a", "x").unwrap();
+    assert_eq!(span, 4..24);
+    let mut result = text.to_string();
+    result.replace_range(span, &replaced);
+    assert_eq!(result, "let x = 1;\nlet b = x + x;\n");
+}
+
+#[test]
+fn test_replace_all_without_matches() {
+    assert!(replace_all_edit("text", "missing", "x").is_none());
+}
+
+#[test]
+fn test_utf16_range() {
+    // "é" is two bytes but one UTF-16 code unit, "𝄞" four bytes and two units
+    let text = "é𝄞ab";
+    assert_eq!(utf16_range(text, 6..8), 3..5);
+    assert_eq!(utf16_range(text, 0..2), 0..1);
+}
@@ -0,0 +1,70 @@
+//! Unit tests for frecency module.
+
+use humanboard::background::BackgroundExecutor;
+use humanboard::frecency::{FrecencyStore, MAX_FRECENCY_BONUS, Usage, frecency, frecency_bonus};
+use tempfile::tempdir;
+
+const HOUR: u64 = 60 * 60;
+const DAY: u64 = 24 * HOUR;
+
+#[test]
+fn test_recent_uses_weigh_more() {
+    let now = 100 * DAY;
+    let recent = Usage {
+        count: 3,
+        last_used: now - 10,
+    };
+    let old = Usage {
+        count: 3,
+        last_used: now - 40 * DAY,
+    };
+    assert!(frecency(&recent, now) > frecency(&old, now));
+}
+
+#[test]
+fn test_frequent_uses_weigh_more() {
+    let now = 100 * DAY;
+    let often = Usage {
+        count: 10,
+        last_used: now - 2 * DAY,
+    };
+    let once = Usage {
+        count: 1,
+        last_used: now - 2 * DAY,
+    };
+    assert!(frecency(&often, now) > frecency(&once, now));
+}
+
+#[test]
+fn test_bonus_is_capped() {
+    assert_eq!(frecency_bonus(0.0), 0);
+    assert_eq!(frecency_bonus(1_000.0), MAX_FRECENCY_BONUS);
+}
+
+#[test]
+fn test_store_counts_uses_and_remembers_them() {
+    let dir = tempdir().unwrap();
+    let file = dir.path().join("frecency.json");
+    let now = 50 * DAY;
+
+    let store = FrecencyStore::new(file.clone());
+    let background = BackgroundExecutor::with_default_workers();
+    assert_eq!(store.bonus_at("theme", now), 0);
+    store.record_at("theme", now - DAY, &background);
+    store.record_at("theme", now, &background);
+    assert_eq!(
+        store.usage("theme"),
+        Some(Usage {
+            count: 2,
+            last_used: now
+        })
+    );
+    assert!(store.bonus_at("theme", now) > store.bonus_at("bg", now));
+
+    // Uses are saved in the background
+    while background.has_pending() {
+        std::thread::sleep(std::time::Duration::from_millis(5));
+    }
+    let reopened = FrecencyStore::new(file);
+    assert_eq!(reopened.usage("theme").map(|usage| usage.count), Some(2));
+}
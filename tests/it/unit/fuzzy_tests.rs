@@ -0,0 +1,77 @@
+//! Unit tests for fuzzy module.
+
+use humanboard::fuzzy::{best_fuzzy_score, command_rank, fuzzy_score};
+
+#[test]
+fn test_initials_match_words() {
+    assert!(fuzzy_score("ocb", "Open Chart Builder").is_some());
+    assert!(fuzzy_score("dh", "distribute horizontally").is_some());
+    assert!(fuzzy_score("OCB", "open chart builder").is_some());
+}
+
+#[test]
+fn test_substrings_always_match() {
+    assert!(fuzzy_score("orld", "Hello World").is_some());
+    assert!(fuzzy_score("hello", "HELLO").is_some());
+    assert!(fuzzy_score("x", "index.rs").is_some());
+}
+
+#[test]
+fn test_characters_must_appear_in_order() {
+    assert_eq!(fuzzy_score("bco", "Open Chart Builder"), None);
+    assert_eq!(fuzzy_score("xyz", "abc"), None);
+    assert_eq!(fuzzy_score("longer", "long"), None);
+}
+
+#[test]
+fn test_scattered_matches_are_rejected() {
+    assert_eq!(
+        fuzzy_score("meeting", "Export selection as Markdown, in reading order"),
+        None
+    );
+}
+
+#[test]
+fn test_word_starts_and_runs_score_higher() {
+    let word_start = fuzzy_score("cb", "chart builder").unwrap();
+    let mid_word = fuzzy_score("cb", "acbd").unwrap();
+    assert!(word_start > mid_word);
+
+    let run = fuzzy_score("hart", "chart").unwrap();
+    let spread = fuzzy_score("hart", "chxaxrxt").unwrap_or(0);
+    assert!(run > spread);
+
+    let camel = fuzzy_score("gts", "goToSymbol").unwrap();
+    let plain = fuzzy_score("gts", "gutsy").unwrap_or(0);
+    assert!(camel > plain);
+}
+
+#[test]
+fn test_spaces_in_the_query_are_ignored() {
+    assert_eq!(
+        fuzzy_score("open chart", "Open Chart Builder"),
+        fuzzy_score("openchart", "Open Chart Builder")
+    );
+    assert_eq!(fuzzy_score("", "anything"), Some(0));
+}
+
+#[test]
+fn test_best_score_takes_the_better_text() {
+    let name = fuzzy_score("bg", "bg");
+    assert_eq!(best_fuzzy_score("bg", &["bg", "Canvas background"]), name);
+    assert_eq!(best_fuzzy_score("zz", &["bg", "Canvas background"]), None);
+}
+
+#[test]
+fn test_command_rank_scores_names_and_breaks_ties_with_descriptions() {
+    let (score, tiebreak) = command_rank("bg", &["bg"], "Canvas background").unwrap();
+    assert_eq!(Some(score), fuzzy_score("bg", "bg"));
+    assert_eq!(Some(tiebreak), fuzzy_score("bg", "Canvas background"));
+
+    // Matching by description alone ranks below any matching name
+    let (score, tiebreak) = command_rank("canvas", &["bg"], "Canvas background").unwrap();
+    assert_eq!(score, 0);
+    assert!(tiebreak > 0);
+
+    assert_eq!(command_rank("zz", &["bg"], "Canvas background"), None);
+}
@@ -0,0 +1,60 @@
+//! Unit tests for group_resize module.
+
+use humanboard::input::group_resize::{GroupHandle, group_scale, handle_at, scale_bounds};
+
+const SELECTION: (f32, f32, f32, f32) = (100.0, 100.0, 200.0, 100.0);
+
+#[test]
+fn test_handle_at_corners() {
+    assert_eq!(
+        handle_at(SELECTION, (102.0, 98.0), 5.0),
+        Some(GroupHandle::TopLeft)
+    );
+    assert_eq!(
+        handle_at(SELECTION, (300.0, 200.0), 5.0),
+        Some(GroupHandle::BottomRight)
+    );
+    assert_eq!(
+        handle_at(SELECTION, (300.0, 100.0), 5.0),
+        Some(GroupHandle::TopRight)
+    );
+    // Edges and the interior are not handles
+    assert_eq!(handle_at(SELECTION, (200.0, 100.0), 5.0), None);
+    assert_eq!(handle_at(SELECTION, (200.0, 150.0), 5.0), None);
+}
+
+#[test]
+fn test_anchor_is_opposite_corner() {
+    assert_eq!(GroupHandle::TopLeft.anchor(SELECTION), (300.0, 200.0));
+    assert_eq!(GroupHandle::BottomLeft.anchor(SELECTION), (300.0, 100.0));
+}
+
+#[test]
+fn test_group_scale_follows_larger_axis() {
+    // Dragging the bottom-right corner right by half the width
+    assert_eq!(
+        group_scale(SELECTION, GroupHandle::BottomRight, (100.0, 0.0), 0.1),
+        1.5
+    );
+    // Dragging the top-left corner up and left grows the box
+    assert_eq!(
+        group_scale(SELECTION, GroupHandle::TopLeft, (-20.0, -50.0), 0.1),
+        1.5
+    );
+    // Shrinking is clamped
+    assert_eq!(
+        group_scale(SELECTION, GroupHandle::BottomRight, (-500.0, -500.0), 0.1),
+        0.1
+    );
+}
+
+#[test]
+fn test_scale_bounds_keeps_layout_proportional() {
+    let anchor = GroupHandle::BottomRight.anchor(SELECTION);
+    // The left item of the selection and one at its far right
+    let left = scale_bounds((100.0, 100.0, 50.0, 50.0), anchor, 2.0);
+    let right = scale_bounds((250.0, 150.0, 50.0, 50.0), anchor, 2.0);
+
+    assert_eq!(left, (100.0, 100.0, 100.0, 100.0));
+    assert_eq!(right, (400.0, 200.0, 100.0, 100.0));
+}
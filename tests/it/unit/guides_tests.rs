@@ -0,0 +1,71 @@
+//! Unit tests for alignment guides.
+
+use humanboard::input::guides::{GuideAxis, align, union_bounds};
+
+#[test]
+fn test_no_alignment_when_far_away() {
+    let alignment = align((0.0, 0.0, 100.0, 100.0), &[(300.0, 300.0, 50.0, 50.0)], 6.0);
+
+    assert_eq!(alignment.dx, None);
+    assert_eq!(alignment.dy, None);
+    assert!(alignment.guides.is_empty());
+}
+
+#[test]
+fn test_snaps_left_edges() {
+    // Moving item's left edge is 4 units right of the neighbor's
+    let alignment = align((104.0, 300.0, 50.0, 50.0), &[(100.0, 0.0, 80.0, 40.0)], 6.0);
+
+    assert_eq!(alignment.dx, Some(-4.0));
+    assert_eq!(alignment.dy, None);
+    assert_eq!(alignment.guides.len(), 1);
+
+    let guide = alignment.guides[0];
+    assert_eq!(guide.axis, GuideAxis::Vertical);
+    assert_eq!(guide.position, 100.0);
+    // Spans from the top of the neighbor to the bottom of the moving item
+    assert_eq!((guide.start, guide.end), (0.0, 350.0));
+}
+
+#[test]
+fn test_snaps_centers() {
+    // Moving item center y = 148, neighbor center y = 150
+    let alignment = align(
+        (300.0, 128.0, 40.0, 40.0),
+        &[(0.0, 100.0, 100.0, 100.0)],
+        6.0,
+    );
+
+    assert_eq!(alignment.dy, Some(2.0));
+    assert_eq!(alignment.guides[0].axis, GuideAxis::Horizontal);
+    assert_eq!(alignment.guides[0].position, 150.0);
+}
+
+#[test]
+fn test_picks_closest_alignment() {
+    let neighbors = [(0.0, 0.0, 100.0, 10.0), (103.0, 50.0, 10.0, 10.0)];
+    // Left edge at 102 is 2 from the first neighbor's right edge (100)
+    // and 1 from the second neighbor's left edge (103)
+    let alignment = align((102.0, 200.0, 20.0, 20.0), &neighbors, 6.0);
+
+    assert_eq!(alignment.dx, Some(1.0));
+}
+
+#[test]
+fn test_guides_on_both_axes() {
+    let alignment = align((2.0, 3.0, 50.0, 50.0), &[(0.0, 0.0, 50.0, 50.0)], 6.0);
+
+    assert_eq!(alignment.dx, Some(-2.0));
+    assert_eq!(alignment.dy, Some(-3.0));
+    // Left, center and right line up, as do top, middle and bottom
+    assert_eq!(alignment.guides.len(), 6);
+}
+
+#[test]
+fn test_union_bounds() {
+    assert_eq!(union_bounds(Vec::new()), None);
+    assert_eq!(
+        union_bounds(vec![(0.0, 0.0, 10.0, 10.0), (20.0, -5.0, 10.0, 10.0)]),
+        Some((0.0, -5.0, 30.0, 15.0))
+    );
+}
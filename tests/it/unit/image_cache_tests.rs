@@ -0,0 +1,73 @@
+//! Unit tests for downscaled image thumbnails.
+
+use humanboard::image_cache::{
+    FileStamp, MIP_LEVELS, cache_key, ensure_mips, levels_for, mip_path, pick_level, remove_mips,
+};
+use image::{Rgba, RgbaImage};
+use std::path::Path;
+use tempfile::tempdir;
+
+fn write_image(path: &Path, width: u32, height: u32) {
+    RgbaImage::from_pixel(width, height, Rgba([200, 80, 40, 255]))
+        .save(path)
+        .unwrap();
+}
+
+#[test]
+fn test_pick_level_uses_smallest_covering_level() {
+    assert_eq!(pick_level(100.0), Some(256));
+    assert_eq!(pick_level(256.0), Some(256));
+    assert_eq!(pick_level(300.0), Some(512));
+    assert_eq!(pick_level(1024.0), Some(1024));
+}
+
+#[test]
+fn test_pick_level_uses_original_past_largest_level() {
+    let largest = *MIP_LEVELS.last().unwrap() as f32;
+    assert_eq!(pick_level(largest + 1.0), None);
+}
+
+#[test]
+fn test_levels_skip_sizes_at_or_above_the_original() {
+    assert_eq!(levels_for((4000, 3000)), vec![256, 512, 1024]);
+    assert_eq!(levels_for((600, 900)), vec![256, 512]);
+    assert!(levels_for((200, 100)).is_empty());
+}
+
+#[test]
+fn test_cache_key_changes_when_file_changes() {
+    let dir = tempdir().unwrap();
+    let path = dir.path().join("photo.png");
+    write_image(&path, 40, 30);
+    let before = cache_key(&path, &FileStamp::of(&path).unwrap());
+    assert_eq!(before, cache_key(&path, &FileStamp::of(&path).unwrap()));
+
+    write_image(&path, 80, 60);
+    let after = cache_key(&path, &FileStamp::of(&path).unwrap());
+    assert_ne!(before, after);
+}
+
+#[test]
+fn test_ensure_mips_writes_downscaled_levels() {
+    let dir = tempdir().unwrap();
+    let source = dir.path().join("large.png");
+    write_image(&source, 800, 400);
+    let cache = dir.path().join("cache");
+
+    let levels = ensure_mips(&source, &cache, "key").unwrap();
+    assert_eq!(levels, vec![256, 512]);
+
+    let small = image::image_dimensions(mip_path(&cache, "key", 256)).unwrap();
+    assert_eq!(small, (256, 128));
+    assert!(!mip_path(&cache, "key", 1024).exists());
+
+    remove_mips(&cache, "key");
+    assert!(!mip_path(&cache, "key", 256).exists());
+    assert!(!mip_path(&cache, "key", 512).exists());
+}
+
+#[test]
+fn test_ensure_mips_fails_for_missing_file() {
+    let dir = tempdir().unwrap();
+    assert!(ensure_mips(&dir.path().join("gone.png"), dir.path(), "key").is_err());
+}
@@ -0,0 +1,104 @@
+//! Unit tests for image viewer module.
+
+use humanboard::image_viewer::{
+    ImagePixelCache, ImageView, ImageZoom, MAX_IMAGE_ZOOM, fit_scale, format_pixel_color,
+    is_viewable_image,
+};
+use image::RgbaImage;
+use std::path::Path;
+
+const VIEWPORT: (f32, f32) = (400.0, 300.0);
+
+#[test]
+fn test_fit_scale_shrinks_large_images() {
+    assert_eq!(fit_scale((800, 300), VIEWPORT), 0.5);
+    assert_eq!(fit_scale((400, 600), VIEWPORT), 0.5);
+}
+
+#[test]
+fn test_fit_scale_never_enlarges() {
+    assert_eq!(fit_scale((100, 50), VIEWPORT), 1.0);
+    assert_eq!(fit_scale((0, 0), VIEWPORT), 1.0);
+    assert_eq!(fit_scale((800, 600), (0.0, 0.0)), 1.0);
+}
+
+#[test]
+fn test_fitted_image_is_centered() {
+    let view = ImageView::default();
+    assert_eq!(view.image_rect((800, 300), VIEWPORT), (0.0, 75.0, 400.0, 150.0));
+}
+
+#[test]
+fn test_zoom_keeps_anchor_in_place() {
+    let mut view = ImageView::default();
+    let image = (800, 600);
+    let anchor = (100.0, 50.0);
+    let before = view.pixel_at(anchor, image, VIEWPORT).unwrap();
+
+    view.zoom_by(2.0, anchor, image, VIEWPORT);
+    assert_eq!(view.zoom, ImageZoom::Scale(1.0));
+    assert_eq!(view.pixel_at(anchor, image, VIEWPORT), Some(before));
+}
+
+#[test]
+fn test_zoom_is_clamped() {
+    let mut view = ImageView::default();
+    view.actual_size();
+    view.zoom_by(1000.0, (200.0, 150.0), (10, 10), VIEWPORT);
+    assert_eq!(view.zoom, ImageZoom::Scale(MAX_IMAGE_ZOOM));
+}
+
+#[test]
+fn test_pan_moves_zoomed_image_only() {
+    let mut view = ImageView::default();
+    view.pan_by((30.0, 10.0));
+    assert_eq!(view.pan, (0.0, 0.0));
+
+    view.actual_size();
+    view.pan_by((30.0, 10.0));
+    assert_eq!(view.pan, (30.0, 10.0));
+
+    view.fit();
+    assert_eq!(view, ImageView::default());
+}
+
+#[test]
+fn test_pixel_at_maps_viewport_to_image() {
+    let mut view = ImageView::default();
+    view.actual_size();
+    // A 100x100 image at 100% sits at (150, 100) in the viewport
+    assert_eq!(view.pixel_at((150.0, 100.0), (100, 100), VIEWPORT), Some((0, 0)));
+    assert_eq!(view.pixel_at((249.9, 199.9), (100, 100), VIEWPORT), Some((99, 99)));
+    assert_eq!(view.pixel_at((149.0, 100.0), (100, 100), VIEWPORT), None);
+    assert_eq!(view.pixel_at((250.0, 150.0), (100, 100), VIEWPORT), None);
+}
+
+#[test]
+fn test_format_pixel_color() {
+    assert_eq!(format_pixel_color([255, 128, 0, 255]), "#FF8000");
+    assert_eq!(format_pixel_color([0, 0, 0, 128]), "#00000080");
+}
+
+#[test]
+fn test_viewable_images() {
+    assert!(is_viewable_image(Path::new("photo.JPG")));
+    assert!(is_viewable_image(Path::new("/a/b.webp")));
+    assert!(!is_viewable_image(Path::new("logo.svg")));
+    assert!(!is_viewable_image(Path::new("notes.md")));
+    assert!(!is_viewable_image(Path::new("no_extension")));
+}
+
+#[test]
+fn test_pixel_cache_insert_and_forget() {
+    let cache = ImagePixelCache::new();
+    let path = Path::new("/images/photo.png");
+    assert!(cache.get(path).is_none());
+
+    cache.insert(path.to_path_buf(), RgbaImage::new(4, 3));
+    assert_eq!(cache.get(path).map(|pixels| pixels.dimensions()), Some((4, 3)));
+    assert!(!cache.failed(path));
+    assert!(!cache.has_pending());
+
+    cache.forget(path);
+    assert!(cache.get(path).is_none());
+}
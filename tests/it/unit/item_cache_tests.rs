@@ -0,0 +1,127 @@
+//! Unit tests for the static item render cache keys.
+
+use crate::helpers::{image_content, test_canvas_item};
+use gpui::hsla;
+use humanboard::render::canvas::TableCard;
+use humanboard::render::item_cache::{
+    ItemColors, RenderKey, bucket_zoom, is_cacheable, zoom_bucket,
+};
+use humanboard::types::ItemContent;
+
+fn colors() -> ItemColors {
+    ItemColors {
+        fg: hsla(0.0, 0.0, 0.9, 1.0),
+        muted_fg: hsla(0.0, 0.0, 0.6, 1.0),
+        muted_bg: hsla(0.0, 0.0, 0.2, 1.0),
+        danger: hsla(0.0, 0.8, 0.5, 1.0),
+    }
+}
+
+#[test]
+fn test_nearby_zoom_levels_share_a_bucket() {
+    assert_eq!(zoom_bucket(1.0), zoom_bucket(1.001));
+    assert_ne!(zoom_bucket(1.0), zoom_bucket(1.01));
+    assert!((bucket_zoom(zoom_bucket(1.234)) - 1.234).abs() < 0.005);
+}
+
+#[test]
+fn test_static_content_is_cacheable() {
+    assert!(is_cacheable(&test_canvas_item(1, "note"), None));
+
+    let mut image = test_canvas_item(2, "");
+    image.content = image_content("/photos/a.png");
+    assert!(!is_cacheable(&image, None));
+}
+
+#[test]
+fn test_text_box_being_edited_is_not_cacheable() {
+    let mut item = test_canvas_item(3, "");
+    item.content = ItemContent::TextBox {
+        text: "Hello".to_string(),
+        font_size: 16.0,
+        color: "#ffffff".to_string(),
+    };
+
+    assert!(is_cacheable(&item, None));
+    assert!(is_cacheable(&item, Some(4)));
+    assert!(!is_cacheable(&item, Some(3)));
+}
+
+#[test]
+fn test_key_is_stable_for_unchanged_item() {
+    let item = test_canvas_item(1, "note");
+    assert_eq!(
+        RenderKey::new(&item, None, 1.0, false, &colors()),
+        RenderKey::new(&item, None, 1.0004, false, &colors()),
+    );
+}
+
+#[test]
+fn test_key_changes_on_edit_zoom_selection_and_theme() {
+    let item = test_canvas_item(1, "note");
+    let key = RenderKey::new(&item, None, 1.0, false, &colors());
+
+    let edited = test_canvas_item(1, "edited note");
+    assert_ne!(key, RenderKey::new(&edited, None, 1.0, false, &colors()));
+    assert_ne!(key, RenderKey::new(&item, None, 2.0, false, &colors()));
+    assert_ne!(key, RenderKey::new(&item, None, 1.0, true, &colors()));
+
+    let mut light = colors();
+    light.fg = hsla(0.0, 0.0, 0.1, 1.0);
+    assert_ne!(key, RenderKey::new(&item, None, 1.0, false, &light));
+}
+
+#[test]
+fn test_key_changes_when_table_data_changes() {
+    let mut item = test_canvas_item(1, "");
+    item.content = ItemContent::Table {
+        data_source_id: 0,
+        show_headers: true,
+        stripe: true,
+    };
+    let card = TableCard {
+        filename: "sales.csv".to_string(),
+        row_count: 10,
+        col_count: 3,
+    };
+    let grown = TableCard {
+        row_count: 11,
+        ..card.clone()
+    };
+
+    assert_ne!(
+        RenderKey::new(&item, Some(&card), 1.0, false, &colors()),
+        RenderKey::new(&item, Some(&grown), 1.0, false, &colors()),
+    );
+}
+
+#[test]
+fn test_key_changes_when_markdown_card_expands() {
+    let mut item = test_canvas_item(1, "");
+    item.content = ItemContent::Markdown {
+        path: "/notes/plan.md".into(),
+        title: "Plan".to_string(),
+        content: "# Plan\n\n- ship it".to_string(),
+    };
+    item.size = (200.0, 36.0);
+    let chip = RenderKey::new(&item, None, 1.0, false, &colors());
+
+    item.size = (240.0, 48.0);
+    assert_eq!(chip, RenderKey::new(&item, None, 1.0, false, &colors()));
+
+    item.size = (400.0, 300.0);
+    assert_ne!(chip, RenderKey::new(&item, None, 1.0, false, &colors()));
+}
+
+#[test]
+fn test_markdown_card_being_edited_is_not_cacheable() {
+    let mut item = test_canvas_item(5, "");
+    item.content = ItemContent::Markdown {
+        path: "/notes/plan.md".into(),
+        title: "Plan".to_string(),
+        content: "# Plan".to_string(),
+    };
+
+    assert!(is_cacheable(&item, None));
+    assert!(!is_cacheable(&item, Some(5)));
+}
@@ -0,0 +1,96 @@
+//! Unit tests for item_clipboard module.
+
+use crate::helpers::test_canvas_item_at;
+use humanboard::item_clipboard::{CopiedItems, data_source_of, remap_items};
+use humanboard::types::{CanvasItem, ChartConfig, DataSource, ItemContent};
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+fn table(id: u64, data_source_id: u64) -> CanvasItem {
+    let mut item = test_canvas_item_at(id, "", (0.0, 0.0), (200.0, 36.0));
+    item.content = ItemContent::Table {
+        data_source_id,
+        show_headers: true,
+        stripe: true,
+    };
+    item
+}
+
+fn chart(id: u64, data_source_id: u64, source_item_id: Option<u64>) -> CanvasItem {
+    let mut item = test_canvas_item_at(id, "", (300.0, 0.0), (400.0, 300.0));
+    item.content = ItemContent::Chart {
+        data_source_id,
+        source_item_id,
+        config: ChartConfig::default(),
+    };
+    item
+}
+
+fn chart_source(item: &CanvasItem) -> Option<u64> {
+    match item.content {
+        ItemContent::Chart { source_item_id, .. } => source_item_id,
+        _ => panic!("not a chart"),
+    }
+}
+
+#[test]
+fn test_copied_items_take_their_data_sources() {
+    let mut sources = HashMap::new();
+    sources.insert(1, DataSource::new_empty(1, "Sales".to_string()));
+    sources.insert(2, DataSource::new_empty(2, "Unused".to_string()));
+    let items = vec![table(10, 1), chart(11, 1, Some(10))];
+
+    let copied = CopiedItems::new("board", PathBuf::from("/boards/board"), items, &sources);
+
+    assert_eq!(copied.data_sources.len(), 1);
+    assert_eq!(copied.data_sources[0].name, "Sales");
+    assert_eq!(data_source_of(&copied.items[1].content), Some(1));
+}
+
+#[test]
+fn test_copied_items_origin_and_text() {
+    let items = vec![
+        test_canvas_item_at(1, "first", (100.0, 50.0), (10.0, 10.0)),
+        test_canvas_item_at(2, "second", (40.0, 300.0), (10.0, 10.0)),
+    ];
+    let copied = CopiedItems::new("board", PathBuf::new(), items, &HashMap::new());
+
+    assert_eq!(copied.origin(), (40.0, 50.0));
+    assert_eq!(copied.text().lines().count(), 2);
+}
+
+#[test]
+fn test_remap_items_gives_new_ids_and_keeps_layout() {
+    let items = vec![
+        test_canvas_item_at(3, "a", (100.0, 100.0), (10.0, 10.0)),
+        test_canvas_item_at(7, "b", (150.0, 120.0), (10.0, 10.0)),
+    ];
+    let pasted = remap_items(&items, 50, (10.0, -20.0), &HashMap::new(), false);
+
+    assert_eq!(pasted[0].id, 50);
+    assert_eq!(pasted[1].id, 51);
+    assert_eq!(pasted[0].position, (110.0, 80.0));
+    assert_eq!(pasted[1].position, (160.0, 100.0));
+}
+
+#[test]
+fn test_remap_items_points_charts_at_pasted_tables() {
+    let items = vec![table(10, 1), chart(11, 1, Some(10))];
+    let data_source_ids = HashMap::from([(1, 4)]);
+    let pasted = remap_items(&items, 20, (0.0, 0.0), &data_source_ids, false);
+
+    assert_eq!(data_source_of(&pasted[0].content), Some(4));
+    assert_eq!(data_source_of(&pasted[1].content), Some(4));
+    assert_eq!(chart_source(&pasted[1]), Some(20));
+}
+
+#[test]
+fn test_remap_items_chart_without_its_table() {
+    let items = vec![chart(11, 1, Some(10))];
+
+    let same_board = remap_items(&items, 20, (0.0, 0.0), &HashMap::new(), true);
+    assert_eq!(chart_source(&same_board[0]), Some(10));
+
+    let other_board = remap_items(&items, 20, (0.0, 0.0), &HashMap::new(), false);
+    assert_eq!(chart_source(&other_board[0]), None);
+}
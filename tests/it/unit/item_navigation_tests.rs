@@ -0,0 +1,70 @@
+//! Unit tests for item_navigation module.
+
+use humanboard::input::guides::Bounds;
+use humanboard::item_navigation::{NavDirection, nearest_in_direction, next_in_order};
+
+/// Two rows of items: 1 and 2 on top, 3 below 1, 4 far right and slightly lower
+fn layout() -> Vec<(u64, Bounds)> {
+    vec![
+        (2, (300.0, 0.0, 100.0, 100.0)),
+        (3, (0.0, 300.0, 100.0, 100.0)),
+        (1, (0.0, 0.0, 100.0, 100.0)),
+        (4, (600.0, 50.0, 100.0, 100.0)),
+    ]
+}
+
+#[test]
+fn test_tab_order_is_reading_order() {
+    let items = layout();
+    assert_eq!(next_in_order(&items, None, false), Some(1));
+    assert_eq!(next_in_order(&items, Some(1), false), Some(2));
+    assert_eq!(next_in_order(&items, Some(2), false), Some(4));
+    // Wraps around at the end
+    assert_eq!(next_in_order(&items, Some(3), false), Some(1));
+}
+
+#[test]
+fn test_shift_tab_goes_backwards() {
+    let items = layout();
+    assert_eq!(next_in_order(&items, None, true), Some(3));
+    assert_eq!(next_in_order(&items, Some(1), true), Some(3));
+    assert_eq!(next_in_order(&items, Some(4), true), Some(2));
+}
+
+#[test]
+fn test_empty_board_has_no_next_item() {
+    assert_eq!(next_in_order(&[], None, false), None);
+}
+
+#[test]
+fn test_nearest_in_direction() {
+    let items = layout();
+    assert_eq!(
+        nearest_in_direction(&items, 1, NavDirection::Right),
+        Some(2)
+    );
+    assert_eq!(nearest_in_direction(&items, 1, NavDirection::Down), Some(3));
+    assert_eq!(
+        nearest_in_direction(&items, 2, NavDirection::Right),
+        Some(4)
+    );
+    assert_eq!(nearest_in_direction(&items, 3, NavDirection::Up), Some(1));
+    // Nothing further left or up
+    assert_eq!(nearest_in_direction(&items, 1, NavDirection::Left), None);
+    assert_eq!(nearest_in_direction(&items, 1, NavDirection::Up), None);
+}
+
+#[test]
+fn test_items_straight_ahead_beat_closer_diagonal_ones() {
+    let items = vec![
+        (1, (0.0, 0.0, 10.0, 10.0)),
+        // Close but well off to the side
+        (2, (100.0, 150.0, 10.0, 10.0)),
+        // Further but in line
+        (3, (300.0, 0.0, 10.0, 10.0)),
+    ];
+    assert_eq!(
+        nearest_in_direction(&items, 1, NavDirection::Right),
+        Some(3)
+    );
+}
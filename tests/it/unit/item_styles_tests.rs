@@ -0,0 +1,165 @@
+//! Unit tests for item_styles module.
+
+use humanboard::constants::{DEFAULT_FONT_SIZE, MAX_ARROW_THICKNESS, MIN_FONT_SIZE};
+use humanboard::item_styles::{ItemStyles, StyledKind};
+use humanboard::settings::{AppSettings, Setting, SettingsContent};
+use humanboard::types::{ArrowHead, ItemContent, ShapeType};
+use std::path::PathBuf;
+
+fn text_box(font_size: f32, color: &str) -> ItemContent {
+    ItemContent::TextBox {
+        text: "hello".to_string(),
+        font_size,
+        color: color.to_string(),
+    }
+}
+
+fn shape(fill: Option<&str>, border_width: f32) -> ItemContent {
+    ItemContent::Shape {
+        shape_type: ShapeType::Ellipse,
+        fill_color: fill.map(str::to_string),
+        border_color: "#112233".to_string(),
+        border_width,
+    }
+}
+
+#[test]
+fn test_new_items_follow_the_styles() {
+    let styles = ItemStyles {
+        text_font_size: 24.0,
+        text_color: "#ff0000".to_string(),
+        shape_fill: Some("#00ff00".to_string()),
+        arrow_thickness: 6.0,
+        arrow_head: ArrowHead::Diamond,
+        table_stripe: false,
+        ..ItemStyles::default()
+    };
+
+    match styles.text_box() {
+        ItemContent::TextBox {
+            text,
+            font_size,
+            color,
+        } => {
+            assert!(text.is_empty());
+            assert_eq!(font_size, 24.0);
+            assert_eq!(color, "#ff0000");
+        }
+        other => panic!("expected a text box, got {:?}", other),
+    }
+    match styles.shape(ShapeType::Rectangle) {
+        ItemContent::Shape {
+            shape_type,
+            fill_color,
+            ..
+        } => {
+            assert_eq!(shape_type, ShapeType::Rectangle);
+            assert_eq!(fill_color.as_deref(), Some("#00ff00"));
+        }
+        other => panic!("expected a shape, got {:?}", other),
+    }
+    match styles.arrow((10.0, 20.0)) {
+        ItemContent::Arrow {
+            end_offset,
+            thickness,
+            head_style,
+            ..
+        } => {
+            assert_eq!(end_offset, (10.0, 20.0));
+            assert_eq!(thickness, 6.0);
+            assert_eq!(head_style, ArrowHead::Diamond);
+        }
+        other => panic!("expected an arrow, got {:?}", other),
+    }
+    match styles.table(7) {
+        ItemContent::Table {
+            data_source_id,
+            show_headers,
+            stripe,
+        } => {
+            assert_eq!(data_source_id, 7);
+            assert!(show_headers);
+            assert!(!stripe);
+        }
+        other => panic!("expected a table, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_default_styles_match_the_old_defaults() {
+    let styles = ItemStyles::default();
+    assert_eq!(styles.text_font_size, DEFAULT_FONT_SIZE);
+    assert!(styles.text_color.is_empty());
+    assert_eq!(styles.shape_fill, None);
+    assert_eq!(styles.arrow_head, ArrowHead::Arrow);
+    assert!(styles.table_headers && styles.table_stripe);
+}
+
+#[test]
+fn test_set_from_takes_the_style_of_one_kind() {
+    let mut styles = ItemStyles::default();
+    assert_eq!(
+        styles.set_from(&shape(Some("#abcdef"), 5.0)),
+        Some(StyledKind::Shape)
+    );
+    assert_eq!(styles.shape_fill.as_deref(), Some("#abcdef"));
+    assert_eq!(styles.shape_border_color, "#112233");
+    assert_eq!(styles.shape_border_width, 5.0);
+    assert_eq!(styles.text_font_size, DEFAULT_FONT_SIZE);
+
+    let image = ItemContent::Image(PathBuf::from("/tmp/cat.png"));
+    assert_eq!(styles.set_from(&image), None);
+}
+
+#[test]
+fn test_set_from_items_uses_the_topmost_of_each_kind() {
+    let items = [
+        text_box(12.0, "#000000"),
+        shape(None, 1.0),
+        ItemContent::Image(PathBuf::from("/tmp/cat.png")),
+        text_box(40.0, "#ffffff"),
+    ];
+    let mut styles = ItemStyles::default();
+    let kinds = styles.set_from_items(items.iter());
+
+    assert_eq!(kinds, vec![StyledKind::TextBox, StyledKind::Shape]);
+    assert_eq!(styles.text_font_size, 40.0);
+    assert_eq!(styles.text_color, "#ffffff");
+    assert_eq!(styles.shape_border_width, 1.0);
+    assert_eq!(StyledKind::TextBox.plural(), "text boxes");
+}
+
+#[test]
+fn test_sanitized_keeps_styles_in_range() {
+    let styles = ItemStyles {
+        text_font_size: 1.0,
+        text_color: "red".to_string(),
+        shape_fill: Some("nope".to_string()),
+        arrow_color: "#abc".to_string(),
+        arrow_thickness: 500.0,
+        ..ItemStyles::default()
+    }
+    .sanitized();
+
+    assert_eq!(styles.text_font_size, MIN_FONT_SIZE);
+    assert!(styles.text_color.is_empty());
+    assert_eq!(styles.shape_fill, None);
+    assert_eq!(styles.arrow_color, "#abc");
+    assert_eq!(styles.arrow_thickness, MAX_ARROW_THICKNESS);
+}
+
+#[test]
+fn test_settings_read_partial_item_styles() {
+    let content: SettingsContent = serde_json::from_str(
+        r#"{"item_styles": {"text_font_size": 20.0, "arrow_head": "Circle"}}"#,
+    )
+    .unwrap();
+    let settings = AppSettings::from_content(&content);
+
+    assert_eq!(settings.item_styles.text_font_size, 20.0);
+    assert_eq!(settings.item_styles.arrow_head, ArrowHead::Circle);
+    assert!(settings.item_styles.table_headers);
+
+    let defaults = AppSettings::from_content(&SettingsContent::default());
+    assert_eq!(defaults.item_styles, ItemStyles::default());
+}
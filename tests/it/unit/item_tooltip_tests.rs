@@ -0,0 +1,64 @@
+//! Unit tests for hover tooltip details.
+
+use crate::helpers::{image_content, test_canvas_item};
+use humanboard::item_tooltip::{format_duration, item_tooltip, media_details};
+use humanboard::types::{DataSource, ItemContent};
+use std::collections::HashMap;
+use std::time::Duration;
+
+#[test]
+fn test_file_item_shows_name_and_full_path() {
+    let mut item = test_canvas_item(1, "");
+    item.content = image_content("/photos/trip/beach.png");
+    item.size = (640.0, 480.0);
+
+    let tooltip = item_tooltip(&item, &HashMap::new());
+    assert_eq!(tooltip.title, "beach.png");
+    assert_eq!(tooltip.path.as_deref(), Some("/photos/trip/beach.png"));
+    assert_eq!(tooltip.details, vec!["640 × 480 on canvas".to_string()]);
+}
+
+#[test]
+fn test_table_shows_row_and_column_counts() {
+    let source = DataSource::new_empty(7, "Sales".to_string());
+    let expected = format!(
+        "{} rows × {} columns",
+        source.row_count(),
+        source.column_count()
+    );
+    let data_sources = HashMap::from([(7, source)]);
+
+    let mut item = test_canvas_item(1, "");
+    item.content = ItemContent::Table {
+        data_source_id: 7,
+        show_headers: true,
+        stripe: false,
+    };
+
+    let tooltip = item_tooltip(&item, &data_sources);
+    assert_eq!(tooltip.title, "Sales");
+    assert_eq!(tooltip.path, None);
+    assert_eq!(tooltip.details[0], expected);
+}
+
+#[test]
+fn test_locked_items_say_so() {
+    let mut item = test_canvas_item(1, "Note");
+    item.locked = true;
+
+    let tooltip = item_tooltip(&item, &HashMap::new());
+    assert_eq!(tooltip.details.last().map(String::as_str), Some("Locked"));
+}
+
+#[test]
+fn test_missing_media_has_no_file_details() {
+    assert!(media_details(&image_content("/no/such/image.png")).is_empty());
+    assert!(media_details(&ItemContent::Audio("/no/such/song.mp3".into())).is_empty());
+}
+
+#[test]
+fn test_format_duration() {
+    assert_eq!(format_duration(Duration::from_secs(5)), "0:05");
+    assert_eq!(format_duration(Duration::from_secs(185)), "3:05");
+    assert_eq!(format_duration(Duration::from_secs(3725)), "1:02:05");
+}
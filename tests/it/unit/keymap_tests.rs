@@ -0,0 +1,145 @@
+//! Unit tests for keymap module.
+
+use humanboard::command_registry::{CommandRegistry, FnCommand};
+use humanboard::keymap::{
+    KeyConflict, UserKeymap, command_bindings, find_conflicts, is_valid_chord, normalize_chord,
+};
+use std::fs;
+use tempfile::tempdir;
+
+fn registry() -> CommandRegistry {
+    let mut registry = CommandRegistry::new();
+    registry.register(
+        FnCommand::new("edit:undo", "Undo", |_, _, _| {})
+            .category("Edit")
+            .keybinding("cmd-z")
+            .key_context("Canvas"),
+    );
+    registry.register(
+        FnCommand::new("view:outline", "Outline", |_, _, _| {})
+            .category("View")
+            .keybinding("cmd-alt-o"),
+    );
+    registry.register(
+        FnCommand::new("board:tidy", "Tidy Board", |_, _, _| {})
+            .category("Board")
+            .keybinding("cmd-y")
+            .key_context("Landing"),
+    );
+    registry
+}
+
+#[test]
+fn test_normalize_chord_orders_modifiers() {
+    assert_eq!(
+        normalize_chord("Shift-Cmd-Z").as_deref(),
+        Some("cmd-shift-z")
+    );
+    assert_eq!(normalize_chord("alt-ctrl-k").as_deref(), Some("ctrl-alt-k"));
+    assert_eq!(normalize_chord("cmd--").as_deref(), Some("cmd--"));
+    assert_eq!(
+        normalize_chord("cmd-k  shift-cmd-s").as_deref(),
+        Some("cmd-k cmd-shift-s")
+    );
+}
+
+#[test]
+fn test_invalid_chords_are_rejected() {
+    assert!(is_valid_chord("f8"));
+    assert!(is_valid_chord("-"));
+    assert!(!is_valid_chord(""));
+    assert!(!is_valid_chord("cmd-"));
+    assert!(!is_valid_chord("hyper-k"));
+    assert!(!is_valid_chord("cmd--k"));
+}
+
+#[test]
+fn test_parse_reads_one_many_and_unbound() {
+    let keymap = UserKeymap::parse(
+        r#"{
+            "view:outline": "shift-cmd-o",
+            "edit:redo": ["cmd-shift-z", "cmd-y", "cmd-y"],
+            "app:new_window": null,
+            "app:quit": [],
+            "edit:undo": ["cmd-", "ctrl-u"]
+        }"#,
+    )
+    .unwrap();
+    assert_eq!(keymap.bindings["view:outline"], vec!["cmd-shift-o"]);
+    assert_eq!(keymap.bindings["edit:redo"], vec!["cmd-shift-z", "cmd-y"]);
+    assert!(keymap.bindings["app:new_window"].is_empty());
+    assert!(keymap.bindings["app:quit"].is_empty());
+    assert_eq!(keymap.bindings["edit:undo"], vec!["ctrl-u"]);
+    assert_eq!(
+        keymap.invalid,
+        vec![("edit:undo".to_string(), "cmd-".to_string())]
+    );
+}
+
+#[test]
+fn test_parse_accepts_an_empty_file_and_rejects_bad_json() {
+    assert_eq!(UserKeymap::parse("  \n").unwrap(), UserKeymap::default());
+    assert!(UserKeymap::parse("{\"edit:undo\": 5}").is_err());
+    assert!(UserKeymap::parse("[").is_err());
+}
+
+#[test]
+fn test_load_missing_and_broken_files() {
+    let dir = tempdir().unwrap();
+    let path = dir.path().join("keymap.json");
+    assert_eq!(UserKeymap::load(&path).unwrap(), UserKeymap::default());
+
+    fs::write(&path, "{\"view:outline\": \"cmd-o\"").unwrap();
+    assert!(UserKeymap::load(&path).is_err());
+
+    fs::write(&path, "{\"view:outline\": \"cmd-o\"}").unwrap();
+    let keymap = UserKeymap::load(&path).unwrap();
+    assert!(keymap.is_customized("view:outline"));
+}
+
+#[test]
+fn test_command_bindings_apply_overrides() {
+    let registry = registry();
+    let keymap =
+        UserKeymap::parse(r#"{"view:outline": "cmd-shift-o", "edit:undo": null}"#).unwrap();
+    let bindings = command_bindings(&registry, &keymap);
+
+    assert_eq!(bindings.len(), 3);
+    assert_eq!(bindings[0].id, "edit:undo");
+    assert!(bindings[0].keys.is_empty());
+    assert!(bindings[0].customized);
+    assert_eq!(bindings[1].keys, vec!["cmd-shift-o"]);
+    assert_eq!(bindings[2].keys, vec!["cmd-y"]);
+    assert!(!bindings[2].customized);
+    assert_eq!(bindings[2].contexts, vec!["Landing"]);
+}
+
+#[test]
+fn test_unknown_commands_are_reported() {
+    let keymap = UserKeymap::parse(r#"{"view:outline": "cmd-o", "view:nope": "cmd-j"}"#).unwrap();
+    assert_eq!(keymap.unknown_commands(&registry()), vec!["view:nope"]);
+}
+
+#[test]
+fn test_conflicts_need_the_same_keystroke_and_overlapping_contexts() {
+    let registry = registry();
+    assert!(find_conflicts(&command_bindings(&registry, &UserKeymap::default())).is_empty());
+
+    // A sequence only clashes with the same sequence
+    let keymap = UserKeymap::parse(r#"{"view:outline": "shift-cmd-z cmd-z"}"#).unwrap();
+    assert!(find_conflicts(&command_bindings(&registry, &keymap)).is_empty());
+
+    // Outline applies everywhere, so it clashes with Undo on the canvas
+    let keymap = UserKeymap::parse(r#"{"view:outline": "ctrl-z"}"#).unwrap();
+    assert_eq!(
+        find_conflicts(&command_bindings(&registry, &keymap)),
+        vec![KeyConflict {
+            keystroke: "ctrl-z".to_string(),
+            commands: vec!["edit:undo", "view:outline"],
+        }]
+    );
+
+    // Undo and Tidy Board apply in different contexts
+    let keymap = UserKeymap::parse(r#"{"board:tidy": "cmd-z"}"#).unwrap();
+    assert!(find_conflicts(&command_bindings(&registry, &keymap)).is_empty());
+}
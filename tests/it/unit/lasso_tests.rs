@@ -0,0 +1,66 @@
+//! Unit tests for lasso module.
+
+use humanboard::input::lasso::{point_in_polygon, polygon_bounds, polygon_intersects_bounds};
+
+/// An L-shaped lasso covering the left column and bottom row of a 300x300 area
+fn l_shape() -> Vec<(f32, f32)> {
+    vec![
+        (0.0, 0.0),
+        (100.0, 0.0),
+        (100.0, 200.0),
+        (300.0, 200.0),
+        (300.0, 300.0),
+        (0.0, 300.0),
+    ]
+}
+
+#[test]
+fn test_point_in_polygon() {
+    let lasso = l_shape();
+    assert!(point_in_polygon((50.0, 50.0), &lasso));
+    assert!(point_in_polygon((250.0, 250.0), &lasso));
+    // The notch of the L is outside
+    assert!(!point_in_polygon((200.0, 100.0), &lasso));
+    assert!(!point_in_polygon((400.0, 50.0), &lasso));
+}
+
+#[test]
+fn test_item_inside_lasso_is_selected() {
+    assert!(polygon_intersects_bounds(&l_shape(), (20.0, 20.0, 40.0, 40.0)));
+}
+
+#[test]
+fn test_item_in_notch_is_not_selected() {
+    // Inside the lasso's bounding box but not the lasso itself
+    assert!(!polygon_intersects_bounds(&l_shape(), (150.0, 50.0, 100.0, 100.0)));
+}
+
+#[test]
+fn test_item_partly_covered_is_selected() {
+    assert!(polygon_intersects_bounds(&l_shape(), (80.0, 50.0, 100.0, 50.0)));
+}
+
+#[test]
+fn test_lasso_inside_item_is_selected() {
+    let small = vec![(10.0, 10.0), (20.0, 10.0), (15.0, 20.0)];
+    assert!(polygon_intersects_bounds(&small, (0.0, 0.0, 100.0, 100.0)));
+}
+
+#[test]
+fn test_lasso_crossing_item_without_corners_inside() {
+    // A thin sliver through the middle of the item
+    let sliver = vec![(-50.0, 45.0), (150.0, 45.0), (150.0, 55.0), (-50.0, 55.0)];
+    assert!(polygon_intersects_bounds(&sliver, (0.0, 0.0, 100.0, 100.0)));
+}
+
+#[test]
+fn test_degenerate_lasso_selects_nothing() {
+    let line = vec![(0.0, 0.0), (100.0, 100.0)];
+    assert!(!polygon_intersects_bounds(&line, (0.0, 0.0, 100.0, 100.0)));
+}
+
+#[test]
+fn test_polygon_bounds() {
+    assert_eq!(polygon_bounds(&l_shape()), Some((0.0, 0.0, 300.0, 300.0)));
+    assert_eq!(polygon_bounds(&[]), None);
+}
@@ -0,0 +1,67 @@
+//! Unit tests for markdown_links module.
+
+use humanboard::markdown_links::{LinkTarget, normalize_path, resolve_link};
+use std::path::{Path, PathBuf};
+
+#[test]
+fn test_urls_are_external() {
+    let base = Path::new("/notes");
+    for url in [
+        "https://example.com/page",
+        "http://example.com",
+        "mailto:someone@example.com",
+    ] {
+        assert_eq!(
+            resolve_link(url, Some(base)),
+            LinkTarget::External(url.to_string())
+        );
+    }
+}
+
+#[test]
+fn test_fragments_and_empty_links_are_anchors() {
+    assert_eq!(resolve_link("#usage", None), LinkTarget::Anchor);
+    assert_eq!(resolve_link("  ", None), LinkTarget::Anchor);
+}
+
+#[test]
+fn test_relative_links_resolve_against_base_dir() {
+    let base = Path::new("/notes/project");
+    assert_eq!(
+        resolve_link("todo.md", Some(base)),
+        LinkTarget::File(PathBuf::from("/notes/project/todo.md"))
+    );
+    assert_eq!(
+        resolve_link("../shared/./diagram.png", Some(base)),
+        LinkTarget::File(PathBuf::from("/notes/shared/diagram.png"))
+    );
+}
+
+#[test]
+fn test_file_links_drop_fragment_and_decode_escapes() {
+    let base = Path::new("/notes");
+    assert_eq!(
+        resolve_link("meeting%20notes.md#agenda", Some(base)),
+        LinkTarget::File(PathBuf::from("/notes/meeting notes.md"))
+    );
+    assert_eq!(
+        resolve_link("file:///tmp/report.pdf", Some(base)),
+        LinkTarget::File(PathBuf::from("/tmp/report.pdf"))
+    );
+}
+
+#[test]
+fn test_absolute_paths_ignore_base_dir() {
+    assert_eq!(
+        resolve_link("/var/data.csv", Some(Path::new("/notes"))),
+        LinkTarget::File(PathBuf::from("/var/data.csv"))
+    );
+}
+
+#[test]
+fn test_normalize_path_keeps_leading_parent_dirs() {
+    assert_eq!(
+        normalize_path(Path::new("../../a/./b/../c")),
+        PathBuf::from("../../a/c")
+    );
+}
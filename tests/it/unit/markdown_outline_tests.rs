@@ -0,0 +1,210 @@
+//! Unit tests for markdown_outline module.
+
+use crate::helpers::{test_content_item_at, text_box_content};
+use humanboard::board::BoardState;
+use humanboard::markdown_outline::{
+    MAX_TABLE_ROWS, compose_outline, demote_headings, export_outline, frame_tree, markdown_table,
+    reading_order,
+};
+use humanboard::types::{
+    CanvasItem, DataCell, DataColumn, DataRow, DataSource, DataType, ItemContent, ShapeType,
+};
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::PathBuf;
+use tempfile::tempdir;
+
+fn rect(id: u64, position: (f32, f32), size: (f32, f32)) -> CanvasItem {
+    test_content_item_at(
+        id,
+        ItemContent::Shape {
+            shape_type: ShapeType::RoundedRect,
+            fill_color: None,
+            border_color: "#888888".to_string(),
+            border_width: 1.0,
+        },
+        position,
+        size,
+    )
+}
+
+fn state(items: Vec<CanvasItem>) -> BoardState {
+    BoardState {
+        canvas_offset: (0.0, 0.0),
+        zoom: 1.0,
+        next_item_id: items.len() as u64,
+        items,
+        data_sources: HashMap::new(),
+        next_data_source_id: 0,
+        background: None,
+        playback_positions: BTreeMap::new(),
+        pdf_highlights: Vec::new(),
+        preview_session: None,
+    }
+}
+
+#[test]
+fn test_reading_order_groups_rows() {
+    let items = vec![
+        test_content_item_at(
+            1,
+            text_box_content("right, a little lower"),
+            (300.0, 10.0),
+            (200.0, 40.0),
+        ),
+        test_content_item_at(2, text_box_content("left"), (0.0, 0.0), (200.0, 40.0)),
+        test_content_item_at(3, text_box_content("next row"), (0.0, 200.0), (200.0, 40.0)),
+    ];
+    let mut refs: Vec<&CanvasItem> = items.iter().collect();
+    reading_order(&mut refs);
+    let ids: Vec<u64> = refs.iter().map(|item| item.id).collect();
+    assert_eq!(ids, vec![2, 1, 3]);
+}
+
+#[test]
+fn test_items_go_into_the_smallest_frame() {
+    let items = vec![
+        rect(1, (0.0, 100.0), (1000.0, 800.0)),
+        test_content_item_at(2, text_box_content("Outer"), (0.0, 50.0), (200.0, 40.0)),
+        rect(3, (50.0, 300.0), (500.0, 500.0)),
+        test_content_item_at(4, text_box_content("Inner"), (50.0, 250.0), (200.0, 40.0)),
+        test_content_item_at(5, text_box_content("deep"), (100.0, 350.0), (200.0, 40.0)),
+        test_content_item_at(6, text_box_content("deeper"), (100.0, 450.0), (200.0, 40.0)),
+        test_content_item_at(
+            7,
+            text_box_content("shallow"),
+            (600.0, 150.0),
+            (200.0, 40.0),
+        ),
+        test_content_item_at(8, text_box_content("outside"), (2000.0, 0.0), (200.0, 40.0)),
+    ];
+    let tree = frame_tree(&items);
+
+    assert_eq!(tree.items, vec![8]);
+    assert_eq!(tree.frames.len(), 1);
+    let outer = &tree.frames[0];
+    assert_eq!(outer.title, "Outer");
+    assert_eq!(outer.items, vec![7]);
+    assert_eq!(outer.frames.len(), 1);
+    assert_eq!(outer.frames[0].title, "Inner");
+    assert_eq!(outer.frames[0].items, vec![5, 6]);
+}
+
+#[test]
+fn test_sticky_note_is_not_a_frame() {
+    let items = vec![
+        rect(1, (0.0, 0.0), (200.0, 200.0)),
+        test_content_item_at(2, text_box_content("a note"), (0.0, 0.0), (200.0, 40.0)),
+        rect(3, (400.0, 0.0), (300.0, 300.0)),
+        test_content_item_at(
+            4,
+            ItemContent::Image(PathBuf::from("/tmp/cat.png")),
+            (420.0, 20.0),
+            (100.0, 100.0),
+        ),
+    ];
+    let tree = frame_tree(&items);
+
+    // The lone image makes a frame; the text box in a rectangle doesn't
+    assert_eq!(tree.items, vec![1, 2]);
+    assert_eq!(tree.frames.len(), 1);
+    assert_eq!(tree.frames[0].title, "Frame 1");
+    assert_eq!(tree.frames[0].items, vec![4]);
+}
+
+#[test]
+fn test_compose_outline() {
+    let mut board = state(vec![
+        test_content_item_at(1, text_box_content("Intro"), (0.0, 0.0), (200.0, 40.0)),
+        rect(2, (0.0, 200.0), (600.0, 400.0)),
+        test_content_item_at(3, text_box_content("Research"), (0.0, 150.0), (200.0, 40.0)),
+        test_content_item_at(
+            4,
+            ItemContent::Markdown {
+                path: PathBuf::from("/notes/a.md"),
+                title: "A".to_string(),
+                content: "# Findings\nSome text".to_string(),
+            },
+            (20.0, 220.0),
+            (200.0, 200.0),
+        ),
+        test_content_item_at(
+            5,
+            ItemContent::Image(PathBuf::from("/pics/chart.png")),
+            (300.0, 220.0),
+            (200.0, 200.0),
+        ),
+        test_content_item_at(
+            6,
+            ItemContent::Table {
+                data_source_id: 9,
+                show_headers: true,
+                stripe: false,
+            },
+            (20.0, 450.0),
+            (200.0, 100.0),
+        ),
+    ]);
+    let mut source = DataSource::new_empty(9, "Results".to_string());
+    source.columns = vec![
+        DataColumn::new("Name", DataType::Text),
+        DataColumn::new("Score", DataType::Number),
+    ];
+    source.rows = vec![DataRow::new(vec![
+        DataCell::Text("a|b".to_string()),
+        DataCell::Number(3.0),
+    ])];
+    board.data_sources = HashMap::from([(9, source)]);
+
+    let document = compose_outline(&board, "Plans", "plans_assets");
+    assert_eq!(
+        document.markdown,
+        "# Plans\n\nIntro\n\n## Research\n\n### Findings\nSome text\n\n\
+         ![chart](plans_assets/chart.png)\n\n**Results**\n\n\
+         | Name | Score |\n| --- | --- |\n| a\\|b | 3 |\n"
+    );
+    assert_eq!(
+        document.assets,
+        vec![(PathBuf::from("/pics/chart.png"), "chart.png".to_string())]
+    );
+}
+
+#[test]
+fn test_demote_headings_skips_code() {
+    assert_eq!(
+        demote_headings("# A\n```\n# not a heading\n```\n###### Deep\n#tag", 2),
+        "### A\n```\n# not a heading\n```\n###### Deep\n#tag"
+    );
+}
+
+#[test]
+fn test_long_tables_are_cut_short() {
+    let mut source = DataSource::new_empty(1, "Big".to_string());
+    source.columns = vec![DataColumn::new("N", DataType::Number)];
+    source.rows = (0..MAX_TABLE_ROWS + 5)
+        .map(|n| DataRow::new(vec![DataCell::Number(n as f64)]))
+        .collect();
+
+    let table = markdown_table(&source);
+    assert_eq!(table.lines().count(), MAX_TABLE_ROWS + 4);
+    assert!(table.ends_with("*5 more rows*"));
+}
+
+#[test]
+fn test_export_outline_copies_images() {
+    let dir = tempdir().unwrap();
+    let image = dir.path().join("photo.png");
+    fs::write(&image, b"png").unwrap();
+    let board = state(vec![
+        test_content_item_at(1, text_box_content("Hello"), (0.0, 0.0), (200.0, 40.0)),
+        test_content_item_at(2, ItemContent::Image(image), (0.0, 100.0), (100.0, 100.0)),
+    ]);
+
+    let path = dir.path().join("out").join("board.md");
+    fs::create_dir_all(path.parent().unwrap()).unwrap();
+    assert_eq!(export_outline(&board, "Board", &path).unwrap(), 2);
+
+    let markdown = fs::read_to_string(&path).unwrap();
+    assert!(markdown.contains("![photo](board_assets/photo.png)"));
+    assert!(dir.path().join("out/board_assets/photo.png").exists());
+}
@@ -0,0 +1,107 @@
+//! Unit tests for media playback commands.
+
+use humanboard::types::MediaVolume;
+use humanboard::webviews::{
+    MediaCommand, PlaybackPosition, seek_script, start_fragment, subtitles_script, volume_script,
+};
+
+#[test]
+fn test_toggle_script_checks_paused_state() {
+    let script = MediaCommand::Toggle.script();
+    assert!(script.contains("window.humanboardMedia.toggle()"));
+    assert!(script.contains("if (media.paused) { media.play(); } else { media.pause(); }"));
+}
+
+#[test]
+fn test_play_and_pause_scripts() {
+    let play = MediaCommand::Play.script();
+    assert!(play.contains("window.humanboardMedia.play()"));
+    assert!(play.contains("media.play();"));
+    assert!(!play.contains("media.pause()"));
+
+    let pause = MediaCommand::Pause.script();
+    assert!(pause.contains("window.humanboardMedia.pause()"));
+    assert!(pause.contains("media.pause();"));
+    assert!(!pause.contains("media.play()"));
+}
+
+#[test]
+fn test_script_targets_audio_and_video_elements() {
+    let script = MediaCommand::Pause.script();
+    assert!(script.contains("document.querySelector('video, audio')"));
+}
+
+#[test]
+fn test_parse_playback_position() {
+    assert_eq!(
+        PlaybackPosition::parse("[30,120,true]"),
+        Some(PlaybackPosition {
+            seconds: 30.0,
+            fraction: 0.25,
+            playing: true
+        })
+    );
+    // Some platforms wrap the JSON result in a string
+    assert_eq!(
+        PlaybackPosition::parse("\"[60,120,false]\""),
+        Some(PlaybackPosition {
+            seconds: 60.0,
+            fraction: 0.5,
+            playing: false
+        })
+    );
+}
+
+#[test]
+fn test_parse_playback_position_before_load() {
+    assert_eq!(PlaybackPosition::parse("null"), None);
+    assert_eq!(PlaybackPosition::parse(""), None);
+    assert_eq!(PlaybackPosition::parse("[\"a\",1,true]"), None);
+    assert_eq!(PlaybackPosition::parse("[0,0,false]"), None);
+}
+
+#[test]
+fn test_parse_playback_position_clamps() {
+    let position = PlaybackPosition::parse("[130,120,true]").unwrap();
+    assert_eq!(position.seconds, 120.0);
+    assert_eq!(position.fraction, 1.0);
+}
+
+#[test]
+fn test_resume_seconds_restarts_finished_media() {
+    let halfway = PlaybackPosition::parse("[60,120,false]").unwrap();
+    assert_eq!(halfway.resume_seconds(), Some(60.0));
+    let finished = PlaybackPosition::parse("[119.5,120,false]").unwrap();
+    assert_eq!(finished.resume_seconds(), None);
+}
+
+#[test]
+fn test_start_fragment() {
+    assert_eq!(start_fragment(0.0), "");
+    assert_eq!(start_fragment(f64::NAN), "");
+    assert_eq!(start_fragment(42.5), "#t=42.50");
+}
+
+#[test]
+fn test_seek_script_clamps_fraction() {
+    assert!(seek_script(0.5).contains("media.currentTime = 0.5 * media.duration"));
+    assert!(seek_script(-1.0).contains("media.currentTime = 0 * media.duration"));
+    assert!(seek_script(3.0).contains("media.currentTime = 1 * media.duration"));
+}
+
+#[test]
+fn test_subtitles_script_sets_track_mode() {
+    assert!(subtitles_script(true).contains("video.textTracks[0].mode = 'showing'"));
+    assert!(subtitles_script(false).contains("video.textTracks[0].mode = 'hidden'"));
+}
+
+#[test]
+fn test_volume_script_sets_element_or_bridge() {
+    let script = volume_script(MediaVolume {
+        level: 0.5,
+        muted: true,
+    });
+    assert!(script.contains("window.humanboardMedia.volume(0.5, true)"));
+    assert!(script.contains("media.volume = 0.5;"));
+    assert!(script.contains("media.muted = true;"));
+}
@@ -0,0 +1,49 @@
+//! Unit tests for media_server module.
+
+use humanboard::webviews::{RouteTarget, media_mime, parse_route};
+use std::path::Path;
+
+#[test]
+fn test_parse_route_targets() {
+    assert_eq!(parse_route("/12/"), Some((12, RouteTarget::Page)));
+    assert_eq!(parse_route("/12"), Some((12, RouteTarget::Page)));
+    assert_eq!(parse_route("/12/media"), Some((12, RouteTarget::Media)));
+    assert_eq!(
+        parse_route("/12/subtitles"),
+        Some((12, RouteTarget::Subtitles))
+    );
+    assert_eq!(parse_route("/12/art"), Some((12, RouteTarget::Art)));
+}
+
+#[test]
+fn test_parse_route_ignores_query_and_fragment() {
+    assert_eq!(parse_route("/3/media?v=1"), Some((3, RouteTarget::Media)));
+    assert_eq!(
+        parse_route("/3/media#t=42.00"),
+        Some((3, RouteTarget::Media))
+    );
+    assert_eq!(parse_route("/3/?autoplay=0"), Some((3, RouteTarget::Page)));
+}
+
+#[test]
+fn test_parse_route_rejects_unknown_paths() {
+    assert_eq!(parse_route("/"), None);
+    assert_eq!(parse_route("/favicon.ico"), None);
+    assert_eq!(parse_route("/3/other"), None);
+    assert_eq!(parse_route("/3/media/extra"), None);
+}
+
+#[test]
+fn test_media_mime_from_extension() {
+    assert_eq!(media_mime(Path::new("song.MP3")), "audio/mpeg");
+    assert_eq!(media_mime(Path::new("clip.mov")), "video/quicktime");
+    assert_eq!(media_mime(Path::new("cover.JPG")), "image/jpeg");
+    assert_eq!(
+        media_mime(Path::new("notes.txt")),
+        "application/octet-stream"
+    );
+    assert_eq!(
+        media_mime(Path::new("no_extension")),
+        "application/octet-stream"
+    );
+}
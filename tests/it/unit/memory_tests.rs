@@ -0,0 +1,132 @@
+//! Unit tests for the media memory budget.
+
+use humanboard::memory::{MediaResource, MemoryMonitor, decoded_image_bytes, format_bytes};
+use image::{Rgba, RgbaImage};
+use std::path::PathBuf;
+use tempfile::tempdir;
+
+fn image(name: &str) -> MediaResource {
+    MediaResource::Image(PathBuf::from(name))
+}
+
+#[test]
+fn test_under_budget_evicts_nothing() {
+    let mut monitor = MemoryMonitor::new(1000);
+    monitor.begin_frame();
+    monitor.mark_visible(image("a.png"), 400);
+    monitor.mark_visible(MediaResource::WebView(1), 500);
+
+    assert!(!monitor.is_over_budget());
+    assert!(monitor.evict().is_empty());
+    assert_eq!(monitor.total_bytes(), 900);
+}
+
+#[test]
+fn test_evicts_least_recently_visible_first() {
+    let mut monitor = MemoryMonitor::new(1000);
+    monitor.begin_frame();
+    monitor.mark_visible(image("old.png"), 400);
+    monitor.begin_frame();
+    monitor.mark_visible(image("recent.png"), 400);
+    monitor.begin_frame();
+    monitor.mark_visible(image("current.png"), 400);
+
+    assert_eq!(monitor.evict(), vec![image("old.png")]);
+    assert!(monitor.is_tracked(&image("recent.png")));
+    assert_eq!(monitor.total_bytes(), 800);
+}
+
+#[test]
+fn test_never_evicts_resources_visible_this_frame() {
+    let mut monitor = MemoryMonitor::new(100);
+    monitor.begin_frame();
+    monitor.mark_visible(image("a.png"), 400);
+    monitor.mark_visible(MediaResource::WebView(1), 400);
+
+    assert!(monitor.evict().is_empty());
+    assert!(monitor.is_over_budget());
+}
+
+#[test]
+fn test_marking_visible_again_keeps_original_size() {
+    let mut monitor = MemoryMonitor::new(1000);
+    monitor.begin_frame();
+    monitor.mark_visible(image("a.png"), 400);
+    monitor.begin_frame();
+    monitor.mark_visible_with(image("a.png"), || panic!("size is only read once"));
+
+    assert_eq!(monitor.total_bytes(), 400);
+}
+
+#[test]
+fn test_loaded_webviews_age_without_being_seen() {
+    let mut monitor = MemoryMonitor::new(1000);
+    monitor.begin_frame();
+    monitor.mark_loaded(MediaResource::WebView(1), 600);
+    monitor.begin_frame();
+    monitor.mark_loaded(MediaResource::WebView(1), 600);
+    monitor.mark_visible(MediaResource::WebView(2), 600);
+
+    assert_eq!(monitor.evict(), vec![MediaResource::WebView(1)]);
+}
+
+#[test]
+fn test_retain_webviews_forgets_closed_ones() {
+    let mut monitor = MemoryMonitor::new(1000);
+    monitor.begin_frame();
+    monitor.mark_visible(MediaResource::WebView(1), 100);
+    monitor.mark_visible(MediaResource::WebView(2), 100);
+    monitor.mark_visible(image("a.png"), 100);
+
+    monitor.retain_webviews(|id| id == 2);
+
+    assert!(!monitor.is_tracked(&MediaResource::WebView(1)));
+    assert!(monitor.is_tracked(&MediaResource::WebView(2)));
+    assert!(monitor.is_tracked(&image("a.png")));
+}
+
+#[test]
+fn test_stats_split_by_kind_and_count_evictions() {
+    let mut monitor = MemoryMonitor::new(1000);
+    monitor.begin_frame();
+    monitor.mark_visible(image("a.png"), 300);
+    monitor.mark_visible(MediaResource::PdfThumbnail(PathBuf::from("doc.png")), 200);
+    monitor.begin_frame();
+    monitor.mark_visible(MediaResource::WebView(1), 700);
+    monitor.evict();
+
+    let stats = monitor.stats();
+    assert_eq!(stats.webview_count, 1);
+    assert_eq!(stats.webview_bytes, 700);
+    assert_eq!(stats.image_count + stats.pdf_count, 1);
+    assert_eq!(stats.evictions, 1);
+    assert!(stats.total_bytes() <= stats.budget_bytes);
+}
+
+#[test]
+fn test_budget_in_megabytes() {
+    let mut monitor = MemoryMonitor::with_budget_mb(2);
+    assert_eq!(monitor.budget_bytes(), 2 * 1024 * 1024);
+    monitor.set_budget_mb(512);
+    assert_eq!(monitor.budget_bytes(), 512 * 1024 * 1024);
+}
+
+#[test]
+fn test_decoded_image_bytes_reads_dimensions() {
+    let dir = tempdir().unwrap();
+    let path = dir.path().join("photo.png");
+    RgbaImage::from_pixel(40, 25, Rgba([0, 0, 0, 255]))
+        .save(&path)
+        .unwrap();
+
+    assert_eq!(decoded_image_bytes(&path), 40 * 25 * 4);
+    assert_eq!(decoded_image_bytes(&dir.path().join("missing.png")), 0);
+}
+
+#[test]
+fn test_format_bytes() {
+    assert_eq!(format_bytes(512), "512 B");
+    assert_eq!(format_bytes(2048), "2.0 KB");
+    assert_eq!(format_bytes(5 * 1024 * 1024 + 512 * 1024), "5.5 MB");
+    assert_eq!(format_bytes(3 * 1024 * 1024 * 1024), "3.0 GB");
+}
@@ -1,15 +1,91 @@
 //! Unit tests for Humanboard.
 
+mod arrange_tests;
+mod audio_metadata_tests;
 mod background_tests;
+mod backgrounds_tests;
+mod board_assets_tests;
+mod board_crypto_tests;
+mod board_diff_tests;
+mod board_export_tests;
+mod board_format_tests;
 mod board_index_tests;
+mod board_journal_tests;
+mod board_outline_tests;
+mod board_thumbnail_tests;
+mod board_versions_tests;
+mod canvas_background_tests;
+mod canvas_find_tests;
+mod canvas_snapshot_tests;
+mod card_refresh_tests;
+mod code_excerpt_tests;
+mod code_symbols_tests;
+mod command_palette_tests;
 mod command_registry_tests;
+mod constrain_tests;
+mod content_search_tests;
+mod context_menu_tests;
+mod custom_themes_tests;
+mod data_preview_tests;
+mod document_export_tests;
+mod file_import_tests;
+mod file_links_tests;
+mod find_replace_tests;
 mod focus_tests;
+mod frecency_tests;
+mod fuzzy_tests;
+mod group_resize_tests;
+mod guides_tests;
 mod hit_testing_tests;
+mod image_cache_tests;
+mod image_viewer_tests;
+mod lasso_tests;
+mod item_cache_tests;
+mod item_clipboard_tests;
+mod item_navigation_tests;
+mod item_styles_tests;
+mod item_tooltip_tests;
+mod keymap_tests;
 mod loading_tests;
+mod markdown_links_tests;
+mod markdown_outline_tests;
+mod media_control_tests;
+mod media_server_tests;
+mod memory_tests;
 mod notifications_tests;
+mod obsidian_canvas_tests;
+mod pdf_fallback_tests;
+mod pdf_highlights_tests;
+mod pdf_outline_tests;
+mod pdf_pages_tests;
+mod pdf_text_index_tests;
+mod pdf_thumbnail_tests;
 mod perf_tests;
+mod playlist_tests;
+mod preview_history_tests;
+mod preview_panes_tests;
+mod preview_session_tests;
+mod preview_tests;
+mod progressive_load_tests;
+mod quick_open_tests;
+mod region_export_tests;
+mod resize_tests;
+mod search_query_tests;
 mod selection_tests;
+mod settings_profile_tests;
 mod settings_watcher_tests;
+mod snap_tests;
 mod snapshot_tests;
+mod subtitles_tests;
+mod syntax_tokens_tests;
+mod tab_zoom_tests;
+mod text_diff_tests;
+mod text_layout_tests;
+mod transform_tests;
 mod types_tests;
+mod undo_history_tests;
+mod url_paste_tests;
 mod validation_tests;
+mod waveform_tests;
+mod whiteboard_import_tests;
+mod wiki_links_tests;
@@ -1,6 +1,6 @@
 //! Unit tests for notifications module.
 
-use humanboard::notifications::{Toast, ToastManager, ToastVariant};
+use humanboard::notifications::{Toast, ToastAction, ToastActionType, ToastManager, ToastVariant};
 use std::time::Duration;
 
 #[test]
@@ -104,6 +104,22 @@ fn test_toast_with_custom_duration() {
     assert_eq!(toast.duration, Duration::from_secs(42));
 }
 
+#[test]
+fn test_recovery_prompt_offers_recover_and_discard() {
+    let toast = Toast::warning("Unsaved changes found")
+        .with_action(ToastAction::recover_board("b1"))
+        .with_secondary_action(ToastAction::discard_recovery("b1"));
+
+    assert_eq!(
+        toast.action.map(|a| a.action_type),
+        Some(ToastActionType::RecoverBoard("b1".to_string()))
+    );
+    assert_eq!(
+        toast.secondary_action.map(|a| a.action_type),
+        Some(ToastActionType::DiscardRecovery("b1".to_string()))
+    );
+}
+
 #[test]
 fn test_toast_manager_remove() {
     let mut manager = ToastManager::new();
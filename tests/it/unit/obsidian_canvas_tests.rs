@@ -0,0 +1,226 @@
+//! Unit tests for importing Obsidian canvases.
+
+use humanboard::obsidian_canvas::{canvas_color, convert_canvas, import_canvas, vault_root};
+use humanboard::types::{ArrowHead, ItemContent, ShapeType};
+use std::fs;
+use std::path::Path;
+use tempfile::tempdir;
+
+#[test]
+fn test_text_node_becomes_text_box() {
+    let json = r##"{"nodes": [
+        {"id": "a", "type": "text", "text": "# Idea\nMore", "x": -10, "y": 20,
+         "width": 250, "height": 60, "color": "4"}
+    ]}"##;
+    let converted = convert_canvas(json, Path::new(".")).unwrap();
+
+    assert_eq!(converted.items.len(), 1);
+    let item = &converted.items[0];
+    assert_eq!(item.position, (-10.0, 20.0));
+    assert_eq!(item.size, (250.0, 60.0));
+    match &item.content {
+        ItemContent::TextBox { text, color, .. } => {
+            assert_eq!(text, "# Idea\nMore");
+            assert_eq!(color, "#44cf6e");
+        }
+        other => panic!("expected a text box, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_groups_go_behind_with_label_above() {
+    let json = r##"{"nodes": [
+        {"id": "a", "type": "text", "text": "inside", "x": 20, "y": 20, "width": 100, "height": 50},
+        {"id": "g", "type": "group", "label": "Research", "x": 0, "y": 0,
+         "width": 400, "height": 300, "color": "#FF0000"}
+    ]}"##;
+    let converted = convert_canvas(json, Path::new(".")).unwrap();
+
+    assert_eq!(converted.items.len(), 3);
+    match &converted.items[0].content {
+        ItemContent::Shape {
+            shape_type,
+            fill_color,
+            border_color,
+            ..
+        } => {
+            assert_eq!(*shape_type, ShapeType::RoundedRect);
+            assert_eq!(*fill_color, None);
+            assert_eq!(border_color, "#ff0000");
+        }
+        other => panic!("expected a shape, got {:?}", other),
+    }
+    assert!(
+        matches!(&converted.items[1].content, ItemContent::TextBox { text, .. } if text == "Research")
+    );
+    assert!(converted.items[1].position.1 < 0.0);
+    assert!(
+        matches!(&converted.items[2].content, ItemContent::TextBox { text, .. } if text == "inside")
+    );
+}
+
+#[test]
+fn test_link_nodes_become_embeds_or_bookmarks() {
+    let json = r#"{"nodes": [
+        {"id": "a", "type": "link", "url": "https://www.youtube.com/watch?v=dQw4w9WgXcQ",
+         "x": 0, "y": 0, "width": 400, "height": 300},
+        {"id": "b", "type": "link", "url": "https://example.com/article",
+         "x": 500, "y": 0, "width": 400, "height": 300}
+    ]}"#;
+    let converted = convert_canvas(json, Path::new(".")).unwrap();
+
+    assert!(matches!(
+        converted.items[0].content,
+        ItemContent::YouTube(_)
+    ));
+    assert!(matches!(
+        &converted.items[1].content,
+        ItemContent::Bookmark { url, .. } if url == "https://example.com/article"
+    ));
+}
+
+#[test]
+fn test_file_nodes_resolve_in_vault() {
+    let vault = tempdir().unwrap();
+    fs::create_dir(vault.path().join("notes")).unwrap();
+    fs::write(vault.path().join("notes/plan.md"), "# Plan\n").unwrap();
+    let json = r#"{"nodes": [
+        {"id": "a", "type": "file", "file": "notes/plan.md", "x": 0, "y": 0, "width": 400, "height": 400},
+        {"id": "b", "type": "file", "file": "gone.png", "x": 0, "y": 0, "width": 400, "height": 400}
+    ]}"#;
+    let converted = convert_canvas(json, vault.path()).unwrap();
+
+    assert_eq!(converted.items.len(), 1);
+    match &converted.items[0].content {
+        ItemContent::Markdown { path, title, .. } => {
+            assert_eq!(path, &vault.path().join("notes/plan.md"));
+            assert_eq!(title, "plan");
+        }
+        other => panic!("expected a markdown card, got {:?}", other),
+    }
+    assert_eq!(
+        converted.skipped,
+        vec!["Missing file 'gone.png'".to_string()]
+    );
+}
+
+#[test]
+fn test_edges_become_arrows_between_sides() {
+    let json = r#"{
+        "nodes": [
+            {"id": "a", "type": "text", "text": "A", "x": 0, "y": 0, "width": 100, "height": 100},
+            {"id": "b", "type": "text", "text": "B", "x": 300, "y": 0, "width": 100, "height": 100}
+        ],
+        "edges": [
+            {"id": "e1", "fromNode": "a", "fromSide": "right", "toNode": "b", "toSide": "left",
+             "label": "leads to", "color": "1"},
+            {"id": "e2", "fromNode": "a", "toNode": "b", "toEnd": "none"},
+            {"id": "e3", "fromNode": "a", "toNode": "missing"}
+        ]
+    }"#;
+    let converted = convert_canvas(json, Path::new(".")).unwrap();
+
+    // Two notes, the first arrow and its label, the second arrow
+    assert_eq!(converted.items.len(), 5);
+    let arrow = &converted.items[2];
+    assert_eq!(arrow.position, (100.0, 50.0));
+    match &arrow.content {
+        ItemContent::Arrow {
+            end_offset,
+            color,
+            head_style,
+            ..
+        } => {
+            assert_eq!(*end_offset, (200.0, 0.0));
+            assert_eq!(color, "#fb464c");
+            assert_eq!(*head_style, ArrowHead::Arrow);
+        }
+        other => panic!("expected an arrow, got {:?}", other),
+    }
+    assert!(
+        matches!(&converted.items[3].content, ItemContent::TextBox { text, .. } if text == "leads to")
+    );
+    // Sides facing each other when none are given, with no head
+    assert_eq!(converted.items[4].position, (100.0, 50.0));
+    assert!(matches!(
+        converted.items[4].content,
+        ItemContent::Arrow {
+            head_style: ArrowHead::None,
+            ..
+        }
+    ));
+    assert_eq!(converted.skipped.len(), 1);
+}
+
+#[test]
+fn test_arrow_points_at_the_end_with_a_head() {
+    let json = r#"{
+        "nodes": [
+            {"id": "a", "type": "text", "text": "A", "x": 0, "y": 0, "width": 100, "height": 100},
+            {"id": "b", "type": "text", "text": "B", "x": 0, "y": 300, "width": 100, "height": 100}
+        ],
+        "edges": [{"id": "e", "fromNode": "a", "toNode": "b", "fromEnd": "arrow", "toEnd": "none"}]
+    }"#;
+    let converted = convert_canvas(json, Path::new(".")).unwrap();
+
+    let arrow = &converted.items[2];
+    assert_eq!(arrow.position, (50.0, 300.0));
+    match &arrow.content {
+        ItemContent::Arrow { end_offset, .. } => assert_eq!(*end_offset, (0.0, -200.0)),
+        other => panic!("expected an arrow, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_unknown_nodes_are_skipped() {
+    let json = r#"{"nodes": [
+        {"id": "x", "type": "widget", "x": 0, "y": 0, "width": 10, "height": 10}
+    ]}"#;
+    let converted = convert_canvas(json, Path::new(".")).unwrap();
+    assert!(converted.items.is_empty());
+    assert_eq!(converted.skipped.len(), 1);
+}
+
+#[test]
+fn test_invalid_json_is_an_error() {
+    assert!(convert_canvas("not json", Path::new(".")).is_err());
+}
+
+#[test]
+fn test_canvas_colors() {
+    assert_eq!(canvas_color("6").as_deref(), Some("#a882ff"));
+    assert_eq!(canvas_color("#ABCDEF").as_deref(), Some("#abcdef"));
+    assert_eq!(canvas_color("7"), None);
+    assert_eq!(canvas_color("#abc"), None);
+}
+
+#[test]
+fn test_vault_root_is_folder_with_obsidian_settings() {
+    let vault = tempdir().unwrap();
+    fs::create_dir(vault.path().join(".obsidian")).unwrap();
+    fs::create_dir_all(vault.path().join("boards/2024")).unwrap();
+
+    let canvas = vault.path().join("boards/2024/map.canvas");
+    assert_eq!(vault_root(&canvas), vault.path());
+
+    let loose = tempdir().unwrap();
+    let canvas = loose.path().join("map.canvas");
+    assert_eq!(vault_root(&canvas), loose.path());
+}
+
+#[test]
+fn test_import_canvas_copies_from_vault() {
+    let vault = tempdir().unwrap();
+    let canvas = vault.path().join("map.canvas");
+    fs::write(
+        &canvas,
+        r#"{"nodes": [{"id": "a", "type": "text", "text": "A", "x": 0, "y": 0, "width": 10, "height": 10}]}"#,
+    )
+    .unwrap();
+
+    let (imported, skipped) = import_canvas(&canvas).unwrap();
+    assert_eq!(imported.items.len(), 1);
+    assert_eq!(imported.board_dir, vault.path());
+    assert!(skipped.is_empty());
+    assert!(import_canvas(&vault.path().join("missing.canvas")).is_err());
+}
@@ -0,0 +1,71 @@
+//! Unit tests for pdf fallback module.
+
+use humanboard::pdf::{PdfiumLoader, fallback_thumbnail, first_embedded_jpeg, placeholder_page};
+use image::{DynamicImage, ImageFormat, RgbImage};
+use std::io::Cursor;
+use tempfile::tempdir;
+
+fn jpeg(width: u32, height: u32) -> Vec<u8> {
+    let image = DynamicImage::ImageRgb8(RgbImage::from_pixel(width, height, [200, 40, 40].into()));
+    let mut bytes = Vec::new();
+    image
+        .write_to(&mut Cursor::new(&mut bytes), ImageFormat::Jpeg)
+        .unwrap();
+    bytes
+}
+
+fn pdf_with_stream(filter: &str, data: &[u8]) -> Vec<u8> {
+    let mut pdf = b"%PDF-1.4\n1 0 obj\n<< /Type /Catalog >>\nendobj\n".to_vec();
+    pdf.extend_from_slice(
+        format!(
+            "2 0 obj\n<< /Type /XObject /Subtype /Image /Filter {} /Length {} >>\nstream\r\n",
+            filter,
+            data.len()
+        )
+        .as_bytes(),
+    );
+    pdf.extend_from_slice(data);
+    pdf.extend_from_slice(b"\nendstream\nendobj\n%%EOF\n");
+    pdf
+}
+
+#[test]
+fn test_first_embedded_jpeg_decodes_dct_stream() {
+    let pdf = pdf_with_stream("/DCTDecode", &jpeg(120, 160));
+    let image = first_embedded_jpeg(&pdf).expect("embedded JPEG");
+    assert_eq!((image.width(), image.height()), (120, 160));
+}
+
+#[test]
+fn test_first_embedded_jpeg_skips_small_and_compressed_images() {
+    let icon = pdf_with_stream("/DCTDecode", &jpeg(16, 16));
+    assert!(first_embedded_jpeg(&icon).is_none());
+
+    let compressed = pdf_with_stream("[/FlateDecode /DCTDecode]", &jpeg(120, 160));
+    assert!(first_embedded_jpeg(&compressed).is_none());
+
+    assert!(first_embedded_jpeg(b"%PDF-1.4\n%%EOF\n").is_none());
+}
+
+#[test]
+fn test_fallback_thumbnail_uses_placeholder_without_images() {
+    let dir = tempdir().unwrap();
+    let path = dir.path().join("text.pdf");
+    std::fs::write(&path, b"%PDF-1.4\n%%EOF\n").unwrap();
+
+    let thumbnail = fallback_thumbnail(&path);
+    let placeholder = placeholder_page();
+    assert_eq!(
+        (thumbnail.width(), thumbnail.height()),
+        (placeholder.width(), placeholder.height())
+    );
+    // Taller than wide, like a page
+    assert!(placeholder.height() > placeholder.width());
+}
+
+#[test]
+fn test_pdfium_remediation_names_library_and_override() {
+    let message = PdfiumLoader::remediation();
+    assert!(message.contains(&PdfiumLoader::library_name()));
+    assert!(message.contains(humanboard::pdf::PDFIUM_LIBRARY_ENV));
+}
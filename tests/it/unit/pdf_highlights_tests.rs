@@ -0,0 +1,48 @@
+//! Unit tests for pdf highlights module.
+
+use humanboard::pdf::{PdfHighlight, matched_text};
+use std::path::PathBuf;
+
+fn highlight(text: &str) -> PdfHighlight {
+    PdfHighlight {
+        id: 0,
+        path: PathBuf::from("/docs/report.pdf"),
+        page: 2,
+        offset: 0,
+        text: text.to_string(),
+        note_item: None,
+    }
+}
+
+#[test]
+fn test_location_counts_pages_from_one() {
+    assert_eq!(highlight("x").location(), "report.pdf, p. 3");
+}
+
+#[test]
+fn test_note_text_quotes_passage_on_one_line() {
+    assert_eq!(
+        highlight("Revenue grew\n  by 12%").note_text(),
+        "“Revenue grew by 12%”\n— report.pdf, p. 3"
+    );
+}
+
+#[test]
+fn test_matched_text_keeps_original_case() {
+    let page = "The Quarterly Figures are in";
+    assert_eq!(
+        matched_text(page, 4, "quarterly figures"),
+        Some("Quarterly Figures".to_string())
+    );
+}
+
+#[test]
+fn test_matched_text_out_of_range() {
+    assert_eq!(matched_text("short", 3, "longer"), None);
+}
+
+#[test]
+fn test_note_item_not_saved_when_unset() {
+    let json = serde_json::to_string(&highlight("x")).unwrap();
+    assert!(!json.contains("note_item"));
+}
@@ -0,0 +1,88 @@
+//! Unit tests for pdf outline module.
+
+use humanboard::pdf::{
+    PdfOutlineCache, PdfOutlineEntry, active_outline_row, outline_rows, read_pdf_outline,
+};
+use std::collections::HashSet;
+use std::path::Path;
+
+fn entry(title: &str, page: Option<usize>, children: Vec<PdfOutlineEntry>) -> PdfOutlineEntry {
+    PdfOutlineEntry {
+        title: title.to_string(),
+        page,
+        children,
+    }
+}
+
+fn sample_outline() -> Vec<PdfOutlineEntry> {
+    vec![
+        entry("Introduction", Some(0), vec![]),
+        entry(
+            "Methods",
+            Some(2),
+            vec![
+                entry("Data", Some(3), vec![]),
+                entry("Analysis", Some(5), vec![]),
+            ],
+        ),
+        entry("Results", Some(8), vec![]),
+    ]
+}
+
+fn titles(rows: &[humanboard::pdf::OutlineRow]) -> Vec<&str> {
+    rows.iter().map(|row| row.title.as_str()).collect()
+}
+
+#[test]
+fn test_outline_rows_expanded_by_default() {
+    let rows = outline_rows(&sample_outline(), &HashSet::new());
+    assert_eq!(
+        titles(&rows),
+        vec!["Introduction", "Methods", "Data", "Analysis", "Results"]
+    );
+    assert_eq!(rows[2].index_path, vec![1, 0]);
+    assert_eq!(rows[2].depth, 1);
+    assert!(rows[1].has_children && rows[1].expanded);
+    assert!(!rows[0].has_children && !rows[0].expanded);
+}
+
+#[test]
+fn test_collapsed_entries_hide_children() {
+    let collapsed = HashSet::from([vec![1]]);
+    let rows = outline_rows(&sample_outline(), &collapsed);
+    assert_eq!(titles(&rows), vec!["Introduction", "Methods", "Results"]);
+    assert!(rows[1].has_children && !rows[1].expanded);
+}
+
+#[test]
+fn test_active_row_is_section_containing_page() {
+    let rows = outline_rows(&sample_outline(), &HashSet::new());
+    assert_eq!(active_outline_row(&rows, 0), Some(0));
+    assert_eq!(active_outline_row(&rows, 4), Some(2));
+    assert_eq!(active_outline_row(&rows, 20), Some(4));
+
+    // Entries without a page are never active
+    let rows = outline_rows(&[entry("Cover", None, vec![])], &HashSet::new());
+    assert_eq!(active_outline_row(&rows, 0), None);
+}
+
+#[test]
+fn test_cache_knows_which_pdfs_have_outlines() {
+    let cache = PdfOutlineCache::new();
+    let path = Path::new("/docs/paper.pdf");
+    assert!(cache.get(path).is_none());
+
+    cache.insert(path.to_path_buf(), sample_outline());
+    cache.insert("/docs/flat.pdf".into(), Vec::new());
+    assert!(cache.has_outline(path));
+    assert!(!cache.has_outline(Path::new("/docs/flat.pdf")));
+    assert!(!cache.has_pending());
+
+    cache.forget(path);
+    assert!(!cache.has_outline(path));
+}
+
+#[test]
+fn test_read_missing_pdf_outline_fails() {
+    assert!(read_pdf_outline(Path::new("/no/such/file.pdf")).is_err());
+}
@@ -0,0 +1,74 @@
+//! Unit tests for pdf pages module.
+
+use humanboard::pdf::{
+    PdfPage, PdfPageCache, export_pdf_page, placed_page_path, render_pdf_page, turn_page,
+};
+use humanboard::render::pdf_controls::page_indicator;
+use std::path::{Path, PathBuf};
+use tempfile::tempdir;
+
+#[test]
+fn test_turn_page_stays_within_document() {
+    assert_eq!(turn_page(0, 1, 5), 1);
+    assert_eq!(turn_page(3, -1, 5), 2);
+    assert_eq!(turn_page(0, -1, 5), 0);
+    assert_eq!(turn_page(4, 1, 5), 4);
+    // A page past the end, e.g. after the file shrank, comes back in range
+    assert_eq!(turn_page(9, -1, 5), 4);
+    assert_eq!(turn_page(0, 1, 0), 0);
+}
+
+#[test]
+fn test_page_count_known_from_any_rendered_page() {
+    let cache = PdfPageCache::new();
+    let path = Path::new("/docs/report.pdf");
+    assert_eq!(cache.page_count(path), None);
+
+    cache.insert(
+        path.to_path_buf(),
+        2,
+        PdfPage {
+            image: PathBuf::from("/tmp/report_2.png"),
+            page_count: 7,
+        },
+    );
+    assert_eq!(cache.page_count(path), Some(7));
+    assert_eq!(cache.page_count(Path::new("/docs/other.pdf")), None);
+    assert!(!cache.has_pending());
+
+    cache.clear();
+    assert_eq!(cache.page_count(path), None);
+}
+
+#[test]
+fn test_render_missing_pdf_fails() {
+    assert!(render_pdf_page(Path::new("/no/such/file.pdf"), 0).is_err());
+}
+
+#[test]
+fn test_export_missing_pdf_writes_nothing() {
+    let dir = tempdir().unwrap();
+    let dest = dir.path().join("page.png");
+    assert!(export_pdf_page(Path::new("/no/such/file.pdf"), 0, &dest).is_err());
+    assert!(!dest.exists());
+}
+
+#[test]
+fn test_placed_page_path_skips_taken_names() {
+    let dir = tempdir().unwrap();
+    let pdf = Path::new("/docs/report.pdf");
+    let first = placed_page_path(dir.path(), pdf, 2);
+    assert_eq!(first, dir.path().join("report-p3.png"));
+
+    std::fs::write(&first, b"png").unwrap();
+    assert_eq!(
+        placed_page_path(dir.path(), pdf, 2),
+        dir.path().join("report-p3-2.png")
+    );
+}
+
+#[test]
+fn test_page_indicator_counts_from_one() {
+    assert_eq!(page_indicator(0, 12), "1 / 12");
+    assert_eq!(page_indicator(2, 12), "3 / 12");
+}
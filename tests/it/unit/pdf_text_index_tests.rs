@@ -0,0 +1,89 @@
+//! Unit tests for pdf text index module.
+
+use humanboard::pdf::{PdfTextEntry, PdfTextIndex, entry_path, find_in_pages, snippet};
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+use tempfile::tempdir;
+
+fn entry(path: &str, pages: &[&str], boards: &[&str]) -> PdfTextEntry {
+    PdfTextEntry {
+        path: PathBuf::from(path),
+        key: "key".to_string(),
+        pages: pages.iter().map(|page| page.to_string()).collect(),
+        boards: boards.iter().map(|board| board.to_string()).collect(),
+    }
+}
+
+#[test]
+fn test_find_in_pages_is_case_insensitive() {
+    let pages = vec![
+        "Quarterly report".to_string(),
+        String::new(),
+        "The report covers the REPORT period".to_string(),
+    ];
+    assert_eq!(
+        find_in_pages(&pages, "report"),
+        vec![(0, 10), (2, 4), (2, 22)]
+    );
+    assert!(find_in_pages(&pages, "").is_empty());
+    assert!(find_in_pages(&pages, "missing").is_empty());
+}
+
+#[test]
+fn test_snippet_keeps_context_on_one_line() {
+    assert_eq!(snippet("short\ntext here", 6, 4), "short text here");
+
+    let long = format!("{}needle{}", "a ".repeat(40), " b".repeat(40));
+    let found = snippet(&long, 80, 6);
+    assert!(found.starts_with('…') && found.ends_with('…'));
+    assert!(found.contains("needle"));
+
+    // Offsets inside a multi-byte character don't panic
+    assert_eq!(snippet("héllo", 2, 1), "héllo");
+}
+
+#[test]
+fn test_search_finds_first_match_per_pdf() {
+    let dir = tempdir().unwrap();
+    let index = PdfTextIndex::new(dir.path().to_path_buf());
+    index.insert(entry(
+        "/docs/a.pdf",
+        &["intro", "budget for 2024"],
+        &["board1"],
+    ));
+    index.insert(entry("/docs/b.pdf", &["no match"], &["board2"]));
+
+    let matches = index.search("Budget");
+    assert_eq!(matches.len(), 1);
+    assert_eq!(matches[0].path, PathBuf::from("/docs/a.pdf"));
+    assert_eq!(matches[0].page, 1);
+    assert_eq!(matches[0].snippet, "budget for 2024");
+    assert_eq!(matches[0].boards, BTreeSet::from(["board1".to_string()]));
+    assert!(index.search("  ").is_empty());
+
+    assert_eq!(
+        index.find_in_pdf(Path::new("/docs/a.pdf"), "o"),
+        Some(vec![(0, 4), (1, 8)])
+    );
+    assert_eq!(index.find_in_pdf(Path::new("/docs/c.pdf"), "o"), None);
+
+    assert_eq!(index.page_count(Path::new("/docs/a.pdf")), Some(2));
+    assert_eq!(index.page_count(Path::new("/docs/c.pdf")), None);
+}
+
+#[test]
+fn test_entries_saved_until_on_no_board() {
+    let dir = tempdir().unwrap();
+    let index = PdfTextIndex::new(dir.path().to_path_buf());
+    index.insert(entry("/docs/a.pdf", &["text"], &["board1", "board2"]));
+    let saved = entry_path(dir.path(), Path::new("/docs/a.pdf"));
+    assert!(saved.exists());
+
+    index.forget_board("board1");
+    assert_eq!(index.search("text")[0].boards.len(), 1);
+    assert!(saved.exists());
+
+    index.forget_board("board2");
+    assert!(index.search("text").is_empty());
+    assert!(!saved.exists());
+}
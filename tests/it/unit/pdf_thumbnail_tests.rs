@@ -0,0 +1,88 @@
+//! Unit tests for pdf thumbnail module.
+
+use humanboard::background::BackgroundExecutor;
+use humanboard::image_cache::FileStamp;
+use humanboard::pdf::{
+    PdfPage, PdfPageCache, PdfThumbnailCache, generate_pdf_thumbnail, thumbnail_path,
+};
+use std::path::{Path, PathBuf};
+use tempfile::tempdir;
+
+#[test]
+fn test_thumbnail_path_changes_when_file_changes() {
+    let dir = tempdir().unwrap();
+    let pdf = dir.path().join("report.pdf");
+    std::fs::write(&pdf, b"%PDF-1.4\n%%EOF\n").unwrap();
+    let before = thumbnail_path(dir.path(), &pdf, &FileStamp::of(&pdf).unwrap());
+    assert_eq!(
+        before,
+        thumbnail_path(dir.path(), &pdf, &FileStamp::of(&pdf).unwrap())
+    );
+    assert!(before.to_string_lossy().ends_with("_thumb.png"));
+
+    std::fs::write(&pdf, b"%PDF-1.4\n% edited\n%%EOF\n").unwrap();
+    let after = thumbnail_path(dir.path(), &pdf, &FileStamp::of(&pdf).unwrap());
+    assert_ne!(before, after);
+}
+
+#[test]
+fn test_generate_thumbnail_of_missing_pdf() {
+    assert_eq!(generate_pdf_thumbnail("/nonexistent/report.pdf"), None);
+}
+
+#[test]
+fn test_thumbnail_cache_returns_thumbnail_of_unchanged_file() {
+    let dir = tempdir().unwrap();
+    let pdf = dir.path().join("report.pdf");
+    std::fs::write(&pdf, b"%PDF-1.4\n%%EOF\n").unwrap();
+    let thumbnail = dir.path().join("report_thumb.png");
+    let executor = BackgroundExecutor::new(1);
+
+    let cache = PdfThumbnailCache::new(dir.path().to_path_buf());
+    cache.insert(pdf.clone(), thumbnail.clone());
+    assert_eq!(cache.current(&pdf, &executor), Some(thumbnail));
+    assert!(!cache.has_pending());
+    assert!(cache.take_refreshed().is_empty());
+
+    // Missing files have no thumbnail
+    let missing = dir.path().join("missing.pdf");
+    assert_eq!(cache.current(&missing, &executor), None);
+    assert!(!cache.has_pending());
+}
+
+#[test]
+fn test_forgetting_pdf_pages_returns_their_images() {
+    let cache = PdfPageCache::new();
+    let report = Path::new("/docs/report.pdf");
+    let other = Path::new("/docs/other.pdf");
+    for page in 0..2 {
+        cache.insert(
+            report.to_path_buf(),
+            page,
+            PdfPage {
+                image: PathBuf::from(format!("/tmp/report_{}.png", page)),
+                page_count: 2,
+            },
+        );
+    }
+    cache.insert(
+        other.to_path_buf(),
+        0,
+        PdfPage {
+            image: PathBuf::from("/tmp/other_0.png"),
+            page_count: 1,
+        },
+    );
+
+    let mut images = cache.forget(report);
+    images.sort();
+    assert_eq!(
+        images,
+        vec![
+            PathBuf::from("/tmp/report_0.png"),
+            PathBuf::from("/tmp/report_1.png")
+        ]
+    );
+    assert_eq!(cache.page_count(report), None);
+    assert_eq!(cache.page_count(other), Some(1));
+}
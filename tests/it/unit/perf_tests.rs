@@ -77,3 +77,50 @@ fn test_operation_stats_recording() {
     // Average should be (5 + 10 + 15) / 3 = 10
     assert!((stats.average() - 10.0).abs() < 0.001);
 }
+
+#[test]
+fn test_chrome_trace_includes_frames_and_operations() {
+    let mut monitor = PerfMonitor::new();
+    monitor.begin_frame();
+    monitor.record_operation("render_items", 2.0, 1);
+    monitor.end_frame();
+
+    let trace = monitor.chrome_trace();
+    let events = trace["traceEvents"].as_array().unwrap();
+    assert_eq!(events.len(), 2);
+
+    let names: Vec<&str> = events.iter().map(|e| e["name"].as_str().unwrap()).collect();
+    assert!(names.contains(&"frame"));
+    assert!(names.contains(&"render_items"));
+    for event in events {
+        assert_eq!(event["ph"], "X");
+        assert!(event["ts"].as_f64().unwrap() >= 0.0);
+    }
+
+    let op = events.iter().find(|e| e["name"] == "render_items").unwrap();
+    assert!((op["dur"].as_f64().unwrap() - 2000.0).abs() < 0.001);
+    assert_eq!(op["args"]["depth"], 1);
+}
+
+#[test]
+fn test_export_trace_writes_json() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("trace.json");
+
+    let mut monitor = PerfMonitor::new();
+    monitor.record_operation("save", 1.5, 0);
+    assert_eq!(monitor.export_trace(&path).unwrap(), 1);
+
+    let json: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+    assert_eq!(json["traceEvents"][0]["name"], "save");
+}
+
+#[test]
+fn test_reset_clears_trace() {
+    let mut monitor = PerfMonitor::new();
+    monitor.record_operation("save", 1.5, 0);
+    monitor.reset();
+    let trace = monitor.chrome_trace();
+    assert!(trace["traceEvents"].as_array().unwrap().is_empty());
+}
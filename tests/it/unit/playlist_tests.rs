@@ -0,0 +1,85 @@
+//! Unit tests for playlist module.
+
+use humanboard::input::guides::Bounds;
+use humanboard::playlist::Playlist;
+use humanboard::webviews::PlaybackPosition;
+
+/// Three audio cards: 1 and 2 side by side, 3 below them
+fn layout() -> Vec<(u64, Bounds)> {
+    vec![
+        (3, (0.0, 300.0, 400.0, 100.0)),
+        (2, (500.0, 0.0, 400.0, 100.0)),
+        (1, (0.0, 0.0, 400.0, 100.0)),
+    ]
+}
+
+fn position(seconds: f64, playing: bool) -> PlaybackPosition {
+    PlaybackPosition {
+        seconds,
+        fraction: (seconds / 100.0) as f32,
+        playing,
+    }
+}
+
+#[test]
+fn test_playlist_follows_reading_order() {
+    let playlist = Playlist::new(&layout(), false).unwrap();
+    assert_eq!(playlist.order(), &[1, 2, 3]);
+    assert_eq!(playlist.current(), 1);
+    assert_eq!(playlist.progress(), (1, 3));
+}
+
+#[test]
+fn test_shuffled_playlist_keeps_every_item() {
+    let playlist = Playlist::new(&layout(), true).unwrap();
+    assert!(playlist.is_shuffled());
+    let mut order = playlist.order().to_vec();
+    order.sort();
+    assert_eq!(order, vec![1, 2, 3]);
+}
+
+#[test]
+fn test_empty_playlist() {
+    assert!(Playlist::new(&[], false).is_none());
+}
+
+#[test]
+fn test_playlist_advances_when_item_finishes() {
+    let mut playlist = Playlist::new(&layout(), false).unwrap();
+
+    // Reports before the item starts, or from other items, don't count
+    assert!(!playlist.track_position(1, &position(100.0, false)));
+    assert!(!playlist.track_position(2, &position(100.0, false)));
+    assert!(!playlist.is_started());
+
+    assert!(!playlist.track_position(1, &position(10.0, true)));
+    assert!(playlist.is_started());
+    // Pausing part way through waits
+    assert!(!playlist.track_position(1, &position(50.0, false)));
+    assert!(playlist.track_position(1, &position(100.0, false)));
+
+    assert_eq!(playlist.advance(), Some(2));
+    assert!(!playlist.is_started());
+    assert_eq!(playlist.advance(), Some(3));
+    assert_eq!(playlist.advance(), None);
+}
+
+#[test]
+fn test_playlist_drops_deleted_items() {
+    let mut playlist = Playlist::new(&layout(), false).unwrap();
+    playlist.advance();
+    playlist.track_position(2, &position(10.0, true));
+
+    // Removing an earlier item keeps the current one
+    assert!(playlist.retain(|id| id != 1));
+    assert_eq!(playlist.current(), 2);
+    assert_eq!(playlist.progress(), (1, 2));
+    assert!(playlist.is_started());
+
+    // Removing the current item moves on to the next
+    assert!(playlist.retain(|id| id != 2));
+    assert_eq!(playlist.current(), 3);
+    assert!(!playlist.is_started());
+
+    assert!(!playlist.retain(|_| false));
+}
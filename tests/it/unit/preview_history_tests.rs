@@ -0,0 +1,87 @@
+//! Unit tests for preview history module.
+
+use humanboard::preview_history::{MAX_NAV_HISTORY, NavHistory, NavLocation, NavPlace, NavTarget};
+use std::path::PathBuf;
+
+fn file(name: &str) -> NavLocation {
+    NavLocation {
+        target: NavTarget::File(PathBuf::from(name)),
+        place: None,
+    }
+}
+
+fn page(name: &str, page: usize) -> NavLocation {
+    NavLocation {
+        target: NavTarget::File(PathBuf::from(name)),
+        place: Some(NavPlace::Page(page)),
+    }
+}
+
+#[test]
+fn test_back_and_forward_retrace_visits() {
+    let mut history = NavHistory::default();
+    assert!(!history.can_go_back());
+    history.visit(file("a.md"));
+    history.visit(page("b.pdf", 0));
+
+    assert_eq!(history.back(Some(page("b.pdf", 4))), Some(page("b.pdf", 0)));
+    assert_eq!(history.back(Some(page("b.pdf", 0))), Some(file("a.md")));
+    assert!(!history.can_go_back());
+    assert_eq!(history.back(Some(file("a.md"))), None);
+
+    assert_eq!(history.forward(Some(file("a.md"))), Some(page("b.pdf", 0)));
+    assert_eq!(
+        history.forward(Some(page("b.pdf", 0))),
+        Some(page("b.pdf", 4))
+    );
+    assert!(!history.can_go_forward());
+}
+
+#[test]
+fn test_visit_drops_places_ahead() {
+    let mut history = NavHistory::default();
+    history.visit(file("a.md"));
+    history.back(Some(file("b.md")));
+    assert!(history.can_go_forward());
+
+    history.visit(file("a.md"));
+    assert!(!history.can_go_forward());
+    assert!(history.can_go_back());
+}
+
+#[test]
+fn test_repeated_and_current_places_are_skipped() {
+    let mut history = NavHistory::default();
+    history.visit(file("a.md"));
+    history.visit(file("a.md"));
+    history.visit(file("b.md"));
+
+    // Going back from b.md skips the entry for b.md itself
+    assert_eq!(history.back(Some(file("b.md"))), Some(file("a.md")));
+    assert!(!history.can_go_back());
+}
+
+#[test]
+fn test_back_without_current_remembers_nothing_ahead() {
+    let mut history = NavHistory::default();
+    history.visit(file("a.md"));
+    history.visit(file("b.md"));
+
+    assert_eq!(history.back(None), Some(file("b.md")));
+    assert!(!history.can_go_forward());
+    history.clear();
+    assert!(!history.can_go_back());
+}
+
+#[test]
+fn test_history_is_capped() {
+    let mut history = NavHistory::default();
+    for index in 0..MAX_NAV_HISTORY + 10 {
+        history.visit(page("doc.pdf", index));
+    }
+    let mut count = 0;
+    while history.back(None).is_some() {
+        count += 1;
+    }
+    assert_eq!(count, MAX_NAV_HISTORY);
+}
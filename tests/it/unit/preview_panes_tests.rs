@@ -0,0 +1,80 @@
+//! Unit tests for preview_panes module.
+
+use humanboard::app::{PaneTree, SplitDropZone};
+
+/// Panes 0 and 1 side by side, pane 1 split into 1 over 2
+fn three_panes() -> PaneTree {
+    let mut tree = PaneTree::default();
+    assert!(tree.split(0, 1, false, false));
+    assert!(tree.split(1, 2, true, false));
+    tree
+}
+
+#[test]
+fn test_split_places_new_pane_beside() {
+    let mut tree = PaneTree::default();
+    assert!(tree.split(0, 1, false, true));
+    assert_eq!(tree.panes(), vec![1, 0]);
+
+    // Panes not in the tree can't be split
+    assert!(!tree.split(5, 2, false, false));
+    assert_eq!(three_panes().panes(), vec![0, 1, 2]);
+}
+
+#[test]
+fn test_remove_fills_split_and_renumbers() {
+    let mut tree = three_panes();
+    tree.remove(1);
+    assert_eq!(tree.panes(), vec![0, 1]);
+    assert_eq!(tree.parent_horizontal(1), Some(false));
+
+    let mut tree = three_panes();
+    tree.remove(0);
+    assert_eq!(tree.panes(), vec![0, 1]);
+    assert_eq!(tree.parent_horizontal(0), Some(true));
+
+    tree.remove(1);
+    assert_eq!(tree, PaneTree::Pane(0));
+}
+
+#[test]
+fn test_pane_bounds_divide_by_ratio() {
+    let mut tree = three_panes();
+    tree.set_ratio(&[], 0.25);
+    let bounds = tree.pane_bounds((0.0, 0.0, 406.0, 206.0), 6.0);
+    assert_eq!(
+        bounds,
+        vec![
+            (0, (0.0, 0.0, 100.0, 206.0)),
+            (1, (106.0, 0.0, 300.0, 100.0)),
+            (2, (106.0, 106.0, 300.0, 100.0)),
+        ]
+    );
+    assert_eq!(
+        tree.split_bounds(&[true], (0.0, 0.0, 406.0, 206.0), 6.0),
+        Some((106.0, 0.0, 300.0, 206.0))
+    );
+    assert_eq!(
+        tree.split_bounds(&[false], (0.0, 0.0, 406.0, 206.0), 6.0),
+        None
+    );
+}
+
+#[test]
+fn test_set_ratio_keeps_parts_visible() {
+    let mut tree = three_panes();
+    tree.set_ratio(&[true], 0.95);
+    match tree.part(&[true]) {
+        Some(PaneTree::Split { ratio, .. }) => assert_eq!(*ratio, 0.8),
+        other => panic!("expected a split, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_edge_pane_follows_side() {
+    let tree = three_panes();
+    assert_eq!(tree.edge_pane(SplitDropZone::Left), 0);
+    assert_eq!(tree.edge_pane(SplitDropZone::Right), 1);
+    assert_eq!(tree.edge_pane(SplitDropZone::Top), 0);
+    assert_eq!(PaneTree::default().edge_pane(SplitDropZone::Bottom), 0);
+}
@@ -0,0 +1,165 @@
+//! Unit tests for preview_session module.
+
+use humanboard::app::PaneTree;
+use humanboard::preview_session::{PreviewSession, SessionPane, SessionTab, SessionTabSource};
+use humanboard::text_diff::DiffBase;
+use std::path::PathBuf;
+
+fn file(name: &str) -> SessionTab {
+    SessionTab {
+        source: SessionTabSource::File(PathBuf::from(format!("/docs/{}", name))),
+        pinned: false,
+        preview: false,
+        page: None,
+    }
+}
+
+fn session(tabs: &[&str], right_tabs: &[&str]) -> PreviewSession {
+    PreviewSession {
+        tabs: tabs.iter().map(|name| file(name)).collect(),
+        active_tab: 0,
+        right_tabs: right_tabs.iter().map(|name| file(name)).collect(),
+        right_active_tab: 0,
+        below_canvas: false,
+        size: 0.4,
+        panes_stacked: false,
+        pane_ratio: 0.5,
+        right_focused: false,
+        more_panes: Vec::new(),
+        layout: None,
+    }
+}
+
+/// Keep every tab but those named in `gone`
+fn without(gone: &'static [&'static str]) -> impl Fn(&SessionTab) -> bool {
+    move |tab| match &tab.source {
+        SessionTabSource::File(path) => !gone.iter().any(|name| path.ends_with(name)),
+        SessionTabSource::Table { .. } | SessionTabSource::Diff { .. } => true,
+    }
+}
+
+#[test]
+fn test_retain_keeps_active_tab() {
+    let mut saved = session(&["a.pdf", "b.md", "c.rs", "d.pdf"], &[]);
+    saved.active_tab = 2;
+
+    let restored = saved.retain(without(&["a.pdf"])).unwrap();
+    assert_eq!(restored.tabs.len(), 3);
+    assert_eq!(restored.tabs[restored.active_tab], file("c.rs"));
+}
+
+#[test]
+fn test_retain_moves_dropped_active_tab_to_previous() {
+    let mut saved = session(&["a.pdf", "b.md", "c.rs"], &[]);
+    saved.active_tab = 1;
+    let restored = saved.retain(without(&["b.md"])).unwrap();
+    assert_eq!(restored.tabs[restored.active_tab], file("a.pdf"));
+
+    let mut saved = session(&["a.pdf", "b.md"], &[]);
+    saved.active_tab = 0;
+    let restored = saved.retain(without(&["a.pdf"])).unwrap();
+    assert_eq!(restored.active_tab, 0);
+}
+
+#[test]
+fn test_retain_closes_empty_panes() {
+    let mut saved = session(&["a.pdf"], &["b.md", "c.rs"]);
+    saved.right_active_tab = 1;
+    saved.right_focused = true;
+    assert!(saved.is_split());
+
+    // Left pane gone: the right pane's tabs take its place
+    let restored = saved.clone().retain(without(&["a.pdf"])).unwrap();
+    assert!(!restored.is_split());
+    assert!(!restored.right_focused);
+    assert_eq!(restored.tabs[restored.active_tab], file("c.rs"));
+
+    // Right pane gone: the split closes
+    let restored = saved.clone().retain(without(&["b.md", "c.rs"])).unwrap();
+    assert!(!restored.is_split());
+    assert_eq!(restored.tabs, vec![file("a.pdf")]);
+
+    // Nothing left: no panel at all
+    assert!(saved.retain(|_| false).is_none());
+}
+
+#[test]
+fn test_retain_closes_panes_of_split_tree() {
+    // Left pane beside the right one, which is stacked on a third
+    let mut layout = PaneTree::default();
+    layout.split(0, 1, false, false);
+    layout.split(1, 2, true, false);
+    let pane = |name: &str, focused: bool| SessionPane {
+        tabs: vec![file(name)],
+        active_tab: 0,
+        focused,
+    };
+    let saved = PreviewSession::from_panes(
+        vec![
+            pane("a.pdf", false),
+            pane("b.md", false),
+            pane("c.rs", true),
+        ],
+        layout.clone(),
+        false,
+        0.4,
+    );
+    assert_eq!(saved.panes().len(), 3);
+    assert_eq!(saved.layout(), layout);
+
+    let json = serde_json::to_string(&saved).unwrap();
+    let loaded: PreviewSession = serde_json::from_str(&json).unwrap();
+    assert_eq!(loaded, saved);
+
+    // The third pane fills the place of the right one
+    let restored = saved.retain(without(&["b.md"])).unwrap();
+    assert_eq!(restored.panes().len(), 2);
+    assert_eq!(restored.layout, None);
+    assert!(!restored.panes_stacked);
+    assert!(restored.right_focused);
+    assert_eq!(restored.right_tabs, vec![file("c.rs")]);
+}
+
+#[test]
+fn test_retain_clamps_sizes() {
+    let mut saved = session(&["a.pdf"], &[]);
+    saved.size = 1.5;
+    saved.pane_ratio = 0.0;
+
+    let restored = saved.retain(|_| true).unwrap();
+    assert_eq!(restored.size, 0.8);
+    assert_eq!(restored.pane_ratio, 0.2);
+}
+
+#[test]
+fn test_session_round_trips_and_fills_defaults() {
+    let mut saved = session(&["a.pdf"], &["b.md"]);
+    saved.tabs[0].page = Some(12);
+    saved.tabs[0].pinned = true;
+    saved.right_tabs.push(SessionTab {
+        source: SessionTabSource::Table {
+            data_source_id: 3,
+            name: "sales.csv".to_string(),
+        },
+        pinned: false,
+        preview: true,
+        page: None,
+    });
+    saved.right_tabs.push(SessionTab {
+        source: SessionTabSource::Diff {
+            path: PathBuf::from("/src/main.rs"),
+            base: DiffBase::Snapshot,
+        },
+        pinned: false,
+        preview: false,
+        page: None,
+    });
+
+    let json = serde_json::to_string(&saved).unwrap();
+    let loaded: PreviewSession = serde_json::from_str(&json).unwrap();
+    assert_eq!(loaded, saved);
+
+    let minimal: PreviewSession =
+        serde_json::from_str(r#"{"tabs":[{"source":{"file":"/docs/a.pdf"}}],"size":0.4}"#).unwrap();
+    assert_eq!(minimal, session(&["a.pdf"], &[]));
+}
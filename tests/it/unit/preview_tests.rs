@@ -0,0 +1,223 @@
+//! Unit tests for preview module.
+
+use humanboard::app::TabMeta;
+use humanboard::image_cache::FileStamp;
+use humanboard::preview::{
+    CANVAS_EDGE_DROP_SIZE, DiskChange, PreviewTab, SplitDirection, SplitDropZone,
+    canvas_edge_drop_zone, closable_tabs, disk_change, in_preview_area, index_after_move,
+    insert_tab, insert_tab_at, move_tab, toggle_tab_pinned,
+};
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime};
+
+fn tab(name: &str, is_pinned: bool) -> PreviewTab {
+    PreviewTab::Pdf {
+        path: PathBuf::from(format!("/docs/{}.pdf", name)),
+        webview: None,
+        meta: TabMeta {
+            is_preview: false,
+            is_pinned,
+            zoom: None,
+        },
+    }
+}
+
+fn names(tabs: &[PreviewTab]) -> Vec<String> {
+    tabs.iter()
+        .map(|t| t.title().trim_end_matches(".pdf").to_string())
+        .collect()
+}
+
+#[test]
+fn test_pinning_moves_tab_after_pinned_tabs() {
+    let mut tabs = vec![tab("a", true), tab("b", false), tab("c", false)];
+    let mut active = 1;
+
+    toggle_tab_pinned(&mut tabs, &mut active, 2);
+    assert_eq!(names(&tabs), ["a", "c", "b"]);
+    assert!(tabs[1].is_pinned());
+    // The active tab is still "b"
+    assert_eq!(active, 2);
+}
+
+#[test]
+fn test_unpinning_moves_tab_after_pinned_tabs() {
+    let mut tabs = vec![tab("a", true), tab("b", true), tab("c", false)];
+    let mut active = 0;
+
+    toggle_tab_pinned(&mut tabs, &mut active, 0);
+    assert_eq!(names(&tabs), ["b", "a", "c"]);
+    assert!(!tabs[1].is_pinned());
+    assert_eq!(active, 1);
+
+    // Out of range does nothing
+    toggle_tab_pinned(&mut tabs, &mut active, 9);
+    assert_eq!(names(&tabs), ["b", "a", "c"]);
+}
+
+#[test]
+fn test_inserted_pinned_tab_stays_left_most() {
+    let mut tabs = vec![tab("a", true), tab("b", false)];
+    let mut active = 1;
+
+    assert_eq!(insert_tab(&mut tabs, &mut active, tab("c", true)), 1);
+    assert_eq!(names(&tabs), ["a", "c", "b"]);
+    assert_eq!(active, 2);
+
+    assert_eq!(insert_tab(&mut tabs, &mut active, tab("d", false)), 3);
+    assert_eq!(active, 2);
+}
+
+#[test]
+fn test_index_after_move() {
+    assert_eq!(index_after_move(2, 2, 0), 0);
+    assert_eq!(index_after_move(1, 0, 3), 0);
+    assert_eq!(index_after_move(1, 3, 0), 2);
+    assert_eq!(index_after_move(4, 0, 2), 4);
+}
+
+#[test]
+fn test_close_others_and_close_all_spare_pinned_tabs() {
+    let tabs = vec![
+        tab("a", true),
+        tab("b", false),
+        tab("c", false),
+        tab("d", false),
+    ];
+    assert_eq!(closable_tabs(&tabs, Some(2)), vec![3, 1]);
+    assert_eq!(closable_tabs(&tabs, None), vec![3, 2, 1]);
+    assert!(closable_tabs(&tabs[..1], None).is_empty());
+}
+
+#[test]
+fn test_tab_dropped_on_other_pane_goes_before_target() {
+    let mut tabs = vec![tab("a", true), tab("b", false), tab("c", false)];
+    let mut active = 2;
+
+    assert_eq!(insert_tab_at(&mut tabs, &mut active, tab("d", false), 2), 2);
+    assert_eq!(names(&tabs), ["a", "b", "d", "c"]);
+    assert_eq!(active, 3);
+
+    // Unpinned tabs can't go among the pinned ones, nor past the end
+    assert_eq!(insert_tab_at(&mut tabs, &mut active, tab("e", false), 0), 1);
+    assert_eq!(
+        insert_tab_at(&mut tabs, &mut active, tab("f", false), 99),
+        5
+    );
+    assert_eq!(names(&tabs), ["a", "e", "b", "d", "c", "f"]);
+
+    // Pinned tabs can't go after the unpinned ones
+    assert_eq!(insert_tab_at(&mut tabs, &mut active, tab("g", true), 4), 1);
+    assert_eq!(names(&tabs)[..3], ["a", "g", "e"]);
+}
+
+#[test]
+fn test_tab_dropped_into_empty_pane() {
+    let mut tabs = Vec::new();
+    let mut active = 0;
+
+    assert_eq!(insert_tab_at(&mut tabs, &mut active, tab("a", false), 3), 0);
+    assert_eq!(active, 0);
+}
+
+#[test]
+fn test_move_tab_within_pane() {
+    let mut tabs = vec![
+        tab("a", true),
+        tab("b", false),
+        tab("c", false),
+        tab("d", false),
+    ];
+    let mut active = 1;
+
+    assert_eq!(move_tab(&mut tabs, &mut active, 1, 3), 3);
+    assert_eq!(names(&tabs), ["a", "c", "d", "b"]);
+    assert_eq!(active, 3);
+
+    // Unpinned tabs stop after the pinned ones
+    assert_eq!(move_tab(&mut tabs, &mut active, 2, 0), 1);
+    assert_eq!(names(&tabs), ["a", "d", "c", "b"]);
+
+    // Pinned tabs stop before the unpinned ones
+    assert_eq!(move_tab(&mut tabs, &mut active, 0, 3), 0);
+    assert_eq!(names(&tabs), ["a", "d", "c", "b"]);
+}
+
+#[test]
+fn test_canvas_edge_drop_zone_beside_panel() {
+    let window = (1000.0, 800.0);
+
+    // Panel on the right half: the strip is just left of x = 500
+    let zone = canvas_edge_drop_zone((480.0, 300.0), window, SplitDirection::Vertical, 0.5);
+    assert_eq!(zone, Some(SplitDropZone::Left));
+    let far = 500.0 - CANVAS_EDGE_DROP_SIZE - 10.0;
+    assert_eq!(
+        canvas_edge_drop_zone((far, 300.0), window, SplitDirection::Vertical, 0.5),
+        None
+    );
+    assert!(!in_preview_area(
+        (480.0, 300.0),
+        window,
+        SplitDirection::Vertical,
+        0.5
+    ));
+    assert!(in_preview_area(
+        (520.0, 300.0),
+        window,
+        SplitDirection::Vertical,
+        0.5
+    ));
+
+    // Panel on the bottom quarter: the strip is just above y = 600
+    let zone = canvas_edge_drop_zone((100.0, 590.0), window, SplitDirection::Horizontal, 0.25);
+    assert_eq!(zone, Some(SplitDropZone::Top));
+    assert_eq!(
+        canvas_edge_drop_zone((100.0, 100.0), window, SplitDirection::Horizontal, 0.25),
+        None
+    );
+}
+
+fn stamp(secs: u64, len: u64) -> Option<FileStamp> {
+    Some(FileStamp {
+        modified: Some(SystemTime::UNIX_EPOCH + Duration::from_secs(secs)),
+        len,
+    })
+}
+
+#[test]
+fn test_disk_change_unchanged_file() {
+    assert_eq!(
+        disk_change(stamp(10, 5), stamp(10, 5), false),
+        DiskChange::Unchanged
+    );
+    assert_eq!(
+        disk_change(stamp(10, 5), stamp(10, 5), true),
+        DiskChange::Unchanged
+    );
+}
+
+#[test]
+fn test_disk_change_reloads_clean_tab() {
+    assert_eq!(
+        disk_change(stamp(10, 5), stamp(20, 5), false),
+        DiskChange::Reload
+    );
+    assert_eq!(disk_change(None, stamp(20, 5), false), DiskChange::Reload);
+}
+
+#[test]
+fn test_disk_change_conflicts_with_unsaved_edits() {
+    assert_eq!(
+        disk_change(stamp(10, 5), stamp(10, 8), true),
+        DiskChange::Conflict
+    );
+}
+
+#[test]
+fn test_disk_change_ignores_unreadable_file() {
+    assert_eq!(disk_change(stamp(10, 5), None, true), DiskChange::Unchanged);
+    assert_eq!(
+        disk_change(stamp(10, 5), None, false),
+        DiskChange::Unchanged
+    );
+}
@@ -0,0 +1,88 @@
+//! Unit tests for progressive media loading.
+
+use humanboard::progressive_load::{LoadKind, ProgressiveLoader};
+
+#[test]
+fn test_first_item_each_frame_always_loads() {
+    let mut loader = ProgressiveLoader::with_budget_ms(0.0);
+    loader.begin_frame();
+    assert!(loader.try_load(1, LoadKind::WebView));
+    assert!(loader.is_loaded(1));
+}
+
+#[test]
+fn test_over_budget_items_wait_for_next_frame() {
+    let mut loader = ProgressiveLoader::with_budget_ms(0.0);
+    loader.begin_frame();
+    assert!(loader.try_load(1, LoadKind::Image));
+    assert!(!loader.try_load(2, LoadKind::Image));
+    assert!(loader.has_deferred());
+    assert!(!loader.is_loaded(2));
+
+    loader.begin_frame();
+    assert!(!loader.has_deferred());
+    assert!(loader.try_load(2, LoadKind::Image));
+}
+
+#[test]
+fn test_loaded_items_stay_loaded() {
+    let mut loader = ProgressiveLoader::with_budget_ms(0.0);
+    loader.begin_frame();
+    loader.try_load(1, LoadKind::Image);
+
+    loader.begin_frame();
+    loader.try_load(2, LoadKind::Image);
+    assert!(loader.try_load(1, LoadKind::Image));
+    assert!(!loader.has_deferred());
+}
+
+#[test]
+fn test_budget_limits_items_per_frame() {
+    let mut loader = ProgressiveLoader::with_budget_ms(1000.0);
+    loader.begin_frame();
+    let started = (0..1000)
+        .filter(|&id| loader.try_load(id, LoadKind::PdfThumbnail))
+        .count();
+    let per_frame = (1000.0 / LoadKind::PdfThumbnail.default_estimate_ms()) as usize;
+    assert!(started > 1);
+    assert!(started <= per_frame);
+    assert_eq!(loader.started_this_frame(), started);
+}
+
+#[test]
+fn test_webview_estimate_follows_measurements() {
+    let mut loader = ProgressiveLoader::new();
+    let initial = loader.estimate_ms(LoadKind::WebView);
+    for _ in 0..50 {
+        loader.record(LoadKind::WebView, 40.0);
+    }
+    let estimate = loader.estimate_ms(LoadKind::WebView);
+    assert!(estimate > initial);
+    assert!((estimate - 40.0).abs() < 1.0);
+}
+
+#[test]
+fn test_image_estimate_is_fixed() {
+    let mut loader = ProgressiveLoader::new();
+    loader.record(LoadKind::Image, 100.0);
+    assert_eq!(
+        loader.estimate_ms(LoadKind::Image),
+        LoadKind::Image.default_estimate_ms()
+    );
+}
+
+#[test]
+fn test_forget_and_clear() {
+    let mut loader = ProgressiveLoader::new();
+    loader.begin_frame();
+    loader.try_load(1, LoadKind::Image);
+    loader.try_load(2, LoadKind::Image);
+    assert_eq!(loader.loaded_count(), 2);
+
+    loader.forget(1);
+    assert!(!loader.is_loaded(1));
+    assert!(loader.is_loaded(2));
+
+    loader.clear();
+    assert_eq!(loader.loaded_count(), 0);
+}
@@ -0,0 +1,131 @@
+//! Unit tests for quick_open module.
+
+use humanboard::quick_open::{
+    FileIndex, IndexedFile, MAX_SCAN_DEPTH, matching_files, quick_open_folders, scan_folders,
+};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc;
+use tempfile::tempdir;
+
+fn touch(path: &Path) {
+    fs::create_dir_all(path.parent().unwrap()).unwrap();
+    fs::write(path, "").unwrap();
+}
+
+fn indexed(display: &str) -> IndexedFile {
+    IndexedFile {
+        path: PathBuf::from("/files").join(display),
+        display: display.to_string(),
+    }
+}
+
+fn displays(files: &[IndexedFile]) -> Vec<&str> {
+    files.iter().map(|file| file.display.as_str()).collect()
+}
+
+#[test]
+fn test_scan_lists_files_breadth_first_under_the_folder_name() {
+    let dir = tempdir().unwrap();
+    let root = dir.path().join("Documents");
+    touch(&root.join("b.txt"));
+    touch(&root.join("a.png"));
+    touch(&root.join("notes").join("plan.md"));
+
+    let files = scan_folders(&[root.clone()], 100);
+    let sep = std::path::MAIN_SEPARATOR;
+    assert_eq!(
+        displays(&files),
+        vec![
+            format!("Documents{sep}a.png"),
+            format!("Documents{sep}b.txt"),
+            format!("Documents{sep}notes{sep}plan.md"),
+        ]
+    );
+    assert_eq!(files[2].path, root.join("notes").join("plan.md"));
+}
+
+#[test]
+fn test_scan_skips_hidden_and_dependency_folders() {
+    let dir = tempdir().unwrap();
+    let root = dir.path().join("code");
+    touch(&root.join("main.rs"));
+    touch(&root.join(".secret"));
+    touch(&root.join(".git").join("HEAD"));
+    touch(&root.join("node_modules").join("lib.js"));
+    touch(&root.join("target").join("app"));
+
+    let files = scan_folders(&[root], 100);
+    assert_eq!(files.len(), 1);
+    assert!(files[0].display.ends_with("main.rs"));
+}
+
+#[test]
+fn test_scan_stops_at_the_depth_and_file_limits() {
+    let dir = tempdir().unwrap();
+    let root = dir.path().join("deep");
+    let mut path = root.clone();
+    for level in 0..=MAX_SCAN_DEPTH + 1 {
+        path = path.join(format!("level{}", level));
+        touch(&path.join("file.txt"));
+    }
+
+    let files = scan_folders(&[root.clone()], 100);
+    assert_eq!(files.len(), MAX_SCAN_DEPTH);
+    assert_eq!(scan_folders(&[root], 3).len(), 3);
+}
+
+#[test]
+fn test_scan_lists_nested_folders_once() {
+    let dir = tempdir().unwrap();
+    let root = dir.path().join("home");
+    touch(&root.join("Pictures").join("cat.jpg"));
+
+    let files = scan_folders(&[root.join("Pictures"), root], 100);
+    assert_eq!(files.len(), 1);
+}
+
+#[test]
+fn test_matching_prefers_names_then_short_paths() {
+    let files = vec![
+        indexed("Documents/reports/budget/summary.pdf"),
+        indexed("Documents/budget.xlsx"),
+        indexed("Downloads/old/budget.xlsx"),
+        indexed("Pictures/cat.jpg"),
+    ];
+
+    assert_eq!(matching_files(&files, "budget", 10), vec![1, 2, 0]);
+    assert_eq!(matching_files(&files, "budget", 1), vec![1]);
+    assert_eq!(matching_files(&files, "cat", 10), vec![3]);
+    assert!(matching_files(&files, "zebra", 10).is_empty());
+    assert_eq!(matching_files(&files, "  ", 2), vec![0, 1]);
+}
+
+#[test]
+fn test_configured_folders_expand_home() {
+    let folders = quick_open_folders(Some(vec!["~/Projects".to_string(), "/tmp".to_string()]));
+    if let Some(home) = dirs::home_dir() {
+        assert_eq!(folders[0], home.join("Projects"));
+    }
+    assert_eq!(folders[1], PathBuf::from("/tmp"));
+    assert!(quick_open_folders(Some(Vec::new())).is_empty());
+}
+
+#[test]
+fn test_file_index_takes_finished_scans() {
+    let mut index = FileIndex::default();
+    assert!(index.needs_scan());
+    assert!(!index.poll());
+
+    let (tx, rx) = mpsc::channel();
+    index.scan_rx = Some(rx);
+    assert!(index.is_scanning());
+    assert!(!index.needs_scan());
+    assert!(!index.poll());
+
+    tx.send(vec![indexed("Documents/a.txt")]).unwrap();
+    assert!(index.poll());
+    assert_eq!(index.files.len(), 1);
+    assert!(!index.is_scanning());
+    assert!(!index.needs_scan());
+}
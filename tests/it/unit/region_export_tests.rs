@@ -0,0 +1,205 @@
+//! Unit tests for exporting a region of the canvas.
+
+use crate::helpers::{board_to_state, empty_board, test_canvas_item_at};
+use humanboard::board::BoardState;
+use humanboard::canvas_snapshot::MAX_SNAPSHOT_DIMENSION;
+use humanboard::region_export::{
+    ExportFormat, PDF_PAGE_SIZE, drawn_region, export_region, export_size, items_region,
+    page_regions, render_pdf, render_svg,
+};
+use humanboard::types::{ArrowHead, CanvasItem, ItemContent, ShapeType};
+use std::collections::HashSet;
+use std::fs;
+use tempfile::tempdir;
+
+fn state_with(items: Vec<CanvasItem>) -> BoardState {
+    let mut state = board_to_state(&empty_board());
+    state.items = items;
+    state
+}
+
+fn arrow(id: u64, position: (f32, f32), end_offset: (f32, f32)) -> CanvasItem {
+    CanvasItem {
+        id,
+        position,
+        size: (end_offset.0.abs(), end_offset.1.abs()),
+        content: ItemContent::Arrow {
+            end_offset,
+            color: "#ff0000".to_string(),
+            thickness: 2.0,
+            head_style: ArrowHead::Arrow,
+        },
+        locked: false,
+        volume: None,
+    }
+}
+
+#[test]
+fn test_items_region_pads_all_items() {
+    let items = vec![
+        test_canvas_item_at(1, "a", (0.0, 0.0), (100.0, 50.0)),
+        test_canvas_item_at(2, "b", (200.0, 100.0), (50.0, 50.0)),
+    ];
+    assert_eq!(
+        items_region(&items, None, 10.0),
+        Some((-10.0, -10.0, 270.0, 170.0))
+    );
+}
+
+#[test]
+fn test_items_region_of_selection() {
+    let items = vec![
+        test_canvas_item_at(1, "a", (0.0, 0.0), (100.0, 50.0)),
+        test_canvas_item_at(2, "b", (200.0, 100.0), (50.0, 50.0)),
+    ];
+    let selected: HashSet<u64> = [2].into_iter().collect();
+    assert_eq!(
+        items_region(&items, Some(&selected), 0.0),
+        Some((200.0, 100.0, 50.0, 50.0))
+    );
+    assert_eq!(items_region(&items, Some(&HashSet::new()), 0.0), None);
+}
+
+#[test]
+fn test_items_region_reaches_arrow_ends() {
+    let items = vec![arrow(1, (100.0, 100.0), (-100.0, 50.0))];
+    assert_eq!(
+        items_region(&items, None, 0.0),
+        Some((0.0, 100.0, 100.0, 50.0))
+    );
+}
+
+#[test]
+fn test_drawn_region_from_any_corner() {
+    assert_eq!(
+        drawn_region((300.0, 200.0), (100.0, 50.0)),
+        (100.0, 50.0, 200.0, 150.0)
+    );
+}
+
+#[test]
+fn test_export_size_scales_and_caps() {
+    assert_eq!(export_size((0.0, 0.0, 400.0, 300.0), 2.0), (800, 600));
+    let (width, height) = export_size((0.0, 0.0, 10_000.0, 5_000.0), 4.0);
+    assert_eq!(width, MAX_SNAPSHOT_DIMENSION);
+    assert_eq!(height, MAX_SNAPSHOT_DIMENSION / 2);
+}
+
+#[test]
+fn test_page_regions_split_row_by_row() {
+    let pages = page_regions((0.0, 0.0, 1000.0, 700.0), PDF_PAGE_SIZE);
+    assert_eq!(pages.len(), 4);
+    assert_eq!(pages[0], (0.0, 0.0, 842.0, 595.0));
+    assert_eq!(pages[1], (842.0, 0.0, 158.0, 595.0));
+    assert_eq!(pages[2], (0.0, 595.0, 842.0, 105.0));
+}
+
+#[test]
+fn test_small_region_is_one_page() {
+    assert_eq!(
+        page_regions((50.0, 50.0, 300.0, 200.0), PDF_PAGE_SIZE).len(),
+        1
+    );
+}
+
+#[test]
+fn test_svg_draws_items_in_region_as_vectors() {
+    let state = state_with(vec![
+        CanvasItem {
+            id: 1,
+            position: (0.0, 0.0),
+            size: (100.0, 60.0),
+            content: ItemContent::Shape {
+                shape_type: ShapeType::Ellipse,
+                fill_color: Some("#00ff00".to_string()),
+                border_color: "#0000ff".to_string(),
+                border_width: 2.0,
+            },
+            locked: false,
+            volume: None,
+        },
+        CanvasItem {
+            id: 2,
+            position: (10.0, 80.0),
+            size: (200.0, 40.0),
+            content: ItemContent::TextBox {
+                text: "Fish & chips".to_string(),
+                font_size: 16.0,
+                color: "#ffffff".to_string(),
+            },
+            locked: false,
+            volume: None,
+        },
+        test_canvas_item_at(3, "far away", (5000.0, 5000.0), (100.0, 100.0)),
+    ]);
+    let svg = render_svg(&state, (0.0, 0.0, 400.0, 200.0), 2.0);
+
+    assert!(svg.starts_with("<svg"));
+    assert!(svg.contains("width=\"800\" height=\"400\""));
+    assert!(svg.contains("viewBox=\"0 0 400 200\""));
+    assert!(svg.contains("<ellipse cx=\"50\" cy=\"30\" rx=\"50\" ry=\"30\""));
+    assert!(svg.contains("fill=\"#00ff00\" stroke=\"#0000ff\""));
+    assert!(svg.contains("Fish &amp; chips"));
+    assert!(!svg.contains("far away"));
+    assert!(svg.trim_end().ends_with("</svg>"));
+}
+
+#[test]
+fn test_svg_draws_arrow_with_head() {
+    let state = state_with(vec![arrow(1, (0.0, 0.0), (100.0, 0.0))]);
+    let svg = render_svg(&state, (0.0, -50.0, 200.0, 100.0), 1.0);
+    assert!(svg.contains("<line x1=\"0\" y1=\"0\" x2=\"100\" y2=\"0\" stroke=\"#ff0000\""));
+    assert!(svg.contains("<polygon points=\"100,0"));
+}
+
+#[test]
+fn test_svg_wraps_text_to_the_item() {
+    let state = state_with(vec![test_canvas_item_at(
+        1,
+        "one two three four five six",
+        (0.0, 0.0),
+        (100.0, 100.0),
+    )]);
+    let svg = render_svg(&state, (0.0, 0.0, 100.0, 100.0), 1.0);
+    assert!(svg.contains("text-anchor=\"middle\""));
+    assert!(svg.matches("<tspan").count() > 1);
+    assert!(svg.contains("one two"));
+}
+
+#[test]
+fn test_pdf_has_one_page_per_region_page() {
+    let state = state_with(vec![test_canvas_item_at(
+        1,
+        "a",
+        (0.0, 0.0),
+        (100.0, 100.0),
+    )]);
+    let pdf = render_pdf(&state, (0.0, 0.0, 1000.0, 300.0), 1.0, 20.0).unwrap();
+    let text = String::from_utf8_lossy(&pdf);
+
+    assert!(pdf.starts_with(b"%PDF-1.4"));
+    assert!(text.contains("/Count 2"));
+    assert_eq!(text.matches("/Type /Page ").count(), 2);
+    assert_eq!(text.matches("/Filter /DCTDecode").count(), 2);
+    assert!(text.trim_end().ends_with("%%EOF"));
+}
+
+#[test]
+fn test_export_region_writes_each_format() {
+    let dir = tempdir().unwrap();
+    let state = state_with(vec![test_canvas_item_at(1, "a", (0.0, 0.0), (100.0, 50.0))]);
+    let region = (0.0, 0.0, 100.0, 50.0);
+
+    let png = dir.path().join("board.png");
+    export_region(&state, region, ExportFormat::Png, 4.0, 20.0, &png).unwrap();
+    let image = image::open(&png).unwrap();
+    assert_eq!((image.width(), image.height()), (400, 200));
+
+    let svg = dir.path().join("board.svg");
+    export_region(&state, region, ExportFormat::Svg, 1.0, 20.0, &svg).unwrap();
+    assert!(fs::read_to_string(&svg).unwrap().starts_with("<svg"));
+
+    let pdf = dir.path().join("board.pdf");
+    export_region(&state, region, ExportFormat::Pdf, 2.0, 20.0, &pdf).unwrap();
+    assert!(fs::read(&pdf).unwrap().starts_with(b"%PDF"));
+}
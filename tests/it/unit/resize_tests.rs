@@ -0,0 +1,80 @@
+//! Unit tests for single-item resize handles.
+
+use humanboard::input::resize::{ResizeHandle, handle_at, snap_resized_length};
+
+const BOUNDS: (f32, f32, f32, f32) = (100.0, 50.0, 200.0, 80.0);
+
+#[test]
+fn test_handle_positions() {
+    assert_eq!(ResizeHandle::TopLeft.position(BOUNDS), (100.0, 50.0));
+    assert_eq!(ResizeHandle::Top.position(BOUNDS), (200.0, 50.0));
+    assert_eq!(ResizeHandle::Right.position(BOUNDS), (300.0, 90.0));
+    assert_eq!(ResizeHandle::BottomRight.position(BOUNDS), (300.0, 130.0));
+    assert_eq!(ResizeHandle::Left.position(BOUNDS), (100.0, 90.0));
+}
+
+#[test]
+fn test_handle_at_finds_corners_and_edges() {
+    assert_eq!(
+        handle_at(BOUNDS, (302.0, 128.0), 5.0),
+        Some(ResizeHandle::BottomRight)
+    );
+    assert_eq!(
+        handle_at(BOUNDS, (198.0, 52.0), 5.0),
+        Some(ResizeHandle::Top)
+    );
+    assert_eq!(
+        handle_at(BOUNDS, (97.0, 91.0), 5.0),
+        Some(ResizeHandle::Left)
+    );
+    assert_eq!(handle_at(BOUNDS, (150.0, 90.0), 5.0), None);
+}
+
+#[test]
+fn test_handle_at_prefers_corners_on_tiny_items() {
+    // Every handle of a 4x4 item overlaps; the corner wins
+    let tiny = (0.0, 0.0, 4.0, 4.0);
+    assert_eq!(
+        handle_at(tiny, (0.0, 0.0), 5.0),
+        Some(ResizeHandle::TopLeft)
+    );
+}
+
+#[test]
+fn test_top_left_resize_keeps_bottom_right_fixed() {
+    let position = ResizeHandle::TopLeft.anchored_position(BOUNDS, (250.0, 100.0));
+    assert_eq!(position, (50.0, 30.0));
+}
+
+#[test]
+fn test_right_and_bottom_resize_keep_position() {
+    assert_eq!(
+        ResizeHandle::Right.anchored_position(BOUNDS, (250.0, 80.0)),
+        (100.0, 50.0)
+    );
+    assert_eq!(
+        ResizeHandle::BottomRight.anchored_position(BOUNDS, (250.0, 100.0)),
+        (100.0, 50.0)
+    );
+}
+
+#[test]
+fn test_edge_handles_change_one_side() {
+    assert_eq!(ResizeHandle::Top.signs(), (0.0, -1.0));
+    assert_eq!(ResizeHandle::Left.signs(), (-1.0, 0.0));
+    assert!(!ResizeHandle::Bottom.is_corner());
+    assert!(ResizeHandle::BottomLeft.is_corner());
+}
+
+#[test]
+fn test_snap_resized_length() {
+    // Right edge: 10 + 47 snaps to 60
+    assert_eq!(snap_resized_length(1.0, 10.0, 40.0, 47.0, 20.0, 10.0), 50.0);
+    // Left edge with the right edge fixed at 110: left at 63 snaps to 60
+    assert_eq!(
+        snap_resized_length(-1.0, 10.0, 100.0, 47.0, 20.0, 10.0),
+        50.0
+    );
+    // Untouched side
+    assert_eq!(snap_resized_length(0.0, 10.0, 40.0, 47.0, 20.0, 10.0), 47.0);
+}
@@ -0,0 +1,124 @@
+//! Unit tests for search_query module.
+
+use crate::helpers::{test_content_item, text_box_content};
+use humanboard::search_query::{
+    DateComparison, DateFilter, SearchQuery, item_kinds, item_tags, parse_date,
+};
+use humanboard::types::ItemContent;
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+fn day(date: &str) -> SystemTime {
+    let days = parse_date(date).unwrap();
+    UNIX_EPOCH + Duration::from_secs(days as u64 * 86_400 + 12 * 3600)
+}
+
+#[test]
+fn test_parse_splits_filters_from_text() {
+    let query = SearchQuery::parse("budget type:Image tag:#Research board:Q3 modified:>2024-01-01");
+    assert_eq!(query.text, "budget");
+    assert_eq!(query.types, vec!["image".to_string()]);
+    assert_eq!(query.tags, vec!["research".to_string()]);
+    assert_eq!(query.board.as_deref(), Some("q3"));
+    assert_eq!(
+        query.modified,
+        vec![DateFilter {
+            comparison: DateComparison::After,
+            day: parse_date("2024-01-01").unwrap(),
+        }]
+    );
+    assert!(query.has_filters());
+}
+
+#[test]
+fn test_parse_keeps_unknown_and_unreadable_operators_as_text() {
+    let query = SearchQuery::parse("http://example.com modified:yesterday tag:");
+    assert_eq!(query.text, "http://example.com modified:yesterday tag:");
+    assert!(!query.has_filters());
+    assert!(!SearchQuery::parse("plain words").has_filters());
+}
+
+#[test]
+fn test_parse_date_counts_days_since_epoch() {
+    assert_eq!(parse_date("1970-01-01"), Some(0));
+    assert_eq!(parse_date("1970-03-01"), Some(59));
+    assert_eq!(parse_date("2000-03-01"), Some(11_017));
+    assert_eq!(parse_date("2024-01-01"), Some(19_723));
+    assert_eq!(parse_date("2024-13-01"), None);
+    assert_eq!(parse_date("2024-01"), None);
+}
+
+#[test]
+fn test_date_filters_compare_by_day() {
+    let after = DateFilter::parse(">2024-01-01").unwrap();
+    let on_or_after = DateFilter::parse(">=2024-01-01").unwrap();
+    let before = DateFilter::parse("<2024-01-01").unwrap();
+    let on = DateFilter::parse("2024-01-01").unwrap();
+
+    assert!(!after.matches(day("2024-01-01")));
+    assert!(after.matches(day("2024-01-02")));
+    assert!(on_or_after.matches(day("2024-01-01")));
+    assert!(before.matches(day("2023-12-31")));
+    assert!(!before.matches(day("2024-01-01")));
+    assert!(on.matches(day("2024-01-01")));
+    assert!(!on.matches(day("2024-01-02")));
+}
+
+#[test]
+fn test_type_filter_keeps_any_listed_kind() {
+    let query = SearchQuery::parse("type:image,pdf");
+    let image = test_content_item(1, ItemContent::Image(PathBuf::from("a.png")));
+    let code = test_content_item(
+        2,
+        ItemContent::Code {
+            path: PathBuf::from("main.py"),
+            language: "Python".to_string(),
+            lines: None,
+        },
+    );
+    assert!(query.matches_item(&image, None));
+    assert!(!query.matches_item(&code, None));
+    assert!(SearchQuery::parse("type:python").matches_item(&code, None));
+    assert!(SearchQuery::parse("type:code").matches_item(&code, None));
+    assert!(item_kinds(&ItemContent::YouTube("abc".to_string())).contains(&"video".to_string()));
+}
+
+#[test]
+fn test_item_tags_are_hashtags_starting_words() {
+    let content = ItemContent::Markdown {
+        path: PathBuf::from("notes.md"),
+        title: "Notes".to_string(),
+        content: "# Heading\nSee #Research and #q3-plan, issue #42, a#b".to_string(),
+    };
+    assert_eq!(
+        item_tags(&content),
+        vec!["research".to_string(), "q3-plan".to_string()]
+    );
+    assert!(item_tags(&ItemContent::Image(PathBuf::from("a.png"))).is_empty());
+}
+
+#[test]
+fn test_tag_filters_need_every_tag() {
+    let both = test_content_item(1, text_box_content("#research #draft"));
+    let one = test_content_item(2, text_box_content("#research only"));
+    let query = SearchQuery::parse("tag:research tag:draft");
+    assert!(query.matches_item(&both, None));
+    assert!(!query.matches_item(&one, None));
+}
+
+#[test]
+fn test_modified_filter_needs_a_file_time() {
+    let image = test_content_item(1, ItemContent::Image(PathBuf::from("a.png")));
+    let query = SearchQuery::parse("modified:>=2024-01-01");
+    assert!(query.matches_item(&image, Some(day("2024-06-01"))));
+    assert!(!query.matches_item(&image, Some(day("2023-06-01"))));
+    assert!(!query.matches_item(&image, None));
+}
+
+#[test]
+fn test_board_filter_matches_part_of_name() {
+    let query = SearchQuery::parse("board:q3");
+    assert!(query.matches_board("Q3 Planning"));
+    assert!(!query.matches_board("Roadmap"));
+    assert!(SearchQuery::parse("anything").matches_board("Roadmap"));
+}
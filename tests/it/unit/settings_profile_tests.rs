@@ -0,0 +1,182 @@
+//! Unit tests for settings_profile module.
+
+use humanboard::canvas_background::CanvasBackground;
+use humanboard::error::SettingsError;
+use humanboard::settings_profile::{
+    BoardOverrides, PROFILE_VERSION, ProfilePaths, collect_profile, install_profile, match_boards,
+    read_profile, write_profile,
+};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+use tempfile::tempdir;
+
+fn paths(root: &Path) -> ProfilePaths {
+    ProfilePaths {
+        settings: root.join("settings.json"),
+        keymap: Some(root.join("keymap.json")),
+        themes: Some(root.join("themes")),
+    }
+}
+
+fn overrides(name: &str) -> BoardOverrides {
+    BoardOverrides {
+        name: name.to_string(),
+        background: Some(CanvasBackground::Lines),
+    }
+}
+
+#[test]
+fn test_profile_carries_settings_themes_and_keymap() {
+    let home = tempdir().unwrap();
+    let paths = paths(home.path());
+    fs::write(
+        &paths.settings,
+        r#"{"theme": "Ocean", "grid_size": 32.0, "onboarding_completed": true}"#,
+    )
+    .unwrap();
+    fs::write(paths.keymap.as_ref().unwrap(), r#"{"app:quit": "cmd-q"}"#).unwrap();
+    let themes = paths.themes.as_ref().unwrap();
+    fs::create_dir_all(themes).unwrap();
+    fs::write(
+        themes.join("ocean.json"),
+        r#"{"name": "Ocean", "themes": []}"#,
+    )
+    .unwrap();
+    fs::write(themes.join("notes.txt"), "not a theme").unwrap();
+
+    let boards = BTreeMap::from([("b1".to_string(), overrides("Plans"))]);
+    let profile = collect_profile(&paths, boards).unwrap();
+    assert_eq!(profile.version, PROFILE_VERSION);
+    assert_eq!(profile.settings.theme.as_deref(), Some("Ocean"));
+    assert_eq!(profile.settings.grid_size, Some(32.0));
+    assert_eq!(profile.settings.onboarding_completed, None);
+    assert_eq!(
+        profile.themes.keys().collect::<Vec<_>>(),
+        vec!["ocean.json"]
+    );
+    assert!(profile.keymap.is_some());
+
+    let file = home.path().join("profile.json");
+    write_profile(&profile, &file).unwrap();
+    let read = read_profile(&file).unwrap();
+    assert_eq!(read.themes, profile.themes);
+    assert_eq!(read.keymap, profile.keymap);
+    assert_eq!(read.boards["b1"], overrides("Plans"));
+}
+
+#[test]
+fn test_install_merges_settings_and_writes_files() {
+    let source = tempdir().unwrap();
+    let source_paths = paths(source.path());
+    fs::write(
+        &source_paths.settings,
+        r#"{"theme": "Ocean", "snap_to_grid": true}"#,
+    )
+    .unwrap();
+    fs::write(
+        source_paths.keymap.as_ref().unwrap(),
+        r#"{"app:quit": "cmd-q"}"#,
+    )
+    .unwrap();
+    let themes = source_paths.themes.as_ref().unwrap();
+    fs::create_dir_all(themes).unwrap();
+    fs::write(
+        themes.join("ocean.json"),
+        r#"{"name": "Ocean", "themes": []}"#,
+    )
+    .unwrap();
+    let profile = collect_profile(&source_paths, BTreeMap::new()).unwrap();
+
+    let target = tempdir().unwrap();
+    let target_paths = paths(target.path());
+    fs::write(
+        &target_paths.settings,
+        r#"{"theme": "Default Dark", "font": "Menlo", "onboarding_completed": true}"#,
+    )
+    .unwrap();
+
+    let installed = install_profile(&profile, &target_paths).unwrap();
+    assert_eq!(installed.themes, 1);
+    assert!(installed.keymap);
+
+    let settings = fs::read_to_string(&target_paths.settings).unwrap();
+    let settings: serde_json::Value = serde_json::from_str(&settings).unwrap();
+    assert_eq!(settings["theme"], "Ocean");
+    assert_eq!(settings["snap_to_grid"], true);
+    assert_eq!(settings["font"], "Menlo");
+    assert_eq!(settings["onboarding_completed"], true);
+    assert!(target.path().join("themes/ocean.json").exists());
+    assert!(target.path().join("keymap.json").exists());
+}
+
+#[test]
+fn test_install_only_writes_plain_theme_names() {
+    let dir = tempdir().unwrap();
+    let mut profile = collect_profile(&paths(dir.path()), BTreeMap::new()).unwrap();
+    for name in [
+        "../escape.json",
+        "sub/dir.json",
+        "theme.txt",
+        ".hidden.json",
+        "ok.json",
+    ] {
+        profile
+            .themes
+            .insert(name.to_string(), serde_json::json!({"themes": []}));
+    }
+
+    let target = tempdir().unwrap();
+    let installed = install_profile(&profile, &paths(target.path())).unwrap();
+    assert_eq!(installed.themes, 1);
+    assert!(!target.path().join("escape.json").exists());
+    assert_eq!(
+        fs::read_dir(target.path().join("themes")).unwrap().count(),
+        1
+    );
+}
+
+#[test]
+fn test_newer_profiles_are_refused() {
+    let dir = tempdir().unwrap();
+    let file = dir.path().join("profile.json");
+    fs::write(&file, format!(r#"{{"version": {}}}"#, PROFILE_VERSION + 1)).unwrap();
+    assert!(matches!(
+        read_profile(&file),
+        Err(SettingsError::InvalidValue(_))
+    ));
+
+    fs::write(&file, "not json").unwrap();
+    assert!(matches!(
+        read_profile(&file),
+        Err(SettingsError::ParseFailed { .. })
+    ));
+}
+
+#[test]
+fn test_match_boards_by_id_then_unique_name() {
+    let profile = BTreeMap::from([
+        ("same-id".to_string(), overrides("Renamed")),
+        ("old-id".to_string(), overrides("Plans")),
+        ("other-id".to_string(), overrides("Notes")),
+        ("gone".to_string(), overrides("Missing")),
+    ]);
+    let boards = vec![
+        ("same-id".to_string(), "Anything".to_string()),
+        ("new-id".to_string(), "Plans".to_string()),
+        ("n1".to_string(), "Notes".to_string()),
+        ("n2".to_string(), "Notes".to_string()),
+    ];
+
+    let matched: Vec<(String, String)> = match_boards(&profile, &boards)
+        .into_iter()
+        .map(|(id, board)| (id, board.name.clone()))
+        .collect();
+    assert_eq!(
+        matched,
+        vec![
+            ("new-id".to_string(), "Plans".to_string()),
+            ("same-id".to_string(), "Renamed".to_string()),
+        ]
+    );
+}
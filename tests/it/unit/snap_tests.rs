@@ -0,0 +1,27 @@
+//! Unit tests for snap-to-grid helpers.
+
+use humanboard::input::snap::{snap_length, snap_to_grid};
+
+#[test]
+fn test_snap_to_grid_rounds_to_nearest_line() {
+    assert_eq!(snap_to_grid(0.0, 20.0), 0.0);
+    assert_eq!(snap_to_grid(9.0, 20.0), 0.0);
+    assert_eq!(snap_to_grid(11.0, 20.0), 20.0);
+    assert_eq!(snap_to_grid(-11.0, 20.0), -20.0);
+    assert_eq!(snap_to_grid(147.0, 20.0), 140.0);
+}
+
+#[test]
+fn test_snap_length_puts_far_edge_on_grid() {
+    // Item at x=5 resized to 108 wide: right edge 113 snaps to 120
+    assert_eq!(snap_length(5.0, 108.0, 20.0, 10.0), 115.0);
+    // Already aligned spans are unchanged
+    assert_eq!(snap_length(0.0, 200.0, 20.0, 10.0), 200.0);
+}
+
+#[test]
+fn test_snap_length_respects_minimum() {
+    // Snapping down would go below the minimum, so use the next grid line
+    assert_eq!(snap_length(0.0, 45.0, 40.0, 50.0), 80.0);
+    assert_eq!(snap_length(15.0, 50.0, 40.0, 50.0), 65.0);
+}
@@ -37,6 +37,8 @@ fn snapshot_canvas_item_image() {
         position: (100.0, 200.0),
         size: (800.0, 600.0),
         content: ItemContent::Image(PathBuf::from("/path/to/image.png")),
+        locked: false,
+        volume: None,
     };
     insta::assert_json_snapshot!("canvas_item_image", item);
 }
@@ -48,6 +50,8 @@ fn snapshot_canvas_item_video() {
         position: (50.0, 100.0),
         size: (400.0, 300.0),
         content: ItemContent::Video(PathBuf::from("/path/to/video.mp4")),
+        locked: false,
+        volume: None,
     };
     insta::assert_json_snapshot!("canvas_item_video", item);
 }
@@ -59,6 +63,8 @@ fn snapshot_canvas_item_audio() {
         position: (0.0, 0.0),
         size: (320.0, 160.0),
         content: ItemContent::Audio(PathBuf::from("/music/song.mp3")),
+        locked: false,
+        volume: None,
     };
     insta::assert_json_snapshot!("canvas_item_audio", item);
 }
@@ -72,7 +78,10 @@ fn snapshot_canvas_item_pdf() {
         content: ItemContent::Pdf {
             path: PathBuf::from("/documents/report.pdf"),
             thumbnail: Some(PathBuf::from("/cache/report_thumb.png")),
+            page: 0,
         },
+        locked: false,
+        volume: None,
     };
     insta::assert_json_snapshot!("canvas_item_pdf", item);
 }
@@ -84,6 +93,8 @@ fn snapshot_canvas_item_text() {
         position: (10.0, 20.0),
         size: (300.0, 100.0),
         content: ItemContent::Text("Hello, Humanboard!".to_string()),
+        locked: false,
+        volume: None,
     };
     insta::assert_json_snapshot!("canvas_item_text", item);
 }
@@ -95,6 +106,8 @@ fn snapshot_canvas_item_link() {
         position: (150.0, 250.0),
         size: (300.0, 150.0),
         content: ItemContent::Link("https://github.com/humanboard-org".to_string()),
+        locked: false,
+        volume: None,
     };
     insta::assert_json_snapshot!("canvas_item_link", item);
 }
@@ -106,6 +119,8 @@ fn snapshot_canvas_item_youtube() {
         position: (200.0, 300.0),
         size: (560.0, 315.0),
         content: ItemContent::YouTube("dQw4w9WgXcQ".to_string()),
+        locked: false,
+        volume: None,
     };
     insta::assert_json_snapshot!("canvas_item_youtube", item);
 }
@@ -121,6 +136,8 @@ fn snapshot_canvas_item_markdown() {
             title: "README".to_string(),
             content: "# Hello\n\nThis is a test document.".to_string(),
         },
+        locked: false,
+        volume: None,
     };
     insta::assert_json_snapshot!("canvas_item_markdown", item);
 }
@@ -134,7 +151,10 @@ fn snapshot_canvas_item_code() {
         content: ItemContent::Code {
             path: PathBuf::from("/src/main.rs"),
             language: "rust".to_string(),
+            lines: None,
         },
+        locked: false,
+        volume: None,
     };
     insta::assert_json_snapshot!("canvas_item_code", item);
 }
@@ -150,6 +170,8 @@ fn snapshot_canvas_item_textbox() {
             font_size: 16.0,
             color: "#ffffff".to_string(),
         },
+        locked: false,
+        volume: None,
     };
     insta::assert_json_snapshot!("canvas_item_textbox", item);
 }
@@ -166,6 +188,8 @@ fn snapshot_canvas_item_arrow() {
             thickness: 3.0,
             head_style: ArrowHead::Arrow,
         },
+        locked: false,
+        volume: None,
     };
     insta::assert_json_snapshot!("canvas_item_arrow", item);
 }
@@ -182,6 +206,8 @@ fn snapshot_canvas_item_shape_rectangle() {
             border_color: "#ffffff".to_string(),
             border_width: 2.0,
         },
+        locked: false,
+        volume: None,
     };
     insta::assert_json_snapshot!("canvas_item_shape_rectangle", item);
 }
@@ -198,6 +224,8 @@ fn snapshot_canvas_item_shape_ellipse() {
             border_color: "#00ff00".to_string(),
             border_width: 4.0,
         },
+        locked: false,
+        volume: None,
     };
     insta::assert_json_snapshot!("canvas_item_shape_ellipse", item);
 }
@@ -213,6 +241,8 @@ fn snapshot_canvas_item_table() {
             show_headers: true,
             stripe: true,
         },
+        locked: false,
+        volume: None,
     };
     insta::assert_json_snapshot!("canvas_item_table", item);
 }
@@ -237,6 +267,8 @@ fn snapshot_canvas_item_chart() {
             source_item_id: Some(14),
             config,
         },
+        locked: false,
+        volume: None,
     };
     insta::assert_json_snapshot!("canvas_item_chart", item);
 }
@@ -441,6 +473,12 @@ fn snapshot_settings_content_partial() {
         onboarding_completed: Some(true),
         reduce_motion: Some("off".to_string()),
         high_contrast: None,
+        trash_retention_days: None,
+        manage_assets: None,
+        git_friendly_save: None,
+        media_memory_budget_mb: None,
+        quick_open_folders: None,
+        item_styles: None,
     };
     insta::assert_json_snapshot!("settings_content_partial", content);
 }
@@ -462,6 +500,12 @@ fn snapshot_settings_content_full() {
         onboarding_completed: Some(false),
         reduce_motion: Some("system".to_string()),
         high_contrast: Some(false),
+        trash_retention_days: Some(30),
+        manage_assets: Some(false),
+        git_friendly_save: Some(false),
+        media_memory_budget_mb: Some(1024),
+        quick_open_folders: None,
+        item_styles: None,
     };
     insta::assert_json_snapshot!("settings_content_full", content);
 }
@@ -480,6 +524,7 @@ fn snapshot_board_metadata() {
         updated_at: 1704153600, // 2024-01-02 00:00:00 UTC
         storage_location: StoredLocation::Default,
         deleted_at: None,
+        encrypted: false,
     };
     insta::assert_json_snapshot!("board_metadata", metadata);
 }
@@ -493,6 +538,7 @@ fn snapshot_board_metadata_deleted() {
         updated_at: 1704153600,
         storage_location: StoredLocation::ICloud,
         deleted_at: Some(1704240000), // 2024-01-03 00:00:00 UTC
+        encrypted: false,
     };
     insta::assert_json_snapshot!("board_metadata_deleted", metadata);
 }
@@ -532,18 +578,24 @@ fn snapshot_canvas_items_collection() {
                 font_size: 24.0,
                 color: "#ffffff".to_string(),
             },
+            locked: false,
+            volume: None,
         },
         CanvasItem {
             id: 2,
             position: (0.0, 120.0),
             size: (400.0, 300.0),
             content: ItemContent::Image(PathBuf::from("/images/diagram.png")),
+            locked: false,
+            volume: None,
         },
         CanvasItem {
             id: 3,
             position: (420.0, 120.0),
             size: (300.0, 150.0),
             content: ItemContent::Link("https://example.com".to_string()),
+            locked: false,
+            volume: None,
         },
     ];
     insta::assert_json_snapshot!("canvas_items_collection", items);
@@ -562,6 +614,7 @@ fn snapshot_type_labels() {
     let pdf = ItemContent::Pdf {
         path: PathBuf::new(),
         thumbnail: None,
+        page: 0,
     };
     let text = ItemContent::Text(String::new());
     let link = ItemContent::Link(String::new());
@@ -574,10 +627,12 @@ fn snapshot_type_labels() {
     let code_rust = ItemContent::Code {
         path: PathBuf::new(),
         language: "rust".to_string(),
+        lines: None,
     };
     let code_python = ItemContent::Code {
         path: PathBuf::new(),
         language: "python".to_string(),
+        lines: None,
     };
 
     let labels: Vec<(&str, &str)> = vec![
@@ -648,6 +703,7 @@ fn snapshot_default_sizes() {
             ItemContent::Pdf {
                 path: PathBuf::new(),
                 thumbnail: None,
+                page: 0,
             }
             .default_size(),
         ),
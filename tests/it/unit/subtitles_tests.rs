@@ -0,0 +1,58 @@
+//! Unit tests for subtitles module.
+
+use humanboard::subtitles::{find_sidecar, load_vtt, srt_to_vtt};
+use std::fs;
+use tempfile::tempdir;
+
+const SRT: &str = "1\r\n00:00:01,000 --> 00:00:04,500\r\nHello, world\r\n\r\n2\r\n00:00:05,000 --> 00:00:06,000\r\nBye\r\n";
+
+#[test]
+fn test_srt_to_vtt_converts_timings_only() {
+    let vtt = srt_to_vtt(SRT);
+    assert!(vtt.starts_with("WEBVTT\n\n1\n"));
+    assert!(vtt.contains("00:00:01.000 --> 00:00:04.500\nHello, world\n"));
+    assert!(!vtt.contains('\r'));
+}
+
+#[test]
+fn test_srt_to_vtt_strips_byte_order_mark() {
+    assert!(srt_to_vtt("\u{feff}1\n").starts_with("WEBVTT\n\n1\n"));
+}
+
+#[test]
+fn test_find_sidecar_prefers_exact_name_and_vtt() {
+    let dir = tempdir().unwrap();
+    let video = dir.path().join("lecture.mp4");
+    fs::write(&video, b"").unwrap();
+    assert_eq!(find_sidecar(&video), None);
+
+    fs::write(dir.path().join("lecture.en.srt"), SRT).unwrap();
+    assert_eq!(
+        find_sidecar(&video),
+        Some(dir.path().join("lecture.en.srt"))
+    );
+
+    fs::write(dir.path().join("lecture.srt"), SRT).unwrap();
+    assert_eq!(find_sidecar(&video), Some(dir.path().join("lecture.srt")));
+
+    fs::write(dir.path().join("lecture.vtt"), "WEBVTT\n").unwrap();
+    assert_eq!(find_sidecar(&video), Some(dir.path().join("lecture.vtt")));
+}
+
+#[test]
+fn test_find_sidecar_ignores_other_videos() {
+    let dir = tempdir().unwrap();
+    let video = dir.path().join("lecture.mp4");
+    fs::write(dir.path().join("lecture2.srt"), SRT).unwrap();
+    fs::write(dir.path().join("lecture.txt"), "notes").unwrap();
+    assert_eq!(find_sidecar(&video), None);
+}
+
+#[test]
+fn test_load_vtt_converts_srt() {
+    let dir = tempdir().unwrap();
+    let srt = dir.path().join("talk.srt");
+    fs::write(&srt, SRT).unwrap();
+    assert!(load_vtt(&srt).unwrap().starts_with("WEBVTT"));
+    assert!(load_vtt(&dir.path().join("missing.vtt")).is_err());
+}
@@ -0,0 +1,61 @@
+//! Unit tests for syntax tokens module.
+
+use humanboard::syntax_tokens::{TokenKind, grammar_name, tokenize_line};
+
+fn tokens<'a>(line: &'a str, language: &str) -> Vec<(&'a str, TokenKind)> {
+    tokenize_line(line, language)
+        .into_iter()
+        .map(|(range, kind)| (&line[range], kind))
+        .collect()
+}
+
+#[test]
+fn test_rust_line() {
+    assert_eq!(
+        tokens(r#"let name = "hi"; // greet 2"#, "rust"),
+        [
+            ("let", TokenKind::Keyword),
+            (r#""hi""#, TokenKind::String),
+            ("// greet 2", TokenKind::Comment),
+        ]
+    );
+}
+
+#[test]
+fn test_numbers_and_identifiers() {
+    assert_eq!(
+        tokens("x1 = 1.5 + 42", "python"),
+        [("1.5", TokenKind::Number), ("42", TokenKind::Number)]
+    );
+}
+
+#[test]
+fn test_comment_prefix_depends_on_language() {
+    assert_eq!(tokens("# note", "python"), [("# note", TokenKind::Comment)]);
+    assert_eq!(tokens("-- note", "sql"), [("-- note", TokenKind::Comment)]);
+    assert!(tokens("# Heading", "markdown").is_empty());
+}
+
+#[test]
+fn test_strings_with_escapes_and_unclosed() {
+    assert_eq!(
+        tokens(r#"s = "a\"b" + 'c"#, "javascript"),
+        [(r#""a\"b""#, TokenKind::String), ("'c", TokenKind::String)]
+    );
+}
+
+#[test]
+fn test_rust_lifetimes_are_not_strings() {
+    assert_eq!(
+        tokens("fn f<'a>(s: &'a str)", "rust"),
+        [("fn", TokenKind::Keyword)]
+    );
+}
+
+#[test]
+fn test_grammar_names() {
+    assert_eq!(grammar_name("rust"), Some("rust"));
+    assert_eq!(grammar_name("tsx"), Some("tsx"));
+    assert_eq!(grammar_name("makefile"), Some("make"));
+    assert_eq!(grammar_name("kotlin"), None);
+}
@@ -0,0 +1,55 @@
+//! Unit tests for tab_zoom module.
+
+use humanboard::tab_zoom::{TAB_ZOOM_LEVELS, TabZoomStore, step_tab_zoom};
+use std::path::Path;
+use tempfile::tempdir;
+
+#[test]
+fn test_step_moves_to_neighbouring_level() {
+    assert_eq!(step_tab_zoom(1.0, true), 1.1);
+    assert_eq!(step_tab_zoom(1.0, false), 0.9);
+    assert_eq!(step_tab_zoom(1.25, true), 1.5);
+}
+
+#[test]
+fn test_step_snaps_between_levels() {
+    assert_eq!(step_tab_zoom(1.2, true), 1.25);
+    assert_eq!(step_tab_zoom(1.2, false), 1.1);
+}
+
+#[test]
+fn test_step_stays_at_the_ends() {
+    let min = TAB_ZOOM_LEVELS[0];
+    let max = TAB_ZOOM_LEVELS[TAB_ZOOM_LEVELS.len() - 1];
+    assert_eq!(step_tab_zoom(min, false), min);
+    assert_eq!(step_tab_zoom(max, true), max);
+    assert_eq!(step_tab_zoom(10.0, true), max);
+}
+
+#[test]
+fn test_store_remembers_levels_across_loads() {
+    let dir = tempdir().unwrap();
+    let file = dir.path().join("tab_zoom.json");
+    let notes = Path::new("/docs/notes.md");
+
+    let store = TabZoomStore::new(file.clone());
+    assert_eq!(store.get(notes), None);
+    store.set(notes, Some(1.5));
+    assert_eq!(store.get(notes), Some(1.5));
+
+    let reopened = TabZoomStore::new(file);
+    assert_eq!(reopened.get(notes), Some(1.5));
+}
+
+#[test]
+fn test_store_forgets_default_zoom() {
+    let dir = tempdir().unwrap();
+    let file = dir.path().join("tab_zoom.json");
+    let notes = Path::new("/docs/notes.md");
+
+    let store = TabZoomStore::new(file.clone());
+    store.set(notes, Some(2.0));
+    store.set(notes, Some(1.0));
+    assert_eq!(store.get(notes), None);
+    assert_eq!(TabZoomStore::new(file).get(notes), None);
+}
@@ -0,0 +1,104 @@
+//! Unit tests for text diff module.
+
+use humanboard::text_diff::{
+    DiffFold, DiffKind, DiffRow, TextDiff, diff_lines, fold_unchanged, side_by_side_rows,
+};
+
+fn kinds(old: &str, new: &str) -> Vec<(DiffKind, String)> {
+    diff_lines(old, new)
+        .into_iter()
+        .map(|line| (line.kind, line.text))
+        .collect()
+}
+
+#[test]
+fn test_identical_texts() {
+    let diff = TextDiff::new("a\nb\n", "a\nb\n");
+    assert!(diff.is_identical());
+    assert_eq!(diff.lines.len(), 2);
+}
+
+#[test]
+fn test_changed_line_is_removed_then_added() {
+    use DiffKind::*;
+    assert_eq!(
+        kinds("a\nb\nc", "a\nx\nc"),
+        [
+            (Unchanged, "a".to_string()),
+            (Removed, "b".to_string()),
+            (Added, "x".to_string()),
+            (Unchanged, "c".to_string()),
+        ]
+    );
+}
+
+#[test]
+fn test_insertions_and_deletions_keep_common_lines() {
+    let diff = TextDiff::new("one\ntwo\nthree\nfour", "zero\none\nthree\nfour\nfive");
+    assert_eq!(diff.added, 2);
+    assert_eq!(diff.removed, 1);
+    let unchanged: Vec<&str> = diff
+        .lines
+        .iter()
+        .filter(|line| line.kind == DiffKind::Unchanged)
+        .map(|line| line.text.as_str())
+        .collect();
+    assert_eq!(unchanged, ["one", "three", "four"]);
+}
+
+#[test]
+fn test_line_numbers() {
+    let lines = diff_lines("a\nb\nc", "a\nc\nd");
+    let numbers: Vec<(Option<usize>, Option<usize>)> = lines
+        .iter()
+        .map(|line| (line.old_number, line.new_number))
+        .collect();
+    assert_eq!(
+        numbers,
+        [
+            (Some(1), Some(1)),
+            (Some(2), None),
+            (Some(3), Some(2)),
+            (None, Some(3)),
+        ]
+    );
+}
+
+#[test]
+fn test_side_by_side_pairs_replacements() {
+    let lines = diff_lines("a\nb\nc\nd", "a\nx\nd");
+    let rows = side_by_side_rows(&lines);
+    // a | a, b | x, c | -, d | d
+    assert_eq!(rows.len(), 4);
+    assert_eq!(
+        rows[1],
+        DiffRow {
+            old: Some(1),
+            new: Some(3)
+        }
+    );
+    assert_eq!(
+        rows[2],
+        DiffRow {
+            old: Some(2),
+            new: None
+        }
+    );
+    assert!(!rows[0].is_change(&lines));
+    assert!(rows[2].is_change(&lines));
+}
+
+#[test]
+fn test_fold_unchanged_keeps_context() {
+    let mut changed = vec![false; 20];
+    changed[10] = true;
+    assert_eq!(
+        fold_unchanged(&changed, 3),
+        [
+            DiffFold::Hidden(0..7),
+            DiffFold::Shown(7..14),
+            DiffFold::Hidden(14..20),
+        ]
+    );
+    assert!(fold_unchanged(&[], 3).is_empty());
+}
@@ -0,0 +1,81 @@
+//! Unit tests for text box line layout caching.
+
+use crate::helpers::test_canvas_item;
+use humanboard::render::text_layout::{TextLayout, TextLayoutCache, TextLayoutKey};
+use humanboard::types::{CanvasItem, ItemContent};
+use std::sync::Arc;
+
+fn textbox(id: u64, text: &str) -> CanvasItem {
+    let mut item = test_canvas_item(id, "");
+    item.content = ItemContent::TextBox {
+        text: text.to_string(),
+        font_size: 16.0,
+        color: String::new(),
+    };
+    item
+}
+
+#[test]
+fn test_layout_splits_lines_and_keeps_empty_ones() {
+    let layout = TextLayout::new("first\n\nthird", 16.0, 1.0);
+    let lines: Vec<&str> = layout.lines.iter().map(|line| line.as_ref()).collect();
+    assert_eq!(lines, vec!["first", " ", "third"]);
+    assert_eq!(layout.font_size, 16.0);
+}
+
+#[test]
+fn test_layout_font_size_uses_zoom_bucket() {
+    let layout = TextLayout::new("note", 10.0, 1.001);
+    assert_eq!(layout.font_size, 10.0);
+}
+
+#[test]
+fn test_key_changes_with_text_font_and_zoom() {
+    let key = TextLayoutKey::new("note", 16.0, 1.0);
+    assert_eq!(key, TextLayoutKey::new("note", 16.0, 1.001));
+    assert_ne!(key, TextLayoutKey::new("notes", 16.0, 1.0));
+    assert_ne!(key, TextLayoutKey::new("note", 18.0, 1.0));
+    assert_ne!(key, TextLayoutKey::new("note", 16.0, 1.5));
+}
+
+#[test]
+fn test_prepare_reuses_unchanged_layouts() {
+    let mut cache = TextLayoutCache::default();
+    let items = vec![textbox(1, "a\nb"), test_canvas_item(2, "plain text")];
+
+    let first = cache.prepare(&items, 1.0);
+    let second = cache.prepare(&items, 1.0);
+
+    assert_eq!(first.len(), 1);
+    assert!(Arc::ptr_eq(&first[&1], &second[&1]));
+}
+
+#[test]
+fn test_prepare_rebuilds_after_edit_and_zoom() {
+    let mut cache = TextLayoutCache::default();
+    let first = cache.prepare(&[textbox(1, "draft")], 1.0);
+
+    let edited = cache.prepare(&[textbox(1, "final")], 1.0);
+    assert!(!Arc::ptr_eq(&first[&1], &edited[&1]));
+    assert_eq!(edited[&1].lines[0].as_ref(), "final");
+
+    let zoomed = cache.prepare(&[textbox(1, "final")], 2.0);
+    assert!(!Arc::ptr_eq(&edited[&1], &zoomed[&1]));
+    assert_eq!(zoomed[&1].font_size, 32.0);
+}
+
+#[test]
+fn test_invalidate_and_offscreen_items_drop_layouts() {
+    let mut cache = TextLayoutCache::default();
+    let items = vec![textbox(1, "a"), textbox(2, "b")];
+    let first = cache.prepare(&items, 1.0);
+    assert_eq!(cache.len(), 2);
+
+    cache.invalidate(1);
+    let second = cache.prepare(&items, 1.0);
+    assert!(!Arc::ptr_eq(&first[&1], &second[&1]));
+    assert!(Arc::ptr_eq(&first[&2], &second[&2]));
+
+    cache.prepare(&items[..1], 1.0);
+    assert_eq!(cache.len(), 1);
+}
@@ -0,0 +1,100 @@
+//! Unit tests for canvas zoom gestures, zoom to fit and revealing items.
+
+use gpui::{ScrollDelta, point, px};
+use humanboard::input::transform::{
+    fit_bounds, reveal_offset, scroll_pan_delta, scroll_zoom_factor,
+};
+
+#[test]
+fn test_scroll_zoom_is_symmetric() {
+    let zoom_in = scroll_zoom_factor(ScrollDelta::Pixels(point(px(0.0), px(-100.0))));
+    let zoom_out = scroll_zoom_factor(ScrollDelta::Pixels(point(px(0.0), px(100.0))));
+
+    assert!(zoom_in > 1.0);
+    assert!((zoom_in * zoom_out - 1.0).abs() < 1e-5);
+}
+
+#[test]
+fn test_large_scroll_never_flips_zoom() {
+    // A fast fling must shrink the canvas, not produce a negative scale
+    let factor = scroll_zoom_factor(ScrollDelta::Pixels(point(px(0.0), px(2000.0))));
+    assert!(factor > 0.0 && factor < 1.0);
+
+    let factor = scroll_zoom_factor(ScrollDelta::Lines(point(0.0, 200.0)));
+    assert!(factor > 0.0 && factor < 1.0);
+}
+
+#[test]
+fn test_fit_bounds_centers_content() {
+    // 1000x500 of content in an 848x448 viewport with 24px padding
+    let (offset, zoom) = fit_bounds((100.0, 200.0, 1000.0, 500.0), (848.0, 448.0), 24.0, 2.0);
+
+    assert!((zoom - 0.8).abs() < 1e-5);
+    // Content center (600, 450) lands on the viewport center (424, 224)
+    assert!((f32::from(offset.x) + 600.0 * zoom - 424.0).abs() < 1e-3);
+    assert!((f32::from(offset.y) + 450.0 * zoom - 224.0).abs() < 1e-3);
+}
+
+#[test]
+fn test_fit_bounds_caps_zoom_for_small_content() {
+    let (_, zoom) = fit_bounds((0.0, 0.0, 10.0, 10.0), (800.0, 600.0), 48.0, 2.0);
+    assert_eq!(zoom, 2.0);
+}
+
+#[test]
+fn test_fit_bounds_limits_zoom_out() {
+    let (_, zoom) = fit_bounds((0.0, 0.0, 1_000_000.0, 10.0), (800.0, 600.0), 48.0, 2.0);
+    assert_eq!(zoom, humanboard::constants::MIN_ZOOM);
+}
+
+#[test]
+fn test_reveal_offset_leaves_visible_items_alone() {
+    let offset = point(px(0.0), px(0.0));
+    assert_eq!(
+        reveal_offset(
+            (100.0, 100.0, 50.0, 50.0),
+            offset,
+            1.0,
+            (800.0, 600.0),
+            48.0
+        ),
+        None
+    );
+}
+
+#[test]
+fn test_reveal_offset_pans_just_enough() {
+    let offset = point(px(0.0), px(0.0));
+    // Off the right edge: pan left until the item sits inside the padding
+    let target = reveal_offset(
+        (900.0, 100.0, 100.0, 50.0),
+        offset,
+        1.0,
+        (800.0, 600.0),
+        48.0,
+    );
+    assert_eq!(target, Some(point(px(-248.0), px(0.0))));
+
+    // Above the top at 2x zoom
+    let target = reveal_offset(
+        (100.0, -100.0, 50.0, 50.0),
+        offset,
+        2.0,
+        (800.0, 600.0),
+        48.0,
+    );
+    assert_eq!(target, Some(point(px(0.0), px(248.0))));
+}
+
+#[test]
+fn test_shift_scroll_pans_horizontally() {
+    assert_eq!(scroll_pan_delta((0.0, -40.0), true), (-40.0, 0.0));
+    assert_eq!(scroll_pan_delta((0.0, -40.0), false), (0.0, -40.0));
+}
+
+#[test]
+fn test_shift_scroll_keeps_horizontal_deltas() {
+    // Trackpads (and macOS) already report Shift+scroll as horizontal
+    assert_eq!(scroll_pan_delta((-40.0, 0.0), true), (-40.0, 0.0));
+    assert_eq!(scroll_pan_delta((10.0, 5.0), true), (10.0, 5.0));
+}
@@ -1,6 +1,10 @@
 //! Unit tests for types module.
 
-use humanboard::types::{CanvasItem, ItemContent};
+use humanboard::constants::CODE_EXPAND_HEIGHT;
+use humanboard::types::{
+    CanvasItem, ItemContent, MediaVolume, extract_soundcloud_url, extract_spotify_embed,
+    extract_vimeo_id, extract_youtube_start, fit_image_size, image_item_size, youtube_link,
+};
 use std::path::PathBuf;
 
 #[test]
@@ -46,7 +50,8 @@ fn test_type_labels() {
     assert_eq!(
         ItemContent::Pdf {
             path: PathBuf::new(),
-            thumbnail: None
+            thumbnail: None,
+            page: 0,
         }
         .type_label(),
         "PDF"
@@ -90,10 +95,30 @@ fn test_default_size_pdf() {
     let content = ItemContent::Pdf {
         path: PathBuf::new(),
         thumbnail: None,
+        page: 0,
     };
     assert_eq!(content.default_size(), (180.0, 240.0));
 }
 
+#[test]
+fn test_default_size_image_is_placeholder() {
+    // Images are measured later, so even a missing file gets the placeholder
+    let content = ItemContent::Image(PathBuf::from("/no/such/photo.png"));
+    assert_eq!(content.default_size(), (800.0, 600.0));
+}
+
+#[test]
+fn test_fit_image_size_scales_down_large_images() {
+    assert_eq!(fit_image_size(4000, 2000), (800.0, 400.0));
+    assert_eq!(fit_image_size(1000, 2000), (400.0, 800.0));
+    assert_eq!(fit_image_size(320, 240), (320.0, 240.0));
+}
+
+#[test]
+fn test_image_item_size_missing_file() {
+    assert_eq!(image_item_size(&PathBuf::from("/no/such/photo.png")), None);
+}
+
 #[test]
 fn test_default_size_link() {
     let content = ItemContent::Link(String::new());
@@ -107,12 +132,81 @@ fn test_canvas_item_creation() {
         position: (100.0, 200.0),
         size: (300.0, 400.0),
         content: ItemContent::Text("Test".to_string()),
+        locked: false,
+        volume: None,
     };
     assert_eq!(item.id, 1);
     assert_eq!(item.position, (100.0, 200.0));
     assert_eq!(item.size, (300.0, 400.0));
 }
 
+#[test]
+fn test_canvas_item_volume_only_saved_when_set() {
+    let mut item = CanvasItem {
+        id: 1,
+        position: (0.0, 0.0),
+        size: (300.0, 100.0),
+        content: ItemContent::Audio(PathBuf::from("/music/song.mp3")),
+        locked: false,
+        volume: None,
+    };
+    assert!(!serde_json::to_string(&item).unwrap().contains("volume"));
+
+    item.volume = Some(MediaVolume {
+        level: 0.5,
+        muted: true,
+    });
+    let json = serde_json::to_string(&item).unwrap();
+    let loaded: CanvasItem = serde_json::from_str(&json).unwrap();
+    assert_eq!(loaded.volume, item.volume);
+}
+
+#[test]
+fn test_pdf_page_only_saved_past_first_page() {
+    let mut content = ItemContent::Pdf {
+        path: PathBuf::from("/docs/report.pdf"),
+        thumbnail: None,
+        page: 0,
+    };
+    assert!(!serde_json::to_string(&content).unwrap().contains("page"));
+
+    if let ItemContent::Pdf { page, .. } = &mut content {
+        *page = 4;
+    }
+    let json = serde_json::to_string(&content).unwrap();
+    let loaded: ItemContent = serde_json::from_str(&json).unwrap();
+    assert!(matches!(loaded, ItemContent::Pdf { page: 4, .. }));
+
+    // Boards saved before pages were remembered open on the first page
+    let old = r#"{"Pdf":{"path":"/docs/report.pdf","thumbnail":null}}"#;
+    let loaded: ItemContent = serde_json::from_str(old).unwrap();
+    assert!(matches!(loaded, ItemContent::Pdf { page: 0, .. }));
+}
+
+#[test]
+fn test_media_volume_with_level() {
+    let muted = MediaVolume::default().toggled_mute();
+    assert!(muted.muted && muted.is_silent());
+    assert_eq!(muted.level, 1.0);
+
+    // Turning the volume up unmutes, clamped to full
+    let louder = muted.with_level(1.5);
+    assert_eq!(louder.level, 1.0);
+    assert!(!louder.muted);
+
+    let silent = MediaVolume::default().with_level(-0.5);
+    assert_eq!(silent.level, 0.0);
+    assert!(silent.is_silent());
+}
+
+#[test]
+fn test_has_volume() {
+    assert!(ItemContent::Audio(PathBuf::new()).has_volume());
+    assert!(ItemContent::YouTube("abc".to_string()).has_volume());
+    assert!(!ItemContent::Spotify("track/abc".to_string()).has_volume());
+    assert!(!ItemContent::Text("note".to_string()).has_volume());
+}
+
 #[test]
 fn test_image_extensions() {
     let extensions = ["jpg", "jpeg", "png", "gif", "webp", "bmp", "svg"];
@@ -172,3 +266,189 @@ fn test_default_size_audio() {
 fn test_type_label_audio() {
     assert_eq!(ItemContent::Audio(PathBuf::new()).type_label(), "AUDIO");
 }
+
+#[test]
+fn test_extract_youtube_start() {
+    assert_eq!(extract_youtube_start("https://youtu.be/abc?t=90"), Some(90));
+    assert_eq!(
+        extract_youtube_start("https://www.youtube.com/watch?v=abc&t=1h2m3s"),
+        Some(3723)
+    );
+    assert_eq!(
+        extract_youtube_start("https://www.youtube.com/embed/abc?start=45"),
+        Some(45)
+    );
+    assert_eq!(
+        extract_youtube_start("https://www.youtube.com/watch?v=abc#t=2m5"),
+        Some(125)
+    );
+}
+
+#[test]
+fn test_extract_youtube_start_ignores_missing_or_bad_times() {
+    assert_eq!(extract_youtube_start("https://youtu.be/abc"), None);
+    assert_eq!(extract_youtube_start("https://youtu.be/abc?t=0"), None);
+    assert_eq!(extract_youtube_start("https://youtu.be/abc?t=soon"), None);
+    assert_eq!(
+        extract_youtube_start("https://www.youtube.com/watch?v=abc&list=t1"),
+        None
+    );
+}
+
+#[test]
+fn test_youtube_link() {
+    assert_eq!(youtube_link("abc", 0), "https://youtu.be/abc");
+    assert_eq!(youtube_link("abc", 90), "https://youtu.be/abc?t=90");
+}
+
+#[test]
+fn test_extract_vimeo_id() {
+    assert_eq!(
+        extract_vimeo_id("https://vimeo.com/76979871"),
+        Some("76979871".to_string())
+    );
+    assert_eq!(
+        extract_vimeo_id("https://player.vimeo.com/video/76979871?h=abc"),
+        Some("76979871".to_string())
+    );
+    assert_eq!(
+        extract_vimeo_id("https://vimeo.com/channels/staffpicks/76979871"),
+        Some("76979871".to_string())
+    );
+}
+
+#[test]
+fn test_extract_vimeo_id_rejects_other_urls() {
+    assert_eq!(extract_vimeo_id("https://vimeo.com/about"), None);
+    assert_eq!(extract_vimeo_id("https://notvimeo.com/76979871"), None);
+    assert_eq!(
+        extract_vimeo_id("https://example.com/vimeo.com/76979871"),
+        None
+    );
+}
+
+#[test]
+fn test_vimeo_labels() {
+    let content = ItemContent::Vimeo("76979871".to_string());
+    assert_eq!(content.type_label(), "VIMEO");
+    assert_eq!(content.display_name(), "Vimeo: 76979871");
+    assert_eq!(content.default_size(), (560.0, 315.0));
+}
+
+#[test]
+fn test_extract_soundcloud_url() {
+    assert_eq!(
+        extract_soundcloud_url("https://soundcloud.com/artist/track-name?si=abc"),
+        Some("https://soundcloud.com/artist/track-name".to_string())
+    );
+    assert_eq!(
+        extract_soundcloud_url("https://m.soundcloud.com/artist/sets/mixtape"),
+        Some("https://soundcloud.com/artist/sets/mixtape".to_string())
+    );
+    // A profile page is kept as a plain link
+    assert_eq!(
+        extract_soundcloud_url("https://soundcloud.com/artist"),
+        None
+    );
+    assert_eq!(
+        extract_soundcloud_url("https://example.com/artist/track"),
+        None
+    );
+}
+
+#[test]
+fn test_extract_spotify_embed() {
+    assert_eq!(
+        extract_spotify_embed("https://open.spotify.com/track/4uLU6hMCjMI75M1A2tKUQC?si=xyz"),
+        Some("track/4uLU6hMCjMI75M1A2tKUQC".to_string())
+    );
+    assert_eq!(
+        extract_spotify_embed("https://open.spotify.com/intl-de/album/1DFixLWuPkv3KT3TnV35m3"),
+        Some("album/1DFixLWuPkv3KT3TnV35m3".to_string())
+    );
+    assert_eq!(
+        extract_spotify_embed("spotify:playlist:37i9dQZF1DXcBWIGoYBM5M"),
+        Some("playlist/37i9dQZF1DXcBWIGoYBM5M".to_string())
+    );
+    assert_eq!(
+        extract_spotify_embed("https://open.spotify.com/user/someone"),
+        None
+    );
+    assert_eq!(
+        extract_spotify_embed("https://example.com/track/4uLU6hMCjMI75M1A2tKUQC"),
+        None
+    );
+}
+
+#[test]
+fn test_music_embed_sizes() {
+    let track = ItemContent::SoundCloud("https://soundcloud.com/artist/track".to_string());
+    let playlist = ItemContent::SoundCloud("https://soundcloud.com/artist/sets/mix".to_string());
+    assert_eq!(track.default_size(), (400.0, 166.0));
+    assert_eq!(playlist.default_size(), (400.0, 450.0));
+
+    let track = ItemContent::Spotify("track/4uLU6hMCjMI75M1A2tKUQC".to_string());
+    let album = ItemContent::Spotify("album/1DFixLWuPkv3KT3TnV35m3".to_string());
+    assert_eq!(track.default_size(), (400.0, 152.0));
+    assert_eq!(album.default_size(), (400.0, 352.0));
+    assert_eq!(track.type_label(), "SPOTIFY");
+    assert_eq!(
+        track.display_name(),
+        "Spotify: track/4uLU6hMCjMI75M1A2tKUQC"
+    );
+}
+
+#[test]
+fn test_markdown_card_expands_when_resized_tall() {
+    let content = ItemContent::Markdown {
+        path: PathBuf::from("/notes/plan.md"),
+        title: "Plan".to_string(),
+        content: "# Plan".to_string(),
+    };
+    let mut item = CanvasItem {
+        id: 1,
+        position: (0.0, 0.0),
+        size: content.default_size(),
+        content,
+        locked: false,
+        volume: None,
+    };
+    assert!(!item.is_expanded_markdown());
+
+    item.size = (400.0, 320.0);
+    assert!(item.is_expanded_markdown());
+
+    item.content = ItemContent::Text("note".to_string());
+    assert!(!item.is_expanded_markdown());
+}
+
+#[test]
+fn test_code_card_expands_when_resized_tall() {
+    let mut item = CanvasItem {
+        id: 1,
+        position: (0.0, 0.0),
+        size: (200.0, 36.0),
+        content: ItemContent::Code {
+            path: PathBuf::from("/src/main.rs"),
+            language: "rust".to_string(),
+            lines: None,
+        },
+        locked: false,
+        volume: None,
+    };
+    assert!(!item.is_expanded_code());
+
+    item.size = (400.0, 320.0);
+    assert!(item.is_expanded_code());
+    assert!(!item.is_expanded_markdown());
+}
+
+#[test]
+fn test_code_card_of_a_line_range_starts_expanded() {
+    let content = ItemContent::Code {
+        path: PathBuf::from("/src/main.rs"),
+        language: "rust".to_string(),
+        lines: Some((20, 45)),
+    };
+    assert!(content.default_size().1 >= CODE_EXPAND_HEIGHT);
+}
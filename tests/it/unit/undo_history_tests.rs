@@ -0,0 +1,143 @@
+//! Unit tests for undo_history module.
+
+use humanboard::board::UndoOperation;
+use humanboard::types::{CanvasItem, ItemContent};
+use humanboard::undo_history::{
+    DEFAULT_STEP_LABEL, cell_name, format_step_time, item_name, items_label, operation_label,
+};
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime};
+
+fn item(id: u64, content: ItemContent) -> CanvasItem {
+    CanvasItem {
+        id,
+        position: (0.0, 0.0),
+        size: (200.0, 100.0),
+        content,
+        locked: false,
+        volume: None,
+    }
+}
+
+fn image(id: u64, name: &str) -> CanvasItem {
+    item(
+        id,
+        ItemContent::Image(PathBuf::from(format!("/img/{}", name))),
+    )
+}
+
+fn no_names(_: u64) -> Option<String> {
+    None
+}
+
+#[test]
+fn test_item_names() {
+    assert_eq!(item_name(&image(1, "cat.png")), "cat.png");
+    assert_eq!(
+        item_name(&item(2, ItemContent::Text("Title\nBody".to_string()))),
+        "Title"
+    );
+    let long = "a".repeat(40);
+    let name = item_name(&item(3, ItemContent::Text(long)));
+    assert_eq!(name.chars().count(), 32);
+    assert!(name.ends_with('…'));
+}
+
+#[test]
+fn test_single_operation_labels() {
+    assert_eq!(
+        operation_label(&UndoOperation::AddItem(image(1, "cat.png")), no_names),
+        "Added cat.png"
+    );
+    assert_eq!(
+        operation_label(&UndoOperation::RemoveItem(image(1, "cat.png")), no_names),
+        "Deleted cat.png"
+    );
+
+    let moved = UndoOperation::MoveItem {
+        id: 4,
+        old_pos: (0.0, 0.0),
+        new_pos: (10.0, 0.0),
+    };
+    assert_eq!(
+        operation_label(&moved, |id| Some(format!("item {}", id))),
+        "Moved item 4"
+    );
+    assert_eq!(operation_label(&moved, no_names), "Moved item");
+
+    let mut locked = image(1, "cat.png");
+    locked.locked = true;
+    let lock = UndoOperation::ModifyItem {
+        old_item: image(1, "cat.png"),
+        new_item: locked,
+    };
+    assert_eq!(operation_label(&lock, no_names), "Locked cat.png");
+}
+
+#[test]
+fn test_batch_labels() {
+    let moves = UndoOperation::Batch(
+        (0..3)
+            .map(|id| UndoOperation::MoveItem {
+                id,
+                old_pos: (0.0, 0.0),
+                new_pos: (0.0, 10.0),
+            })
+            .collect(),
+    );
+    assert_eq!(operation_label(&moves, no_names), "Moved 3 items");
+
+    let mixed = UndoOperation::Batch(vec![
+        UndoOperation::AddItem(image(1, "a.png")),
+        UndoOperation::RemoveItem(image(2, "b.png")),
+    ]);
+    assert_eq!(operation_label(&mixed, no_names), "Changed 2 items");
+
+    let single = UndoOperation::Batch(vec![UndoOperation::AddItem(image(1, "a.png"))]);
+    assert_eq!(operation_label(&single, no_names), "Added a.png");
+
+    assert_eq!(
+        operation_label(&UndoOperation::Batch(Vec::new()), no_names),
+        DEFAULT_STEP_LABEL
+    );
+}
+
+#[test]
+fn test_items_label() {
+    assert_eq!(items_label("Deleted", 1), "Deleted 1 item");
+    assert_eq!(items_label("Deleted", 4), "Deleted 4 items");
+}
+
+#[test]
+fn test_cell_names() {
+    assert_eq!(cell_name(0, 0), "A1");
+    assert_eq!(cell_name(3, 1), "B4");
+    assert_eq!(cell_name(9, 25), "Z10");
+    assert_eq!(cell_name(0, 26), "AA1");
+    assert_eq!(cell_name(0, 27), "AB1");
+    assert_eq!(cell_name(0, 701), "ZZ1");
+    assert_eq!(cell_name(0, 702), "AAA1");
+}
+
+#[test]
+fn test_step_times() {
+    let now = SystemTime::now();
+    assert_eq!(format_step_time(now, now), "Just now");
+    assert_eq!(
+        format_step_time(now - Duration::from_secs(59), now),
+        "Just now"
+    );
+    assert_eq!(
+        format_step_time(now - Duration::from_secs(5 * 60), now),
+        "5 min ago"
+    );
+    assert_eq!(
+        format_step_time(now - Duration::from_secs(2 * 3600), now),
+        "2 hours ago"
+    );
+    // Clocks going backwards don't make steps from the future
+    assert_eq!(
+        format_step_time(now + Duration::from_secs(30), now),
+        "Just now"
+    );
+}
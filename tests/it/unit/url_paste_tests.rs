@@ -0,0 +1,133 @@
+//! Unit tests for url_paste module.
+
+use humanboard::types::ItemContent;
+use humanboard::url_paste::{
+    PageDetails, UrlPasteKind, bare_bookmark, choices, detect, embed_content, is_image_url,
+    parse_page_details, url_host,
+};
+
+#[test]
+fn test_detects_embeds_images_and_pages() {
+    assert_eq!(
+        detect("https://www.youtube.com/watch?v=dQw4w9WgXcQ"),
+        UrlPasteKind::Embed
+    );
+    assert_eq!(
+        detect("https://example.com/photos/cat.JPG?size=large"),
+        UrlPasteKind::Image
+    );
+    assert_eq!(
+        detect("https://example.com/blog/post"),
+        UrlPasteKind::Bookmark
+    );
+    // A host that looks like a file name is still a page
+    assert_eq!(detect("https://example.png"), UrlPasteKind::Bookmark);
+    assert!(matches!(
+        embed_content("https://youtu.be/dQw4w9WgXcQ"),
+        Some(ItemContent::YouTube(_))
+    ));
+}
+
+#[test]
+fn test_image_urls() {
+    assert!(is_image_url("https://cdn.example.com/a/b.webp"));
+    assert!(is_image_url("https://example.com/logo.svg#top"));
+    assert!(!is_image_url("https://example.com/page.html"));
+    assert!(!is_image_url("https://example.com/images"));
+}
+
+#[test]
+fn test_choices_put_the_detected_kind_first() {
+    assert_eq!(
+        choices("https://www.youtube.com/watch?v=dQw4w9WgXcQ"),
+        vec![
+            UrlPasteKind::Embed,
+            UrlPasteKind::Image,
+            UrlPasteKind::Bookmark,
+            UrlPasteKind::Link
+        ]
+    );
+    assert_eq!(
+        choices("https://example.com/blog/post"),
+        vec![
+            UrlPasteKind::Bookmark,
+            UrlPasteKind::Image,
+            UrlPasteKind::Link
+        ]
+    );
+}
+
+#[test]
+fn test_url_hosts() {
+    assert_eq!(url_host("https://www.Example.com/path?q=1"), "example.com");
+    assert_eq!(url_host("http://user@docs.rs:8080"), "docs.rs:8080");
+    assert_eq!(url_host("https://example.com#top"), "example.com");
+}
+
+#[test]
+fn test_bare_bookmark_shows_the_host() {
+    let ItemContent::Bookmark {
+        url,
+        title,
+        description,
+        site,
+    } = bare_bookmark("https://www.example.com/post")
+    else {
+        panic!("expected a bookmark");
+    };
+    assert_eq!(url, "https://www.example.com/post");
+    assert_eq!(title, "example.com");
+    assert_eq!(site, "example.com");
+    assert!(description.is_empty());
+}
+
+#[test]
+fn test_parse_open_graph_details() {
+    let html = r#"<html><head>
+        <title>Plain title</title>
+        <meta name="description" content="Plain description">
+        <meta property="og:title" content="Tom &amp; Jerry&#39;s page" />
+        <meta content='The  og
+            description' property='og:description'>
+        <meta property="og:site_name" content=Example>
+    </head></html>"#;
+
+    let details = parse_page_details(html);
+    assert_eq!(details.title.as_deref(), Some("Tom & Jerry's page"));
+    assert_eq!(details.description.as_deref(), Some("The og description"));
+    assert_eq!(details.site_name.as_deref(), Some("Example"));
+}
+
+#[test]
+fn test_parse_plain_details() {
+    let html = "<HTML><HEAD><TITLE>\n  My  page\n</TITLE>\
+        <META NAME=\"Description\" CONTENT=\"About me\"><metadata content=\"x\"></HEAD>";
+
+    let details = parse_page_details(html);
+    assert_eq!(details.title.as_deref(), Some("My page"));
+    assert_eq!(details.description.as_deref(), Some("About me"));
+    assert_eq!(details.site_name, None);
+    assert_eq!(parse_page_details("no markup"), PageDetails::default());
+}
+
+#[test]
+fn test_long_descriptions_are_cut() {
+    let html = format!(
+        "<meta name=\"description\" content=\"{}\">",
+        "word ".repeat(100)
+    );
+    let description = parse_page_details(&html).description.unwrap();
+    assert_eq!(description.chars().count(), 300);
+    assert!(description.ends_with('…'));
+}
+
+#[test]
+fn test_page_details_fall_back_to_the_host() {
+    let ItemContent::Bookmark { title, site, .. } =
+        PageDetails::default().bookmark("https://example.com/post")
+    else {
+        panic!("expected a bookmark");
+    };
+    assert_eq!(title, "example.com");
+    assert_eq!(site, "example.com");
+}
@@ -37,6 +37,8 @@ fn test_validate_textbox_font_size() {
             font_size: 5.0, // Too small
             color: "#ffffff".to_string(),
         },
+        locked: false,
+        volume: None,
     };
 
     let result = validate_item(&mut item, &constraints);
@@ -59,6 +61,8 @@ fn test_validate_textbox_color() {
             font_size: 16.0,
             color: "invalid".to_string(),
         },
+        locked: false,
+        volume: None,
     };
 
     let result = validate_item(&mut item, &constraints);
@@ -82,6 +86,8 @@ fn test_validate_arrow_thickness() {
             thickness: 100.0, // Too thick
             head_style: ArrowHead::Arrow,
         },
+        locked: false,
+        volume: None,
     };
 
     let result = validate_item(&mut item, &constraints);
@@ -105,6 +111,8 @@ fn test_validate_shape_border() {
             border_color: "#ffffff".to_string(),
             border_width: 100.0, // Too wide
         },
+        locked: false,
+        volume: None,
     };
 
     let result = validate_item(&mut item, &constraints);
@@ -133,6 +141,8 @@ fn test_validate_item_size() {
             font_size: 16.0,
             color: "#ffffff".to_string(),
         },
+        locked: false,
+        volume: None,
     };
 
     let result = validate_item(&mut item, &constraints);
@@ -153,6 +163,8 @@ fn test_valid_item_passes() {
             font_size: 16.0,
             color: "#ffffff".to_string(),
         },
+        locked: false,
+        volume: None,
     };
 
     let result = validate_item(&mut item, &constraints);
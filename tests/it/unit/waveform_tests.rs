@@ -0,0 +1,101 @@
+//! Unit tests for audio waveforms.
+
+use humanboard::waveform::{
+    PeakAccumulator, WAVEFORM_BUCKETS, Waveform, bucket_peaks, decode_waveform, resample_peaks,
+};
+use std::fs;
+use std::path::Path;
+use tempfile::tempdir;
+
+/// Write a mono 16-bit PCM WAV file
+fn write_wav(path: &Path, sample_rate: u32, samples: &[i16]) {
+    let data_len = (samples.len() * 2) as u32;
+    let mut bytes = Vec::new();
+    bytes.extend_from_slice(b"RIFF");
+    bytes.extend_from_slice(&(36 + data_len).to_le_bytes());
+    bytes.extend_from_slice(b"WAVEfmt ");
+    bytes.extend_from_slice(&16u32.to_le_bytes());
+    bytes.extend_from_slice(&1u16.to_le_bytes()); // PCM
+    bytes.extend_from_slice(&1u16.to_le_bytes()); // mono
+    bytes.extend_from_slice(&sample_rate.to_le_bytes());
+    bytes.extend_from_slice(&(sample_rate * 2).to_le_bytes());
+    bytes.extend_from_slice(&2u16.to_le_bytes());
+    bytes.extend_from_slice(&16u16.to_le_bytes());
+    bytes.extend_from_slice(b"data");
+    bytes.extend_from_slice(&data_len.to_le_bytes());
+    for sample in samples {
+        bytes.extend_from_slice(&sample.to_le_bytes());
+    }
+    fs::write(path, bytes).unwrap();
+}
+
+#[test]
+fn test_resample_peaks_takes_loudest_when_shrinking() {
+    let peaks = [0.1, 0.9, 0.2, 0.3];
+    assert_eq!(resample_peaks(&peaks, 2), vec![0.9, 0.3]);
+    assert_eq!(resample_peaks(&peaks, 1), vec![0.9]);
+}
+
+#[test]
+fn test_resample_peaks_repeats_when_stretching() {
+    assert_eq!(resample_peaks(&[0.2, 0.8], 4), vec![0.2, 0.2, 0.8, 0.8]);
+    assert!(resample_peaks(&[], 4).is_empty());
+    assert!(resample_peaks(&[0.5], 0).is_empty());
+}
+
+#[test]
+fn test_bucket_peaks_normalizes_to_loudest() {
+    assert_eq!(bucket_peaks(&[0.25, 0.5, 0.125, 0.5], 2), vec![1.0, 1.0]);
+    assert_eq!(bucket_peaks(&[0.25, 0.5], 8), vec![0.5, 1.0]);
+    assert_eq!(bucket_peaks(&[0.0, 0.0], 2), vec![0.0, 0.0]);
+}
+
+#[test]
+fn test_accumulator_folds_frames_into_blocks() {
+    let mut accumulator = PeakAccumulator::new(2);
+    // Stereo frames: the louder channel counts
+    accumulator.push_interleaved(&[0.1, -0.4, 0.2, 0.0, 0.0, 0.0, 0.8, 0.1, 0.2, 0.2], 2);
+    assert_eq!(accumulator.frames(), 5);
+    // Blocks: [0.4, 0.8, 0.2] -> normalized
+    assert_eq!(accumulator.finish(8), vec![0.5, 1.0, 0.25]);
+}
+
+#[test]
+fn test_waveform_bars() {
+    let waveform = Waveform {
+        peaks: vec![0.2, 1.0, 0.4, 0.6],
+        duration_secs: 1.0,
+    };
+    assert_eq!(waveform.bars(2), vec![1.0, 0.6]);
+    assert_eq!(waveform.bars(4), waveform.peaks);
+}
+
+#[test]
+fn test_decode_wav_waveform() {
+    let dir = tempdir().unwrap();
+    let path = dir.path().join("clip.wav");
+    // One quiet second followed by one loud second
+    let mut samples = vec![1000i16; 8000];
+    samples.extend(std::iter::repeat_n(i16::MAX, 8000));
+    write_wav(&path, 8000, &samples);
+
+    let waveform = decode_waveform(&path, WAVEFORM_BUCKETS).unwrap();
+    assert!((waveform.duration_secs - 2.0).abs() < 0.01);
+    assert!(!waveform.peaks.is_empty() && waveform.peaks.len() <= WAVEFORM_BUCKETS);
+    assert_eq!(*waveform.peaks.last().unwrap(), 1.0);
+    assert!(waveform.peaks[0] < 0.1);
+}
+
+#[test]
+fn test_decode_missing_file_fails() {
+    let dir = tempdir().unwrap();
+    assert!(decode_waveform(&dir.path().join("missing.wav"), WAVEFORM_BUCKETS).is_err());
+}
+
+#[test]
+fn test_decode_non_audio_fails() {
+    let dir = tempdir().unwrap();
+    let path = dir.path().join("notes.wav");
+    fs::write(&path, b"not audio at all").unwrap();
+    assert!(decode_waveform(&path, WAVEFORM_BUCKETS).is_err());
+}
@@ -0,0 +1,274 @@
+//! Unit tests for importing Miro and FigJam boards.
+
+use humanboard::types::{ArrowHead, CanvasItem, ItemContent, ShapeType};
+use humanboard::whiteboard_import::{
+    WhiteboardApp, convert_miro_csv, convert_whiteboard, html_text, import_whiteboard, miro_color,
+};
+use std::fs;
+use std::path::PathBuf;
+use tempfile::tempdir;
+
+fn no_images(_: &str) -> Result<PathBuf, String> {
+    Err("no network".to_string())
+}
+
+fn text_of(item: &CanvasItem) -> Option<&str> {
+    match &item.content {
+        ItemContent::TextBox { text, .. } => Some(text),
+        _ => None,
+    }
+}
+
+#[test]
+fn test_miro_sticky_note_is_filled_shape_with_text() {
+    let json = r#"{"data": [{
+        "id": "3458764", "type": "sticky_note",
+        "data": {"content": "<p>Ship it</p><p>&amp; celebrate</p>", "shape": "square"},
+        "style": {"fillColor": "light_green"},
+        "position": {"x": 100, "y": 100, "origin": "center"},
+        "geometry": {"width": 200, "height": 200}
+    }]}"#;
+    let converted = convert_whiteboard(json, false, &mut no_images).unwrap();
+
+    assert_eq!(converted.app, WhiteboardApp::Miro);
+    assert_eq!(converted.items.len(), 2);
+    let note = &converted.items[0];
+    assert_eq!(note.position, (0.0, 0.0));
+    assert_eq!(note.size, (200.0, 200.0));
+    assert!(matches!(
+        &note.content,
+        ItemContent::Shape { fill_color: Some(fill), .. } if fill == "#d5f692"
+    ));
+    assert_eq!(text_of(&converted.items[1]), Some("Ship it\n& celebrate"));
+    assert!(matches!(
+        &converted.items[1].content,
+        ItemContent::TextBox { color, .. } if color == "#1a1a1a"
+    ));
+}
+
+#[test]
+fn test_miro_shapes_and_frames() {
+    let json = r##"[
+        {"id": "s", "type": "shape", "data": {"shape": "circle", "content": "Hub"},
+         "style": {"fillColor": "#FFFFFF", "borderColor": "#1a1a1a", "borderWidth": "3.0"},
+         "position": {"x": 60, "y": 40}, "geometry": {"width": 120, "height": 80},
+         "parent": {"id": "f"}},
+        {"id": "f", "type": "frame", "data": {"title": "Sprint"},
+         "position": {"x": 500, "y": 500}, "geometry": {"width": 1000, "height": 1000}}
+    ]"##;
+    let converted = convert_whiteboard(json, false, &mut no_images).unwrap();
+
+    assert_eq!(converted.items.len(), 4);
+    assert!(matches!(
+        converted.items[0].content,
+        ItemContent::Shape {
+            shape_type: ShapeType::RoundedRect,
+            ..
+        }
+    ));
+    assert_eq!(text_of(&converted.items[1]), Some("Sprint"));
+    // Positioned inside the frame, whose top-left is the origin
+    let shape = &converted.items[2];
+    assert_eq!(shape.position, (0.0, 0.0));
+    match &shape.content {
+        ItemContent::Shape {
+            shape_type,
+            fill_color,
+            border_width,
+            ..
+        } => {
+            assert_eq!(*shape_type, ShapeType::Ellipse);
+            assert_eq!(fill_color.as_deref(), Some("#ffffff"));
+            assert_eq!(*border_width, 3.0);
+        }
+        other => panic!("expected a shape, got {:?}", other),
+    }
+    assert_eq!(text_of(&converted.items[3]), Some("Hub"));
+}
+
+#[test]
+fn test_miro_connector_between_items() {
+    let json = r##"{"data": [
+        {"id": "a", "type": "text", "data": {"content": "A"},
+         "position": {"x": 50, "y": 50}, "geometry": {"width": 100, "height": 100}},
+        {"id": "b", "type": "text", "data": {"content": "B"},
+         "position": {"x": 350, "y": 50}, "geometry": {"width": 100, "height": 100}},
+        {"id": "c", "type": "connector", "startItem": {"id": "a"}, "endItem": {"id": "b"},
+         "style": {"strokeColor": "#ff0000", "strokeWidth": "4", "endStrokeCap": "stealth"},
+         "captions": [{"content": "<p>then</p>"}]},
+        {"id": "d", "type": "connector", "startItem": {"id": "a"}, "endItem": {"id": "gone"}}
+    ]}"##;
+    let converted = convert_whiteboard(json, false, &mut no_images).unwrap();
+
+    assert_eq!(converted.items.len(), 4);
+    let arrow = &converted.items[2];
+    assert_eq!(arrow.position, (100.0, 50.0));
+    match &arrow.content {
+        ItemContent::Arrow {
+            end_offset,
+            color,
+            thickness,
+            head_style,
+        } => {
+            assert_eq!(*end_offset, (200.0, 0.0));
+            assert_eq!(color, "#ff0000");
+            assert_eq!(*thickness, 4.0);
+            assert_eq!(*head_style, ArrowHead::Arrow);
+        }
+        other => panic!("expected an arrow, got {:?}", other),
+    }
+    assert_eq!(text_of(&converted.items[3]), Some("then"));
+    assert_eq!(converted.skipped.len(), 1);
+}
+
+#[test]
+fn test_miro_images_and_unknown_items() {
+    let json = r#"{"data": [
+        {"id": "i", "type": "image", "data": {"imageUrl": "https://example.com/cat.png", "title": "Cat"},
+         "position": {"x": 0, "y": 0}, "geometry": {"width": 300, "height": 200}},
+        {"id": "j", "type": "image", "data": {"imageUrl": "https://example.com/dog.png", "title": "Dog"},
+         "position": {"x": 0, "y": 0}, "geometry": {"width": 300, "height": 200}},
+        {"id": "k", "type": "app_card", "position": {"x": 0, "y": 0}}
+    ]}"#;
+    let mut resolve = |url: &str| {
+        if url.ends_with("cat.png") {
+            Ok(PathBuf::from("/downloads/cat.png"))
+        } else {
+            Err("404".to_string())
+        }
+    };
+    let converted = convert_whiteboard(json, false, &mut resolve).unwrap();
+
+    assert_eq!(converted.items.len(), 1);
+    assert!(matches!(
+        &converted.items[0].content,
+        ItemContent::Image(path) if path == &PathBuf::from("/downloads/cat.png")
+    ));
+    assert_eq!(converted.items[0].position, (-150.0, -100.0));
+    assert_eq!(
+        converted.skipped,
+        vec!["Image 'Dog': 404".to_string(), "App card 'k'".to_string()]
+    );
+}
+
+#[test]
+fn test_miro_csv_cells_become_stickies_in_a_grid() {
+    let converted = convert_miro_csv("Idea one,Idea two\n,\"Idea, three\"\n").unwrap();
+
+    let notes: Vec<&CanvasItem> = converted
+        .items
+        .iter()
+        .filter(|item| matches!(item.content, ItemContent::Shape { .. }))
+        .collect();
+    assert_eq!(notes.len(), 3);
+    assert_eq!(notes[0].position, (0.0, 0.0));
+    assert_eq!(notes[1].position, (220.0, 0.0));
+    assert_eq!(notes[2].position, (220.0, 220.0));
+    let texts: Vec<&str> = converted.items.iter().filter_map(text_of).collect();
+    assert_eq!(texts, vec!["Idea one", "Idea two", "Idea, three"]);
+}
+
+#[test]
+fn test_figjam_document() {
+    let json = r#"{"document": {"id": "0:0", "type": "DOCUMENT", "children": [
+        {"id": "0:1", "type": "CANVAS", "children": [
+            {"id": "1:1", "type": "SECTION", "name": "Retro",
+             "absoluteBoundingBox": {"x": 0, "y": 0, "width": 800, "height": 600},
+             "children": [
+                {"id": "1:2", "type": "STICKY", "characters": "Went well",
+                 "absoluteBoundingBox": {"x": 20, "y": 20, "width": 240, "height": 240},
+                 "fills": [{"type": "SOLID", "color": {"r": 1, "g": 0.85, "b": 0.4, "a": 1}}]},
+                {"id": "1:3", "type": "SHAPE_WITH_TEXT", "shapeType": "ELLIPSE", "characters": "Goal",
+                 "absoluteBoundingBox": {"x": 400, "y": 20, "width": 200, "height": 100},
+                 "fills": [{"type": "SOLID", "color": {"r": 0, "g": 0, "b": 0, "a": 1}}]}
+             ]},
+            {"id": "1:4", "type": "CONNECTOR", "name": "Connector",
+             "connectorStart": {"endpointNodeId": "1:2"},
+             "connectorEnd": {"endpointNodeId": "1:3"},
+             "connectorEndStrokeCap": "ARROW_LINES",
+             "strokes": [{"type": "SOLID", "color": {"r": 0, "g": 0, "b": 1, "a": 1}}]},
+            {"id": "1:5", "type": "STAMP", "name": "Thumbs up"}
+        ]}
+    ]}}"#;
+    let converted = convert_whiteboard(json, false, &mut no_images).unwrap();
+
+    assert_eq!(converted.app, WhiteboardApp::FigJam);
+    // Section and label, sticky and text, shape and text, arrow
+    assert_eq!(converted.items.len(), 7);
+    assert_eq!(text_of(&converted.items[1]), Some("Retro"));
+    assert!(matches!(
+        &converted.items[2].content,
+        ItemContent::Shape { fill_color: Some(fill), .. } if fill == "#ffd966"
+    ));
+    assert_eq!(text_of(&converted.items[3]), Some("Went well"));
+    assert!(matches!(
+        converted.items[4].content,
+        ItemContent::Shape {
+            shape_type: ShapeType::Ellipse,
+            ..
+        }
+    ));
+    assert!(matches!(
+        &converted.items[5].content,
+        ItemContent::TextBox { color, .. } if color == "#ffffff"
+    ));
+    let arrow = &converted.items[6];
+    assert_eq!(arrow.position, (260.0, 140.0));
+    assert!(matches!(
+        &arrow.content,
+        ItemContent::Arrow { color, head_style: ArrowHead::Arrow, .. } if color == "#0000ff"
+    ));
+    assert_eq!(converted.skipped, vec!["Stamp 'Thumbs up'".to_string()]);
+}
+
+#[test]
+fn test_unrecognized_json_is_an_error() {
+    assert!(convert_whiteboard(r#"{"boards": 1}"#, false, &mut no_images).is_err());
+    assert!(convert_whiteboard("not json", false, &mut no_images).is_err());
+}
+
+#[test]
+fn test_html_text() {
+    assert_eq!(
+        html_text("<p>One<br/>Two</p><p>Three</p>"),
+        "One\nTwo\nThree"
+    );
+    assert_eq!(html_text("a &lt;b&gt; &quot;c&quot;"), "a <b> \"c\"");
+}
+
+#[test]
+fn test_miro_colors() {
+    assert_eq!(miro_color("violet").as_deref(), Some("#c6a2d2"));
+    assert_eq!(miro_color("#ABC").as_deref(), Some("#aabbcc"));
+    assert_eq!(miro_color("transparent"), None);
+}
+
+#[test]
+fn test_import_whiteboard_uses_files_next_to_export() {
+    let dir = tempdir().unwrap();
+    fs::write(dir.path().join("photo.png"), b"not really a png").unwrap();
+    let export = dir.path().join("board.json");
+    fs::write(
+        &export,
+        r#"[{"id": "i", "type": "image", "data": {"imageUrl": "photo.png"},
+             "position": {"x": 0, "y": 0}, "geometry": {"width": 10, "height": 10}},
+            {"id": "j", "type": "image", "data": {"imageUrl": "missing.png"},
+             "position": {"x": 0, "y": 0}, "geometry": {"width": 10, "height": 10}}]"#,
+    )
+    .unwrap();
+
+    let (converted, copied) = import_whiteboard(&export, &dir.path().join("storage")).unwrap();
+    assert_eq!(copied.board_dir, dir.path());
+    assert_eq!(copied.items.len(), 1);
+    assert!(matches!(
+        &copied.items[0].content,
+        ItemContent::Image(path) if path == &dir.path().join("photo.png")
+    ));
+    assert_eq!(converted.skipped.len(), 1);
+
+    let csv = dir.path().join("stickies.csv");
+    fs::write(&csv, "One\n").unwrap();
+    let (converted, copied) = import_whiteboard(&csv, &dir.path().join("storage")).unwrap();
+    assert_eq!(converted.app, WhiteboardApp::Miro);
+    assert_eq!(copied.items.len(), 2);
+}
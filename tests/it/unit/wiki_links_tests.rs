@@ -0,0 +1,90 @@
+//! Unit tests for wiki links between markdown cards.
+
+use crate::helpers::test_canvas_item;
+use humanboard::types::{CanvasItem, ItemContent};
+use humanboard::wiki_links::{WikiLinks, connector_endpoints, link_key, parse_wiki_links};
+
+fn card(id: u64, file: &str, content: &str) -> CanvasItem {
+    let mut item = test_canvas_item(id, "");
+    item.content = ItemContent::Markdown {
+        path: format!("/notes/{}", file).into(),
+        title: file.trim_end_matches(".md").to_string(),
+        content: content.to_string(),
+    };
+    item
+}
+
+#[test]
+fn test_parses_links_with_labels_and_headings() {
+    let links = parse_wiki_links("See [[Plan]], [[Roadmap|the roadmap]] and [[Plan#Goals]].");
+    assert_eq!(links, vec!["Plan".to_string(), "Roadmap".to_string()]);
+}
+
+#[test]
+fn test_ignores_links_in_code() {
+    let content = "[[Real]]\n```\n[[Fenced]]\n```\nand `[[Inline]]` here";
+    assert_eq!(parse_wiki_links(content), vec!["Real".to_string()]);
+}
+
+#[test]
+fn test_ignores_empty_and_unclosed_links() {
+    assert!(parse_wiki_links("[[]] and [[ | x]] and [[open").is_empty());
+}
+
+#[test]
+fn test_link_key_ignores_case_and_extension() {
+    assert_eq!(link_key(" Meeting Notes.md "), "meeting notes");
+    assert_eq!(link_key("Meeting Notes"), link_key("meeting notes"));
+}
+
+#[test]
+fn test_links_connect_cards_by_title_or_file_name() {
+    let items = vec![
+        card(1, "ideas.md", "Goes into [[plan]]"),
+        card(2, "plan.md", "Next: [[Roadmap.md]] and [[Missing]]"),
+        card(3, "roadmap.md", "Back to [[Ideas]] and [[roadmap]]"),
+        test_canvas_item(4, "[[plan]] in a plain note"),
+    ];
+    let mut links = WikiLinks::default();
+    links.update(&items);
+
+    assert_eq!(links.edges(), &[(1, 2), (2, 3), (3, 1)]);
+    assert_eq!(links.backlinks(2), vec![1]);
+    assert_eq!(
+        links.backlink_titles(1, &items),
+        vec!["roadmap".to_string()]
+    );
+    assert!(!links.is_mutual(1, 2));
+}
+
+#[test]
+fn test_links_follow_edited_content() {
+    let mut items = vec![card(1, "a.md", "[[b]]"), card(2, "b.md", "")];
+    let mut links = WikiLinks::default();
+    links.update(&items);
+    assert_eq!(links.edges(), &[(1, 2)]);
+
+    items[1] = card(2, "b.md", "[[a]]");
+    links.update(&items);
+    assert!(links.is_mutual(1, 2));
+
+    items.remove(0);
+    links.update(&items);
+    assert!(links.edges().is_empty());
+}
+
+#[test]
+fn test_connector_uses_facing_sides() {
+    let left = ((0.0, 0.0), (100.0, 50.0));
+    let right = ((300.0, 0.0), (100.0, 50.0));
+    assert_eq!(
+        connector_endpoints(left, right),
+        ((100.0, 25.0), (300.0, 25.0))
+    );
+
+    let below = ((0.0, 200.0), (100.0, 50.0));
+    assert_eq!(
+        connector_endpoints(left, below),
+        ((50.0, 50.0), (50.0, 200.0))
+    );
+}